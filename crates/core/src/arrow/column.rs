@@ -0,0 +1,131 @@
+use super::schema::{Column, DECIMAL_PRECISION, Type};
+use crate::err::Error;
+use crate::expr::{Number, Value};
+use anyhow::Result;
+use arrow_array::ArrayRef;
+use arrow_array::builder::{
+	BinaryBuilder, BooleanBuilder, Decimal128Builder, DurationNanosecondBuilder, Float64Builder,
+	Int64Builder, StringBuilder, TimestampNanosecondBuilder,
+};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Builds the Arrow array for a single column of a record batch
+pub(super) enum Builder {
+	Bool(BooleanBuilder),
+	Int(Int64Builder),
+	Float(Float64Builder),
+	Decimal(Decimal128Builder, u32),
+	Datetime(TimestampNanosecondBuilder),
+	Duration(DurationNanosecondBuilder),
+	String(StringBuilder),
+	Bytes(BinaryBuilder),
+	Json(StringBuilder),
+}
+
+impl Builder {
+	pub(super) fn new(column: &Column, capacity: usize) -> Result<Self> {
+		Ok(match column.kind {
+			Type::Bool => Self::Bool(BooleanBuilder::with_capacity(capacity)),
+			Type::Int => Self::Int(Int64Builder::with_capacity(capacity)),
+			Type::Float => Self::Float(Float64Builder::with_capacity(capacity)),
+			Type::Decimal(scale) => Self::Decimal(
+				Decimal128Builder::with_capacity(capacity)
+					.with_precision_and_scale(DECIMAL_PRECISION, scale as i8)
+					.map_err(|e| Error::Arrow(e.to_string()))?,
+				scale,
+			),
+			Type::Datetime => Self::Datetime(
+				TimestampNanosecondBuilder::with_capacity(capacity).with_timezone("UTC"),
+			),
+			Type::Duration => Self::Duration(DurationNanosecondBuilder::with_capacity(capacity)),
+			Type::String => Self::String(StringBuilder::new()),
+			Type::Bytes => Self::Bytes(BinaryBuilder::new()),
+			Type::Null | Type::Json => Self::Json(StringBuilder::new()),
+		})
+	}
+
+	/// Appends a value to the column, or fails if the value does not have
+	/// the type of the column which was inferred from the sampled rows.
+	pub(super) fn append(&mut self, column: &Column, value: Value) -> Result<()> {
+		if value.is_none_or_null() {
+			self.append_null();
+			return Ok(());
+		}
+		match (self, value) {
+			(Self::Json(b), v) => match serde_json::to_string(&v.into_json()) {
+				Ok(v) => b.append_value(v),
+				Err(e) => return Err(anyhow::Error::new(Error::Arrow(e.to_string()))),
+			},
+			(Self::Bool(b), Value::Bool(v)) => b.append_value(v),
+			(Self::Int(b), Value::Number(Number::Int(v))) => b.append_value(v),
+			(Self::Float(b), Value::Number(Number::Float(v))) => b.append_value(v),
+			(Self::Float(b), Value::Number(Number::Int(v))) => b.append_value(v as f64),
+			(Self::Float(b), Value::Number(v @ Number::Decimal(_))) => b.append_value(v.as_float()),
+			(Self::Decimal(b, scale), Value::Number(v @ (Number::Int(_) | Number::Decimal(_)))) => {
+				let mut v = match v {
+					Number::Int(v) => Decimal::from(v),
+					Number::Decimal(v) => v,
+					Number::Float(_) => unreachable!(),
+				};
+				// Values with a larger scale than the sampled rows are rounded
+				v.rescale(*scale);
+				b.append_value(v.mantissa())
+			}
+			(Self::Datetime(b), Value::Datetime(v)) => match v.0.timestamp_nanos_opt() {
+				Some(v) => b.append_value(v),
+				None => return Err(unrepresentable(column, "datetime")),
+			},
+			(Self::Duration(b), Value::Duration(v)) => match i64::try_from(v.0.as_nanos()) {
+				Ok(v) => b.append_value(v),
+				Err(_) => return Err(unrepresentable(column, "duration")),
+			},
+			(Self::String(b), Value::Strand(v)) => b.append_value(v.0),
+			(Self::String(b), Value::Uuid(v)) => b.append_value(v.0.to_string()),
+			(Self::String(b), Value::Thing(v)) => b.append_value(v.to_string()),
+			(Self::Bytes(b), Value::Bytes(v)) => b.append_value(v.0),
+			(_, v) => {
+				return Err(anyhow::Error::new(Error::Arrow(format!(
+					"Found {} for column '{}' of type {}. Increase the number of rows used to infer the schema",
+					v.kindof(),
+					column.name,
+					column.kind
+				))));
+			}
+		}
+		Ok(())
+	}
+
+	fn append_null(&mut self) {
+		match self {
+			Self::Bool(b) => b.append_null(),
+			Self::Int(b) => b.append_null(),
+			Self::Float(b) => b.append_null(),
+			Self::Decimal(b, _) => b.append_null(),
+			Self::Datetime(b) => b.append_null(),
+			Self::Duration(b) => b.append_null(),
+			Self::String(b) | Self::Json(b) => b.append_null(),
+			Self::Bytes(b) => b.append_null(),
+		}
+	}
+
+	pub(super) fn finish(self) -> ArrayRef {
+		match self {
+			Self::Bool(mut b) => Arc::new(b.finish()),
+			Self::Int(mut b) => Arc::new(b.finish()),
+			Self::Float(mut b) => Arc::new(b.finish()),
+			Self::Decimal(mut b, _) => Arc::new(b.finish()),
+			Self::Datetime(mut b) => Arc::new(b.finish()),
+			Self::Duration(mut b) => Arc::new(b.finish()),
+			Self::String(mut b) | Self::Json(mut b) => Arc::new(b.finish()),
+			Self::Bytes(mut b) => Arc::new(b.finish()),
+		}
+	}
+}
+
+fn unrepresentable(column: &Column, kind: &str) -> anyhow::Error {
+	anyhow::Error::new(Error::Arrow(format!(
+		"Found a {kind} in column '{}' which does not fit in 64-bit nanoseconds",
+		column.name
+	)))
+}
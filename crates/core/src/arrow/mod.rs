@@ -0,0 +1,468 @@
+//! Conversion of query results into Apache Arrow record batches.
+//!
+//! The columns of the exported batches are inferred from a sample of the
+//! result rows, or from the field definitions when the rows are selected
+//! from a schemafull table. Nested values, and columns which contain values
+//! of different types, are written as JSON strings and marked with the
+//! canonical `arrow.json` extension type.
+//!
+//! The batches can be written as an Arrow IPC stream, or as a Parquet file
+//! with a row group for every batch. The result of a query is written as its
+//! rows are produced, so that only the rows which are sampled, and the rows
+//! of the current batch, are held in memory.
+
+mod column;
+mod parquet;
+mod schema;
+
+use self::parquet::ParquetWriter;
+use crate::dbs::QueryFrame;
+use crate::err::Error;
+use crate::expr::Value;
+use crate::expr::statements::DefineFieldStatement;
+use anyhow::Result;
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::Schema;
+use async_channel::{Receiver, Sender};
+use column::Builder;
+use schema::{Column, VALUE_COLUMN};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+pub use arrow_array::RecordBatch;
+pub use arrow_schema::SchemaRef;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+	/// The maximum number of rows in each record batch
+	pub batch_size: usize,
+	/// The number of rows which are sampled to infer the schema
+	pub infer_rows: usize,
+	/// The format which the record batches are written in
+	pub format: Format,
+}
+
+impl Default for Config {
+	fn default() -> Config {
+		Config {
+			batch_size: 8192,
+			infer_rows: 1000,
+			format: Format::Ipc,
+		}
+	}
+}
+
+/// The format which record batches are written in
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+	/// An Arrow IPC stream
+	Ipc,
+	/// A Snappy compressed Parquet file
+	Parquet,
+}
+
+/// The rows of a query result, converted into record batches one at a time.
+pub struct Batches {
+	schema: SchemaRef,
+	columns: Vec<Column>,
+	/// Whether each row is an object with a value for every column
+	objects: bool,
+	rows: VecDeque<Value>,
+	batch_size: usize,
+}
+
+impl Batches {
+	/// Converts the rows of a result, inferring the schema from a sample
+	pub fn new(rows: Vec<Value>, cfg: &Config) -> Self {
+		Self::with_fields(rows, None, cfg)
+	}
+
+	/// Converts the rows of a result, which are records of a schemafull
+	/// table with the given field definitions
+	pub(crate) fn with_fields(
+		rows: Vec<Value>,
+		fields: Option<&[DefineFieldStatement]>,
+		cfg: &Config,
+	) -> Self {
+		let sample = &rows[..rows.len().min(cfg.infer_rows)];
+		let (columns, objects) = schema::infer(sample, fields);
+		let schema = Arc::new(Schema::new(columns.iter().map(Column::field).collect::<Vec<_>>()));
+		Self {
+			schema,
+			columns,
+			objects,
+			rows: rows.into(),
+			batch_size: cfg.batch_size.max(1),
+		}
+	}
+
+	/// The schema of the record batches
+	pub fn schema(&self) -> SchemaRef {
+		self.schema.clone()
+	}
+
+	/// Writes all of the record batches in the given format
+	pub fn write(mut self, format: Format) -> Result<Vec<u8>> {
+		let mut writer = Writer::new(format, &self.schema)?;
+		for batch in self.by_ref() {
+			writer.write(&batch?)?;
+		}
+		writer.finish()
+	}
+
+	/// Adds rows which are converted after the current rows
+	fn push(&mut self, rows: Vec<Value>) {
+		self.rows.extend(rows);
+	}
+
+	/// Checks if there are enough rows for a full record batch
+	fn is_full(&self) -> bool {
+		self.rows.len() >= self.batch_size
+	}
+
+	/// Converts the next rows into a record batch
+	fn next_batch(&mut self) -> Option<Result<RecordBatch>> {
+		if self.rows.is_empty() {
+			return None;
+		}
+		let len = self.rows.len().min(self.batch_size);
+		let rows: Vec<Value> = self.rows.drain(..len).collect();
+		Some(self.convert(rows))
+	}
+
+	fn convert(&self, rows: Vec<Value>) -> Result<RecordBatch> {
+		let mut builders =
+			self.columns.iter().map(|c| Builder::new(c, rows.len())).collect::<Result<Vec<_>>>()?;
+		for row in rows {
+			if !self.objects {
+				builders[0].append(&self.columns[0], row)?;
+				continue;
+			}
+			let Value::Object(mut row) = row else {
+				return Err(anyhow::Error::new(Error::Arrow(format!(
+					"Found {} instead of an object. Increase the number of rows used to infer the schema",
+					row.kindof()
+				))));
+			};
+			for (column, builder) in self.columns.iter().zip(builders.iter_mut()) {
+				let value = row.0.remove(&column.name).unwrap_or_default();
+				builder.append(column, value)?;
+			}
+			// Fail rather than silently dropping fields which were not sampled
+			if let Some((name, _)) = row.0.iter().find(|(_, v)| !v.is_none_or_null()) {
+				return Err(anyhow::Error::new(Error::Arrow(format!(
+					"Found field '{name}' which is not a column. Increase the number of rows used to infer the schema"
+				))));
+			}
+		}
+		let arrays = builders.into_iter().map(Builder::finish).collect();
+		RecordBatch::try_new(self.schema.clone(), arrays).map_err(arrow_error)
+	}
+}
+
+impl Iterator for Batches {
+	type Item = Result<RecordBatch>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.next_batch()
+	}
+}
+
+/// Writes record batches in a format, handing out the bytes of the
+/// output as each record batch is written
+enum Writer {
+	Ipc(StreamWriter<Vec<u8>>),
+	Parquet(ParquetWriter),
+}
+
+impl Writer {
+	fn new(format: Format, schema: &SchemaRef) -> Result<Self> {
+		Ok(match format {
+			Format::Ipc => {
+				Self::Ipc(StreamWriter::try_new(Vec::new(), schema).map_err(arrow_error)?)
+			}
+			Format::Parquet => Self::Parquet(ParquetWriter::new(schema)?),
+		})
+	}
+
+	fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+		match self {
+			Self::Ipc(w) => w.write(batch).map_err(arrow_error),
+			Self::Parquet(w) => w.write(batch),
+		}
+	}
+
+	/// Takes the bytes which have been written so far
+	fn take(&mut self) -> Vec<u8> {
+		match self {
+			Self::Ipc(w) => std::mem::take(w.get_mut()),
+			Self::Parquet(w) => w.take(),
+		}
+	}
+
+	/// Writes the end of the output, returning the remaining bytes
+	fn finish(self) -> Result<Vec<u8>> {
+		match self {
+			Self::Ipc(w) => w.into_inner().map_err(arrow_error),
+			Self::Parquet(w) => w.finish(),
+		}
+	}
+}
+
+/// The rows of a single statement, received from the frames of a streamed query
+struct Frames {
+	frames: Receiver<QueryFrame>,
+	/// Whether the end of the result has been received
+	done: bool,
+}
+
+impl Frames {
+	/// Receives the next rows, or `None` once there are no more rows
+	async fn next(&mut self) -> Result<Option<Vec<Value>>> {
+		if self.done {
+			return Ok(None);
+		}
+		match self.frames.recv().await {
+			Ok(QueryFrame::Rows {
+				rows,
+				..
+			}) => Ok(Some(rows)),
+			Ok(QueryFrame::Done {
+				response,
+				rows,
+				..
+			}) => {
+				self.done = true;
+				let result = response.result?;
+				// The result of a statement which was not streamed
+				match rows {
+					Some(_) => Ok(None),
+					None => match result {
+						Value::Array(v) => Ok(Some(v.0)),
+						v => Ok(Some(vec![v])),
+					},
+				}
+			}
+			// The query failed before the statement was run
+			Err(_) => Ok(None),
+		}
+	}
+}
+
+/// Writes the result of a streamed query with a single statement to a
+/// channel, as the frames of the query are received.
+///
+/// The first chunk is only sent once the rows used to infer the schema have
+/// been received, and a chunk is then sent for every record batch, followed
+/// by a final chunk which ends the output. When the query fails before the
+/// statement is run, nothing is written, as the query returns the error.
+pub(crate) async fn export(
+	frames: Receiver<QueryFrame>,
+	fields: Option<Arc<[DefineFieldStatement]>>,
+	cfg: Config,
+	chn: Sender<Vec<u8>>,
+) -> Result<()> {
+	let mut frames = Frames {
+		frames,
+		done: false,
+	};
+	// Receive the rows which are sampled to infer the schema
+	let mut sample = Vec::new();
+	while sample.len() < cfg.infer_rows {
+		match frames.next().await? {
+			Some(rows) => sample.extend(rows),
+			None => break,
+		}
+	}
+	if !frames.done && sample.len() < cfg.infer_rows {
+		return Ok(());
+	}
+	let mut batches = Batches::with_fields(sample, fields.as_deref(), &cfg);
+	let mut writer = Writer::new(cfg.format, &batches.schema)?;
+	loop {
+		// Fill the next record batch with the received rows
+		while !batches.is_full() {
+			match frames.next().await? {
+				Some(rows) => batches.push(rows),
+				None => break,
+			}
+		}
+		let Some(batch) = batches.next_batch() else {
+			break;
+		};
+		writer.write(&batch?)?;
+		send(&chn, writer.take()).await?;
+	}
+	// The result ended before the statement finished
+	if !frames.done {
+		return Ok(());
+	}
+	send(&chn, writer.finish()?).await
+}
+
+/// Sends a chunk of the output, unless it is empty
+async fn send(chn: &Sender<Vec<u8>>, chunk: Vec<u8>) -> Result<()> {
+	if chunk.is_empty() {
+		return Ok(());
+	}
+	// The receiver stopped receiving the output
+	if chn.send(chunk).await.is_err() {
+		return Err(anyhow::Error::new(Error::QueryCancelled));
+	}
+	Ok(())
+}
+
+fn arrow_error(e: impl std::fmt::Display) -> anyhow::Error {
+	anyhow::Error::new(Error::Arrow(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+	use crate::syn;
+	use arrow_array::cast::AsArray;
+	use arrow_array::types::{Float64Type, Int64Type};
+	use arrow_ipc::reader::StreamReader;
+	use arrow_schema::DataType;
+
+	fn rows(sql: &str) -> Vec<Value> {
+		match Value::from(syn::value(sql).unwrap()) {
+			Value::Array(v) => v.0,
+			v => vec![v],
+		}
+	}
+
+	#[test]
+	fn batches() {
+		let cfg = Config {
+			batch_size: 2,
+			..Default::default()
+		};
+		let batches = Batches::new(
+			rows("[{ id: person:1, age: 1 }, { id: person:2, age: 2.5 }, { id: person:3 }]"),
+			&cfg,
+		);
+		let batches = batches.collect::<Result<Vec<_>>>().unwrap();
+		assert_eq!(batches.len(), 2);
+		assert_eq!(batches[0].num_rows(), 2);
+		assert_eq!(batches[1].num_rows(), 1);
+		let age = batches[0].column_by_name("age").unwrap().as_primitive::<Float64Type>();
+		assert_eq!(age.values(), &[1.0, 2.5]);
+		let id = batches[1].column_by_name("id").unwrap().as_string::<i32>();
+		assert_eq!(id.value(0), "person:3");
+		assert!(batches[1].column_by_name("age").unwrap().is_null(0));
+	}
+
+	#[test]
+	fn nested_values() {
+		let batches =
+			Batches::new(rows("[{ tags: ['a', 'b'] }, { tags: { a: 1 } }]"), &Config::default());
+		let batch = batches.collect::<Result<Vec<_>>>().unwrap().remove(0);
+		let tags = batch.column(0).as_string::<i32>();
+		assert_eq!(tags.value(0), r#"["a","b"]"#);
+		assert_eq!(tags.value(1), r#"{"a":1}"#);
+	}
+
+	#[test]
+	fn unsampled_rows() {
+		let cfg = Config {
+			infer_rows: 1,
+			..Default::default()
+		};
+		let mut batches = Batches::new(rows("[{ age: 1 }, { age: 'one' }]"), &cfg);
+		let err = batches.next().unwrap().unwrap_err();
+		assert!(err.to_string().contains("column 'age' of type int"), "{err}");
+		let mut batches = Batches::new(rows("[{ age: 1 }, { age: 2, name: 'two' }]"), &cfg);
+		let err = batches.next().unwrap().unwrap_err();
+		assert!(err.to_string().contains("field 'name'"), "{err}");
+	}
+
+	#[test]
+	fn ipc_stream() {
+		let batches = Batches::new(rows("[1, 2, 3]"), &Config::default());
+		let schema = batches.schema();
+		assert_eq!(schema.field(0).name(), VALUE_COLUMN);
+		let bytes = batches.write(Format::Ipc).unwrap();
+		let reader = StreamReader::try_new(bytes.as_slice(), None).unwrap();
+		assert_eq!(reader.schema(), schema);
+		let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(batches.len(), 1);
+		assert_eq!(batches[0].column(0).as_primitive::<Int64Type>().values(), &[1, 2, 3]);
+	}
+
+	async fn query(ds: &Datastore, sql: &str, cfg: Config) -> Result<Vec<Vec<u8>>> {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let (send, recv) = async_channel::bounded(1);
+		let (res, chunks) = futures::join!(ds.query_arrow(sql, &ses, None, cfg, send), async {
+			let mut chunks = Vec::new();
+			while let Ok(chunk) = recv.recv().await {
+				chunks.push(chunk);
+			}
+			chunks
+		});
+		res.map(|_| chunks)
+	}
+
+	#[tokio::test]
+	async fn streamed_query() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("FOR $i IN 1..=10 { CREATE person SET num = $i }", &ses, None).await.unwrap();
+		let cfg = Config {
+			batch_size: 3,
+			infer_rows: 2,
+			..Default::default()
+		};
+		let sql = "SELECT VALUE num FROM person";
+		let chunks = query(&ds, sql, cfg).await.unwrap();
+		// A chunk for every record batch, and the end of the stream
+		assert_eq!(chunks.len(), 5);
+		let reader = StreamReader::try_new(chunks.concat().as_slice(), None).unwrap();
+		let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+		let mut nums: Vec<i64> = batches
+			.iter()
+			.flat_map(|b| b.column(0).as_primitive::<Int64Type>().values().to_vec())
+			.collect();
+		nums.sort();
+		assert_eq!(nums, (1..=10).collect::<Vec<_>>());
+		// Nothing is written when the statement fails
+		let cfg = Config {
+			format: Format::Parquet,
+			..Default::default()
+		};
+		let res = query(&ds, "SELECT * FROM person WHERE fn::missing()", cfg).await;
+		assert!(res.is_err());
+	}
+
+	#[tokio::test]
+	async fn schemafull_table() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE TABLE person SCHEMAFULL;
+			DEFINE FIELD name ON person TYPE string;
+			DEFINE FIELD age ON person TYPE option<int>;
+			CREATE person:one SET name = 'one';
+		";
+		ds.execute(sql, &ses, None).await.unwrap();
+		let chunks = query(&ds, "SELECT * FROM person", Config::default()).await.unwrap();
+		let reader = StreamReader::try_new(chunks.concat().as_slice(), None).unwrap();
+		// Fields without any sampled values still have the defined type
+		let schema = reader.schema();
+		let fields: Vec<_> =
+			schema.fields().iter().map(|f| (f.name().as_str(), f.data_type().clone())).collect();
+		assert_eq!(
+			fields,
+			vec![("age", DataType::Int64), ("id", DataType::Utf8), ("name", DataType::Utf8)]
+		);
+		let batch = reader.collect::<Result<Vec<_>, _>>().unwrap().remove(0);
+		assert!(batch.column(0).is_null(0));
+		assert_eq!(batch.column(1).as_string::<i32>().value(0), "person:one");
+		// Only the result of a single statement can be exported
+		let sql = "SELECT * FROM person; SELECT * FROM person";
+		let res = query(&ds, sql, Config::default()).await;
+		assert!(res.is_err());
+	}
+}
@@ -0,0 +1,111 @@
+use super::arrow_error;
+use anyhow::Result;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{DurationNanosecondType, Int64Type};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::sync::Arc;
+
+/// Writes record batches as a Snappy compressed Parquet file.
+///
+/// Every record batch is written as a row group of its own, so that the
+/// bytes of the file can be taken from the writer as each batch is written.
+/// Parquet has no duration type, so duration columns are written as the
+/// number of nanoseconds in a 64-bit integer column.
+pub(super) struct ParquetWriter {
+	schema: SchemaRef,
+	writer: ArrowWriter<Vec<u8>>,
+}
+
+impl ParquetWriter {
+	pub(super) fn new(schema: &SchemaRef) -> Result<Self> {
+		let schema = parquet_schema(schema);
+		let props = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+		let writer =
+			ArrowWriter::try_new(Vec::new(), schema.clone(), Some(props)).map_err(arrow_error)?;
+		Ok(Self {
+			schema,
+			writer,
+		})
+	}
+
+	/// Writes a record batch as a row group
+	pub(super) fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+		let columns = batch
+			.columns()
+			.iter()
+			.map(|c| match c.data_type() {
+				DataType::Duration(_) => Arc::new(
+					c.as_primitive::<DurationNanosecondType>().reinterpret_cast::<Int64Type>(),
+				) as ArrayRef,
+				_ => c.clone(),
+			})
+			.collect();
+		let batch = RecordBatch::try_new(self.schema.clone(), columns).map_err(arrow_error)?;
+		self.writer.write(&batch).map_err(arrow_error)?;
+		self.writer.flush().map_err(arrow_error)
+	}
+
+	/// Takes the bytes which have been written so far
+	pub(super) fn take(&mut self) -> Vec<u8> {
+		std::mem::take(self.writer.inner_mut())
+	}
+
+	/// Writes the footer, returning the remaining bytes of the file
+	pub(super) fn finish(self) -> Result<Vec<u8>> {
+		self.writer.into_inner().map_err(arrow_error)
+	}
+}
+
+/// The schema of the Parquet file, with durations replaced by integers
+fn parquet_schema(schema: &SchemaRef) -> SchemaRef {
+	let fields = schema
+		.fields()
+		.iter()
+		.map(|f| match f.data_type() {
+			DataType::Duration(_) => Arc::new(Field::new(f.name(), DataType::Int64, true)),
+			_ => f.clone(),
+		})
+		.collect::<Vec<_>>();
+	Arc::new(Schema::new(fields))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::arrow::{Batches, Config, Format};
+	use crate::expr::Value;
+	use crate::syn;
+	use bytes::Bytes;
+	use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+	#[test]
+	fn round_trip() {
+		let rows = match Value::from(
+			syn::value("[{ name: 'one', wait: 1s }, { name: 'two', wait: 2ms }]").unwrap(),
+		) {
+			Value::Array(v) => v.0,
+			_ => unreachable!(),
+		};
+		let cfg = Config {
+			batch_size: 1,
+			..Default::default()
+		};
+		let file = Batches::new(rows, &cfg).write(Format::Parquet).unwrap();
+		let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(file)).unwrap();
+		// Every record batch is written as a row group
+		assert_eq!(reader.metadata().num_row_groups(), 2);
+		let reader = reader.build().unwrap();
+		let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+		let wait: Vec<i64> = batches
+			.iter()
+			.flat_map(|b| {
+				b.column_by_name("wait").unwrap().as_primitive::<Int64Type>().values().to_vec()
+			})
+			.collect();
+		assert_eq!(wait, vec![1_000_000_000, 2_000_000]);
+	}
+}
@@ -0,0 +1,269 @@
+use crate::expr::statements::DefineFieldStatement;
+use crate::expr::{Kind, Number, Part, Value};
+use arrow_schema::{DataType, Field, TimeUnit};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// The precision of decimal columns, which is the maximum for 128-bit decimals
+pub(super) const DECIMAL_PRECISION: u8 = 38;
+/// The maximum scale of a decimal value
+const DECIMAL_MAX_SCALE: u32 = 28;
+/// The metadata key which marks the extension type of an Arrow field
+const EXTENSION_NAME: &str = "ARROW:extension:name";
+/// The canonical extension type for columns of JSON strings
+const EXTENSION_JSON: &str = "arrow.json";
+/// The name of the column used for results which are not objects
+pub(super) const VALUE_COLUMN: &str = "value";
+
+/// The type of the values in a column
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Type {
+	/// Only NONE or NULL values have been seen
+	Null,
+	Bool,
+	Int,
+	Float,
+	/// A decimal with the given scale
+	Decimal(u32),
+	Datetime,
+	Duration,
+	String,
+	Bytes,
+	/// Nested or heterogeneous values, which are written as JSON strings
+	Json,
+}
+
+impl fmt::Display for Type {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Null => f.write_str("null"),
+			Self::Bool => f.write_str("bool"),
+			Self::Int => f.write_str("int"),
+			Self::Float => f.write_str("float"),
+			Self::Decimal(_) => f.write_str("decimal"),
+			Self::Datetime => f.write_str("datetime"),
+			Self::Duration => f.write_str("duration"),
+			Self::String => f.write_str("string"),
+			Self::Bytes => f.write_str("bytes"),
+			Self::Json => f.write_str("json"),
+		}
+	}
+}
+
+impl Type {
+	/// The type of a single value
+	pub(super) fn of(v: &Value) -> Self {
+		match v {
+			Value::None | Value::Null => Self::Null,
+			Value::Bool(_) => Self::Bool,
+			Value::Number(Number::Int(_)) => Self::Int,
+			Value::Number(Number::Float(_)) => Self::Float,
+			Value::Number(Number::Decimal(v)) => Self::Decimal(v.scale().min(DECIMAL_MAX_SCALE)),
+			Value::Datetime(_) => Self::Datetime,
+			Value::Duration(_) => Self::Duration,
+			Value::Strand(_) | Value::Uuid(_) | Value::Thing(_) => Self::String,
+			Value::Bytes(_) => Self::Bytes,
+			_ => Self::Json,
+		}
+	}
+
+	/// The type of a field definition, if it determines the type of the column
+	fn from_kind(kind: &Kind) -> Option<Self> {
+		match kind {
+			Kind::Option(kind) => Self::from_kind(kind),
+			Kind::Bool => Some(Self::Bool),
+			Kind::Int => Some(Self::Int),
+			Kind::Float => Some(Self::Float),
+			Kind::Decimal => Some(Self::Decimal(0)),
+			Kind::Datetime => Some(Self::Datetime),
+			Kind::Duration => Some(Self::Duration),
+			Kind::String | Kind::Uuid | Kind::Record(_) => Some(Self::String),
+			Kind::Bytes => Some(Self::Bytes),
+			// The type of numbers and untyped fields depends on the values
+			Kind::Any | Kind::Number => None,
+			_ => Some(Self::Json),
+		}
+	}
+
+	/// The type of a column which contains values of both types
+	pub(super) fn merge(self, other: Self) -> Self {
+		match (self, other) {
+			(Self::Null, v) | (v, Self::Null) => v,
+			(Self::Decimal(a), Self::Decimal(b)) => Self::Decimal(a.max(b)),
+			(a, b) if a == b => a,
+			(Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+			(Self::Int, Self::Decimal(s)) | (Self::Decimal(s), Self::Int) => Self::Decimal(s),
+			(Self::Float, Self::Decimal(_)) | (Self::Decimal(_), Self::Float) => Self::Float,
+			_ => Self::Json,
+		}
+	}
+}
+
+/// A column of the exported record batches
+#[derive(Clone, Debug)]
+pub(super) struct Column {
+	pub name: String,
+	pub kind: Type,
+}
+
+impl Column {
+	/// The Arrow field for this column
+	pub(super) fn field(&self) -> Field {
+		let data_type = match self.kind {
+			Type::Bool => DataType::Boolean,
+			Type::Int => DataType::Int64,
+			Type::Float => DataType::Float64,
+			Type::Decimal(s) => DataType::Decimal128(DECIMAL_PRECISION, s as i8),
+			Type::Datetime => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+			Type::Duration => DataType::Duration(TimeUnit::Nanosecond),
+			Type::String => DataType::Utf8,
+			Type::Bytes => DataType::Binary,
+			Type::Null | Type::Json => DataType::Utf8,
+		};
+		let field = Field::new(&self.name, data_type, true);
+		match self.kind {
+			Type::Null | Type::Json => field.with_metadata(HashMap::from([(
+				EXTENSION_NAME.to_owned(),
+				EXTENSION_JSON.to_owned(),
+			)])),
+			_ => field,
+		}
+	}
+}
+
+/// Infers the columns of a result from a sample of its rows.
+///
+/// Rows which are objects produce a column for every field in the sample,
+/// while any other rows are exported in a single column. When the rows are
+/// records of a schemafull table, the field definitions of the table take
+/// precedence over the types found in the sample.
+pub(super) fn infer(
+	sample: &[Value],
+	fields: Option<&[DefineFieldStatement]>,
+) -> (Vec<Column>, bool) {
+	// Results which are not objects are exported as a single column
+	if sample.iter().any(|v| !matches!(v, Value::Object(_))) {
+		let kind = sample.iter().map(Type::of).fold(Type::Null, Type::merge);
+		let column = Column {
+			name: VALUE_COLUMN.to_owned(),
+			kind,
+		};
+		return (vec![column], false);
+	}
+	// Find the type of every field in the sample
+	let mut columns = BTreeMap::new();
+	for row in sample {
+		if let Value::Object(row) = row {
+			for (k, v) in row.iter() {
+				let kind = columns.entry(k.clone()).or_insert(Type::Null);
+				*kind = kind.merge(Type::of(v));
+			}
+		}
+	}
+	// Use the top-level field definitions of a schemafull table
+	if let Some(fields) = fields {
+		columns.retain(|k, _| k == "id");
+		columns.insert("id".to_owned(), Type::String);
+		for field in fields {
+			let [Part::Field(name)] = field.name.0.as_slice() else {
+				continue;
+			};
+			let sampled = sample
+				.iter()
+				.filter_map(|v| match v {
+					Value::Object(v) => v.get(name.as_str()),
+					_ => None,
+				})
+				.map(Type::of)
+				.fold(Type::Null, Type::merge);
+			let kind = match field.kind.as_ref().and_then(Type::from_kind) {
+				// The scale of decimals is taken from the sample
+				Some(Type::Decimal(_)) => Type::Decimal(0).merge(sampled),
+				Some(kind) => kind,
+				None => sampled,
+			};
+			columns.insert(name.0.clone(), kind);
+		}
+	}
+	let columns = columns
+		.into_iter()
+		.map(|(name, kind)| Column {
+			name,
+			kind,
+		})
+		.collect();
+	(columns, true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn;
+
+	fn sample(sql: &str) -> Vec<Value> {
+		match Value::from(syn::value(sql).unwrap()) {
+			Value::Array(v) => v.0,
+			v => vec![v],
+		}
+	}
+
+	#[test]
+	fn merge() {
+		assert_eq!(Type::Null.merge(Type::Int), Type::Int);
+		assert_eq!(Type::Int.merge(Type::Float), Type::Float);
+		assert_eq!(Type::Int.merge(Type::Decimal(2)), Type::Decimal(2));
+		assert_eq!(Type::Decimal(2).merge(Type::Decimal(4)), Type::Decimal(4));
+		assert_eq!(Type::Decimal(2).merge(Type::Float), Type::Float);
+		assert_eq!(Type::String.merge(Type::Int), Type::Json);
+		assert_eq!(Type::Json.merge(Type::Null), Type::Json);
+	}
+
+	#[test]
+	fn infer_objects() {
+		let rows = sample(
+			"[
+				{ id: person:one, age: 30, score: 1.5, tags: ['a'], born: d'2000-01-01T00:00:00Z' },
+				{ id: person:two, age: NONE, score: 2, tags: [], wait: 1s, balance: 1.25dec },
+			]",
+		);
+		let (columns, objects) = infer(&rows, None);
+		assert!(objects);
+		let columns: Vec<_> = columns.iter().map(|c| (c.name.as_str(), c.kind)).collect();
+		assert_eq!(
+			columns,
+			vec![
+				("age", Type::Int),
+				("balance", Type::Decimal(2)),
+				("born", Type::Datetime),
+				("id", Type::String),
+				("score", Type::Float),
+				("tags", Type::Json),
+				("wait", Type::Duration),
+			]
+		);
+	}
+
+	#[test]
+	fn infer_values() {
+		let (columns, objects) = infer(&sample("[1, 2, NULL, 3.5]"), None);
+		assert!(!objects);
+		assert_eq!(columns.len(), 1);
+		assert_eq!(columns[0].name, VALUE_COLUMN);
+		assert_eq!(columns[0].kind, Type::Float);
+		// Objects mixed with other values are exported as JSON
+		let (columns, objects) = infer(&sample("[{ a: 1 }, 2]"), None);
+		assert!(!objects);
+		assert_eq!(columns[0].kind, Type::Json);
+	}
+
+	#[test]
+	fn infer_json_fields() {
+		let field = Column {
+			name: "nested".to_owned(),
+			kind: Type::Json,
+		}
+		.field();
+		assert_eq!(field.data_type(), &DataType::Utf8);
+		assert_eq!(field.metadata().get(EXTENSION_NAME).map(String::as_str), Some(EXTENSION_JSON));
+	}
+}
@@ -6,4 +6,5 @@ pub(crate) mod writer;
 pub use self::gc::*;
 pub use self::mutations::*;
 pub use self::reader::read;
+pub(crate) use self::writer::Checkpoint;
 pub use self::writer::Writer;
@@ -1,3 +1,4 @@
+use crate::cnf::CHANGEFEED_AUDIT_MAX_SIZE;
 use crate::expr::Operation;
 use crate::expr::array::Array;
 use crate::expr::object::Object;
@@ -11,7 +12,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[non_exhaustive]
 pub enum TableMutation {
@@ -29,6 +30,69 @@ pub enum TableMutation {
 	#[revision(start = 2)]
 	/// Delete a record where the ID is stored, and the now-deleted value
 	DelWithOriginal(Thing, Value),
+	#[revision(start = 3)]
+	/// All the records in the table were removed with TRUNCATE
+	Truncate(DefineTableStatement),
+	#[revision(start = 4)]
+	/// A mutation stored along with who made it, and from which statement
+	Audited(Box<TableMutation>, ChangeAudit),
+}
+
+/// The details of who made a change, and from which statement,
+/// stored when the change feed is defined with INCLUDE AUDIT
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub struct ChangeAudit {
+	/// The record id or the user name of the session which made the change
+	pub identity: Option<String>,
+	/// The origin of the request which made the change
+	pub origin: Option<String>,
+	/// The normalised text of the statement which made the change
+	pub statement: String,
+	/// The fingerprint of the normalised statement
+	pub fingerprint: String,
+}
+
+impl ChangeAudit {
+	/// Creates the audit details, truncating each detail to the configured size
+	pub fn new(
+		identity: Option<String>,
+		origin: Option<String>,
+		statement: String,
+		fingerprint: String,
+	) -> Self {
+		Self {
+			identity: identity.map(truncate),
+			origin: origin.map(truncate),
+			statement: truncate(statement),
+			fingerprint,
+		}
+	}
+}
+
+/// Truncates a string to the maximum audit detail size, on a character boundary
+fn truncate(mut v: String) -> String {
+	let max = *CHANGEFEED_AUDIT_MAX_SIZE;
+	if v.len() > max {
+		let mut end = max;
+		while !v.is_char_boundary(end) {
+			end -= 1;
+		}
+		v.truncate(end);
+	}
+	v
+}
+
+impl From<ChangeAudit> for Value {
+	fn from(v: ChangeAudit) -> Self {
+		Value::from(map! {
+			"identity".to_string() => v.identity.into(),
+			"origin".to_string() => v.origin.into(),
+			"statement".to_string() => v.statement.into(),
+			"fingerprint".to_string() => v.fingerprint.into(),
+		})
+	}
 }
 
 impl From<DefineTableStatement> for Value {
@@ -83,6 +147,13 @@ impl TableMutation {
 	pub fn into_value(self) -> Value {
 		let mut h = BTreeMap::<String, Value>::new();
 		let h = match self {
+			TableMutation::Audited(m, audit) => {
+				let mut v = m.into_value();
+				if let Value::Object(o) = &mut v {
+					o.insert("audit".to_string(), audit.into());
+				}
+				return v;
+			}
 			TableMutation::Set(_thing, v) => {
 				h.insert("update".to_string(), v);
 				h
@@ -123,6 +194,10 @@ impl TableMutation {
 				);
 				h
 			}
+			TableMutation::Truncate(t) => {
+				h.insert("truncate_table".to_string(), Value::from(t));
+				h
+			}
 		};
 		let o = crate::expr::object::Object::from(h);
 		Value::Object(o)
@@ -144,7 +219,7 @@ impl DatabaseMutation {
 impl ChangeSet {
 	pub fn into_value(self) -> Value {
 		let mut m = BTreeMap::<String, Value>::new();
-		m.insert("versionstamp".to_string(), Value::from(self.0.into_u128()));
+		m.insert("versionstamp".to_string(), Value::from(self.0.into_u64_lossy()));
 		m.insert("changes".to_string(), self.1.into_value());
 		let so: Object = m.into();
 		Value::Object(so)
@@ -159,6 +234,8 @@ impl Display for TableMutation {
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
 			TableMutation::DelWithOriginal(id, _) => write!(f, "DEL {}", id),
 			TableMutation::Def(t) => write!(f, "{}", t),
+			TableMutation::Truncate(t) => write!(f, "TRUNCATE TABLE {}", t.name),
+			TableMutation::Audited(m, _) => write!(f, "{}", m),
 		}
 	}
 }
@@ -232,6 +309,10 @@ mod tests {
 						name: "mytb".into(),
 						..DefineTableStatement::default()
 					}),
+					TableMutation::Truncate(DefineTableStatement {
+						name: "mytb".into(),
+						..DefineTableStatement::default()
+					}),
 				],
 			)]),
 		);
@@ -239,7 +320,7 @@ mod tests {
 		let s = serde_json::to_string(&v).unwrap();
 		assert_eq!(
 			s,
-			r#"{"changes":[{"update":{"id":"mytb:tobie","note":"surreal"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}}],"versionstamp":65536}"#
+			r#"{"changes":[{"update":{"id":"mytb:tobie","note":"surreal"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}},{"truncate_table":{"name":"mytb"}}],"versionstamp":1}"#
 		);
 	}
 
@@ -301,7 +382,63 @@ mod tests {
 		let s = serde_json::to_string(&v).unwrap();
 		assert_eq!(
 			s,
-			r#"{"changes":[{"current":{"id":"mytb:tobie","note":"surreal"},"update":[{"op":"add","path":"/`/note`","value":"surreal"}]},{"current":{"id":"mytb:tobie2","note":"surreal"},"update":[{"op":"remove","path":"/`/temp`"}]},{"delete":{"id":"mytb:tobie"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}}],"versionstamp":65536}"#
+			r#"{"changes":[{"current":{"id":"mytb:tobie","note":"surreal"},"update":[{"op":"add","path":"/`/note`","value":"surreal"}]},{"current":{"id":"mytb:tobie2","note":"surreal"},"update":[{"op":"remove","path":"/`/temp`"}]},{"delete":{"id":"mytb:tobie"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}}],"versionstamp":1}"#
+		);
+	}
+
+	#[test]
+	fn serialization_rev4() {
+		use super::*;
+		let cs = ChangeSet(
+			VersionStamp::from_u64(1),
+			DatabaseMutation(vec![TableMutations(
+				"mytb".to_string(),
+				vec![
+					TableMutation::Audited(
+						Box::new(TableMutation::Del(Thing::from((
+							"mytb".to_string(),
+							"tobie".to_string(),
+						)))),
+						ChangeAudit::new(
+							Some("root".to_string()),
+							Some("http://localhost".to_string()),
+							"DELETE mytb:tobie".to_string(),
+							"0123456789abcdef".to_string(),
+						),
+					),
+					TableMutation::Audited(
+						Box::new(TableMutation::Del(Thing::from((
+							"mytb".to_string(),
+							"jaime".to_string(),
+						)))),
+						ChangeAudit::new(
+							None,
+							None,
+							"DELETE mytb:jaime".to_string(),
+							"fedcba9876543210".to_string(),
+						),
+					),
+				],
+			)]),
+		);
+		// Audited mutations survive a round trip through storage
+		let bytes = revision::to_vec(&cs).unwrap();
+		let back: ChangeSet = revision::from_slice(&bytes).unwrap();
+		assert_eq!(back, cs);
+		let v = cs.into_value().into_json();
+		let s = serde_json::to_string(&v).unwrap();
+		assert_eq!(
+			s,
+			r#"{"changes":[{"audit":{"fingerprint":"0123456789abcdef","identity":"root","origin":"http://localhost","statement":"DELETE mytb:tobie"},"delete":{"id":"mytb:tobie"}},{"audit":{"fingerprint":"fedcba9876543210","identity":null,"origin":null,"statement":"DELETE mytb:jaime"},"delete":{"id":"mytb:jaime"}}],"versionstamp":1}"#
 		);
 	}
+
+	#[test]
+	fn audit_truncation() {
+		use super::*;
+		let long = "é".repeat(*CHANGEFEED_AUDIT_MAX_SIZE);
+		let audit = ChangeAudit::new(Some(long.clone()), None, long, String::new());
+		assert!(audit.statement.len() <= *CHANGEFEED_AUDIT_MAX_SIZE);
+		assert_eq!(audit.identity.as_deref(), Some(audit.statement.as_str()));
+	}
 }
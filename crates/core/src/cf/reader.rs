@@ -1,27 +1,30 @@
 use crate::cf::{ChangeSet, DatabaseMutation, TableMutations};
-use crate::err::Error;
 use crate::expr::statements::show::ShowSince;
 use crate::key::change;
 #[cfg(debug_assertions)]
 use crate::key::debug::Sprintable;
 use crate::kvs::{KeyDecode, Transaction};
 use crate::vs::VersionStamp;
-use anyhow::{Result, bail};
+use anyhow::Result;
 
 // Reads the change feed for a specific database or a table,
-// starting from a specific versionstamp.
+// starting from a specific versionstamp, and optionally
+// ending at a specific versionstamp (inclusive).
 //
 // The limit parameter is the maximum number of change sets to return.
 // If the limit is not specified, the default is 100.
 //
-// You can use this to read the change feed in chunks.
-// The second call would start from the last versionstamp + 1 of the first call.
+// You can use this to read the change feed in chunks. The start
+// versionstamp is inclusive, so the second call would start from
+// the last versionstamp + 1 of the first call, which is returned
+// as the versionstamp of the SHOW CHANGES result.
 pub async fn read(
 	tx: &Transaction,
 	ns: &str,
 	db: &str,
 	tb: Option<&str>,
 	start: ShowSince,
+	until: Option<ShowSince>,
 	limit: Option<u32>,
 ) -> Result<Vec<ChangeSet>> {
 	// Calculate the start of the changefeed range
@@ -30,18 +33,33 @@ pub async fn read(
 		ShowSince::Timestamp(x) => {
 			let ts = x.0.timestamp() as u64;
 			let vs = tx.lock().await.get_versionstamp_from_timestamp(ts, ns, db).await?;
-			match vs {
+			// If no versionstamp was recorded before this
+			// timestamp, then read from the start of the feed
+			change::prefix_ts(ns, db, vs.unwrap_or(VersionStamp::ZERO))?
+		}
+	};
+	// Calculate the end of the changefeed range
+	let end = match until {
+		None => change::suffix(ns, db)?,
+		Some(ShowSince::Versionstamp(x)) => match x.checked_add(1) {
+			Some(x) => change::prefix_ts(ns, db, VersionStamp::from_u64(x))?,
+			None => change::suffix(ns, db)?,
+		},
+		Some(ShowSince::Timestamp(x)) => {
+			let ts = x.0.timestamp() as u64;
+			let vs = tx.lock().await.get_versionstamp_from_timestamp(ts, ns, db).await?;
+			match vs.and_then(VersionStamp::next) {
 				Some(vs) => change::prefix_ts(ns, db, vs)?,
-				None => {
-					bail!(Error::Internal(
-						"no versionstamp associated to this timestamp exists yet".to_string(),
-					))
-				}
+				// No versionstamp was recorded before this
+				// timestamp, so there are no changes to read
+				None => return Ok(Vec::new()),
 			}
 		}
 	};
-	// Calculate the end of the changefeed range
-	let end = change::suffix(ns, db)?;
+	// Check that the range is not empty
+	if beg >= end {
+		return Ok(Vec::new());
+	}
 	// Limit the changefeed results with a default
 	let limit = limit.unwrap_or(100).min(1000);
 	// Create an empty buffer for the versionstamp
@@ -50,8 +68,12 @@ pub async fn read(
 	let mut buf: Vec<TableMutations> = Vec::new();
 	// Create an empty buffer for the final changesets
 	let mut res = Vec::<ChangeSet>::new();
+	// Fetch the changefeed entries within the range
+	let entries = tx.scan(beg..end, limit, None).await?;
+	// Check if the scan was cut short by the limit
+	let truncated = entries.len() >= limit as usize;
 	// iterate over _x and put decoded elements to r
-	for (k, v) in tx.scan(beg..end, limit, None).await? {
+	for (k, v) in entries {
 		#[cfg(debug_assertions)]
 		trace!("Reading change feed entry: {}", k.sprint());
 		// Decode the changefeed entry key
@@ -78,8 +100,11 @@ pub async fn read(
 		}
 		buf.push(tb_muts);
 	}
-	// Collect all mutations together
-	if !buf.is_empty() {
+	// Collect all mutations together. When the scan was cut
+	// short, the last change set may be missing mutations for
+	// other tables, so we leave it for the next read, unless
+	// it is the only change set, so that readers still progress.
+	if !buf.is_empty() && (!truncated || tb.is_some() || res.is_empty()) {
 		let db_mut = DatabaseMutation(buf);
 		res.push(ChangeSet(vs.unwrap(), db_mut));
 	}
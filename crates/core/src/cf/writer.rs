@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::cf::{TableMutation, TableMutations};
+use crate::cf::{ChangeAudit, TableMutation, TableMutations};
 use crate::doc::CursorValue;
 use crate::expr::Idiom;
 use crate::expr::statements::DefineTableStatement;
@@ -27,7 +27,10 @@ pub struct Buffer {
 	pub b: HashMap<ChangeKey, TableMutations>,
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+// Checkpoint is the number of buffered mutations for each table at a point in time.
+pub(crate) type Checkpoint = HashMap<ChangeKey, usize>;
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
 #[non_exhaustive]
 pub struct ChangeKey {
 	pub ns: String,
@@ -64,6 +67,22 @@ impl Writer {
 		}
 	}
 
+	// checkpoint returns the number of mutations currently buffered for each table.
+	pub(crate) fn checkpoint(&self) -> Checkpoint {
+		self.buf.b.iter().map(|(k, v)| (k.clone(), v.1.len())).collect()
+	}
+
+	// restore discards all the mutations buffered since the checkpoint was taken.
+	pub(crate) fn restore(&mut self, checkpoint: &Checkpoint) {
+		self.buf.b.retain(|k, v| match checkpoint.get(k) {
+			Some(len) => {
+				v.1.truncate(*len);
+				true
+			}
+			None => false,
+		});
+	}
+
 	#[expect(clippy::too_many_arguments)]
 	pub(crate) fn record_cf_change(
 		&mut self,
@@ -74,42 +93,37 @@ impl Writer {
 		previous: CursorValue,
 		current: CursorValue,
 		store_difference: bool,
+		audit: Option<ChangeAudit>,
 	) {
-		if current.as_ref().is_some() {
-			self.buf.push(
-				ns.to_string(),
-				db.to_string(),
-				tb.to_string(),
-				match store_difference {
-					true => {
-						if previous.as_ref().is_none() {
-							TableMutation::Set(id, current.into_owned())
-						} else {
-							// We intentionally record the patches in reverse (current -> previous)
-							// because we cannot otherwise resolve operations such as "replace" and "remove".
-							let patches_to_create_previous =
-								current.diff(&previous, Idiom::default());
-							TableMutation::SetWithDiff(
-								id,
-								current.into_owned(),
-								patches_to_create_previous,
-							)
-						}
+		let mutation = if current.as_ref().is_some() {
+			match store_difference {
+				true => {
+					if previous.as_ref().is_none() {
+						TableMutation::Set(id, current.into_owned())
+					} else {
+						// We intentionally record the patches in reverse (current -> previous)
+						// because we cannot otherwise resolve operations such as "replace" and "remove".
+						let patches_to_create_previous = current.diff(&previous, Idiom::default());
+						TableMutation::SetWithDiff(
+							id,
+							current.into_owned(),
+							patches_to_create_previous,
+						)
 					}
-					false => TableMutation::Set(id, current.into_owned()),
-				},
-			);
+				}
+				false => TableMutation::Set(id, current.into_owned()),
+			}
 		} else {
-			self.buf.push(
-				ns.to_string(),
-				db.to_string(),
-				tb.to_string(),
-				match store_difference {
-					true => TableMutation::DelWithOriginal(id, previous.into_owned()),
-					false => TableMutation::Del(id),
-				},
-			);
-		}
+			match store_difference {
+				true => TableMutation::DelWithOriginal(id, previous.into_owned()),
+				false => TableMutation::Del(id),
+			}
+		};
+		let mutation = match audit {
+			Some(audit) => TableMutation::Audited(Box::new(mutation), audit),
+			None => mutation,
+		};
+		self.buf.push(ns.to_string(), db.to_string(), tb.to_string(), mutation);
 	}
 
 	pub(crate) fn define_table(&mut self, ns: &str, db: &str, tb: &str, dt: &DefineTableStatement) {
@@ -121,6 +135,21 @@ impl Writer {
 		)
 	}
 
+	pub(crate) fn truncate_table(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		dt: &DefineTableStatement,
+	) {
+		self.buf.push(
+			ns.to_string(),
+			db.to_string(),
+			tb.to_string(),
+			TableMutation::Truncate(dt.to_owned()),
+		)
+	}
+
 	// get returns all the mutations buffered for this transaction,
 	// that are to be written onto the key composed of the specified prefix + the current timestamp + the specified suffix.
 	pub(crate) fn get(&self) -> Result<Vec<PreparedWrite>> {
@@ -197,6 +226,7 @@ mod tests {
 			previous.clone().into(),
 			value_a.into(),
 			DONT_STORE_PREVIOUS,
+			None,
 		);
 		tx1.complete_changes(true).await.unwrap();
 		tx1.commit().await.unwrap();
@@ -215,6 +245,7 @@ mod tests {
 			previous.clone().into(),
 			value_c.into(),
 			DONT_STORE_PREVIOUS,
+			None,
 		);
 		tx2.complete_changes(true).await.unwrap();
 		tx2.commit().await.unwrap();
@@ -233,6 +264,7 @@ mod tests {
 			previous.clone().into(),
 			value_b.into(),
 			DONT_STORE_PREVIOUS,
+			None,
 		);
 		let thing_c2 = Thing {
 			tb: TB.to_owned(),
@@ -247,6 +279,7 @@ mod tests {
 			previous.clone().into(),
 			value_c2.into(),
 			DONT_STORE_PREVIOUS,
+			None,
 		);
 		tx3.complete_changes(true).await.unwrap();
 		tx3.commit().await.unwrap();
@@ -258,9 +291,10 @@ mod tests {
 		let start: u64 = 0;
 
 		let tx4 = ds.transaction(Write, Optimistic).await.unwrap();
-		let r = crate::cf::read(&tx4, NS, DB, Some(TB), ShowSince::Versionstamp(start), Some(10))
-			.await
-			.unwrap();
+		let r =
+			crate::cf::read(&tx4, NS, DB, Some(TB), ShowSince::Versionstamp(start), None, Some(10))
+				.await
+				.unwrap();
 		tx4.commit().await.unwrap();
 
 		let want: Vec<ChangeSet> = vec![
@@ -304,6 +338,35 @@ mod tests {
 
 		assert_eq!(r, want);
 
+		// Reading with an end bound only includes the changes up to that versionstamp
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		let r = crate::cf::read(
+			&tx,
+			NS,
+			DB,
+			Some(TB),
+			ShowSince::Versionstamp(3),
+			Some(ShowSince::Versionstamp(3)),
+			None,
+		)
+		.await
+		.unwrap();
+		assert_eq!(r, want[1..2].to_vec());
+		// Reading an empty range returns no changes
+		let r = crate::cf::read(
+			&tx,
+			NS,
+			DB,
+			Some(TB),
+			ShowSince::Versionstamp(4),
+			Some(ShowSince::Versionstamp(3)),
+			None,
+		)
+		.await
+		.unwrap();
+		assert!(r.is_empty());
+		tx.cancel().await.unwrap();
+
 		let tx5 = ds.transaction(Write, Optimistic).await.unwrap();
 		// gc_all needs to be committed before we can read the changes
 		crate::cf::gc_range(&tx5, NS, DB, VersionStamp::from_u64(4)).await.unwrap();
@@ -312,9 +375,10 @@ mod tests {
 
 		// Now we should see the gc_all results
 		let tx6 = ds.transaction(Write, Optimistic).await.unwrap();
-		let r = crate::cf::read(&tx6, NS, DB, Some(TB), ShowSince::Versionstamp(start), Some(10))
-			.await
-			.unwrap();
+		let r =
+			crate::cf::read(&tx6, NS, DB, Some(TB), ShowSince::Versionstamp(start), None, Some(10))
+				.await
+				.unwrap();
 		tx6.commit().await.unwrap();
 
 		let want: Vec<ChangeSet> = vec![ChangeSet(
@@ -339,7 +403,7 @@ mod tests {
 		ds.changefeed_process_at(None, (ts.0.timestamp() + 5).try_into().unwrap()).await.unwrap();
 
 		let tx7 = ds.transaction(Write, Optimistic).await.unwrap();
-		let r = crate::cf::read(&tx7, NS, DB, Some(TB), ShowSince::Timestamp(ts), Some(10))
+		let r = crate::cf::read(&tx7, NS, DB, Some(TB), ShowSince::Timestamp(ts), None, Some(10))
 			.await
 			.unwrap();
 		tx7.commit().await.unwrap();
@@ -386,6 +450,7 @@ mod tests {
 			DB,
 			Some(TB),
 			ShowSince::Versionstamp(vs.into_u64_lossy()),
+			None,
 			Some(10),
 		)
 		.await
@@ -409,6 +474,7 @@ mod tests {
 			previous,
 			value_a.into(),
 			DONT_STORE_PREVIOUS,
+			None,
 		);
 		tx.lock().await.complete_changes(true).await.unwrap();
 		tx.commit().await.unwrap();
@@ -425,6 +491,7 @@ mod tests {
 			changefeed: Some(ChangeFeed {
 				expiry: Duration::from_secs(10),
 				store_diff,
+				store_audit: false,
 			}),
 			..Default::default()
 		};
@@ -433,6 +500,7 @@ mod tests {
 			changefeed: Some(ChangeFeed {
 				expiry: Duration::from_secs(10 * 60),
 				store_diff,
+				store_audit: false,
 			}),
 			..Default::default()
 		};
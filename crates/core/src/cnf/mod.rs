@@ -23,6 +23,15 @@ pub static MEMORY_THRESHOLD: LazyLock<usize> =
 pub static MAX_CONCURRENT_TASKS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_MAX_CONCURRENT_TASKS", usize, 64);
 
+/// The number of tasks which process the records of a PARALLEL SELECT statement over a table, where 1 disables parallel scans (default: the number of CPU cores)
+#[cfg(not(target_family = "wasm"))]
+pub static PARALLEL_SCAN_TASKS: LazyLock<usize> = LazyLock::new(|| {
+	std::env::var("SURREAL_PARALLEL_SCAN_TASKS")
+		.ok()
+		.and_then(|s| s.parse::<usize>().ok())
+		.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+});
+
 /// Specifies how deep recursive computation will go before erroring (default: 120)
 pub static MAX_COMPUTATION_DEPTH: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_COMPUTATION_DEPTH", u32, 120);
@@ -59,10 +68,21 @@ pub static DATASTORE_CACHE_SIZE: LazyLock<usize> =
 pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_NORMAL_FETCH_SIZE", u32, 500);
 
+/// The maximum number of keys that the batches of a table or range scan grow to, while the scan continues (default: 10,000)
+pub static MAX_FETCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_MAX_FETCH_SIZE", u32, 10_000);
+
 /// The maximum number of keys that should be scanned at once for export queries (default: 1000)
 pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
 
+/// The maximum number of rows sent in each frame of a streamed query result (default: 1000)
+pub static STREAM_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_STREAM_BATCH_SIZE", usize, 1000);
+
+/// The maximum number of writes buffered by a bulk INSERT statement before they are applied to the transaction, or 0 to apply each write immediately (default: 1000)
+pub static WRITE_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WRITE_BATCH_SIZE", usize, 1000);
+
 /// The maximum number of keys that should be scanned at once for count queries (default: 10,000)
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_COUNT_BATCH_SIZE", u32, 10_000);
@@ -71,6 +91,61 @@ pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of index terms a fuzzy full-text query term can match (default: 50)
+pub static FULLTEXT_FUZZY_MAX_EXPANSIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_FULLTEXT_FUZZY_MAX_EXPANSIONS", usize, 50);
+
+/// The maximum number of index terms which can be listed at once by the search::terms function (default: 1000)
+pub static FULLTEXT_MAX_LISTED_TERMS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_FULLTEXT_MAX_LISTED_TERMS", usize, 1000);
+
+/// The maximum number of vector index nodes a KNN search visits when looking for records matching a condition (default: 100000)
+pub static KNN_FILTER_MAX_VISITED: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_KNN_FILTER_MAX_VISITED", usize, 100_000);
+
+/// The maximum number of records the graph::path function visits when looking for a path (default: 10000)
+pub static GRAPH_PATH_MAX_VISITED: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GRAPH_PATH_MAX_VISITED", usize, 10_000);
+
+/// The maximum size in bytes of each audit detail stored alongside a change feed entry (default: 1024)
+pub static CHANGEFEED_AUDIT_MAX_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_CHANGEFEED_AUDIT_MAX_SIZE", usize, 1024);
+
+/// The maximum number of times the delivery of an outbox message is attempted (default: 10)
+pub static OUTBOX_MAX_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_OUTBOX_MAX_ATTEMPTS", u32, 10);
+
+/// The delay in milliseconds before an outbox message is sent again, doubled on every further attempt (default: 1000)
+pub static OUTBOX_RETRY_BACKOFF: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OUTBOX_RETRY_BACKOFF", u64, 1000);
+
+/// The maximum delay in seconds before an outbox message is sent again (default: 3600)
+pub static OUTBOX_MAX_RETRY_DELAY: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OUTBOX_MAX_RETRY_DELAY", u64, 3600);
+
+/// The maximum time in seconds which the delivery of an outbox message is allowed to take (default: 30)
+pub static OUTBOX_REQUEST_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OUTBOX_REQUEST_TIMEOUT", u64, 30);
+
+/// The period of time in seconds for which delivered and failed outbox messages are kept (default: 604800)
+pub static OUTBOX_RETENTION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OUTBOX_RETENTION", u64, 604_800);
+
+/// The maximum time in seconds which a run of a scheduled statement is allowed to take (default: 3600)
+pub static SCHEDULE_RUN_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SCHEDULE_RUN_TIMEOUT", u64, 3600);
+
+/// The maximum number of scheduled statements which are run at the same time (default: 4)
+pub static SCHEDULE_CONCURRENCY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_SCHEDULE_CONCURRENCY", usize, 4);
+
+/// The period of time in seconds for which the run history of a schedule is kept (default: 2592000)
+pub static SCHEDULE_HISTORY_RETENTION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SCHEDULE_HISTORY_RETENTION", u64, 2_592_000);
+
+/// The period of time in seconds for which past versions of the records can be read, 0 for no limit (default: 0)
+pub static VERSION_RETENTION: LazyLock<u64> = lazy_env_parse!("SURREAL_VERSION_RETENTION", u64, 0);
+
 /// The maximum size of the priority queue triggering usage of the priority queue for the result collector.
 pub static MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE", u32, 1000);
@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 #[non_exhaustive]
 pub struct Canceller {
 	/// A reference to the canceled value of a context.
-	cancelled: Arc<AtomicBool>,
+	pub(super) cancelled: Arc<AtomicBool>,
 }
 
 impl Canceller {
@@ -19,4 +19,8 @@ impl Canceller {
 	pub fn cancel(&self) {
 		self.cancelled.store(true, Ordering::Relaxed);
 	}
+	/// Check whether the context has been cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
 }
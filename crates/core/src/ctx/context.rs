@@ -3,8 +3,11 @@ use crate::buc::{self, BucketConnectionKey, BucketConnections};
 use crate::cnf::PROTECTED_PARAM_NAMES;
 use crate::ctx::canceller::Canceller;
 use crate::ctx::reason::Reason;
-use crate::dbs::{Capabilities, Notification};
+use crate::dbs::capabilities::{SleepTarget, StatementTarget};
+use crate::dbs::{Capabilities, FunctionRegistry, Notification, Options};
+use crate::dbs::{QueryMemory, QueryStats, RowStream, SkippedFunctions, SubqueryCache};
 use crate::err::Error;
+use crate::expr::ScopeCapabilities;
 use crate::expr::value::Value;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
@@ -19,7 +22,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 use trice::Instant;
 
@@ -44,6 +47,8 @@ pub struct MutableContext {
 	deadline: Option<Instant>,
 	// Whether or not this context is cancelled.
 	cancelled: Arc<AtomicBool>,
+	// The total duration slept by this query, in nanoseconds.
+	slept: Arc<AtomicU64>,
 	// A collection of read only values stored in this context.
 	values: HashMap<Cow<'static, str>, Arc<Value>>,
 	// Stores the notification channel if available
@@ -74,6 +79,18 @@ pub struct MutableContext {
 	isolated: bool,
 	// A map of bucket connections
 	buckets: Option<Arc<BucketConnections>>,
+	// The functions registered with the datastore
+	functions: Option<Arc<FunctionRegistry>>,
+	// The statistics of the statement, for the slow query log
+	query_stats: Option<Arc<QueryStats>>,
+	// The memory budget of the statement
+	query_memory: Option<Arc<QueryMemory>>,
+	// The functions which the statement skipped, in a dry run
+	skipped_functions: Option<Arc<SkippedFunctions>>,
+	// The results of the uncorrelated subqueries of the statement
+	subquery_cache: Option<Arc<SubqueryCache>>,
+	// The stream which the rows of the statement are sent to
+	row_stream: Option<Arc<RowStream>>,
 }
 
 impl Default for MutableContext {
@@ -109,6 +126,7 @@ impl MutableContext {
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
+			slept: Arc::new(AtomicU64::new(0)),
 			notifications: None,
 			query_planner: None,
 			query_executor: None,
@@ -124,6 +142,12 @@ impl MutableContext {
 			transaction: None,
 			isolated: false,
 			buckets: None,
+			functions: None,
+			query_stats: None,
+			query_memory: None,
+			skipped_functions: None,
+			subquery_cache: None,
+			row_stream: None,
 		}
 	}
 
@@ -133,6 +157,7 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
+			slept: parent.slept.clone(),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
@@ -149,6 +174,12 @@ impl MutableContext {
 			isolated: false,
 			parent: Some(parent.clone()),
 			buckets: parent.buckets.clone(),
+			functions: parent.functions.clone(),
+			query_stats: parent.query_stats.clone(),
+			query_memory: parent.query_memory.clone(),
+			skipped_functions: parent.skipped_functions.clone(),
+			subquery_cache: parent.subquery_cache.clone(),
+			row_stream: parent.row_stream.clone(),
 		}
 	}
 
@@ -160,6 +191,7 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
+			slept: parent.slept.clone(),
 			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
@@ -176,6 +208,12 @@ impl MutableContext {
 			isolated: true,
 			parent: Some(parent.clone()),
 			buckets: parent.buckets.clone(),
+			functions: parent.functions.clone(),
+			query_stats: parent.query_stats.clone(),
+			query_memory: parent.query_memory.clone(),
+			skipped_functions: parent.skipped_functions.clone(),
+			subquery_cache: parent.subquery_cache.clone(),
+			row_stream: parent.row_stream.clone(),
 		}
 	}
 
@@ -188,6 +226,7 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
+			slept: from.slept.clone(),
 			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
 			query_executor: from.query_executor.clone(),
@@ -203,6 +242,12 @@ impl MutableContext {
 			isolated: false,
 			parent: None,
 			buckets: from.buckets.clone(),
+			functions: from.functions.clone(),
+			query_stats: from.query_stats.clone(),
+			query_memory: from.query_memory.clone(),
+			skipped_functions: from.skipped_functions.clone(),
+			subquery_cache: from.subquery_cache.clone(),
+			row_stream: from.row_stream.clone(),
 		}
 	}
 
@@ -217,12 +262,14 @@ impl MutableContext {
 		cache: Arc<DatastoreCache>,
 		#[cfg(storage)] temporary_directory: Option<Arc<PathBuf>>,
 		buckets: Arc<BucketConnections>,
+		functions: Arc<FunctionRegistry>,
 	) -> Result<MutableContext> {
 		let mut ctx = Self {
 			values: HashMap::default(),
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
+			slept: Arc::new(AtomicU64::new(0)),
 			notifications: None,
 			query_planner: None,
 			query_executor: None,
@@ -238,6 +285,12 @@ impl MutableContext {
 			transaction: None,
 			isolated: false,
 			buckets: Some(buckets),
+			functions: Some(functions),
+			query_stats: None,
+			query_memory: None,
+			skipped_functions: None,
+			subquery_cache: None,
+			row_stream: None,
 		};
 		if let Some(timeout) = time_out {
 			ctx.add_timeout(timeout)?;
@@ -285,6 +338,11 @@ impl MutableContext {
 		Canceller::new(cancelled)
 	}
 
+	/// Cancel the context and it's children when an existing canceller is called.
+	pub(crate) fn set_canceller(&mut self, canceller: &Canceller) {
+		self.cancelled = canceller.cancelled.clone();
+	}
+
 	/// Add a deadline to the context. If the current deadline is sooner than
 	/// the provided deadline, this method does nothing.
 	pub(crate) fn add_deadline(&mut self, deadline: Instant) {
@@ -433,6 +491,17 @@ impl MutableContext {
 		Ok(matches!(self.done(true)?, Some(Reason::Timedout)))
 	}
 
+	/// Check if a scan over the datastore should stop. Returns an error
+	/// when the context has timed out, or true when the context has been
+	/// cancelled, in which case the scan should stop without any error.
+	pub(crate) fn should_stop(&self) -> Result<bool> {
+		match self.done(true)? {
+			Some(Reason::Timedout) => bail!(Error::QueryTimedout),
+			Some(Reason::Canceled) => Ok(true),
+			None => Ok(false),
+		}
+	}
+
 	#[cfg(storage)]
 	/// Return the location of the temporary directory if any
 	pub(crate) fn temporary_directory(&self) -> Option<&Arc<PathBuf>> {
@@ -453,7 +522,7 @@ impl MutableContext {
 	}
 
 	/// Get a 'static view into the cancellation status.
-	#[cfg(feature = "scripting")]
+	#[cfg(any(feature = "scripting", storage))]
 	pub(crate) fn cancellation(&self) -> crate::ctx::cancellation::Cancellation {
 		crate::ctx::cancellation::Cancellation::new(
 			self.deadline,
@@ -477,6 +546,61 @@ impl MutableContext {
 		self.capabilities.clone()
 	}
 
+	/// Get the functions registered with the datastore
+	pub(crate) fn get_functions(&self) -> Option<&Arc<FunctionRegistry>> {
+		self.functions.as_ref()
+	}
+
+	/// Set the statistics collected for the slow query log
+	pub(crate) fn set_query_stats(&mut self, stats: Arc<QueryStats>) {
+		self.query_stats = Some(stats);
+	}
+
+	/// Get the statistics collected for the slow query log
+	pub(crate) fn get_query_stats(&self) -> Option<&Arc<QueryStats>> {
+		self.query_stats.as_ref()
+	}
+
+	/// Set the memory budget of the statement
+	pub(crate) fn set_query_memory(&mut self, memory: Arc<QueryMemory>) {
+		self.query_memory = Some(memory);
+	}
+
+	/// Get the memory budget of the statement, if its memory is limited
+	pub(crate) fn get_query_memory(&self) -> Option<&Arc<QueryMemory>> {
+		self.query_memory.as_ref()
+	}
+
+	/// Set the collector of the functions which are skipped in a dry run
+	pub(crate) fn set_skipped_functions(&mut self, skipped: Arc<SkippedFunctions>) {
+		self.skipped_functions = Some(skipped);
+	}
+
+	/// Get the collector of the functions which are skipped in a dry run
+	pub(crate) fn get_skipped_functions(&self) -> Option<&Arc<SkippedFunctions>> {
+		self.skipped_functions.as_ref()
+	}
+
+	/// Set the results of the uncorrelated subqueries of the statement
+	pub(crate) fn set_subquery_cache(&mut self, cache: Arc<SubqueryCache>) {
+		self.subquery_cache = Some(cache);
+	}
+
+	/// Get the results of the uncorrelated subqueries, if they are kept for the statement
+	pub(crate) fn get_subquery_cache(&self) -> Option<&Arc<SubqueryCache>> {
+		self.subquery_cache.as_ref()
+	}
+
+	/// Set the stream which the rows of the statement are sent to
+	pub(crate) fn set_row_stream(&mut self, stream: Arc<RowStream>) {
+		self.row_stream = Some(stream);
+	}
+
+	/// Get the stream which the rows of the statement are sent to, if they are streamed
+	pub(crate) fn get_row_stream(&self) -> Option<&Arc<RowStream>> {
+		self.row_stream.as_ref()
+	}
+
 	/// Check if scripting is allowed
 	#[cfg_attr(not(feature = "scripting"), expect(dead_code))]
 	pub(crate) fn check_allowed_scripting(&self) -> Result<()> {
@@ -488,16 +612,108 @@ impl MutableContext {
 		Ok(())
 	}
 
+	/// Check if a sleep is allowed, recording its duration against the query
+	pub(crate) fn check_allowed_sleep(
+		&self,
+		target: &SleepTarget,
+		duration: Duration,
+	) -> Result<()> {
+		// Check the duration of this sleep
+		if let Some(limit) = self.capabilities.max_sleep(target).filter(|v| duration > *v) {
+			warn!("Capabilities denied sleep attempt, duration: '{duration:?}'");
+			bail!(Error::SleepNotAllowed {
+				capability: "max sleep",
+				duration: duration.into(),
+				limit: limit.into(),
+			});
+		}
+		// Check the total duration slept by this query
+		let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+		let total = self.slept.fetch_add(nanos, Ordering::Relaxed).saturating_add(nanos);
+		let total = Duration::from_nanos(total);
+		if let Some(limit) = self.capabilities.max_query_sleep(target).filter(|v| total > *v) {
+			warn!("Capabilities denied sleep attempt, total duration: '{total:?}'");
+			bail!(Error::SleepNotAllowed {
+				capability: "max query sleep",
+				duration: total.into(),
+				limit: limit.into(),
+			});
+		}
+		trace!("Capabilities allowed sleep, duration: '{duration:?}'");
+		Ok(())
+	}
+
 	/// Check if a function is allowed
-	pub(crate) fn check_allowed_function(&self, target: &str) -> Result<()> {
+	pub(crate) async fn check_allowed_function(&self, opt: &Options, target: &str) -> Result<()> {
 		if !self.capabilities.allows_function_name(target) {
 			warn!("Capabilities denied function execution attempt, target: '{target}'");
 			bail!(Error::FunctionNotAllowed(target.to_string()));
 		}
+		if let Some(scope) = self.denying_scope(opt, |v| v.allows_function(target)).await? {
+			warn!(
+				"Capabilities of the {scope} denied function execution attempt, target: '{target}'"
+			);
+			bail!(Error::ScopedFunctionNotAllowed {
+				name: target.to_string(),
+				scope,
+			});
+		}
 		trace!("Capabilities allowed function execution, target: '{target}'");
 		Ok(())
 	}
 
+	/// Check if a statement is allowed in the current namespace and database
+	pub(crate) async fn check_allowed_statement(
+		&self,
+		opt: &Options,
+		target: &StatementTarget,
+	) -> Result<()> {
+		if let Some(scope) = self.denying_scope(opt, |v| v.allows_statement(target)).await? {
+			warn!(
+				"Capabilities of the {scope} denied statement execution attempt, target: '{target}'"
+			);
+			bail!(Error::StatementNotAllowed {
+				name: target.to_string(),
+				scope,
+			});
+		}
+		trace!("Capabilities allowed statement execution, target: '{target}'");
+		Ok(())
+	}
+
+	/// Returns the scope whose capabilities deny an operation, if any.
+	///
+	/// The capabilities of the database take precedence over the capabilities
+	/// of the namespace. Root users, and anonymous users when authentication is
+	/// disabled, are only restricted by the capabilities of the datastore.
+	async fn denying_scope(
+		&self,
+		opt: &Options,
+		allows: impl Fn(&ScopeCapabilities) -> Option<bool>,
+	) -> Result<Option<String>> {
+		if opt.auth.is_root() || (!opt.auth_enabled && opt.auth.is_anon()) {
+			return Ok(None);
+		}
+		let (Some(txn), Ok(ns)) = (self.transaction.as_ref(), opt.ns()) else {
+			return Ok(None);
+		};
+		if let Ok(db) = opt.db() {
+			if let Ok(def) = txn.get_db(ns, db).await {
+				match def.capabilities.as_ref().and_then(&allows) {
+					Some(true) => return Ok(None),
+					Some(false) => return Ok(Some(format!("database `{db}`"))),
+					None => (),
+				}
+			}
+		}
+		if let Ok(def) = txn.get_ns(ns).await {
+			if let Some(false) = def.capabilities.as_ref().and_then(&allows) {
+				return Ok(Some(format!("namespace `{ns}`")));
+			}
+		}
+		Ok(None)
+	}
+
 	/// Checks if the provided URL's network target is allowed based on current capabilities.
 	///
 	/// This function performs a validation to ensure that the outgoing network connection
@@ -0,0 +1,452 @@
+//! The audit log of definition changes.
+//!
+//! Every DEFINE, REMOVE, and ALTER statement which changes a definition of a
+//! namespace, or of one of its databases, records an entry in the audit log
+//! of the namespace. The entry is written in the same transaction as the
+//! definition, so every committed change is recorded, and no entry is kept
+//! for a change which is cancelled. Statements which leave the definition
+//! unchanged, such as `DEFINE ... IF NOT EXISTS` on an existing definition,
+//! are not recorded. The definitions of the root, such as namespaces and root
+//! users, belong to no namespace, and are not recorded.
+//!
+//! The audit log keeps the number of entries set in the capabilities of the
+//! datastore, after which the oldest entries are removed.
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Base, Datetime, Value};
+use crate::key::namespace::au;
+use crate::kvs::Transaction;
+use anyhow::Result;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A definition which a statement changes
+pub(crate) enum Target<'a> {
+	Namespace,
+	Database(&'a str),
+	Function(&'a str),
+	Analyzer(&'a str),
+	Access(&'a str, &'a Base),
+	Param(&'a str),
+	Table(&'a str),
+	Event(&'a str, &'a str),
+	Field(&'a str, String),
+	Index(&'a str, &'a str),
+	User(&'a str, &'a Base),
+	Model(&'a str, &'a str),
+	Config(&'a str),
+	Api(String),
+	Bucket(&'a str),
+	Sequence(&'a str),
+	Schedule(&'a str),
+}
+
+impl Target<'_> {
+	/// Returns the keyword of the definition, such as `TABLE`
+	pub(crate) fn resource(&self) -> &'static str {
+		match self {
+			Self::Namespace => "NAMESPACE",
+			Self::Database(_) => "DATABASE",
+			Self::Function(_) => "FUNCTION",
+			Self::Analyzer(_) => "ANALYZER",
+			Self::Access(..) => "ACCESS",
+			Self::Param(_) => "PARAM",
+			Self::Table(_) => "TABLE",
+			Self::Event(..) => "EVENT",
+			Self::Field(..) => "FIELD",
+			Self::Index(..) => "INDEX",
+			Self::User(..) => "USER",
+			Self::Model(..) => "MODEL",
+			Self::Config(_) => "CONFIG",
+			Self::Api(_) => "API",
+			Self::Bucket(_) => "BUCKET",
+			Self::Sequence(_) => "SEQUENCE",
+			Self::Schedule(_) => "SCHEDULE",
+		}
+	}
+
+	/// Returns the base which the definition belongs to
+	fn base(&self) -> Base {
+		match self {
+			Self::Namespace => Base::Root,
+			Self::Database(_) => Base::Ns,
+			Self::Access(_, base) | Self::User(_, base) => (*base).clone(),
+			_ => Base::Db,
+		}
+	}
+
+	/// Fetches the current definition
+	async fn fetch(&self, txn: &Transaction, opt: &Options) -> Result<Option<Definition>> {
+		let ns = opt.ns()?;
+		let res = match self {
+			Self::Database(db) => {
+				txn.get_db(ns, db).await.ok().map(|v| Definition::new(v.to_string()))
+			}
+			Self::Function(fc) => txn
+				.get_db_function(ns, opt.db()?, fc)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Analyzer(az) => txn
+				.get_db_analyzer(ns, opt.db()?, az)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Access(ac, Base::Ns) => txn
+				.get_ns_access(ns, ac)
+				.await
+				.ok()
+				.map(|v| Definition::redacted(v.to_string(), v.redacted().to_string())),
+			Self::Access(ac, Base::Db) => txn
+				.get_db_access(ns, opt.db()?, ac)
+				.await
+				.ok()
+				.map(|v| Definition::redacted(v.to_string(), v.redacted().to_string())),
+			Self::Param(pa) => txn
+				.get_db_param(ns, opt.db()?, pa)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Table(tb) => {
+				txn.get_tb(ns, opt.db()?, tb).await.ok().map(|v| Definition::new(v.to_string()))
+			}
+			Self::Event(tb, ev) => txn
+				.get_tb_event(ns, opt.db()?, tb, ev)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Field(tb, fd) => txn
+				.get_tb_field(ns, opt.db()?, tb, fd)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Index(tb, ix) => txn
+				.get_tb_index(ns, opt.db()?, tb, ix)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::User(us, Base::Ns) => {
+				txn.get_ns_user(ns, us).await.ok().map(|v| Definition::new(v.to_string()))
+			}
+			Self::User(us, Base::Db) => txn
+				.get_db_user(ns, opt.db()?, us)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Model(ml, vn) => txn
+				.get_db_model(ns, opt.db()?, ml, vn)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Config(cg) => txn
+				.get_db_config(ns, opt.db()?, cg)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Api(ap) => {
+				txn.get_db_api(ns, opt.db()?, ap).await.ok().map(|v| Definition::new(v.to_string()))
+			}
+			Self::Bucket(bu) => txn
+				.get_db_bucket(ns, opt.db()?, bu)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Sequence(sq) => txn
+				.get_db_sequence(ns, opt.db()?, sq)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			Self::Schedule(sc) => txn
+				.get_db_schedule(ns, opt.db()?, sc)
+				.await
+				.ok()
+				.map(|v| Definition::new(v.to_string())),
+			// The definitions of the root are not recorded
+			Self::Namespace | Self::Access(..) | Self::User(..) => None,
+		};
+		Ok(res)
+	}
+}
+
+/// A definition, as it is compared and as it is recorded
+#[derive(PartialEq)]
+struct Definition {
+	/// The definition, including its secrets
+	full: String,
+	/// The definition, with its secrets redacted
+	redacted: Option<String>,
+}
+
+impl Definition {
+	fn new(full: String) -> Self {
+		Self {
+			full,
+			redacted: None,
+		}
+	}
+
+	fn redacted(full: String, redacted: String) -> Self {
+		Self {
+			full,
+			redacted: Some(redacted),
+		}
+	}
+
+	/// Returns the definition which is recorded in the audit log
+	fn into_recorded(self) -> String {
+		self.redacted.unwrap_or(self.full)
+	}
+}
+
+/// The audit of a statement which changes a definition
+pub(crate) struct Audit<'a> {
+	/// The changed definition, if the change is recorded
+	target: Option<Target<'a>>,
+	/// The definition before the statement ran
+	previous: Option<Definition>,
+}
+
+impl<'a> Audit<'a> {
+	/// Starts the audit of a statement, before the statement runs
+	pub(crate) async fn begin(ctx: &Context, opt: &Options, target: Target<'a>) -> Result<Self> {
+		// Check if the change is recorded
+		if ctx.get_capabilities().audit_log_size() == 0 || target.base() == Base::Root {
+			return Ok(Self {
+				target: None,
+				previous: None,
+			});
+		}
+		// Fetch the definition before the change
+		let previous = target.fetch(&ctx.tx(), opt).await?;
+		Ok(Self {
+			target: Some(target),
+			previous,
+		})
+	}
+
+	/// Records the change in the audit log, once the statement succeeded
+	pub(crate) async fn finish(
+		self,
+		ctx: &Context,
+		opt: &Options,
+		statement: impl FnOnce() -> String,
+	) -> Result<()> {
+		let Some(target) = self.target else {
+			return Ok(());
+		};
+		let txn = ctx.tx();
+		// Check if the definition was changed
+		let current = target.fetch(&txn, opt).await?;
+		if current == self.previous {
+			return Ok(());
+		}
+		let ns = opt.ns()?;
+		let entry = AuditEntry {
+			statement: statement(),
+			db: match &target {
+				Target::Database(db) => Some((*db).to_owned()),
+				_ if target.base() == Base::Db => Some(opt.db()?.to_owned()),
+				_ => None,
+			},
+			actor: opt.auth.id().to_owned(),
+			level: opt.auth.level().to_string(),
+			ip: session(ctx, "ip"),
+			origin: session(ctx, "or"),
+			at: Datetime::default(),
+			previous: self.previous.map(Definition::into_recorded),
+		};
+		let key = au::new(ns, Uuid::now_v7());
+		txn.set(key, revision::to_vec(&entry)?, None).await?;
+		// Remove the oldest entries over the size of the audit log
+		let size = ctx.get_capabilities().audit_log_size();
+		let beg = au::prefix(ns)?;
+		let end = au::suffix(ns)?;
+		let keys = txn.keysr(beg.clone()..end, size as u32 + 1, None).await?;
+		if keys.len() > size {
+			if let Some(last) = keys.last() {
+				let mut end = last.clone();
+				end.push(0x00);
+				txn.delr(beg..end).await?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A change of a definition, which was recorded in the audit log
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub struct AuditEntry {
+	/// The statement which changed the definition
+	pub statement: String,
+	/// The database of the definition, if it belongs to a database
+	pub db: Option<String>,
+	/// The user which ran the statement
+	pub actor: String,
+	/// The level of the user which ran the statement
+	pub level: String,
+	/// The ip address of the session which ran the statement
+	pub ip: Option<String>,
+	/// The origin of the session which ran the statement
+	pub origin: Option<String>,
+	/// The time at which the statement ran
+	pub at: Datetime,
+	/// The definition before the statement ran, if it existed
+	pub previous: Option<String>,
+}
+
+impl InfoStructure for AuditEntry {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"statement".to_string() => Value::from(self.statement),
+			"db".to_string(), if let Some(v) = self.db => Value::from(v),
+			"actor".to_string() => Value::from(self.actor),
+			"level".to_string() => Value::from(self.level),
+			"ip".to_string(), if let Some(v) = self.ip => Value::from(v),
+			"origin".to_string(), if let Some(v) = self.origin => Value::from(v),
+			"at".to_string() => Value::Datetime(self.at),
+			"previous".to_string(), if let Some(v) = self.previous => Value::from(v),
+		})
+	}
+}
+
+/// Fetches the entries of the audit log of a namespace, from the oldest to
+/// the newest, limited to the entries of a database if one is specified
+pub(crate) async fn entries(
+	txn: &Transaction,
+	ns: &str,
+	db: Option<&str>,
+) -> Result<Vec<AuditEntry>> {
+	let beg = au::prefix(ns)?;
+	let end = au::suffix(ns)?;
+	let mut out = Vec::new();
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = txn.batch_keys_vals(rng, *crate::cnf::NORMAL_FETCH_SIZE, None).await?;
+		next = res.next;
+		for (_, v) in res.result {
+			let entry: AuditEntry = revision::from_slice(&v)?;
+			if db.is_none_or(|db| entry.db.as_deref() == Some(db)) {
+				out.push(entry);
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Fetches a string property of the session
+fn session(ctx: &Context, key: &str) -> Option<String> {
+	match ctx.value("session") {
+		Some(Value::Object(v)) => match v.get(key) {
+			Some(Value::Strand(v)) => Some(v.0.clone()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::{Capabilities, Session};
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType::*;
+	use crate::kvs::TransactionType::*;
+
+	async fn entries(ds: &Datastore) -> Vec<super::AuditEntry> {
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = super::entries(&txn, "test", Some("test")).await.unwrap();
+		txn.cancel().await.unwrap();
+		res
+	}
+
+	#[tokio::test]
+	async fn definition_changes_are_recorded() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut sess = Session::owner().with_ns("test").with_db("test");
+		sess.ip = Some("127.0.0.1".to_string());
+		let sql = "
+			DEFINE TABLE person SCHEMALESS;
+			DEFINE TABLE IF NOT EXISTS person SCHEMAFULL;
+			DEFINE TABLE person SCHEMAFULL;
+			DEFINE TABLE OVERWRITE person SCHEMAFULL;
+			REMOVE TABLE IF EXISTS nothing;
+			DEFINE ACCESS api ON DATABASE TYPE JWT ALGORITHM HS512 KEY 'secret';
+			BEGIN;
+			DEFINE PARAM $cancelled VALUE 1;
+			CANCEL;
+			REMOVE TABLE person;
+		";
+		ds.execute(sql, &sess, None).await.unwrap();
+		let entries = entries(&ds).await;
+		let statements: Vec<_> = entries.iter().map(|e| e.statement.as_str()).collect();
+		assert_eq!(
+			statements,
+			vec![
+				"DEFINE TABLE person TYPE ANY SCHEMALESS PERMISSIONS NONE",
+				"DEFINE TABLE OVERWRITE person TYPE ANY SCHEMAFULL PERMISSIONS NONE",
+				statements[2],
+				"REMOVE TABLE person",
+			]
+		);
+		// The secrets of access methods are redacted
+		assert!(statements[2].starts_with("DEFINE ACCESS api ON DATABASE"), "{}", statements[2]);
+		assert!(!statements[2].contains("secret"), "{}", statements[2]);
+		// The previous definition is recorded for diffing
+		assert_eq!(entries[0].previous, None);
+		assert_eq!(
+			entries[3].previous.as_deref(),
+			Some("DEFINE TABLE person TYPE ANY SCHEMAFULL PERMISSIONS NONE")
+		);
+		// The session which made the change is recorded
+		assert_eq!(entries[0].ip.as_deref(), Some("127.0.0.1"));
+		assert_eq!(entries[0].level, "/");
+		assert_eq!(entries[0].db.as_deref(), Some("test"));
+	}
+
+	#[tokio::test]
+	async fn audit_log_keeps_the_newest_entries() {
+		let caps = Capabilities::default().with_audit_log_size(2);
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(caps);
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE PARAM $a VALUE 1; DEFINE PARAM $b VALUE 2; DEFINE PARAM $c VALUE 3;";
+		ds.execute(sql, &sess, None).await.unwrap();
+		let statements: Vec<_> = entries(&ds).await.into_iter().map(|e| e.statement).collect();
+		assert_eq!(
+			statements,
+			vec![
+				"DEFINE PARAM $b VALUE 2 PERMISSIONS FULL",
+				"DEFINE PARAM $c VALUE 3 PERMISSIONS FULL"
+			]
+		);
+		// The audit log can be disabled
+		let caps = Capabilities::default().with_audit_log_size(0);
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(caps);
+		ds.execute(sql, &sess, None).await.unwrap();
+		assert!(entries(&ds).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn audit_log_is_only_shown_to_owners() {
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(true);
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE DATABASE other; DEFINE TABLE person", &sess, None).await.unwrap();
+		let res = ds.execute("INFO FOR DB AUDIT", &sess, None).await.unwrap();
+		let Value::Array(db) = res.into_iter().next().unwrap().result.unwrap() else {
+			panic!("expected the entries of the audit log");
+		};
+		assert_eq!(db.len(), 1);
+		// The audit log of the namespace includes the changes of its definitions
+		let res = ds.execute("INFO FOR NS AUDIT", &sess, None).await.unwrap();
+		let Value::Array(ns) = res.into_iter().next().unwrap().result.unwrap() else {
+			panic!("expected the entries of the audit log");
+		};
+		assert_eq!(ns.len(), 2);
+		let sess = Session::editor().with_ns("test").with_db("test");
+		let res = ds.execute("INFO FOR DB AUDIT", &sess, None).await.unwrap();
+		assert!(res.into_iter().next().unwrap().result.is_err());
+	}
+}
@@ -1,12 +1,14 @@
+use crate::iam::throttle::AuthThrottleLimits;
 use crate::iam::{Auth, Level};
 use crate::rpc::Method;
 use ipnet::IpNet;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::Hash;
 use std::net::IpAddr;
 #[cfg(all(target_family = "wasm", feature = "http"))]
 use std::net::ToSocketAddrs;
+use std::time::Duration;
 #[cfg(all(not(target_family = "wasm"), feature = "http"))]
 use tokio::net::lookup_host;
 use url::Url;
@@ -111,6 +113,98 @@ impl std::str::FromStr for FuncTarget {
 	}
 }
 
+/// The statements which can be allowed or denied by the capabilities of a namespace or database
+const STATEMENT_KINDS: &[&str] = &["DEFINE", "ALTER", "REMOVE"];
+
+/// The resources which a statement kind can be narrowed down to
+const STATEMENT_RESOURCES: &[&str] = &[
+	"NAMESPACE",
+	"DATABASE",
+	"FUNCTION",
+	"ANALYZER",
+	"ACCESS",
+	"PARAM",
+	"TABLE",
+	"EVENT",
+	"FIELD",
+	"INDEX",
+	"USER",
+	"MODEL",
+	"CONFIG",
+	"API",
+	"BUCKET",
+	"SEQUENCE",
+	"SCHEDULE",
+];
+
+/// A statement kind, such as `REMOVE` or `REMOVE DATABASE`
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct StatementTarget(pub String, pub Option<String>);
+
+impl StatementTarget {
+	pub(crate) fn new(kind: &str, resource: &str) -> Self {
+		Self(kind.to_string(), Some(resource.to_string()))
+	}
+}
+
+impl fmt::Display for StatementTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.1 {
+			Some(resource) => write!(f, "{} {resource}", self.0),
+			None => write!(f, "{}", self.0),
+		}
+	}
+}
+
+impl Target for StatementTarget {
+	fn matches(&self, elem: &StatementTarget) -> bool {
+		match self {
+			Self(kind, Some(resource)) => kind == &elem.0 && elem.1.as_ref() == Some(resource),
+			Self(kind, None) => kind == &elem.0,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub enum ParseStatementTargetError {
+	InvalidKind,
+	InvalidResource,
+}
+
+impl std::error::Error for ParseStatementTargetError {}
+impl fmt::Display for ParseStatementTargetError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			ParseStatementTargetError::InvalidKind => {
+				write!(f, "invalid statement target, expected a DEFINE, ALTER, or REMOVE statement")
+			}
+			ParseStatementTargetError::InvalidResource => {
+				write!(f, "invalid statement target resource")
+			}
+		}
+	}
+}
+
+impl std::str::FromStr for StatementTarget {
+	type Err = ParseStatementTargetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim().to_ascii_uppercase();
+		let mut parts = s.split_whitespace();
+		let kind = parts.next().ok_or(ParseStatementTargetError::InvalidKind)?;
+		if !STATEMENT_KINDS.contains(&kind) {
+			return Err(ParseStatementTargetError::InvalidKind);
+		}
+		let resource = match (parts.next(), parts.next()) {
+			(None, _) => None,
+			(Some(v), None) if STATEMENT_RESOURCES.contains(&v) => Some(v.to_string()),
+			_ => return Err(ParseStatementTargetError::InvalidResource),
+		};
+		Ok(StatementTarget(kind.to_string(), resource))
+	}
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum ExperimentalTarget {
@@ -523,6 +617,95 @@ impl std::str::FromStr for ArbitraryQueryTarget {
 	}
 }
 
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SleepTarget {
+	Guest,
+	Record,
+	Database,
+	Namespace,
+	Root,
+}
+
+impl fmt::Display for SleepTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Guest => write!(f, "guest"),
+			Self::Record => write!(f, "record"),
+			Self::Database => write!(f, "database"),
+			Self::Namespace => write!(f, "namespace"),
+			Self::Root => write!(f, "root"),
+		}
+	}
+}
+
+impl<'a> From<&'a Level> for SleepTarget {
+	fn from(level: &'a Level) -> Self {
+		match level {
+			Level::No => SleepTarget::Guest,
+			Level::Root => SleepTarget::Root,
+			Level::Namespace(_) => SleepTarget::Namespace,
+			Level::Database(_, _) => SleepTarget::Database,
+			Level::Record(_, _, _) => SleepTarget::Record,
+		}
+	}
+}
+
+impl<'a> From<&'a Auth> for SleepTarget {
+	fn from(auth: &'a Auth) -> Self {
+		auth.level().into()
+	}
+}
+
+impl Target for SleepTarget {
+	fn matches(&self, elem: &SleepTarget) -> bool {
+		self == elem
+	}
+}
+
+impl Target<str> for SleepTarget {
+	fn matches(&self, elem: &str) -> bool {
+		match self {
+			Self::Guest => elem.eq_ignore_ascii_case("guest"),
+			Self::Record => elem.eq_ignore_ascii_case("record"),
+			Self::Database => elem.eq_ignore_ascii_case("database"),
+			Self::Namespace => elem.eq_ignore_ascii_case("namespace"),
+			Self::Root => elem.eq_ignore_ascii_case("root"),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub enum ParseSleepTargetError {
+	InvalidName,
+}
+
+impl std::error::Error for ParseSleepTargetError {}
+impl fmt::Display for ParseSleepTargetError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			ParseSleepTargetError::InvalidName => {
+				write!(f, "invalid sleep target name")
+			}
+		}
+	}
+}
+
+impl std::str::FromStr for SleepTarget {
+	type Err = ParseSleepTargetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"guest" => Ok(SleepTarget::Guest),
+			"record" => Ok(SleepTarget::Record),
+			"database" => Ok(SleepTarget::Database),
+			"namespace" => Ok(SleepTarget::Namespace),
+			"root" => Ok(SleepTarget::Root),
+			_ => Err(ParseSleepTargetError::InvalidName),
+		}
+	}
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Targets<T: Hash + Eq + PartialEq> {
@@ -567,6 +750,73 @@ impl<T: Target + Hash + Eq + PartialEq + fmt::Display> fmt::Display for Targets<
 	}
 }
 
+/// What happens when the notification queue of a live query is full
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LiveQueryOverflow {
+	/// Drop the oldest queued notification
+	DropOldest,
+	/// Drop the notification which did not fit in the queue
+	DropNewest,
+	/// Kill the live query, and notify the client that it fell behind
+	#[default]
+	Kill,
+}
+
+impl fmt::Display for LiveQueryOverflow {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DropOldest => write!(f, "drop_oldest"),
+			Self::DropNewest => write!(f, "drop_newest"),
+			Self::Kill => write!(f, "kill"),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub enum ParseLiveQueryOverflowError {
+	InvalidName,
+}
+
+impl std::error::Error for ParseLiveQueryOverflowError {}
+impl fmt::Display for ParseLiveQueryOverflowError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			ParseLiveQueryOverflowError::InvalidName => {
+				write!(f, "invalid live query overflow policy")
+			}
+		}
+	}
+}
+
+impl std::str::FromStr for LiveQueryOverflow {
+	type Err = ParseLiveQueryOverflowError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"drop_oldest" => Ok(LiveQueryOverflow::DropOldest),
+			"drop_newest" => Ok(LiveQueryOverflow::DropNewest),
+			"kill" => Ok(LiveQueryOverflow::Kill),
+			_ => Err(ParseLiveQueryOverflowError::InvalidName),
+		}
+	}
+}
+
+/// The default window over which the failed authentication attempts are counted
+pub const DEFAULT_AUTH_ATTEMPTS_WINDOW: Duration = Duration::from_secs(60);
+
+/// The default number of prepared queries which are kept on each connection
+pub const DEFAULT_PREPARED_QUERIES: usize = 100;
+
+/// The default estimated number of bytes which an ORDER BY clause sorts in memory
+pub const DEFAULT_SORT_MEMORY: u64 = 64 * 1024 * 1024;
+
+/// The default number of entries which are kept in the audit log of each namespace
+pub const DEFAULT_AUDIT_LOG_SIZE: usize = 10_000;
+
+/// The default maximum number of retries of an http function request
+pub const DEFAULT_MAX_HTTP_RETRIES: u32 = 5;
+
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Capabilities {
@@ -586,13 +836,36 @@ pub struct Capabilities {
 	deny_experimental: Targets<ExperimentalTarget>,
 	allow_arbitrary_query: Targets<ArbitraryQueryTarget>,
 	deny_arbitrary_query: Targets<ArbitraryQueryTarget>,
+
+	max_sleep: Option<Duration>,
+	max_query_sleep: Option<Duration>,
+	unlimited_sleep: Targets<SleepTarget>,
+
+	live_query_queue_size: Option<usize>,
+	live_query_overflow: LiveQueryOverflow,
+
+	auth_throttle: Option<AuthThrottleLimits>,
+
+	prepared_queries: usize,
+
+	max_query_memory: Option<u64>,
+	namespace_max_query_memory: HashMap<String, u64>,
+
+	sort_memory: u64,
+	max_sort_spill: Option<u64>,
+
+	audit_log_size: usize,
+
+	max_http_timeout: Option<Duration>,
+	max_http_retries: u32,
+	max_http_body: Option<u64>,
 }
 
 impl fmt::Display for Capabilities {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}",
+			"scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}, max_sleep={}, max_query_sleep={}, unlimited_sleep={}, live_query_queue_size={}, live_query_overflow={}, auth_throttle={}, prepared_queries={}, max_query_memory={}, namespace_max_query_memory={}, sort_memory={} bytes, max_sort_spill={}, audit_log_size={}, max_http_timeout={}, max_http_retries={}, max_http_body={}",
 			self.scripting,
 			self.guest_access,
 			self.live_query_notifications,
@@ -608,10 +881,62 @@ impl fmt::Display for Capabilities {
 			self.deny_experimental,
 			self.allow_arbitrary_query,
 			self.deny_arbitrary_query,
+			fmt_limit(self.max_sleep),
+			fmt_limit(self.max_query_sleep),
+			self.unlimited_sleep,
+			fmt_size(self.live_query_queue_size),
+			self.live_query_overflow,
+			fmt_throttle(self.auth_throttle),
+			self.prepared_queries,
+			fmt_bytes(self.max_query_memory),
+			fmt_namespace_bytes(&self.namespace_max_query_memory),
+			self.sort_memory,
+			fmt_bytes(self.max_sort_spill),
+			self.audit_log_size,
+			fmt_limit(self.max_http_timeout),
+			self.max_http_retries,
+			fmt_bytes(self.max_http_body),
 		)
 	}
 }
 
+fn fmt_limit(limit: Option<Duration>) -> String {
+	match limit {
+		Some(v) => format!("{v:?}"),
+		None => "none".to_string(),
+	}
+}
+
+fn fmt_size(limit: Option<usize>) -> String {
+	match limit {
+		Some(v) => v.to_string(),
+		None => "none".to_string(),
+	}
+}
+
+fn fmt_bytes(limit: Option<u64>) -> String {
+	match limit {
+		Some(v) => format!("{v} bytes"),
+		None => "none".to_string(),
+	}
+}
+
+fn fmt_namespace_bytes(limits: &HashMap<String, u64>) -> String {
+	if limits.is_empty() {
+		return "none".to_string();
+	}
+	let mut limits = limits.iter().map(|(ns, v)| format!("{ns}={v} bytes")).collect::<Vec<_>>();
+	limits.sort();
+	limits.join(", ")
+}
+
+fn fmt_throttle(limits: Option<AuthThrottleLimits>) -> String {
+	match limits {
+		Some(v) => format!("{} per {:?}", v.attempts, v.window),
+		None => "none".to_string(),
+	}
+}
+
 impl Default for Capabilities {
 	fn default() -> Self {
 		Self {
@@ -631,6 +956,29 @@ impl Default for Capabilities {
 			deny_experimental: Targets::None,
 			allow_arbitrary_query: Targets::All,
 			deny_arbitrary_query: Targets::None,
+
+			max_sleep: None,
+			max_query_sleep: None,
+			unlimited_sleep: Targets::from(SleepTarget::Root),
+
+			live_query_queue_size: None,
+			live_query_overflow: LiveQueryOverflow::default(),
+
+			auth_throttle: None,
+
+			prepared_queries: DEFAULT_PREPARED_QUERIES,
+
+			max_query_memory: None,
+			namespace_max_query_memory: HashMap::new(),
+
+			sort_memory: DEFAULT_SORT_MEMORY,
+			max_sort_spill: None,
+
+			audit_log_size: DEFAULT_AUDIT_LOG_SIZE,
+
+			max_http_timeout: None,
+			max_http_retries: DEFAULT_MAX_HTTP_RETRIES,
+			max_http_body: None,
 		}
 	}
 }
@@ -654,6 +1002,29 @@ impl Capabilities {
 			deny_experimental: Targets::None,
 			allow_arbitrary_query: Targets::All,
 			deny_arbitrary_query: Targets::None,
+
+			max_sleep: None,
+			max_query_sleep: None,
+			unlimited_sleep: Targets::from(SleepTarget::Root),
+
+			live_query_queue_size: None,
+			live_query_overflow: LiveQueryOverflow::default(),
+
+			auth_throttle: None,
+
+			prepared_queries: DEFAULT_PREPARED_QUERIES,
+
+			max_query_memory: None,
+			namespace_max_query_memory: HashMap::new(),
+
+			sort_memory: DEFAULT_SORT_MEMORY,
+			max_sort_spill: None,
+
+			audit_log_size: DEFAULT_AUDIT_LOG_SIZE,
+
+			max_http_timeout: None,
+			max_http_retries: DEFAULT_MAX_HTTP_RETRIES,
+			max_http_body: None,
 		}
 	}
 
@@ -675,6 +1046,29 @@ impl Capabilities {
 			deny_experimental: Targets::None,
 			allow_arbitrary_query: Targets::None,
 			deny_arbitrary_query: Targets::None,
+
+			max_sleep: None,
+			max_query_sleep: None,
+			unlimited_sleep: Targets::None,
+
+			live_query_queue_size: None,
+			live_query_overflow: LiveQueryOverflow::default(),
+
+			auth_throttle: None,
+
+			prepared_queries: DEFAULT_PREPARED_QUERIES,
+
+			max_query_memory: None,
+			namespace_max_query_memory: HashMap::new(),
+
+			sort_memory: DEFAULT_SORT_MEMORY,
+			max_sort_spill: None,
+
+			audit_log_size: DEFAULT_AUDIT_LOG_SIZE,
+
+			max_http_timeout: None,
+			max_http_retries: DEFAULT_MAX_HTTP_RETRIES,
+			max_http_body: None,
 		}
 	}
 
@@ -745,6 +1139,91 @@ impl Capabilities {
 		self
 	}
 
+	pub fn with_max_sleep(mut self, max_sleep: Option<Duration>) -> Self {
+		self.max_sleep = max_sleep;
+		self
+	}
+
+	pub fn with_max_query_sleep(mut self, max_query_sleep: Option<Duration>) -> Self {
+		self.max_query_sleep = max_query_sleep;
+		self
+	}
+
+	pub fn with_unlimited_sleep(mut self, unlimited_sleep: Targets<SleepTarget>) -> Self {
+		self.unlimited_sleep = unlimited_sleep;
+		self
+	}
+
+	/// Bound the notification queues of the live queries on each connection
+	pub fn with_live_query_queue_size(mut self, live_query_queue_size: Option<usize>) -> Self {
+		self.live_query_queue_size = live_query_queue_size;
+		self
+	}
+
+	pub fn with_live_query_overflow(mut self, live_query_overflow: LiveQueryOverflow) -> Self {
+		self.live_query_overflow = live_query_overflow;
+		self
+	}
+
+	/// Throttle the authentication attempts which fail too often
+	pub fn with_auth_throttle(mut self, limits: Option<AuthThrottleLimits>) -> Self {
+		self.auth_throttle = limits;
+		self
+	}
+
+	/// Set the number of prepared queries which are kept on each connection.
+	/// Prepared queries are disabled when this is zero.
+	pub fn with_prepared_queries(mut self, prepared_queries: usize) -> Self {
+		self.prepared_queries = prepared_queries;
+		self
+	}
+
+	pub fn with_max_query_memory(mut self, max_query_memory: Option<u64>) -> Self {
+		self.max_query_memory = max_query_memory;
+		self
+	}
+
+	pub fn with_namespace_max_query_memory(mut self, ns: impl Into<String>, limit: u64) -> Self {
+		self.namespace_max_query_memory.insert(ns.into(), limit);
+		self
+	}
+
+	/// Set the estimated number of bytes which an ORDER BY clause sorts in memory,
+	/// before the sorted rows are written to the temporary directory
+	pub fn with_sort_memory(mut self, sort_memory: u64) -> Self {
+		self.sort_memory = sort_memory;
+		self
+	}
+
+	/// Set the number of bytes which an ORDER BY clause can write to the temporary directory
+	pub fn with_max_sort_spill(mut self, max_sort_spill: Option<u64>) -> Self {
+		self.max_sort_spill = max_sort_spill;
+		self
+	}
+
+	pub fn with_audit_log_size(mut self, audit_log_size: usize) -> Self {
+		self.audit_log_size = audit_log_size;
+		self
+	}
+
+	/// Limit the time which an http function request can take
+	pub fn with_max_http_timeout(mut self, max_http_timeout: Option<Duration>) -> Self {
+		self.max_http_timeout = max_http_timeout;
+		self
+	}
+
+	/// Limit the number of times which a failed http function request is retried
+	pub fn with_max_http_retries(mut self, max_http_retries: u32) -> Self {
+		self.max_http_retries = max_http_retries;
+		self
+	}
+
+	/// Limit the number of bytes accepted in the response body of an http function
+	pub fn with_max_http_body(mut self, max_http_body: Option<u64>) -> Self {
+		self.max_http_body = max_http_body;
+		self
+	}
+
 	pub fn with_network_targets(mut self, allow_net: Targets<NetTarget>) -> Self {
 		self.allow_net = allow_net;
 		self
@@ -811,6 +1290,79 @@ impl Capabilities {
 		self.allow_arbitrary_query.matches(target) && !self.deny_arbitrary_query.matches(target)
 	}
 
+	/// The longest duration a single sleep may run for at the specified level
+	pub fn max_sleep(&self, target: &SleepTarget) -> Option<Duration> {
+		match self.unlimited_sleep.matches(target) {
+			true => None,
+			false => self.max_sleep,
+		}
+	}
+
+	/// The longest total duration a query may sleep for at the specified level
+	pub fn max_query_sleep(&self, target: &SleepTarget) -> Option<Duration> {
+		match self.unlimited_sleep.matches(target) {
+			true => None,
+			false => self.max_query_sleep,
+		}
+	}
+
+	/// The maximum number of notifications which can be queued for each live
+	/// query on a connection. Connections can ask for a smaller queue, and the
+	/// queues are unbounded when this is not set.
+	pub fn live_query_queue_size(&self) -> Option<usize> {
+		self.live_query_queue_size
+	}
+
+	/// What happens when the notification queue of a live query is full
+	pub fn live_query_overflow(&self) -> LiveQueryOverflow {
+		self.live_query_overflow
+	}
+
+	/// The thresholds used when throttling the authentication attempts
+	pub fn auth_throttle(&self) -> Option<AuthThrottleLimits> {
+		self.auth_throttle
+	}
+
+	/// The number of prepared queries which are kept on each connection
+	pub fn prepared_queries(&self) -> usize {
+		self.prepared_queries
+	}
+
+	/// The estimated number of bytes which a statement can hold in the specified namespace
+	pub fn max_query_memory(&self, ns: Option<&str>) -> Option<u64> {
+		ns.and_then(|ns| self.namespace_max_query_memory.get(ns).copied()).or(self.max_query_memory)
+	}
+
+	/// The estimated number of bytes which an ORDER BY clause sorts in memory
+	pub fn sort_memory(&self) -> u64 {
+		self.sort_memory
+	}
+
+	/// The number of bytes which an ORDER BY clause can write to the temporary directory
+	pub fn max_sort_spill(&self) -> Option<u64> {
+		self.max_sort_spill
+	}
+
+	/// The number of entries which are kept in the audit log of each namespace
+	pub fn audit_log_size(&self) -> usize {
+		self.audit_log_size
+	}
+
+	/// The maximum time which an http function request can take
+	pub fn max_http_timeout(&self) -> Option<Duration> {
+		self.max_http_timeout
+	}
+
+	/// The maximum number of times which a failed http function request is retried
+	pub fn max_http_retries(&self) -> u32 {
+		self.max_http_retries
+	}
+
+	/// The maximum number of bytes accepted in the response body of an http function
+	pub fn max_http_body(&self) -> Option<u64> {
+		self.max_http_body
+	}
+
 	pub fn allows_network_target(&self, target: &NetTarget) -> bool {
 		self.allow_net.matches(target) && !self.deny_net.matches(target)
 	}
@@ -863,6 +1415,23 @@ mod tests {
 		assert!(!FuncTarget::from_str("test::name").unwrap().matches("test::name2"));
 	}
 
+	#[test]
+	fn test_invalid_statement_target() {
+		StatementTarget::from_str("").unwrap_err();
+		StatementTarget::from_str("SELECT").unwrap_err();
+		StatementTarget::from_str("REMOVE DATABSE").unwrap_err();
+		StatementTarget::from_str("REMOVE DATABASE test").unwrap_err();
+	}
+
+	#[test]
+	fn test_statement_target() {
+		let target = StatementTarget::new("REMOVE", "DATABASE");
+		assert!(StatementTarget::from_str("REMOVE").unwrap().matches(&target));
+		assert!(StatementTarget::from_str("remove  database").unwrap().matches(&target));
+		assert!(!StatementTarget::from_str("REMOVE TABLE").unwrap().matches(&target));
+		assert!(!StatementTarget::from_str("DEFINE DATABASE").unwrap().matches(&target));
+	}
+
 	#[test]
 	fn test_net_target() {
 		// IPNet IPv4
@@ -1081,6 +1650,53 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_live_query_overflow() {
+		assert_eq!(
+			LiveQueryOverflow::from_str("drop_oldest").unwrap(),
+			LiveQueryOverflow::DropOldest
+		);
+		assert_eq!(
+			LiveQueryOverflow::from_str("DROP_NEWEST").unwrap(),
+			LiveQueryOverflow::DropNewest
+		);
+		assert_eq!(LiveQueryOverflow::from_str("kill").unwrap(), LiveQueryOverflow::Kill);
+		assert!(LiveQueryOverflow::from_str("block").is_err());
+		assert_eq!(LiveQueryOverflow::DropOldest.to_string(), "drop_oldest");
+		assert_eq!(Capabilities::default().live_query_overflow(), LiveQueryOverflow::Kill);
+		// The queues are unbounded unless a bound is set
+		assert_eq!(Capabilities::default().live_query_queue_size(), None);
+	}
+
+	#[test]
+	fn test_max_query_memory() {
+		assert_eq!(Capabilities::default().max_query_memory(Some("test")), None);
+		let caps = Capabilities::default()
+			.with_max_query_memory(Some(1024))
+			.with_namespace_max_query_memory("large", 4096);
+		assert_eq!(caps.max_query_memory(None), Some(1024));
+		assert_eq!(caps.max_query_memory(Some("test")), Some(1024));
+		assert_eq!(caps.max_query_memory(Some("large")), Some(4096));
+		let caps = Capabilities::default().with_namespace_max_query_memory("small", 16);
+		assert_eq!(caps.max_query_memory(Some("small")), Some(16));
+		assert_eq!(caps.max_query_memory(Some("test")), None);
+	}
+
+	#[test]
+	fn test_max_http() {
+		let caps = Capabilities::default();
+		assert_eq!(caps.max_http_timeout(), None);
+		assert_eq!(caps.max_http_retries(), DEFAULT_MAX_HTTP_RETRIES);
+		assert_eq!(caps.max_http_body(), None);
+		let caps = Capabilities::default()
+			.with_max_http_timeout(Some(Duration::from_secs(1)))
+			.with_max_http_retries(1)
+			.with_max_http_body(Some(1024));
+		assert_eq!(caps.max_http_timeout(), Some(Duration::from_secs(1)));
+		assert_eq!(caps.max_http_retries(), 1);
+		assert_eq!(caps.max_http_body(), Some(1024));
+	}
+
 	#[test]
 	fn test_targets() {
 		assert!(Targets::<NetTarget>::All.matches(&NetTarget::from_str("example.com").unwrap()));
@@ -0,0 +1,148 @@
+//! The reports of a dry run.
+//!
+//! A dry run executes every statement of a query as normal, within a single
+//! transaction which is always cancelled, so that nothing is written. The
+//! functions which have effects outside of the transaction are not run, and
+//! return NONE instead. The result of each statement is replaced with a
+//! report stating whether the statement would have succeeded.
+use crate::expr::{Object, Value};
+use parking_lot::Mutex;
+use std::collections::BTreeSet;
+
+/// The functions which have effects outside of the transaction
+const SIDE_EFFECTS: &[&str] = &[
+	"file::copy",
+	"file::copy_if_not_exists",
+	"file::delete",
+	"file::put",
+	"file::put_if_not_exists",
+	"file::rename",
+	"file::rename_if_not_exists",
+	"sequence::nextval",
+	"sleep",
+];
+
+/// Checks if a builtin function can not be run in a dry run
+pub(crate) fn has_side_effects(name: &str) -> bool {
+	name.starts_with("http::") || SIDE_EFFECTS.contains(&name)
+}
+
+/// The functions which a statement skipped while it was running
+#[derive(Debug, Default)]
+pub(crate) struct SkippedFunctions(Mutex<BTreeSet<String>>);
+
+impl SkippedFunctions {
+	/// Records that a function was not run
+	pub(crate) fn add(&self, name: &str) {
+		self.0.lock().insert(name.to_owned());
+	}
+
+	/// Takes the names of the functions which were not run
+	pub(crate) fn take(&self) -> Vec<String> {
+		std::mem::take(&mut *self.0.lock()).into_iter().collect()
+	}
+}
+
+/// Whether a statement would have succeeded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DryRunStatus {
+	/// The statement would have succeeded
+	Succeeded,
+	/// The statement would have failed
+	Failed,
+	/// The statement succeeded, but called functions which were not run
+	Unverified,
+}
+
+impl DryRunStatus {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Succeeded => "succeeded",
+			Self::Failed => "failed",
+			Self::Unverified => "unverified",
+		}
+	}
+}
+
+/// The report which replaces the result of a statement in a dry run
+#[derive(Debug)]
+pub(crate) struct DryRunReport {
+	/// The statement which was run
+	pub(crate) statement: String,
+	/// The error which the statement would have failed with
+	pub(crate) error: Option<String>,
+	/// The functions which the statement called, but which were not run
+	pub(crate) unverified: Vec<String>,
+}
+
+impl DryRunReport {
+	pub(crate) fn status(&self) -> DryRunStatus {
+		if self.error.is_some() {
+			DryRunStatus::Failed
+		} else if !self.unverified.is_empty() {
+			DryRunStatus::Unverified
+		} else {
+			DryRunStatus::Succeeded
+		}
+	}
+}
+
+impl From<DryRunReport> for Value {
+	fn from(r: DryRunReport) -> Self {
+		let mut res = Object::default();
+		res.insert("status".to_owned(), Value::from(r.status().as_str()));
+		res.insert("statement".to_owned(), Value::from(r.statement));
+		if let Some(error) = r.error {
+			res.insert("error".to_owned(), Value::from(error));
+		}
+		if !r.unverified.is_empty() {
+			let unverified = r.unverified.into_iter().map(Value::from).collect::<Vec<_>>();
+			res.insert("unverified".to_owned(), Value::from(unverified));
+		}
+		Value::from(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn side_effects() {
+		assert!(has_side_effects("http::get"));
+		assert!(has_side_effects("sleep"));
+		assert!(has_side_effects("file::put"));
+		assert!(!has_side_effects("file::get"));
+		assert!(!has_side_effects("rand::uuid"));
+	}
+
+	#[test]
+	fn report() {
+		let report = DryRunReport {
+			statement: "CREATE person".to_owned(),
+			error: None,
+			unverified: Vec::new(),
+		};
+		assert_eq!(report.status(), DryRunStatus::Succeeded);
+		assert_eq!(
+			Value::from(report).to_string(),
+			"{ statement: 'CREATE person', status: 'succeeded' }"
+		);
+		let report = DryRunReport {
+			statement: "RETURN http::get('https://surrealdb.com')".to_owned(),
+			error: None,
+			unverified: vec!["http::get".to_owned()],
+		};
+		assert_eq!(report.status(), DryRunStatus::Unverified);
+		assert_eq!(
+			Value::from(report).to_string(),
+			"{ statement: \"RETURN http::get('https://surrealdb.com')\", status: 'unverified', unverified: ['http::get'] }"
+		);
+		let report = DryRunReport {
+			statement: "THROW 'oops'".to_owned(),
+			error: Some("An error occurred: oops".to_owned()),
+			unverified: vec!["sleep".to_owned()],
+		};
+		assert_eq!(report.status(), DryRunStatus::Failed);
+	}
+}
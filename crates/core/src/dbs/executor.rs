@@ -1,9 +1,14 @@
-use crate::ctx::Context;
 use crate::ctx::reason::Reason;
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::Force;
 use crate::dbs::Options;
+use crate::dbs::QueryMemory;
 use crate::dbs::QueryType;
 use crate::dbs::response::Response;
+use crate::dbs::slowlog::{SlowQuery, SlowQueryLog};
+use crate::dbs::stats::{QueryStats, ResponseStats};
+use crate::dbs::{DryRunReport, SkippedFunctions};
+use crate::dbs::{QueryFrame, RowStream};
 use crate::err;
 use crate::err::Error;
 use crate::expr::Base;
@@ -22,7 +27,8 @@ use crate::sql::planner::SqlToLogical;
 use crate::sql::query::Query;
 use crate::sql::statement::Statement;
 use crate::sql::statements::{OptionStatement, UseStatement};
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Result, anyhow, bail, ensure};
+use async_channel::Sender;
 use futures::{Stream, StreamExt};
 use reblessive::TreeStack;
 use std::pin::{Pin, pin};
@@ -37,20 +43,71 @@ use wasm_bindgen_futures::spawn_local as spawn;
 
 const TARGET: &str = "surrealdb::core::dbs";
 
+/// The result of a statement in a dry run, which is held back until its
+/// transaction block has ended
+struct DryRunResponse {
+	time: Duration,
+	report: DryRunReport,
+	stats: Option<ResponseStats>,
+}
+
 pub struct Executor {
 	stack: TreeStack,
 	results: Vec<Response>,
 	opt: Options,
 	ctx: Context,
+	slow_log: Option<Arc<SlowQueryLog>>,
+	slow_queries: Vec<SlowQuery>,
+	/// The statistics of the last statement, when they were requested
+	stats: Option<ResponseStats>,
+	/// The channel which the results are sent to, when they are streamed
+	frames: Option<Sender<QueryFrame>>,
+	/// The number of statement results which were sent to the channel
+	sent: usize,
+	/// The stream for the rows of the next statement
+	row_stream: Option<Arc<RowStream>>,
+	/// The number of rows which the last statement streamed
+	streamed: Option<usize>,
+	/// The functions which the last statement skipped, in a dry run
+	skipped: Vec<String>,
 }
 
 impl Executor {
-	pub fn new(ctx: Context, opt: Options) -> Self {
+	pub fn new(kvs: &Datastore, ctx: Context, opt: Options) -> Self {
 		Executor {
 			stack: TreeStack::new(),
 			results: Vec::new(),
 			opt,
 			ctx,
+			slow_log: kvs.slow_query_log().cloned(),
+			slow_queries: Vec::new(),
+			stats: None,
+			frames: None,
+			sent: 0,
+			row_stream: None,
+			streamed: None,
+			skipped: Vec::new(),
+		}
+	}
+
+	/// Measures the reads of a transaction, when the statistics were requested
+	fn measured(&self, txn: Transaction) -> Transaction {
+		if self.opt.stats {
+			txn.with_read_metrics()
+		} else {
+			txn
+		}
+	}
+
+	/// Stores the slow statements which ran so far in the slow query log table
+	async fn store_slow_queries(&mut self, kvs: &Datastore) {
+		let Some(table) = self.slow_log.as_ref().and_then(|l| l.table()) else {
+			return;
+		};
+		for query in std::mem::take(&mut self.slow_queries) {
+			if let Err(e) = query.store(kvs, table).await {
+				warn!(target: TARGET, "Failed to store a slow query: {e}");
+			}
 		}
 	}
 
@@ -102,6 +159,13 @@ impl Executor {
 					self.opt.set_futures_never();
 				}
 			}
+			"TENANT" => {
+				// Only root users can access the records of every tenant
+				if !stmt.what {
+					self.opt.is_allowed(Action::Edit, ResourceKind::Option, &Base::Root)?;
+				}
+				self.opt.tenant = stmt.what;
+			}
 			_ => {}
 		};
 		Ok(())
@@ -114,55 +178,102 @@ impl Executor {
 		txn: Arc<Transaction>,
 		plan: LogicalPlan,
 	) -> FlowResult<Value> {
-		let res = match plan {
-			LogicalPlan::Set(stm) => {
-				// Avoid moving in and out of the context via Arc::get_mut
-				Arc::get_mut(&mut self.ctx)
-					.ok_or_else(|| {
-						err::Error::unreachable(
-							"Tried to unfreeze a Context with multiple references",
-						)
-					})
-					.map_err(anyhow::Error::new)?
-					.set_transaction(txn);
-				// Run the statement
-				match self
-					.stack
-					.enter(|stk| stm.compute(stk, &self.ctx, &self.opt, None))
-					.finish()
-					.await
-				{
-					Ok(val) => {
-						// Set the parameter
-						Arc::get_mut(&mut self.ctx)
-							.ok_or_else(|| {
-								err::Error::unreachable(
-									"Tried to unfreeze a Context with multiple references",
-								)
-							})
-							.map_err(anyhow::Error::new)?
-							.add_value(stm.name, val.into());
-						// Finalise transaction, returning nothing unless it couldn't commit
-						Ok(Value::None)
-					}
-					Err(err) => Err(err),
-				}
+		// The bytes read by the transaction before this statement
+		let bytes_read = txn.bytes_read();
+		// The transaction began successfully
+		Arc::get_mut(&mut self.ctx)
+			.ok_or_else(|| {
+				err::Error::unreachable("Tried to unfreeze a Context with multiple references")
+			})
+			.map_err(anyhow::Error::new)?
+			.set_transaction(txn);
+		// Apply the default session timeout, if the statement has none
+		let started = Instant::now();
+		let timeout = match plan.timeout() {
+			Some(_) => None,
+			None => self.opt.timeout,
+		};
+		// Collect the statistics of the statement, if they are requested
+		// for the response or needed by the slow query log
+		let stats =
+			(self.opt.stats || self.slow_log.is_some()).then(|| Arc::new(QueryStats::default()));
+		// Limit the memory of the statement, if this is configured for the namespace
+		let memory = self.ctx.get_capabilities().max_query_memory(self.opt.ns().ok());
+		// Send the rows of the statement as they are produced, if they are streamed
+		let stream = self.row_stream.take();
+		// Skip the functions with effects outside of the transaction, in a dry run
+		let skipped = self.opt.dry_run.then(|| Arc::new(SkippedFunctions::default()));
+		let ctx = if timeout.is_some()
+			|| stats.is_some()
+			|| memory.is_some()
+			|| stream.is_some()
+			|| skipped.is_some()
+		{
+			let mut ctx = MutableContext::new(&self.ctx);
+			if let Some(timeout) = timeout {
+				ctx.add_timeout(timeout).map_err(anyhow::Error::new)?;
 			}
-			// Process all other normal statements
-			plan => {
-				// The transaction began successfully
-				Arc::get_mut(&mut self.ctx)
-					.ok_or_else(|| {
-						err::Error::unreachable(
-							"Tried to unfreeze a Context with multiple references",
-						)
-					})
-					.map_err(anyhow::Error::new)?
-					.set_transaction(txn);
-				// Process the statement
-				self.stack.enter(|stk| plan.compute(stk, &self.ctx, &self.opt, None)).finish().await
+			if let Some(stats) = &stats {
+				ctx.set_query_stats(stats.clone());
+			}
+			if let Some(limit) = memory {
+				ctx.set_query_memory(Arc::new(QueryMemory::new(limit)));
+			}
+			if let Some(stream) = &stream {
+				ctx.set_row_stream(stream.clone());
 			}
+			if let Some(skipped) = &skipped {
+				ctx.set_skipped_functions(skipped.clone());
+			}
+			ctx.freeze()
+		} else {
+			self.ctx.clone()
 		};
+		// Process the statement
+		let res = self.stack.enter(|stk| plan.compute(stk, &ctx, &self.opt, None)).finish().await;
+		// The rows which were sent before the end of the statement
+		self.streamed = stream.filter(|s| s.is_claimed()).map(|s| s.sent());
+		// The functions which were not run
+		self.skipped = skipped.map(|s| s.take()).unwrap_or_default();
+		// Return the statistics with the result of the statement
+		if let Some(stats) = stats.as_ref().filter(|_| self.opt.stats) {
+			let read = ctx.tx().bytes_read().unwrap_or(0) - bytes_read.unwrap_or(0);
+			let mut response = stats.response(res.as_ref().ok(), read);
+			response.returned += self.streamed.unwrap_or(0) as u64;
+			self.stats = Some(response);
+		}
+		// Report the statement if it took longer than the threshold
+		if let (Some(log), Some(stats)) = (&self.slow_log, &stats) {
+			let elapsed = started.elapsed();
+			if elapsed >= log.threshold() {
+				let query =
+					SlowQuery::new(&ctx, &self.opt, &plan, stats, elapsed, res.as_ref().ok());
+				query.log();
+				// Nothing is written in a dry run, including the slow query log
+				if log.table().is_some() && !self.opt.dry_run {
+					self.slow_queries.push(query);
+				}
+			}
+		}
+
+		// Catch the default session timeout during running.
+		if let Some(timeout) = timeout {
+			let timedout = match &res {
+				Ok(_) => ctx.is_timedout().await?,
+				Err(ControlFlow::Err(e)) => matches!(e.downcast_ref(), Some(Error::QueryTimedout)),
+				Err(_) => false,
+			};
+			let elapsed = started.elapsed();
+			if timedout && elapsed >= timeout {
+				return Err(ControlFlow::from(anyhow!(Error::StatementTimedout {
+					statement: plan.to_string(),
+					timeout: timeout.into(),
+					elapsed: elapsed.into(),
+				})));
+			}
+		}
+		// Release the statement context
+		drop(ctx);
 
 		// Catch cancellation during running.
 		match self.ctx.done(true)? {
@@ -175,7 +286,22 @@ impl Executor {
 			}
 		}
 
-		res
+		match (plan, res) {
+			(LogicalPlan::Set(stm), Ok(val)) => {
+				// Set the parameter
+				Arc::get_mut(&mut self.ctx)
+					.ok_or_else(|| {
+						err::Error::unreachable(
+							"Tried to unfreeze a Context with multiple references",
+						)
+					})
+					.map_err(anyhow::Error::new)?
+					.add_value(stm.name, val.into());
+				// Finalise transaction, returning nothing unless it couldn't commit
+				Ok(Value::None)
+			}
+			(_, res) => res,
+		}
 	}
 
 	/// Execute a query not wrapped in a transaction block.
@@ -205,7 +331,21 @@ impl Executor {
 
 	async fn execute_plan_impl(&mut self, kvs: &Datastore, plan: LogicalPlan) -> Result<Value> {
 		let writeable = plan.writeable();
-		let txn = Arc::new(kvs.transaction(writeable.into(), LockType::Optimistic).await?);
+		// Read-only queries can not contain statements which could modify data
+		ensure!(
+			!(writeable && self.opt.readonly),
+			Error::QueryReadonly {
+				statement: plan.to_string(),
+			}
+		);
+		let txn = kvs.transaction(writeable.into(), LockType::Optimistic).await?;
+		let txn = Arc::new(self.measured(txn));
+		// Stream the rows of a top-level SELECT statement, when the results are streamed
+		if let (Some(frames), LogicalPlan::Select(stm)) = (&self.frames, &plan) {
+			if !stm.only {
+				self.row_stream = Some(Arc::new(RowStream::new(self.sent, frames.clone())));
+			}
+		}
 		let receiver = self.ctx.has_notifications().then(|| {
 			let (send, recv) = async_channel::unbounded();
 			self.opt.sender = Some(send);
@@ -254,7 +394,7 @@ impl Executor {
 
 				Ok(value)
 			}
-			Err(ControlFlow::Continue) | Err(ControlFlow::Break) => {
+			Err(ControlFlow::Continue(_)) | Err(ControlFlow::Break(_)) => {
 				bail!(Error::InvalidControlFlow)
 			}
 			Err(ControlFlow::Err(e)) => {
@@ -287,6 +427,7 @@ impl Executor {
 					time: Duration::ZERO,
 					result: Err(anyhow!(Error::QueryNotExecuted)),
 					query_type: QueryType::Other,
+					stats: None,
 				});
 			}
 
@@ -303,7 +444,7 @@ impl Executor {
 			recv
 		});
 
-		let txn = Arc::new(txn);
+		let txn = Arc::new(self.measured(txn));
 		let start_results = self.results.len();
 		let mut skip_remaining = false;
 
@@ -344,6 +485,7 @@ impl Executor {
 							Reason::Canceled => anyhow!(Error::QueryCancelled),
 						}),
 						query_type: QueryType::Other,
+						stats: None,
 					});
 				}
 
@@ -382,6 +524,7 @@ impl Executor {
 									.to_string(),
 						})),
 						query_type: QueryType::Other,
+						stats: None,
 					});
 
 					self.opt.sender = None;
@@ -397,6 +540,7 @@ impl Executor {
 							time: Duration::ZERO,
 							result: Err(anyhow!(Error::QueryNotExecuted)),
 							query_type: QueryType::Other,
+							stats: None,
 						});
 					}
 
@@ -473,13 +617,26 @@ impl Executor {
 					let planner = SqlToLogical::new();
 					let plan = planner.statement_to_logical(stmt)?;
 
-					let r = match self.execute_plan_in_transaction(txn.clone(), plan).await {
+					// Range deletes can not be rolled back on some datastores
+					let res = if matches!(plan, LogicalPlan::Truncate(_))
+						&& !txn.transactional_range_delete()
+					{
+						Err(ControlFlow::from(anyhow!(Error::TbTruncateInTransaction)))
+					} else if self.opt.readonly && plan.writeable() {
+						Err(ControlFlow::from(anyhow!(Error::QueryReadonly {
+							statement: plan.to_string(),
+						})))
+					} else {
+						self.execute_plan_in_transaction(txn.clone(), plan).await
+					};
+
+					let r = match res {
 						Ok(x) => Ok(x),
 						Err(ControlFlow::Return(value)) => {
 							skip_remaining = true;
 							Ok(value)
 						}
-						Err(ControlFlow::Break) | Err(ControlFlow::Continue) => {
+						Err(ControlFlow::Break(_)) | Err(ControlFlow::Continue(_)) => {
 							Err(anyhow!(Error::InvalidControlFlow))
 						}
 						Err(ControlFlow::Err(e)) => {
@@ -493,6 +650,7 @@ impl Executor {
 								time: before.elapsed(),
 								result: Err(e),
 								query_type,
+								stats: self.stats.take(),
 							});
 
 							let _ = txn.cancel().await;
@@ -510,6 +668,7 @@ impl Executor {
 									time: Duration::ZERO,
 									result: Err(anyhow!(Error::QueryNotExecuted)),
 									query_type: QueryType::Other,
+									stats: None,
 								});
 							}
 
@@ -533,6 +692,7 @@ impl Executor {
 				time: before.elapsed(),
 				result: value,
 				query_type,
+				stats: self.stats.take(),
 			});
 		}
 
@@ -563,27 +723,140 @@ impl Executor {
 		Self::execute_stream(kvs, ctx, opt, false, stream).await
 	}
 
+	/// Executes a query within a transaction of the caller, which is left open
+	/// to be committed or cancelled by the caller. A statement which fails
+	/// cancels the transaction, and the remaining statements are not run.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub(crate) async fn execute_in_transaction(
+		kvs: &Datastore,
+		ctx: Context,
+		opt: Options,
+		txn: Arc<Transaction>,
+		qry: Query,
+	) -> Result<Vec<Response>> {
+		let mut this = Executor::new(kvs, ctx, opt);
+		let mut failed = false;
+
+		for stmt in qry.into_iter() {
+			yield_now!();
+			let query_type: QueryType = (&stmt).into();
+			// The transaction was already cancelled
+			if failed {
+				this.results.push(Response {
+					time: Duration::ZERO,
+					result: Err(anyhow!(Error::QueryNotExecuted)),
+					query_type,
+					stats: None,
+				});
+				continue;
+			}
+
+			let now = Instant::now();
+			let result = match stmt {
+				// handle option here because it doesn't produce a result.
+				Statement::Option(stmt) => {
+					this.execute_option_statement(stmt)?;
+					continue;
+				}
+				Statement::Begin(_) | Statement::Cancel(_) | Statement::Commit(_) => {
+					Err(anyhow!(Error::QueryNotExecutedDetail {
+						message: "Tried to control a transaction which is managed by the client"
+							.to_string(),
+					}))
+				}
+				Statement::Use(stmt) => this.execute_use_statement(stmt).map(|_| Value::None),
+				stmt => this.execute_interactive_statement(txn.clone(), stmt).await,
+			};
+			this.store_slow_queries(kvs).await;
+
+			if result.is_err() {
+				let _ = txn.cancel().await;
+				this.opt.sender = None;
+				failed = true;
+			}
+
+			this.results.push(Response {
+				time: now.elapsed(),
+				result,
+				query_type,
+				stats: this.stats.take(),
+			});
+		}
+		Ok(this.results)
+	}
+
+	/// Execute a statement within a transaction of the caller.
+	async fn execute_interactive_statement(
+		&mut self,
+		txn: Arc<Transaction>,
+		stmt: Statement,
+	) -> Result<Value> {
+		let planner = SqlToLogical::new();
+		let plan = planner.statement_to_logical(stmt)?;
+		self.execute_interactive_plan(txn, plan).await
+	}
+
+	/// Execute a planned statement within an open transaction.
+	async fn execute_interactive_plan(
+		&mut self,
+		txn: Arc<Transaction>,
+		plan: LogicalPlan,
+	) -> Result<Value> {
+		// Range deletes can not be rolled back on some datastores
+		ensure!(
+			!matches!(plan, LogicalPlan::Truncate(_)) || txn.transactional_range_delete(),
+			Error::TbTruncateInTransaction
+		);
+		// Read-only queries can not contain statements which could modify data
+		ensure!(
+			!(self.opt.readonly && plan.writeable()),
+			Error::QueryReadonly {
+				statement: plan.to_string(),
+			}
+		);
+		match self.execute_plan_in_transaction(txn, plan).await {
+			Ok(value) | Err(ControlFlow::Return(value)) => Ok(value),
+			Err(ControlFlow::Continue(_)) | Err(ControlFlow::Break(_)) => {
+				bail!(Error::InvalidControlFlow)
+			}
+			Err(ControlFlow::Err(e)) => Err(e),
+		}
+	}
+
 	pub async fn execute_plan(
 		kvs: &Datastore,
 		ctx: Context,
 		opt: Options,
 		plan: LogicalPlan,
 	) -> Result<Vec<Response>> {
-		let mut this = Executor::new(ctx, opt);
+		let mut this = Executor::new(kvs, ctx, opt);
 
 		let query_type = match &plan {
+			_ if this.opt.dry_run => QueryType::Other,
 			LogicalPlan::Live(_) => QueryType::Live,
 			LogicalPlan::Kill(_) => QueryType::Kill,
 			_ => QueryType::Other,
 		};
 
 		let now = Instant::now();
-		let result = this.execute_plan_impl(kvs, plan).await;
+		let result = if this.opt.dry_run {
+			let statement = plan.to_string();
+			let txn = kvs.transaction(TransactionType::Write, LockType::Optimistic).await?;
+			let txn = Arc::new(this.measured(txn));
+			let res = this.execute_dry_plan(txn.clone(), plan).await;
+			// Nothing which was run is ever written
+			let _ = txn.cancel().await;
+			Ok(this.dry_run_report(statement, res).into())
+		} else {
+			this.execute_plan_impl(kvs, plan).await
+		};
+		this.store_slow_queries(kvs).await;
 
 		Ok(vec![Response {
 			time: now.elapsed(),
 			result,
 			query_type,
+			stats: this.stats.take(),
 		}])
 	}
 
@@ -598,54 +871,425 @@ impl Executor {
 	where
 		S: Stream<Item = Result<Statement>>,
 	{
-		let mut this = Executor::new(ctx, opt);
+		let mut this = Executor::new(kvs, ctx, opt);
+		this.run_stream(kvs, skip_success_results, stream).await?;
+		Ok(this.results)
+	}
+
+	/// Executes a stream of statements, sending the results to a channel
+	/// as they are produced, instead of collecting them.
+	///
+	/// The rows of each top-level SELECT statement are sent in batches while
+	/// the statement runs, and the result of every statement is followed by a
+	/// [`QueryFrame::Done`] frame. The results of a transaction are sent once
+	/// the transaction has finished. The query is cancelled if the receiver
+	/// of the channel is dropped.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub(crate) async fn execute_streamed<S>(
+		kvs: &Datastore,
+		ctx: Context,
+		opt: Options,
+		stream: S,
+		frames: Sender<QueryFrame>,
+	) -> Result<()>
+	where
+		S: Stream<Item = Result<Statement>>,
+	{
+		let mut this = Executor::new(kvs, ctx, opt);
+		this.frames = Some(frames);
+		this.run_stream(kvs, false, stream).await
+	}
+
+	async fn run_stream<S>(
+		&mut self,
+		kvs: &Datastore,
+		skip_success_results: bool,
+		stream: S,
+	) -> Result<()>
+	where
+		S: Stream<Item = Result<Statement>>,
+	{
 		let mut stream = pin!(stream);
 
+		if self.opt.dry_run {
+			self.run_dry_stream(kvs, skip_success_results, stream.as_mut()).await?;
+			return self.send_results().await;
+		}
+
 		while let Some(stmt) = stream.next().await {
 			yield_now!();
 			let stmt = match stmt {
 				Ok(x) => x,
 				Err(e) => {
-					this.results.push(Response {
+					self.results.push(Response {
 						time: Duration::ZERO,
 						result: Err(e),
 						query_type: QueryType::Other,
+						stats: None,
 					});
 
-					return Ok(this.results);
+					break;
 				}
 			};
 
 			match stmt {
-				Statement::Option(stmt) => this.execute_option_statement(stmt)?,
+				Statement::Option(stmt) => self.execute_option_statement(stmt)?,
 				// handle option here because it doesn't produce a result.
 				Statement::Begin(_) => {
-					if let Err(e) = this.execute_begin_statement(kvs, stream.as_mut()).await {
-						this.results.push(Response {
+					let res = self.execute_begin_statement(kvs, stream.as_mut()).await;
+					self.store_slow_queries(kvs).await;
+					if let Err(e) = res {
+						self.results.push(Response {
 							time: Duration::ZERO,
 							result: Err(e),
 							query_type: QueryType::Other,
+							stats: None,
 						});
 
-						return Ok(this.results);
+						break;
 					}
 				}
 				stmt => {
 					let query_type: QueryType = (&stmt).into();
 
 					let now = Instant::now();
-					let result = this.execute_bare_statement(kvs, stmt).await;
+					let result = self.execute_bare_statement(kvs, stmt).await;
+					self.store_slow_queries(kvs).await;
+					let stats = self.stats.take();
 					if !skip_success_results || result.is_err() {
-						this.results.push(Response {
+						self.results.push(Response {
 							time: now.elapsed(),
 							result,
 							query_type,
+							stats,
 						});
 					}
 				}
 			}
+			self.send_results().await?;
 		}
-		Ok(this.results)
+		self.send_results().await
+	}
+
+	/// Executes a stream of statements in a dry run.
+	///
+	/// Every statement runs within a single transaction, so that each statement
+	/// sees the changes which the statements before it would have made, and the
+	/// transaction is cancelled once the stream has finished. The result of each
+	/// statement is replaced with a report stating whether it would have succeeded.
+	async fn run_dry_stream<S>(
+		&mut self,
+		kvs: &Datastore,
+		skip_success_results: bool,
+		stream: Pin<&mut S>,
+	) -> Result<()>
+	where
+		S: Stream<Item = Result<Statement>>,
+	{
+		let txn = kvs.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		let txn = Arc::new(self.measured(txn));
+		let res = self.run_dry_statements(txn.clone(), skip_success_results, stream).await;
+		// Nothing which was run is ever written
+		let _ = txn.cancel().await;
+		res
+	}
+
+	async fn run_dry_statements<S>(
+		&mut self,
+		txn: Arc<Transaction>,
+		skip_success_results: bool,
+		mut stream: Pin<&mut S>,
+	) -> Result<()>
+	where
+		S: Stream<Item = Result<Statement>>,
+	{
+		// The reports of the statements in the open transaction block
+		let mut block: Option<Vec<DryRunResponse>> = None;
+
+		while let Some(stmt) = stream.next().await {
+			yield_now!();
+			let stmt = match stmt {
+				Ok(x) => x,
+				Err(e) => {
+					self.results.push(Response {
+						time: Duration::ZERO,
+						result: Err(e),
+						query_type: QueryType::Other,
+						stats: None,
+					});
+
+					break;
+				}
+			};
+
+			let statement = stmt.to_string();
+			let now = Instant::now();
+			let result = match stmt {
+				// handle option here because it doesn't produce a result.
+				Statement::Option(stmt) => {
+					self.execute_option_statement(stmt)?;
+					continue;
+				}
+				Statement::Begin(_) if block.is_none() => {
+					txn.lock().await.new_save_point().await?;
+					block = Some(Vec::new());
+					continue;
+				}
+				Statement::Commit(_) if block.is_some() => {
+					let block = block.take().unwrap_or_default();
+					self.end_dry_block(&txn, block, None, skip_success_results).await?;
+					self.send_results().await?;
+					continue;
+				}
+				Statement::Cancel(_) if block.is_some() => {
+					let block = block.take().unwrap_or_default();
+					let error = Error::QueryCancelled;
+					self.end_dry_block(&txn, block, Some(error), skip_success_results).await?;
+					self.send_results().await?;
+					continue;
+				}
+				Statement::Begin(_) => Err(anyhow!(Error::QueryNotExecutedDetail {
+					message: "Tried to start a transaction while another transaction was open"
+						.to_string(),
+				})),
+				// The statements after a failed statement in a transaction are not run
+				_ if block.as_ref().is_some_and(|b| b.iter().any(|r| r.report.error.is_some())) => {
+					Err(anyhow!(Error::QueryNotExecuted))
+				}
+				stmt => self.execute_dry_statement(txn.clone(), stmt).await,
+			};
+
+			let response = DryRunResponse {
+				time: now.elapsed(),
+				report: self.dry_run_report(statement, result),
+				stats: self.stats.take(),
+			};
+			match &mut block {
+				Some(block) => block.push(response),
+				None => {
+					self.push_dry_run_response(response, skip_success_results);
+					self.send_results().await?;
+				}
+			}
+		}
+
+		// we ran out of query but we still have an open transaction.
+		// Be conservative and treat this essentially as a CANCEL statement.
+		if let Some(block) = block {
+			let error = Error::QueryNotExecutedDetail {
+				message: "Missing COMMIT statement".to_string(),
+			};
+			self.end_dry_block(&txn, block, Some(error), skip_success_results).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Execute a statement in a dry run.
+	async fn execute_dry_statement(
+		&mut self,
+		txn: Arc<Transaction>,
+		stmt: Statement,
+	) -> Result<Value> {
+		// Don't even try to run if the query should already be finished.
+		match self.ctx.done(true)? {
+			None => {}
+			Some(Reason::Timedout) => {
+				bail!(Error::QueryTimedout);
+			}
+			Some(Reason::Canceled) => {
+				bail!(Error::QueryCancelled);
+			}
+		}
+
+		match stmt {
+			// These statements don't need a transaction.
+			Statement::Use(stmt) => self.execute_use_statement(stmt).map(|_| Value::None),
+			stmt => {
+				let planner = SqlToLogical::new();
+				let plan = planner.statement_to_logical(stmt)?;
+
+				self.execute_dry_plan(txn, plan).await
+			}
+		}
+	}
+
+	/// Execute a planned statement in a dry run, within a save point of the
+	/// transaction, so that its changes are undone if the statement fails.
+	async fn execute_dry_plan(
+		&mut self,
+		txn: Arc<Transaction>,
+		plan: LogicalPlan,
+	) -> Result<Value> {
+		txn.lock().await.new_save_point().await?;
+		let res = self.execute_interactive_plan(txn.clone(), plan).await;
+		let mut lock = txn.lock().await;
+		if res.is_ok() {
+			lock.release_last_save_point()?;
+		} else {
+			lock.rollback_to_save_point().await?;
+			drop(lock);
+			// The cache may hold the definitions which were rolled back
+			txn.clear();
+		}
+		res
+	}
+
+	/// Ends a transaction block in a dry run.
+	///
+	/// The changes of the block are undone if it would not have been committed,
+	/// in which case the statements which succeeded are reported with the error.
+	async fn end_dry_block(
+		&mut self,
+		txn: &Transaction,
+		mut block: Vec<DryRunResponse>,
+		error: Option<Error>,
+		skip_success_results: bool,
+	) -> Result<()> {
+		let failed = block.iter().any(|r| r.report.error.is_some());
+		let mut lock = txn.lock().await;
+		if error.is_none() && !failed {
+			lock.release_last_save_point()?;
+		} else {
+			lock.rollback_to_save_point().await?;
+			drop(lock);
+			txn.clear();
+			let message = error.unwrap_or(Error::QueryNotExecuted).to_string();
+			for res in block.iter_mut().filter(|r| r.report.error.is_none()) {
+				res.report.error = Some(message.clone());
+			}
+		}
+		for res in block {
+			self.push_dry_run_response(res, skip_success_results);
+		}
+		Ok(())
+	}
+
+	/// The report which replaces the result of a statement in a dry run
+	fn dry_run_report(&mut self, statement: String, result: Result<Value>) -> DryRunReport {
+		DryRunReport {
+			statement,
+			error: result.err().map(|e| e.to_string()),
+			unverified: std::mem::take(&mut self.skipped),
+		}
+	}
+
+	fn push_dry_run_response(&mut self, res: DryRunResponse, skip_success_results: bool) {
+		if skip_success_results && res.report.error.is_none() {
+			return;
+		}
+		self.results.push(Response {
+			time: res.time,
+			result: Ok(res.report.into()),
+			query_type: QueryType::Other,
+			stats: res.stats,
+		});
+	}
+
+	/// Sends the results collected so far to the channel, when they are streamed
+	async fn send_results(&mut self) -> Result<()> {
+		let Some(frames) = &self.frames else {
+			return Ok(());
+		};
+		// Only the last result can be from a top-level statement which streamed its rows
+		let streamed = self.streamed.take();
+		let last = self.results.len().saturating_sub(1);
+		for (i, response) in self.results.drain(..).enumerate() {
+			let frame = QueryFrame::Done {
+				query: self.sent,
+				response,
+				rows: streamed.filter(|_| i == last),
+			};
+			self.sent += 1;
+			// The client stopped receiving the results
+			if frames.send(frame).await.is_err() {
+				bail!(Error::QueryCancelled);
+			}
+		}
+		Ok(())
+	}
+
+	/// Execute a stream of statements for an import, where each statement carries
+	/// its position in the imported source.
+	///
+	/// After each top-level statement, or each transaction, `report` is called
+	/// with the positions of the first and last statements which were consumed,
+	/// and the errors which they returned. Execution stops once `report` returns
+	/// false, or after the first statement which fails to parse.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub(crate) async fn execute_import_stream<S, P, F>(
+		kvs: &Datastore,
+		ctx: Context,
+		opt: Options,
+		stream: S,
+		mut report: F,
+	) -> Result<()>
+	where
+		S: Stream<Item = (P, Result<Statement>)>,
+		P: Clone,
+		F: FnMut(P, P, Vec<anyhow::Error>) -> bool,
+	{
+		// The statements of an import are committed as they are consumed
+		ensure!(
+			!opt.dry_run,
+			Error::QueryNotExecutedDetail {
+				message: "An import can not be validated with a dry run".to_string(),
+			}
+		);
+		let mut this = Executor::new(kvs, ctx, opt);
+		let mut stream = pin!(stream);
+
+		while let Some((start, stmt)) = stream.next().await {
+			yield_now!();
+			let mut end = start.clone();
+			let stmt = match stmt {
+				Ok(x) => x,
+				Err(e) => {
+					report(start, end, vec![e]);
+					return Ok(());
+				}
+			};
+
+			let mut failed = None;
+			match stmt {
+				Statement::Option(stmt) => {
+					if let Err(e) = this.execute_option_statement(stmt) {
+						failed = Some(e);
+					}
+				}
+				Statement::Begin(_) => {
+					let inner = stream.as_mut().map(|(pos, stmt)| {
+						end = pos;
+						stmt
+					});
+					let res = this.execute_begin_statement(kvs, pin!(inner)).await;
+					this.store_slow_queries(kvs).await;
+					if let Err(e) = res {
+						// The transaction contained a statement which failed to parse.
+						let errors = this.take_errors().chain([e]).collect();
+						report(start, end, errors);
+						return Ok(());
+					}
+				}
+				stmt => {
+					if let Err(e) = this.execute_bare_statement(kvs, stmt).await {
+						failed = Some(e);
+					}
+					this.store_slow_queries(kvs).await;
+				}
+			}
+
+			let errors = this.take_errors().chain(failed).collect();
+			if !report(start, end, errors) {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Removes the results collected so far, returning only the errors.
+	fn take_errors(&mut self) -> impl Iterator<Item = anyhow::Error> + '_ {
+		self.results.drain(..).filter_map(|res| res.result.err())
 	}
 }
 
@@ -868,4 +1512,75 @@ mod tests {
 			);
 		}
 	}
+
+	#[tokio::test]
+	async fn check_execute_readonly() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		ds.execute("CREATE test:one", &ses, None).await.unwrap();
+		let ses = ses.with_readonly(true);
+		let res = ds
+			.execute(
+				"SELECT * FROM test; CREATE test:two; BEGIN; RETURN 1; CREATE test:three; COMMIT;",
+				&ses,
+				None,
+			)
+			.await
+			.unwrap();
+		assert!(res[0].result.is_ok(), "Failed to select in a read-only query: {:?}", res[0]);
+		let err = res[1].result.as_ref().unwrap_err().to_string();
+		assert!(err.contains("read-only"), "Expected to find read-only error: {:?}", err);
+		assert!(res[3].result.is_err(), "Expected the transaction to fail: {:?}", res[3]);
+		let res = ds.execute("SELECT * FROM test", &ses, None).await.unwrap();
+		let val = res[0].result.as_ref().unwrap();
+		assert_eq!(val.to_string(), "[{ id: test:one }]");
+	}
+
+	#[tokio::test]
+	async fn check_execute_dry_run() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		let sql = "DEFINE TABLE person SCHEMAFULL; DEFINE FIELD age ON person TYPE int; CREATE person:one SET age = 1;";
+		ds.execute(sql, &ses, None).await.unwrap();
+		let ses = ses.with_dry_run(true);
+		let res = ds
+			.execute(
+				"DEFINE FIELD name ON person TYPE string;
+				CREATE person:two CONTENT { age: 2, name: 'Tobie' };
+				CREATE person:three CONTENT { age: 'three' };
+				RETURN sleep(1h);
+				BEGIN;
+				CREATE person:four SET age = 4;
+				THROW 'oops';
+				COMMIT;",
+				&ses,
+				None,
+			)
+			.await
+			.unwrap();
+		assert_eq!(res.len(), 6);
+		let reports: Vec<String> =
+			res.iter().map(|r| r.result.as_ref().unwrap().to_string()).collect();
+		// Later statements see the changes of the statements before them
+		assert!(reports[0].contains("status: 'succeeded'"), "{}", reports[0]);
+		assert!(reports[1].contains("status: 'succeeded'"), "{}", reports[1]);
+		assert!(reports[2].contains("status: 'failed'"), "{}", reports[2]);
+		assert!(reports[2].contains("Couldn't coerce value for field `age`"), "{}", reports[2]);
+		// Functions with external effects are not run
+		assert_eq!(
+			reports[3],
+			"{ statement: 'RETURN sleep(1h)', status: 'unverified', unverified: ['sleep'] }"
+		);
+		// A failed statement fails the whole transaction
+		assert!(reports[4].contains("due to a failed transaction"), "{}", reports[4]);
+		assert!(reports[5].contains("oops"), "{}", reports[5]);
+		// Nothing was written
+		let ses = ses.with_dry_run(false);
+		let res =
+			ds.execute("SELECT * FROM person; INFO FOR TABLE person;", &ses, None).await.unwrap();
+		let val = res[0].result.as_ref().unwrap();
+		assert_eq!(val.to_string(), "[{ age: 1, id: person:one }]");
+		let val = res[1].result.as_ref().unwrap().to_string();
+		assert!(!val.contains("name"), "The field should not be defined: {}", val);
+	}
 }
@@ -184,7 +184,7 @@ impl FileCollector {
 	}
 }
 
-struct FileWriter {
+pub(super) struct FileWriter {
 	index: BufWriter<File>,
 	records: BufWriter<File>,
 	offset: usize,
@@ -213,7 +213,7 @@ impl FileWriter {
 		Ok(())
 	}
 
-	fn write_value<W: Write>(writer: &mut W, value: Value) -> Result<usize, Error> {
+	pub(super) fn write_value<W: Write>(writer: &mut W, value: Value) -> Result<usize, Error> {
 		let mut val = Vec::new();
 		value.serialize_revisioned(&mut val)?;
 		// Write the size of the buffer in the index
@@ -239,7 +239,7 @@ impl FileWriter {
 	}
 }
 
-struct FileReader {
+pub(super) struct FileReader {
 	/// The amount of values present in the file of this reader.
 	len: usize,
 	index: PathBuf,
@@ -257,7 +257,7 @@ impl FileReader {
 		})
 	}
 
-	fn read_value<R: Read>(reader: &mut R) -> Result<Value, Error> {
+	pub(super) fn read_value<R: Read>(reader: &mut R) -> Result<Value, Error> {
 		let len = FileReader::read_usize(reader)?;
 		let mut buf = vec![0u8; len];
 		if let Err(e) = reader.read_exact(&mut buf) {
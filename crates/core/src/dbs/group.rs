@@ -1,4 +1,5 @@
 use crate::ctx::Context;
+use crate::dbs::MemoryReservation;
 use crate::dbs::plan::Explanation;
 use crate::dbs::store::MemoryCollector;
 use crate::dbs::{Options, Statement};
@@ -10,11 +11,14 @@ use anyhow::Result;
 use reblessive::tree::Stk;
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
+use std::mem::size_of;
 
 pub(super) struct GroupsCollector {
 	base: Vec<Aggregator>,
 	idioms: Vec<Idiom>,
 	grp: BTreeMap<Array, Vec<Aggregator>>,
+	/// The memory held by the groups, when the memory of the statement is limited
+	reserved: MemoryReservation,
 }
 
 #[derive(Default)]
@@ -33,7 +37,7 @@ struct Aggregator {
 
 impl GroupsCollector {
 	#[expect(clippy::mutable_key_type)]
-	pub(super) fn new(stm: &Statement<'_>) -> Self {
+	pub(super) fn new(ctx: &Context, stm: &Statement<'_>) -> Self {
 		let mut idioms_agr: HashMap<Idiom, Aggregator> = HashMap::new();
 		if let Some(fields) = stm.expr() {
 			for field in fields.other() {
@@ -57,6 +61,7 @@ impl GroupsCollector {
 			base,
 			idioms,
 			grp: Default::default(),
+			reserved: MemoryReservation::new(ctx),
 		}
 	}
 
@@ -75,26 +80,33 @@ impl GroupsCollector {
 			// Loop over each group clause
 			for group in groups.iter() {
 				// Get the value at the path
-				let val = obj.pick(group);
+				let val = group.key(obj.pick(group));
 				// Set the value at the path
 				arr.push(val);
 			}
+			// Account for the memory of a new group
+			if self.reserved.is_active() && !self.grp.contains_key(&arr) {
+				self.reserved.add(&Value::Array(arr.clone()))?;
+				self.reserved.add_bytes(self.base.len() * size_of::<Aggregator>())?;
+			}
 			// Add to grouped collection
 			let agr = self
 				.grp
 				.entry(arr)
 				.or_insert_with(|| self.base.iter().map(|a| a.new_instance()).collect());
-			Self::pushes(stk, ctx, opt, agr, &self.idioms, rs, obj).await?
+			Self::pushes(stk, ctx, opt, agr, &self.idioms, &mut self.reserved, rs, obj).await?
 		}
 		Ok(())
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	async fn pushes(
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		agrs: &mut [Aggregator],
 		idioms: &[Idiom],
+		reserved: &mut MemoryReservation,
 		rs: RecordStrategy,
 		obj: Value,
 	) -> Result<()> {
@@ -110,7 +122,7 @@ impl GroupsCollector {
 			} else {
 				stk.run(|stk| obj.get(stk, ctx, opt, None, idiom)).await.catch_return()?
 			};
-			agr.push(stk, ctx, opt, rs, val).await?;
+			agr.push(stk, ctx, opt, reserved, rs, val).await?;
 		}
 		Ok(())
 	}
@@ -275,6 +287,7 @@ impl Aggregator {
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
+		reserved: &mut MemoryReservation,
 		rs: RecordStrategy,
 		val: Value,
 	) -> Result<()> {
@@ -337,6 +350,8 @@ impl Aggregator {
 			}
 		}
 		if let Some(ref mut a) = self.array {
+			// The values are held until the aggregation is computed
+			reserved.add(&val)?;
 			a.0.push(val);
 		} else if let Some(ref mut v) = self.first_val {
 			if v.is_none() {
@@ -1,25 +1,36 @@
+#[cfg(not(target_family = "wasm"))]
+use crate::cnf::PARALLEL_SCAN_TASKS;
+use crate::cnf::STREAM_BATCH_SIZE;
 use crate::ctx::Context;
 use crate::ctx::{Canceller, MutableContext};
+use crate::dbs::MemoryReservation;
 use crate::dbs::Options;
+use crate::dbs::RowStream;
 use crate::dbs::Statement;
 use crate::dbs::distinct::SyncDistinct;
+#[cfg(not(target_family = "wasm"))]
+use crate::dbs::parallel::ParallelScan;
 use crate::dbs::plan::{Explanation, Plan};
 use crate::dbs::result::Results;
+use crate::dbs::stats::estimated_size;
 use crate::doc::{Document, IgnoreError};
 use crate::err::Error;
 use crate::expr::array::Array;
 use crate::expr::edges::Edges;
 use crate::expr::mock::Mock;
 use crate::expr::object::Object;
+use crate::expr::statements::SelectStatement;
 use crate::expr::table::Table;
 use crate::expr::thing::Thing;
 use crate::expr::value::Value;
+use crate::expr::value::fetch::{FetchedRecords, Fetching};
 use crate::expr::{Fields, Id, IdRange};
 use crate::idx::planner::iterators::{IteratorRecord, IteratorRef};
 use crate::idx::planner::{
 	GrantedPermission, IterationStage, QueryPlanner, RecordStrategy, ScanDirection,
 	StatementContext,
 };
+use crate::kvs::ScanSummary;
 use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use std::mem;
@@ -122,6 +133,20 @@ pub(crate) struct Iterator {
 	guaranteed: Option<Iterable>,
 	/// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	/// Set if the scan of the records can stop once it reaches the limit
+	scan_limit: Option<usize>,
+	/// The batches which the scans fetched from the datastore
+	scans: ScanSummary,
+	/// The estimated size of the results, when the statistics are collected
+	memory: usize,
+	/// The memory held by the results, when the memory of the statement is limited
+	reserved: MemoryReservation,
+	/// The stream which the rows are sent to, when they are streamed
+	stream: Option<Arc<RowStream>>,
+	/// Whether the rows are sent to the stream while they are iterated
+	incremental: bool,
+	/// The number of rows which were sent to the stream while iterating
+	streamed: usize,
 }
 
 impl Clone for Iterator {
@@ -136,6 +161,13 @@ impl Clone for Iterator {
 			entries: self.entries.clone(),
 			guaranteed: None,
 			cancel_on_limit: None,
+			scan_limit: None,
+			scans: ScanSummary::default(),
+			memory: 0,
+			reserved: MemoryReservation::default(),
+			stream: None,
+			incremental: false,
+			streamed: 0,
 		}
 	}
 }
@@ -151,6 +183,12 @@ impl Iterator {
 		self.entries.push(val)
 	}
 
+	/// Sends the rows to a stream while they are iterated, when
+	/// nothing in the statement needs all of the rows at once
+	pub(crate) fn set_stream(&mut self, stream: Arc<RowStream>) {
+		self.stream = Some(stream);
+	}
+
 	/// Prepares a value for processing
 	pub(crate) async fn prepare(
 		&mut self,
@@ -362,15 +400,17 @@ impl Iterator {
 		// Process the query START clause
 		self.setup_start(stk, &cancel_ctx, opt, stm).await?;
 		// Prepare the results with possible optimisations on groups
-		self.results = self.results.prepare(
-			#[cfg(storage)]
-			ctx,
-			stm,
-			self.start,
-			self.limit,
-		)?;
+		self.results = self.results.prepare(ctx, stm, self.start, self.limit)?;
+		// Account for the memory held by the results
+		self.reserved = MemoryReservation::new(ctx);
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
+		// Record the chosen iterators for the statistics
+		if let Some(stats) = ctx.get_query_stats() {
+			for e in &self.entries {
+				stats.add_plan(Plan::planner_choice(ctx, e));
+			}
+		}
 		// Check if we actually need to process and iterate over the results
 		if plan.do_iterate {
 			if let Some(e) = &mut plan.explanation {
@@ -398,8 +438,10 @@ impl Iterator {
 			if let Some(e) = self.error.take() {
 				return Err(e);
 			}
+			// Don't process partial results once timed out
+			ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
 			// If no results, then create a record
-			if self.results.is_empty() {
+			if self.results.is_empty() && self.streamed == 0 {
 				// Check if a guaranteed record response is expected
 				if let Some(guaranteed) = self.guaranteed.take() {
 					// Ingest the pre-defined guaranteed record yield
@@ -408,19 +450,33 @@ impl Iterator {
 					self.iterate(stk, &cancel_ctx, opt, stm, sp, None).await?;
 				}
 			}
+			plan.add_scan(self.scans);
+			plan.add_stage("Iterate", self.results.len());
 			// Process any SPLIT AT clause
 			self.output_split(stk, ctx, opt, stm, rs).await?;
+			plan.add_stage("Split", self.results.len());
 			// Process any GROUP BY clause
 			self.output_group(stk, ctx, opt, stm).await?;
+			plan.add_stage("Group", self.results.len());
+			// Process any HAVING clause
+			self.results.having(stk, ctx, opt, stm).await?;
+			plan.add_stage("Having", self.results.len());
+			// Process any window functions
+			self.results.windows(stk, ctx, opt, stm).await?;
+			plan.add_stage("Window", self.results.len());
 			// Process any ORDER BY clause
 			if let Some(orders) = stm.order() {
+				self.reserved.add_bytes(self.results.sort_overhead())?;
 				#[cfg(not(target_family = "wasm"))]
 				self.results.sort(orders).await?;
 				#[cfg(target_family = "wasm")]
 				self.results.sort(orders);
 			}
-			// Process any START & LIMIT clause
-			self.results.start_limit(self.start_skip, self.start, self.limit).await?;
+			plan.add_stage("Order", self.results.len());
+			// Process any START & LIMIT clause, excluding the rows which were streamed
+			let limit = self.limit.map(|l| l.saturating_sub(self.streamed as u32));
+			self.results.start_limit(self.start_skip, self.start, limit).await?;
+			plan.add_stage("Limit", self.results.len());
 			// Process any FETCH clause
 			if let Some(e) = &mut plan.explanation {
 				e.add_fetch(self.results.len());
@@ -486,6 +542,16 @@ impl Iterator {
 			return false;
 		}
 
+		// If the rows are filtered after grouping we can't
+		if stm.having().is_some() {
+			return false;
+		}
+
+		// If there are window functions we can't
+		if stm.has_windows() {
+			return false;
+		}
+
 		// If there is no specified order, we can
 		if stm.order().is_none() {
 			return true;
@@ -516,6 +582,14 @@ impl Iterator {
 		if self.check_set_start_limit(ctx, stm) {
 			if let Some(l) = self.limit {
 				self.cancel_on_limit = Some(l);
+				// Every scanned record is output when nothing filters the records
+				if stm.is_select()
+					&& self.entries.len() == 1
+					&& stm.cond().is_none()
+					&& !is_specific_permission
+				{
+					self.scan_limit = Some(l as usize);
+				}
 			}
 			// Check if we can skip processing the document below "start".
 			if !is_specific_permission {
@@ -527,6 +601,35 @@ impl Iterator {
 		}
 	}
 
+	/// Check if the rows can be streamed while they are iterated, as they
+	/// are output in the order in which they are produced, and the START and
+	/// LIMIT clauses are applied while iterating
+	fn is_incremental(&self, stm: &Statement<'_>) -> bool {
+		self.stream.is_some()
+			&& matches!(self.results, Results::Memory(_))
+			&& stm.split().is_none()
+			&& stm.having().is_none()
+			&& stm.fetch().is_none()
+			&& !stm.has_windows()
+			&& (self.start.is_none() || self.start_skip.is_some())
+			&& (self.limit.is_none() || self.cancel_on_limit.is_some())
+	}
+
+	/// Sends the rows collected so far to the stream
+	async fn flush(&mut self) -> Result<()> {
+		if let Some(stream) = &self.stream {
+			let mut rows = self.results.take().await?;
+			// Rows can still be produced once the iterator was cancelled at the limit
+			if let Some(l) = self.limit {
+				rows.truncate((l as usize).saturating_sub(self.streamed));
+			}
+			self.streamed += rows.len();
+			stream.send(rows).await?;
+			self.reserved.clear();
+		}
+		Ok(())
+	}
+
 	/// Return the number of record that should be skipped
 	pub(super) fn skippable(&self) -> usize {
 		self.start_skip.unwrap_or(0)
@@ -539,6 +642,16 @@ impl Iterator {
 		}
 	}
 
+	/// Return the number of records after which a scan can stop
+	pub(super) fn scan_limit(&self) -> Option<usize> {
+		self.scan_limit
+	}
+
+	/// Record the batches which a scan fetched from the datastore
+	pub(super) fn scanned(&mut self, summary: ScanSummary) {
+		self.scans.add(summary);
+	}
+
 	async fn output_split(
 		&mut self,
 		stk: &mut Stk,
@@ -606,16 +719,25 @@ impl Iterator {
 		stm: &Statement<'_>,
 	) -> Result<()> {
 		if let Some(fetchs) = stm.fetch() {
-			let mut idioms = Vec::with_capacity(fetchs.0.len());
-			for fetch in fetchs.iter() {
-				fetch.compute(stk, ctx, opt, &mut idioms).await?;
-			}
-			for i in &idioms {
+			let paths = fetchs.paths(stk, ctx, opt).await?;
+			// The records are only selected once for the whole statement
+			let records = FetchedRecords::default();
+			for (i, depth) in &paths {
+				let fetching = Fetching::new(i, *depth, &records);
 				let mut values = self.results.take().await?;
 				// Loop over each result value
 				for obj in &mut values {
+					// Account for the size of the fetched records
+					let size = if self.reserved.is_active() {
+						estimated_size(obj)
+					} else {
+						0
+					};
 					// Fetch the value at the path
-					stk.run(|stk| obj.fetch(stk, ctx, opt, i)).await?;
+					stk.run(|stk| obj.fetch(stk, ctx, opt, &fetching)).await?;
+					if self.reserved.is_active() {
+						self.reserved.add_bytes(estimated_size(obj).saturating_sub(size))?;
+					}
 				}
 				self.results = values.into();
 			}
@@ -634,13 +756,38 @@ impl Iterator {
 	) -> Result<()> {
 		// Compute iteration limits
 		self.compute_start_limit(ctx, stm, is_specific_permission);
+		// Stream the rows while iterating, unless the statement is explained
+		self.incremental = exp.is_none() && self.is_incremental(stm);
+		// Check if the records are processed on several tasks
+		#[cfg(not(target_family = "wasm"))]
+		let parallel = self.parallel_scan(ctx, stm);
+		#[cfg(target_family = "wasm")]
+		let parallel: Result<std::convert::Infallible, &str> =
+			Err("parallel scans are not supported on this platform");
 		if let Some(e) = exp {
 			if self.start_skip.is_some() || self.cancel_on_limit.is_some() {
 				e.add_start_limit(self.start_skip, self.cancel_on_limit);
 			}
+			match &parallel {
+				#[cfg(not(target_family = "wasm"))]
+				Ok((.., tasks)) => e.add_parallel(*tasks),
+				// The reason is only given when a number of tasks was requested
+				Err(reason) => {
+					if matches!(stm, Statement::Select(s) if s.parallel_tasks.is_some()) {
+						e.add_parallel_ignored(reason);
+					}
+				}
+			}
 		}
 		// Prevent deep recursion
 		let opt = opt.dive(4)?;
+		// Scan a single table on several tasks
+		#[cfg(not(target_family = "wasm"))]
+		if let Ok((select, tb, sc, tasks)) = parallel {
+			self.entries.clear();
+			let scan = ParallelScan::start(ctx, &opt, select, &tb, sc, tasks).await?;
+			return self.iterate_parallel(stk, ctx, &opt, stm, scan).await;
+		}
 		// If any iterator requires distinct, we need to create a global distinct instance
 		let mut distinct = SyncDistinct::new(ctx);
 		// Process all prepared values
@@ -656,6 +803,77 @@ impl Iterator {
 		Ok(())
 	}
 
+	/// Check if the records of a single table can be processed on several
+	/// tasks, as the statement is a PARALLEL SELECT in a read-only transaction.
+	/// Returns the number of tasks, or the reason why the scan is not parallel.
+	#[cfg(not(target_family = "wasm"))]
+	fn parallel_scan<'a>(
+		&self,
+		ctx: &Context,
+		stm: &Statement<'a>,
+	) -> Result<(&'a SelectStatement, Table, ScanDirection, usize), &'static str> {
+		let Statement::Select(select) = stm else {
+			return Err("the statement is not a SELECT statement");
+		};
+		if !select.parallel {
+			return Err("the statement is not a PARALLEL statement");
+		}
+		if *PARALLEL_SCAN_TASKS < 2 {
+			return Err("parallel scans are disabled on this server");
+		}
+		// The number of tasks is limited by the server
+		let tasks = select
+			.parallel_tasks
+			.map_or(*PARALLEL_SCAN_TASKS, |n| (n as usize).min(*PARALLEL_SCAN_TASKS));
+		if tasks < 2 {
+			return Err("a single task was requested");
+		}
+		if ctx.tx().writeable() {
+			return Err("the transaction is writeable");
+		}
+		// The records before the START are skipped, or the scan stops at the LIMIT
+		if self.start_skip.is_some() || self.scan_limit.is_some() {
+			return Err("the statement has a START or LIMIT clause");
+		}
+		// The table is not iterated for a KNN search
+		if !matches!(ctx.get_iteration_stage(), None | Some(IterationStage::Iterate(None))) {
+			return Err("the statement is a KNN search");
+		}
+		match self.entries.as_slice() {
+			[Iterable::Table(tb, RecordStrategy::KeysAndValues, sc)] => {
+				Ok((*select, tb.clone(), *sc, tasks))
+			}
+			_ => Err("the statement does not scan a single table"),
+		}
+	}
+
+	/// Process the records of a table which is scanned on several tasks
+	#[cfg(not(target_family = "wasm"))]
+	async fn iterate_parallel(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		mut scan: ParallelScan,
+	) -> Result<()> {
+		while let Some(records) = scan.next().await? {
+			for res in records {
+				// Count the records scanned for the slow query log
+				if let Some(stats) = ctx.get_query_stats() {
+					stats.add_scanned();
+				}
+				self.result(stk, ctx, opt, stm, RecordStrategy::KeysAndValues, res).await;
+			}
+			// Check if the context is finished
+			if ctx.is_done(true).await? {
+				break;
+			}
+		}
+		// Everything processed ok
+		Ok(())
+	}
+
 	/// Process a new record Thing and Value
 	pub async fn process(
 		&mut self,
@@ -666,6 +884,10 @@ impl Iterator {
 		pro: Processed,
 	) -> Result<()> {
 		let rs = pro.rs;
+		// Count the records scanned for the slow query log
+		if let Some(stats) = ctx.get_query_stats() {
+			stats.add_scanned();
+		}
 		// Extract the value
 		let res = Self::extract_value(stk, ctx, opt, stm, pro).await;
 		// Process the result
@@ -674,7 +896,7 @@ impl Iterator {
 		Ok(())
 	}
 
-	async fn extract_value(
+	pub(super) async fn extract_value(
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
@@ -718,6 +940,18 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
+				if let Some(stats) = ctx.get_query_stats() {
+					self.memory += estimated_size(&v);
+					stats.add_memory(self.memory);
+				}
+				// Account for the rows which are held until the output
+				if self.results.buffers_rows() {
+					if let Err(e) = self.reserved.add(&v) {
+						self.error = Some(e);
+						self.run.cancel();
+						return;
+					}
+				}
 				if let Err(e) = self.results.push(stk, ctx, opt, stm, rs, v).await {
 					self.error = Some(e);
 					self.run.cancel();
@@ -725,9 +959,17 @@ impl Iterator {
 				}
 			}
 		}
+		// Send a batch of rows, once enough were collected
+		if self.incremental && self.results.len() >= *STREAM_BATCH_SIZE {
+			if let Err(e) = self.flush().await {
+				self.error = Some(e);
+				self.run.cancel();
+				return;
+			}
+		}
 		// Check if we have enough results
 		if let Some(l) = self.cancel_on_limit {
-			if self.results.len() == l as usize {
+			if self.streamed + self.results.len() == l as usize {
 				self.run.cancel()
 			}
 		}
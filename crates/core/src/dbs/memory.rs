@@ -0,0 +1,195 @@
+//! The memory accounting of the statements.
+//!
+//! When the capabilities set a memory limit for the namespace of a statement,
+//! the intermediate collections of the statement reserve the estimated size
+//! of the values which they hold against the limit of the statement. These
+//! are the rows buffered before they are grouped, sorted or returned, the
+//! records expanded by a FETCH clause, and the arrays returned from the array
+//! functions. The reservations are released once the collections are dropped,
+//! and the statement fails with an error as soon as the limit is exceeded.
+//! Otherwise no budget is attached to the context, and nothing is counted.
+use crate::ctx::Context;
+use crate::dbs::stats::estimated_size;
+use crate::err::Error;
+use crate::expr::Value;
+use anyhow::{Result, ensure};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The memory budget of a statement
+#[derive(Debug)]
+pub(crate) struct QueryMemory {
+	/// The estimated number of bytes currently reserved
+	used: AtomicU64,
+	/// The maximum number of bytes which can be reserved
+	limit: u64,
+}
+
+impl QueryMemory {
+	pub(crate) fn new(limit: u64) -> Self {
+		Self {
+			used: AtomicU64::new(0),
+			limit,
+		}
+	}
+
+	/// Reserves memory, failing if this exceeds the limit
+	fn reserve(&self, bytes: u64) -> Result<()> {
+		let used = self.used.fetch_add(bytes, Ordering::Relaxed).saturating_add(bytes);
+		if used > self.limit {
+			self.used.fetch_sub(bytes, Ordering::Relaxed);
+		}
+		ensure!(
+			used <= self.limit,
+			Error::MemoryLimitExceeded {
+				used,
+				limit: self.limit,
+			}
+		);
+		Ok(())
+	}
+
+	/// Releases memory which was reserved
+	fn release(&self, bytes: u64) {
+		self.used.fetch_sub(bytes, Ordering::Relaxed);
+	}
+
+	/// Checks that a value which is not kept fits within the limit
+	pub(crate) fn check(&self, val: &Value) -> Result<()> {
+		let used = self.used.load(Ordering::Relaxed).saturating_add(estimated_size(val) as u64);
+		ensure!(
+			used <= self.limit,
+			Error::MemoryLimitExceeded {
+				used,
+				limit: self.limit,
+			}
+		);
+		Ok(())
+	}
+
+	/// The estimated number of bytes currently reserved
+	#[cfg(test)]
+	pub(crate) fn used(&self) -> u64 {
+		self.used.load(Ordering::Relaxed)
+	}
+}
+
+/// The memory held by a collection, which is released when it is dropped
+#[derive(Debug, Default)]
+pub(crate) struct MemoryReservation {
+	memory: Option<Arc<QueryMemory>>,
+	bytes: u64,
+}
+
+impl MemoryReservation {
+	/// Reserves memory against the budget of the statement, if it has one
+	pub(crate) fn new(ctx: &Context) -> Self {
+		Self {
+			memory: ctx.get_query_memory().cloned(),
+			bytes: 0,
+		}
+	}
+
+	/// Checks if the memory of the statement is limited
+	pub(crate) fn is_active(&self) -> bool {
+		self.memory.is_some()
+	}
+
+	/// Reserves the estimated size of a value which is held by the collection
+	pub(crate) fn add(&mut self, val: &Value) -> Result<()> {
+		if self.memory.is_some() {
+			self.add_bytes(estimated_size(val))?;
+		}
+		Ok(())
+	}
+
+	/// Reserves a number of bytes which are held by the collection
+	pub(crate) fn add_bytes(&mut self, bytes: usize) -> Result<()> {
+		if let Some(memory) = &self.memory {
+			memory.reserve(bytes as u64)?;
+			self.bytes += bytes as u64;
+		}
+		Ok(())
+	}
+
+	/// Releases everything reserved so far, once the values are no longer held
+	pub(crate) fn clear(&mut self) {
+		if let Some(memory) = &self.memory {
+			memory.release(self.bytes);
+		}
+		self.bytes = 0;
+	}
+}
+
+impl Drop for MemoryReservation {
+	fn drop(&mut self) {
+		if let Some(memory) = &self.memory {
+			memory.release(self.bytes);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ctx::MutableContext;
+	use crate::dbs::{Capabilities, Session};
+	use crate::kvs::Datastore;
+
+	#[test]
+	fn reservations_are_released() {
+		let memory = Arc::new(QueryMemory::new(100));
+		let mut ctx = MutableContext::background();
+		ctx.set_query_memory(memory.clone());
+		let ctx = ctx.freeze();
+		let mut a = MemoryReservation::new(&ctx);
+		a.add_bytes(60).unwrap();
+		let mut b = MemoryReservation::new(&ctx);
+		let err = b.add_bytes(50).unwrap_err();
+		assert!(matches!(
+			err.downcast_ref(),
+			Some(Error::MemoryLimitExceeded {
+				used: 110,
+				limit: 100
+			})
+		));
+		// Failed reservations are not counted
+		assert_eq!(memory.used(), 60);
+		drop(a);
+		b.add_bytes(50).unwrap();
+		assert_eq!(memory.used(), 50);
+		drop(b);
+		assert_eq!(memory.used(), 0);
+	}
+
+	#[tokio::test]
+	async fn limits_are_set_per_namespace() {
+		let caps = Capabilities::default()
+			.with_max_query_memory(Some(1024 * 1024))
+			.with_namespace_max_query_memory("small", 16 * 1024);
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(caps);
+		let sql = "
+			FOR $i IN 0..1000 { CREATE person SET age = $i % 10, name = string::repeat('a', 100) };
+			SELECT * FROM person ORDER BY name;
+			SELECT age, array::group(name) AS names FROM person GROUP BY age;
+			SELECT age, count() AS total FROM person GROUP BY age;
+		";
+		// The rows fit within the default limit
+		let sess = Session::owner().with_ns("large").with_db("test");
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res.iter().all(|r| r.result.is_ok()));
+		// The sort and group buffers exceed the limit of the namespace
+		let sess = Session::owner().with_ns("small").with_db("test");
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res[0].result.is_ok());
+		for r in res.drain(1..3) {
+			let err = r.result.unwrap_err();
+			assert!(
+				matches!(err.downcast_ref(), Some(Error::MemoryLimitExceeded { limit, .. }) if *limit == 16 * 1024),
+				"{err}"
+			);
+		}
+		// The optimised aggregates don't hold the rows
+		assert!(res[1].result.is_ok());
+	}
+}
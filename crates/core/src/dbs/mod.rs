@@ -4,9 +4,11 @@
 //! and executors to process the operations. This module also gives a `context` to the transaction.
 
 mod distinct;
+mod dryrun;
 mod executor;
 mod group;
 mod iterator;
+mod memory;
 mod notification;
 mod options;
 mod plan;
@@ -15,24 +17,48 @@ mod response;
 mod result;
 mod session;
 mod statement;
+mod stats;
 mod store;
+mod stream;
+mod subquery;
 mod variables;
+mod window;
 
+pub(crate) mod audit;
 pub mod capabilities;
 pub mod node;
+pub(crate) mod outbox;
+pub mod registry;
+pub(crate) mod schedule;
+pub mod slowlog;
 
 pub use self::capabilities::Capabilities;
+pub(crate) use self::dryrun::{DryRunReport, SkippedFunctions, has_side_effects};
 pub(crate) use self::executor::*;
 pub(crate) use self::iterator::*;
+pub(crate) use self::memory::{MemoryReservation, QueryMemory};
 pub use self::notification::*;
 pub use self::options::*;
+pub use self::registry::FunctionRegistry;
 pub use self::response::*;
 pub use self::session::*;
+pub use self::slowlog::SlowQueryLog;
 pub(crate) use self::statement::*;
+pub(crate) use self::stats::QueryStats;
+pub use self::stats::ResponseStats;
+pub use self::stream::QueryFrame;
+pub(crate) use self::stream::RowStream;
+pub(crate) use self::subquery::SubqueryCache;
 pub use self::variables::*;
 
 #[cfg(storage)]
 mod file;
 
+#[cfg(not(target_family = "wasm"))]
+mod parallel;
+
+#[cfg(all(storage, not(target_family = "wasm")))]
+mod sort;
+
 #[cfg(test)]
 pub(crate) mod test;
@@ -3,7 +3,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 #[non_exhaustive]
@@ -13,6 +13,11 @@ pub enum Action {
 	Delete,
 	#[revision(start = 2)]
 	Killed,
+	#[revision(start = 3)]
+	Truncate,
+	/// A record which matched a `LIVE SELECT ... SNAPSHOT` query when it started
+	#[revision(start = 4)]
+	Snapshot,
 }
 
 impl Display for Action {
@@ -22,6 +27,8 @@ impl Display for Action {
 			Action::Update => write!(f, "UPDATE"),
 			Action::Delete => write!(f, "DELETE"),
 			Action::Killed => write!(f, "KILLED"),
+			Action::Truncate => write!(f, "TRUNCATE"),
+			Action::Snapshot => write!(f, "SNAPSHOT"),
 		}
 	}
 }
@@ -7,6 +7,7 @@ use crate::iam::{Action, Auth, ResourceKind};
 use anyhow::{Result, bail};
 use async_channel::Sender;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// An Options is passed around when processing a set of query
@@ -37,6 +38,8 @@ pub struct Options {
 	pub(crate) force: Force,
 	/// Should we run permissions checks?
 	pub(crate) perms: bool,
+	/// Should we filter the records of tables by their tenant?
+	pub(crate) tenant: bool,
 	/// Should we error if tables don't exist?
 	pub(crate) strict: bool,
 	/// Should we process field queries?
@@ -47,6 +50,14 @@ pub struct Options {
 	pub(crate) version: Option<u64>,
 	/// The channel over which we send notifications
 	pub(crate) sender: Option<Sender<Notification>>,
+	/// The default timeout for statements without a TIMEOUT clause
+	pub(crate) timeout: Option<Duration>,
+	/// Should we reject statements which could modify data?
+	pub(crate) readonly: bool,
+	/// Should we collect the execution statistics of the statements?
+	pub(crate) stats: bool,
+	/// Should we validate the statements without writing anything?
+	pub(crate) dry_run: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -81,6 +92,7 @@ impl Options {
 			dive: *MAX_COMPUTATION_DEPTH,
 			live: false,
 			perms: true,
+			tenant: true,
 			force: Force::None,
 			strict: false,
 			import: false,
@@ -89,6 +101,10 @@ impl Options {
 			sender: None,
 			auth: Arc::new(Auth::default()),
 			version: None,
+			timeout: None,
+			readonly: false,
+			stats: false,
+			dry_run: false,
 		}
 	}
 
@@ -149,6 +165,34 @@ impl Options {
 		self
 	}
 
+	/// Specify the default timeout for statements which
+	/// don't specify a timeout themselves, with chaining.
+	pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// Specify whether statements which could modify
+	/// data should be rejected, with chaining.
+	pub fn with_readonly(mut self, readonly: bool) -> Self {
+		self.readonly = readonly;
+		self
+	}
+
+	/// Specify whether the execution statistics of the
+	/// statements should be collected, with chaining.
+	pub fn with_stats(mut self, stats: bool) -> Self {
+		self.stats = stats;
+		self
+	}
+
+	/// Specify whether the statements should be validated
+	/// without writing any changes, with chaining.
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+		self
+	}
+
 	/// Specify whether permissions should be run for
 	/// code which uses this `Options`, with chaining.
 	pub fn with_perms(mut self, perms: bool) -> Self {
@@ -430,6 +474,15 @@ impl Options {
 		})
 	}
 
+	/// Checks whether the records of tables which are
+	/// defined with a `TENANT` clause should be limited
+	/// to the tenant of the session. Unlike permissions,
+	/// this applies to every user, unless the query was
+	/// run with `OPTION TENANT = FALSE`, or is an import.
+	pub fn check_tenant(&self) -> bool {
+		self.perms && self.tenant && !self.import
+	}
+
 	/// Checks the current server configuration, and
 	/// user authentication information to determine
 	/// whether we need to process table permissions
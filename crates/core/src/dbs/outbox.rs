@@ -0,0 +1,145 @@
+//! The transactional outbox of a database.
+//!
+//! The `outbox::enqueue` function stores a message in the outbox of the
+//! database, in the same transaction as the statement which called it, so a
+//! message is only ever delivered for a change which was committed. The
+//! messages are sent by a background dispatcher, which retries the messages
+//! which could not be delivered with an exponential delay, until they are
+//! delivered or the maximum number of attempts is reached. A message may be
+//! sent again if the dispatcher stops after sending it, but before recording
+//! its delivery, so the delivery is at-least-once.
+//!
+//! The messages of a database, along with their delivery status, can be
+//! listed with `INFO FOR DATABASE OUTBOX`. The delivered and the failed
+//! messages are kept for the configured retention period.
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Datetime, Object, Value};
+use crate::key::database::ob;
+use crate::kvs::{KeyDecode, Transaction};
+use anyhow::Result;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// The delivery status of a message in the outbox
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub enum OutboxStatus {
+	/// The message is waiting to be sent
+	Pending,
+	/// The message was sent successfully
+	Delivered,
+	/// The message could not be sent after the maximum number of attempts
+	Failed,
+}
+
+impl fmt::Display for OutboxStatus {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Pending => f.write_str("pending"),
+			Self::Delivered => f.write_str("delivered"),
+			Self::Failed => f.write_str("failed"),
+		}
+	}
+}
+
+/// A message which is sent to an external system
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub struct OutboxMessage {
+	/// The url which the message is posted to
+	pub url: String,
+	/// The body of the message
+	pub payload: Value,
+	/// The headers which are sent with the message
+	pub headers: Object,
+	/// The delivery status of the message
+	pub status: OutboxStatus,
+	/// The number of times the message was sent
+	pub attempts: u32,
+	/// The time at which the message was enqueued
+	pub created: Datetime,
+	/// The time at which the delivery status was last updated
+	pub updated: Datetime,
+	/// The time after which the message is sent again, if it is pending
+	pub next: Datetime,
+	/// The error of the last failed attempt
+	pub error: Option<String>,
+}
+
+impl OutboxMessage {
+	pub(crate) fn new(url: String, payload: Value, headers: Object) -> Self {
+		let now = Datetime::default();
+		Self {
+			url,
+			payload,
+			headers,
+			status: OutboxStatus::Pending,
+			attempts: 0,
+			created: now.clone(),
+			updated: now.clone(),
+			next: now,
+			error: None,
+		}
+	}
+}
+
+/// A message of the outbox, along with its id
+pub(crate) struct OutboxEntry {
+	pub id: Uuid,
+	pub message: OutboxMessage,
+}
+
+impl InfoStructure for OutboxEntry {
+	fn structure(self) -> Value {
+		let m = self.message;
+		// The headers are not listed, as they often contain credentials
+		Value::from(map! {
+			"id".to_string() => Value::from(self.id),
+			"url".to_string() => Value::from(m.url),
+			"payload".to_string() => m.payload,
+			"status".to_string() => Value::from(m.status.to_string()),
+			"attempts".to_string() => Value::from(m.attempts),
+			"created".to_string() => Value::Datetime(m.created),
+			"updated".to_string() => Value::Datetime(m.updated),
+			"next".to_string(), if m.status == OutboxStatus::Pending => Value::Datetime(m.next),
+			"error".to_string(), if let Some(v) = m.error => Value::from(v),
+		})
+	}
+}
+
+/// Stores a message in the outbox of a database, in the given transaction
+pub(crate) async fn enqueue(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	message: OutboxMessage,
+) -> Result<Uuid> {
+	let id = Uuid::now_v7();
+	let key = ob::new(ns, db, id);
+	txn.set(key, revision::to_vec(&message)?, None).await?;
+	Ok(id)
+}
+
+/// Fetches the messages of the outbox of a database, from the oldest to the newest
+pub(crate) async fn entries(txn: &Transaction, ns: &str, db: &str) -> Result<Vec<OutboxEntry>> {
+	let beg = ob::prefix(ns, db)?;
+	let end = ob::suffix(ns, db)?;
+	let mut out = Vec::new();
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = txn.batch_keys_vals(rng, *crate::cnf::NORMAL_FETCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result {
+			let key = ob::Ob::decode(&k)?;
+			out.push(OutboxEntry {
+				id: key.ob,
+				message: revision::from_slice(&v)?,
+			});
+		}
+	}
+	Ok(out)
+}
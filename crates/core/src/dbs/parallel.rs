@@ -0,0 +1,263 @@
+//! Parallel scans of the tables of PARALLEL SELECT statements.
+//!
+//! When a PARALLEL SELECT statement iterates over a single table in a
+//! read-only transaction, the key range of the table is split into shards of
+//! consecutive records as it is scanned, and the shards are processed by a
+//! number of tasks. Each task decodes the records of a shard, and checks the
+//! WHERE clause and the permissions, and computes the fields of the records,
+//! against the same transaction as the statement. The processed shards are
+//! then merged in the order of their keys, so the rows are output in the same
+//! order as when the table is scanned on a single task, before they are
+//! grouped, sorted, and limited as usual.
+use super::processor::Collected;
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::{Iterator, Options, Statement};
+use crate::doc::IgnoreError;
+use crate::err::Error;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{Table, Value};
+use crate::idx::planner::ScanDirection;
+use crate::key::thing;
+use crate::kvs::{Key, Val};
+use anyhow::Result;
+use async_channel::{Receiver, Sender};
+use futures::StreamExt;
+use reblessive::TreeStack;
+use std::collections::BTreeMap;
+use std::mem;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// A shard of consecutive records, in the order in which it was scanned
+type Shard = (usize, Vec<(Key, Val)>);
+
+/// The processed records of a shard
+type Processed = (usize, Vec<Result<Value, IgnoreError>>);
+
+/// The scan of a table on several tasks
+pub(super) struct ParallelScan {
+	/// The processed shards, or the error which stopped the scan
+	results: Receiver<Result<Processed>>,
+	/// The processed shards which arrived before the previous shards
+	pending: BTreeMap<usize, Vec<Result<Value, IgnoreError>>>,
+	/// The next shard to output
+	next: usize,
+	/// The tasks which scan and process the shards
+	tasks: Vec<JoinHandle<()>>,
+}
+
+impl ParallelScan {
+	/// Starts scanning a table, processing its records on a number of tasks
+	pub(super) async fn start(
+		ctx: &Context,
+		opt: &Options,
+		stm: &SelectStatement,
+		tb: &Table,
+		sc: ScanDirection,
+		tasks: usize,
+	) -> Result<Self> {
+		// Check that the table exists
+		let (ns, db) = opt.ns_db()?;
+		ctx.tx().check_ns_db_tb(ns, db, tb, opt.strict).await?;
+		// Prepare the start and end keys
+		let rng = thing::prefix(ns, db, tb)?..thing::suffix(ns, db, tb)?;
+		// Set the query executor matching the table
+		let ctx = match ctx.get_query_planner().and_then(|qp| qp.get_query_executor(&tb.0)) {
+			Some(exe) => {
+				let mut ctx = MutableContext::new(ctx);
+				ctx.set_query_executor(exe.clone());
+				ctx.freeze()
+			}
+			None => ctx.clone(),
+		};
+		let stm = Arc::new(stm.clone());
+		let (shards, queue) = async_channel::bounded::<Shard>(tasks * 2);
+		let (send, results) = async_channel::bounded(tasks * 2);
+		// Scan the shards of the table
+		let mut handles = vec![tokio::spawn({
+			let ctx = ctx.clone();
+			let version = opt.version;
+			let send = send.clone();
+			async move {
+				if let Err(e) = scan(&ctx, rng, version, sc, shards).await {
+					send.send(Err(e)).await.ok();
+				}
+			}
+		})];
+		// Process the shards
+		for _ in 0..tasks {
+			handles.push(tokio::spawn({
+				let ctx = ctx.clone();
+				let opt = opt.clone();
+				let stm = stm.clone();
+				let queue = queue.clone();
+				let send = send.clone();
+				async move {
+					if let Err(e) = process(&ctx, &opt, &stm, queue, &send).await {
+						send.send(Err(e)).await.ok();
+					}
+				}
+			}));
+		}
+		Ok(Self {
+			results,
+			pending: BTreeMap::new(),
+			next: 0,
+			tasks: handles,
+		})
+	}
+
+	/// Returns the processed records of the next shard, once the
+	/// shards before it were output, or None once the scan is complete
+	pub(super) async fn next(&mut self) -> Result<Option<Vec<Result<Value, IgnoreError>>>> {
+		loop {
+			if let Some(records) = self.pending.remove(&self.next) {
+				self.next += 1;
+				return Ok(Some(records));
+			}
+			match self.results.recv().await {
+				Ok(Ok((seq, records))) => {
+					self.pending.insert(seq, records);
+				}
+				Ok(Err(e)) => return Err(e),
+				// Every task finished
+				Err(_) => {
+					return match self.pending.is_empty() {
+						true => Ok(None),
+						false => Err(anyhow::Error::new(Error::unreachable(
+							"A shard of a parallel scan was not processed",
+						))),
+					};
+				}
+			}
+		}
+	}
+}
+
+impl Drop for ParallelScan {
+	fn drop(&mut self) {
+		// Stop the tasks once the statement has enough records
+		for task in &self.tasks {
+			task.abort();
+		}
+	}
+}
+
+/// Scans the records of the table, and sends them to the tasks in shards
+async fn scan(
+	ctx: &Context,
+	rng: std::ops::Range<Key>,
+	version: Option<u64>,
+	sc: ScanDirection,
+	shards: Sender<Shard>,
+) -> Result<()> {
+	let txn = ctx.tx();
+	let size = *NORMAL_FETCH_SIZE as usize;
+	let mut stream = txn.stream(ctx, rng, version, None, sc);
+	let mut shard = Vec::with_capacity(size);
+	let mut seq = 0;
+	while let Some(res) = stream.next().await {
+		shard.push(res?);
+		if shard.len() == size {
+			// The statement stopped receiving the records
+			if shards.send((seq, mem::take(&mut shard))).await.is_err() {
+				return Ok(());
+			}
+			seq += 1;
+		}
+	}
+	if !shard.is_empty() {
+		shards.send((seq, shard)).await.ok();
+	}
+	Ok(())
+}
+
+/// Processes the records of the shards, until every shard was processed
+async fn process(
+	ctx: &Context,
+	opt: &Options,
+	stm: &SelectStatement,
+	queue: Receiver<Shard>,
+	send: &Sender<Result<Processed>>,
+) -> Result<()> {
+	let stm = Statement::from(stm);
+	let txn = ctx.tx();
+	let mut stack = TreeStack::new();
+	while let Ok((seq, shard)) = queue.recv().await {
+		let mut records = Vec::with_capacity(shard.len());
+		for (count, (k, v)) in shard.into_iter().enumerate() {
+			// Check if the context is finished
+			if ctx.is_done(count % 100 == 0).await? {
+				return Ok(());
+			}
+			let pro = Collected::KeyVal(k, v).process(opt, &txn, false).await?;
+			let res =
+				stack.enter(|stk| Iterator::extract_value(stk, ctx, opt, &stm, pro)).finish().await;
+			records.push(res);
+		}
+		// The statement stopped receiving the records
+		if send.send(Ok((seq, records))).await.is_err() {
+			return Ok(());
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+	async fn parallel_selects_match_sequential_selects() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql =
+			"FOR $i IN 0..5000 { CREATE person:[$i] SET age = $i % 90, name = 'p' + <string> $i }";
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res.into_iter().all(|r| r.result.is_ok()));
+		for sql in [
+			"SELECT * FROM person WHERE age > 30",
+			"SELECT name FROM person WHERE age > 30 ORDER BY name DESC",
+			"SELECT VALUE id FROM person WHERE age = 7 ORDER BY id LIMIT 10 START 5",
+			"SELECT age, count() AS total FROM person WHERE age < 10 GROUP BY age",
+			"SELECT VALUE id FROM person LIMIT 20",
+			"SELECT count() FROM person WHERE string::ends_with(name, '7') GROUP ALL",
+		] {
+			let sequential = ds.execute(sql, &sess, None).await.unwrap().remove(0).result.unwrap();
+			let parallel = format!("{sql} PARALLEL");
+			let parallel =
+				ds.execute(&parallel, &sess, None).await.unwrap().remove(0).result.unwrap();
+			assert_eq!(sequential, parallel, "{sql}");
+		}
+	}
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+	async fn parallel_scans_report_errors() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			FOR $i IN 0..2000 { CREATE person:[$i] SET age = $i };
+			SELECT VALUE IF age = 1500 { THROW 'failed' } ELSE { age } FROM person PARALLEL;
+			BEGIN;
+			UPDATE person:[1] SET age = 0;
+			SELECT VALUE age FROM person WHERE age = 0 PARALLEL;
+			COMMIT;
+			UPDATE person SET age += 1 WHERE age < 5 PARALLEL;
+		";
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		let err = res.remove(1).result.unwrap_err();
+		assert!(err.to_string().contains("failed"), "{err}");
+		// The writes of a transaction are seen, as its statements are not parallel
+		assert_eq!(res.remove(2).result.unwrap(), value("[0, 0]"));
+		// Mutating statements are not parallel
+		let updated = res.remove(2).result.unwrap();
+		assert!(matches!(updated, Value::Array(a) if a.len() == 5));
+	}
+
+	fn value(sql: &str) -> Value {
+		crate::syn::value(sql).unwrap().into()
+	}
+}
@@ -1,9 +1,13 @@
 use crate::ctx::Context;
 use crate::dbs::result::Results;
 use crate::dbs::{Iterable, Statement};
-use crate::expr::{Object, Value};
-use crate::idx::planner::RecordStrategy;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{Expression, Field, Object, Subquery, Value};
+use crate::idx::planner::{RecordStrategy, TableAnalysis};
+use crate::kvs::ScanSummary;
 use std::collections::HashMap;
+use std::time::Duration;
+use trice::Instant;
 
 pub(super) struct Plan {
 	pub(super) do_iterate: bool,
@@ -20,7 +24,7 @@ impl Plan {
 		let (do_iterate, explanation) = match stm.explain() {
 			None => (true, None),
 			Some(e) => {
-				let mut exp = Explanation::default();
+				let mut exp = Explanation::new(e.1);
 				for i in iterables {
 					exp.add_iter(ctx, i);
 				}
@@ -28,6 +32,18 @@ impl Plan {
 					for reason in qp.fallbacks() {
 						exp.add_fallback(reason.to_string());
 					}
+					if exp.verbose {
+						for analysis in qp.analyses() {
+							exp.add_analysis(analysis);
+						}
+					}
+				}
+				// The subqueries which are computed for the rows
+				if let Statement::Select(s) = stm {
+					let cache = ctx.get_subquery_cache().is_some();
+					for sq in row_subqueries(s) {
+						exp.add_subquery(sq, cache && sq.is_cacheable());
+					}
 				}
 				results.explain(&mut exp);
 				(e.0, Some(exp))
@@ -38,18 +54,91 @@ impl Plan {
 			explanation,
 		}
 	}
+
+	/// Describes an iterator chosen by the planner, without the values it
+	/// iterates over, so that it can be reported in the slow query log
+	pub(super) fn planner_choice(ctx: &Context, iter: &Iterable) -> Value {
+		let item = ExplainItem::new_iter(ctx, iter);
+		let mut res = Object::default();
+		res.insert("iterator".to_owned(), item.name);
+		for (k, v) in item.details {
+			match (k, v) {
+				("table", v) => {
+					res.insert(k.to_owned(), v);
+				}
+				("plan", Value::Object(mut p)) => {
+					for k in ["index", "operator"] {
+						if let Some(v) = p.remove(k) {
+							res.insert(k.to_owned(), v);
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+		Value::from(res)
+	}
+
+	/// Reports a completed stage of the iteration to any verbose explanation
+	pub(super) fn add_stage(&mut self, stage: &'static str, rows: usize) {
+		if let Some(e) = &mut self.explanation {
+			e.add_stage(stage, rows);
+		}
+	}
+
+	/// Reports the batches which the scans fetched to any verbose explanation
+	pub(super) fn add_scan(&mut self, summary: ScanSummary) {
+		if let Some(e) = &mut self.explanation {
+			e.add_scan(summary);
+		}
+	}
 }
 
-#[derive(Default)]
-pub(super) struct Explanation(Vec<ExplainItem>);
+pub(super) struct Explanation {
+	items: Vec<ExplainItem>,
+	/// Whether the planner analysis and the stage timings are explained
+	verbose: bool,
+	/// When the current stage of the iteration started
+	stage: Instant,
+}
 
 impl Explanation {
+	fn new(verbose: bool) -> Self {
+		Self {
+			items: vec![],
+			verbose,
+			stage: Instant::now(),
+		}
+	}
+
 	fn add_iter(&mut self, ctx: &Context, iter: &Iterable) {
-		self.0.push(ExplainItem::new_iter(ctx, iter));
+		self.items.push(ExplainItem::new_iter(ctx, iter));
+	}
+
+	fn add_analysis(&mut self, analysis: &TableAnalysis) {
+		self.items.push(ExplainItem::new_analysis(analysis));
+	}
+
+	/// Reports the number of rows and the time taken after a stage
+	/// of the iteration, when the explanation is verbose
+	fn add_stage(&mut self, stage: &'static str, rows: usize) {
+		if self.verbose {
+			let elapsed = self.stage.elapsed();
+			self.items.push(ExplainItem::new_stage(stage, rows, elapsed));
+			self.stage = Instant::now();
+		}
+	}
+
+	/// Reports the round trips of the table and range scans to
+	/// the datastore, when the explanation is verbose
+	fn add_scan(&mut self, summary: ScanSummary) {
+		if self.verbose && summary.batches > 0 {
+			self.items.push(ExplainItem::new_scan(summary));
+		}
 	}
 
 	pub(super) fn add_fetch(&mut self, count: usize) {
-		self.0.push(ExplainItem::new_fetch(count));
+		self.items.push(ExplainItem::new_fetch(count));
 	}
 
 	pub(super) fn add_collector(
@@ -57,15 +146,28 @@ impl Explanation {
 		collector_type: &str,
 		details: Vec<(&'static str, Value)>,
 	) {
-		self.0.push(ExplainItem::new_collector(collector_type, details));
+		self.items.push(ExplainItem::new_collector(collector_type, details));
 	}
 
 	fn add_fallback(&mut self, reason: String) {
-		self.0.push(ExplainItem::new_fallback(reason));
+		self.items.push(ExplainItem::new_fallback(reason));
 	}
 
 	pub(super) fn add_record_strategy(&mut self, rs: RecordStrategy) {
-		self.0.push(ExplainItem::new_record_strategy(rs));
+		self.items.push(ExplainItem::new_record_strategy(rs));
+	}
+
+	#[cfg(not(target_family = "wasm"))]
+	pub(super) fn add_parallel(&mut self, tasks: usize) {
+		self.items.push(ExplainItem::new_parallel(tasks));
+	}
+
+	pub(super) fn add_parallel_ignored(&mut self, reason: &str) {
+		self.items.push(ExplainItem::new_parallel_ignored(reason));
+	}
+
+	fn add_subquery(&mut self, subquery: &Subquery, cached: bool) {
+		self.items.push(ExplainItem::new_subquery(subquery, cached));
 	}
 
 	pub(super) fn add_start_limit(
@@ -73,13 +175,64 @@ impl Explanation {
 		start_skip: Option<usize>,
 		cancel_on_limit: Option<u32>,
 	) {
-		self.0.push(ExplainItem::new_start_limit(start_skip, cancel_on_limit));
+		self.items.push(ExplainItem::new_start_limit(start_skip, cancel_on_limit));
 	}
 	pub(super) fn output(self) -> Vec<Value> {
-		self.0.into_iter().map(|e| e.into()).collect()
+		self.items.into_iter().map(|e| e.into()).collect()
 	}
 }
 
+/// Collects the SELECT subqueries in the fields and the conditions of a statement
+fn row_subqueries(stm: &SelectStatement) -> Vec<&Subquery> {
+	fn collect<'a>(v: &'a Value, out: &mut Vec<&'a Subquery>) {
+		match v {
+			Value::Subquery(s) if matches!(s.as_ref(), Subquery::Select(_)) => out.push(s),
+			Value::Subquery(s) => {
+				if let Subquery::Value(v) = s.as_ref() {
+					collect(v, out);
+				}
+			}
+			Value::Array(a) => a.iter().for_each(|v| collect(v, out)),
+			Value::Object(o) => o.values().for_each(|v| collect(v, out)),
+			Value::Cast(c) => collect(&c.1, out),
+			Value::Function(f) => f.args().iter().for_each(|v| collect(v, out)),
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Unary {
+					v,
+					..
+				} => collect(v, out),
+				Expression::Binary {
+					l,
+					r,
+					..
+				} => {
+					collect(l, out);
+					collect(r, out);
+				}
+			},
+			_ => {}
+		}
+	}
+	let mut out = Vec::new();
+	for f in stm.expr.iter() {
+		if let Field::Single {
+			expr,
+			..
+		}
+		| Field::Window {
+			expr,
+			..
+		} = f
+		{
+			collect(expr, &mut out);
+		}
+	}
+	for c in stm.cond.iter().chain(stm.having.iter()) {
+		collect(&c.0, &mut out);
+	}
+	out
+}
+
 struct ExplainItem {
 	name: Value,
 	details: Vec<(&'static str, Value)>,
@@ -93,6 +246,66 @@ impl ExplainItem {
 		}
 	}
 
+	fn new_analysis(analysis: &TableAnalysis) -> Self {
+		let candidates = analysis
+			.candidates
+			.iter()
+			.map(|c| {
+				let mut candidate = vec![
+					("index", Value::from(c.index.clone())),
+					("expression", Value::from(c.expression.clone())),
+					("usable", Value::from(c.rejection.is_none())),
+				];
+				if let Some(r) = c.rejection {
+					candidate.push(("reason", Value::from(r.to_string())));
+				}
+				if let Some(rows) = c.estimated_rows {
+					candidate.push(("estimated_rows", Value::from(rows)));
+				}
+				Value::Object(Object::from(HashMap::from_iter(candidate)))
+			})
+			.collect::<Vec<_>>();
+		let indexes = analysis.indexes.iter().cloned().map(Value::from).collect::<Vec<_>>();
+		Self {
+			name: "Analysis".into(),
+			details: vec![
+				("table", Value::from(analysis.table.clone())),
+				("candidates", Value::from(candidates)),
+				("access", Value::from(analysis.access)),
+				("indexes", Value::from(indexes)),
+			],
+		}
+	}
+
+	fn new_scan(summary: ScanSummary) -> Self {
+		Self {
+			name: "Scan".into(),
+			details: vec![
+				("batches", summary.batches.into()),
+				("keys", summary.keys.into()),
+				("largest", summary.largest.into()),
+			],
+		}
+	}
+
+	fn new_stage(stage: &'static str, rows: usize, elapsed: Duration) -> Self {
+		Self {
+			name: "Stage".into(),
+			details: vec![
+				("stage", stage.into()),
+				("rows", rows.into()),
+				("elapsed", Value::from(elapsed)),
+			],
+		}
+	}
+
+	fn new_subquery(subquery: &Subquery, cached: bool) -> Self {
+		Self {
+			name: "Subquery".into(),
+			details: vec![("subquery", subquery.to_string().into()), ("cached", cached.into())],
+		}
+	}
+
 	fn new_fallback(reason: String) -> Self {
 		Self {
 			name: "Fallback".into(),
@@ -213,6 +426,21 @@ impl ExplainItem {
 		}
 	}
 
+	#[cfg(not(target_family = "wasm"))]
+	pub(super) fn new_parallel(tasks: usize) -> Self {
+		Self {
+			name: "Parallel".into(),
+			details: vec![("tasks", tasks.into())],
+		}
+	}
+
+	pub(super) fn new_parallel_ignored(reason: &str) -> Self {
+		Self {
+			name: "Parallel".into(),
+			details: vec![("tasks", 1usize.into()), ("reason", reason.into())],
+		}
+	}
+
 	pub(super) fn new_start_limit(start_skip: Option<usize>, cancel_on_limit: Option<u32>) -> Self {
 		let mut details = vec![];
 		if let Some(s) = start_skip {
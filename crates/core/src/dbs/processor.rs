@@ -3,7 +3,6 @@ use crate::ctx::{Context, MutableContext};
 use crate::dbs::distinct::SyncDistinct;
 use crate::dbs::{Iterable, Iterator, Operable, Options, Processed, Statement};
 use crate::err::Error;
-use crate::expr::dir::Dir;
 use crate::expr::id::range::IdRange;
 use crate::expr::{Edges, Table, Thing, Value};
 use crate::idx::planner::iterators::{IndexItemRecord, IteratorRef, ThingIterator};
@@ -131,7 +130,7 @@ impl Collected {
 			Arc::new(Value::Null)
 		} else {
 			let (ns, db) = opt.ns_db()?;
-			txn.get_record(ns, db, gra.ft, &gra.fk, None).await?
+			txn.get_record(ns, db, gra.ft, &gra.fk, opt.version).await?
 		};
 		let rid = Thing::from((gra.ft, gra.fk));
 		// Parse the data from the store
@@ -530,6 +529,7 @@ pub(super) trait Collector {
 		ctx: &Context,
 		txn: &Transaction,
 		mut rng: Range<Key>,
+		version: Option<u64>,
 		sc: ScanDirection,
 	) -> Result<Option<Range<Key>>> {
 		let ite = self.iterator();
@@ -539,7 +539,7 @@ pub(super) trait Collector {
 			return Ok(Some(rng));
 		}
 		// We only need to iterate over keys.
-		let mut stream = txn.stream_keys(rng.clone(), Some(skippable), sc);
+		let mut stream = txn.stream_keys(ctx, rng.clone(), version, Some(skippable), sc);
 		let mut skipped = 0;
 		let mut last_key = vec![];
 		while let Some(res) = stream.next().await {
@@ -549,6 +549,8 @@ pub(super) trait Collector {
 			last_key = res?;
 			skipped += 1;
 		}
+		// Record the batches which were fetched
+		ite.scanned(stream.summary());
 		// If we don't have a last key, we're done
 		if last_key.is_empty() {
 			return Ok(None);
@@ -585,13 +587,14 @@ pub(super) trait Collector {
 		let beg = thing::prefix(ns, db, v)?;
 		let end = thing::suffix(ns, db, v)?;
 		// Optionally skip keys
-		let rng = if let Some(r) = self.start_skip(ctx, &txn, beg..end, sc).await? {
+		let rng = if let Some(r) = self.start_skip(ctx, &txn, beg..end, opt.version, sc).await? {
 			r
 		} else {
 			return Ok(());
 		};
-		// Create a new iterable range
-		let mut stream = txn.stream(rng, opt.version, None, sc);
+		// Create a new iterable range, which stops at any limit
+		let limit = self.iterator().scan_limit();
+		let mut stream = txn.stream(ctx, rng, opt.version, limit, sc);
 
 		// Loop until no more entries
 		let mut count = 0;
@@ -605,6 +608,8 @@ pub(super) trait Collector {
 			self.collect(Collected::KeyVal(k, v)).await?;
 			count += 1;
 		}
+		// Record the batches which were fetched
+		self.iterator().scanned(stream.summary());
 		// Everything ok
 		Ok(())
 	}
@@ -625,15 +630,16 @@ pub(super) trait Collector {
 		let beg = thing::prefix(ns, db, v)?;
 		let end = thing::suffix(ns, db, v)?;
 		// Optionally skip keys
-		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, sc).await? {
+		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, opt.version, sc).await? {
 			// Returns the next range of keys
 			rng
 		} else {
 			// There is nothing left to iterate
 			return Ok(());
 		};
-		// Create a new iterable range
-		let mut stream = txn.stream_keys(rng, None, sc);
+		// Create a new iterable range, which stops at any limit
+		let limit = self.iterator().scan_limit();
+		let mut stream = txn.stream_keys(ctx, rng, opt.version, limit, sc);
 		// Loop until no more entries
 		let mut count = 0;
 		while let Some(res) = stream.next().await {
@@ -647,6 +653,8 @@ pub(super) trait Collector {
 			self.collect(Collected::TableKey(k)).await?;
 			count += 1;
 		}
+		// Record the batches which were fetched
+		self.iterator().scanned(stream.summary());
 		// Everything ok
 		Ok(())
 	}
@@ -661,7 +669,7 @@ pub(super) trait Collector {
 		let beg = thing::prefix(ns, db, v)?;
 		let end = thing::suffix(ns, db, v)?;
 		// Create a new iterable range
-		let count = txn.count(beg..end).await?;
+		let count = txn.count_cancellable(ctx, beg..end, opt.version).await?;
 		// Collect the count
 		self.collect(Collected::Count(count)).await?;
 		// Everything ok
@@ -713,15 +721,16 @@ pub(super) trait Collector {
 		// Prepare
 		let (beg, end) = Self::range_prepare(&txn, opt, tb, r).await?;
 		// Optionally skip keys
-		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, sc).await? {
+		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, opt.version, sc).await? {
 			// Returns the next range of keys
 			rng
 		} else {
 			// There is nothing left to iterate
 			return Ok(());
 		};
-		// Create a new iterable range
-		let mut stream = txn.stream(rng, None, None, sc);
+		// Create a new iterable range, which stops at any limit
+		let limit = self.iterator().scan_limit();
+		let mut stream = txn.stream(ctx, rng, opt.version, limit, sc);
 		// Loop until no more entries
 		let mut count = 0;
 		while let Some(res) = stream.next().await {
@@ -735,6 +744,8 @@ pub(super) trait Collector {
 			self.collect(Collected::KeyVal(k, v)).await?;
 			count += 1;
 		}
+		// Record the batches which were fetched
+		self.iterator().scanned(stream.summary());
 		// Everything ok
 		Ok(())
 	}
@@ -752,15 +763,16 @@ pub(super) trait Collector {
 		// Prepare
 		let (beg, end) = Self::range_prepare(&txn, opt, tb, r).await?;
 		// Optionally skip keys
-		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, sc).await? {
+		let rng = if let Some(rng) = self.start_skip(ctx, &txn, beg..end, opt.version, sc).await? {
 			// Returns the next range of keys
 			rng
 		} else {
 			// There is nothing left to iterate
 			return Ok(());
 		};
-		// Create a new iterable range
-		let mut stream = txn.stream_keys(rng, None, sc);
+		// Create a new iterable range, which stops at any limit
+		let limit = self.iterator().scan_limit();
+		let mut stream = txn.stream_keys(ctx, rng, opt.version, limit, sc);
 		// Loop until no more entries
 		let mut count = 0;
 		while let Some(res) = stream.next().await {
@@ -773,6 +785,8 @@ pub(super) trait Collector {
 			self.collect(Collected::RangeKey(k)).await?;
 			count += 1;
 		}
+		// Record the batches which were fetched
+		self.iterator().scanned(stream.summary());
 		// Everything ok
 		Ok(())
 	}
@@ -789,7 +803,7 @@ pub(super) trait Collector {
 		// Prepare
 		let (beg, end) = Self::range_prepare(&txn, opt, tb, r).await?;
 		// Create a new iterable range
-		let count = txn.count(beg..end).await?;
+		let count = txn.count_cancellable(ctx, beg..end, opt.version).await?;
 		// Collect the count
 		self.collect(Collected::Count(count)).await?;
 		// Everything ok
@@ -799,53 +813,16 @@ pub(super) trait Collector {
 	async fn collect_edges(&mut self, ctx: &Context, opt: &Options, e: Edges) -> Result<()> {
 		// Pull out options
 		let (ns, db) = opt.ns_db()?;
-		let tb = &e.from.tb;
-		let id = &e.from.id;
 		// Fetch start and end key pairs
-		let keys = match e.what.len() {
-			0 => match e.dir {
-				// /ns/db/tb/id
-				Dir::Both => {
-					vec![(graph::prefix(ns, db, tb, id), graph::suffix(ns, db, tb, id))]
-				}
-				// /ns/db/tb/id/IN
-				Dir::In => vec![(
-					graph::egprefix(ns, db, tb, id, &e.dir),
-					graph::egsuffix(ns, db, tb, id, &e.dir),
-				)],
-				// /ns/db/tb/id/OUT
-				Dir::Out => vec![(
-					graph::egprefix(ns, db, tb, id, &e.dir),
-					graph::egsuffix(ns, db, tb, id, &e.dir),
-				)],
-			},
-			_ => match e.dir {
-				// /ns/db/tb/id/IN/TB
-				Dir::In => {
-					e.what.iter().map(|v| v.presuf(ns, db, tb, id, &e.dir)).collect::<Vec<_>>()
-				}
-				// /ns/db/tb/id/OUT/TB
-				Dir::Out => {
-					e.what.iter().map(|v| v.presuf(ns, db, tb, id, &e.dir)).collect::<Vec<_>>()
-				}
-				// /ns/db/tb/id/IN/TB, /ns/db/tb/id/OUT/TB
-				Dir::Both => e
-					.what
-					.iter()
-					.flat_map(|v| {
-						[v.presuf(ns, db, tb, id, &Dir::In), v.presuf(ns, db, tb, id, &Dir::Out)]
-					})
-					.collect::<Vec<_>>(),
-			},
-		};
+		let keys = e.key_ranges(ns, db)?;
 		// Get the transaction
 		let txn = ctx.tx();
 		// Check that the table exists
-		txn.check_ns_db_tb(ns, db, tb, opt.strict).await?;
+		txn.check_ns_db_tb(ns, db, &e.from.tb, opt.strict).await?;
 		// Loop over the chosen edge types
-		for (beg, end) in keys.into_iter() {
+		for rng in keys.into_iter() {
 			// Create a new iterable range
-			let mut stream = txn.stream(beg?..end?, None, None, ScanDirection::Forward);
+			let mut stream = txn.stream(ctx, rng, opt.version, None, ScanDirection::Forward);
 			// Loop until no more entries
 			let mut count = 0;
 			while let Some(res) = stream.next().await {
@@ -914,6 +891,9 @@ pub(super) trait Collector {
 			if records.is_empty() {
 				break;
 			}
+			if let Some(stats) = ctx.get_query_stats() {
+				stats.add_index_entries(records.len());
+			}
 			for (c, r) in records.into_iter().enumerate() {
 				if ctx.is_done(c % 100 == 0).await? {
 					break;
@@ -936,6 +916,9 @@ pub(super) trait Collector {
 			if records.is_empty() {
 				break;
 			}
+			if let Some(stats) = ctx.get_query_stats() {
+				stats.add_index_entries(records.len());
+			}
 			for (c, r) in records.into_iter().enumerate() {
 				if ctx.is_done(c % 100 == 0).await? {
 					break;
@@ -960,6 +943,9 @@ pub(super) trait Collector {
 			}
 			total_count += count;
 		}
+		if let Some(stats) = ctx.get_query_stats() {
+			stats.add_index_entries(total_count);
+		}
 		self.collect(Collected::Count(total_count)).await
 	}
 }
@@ -973,7 +959,7 @@ impl Iterable {
 	) -> Result<Arc<Value>> {
 		// Fetch and parse the data from the store
 		let (ns, db) = opt.ns_db()?;
-		let val = txn.get_record(ns, db, &thg.tb, &thg.id, None).await?;
+		let val = txn.get_record(ns, db, &thg.tb, &thg.id, opt.version).await?;
 		// Return the result
 		Ok(val)
 	}
@@ -0,0 +1,230 @@
+//! Functions implemented in Rust which are registered by an application embedding the datastore.
+//! These are called from SurrealQL under the `fn::` namespace, in the same way as functions
+//! created with DEFINE FUNCTION, and can therefore never shadow a builtin function. A function
+//! created with DEFINE FUNCTION in the selected database takes precedence over a registered
+//! function with the same name.
+
+use crate::err::Error;
+use crate::expr::{Kind, Value};
+use anyhow::{Result, bail, ensure};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+#[cfg(not(target_family = "wasm"))]
+pub type FunctionFuture = Pin<Box<dyn Future<Output = Result<Value>> + Send>>;
+
+#[cfg(target_family = "wasm")]
+pub type FunctionFuture = Pin<Box<dyn Future<Output = Result<Value>>>>;
+
+/// A synchronous registered function
+pub type SyncFunction = Arc<dyn Fn(&[Value]) -> Result<Value> + Send + Sync>;
+
+/// An asynchronous registered function
+pub type AsyncFunction = Arc<dyn Fn(Vec<Value>) -> FunctionFuture + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) enum Handler {
+	Sync(SyncFunction),
+	Async(AsyncFunction),
+}
+
+/// A function which has been registered with the datastore
+#[derive(Clone)]
+pub struct RegisteredFunction {
+	/// The name of the function, without the `fn::` prefix
+	pub(crate) name: String,
+	/// The kinds of the arguments the function accepts
+	pub(crate) args: Vec<Kind>,
+	/// The implementation of the function
+	pub(crate) handler: Handler,
+}
+
+impl RegisteredFunction {
+	/// The name of the function, without the `fn::` prefix
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// The kinds of the arguments the function accepts
+	pub fn args(&self) -> &[Kind] {
+		&self.args
+	}
+
+	/// Whether the function is asynchronous
+	pub fn is_async(&self) -> bool {
+		matches!(self.handler, Handler::Async(_))
+	}
+
+	/// Check the argument count and coerce the arguments to the function signature
+	pub(crate) fn coerce_args(&self, mut args: Vec<Value>) -> Result<Vec<Value>> {
+		// Trailing optional arguments may be omitted
+		let max_args_len = self.args.len();
+		let mut min_args_len = 0;
+		self.args.iter().rev().for_each(|kind| match kind {
+			Kind::Option(_) if min_args_len == 0 => {}
+			Kind::Any if min_args_len == 0 => {}
+			_ => min_args_len += 1,
+		});
+		ensure!(
+			args.len() >= min_args_len && args.len() <= max_args_len,
+			Error::InvalidArguments {
+				name: format!("fn::{}", self.name),
+				message: match (min_args_len, max_args_len) {
+					(1, 1) => String::from("The function expects 1 argument."),
+					(r, t) if r == t => format!("The function expects {r} arguments."),
+					(r, t) => format!("The function expects {r} to {t} arguments."),
+				},
+			}
+		);
+		// The omitted arguments are passed to the handler as NONE
+		args.resize(max_args_len, Value::None);
+		args.into_iter()
+			.zip(&self.args)
+			.map(|(val, kind)| {
+				val.coerce_to_kind(kind).map_err(Error::from).map_err(anyhow::Error::new)
+			})
+			.collect()
+	}
+
+	/// Run the function with arguments which have already been coerced
+	pub(crate) async fn run(&self, args: Vec<Value>) -> Result<Value> {
+		match &self.handler {
+			Handler::Sync(f) => f(&args),
+			Handler::Async(f) => f(args).await,
+		}
+	}
+
+	/// Describe the signature of this function for INFO statements
+	pub(crate) fn signature(&self) -> String {
+		let args = self.args.iter().map(|k| k.to_string()).collect::<Vec<_>>().join(", ");
+		format!("fn::{}({args})", self.name)
+	}
+
+	/// Describe this function as a structured value for INFO statements
+	pub(crate) fn structure(&self) -> Value {
+		Value::from(map! {
+			"name".to_string() => Value::from(self.name.clone()),
+			"args".to_string() => Value::from(self.args.iter().map(|k| Value::from(k.to_string())).collect::<Vec<_>>()),
+			"async".to_string() => Value::from(self.is_async()),
+		})
+	}
+}
+
+/// The collection of functions registered with a datastore
+#[derive(Default)]
+pub struct FunctionRegistry {
+	functions: RwLock<BTreeMap<String, Arc<RegisteredFunction>>>,
+}
+
+impl FunctionRegistry {
+	/// Register a synchronous function, callable as `fn::<name>`
+	pub fn register_function(
+		&self,
+		name: &str,
+		args: Vec<Kind>,
+		function: SyncFunction,
+	) -> Result<()> {
+		self.insert(name, args, Handler::Sync(function))
+	}
+
+	/// Register an asynchronous function, callable as `fn::<name>`
+	pub fn register_async_function(
+		&self,
+		name: &str,
+		args: Vec<Kind>,
+		function: AsyncFunction,
+	) -> Result<()> {
+		self.insert(name, args, Handler::Async(function))
+	}
+
+	/// Remove a registered function, returning whether it existed
+	pub fn unregister_function(&self, name: &str) -> bool {
+		let name = name.strip_prefix("fn::").unwrap_or(name);
+		self.functions.write().unwrap_or_else(|e| e.into_inner()).remove(name).is_some()
+	}
+
+	/// Fetch a registered function by its name, without the `fn::` prefix
+	pub(crate) fn get(&self, name: &str) -> Option<Arc<RegisteredFunction>> {
+		self.functions.read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+	}
+
+	/// Fetch all registered functions, sorted by name
+	pub fn all(&self) -> Vec<Arc<RegisteredFunction>> {
+		self.functions.read().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+	}
+
+	fn insert(&self, name: &str, args: Vec<Kind>, handler: Handler) -> Result<()> {
+		let name = name.strip_prefix("fn::").unwrap_or(name);
+		// Each path segment must be a plain identifier
+		let valid = !name.is_empty()
+			&& name
+				.split("::")
+				.all(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+		if !valid {
+			bail!(Error::InvalidFunction {
+				name: format!("fn::{name}"),
+				message: String::from(
+					"Registered function names must be identifiers separated by '::'"
+				),
+			});
+		}
+		let mut functions = self.functions.write().unwrap_or_else(|e| e.into_inner());
+		ensure!(
+			!functions.contains_key(name),
+			Error::FcAlreadyExists {
+				name: name.to_owned(),
+			}
+		);
+		functions.insert(
+			name.to_owned(),
+			Arc::new(RegisteredFunction {
+				name: name.to_owned(),
+				args,
+				handler,
+			}),
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn register_and_coerce() {
+		let registry = FunctionRegistry::default();
+		registry
+			.register_function(
+				"fn::tax::vat",
+				vec![Kind::Number, Kind::Option(Box::new(Kind::String))],
+				Arc::new(|args| Ok(args[0].clone())),
+			)
+			.unwrap();
+		let f = registry.get("tax::vat").unwrap();
+		assert_eq!(f.name(), "tax::vat");
+		assert_eq!(f.signature(), "fn::tax::vat(number, option<string>)");
+		let args = f.coerce_args(vec![Value::from(1)]).unwrap();
+		assert_eq!(args.len(), 2);
+		assert_eq!(args[1], Value::None);
+		assert!(f.coerce_args(vec![]).is_err());
+		assert!(f.coerce_args(vec![Value::from("one")]).is_err());
+		assert!(f.coerce_args(vec![1.into(), "gb".into(), 3.into()]).is_err());
+	}
+
+	#[test]
+	fn register_invalid_names() {
+		let registry = FunctionRegistry::default();
+		let f: SyncFunction = Arc::new(|_| Ok(Value::None));
+		assert!(registry.register_function("", vec![], f.clone()).is_err());
+		assert!(registry.register_function("fn::", vec![], f.clone()).is_err());
+		assert!(registry.register_function("tax::", vec![], f.clone()).is_err());
+		assert!(registry.register_function("tax-vat", vec![], f.clone()).is_err());
+		assert!(registry.register_function("tax", vec![], f.clone()).is_ok());
+		assert!(registry.register_function("fn::tax", vec![], f.clone()).is_err());
+		assert!(registry.unregister_function("tax"));
+		assert!(registry.register_function("tax", vec![], f).is_ok());
+	}
+}
@@ -1,3 +1,5 @@
+use crate::dbs::ResponseStats;
+use crate::err::Error;
 use crate::expr::Value as CoreValue;
 use crate::sql::statement::Statement;
 use anyhow::Result;
@@ -48,6 +50,8 @@ pub struct Response {
 	pub result: Result<CoreValue>,
 	// Record the query type in case processing the response is necessary (such as tracking live queries).
 	pub query_type: QueryType,
+	/// The execution statistics, when they were requested for the query
+	pub stats: Option<ResponseStats>,
 }
 
 impl Response {
@@ -77,13 +81,17 @@ impl Serialize for Response {
 		S: serde::Serializer,
 	{
 		let includes_type = !self.query_type.is_other();
+		// Get any value thrown with a THROW statement
+		let detail = match &self.result {
+			Err(e) => match e.downcast_ref() {
+				Some(Error::ThrownValue(v)) => Some(v),
+				_ => None,
+			},
+			Ok(_) => None,
+		};
 		let mut val = serializer.serialize_struct(
 			TOKEN,
-			if includes_type {
-				3
-			} else {
-				4
-			},
+			3 + includes_type as usize + detail.is_some() as usize + self.stats.is_some() as usize,
 		)?;
 
 		val.serialize_field("time", self.speed().as_str())?;
@@ -99,8 +107,14 @@ impl Serialize for Response {
 			Err(e) => {
 				val.serialize_field("status", &Status::Err)?;
 				val.serialize_field("result", &CoreValue::from(e.to_string()))?;
+				if let Some(v) = detail {
+					val.serialize_field("detail", v)?;
+				}
 			}
 		}
+		if let Some(stats) = &self.stats {
+			val.serialize_field("stats", stats)?;
+		}
 		val.end()
 	}
 }
@@ -4,13 +4,17 @@ use crate::ctx::Context;
 use crate::dbs::file::FileCollector;
 use crate::dbs::group::GroupsCollector;
 use crate::dbs::plan::Explanation;
+#[cfg(all(storage, not(target_family = "wasm")))]
+use crate::dbs::sort::ExternalOrdered;
 use crate::dbs::store::{MemoryCollector, MemoryOrdered, MemoryOrderedLimit, MemoryRandom};
-use crate::dbs::{Options, Statement};
-use crate::expr::Value;
+use crate::dbs::{Options, Statement, window};
+use crate::doc::CursorDoc;
 use crate::expr::order::Ordering;
+use crate::expr::{FlowResultExt as _, Value};
 use crate::idx::planner::RecordStrategy;
 use anyhow::Result;
 use reblessive::tree::Stk;
+use std::mem::size_of;
 
 pub(super) enum Results {
 	None,
@@ -18,6 +22,8 @@ pub(super) enum Results {
 	MemoryRandom(MemoryRandom),
 	MemoryOrdered(MemoryOrdered),
 	MemoryOrderedLimit(MemoryOrderedLimit),
+	#[cfg(all(storage, not(target_family = "wasm")))]
+	ExternalOrdered(Box<ExternalOrdered>),
 	#[cfg(storage)]
 	File(Box<FileCollector>),
 	Groups(GroupsCollector),
@@ -26,13 +32,13 @@ pub(super) enum Results {
 impl Results {
 	pub(super) fn prepare(
 		&mut self,
-		#[cfg(storage)] ctx: &Context,
+		ctx: &Context,
 		stm: &Statement<'_>,
 		start: Option<u32>,
 		limit: Option<u32>,
 	) -> Result<Self> {
 		if stm.expr().is_some() && stm.group().is_some() {
-			return Ok(Self::Groups(GroupsCollector::new(stm)));
+			return Ok(Self::Groups(GroupsCollector::new(ctx, stm)));
 		}
 		#[cfg(storage)]
 		if stm.tempfiles() {
@@ -44,7 +50,9 @@ impl Results {
 			return match ordering {
 				Ordering::Random => Ok(Self::MemoryRandom(MemoryRandom::new(None))),
 				Ordering::Order(orders) => {
-					if let Some(limit) = limit {
+					// A HAVING clause or a window function needs all of the rows
+					// once they are collected, so they can't be limited beforehand
+					if let (Some(limit), None, false) = (limit, stm.having(), stm.has_windows()) {
 						let limit = start.unwrap_or(0) + limit;
 						if limit <= *MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE {
 							return Ok(Self::MemoryOrderedLimit(MemoryOrderedLimit::new(
@@ -53,6 +61,15 @@ impl Results {
 							)));
 						}
 					}
+					// Rows which exceed the sort memory are sorted on disk
+					#[cfg(all(storage, not(target_family = "wasm")))]
+					if let Some(temp_dir) = ctx.temporary_directory() {
+						return Ok(Self::ExternalOrdered(Box::new(ExternalOrdered::new(
+							ctx,
+							temp_dir.clone(),
+							orders.clone(),
+						))));
+					}
 					Ok(Self::MemoryOrdered(MemoryOrdered::new(orders.clone(), None)))
 				}
 			};
@@ -83,6 +100,10 @@ impl Results {
 			Self::MemoryRandom(c) => {
 				c.push(val);
 			}
+			#[cfg(all(storage, not(target_family = "wasm")))]
+			Self::ExternalOrdered(c) => {
+				c.push(val).await?;
+			}
 			#[cfg(storage)]
 			Self::File(e) => {
 				e.push(val).await?;
@@ -103,6 +124,8 @@ impl Results {
 			Self::MemoryOrdered(c) => c.sort().await?,
 			Self::MemoryOrderedLimit(c) => c.sort(),
 			Self::MemoryRandom(c) => c.sort(),
+			#[cfg(storage)]
+			Self::ExternalOrdered(c) => c.sort().await?,
 			Self::None | Self::Memory(_) | Self::Groups(_) => {}
 		}
 		Ok(())
@@ -121,6 +144,72 @@ impl Results {
 		}
 	}
 
+	/// Filters the output rows with the HAVING clause, once any GROUP BY
+	/// clause has been processed. The remaining rows are collected again,
+	/// so that any ORDER BY clause is applied after the HAVING clause.
+	pub(super) async fn having(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<()> {
+		let Some(cond) = stm.having() else {
+			return Ok(());
+		};
+		let values = self.take().await?;
+		let mut rows = Vec::with_capacity(values.len());
+		for val in values {
+			let doc = CursorDoc::new(None, None, val);
+			if stk
+				.run(|stk| cond.compute(stk, ctx, opt, Some(&doc)))
+				.await
+				.catch_return()?
+				.is_truthy()
+			{
+				rows.push(doc.doc.into_owned());
+			}
+		}
+		*self = Self::recollect(stm, rows);
+		Ok(())
+	}
+
+	/// Computes any window functions over the output rows, once any GROUP BY
+	/// and HAVING clauses have been processed. The rows are collected again,
+	/// so that any ORDER BY clause is applied after the window functions.
+	pub(super) async fn windows(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<()> {
+		if !stm.has_windows() {
+			return Ok(());
+		}
+		let mut rows = self.take().await?;
+		window::compute(stk, ctx, opt, stm, &mut rows).await?;
+		*self = Self::recollect(stm, rows);
+		Ok(())
+	}
+
+	/// Collects rows which have already been processed in memory
+	fn recollect(stm: &Statement<'_>, rows: Vec<Value>) -> Self {
+		match stm.order() {
+			Some(Ordering::Random) => {
+				let mut c = MemoryRandom::new(None);
+				rows.into_iter().for_each(|v| c.push(v));
+				Self::MemoryRandom(c)
+			}
+			Some(Ordering::Order(orders)) => {
+				let mut c = MemoryOrdered::new(orders.clone(), None);
+				rows.into_iter().for_each(|v| c.push(v));
+				Self::MemoryOrdered(c)
+			}
+			None => rows.into(),
+		}
+	}
+
 	pub(super) async fn start_limit(
 		&mut self,
 		skip: Option<usize>,
@@ -137,6 +226,8 @@ impl Results {
 			Self::MemoryOrdered(m) => m.start_limit(start, limit),
 			Self::MemoryOrderedLimit(m) => m.start_limit(start, limit),
 			Self::MemoryRandom(c) => c.start_limit(start, limit),
+			#[cfg(all(storage, not(target_family = "wasm")))]
+			Self::ExternalOrdered(c) => c.start_limit(start, limit),
 			#[cfg(storage)]
 			Self::File(f) => f.start_limit(start, limit),
 			Self::None | Self::Groups(_) => {}
@@ -144,6 +235,23 @@ impl Results {
 		Ok(())
 	}
 
+	/// Whether every row is held in memory until the output. The rows which
+	/// are grouped, limited while collected, or written to disk are not.
+	pub(super) fn buffers_rows(&self) -> bool {
+		matches!(self, Self::Memory(_) | Self::MemoryOrdered(_) | Self::MemoryRandom(_))
+	}
+
+	/// The estimated memory needed to sort the rows, in addition to the rows
+	pub(super) fn sort_overhead(&self) -> usize {
+		match self {
+			// The ordered indexes, and the sorted result
+			Self::MemoryOrdered(_) | Self::MemoryRandom(_) => {
+				self.len() * (size_of::<usize>() + size_of::<Value>())
+			}
+			_ => 0,
+		}
+	}
+
 	pub(super) fn is_empty(&self) -> bool {
 		self.len() == 0
 	}
@@ -155,6 +263,8 @@ impl Results {
 			Self::MemoryOrdered(s) => s.len(),
 			Self::MemoryOrderedLimit(s) => s.len(),
 			Self::MemoryRandom(s) => s.len(),
+			#[cfg(all(storage, not(target_family = "wasm")))]
+			Self::ExternalOrdered(s) => s.len(),
 			#[cfg(storage)]
 			Self::File(e) => e.len(),
 			Self::Groups(g) => g.len(),
@@ -167,6 +277,8 @@ impl Results {
 			Self::MemoryOrdered(c) => c.take_vec(),
 			Self::MemoryOrderedLimit(c) => c.take_vec(),
 			Self::MemoryRandom(c) => c.take_vec(),
+			#[cfg(all(storage, not(target_family = "wasm")))]
+			Self::ExternalOrdered(c) => c.take_vec().await?,
 			#[cfg(storage)]
 			Self::File(f) => f.take_vec().await?,
 			Self::None | Self::Groups(_) => vec![],
@@ -182,6 +294,8 @@ impl Results {
 			Self::MemoryOrdered(c) => c.explain(exp),
 			Self::MemoryOrderedLimit(c) => c.explain(exp),
 			Self::MemoryRandom(c) => c.explain(exp),
+			#[cfg(all(storage, not(target_family = "wasm")))]
+			Self::ExternalOrdered(c) => c.explain(exp),
 			#[cfg(storage)]
 			Self::File(e) => {
 				e.explain(exp);
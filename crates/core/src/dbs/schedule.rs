@@ -0,0 +1,310 @@
+//! The scheduled statements of a database.
+//!
+//! A schedule, defined with `DEFINE SCHEDULE`, runs its statements whenever
+//! its cron expression matches, in UTC. The schedules are run by a background
+//! task, which claims each run in a transaction before running it, so a run
+//! is never started while the previous run of the same schedule is still in
+//! progress, and the runs which are missed, because the previous run took too
+//! long or because no node was running, are skipped rather than caught up.
+//!
+//! The statements are run by a system user of the database, which is named
+//! after the schedule and which has the roles of the schedule. Every run is
+//! recorded in the run history of the schedule, along with the time at which
+//! it started and finished, and the error of the run if it failed. The run
+//! history can be listed with `INFO FOR SCHEDULE`, and is kept for the
+//! configured retention period.
+//!
+//! A run which is in progress when the schedule is removed is not cancelled.
+//! It is allowed to finish, and its changes are committed, but its outcome is
+//! not recorded, as the run history is removed along with the schedule.
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Datetime, Value};
+use crate::key::database::sh;
+use crate::kvs::{KeyDecode, Transaction};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, TimeZone, Timelike, Utc};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+const MONTHS: &[&str] =
+	&["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+const WEEKDAYS: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed cron expression, with the minute, hour, day of the month, month,
+/// and day of the week fields of the expression, each stored as a bit set
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Cron {
+	minutes: u64,
+	hours: u64,
+	days: u64,
+	months: u64,
+	weekdays: u64,
+	/// Whether the day of the month field starts with a `*`
+	any_day: bool,
+	/// Whether the day of the week field starts with a `*`
+	any_weekday: bool,
+}
+
+impl FromStr for Cron {
+	type Err = Error;
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		let invalid = |message: String| Error::InvalidCron {
+			value: value.to_owned(),
+			message,
+		};
+		// Expand the predefined expressions
+		let expr = match value.trim().to_ascii_lowercase().as_str() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly" => "0 0 1 * *",
+			"@weekly" => "0 0 * * 0",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@hourly" => "0 * * * *",
+			v if v.starts_with('@') => return Err(invalid("unknown predefined expression".into())),
+			_ => value,
+		};
+		let fields: Vec<&str> = expr.split_whitespace().collect();
+		let &[minute, hour, day, month, weekday] = fields.as_slice() else {
+			return Err(invalid(format!("expected 5 fields, found {}", fields.len())));
+		};
+		let weekdays = parse_field(weekday, "day of the week", 0, 7, WEEKDAYS).map_err(invalid)?;
+		let cron = Cron {
+			minutes: parse_field(minute, "minute", 0, 59, &[]).map_err(invalid)?,
+			hours: parse_field(hour, "hour", 0, 23, &[]).map_err(invalid)?,
+			days: parse_field(day, "day of the month", 1, 31, &[]).map_err(invalid)?,
+			months: parse_field(month, "month", 1, 12, MONTHS).map_err(invalid)?,
+			// Sunday can be written as both 0 and 7
+			weekdays: (weekdays | (weekdays >> 7)) & 0x7f,
+			any_day: day.starts_with('*'),
+			any_weekday: weekday.starts_with('*'),
+		};
+		// Check that the expression can ever match
+		if cron.next_after(Utc::now()).is_none() {
+			return Err(invalid("the expression never matches".into()));
+		}
+		Ok(cron)
+	}
+}
+
+/// Parses a field of a cron expression into a bit set of the matching values
+fn parse_field(field: &str, name: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+	// Parse a single value, which can also be the name of a month or a day
+	let value = |v: &str| -> Result<u32, String> {
+		let res = match names.iter().position(|n| n.eq_ignore_ascii_case(v)) {
+			Some(i) => i as u32 + min,
+			None => v.parse().map_err(|_| format!("invalid {name} '{v}'"))?,
+		};
+		match (min..=max).contains(&res) {
+			true => Ok(res),
+			false => Err(format!("the {name} '{v}' is not between {min} and {max}")),
+		}
+	};
+	let mut bits = 0u64;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => match step.parse::<u32>() {
+				Ok(step) if step > 0 => (range, Some(step)),
+				_ => return Err(format!("invalid step '{step}' in the {name} field")),
+			},
+			None => (part, None),
+		};
+		let (beg, end) = match range.split_once('-') {
+			_ if range == "*" => (min, max),
+			Some((beg, end)) => (value(beg)?, value(end)?),
+			// A single value with a step ranges up to the maximum
+			None if step.is_some() => (value(range)?, max),
+			None => (value(range)?, value(range)?),
+		};
+		if beg > end {
+			return Err(format!("invalid range '{range}' in the {name} field"));
+		}
+		for v in (beg..=end).step_by(step.unwrap_or(1) as usize) {
+			bits |= 1 << v;
+		}
+	}
+	Ok(bits)
+}
+
+impl Cron {
+	/// Returns the first time after the given time at which the expression matches
+	pub(crate) fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		// The expression matches whole minutes
+		let mut time = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+		// Every expression which can match does so within a few years
+		let limit = after.year() + 10;
+		while time.year() <= limit {
+			if self.months & (1 << time.month()) == 0 {
+				let (year, month) = match time.month() {
+					12 => (time.year() + 1, 1),
+					m => (time.year(), m + 1),
+				};
+				time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+				continue;
+			}
+			if !self.matches_day(time.date_naive()) {
+				let next = time.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+				time = Utc.from_utc_datetime(&next);
+				continue;
+			}
+			if self.hours & (1 << time.hour()) == 0 {
+				time = time.with_minute(0)? + TimeDelta::hours(1);
+				continue;
+			}
+			if self.minutes & (1 << time.minute()) == 0 {
+				time += TimeDelta::minutes(1);
+				continue;
+			}
+			return Some(time);
+		}
+		None
+	}
+
+	/// Checks whether the expression matches a day. When both the day of the
+	/// month and the day of the week are restricted, either of them matches.
+	fn matches_day(&self, date: NaiveDate) -> bool {
+		let day = self.days & (1 << date.day()) != 0;
+		let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+		match (self.any_day, self.any_weekday) {
+			(true, true) => true,
+			(true, false) => weekday,
+			(false, true) => day,
+			(false, false) => day || weekday,
+		}
+	}
+}
+
+/// The state of a schedule, which is shared by all nodes
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub(crate) struct ScheduleState {
+	/// The time at which the schedule is next run
+	pub next: Option<Datetime>,
+	/// The id of the run which is in progress
+	pub running: Option<Uuid>,
+}
+
+impl ScheduleState {
+	/// Creates the state of a schedule which is next run after the given time
+	pub(crate) fn new(cron: &Cron, now: DateTime<Utc>) -> Self {
+		Self {
+			next: cron.next_after(now).map(Datetime::from),
+			running: None,
+		}
+	}
+}
+
+/// A run of a schedule
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub(crate) struct ScheduleRun {
+	/// The time at which the run started
+	pub started: Datetime,
+	/// The time at which the run finished
+	pub finished: Option<Datetime>,
+	/// The error of the run, if it failed
+	pub error: Option<String>,
+}
+
+/// A run in the history of a schedule, along with its id
+pub(crate) struct ScheduleRunEntry {
+	pub id: Uuid,
+	pub run: ScheduleRun,
+}
+
+impl InfoStructure for ScheduleRunEntry {
+	fn structure(self) -> Value {
+		let r = self.run;
+		let status = match (&r.finished, &r.error) {
+			(None, _) => "running",
+			(Some(_), None) => "success",
+			(Some(_), Some(_)) => "failed",
+		};
+		Value::from(map! {
+			"id".to_string() => Value::from(self.id),
+			"started".to_string() => Value::Datetime(r.started),
+			"finished".to_string(), if let Some(v) = r.finished => Value::Datetime(v),
+			"status".to_string() => Value::from(status),
+			"error".to_string(), if let Some(v) = r.error => Value::from(v),
+		})
+	}
+}
+
+/// Fetches the run history of a schedule, from the oldest to the newest run
+pub(crate) async fn runs(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	sc: &str,
+) -> Result<Vec<ScheduleRunEntry>> {
+	let beg = sh::prefix(ns, db, sc)?;
+	let end = sh::suffix(ns, db, sc)?;
+	let mut out = Vec::new();
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = txn.batch_keys_vals(rng, *crate::cnf::NORMAL_FETCH_SIZE, None).await?;
+		next = res.next;
+		for (k, v) in res.result {
+			let key = sh::Sh::decode(&k)?;
+			out.push(ScheduleRunEntry {
+				id: key.sh,
+				run: revision::from_slice(&v)?,
+			});
+		}
+	}
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn next(cron: &str, after: &str) -> String {
+		let cron: Cron = cron.parse().unwrap();
+		let after = DateTime::parse_from_rfc3339(after).unwrap().to_utc();
+		cron.next_after(after).unwrap().to_rfc3339()
+	}
+
+	#[test]
+	fn parse_invalid() {
+		for v in [
+			"",
+			"* * * *",
+			"* * * * * *",
+			"60 * * * *",
+			"* 24 * * *",
+			"* * 0 * *",
+			"* * * 13 *",
+			"* * * * 8",
+			"*/0 * * * *",
+			"5-1 * * * *",
+			"a * * * *",
+			"@never",
+			"0 0 30 2 *",
+		] {
+			assert!(v.parse::<Cron>().is_err(), "{v}");
+		}
+	}
+
+	#[test]
+	fn next_run() {
+		assert_eq!(next("0 3 * * *", "2024-01-01T10:00:00Z"), "2024-01-02T03:00:00+00:00");
+		assert_eq!(next("0 3 * * *", "2024-01-01T02:59:59Z"), "2024-01-01T03:00:00+00:00");
+		assert_eq!(next("0 3 * * *", "2024-01-01T03:00:00Z"), "2024-01-02T03:00:00+00:00");
+		assert_eq!(next("*/15 * * * *", "2024-01-01T10:16:00Z"), "2024-01-01T10:30:00+00:00");
+		assert_eq!(next("5,10-12 * * * *", "2024-01-01T10:10:00Z"), "2024-01-01T10:11:00+00:00");
+		assert_eq!(next("0 0 1 jan *", "2024-03-01T00:00:00Z"), "2025-01-01T00:00:00+00:00");
+		assert_eq!(next("@hourly", "2024-12-31T23:30:00Z"), "2025-01-01T00:00:00+00:00");
+		assert_eq!(next("0 0 29 2 *", "2024-03-01T00:00:00Z"), "2028-02-29T00:00:00+00:00");
+		// Sunday can be written as 0, 7, or SUN
+		assert_eq!(next("0 12 * * 7", "2024-01-01T00:00:00Z"), "2024-01-07T12:00:00+00:00");
+		assert_eq!(next("0 12 * * sun", "2024-01-01T00:00:00Z"), "2024-01-07T12:00:00+00:00");
+		// Either the day of the month or the day of the week matches
+		assert_eq!(next("0 0 15 * mon", "2024-01-02T00:00:00Z"), "2024-01-08T00:00:00+00:00");
+		assert_eq!(next("0 0 3 * mon", "2024-01-02T00:00:00Z"), "2024-01-03T00:00:00+00:00");
+	}
+}
@@ -1,10 +1,12 @@
 use crate::ctx::MutableContext;
+use crate::dbs::capabilities::LiveQueryOverflow;
 use crate::expr::value::Value;
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
 use chrono::Utc;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Specifies the current session information when processing a query.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -20,6 +22,8 @@ pub struct Session {
 	pub or: Option<String>,
 	/// The current connection ID
 	pub id: Option<String>,
+	/// The current connection endpoint type (ws or http)
+	pub ep: Option<String>,
 	/// The currently selected namespace
 	pub ns: Option<String>,
 	/// The currently selected database
@@ -34,6 +38,21 @@ pub struct Session {
 	pub exp: Option<i64>,
 	/// The parameters set
 	pub parameters: BTreeMap<String, Value>,
+	/// The default timeout for statements without a TIMEOUT clause
+	pub timeout: Option<Duration>,
+	/// The maximum duration of each query, capped by the datastore query timeout
+	pub query_timeout: Option<Duration>,
+	/// Whether statements which could modify data are rejected
+	pub readonly: bool,
+	/// Whether execution statistics are returned with each statement result
+	pub stats: bool,
+	/// Whether queries are validated without writing any of their changes
+	pub dry_run: bool,
+	/// The number of notifications which can be queued for each live query
+	/// on this connection, or `None` if the queues are unbounded
+	pub live_query_queue_size: Option<usize>,
+	/// What happens when the notification queue of a live query is full
+	pub live_query_overflow: LiveQueryOverflow,
 }
 
 impl Session {
@@ -55,12 +74,48 @@ impl Session {
 		self
 	}
 
+	/// Set the connection endpoint type for the session
+	pub fn with_ep(mut self, ep: &str) -> Session {
+		self.ep = Some(ep.to_owned());
+		self
+	}
+
 	// Set the realtime functionality of the session
 	pub fn with_rt(mut self, rt: bool) -> Session {
 		self.rt = rt;
 		self
 	}
 
+	/// Set the default timeout for statements which don't specify a timeout
+	pub fn with_timeout(mut self, timeout: Duration) -> Session {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Set the maximum duration of each query executed in the session
+	pub fn with_query_timeout(mut self, timeout: Duration) -> Session {
+		self.query_timeout = Some(timeout);
+		self
+	}
+
+	/// Set whether statements which could modify data are rejected
+	pub fn with_readonly(mut self, readonly: bool) -> Session {
+		self.readonly = readonly;
+		self
+	}
+
+	/// Set whether execution statistics are returned with each statement result
+	pub fn with_stats(mut self, stats: bool) -> Session {
+		self.stats = stats;
+		self
+	}
+
+	/// Set whether queries are validated without writing any of their changes
+	pub fn with_dry_run(mut self, dry_run: bool) -> Session {
+		self.dry_run = dry_run;
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -93,10 +148,16 @@ impl Session {
 			"ac".to_string() => self.ac.clone().into(),
 			"exp".to_string() => self.exp.into(),
 			"db".to_string() => self.db.clone().into(),
+			"ep".to_string() => self.ep.clone().into(),
 			"id".to_string() => self.id.clone().into(),
 			"ip".to_string() => self.ip.clone().into(),
+			"lq".to_string() => Value::from(map! {
+				"overflow".to_string() => self.live_query_overflow.to_string().into(),
+				"queue".to_string() => self.live_query_queue_size.into(),
+			}),
 			"ns".to_string() => self.ns.clone().into(),
 			"or".to_string() => self.or.clone().into(),
+			"pv".to_string() => Value::from(self.parameters.clone()),
 			"rd".to_string() => self.rd.clone().into(),
 			"tk".to_string() => self.tk.clone().into(),
 		});
@@ -143,6 +204,7 @@ impl Session {
 			ip: None,
 			or: None,
 			id: None,
+			ep: None,
 			ns: Some(ns.to_owned()),
 			db: Some(db.to_owned()),
 			tk: None,
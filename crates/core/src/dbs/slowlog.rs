@@ -0,0 +1,220 @@
+//! The slow query log.
+//!
+//! When enabled on the datastore, every statement which takes longer than
+//! the configured threshold is reported with a structured tracing event,
+//! and can also be stored as a record in a table of its namespace and
+//! database. The statements are normalised, so that the statements which
+//! only differ by their literal values can be aggregated by fingerprint.
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::stats::{QueryStats, returned};
+use crate::expr::paths::{AC, IP};
+use crate::expr::{Id, LogicalPlan, Object, Thing, Value};
+use crate::kvs::{Datastore, LockType, TransactionType};
+use anyhow::Result;
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::dbs::slowlog";
+
+/// The configuration of the slow query log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowQueryLog {
+	threshold: Duration,
+	table: Option<String>,
+}
+
+impl SlowQueryLog {
+	/// Reports the statements which take at least the given duration
+	pub fn new(threshold: Duration) -> Self {
+		Self {
+			threshold,
+			table: None,
+		}
+	}
+
+	/// Also stores the slow statements as records in the given table,
+	/// in the namespace and database which the statement ran in
+	pub fn with_table(mut self, table: impl Into<String>) -> Self {
+		self.table = Some(table.into());
+		self
+	}
+
+	/// The minimum duration of the reported statements
+	pub fn threshold(&self) -> Duration {
+		self.threshold
+	}
+
+	/// The table which the slow statements are stored in, if any
+	pub fn table(&self) -> Option<&str> {
+		self.table.as_deref()
+	}
+}
+
+/// A statement which took longer than the threshold of the slow query log
+#[derive(Debug)]
+pub(crate) struct SlowQuery {
+	/// The normalised text of the statement
+	query: String,
+	/// The fingerprint of the normalised statement
+	fingerprint: String,
+	duration: Duration,
+	scanned: u64,
+	returned: u64,
+	plan: Vec<Value>,
+	ns: Option<String>,
+	db: Option<String>,
+	/// The level and identifier of the authenticated user
+	level: String,
+	user: String,
+	access: Option<String>,
+	ip: Option<String>,
+}
+
+impl SlowQuery {
+	pub(crate) fn new(
+		ctx: &Context,
+		opt: &Options,
+		plan: &LogicalPlan,
+		stats: &QueryStats,
+		duration: Duration,
+		result: Option<&Value>,
+	) -> Self {
+		let (query, fingerprint) = plan.fingerprint();
+		let session = ctx.value("session");
+		let pick = |path: &[_]| {
+			session.map(|s| s.pick(path)).filter(|v| v.is_some()).map(|v| v.as_raw_string())
+		};
+		Self {
+			query,
+			fingerprint,
+			duration,
+			scanned: stats.scanned(),
+			returned: returned(result),
+			plan: stats.take_plan(),
+			ns: opt.ns().ok().map(str::to_owned),
+			db: opt.db().ok().map(str::to_owned),
+			level: opt.auth.level().to_string(),
+			user: opt.auth.id().to_owned(),
+			access: pick(AC.as_ref()),
+			ip: pick(IP.as_ref()),
+		}
+	}
+
+	/// Reports the statement with a tracing event
+	pub(crate) fn log(&self) {
+		warn!(
+			target: TARGET,
+			fingerprint = %self.fingerprint,
+			query = %self.query,
+			duration = ?self.duration,
+			scanned = self.scanned,
+			returned = self.returned,
+			plan = %Value::from(self.plan.clone()),
+			ns = self.ns.as_deref(),
+			db = self.db.as_deref(),
+			level = %self.level,
+			user = %self.user,
+			access = self.access.as_deref(),
+			ip = self.ip.as_deref(),
+			"Slow query"
+		);
+	}
+
+	/// Stores the statement in the table of the slow query log.
+	/// Statements which did not run in a database are only logged.
+	pub(crate) async fn store(self, kvs: &Datastore, table: &str) -> Result<()> {
+		let (Some(ns), Some(db)) = (self.ns.clone(), self.db.clone()) else {
+			return Ok(());
+		};
+		let txn = kvs.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		let res = async {
+			txn.ensure_ns_db_tb(&ns, &db, table, false).await?;
+			let rid = Thing::from((table.to_owned(), Id::ulid()));
+			let mut val = Value::from(self);
+			val.def(&rid);
+			let key = crate::key::thing::new(&ns, &db, table, &rid.id);
+			txn.set(key, revision::to_vec(&val)?, None).await
+		}
+		.await;
+		match res {
+			Ok(_) => txn.commit().await,
+			Err(e) => {
+				txn.cancel().await?;
+				Err(e)
+			}
+		}
+	}
+}
+
+impl From<SlowQuery> for Value {
+	fn from(q: SlowQuery) -> Self {
+		let mut res = Object::default();
+		res.insert("time".to_owned(), Value::from(crate::expr::Datetime::default()));
+		res.insert("fingerprint".to_owned(), Value::from(q.fingerprint));
+		res.insert("query".to_owned(), Value::from(q.query));
+		res.insert("duration".to_owned(), Value::from(q.duration));
+		res.insert("scanned".to_owned(), Value::from(q.scanned));
+		res.insert("returned".to_owned(), Value::from(q.returned));
+		res.insert("plan".to_owned(), Value::from(q.plan));
+		res.insert("ns".to_owned(), Value::from(q.ns));
+		res.insert("db".to_owned(), Value::from(q.db));
+		res.insert("level".to_owned(), Value::from(q.level));
+		res.insert("user".to_owned(), Value::from(q.user));
+		res.insert("access".to_owned(), Value::from(q.access));
+		res.insert("ip".to_owned(), Value::from(q.ip));
+		Value::from(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::expr::Part;
+
+	#[tokio::test]
+	async fn slow_queries_are_stored() {
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_slow_query_log(Some(SlowQueryLog::new(Duration::ZERO).with_table("slow")));
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "CREATE person:1 SET age = 20; CREATE person:2 SET age = 30; CREATE person:3 SET age = 10;";
+		ds.execute(sql, &sess, None).await.unwrap();
+		ds.execute("SELECT * FROM person WHERE age > 15", &sess, None).await.unwrap();
+		ds.execute("SELECT * FROM person WHERE age > 25", &sess, None).await.unwrap();
+		// The statements which only differ by their literals are grouped together
+		let sql = "
+			LET $slow = SELECT * FROM slow WHERE query = 'SELECT * FROM person WHERE age > $?' ORDER BY id;
+			RETURN [
+				$slow.map(|$v| [$v.scanned, $v.returned, $v.plan.iterator, $v.user, $v.ns]),
+				array::distinct($slow.fingerprint).len(),
+			];
+		";
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		let val = res.remove(1).result.unwrap();
+		let row = |returned: i64| {
+			Value::from(vec![
+				Value::from(3),
+				Value::from(returned),
+				Value::from(vec![Value::from("Iterate Table")]),
+				Value::from("system_auth"),
+				Value::from("test"),
+			])
+		};
+		assert_eq!(val, Value::from(vec![Value::from(vec![row(2), row(1)]), Value::from(1)]));
+	}
+
+	#[tokio::test]
+	async fn fast_queries_are_not_logged() {
+		let ds = Datastore::new("memory").await.unwrap().with_slow_query_log(Some(
+			SlowQueryLog::new(Duration::from_secs(60)).with_table("slow"),
+		));
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE person:1; SELECT * FROM person", &sess, None).await.unwrap();
+		// Only the slow statements are stored, so the table is never created
+		let mut res = ds.execute("INFO FOR DB", &sess, None).await.unwrap();
+		let info = res.remove(0).result.unwrap();
+		assert!(info.pick(&[Part::from("tables"), Part::from("slow")]).is_none());
+	}
+}
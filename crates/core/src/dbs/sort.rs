@@ -0,0 +1,380 @@
+//! The external sorting of the ORDER BY clause.
+//!
+//! When a temporary directory is configured, the rows of an ORDER BY clause
+//! are collected in memory until their estimated size reaches the sort memory
+//! set by the capabilities. The rows which are buffered are then sorted, and
+//! written as a run to a temporary directory of the statement, using the
+//! internal serialisation of the values. Once every row is collected, the runs
+//! are merged lazily, so that only the rows within the START and LIMIT clauses
+//! are kept in memory. The directory of the statement is removed when the
+//! collector is dropped, which also happens when the statement is cancelled,
+//! and a merge which is running stops as soon as the statement is cancelled.
+use crate::ctx::Context;
+use crate::ctx::cancellation::Cancellation;
+use crate::dbs::file::{FileReader, FileWriter};
+use crate::dbs::plan::Explanation;
+use crate::dbs::stats::estimated_size;
+use crate::err::Error;
+use crate::expr::Value;
+use crate::expr::order::OrderList;
+use anyhow::{Result, ensure};
+use rayon::prelude::ParallelSliceMut;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
+use std::mem::{self, size_of};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::{Builder, TempDir};
+use tokio::task::spawn_blocking;
+
+/// How many rows are merged between each check for cancellation
+const CANCELLATION_INTERVAL: usize = 1_000;
+
+pub(super) struct ExternalOrdered {
+	/// The configured temporary directory
+	temp_dir: Arc<PathBuf>,
+	/// The directory of this statement, created once the first run is written
+	dir: Option<TempDir>,
+	/// The order specification
+	orders: Arc<OrderList>,
+	/// The rows which are not yet written to a run
+	buffer: Vec<Value>,
+	/// The estimated size of the buffered rows
+	buffered: usize,
+	/// The estimated size at which the buffered rows are written to a run
+	memory: usize,
+	/// The sorted runs which have been written to disk
+	runs: Vec<Run>,
+	/// The number of bytes written to disk
+	written: u64,
+	/// The maximum number of bytes which can be written to disk
+	limit: Option<u64>,
+	/// The total number of rows
+	len: usize,
+	/// Whether the buffered rows are sorted
+	sorted: bool,
+	/// The START clause, applied while merging
+	start: usize,
+	/// The LIMIT clause, applied while merging
+	num: Option<usize>,
+	/// The cancellation status of the statement
+	cancellation: Cancellation,
+}
+
+/// A sorted run of rows, written to disk
+struct Run {
+	path: PathBuf,
+	len: usize,
+}
+
+impl ExternalOrdered {
+	pub(super) fn new(ctx: &Context, temp_dir: Arc<PathBuf>, orders: OrderList) -> Self {
+		let caps = ctx.get_capabilities();
+		Self {
+			temp_dir,
+			dir: None,
+			orders: Arc::new(orders),
+			buffer: Vec::new(),
+			buffered: 0,
+			memory: caps.sort_memory() as usize,
+			runs: Vec::new(),
+			written: 0,
+			limit: caps.max_sort_spill(),
+			len: 0,
+			sorted: false,
+			start: 0,
+			num: None,
+			cancellation: ctx.cancellation(),
+		}
+	}
+
+	pub(super) async fn push(&mut self, value: Value) -> Result<()> {
+		self.buffered += estimated_size(&value);
+		self.buffer.push(value);
+		self.len += 1;
+		self.sorted = false;
+		if self.buffered >= self.memory {
+			self.spill().await?;
+		}
+		Ok(())
+	}
+
+	/// Sorts the buffered rows, and writes them to a new run
+	async fn spill(&mut self) -> Result<()> {
+		let dir = match &mut self.dir {
+			Some(dir) => dir,
+			dir => {
+				dir.insert(Builder::new().prefix("SURREAL").tempdir_in(self.temp_dir.as_path())?)
+			}
+		};
+		let path = dir.path().join(format!("run-{}", self.runs.len()));
+		let mut rows = mem::take(&mut self.buffer);
+		let len = rows.len();
+		self.buffered = 0;
+		let (orders, file, written, limit) =
+			(self.orders.clone(), path.clone(), self.written, self.limit);
+		self.written = spawn_blocking(move || {
+			rows.par_sort_unstable_by(|a, b| orders.compare(a, b));
+			write_run(&file, rows, written, limit)
+		})
+		.await
+		.map_err(|e| Error::OrderingError(format!("{e}")))??;
+		self.runs.push(Run {
+			path,
+			len,
+		});
+		Ok(())
+	}
+
+	pub(super) async fn sort(&mut self) -> Result<()> {
+		if self.runs.is_empty() {
+			// Every row fits in memory
+			if !self.sorted {
+				let mut rows = mem::take(&mut self.buffer);
+				let orders = self.orders.clone();
+				self.buffer = spawn_blocking(move || {
+					rows.par_sort_unstable_by(|a, b| orders.compare(a, b));
+					rows
+				})
+				.await
+				.map_err(|e| Error::OrderingError(format!("{e}")))?;
+			}
+		} else if !self.buffer.is_empty() {
+			// The remaining rows are merged with the other runs
+			self.spill().await?;
+		}
+		self.sorted = true;
+		Ok(())
+	}
+
+	pub(super) fn start_limit(&mut self, start: Option<u32>, limit: Option<u32>) {
+		self.start = start.unwrap_or(0) as usize;
+		self.num = limit.map(|l| l as usize);
+	}
+
+	pub(super) fn len(&self) -> usize {
+		let len = self.len.saturating_sub(self.start);
+		self.num.map_or(len, |num| len.min(num))
+	}
+
+	pub(super) async fn take_vec(&mut self) -> Result<Vec<Value>> {
+		self.sort().await?;
+		let (start, num) = (self.start, self.num);
+		if self.runs.is_empty() {
+			let mut rows = mem::take(&mut self.buffer);
+			rows.drain(..start.min(rows.len()));
+			if let Some(num) = num {
+				rows.truncate(num);
+			}
+			return Ok(rows);
+		}
+		let runs = mem::take(&mut self.runs);
+		let orders = self.orders.clone();
+		let cancellation = self.cancellation.clone();
+		let res = spawn_blocking(move || merge(runs, orders, start, num, cancellation))
+			.await
+			.map_err(|e| Error::OrderingError(format!("{e}")))?;
+		// The runs are no longer needed
+		self.dir = None;
+		res
+	}
+
+	pub(super) fn explain(&self, exp: &mut Explanation) {
+		// The rows are sorted in memory, unless they exceed the sort memory
+		exp.add_collector("MemoryOrdered", vec![]);
+	}
+}
+
+/// Writes sorted rows to a run, returning the total number of bytes written
+/// by the sort, and failing as soon as this exceeds the limit.
+fn write_run(path: &Path, rows: Vec<Value>, mut written: u64, limit: Option<u64>) -> Result<u64> {
+	let file = OpenOptions::new().create_new(true).append(true).open(path)?;
+	let mut writer = BufWriter::new(file);
+	for row in rows {
+		let len = FileWriter::write_value(&mut writer, row)?;
+		written += (len + size_of::<usize>()) as u64;
+		if let Some(limit) = limit {
+			ensure!(
+				written <= limit,
+				Error::SortSpillExceeded {
+					written,
+					limit,
+				}
+			);
+		}
+	}
+	writer.flush()?;
+	Ok(written)
+}
+
+/// Merges the sorted runs, keeping only the rows within the START and LIMIT clauses
+fn merge(
+	runs: Vec<Run>,
+	orders: Arc<OrderList>,
+	start: usize,
+	num: Option<usize>,
+	cancellation: Cancellation,
+) -> Result<Vec<Value>> {
+	let total = runs.iter().map(|run| run.len).sum::<usize>().saturating_sub(start);
+	let mut readers = Vec::with_capacity(runs.len());
+	let mut heap = BinaryHeap::with_capacity(runs.len());
+	for run in runs {
+		let mut reader = RunReader {
+			reader: BufReader::new(OpenOptions::new().read(true).open(&run.path)?),
+			remaining: run.len,
+		};
+		if let Some(value) = reader.next()? {
+			heap.push(Head {
+				value,
+				run: readers.len(),
+				orders: orders.clone(),
+			});
+		}
+		readers.push(reader);
+	}
+	let mut res = Vec::with_capacity(num.map_or(total, |num| num.min(total)));
+	let mut pos = 0;
+	while let Some(Head {
+		value,
+		run,
+		..
+	}) = heap.pop()
+	{
+		if num.is_some_and(|num| res.len() >= num) {
+			break;
+		}
+		if pos % CANCELLATION_INTERVAL == 0 {
+			ensure!(!cancellation.is_done(), Error::QueryCancelled);
+		}
+		// Replace the row with the next row from the same run
+		if let Some(value) = readers[run].next()? {
+			heap.push(Head {
+				value,
+				run,
+				orders: orders.clone(),
+			});
+		}
+		if pos >= start {
+			res.push(value);
+		}
+		pos += 1;
+	}
+	Ok(res)
+}
+
+struct RunReader {
+	reader: BufReader<File>,
+	remaining: usize,
+}
+
+impl RunReader {
+	fn next(&mut self) -> Result<Option<Value>, Error> {
+		if self.remaining == 0 {
+			return Ok(None);
+		}
+		self.remaining -= 1;
+		FileReader::read_value(&mut self.reader).map(Some)
+	}
+}
+
+/// The next row of a run, ordered so that the heap returns the first row
+struct Head {
+	value: Value,
+	run: usize,
+	orders: Arc<OrderList>,
+}
+
+impl PartialEq for Head {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Head {}
+
+impl PartialOrd for Head {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Head {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Reversed, as the binary heap returns the greatest value first,
+		// with equal rows returned in the order of their runs
+		self.orders.compare(&other.value, &self.value).then_with(|| other.run.cmp(&self.run))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::{Capabilities, Session};
+	use crate::err::Error;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+	use tempfile::TempDir;
+
+	async fn datastore(caps: Capabilities, dir: &TempDir) -> Datastore {
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(caps)
+			.with_temporary_directory(Some(dir.path().to_path_buf()));
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "FOR $i IN 0..2000 { CREATE person SET age = ($i * 7919) % 2000, name = string::repeat('a', 100) }";
+		ds.execute(sql, &sess, None).await.unwrap();
+		ds
+	}
+
+	fn ages(val: Value) -> Vec<i64> {
+		let Value::Array(arr) = val else {
+			panic!("expected an array");
+		};
+		arr.iter()
+			.map(|v| match v {
+				Value::Object(o) => o["age"].clone().coerce_to::<i64>().unwrap(),
+				v => panic!("expected an object, found {v}"),
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn rows_larger_than_the_sort_memory_are_merged() {
+		let dir = TempDir::new().unwrap();
+		let caps = Capabilities::default().with_sort_memory(16 * 1024);
+		let ds = datastore(caps, &dir).await;
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			SELECT age, name FROM person ORDER BY age;
+			SELECT age, name FROM person ORDER BY age DESC START 1500 LIMIT 1000;
+		";
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		assert_eq!(ages(res.remove(0).result.unwrap()), (0..2000).collect::<Vec<_>>());
+		assert_eq!(ages(res.remove(0).result.unwrap()), (0..500).rev().collect::<Vec<_>>());
+		// The runs are removed once the statement finishes
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+	}
+
+	#[tokio::test]
+	async fn spilled_rows_are_limited() {
+		let dir = TempDir::new().unwrap();
+		let caps = Capabilities::default()
+			.with_sort_memory(16 * 1024)
+			.with_max_sort_spill(Some(64 * 1024));
+		let ds = datastore(caps, &dir).await;
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let mut res = ds.execute("SELECT * FROM person ORDER BY age", &sess, None).await.unwrap();
+		let err = res.remove(0).result.unwrap_err();
+		assert!(
+			matches!(
+				err.downcast_ref(),
+				Some(Error::SortSpillExceeded { written, limit: 65536 }) if *written > 65536
+			),
+			"{err}"
+		);
+		// The runs are removed when the statement fails
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+	}
+}
@@ -22,11 +22,12 @@ use crate::expr::statements::select::SelectStatement;
 use crate::expr::statements::show::ShowStatement;
 use crate::expr::statements::update::UpdateStatement;
 use crate::expr::statements::upsert::UpsertStatement;
-use crate::expr::{Explain, Permission, Timeout, With};
+use crate::expr::{Explain, LogicalPlan, Permission, Timeout, Value, With};
 use crate::idx::planner::QueryPlanner;
-use anyhow::Result;
+use anyhow::{Result, bail};
 use std::borrow::Cow;
 use std::fmt;
+use trice::Instant;
 
 #[derive(Clone, Debug)]
 pub(crate) enum Statement<'a> {
@@ -120,6 +121,23 @@ impl fmt::Display for Statement<'_> {
 }
 
 impl Statement<'_> {
+	/// Returns the normalised text of the statement, and its fingerprint
+	pub(crate) fn fingerprint(&self) -> (String, String) {
+		let plan = match self {
+			Statement::Live(v) => LogicalPlan::Live((*v).clone()),
+			Statement::Show(v) => LogicalPlan::Show((*v).clone()),
+			Statement::Select(v) => LogicalPlan::Select((*v).clone()),
+			Statement::Create(v) => LogicalPlan::Create((*v).clone()),
+			Statement::Upsert(v) => LogicalPlan::Upsert((*v).clone()),
+			Statement::Update(v) => LogicalPlan::Update((*v).clone()),
+			Statement::Relate(v) => LogicalPlan::Relate((*v).clone()),
+			Statement::Delete(v) => LogicalPlan::Delete((*v).clone()),
+			Statement::Insert(v) => LogicalPlan::Insert((*v).clone()),
+			Statement::Access(v) => LogicalPlan::Access((*v).clone()),
+		};
+		plan.fingerprint()
+	}
+
 	/// Check if this is a SELECT statement
 	pub(crate) fn is_select(&self) -> bool {
 		matches!(self, Statement::Select(_))
@@ -284,6 +302,14 @@ impl Statement<'_> {
 		}
 	}
 
+	/// Returns any IF VERSION clause if specified
+	pub(crate) fn if_version(&self) -> Option<&Value> {
+		match self {
+			Statement::Update(v) => v.if_version.as_ref(),
+			_ => None,
+		}
+	}
+
 	/// Returns any SPLIT clause if specified
 	pub(crate) fn split(&self) -> Option<&Splits> {
 		match self {
@@ -295,11 +321,25 @@ impl Statement<'_> {
 	/// Returns any GROUP clause if specified
 	pub(crate) fn group(&self) -> Option<&Groups> {
 		match self {
+			Statement::Live(v) => v.group.as_ref(),
 			Statement::Select(v) => v.group.as_ref(),
 			_ => None,
 		}
 	}
 
+	/// Returns whether any of the fields is a window function
+	pub(crate) fn has_windows(&self) -> bool {
+		self.expr().is_some_and(Fields::has_windows)
+	}
+
+	/// Returns any HAVING clause if specified
+	pub(crate) fn having(&self) -> Option<&Cond> {
+		match self {
+			Statement::Select(v) => v.having.as_ref(),
+			_ => None,
+		}
+	}
+
 	/// Returns any ORDER clause if specified
 	pub(crate) fn order(&self) -> Option<&Ordering> {
 		match self {
@@ -411,6 +451,7 @@ impl Statement<'_> {
 			Statement::Create(s) => s.timeout.as_ref(),
 			Statement::Delete(s) => s.timeout.as_ref(),
 			Statement::Insert(s) => s.timeout.as_ref(),
+			Statement::Relate(s) => s.timeout.as_ref(),
 			Statement::Select(s) => s.timeout.as_ref(),
 			Statement::Update(s) => s.timeout.as_ref(),
 			Statement::Upsert(s) => s.timeout.as_ref(),
@@ -427,6 +468,35 @@ impl Statement<'_> {
 		}
 	}
 
+	/// Checks whether this statement was interrupted by a timeout. When the
+	/// statement ran beyond its own TIMEOUT clause, the error identifies
+	/// the statement, and how long it actually ran for.
+	pub(crate) async fn check_timeout<T>(
+		&self,
+		ctx: &Context,
+		started: Instant,
+		res: Result<T>,
+	) -> Result<T> {
+		// Check if the statement was interrupted
+		let timedout = match &res {
+			Ok(_) => ctx.is_timedout().await?,
+			Err(e) => matches!(e.downcast_ref(), Some(Error::QueryTimedout)),
+		};
+		if !timedout {
+			return res;
+		}
+		// Check if the statement exceeded its own timeout
+		let elapsed = started.elapsed();
+		match self.timeout() {
+			Some(t) if elapsed >= *t.0 => bail!(Error::StatementTimedout {
+				statement: self.to_string(),
+				timeout: t.0,
+				elapsed: elapsed.into(),
+			}),
+			_ => bail!(Error::QueryTimedout),
+		}
+	}
+
 	pub(crate) fn setup_query_planner<'a>(
 		&self,
 		planner: QueryPlanner,
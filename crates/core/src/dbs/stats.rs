@@ -0,0 +1,171 @@
+//! The execution statistics of the statements.
+//!
+//! The statistics are only collected when they are requested for a query,
+//! or when the slow query log is enabled. Otherwise no counters are attached
+//! to the context or the transaction, and the iterators skip the counting.
+use crate::expr::{Id, Value};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The statistics collected while a statement is running
+#[derive(Debug, Default)]
+pub(crate) struct QueryStats {
+	/// The number of records processed by the iterators
+	scanned: AtomicU64,
+	/// The number of entries read from the indexes
+	index_entries: AtomicU64,
+	/// The largest estimated size of the results held by an iterator
+	peak_memory: AtomicU64,
+	/// The iterators chosen by the query planner
+	plan: Mutex<Vec<Value>>,
+}
+
+impl QueryStats {
+	/// Records that a record was processed by an iterator
+	pub(crate) fn add_scanned(&self) {
+		self.scanned.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Records entries read from an index
+	pub(crate) fn add_index_entries(&self, count: usize) {
+		self.index_entries.fetch_add(count as u64, Ordering::Relaxed);
+	}
+
+	/// Records the estimated size of the results held by an iterator
+	pub(crate) fn add_memory(&self, bytes: usize) {
+		self.peak_memory.fetch_max(bytes as u64, Ordering::Relaxed);
+	}
+
+	/// Records an iterator chosen by the query planner
+	pub(crate) fn add_plan(&self, plan: Value) {
+		self.plan.lock().push(plan);
+	}
+
+	/// The number of records processed by the iterators
+	pub(crate) fn scanned(&self) -> u64 {
+		self.scanned.load(Ordering::Relaxed)
+	}
+
+	/// Takes the iterators chosen by the query planner
+	pub(crate) fn take_plan(&self) -> Vec<Value> {
+		std::mem::take(&mut *self.plan.lock())
+	}
+
+	/// The statistics which are returned with the result of the statement
+	pub(crate) fn response(&self, result: Option<&Value>, bytes_read: u64) -> ResponseStats {
+		ResponseStats {
+			scanned: self.scanned(),
+			returned: returned(result),
+			index_entries: self.index_entries.load(Ordering::Relaxed),
+			bytes_read,
+			peak_memory: self.peak_memory.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// The execution statistics of a statement,
+/// returned alongside its result when requested
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ResponseStats {
+	/// The number of records processed by the iterators
+	pub scanned: u64,
+	/// The number of records returned by the statement
+	pub returned: u64,
+	/// The number of entries read from the indexes
+	pub index_entries: u64,
+	/// The number of bytes read from the key-value store
+	pub bytes_read: u64,
+	/// The estimated peak size, in bytes, of the intermediate results
+	pub peak_memory: u64,
+}
+
+/// The number of records returned by a statement
+pub(crate) fn returned(result: Option<&Value>) -> u64 {
+	match result {
+		Some(Value::Array(a)) => a.len() as u64,
+		Some(Value::None) | None => 0,
+		Some(_) => 1,
+	}
+}
+
+/// Estimates the memory used by a value, including its allocations
+pub(crate) fn estimated_size(v: &Value) -> usize {
+	let heap = match v {
+		Value::Strand(s) => s.len(),
+		Value::Bytes(b) => b.len(),
+		Value::Array(a) => a.iter().map(estimated_size).sum(),
+		Value::Object(o) => o.iter().map(|(k, v)| k.len() + estimated_size(v)).sum(),
+		Value::Thing(t) => {
+			t.tb.len()
+				+ match &t.id {
+					Id::String(s) => s.len(),
+					Id::Array(a) => a.iter().map(estimated_size).sum(),
+					Id::Object(o) => o.iter().map(|(k, v)| k.len() + estimated_size(v)).sum(),
+					_ => 0,
+				}
+		}
+		_ => 0,
+	};
+	size_of::<Value>() + heap
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::expr::{Array, Thing};
+	use crate::kvs::Datastore;
+
+	#[test]
+	fn estimated_sizes() {
+		let base = size_of::<Value>();
+		assert_eq!(estimated_size(&Value::None), base);
+		assert_eq!(estimated_size(&Value::from("test")), base + 4);
+		let arr = Value::Array(Array(vec![Value::from(1), Value::from("ab")]));
+		assert_eq!(estimated_size(&arr), base * 3 + 2);
+		let rid = Value::Thing(Thing::from(("person", "tobie")));
+		assert_eq!(estimated_size(&rid), base + 11);
+	}
+
+	#[test]
+	fn returned_records() {
+		assert_eq!(returned(None), 0);
+		assert_eq!(returned(Some(&Value::None)), 0);
+		assert_eq!(returned(Some(&Value::from(1))), 1);
+		assert_eq!(returned(Some(&Value::Array(Array(vec![Value::Null, Value::Null])))), 2);
+	}
+
+	#[tokio::test]
+	async fn statistics_are_returned_when_requested() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE INDEX age ON person FIELDS age;
+			CREATE person:1 SET age = 20;
+			CREATE person:2 SET age = 30;
+			CREATE person:3 SET age = 10;
+		";
+		ds.execute(sql, &sess, None).await.unwrap();
+		let sql = "SELECT * FROM person WHERE name = NONE; SELECT * FROM person WHERE age > 15";
+		// The statistics are not collected by default
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res.iter().all(|r| r.stats.is_none()));
+		// The statistics are returned with each statement result
+		let res = ds.execute(sql, &sess.with_stats(true), None).await.unwrap();
+		let table = res[0].stats.unwrap();
+		assert_eq!(table.scanned, 3);
+		assert_eq!(table.returned, 3);
+		assert_eq!(table.index_entries, 0);
+		assert!(table.bytes_read > 0);
+		assert!(table.peak_memory > 0);
+		let index = res[1].stats.unwrap();
+		assert_eq!(index.scanned, 2);
+		assert_eq!(index.returned, 2);
+		assert_eq!(index.index_entries, 2);
+		assert!(index.bytes_read > 0);
+		assert!(index.peak_memory < table.peak_memory);
+	}
+}
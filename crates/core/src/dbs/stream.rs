@@ -0,0 +1,289 @@
+//! Streaming of the results of a query.
+//!
+//! When a query is executed with a frame channel, the rows which a `SELECT`
+//! statement produces are sent to the channel in batches of rows as they are
+//! iterated, instead of being collected into the result of the statement. A
+//! statement which needs all of its rows before it can output any of them,
+//! such as one with an ORDER BY or a GROUP BY clause, still buffers the rows
+//! internally, but then sends its output in batches. Every statement of the
+//! query then finishes with a frame carrying its status, time, and statistics.
+//! The result of a statement whose rows were streamed is not repeated in this
+//! frame. When a statement fails after some of its rows were already sent,
+//! its final frame carries the error, and is marked as a partial result.
+use crate::cnf::STREAM_BATCH_SIZE;
+use crate::dbs::Response;
+use crate::err::Error;
+use crate::expr::{self, Value};
+use anyhow::Result;
+use async_channel::Sender;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// A frame of the streamed results of a query
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QueryFrame {
+	/// A batch of the rows produced by a statement
+	Rows {
+		/// The position of the statement in the query
+		query: usize,
+		/// The rows, in the order in which they were produced
+		rows: Vec<Value>,
+	},
+	/// The end of the result of a statement
+	Done {
+		/// The position of the statement in the query
+		query: usize,
+		/// The status, time, and statistics of the statement
+		response: Response,
+		/// The number of rows which were streamed, if they were
+		rows: Option<usize>,
+	},
+}
+
+impl QueryFrame {
+	/// Checks if this is the end of a statement which failed after some of its rows were sent
+	pub fn is_partial(&self) -> bool {
+		match self {
+			Self::Done {
+				response,
+				rows,
+				..
+			} => response.result.is_err() && rows.is_some_and(|n| n > 0),
+			Self::Rows {
+				..
+			} => false,
+		}
+	}
+
+	/// Converts the frame into the value which is sent to the client
+	pub fn into_value(self) -> Result<Value> {
+		let partial = self.is_partial();
+		match self {
+			Self::Rows {
+				query,
+				rows,
+			} => Ok(Value::from(map! {
+				"frame".to_string() => Value::from("rows"),
+				"query".to_string() => Value::from(query),
+				"rows".to_string() => Value::from(rows),
+			})),
+			Self::Done {
+				query,
+				response,
+				rows,
+			} => {
+				let streamed = rows.is_some() && response.result.is_ok();
+				let Value::Object(mut obj) = expr::to_value(response)? else {
+					return Err(anyhow::Error::new(Error::unreachable(
+						"A response was not serialised as an object",
+					)));
+				};
+				// The rows were already sent in the previous frames
+				if streamed {
+					obj.remove("result");
+				}
+				obj.insert("frame".to_string(), Value::from("done"));
+				obj.insert("query".to_string(), Value::from(query));
+				if let Some(rows) = rows {
+					obj.insert("rows".to_string(), Value::from(rows));
+					obj.insert("partial".to_string(), Value::from(partial));
+				}
+				Ok(Value::Object(obj))
+			}
+		}
+	}
+}
+
+/// The stream of the rows of a statement, which is
+/// attached to the context of the statement
+#[derive(Debug)]
+pub(crate) struct RowStream {
+	/// The position of the statement in the query
+	query: usize,
+	/// The channel which the frames are sent to
+	sender: Sender<QueryFrame>,
+	/// Whether a SELECT statement is sending its rows
+	claimed: AtomicBool,
+	/// The number of rows which were sent
+	sent: AtomicUsize,
+}
+
+impl RowStream {
+	pub(crate) fn new(query: usize, sender: Sender<QueryFrame>) -> Self {
+		Self {
+			query,
+			sender,
+			claimed: AtomicBool::new(false),
+			sent: AtomicUsize::new(0),
+		}
+	}
+
+	/// Claims the stream for the rows of the outermost SELECT statement,
+	/// returning false if it was already claimed, such as for a subquery
+	pub(crate) fn claim(&self) -> bool {
+		!self.claimed.swap(true, Ordering::AcqRel)
+	}
+
+	/// Checks if the rows of a statement were sent to the stream
+	pub(crate) fn is_claimed(&self) -> bool {
+		self.claimed.load(Ordering::Acquire)
+	}
+
+	/// The number of rows which were sent
+	pub(crate) fn sent(&self) -> usize {
+		self.sent.load(Ordering::Acquire)
+	}
+
+	/// Sends a batch of rows, waiting while the client has not received the previous ones
+	pub(crate) async fn send(&self, rows: Vec<Value>) -> Result<()> {
+		if rows.is_empty() {
+			return Ok(());
+		}
+		self.sent.fetch_add(rows.len(), Ordering::AcqRel);
+		let frame = QueryFrame::Rows {
+			query: self.query,
+			rows,
+		};
+		// The client stopped receiving the results
+		if self.sender.send(frame).await.is_err() {
+			return Err(anyhow::Error::new(Error::QueryCancelled));
+		}
+		Ok(())
+	}
+
+	/// Sends the output of a statement in batches of rows
+	pub(crate) async fn send_all(&self, output: Value) -> Result<()> {
+		let mut rows = match output {
+			Value::Array(v) => v.0.into_iter(),
+			Value::None => Vec::new().into_iter(),
+			v => vec![v].into_iter(),
+		};
+		while rows.len() > 0 {
+			self.send(rows.by_ref().take(*STREAM_BATCH_SIZE).collect()).await?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+	use crate::syn;
+
+	fn value(sql: &str) -> Value {
+		syn::value(sql).unwrap().into()
+	}
+
+	async fn frames(ds: &Datastore, sql: &str) -> Vec<QueryFrame> {
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let (send, recv) = async_channel::bounded(4);
+		let (res, frames) = futures::join!(ds.execute_streamed(sql, &sess, None, send), async {
+			let mut frames = Vec::new();
+			while let Ok(frame) = recv.recv().await {
+				frames.push(frame);
+			}
+			frames
+		});
+		res.unwrap();
+		frames
+	}
+
+	fn rows(frames: &[QueryFrame], query: usize) -> Vec<Value> {
+		frames
+			.iter()
+			.filter_map(|f| match f {
+				QueryFrame::Rows {
+					query: q,
+					rows,
+				} if *q == query => Some(rows.clone()),
+				_ => None,
+			})
+			.flatten()
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn select_rows_are_streamed_in_batches() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sql = format!(
+			"
+			FOR $i IN 0..{total} {{ CREATE person:[$i] SET age = $i % 10 }};
+			SELECT VALUE age FROM person;
+			SELECT VALUE age FROM person LIMIT 5 START 2;
+			SELECT VALUE id FROM person ORDER BY age DESC, id LIMIT 3;
+			SELECT age, count() AS total FROM person GROUP BY age;
+			SELECT * FROM ONLY person:[0];
+			",
+			total = *STREAM_BATCH_SIZE * 2 + 10
+		);
+		let frames = frames(&ds, &sql).await;
+		// The rows of the first SELECT are sent in several batches
+		let batches = frames
+			.iter()
+			.filter(|f| {
+				matches!(
+					f,
+					QueryFrame::Rows {
+						query: 1,
+						..
+					}
+				)
+			})
+			.count();
+		assert_eq!(batches, 3);
+		assert_eq!(rows(&frames, 1).len(), *STREAM_BATCH_SIZE * 2 + 10);
+		// The limits are applied to the streamed rows
+		assert_eq!(rows(&frames, 2).len(), 5);
+		// The sorted and grouped output is streamed once it is complete
+		assert_eq!(
+			rows(&frames, 3),
+			vec![value("person:[9]"), value("person:[19]"), value("person:[29]")]
+		);
+		assert_eq!(rows(&frames, 4).len(), 10);
+		// Every statement ends with a frame, which doesn't repeat the rows
+		let done: Vec<_> = frames
+			.into_iter()
+			.filter_map(|f| match f {
+				QueryFrame::Done {
+					query,
+					response,
+					rows,
+				} => Some((query, response, rows)),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(done.len(), 6);
+		assert_eq!(done[0].2, None);
+		assert_eq!(done[1].2, Some(*STREAM_BATCH_SIZE * 2 + 10));
+		assert_eq!(done[2].2, Some(5));
+		// The result of ONLY is returned in the final frame
+		assert_eq!(done[5].2, None);
+		assert_eq!(done[5].1.result.as_ref().unwrap(), &value("{ age: 0, id: person:[0] }"));
+		for (i, (query, response, _)) in done.into_iter().enumerate() {
+			assert_eq!(query, i);
+			assert!(response.result.is_ok());
+		}
+	}
+
+	#[tokio::test]
+	async fn errors_after_rows_are_partial_results() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sql = format!(
+			"
+			FOR $i IN 0..{total} {{ CREATE type::thing('person', $i) SET age = $i }};
+			SELECT VALUE IF age < {total} - 1 {{ age }} ELSE {{ THROW 'failed' }} FROM person;
+			",
+			total = *STREAM_BATCH_SIZE + 10
+		);
+		let frames = frames(&ds, &sql).await;
+		assert_eq!(rows(&frames, 1).len(), *STREAM_BATCH_SIZE);
+		let last = frames.into_iter().last().unwrap();
+		assert!(last.is_partial());
+		let value = last.into_value().unwrap();
+		assert_eq!(value.pick(&["status".into()]), Value::from("ERR"));
+		assert_eq!(value.pick(&["partial".into()]), Value::from(true));
+		assert_eq!(value.pick(&["rows".into()]), Value::from(*STREAM_BATCH_SIZE));
+	}
+}
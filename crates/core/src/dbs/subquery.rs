@@ -0,0 +1,111 @@
+//! The results of the subqueries which are computed once per statement.
+//!
+//! A SELECT subquery which does not refer to the document of the statement
+//! which contains it, to any parameter which can change, or to any function
+//! whose result varies between calls, returns the same result for every row
+//! of a statement which does not modify any data. The result is kept for the
+//! duration of the statement, so that the subquery is only computed once.
+use crate::dbs::Options;
+use crate::expr::{Subquery, Value};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// The options which change the result of a subquery
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+	perms: bool,
+	tenant: bool,
+	version: Option<u64>,
+	subquery: Subquery,
+}
+
+impl CacheKey {
+	fn new(opt: &Options, subquery: &Subquery) -> Self {
+		Self {
+			perms: opt.perms,
+			tenant: opt.tenant,
+			version: opt.version,
+			subquery: subquery.clone(),
+		}
+	}
+}
+
+/// The results of the uncorrelated subqueries of a statement
+#[derive(Debug, Default)]
+pub(crate) struct SubqueryCache(Mutex<HashMap<CacheKey, Value>>);
+
+impl SubqueryCache {
+	/// Gets the result of a subquery, if it was already computed
+	pub(crate) fn get(&self, opt: &Options, subquery: &Subquery) -> Option<Value> {
+		self.0.lock().get(&CacheKey::new(opt, subquery)).cloned()
+	}
+
+	/// Keeps the result of a subquery for the rest of the statement
+	pub(crate) fn insert(&self, opt: &Options, subquery: &Subquery, value: Value) {
+		self.0.lock().insert(CacheKey::new(opt, subquery), value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+	use crate::syn;
+
+	#[test]
+	fn uncorrelated_subqueries() {
+		let check = |sql: &str| {
+			let Value::Subquery(s) = Value::from(syn::value(sql).unwrap()) else {
+				panic!("{sql} is not a subquery")
+			};
+			s.is_uncorrelated()
+		};
+		assert!(check("(SELECT VALUE rate FROM config:main)"));
+		assert!(check("(SELECT * FROM person WHERE age > 18 ORDER BY name LIMIT 10)"));
+		assert!(check("(SELECT count() FROM person WHERE $auth.id = owner GROUP ALL)"));
+		assert!(check("(SELECT * FROM person WHERE id IN (SELECT VALUE id FROM admin))"));
+		// The outer document
+		assert!(!check("(SELECT * FROM person WHERE age > $parent.age)"));
+		assert!(!check("(SELECT * FROM $this.friends)"));
+		assert!(!check("(SELECT * FROM person LIMIT n)"));
+		assert!(!check("(SELECT * FROM ->knows->person)"));
+		// The parameters which can change
+		assert!(!check("(SELECT * FROM person WHERE age > $age)"));
+		// The functions whose results vary
+		assert!(!check("(SELECT * FROM person WHERE rand::bool())"));
+		assert!(!check("(SELECT * FROM person WHERE created < time::now())"));
+		assert!(!check("(SELECT * FROM person WHERE fn::check(id))"));
+		assert!(!check("(SELECT * FROM person ORDER BY RAND())"));
+		// The statements which are not SELECT statements
+		assert!(!check("(CREATE person)"));
+	}
+
+	#[tokio::test]
+	async fn uncorrelated_subqueries_are_computed_once() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			CREATE config:main SET rate = 2;
+			FOR $i IN 0..10000 { CREATE type::thing('item', $i) SET price = $i };
+		";
+		ds.execute(sql, &sess, None).await.unwrap();
+		let sess = sess.with_stats(true);
+		// The records of the subquery are scanned once, along with the items
+		let sql = "SELECT VALUE price * (SELECT VALUE rate FROM ONLY config:main) FROM item";
+		let mut res = ds.execute(sql, &sess, None).await.unwrap();
+		assert_eq!(res[0].stats.unwrap().scanned, 10_001);
+		let res = res.remove(0).result.unwrap();
+		assert_eq!(res.first(), Value::from(0));
+		assert_eq!(res.last(), Value::from(19_998));
+		// A correlated subquery is computed for every item
+		let sql =
+			"SELECT (SELECT VALUE rate FROM config WHERE rate < $parent.price) AS rate FROM item";
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert_eq!(res[0].stats.unwrap().scanned, 20_000);
+		// The subqueries are computed again when the statement modifies data
+		let sql = "UPDATE item SET cost = price * (SELECT VALUE rate FROM ONLY config:main)";
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert_eq!(res[0].stats.unwrap().scanned, 20_000);
+	}
+}
@@ -0,0 +1,263 @@
+//! Computes the window functions of a SELECT statement, once all of the output rows are known.
+//!
+//! Window functions are computed after any GROUP BY and HAVING clauses have been processed,
+//! and before the output rows are sorted by any ORDER BY clause. Without a GROUP BY clause
+//! the arguments, partitions, and ordering of each window are computed from the source
+//! records. With a GROUP BY clause they are computed from the grouped output rows instead,
+//! so they can refer to the grouped fields and to the aliases of aggregate functions.
+
+use crate::ctx::Context;
+use crate::dbs::{Options, Statement};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::order::OrderList;
+use crate::expr::{Array, Field, FlowResultExt as _, Function, Value};
+use anyhow::{Result, bail, ensure};
+use reblessive::tree::Stk;
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::BTreeMap;
+
+/// The values computed for a single row of a window
+struct Row {
+	/// The computed arguments of the window function
+	args: Vec<Value>,
+	/// The values which identify the partition of the row
+	partition: Array,
+	/// The values which the row is ordered by within its partition
+	order: Vec<Value>,
+}
+
+impl TryFrom<Value> for Row {
+	type Error = anyhow::Error;
+	fn try_from(val: Value) -> Result<Self> {
+		let Value::Array(Array(v)) = val else {
+			fail!("Expected the computed inputs of a window function, found: {val}");
+		};
+		match <[Value; 3]>::try_from(v) {
+			Ok([Value::Array(args), Value::Array(partition), Value::Array(order)]) => Ok(Self {
+				args: args.0,
+				partition,
+				order: order.0,
+			}),
+			_ => fail!("Expected the computed inputs of a window function"),
+		}
+	}
+}
+
+/// The functions which can be computed over a window
+enum Kind<'a> {
+	RowNumber,
+	Rank,
+	DenseRank,
+	Lag,
+	Lead,
+	Aggregate(&'a Function),
+}
+
+impl<'a> Kind<'a> {
+	fn new(func: &'a Function) -> Result<Self> {
+		let Function::Normal(name, args) = func else {
+			bail!(Error::InvalidFunction {
+				name: func.to_idiom().to_string(),
+				message: String::from("Only builtin functions can be used with an OVER clause"),
+			});
+		};
+		let (kind, max) = match name.as_str() {
+			"row_number" => (Self::RowNumber, 0),
+			"rank" => (Self::Rank, 0),
+			"dense_rank" => (Self::DenseRank, 0),
+			"lag" => (Self::Lag, 3),
+			"lead" => (Self::Lead, 3),
+			_ if func.is_aggregate() => return Ok(Self::Aggregate(func)),
+			_ => bail!(Error::InvalidFunction {
+				name: name.clone(),
+				message: String::from(
+					"This function can not be used with an OVER clause, only aggregate and window functions can be"
+				),
+			}),
+		};
+		ensure!(
+			args.len() <= max && (max == 0 || !args.is_empty()),
+			Error::InvalidArguments {
+				name: name.clone(),
+				message: match max {
+					0 => String::from("The function expects 0 arguments."),
+					_ => format!("The function expects 1 to {max} arguments."),
+				},
+			}
+		);
+		Ok(kind)
+	}
+}
+
+/// Compute each window function field, replacing the stored inputs of every row with the result
+pub(super) async fn compute(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	stm: &Statement<'_>,
+	rows: &mut [Value],
+) -> Result<()> {
+	let Some(fields) = stm.expr() else {
+		return Ok(());
+	};
+	let single = fields.single().is_some();
+	for field in fields.iter() {
+		let Field::Window {
+			expr,
+			window,
+			alias,
+		} = field
+		else {
+			continue;
+		};
+		let Value::Function(func) = expr else {
+			fail!("Expected a function call for a window field: {expr}");
+		};
+		// Check this function is allowed
+		if let Some(name) = func.name() {
+			ctx.check_allowed_function(opt, name).await?;
+		}
+		let kind = Kind::new(func)?;
+		let name = alias.as_ref().map(Cow::Borrowed).unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+		// Fetch the inputs of each row
+		let mut inputs = Vec::with_capacity(rows.len());
+		for row in rows.iter() {
+			let input = match stm.group() {
+				// Grouped rows are computed from the grouped output
+				Some(_) => {
+					let doc = CursorDoc::new(None, None, row.clone());
+					window.input(stk, ctx, opt, &doc, func).await?
+				}
+				// Other rows were computed from the source record
+				None if single => row.clone(),
+				None => row.pick(&name),
+			};
+			inputs.push(Row::try_from(input)?);
+		}
+		// Split the rows into their partitions
+		let mut partitions: BTreeMap<&Array, Vec<usize>> = BTreeMap::new();
+		for (i, row) in inputs.iter().enumerate() {
+			partitions.entry(&row.partition).or_default().push(i);
+		}
+		// Compute the function within each partition
+		let mut results = vec![Value::None; rows.len()];
+		for mut idx in partitions.into_values() {
+			if let Some(orders) = &window.order {
+				// Rows which are equal keep their original order
+				idx.sort_by(|a, b| compare(orders, &inputs[*a].order, &inputs[*b].order));
+			}
+			apply(stk, ctx, opt, &kind, window.order.as_ref(), &inputs, &idx, &mut results).await?;
+		}
+		// Replace the stored inputs with the results
+		for (row, val) in rows.iter_mut().zip(results) {
+			match single {
+				true => *row = val,
+				false => row.put(&name, val),
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Compare the ordering values of two rows within a partition
+fn compare(orders: &OrderList, a: &[Value], b: &[Value]) -> cmp::Ordering {
+	for (order, (a, b)) in orders.iter().zip(a.iter().zip(b)) {
+		// Place any NONE and NULL values at the requested end
+		if let Some(o) = order.compare_nulls(a, b) {
+			return o;
+		}
+		// Reverse the ordering if DESC
+		let o = match order.direction {
+			true => a.compare(b, &[], order.collate, order.numeric),
+			false => b.compare(a, &[], order.collate, order.numeric),
+		};
+		match o {
+			Some(cmp::Ordering::Equal) | None => continue,
+			Some(o) => return o,
+		}
+	}
+	cmp::Ordering::Equal
+}
+
+/// Compute the function for the sorted rows of a single partition
+#[expect(clippy::too_many_arguments)]
+async fn apply(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	kind: &Kind<'_>,
+	orders: Option<&OrderList>,
+	inputs: &[Row],
+	idx: &[usize],
+	results: &mut [Value],
+) -> Result<()> {
+	// Rows are peers when they are equal in the window ordering,
+	// and every row is a peer when the window has no ordering
+	let peers = |a: usize, b: usize| {
+		orders.is_none_or(|o| compare(o, &inputs[a].order, &inputs[b].order).is_eq())
+	};
+	match kind {
+		Kind::RowNumber => {
+			for (pos, &i) in idx.iter().enumerate() {
+				results[i] = Value::from(pos as i64 + 1);
+			}
+		}
+		Kind::Rank | Kind::DenseRank => {
+			let mut rank = 0;
+			for (pos, &i) in idx.iter().enumerate() {
+				if pos == 0 || !peers(idx[pos - 1], i) {
+					rank = match kind {
+						Kind::Rank => pos as i64 + 1,
+						_ => rank + 1,
+					};
+				}
+				results[i] = Value::from(rank);
+			}
+		}
+		Kind::Lag | Kind::Lead => {
+			for (pos, &i) in idx.iter().enumerate() {
+				let args = &inputs[i].args;
+				let offset = match args.get(1) {
+					Some(v) if !v.is_none_or_null() => v.clone().coerce_to::<i64>()?,
+					_ => 1,
+				};
+				let target = match kind {
+					Kind::Lag => (pos as i64).checked_sub(offset),
+					_ => (pos as i64).checked_add(offset),
+				};
+				results[i] = match target.and_then(|t| usize::try_from(t).ok()) {
+					Some(t) if t < idx.len() => inputs[idx[t]].args[0].clone(),
+					_ => args.get(2).cloned().unwrap_or_default(),
+				};
+			}
+		}
+		Kind::Aggregate(func) => {
+			// The frame of each row runs from the start of the
+			// partition up to and including the last of its peers
+			let mut values = Vec::with_capacity(idx.len());
+			let mut pos = 0;
+			while pos < idx.len() {
+				let mut end = pos + 1;
+				while end < idx.len() && peers(idx[pos], idx[end]) {
+					end += 1;
+				}
+				for &i in &idx[pos..end] {
+					// A function without arguments counts every row
+					values.push(inputs[i].args.first().cloned().unwrap_or(Value::Bool(true)));
+				}
+				let val = func
+					.aggregate(Value::from(values.clone()))?
+					.compute(stk, ctx, opt, None)
+					.await
+					.catch_return()?;
+				for &i in &idx[pos..end] {
+					results[i] = val.clone();
+				}
+				pos = end;
+			}
+		}
+	}
+	Ok(())
+}
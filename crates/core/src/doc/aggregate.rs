@@ -0,0 +1,515 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::permission::Permission;
+use crate::expr::statements::{DefineTableStatement, LiveStatement};
+use crate::expr::value::{TryAdd, TrySub};
+use crate::expr::{Array, Field, FlowResultExt as _, Group, Id, Idiom, Thing, Value};
+use crate::iam::Action;
+use crate::kvs::{KeyDecode as _, Transaction};
+use anyhow::Result;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The number of shards which the aggregate state of each group is split
+/// across, so that records which are written concurrently rarely conflict
+const SHARDS: u32 = 16;
+
+/// An aggregate function which can be maintained
+/// incrementally by a LIVE SELECT query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Aggregate {
+	/// The `count()` function
+	Count,
+	/// The `count(value)` function
+	CountIf,
+	/// The `math::sum(value)` function
+	MathSum,
+	/// The `math::min(value)` function
+	MathMin,
+	/// The `math::max(value)` function
+	MathMax,
+	/// The `time::min(value)` function
+	TimeMin,
+	/// The `time::max(value)` function
+	TimeMax,
+}
+
+impl Aggregate {
+	/// The initial value of this aggregate, for a group without any records
+	fn initial(&self) -> Value {
+		match self {
+			Self::Count | Self::CountIf | Self::MathSum => 0.into(),
+			_ => Value::None,
+		}
+	}
+	/// Check if a value is taken into account by this aggregate
+	fn accepts(&self, val: &Value) -> bool {
+		match self {
+			Self::Count => true,
+			Self::CountIf => val.is_truthy(),
+			Self::MathSum | Self::MathMin | Self::MathMax => val.is_number(),
+			Self::TimeMin | Self::TimeMax => val.is_datetime(),
+		}
+	}
+	/// Check if this aggregate is kept as the ordered values of the group,
+	/// instead of in the state of the group
+	fn is_ordered(&self) -> bool {
+		!matches!(self, Self::Count | Self::CountIf | Self::MathSum)
+	}
+	/// Check if this aggregate is the first of the ordered values of the group
+	fn is_min(&self) -> bool {
+		matches!(self, Self::MathMin | Self::TimeMin)
+	}
+	/// The position of a value within the ordered values of the group
+	fn order(val: &Value) -> Option<(u64, u32)> {
+		match val {
+			Value::Number(v) => {
+				let v = v.to_float();
+				if v.is_nan() {
+					return None;
+				}
+				// Flip the bits of the float so that it sorts as an integer
+				let b = v.to_bits();
+				match b >> 63 {
+					1 => Some((!b, 0)),
+					_ => Some((b | 1 << 63, 0)),
+				}
+			}
+			Value::Datetime(v) => {
+				let secs = (v.0.timestamp() as u64) ^ 1 << 63;
+				Some((secs, v.0.timestamp_subsec_nanos()))
+			}
+			_ => None,
+		}
+	}
+}
+
+/// The aggregate state of a single group of a LIVE SELECT query, or of a
+/// single shard of the group. The minimum and maximum values are only
+/// present in the state of the whole group.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AggregateState {
+	/// The number of records in this group
+	pub rows: u64,
+	/// The current value of each aggregate field
+	pub values: Vec<Value>,
+}
+
+impl AggregateState {
+	/// Create the state of a group without any records
+	fn new(aggs: &[Aggregate]) -> Self {
+		Self {
+			rows: 0,
+			values: aggs.iter().map(Aggregate::initial).collect(),
+		}
+	}
+	/// Add the values of a record to this group
+	fn add(&mut self, aggs: &[Aggregate], vals: &[Value]) -> Result<()> {
+		self.rows += 1;
+		for ((agg, acc), val) in aggs.iter().zip(self.values.iter_mut()).zip(vals) {
+			if !agg.accepts(val) {
+				continue;
+			}
+			match agg {
+				Aggregate::Count | Aggregate::CountIf => *acc = acc.clone().try_add(1.into())?,
+				Aggregate::MathSum => *acc = acc.clone().try_add(val.clone())?,
+				_ => (),
+			}
+		}
+		Ok(())
+	}
+	/// Remove the values of a record from this group
+	fn sub(&mut self, aggs: &[Aggregate], vals: &[Value]) -> Result<()> {
+		self.rows = self.rows.saturating_sub(1);
+		// The last record was removed from this group
+		if self.rows == 0 {
+			*self = Self::new(aggs);
+			return Ok(());
+		}
+		for ((agg, acc), val) in aggs.iter().zip(self.values.iter_mut()).zip(vals) {
+			if !agg.accepts(val) {
+				continue;
+			}
+			match agg {
+				Aggregate::Count | Aggregate::CountIf => *acc = acc.clone().try_sub(1.into())?,
+				Aggregate::MathSum => *acc = acc.clone().try_sub(val.clone())?,
+				_ => (),
+			}
+		}
+		Ok(())
+	}
+	/// Merge the state of a shard into the state of this group
+	fn merge(&mut self, aggs: &[Aggregate], shard: &AggregateState) -> Result<()> {
+		self.rows += shard.rows;
+		for ((agg, acc), val) in aggs.iter().zip(self.values.iter_mut()).zip(&shard.values) {
+			if !agg.is_ordered() {
+				*acc = acc.clone().try_add(val.clone())?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl LiveStatement {
+	/// Checks that the fields of this LIVE SELECT query can be aggregated
+	/// incrementally, returning the aggregate function for each field, or
+	/// `None` for each field which is present in the GROUP clause.
+	fn aggregates(&self) -> Result<Vec<(Idiom, Option<Aggregate>)>> {
+		let Some(groups) = &self.group else {
+			fail!("Expected a GROUP clause for an aggregate live query");
+		};
+		let invalid = |message: String| {
+			anyhow::Error::new(Error::LiveStatementGroup {
+				message,
+			})
+		};
+		if self.expr.0.is_empty() {
+			return Err(invalid("DIFF can not be used".to_owned()));
+		}
+		if self.expr.1 {
+			return Err(invalid("VALUE can not be used".to_owned()));
+		}
+		if self.fetch.is_some() {
+			return Err(invalid("FETCH can not be used".to_owned()));
+		}
+		if let Some(group) = groups.iter().find(|g| g.1.is_some()) {
+			return Err(invalid(format!("the collation of '{group}' can not be used")));
+		}
+		let mut out = Vec::with_capacity(self.expr.0.len());
+		for field in self.expr.0.iter() {
+			let Field::Single {
+				expr,
+				alias,
+			} = field
+			else {
+				return Err(invalid(format!("'{field}' can not be used")));
+			};
+			let name = alias.clone().unwrap_or_else(|| expr.to_idiom());
+			let agg = match expr {
+				Value::Function(f) if f.is_aggregate() => {
+					let agg = match (f.name(), f.args().len()) {
+						(Some("count"), 0) => Aggregate::Count,
+						(Some("count"), 1) => Aggregate::CountIf,
+						(Some("math::sum"), 1) => Aggregate::MathSum,
+						(Some("math::min"), 1) => Aggregate::MathMin,
+						(Some("math::max"), 1) => Aggregate::MathMax,
+						(Some("time::min"), 1) => Aggregate::TimeMin,
+						(Some("time::max"), 1) => Aggregate::TimeMax,
+						_ => {
+							return Err(invalid(format!(
+								"the aggregate function '{expr}' is not supported"
+							)));
+						}
+					};
+					Some(agg)
+				}
+				_ if groups.iter().any(|g| g.0 == name) => None,
+				_ => {
+					return Err(invalid(format!(
+						"'{field}' is not an aggregate function, and is not present in the GROUP clause"
+					)));
+				}
+			};
+			out.push((name, agg));
+		}
+		Ok(out)
+	}
+
+	/// Checks that this LIVE SELECT query can be aggregated incrementally
+	pub(crate) fn check_aggregate(&self) -> Result<()> {
+		self.aggregates().map(|_| ())
+	}
+
+	/// Computes the group key, and the aggregate
+	/// function inputs, for a single record.
+	async fn aggregate_input(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<(Array, Vec<Value>)> {
+		let aggs = self.aggregates()?;
+		// Compute the aggregate function inputs for this record
+		let out = self.expr.compute(stk, ctx, opt, Some(doc), true).await?;
+		// Compute the group key for this record
+		let group = match &self.group {
+			Some(groups) => groups.iter().map(|g| out.pick(g)).collect::<Vec<_>>().into(),
+			None => Array::new(),
+		};
+		// Fetch the input for each aggregate field
+		let vals = aggs.iter().filter(|(_, agg)| agg.is_some()).map(|(i, _)| out.pick(i)).collect();
+		Ok((group, vals))
+	}
+
+	/// Checks if a record is matched by the WHERE clause of this LIVE
+	/// SELECT query, and can be viewed by the user who created it.
+	async fn aggregate_allow(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &DefineTableStatement,
+		doc: &CursorDoc,
+	) -> Result<bool> {
+		// Check where condition
+		if let Some(cond) = &self.cond {
+			if !cond.compute(stk, ctx, opt, Some(doc)).await.catch_return()?.is_truthy() {
+				return Ok(false);
+			}
+		}
+		// Should we run permissions checks?
+		if opt.check_perms(Action::View)? {
+			match &tb.permissions.select {
+				Permission::None => return Ok(false),
+				Permission::Full => return Ok(true),
+				Permission::Specific(e) => {
+					// Disable permissions
+					let opt = &opt.new_with_perms(false);
+					// Process the PERMISSION clause
+					return Ok(e
+						.compute(stk, ctx, opt, Some(doc))
+						.await
+						.catch_return()
+						.is_ok_and(|x| x.is_truthy()));
+				}
+			}
+		}
+		Ok(true)
+	}
+
+	/// Computes the group key, and the aggregate function inputs, for a
+	/// single record, if the record is part of this LIVE SELECT query.
+	pub(crate) async fn aggregate_record(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &DefineTableStatement,
+		doc: &CursorDoc,
+	) -> Result<Option<(Array, Vec<Value>)>> {
+		if !self.aggregate_allow(stk, ctx, opt, tb, doc).await? {
+			return Ok(None);
+		}
+		self.aggregate_input(stk, ctx, opt, doc).await.map(Some)
+	}
+
+	/// Stores the aggregate state of every group of this LIVE SELECT
+	/// query, by scanning all of the records in the table.
+	pub(crate) async fn aggregate_init(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &str,
+	) -> Result<()> {
+		let aggs = self.aggregate_kinds()?;
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		let def = txn.get_tb(ns, db, tb).await?;
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		let mut states = BTreeMap::new();
+		while let Some(rng) = next {
+			// Fetch the next batch of keys and values
+			let res = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				// Decode the record
+				let key = crate::key::thing::Thing::decode(k)?;
+				let mut val: Value = revision::from_slice(v)?;
+				let rid = Arc::new(Thing::from((key.tb, key.id)));
+				// Inject the id field into the document
+				val.def(&rid);
+				let doc = CursorDoc::new(Some(rid.clone()), None, val);
+				// Check if this record is part of the aggregate
+				let Some((group, vals)) = self.aggregate_record(stk, ctx, opt, &def, &doc).await?
+				else {
+					continue;
+				};
+				// Store the ordered values of the record
+				self.aggregate_order(&txn, ns, db, tb, &group, &rid.id, &vals, true).await?;
+				// Add the record to the state of its shard
+				states
+					.entry((group, Self::aggregate_shard(&rid)))
+					.or_insert_with(|| AggregateState::new(&aggs))
+					.add(&aggs, &vals)?;
+			}
+			// Pause and yield execution
+			yield_now!();
+		}
+		// Store the state of each shard
+		for ((group, shard), state) in states {
+			let key = crate::key::table::la::new(ns, db, tb, self.id.0, &group, shard);
+			txn.replace(key, revision::to_vec(&state)?).await?;
+		}
+		Ok(())
+	}
+
+	/// Fetches the aggregate state of a group of this LIVE SELECT query, by
+	/// merging the state of each shard of the group, and by reading the
+	/// first or the last of the ordered values for the `min` and `max` fields
+	pub(crate) async fn aggregate_load(
+		&self,
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		group: &Array,
+	) -> Result<AggregateState> {
+		let aggs = self.aggregate_kinds()?;
+		let mut state = AggregateState::new(&aggs);
+		// Merge the state of each shard of the group
+		let beg = crate::key::table::la::group_prefix(ns, db, tb, self.id.0, group)?;
+		let end = crate::key::table::la::group_suffix(ns, db, tb, self.id.0, group)?;
+		for (_, v) in txn.getr(beg..end, None).await? {
+			let shard: AggregateState = revision::from_slice(&v)?;
+			state.merge(&aggs, &shard)?;
+		}
+		if state.rows == 0 {
+			return Ok(state);
+		}
+		// Fetch the minimum or the maximum value of the group
+		for (i, agg) in aggs.iter().enumerate().filter(|(_, agg)| agg.is_ordered()) {
+			let beg = crate::key::table::lm::field_prefix(ns, db, tb, self.id.0, group, i as u16)?;
+			let end = crate::key::table::lm::field_suffix(ns, db, tb, self.id.0, group, i as u16)?;
+			let res = match agg.is_min() {
+				true => txn.scan(beg..end, 1, None).await?,
+				false => txn.scanr(beg..end, 1, None).await?,
+			};
+			if let Some((_, v)) = res.into_iter().next() {
+				state.values[i] = revision::from_slice(&v)?;
+			}
+		}
+		Ok(state)
+	}
+
+	/// Adds the inputs of a record to the state of a group
+	#[expect(clippy::too_many_arguments)]
+	pub(crate) async fn aggregate_insert(
+		&self,
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		group: &Array,
+		rid: &Thing,
+		vals: &[Value],
+	) -> Result<()> {
+		let aggs = self.aggregate_kinds()?;
+		let shard = Self::aggregate_shard(rid);
+		let key = crate::key::table::la::new(ns, db, tb, self.id.0, group, shard);
+		let mut state = match txn.get(key.clone(), None).await? {
+			Some(v) => revision::from_slice(&v)?,
+			None => AggregateState::new(&aggs),
+		};
+		state.add(&aggs, vals)?;
+		txn.replace(key, revision::to_vec(&state)?).await?;
+		self.aggregate_order(txn, ns, db, tb, group, &rid.id, vals, true).await
+	}
+
+	/// Removes the inputs of a record from the state of a group
+	#[expect(clippy::too_many_arguments)]
+	pub(crate) async fn aggregate_remove(
+		&self,
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		group: &Array,
+		rid: &Thing,
+		vals: &[Value],
+	) -> Result<()> {
+		let aggs = self.aggregate_kinds()?;
+		let shard = Self::aggregate_shard(rid);
+		let key = crate::key::table::la::new(ns, db, tb, self.id.0, group, shard);
+		if let Some(v) = txn.get(key.clone(), None).await? {
+			let mut state: AggregateState = revision::from_slice(&v)?;
+			state.sub(&aggs, vals)?;
+			match state.rows {
+				0 => txn.del(key).await?,
+				_ => txn.replace(key, revision::to_vec(&state)?).await?,
+			}
+		}
+		self.aggregate_order(txn, ns, db, tb, group, &rid.id, vals, false).await
+	}
+
+	/// Adds or removes the values of a record for the `min` and `max` fields
+	#[expect(clippy::too_many_arguments)]
+	async fn aggregate_order(
+		&self,
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		group: &Array,
+		id: &Id,
+		vals: &[Value],
+		add: bool,
+	) -> Result<()> {
+		let aggs = self.aggregate_kinds()?;
+		for (i, (agg, val)) in aggs.iter().zip(vals).enumerate() {
+			if !agg.is_ordered() || !agg.accepts(val) {
+				continue;
+			}
+			let Some(ord) = Aggregate::order(val) else {
+				continue;
+			};
+			let key = crate::key::table::lm::new(ns, db, tb, self.id.0, group, i as u16, ord, id);
+			match add {
+				true => txn.replace(key, revision::to_vec(val)?).await?,
+				false => txn.del(key).await?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the shard of a group which holds the state of a record
+	fn aggregate_shard(rid: &Thing) -> u16 {
+		// A stable hash, so that every node picks the same shard
+		let hash = rid
+			.to_string()
+			.bytes()
+			.fold(0x811c9dc5u32, |h, b| (h ^ b as u32).wrapping_mul(0x01000193));
+		(hash % SHARDS) as u16
+	}
+
+	/// Returns the state of a group without any records
+	pub(crate) fn aggregate_empty(&self) -> Result<AggregateState> {
+		Ok(AggregateState::new(&self.aggregate_kinds()?))
+	}
+
+	/// Computes the output of this LIVE SELECT query for a single group
+	pub(crate) fn aggregate_output(&self, group: &Array, state: &AggregateState) -> Result<Value> {
+		let aggs = self.aggregates()?;
+		let groups: &[Group] = self.group.as_ref().map(|g| g.0.as_slice()).unwrap_or_default();
+		let mut out = Value::base();
+		let mut vals = state.values.iter();
+		for (name, agg) in aggs.iter() {
+			let val = match agg {
+				Some(_) => vals.next().cloned().unwrap_or_default(),
+				None => groups
+					.iter()
+					.position(|g| &g.0 == name)
+					.and_then(|i| group.get(i))
+					.cloned()
+					.unwrap_or_default(),
+			};
+			out.put(name, val);
+		}
+		Ok(out)
+	}
+
+	/// Returns the aggregate function of each aggregate field
+	fn aggregate_kinds(&self) -> Result<Vec<Aggregate>> {
+		Ok(self.aggregates()?.into_iter().filter_map(|(_, agg)| agg).collect())
+	}
+}
@@ -1,7 +1,9 @@
+use crate::cf::ChangeAudit;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
+use crate::expr::paths::OR;
 use anyhow::Result;
 
 impl Document {
@@ -9,7 +11,7 @@ impl Document {
 		&self,
 		ctx: &Context,
 		opt: &Options,
-		_stm: &Statement<'_>,
+		stm: &Statement<'_>,
 	) -> Result<()> {
 		// Check if changed
 		if !self.changed() {
@@ -29,6 +31,17 @@ impl Document {
 		if let Some(cf) = dbcf.or(tbcf) {
 			// Create the changefeed entry
 			if let Some(id) = &self.id {
+				// Collect who made the change, and from which statement
+				let audit = cf.store_audit.then(|| {
+					let identity = Some(opt.auth.id()).filter(|v| !v.is_empty());
+					let origin = ctx
+						.value("session")
+						.map(|s| s.pick(&*OR))
+						.filter(|v| v.is_some())
+						.map(|v| v.as_raw_string());
+					let (statement, fingerprint) = stm.fingerprint();
+					ChangeAudit::new(identity.map(str::to_owned), origin, statement, fingerprint)
+				});
 				ctx.tx().lock().await.record_change(
 					ns,
 					db,
@@ -37,6 +50,7 @@ impl Document {
 					self.initial.doc.clone(),
 					self.current.doc.clone(),
 					cf.store_diff,
+					audit,
 				);
 			}
 		}
@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::dbs::Workable;
+use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::doc::Permitted::*;
 use crate::err::Error;
@@ -349,6 +350,48 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+	/// Checks that the record is at the version which
+	/// is expected by an `IF VERSION` clause, so that a
+	/// record which has been changed since it was read
+	/// is not overwritten. Instead of ignoring a record
+	/// at a different version, a conflict error is
+	/// returned, and the statement fails. The table
+	/// must be defined as VERSIONED.
+	pub(super) async fn check_record_version(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), IgnoreError> {
+		// Check if an IF VERSION clause is specified
+		let Some(expected) = stm.if_version() else {
+			return Ok(());
+		};
+		// Get the record id
+		let rid = self.id()?;
+		// Check if the table keeps record versions
+		if !self.tb(ctx, opt).await?.versioned {
+			return Err(IgnoreError::from(anyhow::Error::new(Error::TbNotVersioned {
+				name: rid.tb.clone(),
+			})));
+		}
+		// Get the NS + DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the stored version of the record
+		let version = ctx.tx().get_record_version(ns, db, &rid.tb, &rid.id).await?;
+		// Process the expected version
+		let expected = expected.compute(stk, ctx, opt, Some(&self.current)).await.catch_return()?;
+		if expected != Value::from(version) {
+			return Err(IgnoreError::from(anyhow::Error::new(Error::VersionConflict {
+				thing: rid.as_ref().to_owned(),
+				expected: expected.to_string(),
+				version,
+			})));
+		}
+		// Carry on
+		Ok(())
+	}
 	/// Checks the `PERMISSIONS` clause for viewing a
 	/// record, based on the `select` permissions for
 	/// the table that this record belongs to. This
@@ -436,6 +479,14 @@ impl Document {
 	) -> Result<(), IgnoreError> {
 		// Check if this record exists
 		if self.id.is_some() {
+			// Check the tenant of the existing record
+			if !self.is_new() {
+				self.check_tenant(stk, ctx, opt, &self.initial).await?;
+			}
+			// Check the tenant of the modified record
+			if !stm.is_delete() {
+				self.check_tenant(stk, ctx, opt, &self.current).await?;
+			}
 			// Should we run permissions checks?
 			if opt.check_perms(stm.into())? {
 				// Check that record authentication matches session
@@ -488,4 +539,55 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+
+	/// Checks the `ASSERT` clause on the table for this
+	/// record, once the fields of the record have been
+	/// processed. The clause is checked when a record
+	/// is created or updated, but not when it is deleted.
+	pub(super) async fn check_table_assert(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<()> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
+		// Get the table for this document
+		let table = self.tb(ctx, opt).await?;
+		// Process the ASSERT clause
+		table.check_assert(stk, ctx, opt, &self.current).await
+	}
+
+	/// Checks the `TENANT` clause on the table for this
+	/// record, ensuring that the record belongs to the
+	/// tenant of the session. Unlike the table permissions,
+	/// the tenant is checked for every user, unless the
+	/// query is run with `OPTION TENANT = FALSE`.
+	pub(super) async fn check_tenant(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<(), IgnoreError> {
+		// Should we check the tenant of the record?
+		if !opt.check_tenant() {
+			return Ok(());
+		}
+		// Get the table for this document
+		let table = self.tb(ctx, opt).await?;
+		// Check the tenant of the record
+		if let Some(tenant) = &table.tenant {
+			// Disable permissions
+			let opt = &opt.new_with_perms(false);
+			// Process the TENANT clause
+			if !tenant.matches(stk, ctx, opt, doc).await? {
+				return Err(IgnoreError::Ignore);
+			}
+		}
+		// Carry on
+		Ok(())
+	}
 }
@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
+use crate::doc::Permitted;
 use crate::expr::value::Value;
 use reblessive::tree::Stk;
 
@@ -19,6 +20,10 @@ impl Document {
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_where_condition(stk, ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		// Keep the record if the table is soft delete
+		if self.tb(ctx, opt).await?.soft_delete {
+			return self.soft_delete(stk, ctx, opt, stm).await;
+		}
 		self.cleanup_table_references(stk, ctx, opt).await?;
 		self.clear_record_data();
 		self.store_index_data(stk, ctx, opt, stm).await?;
@@ -27,6 +32,7 @@ impl Document {
 		self.process_table_lives(stk, ctx, opt, stm).await?;
 		self.process_table_events(stk, ctx, opt, stm).await?;
 		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 }
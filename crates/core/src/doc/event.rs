@@ -30,29 +30,40 @@ impl Document {
 		}
 		// Don't run permissions
 		let opt = &opt.new_with_perms(false);
+		// Get the event action
+		let evt = if stm.is_delete() {
+			Value::from("DELETE")
+		} else if self.is_new() {
+			Value::from("CREATE")
+		} else {
+			Value::from("UPDATE")
+		};
+		// Process the events in their defined order
+		let evs = self.ev(ctx, opt).await?;
+		let mut evs = evs.iter().collect::<Vec<_>>();
+		evs.sort_by_key(|ev| ev.order.unwrap_or_default());
+		// The document state as written by any previous events
+		let before = self.initial.doc.as_arc();
+		let mut after = self.current.doc.as_arc();
 		// Loop through all event statements
-		for ev in self.ev(ctx, opt).await?.iter() {
-			// Get the event action
-			let evt = if stm.is_delete() {
-				Value::from("DELETE")
-			} else if self.is_new() {
-				Value::from("CREATE")
-			} else {
-				Value::from("UPDATE")
-			};
-			let after = self.current.doc.as_arc();
-			let before = self.initial.doc.as_arc();
+		for (i, ev) in evs.iter().enumerate() {
+			// Skip the event if the guard condition fails
+			if let Some(guard) = &ev.guard {
+				if !guard.compute(stk, ctx, opt, None).await.catch_return()?.is_truthy() {
+					continue;
+				}
+			}
 			// Depending on type of event, how do we populate the document
-			let doc = match stm.is_delete() {
-				true => &mut self.initial,
-				false => &mut self.current,
+			let (doc, value) = match stm.is_delete() {
+				true => (&mut self.initial, before.clone()),
+				false => (&mut self.current, after.clone()),
 			};
 			// Configure the context
 			let mut ctx = MutableContext::new(ctx);
-			ctx.add_value("event", evt.into());
-			ctx.add_value("value", doc.doc.as_arc());
-			ctx.add_value("after", after);
-			ctx.add_value("before", before);
+			ctx.add_value("event", evt.clone().into());
+			ctx.add_value("value", value);
+			ctx.add_value("after", after.clone());
+			ctx.add_value("before", before.clone());
 			// Freeze the context
 			let ctx = ctx.freeze();
 			// Process conditional clause
@@ -62,6 +73,13 @@ impl Document {
 				for v in ev.then.iter() {
 					v.compute(stk, &ctx, opt, Some(doc)).await.catch_return()?;
 				}
+				// Fetch any changes made to the record for the following events
+				if let Some(rid) =
+					self.id.as_ref().filter(|_| !stm.is_delete() && i + 1 < evs.len())
+				{
+					let (ns, db) = opt.ns_db()?;
+					after = ctx.tx().get_record(ns, db, &rid.tb, &rid.id, None).await?;
+				}
 			}
 		}
 		// Carry on
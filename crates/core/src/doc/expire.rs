@@ -0,0 +1,128 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Statement, Workable};
+use crate::doc::{CursorDoc, Document};
+use crate::expr::statements::{DefineFieldStatement, DeleteStatement};
+use crate::expr::{Thing, Value, Values};
+use crate::idx::planner::RecordStrategy;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reblessive::tree::Stk;
+use std::sync::Arc;
+
+use super::IgnoreError;
+
+impl Document {
+	/// Treats a record which has expired as if it had been deleted.
+	///
+	/// A record expires once the timestamp of the transaction reaches the
+	/// datetime stored in any of the fields which are defined with EXPIRE.
+	/// Statements which only read a record ignore it once it has expired,
+	/// while any other statements first remove the expired record, so that
+	/// live queries and changefeeds are notified of the deletion.
+	pub(super) async fn check_record_expired(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), IgnoreError> {
+		// Statements which write a record don't fetch it first
+		let writes = matches!(
+			stm,
+			Statement::Create(_)
+				| Statement::Upsert(_)
+				| Statement::Insert(_)
+				| Statement::Relate(_)
+		);
+		// Records which don't exist can't expire
+		let fetched = !self.current.doc.as_ref().is_none();
+		if !fetched && !writes {
+			return Ok(());
+		}
+		// Get the record id
+		let Some(rid) = self.id.clone() else {
+			return Ok(());
+		};
+		// Get the fields which expire the record
+		let fds = self.fd(ctx, opt).await?;
+		if !fds.iter().any(|fd| fd.expire) {
+			return Ok(());
+		}
+		// Get the transaction
+		let txn = ctx.tx();
+		// Check the stored record
+		match fetched {
+			true => {
+				if !Self::is_expired(&fds, self.current.doc.as_ref(), txn.timestamp()) {
+					return Ok(());
+				}
+				// Only writes remove the expired record
+				if stm.is_select() {
+					return Err(IgnoreError::Ignore);
+				}
+				let val = self.current.doc.as_arc();
+				Self::expire(stk, ctx, opt, rid.clone(), val).await?;
+				// Continue with a new record
+				match stm {
+					Statement::Update(_) | Statement::Delete(_) => Err(IgnoreError::Ignore),
+					_ => {
+						self.current = CursorDoc::new(Some(rid.clone()), None, Value::None);
+						self.initial = CursorDoc::new(Some(rid.clone()), None, Value::None);
+						self.current_reduced = CursorDoc::new(Some(rid.clone()), None, Value::None);
+						self.initial_reduced = CursorDoc::new(Some(rid), None, Value::None);
+						Ok(())
+					}
+				}
+			}
+			false => {
+				let (ns, db) = opt.ns_db()?;
+				let val = txn.get_record(ns, db, &rid.tb, &rid.id, opt.version).await?;
+				if Self::is_expired(&fds, &val, txn.timestamp()) {
+					Self::expire(stk, ctx, opt, rid, val).await?;
+				}
+				Ok(())
+			}
+		}
+	}
+
+	/// Checks if a record has expired at the specified time
+	fn is_expired(fds: &[DefineFieldStatement], val: &Value, ts: DateTime<Utc>) -> bool {
+		fds.iter()
+			.filter(|fd| fd.expire)
+			.any(|fd| matches!(val.pick(&fd.name), Value::Datetime(v) if v.0 <= ts))
+	}
+
+	/// Removes a record which has expired, running it through the
+	/// DELETE statement lifecycle without checking permissions
+	async fn expire(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rid: Arc<Thing>,
+		val: Arc<Value>,
+	) -> Result<()> {
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Setup the delete statement
+		let stm = DeleteStatement {
+			what: Values(vec![Value::from(rid.as_ref().clone())]),
+			..DeleteStatement::default()
+		};
+		let stm = Statement::from(&stm);
+		// Setup a new document
+		let mut doc = Document::new(
+			Some(rid),
+			None,
+			None,
+			val,
+			Workable::Normal,
+			false,
+			RecordStrategy::KeysAndValues,
+		);
+		// Delete the record
+		match doc.delete(stk, ctx, opt, &stm).await {
+			Err(IgnoreError::Error(e)) => Err(e),
+			_ => Ok(()),
+		}
+	}
+}
@@ -3,7 +3,9 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::doc::Document;
+use crate::doc::Permitted;
 use crate::err::Error;
+use crate::expr::Datetime;
 use crate::expr::data::Data;
 use crate::expr::idiom::{Idiom, IdiomTrie, IdiomTrieContains};
 use crate::expr::kind::Kind;
@@ -119,6 +121,63 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+	/// Computes the COMPUTED fields of the table,
+	/// which are never stored, so that they can be
+	/// filtered and output along with the record.
+	/// Each field is computed after the computed
+	/// fields which it refers to.
+	pub(super) async fn process_computed_fields(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		permitted: Permitted,
+	) -> Result<()> {
+		// Check if this record exists
+		if self.id.is_none() {
+			return Ok(());
+		}
+		// Get the fields which are computed
+		let fds = self.fd(ctx, opt).await?;
+		let computed = computed_order(&fds);
+		if computed.is_empty() {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Fetch the targets to process
+		let targets = match permitted {
+			Permitted::Initial => vec![&mut self.initial],
+			Permitted::Current => vec![&mut self.current],
+			Permitted::Both => vec![&mut self.initial, &mut self.current],
+		};
+		// Loop over the targets to process
+		for doc in targets {
+			// A missing or deleted record has no fields
+			if !doc.doc.as_ref().is_object() {
+				continue;
+			}
+			for fd in computed.iter() {
+				let Some(expr) = &fd.computed else {
+					continue;
+				};
+				// Process the COMPUTED clause
+				let val = expr.compute(stk, ctx, opt, Some(&*doc)).await.catch_return()?;
+				// Check the field TYPE
+				let val = match &fd.kind {
+					Some(kind) => val.coerce_to_kind(kind).map_err(|e| Error::FieldCoerce {
+						thing: rid.to_string(),
+						field_name: fd.name.to_string(),
+						error: Box::new(e),
+					})?,
+					None => val,
+				};
+				doc.doc.to_mut().put(&fd.name, val);
+			}
+		}
+		// Carry on
+		Ok(())
+	}
 	/// Processes `DEFINE FIELD` statements which
 	/// have been defined on the table for this
 	/// record. These fields are executed for
@@ -138,12 +197,36 @@ impl Document {
 		let rid = self.id()?;
 		// Get the user applied input
 		let inp = self.initial.doc.as_ref().changed(self.current.doc.as_ref());
+		// Get the datetime of any CREATED or MODIFIED fields
+		let now = Value::from(Datetime::default());
 		// When set, any matching embedded object fields
 		// which are prefixed with the specified idiom
 		// will be skipped, as the parent object is optional
 		let mut skip: Option<&Idiom> = None;
+		// Get the fields of the table
+		let fds = self.fd(ctx, opt).await?;
+		// Get the fields which are computed on read
+		let computed: Vec<&Idiom> =
+			fds.iter().filter(|fd| fd.computed.is_some()).map(|fd| &fd.name).collect();
 		// Loop through all field statements
-		for fd in self.fd(ctx, opt).await?.iter() {
+		for fd in fds.iter() {
+			// A COMPUTED field is never stored, so any
+			// value written to it by the user is rejected
+			if fd.computed.is_some() {
+				ensure!(
+					inp.pick(&fd.name).is_none(),
+					Error::FieldComputed {
+						field: fd.name.clone(),
+						thing: rid.to_string(),
+					}
+				);
+				self.current.doc.to_mut().cut(&fd.name);
+				continue;
+			}
+			// The fields nested in a COMPUTED field are not stored either
+			if computed.iter().any(|c| fd.name.starts_with(c)) {
+				continue;
+			}
 			// Check if we should skip this field
 			let skipped = match skip {
 				// We are skipping a parent field
@@ -236,6 +319,17 @@ impl Document {
 					old,
 					inp,
 				};
+				// Process any CREATED or MODIFIED clause
+				if let Some(v) = field.process_timestamp_clause(&val, &now).await? {
+					// The value of this field is generated, so
+					// only the TYPE and ASSERT clauses apply
+					let v = field.process_type_clause(v).await?;
+					let v = field.process_assert_clause(v).await?;
+					if !skipped {
+						self.current.doc.to_mut().put(&k, v);
+					}
+					continue;
+				}
 				// Process a potential `references` TYPE
 				let res = field.process_refs_type().await?;
 				if let Some(v) = res {
@@ -354,6 +448,7 @@ struct FieldEditContext<'a> {
 enum RefAction<'a> {
 	Set(&'a Thing),
 	Delete(Vec<&'a Thing>, String),
+	Replace(Vec<&'a Thing>, String, Option<&'a Thing>),
 	Ignore,
 }
 
@@ -496,6 +591,76 @@ impl FieldEditContext<'_> {
 		// Return the original value
 		Ok(val)
 	}
+	/// Process any CREATED or MODIFIED clause for the field definition
+	async fn process_timestamp_clause(
+		&mut self,
+		val: &Value,
+		now: &Value,
+	) -> Result<Option<Value>> {
+		// Check for a CREATED or MODIFIED clause
+		if !self.def.created && !self.def.modified {
+			return Ok(None);
+		}
+		// A value supplied by the user is only kept
+		// when a specific PERMISSIONS clause allows it
+		if !val.is_none()
+			&& val != self.old.as_ref()
+			&& self.process_timestamp_override(val).await?
+		{
+			return Ok(Some(val.clone()));
+		}
+		// A CREATED field keeps its initial value
+		// once the record has been created
+		if self.def.created && !self.doc.is_new() {
+			return Ok(Some(self.old.as_ref().clone()));
+		}
+		// Otherwise the field is set to the current datetime
+		Ok(Some(now.clone()))
+	}
+	/// Check if a specific PERMISSIONS clause allows
+	/// a CREATED or MODIFIED field to be overridden
+	async fn process_timestamp_override(&mut self, val: &Value) -> Result<bool> {
+		// Get the permission clause
+		let perms = if self.doc.is_new() {
+			&self.def.permissions.create
+		} else {
+			&self.def.permissions.update
+		};
+		// Only a custom expression can allow an override
+		let Permission::Specific(expr) = perms else {
+			return Ok(false);
+		};
+		// Arc the current value
+		let now = Arc::new(val.clone());
+		// Get the current document
+		let doc = Some(&self.doc.current);
+		// Disable permissions
+		let opt = &self.opt.new_with_perms(false);
+		// Configure the context
+		let ctx = match self.context.take() {
+			Some(mut ctx) => {
+				ctx.add_value("after", now.clone());
+				ctx.add_value("value", now);
+				ctx
+			}
+			None => {
+				let mut ctx = MutableContext::new(self.ctx);
+				ctx.add_value("before", self.old.clone());
+				ctx.add_value("input", self.inp.clone());
+				ctx.add_value("after", now.clone());
+				ctx.add_value("value", now);
+				ctx
+			}
+		};
+		// Freeze the new context
+		let ctx = ctx.freeze();
+		// Process the PERMISSION clause
+		let res = expr.compute(self.stk, &ctx, opt, doc).await.catch_return()?;
+		// Unfreeze the new context
+		self.context = Some(MutableContext::unfreeze(ctx)?);
+		// Check the PERMISSION clause result
+		Ok(res.is_truthy())
+	}
 	/// Process any ASSERT clause for the field definition
 	async fn process_assert_clause(&mut self, val: Value) -> Result<Value> {
 		// If the field TYPE is optional, and the
@@ -649,16 +814,23 @@ impl FieldEditContext<'_> {
 					.catch_return()?;
 				// If the reference is contained in an array, we only delete it from the array
 				// if there is no other reference to the same record id in the array
-				if let Value::Array(arr) = others {
+				let removed = if let Value::Array(arr) = others {
 					if arr.iter().any(|v| v == old) {
-						RefAction::Ignore
+						vec![]
 					} else {
-						RefAction::Delete(vec![thing], self.def.name.to_string())
+						vec![thing]
 					}
 				} else {
 					// Otherwise we delete the reference
-					RefAction::Delete(vec![thing], self.def.name.to_string())
-				}
+					vec![thing]
+				};
+				// The field may now reference another record id
+				let added = if let Value::Thing(thing) = val {
+					Some(thing)
+				} else {
+					None
+				};
+				RefAction::Replace(removed, self.def.name.to_string(), added)
 			} else if let Value::Array(oldarr) = old {
 				// If the new value is still an array, we only filter out the record ids that are not present in the new array
 				let removed = if let Value::Array(newarr) = val {
@@ -705,43 +877,22 @@ impl FieldEditContext<'_> {
 				// Nothing to process
 				RefAction::Ignore => Ok(()),
 				// Create the reference, if it does not exist yet.
-				RefAction::Set(thing) => {
-					let (ns, db) = self.opt.ns_db()?;
-					let key = crate::key::r#ref::new(
-						ns,
-						db,
-						&thing.tb,
-						&thing.id,
-						&self.rid.tb,
-						&self.def.name.to_string(),
-						&self.rid.id,
-					)
-					.encode_owned()
-					.unwrap();
-
-					self.ctx.tx().set(key, vec![], None).await?;
-
-					Ok(())
-				}
+				RefAction::Set(thing) => self.set_reference(thing).await,
 				// Delete the reference, if it exists
 				RefAction::Delete(things, ff) => {
-					let (ns, db) = self.opt.ns_db()?;
 					for thing in things {
-						let key = crate::key::r#ref::new(
-							ns,
-							db,
-							&thing.tb,
-							&thing.id,
-							&self.rid.tb,
-							&ff,
-							&self.rid.id,
-						)
-						.encode_owned()
-						.unwrap();
-
-						self.ctx.tx().del(key).await?;
+						self.delete_reference(thing, &ff).await?;
+					}
+					Ok(())
+				}
+				// Delete the old references, and create the new one
+				RefAction::Replace(things, ff, thing) => {
+					for thing in things {
+						self.delete_reference(thing, &ff).await?;
+					}
+					if let Some(thing) = thing {
+						self.set_reference(thing).await?;
 					}
-
 					Ok(())
 				}
 			}
@@ -750,6 +901,23 @@ impl FieldEditContext<'_> {
 		}
 	}
 
+	/// Creates the reference from this record to a record id
+	async fn set_reference(&self, thing: &Thing) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		let ff = self.def.name.to_string();
+		let key =
+			crate::key::r#ref::new(ns, db, &thing.tb, &thing.id, &self.rid.tb, &ff, &self.rid.id);
+		self.ctx.tx().set(key, vec![], None).await
+	}
+
+	/// Deletes the reference from this record to a record id
+	async fn delete_reference(&self, thing: &Thing, ff: &str) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		let key =
+			crate::key::r#ref::new(ns, db, &thing.tb, &thing.id, &self.rid.tb, ff, &self.rid.id);
+		self.ctx.tx().del(key).await
+	}
+
 	/// Process any `TYPE reference` clause for the field definition
 	async fn process_refs_type(&mut self) -> Result<Option<Value>> {
 		if !self.ctx.get_capabilities().allows_experimental(&ExperimentalTarget::RecordReferences) {
@@ -790,3 +958,32 @@ impl FieldEditContext<'_> {
 		Ok(Some(Value::Refs(refs)))
 	}
 }
+
+/// Orders the COMPUTED fields of a table, so that each
+/// field comes after the computed fields it refers to
+fn computed_order(fds: &[DefineFieldStatement]) -> Vec<&DefineFieldStatement> {
+	fn visit<'a>(
+		fd: &'a DefineFieldStatement,
+		computed: &[&'a DefineFieldStatement],
+		visiting: &mut Vec<&'a Idiom>,
+		out: &mut Vec<&'a DefineFieldStatement>,
+	) {
+		// Cycles are rejected when the fields are defined
+		if visiting.contains(&&fd.name) || out.iter().any(|o| o.name == fd.name) {
+			return;
+		}
+		visiting.push(&fd.name);
+		for dep in computed.iter().copied().filter(|dep| fd.depends_on(dep)) {
+			visit(dep, computed, visiting, out);
+		}
+		visiting.pop();
+		out.push(fd);
+	}
+	let computed: Vec<&DefineFieldStatement> =
+		fds.iter().filter(|fd| fd.computed.is_some()).collect();
+	let mut out = Vec::with_capacity(computed.len());
+	for fd in computed.iter().copied() {
+		visit(fd, &computed, &mut Vec::new(), &mut out);
+	}
+	out
+}
@@ -10,6 +10,7 @@ use crate::expr::{FlowResultExt as _, Part, Thing, Value};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
+use crate::idx::trigram;
 use crate::key;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::ConsumeResult;
@@ -48,6 +49,10 @@ impl Document {
 		let rid = self.id()?;
 		// Loop through all index statements
 		for ix in ixs.iter() {
+			// Cancelled indexes are only updated once they are rebuilt
+			if ix.cancelled {
+				continue;
+			}
 			// Calculate old values
 			let o = Self::build_opt_values(stk, ctx, opt, ix, &self.initial).await?;
 
@@ -78,6 +83,8 @@ impl Document {
 				// The index builder consumed the value, which means it is currently building the index asynchronously,
 				// we don't index the document and let the index builder do it later.
 				ConsumeResult::Enqueued => return Ok(()),
+				// The index building was cancelled, the document will be indexed when the index is rebuilt
+				ConsumeResult::Skipped => return Ok(()),
 				// The index builder is done, the index has been built, we can proceed normally
 				ConsumeResult::Ignored(o, n) => (o, n),
 			}
@@ -95,6 +102,7 @@ impl Document {
 			Index::Search(p) => ic.index_full_text(stk, ctx, p).await?,
 			Index::MTree(p) => ic.index_mtree(stk, ctx, p).await?,
 			Index::Hnsw(p) => ic.index_hnsw(ctx, p).await?,
+			Index::Trigram => ic.index_trigram(ctx).await?,
 		}
 		Ok(())
 	}
@@ -103,6 +111,7 @@ impl Document {
 	/// Eg. IF the index is composed of the columns `name` and `instrument`
 	/// Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie" }
 	/// It will return: ["Tobie", "piano"]
+	/// Documents which do not match the WHERE clause of a partial index are not indexed.
 	pub(crate) async fn build_opt_values(
 		stk: &mut Stk,
 		ctx: &Context,
@@ -113,6 +122,11 @@ impl Document {
 		if !doc.doc.as_ref().is_some() {
 			return Ok(None);
 		}
+		if let Some(cond) = &ix.cond {
+			if !cond.compute(stk, ctx, opt, Some(doc)).await.catch_return()?.is_truthy() {
+				return Ok(None);
+			}
+		}
 		let mut o = Vec::with_capacity(ix.cols.len());
 		for i in ix.cols.iter() {
 			let v = i.compute(stk, ctx, opt, Some(doc)).await.catch_return()?;
@@ -430,4 +444,10 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_trigram(&mut self, ctx: &Context) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		let (o, n) = (self.o.take(), self.n.take());
+		trigram::index_trigrams(&ctx.tx(), ns, db, self.ix, self.rid, o, n).await
+	}
 }
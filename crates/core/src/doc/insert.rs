@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
+use crate::doc::Permitted;
 use crate::err;
 use crate::err::Error;
 use crate::expr::statements::InsertStatement;
@@ -33,7 +34,7 @@ impl Document {
 		let retryable = stm.update.is_some();
 		if retryable {
 			// it is retryable so generate a save point we can roll back to.
-			ctx.tx().lock().await.new_save_point();
+			ctx.tx().lock().await.new_save_point().await?;
 		}
 
 		// First try to create the value and if that is not possible due to an existing value fall
@@ -161,6 +162,7 @@ impl Document {
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
+		self.check_table_assert(stk, ctx, opt).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
@@ -168,6 +170,7 @@ impl Document {
 		self.process_table_lives(stk, ctx, opt, stm).await?;
 		self.process_table_events(stk, ctx, opt, stm).await?;
 		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 	/// Attempt to run an INSERT statement to
@@ -187,6 +190,7 @@ impl Document {
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
+		self.check_table_assert(stk, ctx, opt).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
@@ -194,6 +198,7 @@ impl Document {
 		self.process_table_lives(stk, ctx, opt, stm).await?;
 		self.process_table_events(stk, ctx, opt, stm).await?;
 		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 }
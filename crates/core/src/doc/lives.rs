@@ -5,14 +5,18 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::CursorDoc;
 use crate::doc::Document;
+use crate::doc::aggregate::AggregateState;
 use crate::err::Error;
 use crate::expr::paths::AC;
 use crate::expr::paths::META;
 use crate::expr::paths::RD;
 use crate::expr::paths::TK;
 use crate::expr::permission::Permission;
-use crate::expr::{FlowResultExt as _, Value};
+use crate::expr::statements::LiveStatement;
+use crate::expr::value::fetch::{FetchedRecords, Fetching};
+use crate::expr::{Array, FlowResultExt as _, Value};
 use anyhow::Result;
+use async_channel::Sender;
 use reblessive::tree::Stk;
 use std::sync::Arc;
 
@@ -121,6 +125,15 @@ impl Document {
 			// clause of the LIVE query is matched by this
 			// document. If it is then we can continue.
 			let lqctx = lqctx.freeze();
+			// Live queries with a GROUP clause are aggregated
+			// and notify about the groups which changed, rather
+			// than about the individual records.
+			if lv.group.is_some() {
+				if !self.lq_aggregate(stk, &lqctx, &lqopt, lv, chn, stm.is_delete()).await? {
+					return Ok(());
+				}
+				continue;
+			}
 			match self.lq_check(stk, &lqctx, &lqopt, &lq, doc).await {
 				Err(IgnoreError::Ignore) => continue,
 				Err(IgnoreError::Error(e)) => return Err(e),
@@ -192,15 +205,7 @@ impl Document {
 			};
 
 			// Process any potential `FETCH` clause on the live statement
-			if let Some(fetchs) = &lv.fetch {
-				let mut idioms = Vec::with_capacity(fetchs.0.len());
-				for fetch in fetchs.iter() {
-					fetch.compute(stk, &lqctx, &lqopt, &mut idioms).await?;
-				}
-				for i in &idioms {
-					stk.run(|stk| result.fetch(stk, &lqctx, &lqopt, i)).await?;
-				}
-			}
+			Self::lq_fetch(stk, &lqctx, &lqopt, lv, &mut result).await?;
 
 			// Send the notification
 			let res = chn
@@ -221,6 +226,154 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+	/// Updates the state of an aggregate LIVE query, sending
+	/// a notification for each group which was changed by this
+	/// document. Returns false if the notification channel was
+	/// closed.
+	async fn lq_aggregate(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		chn: &Sender<Notification>,
+		delete: bool,
+	) -> Result<bool> {
+		// Get the NS + DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the table for this document
+		let tb = self.tb(ctx, opt).await?;
+		// Get the aggregate inputs before this change
+		let before = match self.is_new() {
+			true => None,
+			false => lv.aggregate_record(stk, ctx, opt, &tb, &self.initial).await?,
+		};
+		// Get the aggregate inputs after this change
+		let after = match delete {
+			true => None,
+			false => lv.aggregate_record(stk, ctx, opt, &tb, &self.current).await?,
+		};
+		// Check if the aggregate is affected
+		if before == after {
+			return Ok(true);
+		}
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the record id
+		let rid = self.id()?;
+		// Notifications are sent from the node of the live query
+		let notify = opt.id()? == lv.node.0;
+		// The previous state of each changed group
+		let mut changes: Vec<(Array, AggregateState)> = Vec::with_capacity(2);
+		if notify {
+			for (group, _) in before.iter().chain(after.iter()) {
+				if changes.iter().all(|(g, _)| g != group) {
+					let old = lv.aggregate_load(&txn, ns, db, &tb.name, group).await?;
+					changes.push((group.clone(), old));
+				}
+			}
+		}
+		// Remove this record from its previous group
+		if let Some((group, vals)) = &before {
+			lv.aggregate_remove(&txn, ns, db, &tb.name, group, &rid, vals).await?;
+		}
+		// Add this record to its current group
+		if let Some((group, vals)) = &after {
+			lv.aggregate_insert(&txn, ns, db, &tb.name, group, &rid, vals).await?;
+		}
+		if !notify {
+			// TODO: Send to message broker
+			return Ok(true);
+		}
+		// A GROUP ALL query always has a single group
+		let all = lv.group.as_ref().is_some_and(|g| g.is_empty());
+		// Send a notification for each changed group
+		for (group, old) in changes {
+			let new = lv.aggregate_load(&txn, ns, db, &tb.name, &group).await?;
+			let before = lv.aggregate_output(&group, &old)?;
+			let after = lv.aggregate_output(&group, &new)?;
+			let (action, result) = match (old.rows, new.rows) {
+				_ if all => (Action::Update, after),
+				(0, 0) => continue,
+				(0, _) => (Action::Create, after),
+				(_, 0) => (Action::Delete, before),
+				_ => (Action::Update, after),
+			};
+			// Check if the output of this group changed
+			if action == Action::Update && result == before {
+				continue;
+			}
+			// Send the notification
+			let res = chn
+				.send(Notification {
+					id: lv.id,
+					action,
+					record: Value::Array(group),
+					result,
+				})
+				.await;
+			if res.is_err() {
+				return Ok(false);
+			}
+		}
+		// Carry on
+		Ok(true)
+	}
+	/// Computes the result of a `LIVE SELECT ... SNAPSHOT` query for this
+	/// document, as if the document had just been created. Returns `None`
+	/// if the document is not matched by the WHERE clause, or can not be
+	/// viewed by the user who created the LIVE query.
+	pub(super) async fn lq_snapshot(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+	) -> Result<Option<Value>> {
+		// The document did not exist before the live query started
+		self.initial = CursorDoc::new(self.id.clone(), None, Value::None);
+		self.initial_reduced = CursorDoc::new(self.id.clone(), None, Value::None);
+		// Create a new statement
+		let lq = Statement::from(lv);
+		// Check the WHERE clause and PERMISSIONS
+		match self.lq_check(stk, ctx, opt, &lq, &self.current).await {
+			Err(IgnoreError::Ignore) => return Ok(None),
+			Err(IgnoreError::Error(e)) => return Err(e),
+			Ok(_) => (),
+		}
+		match self.lq_allow(stk, ctx, opt, &lq, &self.current).await {
+			Err(IgnoreError::Ignore) => return Ok(None),
+			Err(IgnoreError::Error(e)) => return Err(e),
+			Ok(_) => (),
+		}
+		// Prepare a CREATE style result
+		let mut result = match self.pluck(stk, ctx, opt, &lq).await {
+			Err(IgnoreError::Ignore) => return Ok(None),
+			Err(IgnoreError::Error(e)) => return Err(e),
+			Ok(x) => x,
+		};
+		// Process any potential `FETCH` clause on the live statement
+		Self::lq_fetch(stk, ctx, opt, lv, &mut result).await?;
+		Ok(Some(result))
+	}
+	/// Process any `FETCH` clause for a LIVE query
+	async fn lq_fetch(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		result: &mut Value,
+	) -> Result<()> {
+		if let Some(fetchs) = &lv.fetch {
+			let paths = fetchs.paths(stk, ctx, opt).await?;
+			let records = FetchedRecords::default();
+			for (i, depth) in &paths {
+				let fetching = Fetching::new(i, *depth, &records);
+				stk.run(|stk| result.fetch(stk, ctx, opt, &fetching)).await?;
+			}
+		}
+		Ok(())
+	}
 	/// Check the WHERE clause for a LIVE query
 	async fn lq_check(
 		&self,
@@ -250,6 +403,8 @@ impl Document {
 		stm: &Statement<'_>,
 		doc: &CursorDoc,
 	) -> Result<(), IgnoreError> {
+		// Check the tenant of the record
+		self.check_tenant(stk, ctx, opt, doc).await?;
 		// Should we run permissions checks?
 		if opt.check_perms(stm.into())? {
 			// Get the table
@@ -19,16 +19,20 @@ mod select; // Processes a SELECT statement for this document
 mod update; // Processes a UPDATE statement for this document
 mod upsert; // Processes a UPSERT statement for this document
 
+pub(crate) mod aggregate; // Maintains the state of any aggregate live queries
 mod alter; // Modifies and updates the fields in this document
 mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Data and condition checking for this document
 mod edges; // Attempts to store the edge data for this document
 mod event; // Processes any table events relevant for this document
+mod expire; // Removes this document once it has expired
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
 mod pluck; // Pulls the projected expressions from the document
 mod purge; // Deletes this document, and any edges or indexes
+mod snapshot; // Sends the initial records of any snapshot live queries
+mod soft; // Keeps this document with a tombstone once it is deleted
 mod store; // Writes the document content to the storage engine
 mod table; // Processes any foreign tables relevant for this document'
 
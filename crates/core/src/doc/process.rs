@@ -35,6 +35,10 @@ impl Document {
 		let mut doc = Document::new(pro.rid, pro.ir, pro.generate, ins.0, ins.1, false, pro.rs);
 		// Generate a new document id if necessary
 		doc.generate_record_id(stk, ctx, opt, stm).await?;
+		// Check if the record has been deleted
+		doc.check_record_deleted(ctx, opt, stm).await?;
+		// Check if the record has expired
+		doc.check_record_expired(stk, ctx, opt, stm).await?;
 		// Process the statement
 		let res = match stm {
 			Statement::Select(_) => doc.select(stk, ctx, opt, stm).await?,
@@ -18,16 +18,20 @@ use crate::expr::graph::GraphSubjects;
 use crate::expr::paths::EDGE;
 use crate::expr::paths::IN;
 use crate::expr::paths::OUT;
-use crate::expr::reference::ReferenceDeleteStrategy;
+use crate::expr::reference::{Reference, ReferenceDeleteStrategy};
 use crate::expr::statements::DeleteStatement;
 use crate::expr::statements::UpdateStatement;
 use crate::expr::value::{Value, Values};
 use crate::idx::planner::ScanDirection;
 use crate::key::r#ref::Ref;
-use crate::kvs::KeyDecode;
+use crate::kvs::{Key, KeyDecode};
 use anyhow::{Result, bail};
 use futures::StreamExt;
 use reblessive::tree::Stk;
+use std::ops::Range;
+
+/// The number of rejecting references which are listed when a delete is rejected
+const REJECT_SAMPLE_SIZE: usize = 10;
 
 impl Document {
 	pub(super) async fn purge(
@@ -97,8 +101,11 @@ impl Document {
 
 				// Obtain a transaction
 				let txn = ctx.tx();
+				// Reject the delete operation before any reference is processed
+				Self::check_rejecting_references(ctx, ns, db, rid, range.clone()).await?;
 				// Obtain a stream of keys
-				let mut stream = txn.stream_keys(range.clone(), None, ScanDirection::Forward);
+				let mut stream =
+					txn.stream_keys(ctx, range.clone(), None, None, ScanDirection::Forward);
 				// Loop until no more entries
 				while let Some(res) = stream.next().await {
 					yield_now!();
@@ -112,18 +119,8 @@ impl Document {
 						match &reference.on_delete {
 							// Ignore this reference
 							ReferenceDeleteStrategy::Ignore => (),
-							// Reject the delete operation, as indicated by the reference
-							ReferenceDeleteStrategy::Reject => {
-								let thing = Thing {
-									tb: r#ref.ft.to_string(),
-									id: r#ref.fk.clone(),
-								};
-
-								bail!(Error::DeleteRejectedByReference(
-									rid.to_string(),
-									thing.to_string(),
-								));
-							}
+							// The rejecting references were checked above
+							ReferenceDeleteStrategy::Reject => (),
 							// Delete the remote record which referenced this record
 							ReferenceDeleteStrategy::Cascade => {
 								let thing = Thing {
@@ -228,4 +225,57 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+
+	/// Checks whether any reference to the record has an ON DELETE REJECT
+	/// clause, in which case the delete operation is rejected, listing a
+	/// sample of the rejecting references in the error.
+	async fn check_rejecting_references(
+		ctx: &Context,
+		ns: &str,
+		db: &str,
+		rid: &Thing,
+		range: Range<Key>,
+	) -> Result<()> {
+		// Obtain a transaction
+		let txn = ctx.tx();
+		// The rejecting references which are listed in the error
+		let mut sample = Vec::new();
+		// The total number of rejecting references
+		let mut count = 0;
+		// Obtain a stream of keys
+		let mut stream = txn.stream_keys(ctx, range, None, None, ScanDirection::Forward);
+		// Loop until no more entries
+		while let Some(res) = stream.next().await {
+			yield_now!();
+			// Decode the key
+			let key = res?;
+			let r#ref = Ref::decode(&key)?;
+			// Obtain the remote field definition
+			let fd = txn.get_tb_field(ns, db, r#ref.ft, r#ref.ff).await?;
+			// Check if the reference rejects the delete operation
+			if let Some(Reference {
+				on_delete: ReferenceDeleteStrategy::Reject,
+				..
+			}) = &fd.reference
+			{
+				if sample.len() < REJECT_SAMPLE_SIZE {
+					let thing = Thing {
+						tb: r#ref.ft.to_string(),
+						id: r#ref.fk.clone(),
+					};
+					sample.push(format!("`{thing}`"));
+				}
+				count += 1;
+			}
+		}
+		// Reject the delete operation
+		if count > 0 {
+			let mut references = sample.join(", ");
+			if count > sample.len() {
+				references.push_str(&format!(" and {} more", count - sample.len()));
+			}
+			bail!(Error::DeleteRejectedByReference(rid.to_string(), references));
+		}
+		Ok(())
+	}
 }
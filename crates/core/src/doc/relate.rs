@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
+use crate::doc::Permitted;
 use crate::expr::value::Value;
 use reblessive::tree::Stk;
 
@@ -45,6 +46,7 @@ impl Document {
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
+		self.check_table_assert(stk, ctx, opt).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
@@ -52,6 +54,7 @@ impl Document {
 		self.process_table_lives(stk, ctx, opt, stm).await?;
 		self.process_changefeeds(ctx, opt, stm).await?;
 		self.process_table_events(stk, ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 	/// Attempt to run an UPDATE clause
@@ -71,6 +74,7 @@ impl Document {
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
+		self.check_table_assert(stk, ctx, opt).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
@@ -78,6 +82,7 @@ impl Document {
 		self.process_table_lives(stk, ctx, opt, stm).await?;
 		self.process_table_events(stk, ctx, opt, stm).await?;
 		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 }
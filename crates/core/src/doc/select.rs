@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
+use crate::doc::Permitted;
 use crate::expr::value::Value;
 use reblessive::tree::Stk;
 
@@ -16,6 +17,7 @@ impl Document {
 		stm: &Statement<'_>,
 	) -> Result<Value, IgnoreError> {
 		self.check_record_exists(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Current).await?;
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_where_condition(stk, ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
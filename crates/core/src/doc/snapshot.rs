@@ -0,0 +1,99 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::{Action, Notification, Options, Workable};
+use crate::doc::Document;
+use crate::expr::paths::{AC, RD, TK};
+use crate::expr::statements::LiveStatement;
+use crate::expr::{Thing, Value};
+use crate::idx::planner::RecordStrategy;
+use crate::kvs::KeyDecode as _;
+use anyhow::Result;
+use async_channel::Sender;
+use reblessive::tree::Stk;
+use std::sync::Arc;
+
+impl LiveStatement {
+	/// Sends a `SNAPSHOT` notification for every record in the table which
+	/// is currently matched by this LIVE SELECT query. The records are read
+	/// in batches, and each notification is sent as soon as it is computed,
+	/// so that the snapshot of a large table is never buffered in memory.
+	pub(crate) async fn snapshot_scan(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &str,
+		chn: &Sender<Notification>,
+	) -> Result<()> {
+		// Ensure that a session and auth info exist on the LIVE query
+		let (Some(sess), Some(auth)) = (self.session.as_ref(), self.auth.clone()) else {
+			return Ok(());
+		};
+		// Use the session of the user who created the LIVE query,
+		// processing each record as if it had just been created.
+		let mut lqctx = MutableContext::new(ctx);
+		lqctx.add_value("access", sess.pick(AC.as_ref()).into());
+		lqctx.add_value("auth", sess.pick(RD.as_ref()).into());
+		lqctx.add_value("token", sess.pick(TK.as_ref()).into());
+		lqctx.add_value("session", sess.clone().into());
+		lqctx.add_value("event", Value::from("CREATE").into());
+		lqctx.add_value("before", Value::None.into());
+		let lqctx = lqctx.freeze();
+		// Use the auth data of the user who created the LIVE query
+		let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
+		// Scan the records in the table
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Fetch the next batch of keys and values
+			let res = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				// Decode the record
+				let key = crate::key::thing::Thing::decode(k)?;
+				let mut val: Value = revision::from_slice(v)?;
+				let rid = Arc::new(Thing::from((key.tb, key.id)));
+				// Inject the id field into the document
+				val.def(&rid);
+				let val = Arc::new(val);
+				// Add the $value and $after params for this record
+				let mut ctx = MutableContext::new(&lqctx);
+				ctx.add_value("value", val.clone());
+				ctx.add_value("after", val.clone());
+				let ctx = ctx.freeze();
+				// Compute the result for this record
+				let mut doc = Document::new(
+					Some(rid.clone()),
+					None,
+					None,
+					val,
+					Workable::Normal,
+					false,
+					RecordStrategy::KeysAndValues,
+				);
+				let Some(result) = doc.lq_snapshot(stk, &ctx, &lqopt, self).await? else {
+					continue;
+				};
+				// Send the notification
+				let res = chn
+					.send(Notification {
+						id: self.id,
+						action: Action::Snapshot,
+						record: Value::Thing(rid.as_ref().clone()),
+						result,
+					})
+					.await;
+				// Stop once the channel is closed
+				if res.is_err() {
+					return Ok(());
+				}
+			}
+			// Pause and yield execution
+			yield_now!();
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,184 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Statement, Workable};
+use crate::doc::{CursorDoc, Document, Permitted};
+use crate::err::Error;
+use crate::expr::paths::DELETED_AT;
+use crate::expr::statements::UpdateStatement;
+use crate::expr::{Datetime, Thing, Value, Values, With};
+use crate::idx::planner::RecordStrategy;
+use anyhow::Result;
+use reblessive::tree::Stk;
+use std::sync::Arc;
+
+use super::IgnoreError;
+
+impl Document {
+	/// Hides the records of a soft delete table which have been deleted.
+	///
+	/// A record of a table defined with SOFT DELETE is kept when it is
+	/// deleted, with the time of the deletion stored in its `deleted_at`
+	/// field. Statements ignore a deleted record, unless it is selected
+	/// WITH DELETED, and statements which would create the record again
+	/// fail, as the record can only be brought back with record::restore().
+	pub(super) async fn check_record_deleted(
+		&mut self,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), IgnoreError> {
+		// Statements which write a record don't fetch it first
+		let writes = matches!(
+			stm,
+			Statement::Create(_)
+				| Statement::Upsert(_)
+				| Statement::Insert(_)
+				| Statement::Relate(_)
+		);
+		// Records which don't exist can't be deleted
+		let fetched = !self.current.doc.as_ref().is_none();
+		if !fetched && !writes {
+			return Ok(());
+		}
+		// Get the record id
+		let Some(rid) = self.id.clone() else {
+			return Ok(());
+		};
+		// Check if the table keeps the deleted records
+		if !self.tb(ctx, opt).await?.soft_delete {
+			return Ok(());
+		}
+		match fetched {
+			true => {
+				if !Self::is_deleted(self.current.doc.as_ref()) {
+					return Ok(());
+				}
+				// Deleted records can be selected explicitly
+				if stm.is_select() && matches!(stm.with(), Some(With::Deleted)) {
+					return Ok(());
+				}
+				Err(IgnoreError::Ignore)
+			}
+			false => {
+				let (ns, db) = opt.ns_db()?;
+				let val = ctx.tx().get_record(ns, db, &rid.tb, &rid.id, opt.version).await?;
+				if Self::is_deleted(&val) {
+					return Err(IgnoreError::from(anyhow::Error::new(Error::RecordDeleted {
+						thing: rid.as_ref().to_owned(),
+					})));
+				}
+				Ok(())
+			}
+		}
+	}
+
+	/// Checks if a record has been deleted from a soft delete table
+	fn is_deleted(val: &Value) -> bool {
+		val.pick(&*DELETED_AT).is_some()
+	}
+
+	/// Deletes a record of a soft delete table, keeping the record with
+	/// the time of the deletion. The record is removed from any indexes,
+	/// views, and live queries, but its edges and references are kept,
+	/// so that they are still in place when the record is restored.
+	pub(super) async fn soft_delete(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<Value, IgnoreError> {
+		self.clear_record_data();
+		self.store_index_data(stk, ctx, opt, stm).await?;
+		self.process_table_views(stk, ctx, opt, stm).await?;
+		self.process_table_lives(stk, ctx, opt, stm).await?;
+		self.process_table_events(stk, ctx, opt, stm).await?;
+		self.tombstone_record_data(ctx);
+		self.store_record_data(ctx, opt, stm).await?;
+		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
+		self.pluck(stk, ctx, opt, stm).await
+	}
+
+	/// Sets the current document to the deleted record
+	fn tombstone_record_data(&mut self, ctx: &Context) {
+		let mut val = self.initial.doc.as_ref().clone();
+		val.put(&*DELETED_AT, Datetime::from(ctx.tx().timestamp()).into());
+		*self.current.doc.to_mut() = val;
+	}
+
+	/// Restores a record which was deleted from a soft delete table,
+	/// running it through the UPDATE statement lifecycle. The restored
+	/// record is added back to any indexes, views, and live queries as
+	/// if it had been created. Records which have not been deleted are
+	/// left unchanged, and NONE is returned.
+	pub(crate) async fn restore(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rid: Thing,
+	) -> Result<Value> {
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		// Check if the table keeps the deleted records
+		match txn.get_tb(ns, db, &rid.tb).await {
+			Ok(tb) if tb.soft_delete => {}
+			Ok(_) => return Ok(Value::None),
+			Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => {
+				return Ok(Value::None);
+			}
+			Err(e) => return Err(e),
+		}
+		// Check if the record has been deleted
+		let val = txn.get_record(ns, db, &rid.tb, &rid.id, None).await?;
+		if !Self::is_deleted(&val) {
+			return Ok(Value::None);
+		}
+		// Setup the update statement
+		let stm = UpdateStatement {
+			what: Values(vec![Value::from(rid.clone())]),
+			..UpdateStatement::default()
+		};
+		let stm = Statement::from(&stm);
+		// Setup a new document
+		let mut doc = Document::new(
+			Some(Arc::new(rid)),
+			None,
+			None,
+			val,
+			Workable::Normal,
+			false,
+			RecordStrategy::KeysAndValues,
+		);
+		// Restore the record
+		match doc.restore_record(stk, ctx, opt, &stm).await {
+			Ok(v) => Ok(v),
+			Err(IgnoreError::Ignore) => Ok(Value::None),
+			Err(IgnoreError::Error(e)) => Err(e),
+		}
+	}
+
+	async fn restore_record(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<Value, IgnoreError> {
+		self.check_permissions_quick(stk, ctx, opt, stm).await?;
+		self.current.doc.to_mut().cut(&*DELETED_AT);
+		self.current_reduced.doc.to_mut().cut(&*DELETED_AT);
+		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		// The record is stored and indexed as a new record
+		let rid = self.id.clone();
+		self.initial = CursorDoc::new(rid.clone(), None, Value::None);
+		self.initial_reduced = CursorDoc::new(rid, None, Value::None);
+		self.store_record_data(ctx, opt, stm).await?;
+		self.store_index_data(stk, ctx, opt, stm).await?;
+		self.process_table_views(stk, ctx, opt, stm).await?;
+		self.process_table_lives(stk, ctx, opt, stm).await?;
+		self.process_table_events(stk, ctx, opt, stm).await?;
+		self.process_changefeeds(ctx, opt, stm).await?;
+		self.process_computed_fields(stk, ctx, opt, Permitted::Both).await?;
+		self.pluck(stk, ctx, opt, stm).await
+	}
+}
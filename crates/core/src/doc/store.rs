@@ -123,6 +123,10 @@ impl Document {
 		}?;
 		// Update the cache
 		ctx.tx().set_record_cache(ns, db, &rid.tb, &rid.id, doc_without_id.as_arc())?;
+		// Increment the version of the record
+		if self.tb(ctx, opt).await?.versioned {
+			ctx.tx().next_record_version(ns, db, &rid.tb, &rid.id).await?;
+		}
 		// Carry on
 		Ok(())
 	}
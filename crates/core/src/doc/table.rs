@@ -1,6 +1,6 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
-use crate::dbs::{Force, Statement};
+use crate::dbs::{Force, Statement, Workable};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::data::Data;
@@ -19,14 +19,16 @@ use crate::expr::subquery::Subquery;
 use crate::expr::thing::Thing;
 use crate::expr::value::{Value, Values};
 use crate::expr::{Cond, FlowResultExt as _, Function, Groups, View};
+use crate::idx::planner::RecordStrategy;
 use anyhow::{Result, bail};
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
 use rust_decimal::Decimal;
+use std::sync::Arc;
 
 type Ops = Vec<(Idiom, Operator, Value)>;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Action {
 	Create,
 	Update,
@@ -96,191 +98,190 @@ impl Document {
 		};
 		// Loop through all foreign table statements
 		for ft in fts.iter() {
-			// Get the table definition
-			let tb = ft.view.as_ref().unwrap();
-			// Check if there is a GROUP BY clause
-			match &tb.group {
-				// There is a GROUP BY clause specified
-				Some(group) => {
-					// Check if a WHERE clause is specified
-					match &tb.cond {
-						// There is a WHERE clause specified
-						Some(cond) => {
-							// What do we do with the initial value on UPDATE and DELETE?
-							if !targeted_force
-								&& act != Action::Create && cond
+			// Keep track of the change while the view is suspended
+			if ft.suspended.is_some() && !targeted_force {
+				self.track_view_change(ctx, opt, ft, &rid).await?;
+				continue;
+			}
+			// Process the view data
+			self.process_table_view(stk, ctx, opt, ft, act, targeted_force).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Processes a single DEFINE TABLE AS clause
+	/// for the change made to this record.
+	async fn process_table_view(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		act: Action,
+		targeted_force: bool,
+	) -> Result<()> {
+		// Get the record id
+		let rid = self.id()?;
+		// Get the table definition
+		let tb = ft.view.as_ref().unwrap();
+		// Check if there is a GROUP BY clause
+		match &tb.group {
+			// There is a GROUP BY clause specified
+			Some(group) => {
+				// Check if a WHERE clause is specified
+				match &tb.cond {
+					// There is a WHERE clause specified
+					Some(cond) => {
+						// What do we do with the initial value on UPDATE and DELETE?
+						if !targeted_force
+							&& act != Action::Create
+							&& cond
 								.compute(stk, ctx, opt, Some(&self.initial))
 								.await
 								.catch_return()?
 								.is_truthy()
-							{
-								// Delete the old value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Sub,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.initial,
-									)
+						{
+							// Delete the old value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Sub,
+								view: tb,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.initial)
 									.await?,
-									doc: &self.initial,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
-							// What do we do with the current value on CREATE and UPDATE?
-							if act != Action::Delete
-								&& cond
-									.compute(stk, ctx, opt, Some(&self.current))
-									.await
-									.catch_return()?
-									.is_truthy()
-							{
-								// Update the new value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Add,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.current,
-									)
+								doc: &self.initial,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
+						}
+						// What do we do with the current value on CREATE and UPDATE?
+						if act != Action::Delete
+							&& cond
+								.compute(stk, ctx, opt, Some(&self.current))
+								.await
+								.catch_return()?
+								.is_truthy()
+						{
+							// Update the new value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Add,
+								view: tb,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.current)
 									.await?,
-									doc: &self.current,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
+								doc: &self.current,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
-						// No WHERE clause is specified
-						None => {
-							if !targeted_force && act != Action::Create {
-								// Delete the old value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Sub,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.initial,
-									)
+					}
+					// No WHERE clause is specified
+					None => {
+						if !targeted_force && act != Action::Create {
+							// Delete the old value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Sub,
+								view: tb,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.initial)
 									.await?,
-									doc: &self.initial,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
-							if act != Action::Delete {
-								// Update the new value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Add,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.current,
-									)
+								doc: &self.initial,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
+						}
+						if act != Action::Delete {
+							// Update the new value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Add,
+								view: tb,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.current)
 									.await?,
-									doc: &self.current,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
+								doc: &self.current,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
 					}
 				}
-				// No GROUP BY clause is specified
-				None => {
-					// Set the current record id
-					let rid = Thing {
-						tb: ft.name.to_raw(),
-						id: rid.id.clone(),
-					};
-					// Check if a WHERE clause is specified
-					match &tb.cond {
-						// There is a WHERE clause specified
-						Some(cond) => {
-							match cond
-								.compute(stk, ctx, opt, Some(&self.current))
-								.await
-								.catch_return()?
-							{
-								v if v.is_truthy() => {
-									// Define the statement
-									match act {
-										// Delete the value in the table
-										Action::Delete => {
-											let stm = DeleteStatement {
-												what: Values(vec![Value::from(rid)]),
-												..DeleteStatement::default()
-											};
-											// Execute the statement
-											stm.compute(stk, ctx, opt, None).await?;
-										}
-										// Update the value in the table
-										_ => {
-											let stm = UpsertStatement {
-												what: Values(vec![Value::from(rid)]),
-												data: Some(
-													self.full(stk, ctx, opt, &tb.expr).await?,
-												),
-												..UpsertStatement::default()
-											};
-											// Execute the statement
-											stm.compute(stk, ctx, opt, None).await?;
-										}
-									};
-								}
-								_ => {
+			}
+			// No GROUP BY clause is specified
+			None => {
+				// Set the current record id
+				let rid = Thing {
+					tb: ft.name.to_raw(),
+					id: rid.id.clone(),
+				};
+				// Check if a WHERE clause is specified
+				match &tb.cond {
+					// There is a WHERE clause specified
+					Some(cond) => {
+						match cond
+							.compute(stk, ctx, opt, Some(&self.current))
+							.await
+							.catch_return()?
+						{
+							v if v.is_truthy() => {
+								// Define the statement
+								match act {
 									// Delete the value in the table
-									let stm = DeleteStatement {
-										what: Values(vec![Value::from(rid)]),
-										..DeleteStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
+									Action::Delete => {
+										let stm = DeleteStatement {
+											what: Values(vec![Value::from(rid)]),
+											..DeleteStatement::default()
+										};
+										// Execute the statement
+										stm.compute(stk, ctx, opt, None).await?;
+									}
+									// Update the value in the table
+									_ => {
+										let stm = UpsertStatement {
+											what: Values(vec![Value::from(rid)]),
+											data: Some(self.full(stk, ctx, opt, &tb.expr).await?),
+											..UpsertStatement::default()
+										};
+										// Execute the statement
+										stm.compute(stk, ctx, opt, None).await?;
+									}
+								};
 							}
-						}
-						// No WHERE clause is specified
-						None => {
-							// Define the statement
-							match act {
+							_ => {
 								// Delete the value in the table
-								Action::Delete => {
-									let stm = DeleteStatement {
-										what: Values(vec![Value::from(rid)]),
-										..DeleteStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
-								// Update the value in the table
-								_ => {
-									let stm = UpsertStatement {
-										what: Values(vec![Value::from(rid)]),
-										data: Some(self.full(stk, ctx, opt, &tb.expr).await?),
-										..UpsertStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
-							};
+								let stm = DeleteStatement {
+									what: Values(vec![Value::from(rid)]),
+									..DeleteStatement::default()
+								};
+								// Execute the statement
+								stm.compute(stk, ctx, opt, None).await?;
+							}
 						}
 					}
+					// No WHERE clause is specified
+					None => {
+						// Define the statement
+						match act {
+							// Delete the value in the table
+							Action::Delete => {
+								let stm = DeleteStatement {
+									what: Values(vec![Value::from(rid)]),
+									..DeleteStatement::default()
+								};
+								// Execute the statement
+								stm.compute(stk, ctx, opt, None).await?;
+							}
+							// Update the value in the table
+							_ => {
+								let stm = UpsertStatement {
+									what: Values(vec![Value::from(rid)]),
+									data: Some(self.full(stk, ctx, opt, &tb.expr).await?),
+									..UpsertStatement::default()
+								};
+								// Execute the statement
+								stm.compute(stk, ctx, opt, None).await?;
+							}
+						};
+					}
 				}
 			}
 		}
@@ -288,6 +289,72 @@ impl Document {
 		Ok(())
 	}
 
+	/// Keeps track of a change to this record while the maintenance
+	/// of a view is suspended. The initial value of the record is only
+	/// stored the first time that the record is changed, so that the
+	/// view can catch up with all of the changes when it is resumed.
+	async fn track_view_change(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		rid: &Thing,
+	) -> Result<()> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Store the initial value of the record
+		let key = crate::key::table::vc::new(ns, db, &ft.name, &rid.tb, &rid.id);
+		if !txn.exists(key.clone(), None).await? {
+			txn.set(key, revision::to_vec(self.initial.doc.as_ref())?, None).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Processes a record change which was tracked while the maintenance
+	/// of a view was suspended, bringing the view up to date with the
+	/// current value of the record.
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) async fn process_view_change(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		rid: Arc<Thing>,
+		initial: Value,
+		current: Arc<Value>,
+	) -> Result<()> {
+		// Create a document for the change
+		let mut doc = Document::new(
+			Some(rid.clone()),
+			None,
+			None,
+			current,
+			Workable::Normal,
+			false,
+			RecordStrategy::KeysAndValues,
+		);
+		doc.initial = CursorDoc::new(Some(rid), None, initial);
+		// Skip records which were changed back
+		if !doc.changed() {
+			return Ok(());
+		}
+		// Get the change action
+		let act = if doc.current.doc.as_ref().is_none() {
+			Action::Delete
+		} else if doc.initial.doc.as_ref().is_none() {
+			Action::Create
+		} else {
+			Action::Update
+		};
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Process the view data
+		doc.process_table_view(stk, ctx, opt, ft, act, false).await
+	}
+
 	async fn get_group_ids(
 		stk: &mut Stk,
 		ctx: &Context,
@@ -1,5 +1,6 @@
 use crate::api::err::ApiError;
 use crate::buc::BucketOperation;
+use crate::expr::duration::Duration;
 use crate::expr::idiom::Idiom;
 use crate::expr::index::Distance;
 use crate::expr::thing::Thing;
@@ -43,6 +44,10 @@ pub enum Error {
 	#[error("An error occurred: {0}")]
 	Thrown(String),
 
+	/// A custom error has been thrown with a structured value
+	#[error("An error occurred: {0}")]
+	ThrownValue(Value),
+
 	/// There was a problem with the underlying datastore
 	#[error("There was a problem with the underlying datastore: {0}")]
 	Ds(String),
@@ -127,6 +132,12 @@ pub enum Error {
 		message: String,
 	},
 
+	/// The extended JSON could not be converted into a value
+	#[error("Unable to convert the extended JSON into a value: {message}")]
+	InvalidExtendedJson {
+		message: String,
+	},
+
 	/// Given test operation failed for JSON Patch
 	#[error(
 		"Given test operation failed for JSON Patch. Expected `{expected}`, but got `{got}` instead."
@@ -184,14 +195,14 @@ pub enum Error {
 		field: String,
 	},
 
-	/// The LIMIT clause must evaluate to a positive integer
-	#[error("Found {value} but the LIMIT clause must evaluate to a positive integer")]
+	/// The LIMIT clause must evaluate to a non-negative integer
+	#[error("Found {value} but the LIMIT clause must evaluate to a non-negative integer")]
 	InvalidLimit {
 		value: String,
 	},
 
-	/// The START clause must evaluate to a positive integer
-	#[error("Found {value} but the START clause must evaluate to a positive integer")]
+	/// The START clause must evaluate to a non-negative integer
+	#[error("Found {value} but the START clause must evaluate to a non-negative integer")]
 	InvalidStart {
 		value: String,
 	},
@@ -276,6 +287,13 @@ pub enum Error {
 	#[error("Invalid regular expression: {0:?}")]
 	InvalidRegex(String),
 
+	/// Invalid cron expression
+	#[error("Invalid cron expression '{value}': {message}")]
+	InvalidCron {
+		value: String,
+		message: String,
+	},
+
 	/// Invalid timeout
 	#[error("Invalid timeout: {0:?} seconds")]
 	InvalidTimeout(u64),
@@ -288,14 +306,54 @@ pub enum Error {
 	#[error("The query was not executed because it exceeded the timeout")]
 	QueryTimedout,
 
+	/// The statement was cancelled because it exceeded its timeout
+	#[error(
+		"The statement '{statement}' was cancelled after running for {elapsed}, as it exceeded the timeout of {timeout}"
+	)]
+	StatementTimedout {
+		statement: String,
+		timeout: Duration,
+		elapsed: Duration,
+	},
+
+	/// The sleep duration exceeded a limit set by the capabilities
+	#[error("Unable to sleep for {duration}, as it exceeds the {capability} capability of {limit}")]
+	SleepNotAllowed {
+		capability: &'static str,
+		duration: Duration,
+		limit: Duration,
+	},
+
 	/// The query did not execute, because the transaction was cancelled
 	#[error("The query was not executed due to a cancelled transaction")]
 	QueryCancelled,
 
+	/// The query did not execute, because the datastore is shutting down
+	#[error("The query was not executed because the server is shutting down")]
+	ServerShuttingDown,
+
 	/// The query did not execute, because the memory threshold has been reached
 	#[error("The query was not executed due to the memory threshold being reached")]
 	QueryBeyondMemoryThreshold,
 
+	/// The statement held more memory than the limit set by the capabilities
+	#[error(
+		"The statement was cancelled, as the memory limit was exceeded (used {used} of {limit} bytes)"
+	)]
+	MemoryLimitExceeded {
+		used: u64,
+		limit: u64,
+	},
+
+	/// The sort wrote more to the temporary directory than the limit set by the capabilities
+	#[error(
+		"The statement was cancelled, as the sort exceeded the limit for temporary files (wrote {written} of {limit} bytes)"
+	)]
+	SortSpillExceeded {
+		written: u64,
+		limit: u64,
+	},
+
 	/// The query did not execute, because the transaction has failed
 	#[error("The query was not executed due to a failed transaction")]
 	QueryNotExecuted,
@@ -306,6 +364,14 @@ pub enum Error {
 		message: String,
 	},
 
+	/// The query was executed as read-only, but contained a statement which could modify data
+	#[error(
+		"The statement '{statement}' was not executed, as it could modify data in a read-only query"
+	)]
+	QueryReadonly {
+		statement: String,
+	},
+
 	/// The permissions do not allow for changing to the specified namespace
 	#[error("You don't have permission to change to the {ns} namespace")]
 	NsNotAllowed {
@@ -384,6 +450,12 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested schedule does not exist
+	#[error("The schedule '{name}' does not exist")]
+	ScNotFound {
+		name: String,
+	},
+
 	/// The requested config does not exist
 	#[error("The config for {name} does not exist")]
 	CgNotFound {
@@ -432,6 +504,16 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The WITH INDEX clause of a statement lists an index which does not exist
+	#[error(
+		"The index '{name}' does not exist on the table '{table}' (available indexes: {available})"
+	)]
+	WithIxNotFound {
+		name: String,
+		table: String,
+		available: String,
+	},
+
 	/// The requested record does not exist
 	#[error("The record '{rid}' does not exist")]
 	IdNotFound {
@@ -522,6 +604,15 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not execute RELATE statement with ZIP using lists of different lengths
+	#[error(
+		"Can not execute RELATE statement with ZIP, as there are {from} 'in' records and {with} 'out' records"
+	)]
+	RelateStatementZip {
+		from: usize,
+		with: usize,
+	},
+
 	/// Can not execute DELETE statement using the specified value
 	#[error("Can not execute DELETE statement using value: {value}")]
 	DeleteStatement {
@@ -558,6 +649,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not execute a LIVE statement with a GROUP clause using the specified fields
+	#[error("Can not execute LIVE statement with a GROUP clause, as {message}")]
+	LiveStatementGroup {
+		message: String,
+	},
+
 	/// Can not execute KILL statement using the specified id
 	#[error("Can not execute KILL statement using id: {value}")]
 	KillStatement {
@@ -605,6 +702,26 @@ pub enum Error {
 		thing: Thing,
 	},
 
+	/// A database entry for the specified record has been soft deleted
+	#[error("Database record `{thing}` has been deleted, and can be restored")]
+	RecordDeleted {
+		thing: Thing,
+	},
+
+	/// A database entry for the specified record is not at the expected version
+	#[error("Database record `{thing}` is at version {version}, not at version {expected}")]
+	VersionConflict {
+		thing: Thing,
+		expected: String,
+		version: u64,
+	},
+
+	/// An `IF VERSION` clause was used on a table which does not keep record versions
+	#[error("The table `{name}` does not keep record versions, as it is not defined as VERSIONED")]
+	TbNotVersioned {
+		name: String,
+	},
+
 	/// A database index entry for the specified record already exists
 	#[error("Database index `{index}` already contains {value}, with record `{thing}`")]
 	IndexExists {
@@ -632,6 +749,14 @@ pub enum Error {
 		check: String,
 	},
 
+	/// The record did not conform to the ASSERT clause of its table
+	#[error("Found record `{thing}` in table `{table}`, but the record must conform to: {check}")]
+	TableAssert {
+		thing: String,
+		table: String,
+		check: String,
+	},
+
 	/// The specified value did not conform to the LET type check
 	#[error("Tried to set `${name}`, but couldn't coerce value: {error}")]
 	SetCoerce {
@@ -671,6 +796,15 @@ pub enum Error {
 		field: Idiom,
 	},
 
+	/// A value was written to a computed field, which is never stored
+	#[error(
+		"Found changed value for field `{field}`, with record `{thing}`, but field is computed"
+	)]
+	FieldComputed {
+		thing: String,
+		field: Idiom,
+	},
+
 	/// The specified field on a SCHEMAFUL table was not defined
 	#[error("Found field '{field}', but no such field exists for table '{table}'")]
 	FieldUndefined {
@@ -777,6 +911,14 @@ pub enum Error {
 	#[error("There was an error processing a remote HTTP request: {0}")]
 	Http(String),
 
+	/// A remote HTTP request did not complete within the allowed time
+	#[error("The remote HTTP request to '{0}' timed out")]
+	HttpTimeout(String),
+
+	/// A remote HTTP response body exceeded the allowed size
+	#[error("The remote HTTP response body exceeded the maximum size of {0} bytes")]
+	HttpBodyTooLarge(u64),
+
 	/// There was an error processing a value in parallel
 	#[error("There was an error processing a value in parallel: {0}")]
 	Channel(String),
@@ -807,6 +949,10 @@ pub enum Error {
 		exp: String,
 	},
 
+	/// The BM25 parameters of a search index are out of range
+	#[error("Invalid BM25 parameters: {0}")]
+	InvalidBm25Params(String),
+
 	/// Represents an error when analyzing a value
 	#[error("A value can't be analyzed: {0}")]
 	AnalyzerError(String),
@@ -815,6 +961,13 @@ pub enum Error {
 	#[error("A value can't be highlighted: {0}")]
 	HighlightError(String),
 
+	/// A full-text query string is malformed
+	#[error("Invalid full-text query at character {position}: {message}")]
+	InvalidFtQuery {
+		position: usize,
+		message: String,
+	},
+
 	/// Represents an underlying error with Bincode serializing / deserializing
 	#[error("Bincode error: {0}")]
 	Bincode(#[from] BincodeError),
@@ -877,6 +1030,20 @@ pub enum Error {
 	#[error("Function '{0}' is not allowed to be executed")]
 	FunctionNotAllowed(String),
 
+	/// Function is not allowed by the capabilities of a namespace or database
+	#[error("Function '{name}' is not allowed to be executed in the {scope}")]
+	ScopedFunctionNotAllowed {
+		name: String,
+		scope: String,
+	},
+
+	/// Statement is not allowed by the capabilities of a namespace or database
+	#[error("Statement '{name}' is not allowed to be executed in the {scope}")]
+	StatementNotAllowed {
+		name: String,
+		scope: String,
+	},
+
 	/// Network target is not allowed
 	#[error("Access to network target '{0}' is not allowed")]
 	NetTargetNotAllowed(String),
@@ -917,6 +1084,14 @@ pub enum Error {
 	#[error("There was an unexpected error while performing authentication")]
 	UnexpectedAuth,
 
+	/// Too many authentication attempts have failed recently
+	///
+	/// This error is returned whether or not the user exists
+	#[error("Too many authentication attempts, retry after {retry_after} seconds")]
+	TooManyAuthAttempts {
+		retry_after: u64,
+	},
+
 	/// There was an error with signing up
 	#[error("There was a problem with signing up")]
 	InvalidSignup,
@@ -955,6 +1130,15 @@ pub enum Error {
 		name: String,
 	},
 
+	/// An analyzer can not be changed while full-text indexes use it
+	#[error(
+		"The analyzer '{name}' can not be changed, as it is used by the indexes {indexes}. Remove these indexes first, and define them again once the analyzer is changed"
+	)]
+	AzInUse {
+		name: String,
+		indexes: String,
+	},
+
 	/// The requested api already exists
 	#[error("The bucket '{value}' already exists")]
 	BuAlreadyExists {
@@ -1021,12 +1205,52 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested schedule already exists
+	#[error("The schedule '{name}' already exists")]
+	ScAlreadyExists {
+		name: String,
+	},
+
 	/// The requested table already exists
 	#[error("The table '{name}' already exists")]
 	TbAlreadyExists {
 		name: String,
 	},
 
+	/// A view, or a table which is used by a view, can not be renamed
+	#[error("The table '{name}' can not be renamed, as it is a view or is used by a view")]
+	TbRenameView {
+		name: String,
+	},
+
+	/// A view, or a table which is used by a view, can not be truncated
+	#[error("The table '{name}' can not be truncated, as it is a view or is used by a view")]
+	TbTruncateView {
+		name: String,
+	},
+
+	/// A table or field can not be removed while other definitions depend on it
+	#[error(
+		"The {what} '{name}' can not be removed, as it is used by {dependents}. Use CASCADE to also remove or update these"
+	)]
+	RemoveDependents {
+		what: String,
+		name: String,
+		dependents: String,
+	},
+
+	/// The maintenance of a table can only be controlled if it is a view
+	#[error("The table '{name}' is not a view")]
+	TbNotView {
+		name: String,
+	},
+
+	/// The datastore can not delete key ranges within a transaction
+	#[error(
+		"TRUNCATE can not be used within a manual transaction, as the datastore can not delete key ranges transactionally"
+	)]
+	TbTruncateInTransaction,
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{name}' already exists")]
 	NtAlreadyExists {
@@ -1066,6 +1290,19 @@ pub enum Error {
 		name: String,
 	},
 
+	/// A database index entry for the specified table is not being built
+	#[error("Database index `{name}` is not currently building")]
+	IndexNotBuilding {
+		name: String,
+	},
+
+	/// A database index entry for the specified table can not be rebuilt concurrently
+	#[error("Database index `{name}` can not be rebuilt concurrently: {reason}")]
+	IndexNotRebuildable {
+		name: String,
+		reason: String,
+	},
+
 	/// A database index entry for the specified table is already building
 	#[error("Index building has been cancelled")]
 	IndexingBuildingCancelled,
@@ -1152,6 +1389,20 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The access method already has a key with the same identifier
+	#[error("The access method '{ac}' already has a key with the identifier '{kid}'")]
+	AccessKeyAlreadyExists {
+		ac: String,
+		kid: String,
+	},
+
+	/// The access method does not have a key with the requested identifier
+	#[error("The access method '{ac}' does not have a key with the identifier '{kid}'")]
+	AccessKeyNotFound {
+		ac: String,
+		kid: String,
+	},
+
 	/// The access method cannot be defined on the requested level
 	#[error("The access method cannot be defined on the requested level")]
 	AccessLevelMismatch,
@@ -1261,6 +1512,56 @@ pub enum Error {
 	#[error("Encountered an issue while processed export config: found {0}, but expected {1}.")]
 	InvalidExportConfig(Value, String),
 
+	#[error("Failed to export as Arrow: {0}.")]
+	Arrow(String),
+
+	#[error("Encountered an invalid binary export: {0}.")]
+	InvalidBinaryExport(String),
+
+	/// The binary export was created with a newer version of the format
+	#[error(
+		"The binary export uses format version {version}, but this server only supports up to version {supported}. Import it with a newer version of SurrealDB."
+	)]
+	BinaryExportVersion {
+		version: u16,
+		supported: u16,
+	},
+
+	/// A migration which was already applied was changed since
+	#[error(
+		"The migration '{id}' was changed after it was applied, its checksum was {applied} but is now {current}"
+	)]
+	MigrationChanged {
+		id: String,
+		applied: String,
+		current: String,
+	},
+
+	/// A migration failed, so its changes were not applied
+	#[error("The migration '{id}' failed: {message}")]
+	MigrationFailed {
+		id: String,
+		message: String,
+	},
+
+	/// The same migration was specified more than once
+	#[error("The migration '{id}' is specified more than once")]
+	MigrationDuplicate {
+		id: String,
+	},
+
+	/// The migration to revert has no down query
+	#[error("The migration '{id}' can not be reverted, as it has no down query")]
+	MigrationIrreversible {
+		id: String,
+	},
+
+	/// The applied migration is not one of the specified migrations
+	#[error("The migration '{id}' was applied, but is not one of the specified migrations")]
+	MigrationNotFound {
+		id: String,
+	},
+
 	/// Found an unexpected value in a range
 	#[error("Found {found} for bound but expected {expected}.")]
 	InvalidBound {
@@ -1288,17 +1589,34 @@ pub enum Error {
 	#[error("Tried to use a `@` repeat recurse symbol in a position where it is not supported")]
 	UnsupportedRepeatRecurse,
 
-	#[error("Error while computing version: expected a datetime, but found {found}")]
+	#[error(
+		"Error while computing version: expected a datetime or a versionstamp, but found {found}"
+	)]
 	InvalidVersion {
 		found: Value,
 	},
 
+	/// The requested version is older than the versions which are retained
+	#[error(
+		"The version {version} precedes the retention window of the datastore, which starts at {earliest}"
+	)]
+	VersionBeforeRetention {
+		version: String,
+		earliest: String,
+	},
+
+	/// A statement reading a past version tried to modify data
+	#[error("Can not modify data in a query reading the version {version}")]
+	VersionedMutation {
+		version: String,
+	},
+
 	/// Tried to use an idiom RepeatRecurse symbol in a position where it is not supported
 	#[error("Can not construct a recursion plan when an instruction is provided")]
 	RecursionInstructionPlanConflict,
 
 	/// The record cannot be deleted as it's still referenced elsewhere
-	#[error("Cannot delete `{0}` as it is referenced by `{1}` with an ON DELETE REJECT clause")]
+	#[error("Cannot delete `{0}` as it is referenced by {1} with an ON DELETE REJECT clause")]
 	DeleteRejectedByReference(String, String),
 
 	/// The `REFERENCE` keyword can only be used in combination with a type referencing a record
@@ -1337,6 +1655,13 @@ pub enum Error {
 		existing_kind: String,
 	},
 
+	/// A computed field depends on itself, through the computed fields it refers to
+	#[error("The computed field `{name}` can not depend on itself, found the cycle `{cycle}`")]
+	FdComputedCycle {
+		name: String,
+		cycle: String,
+	},
+
 	#[error("An API error occurred: {0}")]
 	ApiError(ApiError),
 
@@ -1391,7 +1716,9 @@ impl Error {
 			self,
 			Error::FieldCoerce { .. }
 				| Error::FieldValue { .. }
+				| Error::TableAssert { .. }
 				| Error::FieldReadonly { .. }
+				| Error::FieldComputed { .. }
 				| Error::FieldUndefined { .. }
 		)
 	}
@@ -48,6 +48,17 @@ impl Jwt for AccessType {
 	}
 }
 
+impl AccessType {
+	/// Returns the JWT configuration used to verify the tokens of the access method
+	pub(crate) fn jwt_mut(&mut self) -> &mut JwtAccess {
+		match self {
+			AccessType::Record(at) => &mut at.jwt,
+			AccessType::Jwt(at) => at,
+			AccessType::Bearer(at) => &mut at.jwt,
+		}
+	}
+}
+
 impl Display for AccessType {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
@@ -128,7 +139,7 @@ impl AccessType {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct JwtAccess {
@@ -137,6 +148,10 @@ pub struct JwtAccess {
 	// Issue is optional
 	// It is possible to only verify externally issued tokens
 	pub issue: Option<JwtAccessIssue>,
+	// Additional keys which can verify tokens
+	// These allow rotating keys without invalidating the existing tokens
+	#[revision(start = 2)]
+	pub keys: Vec<JwtAccessKey>,
 }
 
 impl Default for JwtAccess {
@@ -154,6 +169,7 @@ impl Default for JwtAccess {
 				alg,
 				key,
 			}),
+			keys: Vec::new(),
 		}
 	}
 }
@@ -174,6 +190,9 @@ impl Display for JwtAccess {
 				write!(f, "URL {}", QuoteStr(&v.url),)?;
 			}
 		}
+		for k in &self.keys {
+			write!(f, " WITH {k}")?;
+		}
 		if let Some(iss) = &self.issue {
 			write!(f, " WITH ISSUER KEY {}", QuoteStr(&iss.key))?;
 		}
@@ -197,6 +216,9 @@ impl InfoStructure for JwtAccess {
 				"alg".to_string() => v.alg.structure(),
 				"key".to_string() => v.key.into(),
 			}),
+			"keys".to_string(), if !self.keys.is_empty() => Value::from(
+				self.keys.into_iter().map(JwtAccessKey::structure).collect::<Vec<_>>()
+			),
 		})
 	}
 }
@@ -223,8 +245,45 @@ impl JwtAccess {
 			}
 			None => None,
 		};
+		for key in jwt.keys.iter_mut() {
+			// If algorithm is symmetric, the verification key is a secret
+			if key.alg.is_symmetric() {
+				key.key = "[REDACTED]".to_string();
+			}
+		}
 		jwt
 	}
+
+	/// Returns the additional key matching a key identifier
+	pub(crate) fn key(&self, kid: &str) -> Option<&JwtAccessKey> {
+		self.keys.iter().find(|k| k.kid == kid)
+	}
+}
+
+/// An additional key, identified by a key identifier, which can verify tokens
+#[revisioned(revision = 1)]
+#[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct JwtAccessKey {
+	pub kid: String,
+	pub alg: Algorithm,
+	pub key: String,
+}
+
+impl Display for JwtAccessKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "KEY {} ALGORITHM {} KEY {}", QuoteStr(&self.kid), self.alg, QuoteStr(&self.key))
+	}
+}
+
+impl InfoStructure for JwtAccessKey {
+	// The key material is never shown, only its identifier and algorithm
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"kid".to_string() => self.kid.into(),
+			"alg".to_string() => self.alg.structure(),
+		})
+	}
 }
 
 #[revisioned(revision = 1)]
@@ -8,7 +8,7 @@ use crate::expr::statements::{
 	AlterStatement, BreakStatement, ContinueStatement, CreateStatement, DefineStatement,
 	DeleteStatement, ForeachStatement, IfelseStatement, InsertStatement, OutputStatement,
 	RelateStatement, RemoveStatement, SelectStatement, SetStatement, ThrowStatement,
-	UpdateStatement, UpsertStatement,
+	TrycatchStatement, UpdateStatement, UpsertStatement,
 };
 use crate::expr::value::Value;
 use reblessive::tree::Stk;
@@ -124,6 +124,9 @@ impl Block {
 				Entry::Info(v) => {
 					v.compute(stk, &ctx, opt, doc).await?;
 				}
+				Entry::Trycatch(v) => {
+					v.compute(stk, &ctx, opt, doc).await?;
+				}
 				Entry::Value(v) => {
 					if i == self.len() - 1 {
 						// If the last entry then return the value
@@ -191,7 +194,7 @@ impl InfoStructure for Block {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -220,6 +223,8 @@ pub enum Entry {
 	Alter(AlterStatement),
 	#[revision(start = 5)]
 	Info(InfoStatement),
+	#[revision(start = 6)]
+	Trycatch(TrycatchStatement),
 }
 
 impl PartialOrd for Entry {
@@ -253,6 +258,7 @@ impl Entry {
 			Self::Foreach(v) => v.writeable(),
 			Self::Alter(v) => v.writeable(),
 			Self::Info(v) => v.writeable(),
+			Self::Trycatch(v) => v.writeable(),
 		}
 	}
 }
@@ -280,6 +286,7 @@ impl Display for Entry {
 			Self::Foreach(v) => write!(f, "{v}"),
 			Self::Alter(v) => write!(f, "{v}"),
 			Self::Info(v) => write!(f, "{v}"),
+			Self::Trycatch(v) => write!(f, "{v}"),
 		}
 	}
 }
@@ -0,0 +1,81 @@
+use crate::dbs::capabilities::{FuncTarget, StatementTarget, Target};
+use crate::expr::fmt::Fmt;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Strand, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The functions and statements which are allowed or denied within a namespace or database,
+/// on top of the capabilities of the datastore.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ScopeCapabilities {
+	pub allow_functions: Vec<Strand>,
+	pub deny_functions: Vec<Strand>,
+	pub allow_statements: Vec<Strand>,
+	pub deny_statements: Vec<Strand>,
+}
+
+impl ScopeCapabilities {
+	/// Checks if any rule of these capabilities allows something
+	pub(crate) fn has_allow_rules(&self) -> bool {
+		!self.allow_functions.is_empty() || !self.allow_statements.is_empty()
+	}
+	/// Checks if a function is allowed, or returns `None` when no rule matches the function
+	pub(crate) fn allows_function(&self, target: &str) -> Option<bool> {
+		let matches =
+			|v: &Strand| v.as_str().parse::<FuncTarget>().is_ok_and(|t| t.matches(target));
+		Self::decide(
+			self.allow_functions.iter().any(matches),
+			self.deny_functions.iter().any(matches),
+		)
+	}
+	/// Checks if a statement is allowed, or returns `None` when no rule matches the statement
+	pub(crate) fn allows_statement(&self, target: &StatementTarget) -> Option<bool> {
+		let matches =
+			|v: &Strand| v.as_str().parse::<StatementTarget>().is_ok_and(|t| t.matches(target));
+		Self::decide(
+			self.allow_statements.iter().any(matches),
+			self.deny_statements.iter().any(matches),
+		)
+	}
+	/// A deny rule always takes precedence over an allow rule of the same scope
+	fn decide(allowed: bool, denied: bool) -> Option<bool> {
+		match (allowed, denied) {
+			(_, true) => Some(false),
+			(true, false) => Some(true),
+			(false, false) => None,
+		}
+	}
+}
+
+impl Display for ScopeCapabilities {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "CAPABILITIES")?;
+		for (rule, targets) in [
+			("ALLOW FUNCTIONS", &self.allow_functions),
+			("DENY FUNCTIONS", &self.deny_functions),
+			("ALLOW STATEMENTS", &self.allow_statements),
+			("DENY STATEMENTS", &self.deny_statements),
+		] {
+			if !targets.is_empty() {
+				write!(f, " {rule} {}", Fmt::comma_separated(targets))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for ScopeCapabilities {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"allow_functions".to_string(), if !self.allow_functions.is_empty() => self.allow_functions.into(),
+			"deny_functions".to_string(), if !self.deny_functions.is_empty() => self.deny_functions.into(),
+			"allow_statements".to_string(), if !self.allow_statements.is_empty() => self.allow_statements.into(),
+			"deny_statements".to_string(), if !self.deny_statements.is_empty() => self.deny_statements.into(),
+		})
+	}
+}
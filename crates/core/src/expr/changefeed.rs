@@ -7,19 +7,25 @@ use std::fmt::{self, Display, Formatter};
 use std::str;
 use std::time;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[non_exhaustive]
 pub struct ChangeFeed {
 	pub expiry: time::Duration,
 	#[revision(start = 2)]
 	pub store_diff: bool,
+	/// Whether the identity, the origin, and the statement of each change are stored
+	#[revision(start = 3)]
+	pub store_audit: bool,
 }
 impl Display for ChangeFeed {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		write!(f, "CHANGEFEED {}", Duration(self.expiry))?;
-		if self.store_diff {
-			write!(f, " INCLUDE ORIGINAL")?;
+		match (self.store_diff, self.store_audit) {
+			(true, true) => write!(f, " INCLUDE ORIGINAL, AUDIT")?,
+			(true, false) => write!(f, " INCLUDE ORIGINAL")?,
+			(false, true) => write!(f, " INCLUDE AUDIT")?,
+			(false, false) => (),
 		};
 		Ok(())
 	}
@@ -30,6 +36,7 @@ impl Default for ChangeFeed {
 		Self {
 			expiry: time::Duration::from_secs(0),
 			store_diff: false,
+			store_audit: false,
 		}
 	}
 }
@@ -39,6 +46,7 @@ impl InfoStructure for ChangeFeed {
 		Value::from(map! {
 			"expiry".to_string() => Duration(self.expiry).structure(),
 			"original".to_string() => self.store_diff.into(),
+			"audit".to_string(), if self.store_audit => true.into(),
 		})
 	}
 }
@@ -2,12 +2,15 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::expr::table::Tables;
 use crate::expr::thing::Thing;
+use crate::key::graph;
+use crate::kvs::Key;
 use crate::{ctx::Context, expr::dir::Dir};
 use anyhow::Result;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::ops::Range;
 
 use super::Value;
 use super::graph::GraphSubjects;
@@ -42,6 +45,53 @@ impl Edges {
 		Ok(())
 	}
 
+	/// The ranges of the graph keys which hold the edges
+	pub(crate) fn key_ranges(&self, ns: &str, db: &str) -> Result<Vec<Range<Key>>> {
+		let tb = &self.from.tb;
+		let id = &self.from.id;
+		let keys = match self.what.len() {
+			0 => match self.dir {
+				// /ns/db/tb/id
+				Dir::Both => {
+					vec![(graph::prefix(ns, db, tb, id), graph::suffix(ns, db, tb, id))]
+				}
+				// /ns/db/tb/id/IN
+				Dir::In => vec![(
+					graph::egprefix(ns, db, tb, id, &self.dir),
+					graph::egsuffix(ns, db, tb, id, &self.dir),
+				)],
+				// /ns/db/tb/id/OUT
+				Dir::Out => vec![(
+					graph::egprefix(ns, db, tb, id, &self.dir),
+					graph::egsuffix(ns, db, tb, id, &self.dir),
+				)],
+			},
+			_ => match self.dir {
+				// /ns/db/tb/id/IN/TB
+				Dir::In => self
+					.what
+					.iter()
+					.map(|v| v.presuf(ns, db, tb, id, &self.dir))
+					.collect::<Vec<_>>(),
+				// /ns/db/tb/id/OUT/TB
+				Dir::Out => self
+					.what
+					.iter()
+					.map(|v| v.presuf(ns, db, tb, id, &self.dir))
+					.collect::<Vec<_>>(),
+				// /ns/db/tb/id/IN/TB, /ns/db/tb/id/OUT/TB
+				Dir::Both => self
+					.what
+					.iter()
+					.flat_map(|v| {
+						[v.presuf(ns, db, tb, id, &Dir::In), v.presuf(ns, db, tb, id, &Dir::Out)]
+					})
+					.collect::<Vec<_>>(),
+			},
+		};
+		keys.into_iter().map(|(beg, end)| Ok(beg?..end?)).collect()
+	}
+
 	pub(crate) async fn compute(
 		&self,
 		stk: &mut Stk,
@@ -185,7 +185,7 @@ impl Expression {
 					Operator::NoneInside => fnc::operate::inside_none(&l, &r),
 					Operator::Outside => fnc::operate::outside(&l, &r),
 					Operator::Intersects => fnc::operate::intersects(&l, &r),
-					Operator::Matches(_) => {
+					Operator::Matches(..) => {
 						fnc::operate::matches(stk, ctx, opt, doc, self, l, r).await
 					}
 					Operator::Knn(_, _) | Operator::Ann(_, _) => {
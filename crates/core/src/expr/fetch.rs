@@ -41,19 +41,41 @@ impl fmt::Display for Fetchs {
 	}
 }
 
+impl Fetchs {
+	/// Computes the paths to fetch, along with the depth each path is fetched to
+	pub(crate) async fn paths(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Vec<(Idiom, u32)>> {
+		let mut paths = Vec::with_capacity(self.0.len());
+		let mut idioms = Vec::new();
+		for fetch in self.iter() {
+			fetch.compute(stk, ctx, opt, &mut idioms).await?;
+			let depth = fetch.1.unwrap_or(1);
+			paths.extend(idioms.drain(..).map(|i| (i, depth)));
+		}
+		Ok(paths)
+	}
+}
+
 impl InfoStructure for Fetchs {
 	fn structure(self) -> Value {
 		self.into_iter().map(Fetch::structure).collect::<Vec<_>>().into()
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Fetch(
 	#[revision(end = 2, convert_fn = "convert_fetch_idiom")] pub Idiom,
 	#[revision(start = 2)] pub Value,
+	/// The number of times the path is fetched again from the fetched records
+	#[revision(start = 3)]
+	pub Option<u32>,
 );
 
 impl Fetch {
@@ -138,7 +160,7 @@ impl Fetch {
 
 impl From<Value> for Fetch {
 	fn from(value: Value) -> Self {
-		Self(value)
+		Self(value, None)
 	}
 }
 
@@ -151,7 +173,11 @@ impl Deref for Fetch {
 
 impl Display for Fetch {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		Display::fmt(&self.0, f)
+		Display::fmt(&self.0, f)?;
+		if let Some(depth) = self.1 {
+			write!(f, " DEPTH {depth}")?;
+		}
+		Ok(())
 	}
 }
 
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Idiom, Part, Value, fmt::Fmt};
+use crate::expr::{Idiom, Part, Value, Window, fmt::Fmt};
 use crate::syn;
 use anyhow::Result;
 use reblessive::tree::Stk;
@@ -55,6 +55,10 @@ impl Fields {
 			_ => None,
 		}
 	}
+	/// Check to see if any of the fields is a window function
+	pub(crate) fn has_windows(&self) -> bool {
+		self.0.iter().any(|v| matches!(v, Field::Window { .. }))
+	}
 	/// Check if the fields are only about counting
 	pub(crate) fn is_count_all_only(&self) -> bool {
 		let mut is_count_only = false;
@@ -141,6 +145,30 @@ impl Fields {
 		for v in self.other() {
 			match v {
 				Field::All => (),
+				Field::Window {
+					expr,
+					window,
+					alias,
+				} => {
+					// Windows over grouped rows are computed from the grouped output
+					if group {
+						continue;
+					}
+					let Value::Function(f) = expr else {
+						fail!("Expected a function call for a window field: {expr}");
+					};
+					let name = alias
+						.as_ref()
+						.map(Cow::Borrowed)
+						.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+					// Store the row inputs until the window is computed over all rows
+					let x = window.input(stk, ctx, opt, doc, f).await?;
+					// Check if this is a single VALUE field expression
+					match self.single().is_some() {
+						false => out.set(stk, ctx, opt, name.as_ref(), x).await?,
+						true => out = x,
+					}
+				}
 				Field::Single {
 					expr,
 					alias,
@@ -287,7 +315,7 @@ impl Fields {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -301,6 +329,14 @@ pub enum Field {
 		/// The `quality` in `SELECT rating AS quality FROM ...`
 		alias: Option<Idiom>,
 	},
+	/// The 'rank() OVER (ORDER BY score)' in `SELECT rank() OVER (ORDER BY score) FROM ...`
+	#[revision(start = 2)]
+	Window {
+		expr: Value,
+		window: Window,
+		/// The `position` in `SELECT rank() OVER (ORDER BY score) AS position FROM ...`
+		alias: Option<Idiom>,
+	},
 }
 
 impl Display for Field {
@@ -319,6 +355,19 @@ impl Display for Field {
 					Ok(())
 				}
 			}
+			Self::Window {
+				expr,
+				window,
+				alias,
+			} => {
+				write!(f, "{expr} {window}")?;
+				if let Some(alias) = alias {
+					f.write_str(" AS ")?;
+					Display::fmt(alias, f)
+				} else {
+					Ok(())
+				}
+			}
 		}
 	}
 }
@@ -1,10 +1,13 @@
+use crate::expr::escape::QuoteStr;
+use crate::expr::fmt::Fmt;
 use crate::expr::language::Language;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +19,15 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	/// Removes the tokens which are in the list
+	#[revision(start = 2)]
+	Stopwords(Vec<String>),
+	/// Replaces the substrings of each token with their mapped values
+	#[revision(start = 2)]
+	Mapping(BTreeMap<String, String>),
+	/// Removes the tokens whose number of characters is outside of the range
+	#[revision(start = 2)]
+	Length(u16, u16),
 }
 
 impl Display for Filter {
@@ -28,6 +40,23 @@ impl Display for Filter {
 			Self::Snowball(lang) => write!(f, "SNOWBALL({lang})"),
 			Self::Uppercase => f.write_str("UPPERCASE"),
 			Self::Mapper(path) => write!(f, "MAPPER({path})"),
+			Self::Stopwords(words) => {
+				write!(
+					f,
+					"STOPWORDS([{}])",
+					Fmt::comma_separated(words.iter().map(|w| QuoteStr(w)))
+				)
+			}
+			Self::Mapping(map) => write!(
+				f,
+				"MAPPING({{ {} }})",
+				Fmt::comma_separated(map.iter().map(|(k, v)| format!(
+					"{}: {}",
+					QuoteStr(k),
+					QuoteStr(v)
+				)))
+			),
+			Self::Length(min, max) => write!(f, "LENGTH({min},{max})"),
 		}
 	}
 }
@@ -0,0 +1,297 @@
+//! Aggregate functions over graph traversals.
+//!
+//! An aggregate function like `count(->ordered->product)` or
+//! `math::sum(->ordered->order_line.quantity)` would otherwise collect all of
+//! the records reached by the traversal into a single array before being
+//! called. Instead the graph edges are scanned in batches, and the values
+//! reached are folded into the result of the function as they are found.
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::graph::Graph;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	Edges, Field, Fields, FlowResultExt as _, Id, Idiom, Number, Part, Thing, Value, Values,
+};
+use crate::key::graph;
+use anyhow::Result;
+use reblessive::tree::Stk;
+
+/// The result of an aggregate function, as it is being folded
+enum Fold {
+	Count(i64),
+	Sum(Number),
+	Min(Option<Number>),
+	Max(Option<Number>),
+	Mean(f64, usize),
+}
+
+impl Fold {
+	fn new(name: &str) -> Option<Self> {
+		match name {
+			"count" => Some(Self::Count(0)),
+			"math::sum" => Some(Self::Sum(Number::Int(0))),
+			"math::min" => Some(Self::Min(None)),
+			"math::max" => Some(Self::Max(None)),
+			"math::mean" => Some(Self::Mean(0.0, 0)),
+			_ => None,
+		}
+	}
+
+	/// Adds a value to the result, returning false if the
+	/// function would not accept the value in an array
+	fn push(&mut self, value: Value) -> bool {
+		// Nested arrays are left to the function itself
+		if matches!(value, Value::Array(_)) {
+			return false;
+		}
+		if let Self::Count(count) = self {
+			*count += value.is_truthy() as i64;
+			return true;
+		}
+		let Ok(number) = value.coerce_to::<Number>() else {
+			return false;
+		};
+		match self {
+			Self::Count(_) => (),
+			Self::Sum(sum) => *sum = std::mem::replace(sum, Number::Int(0)) + number,
+			Self::Min(min) => {
+				// The first of the smallest numbers is kept
+				if min.as_ref().is_none_or(|min| number < *min) {
+					*min = Some(number);
+				}
+			}
+			Self::Max(max) => {
+				// The last of the largest numbers is kept
+				if max.as_ref().is_none_or(|max| number >= *max) {
+					*max = Some(number);
+				}
+			}
+			Self::Mean(sum, count) => {
+				*sum += number.to_float();
+				*count += 1;
+			}
+		}
+		true
+	}
+
+	fn finish(self) -> Value {
+		match self {
+			Self::Count(count) => count.into(),
+			Self::Sum(sum) => sum.into(),
+			Self::Min(v) | Self::Max(v) => v.map(Value::from).unwrap_or_default(),
+			// This is NaN when no numbers were found
+			Self::Mean(sum, count) => (sum / count as f64).into(),
+		}
+	}
+}
+
+/// A graph traversal which can be folded, with the
+/// fields which are selected from the reached records
+struct Traversal<'a> {
+	graphs: Vec<&'a Graph>,
+	fields: &'a [Part],
+}
+
+impl<'a> Traversal<'a> {
+	fn new(parts: &'a [Part]) -> Option<Self> {
+		let mut graphs = Vec::new();
+		let mut rest = parts;
+		while let Some((Part::Graph(g), next)) = rest.split_first() {
+			// Only the edges and the records can be filtered
+			if g.expr.is_some()
+				|| g.split.is_some()
+				|| g.group.is_some()
+				|| g.order.is_some()
+				|| g.limit.is_some()
+				|| g.start.is_some()
+				|| g.alias.is_some()
+			{
+				return None;
+			}
+			graphs.push(g);
+			rest = next;
+		}
+		if graphs.is_empty() || !rest.iter().all(|p| matches!(p, Part::Field(_))) {
+			return None;
+		}
+		Some(Self {
+			graphs,
+			fields: rest,
+		})
+	}
+
+	/// Follows the edges of a record for the traversal step at
+	/// the given depth, returning false if a reached value can
+	/// not be folded
+	async fn fold(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		from: Thing,
+		depth: usize,
+		fold: &mut Fold,
+	) -> Result<bool> {
+		let g = self.graphs[depth];
+		let last = depth + 1 == self.graphs.len();
+		let edges = Edges {
+			from,
+			dir: g.dir.clone(),
+			what: g.what.clone().compute(stk, ctx, opt, doc).await?,
+		};
+		// The fields which are selected from the reached records
+		let expr = match (last, self.fields.is_empty()) {
+			(true, false) => Fields(
+				vec![Field::Single {
+					expr: Value::Idiom(Idiom(self.fields.to_vec())),
+					alias: None,
+				}],
+				true,
+			),
+			_ => Fields::value_id(),
+		};
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		txn.check_ns_db_tb(ns, db, &edges.from.tb, opt.strict).await?;
+		for rng in edges.key_ranges(ns, db)? {
+			let (mut beg, end) = (rng.start, rng.end);
+			loop {
+				// Check if the context is finished
+				if ctx.is_done(true).await? {
+					return Ok(true);
+				}
+				let keys =
+					txn.keys(beg.clone()..end.clone(), *NORMAL_FETCH_SIZE, opt.version).await?;
+				let Some(next) = keys.last() else {
+					break;
+				};
+				// The next batch starts right after the last key
+				beg = next.clone();
+				beg.push(0x00);
+				let ids = keys
+					.iter()
+					.map(|k| {
+						let gra = graph::Graph::decode(k)?;
+						Ok(Value::Thing(Thing::from((gra.ft, gra.fk))))
+					})
+					.collect::<Result<Vec<_>>>()?;
+				// Select the reached records which can be seen, and which match the filter
+				let stm = SelectStatement {
+					expr: expr.clone(),
+					what: Values(ids),
+					cond: g.cond.clone(),
+					..SelectStatement::default()
+				};
+				let Value::Array(found) = stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?
+				else {
+					continue;
+				};
+				for v in found {
+					let done = match (last, v) {
+						(true, v) => fold.push(v),
+						(false, Value::Thing(v)) => {
+							stk.run(|stk| self.fold(stk, ctx, opt, doc, v, depth + 1, fold)).await?
+						}
+						(false, _) => true,
+					};
+					if !done {
+						return Ok(false);
+					}
+				}
+			}
+		}
+		Ok(true)
+	}
+}
+
+/// Runs an aggregate function over a graph traversal without
+/// collecting the traversed records, returning `None` when the
+/// function and its argument can not be folded
+pub(crate) async fn aggregate(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	doc: Option<&CursorDoc>,
+	name: &str,
+	args: &[Value],
+) -> Result<Option<Value>> {
+	let Some(mut fold) = Fold::new(name) else {
+		return Ok(None);
+	};
+	let [Value::Idiom(idiom)] = args else {
+		return Ok(None);
+	};
+	// Find the record which the traversal starts from
+	let (from, parts) = match idiom.split_first() {
+		Some((Part::Start(v @ (Value::Thing(_) | Value::Param(_))), parts)) => {
+			(stk.run(|stk| v.compute(stk, ctx, opt, doc)).await.catch_return()?, parts)
+		}
+		Some((Part::Graph(_), _)) => match doc {
+			Some(doc) => (doc.doc.as_ref().rid(), idiom.0.as_slice()),
+			None => return Ok(None),
+		},
+		_ => return Ok(None),
+	};
+	let Value::Thing(from) = from else {
+		return Ok(None);
+	};
+	if matches!(from.id, Id::Range(_)) {
+		return Ok(None);
+	}
+	let Some(traversal) = Traversal::new(parts) else {
+		return Ok(None);
+	};
+	match traversal.fold(stk, ctx, opt, doc, from, 0, &mut fold).await? {
+		true => Ok(Some(fold.finish())),
+		false => Ok(None),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+
+	#[tokio::test]
+	async fn aggregates_are_folded_along_traversals() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			CREATE person:1;
+			FOR $i IN 1..=1000 {
+				RELATE person:1->ordered->(CREATE ONLY order_line SET quantity = $i % 10)
+					SET rush = $i % 4 = 0;
+			};
+		";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let sql = "
+			RETURN count(person:1->ordered->order_line);
+			RETURN array::len(person:1->ordered->order_line);
+			RETURN math::sum(person:1->ordered->order_line.quantity);
+			RETURN person:1->ordered->order_line.quantity;
+			RETURN math::max(person:1->ordered->order_line.quantity);
+			RETURN math::sum(person:1->(ordered WHERE rush)->order_line.quantity);
+			RETURN count(person:1->ordered->(order_line WHERE quantity > 4));
+		";
+		let mut res = ds.execute(sql, &sess.with_stats(true), None).await.unwrap();
+		let stats: Vec<_> = res.iter().map(|r| r.stats.unwrap()).collect();
+		let mut results = res.drain(..).map(|r| r.result.unwrap());
+		assert_eq!(results.next(), Some(Value::from(1000)));
+		assert_eq!(results.next(), Some(Value::from(1000)));
+		assert_eq!(results.next(), Some(Value::from(4500)));
+		assert!(matches!(results.next(), Some(Value::Array(v)) if v.len() == 1000));
+		assert_eq!(results.next(), Some(Value::from(9)));
+		// Every fourth order has an even quantity from 0 to 8
+		assert_eq!(results.next(), Some(Value::from(1000)));
+		assert_eq!(results.next(), Some(Value::from(500)));
+		// The traversed records are not all held at once
+		assert!(stats[2].peak_memory > 0);
+		assert!(stats[2].peak_memory * 2 < stats[3].peak_memory);
+	}
+}
@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::Permission;
 use crate::expr::fmt::Fmt;
+use crate::expr::fold;
 use crate::expr::idiom::Idiom;
 use crate::expr::operator::BindingPower;
 use crate::expr::script::Script;
@@ -18,7 +19,7 @@ use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::fmt;
 
-use super::{ControlFlow, FlowResult, FlowResultExt as _, Kind};
+use super::{ControlFlow, FlowResult, FlowResultExt as _};
 
 pub(crate) const TOKEN: &str = "$surrealdb::private::sql::Function";
 
@@ -231,7 +232,11 @@ impl Function {
 		match self {
 			Self::Normal(s, x) => {
 				// Check this function is allowed
-				ctx.check_allowed_function(s)?;
+				ctx.check_allowed_function(opt, s).await?;
+				// Aggregate a graph traversal as it is traversed
+				if let Some(v) = fold::aggregate(stk, ctx, opt, doc, s, x).await? {
+					return Ok(v);
+				}
 				// Compute the function arguments
 				let a = stk
 					.scope(|scope| {
@@ -281,10 +286,51 @@ impl Function {
 				// Get the full name of this function
 				let name = format!("fn::{s}");
 				// Check this function is allowed
-				ctx.check_allowed_function(name.as_str())?;
-				// Get the function definition
-				let (ns, db) = opt.ns_db()?;
-				let val = ctx.tx().get_db_function(ns, db, s).await?;
+				ctx.check_allowed_function(opt, name.as_str()).await?;
+				// Get the function definition, which takes precedence over a registered function
+				let fc = match opt.ns_db() {
+					Ok((ns, db)) => match ctx.tx().get_db_function(ns, db, s).await {
+						Err(e) if !matches!(e.downcast_ref(), Some(Error::FcNotFound { .. })) => {
+							return Err(ControlFlow::from(e));
+						}
+						res => res,
+					},
+					Err(e) => Err(e),
+				};
+				let fc = match fc {
+					Ok(fc) => fc,
+					Err(e) => {
+						// Check for a function registered with the datastore
+						let Some(f) = ctx.get_functions().and_then(|r| r.get(s)) else {
+							return Err(ControlFlow::from(e));
+						};
+						// Compute the function arguments
+						let a = stk
+							.scope(|scope| {
+								try_join_all(
+									x.iter()
+										.map(|v| scope.run(|stk| v.compute(stk, ctx, opt, doc))),
+								)
+							})
+							.await?;
+						// Check and coerce the function arguments
+						let a = f.coerce_args(a)?;
+						// Run the registered function
+						return Ok(f.run(a).await?);
+					}
+				};
+				// Get the signature for the number of arguments
+				let val = match fc.signature(x.len()) {
+					Some(val) => val,
+					None => {
+						return Err(ControlFlow::from(anyhow::Error::new(
+							Error::InvalidArguments {
+								name: format!("fn::{}", fc.name),
+								message: fc.arguments_message(),
+							},
+						)));
+					}
+				};
 				// Check permissions
 				if opt.check_perms(Action::View)? {
 					match &val.permissions {
@@ -310,27 +356,6 @@ impl Function {
 						}
 					}
 				}
-				// Get the number of function arguments
-				let max_args_len = val.args.len();
-				// Track the number of required arguments
-				let mut min_args_len = 0;
-				// Check for any final optional arguments
-				val.args.iter().rev().for_each(|(_, kind)| match kind {
-					Kind::Option(_) if min_args_len == 0 => {}
-					Kind::Any if min_args_len == 0 => {}
-					_ => min_args_len += 1,
-				});
-				// Check the necessary arguments are passed
-				if x.len() < min_args_len || max_args_len < x.len() {
-					return Err(ControlFlow::from(anyhow::Error::new(Error::InvalidArguments {
-						name: format!("fn::{}", val.name),
-						message: match (min_args_len, max_args_len) {
-							(1, 1) => String::from("The function expects 1 argument."),
-							(r, t) if r == t => format!("The function expects {r} arguments."),
-							(r, t) => format!("The function expects {r} to {t} arguments."),
-						},
-					})));
-				}
 				// Compute the function arguments
 				let a = stk
 					.scope(|scope| {
@@ -8,7 +8,7 @@ use crate::expr::field::Fields;
 use crate::expr::group::Groups;
 use crate::expr::idiom::Idiom;
 use crate::expr::limit::Limit;
-use crate::expr::order::{OldOrders, Order, OrderList, Ordering};
+use crate::expr::order::{Collate, OldOrders, Order, OrderList, Ordering};
 use crate::expr::split::Splits;
 use crate::expr::start::Start;
 use crate::expr::table::Tables;
@@ -69,9 +69,10 @@ impl Graph {
 			x.0.into_iter()
 				.map(|x| Order {
 					value: x.order,
-					collate: x.collate,
+					collate: x.collate.then_some(Collate::Lexical),
 					numeric: x.numeric,
 					direction: x.direction,
+					nulls: None,
 				})
 				.collect();
 
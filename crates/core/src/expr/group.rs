@@ -1,5 +1,8 @@
+use crate::expr::Value;
 use crate::expr::fmt::Fmt;
 use crate::expr::idiom::Idiom;
+use crate::expr::order::Collate;
+use crate::expr::statements::info::InfoStructure;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
@@ -36,11 +39,22 @@ impl Display for Groups {
 	}
 }
 
-#[revisioned(revision = 1)]
+impl InfoStructure for Groups {
+	fn structure(self) -> Value {
+		self.into_iter().map(|v| v.0.structure()).collect::<Vec<_>>().into()
+	}
+}
+
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Group(pub Idiom);
+pub struct Group(
+	pub Idiom,
+	/// The collation which the grouped strings are compared with
+	#[revision(start = 2)]
+	pub Option<Collate>,
+);
 
 impl Deref for Group {
 	type Target = Idiom;
@@ -49,8 +63,26 @@ impl Deref for Group {
 	}
 }
 
+impl Group {
+	/// Folds a grouped value with the collation of this group, so that
+	/// the strings which compare as equal are placed in the same group
+	pub(crate) fn key(&self, val: Value) -> Value {
+		match (self.1, val) {
+			// Lexical strings are only equal when they are identical
+			(Some(collate @ (Collate::NoCase | Collate::Unicode)), Value::Strand(s)) => {
+				Value::from(collate.fold(&s))
+			}
+			(_, val) => val,
+		}
+	}
+}
+
 impl Display for Group {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		Display::fmt(&self.0, f)
+		Display::fmt(&self.0, f)?;
+		if let Some(collate) = &self.1 {
+			write!(f, " {collate}")?;
+		}
+		Ok(())
 	}
 }
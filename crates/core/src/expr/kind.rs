@@ -16,7 +16,7 @@ use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter, Write};
 
 /// The kind, or data type, of a value or field.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -65,9 +65,17 @@ pub enum Kind {
 	/// Can be any of the kinds in the vec.
 	Either(Vec<Kind>),
 	/// A set type.
+	#[revision(end = 3, convert_fn = "convert_old_set", fields_name = "OldSetFields")]
 	Set(Box<Kind>, Option<u64>),
+	/// A set type, whose duplicated elements are removed.
+	#[revision(start = 3)]
+	Set(Box<Kind>, ArrayConstraints),
 	/// An array type.
+	#[revision(end = 3, convert_fn = "convert_old_array", fields_name = "OldArrayFields")]
 	Array(Box<Kind>, Option<u64>),
+	/// An array type.
+	#[revision(start = 3)]
+	Array(Box<Kind>, ArrayConstraints),
 	/// A function type.
 	/// The first option is the argument types, the second is the optional return type.
 	Function(Option<Vec<Kind>>, Option<Box<Kind>>),
@@ -93,6 +101,14 @@ impl Default for Kind {
 }
 
 impl Kind {
+	fn convert_old_set(fields: OldSetFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(Kind::Set(fields.0, ArrayConstraints::from_len(fields.1)))
+	}
+
+	fn convert_old_array(fields: OldArrayFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(Kind::Array(fields.0, ArrayConstraints::from_len(fields.1)))
+	}
+
 	/// Returns the kind of a type.
 	pub(crate) fn of<T: HasKind>() -> Kind {
 		T::kind()
@@ -130,6 +146,23 @@ impl Kind {
 		false
 	}
 
+	/// Returns the values which this type allows, if this type is
+	/// a literal, or a union of literals such as `"draft" | "published"`
+	pub(crate) fn literal_variants(&self) -> Option<Vec<Value>> {
+		match self {
+			Kind::Option(x) => x.literal_variants(),
+			Kind::Literal(x) => x.to_value().map(|v| vec![v]),
+			Kind::Either(x) => x
+				.iter()
+				.map(|x| match x {
+					Kind::Literal(x) => x.to_value(),
+					_ => None,
+				})
+				.collect(),
+			_ => None,
+		}
+	}
+
 	/// Returns true if this type is a set or array.
 	pub(crate) fn is_array_like(&self) -> bool {
 		matches!(self, Kind::Array(_, _) | Kind::Set(_, _) | Kind::Literal(Literal::Array(_)))
@@ -182,6 +215,45 @@ impl Kind {
 		}
 	}
 
+	/// Replaces any references to a table in this type, returning true if the type changed
+	pub(crate) fn rename_table(&mut self, from: &str, to: &str) -> bool {
+		match self {
+			Kind::Record(tables) => {
+				let mut changed = false;
+				for table in tables.iter_mut().filter(|t| t.0 == from) {
+					*table = Table::from(to);
+					changed = true;
+				}
+				changed
+			}
+			Kind::References(Some(table), _) if table.0 == from => {
+				*table = Table::from(to);
+				true
+			}
+			Kind::Option(x) | Kind::Array(x, _) | Kind::Set(x, _) => x.rename_table(from, to),
+			Kind::Either(x) => x.iter_mut().fold(false, |c, x| x.rename_table(from, to) || c),
+			_ => false,
+		}
+	}
+
+	/// Removes any references to a table from this type, returning true if the type changed
+	pub(crate) fn remove_table(&mut self, name: &str) -> bool {
+		match self {
+			Kind::Record(tables) => {
+				let len = tables.len();
+				tables.retain(|t| t.0 != name);
+				tables.len() != len
+			}
+			Kind::References(Some(table), _) if table.0 == name => {
+				*self = Kind::References(None, None);
+				true
+			}
+			Kind::Option(x) | Kind::Array(x, _) | Kind::Set(x, _) => x.remove_table(name),
+			Kind::Either(x) => x.iter_mut().fold(false, |c, x| x.remove_table(name) || c),
+			_ => false,
+		}
+	}
+
 	/// Get the inner kind of a [`Kind::Option`] or return the original [`Kind`] if it is not the Option variant.
 	pub(crate) fn get_optional_inner_kind(&self) -> &Kind {
 		match self {
@@ -202,12 +274,12 @@ impl Kind {
 				Kind::Either(kinds) => {
 					return kinds.iter().all(|k| k.allows_nested_kind(path, kind));
 				}
-				Kind::Array(inner, len) | Kind::Set(inner, len) => {
+				Kind::Array(inner, constraints) | Kind::Set(inner, constraints) => {
 					return match path.first() {
 						Some(Part::All) => inner.allows_nested_kind(&path[1..], kind),
 						Some(Part::Index(i)) => {
-							if let Some(len) = len {
-								if i.as_usize() >= *len as usize {
+							if let Some(max) = constraints.max {
+								if i.as_usize() >= max as usize {
 									return false;
 								}
 							}
@@ -255,20 +327,20 @@ impl<T: HasKind> HasKind for Option<T> {
 impl<T: HasKind> HasKind for Vec<T> {
 	fn kind() -> Kind {
 		let kind = T::kind();
-		Kind::Array(Box::new(kind), None)
+		Kind::Array(Box::new(kind), ArrayConstraints::default())
 	}
 }
 
 impl HasKind for Array {
 	fn kind() -> Kind {
-		Kind::Array(Box::new(Kind::Any), None)
+		Kind::Array(Box::new(Kind::Any), ArrayConstraints::default())
 	}
 }
 
 impl<T: HasKind, const SIZE: usize> HasKind for [T; SIZE] {
 	fn kind() -> Kind {
 		let kind = T::kind();
-		Kind::Array(Box::new(kind), Some(SIZE as u64))
+		Kind::Array(Box::new(kind), ArrayConstraints::exact(SIZE as u64))
 	}
 }
 
@@ -394,16 +466,22 @@ impl Display for Kind {
 					write!(f, "geometry<{}>", Fmt::verbar_separated(k))
 				}
 			}
-			Kind::Set(k, l) => match (k, l) {
-				(k, None) if k.is_any() => write!(f, "set"),
-				(k, None) => write!(f, "set<{k}>"),
-				(k, Some(l)) => write!(f, "set<{k}, {l}>"),
-			},
-			Kind::Array(k, l) => match (k, l) {
-				(k, None) if k.is_any() => write!(f, "array"),
-				(k, None) => write!(f, "array<{k}>"),
-				(k, Some(l)) => write!(f, "array<{k}, {l}>"),
+			Kind::Set(k, c) => match (k, c.is_unbounded()) {
+				(k, true) if k.is_any() => f.write_str("set"),
+				(k, true) => write!(f, "set<{k}>"),
+				(k, false) => write!(f, "set<{k}, {c}>"),
 			},
+			Kind::Array(k, c) => {
+				match (k, c.is_unbounded()) {
+					(k, true) if k.is_any() => f.write_str("array")?,
+					(k, true) => write!(f, "array<{k}>")?,
+					(k, false) => write!(f, "array<{k}, {c}>")?,
+				}
+				if c.unique {
+					f.write_str(" UNIQUE")?;
+				}
+				Ok(())
+			}
 			Kind::Either(k) => write!(f, "{}", Fmt::verbar_separated(k)),
 			Kind::Range => f.write_str("range"),
 			Kind::Literal(l) => write!(f, "{}", l),
@@ -429,6 +507,100 @@ impl InfoStructure for Kind {
 	}
 }
 
+/// The constraints on the length, and on the elements, of an array or a set kind.
+///
+/// A single length, like `array<string, 10>`, requires an exact length, while a
+/// range, like `array<string, 1..10>`, bounds the length. An array kind followed
+/// by `UNIQUE` rejects the arrays with duplicated elements, while a set kind
+/// removes the duplicated elements before its length is checked.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ArrayConstraints {
+	/// The minimum length, inclusive
+	pub min: Option<u64>,
+	/// The maximum length, inclusive
+	pub max: Option<u64>,
+	/// Whether the duplicated elements are rejected
+	pub unique: bool,
+}
+
+impl ArrayConstraints {
+	/// Requires an exact length
+	pub fn exact(len: u64) -> Self {
+		Self {
+			min: Some(len),
+			max: Some(len),
+			unique: false,
+		}
+	}
+
+	/// Bounds the length, where both bounds are inclusive
+	pub fn bounds(min: Option<u64>, max: Option<u64>) -> Self {
+		Self {
+			min,
+			max,
+			unique: false,
+		}
+	}
+
+	/// Requires the elements to be unique
+	pub fn with_unique(mut self, unique: bool) -> Self {
+		self.unique = unique;
+		self
+	}
+
+	fn from_len(len: Option<u64>) -> Self {
+		len.map(Self::exact).unwrap_or_default()
+	}
+
+	/// The length required by these constraints, if it is exact
+	pub(crate) fn exact_len(&self) -> Option<u64> {
+		self.min.filter(|min| self.max == Some(*min))
+	}
+
+	/// Checks if these constraints do not bound the length
+	pub(crate) fn is_unbounded(&self) -> bool {
+		self.min.is_none() && self.max.is_none()
+	}
+
+	/// Checks if a length is within the bounds
+	pub(crate) fn allows_len(&self, len: usize) -> bool {
+		let len = len as u64;
+		self.min.is_none_or(|min| len >= min) && self.max.is_none_or(|max| len <= max)
+	}
+
+	/// Finds the first element which is a duplicate of a previous element,
+	/// returning its index along with the index of the previous element
+	pub(crate) fn find_duplicate(array: &Array) -> Option<(usize, usize)> {
+		let mut seen = BTreeMap::new();
+		for (index, value) in array.iter().enumerate() {
+			if let Some(first) = seen.insert(value, index) {
+				return Some((index, first));
+			}
+		}
+		None
+	}
+}
+
+impl Display for ArrayConstraints {
+	/// Writes the length bounds, in the syntax of the kind parser
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let Some(len) = self.exact_len() {
+			return write!(f, "{len}");
+		}
+		if let Some(min) = self.min {
+			write!(f, "{min}")?;
+		}
+		f.write_str("..")?;
+		if let Some(max) = self.max {
+			write!(f, "={max}")?;
+		}
+		Ok(())
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -452,11 +624,14 @@ impl Literal {
 			Self::Array(a) => {
 				if let Some(inner) = a.first() {
 					if a.iter().all(|x| x == inner) {
-						return Kind::Array(Box::new(inner.to_owned()), Some(a.len() as u64));
+						return Kind::Array(
+							Box::new(inner.to_owned()),
+							ArrayConstraints::exact(a.len() as u64),
+						);
 					}
 				}
 
-				Kind::Array(Box::new(Kind::Any), None)
+				Kind::Array(Box::new(Kind::Any), ArrayConstraints::default())
 			}
 			Self::Object(_) => Kind::Object,
 			Self::DiscriminatedObject(_, _) => Kind::Object,
@@ -464,6 +639,17 @@ impl Literal {
 		}
 	}
 
+	/// Returns the value of a string, number, duration, or boolean literal
+	pub(crate) fn to_value(&self) -> Option<Value> {
+		match self {
+			Self::String(v) => Some(Value::Strand(v.clone())),
+			Self::Number(v) => Some(Value::Number(*v)),
+			Self::Duration(v) => Some(Value::Duration(*v)),
+			Self::Bool(v) => Some(Value::Bool(*v)),
+			_ => None,
+		}
+	}
+
 	pub fn validate_value(&self, value: &Value) -> bool {
 		match self {
 			Self::String(v) => match value {
@@ -732,13 +918,13 @@ mod tests {
 	#[case::literal(Kind::Literal(Literal::Duration(Duration::new(1, 0))), false)]
 	#[case::literal(Kind::Literal(Literal::Bool(true)), false)]
 	#[case::literal(Kind::Literal(Literal::Array(vec![])), true)]
-	#[case::array(Kind::Array(Box::new(Kind::Bool), None), true)]
-	#[case::array(Kind::Array(Box::new(Kind::Literal(Literal::String("a".into()))), None), true)]
+	#[case::array(Kind::Array(Box::new(Kind::Bool), ArrayConstraints::default()), true)]
+	#[case::array(Kind::Array(Box::new(Kind::Literal(Literal::String("a".into()))), ArrayConstraints::default()), true)]
 	#[case::object(Kind::Object, false)]
 	#[case::geometry(Kind::Geometry(vec![]), false)]
 	#[case::geometry(Kind::Geometry(vec!["point".to_string()]), false)]
-	#[case::set(Kind::Set(Box::new(Kind::Bool), None), true)]
-	#[case::set(Kind::Set(Box::new(Kind::Literal(Literal::String("a".into()))), None), true)]
+	#[case::set(Kind::Set(Box::new(Kind::Bool), ArrayConstraints::default()), true)]
+	#[case::set(Kind::Set(Box::new(Kind::Literal(Literal::String("a".into()))), ArrayConstraints::default()), true)]
 	#[case::either(Kind::Either(vec![]), false)]
 	#[case::either(Kind::Either(vec![Kind::Bool]), false)]
 	#[case::either(Kind::Either(vec![Kind::Literal(Literal::String("a".into()))]), false)]
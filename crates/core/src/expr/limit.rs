@@ -2,7 +2,6 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::number::Number;
 use crate::expr::value::Value;
 use anyhow::Result;
 use reblessive::tree::Stk;
@@ -28,18 +27,15 @@ impl Limit {
 	) -> Result<u32> {
 		match self.0.compute(stk, ctx, opt, doc).await.catch_return() {
 			// This is a valid limiting number
-			Ok(Value::Number(Number::Int(v))) if v >= 0 => {
-				if v > u32::MAX as i64 {
-					Err(anyhow::Error::new(Error::InvalidLimit {
-						value: v.to_string(),
-					}))
-				} else {
-					Ok(v as u32)
-				}
-			}
+			Ok(Value::Number(v)) if v.is_integer() => match u32::try_from(v.to_int()) {
+				Ok(v) => Ok(v),
+				Err(_) => Err(anyhow::Error::new(Error::InvalidLimit {
+					value: v.to_string(),
+				})),
+			},
 			// An invalid value was specified
 			Ok(v) => Err(anyhow::Error::new(Error::InvalidLimit {
-				value: v.as_string(),
+				value: v.to_string(),
 			})),
 			// A different error occurred
 			Err(e) => Err(e),
@@ -10,6 +10,7 @@ pub(crate) mod base;
 pub(crate) mod block;
 pub(crate) mod bytes;
 pub(crate) mod bytesize;
+pub(crate) mod capabilities;
 pub(crate) mod cast;
 pub(crate) mod change_feed_include;
 pub(crate) mod changefeed;
@@ -29,6 +30,7 @@ pub(crate) mod field;
 pub(crate) mod file;
 pub(crate) mod filter;
 pub(crate) mod fmt;
+pub(crate) mod fold;
 pub(crate) mod function;
 pub(crate) mod future;
 pub(crate) mod geometry;
@@ -42,6 +44,7 @@ pub(crate) mod language;
 pub(crate) mod limit;
 pub(crate) mod mock;
 pub(crate) mod model;
+pub(crate) mod normalize;
 pub(crate) mod number;
 pub(crate) mod object;
 pub(crate) mod operation;
@@ -73,6 +76,7 @@ pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -93,6 +97,7 @@ pub use self::block::Block;
 pub use self::block::Entry;
 pub use self::bytes::Bytes;
 pub use self::bytesize::Bytesize;
+pub use self::capabilities::ScopeCapabilities;
 pub use self::cast::Cast;
 pub use self::changefeed::ChangeFeed;
 pub use self::closure::Closure;
@@ -123,6 +128,7 @@ pub use self::ident::Ident;
 pub use self::idiom::Idiom;
 pub use self::idiom::Idioms;
 pub use self::index::Index;
+pub use self::kind::ArrayConstraints;
 pub use self::kind::Kind;
 pub use self::kind::Literal;
 pub use self::limit::Limit;
@@ -133,7 +139,7 @@ pub use self::number::Number;
 pub use self::object::Object;
 pub use self::operation::Operation;
 pub use self::operator::Operator;
-pub use self::order::{OldOrder, OldOrders, Order, OrderList, Ordering};
+pub use self::order::{Collate, Nulls, OldOrder, OldOrders, Order, OrderList, Ordering};
 pub use self::output::Output;
 pub use self::param::Param;
 pub use self::part::Part;
@@ -162,9 +168,11 @@ pub use self::value::Value;
 pub use self::value::Values;
 pub use self::value::serde::from_value;
 pub use self::value::serde::to_value;
+pub use self::value::serde::{DeserializeError, Deserializer};
 pub use self::version::Version;
 pub use self::view::View;
-pub use self::with::With;
+pub use self::window::Window;
+pub use self::with::{IndexHint, With};
 
 /// Result of functions which can impact the controlflow of query execution.
 pub type FlowResult<T> = Result<T, ControlFlow>;
@@ -174,8 +182,10 @@ pub type FlowResult<T> = Result<T, ControlFlow>;
 /// Returned by compute functions which can impact control flow.
 #[derive(Debug)]
 pub enum ControlFlow {
-	Break,
-	Continue,
+	/// Break out of the labelled, or innermost, loop
+	Break(Option<Ident>),
+	/// Continue the labelled, or innermost, loop
+	Continue(Option<Ident>),
 	Return(Value),
 	Err(anyhow::Error),
 }
@@ -198,7 +208,7 @@ pub trait FlowResultExt {
 impl FlowResultExt for FlowResult<Value> {
 	fn catch_return(self) -> Result<Value, anyhow::Error> {
 		match self {
-			Err(ControlFlow::Break) | Err(ControlFlow::Continue) => {
+			Err(ControlFlow::Break(_)) | Err(ControlFlow::Continue(_)) => {
 				Err(anyhow::Error::new(Error::InvalidControlFlow))
 			}
 			Err(ControlFlow::Return(x)) => Ok(x),
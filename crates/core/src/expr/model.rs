@@ -73,7 +73,7 @@ impl Model {
 		// Get the full name of this model
 		let name = format!("ml::{}", self.name);
 		// Check this function is allowed
-		ctx.check_allowed_function(name.as_str())?;
+		ctx.check_allowed_function(opt, name.as_str()).await?;
 		// Get the model definition
 		let (ns, db) = opt.ns_db()?;
 		let val = ctx.tx().get_db_model(ns, db, &self.name, &self.version).await?;
@@ -0,0 +1,413 @@
+//! Normalisation of the statements, used to group similar queries together.
+//!
+//! Every literal value of a statement is replaced with the `$?` placeholder,
+//! while the structure of the statement (the tables, fields, operators and
+//! functions) is kept, so that two statements which only differ by their
+//! literal values have the same normalised text, and the same fingerprint.
+use crate::expr::statements::{
+	CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement, InsertStatement,
+	OutputStatement, RelateStatement, SelectStatement, SetStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::expr::{
+	Block, Cond, Data, Entry, Expression, Field, Fields, Function, Id, Idiom, Limit, LogicalPlan,
+	Param, Part, Start, Subquery, Value, Values,
+};
+use std::ops::Bound;
+
+/// The name of the parameter which replaces the literal values
+const PLACEHOLDER: &str = "?";
+
+/// Replaces the literal values of a statement with placeholders
+pub(crate) trait Normalize {
+	fn normalize(&mut self);
+}
+
+impl LogicalPlan {
+	/// Returns the normalised text of the statement, and its fingerprint.
+	/// The fingerprint is a stable hash of the normalised text, and is the
+	/// same for all statements which only differ by their literal values.
+	pub(crate) fn fingerprint(&self) -> (String, String) {
+		let mut plan = self.clone();
+		plan.normalize();
+		let text = plan.to_string();
+		let hash = blake3::hash(text.as_bytes());
+		let fingerprint = hex::encode(&hash.as_bytes()[..8]);
+		(text, fingerprint)
+	}
+}
+
+impl Normalize for LogicalPlan {
+	fn normalize(&mut self) {
+		match self {
+			Self::Value(v) => v.normalize(),
+			Self::Create(v) => v.normalize(),
+			Self::Delete(v) => v.normalize(),
+			Self::Foreach(v) => v.normalize(),
+			Self::Ifelse(v) => v.normalize(),
+			Self::Insert(v) => v.normalize(),
+			Self::Output(v) => v.normalize(),
+			Self::Relate(v) => v.normalize(),
+			Self::Select(v) => v.normalize(),
+			Self::Set(v) => v.normalize(),
+			Self::Update(v) => v.normalize(),
+			Self::Upsert(v) => v.normalize(),
+			Self::Throw(v) => v.error.normalize(),
+			Self::Trycatch(v) => {
+				v.block.normalize();
+				v.catch.normalize();
+			}
+			// The other statements define or manage the resources,
+			// and are kept as they are written.
+			_ => {}
+		}
+	}
+}
+
+impl Value {
+	/// Checks if this value is a literal which is replaced by a placeholder
+	fn is_literal(&self) -> bool {
+		matches!(
+			self,
+			Value::Bool(_)
+				| Value::Number(_)
+				| Value::Strand(_)
+				| Value::Duration(_)
+				| Value::Datetime(_)
+				| Value::Uuid(_)
+				| Value::Geometry(_)
+				| Value::Bytes(_)
+				| Value::Regex(_)
+		)
+	}
+}
+
+impl Normalize for Value {
+	fn normalize(&mut self) {
+		match self {
+			v if v.is_literal() => *v = Value::Param(Param::from(PLACEHOLDER)),
+			// A list of literals is replaced as a whole, so that
+			// lists of different lengths have the same fingerprint
+			Value::Array(a) if !a.is_empty() && a.iter().all(Value::is_literal) => {
+				*self = Value::Param(Param::from(PLACEHOLDER))
+			}
+			Value::Array(a) => a.iter_mut().for_each(Normalize::normalize),
+			Value::Object(o) => o.values_mut().for_each(Normalize::normalize),
+			Value::Thing(t) => t.id.normalize(),
+			Value::Idiom(i) => i.normalize(),
+			Value::Cast(c) => c.1.normalize(),
+			Value::Block(b) => b.normalize(),
+			Value::Range(r) => {
+				normalize_bound(&mut r.beg);
+				normalize_bound(&mut r.end);
+			}
+			Value::Edges(e) => e.from.id.normalize(),
+			Value::Future(f) => f.0.normalize(),
+			Value::Function(f) => f.normalize(),
+			Value::Subquery(s) => s.normalize(),
+			Value::Expression(e) => e.normalize(),
+			Value::Model(m) => m.args.iter_mut().for_each(Normalize::normalize),
+			Value::Closure(c) => c.body.normalize(),
+			_ => {}
+		}
+	}
+}
+
+fn normalize_bound(bound: &mut Bound<Value>) {
+	if let Bound::Included(v) | Bound::Excluded(v) = bound {
+		v.normalize();
+	}
+}
+
+impl Normalize for Id {
+	fn normalize(&mut self) {
+		// Generated identifiers are part of the structure of the statement
+		if !matches!(self, Id::Generate(_)) {
+			*self = Id::String(PLACEHOLDER.to_owned());
+		}
+	}
+}
+
+impl Normalize for Values {
+	fn normalize(&mut self) {
+		self.0.iter_mut().for_each(Normalize::normalize)
+	}
+}
+
+impl Normalize for Idiom {
+	fn normalize(&mut self) {
+		for p in self.0.iter_mut() {
+			match p {
+				Part::Where(v) | Part::Value(v) | Part::Start(v) => v.normalize(),
+				Part::Method(_, args) => args.iter_mut().for_each(Normalize::normalize),
+				_ => {}
+			}
+		}
+	}
+}
+
+impl Normalize for Expression {
+	fn normalize(&mut self) {
+		match self {
+			Expression::Unary {
+				v,
+				..
+			} => v.normalize(),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => {
+				l.normalize();
+				r.normalize();
+			}
+		}
+	}
+}
+
+impl Normalize for Function {
+	fn normalize(&mut self) {
+		match self {
+			Function::Normal(_, args) | Function::Custom(_, args) | Function::Script(_, args) => {
+				args.iter_mut().for_each(Normalize::normalize)
+			}
+			Function::Anonymous(v, args, _) => {
+				v.normalize();
+				args.iter_mut().for_each(Normalize::normalize)
+			}
+		}
+	}
+}
+
+impl Normalize for Subquery {
+	fn normalize(&mut self) {
+		match self {
+			Subquery::Value(v) => v.normalize(),
+			Subquery::Ifelse(v) => v.normalize(),
+			Subquery::Output(v) => v.normalize(),
+			Subquery::Select(v) => v.normalize(),
+			Subquery::Create(v) => v.normalize(),
+			Subquery::Update(v) => v.normalize(),
+			Subquery::Delete(v) => v.normalize(),
+			Subquery::Relate(v) => v.normalize(),
+			Subquery::Insert(v) => v.normalize(),
+			Subquery::Upsert(v) => v.normalize(),
+			_ => {}
+		}
+	}
+}
+
+impl Normalize for Block {
+	fn normalize(&mut self) {
+		for e in self.0.iter_mut() {
+			match e {
+				Entry::Value(v) => v.normalize(),
+				Entry::Set(v) => v.normalize(),
+				Entry::Ifelse(v) => v.normalize(),
+				Entry::Select(v) => v.normalize(),
+				Entry::Create(v) => v.normalize(),
+				Entry::Update(v) => v.normalize(),
+				Entry::Delete(v) => v.normalize(),
+				Entry::Relate(v) => v.normalize(),
+				Entry::Insert(v) => v.normalize(),
+				Entry::Output(v) => v.normalize(),
+				Entry::Throw(v) => v.error.normalize(),
+				Entry::Foreach(v) => v.normalize(),
+				Entry::Upsert(v) => v.normalize(),
+				_ => {}
+			}
+		}
+	}
+}
+
+impl Normalize for Fields {
+	fn normalize(&mut self) {
+		for f in self.0.iter_mut() {
+			match f {
+				Field::All => {}
+				Field::Single {
+					expr,
+					..
+				}
+				| Field::Window {
+					expr,
+					..
+				} => expr.normalize(),
+			}
+		}
+	}
+}
+
+impl Normalize for Data {
+	fn normalize(&mut self) {
+		match self {
+			Data::EmptyExpression | Data::UnsetExpression(_) => {}
+			Data::SetExpression(v) | Data::UpdateExpression(v) => {
+				v.iter_mut().for_each(|(_, _, v)| v.normalize())
+			}
+			Data::PatchExpression(v)
+			| Data::MergeExpression(v)
+			| Data::ReplaceExpression(v)
+			| Data::ContentExpression(v)
+			| Data::SingleExpression(v) => v.normalize(),
+			Data::ValuesExpression(rows) => {
+				rows.iter_mut().flatten().for_each(|(_, v)| v.normalize())
+			}
+		}
+	}
+}
+
+impl<T: Normalize> Normalize for Option<T> {
+	fn normalize(&mut self) {
+		if let Some(v) = self {
+			v.normalize()
+		}
+	}
+}
+
+impl Normalize for Cond {
+	fn normalize(&mut self) {
+		self.0.normalize()
+	}
+}
+
+impl Normalize for Limit {
+	fn normalize(&mut self) {
+		self.0.normalize()
+	}
+}
+
+impl Normalize for Start {
+	fn normalize(&mut self) {
+		self.0.normalize()
+	}
+}
+
+impl Normalize for SelectStatement {
+	fn normalize(&mut self) {
+		self.expr.normalize();
+		self.what.normalize();
+		self.cond.normalize();
+		self.having.normalize();
+		self.limit.normalize();
+		self.start.normalize();
+	}
+}
+
+impl Normalize for CreateStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+		self.data.normalize();
+	}
+}
+
+impl Normalize for UpdateStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+		self.data.normalize();
+		self.cond.normalize();
+		self.limit.normalize();
+	}
+}
+
+impl Normalize for UpsertStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+		self.data.normalize();
+		self.cond.normalize();
+	}
+}
+
+impl Normalize for DeleteStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+		self.cond.normalize();
+		self.limit.normalize();
+	}
+}
+
+impl Normalize for RelateStatement {
+	fn normalize(&mut self) {
+		self.kind.normalize();
+		self.from.normalize();
+		self.with.normalize();
+		self.data.normalize();
+	}
+}
+
+impl Normalize for InsertStatement {
+	fn normalize(&mut self) {
+		self.into.normalize();
+		self.data.normalize();
+		self.update.normalize();
+	}
+}
+
+impl Normalize for OutputStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+	}
+}
+
+impl Normalize for SetStatement {
+	fn normalize(&mut self) {
+		self.what.normalize();
+	}
+}
+
+impl Normalize for IfelseStatement {
+	fn normalize(&mut self) {
+		for (cond, then) in self.exprs.iter_mut() {
+			cond.normalize();
+			then.normalize();
+		}
+		self.close.normalize();
+	}
+}
+
+impl Normalize for ForeachStatement {
+	fn normalize(&mut self) {
+		self.range.normalize();
+		self.block.normalize();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::sql::planner::SqlToLogical;
+	use crate::syn;
+
+	fn fingerprint(sql: &str) -> (String, String) {
+		let mut stmts = syn::parse(sql).unwrap().0.0;
+		let plan = SqlToLogical::new().statement_to_logical(stmts.remove(0)).unwrap();
+		plan.fingerprint()
+	}
+
+	#[test]
+	fn normalize_literals() {
+		let (text, _) = fingerprint(
+			"SELECT name, age + 1 FROM person WHERE age > 18 AND tags CONTAINSANY ['a', 'b'] LIMIT 10",
+		);
+		assert_eq!(
+			text,
+			"SELECT name, age + $? FROM person WHERE age > $? AND tags CONTAINSANY $? LIMIT $?"
+		);
+		let (text, _) = fingerprint("UPDATE person:tobie SET name = 'Tobie', score += 10");
+		assert_eq!(text, "UPDATE person:⟨?⟩ SET name = $?, score += $?");
+		// Parameters are already placeholders
+		let (text, _) = fingerprint("SELECT * FROM person WHERE name = $name");
+		assert_eq!(text, "SELECT * FROM person WHERE name = $name");
+	}
+
+	#[test]
+	fn fingerprint_ignores_literals() {
+		let (_, a) = fingerprint("SELECT * FROM person WHERE age > 18 AND id IN [1, 2, 3]");
+		let (_, b) = fingerprint("SELECT * FROM person WHERE age > 42 AND id IN [4]");
+		let (_, c) = fingerprint("SELECT * FROM person WHERE age < 18 AND id IN [1, 2, 3]");
+		let (_, d) = fingerprint("SELECT * FROM user WHERE age > 18 AND id IN [1, 2, 3]");
+		assert_eq!(a.len(), 16);
+		assert_eq!(a, b);
+		assert_ne!(a, c);
+		assert_ne!(a, d);
+	}
+}
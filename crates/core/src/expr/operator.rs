@@ -1,5 +1,5 @@
 use crate::expr::index::Distance;
-use crate::idx::ft::MatchRef;
+use crate::idx::ft::{Fuzziness, MatchRef};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -8,7 +8,7 @@ use std::fmt::Write;
 use super::Value;
 
 /// Binary operators.
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -45,7 +45,7 @@ pub enum Operator {
 	AllLike, // *~
 	#[revision(end = 3, convert_fn = "like_convert")]
 	AnyLike, // ?~
-	Matches(Option<MatchRef>), // @{ref}@
+	Matches(Option<MatchRef>, #[revision(start = 4)] Option<Fuzziness>), // @{ref}~{fuzziness}@
 	//
 	LessThan,        // <
 	LessThanOrEqual, // <=
@@ -124,12 +124,15 @@ impl fmt::Display for Operator {
 			Self::NoneInside => f.write_str("NONEINSIDE"),
 			Self::Outside => f.write_str("OUTSIDE"),
 			Self::Intersects => f.write_str("INTERSECTS"),
-			Self::Matches(reference) => {
+			Self::Matches(reference, fuzziness) => {
+				f.write_char('@')?;
 				if let Some(r) = reference {
-					write!(f, "@{r}@")
-				} else {
-					f.write_str("@@")
+					write!(f, "{r}")?;
+				}
+				if let Some(d) = fuzziness {
+					write!(f, "~{d}")?;
 				}
+				f.write_char('@')
 			}
 			Self::Knn(k, dist) => {
 				if let Some(d) = dist {
@@ -190,7 +193,7 @@ impl BindingPower {
 			| Operator::LessThanOrEqual
 			| Operator::MoreThan
 			| Operator::MoreThanOrEqual
-			| Operator::Matches(_)
+			| Operator::Matches(..)
 			| Operator::Contain
 			| Operator::NotContain
 			| Operator::ContainAll
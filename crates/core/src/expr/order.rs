@@ -46,6 +46,13 @@ impl fmt::Display for OrderList {
 impl OrderList {
 	pub(crate) fn compare(&self, a: &Value, b: &Value) -> cmp::Ordering {
 		for order in &self.0 {
+			// Place any NONE and NULL values at the requested end
+			if order.nulls.is_some() {
+				let (x, y) = (a.pick(&order.value), b.pick(&order.value));
+				if let Some(o) = order.compare_nulls(&x, &y) {
+					return o;
+				}
+			}
 			// Reverse the ordering if DESC
 			let o = match order.direction {
 				true => a.compare(b, &order.value.0, order.collate, order.numeric),
@@ -63,24 +70,62 @@ impl OrderList {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Order {
 	/// The value to order by
 	pub value: Idiom,
-	pub collate: bool,
+	#[revision(end = 2, convert_fn = "convert_old_collate")]
+	pub old_collate: bool,
+	/// The collation used to compare strings
+	#[revision(start = 2)]
+	pub collate: Option<Collate>,
 	pub numeric: bool,
 	/// true if the direction is ascending
 	pub direction: bool,
+	/// Where NONE and NULL values are placed, if specified
+	#[revision(start = 3)]
+	pub nulls: Option<Nulls>,
+}
+
+impl Order {
+	fn convert_old_collate(&mut self, _rev: u16, old: bool) -> Result<(), revision::Error> {
+		self.collate = old.then_some(Collate::Lexical);
+		Ok(())
+	}
+
+	/// Returns whether NONE and NULL values are placed before all other values.
+	/// Unless specified, these come first when ascending and last when descending.
+	pub(crate) fn nulls_first(&self) -> bool {
+		match self.nulls {
+			Some(nulls) => nulls == Nulls::First,
+			None => self.direction,
+		}
+	}
+
+	/// Compares two values when only one of them is NONE or NULL,
+	/// placing that value at the requested end regardless of the direction
+	pub(crate) fn compare_nulls(&self, a: &Value, b: &Value) -> Option<cmp::Ordering> {
+		self.nulls?;
+		let o = match (a.is_none_or_null(), b.is_none_or_null()) {
+			(true, false) => cmp::Ordering::Less,
+			(false, true) => cmp::Ordering::Greater,
+			_ => return None,
+		};
+		Some(match self.nulls_first() {
+			true => o,
+			false => o.reverse(),
+		})
+	}
 }
 
 impl fmt::Display for Order {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", self.value)?;
-		if self.collate {
-			write!(f, " COLLATE")?;
+		if let Some(collate) = &self.collate {
+			write!(f, " {collate}")?;
 		}
 		if self.numeric {
 			write!(f, " NUMERIC")?;
@@ -88,10 +133,69 @@ impl fmt::Display for Order {
 		if !self.direction {
 			write!(f, " DESC")?;
 		}
+		if let Some(nulls) = &self.nulls {
+			write!(f, " {nulls}")?;
+		}
 		Ok(())
 	}
 }
 
+/// Where NONE and NULL values are placed in an ORDER BY clause
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Nulls {
+	/// NONE and NULL values are placed before all other values
+	First,
+	/// NONE and NULL values are placed after all other values
+	Last,
+}
+
+impl fmt::Display for Nulls {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::First => write!(f, "NULLS FIRST"),
+			Self::Last => write!(f, "NULLS LAST"),
+		}
+	}
+}
+
+/// The collation used when comparing strings in an ORDER BY clause
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Collate {
+	/// Lexical ordering, where letters are compared before their case
+	Lexical,
+	/// Ordering which ignores the case of letters
+	NoCase,
+	/// Ordering which ignores both the case and the accents of letters
+	Unicode,
+}
+
+impl fmt::Display for Collate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Lexical => write!(f, "COLLATE"),
+			Self::NoCase => write!(f, "COLLATE NOCASE"),
+			Self::Unicode => write!(f, "COLLATE UNICODE"),
+		}
+	}
+}
+
+impl Collate {
+	/// Folds a string into the form which is compared for this collation
+	pub(crate) fn fold(&self, s: &str) -> String {
+		match self {
+			Self::Lexical | Self::NoCase => s.to_lowercase(),
+			// Decompose the string into plain characters before folding the case
+			Self::Unicode => deunicode::deunicode(s).to_lowercase(),
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -17,6 +17,14 @@ pub static AC: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_ACCES
 
 pub static RD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_AUTH)]);
 
+pub static EP: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("ep")]);
+
+pub static EXP: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("exp")]);
+
+pub static DELETED_AT: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("deleted_at")]);
+
+pub static PV: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("pv")]);
+
 pub static OR: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("or")]);
 
 pub static TK: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_TOKEN)]);
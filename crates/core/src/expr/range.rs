@@ -278,6 +278,21 @@ impl Range {
 		})))
 	}
 
+	/// Check whether a value falls within the bounds of this range
+	pub fn contains(&self, value: &Value) -> bool {
+		let above = match &self.beg {
+			Bound::Included(beg) => value >= beg,
+			Bound::Excluded(beg) => value > beg,
+			Bound::Unbounded => true,
+		};
+		let below = match &self.end {
+			Bound::Included(end) => value <= end,
+			Bound::Excluded(end) => value < end,
+			Bound::Unbounded => true,
+		};
+		above && below
+	}
+
 	/// Validate that a Range contains only computed Values
 	pub fn validate_computed(&self) -> Result<()> {
 		match &self.beg {
@@ -1,3 +1,5 @@
+use crate::err::Error;
+use anyhow::{Result, ensure};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -51,6 +53,27 @@ impl Hash for Scoring {
 	}
 }
 
+impl Scoring {
+	/// Checks that the scoring parameters are within their valid ranges
+	pub(crate) fn check(&self) -> Result<()> {
+		if let Self::Bm {
+			k1,
+			b,
+		} = self
+		{
+			ensure!(
+				k1.is_finite() && *k1 >= 0.0,
+				Error::InvalidBm25Params(format!("k1 must be a non-negative number, found {k1}"))
+			);
+			ensure!(
+				(0.0..=1.0).contains(b),
+				Error::InvalidBm25Params(format!("b must be between 0 and 1, found {b}"))
+			);
+		}
+		Ok(())
+	}
+}
+
 impl Default for Scoring {
 	fn default() -> Self {
 		Self::Bm {
@@ -5,6 +5,7 @@ use crate::err::Error;
 use crate::expr::statements::AccessStatement;
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::{
+	Timeout,
 	fmt::{Fmt, Pretty},
 	statements::{
 		AlterStatement, AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
@@ -12,7 +13,7 @@ use crate::expr::{
 		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
 		LiveStatement, OptionStatement, OutputStatement, RelateStatement, RemoveStatement,
 		SelectStatement, SetStatement, ShowStatement, SleepStatement, ThrowStatement,
-		UpdateStatement, UpsertStatement, UseStatement,
+		TruncateStatement, TrycatchStatement, UpdateStatement, UpsertStatement, UseStatement,
 	},
 	value::Value,
 };
@@ -57,7 +58,7 @@ impl Display for LogicalPlans {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -98,6 +99,10 @@ pub enum LogicalPlan {
 	// TODO(gguillemas): Document once bearer access is no longer experimental.
 	#[revision(start = 5)]
 	Access(AccessStatement),
+	#[revision(start = 6)]
+	Truncate(TruncateStatement),
+	#[revision(start = 7)]
+	Trycatch(TrycatchStatement),
 }
 
 impl LogicalPlan {
@@ -107,7 +112,7 @@ impl LogicalPlan {
 			Self::Value(v) => v.writeable(),
 			Self::Access(_) => true,
 			Self::Alter(_) => true,
-			Self::Analyze(_) => false,
+			Self::Analyze(v) => v.writeable(),
 			Self::Break(_) => false,
 			Self::Continue(_) => false,
 			Self::Create(v) => v.writeable(),
@@ -129,12 +134,27 @@ impl LogicalPlan {
 			Self::Show(_) => false,
 			Self::Sleep(_) => false,
 			Self::Throw(_) => false,
+			Self::Truncate(_) => true,
+			Self::Trycatch(v) => v.writeable(),
 			Self::Upsert(v) => v.writeable(),
 			Self::Update(v) => v.writeable(),
 			Self::Use(_) => false,
 			_ => false,
 		}
 	}
+	/// Returns the timeout specified by the TIMEOUT clause, if any
+	pub(crate) fn timeout(&self) -> Option<&Timeout> {
+		match self {
+			Self::Create(v) => v.timeout.as_ref(),
+			Self::Delete(v) => v.timeout.as_ref(),
+			Self::Insert(v) => v.timeout.as_ref(),
+			Self::Relate(v) => v.timeout.as_ref(),
+			Self::Select(v) => v.timeout.as_ref(),
+			Self::Update(v) => v.timeout.as_ref(),
+			Self::Upsert(v) => v.timeout.as_ref(),
+			_ => None,
+		}
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -185,6 +205,8 @@ impl LogicalPlan {
 			Self::Show(v) => v.compute(ctx, opt, doc).await,
 			Self::Sleep(v) => v.compute(ctx, opt, doc).await,
 			Self::Throw(v) => return v.compute(stk, ctx, opt, doc).await,
+			Self::Truncate(v) => v.compute(ctx, opt, doc).await,
+			Self::Trycatch(v) => return v.compute(stk, ctx, opt, doc).await,
 			Self::Update(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Upsert(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Value(v) => {
@@ -245,6 +267,8 @@ impl Display for LogicalPlan {
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
 			Self::Sleep(v) => write!(Pretty::from(f), "{v}"),
 			Self::Throw(v) => write!(Pretty::from(f), "{v}"),
+			Self::Truncate(v) => write!(Pretty::from(f), "{v}"),
+			Self::Trycatch(v) => write!(Pretty::from(f), "{v}"),
 			Self::Update(v) => write!(Pretty::from(f), "{v}"),
 			Self::Upsert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Use(v) => write!(Pretty::from(f), "{v}"),
@@ -0,0 +1,135 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::access_type::JwtAccessKey;
+use crate::expr::escape::QuoteStr;
+use crate::expr::statements::DefineAccessStatement;
+use crate::expr::{Base, Ident, Value};
+use crate::iam::{Action, ResourceKind};
+use anyhow::{Result, bail, ensure};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterAccessStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+	pub add_keys: Vec<JwtAccessKey>,
+	pub remove_keys: Vec<String>,
+}
+
+impl AlterAccessStatement {
+	/// Returns a version of the statement where the added keys are redacted
+	pub fn redacted(&self) -> AlterAccessStatement {
+		let mut aas = self.clone();
+		for key in aas.add_keys.iter_mut() {
+			key.key = "[REDACTED]".to_string();
+		}
+		aas
+	}
+
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the access method definition
+		let res = match &self.base {
+			Base::Root => txn.get_root_access(&self.name).await,
+			Base::Ns => txn.get_ns_access(opt.ns()?, &self.name).await,
+			Base::Db => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_access(ns, db, &self.name).await
+			}
+			// Other levels are not supported
+			_ => bail!(Error::InvalidLevel(self.base.to_string())),
+		};
+		let mut ac = match res {
+			Ok(ac) => ac.as_ref().clone(),
+			Err(e) => {
+				if self.if_exists
+					&& matches!(
+						e.downcast_ref(),
+						Some(
+							Error::AccessRootNotFound { .. }
+								| Error::AccessNsNotFound { .. }
+								| Error::AccessDbNotFound { .. }
+						)
+					) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Process the statement
+		self.alter_keys(&mut ac)?;
+		// Set the access method definition
+		let val = revision::to_vec(&ac)?;
+		match &self.base {
+			Base::Root => txn.set(crate::key::root::ac::new(&self.name), val, None).await?,
+			Base::Ns => {
+				let key = crate::key::namespace::ac::new(opt.ns()?, &self.name);
+				txn.set(key, val, None).await?
+			}
+			_ => {
+				let (ns, db) = opt.ns_db()?;
+				let key = crate::key::database::ac::new(ns, db, &self.name);
+				txn.set(key, val, None).await?
+			}
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Removes and then adds the verification keys of the access method.
+	/// The keys are removed first, so that a key can be replaced.
+	fn alter_keys(&self, ac: &mut DefineAccessStatement) -> Result<()> {
+		let jwt = ac.kind.jwt_mut();
+		for kid in &self.remove_keys {
+			let Some(i) = jwt.keys.iter().position(|k| &k.kid == kid) else {
+				bail!(Error::AccessKeyNotFound {
+					ac: self.name.to_raw(),
+					kid: kid.clone(),
+				});
+			};
+			jwt.keys.remove(i);
+		}
+		for key in &self.add_keys {
+			ensure!(
+				jwt.key(&key.kid).is_none(),
+				Error::AccessKeyAlreadyExists {
+					ac: self.name.to_raw(),
+					kid: key.kid.clone(),
+				}
+			);
+			jwt.keys.push(key.clone());
+		}
+		Ok(())
+	}
+}
+
+impl Display for AlterAccessStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER ACCESS")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for k in &self.add_keys {
+			write!(f, " ADD {k}")?
+		}
+		for k in &self.remove_keys {
+			write!(f, " REMOVE KEY {}", QuoteStr(k))?
+		}
+		Ok(())
+	}
+}
@@ -1,22 +1,25 @@
-use crate::ctx::Context;
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::reference::Reference;
-use crate::expr::statements::DefineTableStatement;
-use crate::expr::{Base, Ident, Permissions, Strand, Value};
+use crate::expr::statements::{DefineFieldStatement, DefineTableStatement};
+use crate::expr::{Base, FlowResultExt as _, Ident, Permissions, Strand, Thing, Value};
 use crate::expr::{Idiom, Kind};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::KeyDecode;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use std::ops::Deref;
+use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,12 +37,15 @@ pub struct AlterFieldStatement {
 	pub comment: Option<Option<Strand>>,
 	pub reference: Option<Option<Reference>>,
 	pub default_always: Option<bool>,
+	/// Whether the existing records are checked against the altered definition
+	#[revision(start = 2)]
+	pub validate: bool,
 }
 
 impl AlterFieldStatement {
 	pub(crate) async fn compute(
 		&self,
-		_stk: &mut Stk,
+		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
@@ -110,6 +116,11 @@ impl AlterFieldStatement {
 		// Disallow mismatched types
 		df.disallow_mismatched_types(ctx, ns, db).await?;
 
+		// Check the existing records against the altered definition
+		if self.validate {
+			Self::validate_records(stk, ctx, opt, &df).await?;
+		}
+
 		// Set the table definition
 		let key = crate::key::table::fd::new(ns, db, &self.what, &name);
 		txn.set(key, revision::to_vec(&df)?, None).await?;
@@ -134,6 +145,68 @@ impl AlterFieldStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Checks that the field of every record in the table matches the TYPE and ASSERT clauses
+	async fn validate_records(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		df: &DefineFieldStatement,
+	) -> Result<()> {
+		if df.kind.is_none() && df.assert.is_none() {
+			return Ok(());
+		}
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		let beg = crate::key::thing::prefix(ns, db, &df.what)?;
+		let end = crate::key::thing::suffix(ns, db, &df.what)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let key = crate::key::thing::Thing::decode(&k)?;
+				let rid = Thing::from((key.tb, key.id));
+				let doc: Value = revision::from_slice(&v)?;
+				let val = doc.pick(&df.name);
+				// Check the type of the field value
+				if let Some(kind) = &df.kind {
+					val.clone().coerce_to_kind(kind).map_err(|e| Error::FieldCoerce {
+						thing: rid.to_string(),
+						field_name: df.name.to_string(),
+						error: Box::new(e),
+					})?;
+				}
+				// An optional field which is not set is not asserted
+				if val.is_none() && df.kind.as_ref().is_some_and(Kind::can_be_none) {
+					continue;
+				}
+				// Check the ASSERT clause
+				if let Some(expr) = &df.assert {
+					let now = Arc::new(val);
+					let mut ctx = MutableContext::new(ctx);
+					ctx.add_value("after", now.clone());
+					ctx.add_value("value", now.clone());
+					let ctx = ctx.freeze();
+					let doc = CursorDoc::new(Some(rid.clone().into()), None, doc);
+					let res = stk
+						.run(|stk| expr.compute(stk, &ctx, opt, Some(&doc)))
+						.await
+						.catch_return()?;
+					ensure!(
+						res.is_truthy(),
+						Error::FieldValue {
+							thing: rid.to_string(),
+							field: df.name.clone(),
+							check: expr.to_string(),
+							value: now.to_string(),
+						}
+					);
+				}
+			}
+		}
+		Ok(())
+	}
 }
 
 impl Display for AlterFieldStatement {
@@ -207,6 +280,9 @@ impl Display for AlterFieldStatement {
 				write!(f, " DROP REFERENCE")?;
 			}
 		}
+		if self.validate {
+			write!(f, " VALIDATE")?;
+		}
 		Ok(())
 	}
 }
@@ -0,0 +1,105 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+#[cfg(not(target_family = "wasm"))]
+use crate::expr::statements::{DefineIndexStatement, DefineTableStatement};
+use crate::expr::{Base, Ident, Value};
+use crate::iam::{Action, ResourceKind};
+use anyhow::Result;
+#[cfg(target_family = "wasm")]
+use anyhow::bail;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+#[cfg(not(target_family = "wasm"))]
+use uuid::Uuid;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterIndexStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub cancel_build: bool,
+}
+
+impl AlterIndexStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the index definition
+		let ix = match txn.get_tb_index(ns, db, &self.what, &self.name).await {
+			Ok(ix) => ix,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::IxNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Stop any concurrent building of the index
+		if self.cancel_build {
+			#[cfg(not(target_family = "wasm"))]
+			{
+				let incomplete = ctx
+					.get_index_stores()
+					.index_build_cancelled(ctx.get_index_builder(), ns, db, &ix)
+					.await?;
+				// Keep the index incomplete until it is rebuilt, even after a restart
+				if incomplete {
+					let key = crate::key::table::ix::new(ns, db, &self.what, &self.name);
+					let ix = DefineIndexStatement {
+						cancelled: true,
+						..ix.as_ref().clone()
+					};
+					txn.set(key, revision::to_vec(&ix)?, None).await?;
+					// Refresh the table cache for indexes
+					let key = crate::key::database::tb::new(ns, db, &self.what);
+					let tb = txn.get_tb(ns, db, &self.what).await?;
+					txn.set(
+						key,
+						revision::to_vec(&DefineTableStatement {
+							cache_indexes_ts: Uuid::now_v7(),
+							..tb.as_ref().clone()
+						})?,
+						None,
+					)
+					.await?;
+					// Clear the cache
+					if let Some(cache) = ctx.get_cache() {
+						cache.clear_tb(ns, db, &self.what);
+					}
+					txn.clear();
+				}
+			}
+			#[cfg(target_family = "wasm")]
+			bail!(Error::IndexNotBuilding {
+				name: ix.name.to_raw(),
+			});
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterIndexStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER INDEX")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.cancel_build {
+			write!(f, " CANCEL BUILD")?
+		}
+		Ok(())
+	}
+}
@@ -1,13 +1,19 @@
+mod access;
 mod field;
+mod index;
 mod sequence;
 mod table;
 
+pub use access::AlterAccessStatement;
 pub use field::AlterFieldStatement;
+pub use index::AlterIndexStatement;
 pub use sequence::AlterSequenceStatement;
 pub use table::AlterTableStatement;
 
 use crate::ctx::Context;
 use crate::dbs::Options;
+use crate::dbs::audit::{Audit, Target};
+use crate::dbs::capabilities::StatementTarget;
 use crate::doc::CursorDoc;
 use crate::expr::value::Value;
 use anyhow::Result;
@@ -17,7 +23,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -27,6 +33,10 @@ pub enum AlterStatement {
 	Sequence(AlterSequenceStatement),
 	#[revision(start = 3)]
 	Field(AlterFieldStatement),
+	#[revision(start = 4)]
+	Index(AlterIndexStatement),
+	#[revision(start = 5)]
+	Access(AlterAccessStatement),
 }
 
 impl AlterStatement {
@@ -42,10 +52,35 @@ impl AlterStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		match self {
+		let target = self.target();
+		// Check if the statement is allowed
+		ctx.check_allowed_statement(opt, &StatementTarget::new("ALTER", target.resource())).await?;
+		// Fetch the definition before it is altered
+		let audit = Audit::begin(ctx, opt, target).await?;
+		let res = match self {
 			Self::Table(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(ctx, opt).await,
 			Self::Field(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Index(v) => v.compute(ctx, opt).await,
+			Self::Access(v) => v.compute(ctx, opt).await,
+		}?;
+		// Record the change in the audit log
+		audit
+			.finish(ctx, opt, || match self {
+				Self::Access(v) => v.redacted().to_string(),
+				_ => self.to_string(),
+			})
+			.await?;
+		Ok(res)
+	}
+	/// Returns the definition which this statement alters
+	fn target(&self) -> Target<'_> {
+		match self {
+			Self::Table(v) => Target::Table(&v.name),
+			Self::Sequence(v) => Target::Sequence(&v.name),
+			Self::Field(v) => Target::Field(&v.what, v.name.to_string()),
+			Self::Index(v) => Target::Index(&v.what, &v.name),
+			Self::Access(v) => Target::Access(&v.name, &v.base),
 		}
 	}
 }
@@ -56,6 +91,8 @@ impl Display for AlterStatement {
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
 			Self::Field(v) => Display::fmt(v, f),
+			Self::Index(v) => Display::fmt(v, f),
+			Self::Access(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -73,7 +110,7 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(15, enc.len());
+		assert_eq!(17, enc.len());
 	}
 
 	#[test]
@@ -94,6 +131,27 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(30, enc.len());
+		assert_eq!(31, enc.len());
+	}
+
+	#[test]
+	fn check_alter_serialize_index() {
+		let stm = AlterStatement::Index(AlterIndexStatement {
+			name: Ident::from("test"),
+			what: Ident::from("test"),
+			..Default::default()
+		});
+		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
+		assert_eq!(17, enc.len());
+	}
+
+	#[test]
+	fn check_alter_serialize_access() {
+		let stm = AlterStatement::Access(AlterAccessStatement {
+			name: Ident::from("test"),
+			..Default::default()
+		});
+		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
+		assert_eq!(14, enc.len());
 	}
 }
@@ -1,21 +1,35 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
-use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::dbs::{self, Force, Notification, Options};
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::fmt::{is_pretty, pretty_indent};
-use crate::expr::statements::DefineTableStatement;
-use crate::expr::{Base, ChangeFeed, Ident, Permissions, Strand, Value};
+use crate::expr::paths::{EDGE, ID, IN, OUT};
+use crate::expr::statements::truncate::DATA_CATEGORIES;
+use crate::expr::statements::{
+	DefineFieldStatement, DefineIndexStatement, DefineTableStatement, UpdateStatement,
+};
+use crate::expr::{
+	Base, ChangeFeed, Dir, Id, Ident, Output, Permissions, Strand, Table, Thing, Value, Values,
+};
 use crate::expr::{Kind, TableType};
 use crate::iam::{Action, ResourceKind};
-use anyhow::Result;
+use crate::idx::index::IndexOperation;
+use crate::key::graph::Graph;
+use crate::key::r#ref::Ref;
+use crate::kvs::{KeyDecode, KeyEncode, Transaction};
+use crate::vs::VersionStamp;
+use anyhow::{Result, bail, ensure};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 use std::ops::Deref;
+use std::sync::Arc;
+use uuid::Uuid;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +43,22 @@ pub struct AlterTableStatement {
 	pub changefeed: Option<Option<ChangeFeed>>,
 	pub comment: Option<Option<Strand>>,
 	pub kind: Option<TableType>,
+	#[revision(start = 3)]
+	pub rename: Option<Ident>,
+	#[revision(start = 3)]
+	pub cascade: bool,
+	/// Suspend or resume the maintenance of a view
+	#[revision(start = 4)]
+	pub suspend: Option<bool>,
+	/// Recompute the contents of a view
+	#[revision(start = 4)]
+	pub rebuild: bool,
+	/// The condition which every record of the table must conform to
+	#[revision(start = 5)]
+	pub assert: Option<Option<Value>>,
+	/// Whether the existing records are checked against the `ASSERT` clause
+	#[revision(start = 5)]
+	pub validate: bool,
 }
 
 impl AlterTableStatement {
@@ -42,7 +72,7 @@ impl AlterTableStatement {
 
 	pub(crate) async fn compute(
 		&self,
-		_stk: &mut Stk,
+		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
@@ -66,7 +96,6 @@ impl AlterTableStatement {
 			}
 		};
 		// Process the statement
-		let key = crate::key::database::tb::new(ns, db, &self.name);
 		if let Some(full) = &self.full {
 			dt.full = *full;
 		}
@@ -82,22 +111,657 @@ impl AlterTableStatement {
 		if let Some(kind) = &self.kind {
 			dt.kind = kind.clone();
 		}
+		if let Some(assert) = &self.assert {
+			dt.assert.clone_from(assert);
+			// Check the existing records against the ASSERT clause
+			if self.validate {
+				dt.validate_records(stk, ctx, opt).await?;
+			}
+		}
 
 		// Add table relational fields
 		if matches!(self.kind, Some(TableType::Relation(_))) {
 			DefineTableStatement::add_in_out_fields(&txn, ns, db, &mut dt).await?;
 		}
+		// Control the maintenance of the view
+		if self.suspend.is_some() || self.rebuild {
+			self.alter_view(stk, ctx, opt, &mut dt).await?;
+		}
+		// Move the table and its contents to the new name
+		let name = match &self.rename {
+			Some(name) => {
+				self.move_table(stk, ctx, opt, &mut dt, name).await?;
+				name
+			}
+			None => &self.name,
+		};
 		// Set the table definition
+		let key = crate::key::database::tb::new(ns, db, name);
 		txn.set(key, revision::to_vec(&dt)?, None).await?;
 		// Record definition change
 		if self.changefeed.is_some() && dt.changefeed.is_some() {
-			txn.lock().await.record_table_change(ns, db, &self.name, &dt);
+			txn.lock().await.record_table_change(ns, db, name, &dt);
 		}
 		// Clear the cache
 		txn.clear();
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Suspends, resumes, or rebuilds the contents of a view.
+	///
+	/// While a view is suspended, the records which are changed in its
+	/// foreign tables are tracked, so that the view can catch up with
+	/// only those changes when it is resumed.
+	async fn alter_view(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		dt: &mut DefineTableStatement,
+	) -> Result<()> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check that the table is a view
+		let Some(view) = dt.view.clone() else {
+			bail!(Error::TbNotView {
+				name: self.name.to_raw(),
+			});
+		};
+		// The current versionstamp of the database
+		let vs = txn.get_db_versionstamp(ns, db).await?.map(VersionStamp::into_u128).unwrap_or(0);
+		// Resume the view, catching up with the tracked changes
+		if self.suspend == Some(false) && dt.suspended.is_some() {
+			// Don't run permissions
+			let opt = &opt.new_with_perms(false);
+			let beg = crate::key::table::vc::prefix(ns, db, &self.name)?;
+			let end = crate::key::table::vc::suffix(ns, db, &self.name)?;
+			let mut next = Some(beg.clone()..end.clone());
+			while let Some(rng) = next {
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for (k, v) in batch.result {
+					let vc = crate::key::table::vc::Vc::decode(&k)?;
+					let rid = Arc::new(Thing::from((vc.ft, vc.id.clone())));
+					let initial: Value = revision::from_slice(&v)?;
+					let current = txn.get_record(ns, db, vc.ft, &vc.id, None).await?;
+					stk.run(|stk| {
+						Document::process_view_change(stk, ctx, opt, dt, rid, initial, current)
+					})
+					.await?;
+				}
+			}
+			txn.delr(beg..end).await?;
+		}
+		// Recompute the contents of the view
+		if self.rebuild {
+			// Remove the index stores
+			#[cfg(not(target_family = "wasm"))]
+			ctx.get_index_stores()
+				.table_removed(ctx.get_index_builder(), &txn, ns, db, &self.name)
+				.await?;
+			#[cfg(target_family = "wasm")]
+			ctx.get_index_stores().table_removed(&txn, ns, db, &self.name).await?;
+			// Remove the view data, and the tracked changes
+			let key = crate::key::table::all::new(ns, db, &self.name).encode()?;
+			for category in DATA_CATEGORIES {
+				let mut beg = key.clone();
+				beg.extend_from_slice(&[category, 0x00]);
+				let mut end = key.clone();
+				end.extend_from_slice(&[category, 0xff]);
+				txn.delr(beg..end).await?;
+			}
+			let beg = crate::key::table::vc::prefix(ns, db, &self.name)?;
+			let end = crate::key::table::vc::suffix(ns, db, &self.name)?;
+			txn.delr(beg..end).await?;
+			// Force queries to run
+			let opt = &opt.new_with_force(Force::Table(Arc::new([dt.clone()])));
+			// Process each foreign table
+			for ft in view.what.0.iter() {
+				let stm = UpdateStatement {
+					what: Values(vec![Value::Table(ft.clone())]),
+					output: Some(Output::None),
+					..UpdateStatement::default()
+				};
+				stm.compute(stk, ctx, opt, None).await?;
+			}
+		}
+		// Update the maintenance state of the view
+		dt.suspended = match (self.suspend, dt.suspended) {
+			(Some(false), _) => None,
+			(Some(true), None) => Some(vs),
+			(_, Some(_)) if self.rebuild => Some(vs),
+			(_, suspended) => suspended,
+		};
+		// Update the view config of each foreign table
+		for ft in view.what.0.iter() {
+			let Some(fv) =
+				txn.all_tb_views(ns, db, ft).await?.iter().find(|fv| fv.name == self.name).cloned()
+			else {
+				continue;
+			};
+			let key = crate::key::table::ft::new(ns, db, ft, &self.name);
+			txn.set(
+				key,
+				revision::to_vec(&DefineTableStatement {
+					suspended: dt.suspended,
+					..fv
+				})?,
+				None,
+			)
+			.await?;
+			// Refresh the table cache
+			let key = crate::key::database::tb::new(ns, db, ft);
+			let tb = txn.get_tb(ns, db, ft).await?;
+			txn.set(
+				key,
+				revision::to_vec(&DefineTableStatement {
+					cache_tables_ts: Uuid::now_v7(),
+					..tb.as_ref().clone()
+				})?,
+				None,
+			)
+			.await?;
+			// Clear the cache
+			if let Some(cache) = ctx.get_cache() {
+				cache.clear_tb(ns, db, ft);
+			}
+		}
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.name);
+		}
+		// Clear the cache
+		txn.clear();
+		Ok(())
+	}
+
+	/// Moves the definitions, records, and edges of this table to a new name.
+	///
+	/// The `in` and `out` fields of the edges of the moved records, and the
+	/// fields which hold a reference to a moved record, are always rewritten.
+	/// Other record links to this table, which are stored in the fields of
+	/// any records, are only rewritten when CASCADE is specified. Otherwise
+	/// they are left in place, pointing at the old table name.
+	async fn move_table(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		dt: &mut DefineTableStatement,
+		name: &str,
+	) -> Result<()> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// The names of the tables
+		let old = self.name.as_str();
+		// Check that the new table does not exist
+		match txn.get_tb(ns, db, name).await {
+			Ok(_) => bail!(Error::TbAlreadyExists {
+				name: name.to_owned(),
+			}),
+			Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => (),
+			Err(e) => return Err(e),
+		}
+		// Views refer to their source tables by name
+		ensure!(
+			dt.view.is_none() && txn.all_tb_views(ns, db, old).await?.is_empty(),
+			Error::TbRenameView {
+				name: old.to_owned(),
+			}
+		);
+		// Remove the index stores
+		#[cfg(not(target_family = "wasm"))]
+		ctx.get_index_stores().table_removed(ctx.get_index_builder(), &txn, ns, db, old).await?;
+		#[cfg(target_family = "wasm")]
+		ctx.get_index_stores().table_removed(&txn, ns, db, old).await?;
+		// Get the live queries
+		let lvs = txn.all_tb_lives(ns, db, old).await?;
+		// Move the field definitions
+		let mut links = Vec::new();
+		let mut dangling = Vec::new();
+		for fd in txn.all_tb_fields(ns, db, old, None).await?.iter() {
+			let mut fd = DefineFieldStatement {
+				what: name.into(),
+				..fd.clone()
+			};
+			let mut kind = fd.kind.clone();
+			if kind.as_mut().is_some_and(|k| k.rename_table(old, name)) {
+				if self.cascade {
+					fd.kind = kind;
+					links.push(fd.clone());
+				} else {
+					dangling.push(format!("{name}.{}", fd.name));
+				}
+			}
+			let key = crate::key::table::fd::new(ns, db, name, &fd.name.to_string());
+			txn.set(key, revision::to_vec(&fd)?, None).await?;
+		}
+		// Move the event definitions
+		for ev in txn.all_tb_events(ns, db, old).await?.iter() {
+			let mut ev = ev.clone();
+			ev.what = name.into();
+			let key = crate::key::table::ev::new(ns, db, name, &ev.name);
+			txn.set(key, revision::to_vec(&ev)?, None).await?;
+		}
+		// Move the index definitions
+		let mut ixs = Vec::new();
+		for ix in txn.all_tb_indexes(ns, db, old).await?.iter() {
+			let ix = DefineIndexStatement {
+				what: name.into(),
+				..ix.clone()
+			};
+			let key = crate::key::table::ix::new(ns, db, name, &ix.name);
+			txn.set(key, revision::to_vec(&ix)?, None).await?;
+			ixs.push(ix);
+		}
+		// Fields with a record reference
+		let refs: Vec<_> = txn
+			.all_tb_fields(ns, db, old, None)
+			.await?
+			.iter()
+			.filter(|fd| fd.reference.is_some())
+			.cloned()
+			.collect();
+		// Move the records
+		let beg = crate::key::thing::prefix(ns, db, old)?;
+		let end = crate::key::thing::suffix(ns, db, old)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let id = crate::key::thing::Thing::decode(&k)?.id;
+				let rid = Thing::from((name, id.clone()));
+				let mut doc: Value = revision::from_slice(&v)?;
+				doc.put(&*ID, rid.clone().into());
+				// Edges between records of this table are moved here
+				if doc.pick(&*EDGE).is_true() {
+					Self::rename_edge(&mut doc, old, name);
+				}
+				// References to records of this table are moved here
+				Self::rename_links(&mut doc, &refs, old, name);
+				if !links.is_empty() {
+					Self::rename_links(&mut doc, &links, old, name);
+				}
+				txn.set_record(ns, db, name, &id, doc.clone()).await?;
+				// Move the version of the record
+				if dt.versioned {
+					let version = txn.get_record_version(ns, db, old, &id).await?;
+					txn.set_record_version(ns, db, name, &id, version).await?;
+				}
+				// Index the record under the new name
+				let cur = CursorDoc::new(Some(rid.clone().into()), None, doc.clone());
+				for ix in ixs.iter() {
+					let n =
+						stk.run(|stk| Document::build_opt_values(stk, ctx, opt, ix, &cur)).await?;
+					let mut io = IndexOperation::new(ctx, opt, ix, None, n, &rid);
+					stk.run(|stk| io.compute(stk)).await?;
+				}
+				// Move the edges of the record, and the records on the other side
+				for fk in Self::rename_edges(&txn, ns, db, old, name, &id).await? {
+					Self::relink(stk, ctx, opt, &fk, old, name, |doc| {
+						doc.pick(&*EDGE).is_true() && Self::rename_edge(doc, old, name)
+					})
+					.await?;
+				}
+				// Move the references of the record, and the records holding them
+				for (fk, fd) in Self::rename_refs(&txn, ns, db, old, name, &id).await? {
+					Self::relink(stk, ctx, opt, &fk, old, name, |doc| {
+						Self::rename_links(doc, &[fd], old, name)
+					})
+					.await?;
+				}
+				for fd in refs.iter() {
+					for r in Self::record_links(&doc.pick(&fd.name)) {
+						if r.tb != old && r.tb != name {
+							Self::rename_referrer(&txn, ns, db, old, name, &id, &r).await?;
+						}
+					}
+				}
+			}
+		}
+		// Rewrite the record links in other tables
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			if tb.name.as_str() == old {
+				continue;
+			}
+			let mut links = Vec::new();
+			for fd in txn.all_tb_fields(ns, db, &tb.name, None).await?.iter() {
+				let mut fd = fd.clone();
+				if fd.kind.as_mut().is_some_and(|k| k.rename_table(old, name)) {
+					links.push(fd);
+				}
+			}
+			if links.is_empty() {
+				continue;
+			}
+			if self.cascade {
+				Self::cascade(stk, ctx, opt, &tb.name, &links, old, name).await?;
+			} else {
+				dangling.extend(links.iter().map(|fd| format!("{}.{}", tb.name, fd.name)));
+			}
+		}
+		if !dangling.is_empty() {
+			warn!(
+				"Renamed table '{old}' to '{name}', but record links in {} still refer to '{old}'",
+				dangling.join(", ")
+			);
+		}
+		// Delete the old definition and data
+		txn.del(crate::key::database::tb::new(ns, db, old)).await?;
+		txn.delp(crate::key::table::all::new(ns, db, old)).await?;
+		// Terminate the live queries on the old table
+		if let Some(chn) = opt.sender.as_ref() {
+			for lv in lvs.iter() {
+				chn.send(Notification {
+					id: lv.id,
+					action: dbs::Action::Killed,
+					record: Value::Table(Table::from(old)),
+					result: Value::Table(Table::from(name)),
+				})
+				.await?;
+			}
+		}
+		// Update the table definition
+		dt.name = name.into();
+		if self.cascade {
+			if let TableType::Relation(rel) = &mut dt.kind {
+				for kind in [&mut rel.from, &mut rel.to].into_iter().flatten() {
+					kind.rename_table(old, name);
+				}
+			}
+		}
+		dt.cache_fields_ts = Uuid::now_v7();
+		dt.cache_events_ts = Uuid::now_v7();
+		dt.cache_indexes_ts = Uuid::now_v7();
+		dt.cache_tables_ts = Uuid::now_v7();
+		dt.cache_lives_ts = Uuid::now_v7();
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, old);
+			cache.clear_tb(ns, db, name);
+		}
+		Ok(())
+	}
+
+	/// Rewrites the record links in the specified fields of all records in a table
+	async fn cascade(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &str,
+		links: &[DefineFieldStatement],
+		old: &str,
+		name: &str,
+	) -> Result<()> {
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		// Save the field definitions which now refer to the new table
+		for fd in links.iter() {
+			let key = crate::key::table::fd::new(ns, db, tb, &fd.name.to_string());
+			txn.set(key, revision::to_vec(fd)?, None).await?;
+		}
+		// Rewrite the table type of relation tables
+		let mut dt = txn.get_tb(ns, db, tb).await?.deref().clone();
+		if let TableType::Relation(rel) = &mut dt.kind {
+			for kind in [&mut rel.from, &mut rel.to].into_iter().flatten() {
+				kind.rename_table(old, name);
+			}
+			dt.cache_fields_ts = Uuid::now_v7();
+			let key = crate::key::database::tb::new(ns, db, tb);
+			txn.set(key, revision::to_vec(&dt)?, None).await?;
+		}
+		// Rewrite the records, and update their indexes
+		let ixs = txn.all_tb_indexes(ns, db, tb).await?;
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let id = crate::key::thing::Thing::decode(&k)?.id;
+				let rid = Thing::from((tb, id.clone()));
+				let before: Value = revision::from_slice(&v)?;
+				let mut after = before.clone();
+				if !Self::rename_links(&mut after, links, old, name) {
+					continue;
+				}
+				Self::store(stk, ctx, opt, &ixs, &rid, before, after).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Rewrites a record which links to a moved record, if it is changed
+	async fn relink(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rid: &Thing,
+		old: &str,
+		name: &str,
+		rename: impl FnOnce(&mut Value) -> bool,
+	) -> Result<()> {
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		// Records of the moved table are rewritten when they are moved
+		if rid.tb == old || rid.tb == name {
+			return Ok(());
+		}
+		let before = txn.get_record(ns, db, &rid.tb, &rid.id, None).await?;
+		let mut after = before.as_ref().clone();
+		if !rename(&mut after) {
+			return Ok(());
+		}
+		let ixs = txn.all_tb_indexes(ns, db, &rid.tb).await?;
+		Self::store(stk, ctx, opt, &ixs, rid, before.as_ref().clone(), after).await
+	}
+
+	/// Stores a rewritten record, and updates its indexes
+	async fn store(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ixs: &[DefineIndexStatement],
+		rid: &Thing,
+		before: Value,
+		after: Value,
+	) -> Result<()> {
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		txn.set_record(ns, db, &rid.tb, &rid.id, after.clone()).await?;
+		let before = CursorDoc::new(Some(rid.clone().into()), None, before);
+		let after = CursorDoc::new(Some(rid.clone().into()), None, after);
+		for ix in ixs.iter() {
+			let o = stk.run(|stk| Document::build_opt_values(stk, ctx, opt, ix, &before)).await?;
+			let n = stk.run(|stk| Document::build_opt_values(stk, ctx, opt, ix, &after)).await?;
+			if o != n {
+				let mut io = IndexOperation::new(ctx, opt, ix, o, n, rid);
+				stk.run(|stk| io.compute(stk)).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Rewrites the `in` and `out` fields of an edge record which link to a table
+	fn rename_edge(doc: &mut Value, old: &str, name: &str) -> bool {
+		let mut changed = false;
+		for path in [&*IN, &*OUT] {
+			let mut val = doc.pick(path);
+			if Self::rename_link(&mut val, old, name) {
+				doc.put(path, val);
+				changed = true;
+			}
+		}
+		changed
+	}
+
+	/// Rewrites the record links in the specified fields of a document
+	fn rename_links(
+		doc: &mut Value,
+		links: &[DefineFieldStatement],
+		old: &str,
+		name: &str,
+	) -> bool {
+		let mut changed = false;
+		for fd in links.iter() {
+			for (path, mut val) in doc.walk(&fd.name) {
+				if Self::rename_link(&mut val, old, name) {
+					doc.put(&path, val);
+					changed = true;
+				}
+			}
+		}
+		changed
+	}
+
+	/// Rewrites any record links to a table within a value
+	fn rename_link(val: &mut Value, old: &str, name: &str) -> bool {
+		match val {
+			Value::Thing(t) if t.tb == old => {
+				t.tb = name.to_owned();
+				true
+			}
+			Value::Array(a) => {
+				a.iter_mut().fold(false, |c, v| Self::rename_link(v, old, name) || c)
+			}
+			Value::Object(o) => {
+				o.values_mut().fold(false, |c, v| Self::rename_link(v, old, name) || c)
+			}
+			_ => false,
+		}
+	}
+
+	/// Collects the record links within a value
+	fn record_links(val: &Value) -> Vec<Thing> {
+		match val {
+			Value::Thing(t) => vec![t.clone()],
+			Value::Array(a) => a.iter().flat_map(Self::record_links).collect(),
+			Value::Object(o) => o.values().flat_map(Self::record_links).collect(),
+			_ => vec![],
+		}
+	}
+
+	/// Moves the graph edges of a record, along with the edges pointing back at
+	/// it, returning the records in other tables on the other side of the edges
+	async fn rename_edges(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		old: &str,
+		name: &str,
+		id: &Id,
+	) -> Result<Vec<Thing>> {
+		let mut linked = Vec::new();
+		let beg = crate::key::graph::prefix(ns, db, old, id)?;
+		let end = crate::key::graph::suffix(ns, db, old, id)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let eg = Graph::decode(&k)?;
+				// Edges between records of this table are both moved here
+				if eg.ft == old {
+					let key = Graph::new_from_id(ns, db, name, id.clone(), eg.eg, name, eg.fk);
+					txn.set(key, vec![], None).await?;
+					continue;
+				}
+				let key = Graph::new_from_id(
+					ns,
+					db,
+					name,
+					id.clone(),
+					eg.eg.clone(),
+					eg.ft,
+					eg.fk.clone(),
+				);
+				txn.set(key, vec![], None).await?;
+				// Move the edge pointing back at this record
+				let dir = match eg.eg {
+					Dir::In => Dir::Out,
+					Dir::Out => Dir::In,
+					Dir::Both => Dir::Both,
+				};
+				let key =
+					Graph::new_from_id(ns, db, eg.ft, eg.fk.clone(), dir.clone(), old, id.clone());
+				txn.del(key).await?;
+				let key = Graph::new_from_id(ns, db, eg.ft, eg.fk.clone(), dir, name, id.clone());
+				txn.set(key, vec![], None).await?;
+				linked.push(Thing::from((eg.ft, eg.fk)));
+			}
+		}
+		Ok(linked)
+	}
+
+	/// Moves the references pointing at a record, returning the records in
+	/// other tables which hold the references, with the referencing fields
+	async fn rename_refs(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		old: &str,
+		name: &str,
+		id: &Id,
+	) -> Result<Vec<(Thing, DefineFieldStatement)>> {
+		let mut linked = Vec::new();
+		let beg = crate::key::r#ref::prefix(ns, db, old, id)?;
+		let end = crate::key::r#ref::suffix(ns, db, old, id)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let r = Ref::decode(&k)?;
+				let ft = if r.ft == old {
+					name
+				} else {
+					r.ft
+				};
+				let key = Ref::new(ns, db, name, id.clone(), ft, r.ff, r.fk.clone());
+				txn.set(key, vec![], None).await?;
+				if r.ft != old {
+					let fd = txn.get_tb_field(ns, db, r.ft, r.ff).await?;
+					linked.push((Thing::from((r.ft, r.fk)), fd.as_ref().clone()));
+				}
+			}
+		}
+		Ok(linked)
+	}
+
+	/// Moves the reference stored on a record which is referenced by a record in this table
+	async fn rename_referrer(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		old: &str,
+		name: &str,
+		id: &Id,
+		rid: &Thing,
+	) -> Result<()> {
+		let beg = crate::key::r#ref::ftprefix(ns, db, &rid.tb, &rid.id, old)?;
+		let end = crate::key::r#ref::ftsuffix(ns, db, &rid.tb, &rid.id, old)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let r = Ref::decode(&k)?;
+				if r.fk == *id {
+					txn.del(&k).await?;
+					let key = Ref::new(ns, db, &rid.tb, rid.id.clone(), name, r.ff, r.fk);
+					txn.set(key, vec![], None).await?;
+				}
+			}
+		}
+		Ok(())
+	}
 }
 
 impl Display for AlterTableStatement {
@@ -156,6 +820,32 @@ impl Display for AlterTableStatement {
 				write!(f, " DROP CHANGEFEED")?;
 			}
 		}
+		if let Some(rename) = &self.rename {
+			write!(f, " RENAME TO {rename}")?;
+			if self.cascade {
+				f.write_str(" CASCADE")?;
+			}
+		}
+		if let Some(suspend) = self.suspend {
+			f.write_str(if suspend {
+				" SUSPEND"
+			} else {
+				" RESUME"
+			})?;
+		}
+		if self.rebuild {
+			f.write_str(" REBUILD")?;
+		}
+		if let Some(assert) = &self.assert {
+			if let Some(assert) = assert {
+				write!(f, " ASSERT {assert}")?;
+				if self.validate {
+					f.write_str(" VALIDATE")?;
+				}
+			} else {
+				write!(f, " DROP ASSERT")?;
+			}
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -1,32 +1,41 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::Base;
 use crate::expr::ident::Ident;
 use crate::expr::index::Index;
 use crate::expr::value::Value;
+use crate::expr::{Base, Datetime, Object};
 use crate::iam::{Action, ResourceKind};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
-use crate::kvs::TransactionType;
+use crate::kvs::{KeyDecode, KeyEncode, TransactionType};
+use crate::vs::VersionStamp;
 use anyhow::{Result, bail};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum AnalyzeStatement {
 	Idx(Ident, Ident),
+	#[revision(start = 2)]
+	Tb(Ident),
 }
 
 impl AnalyzeStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		matches!(self, Self::Tb(_))
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -64,7 +73,162 @@ impl AnalyzeStatement {
 				// Return the result object
 				Ok(value)
 			}
+			AnalyzeStatement::Tb(tb) => {
+				// Allowed to run?
+				opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+				// Get the NS and DB
+				let (ns, db) = opt.ns_db()?;
+				// Get the transaction
+				let txn = ctx.tx();
+				// Check that the table exists
+				txn.get_tb(ns, db, tb).await?;
+				// Compute the statistics
+				let stats = TableStatistics::compute(ctx, ns, db, tb).await?;
+				// Save the statistics in the catalog
+				let key = crate::key::table::st::new(ns, db, tb);
+				txn.set(key, revision::to_vec(&stats)?, None).await?;
+				// Return the result object
+				Ok(stats.into())
+			}
+		}
+	}
+}
+
+/// The statistics of a table, as computed by `ANALYZE TABLE`.
+///
+/// The statistics are approximate, as they are only
+/// refreshed when the table is analyzed again.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct TableStatistics {
+	/// The number of records in the table
+	pub(crate) rows: u64,
+	/// The total size in bytes of the keys and values of the records
+	pub(crate) bytes: u64,
+	/// The statistics of each index on the table
+	pub(crate) indexes: BTreeMap<String, IndexStatistics>,
+	/// The versionstamp of the database when the table was analyzed
+	pub(crate) versionstamp: u128,
+	/// The time when the table was analyzed
+	pub(crate) analyzed: Datetime,
+}
+
+/// The statistics of an index, as computed by `ANALYZE TABLE`.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct IndexStatistics {
+	/// The number of keys stored for the index
+	pub(crate) entries: u64,
+	/// The number of distinct indexed values, for standard and unique indexes
+	pub(crate) distinct: Option<u64>,
+	/// The total size in bytes of the keys and values of the index
+	pub(crate) bytes: u64,
+}
+
+impl TableStatistics {
+	/// Scans the records and indexes of a table, in batches
+	async fn compute(ctx: &Context, ns: &str, db: &str, tb: &str) -> Result<Self> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// Scan the records
+		let mut stats = TableStatistics::default();
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			if ctx.is_done(true).await? {
+				break;
+			}
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				stats.rows += 1;
+				stats.bytes += (k.len() + v.len()) as u64;
+			}
+			yield_now!();
+		}
+		// Scan the indexes
+		for ix in txn.all_tb_indexes(ns, db, tb).await?.iter() {
+			let mut ixs = IndexStatistics::default();
+			// Standard and unique indexes store a key for each indexed value
+			let values = match ix.index {
+				Index::Idx | Index::Uniq => {
+					let beg = crate::key::index::Index::prefix_beg(ns, db, tb, &ix.name)?;
+					let end = crate::key::index::Index::prefix_end(ns, db, tb, &ix.name)?;
+					ixs.distinct = Some(0);
+					Some(beg..end)
+				}
+				_ => None,
+			};
+			let mut last = None;
+			let mut beg = crate::key::index::all::new(ns, db, tb, &ix.name).encode()?;
+			let mut end = beg.clone();
+			beg.push(0x00);
+			end.push(0xff);
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				if ctx.is_done(true).await? {
+					break;
+				}
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for (k, v) in batch.result {
+					ixs.entries += 1;
+					ixs.bytes += (k.len() + v.len()) as u64;
+					// Keys are sorted, so equal values are adjacent
+					if let (Some(rng), Some(distinct)) = (&values, &mut ixs.distinct) {
+						if rng.contains(&k) {
+							let key = crate::key::index::Index::decode(&k)?;
+							if last.as_ref() != Some(&*key.fd) {
+								*distinct += 1;
+								last = Some(key.fd.into_owned());
+							}
+						}
+					}
+				}
+				yield_now!();
+			}
+			stats.indexes.insert(ix.name.to_raw(), ixs);
+		}
+		// Record when the table was analyzed
+		stats.versionstamp =
+			txn.get_db_versionstamp(ns, db).await?.map(VersionStamp::into_u128).unwrap_or(0);
+		stats.analyzed = Datetime(txn.timestamp());
+		Ok(stats)
+	}
+
+	/// Estimates the number of records matching a single value of an index
+	pub(crate) fn estimate(&self, ix: &str) -> Option<u64> {
+		let ixs = self.indexes.get(ix)?;
+		match ixs.distinct? {
+			0 => Some(0),
+			n => Some(ixs.entries.div_ceil(n)),
+		}
+	}
+}
+
+impl From<TableStatistics> for Value {
+	fn from(v: TableStatistics) -> Self {
+		let mut indexes = Object::default();
+		for (name, ix) in v.indexes {
+			indexes.insert(
+				name,
+				Value::from(map! {
+					"entries".to_string() => ix.entries.into(),
+					"distinct".to_string(), if let Some(n) = ix.distinct => n.into(),
+					"bytes".to_string() => ix.bytes.into(),
+				}),
+			);
 		}
+		Value::from(map! {
+			"rows".to_string() => v.rows.into(),
+			"bytes".to_string() => v.bytes.into(),
+			"indexes".to_string() => indexes.into(),
+			"versionstamp".to_string() => v.versionstamp.into(),
+			"analyzed".to_string() => v.analyzed.into(),
+		})
 	}
 }
 
@@ -72,6 +236,7 @@ impl Display for AnalyzeStatement {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Self::Idx(tb, idx) => write!(f, "ANALYZE INDEX {idx} ON {tb}"),
+			Self::Tb(tb) => write!(f, "ANALYZE TABLE {tb}"),
 		}
 	}
 }
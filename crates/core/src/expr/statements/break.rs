@@ -1,18 +1,21 @@
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
-use crate::expr::ControlFlow;
 use crate::expr::value::Value;
+use crate::expr::{ControlFlow, Ident};
 use crate::{ctx::Context, expr::FlowResult};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct BreakStatement;
+pub struct BreakStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
+}
 
 impl BreakStatement {
 	/// Check if we require a writeable transaction
@@ -27,12 +30,16 @@ impl BreakStatement {
 		_opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> FlowResult<Value> {
-		Err(ControlFlow::Break)
+		Err(ControlFlow::Break(self.label.clone()))
 	}
 }
 
 impl fmt::Display for BreakStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("BREAK")
+		f.write_str("BREAK")?;
+		if let Some(ref v) = self.label {
+			write!(f, " {v}")?;
+		}
+		Ok(())
 	}
 }
@@ -1,17 +1,20 @@
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
-use crate::expr::{ControlFlow, Value};
+use crate::expr::{ControlFlow, Ident, Value};
 use crate::{ctx::Context, expr::FlowResult};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct ContinueStatement;
+pub struct ContinueStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
+}
 
 impl ContinueStatement {
 	/// Check if we require a writeable transaction
@@ -26,12 +29,16 @@ impl ContinueStatement {
 		_opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> FlowResult<Value> {
-		Err(ControlFlow::Continue)
+		Err(ControlFlow::Continue(self.label.clone()))
 	}
 }
 
 impl fmt::Display for ContinueStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("CONTINUE")
+		f.write_str("CONTINUE")?;
+		if let Some(ref v) = self.label {
+			write!(f, " {v}")?;
+		}
+		Ok(())
 	}
 }
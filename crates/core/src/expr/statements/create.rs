@@ -4,12 +4,13 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::{Data, FlowResultExt as _, Output, Timeout, Value, Values, Version};
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
-use anyhow::{Result, ensure};
+use anyhow::Result;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
 #[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -61,6 +62,7 @@ impl CreateStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false).with_version(version);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = stm.setup_timeout(ctx)?;
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
@@ -88,9 +90,9 @@ impl CreateStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		match res {
 			// This is a single record result
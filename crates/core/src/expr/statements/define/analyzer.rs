@@ -3,9 +3,10 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Array, Base, Ident, Strand, Value, filter::Filter, tokenizer::Tokenizer};
+use crate::expr::{Array, Base, Ident, Index, Strand, Value, filter::Filter, tokenizer::Tokenizer};
 use crate::iam::{Action, ResourceKind};
-use anyhow::{Result, bail};
+use crate::kvs::Transaction;
+use anyhow::{Result, bail, ensure};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -41,7 +42,7 @@ impl DefineAnalyzerStatement {
 		let txn = ctx.tx();
 		let (ns, db) = opt.ns_db()?;
 		// Check if the definition exists
-		if txn.get_db_analyzer(ns, db, &self.name).await.is_ok() {
+		if let Ok(az) = txn.get_db_analyzer(ns, db, &self.name).await {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite && !opt.import {
@@ -49,6 +50,17 @@ impl DefineAnalyzerStatement {
 					name: self.name.to_string(),
 				});
 			}
+			// The terms of the indexes which use the analyzer would no longer match
+			if !opt.import && !self.analyzes_like(&az) {
+				let indexes = Self::indexes(&txn, ns, db, &self.name).await?;
+				ensure!(
+					indexes.is_empty(),
+					Error::AzInUse {
+						name: self.name.to_string(),
+						indexes: indexes.join(", "),
+					}
+				);
+			}
 		}
 		// Process the statement
 		let key = crate::key::database::az::new(ns, db, &self.name);
@@ -67,6 +79,28 @@ impl DefineAnalyzerStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Checks if another definition produces the same terms
+	fn analyzes_like(&self, other: &Self) -> bool {
+		self.function == other.function
+			&& self.tokenizers == other.tokenizers
+			&& self.filters == other.filters
+	}
+
+	/// Lists the full-text indexes which use an analyzer
+	async fn indexes(txn: &Transaction, ns: &str, db: &str, az: &str) -> Result<Vec<String>> {
+		let mut names = Vec::new();
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			for ix in txn.all_tb_indexes(ns, db, &tb.name).await?.iter() {
+				if let Index::Search(p) = &ix.index {
+					if p.az.0 == az {
+						names.push(format!("'{}.{}'", tb.name, ix.name));
+					}
+				}
+			}
+		}
+		Ok(names)
+	}
 }
 
 impl Display for DefineAnalyzerStatement {
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Base, Ident, Strand, Value, changefeed::ChangeFeed};
+use crate::expr::{Base, Ident, ScopeCapabilities, Strand, Value, changefeed::ChangeFeed};
 use crate::iam::{Action, ResourceKind};
 use anyhow::{Result, bail};
 
@@ -11,7 +11,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -24,6 +24,8 @@ pub struct DefineDatabaseStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	#[revision(start = 4)]
+	pub capabilities: Option<ScopeCapabilities>,
 }
 
 impl DefineDatabaseStatement {
@@ -36,6 +38,10 @@ impl DefineDatabaseStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
+		// Only root users can allow what the namespace denies
+		if self.capabilities.as_ref().is_some_and(ScopeCapabilities::has_allow_rules) {
+			opt.is_allowed(Action::Edit, ResourceKind::Namespace, &Base::Root)?;
+		}
 		// Get the NS
 		let ns = opt.ns()?;
 		// Fetch the transaction
@@ -96,6 +102,9 @@ impl Display for DefineDatabaseStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.capabilities {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -105,6 +114,7 @@ impl InfoStructure for DefineDatabaseStatement {
 		Value::from(map! {
 			"name".to_string() => self.name.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"capabilities".to_string(), if let Some(v) = self.capabilities => v.structure(),
 		})
 	}
 }
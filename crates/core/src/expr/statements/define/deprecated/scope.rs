@@ -42,6 +42,7 @@ impl From<DefineScopeStatement> for DefineAccessStatement {
 						alg: Algorithm::Hs512,
 						key: sc.code,
 					}),
+					keys: Vec::new(),
 				},
 				bearer: None,
 			}),
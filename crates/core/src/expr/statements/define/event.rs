@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use uuid::Uuid;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -27,6 +27,12 @@ pub struct DefineEventStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	/// The order in which the events on a table are processed
+	#[revision(start = 4)]
+	pub order: Option<u32>,
+	/// A condition which is checked before the event context is created
+	#[revision(start = 4)]
+	pub guard: Option<Value>,
 }
 
 impl DefineEventStatement {
@@ -44,31 +50,40 @@ impl DefineEventStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb_event(ns, db, &self.what, &self.name).await.is_ok() {
-			if self.if_not_exists {
-				return Ok(Value::None);
-			} else if !self.overwrite && !opt.import {
-				bail!(Error::EvAlreadyExists {
+		let mut def = DefineEventStatement {
+			// Don't persist the `IF NOT EXISTS` clause to schema
+			if_not_exists: false,
+			overwrite: false,
+			..self.clone()
+		};
+		match txn.get_tb_event(ns, db, &self.what, &self.name).await {
+			Ok(ev) => {
+				if self.if_not_exists {
+					return Ok(Value::None);
+				} else if !self.overwrite && !opt.import {
+					bail!(Error::EvAlreadyExists {
+						name: self.name.to_string(),
+					});
+				}
+				// Keep the existing bodies if none are specified
+				if self.overwrite && self.then.is_empty() {
+					def.when = ev.when.clone();
+					def.then = ev.then.clone();
+				}
+			}
+			Err(_) if self.overwrite && self.then.is_empty() => {
+				bail!(Error::EvNotFound {
 					name: self.name.to_string(),
 				});
 			}
+			Err(_) => (),
 		}
 		// Process the statement
 		let key = crate::key::table::ev::new(ns, db, &self.what, &self.name);
 		txn.get_or_add_ns(ns, opt.strict).await?;
 		txn.get_or_add_db(ns, db, opt.strict).await?;
 		txn.get_or_add_tb(ns, db, &self.what, opt.strict).await?;
-		txn.set(
-			key,
-			revision::to_vec(&DefineEventStatement {
-				// Don't persist the `IF NOT EXISTS` clause to schema
-				if_not_exists: false,
-				overwrite: false,
-				..self.clone()
-			})?,
-			None,
-		)
-		.await?;
+		txn.set(key, revision::to_vec(&def)?, None).await?;
 		// Refresh the table cache
 		let key = crate::key::database::tb::new(ns, db, &self.what);
 		let tb = txn.get_tb(ns, db, &self.what).await?;
@@ -101,7 +116,17 @@ impl Display for DefineEventStatement {
 		if self.overwrite {
 			write!(f, " OVERWRITE")?
 		}
-		write!(f, " {} ON {} WHEN {} THEN {}", self.name, self.what, self.when, self.then)?;
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if let Some(ref v) = self.order {
+			write!(f, " ORDER {v}")?
+		}
+		if let Some(ref v) = self.guard {
+			write!(f, " IF {v}")?
+		}
+		// An overwrite may leave out the bodies of the event
+		if !self.then.is_empty() || !self.overwrite {
+			write!(f, " WHEN {} THEN {}", self.when, self.then)?;
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -116,6 +141,8 @@ impl InfoStructure for DefineEventStatement {
 			"what".to_string() => self.what.structure(),
 			"when".to_string() => self.when.structure(),
 			"then".to_string() => self.then.structure(),
+			"order".to_string(), if let Some(v) = self.order => v.into(),
+			"if".to_string(), if let Some(v) = self.guard => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
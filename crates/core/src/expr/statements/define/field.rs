@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::capabilities::ExperimentalTarget;
@@ -8,10 +9,11 @@ use crate::expr::reference::Reference;
 use crate::expr::statements::DefineTableStatement;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Base, Ident, Idiom, Kind, Permissions, Strand, Value};
+use crate::expr::{Expression, Subquery};
 use crate::expr::{Literal, Part};
 use crate::expr::{Relation, TableType};
 use crate::iam::{Action, ResourceKind};
-use crate::kvs::Transaction;
+use crate::kvs::{KeyDecode as _, Transaction};
 use anyhow::{Result, bail, ensure};
 
 use revision::revisioned;
@@ -20,7 +22,7 @@ use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 6)]
+#[revisioned(revision = 9)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -46,6 +48,19 @@ pub struct DefineFieldStatement {
 	pub reference: Option<Reference>,
 	#[revision(start = 6)]
 	pub default_always: bool,
+	/// Whether the record expires at the datetime stored in this field
+	#[revision(start = 7)]
+	pub expire: bool,
+	/// Whether the field is set to the datetime at which the record was created
+	#[revision(start = 8)]
+	pub created: bool,
+	/// Whether the field is set to the datetime at which the record was last written
+	#[revision(start = 8)]
+	pub modified: bool,
+	/// The expression which computes the field when the record is read,
+	/// as the field is never stored
+	#[revision(start = 9)]
+	pub computed: Option<Value>,
 }
 
 impl DefineFieldStatement {
@@ -73,12 +88,16 @@ impl DefineFieldStatement {
 		// Disallow mismatched types
 		self.disallow_mismatched_types(ctx, ns, db).await?;
 
+		// Disallow computed fields which depend on themselves
+		self.disallow_computed_cycles(ctx, ns, db).await?;
+
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Get the name of the field
 		let fd = self.name.to_string();
 		// Check if the definition exists
-		if txn.get_tb_field(ns, db, &self.what, &fd).await.is_ok() {
+		let existing = txn.get_tb_field(ns, db, &self.what, &fd).await.ok();
+		if existing.is_some() {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite && !opt.import {
@@ -124,6 +143,10 @@ impl DefineFieldStatement {
 		txn.clear();
 		// Process possible recursive defitions
 		self.process_recursive_definitions(ns, db, txn.clone()).await?;
+		// Create the references of the existing records
+		if self.reference.is_some() && existing.is_none_or(|fd| fd.reference.is_none()) {
+			self.process_existing_references(ctx, ns, db).await?;
+		}
 		// If this is an `in` field then check relation definitions
 		if fd.as_str() == "in" {
 			// Get the table definition that this field belongs to
@@ -266,6 +289,52 @@ impl DefineFieldStatement {
 		Ok(())
 	}
 
+	/// Creates the references of the records which already exist in the
+	/// table, as they were stored before the `REFERENCE` clause was added
+	async fn process_existing_references(&self, ctx: &Context, ns: &str, db: &str) -> Result<()> {
+		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::RecordReferences) {
+			return Ok(());
+		}
+		let txn = ctx.tx();
+		let beg = crate::key::thing::prefix(ns, db, &self.what)?;
+		let end = crate::key::thing::suffix(ns, db, &self.what)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Fetch the next batch of keys and values
+			let res = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				// Decode the record
+				let key = crate::key::thing::Thing::decode(k)?;
+				let val: Value = revision::from_slice(v)?;
+				// The record ids nested in arrays are referenced by the subtype fields
+				let mut name = self.name.clone();
+				let mut vals = vec![val.pick(&name)];
+				while !vals.is_empty() {
+					let ff = name.to_string();
+					let mut nested = Vec::new();
+					for v in vals {
+						match v {
+							Value::Thing(t) => {
+								let key = crate::key::r#ref::new(
+									ns, db, &t.tb, &t.id, &self.what, &ff, &key.id,
+								);
+								txn.set(key, vec![], None).await?;
+							}
+							Value::Array(a) => nested.extend(a.0),
+							_ => {}
+						}
+					}
+					name.0.push(Part::All);
+					vals = nested;
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+
 	pub(crate) fn validate_reference_options(&self, ctx: &Context) -> Result<()> {
 		if !ctx.get_capabilities().allows_experimental(&ExperimentalTarget::RecordReferences) {
 			return Ok(());
@@ -306,6 +375,15 @@ impl DefineFieldStatement {
 				ensure!(!self.flex, Error::RefsTypeConflict("FLEXIBLE".into(), typename));
 
 				ensure!(!self.readonly, Error::RefsTypeConflict("READONLY".into(), typename));
+
+				ensure!(!self.created, Error::RefsTypeConflict("CREATED".into(), typename));
+
+				ensure!(!self.modified, Error::RefsTypeConflict("MODIFIED".into(), typename));
+
+				ensure!(
+					self.computed.is_none(),
+					Error::RefsTypeConflict("COMPUTED".into(), typename)
+				);
 			}
 
 			// If a reference is defined, the field must be a record
@@ -403,6 +481,114 @@ impl DefineFieldStatement {
 
 		Ok(())
 	}
+
+	pub(crate) async fn disallow_computed_cycles(
+		&self,
+		ctx: &Context,
+		ns: &str,
+		db: &str,
+	) -> Result<()> {
+		if self.computed.is_none() {
+			return Ok(());
+		}
+		let fds = ctx.tx().all_tb_fields(ns, db, &self.what, None).await?;
+		// This definition replaces any existing definition of the field
+		let mut computed: Vec<&DefineFieldStatement> =
+			fds.iter().filter(|fd| fd.computed.is_some() && fd.name != self.name).collect();
+		computed.push(self);
+		// Follow the computed fields which this field refers to
+		let mut path = vec![self];
+		if find_cycle(&computed, &mut path) {
+			bail!(Error::FdComputedCycle {
+				name: self.name.to_string(),
+				cycle: path.iter().map(|fd| fd.name.to_string()).collect::<Vec<_>>().join(" -> "),
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Checks if the COMPUTED clause of this field refers to another field
+	pub(crate) fn depends_on(&self, other: &DefineFieldStatement) -> bool {
+		let Some(expr) = &self.computed else {
+			return false;
+		};
+		let mut fields = Vec::new();
+		referenced_fields(expr, &mut fields);
+		fields.iter().any(|f| f.starts_with(&other.name) || other.name.starts_with(f))
+	}
+}
+
+/// Extends a path of computed fields until it leads back to its first field
+fn find_cycle<'a>(
+	computed: &[&'a DefineFieldStatement],
+	path: &mut Vec<&'a DefineFieldStatement>,
+) -> bool {
+	let Some(last) = path.last().copied() else {
+		return false;
+	};
+	for fd in computed.iter().copied().filter(|fd| last.depends_on(fd)) {
+		if fd.name == path[0].name {
+			path.push(fd);
+			return true;
+		}
+		// This cycle does not lead back to the first field
+		if path.iter().any(|p| p.name == fd.name) {
+			continue;
+		}
+		path.push(fd);
+		if find_cycle(computed, path) {
+			return true;
+		}
+		path.pop();
+	}
+	false
+}
+
+/// Collects the fields of the document which an expression refers to
+fn referenced_fields(v: &Value, out: &mut Vec<Idiom>) {
+	match v {
+		Value::Idiom(i) => {
+			// Only the leading fields of an idiom name a field of the document
+			let parts: Vec<Part> =
+				i.iter().take_while(|p| matches!(p, Part::Field(_))).cloned().collect();
+			if !parts.is_empty() {
+				out.push(Idiom(parts));
+			}
+		}
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => referenced_fields(v, out),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => {
+				referenced_fields(l, out);
+				referenced_fields(r, out);
+			}
+		},
+		Value::Function(f) => f.args().iter().for_each(|v| referenced_fields(v, out)),
+		Value::Array(a) => a.iter().for_each(|v| referenced_fields(v, out)),
+		Value::Object(o) => o.values().for_each(|v| referenced_fields(v, out)),
+		Value::Cast(c) => referenced_fields(&c.1, out),
+		Value::Subquery(s) => match s.as_ref() {
+			Subquery::Value(v) => referenced_fields(v, out),
+			Subquery::Ifelse(s) => {
+				for (cond, then) in s.exprs.iter() {
+					referenced_fields(cond, out);
+					referenced_fields(then, out);
+				}
+				if let Some(v) = &s.close {
+					referenced_fields(v, out);
+				}
+			}
+			_ => (),
+		},
+		_ => (),
+	}
 }
 
 impl Display for DefineFieldStatement {
@@ -432,6 +618,18 @@ impl Display for DefineFieldStatement {
 		if self.readonly {
 			write!(f, " READONLY")?
 		}
+		if self.expire {
+			write!(f, " EXPIRE")?
+		}
+		if self.created {
+			write!(f, " CREATED")?
+		}
+		if self.modified {
+			write!(f, " MODIFIED")?
+		}
+		if let Some(ref v) = self.computed {
+			write!(f, " COMPUTED {v}")?
+		}
 		if let Some(ref v) = self.value {
 			write!(f, " VALUE {v}")?
 		}
@@ -467,11 +665,15 @@ impl InfoStructure for DefineFieldStatement {
 			"flex".to_string() => self.flex.into(),
 			"kind".to_string(), if let Some(v) = self.kind => v.structure(),
 			"value".to_string(), if let Some(v) = self.value => v.structure(),
+			"computed".to_string(), if let Some(v) = self.computed => v.structure(),
 			"assert".to_string(), if let Some(v) = self.assert => v.structure(),
 			"default_always".to_string(), if self.default.is_some() => self.default_always.into(), // Only reported if DEFAULT is also enabled for this field
 			"default".to_string(), if let Some(v) = self.default => v.structure(),
 			"reference".to_string(), if let Some(v) = self.reference => v.structure(),
 			"readonly".to_string() => self.readonly.into(),
+			"expire".to_string(), if self.expire => true.into(),
+			"created".to_string(), if self.created => true.into(),
+			"modified".to_string(), if self.modified => true.into(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
@@ -12,7 +12,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -28,9 +28,49 @@ pub struct DefineFunctionStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub returns: Option<Kind>,
+	/// Further signatures of this function, with different numbers of arguments
+	#[revision(start = 5)]
+	pub overloads: Vec<DefineFunctionStatement>,
 }
 
 impl DefineFunctionStatement {
+	/// Get the minimum and maximum number of arguments of this signature
+	pub(crate) fn arity(&self) -> (usize, usize) {
+		// Any final optional arguments can be omitted
+		let optional = self
+			.args
+			.iter()
+			.rev()
+			.take_while(|(_, kind)| matches!(kind, Kind::Option(_) | Kind::Any))
+			.count();
+		(self.args.len() - optional, self.args.len())
+	}
+	/// Get the signature which accepts the specified number of arguments
+	pub(crate) fn signature(&self, len: usize) -> Option<&DefineFunctionStatement> {
+		std::iter::once(self).chain(&self.overloads).find(|v| {
+			let (min, max) = v.arity();
+			min <= len && len <= max
+		})
+	}
+	/// Describe the number of arguments accepted by this function
+	pub(crate) fn arguments_message(&self) -> String {
+		let arities = std::iter::once(self)
+			.chain(&self.overloads)
+			.map(|v| match v.arity() {
+				(1, 1) => String::from("1 argument"),
+				(r, t) if r == t => format!("{r} arguments"),
+				(r, t) => format!("{r} to {t} arguments"),
+			})
+			.collect::<Vec<_>>();
+		format!("The function expects {}.", arities.join(" or "))
+	}
+	/// Get each of the signatures of this function separately
+	fn signatures(&self) -> impl Iterator<Item = DefineFunctionStatement> + '_ {
+		std::iter::once(self).chain(&self.overloads).map(|v| DefineFunctionStatement {
+			overloads: Vec::new(),
+			..v.clone()
+		})
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -44,30 +84,41 @@ impl DefineFunctionStatement {
 		let txn = ctx.tx();
 		// Check if the definition exists
 		let (ns, db) = opt.ns_db()?;
-		if txn.get_db_function(ns, db, &self.name).await.is_ok() {
-			if self.if_not_exists {
-				return Ok(Value::None);
-			} else if !self.overwrite && !opt.import {
-				bail!(Error::FcAlreadyExists {
-					name: self.name.to_string(),
-				});
+		let mut signatures = Vec::new();
+		if let Ok(fc) = txn.get_db_function(ns, db, &self.name).await {
+			// Signatures with a different number of arguments are overloads
+			let (min, max) = self.arity();
+			let (existing, overloads): (Vec<_>, Vec<_>) = fc.signatures().partition(|v| {
+				let (vmin, vmax) = v.arity();
+				vmin <= max && min <= vmax
+			});
+			if !existing.is_empty() {
+				if self.if_not_exists {
+					return Ok(Value::None);
+				} else if !self.overwrite && !opt.import {
+					bail!(Error::FcAlreadyExists {
+						name: self.name.to_string(),
+					});
+				}
 			}
+			signatures = overloads;
 		}
+		signatures.push(DefineFunctionStatement {
+			// Don't persist the `IF NOT EXISTS` clause to schema
+			if_not_exists: false,
+			overwrite: false,
+			overloads: Vec::new(),
+			..self.clone()
+		});
+		// Store the signatures ordered by their arguments
+		signatures.sort_by_key(|v| v.arity());
+		let mut fc = signatures.remove(0);
+		fc.overloads = signatures;
 		// Process the statement
 		let key = crate::key::database::fc::new(ns, db, &self.name);
 		txn.get_or_add_ns(ns, opt.strict).await?;
 		txn.get_or_add_db(ns, db, opt.strict).await?;
-		txn.set(
-			key,
-			revision::to_vec(&DefineFunctionStatement {
-				// Don't persist the `IF NOT EXISTS` clause to schema
-				if_not_exists: false,
-				overwrite: false,
-				..self.clone()
-			})?,
-			None,
-		)
-		.await?;
+		txn.set(key, revision::to_vec(&fc)?, None).await?;
 		// Clear the cache
 		txn.clear();
 		// Ok all good
@@ -106,6 +157,11 @@ impl fmt::Display for DefineFunctionStatement {
 			None
 		};
 		write!(f, "PERMISSIONS {}", self.permissions)?;
+		drop(_indent);
+		// Output each overload as a separate statement
+		for v in self.overloads.iter() {
+			write!(f, "; {v}")?;
+		}
 		Ok(())
 	}
 }
@@ -123,6 +179,11 @@ impl InfoStructure for DefineFunctionStatement {
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"returns".to_string(), if let Some(v) = self.returns => v.structure(),
+			"overloads".to_string(), if !self.overloads.is_empty() => self.overloads
+				.into_iter()
+				.map(InfoStructure::structure)
+				.collect::<Vec<Value>>()
+				.into(),
 		})
 	}
 }
@@ -8,7 +8,7 @@ use crate::expr::statements::DefineTableStatement;
 use crate::expr::statements::info::InfoStructure;
 #[cfg(target_family = "wasm")]
 use crate::expr::statements::{RemoveIndexStatement, UpdateStatement};
-use crate::expr::{Base, Ident, Idioms, Index, Part, Strand, Value};
+use crate::expr::{Base, Cond, Ident, Idioms, Index, Part, Strand, Value};
 #[cfg(target_family = "wasm")]
 use crate::expr::{Output, Values};
 use crate::iam::{Action, ResourceKind};
@@ -21,7 +21,7 @@ use std::fmt::{self, Display};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -37,9 +37,28 @@ pub struct DefineIndexStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub concurrently: bool,
+	#[revision(start = 5)]
+	pub cond: Option<Cond>,
+	/// Incremented every time the index is rebuilt concurrently
+	#[revision(start = 6)]
+	pub generation: u64,
+	/// Set when the build of the index was cancelled, until it is built again
+	#[revision(start = 7)]
+	pub cancelled: bool,
 }
 
 impl DefineIndexStatement {
+	/// The name under which the data of the index is stored.
+	/// A concurrent rebuild writes the new structure under the next generation,
+	/// so that the current one can still be used until the rebuild is complete.
+	pub(crate) fn storage_name(&self) -> String {
+		if self.generation == 0 {
+			self.name.to_raw()
+		} else {
+			format!("{}#{}", self.name.as_str(), self.generation)
+		}
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -50,12 +69,16 @@ impl DefineIndexStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		// Check the scoring parameters of a search index
+		if let Index::Search(p) = &self.index {
+			p.sc.check()?;
+		}
 		// Get the NS and DB
 		let (ns, db) = opt.ns_db()?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb_index(ns, db, &self.what, &self.name).await.is_ok() {
+		if let Ok(ix) = txn.get_tb_index(ns, db, &self.what, &self.name).await {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite && !opt.import {
@@ -70,6 +93,12 @@ impl DefineIndexStatement {
 				.await?;
 			#[cfg(target_family = "wasm")]
 			ctx.get_index_stores().index_removed(&txn, ns, db, &self.what, &self.name).await?;
+			// Remove the data of a previous generation of the index
+			if ix.generation != self.generation {
+				let name = ix.storage_name();
+				let key = crate::key::index::all::new(ns, db, &self.what, &name);
+				txn.delp(key).await?;
+			}
 		}
 		// Does the table exist?
 		match txn.get_tb(ns, db, &self.what).await {
@@ -198,6 +227,9 @@ impl Display for DefineIndexStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {} ON {} FIELDS {}", self.name, self.what, self.cols)?;
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
@@ -218,6 +250,7 @@ impl InfoStructure for DefineIndexStatement {
 			"what".to_string() => self.what.structure(),
 			"cols".to_string() => self.cols.structure(),
 			"index".to_string() => self.index.structure(),
+			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
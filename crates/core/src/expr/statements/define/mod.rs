@@ -12,6 +12,7 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod schedule;
 mod sequence;
 mod table;
 mod user;
@@ -29,8 +30,9 @@ pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use schedule::DefineScheduleStatement;
 pub use sequence::DefineSequenceStatement;
-pub use table::DefineTableStatement;
+pub use table::{DefineTableStatement, Tenant};
 pub use user::DefineUserStatement;
 
 pub use deprecated::scope::DefineScopeStatement;
@@ -42,18 +44,22 @@ pub use api::FindApi;
 
 pub use bucket::BucketDefinition;
 
+use crate::api::path::Path;
 use crate::ctx::Context;
 use crate::dbs::Options;
+use crate::dbs::audit::{Audit, Target};
+use crate::dbs::capabilities::StatementTarget;
 use crate::doc::CursorDoc;
 use crate::expr::value::Value;
 use anyhow::Result;
+use config::ConfigInner;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -90,6 +96,8 @@ pub enum DefineStatement {
 	Bucket(DefineBucketStatement),
 	#[revision(start = 5)]
 	Sequence(DefineSequenceStatement),
+	#[revision(start = 6)]
+	Schedule(DefineScheduleStatement),
 }
 
 // Revision implementations
@@ -122,7 +130,13 @@ impl DefineStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		match self {
+		let target = self.target();
+		// Check if the statement is allowed
+		ctx.check_allowed_statement(opt, &StatementTarget::new("DEFINE", target.resource()))
+			.await?;
+		// Fetch the definition before it is changed
+		let audit = Audit::begin(ctx, opt, target).await?;
+		let res = match self {
 			Self::Namespace(v) => v.compute(ctx, opt, doc).await,
 			Self::Database(v) => v.compute(ctx, opt, doc).await,
 			Self::Function(v) => v.compute(ctx, opt, doc).await,
@@ -139,6 +153,43 @@ impl DefineStatement {
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(ctx, opt).await,
+			Self::Schedule(v) => v.compute(ctx, opt).await,
+		}?;
+		// Record the change in the audit log
+		audit
+			.finish(ctx, opt, || match self {
+				Self::Access(v) => v.redacted().to_string(),
+				_ => self.to_string(),
+			})
+			.await?;
+		Ok(res)
+	}
+	/// Returns the definition which this statement changes
+	fn target(&self) -> Target<'_> {
+		match self {
+			Self::Namespace(_) => Target::Namespace,
+			Self::Database(v) => Target::Database(&v.name),
+			Self::Function(v) => Target::Function(&v.name),
+			Self::Param(v) => Target::Param(&v.name),
+			Self::Table(v) => Target::Table(&v.name),
+			Self::Event(v) => Target::Event(&v.what, &v.name),
+			Self::Field(v) => Target::Field(&v.what, v.name.to_string()),
+			Self::Index(v) => Target::Index(&v.what, &v.name),
+			Self::Analyzer(v) => Target::Analyzer(&v.name),
+			Self::User(v) => Target::User(&v.name, &v.base),
+			Self::Model(v) => Target::Model(&v.name, &v.version),
+			Self::Access(v) => Target::Access(&v.name, &v.base),
+			Self::Config(v) => Target::Config(match &v.inner {
+				ConfigInner::GraphQL(_) => "graphql",
+				ConfigInner::Api(_) => "api",
+			}),
+			Self::Api(v) => Target::Api(match &v.path {
+				Value::Strand(s) => s.parse::<Path>().map(|p| p.to_string()).unwrap_or_default(),
+				v => v.to_string(),
+			}),
+			Self::Bucket(v) => Target::Bucket(&v.name),
+			Self::Sequence(v) => Target::Sequence(&v.name),
+			Self::Schedule(v) => Target::Schedule(&v.name),
 		}
 	}
 }
@@ -162,6 +213,7 @@ impl Display for DefineStatement {
 			Self::Api(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Schedule(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -179,6 +231,6 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(13, enc.len());
+		assert_eq!(14, enc.len());
 	}
 }
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Base, Ident, Strand, Value};
+use crate::expr::{Base, Ident, ScopeCapabilities, Strand, Value};
 use crate::iam::{Action, ResourceKind};
 use anyhow::{Result, bail};
 
@@ -11,7 +11,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -23,6 +23,8 @@ pub struct DefineNamespaceStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	#[revision(start = 4)]
+	pub capabilities: Option<ScopeCapabilities>,
 }
 
 impl DefineNamespaceStatement {
@@ -84,6 +86,9 @@ impl Display for DefineNamespaceStatement {
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if let Some(ref v) = self.capabilities {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -93,6 +98,7 @@ impl InfoStructure for DefineNamespaceStatement {
 		Value::from(map! {
 			"name".to_string() => self.name.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"capabilities".to_string(), if let Some(v) = self.capabilities => v.structure(),
 		})
 	}
 }
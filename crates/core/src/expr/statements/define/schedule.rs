@@ -0,0 +1,118 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::schedule::{Cron, ScheduleState};
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Base, Block, Ident, Strand, Value};
+use crate::iam::{Action, Actor, Auth, Level, ResourceKind, Role};
+use crate::key::database::{sc, ss};
+use anyhow::{Result, bail};
+use chrono::Utc;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineScheduleStatement {
+	pub name: Ident,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+	/// The cron expression which specifies when the schedule is run
+	pub cron: Strand,
+	/// The roles of the system user which runs the statements
+	pub roles: Vec<Ident>,
+	pub then: Block,
+	pub comment: Option<Strand>,
+}
+
+impl DefineScheduleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Schedule, &Base::Db)?;
+		// Check the cron expression
+		let cron: Cron = self.cron.parse()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		let (ns, db) = opt.ns_db()?;
+		// Check if the definition exists
+		if txn.get_db_schedule(ns, db, &self.name).await.is_ok() {
+			if self.if_not_exists {
+				return Ok(Value::None);
+			} else if !self.overwrite && !opt.import {
+				bail!(Error::ScAlreadyExists {
+					name: self.name.to_string(),
+				});
+			}
+		}
+		// Process the statement
+		let key = sc::new(ns, db, &self.name);
+		txn.get_or_add_ns(ns, opt.strict).await?;
+		txn.get_or_add_db(ns, db, opt.strict).await?;
+		let def = DefineScheduleStatement {
+			// Don't persist the `IF NOT EXISTS` clause to schema
+			if_not_exists: false,
+			overwrite: false,
+			..self.clone()
+		};
+		// Set the definition
+		txn.set(key, revision::to_vec(&def)?, None).await?;
+		// Schedule the next run, keeping any run which is in progress
+		let key = ss::new(ns, db, &self.name);
+		let mut state = ScheduleState::new(&cron, Utc::now());
+		if let Some(v) = txn.get(key.clone(), None).await? {
+			state.running = revision::from_slice::<ScheduleState>(&v)?.running;
+		}
+		txn.set(key, revision::to_vec(&state)?, None).await?;
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Returns the system user of the database which runs the statements
+	pub(crate) fn auth(&self, ns: &str, db: &str) -> Auth {
+		let roles = self.roles.iter().filter_map(|r| r.parse::<Role>().ok()).collect();
+		let level = Level::Database(ns.to_owned(), db.to_owned());
+		Auth::new(Actor::new(self.name.to_raw(), roles, level))
+	}
+}
+
+impl Display for DefineScheduleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE SCHEDULE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} ON DATABASE CRON {}", self.name, self.cron)?;
+		write!(
+			f,
+			" ROLES {}",
+			self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<_>>().join(", ")
+		)?;
+		write!(f, " THEN {}", self.then)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineScheduleStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"cron".to_string() => self.cron.into(),
+			"roles".to_string() => self.roles.into_iter().map(Ident::structure).collect(),
+			"then".to_string() => self.then.structure(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
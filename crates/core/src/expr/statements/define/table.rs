@@ -1,19 +1,21 @@
-use super::DefineFieldStatement;
+use super::{DefineFieldStatement, DefineIndexStatement};
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Force, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::fmt::{is_pretty, pretty_indent};
 use crate::expr::paths::{IN, OUT};
+use crate::expr::statements::RemoveIndexStatement;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{
-	Base, Ident, Output, Permissions, Strand, Value, Values, View, changefeed::ChangeFeed,
-	statements::UpdateStatement,
+	Base, FlowResultExt as _, Ident, Output, Permissions, Strand, Thing, Value, Values, View,
+	changefeed::ChangeFeed, statements::UpdateStatement,
 };
-use crate::expr::{Idiom, Kind, TableType};
+use crate::expr::{Idiom, Idioms, Index, Kind, TableType};
 use crate::iam::{Action, ResourceKind};
-use crate::kvs::Transaction;
-use anyhow::{Result, bail};
+use crate::kvs::{KeyDecode as _, Transaction};
+use anyhow::{Result, bail, ensure};
 
 use reblessive::tree::Stk;
 use revision::Error as RevisionError;
@@ -23,7 +25,10 @@ use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 6)]
+/// The name of the index which is added to the tables of unique relations
+const RELATION_UNIQUE_INDEX: &str = "unique_in_out";
+
+#[revisioned(revision = 11)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -58,6 +63,70 @@ pub struct DefineTableStatement {
 	/// The last time that a LIVE query was added to this table
 	#[revision(start = 5, end = 6, convert_fn = "convert_cache_ts")]
 	pub cache_lives_ts: Uuid,
+	/// The database versionstamp at which the maintenance of this view was suspended
+	#[revision(start = 7)]
+	pub suspended: Option<u128>,
+	/// The tenant which the records of this table belong to
+	#[revision(start = 8)]
+	pub tenant: Option<Tenant>,
+	/// The condition which every record of this table must conform to
+	#[revision(start = 9)]
+	pub assert: Option<Value>,
+	/// Whether the existing records are checked against the `ASSERT` clause
+	#[revision(start = 9)]
+	pub validate: bool,
+	/// Whether the deleted records are kept with a tombstone, instead of being removed
+	#[revision(start = 10)]
+	pub soft_delete: bool,
+	/// Whether the records keep a version, which is incremented on every write
+	#[revision(start = 11)]
+	pub versioned: bool,
+}
+
+/// The `TENANT` clause of a table, which limits every session to the
+/// records whose tenant field matches the tenant value of the session.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Tenant {
+	/// The field which holds the tenant of a record
+	pub field: Idiom,
+	/// The tenant of the session, such as `$auth.tenant`
+	pub value: Value,
+}
+
+impl Tenant {
+	/// Checks if a record belongs to the tenant of the session.
+	/// A session without a tenant can not access any record.
+	pub(crate) async fn matches(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<bool> {
+		let tenant = self.value.compute(stk, ctx, opt, Some(doc)).await.catch_return()?;
+		if tenant.is_none_or_null() {
+			return Ok(false);
+		}
+		Ok(doc.doc.as_ref().pick(&self.field) == tenant)
+	}
+}
+
+impl Display for Tenant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TENANT {} VALUE {}", self.field, self.value)
+	}
+}
+
+impl InfoStructure for Tenant {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"field".to_string() => self.field.structure(),
+			"value".to_string() => self.value.structure(),
+		})
+	}
 }
 
 impl DefineTableStatement {
@@ -75,7 +144,8 @@ impl DefineTableStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb(ns, db, &self.name).await.is_ok() {
+		let existing = txn.get_tb(ns, db, &self.name).await.ok();
+		if existing.is_some() {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite && !opt.import {
@@ -99,8 +169,13 @@ impl DefineTableStatement {
 			// Don't persist the `IF NOT EXISTS` clause to the schema
 			if_not_exists: false,
 			overwrite: false,
+			validate: false,
 			..self.clone()
 		};
+		// Check the existing records against the ASSERT clause
+		if self.validate {
+			dt.validate_records(stk, ctx, opt).await?;
+		}
 		// Make sure we are refreshing the caches
 		dt.cache_fields_ts = Uuid::now_v7();
 		dt.cache_events_ts = Uuid::now_v7();
@@ -120,6 +195,20 @@ impl DefineTableStatement {
 		if dt.changefeed.is_some() {
 			txn.lock().await.record_table_change(ns, db, &self.name, &dt);
 		}
+		// Index the `in` and `out` fields of a unique relation
+		if dt.is_unique_relation() {
+			if txn.get_tb_index(ns, db, &self.name, RELATION_UNIQUE_INDEX).await.is_err() {
+				dt.unique_relation_index().compute(stk, ctx, opt, doc).await?;
+			}
+		} else if existing.is_some_and(|tb| tb.is_unique_relation()) {
+			RemoveIndexStatement {
+				name: Ident::from(RELATION_UNIQUE_INDEX),
+				what: self.name.clone(),
+				if_exists: true,
+			}
+			.compute(ctx, opt)
+			.await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
 			// Force queries to run
@@ -175,10 +264,84 @@ impl DefineTableStatement {
 }
 
 impl DefineTableStatement {
+	/// Checks that a record conforms to the `ASSERT` clause of this table
+	pub(crate) async fn check_assert(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<()> {
+		// Check for an ASSERT clause
+		if let Some(expr) = &self.assert {
+			// Disable permissions
+			let opt = &opt.new_with_perms(false);
+			// Process the ASSERT clause
+			let res = expr.compute(stk, ctx, opt, Some(doc)).await.catch_return()?;
+			// Check the ASSERT clause result
+			ensure!(
+				res.is_truthy(),
+				Error::TableAssert {
+					thing: doc.rid.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+					table: self.name.to_raw(),
+					check: expr.to_string(),
+				}
+			);
+		}
+		Ok(())
+	}
+	/// Checks that the existing records of this table
+	/// conform to the `ASSERT` clause of this table
+	pub(crate) async fn validate_records(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<()> {
+		// Check for an ASSERT clause
+		if self.assert.is_none() {
+			return Ok(());
+		}
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check each record of the table
+		let beg = crate::key::thing::prefix(ns, db, &self.name)?;
+		let end = crate::key::thing::suffix(ns, db, &self.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let id = crate::key::thing::Thing::decode(&k)?.id;
+				let rid = Thing::from((self.name.as_str(), id));
+				let val: Value = revision::from_slice(&v)?;
+				let doc = CursorDoc::new(Some(Arc::new(rid)), None, val);
+				stk.run(|stk| self.check_assert(stk, ctx, opt, &doc)).await?;
+			}
+		}
+		Ok(())
+	}
 	/// Checks if this is a TYPE RELATION table
 	pub fn is_relation(&self) -> bool {
 		matches!(self.kind, TableType::Relation(_))
 	}
+	/// Checks if this is a TYPE RELATION table which relates a pair of records only once
+	pub fn is_unique_relation(&self) -> bool {
+		matches!(&self.kind, TableType::Relation(rel) if rel.unique)
+	}
+	/// The unique index which prevents a pair of records from being related twice
+	fn unique_relation_index(&self) -> DefineIndexStatement {
+		DefineIndexStatement {
+			name: Ident::from(RELATION_UNIQUE_INDEX),
+			what: self.name.clone(),
+			cols: Idioms(vec![Idiom::from(IN.to_vec()), Idiom::from(OUT.to_vec())]),
+			index: Index::Uniq,
+			overwrite: true,
+			..Default::default()
+		}
+	}
 	/// Checks if this table allows graph edges / relations
 	pub fn allows_relation(&self) -> bool {
 		matches!(self.kind, TableType::Relation(_) | TableType::Any)
@@ -269,6 +432,9 @@ impl Display for DefineTableStatement {
 				if rel.enforced {
 					write!(f, " ENFORCED")?;
 				}
+				if rel.unique {
+					write!(f, " UNIQUE")?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -277,6 +443,12 @@ impl Display for DefineTableStatement {
 		if self.drop {
 			f.write_str(" DROP")?;
 		}
+		if self.soft_delete {
+			f.write_str(" SOFT DELETE")?;
+		}
+		if self.versioned {
+			f.write_str(" VERSIONED")?;
+		}
 		f.write_str(if self.full {
 			" SCHEMAFULL"
 		} else {
@@ -291,6 +463,15 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.tenant {
+			write!(f, " {v}")?;
+		}
+		if let Some(ref v) = self.assert {
+			write!(f, " ASSERT {v}")?;
+			if self.validate {
+				f.write_str(" VALIDATE")?;
+			}
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -311,6 +492,10 @@ impl InfoStructure for DefineTableStatement {
 			"kind".to_string() => self.kind.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"tenant".to_string(), if let Some(v) = self.tenant => v.structure(),
+			"assert".to_string(), if let Some(v) = self.assert => v.structure(),
+			"soft_delete".to_string(), if self.soft_delete => true.into(),
+			"versioned".to_string(), if self.versioned => true.into(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
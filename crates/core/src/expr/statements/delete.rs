@@ -2,16 +2,21 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::{Cond, Explain, FlowResultExt as _, Output, Timeout, Value, Values, With};
+use crate::expr::order::Ordering;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	Cond, Explain, Fields, FlowResultExt as _, Limit, Output, Timeout, Value, Values, With,
+};
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
-use anyhow::{Result, ensure};
+use anyhow::Result;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -22,6 +27,10 @@ pub struct DeleteStatement {
 	#[revision(start = 3)]
 	pub with: Option<With>,
 	pub cond: Option<Cond>,
+	#[revision(start = 4)]
+	pub order: Option<Ordering>,
+	#[revision(start = 4)]
+	pub limit: Option<Limit>,
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
@@ -51,12 +60,30 @@ impl DeleteStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = stm.setup_timeout(ctx)?;
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
 		let stm_ctx = StatementContext::new(&ctx, opt, &stm)?;
+		// Order and limit the records before they are deleted
+		let what = match (&self.order, &self.limit) {
+			(None, None) => None,
+			_ => Some(
+				SelectStatement {
+					expr: Fields::all(),
+					what: self.what.clone(),
+					with: self.with.clone(),
+					cond: self.cond.clone(),
+					order: self.order.clone(),
+					limit: self.limit.clone(),
+					..Default::default()
+				}
+				.compute_ids(stk, &ctx, opt, doc)
+				.await?,
+			),
+		};
 		// Loop over the delete targets
-		for w in self.what.0.iter() {
+		for w in what.as_ref().unwrap_or(&self.what).0.iter() {
 			let v = w.compute(stk, &ctx, opt, doc).await.catch_return()?;
 			i.prepare(stk, &mut planner, &stm_ctx, v).await.map_err(|e| {
 				if matches!(e.downcast_ref(), Some(Error::InvalidStatementTarget { .. })) {
@@ -77,9 +104,9 @@ impl DeleteStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		match res {
 			// This is a single record result
@@ -108,6 +135,12 @@ impl fmt::Display for DeleteStatement {
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.output {
 			write!(f, " {v}")?
 		}
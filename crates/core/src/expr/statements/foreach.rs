@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::range::TypedRange;
-use crate::expr::{Block, Param, Value, block::Entry};
+use crate::expr::{Block, Ident, Kind, Param, Value, block::Entry};
 use crate::expr::{ControlFlow, FlowResult};
 
 use reblessive::tree::Stk;
@@ -11,12 +11,18 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct ForeachStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
 	pub param: Param,
+	#[revision(start = 3)]
+	pub kind: Option<Kind>,
+	#[revision(start = 2)]
+	pub index: Option<Param>,
 	pub range: Value,
 	pub block: Block,
 }
@@ -42,6 +48,10 @@ impl ForeachStatement {
 	pub(crate) fn writeable(&self) -> bool {
 		self.range.writeable() || self.block.writeable()
 	}
+	/// Check if a BREAK or CONTINUE statement applies to this loop
+	fn is_target(&self, label: &Option<Ident>) -> bool {
+		label.as_ref().is_none_or(|l| self.label.as_ref() == Some(l))
+	}
 	/// Process this type returning a computed simple Value
 	///
 	/// Was marked recursive
@@ -85,7 +95,7 @@ impl ForeachStatement {
 		};
 
 		// Loop over the values
-		for v in iter {
+		for (i, v) in iter.enumerate() {
 			if ctx.is_timedout().await? {
 				return Err(ControlFlow::from(anyhow::Error::new(Error::QueryTimedout)));
 			}
@@ -94,8 +104,23 @@ impl ForeachStatement {
 			// Set the current parameter
 			let key = self.param.0.to_raw();
 			let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
+			// Check and coerce the current parameter
+			let val = match &self.kind {
+				Some(kind) => val
+					.coerce_to_kind(kind)
+					.map_err(|e| Error::SetCoerce {
+						name: key.clone(),
+						error: Box::new(e),
+					})
+					.map_err(anyhow::Error::new)?,
+				None => val,
+			};
 			let mut ctx = MutableContext::unfreeze(ctx)?;
 			ctx.add_value(key, val.into());
+			// Set the current index parameter
+			if let Some(index) = &self.index {
+				ctx.add_value(index.0.to_raw(), Value::from(i).into());
+			}
 			let mut ctx = ctx.freeze();
 			// Loop over the code block statements
 			for v in self.block.iter() {
@@ -103,6 +128,17 @@ impl ForeachStatement {
 				let res = match v {
 					Entry::Set(v) => {
 						let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
+						// Check and coerce the current parameter
+						let val = match &self.kind {
+							Some(kind) => val
+								.coerce_to_kind(kind)
+								.map_err(|e| Error::SetCoerce {
+									name: key.clone(),
+									error: Box::new(e),
+								})
+								.map_err(anyhow::Error::new)?,
+							None => val,
+						};
 						let mut c = MutableContext::unfreeze(ctx)?;
 						c.add_value(v.name.clone(), val.into());
 						ctx = c.freeze();
@@ -129,11 +165,12 @@ impl ForeachStatement {
 						return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await;
 					}
 					Entry::Throw(v) => return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Trycatch(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
 				};
 				// Catch any special errors
 				match res {
-					Err(ControlFlow::Continue) => break,
-					Err(ControlFlow::Break) => return Ok(Value::None),
+					Err(ControlFlow::Continue(l)) if self.is_target(&l) => break,
+					Err(ControlFlow::Break(l)) if self.is_target(&l) => return Ok(Value::None),
 					Err(err) => return Err(err),
 					_ => (),
 				};
@@ -148,6 +185,16 @@ impl ForeachStatement {
 
 impl Display for ForeachStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "FOR {} IN {} {}", self.param, self.range, self.block)
+		if let Some(ref v) = self.label {
+			write!(f, "{v}: ")?;
+		}
+		write!(f, "FOR {}", self.param)?;
+		if let Some(ref v) = self.kind {
+			write!(f, ": {v}")?;
+		}
+		if let Some(ref v) = self.index {
+			write!(f, ", {v}")?;
+		}
+		write!(f, " IN {} {}", self.range, self.block)
 	}
 }
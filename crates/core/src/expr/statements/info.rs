@@ -1,10 +1,23 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
+use crate::dbs::audit;
+use crate::dbs::outbox;
+use crate::dbs::schedule::{self, ScheduleState};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::{Base, Ident, Object, Value, Version};
+#[cfg(not(target_family = "wasm"))]
+use crate::expr::Index;
+use crate::expr::{Base, Ident, Object, ScopeCapabilities, Value, Version};
 use crate::iam::Action;
 use crate::iam::ResourceKind;
+#[cfg(not(target_family = "wasm"))]
+use crate::idx::IndexKeyBase;
+#[cfg(not(target_family = "wasm"))]
+use crate::idx::trees::hnsw::index::HnswIndex;
+#[cfg(not(target_family = "wasm"))]
+use crate::idx::trees::mtree::MTreeIndex;
+use crate::key::database::ss;
+use crate::kvs::Transaction;
 use crate::sys::INFORMATION;
 use anyhow::Result;
 use anyhow::bail;
@@ -15,7 +28,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 8)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -40,6 +53,15 @@ pub enum InfoStatement {
 	#[revision(start = 3)]
 	#[revision(override(revision = 3, discriminant = 10))]
 	Index(Ident, Ident, bool),
+
+	#[revision(start = 6)]
+	Audit(Base),
+
+	#[revision(start = 7)]
+	Outbox,
+
+	#[revision(start = 8)]
+	Schedule(Ident),
 }
 
 impl InfoStatement {
@@ -61,10 +83,13 @@ impl InfoStatement {
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get any functions registered with the datastore
+				let functions = ctx.get_functions().map(|r| r.all()).filter(|v| !v.is_empty());
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_root_accesses().await?.iter().map(|v| v.redacted()).collect()),
+						"functions".to_string(), if let Some(v) = &functions => v.iter().map(|f| f.structure()).collect::<Vec<_>>().into(),
 						"namespaces".to_string() => process(txn.all_ns().await?),
 						"nodes".to_string() => process(txn.all_nodes().await?),
 						"system".to_string() => system().await,
@@ -78,6 +103,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"functions".to_string(), if let Some(v) = &functions => {
+							let mut out = Object::default();
+							for f in v.iter() {
+								out.insert(f.name().to_owned(), f.signature().into());
+							}
+							out.into()
+						},
 						"namespaces".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_ns().await?.iter() {
@@ -116,6 +148,7 @@ impl InfoStatement {
 						"accesses".to_string() => process(txn.all_ns_accesses(ns).await?.iter().map(|v| v.redacted()).collect()),
 						"databases".to_string() => process(txn.all_db(ns).await?),
 						"users".to_string() => process(txn.all_ns_users(ns).await?),
+						"capabilities".to_string(), if let Some(v) = capabilities(&txn, ns, None, true).await => v,
 					}),
 					false => Value::from(map! {
 						"accesses".to_string() => {
@@ -139,6 +172,7 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"capabilities".to_string(), if let Some(v) = capabilities(&txn, ns, None, false).await => v,
 					}),
 				})
 			}
@@ -168,6 +202,8 @@ impl InfoStatement {
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
 						"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
+						"schedules".to_string() => process(txn.all_db_schedules(ns, db).await?),
+						"capabilities".to_string(), if let Some(v) = capabilities(&txn, ns, Some(db), true).await => v,
 					}),
 					false => Value::from(map! {
 						"accesses".to_string() => {
@@ -247,6 +283,14 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"schedules".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_schedules(ns, db).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"capabilities".to_string(), if let Some(v) = capabilities(&txn, ns, Some(db), false).await => v,
 					}),
 				})
 			}
@@ -262,6 +306,16 @@ impl InfoStatement {
 				};
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the maintenance state of a view
+				let view = view_state(&txn, ns, db, tb).await?;
+				// Get the statistics, if the table has been analyzed
+				let stats = txn.get_tb_statistics(ns, db, tb).await?.map(Value::from);
+				// Get the ASSERT clause of the table
+				let assert = match txn.get_tb(ns, db, tb).await {
+					Ok(dt) => dt.assert.clone(),
+					Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => None,
+					Err(e) => return Err(e),
+				};
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
@@ -270,6 +324,9 @@ impl InfoStatement {
 						"indexes".to_string() => process(txn.all_tb_indexes(ns, db, tb).await?),
 						"lives".to_string() => process(txn.all_tb_lives(ns, db, tb).await?),
 						"tables".to_string() => process(txn.all_tb_views(ns, db, tb).await?),
+						"view".to_string(), if let Some(v) = view => v,
+						"stats".to_string(), if let Some(v) = stats => v,
+						"assert".to_string(), if let Some(v) = assert => v.structure(),
 					}),
 					false => Value::from(map! {
 						"events".to_string() => {
@@ -307,6 +364,9 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"view".to_string(), if let Some(v) = view => v,
+						"stats".to_string(), if let Some(v) = stats => v,
+						"assert".to_string(), if let Some(v) = assert => v.to_string().into(),
 					}),
 				})
 			}
@@ -351,11 +411,72 @@ impl InfoStatement {
 						let status = ib.get_status(ns, db, &res).await;
 						let mut out = Object::default();
 						out.insert("building".to_string(), status.into());
+						// The status of the last concurrent rebuild, if any
+						if let Some(status) = ib.get_rebuild_status(ns, db, &res).await {
+							out.insert("rebuilding".to_string(), status.into());
+						}
+						// The health of the structure of a vector index
+						let ikb = IndexKeyBase::new(ns, db, &res)?;
+						let health = match &res.index {
+							Index::MTree(_) => Some(MTreeIndex::health(&txn, &ikb).await?),
+							Index::Hnsw(_) => Some(HnswIndex::health(&txn, &ikb).await?),
+							_ => None,
+						};
+						if let Some(health) = health {
+							out.insert("health".to_string(), health.into());
+						}
 						return Ok(out.into());
 					}
 				}
 				Ok(Object::default().into())
 			}
+			InfoStatement::Audit(base) => {
+				// Only owners can view the audit log
+				opt.is_allowed(Action::Edit, ResourceKind::Actor, base)?;
+				// Get the NS, and the DB if the audit log is limited to a database
+				let ns = opt.ns()?;
+				let db = match base {
+					Base::Db => Some(opt.db()?),
+					_ => None,
+				};
+				// Get the transaction
+				let txn = ctx.tx();
+				// Fetch the entries of the audit log
+				let entries = audit::entries(&txn, ns, db).await?;
+				Ok(Value::Array(entries.into_iter().map(InfoStructure::structure).collect()))
+			}
+			InfoStatement::Outbox => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Get the NS and DB
+				let (ns, db) = opt.ns_db()?;
+				// Get the transaction
+				let txn = ctx.tx();
+				// Fetch the messages of the outbox
+				let entries = outbox::entries(&txn, ns, db).await?;
+				Ok(Value::Array(entries.into_iter().map(InfoStructure::structure).collect()))
+			}
+			InfoStatement::Schedule(sc) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Get the NS and DB
+				let (ns, db) = opt.ns_db()?;
+				// Get the transaction
+				let txn = ctx.tx();
+				// Check that the schedule exists
+				txn.get_db_schedule(ns, db, sc).await?;
+				// Fetch the state and the run history of the schedule
+				let state = match txn.get(ss::new(ns, db, sc), None).await? {
+					Some(v) => revision::from_slice::<ScheduleState>(&v)?,
+					None => ScheduleState::default(),
+				};
+				let runs = schedule::runs(&txn, ns, db, sc).await?;
+				Ok(Value::from(map! {
+					"next".to_string() => state.next.map(Value::Datetime).unwrap_or_default(),
+					"running".to_string() => state.running.map(Value::from).unwrap_or_default(),
+					"runs".to_string() => Value::Array(runs.into_iter().map(InfoStructure::structure).collect()),
+				}))
+			}
 		}
 	}
 }
@@ -394,6 +515,10 @@ impl fmt::Display for InfoStatement {
 			},
 			Self::Index(i, t, false) => write!(f, "INFO FOR INDEX {i} ON {t}"),
 			Self::Index(i, t, true) => write!(f, "INFO FOR INDEX {i} ON {t} STRUCTURE"),
+			Self::Audit(Base::Db) => f.write_str("INFO FOR DATABASE AUDIT"),
+			Self::Audit(_) => f.write_str("INFO FOR NAMESPACE AUDIT"),
+			Self::Outbox => f.write_str("INFO FOR DATABASE OUTBOX"),
+			Self::Schedule(s) => write!(f, "INFO FOR SCHEDULE {s}"),
 		}
 	}
 }
@@ -409,6 +534,58 @@ where
 	Value::Array(a.iter().cloned().map(InfoStructure::structure).collect())
 }
 
+/// Fetches the maintenance state of a view, or nothing if the table is not a view
+async fn view_state(txn: &Transaction, ns: &str, db: &str, tb: &str) -> Result<Option<Value>> {
+	let dt = match txn.get_tb(ns, db, tb).await {
+		Ok(dt) if dt.view.is_some() => dt,
+		Ok(_) => return Ok(None),
+		Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	// Count the changes which are waiting to be processed
+	let beg = crate::key::table::vc::prefix(ns, db, tb)?;
+	let end = crate::key::table::vc::suffix(ns, db, tb)?;
+	let pending = txn.count(beg..end).await?;
+	// The versionstamp which the view is up to date with, which is
+	// only known when the database keeps versionstamps for a CHANGEFEED
+	let versionstamp = match txn.get_db_versionstamp(ns, db).await? {
+		Some(vs) => Value::from(dt.suspended.unwrap_or(vs.into_u128())),
+		None => Value::None,
+	};
+	Ok(Some(Value::from(map! {
+		"suspended".to_string() => dt.suspended.is_some().into(),
+		"pending".to_string() => pending.into(),
+		"versionstamp".to_string() => versionstamp,
+	})))
+}
+
+/// Fetches the capabilities which apply within a namespace, and within a database if specified
+async fn capabilities(
+	txn: &Transaction,
+	ns: &str,
+	db: Option<&str>,
+	structured: bool,
+) -> Option<Value> {
+	let info = |v: &ScopeCapabilities| match structured {
+		true => v.clone().structure(),
+		false => v.to_string().into(),
+	};
+	let mut out = Object::default();
+	if let Ok(v) = txn.get_ns(ns).await {
+		if let Some(v) = v.capabilities.as_ref() {
+			out.insert("namespace".to_string(), info(v));
+		}
+	}
+	if let Some(db) = db {
+		if let Ok(v) = txn.get_db(ns, db).await {
+			if let Some(v) = v.capabilities.as_ref() {
+				out.insert("database".to_string(), info(v));
+			}
+		}
+	}
+	(!out.is_empty()).then(|| out.into())
+}
+
 async fn system() -> Value {
 	let info = INFORMATION.lock().await;
 	Value::from(map! {
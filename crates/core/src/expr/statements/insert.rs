@@ -6,12 +6,13 @@ use crate::expr::paths::IN;
 use crate::expr::paths::OUT;
 use crate::expr::{Data, FlowResultExt as _, Id, Output, Table, Thing, Timeout, Value, Version};
 use crate::idx::planner::RecordStrategy;
-use anyhow::{Result, bail, ensure};
+use anyhow::{Result, bail};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
 #[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -57,6 +58,7 @@ impl InsertStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false).with_version(version);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = match self.timeout.as_ref() {
 			Some(timeout) => {
 				let mut ctx = MutableContext::new(ctx);
@@ -124,10 +126,17 @@ impl InsertStatement {
 		}
 		// Assign the statement
 		let stm = Statement::from(self);
+		// Batch the writes of the inserted records
+		ctx.tx().lock().await.begin_write_batch();
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
+		// Apply any writes which are still buffered
+		let res = match ctx.tx().lock().await.end_write_batch().await {
+			Ok(()) => res,
+			Err(e) => res.and(Err(e)),
+		};
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		Ok(res)
 	}
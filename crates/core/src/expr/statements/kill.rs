@@ -1,8 +1,11 @@
 use crate::dbs::{Action, Notification, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::Value;
-use crate::kvs::Live;
+use crate::expr::paths::ID;
+use crate::expr::statements::LiveStatement;
+use crate::expr::{Base, Ident, Value};
+use crate::iam::{Action as IamAction, ResourceKind};
+use crate::kvs::{KeyDecode as _, Live};
 use crate::{ctx::Context, expr::FlowResultExt as _, expr::Uuid};
 use anyhow::{Result, bail};
 
@@ -11,14 +14,21 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
+	// or Array of Uuids of Live Queries
 	pub id: Value,
+	// Kill all live queries of the current session,
+	// or of the table when a table is specified
+	#[revision(start = 2)]
+	pub all: bool,
+	#[revision(start = 2)]
+	pub table: Option<Ident>,
 }
 
 impl KillStatement {
@@ -34,41 +44,64 @@ impl KillStatement {
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
-		// Resolve live query id
-		let lid = match self.id.compute(stk, ctx, opt, None).await.catch_return()?.cast_to::<Uuid>()
-		{
-			Err(_) => {
-				bail!(Error::KillStatement {
-					value: self.id.to_string(),
-				})
+		// Kill all matching live queries
+		if self.all {
+			let lqs = match &self.table {
+				Some(tb) => self.table_queries(ctx, opt, tb).await?,
+				None => self.session_queries(ctx, opt).await?,
+			};
+			let mut ids = Vec::with_capacity(lqs.len());
+			for (nid, lid, val) in lqs {
+				Self::kill(ctx, opt, nid, lid, &val).await?;
+				ids.push(Value::Uuid(lid.into()));
+			}
+			// Return the killed query ids
+			return Ok(ids.into());
+		}
+		// Resolve live query ids
+		match self.id.compute(stk, ctx, opt, None).await.catch_return()? {
+			Value::Array(ids) => {
+				let mut out = Vec::with_capacity(ids.len());
+				for id in ids {
+					let lid = self.resolve(id)?;
+					self.kill_node_query(ctx, opt, lid).await?;
+					out.push(Value::Uuid(lid.into()));
+				}
+				// Return the killed query ids
+				Ok(out.into())
+			}
+			id => {
+				let lid = self.resolve(id)?;
+				self.kill_node_query(ctx, opt, lid).await?;
+				// Return nothing
+				Ok(Value::None)
 			}
-			Ok(id) => id,
-		};
+		}
+	}
+
+	/// Resolves a value to a live query id
+	fn resolve(&self, id: Value) -> Result<uuid::Uuid> {
+		match id.cast_to::<Uuid>() {
+			Ok(id) => Ok(id.0),
+			Err(_) => bail!(Error::KillStatement {
+				value: self.id.to_string(),
+			}),
+		}
+	}
+
+	/// Kills a live query which was started on the current node
+	async fn kill_node_query(&self, ctx: &Context, opt: &Options, lid: uuid::Uuid) -> Result<()> {
 		// Get the Node ID
 		let nid = opt.id()?;
-		// Get the LIVE ID
-		let lid = lid.0;
-		// Get the transaction
-		let txn = ctx.tx();
 		// Fetch the live query key
 		let key = crate::key::node::lq::new(nid, lid);
 		// Fetch the live query key if it exists
-		match txn.get(key, None).await? {
+		match ctx.tx().get(key, None).await? {
 			Some(val) => {
 				// Decode the data for this live query
 				let val: Live = revision::from_slice(&val)?;
-				// Delete the node live query
-				let key = crate::key::node::lq::new(nid, lid);
-				txn.clr(key).await?;
-				// Delete the table live query
-				let key = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, lid);
-				txn.clr(key).await?;
-				// Refresh the table cache for lives
-				if let Some(cache) = ctx.get_cache() {
-					cache.new_live_queries_version(&val.ns, &val.db, &val.tb);
-				}
-				// Clear the cache
-				txn.clear();
+				// Kill the live query
+				Self::kill(ctx, opt, nid, lid, &val).await
 			}
 			None => {
 				bail!(Error::KillStatement {
@@ -76,6 +109,106 @@ impl KillStatement {
 				});
 			}
 		}
+	}
+
+	/// Fetches the live queries started on the current node by the current session
+	async fn session_queries(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Vec<(uuid::Uuid, uuid::Uuid, Live)>> {
+		// Get the Node ID
+		let nid = opt.id()?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the current session id
+		let sid = ctx.value("session").map(|v| v.pick(ID.as_ref())).unwrap_or_default();
+		// Sessions without an id don't own any live queries
+		if sid.is_none_or_null() {
+			return Ok(Vec::new());
+		}
+		// Get the transaction
+		let txn = ctx.tx();
+		// Scan the live queries for this node
+		let beg = crate::key::node::lq::prefix(nid)?;
+		let end = crate::key::node::lq::suffix(nid)?;
+		let mut out = Vec::new();
+		for (k, v) in txn.getr(beg..end, None).await? {
+			// Decode the data for this live query
+			let val: Live = revision::from_slice(&v)?;
+			if val.ns != ns || val.db != db {
+				continue;
+			}
+			let lid = crate::key::node::lq::Lq::decode(&k)?.lq;
+			// Fetch the table live query
+			let key = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, lid);
+			let Some(stm) = txn.get(key, None).await? else {
+				continue;
+			};
+			let stm: LiveStatement = revision::from_slice(&stm)?;
+			// Check that the live query belongs to this session
+			let session = stm.session.as_ref().map(|v| v.pick(ID.as_ref())).unwrap_or_default();
+			if session == sid {
+				out.push((nid, lid, val));
+			}
+		}
+		Ok(out)
+	}
+
+	/// Fetches the live queries on a table, started on any node
+	async fn table_queries(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		tb: &Ident,
+	) -> Result<Vec<(uuid::Uuid, uuid::Uuid, Live)>> {
+		// Allowed to run?
+		opt.is_allowed(IamAction::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the live queries on the table
+		let lvs = ctx.tx().all_tb_lives(ns, db, tb).await?;
+		Ok(lvs
+			.iter()
+			.map(|lv| {
+				let val = Live {
+					ns: ns.to_string(),
+					db: db.to_string(),
+					tb: tb.to_raw(),
+				};
+				(lv.node.0, lv.id.0, val)
+			})
+			.collect())
+	}
+
+	/// Removes a live query, and notifies its subscriber
+	async fn kill(
+		ctx: &Context,
+		opt: &Options,
+		nid: uuid::Uuid,
+		lid: uuid::Uuid,
+		val: &Live,
+	) -> Result<()> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// Delete the node live query
+		let key = crate::key::node::lq::new(nid, lid);
+		txn.clr(key).await?;
+		// Delete the table live query
+		let key = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, lid);
+		txn.clr(key).await?;
+		// Delete any aggregate state of the live query
+		let key = crate::key::table::la::prefix(&val.ns, &val.db, &val.tb, lid)?;
+		txn.clrp(key).await?;
+		let key = crate::key::table::lm::prefix(&val.ns, &val.db, &val.tb, lid)?;
+		txn.clrp(key).await?;
+		// Refresh the table cache for lives
+		if let Some(cache) = ctx.get_cache() {
+			cache.new_live_queries_version(&val.ns, &val.db, &val.tb);
+		}
+		// Clear the cache
+		txn.clear();
+		// Notify the subscriber
 		if let Some(chn) = opt.sender.as_ref() {
 			chn.send(Notification {
 				id: lid.into(),
@@ -85,13 +218,19 @@ impl KillStatement {
 			})
 			.await?;
 		}
-		// Return the query id
-		Ok(Value::None)
+		Ok(())
 	}
 }
 
 impl fmt::Display for KillStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "KILL {}", self.id)
+		if !self.all {
+			return write!(f, "KILL {}", self.id);
+		}
+		write!(f, "KILL ALL")?;
+		if let Some(ref v) = self.table {
+			write!(f, " FOR TABLE {v}")?
+		}
+		Ok(())
 	}
 }
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{Cond, Fetchs, Fields, FlowResultExt as _, Uuid, Value};
+use crate::expr::{Cond, Fetchs, Fields, FlowResultExt as _, Groups, Uuid, Value};
 use crate::iam::Auth;
 use crate::kvs::Live;
 use anyhow::{Result, bail};
@@ -13,7 +13,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -24,6 +24,8 @@ pub struct LiveStatement {
 	pub what: Value,
 	pub cond: Option<Cond>,
 	pub fetch: Option<Fetchs>,
+	#[revision(start = 2)]
+	pub group: Option<Groups>,
 	// When a live query is created, we must also store the
 	// authenticated session of the user who made the query,
 	// so we can check it later when sending notifications.
@@ -36,6 +38,10 @@ pub struct LiveStatement {
 	// This is optional as it is only set by the database
 	// runtime when storing the live query to storage.
 	pub(crate) session: Option<Value>,
+	/// Whether the current matching records are sent
+	/// before any changes, when the live query starts.
+	#[revision(start = 3)]
+	pub snapshot: bool,
 }
 
 impl LiveStatement {
@@ -70,6 +76,10 @@ impl LiveStatement {
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
+		// Check that the fields can be aggregated
+		if self.group.is_some() {
+			self.check_aggregate()?;
+		}
 		// Get the Node ID
 		let nid = opt.id()?;
 		// Check that auth has been set
@@ -109,6 +119,10 @@ impl LiveStatement {
 				// Insert the table live query
 				let key = crate::key::table::lq::new(ns, db, &tb, id);
 				txn.replace(key, revision::to_vec(&stm)?).await?;
+				// Insert the initial state of each group
+				if stm.group.is_some() {
+					stm.aggregate_init(stk, ctx, opt, &tb).await?;
+				}
 				// Refresh the table cache for lives
 				if let Some(cache) = ctx.get_cache() {
 					cache.new_live_queries_version(ns, db, &tb);
@@ -133,9 +147,15 @@ impl fmt::Display for LiveStatement {
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.group {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if self.snapshot {
+			f.write_str(" SNAPSHOT")?
+		}
 		Ok(())
 	}
 }
@@ -146,7 +166,9 @@ impl InfoStructure for LiveStatement {
 			"expr".to_string() => self.expr.structure(),
 			"what".to_string() => self.what.structure(),
 			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
+			"group".to_string(), if let Some(v) = self.group => v.structure(),
 			"fetch".to_string(), if let Some(v) = self.fetch => v.structure(),
+			"snapshot".to_string(), if self.snapshot => true.into(),
 		})
 	}
 }
@@ -154,11 +176,11 @@ impl InfoStructure for LiveStatement {
 #[cfg(test)]
 mod tests {
 	use crate::dbs::{Action, Capabilities, Notification, Session};
-	use crate::expr::Thing;
 	use crate::expr::Value;
+	use crate::expr::{Part, Thing};
 	use crate::kvs::Datastore;
 	use crate::kvs::LockType::Optimistic;
-	use crate::kvs::TransactionType::Write;
+	use crate::kvs::TransactionType::{Read, Write};
 	use crate::sql::SqlValue;
 	use crate::syn::Parse;
 	use anyhow::Result;
@@ -275,4 +297,338 @@ mod tests {
 		assert_eq!(table_occurrences[0].name.0, tb);
 		tx.cancel().await.unwrap();
 	}
+
+	#[tokio::test]
+	async fn test_aggregate_live_query() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		dbs.execute("CREATE order:1 SET status = 'open', total = 10", &ses, None).await.unwrap();
+
+		// Initiate an aggregate live query statement
+		let lq_stmt = "LIVE SELECT status, count() AS count, math::sum(total) AS total FROM order GROUP BY status";
+		let res = &mut dbs.execute(lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+		let notify = |action, group: &str, result: &str| {
+			Notification::new(
+				live_id,
+				action,
+				SqlValue::parse(group).into(),
+				SqlValue::parse(result).into(),
+			)
+		};
+
+		// The initial state of each group is sent once subscribed
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Create, "['open']", "{ status: 'open', count: 1, total: 10 }")
+		);
+
+		// Adding a record updates its group
+		dbs.execute("CREATE order:2 SET status = 'open', total = 5", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "['open']", "{ status: 'open', count: 2, total: 15 }")
+		);
+
+		// Moving a record to another group updates both groups
+		dbs.execute("UPDATE order:1 SET status = 'closed'", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "['open']", "{ status: 'open', count: 1, total: 5 }")
+		);
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Create, "['closed']", "{ status: 'closed', count: 1, total: 10 }")
+		);
+
+		// Removing the last record of a group deletes the group
+		dbs.execute("DELETE order:2", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Delete, "['open']", "{ status: 'open', count: 1, total: 5 }")
+		);
+		assert!(notifications.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_aggregate_live_query_group_all() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		// Initiate an aggregate live query statement
+		let lq_stmt = "LIVE SELECT count(), math::max(total) AS max FROM order WHERE status = 'open' GROUP ALL";
+		let res = &mut dbs.execute(lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+		let notify = |action, result: &str| {
+			Notification::new(
+				live_id,
+				action,
+				SqlValue::parse("[]").into(),
+				SqlValue::parse(result).into(),
+			)
+		};
+
+		// The single group is sent even when there are no records
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Create, "{ count: 0, max: NONE }")
+		);
+
+		dbs.execute("CREATE order:1 SET status = 'open', total = 10", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 1, max: 10 }")
+		);
+
+		dbs.execute("CREATE order:2 SET status = 'open', total = 20", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 2, max: 20 }")
+		);
+
+		// Records which are not matched do not change the aggregate
+		dbs.execute("CREATE order:3 SET status = 'closed', total = 30", &ses, None).await.unwrap();
+		assert!(notifications.is_empty());
+
+		// Removing the maximum value reads the next one from the ordered values
+		dbs.execute("UPDATE order:2 SET status = 'closed'", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 1, max: 10 }")
+		);
+
+		// The group remains once all of its records are removed
+		dbs.execute("DELETE order:1", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 0, max: NONE }")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_aggregate_live_query_shards() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		// Create the records before the live query is started
+		let sql = "FOR $i IN 1..=50 { CREATE type::thing('order', $i) SET total = $i }";
+		dbs.execute(sql, &ses, None).await.unwrap();
+
+		// Initiate an aggregate live query statement
+		let lq_stmt = "LIVE SELECT count(), math::min(total) AS min, math::max(total) AS max FROM order GROUP ALL";
+		let res = &mut dbs.execute(lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+		let notify = |action, result: &str| {
+			Notification::new(
+				live_id,
+				action,
+				SqlValue::parse("[]").into(),
+				SqlValue::parse(result).into(),
+			)
+		};
+
+		// The state of the group is split across shards
+		let txn = dbs.transaction(Read, Optimistic).await.unwrap();
+		let beg = crate::key::table::la::prefix("test", "test", "order", live_id.0).unwrap();
+		let end = crate::key::table::la::suffix("test", "test", "order", live_id.0).unwrap();
+		let keys = txn.keys(beg..end, u32::MAX, None).await.unwrap();
+		txn.cancel().await.unwrap();
+		assert!(keys.len() > 1);
+
+		// The shards are merged when the group is fetched
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Create, "{ count: 50, min: 1, max: 50 }")
+		);
+
+		// Removing the minimum and maximum values reads the next ones
+		dbs.execute("DELETE order:1, order:50", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 49, min: 2, max: 50 }")
+		);
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Update, "{ count: 48, min: 2, max: 49 }")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_aggregate_live_query_invalid_fields() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+
+		let res = &mut dbs
+			.execute("LIVE SELECT count(), total FROM order GROUP ALL", &ses, None)
+			.await
+			.unwrap();
+		let err = res.remove(0).result.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"Can not execute LIVE statement with a GROUP clause, as 'total' is not an aggregate function, and is not present in the GROUP clause"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_snapshot_live_query() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		let sql = "
+			CREATE person:tobie SET name = 'Tobie';
+			CREATE task:1 SET assignee = 'tobie', owner = person:tobie;
+			CREATE task:2 SET assignee = 'jaime';
+			CREATE task:3 SET assignee = 'tobie';
+		";
+		dbs.execute(sql, &ses, None).await.unwrap();
+
+		// Initiate a snapshot live query statement
+		let lq_stmt = "LIVE SELECT * FROM task WHERE assignee = 'tobie' FETCH owner SNAPSHOT";
+		let res = &mut dbs.execute(lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+		let notify = |action, record: &str, result: &str| {
+			Notification::new(
+				live_id,
+				action,
+				SqlValue::parse(record).into(),
+				SqlValue::parse(result).into(),
+			)
+		};
+
+		// The matching records are sent once subscribed
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(
+				Action::Snapshot,
+				"task:1",
+				"{ id: task:1, assignee: 'tobie', owner: { id: person:tobie, name: 'Tobie' } }"
+			)
+		);
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Snapshot, "task:3", "{ id: task:3, assignee: 'tobie' }")
+		);
+		assert!(notifications.is_empty());
+
+		// Changes are sent after the snapshot
+		dbs.execute("CREATE task:4 SET assignee = 'tobie'", &ses, None).await.unwrap();
+		assert_eq!(
+			notifications.recv().await.unwrap(),
+			notify(Action::Create, "task:4", "{ id: task:4, assignee: 'tobie' }")
+		);
+
+		// Live queries without a SNAPSHOT clause send nothing
+		let res = &mut dbs.execute("LIVE SELECT * FROM task", &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		assert!(notifications.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_snapshot_live_query_permissions() {
+		let dbs = new_ds().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		let sql = "
+			DEFINE TABLE task PERMISSIONS FOR select WHERE assignee = $auth;
+			DEFINE ACCESS user ON DATABASE TYPE RECORD;
+			CREATE user:tobie;
+			CREATE task:1 SET assignee = user:tobie;
+			CREATE task:2 SET assignee = user:jaime;
+		";
+		dbs.execute(sql, &ses, None).await.unwrap();
+
+		// Initiate a snapshot live query as a record user
+		let ses = Session::for_record(
+			"test",
+			"test",
+			"user",
+			Value::from(Thing::from(("user", "tobie"))),
+		)
+		.with_rt(true);
+		let res = &mut dbs.execute("LIVE SELECT * FROM task SNAPSHOT", &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid")
+		};
+
+		// Only the records which can be viewed are sent
+		dbs.live_query_snapshot(live_id.0).await.unwrap();
+		let notification = notifications.recv().await.unwrap();
+		assert_eq!(notification.action, Action::Snapshot);
+		assert_eq!(notification.record.to_string(), "task:1");
+		assert!(notifications.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_kill_multiple_live_queries() {
+		let dbs = new_ds().await.unwrap();
+		let mut ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		ses.id = Some("session".to_string());
+		let notifications = dbs.notifications().expect("expected notifications");
+		let killed = |id: &Value| {
+			let Value::Uuid(id) = id else {
+				panic!("expected uuid")
+			};
+			Notification::new(*id, Action::Killed, Value::None, Value::None)
+		};
+
+		let sql =
+			"LIVE SELECT * FROM person; LIVE SELECT * FROM person; LIVE SELECT * FROM animal;";
+		let res = dbs.execute(sql, &ses, None).await.unwrap();
+		let ids: Vec<Value> = res.into_iter().map(|r| r.result.unwrap()).collect();
+
+		// Kill an array of live queries
+		let sql = format!("KILL [{}, {}]", ids[0], ids[2]);
+		let res = &mut dbs.execute(&sql, &ses, None).await.unwrap();
+		assert_eq!(
+			res.remove(0).result.unwrap(),
+			Value::from(vec![ids[0].clone(), ids[2].clone()])
+		);
+		assert_eq!(notifications.recv().await.unwrap(), killed(&ids[0]));
+		assert_eq!(notifications.recv().await.unwrap(), killed(&ids[2]));
+
+		// Kill all live queries on a table
+		let res = &mut dbs.execute("KILL ALL FOR TABLE person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::from(vec![ids[1].clone()]));
+		assert_eq!(notifications.recv().await.unwrap(), killed(&ids[1]));
+		let res = &mut dbs.execute("INFO FOR TABLE person", &ses, None).await.unwrap();
+		assert_eq!(
+			res.remove(0).result.unwrap().pick(&[Part::from("lives")]),
+			SqlValue::parse("{}").into()
+		);
+
+		// Kill all live queries of the current session
+		let res = &mut dbs.execute("LIVE SELECT * FROM animal", &ses, None).await.unwrap();
+		let id = res.remove(0).result.unwrap();
+		let res = &mut dbs.execute("KILL ALL", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::from(vec![id.clone()]));
+		assert_eq!(notifications.recv().await.unwrap(), killed(&id));
+
+		// Sessions without an id don't match each other
+		let anon = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		dbs.execute("LIVE SELECT * FROM animal", &anon, None).await.unwrap();
+		let res = &mut dbs.execute("KILL ALL", &anon, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::from(Vec::<Value>::new()));
+		assert!(notifications.is_empty());
+	}
 }
@@ -3,6 +3,7 @@ use crate::doc::CursorDoc;
 use crate::expr::ControlFlow;
 use crate::expr::fetch::Fetchs;
 use crate::expr::value::Value;
+use crate::expr::value::fetch::{FetchedRecords, Fetching};
 use crate::{ctx::Context, expr::FlowResult};
 
 use reblessive::tree::Stk;
@@ -38,12 +39,10 @@ impl OutputStatement {
 		let mut value = self.what.compute(stk, ctx, opt, doc).await?;
 		// Fetch any
 		if let Some(fetchs) = &self.fetch {
-			let mut idioms = Vec::with_capacity(fetchs.0.len());
-			for fetch in fetchs.iter() {
-				fetch.compute(stk, ctx, opt, &mut idioms).await?
-			}
-			for i in &idioms {
-				value.fetch(stk, ctx, opt, i).await?;
+			let paths = fetchs.paths(stk, ctx, opt).await?;
+			let records = FetchedRecords::default();
+			for (i, depth) in &paths {
+				value.fetch(stk, ctx, opt, &Fetching::new(i, *depth, &records)).await?;
 			}
 		}
 		//
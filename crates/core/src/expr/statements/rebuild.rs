@@ -4,9 +4,10 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::Base;
 use crate::expr::ident::Ident;
+use crate::expr::index::Index;
 use crate::expr::value::Value;
 use crate::iam::{Action, ResourceKind};
-use anyhow::Result;
+use anyhow::{Result, ensure};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -49,7 +50,7 @@ impl Display for RebuildStatement {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -57,6 +58,8 @@ pub struct RebuildIndexStatement {
 	pub name: Ident,
 	pub what: Ident,
 	pub if_exists: bool,
+	#[revision(start = 2)]
+	pub concurrently: bool,
 }
 
 impl RebuildIndexStatement {
@@ -83,10 +86,29 @@ impl RebuildIndexStatement {
 				}
 			}
 		};
+		// Rebuild the index in the background, while the current structure is still used
+		if self.concurrently {
+			ensure!(
+				matches!(ix.index, Index::MTree(_) | Index::Hnsw(_)),
+				Error::IndexNotRebuildable {
+					name: self.name.to_raw(),
+					reason: "only MTREE and HNSW indexes can be rebuilt concurrently".to_string(),
+				}
+			);
+			#[cfg(not(target_family = "wasm"))]
+			{
+				ctx.get_index_builder()
+					.ok_or_else(|| Error::unreachable("No Index Builder"))?
+					.rebuild(ctx, opt.clone(), &ix)
+					.await?;
+				return Ok(Value::None);
+			}
+		}
 		let mut ix = ix.as_ref().clone();
 
 		ix.overwrite = true;
 		ix.if_not_exists = false;
+		ix.cancelled = false;
 		// Rebuild the index
 		ix.compute(stk, ctx, opt, doc).await?;
 		// Ok all good
@@ -101,6 +123,9 @@ impl Display for RebuildIndexStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.concurrently {
+			write!(f, " CONCURRENTLY")?
+		}
 		Ok(())
 	}
 }
@@ -1,17 +1,20 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Iterable, Iterator, Options, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::{Data, FlowResultExt as _, Output, Timeout, Value};
+use crate::expr::{Data, Dir, FlowResultExt as _, Output, Thing, Timeout, Value};
 use crate::idx::planner::RecordStrategy;
+use crate::kvs::KeyDecode as _;
 use anyhow::{Result, bail, ensure};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -26,6 +29,8 @@ pub struct RelateStatement {
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
+	#[revision(start = 3)]
+	pub zip: bool,
 }
 
 impl RelateStatement {
@@ -48,6 +53,7 @@ impl RelateStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = match self.timeout.as_ref() {
 			Some(timeout) => {
 				let mut ctx = MutableContext::new(ctx);
@@ -139,42 +145,68 @@ impl RelateStatement {
 			};
 			out
 		};
-		//
-		for f in from.iter() {
-			for w in with.iter() {
-				let f = f.clone();
-				let w = w.clone();
-				match &self.kind.compute(stk, &ctx, opt, doc).await.catch_return()? {
-					// The relation has a specific record id
-					Value::Thing(id) => i.ingest(Iterable::Relatable(f, id.to_owned(), w, None)),
-					// The relation does not have a specific record id
-					Value::Table(tb) => match &self.data {
-						// There is a data clause so check for a record id
-						Some(data) => {
-							let id = match data.rid(stk, &ctx, opt).await? {
-								Some(id) => id.generate(tb, false)?,
-								None => tb.generate(),
-							};
-							i.ingest(Iterable::Relatable(f, id, w, None))
-						}
-						// There is no data clause so create a record id
-						None => i.ingest(Iterable::Relatable(f, tb.generate(), w, None)),
-					},
-					// The relation can not be any other type
-					v => {
-						bail!(Error::RelateStatementOut {
-							value: v.to_string(),
-						})
-					}
+		// Pair the targets index-wise, or relate every combination
+		let pairs: Vec<(&Thing, &Thing)> = if self.zip {
+			ensure!(
+				from.len() == with.len(),
+				Error::RelateStatementZip {
+					from: from.len(),
+					with: with.len(),
+				}
+			);
+			from.iter().zip(with.iter()).collect()
+		} else {
+			from.iter().flat_map(|f| with.iter().map(move |w| (f, w))).collect()
+		};
+		// Create an edge for each pair
+		for (f, w) in pairs {
+			let f = f.clone();
+			let w = w.clone();
+			let kind = self.kind.compute(stk, &ctx, opt, doc).await.catch_return()?;
+			// Reuse the edge which already relates the records
+			if self.uniq {
+				let tb = match &kind {
+					Value::Thing(id) => Some(id.tb.as_str()),
+					Value::Table(tb) => Some(tb.as_str()),
+					_ => None,
 				};
+				if let Some(tb) = tb {
+					if let Some(id) = Self::find_edge(&ctx, opt, &f, tb, &w).await? {
+						i.ingest(Iterable::Relatable(f, id, w, None));
+						continue;
+					}
+				}
 			}
+			match &kind {
+				// The relation has a specific record id
+				Value::Thing(id) => i.ingest(Iterable::Relatable(f, id.to_owned(), w, None)),
+				// The relation does not have a specific record id
+				Value::Table(tb) => match &self.data {
+					// There is a data clause so check for a record id
+					Some(data) => {
+						let id = match data.rid(stk, &ctx, opt).await? {
+							Some(id) => id.generate(tb, false)?,
+							None => tb.generate(),
+						};
+						i.ingest(Iterable::Relatable(f, id, w, None))
+					}
+					// There is no data clause so create a record id
+					None => i.ingest(Iterable::Relatable(f, tb.generate(), w, None)),
+				},
+				// The relation can not be any other type
+				v => {
+					bail!(Error::RelateStatementOut {
+						value: v.to_string(),
+					})
+				}
+			};
 		}
 		// Assign the statement
 		let stm = Statement::from(self);
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		match res {
 			// This is a single record result
@@ -190,6 +222,37 @@ impl RelateStatement {
 	}
 }
 
+impl RelateStatement {
+	/// Finds an edge of a table which already relates one record to another
+	async fn find_edge(
+		ctx: &Context,
+		opt: &Options,
+		from: &Thing,
+		tb: &str,
+		with: &Thing,
+	) -> Result<Option<Thing>> {
+		let (ns, db) = opt.ns_db()?;
+		let txn = ctx.tx();
+		// Scan the edges of the table which leave the record
+		let beg = crate::key::graph::ftprefix(ns, db, &from.tb, &from.id, &Dir::Out, tb)?;
+		let end = crate::key::graph::ftsuffix(ns, db, &from.tb, &from.id, &Dir::Out, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, opt.version).await?;
+			next = batch.next;
+			for k in batch.result {
+				let gra = crate::key::graph::Graph::decode(&k)?;
+				// Check if the edge leads to the other record
+				let key = crate::key::graph::new(ns, db, gra.ft, &gra.fk, &Dir::Out, with);
+				if txn.exists(key, opt.version).await? {
+					return Ok(Some(Thing::from((gra.ft, gra.fk))));
+				}
+			}
+		}
+		Ok(None)
+	}
+}
+
 impl fmt::Display for RelateStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "RELATE")?;
@@ -200,6 +263,9 @@ impl fmt::Display for RelateStatement {
 		if self.uniq {
 			f.write_str(" UNIQUE")?
 		}
+		if self.zip {
+			f.write_str(" ZIP")?
+		}
 		if let Some(ref v) = self.data {
 			write!(f, " {v}")?
 		}
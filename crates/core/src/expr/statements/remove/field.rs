@@ -1,17 +1,21 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
-use crate::expr::statements::define::DefineTableStatement;
-use crate::expr::{Base, Ident, Idiom, Value};
+use crate::expr::statements::define::{
+	DefineFieldStatement, DefineIndexStatement, DefineTableStatement,
+};
+use crate::expr::statements::{RemoveIndexStatement, RemoveTableStatement};
+use crate::expr::{Base, Expression, Field, Ident, Idiom, Value};
 use crate::iam::{Action, ResourceKind};
-use anyhow::Result;
+use crate::kvs::Transaction;
+use anyhow::{Result, ensure};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -20,6 +24,8 @@ pub struct RemoveFieldStatement {
 	pub what: Ident,
 	#[revision(start = 2)]
 	pub if_exists: bool,
+	#[revision(start = 3)]
+	pub cascade: bool,
 }
 
 impl RemoveFieldStatement {
@@ -44,6 +50,49 @@ impl RemoveFieldStatement {
 				}
 			}
 		};
+		// Get the definitions which depend on this field
+		let (fds, ixs, fts) = self.dependents(&txn, ns, db).await?;
+		if self.cascade {
+			// Remove the views which use this field
+			for ft in fts.iter() {
+				let stm = RemoveTableStatement {
+					name: ft.name.clone(),
+					if_exists: true,
+					cascade: true,
+					..Default::default()
+				};
+				stm.compute(ctx, opt).await?;
+			}
+			// Remove the indexes which use this field
+			for ix in ixs.iter() {
+				let stm = RemoveIndexStatement {
+					name: ix.name.clone(),
+					what: ix.what.clone(),
+					if_exists: true,
+				};
+				stm.compute(ctx, opt).await?;
+			}
+			// Remove the nested field definitions
+			for fd in fds.iter() {
+				let key = crate::key::table::fd::new(ns, db, &fd.what, &fd.name.to_string());
+				txn.del(key).await?;
+			}
+		} else {
+			let dependents: Vec<_> = fds
+				.iter()
+				.map(|fd| format!("field '{}.{}'", fd.what, fd.name))
+				.chain(ixs.iter().map(|ix| format!("index '{}.{}'", ix.what, ix.name)))
+				.chain(fts.iter().map(|ft| format!("view '{}'", ft.name)))
+				.collect();
+			ensure!(
+				dependents.is_empty(),
+				Error::RemoveDependents {
+					what: "field".to_owned(),
+					name: format!("{}.{}", self.what, self.name),
+					dependents: dependents.join(", "),
+				}
+			);
+		}
 		// Delete the definition
 		let key = crate::key::table::fd::new(ns, db, &fd.what, &na);
 		txn.del(key).await?;
@@ -68,6 +117,80 @@ impl RemoveFieldStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Finds the nested fields, indexes, and views which use this field
+	async fn dependents(
+		&self,
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+	) -> Result<(Vec<DefineFieldStatement>, Vec<DefineIndexStatement>, Vec<DefineTableStatement>)>
+	{
+		// Get the nested field definitions
+		let fds = txn
+			.all_tb_fields(ns, db, &self.what, None)
+			.await?
+			.iter()
+			.filter(|fd| fd.name.len() > self.name.len() && fd.name.starts_with(&self.name))
+			.cloned()
+			.collect();
+		// Get the indexes which use this field
+		let ixs = txn
+			.all_tb_indexes(ns, db, &self.what)
+			.await?
+			.iter()
+			.filter(|ix| ix.cols.iter().any(|col| col.starts_with(&self.name)))
+			.cloned()
+			.collect();
+		// Get the views which use this field
+		let fts = txn
+			.all_tb_views(ns, db, &self.what)
+			.await?
+			.iter()
+			.filter(|ft| {
+				ft.view.as_ref().is_some_and(|v| {
+					v.expr.iter().any(|f| match f {
+						Field::All => false,
+						Field::Single {
+							expr,
+							..
+						}
+						| Field::Window {
+							expr,
+							..
+						} => Self::uses_field(expr, &self.name),
+					}) || v.cond.as_ref().is_some_and(|c| Self::uses_field(&c.0, &self.name))
+						|| v.group
+							.as_ref()
+							.is_some_and(|g| g.iter().any(|g| g.starts_with(&self.name)))
+				})
+			})
+			.cloned()
+			.collect();
+		Ok((fds, ixs, fts))
+	}
+
+	/// Checks if a value refers to a field
+	fn uses_field(val: &Value, name: &Idiom) -> bool {
+		match val {
+			Value::Idiom(i) => i.starts_with(name),
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Unary {
+					v,
+					..
+				} => Self::uses_field(v, name),
+				Expression::Binary {
+					l,
+					r,
+					..
+				} => Self::uses_field(l, name) || Self::uses_field(r, name),
+			},
+			Value::Function(f) => f.args().iter().any(|v| Self::uses_field(v, name)),
+			Value::Array(a) => a.iter().any(|v| Self::uses_field(v, name)),
+			Value::Object(o) => o.values().any(|v| Self::uses_field(v, name)),
+			_ => false,
+		}
+	}
 }
 
 impl Display for RemoveFieldStatement {
@@ -77,6 +200,9 @@ impl Display for RemoveFieldStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.cascade {
+			write!(f, " CASCADE")?
+		}
 		Ok(())
 	}
 }
@@ -47,12 +47,20 @@ impl RemoveIndexStatement {
 			return Err(e);
 		}
 
+		// Get the definition
+		let ix = txn.get_tb_index(ns, db, &self.what, &self.name).await?;
 		// Delete the definition
 		let key = crate::key::table::ix::new(ns, db, &self.what, &self.name);
 		txn.del(key).await?;
 		// Remove the index data
 		let key = crate::key::index::all::new(ns, db, &self.what, &self.name);
 		txn.delp(key).await?;
+		// Remove the data of a rebuilt generation of the index
+		if ix.generation != 0 {
+			let name = ix.storage_name();
+			let key = crate::key::index::all::new(ns, db, &self.what, &name);
+			txn.delp(key).await?;
+		}
 		// Refresh the table cache for indexes
 		let key = crate::key::database::tb::new(ns, db, &self.what);
 		let tb = txn.get_tb(ns, db, &self.what).await?;
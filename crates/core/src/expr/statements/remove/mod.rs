@@ -9,6 +9,7 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod schedule;
 mod sequence;
 mod table;
 mod user;
@@ -24,12 +25,15 @@ pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use schedule::RemoveScheduleStatement;
 pub use sequence::RemoveSequenceStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
 
 use crate::ctx::Context;
 use crate::dbs::Options;
+use crate::dbs::audit::{Audit, Target};
+use crate::dbs::capabilities::StatementTarget;
 use crate::doc::CursorDoc;
 use crate::expr::Value;
 use anyhow::Result;
@@ -57,6 +61,7 @@ pub enum RemoveStatement {
 	Model(RemoveModelStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Schedule(RemoveScheduleStatement),
 }
 
 impl RemoveStatement {
@@ -71,7 +76,13 @@ impl RemoveStatement {
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value> {
-		match self {
+		let target = self.target();
+		// Check if the statement is allowed
+		ctx.check_allowed_statement(opt, &StatementTarget::new("REMOVE", target.resource()))
+			.await?;
+		// Fetch the definition before it is removed
+		let audit = Audit::begin(ctx, opt, target).await?;
+		let res = match self {
 			Self::Namespace(v) => v.compute(ctx, opt).await,
 			Self::Database(v) => v.compute(ctx, opt).await,
 			Self::Function(v) => v.compute(ctx, opt).await,
@@ -86,6 +97,30 @@ impl RemoveStatement {
 			Self::Model(v) => v.compute(ctx, opt).await,
 			Self::Bucket(v) => v.compute(ctx, opt).await,
 			Self::Sequence(v) => v.compute(ctx, opt).await,
+			Self::Schedule(v) => v.compute(ctx, opt).await,
+		}?;
+		// Record the change in the audit log
+		audit.finish(ctx, opt, || self.to_string()).await?;
+		Ok(res)
+	}
+	/// Returns the definition which this statement removes
+	fn target(&self) -> Target<'_> {
+		match self {
+			Self::Namespace(_) => Target::Namespace,
+			Self::Database(v) => Target::Database(&v.name),
+			Self::Function(v) => Target::Function(&v.name),
+			Self::Access(v) => Target::Access(&v.name, &v.base),
+			Self::Param(v) => Target::Param(&v.name),
+			Self::Table(v) => Target::Table(&v.name),
+			Self::Event(v) => Target::Event(&v.what, &v.name),
+			Self::Field(v) => Target::Field(&v.what, v.name.to_string()),
+			Self::Index(v) => Target::Index(&v.what, &v.name),
+			Self::Analyzer(v) => Target::Analyzer(&v.name),
+			Self::User(v) => Target::User(&v.name, &v.base),
+			Self::Model(v) => Target::Model(&v.name, &v.version),
+			Self::Bucket(v) => Target::Bucket(&v.name),
+			Self::Sequence(v) => Target::Sequence(&v.name),
+			Self::Schedule(v) => Target::Schedule(&v.name),
 		}
 	}
 }
@@ -107,6 +142,7 @@ impl Display for RemoveStatement {
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Schedule(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -0,0 +1,68 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::{Base, Ident, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::key::database::{sc, sh, ss};
+use anyhow::Result;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Removes a schedule, along with its run history.
+///
+/// A run of the schedule which is in progress is not cancelled, but is
+/// allowed to finish, and its outcome is not recorded.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemoveScheduleStatement {
+	pub name: Ident,
+	pub if_exists: bool,
+}
+
+impl RemoveScheduleStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value> {
+		let (ns, db) = opt.ns_db()?;
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Schedule, &Base::Db)?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let def = match txn.get_db_schedule(ns, db, &self.name).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::ScNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the run history
+		let beg = sh::prefix(ns, db, &def.name)?;
+		let end = sh::suffix(ns, db, &def.name)?;
+		txn.delr(beg..end).await?;
+		// Delete the run state
+		txn.del(ss::new(ns, db, &def.name)).await?;
+		// Delete the definition
+		txn.del(sc::new(ns, db, &def.name)).await?;
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for RemoveScheduleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE SCHEDULE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {}", self.name)?;
+		Ok(())
+	}
+}
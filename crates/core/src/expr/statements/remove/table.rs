@@ -1,17 +1,19 @@
 use crate::ctx::Context;
 use crate::dbs::{self, Notification, Options};
 use crate::err::Error;
-use crate::expr::statements::define::DefineTableStatement;
-use crate::expr::{Base, Ident, Value};
+use crate::expr::statements::define::{DefineFieldStatement, DefineTableStatement};
+use crate::expr::{Base, Ident, Kind, TableType, Value};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::Transaction;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -21,6 +23,8 @@ pub struct RemoveTableStatement {
 	pub if_exists: bool,
 	#[revision(start = 3)]
 	pub expunge: bool,
+	#[revision(start = 4)]
+	pub cascade: bool,
 }
 
 impl RemoveTableStatement {
@@ -32,13 +36,6 @@ impl RemoveTableStatement {
 		let (ns, db) = opt.ns_db()?;
 		// Get the transaction
 		let txn = ctx.tx();
-		// Remove the index stores
-		#[cfg(not(target_family = "wasm"))]
-		ctx.get_index_stores()
-			.table_removed(ctx.get_index_builder(), &txn, ns, db, &self.name)
-			.await?;
-		#[cfg(target_family = "wasm")]
-		ctx.get_index_stores().table_removed(&txn, ns, db, &self.name).await?;
 		// Get the defined table
 		let tb = match txn.get_tb(ns, db, &self.name).await {
 			Ok(x) => x,
@@ -50,19 +47,67 @@ impl RemoveTableStatement {
 				}
 			}
 		};
+		// Get the definitions which depend on this table
+		let (fts, fds) = Self::dependents(&txn, ns, db, &self.name).await?;
+		if self.cascade {
+			// Remove the views which use this table
+			for ft in Self::views(&txn, ns, db, &self.name).await? {
+				let ft = txn.get_tb(ns, db, &ft.name).await?;
+				Self::remove(ctx, opt, &ft, self.expunge).await?;
+			}
+			// Update the fields which link to this table
+			let (_, fds) = Self::dependents(&txn, ns, db, &self.name).await?;
+			self.unlink(ctx, opt, &fds).await?;
+		} else {
+			let dependents: Vec<_> = fts
+				.iter()
+				.map(|ft| format!("view '{}'", ft.name))
+				.chain(fds.iter().map(|fd| format!("field '{}.{}'", fd.what, fd.name)))
+				.collect();
+			ensure!(
+				dependents.is_empty(),
+				Error::RemoveDependents {
+					what: "table".to_owned(),
+					name: self.name.to_raw(),
+					dependents: dependents.join(", "),
+				}
+			);
+		}
+		// Remove the table
+		Self::remove(ctx, opt, &tb, self.expunge).await
+	}
+
+	/// Removes a table definition along with all of its data
+	async fn remove(
+		ctx: &Context,
+		opt: &Options,
+		tb: &DefineTableStatement,
+		expunge: bool,
+	) -> Result<Value> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Remove the index stores
+		#[cfg(not(target_family = "wasm"))]
+		ctx.get_index_stores()
+			.table_removed(ctx.get_index_builder(), &txn, ns, db, &tb.name)
+			.await?;
+		#[cfg(target_family = "wasm")]
+		ctx.get_index_stores().table_removed(&txn, ns, db, &tb.name).await?;
 		// Get the foreign tables
-		let fts = txn.all_tb_views(ns, db, &self.name).await?;
+		let fts = txn.all_tb_views(ns, db, &tb.name).await?;
 		// Get the live queries
-		let lvs = txn.all_tb_lives(ns, db, &self.name).await?;
+		let lvs = txn.all_tb_lives(ns, db, &tb.name).await?;
 		// Delete the definition
-		let key = crate::key::database::tb::new(ns, db, &self.name);
-		match self.expunge {
+		let key = crate::key::database::tb::new(ns, db, &tb.name);
+		match expunge {
 			true => txn.clr(key).await?,
 			false => txn.del(key).await?,
 		};
 		// Remove the resource data
-		let key = crate::key::table::all::new(ns, db, &self.name);
-		match self.expunge {
+		let key = crate::key::table::all::new(ns, db, &tb.name);
+		match expunge {
 			true => txn.clrp(key).await?,
 			false => txn.delp(key).await?,
 		};
@@ -70,12 +115,12 @@ impl RemoveTableStatement {
 		for ft in fts.iter() {
 			// Refresh the table cache
 			let key = crate::key::database::tb::new(ns, db, &ft.name);
-			let tb = txn.get_tb(ns, db, &ft.name).await?;
+			let dt = txn.get_tb(ns, db, &ft.name).await?;
 			txn.set(
 				key,
 				revision::to_vec(&DefineTableStatement {
 					cache_tables_ts: Uuid::now_v7(),
-					..tb.as_ref().clone()
+					..dt.as_ref().clone()
 				})?,
 				None,
 			)
@@ -86,16 +131,16 @@ impl RemoveTableStatement {
 			// Process each foreign table
 			for ft in view.what.0.iter() {
 				// Save the view config
-				let key = crate::key::table::ft::new(ns, db, ft, &self.name);
+				let key = crate::key::table::ft::new(ns, db, ft, &tb.name);
 				txn.del(key).await?;
 				// Refresh the table cache for foreign tables
 				let key = crate::key::database::tb::new(ns, db, ft);
-				let tb = txn.get_tb(ns, db, ft).await?;
+				let dt = txn.get_tb(ns, db, ft).await?;
 				txn.set(
 					key,
 					revision::to_vec(&DefineTableStatement {
 						cache_tables_ts: Uuid::now_v7(),
-						..tb.as_ref().clone()
+						..dt.as_ref().clone()
 					})?,
 					None,
 				)
@@ -115,13 +160,119 @@ impl RemoveTableStatement {
 		}
 		// Clear the cache
 		if let Some(cache) = ctx.get_cache() {
-			cache.clear_tb(ns, db, &self.name);
+			cache.clear_tb(ns, db, &tb.name);
 		}
 		// Clear the cache
 		txn.clear();
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Finds all views which use a table, directly or through other views,
+	/// ordered so that each view is removed before the views which it uses
+	async fn views(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		name: &str,
+	) -> Result<Vec<DefineTableStatement>> {
+		// Find the views which use this table
+		let mut views: Vec<DefineTableStatement> = Vec::new();
+		let mut next = vec![name.to_owned()];
+		while let Some(tb) = next.pop() {
+			for ft in txn.all_tb_views(ns, db, &tb).await?.iter() {
+				if !views.iter().any(|v| v.name == ft.name) {
+					next.push(ft.name.to_raw());
+					views.push(ft.clone());
+				}
+			}
+		}
+		// A view can be removed once no other view uses it
+		let mut out = Vec::with_capacity(views.len());
+		while !views.is_empty() {
+			let pos = views
+				.iter()
+				.position(|v| {
+					!views.iter().any(|o| {
+						o.view.as_ref().is_some_and(|w| w.what.0.iter().any(|t| t.0 == v.name.0))
+					})
+				})
+				.unwrap_or(0);
+			out.push(views.remove(pos));
+		}
+		Ok(out)
+	}
+
+	/// Finds the views which use a table, and the fields on other tables which
+	/// link to it, returning the fields with the links to the table removed
+	async fn dependents(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		name: &str,
+	) -> Result<(Vec<DefineTableStatement>, Vec<DefineFieldStatement>)> {
+		// Get the foreign tables
+		let fts = txn.all_tb_views(ns, db, name).await?.to_vec();
+		// Get the fields which link to this table
+		let mut fds = Vec::new();
+		for tb in txn.all_tb(ns, db, None).await?.iter() {
+			if tb.name.as_str() == name {
+				continue;
+			}
+			for fd in txn.all_tb_fields(ns, db, &tb.name, None).await?.iter() {
+				let mut fd = fd.clone();
+				if fd.kind.as_mut().is_some_and(|k| k.remove_table(name)) {
+					fds.push(fd);
+				}
+			}
+		}
+		Ok((fts, fds))
+	}
+
+	/// Saves the fields which no longer link to this table
+	async fn unlink(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		fds: &[DefineFieldStatement],
+	) -> Result<()> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Save the field definitions
+		let mut tbs = BTreeSet::new();
+		for fd in fds.iter() {
+			let key = crate::key::table::fd::new(ns, db, &fd.what, &fd.name.to_string());
+			txn.set(key, revision::to_vec(fd)?, None).await?;
+			tbs.insert(fd.what.to_raw());
+		}
+		// Refresh the table cache for fields
+		for tb in tbs.iter() {
+			let mut dt = txn.get_tb(ns, db, tb).await?.as_ref().clone();
+			// Relations may also no longer link to this table
+			if let TableType::Relation(rel) = &mut dt.kind {
+				for kind in [&mut rel.from, &mut rel.to] {
+					if kind.as_mut().is_some_and(|k| k.remove_table(&self.name))
+						&& matches!(kind, Some(Kind::Record(t)) if t.is_empty())
+					{
+						*kind = None;
+					}
+				}
+			}
+			dt.cache_fields_ts = Uuid::now_v7();
+			let key = crate::key::database::tb::new(ns, db, tb);
+			txn.set(key, revision::to_vec(&dt)?, None).await?;
+			// Clear the cache
+			if let Some(cache) = ctx.get_cache() {
+				cache.clear_tb(ns, db, tb);
+			}
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(())
+	}
 }
 
 impl Display for RemoveTableStatement {
@@ -131,6 +282,9 @@ impl Display for RemoveTableStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {}", self.name)?;
+		if self.cascade {
+			write!(f, " CASCADE")?
+		}
 		Ok(())
 	}
 }
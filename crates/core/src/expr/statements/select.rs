@@ -1,12 +1,12 @@
-use crate::ctx::Context;
-use crate::dbs::{Iterator, Options, Statement};
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::{Iterator, Options, Statement, SubqueryCache};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::{
 	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Splits, Start, Timeout, Value,
 	Values, Version, With,
-	order::{OldOrders, Order, OrderList, Ordering},
+	order::{Collate, OldOrders, Order, OrderList, Ordering},
 };
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
 use anyhow::{Result, ensure};
@@ -14,10 +14,12 @@ use anyhow::{Result, ensure};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use std::sync::Arc;
+use trice::Instant;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,6 +35,9 @@ pub struct SelectStatement {
 	pub cond: Option<Cond>,
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
+	/// The condition applied to the output rows, after any grouping.
+	#[revision(start = 5)]
+	pub having: Option<Cond>,
 	#[revision(end = 4, convert_fn = "convert_old_orders")]
 	pub old_order: Option<OldOrders>,
 	#[revision(start = 4)]
@@ -43,6 +48,9 @@ pub struct SelectStatement {
 	pub version: Option<Version>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
+	/// The number of tasks requested by a PARALLEL clause
+	#[revision(start = 6)]
+	pub parallel_tasks: Option<u32>,
 	pub explain: Option<Explain>,
 	#[revision(start = 3)]
 	pub tempfiles: bool,
@@ -68,9 +76,10 @@ impl SelectStatement {
 			x.0.into_iter()
 				.map(|x| Order {
 					value: x.order,
-					collate: x.collate,
+					collate: x.collate.then_some(Collate::Lexical),
 					numeric: x.numeric,
 					direction: x.direction,
+					nulls: None,
 				})
 				.collect();
 
@@ -86,6 +95,10 @@ impl SelectStatement {
 			Field::Single {
 				expr,
 				..
+			}
+			| Field::Window {
+				expr,
+				..
 			} => expr.writeable(),
 		}) {
 			return true;
@@ -93,6 +106,9 @@ impl SelectStatement {
 		if self.what.iter().any(|v| v.writeable()) {
 			return true;
 		}
+		if self.having.as_deref().is_some_and(Value::writeable) {
+			return true;
+		}
 		self.cond.as_deref().is_some_and(Value::writeable)
 	}
 
@@ -110,11 +126,33 @@ impl SelectStatement {
 		let stm = Statement::from(self);
 		// Create a new iterator
 		let mut i = Iterator::new();
-		// Ensure futures are stored and the version is set if specified
-		let version = match &self.version {
-			Some(v) => Some(v.compute(stk, ctx, opt, doc).await?),
+		// Stream the rows of a top-level statement, before any subquery claims the stream
+		let stream = match ctx.get_row_stream() {
+			Some(stream) if !self.only && stream.claim() => {
+				i.set_stream(stream.clone());
+				Some(stream.clone())
+			}
 			_ => None,
 		};
+		// Ensure futures are stored and the version is set if specified.
+		// Without a VERSION clause, the version of the parent statement is
+		// kept, so that subqueries, graph traversals and fetched records all
+		// read the same snapshot as the statement which contains them.
+		let version = match &self.version {
+			Some(v) => {
+				let version = v.compute(stk, ctx, opt, doc).await?;
+				Version::check_retention(version)?;
+				// A statement reading a past version can not modify data
+				ensure!(
+					!self.writeable(),
+					Error::VersionedMutation {
+						version: Version::to_datetime(version).to_string(),
+					}
+				);
+				Some(version)
+			}
+			_ => opt.version,
+		};
 		let opt = Arc::new(opt.new_with_futures(false).with_version(version));
 		// Extract the limits
 		i.setup_limit(stk, ctx, &opt, &stm).await?;
@@ -124,7 +162,16 @@ impl SelectStatement {
 			Error::SingleOnlyOutput
 		);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = stm.setup_timeout(ctx)?;
+		// The uncorrelated subqueries are computed once, unless data is modified
+		let ctx = if ctx.get_subquery_cache().is_none() && !self.writeable() {
+			let mut ctx = MutableContext::new(&ctx);
+			ctx.set_subquery_cache(Arc::new(SubqueryCache::default()));
+			Cow::Owned(ctx.freeze())
+		} else {
+			ctx
+		};
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
 		let stm_ctx = StatementContext::new(&ctx, &opt, &stm)?;
@@ -136,9 +183,14 @@ impl SelectStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, &opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, &opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
+		// Send the rows which were not streamed while iterating
+		if let Some(stream) = stream {
+			stream.send_all(res).await?;
+			return Ok(Value::Array(Default::default()));
+		}
 		// Output the results
 		match res {
 			// This is a single record result
@@ -154,6 +206,23 @@ impl SelectStatement {
 			v => Ok(v),
 		}
 	}
+
+	/// Process this statement, returning the ids of the selected records
+	pub(crate) async fn compute_ids(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Values> {
+		let ids = match self.compute(stk, ctx, opt, doc).await? {
+			Value::Array(a) => {
+				a.into_iter().map(|v| v.rid()).filter(|v| matches!(v, Value::Thing(_))).collect()
+			}
+			_ => Vec::new(),
+		};
+		Ok(Values(ids))
+	}
 }
 
 impl fmt::Display for SelectStatement {
@@ -179,6 +248,9 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.having {
+			write!(f, " HAVING {}", v.0)?
+		}
 		if let Some(ref v) = self.order {
 			write!(f, " {v}")?
 		}
@@ -200,6 +272,9 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
+		if let Some(v) = self.parallel_tasks {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
@@ -33,13 +33,15 @@ impl ShowSince {
 }
 
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct ShowStatement {
 	pub table: Option<Table>,
 	pub since: ShowSince,
+	#[revision(start = 2)]
+	pub until: Option<ShowSince>,
 	pub limit: Option<u32>,
 }
 
@@ -63,12 +65,23 @@ impl ShowStatement {
 			db,
 			self.table.as_deref().map(String::as_str),
 			self.since.clone(),
+			self.until.clone(),
 			self.limit,
 		)
 		.await?;
+		// Fetch the versionstamp after the last change set, which
+		// can be passed to SINCE to resume the feed, as SINCE is
+		// inclusive of the change set at the given versionstamp
+		let vs = match r.last().and_then(|v| v.0.next()) {
+			Some(v) => Value::from(v.into_u64_lossy()),
+			None => Value::None,
+		};
 		// Return the changes
-		let a: Vec<Value> = r.iter().cloned().map(|x| x.into_value()).collect();
-		Ok(a.into())
+		let a: Vec<Value> = r.into_iter().map(|x| x.into_value()).collect();
+		Ok(Value::from(map! {
+			"changes".to_string() => Value::from(a),
+			"versionstamp".to_string() => vs,
+		}))
 	}
 }
 
@@ -83,6 +96,11 @@ impl fmt::Display for ShowStatement {
 			ShowSince::Timestamp(ref v) => write!(f, " SINCE {}", v)?,
 			ShowSince::Versionstamp(ref v) => write!(f, " SINCE {}", v)?,
 		}
+		match self.until {
+			Some(ShowSince::Timestamp(ref v)) => write!(f, " UNTIL {}", v)?,
+			Some(ShowSince::Versionstamp(ref v)) => write!(f, " UNTIL {}", v)?,
+			None => {}
+		}
 		if let Some(ref v) = self.limit {
 			write!(f, " LIMIT {}", v)?
 		}
@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::expr::{Base, Duration, Value};
+use crate::fnc;
 use crate::iam::{Action, ResourceKind};
 use anyhow::Result;
 
@@ -26,18 +27,8 @@ impl SleepStatement {
 	) -> Result<Value> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Root)?;
-		// Calculate the sleep duration
-		let dur = match (ctx.timeout(), self.duration.0) {
-			(Some(t), d) if t < d => t,
-			(_, d) => d,
-		};
 		// Sleep for the specified time
-		#[cfg(target_family = "wasm")]
-		wasmtimer::tokio::sleep(dur).await;
-		#[cfg(not(target_family = "wasm"))]
-		tokio::time::sleep(dur).await;
-		// Ok all good
-		Ok(Value::None)
+		fnc::sleep::sleep((ctx, opt), (self.duration,)).await
 	}
 }
 
@@ -50,7 +41,12 @@ impl fmt::Display for SleepStatement {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::ctx::MutableContext;
+	use crate::dbs::Capabilities;
 	use crate::dbs::test::mock;
+	use crate::err::Error;
+	use crate::iam::{Auth, Role};
+	use std::sync::Arc;
 	use std::time::{self, SystemTime};
 
 	#[tokio::test]
@@ -64,4 +60,64 @@ mod tests {
 		assert!(time.elapsed().unwrap() >= time::Duration::from_micros(500));
 		assert_eq!(value, Value::None);
 	}
+
+	/// Creates a context whose capabilities limit sleeps for non-root users
+	fn with_sleep_limits(ctx: &Context) -> Context {
+		let caps = Capabilities::default()
+			.with_max_sleep(Some(time::Duration::from_millis(10)))
+			.with_max_query_sleep(Some(time::Duration::from_millis(15)));
+		let mut ctx = MutableContext::new(ctx);
+		ctx.add_capabilities(Arc::new(caps));
+		ctx.freeze()
+	}
+
+	#[tokio::test]
+	async fn test_sleep_limits() {
+		let (ctx, opt) = mock().await;
+		let ctx = with_sleep_limits(&ctx);
+		let opt = opt.with_auth(Arc::new(Auth::for_ns(Role::Owner, "test")));
+		// A single sleep can not exceed the maximum sleep duration
+		let err = fnc::sleep::sleep((&ctx, &opt), (Duration::from_millis(20),)).await.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"Unable to sleep for 20ms, as it exceeds the max sleep capability of 10ms"
+		);
+		// The total sleep duration of a query is limited
+		fnc::sleep::sleep((&ctx, &opt), (Duration::from_millis(10),)).await.unwrap();
+		let err = fnc::sleep::sleep((&ctx, &opt), (Duration::from_millis(10),)).await.unwrap_err();
+		assert!(matches!(
+			err.downcast_ref(),
+			Some(Error::SleepNotAllowed {
+				capability: "max query sleep",
+				..
+			})
+		));
+	}
+
+	#[tokio::test]
+	async fn test_sleep_limits_root() {
+		let (ctx, opt) = mock().await;
+		let ctx = with_sleep_limits(&ctx);
+		// Root users are not limited by default
+		let stm = SleepStatement {
+			duration: Duration::from_millis(20),
+		};
+		stm.compute(&ctx, &opt, None).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_sleep_cancelled() {
+		let (ctx, opt) = mock().await;
+		let mut ctx = MutableContext::new(&ctx);
+		let canceller = ctx.add_cancel();
+		let ctx = ctx.freeze();
+		canceller.cancel();
+		// A cancelled sleep stops early
+		let time = SystemTime::now();
+		let stm = SleepStatement {
+			duration: Duration(time::Duration::from_secs(10)),
+		};
+		stm.compute(&ctx, &opt, None).await.unwrap();
+		assert!(time.elapsed().unwrap() < time::Duration::from_secs(1));
+	}
 }
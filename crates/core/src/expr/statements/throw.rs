@@ -30,9 +30,11 @@ impl ThrowStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> FlowResult<Value> {
-		Err(ControlFlow::from(anyhow::Error::new(Error::Thrown(
-			self.error.compute(stk, ctx, opt, doc).await?.to_raw_string(),
-		))))
+		let err = match self.error.compute(stk, ctx, opt, doc).await? {
+			Value::Strand(v) => Error::Thrown(v.0),
+			v => Error::ThrownValue(v),
+		};
+		Err(ControlFlow::from(anyhow::Error::new(err)))
 	}
 }
 
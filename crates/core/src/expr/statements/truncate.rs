@@ -0,0 +1,112 @@
+use crate::ctx::Context;
+use crate::dbs::{self, Notification, Options};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Ident, Table, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::KeyEncode;
+
+use anyhow::{Result, ensure};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The key categories which hold the data stored under a table: the
+/// records, the graph edges, the record references, and the index data.
+/// The record versions are kept, in the same way as when a record is
+/// deleted, so that the records which are created again never reuse one.
+pub(crate) const DATA_CATEGORIES: [u8; 4] = [b'*', b'~', b'&', b'+'];
+
+/// Removes all the records in a table, while keeping the table, field,
+/// event, and index definitions in place. No events are run, and a
+/// single notification is sent to each live query on the table.
+///
+/// Graph edges stored in other tables, and record links pointing to the
+/// removed records, are left in place.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TruncateStatement {
+	pub name: Ident,
+}
+
+impl TruncateStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the defined table
+		let tb = txn.get_tb(ns, db, &self.name).await?;
+		// Views are kept in sync with their foreign tables
+		ensure!(
+			tb.view.is_none() && txn.all_tb_views(ns, db, &self.name).await?.is_empty(),
+			Error::TbTruncateView {
+				name: self.name.to_raw(),
+			}
+		);
+		// Get the live queries
+		let lvs = txn.all_tb_lives(ns, db, &self.name).await?;
+		// Remove the index stores
+		#[cfg(not(target_family = "wasm"))]
+		ctx.get_index_stores()
+			.table_removed(ctx.get_index_builder(), &txn, ns, db, &self.name)
+			.await?;
+		#[cfg(target_family = "wasm")]
+		ctx.get_index_stores().table_removed(&txn, ns, db, &self.name).await?;
+		// Remove the table data, one key range at a time
+		let key = crate::key::table::all::new(ns, db, &self.name).encode()?;
+		for category in DATA_CATEGORIES {
+			let mut beg = key.clone();
+			beg.extend_from_slice(&[category, 0x00]);
+			let mut end = key.clone();
+			end.extend_from_slice(&[category, 0xff]);
+			txn.delr(beg..end).await?;
+		}
+		// Remove the state of any aggregate live queries
+		for lv in lvs.iter().filter(|lv| lv.group.is_some()) {
+			txn.clrp(crate::key::table::la::prefix(ns, db, &self.name, lv.id.0)?).await?;
+			txn.clrp(crate::key::table::lm::prefix(ns, db, &self.name, lv.id.0)?).await?;
+		}
+		// Record the truncation in the changefeed
+		let dbv = txn.get_db(ns, db).await?;
+		if dbv.changefeed.or(tb.changefeed).is_some() {
+			txn.lock().await.record_table_truncate(ns, db, &self.name, &tb);
+		}
+		// Notify the live queries
+		if let Some(chn) = opt.sender.as_ref() {
+			for lv in lvs.iter() {
+				chn.send(Notification {
+					id: lv.id,
+					action: dbs::Action::Truncate,
+					record: Value::Table(Table::from(self.name.clone())),
+					result: Value::None,
+				})
+				.await?;
+			}
+		}
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.name);
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for TruncateStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TRUNCATE TABLE {}", self.name)
+	}
+}
@@ -0,0 +1,134 @@
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Block, ControlFlow, FlowResult, Param, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TrycatchStatement {
+	pub block: Block,
+	pub param: Option<Param>,
+	pub catch: Block,
+}
+
+impl TrycatchStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.block.writeable() || self.catch.writeable()
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> FlowResult<Value> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// Only writes need to be rolled back
+		let writeable = self.block.writeable();
+		// Create a save point to roll back to
+		if writeable {
+			txn.lock().await.new_save_point().await?;
+		}
+		// Hold back any notifications until the block succeeds
+		let (sender, receiver) = match opt.sender {
+			Some(_) => {
+				let (send, recv) = async_channel::unbounded();
+				(Some(send), Some(recv))
+			}
+			None => (None, None),
+		};
+		let res = match sender {
+			Some(send) => {
+				let opt = opt.new_with_sender(send);
+				stk.run(|stk| self.block.compute(stk, ctx, &opt, doc)).await
+			}
+			None => stk.run(|stk| self.block.compute(stk, ctx, opt, doc)).await,
+		};
+		// Check whether the error can be caught
+		match res {
+			Err(ControlFlow::Err(e)) if Self::catchable(&e) => {
+				// Revert any changes made within the block
+				if writeable {
+					txn.lock().await.rollback_to_save_point().await?;
+					txn.clear();
+				}
+				// Expose the error to the catch block
+				let mut ctx = MutableContext::new(ctx);
+				if let Some(param) = &self.param {
+					ctx.add_value(param.0.to_raw(), Self::error(&e).into());
+				}
+				let ctx = ctx.freeze();
+				stk.run(|stk| self.catch.compute(stk, &ctx, opt, doc)).await
+			}
+			res => {
+				// Keep any changes made within the block
+				if writeable {
+					txn.lock().await.release_last_save_point()?;
+				}
+				// Send any held back notifications
+				if let (Some(recv), Some(chn)) = (receiver, opt.sender.as_ref()) {
+					while let Ok(v) = recv.try_recv() {
+						if chn.send(v).await.is_err() {
+							break;
+						}
+					}
+				}
+				res
+			}
+		}
+	}
+	/// Check if an error can be caught, as errors which
+	/// affect the whole query or transaction can not be
+	fn catchable(e: &anyhow::Error) -> bool {
+		!matches!(
+			e.downcast_ref(),
+			Some(
+				Error::QueryCancelled
+					| Error::QueryTimedout
+					| Error::StatementTimedout { .. }
+					| Error::Tx(_) | Error::TxFailure
+					| Error::TxRetryable
+					| Error::TxFinished
+			)
+		)
+	}
+	/// Convert an error into the value exposed to the catch block
+	fn error(e: &anyhow::Error) -> Value {
+		let kind = match e.downcast_ref::<Error>() {
+			// A value thrown with a THROW statement is exposed as is
+			Some(Error::ThrownValue(v)) => return v.clone(),
+			Some(Error::Thrown(v)) => return Value::from(v.as_str()),
+			// Use the name of the error variant as the kind
+			Some(err) => {
+				let kind = format!("{err:?}");
+				kind.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default().to_owned()
+			}
+			None => "Internal".to_owned(),
+		};
+		Value::from(map! {
+			"kind".to_string() => Value::from(kind),
+			"message".to_string() => Value::from(e.to_string()),
+		})
+	}
+}
+
+impl Display for TrycatchStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TRY {} CATCH", self.block)?;
+		if let Some(ref v) = self.param {
+			write!(f, " ({v})")?;
+		}
+		write!(f, " {}", self.catch)
+	}
+}
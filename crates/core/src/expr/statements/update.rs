@@ -2,16 +2,21 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::expr::{Cond, Data, Explain, FlowResultExt as _, Output, Timeout, Value, Values, With};
+use crate::expr::order::Ordering;
+use crate::expr::statements::SelectStatement;
+use crate::expr::{
+	Cond, Data, Explain, Fields, FlowResultExt as _, Limit, Output, Timeout, Value, Values, With,
+};
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
-use anyhow::{Result, ensure};
+use anyhow::Result;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -22,7 +27,14 @@ pub struct UpdateStatement {
 	#[revision(start = 3)]
 	pub with: Option<With>,
 	pub data: Option<Data>,
+	/// The version which the records are expected to be at
+	#[revision(start = 5)]
+	pub if_version: Option<Value>,
 	pub cond: Option<Cond>,
+	#[revision(start = 4)]
+	pub order: Option<Ordering>,
+	#[revision(start = 4)]
+	pub limit: Option<Limit>,
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
@@ -52,12 +64,30 @@ impl UpdateStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = stm.setup_timeout(ctx)?;
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
 		let stm_ctx = StatementContext::new(&ctx, opt, &stm)?;
+		// Order and limit the records before they are updated
+		let what = match (&self.order, &self.limit) {
+			(None, None) => None,
+			_ => Some(
+				SelectStatement {
+					expr: Fields::all(),
+					what: self.what.clone(),
+					with: self.with.clone(),
+					cond: self.cond.clone(),
+					order: self.order.clone(),
+					limit: self.limit.clone(),
+					..Default::default()
+				}
+				.compute_ids(stk, &ctx, opt, doc)
+				.await?,
+			),
+		};
 		// Loop over the update targets
-		for w in self.what.0.iter() {
+		for w in what.as_ref().unwrap_or(&self.what).0.iter() {
 			let v = w.compute(stk, &ctx, opt, doc).await.catch_return()?;
 			i.prepare(stk, &mut planner, &stm_ctx, v).await.map_err(|e| {
 				if matches!(e.downcast_ref(), Some(Error::InvalidStatementTarget { .. })) {
@@ -78,9 +108,9 @@ impl UpdateStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		match res {
 			// This is a single record result
@@ -109,9 +139,18 @@ impl fmt::Display for UpdateStatement {
 		if let Some(ref v) = self.data {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.if_version {
+			write!(f, " IF VERSION {v}")?
+		}
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.output {
 			write!(f, " {v}")?
 		}
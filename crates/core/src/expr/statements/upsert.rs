@@ -4,12 +4,13 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::{Cond, Data, Explain, FlowResultExt as _, Output, Timeout, Value, Values, With};
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
-use anyhow::{Result, ensure};
+use anyhow::Result;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use trice::Instant;
 
 #[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -51,6 +52,7 @@ impl UpsertStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false);
 		// Check if there is a timeout
+		let started = Instant::now();
 		let ctx = stm.setup_timeout(ctx)?;
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
@@ -77,9 +79,9 @@ impl UpsertStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output(stk, &ctx, opt, &stm, RecordStrategy::KeysAndValues).await;
 		// Catch statement timeout
-		ensure!(!ctx.is_timedout().await?, Error::QueryTimedout);
+		let res = stm.check_timeout(&ctx, started, res).await?;
 		// Output the results
 		match res {
 			// This is a single record result
@@ -1,6 +1,8 @@
+use crate::cnf::PROTECTED_PARAM_NAMES;
 use crate::ctx::{Context, MutableContext};
-use crate::dbs::Options;
+use crate::dbs::{Options, has_side_effects};
 use crate::doc::CursorDoc;
+use crate::expr::order::Ordering as OrderBy;
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::{
 	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, IfelseStatement,
@@ -8,6 +10,7 @@ use crate::expr::statements::{
 	UpdateStatement, UpsertStatement,
 };
 use crate::expr::value::Value;
+use crate::expr::{Expression, Field, Function, Id, Idiom, Part};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -75,6 +78,22 @@ impl Subquery {
 		}
 	}
 
+	/// Checks if the subquery returns the same result for every document of
+	/// the statement which contains it, as it does not refer to the document,
+	/// to any parameter which can change, or to any function whose result varies
+	pub(crate) fn is_uncorrelated(&self) -> bool {
+		match self {
+			Self::Value(v) => uncorrelated(v),
+			Self::Select(v) => uncorrelated_select(v),
+			_ => false,
+		}
+	}
+
+	/// Checks if the result of the subquery can be kept for the rest of the statement
+	pub(crate) fn is_cacheable(&self) -> bool {
+		matches!(self, Self::Select(_)) && self.is_uncorrelated()
+	}
+
 	/// Process this type returning a computed simple Value, without catching errors
 	pub(crate) async fn compute(
 		&self,
@@ -82,6 +101,27 @@ impl Subquery {
 		ctx: &Context,
 		opt: &Options,
 		doc: Option<&CursorDoc>,
+	) -> FlowResult<Value> {
+		// An uncorrelated SELECT is only computed once per statement
+		if let Some(cache) = ctx.get_subquery_cache() {
+			if self.is_cacheable() {
+				if let Some(v) = cache.get(opt, self) {
+					return Ok(v);
+				}
+				let v = self.compute_uncached(stk, ctx, opt, doc).await?;
+				cache.insert(opt, self, v.clone());
+				return Ok(v);
+			}
+		}
+		self.compute_uncached(stk, ctx, opt, doc).await
+	}
+
+	async fn compute_uncached(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
 	) -> FlowResult<Value> {
 		// Duplicate context
 		let mut ctx = MutableContext::new(ctx);
@@ -113,6 +153,128 @@ impl Subquery {
 	}
 }
 
+/// Checks if a SELECT subquery does not depend on the outer document.
+/// The targets and the limits are computed against the outer document,
+/// while the other clauses are computed against the selected records.
+fn uncorrelated_select(s: &SelectStatement) -> bool {
+	let fixed = |v: &Value| v.is_static() && uncorrelated(v);
+	if s.explain.is_some() {
+		return false;
+	}
+	if !s.what.iter().all(|v| matches!(v, Value::Table(_)) || fixed(v)) {
+		return false;
+	}
+	if !s.limit.iter().all(|v| fixed(&v.0)) || !s.start.iter().all(|v| fixed(&v.0)) {
+		return false;
+	}
+	if !s.version.iter().all(|v| fixed(&v.1)) {
+		return false;
+	}
+	if !s.expr.iter().all(|f| match f {
+		Field::All => true,
+		Field::Single {
+			expr,
+			..
+		} => uncorrelated(expr),
+		Field::Window {
+			..
+		} => false,
+	}) {
+		return false;
+	}
+	if !s.omit.iter().flat_map(|v| v.iter()).all(uncorrelated_idiom) {
+		return false;
+	}
+	if !s.cond.iter().chain(s.having.iter()).all(|v| uncorrelated(&v.0)) {
+		return false;
+	}
+	if !s.split.iter().flat_map(|v| v.iter()).all(|v| uncorrelated_idiom(&v.0)) {
+		return false;
+	}
+	if !s.group.iter().flat_map(|v| v.iter()).all(|v| uncorrelated_idiom(&v.0)) {
+		return false;
+	}
+	if !s.fetch.iter().flat_map(|v| v.iter()).all(|v| uncorrelated(&v.1)) {
+		return false;
+	}
+	match &s.order {
+		Some(OrderBy::Random) => false,
+		Some(OrderBy::Order(o)) => o.iter().all(|v| uncorrelated_idiom(&v.value)),
+		None => true,
+	}
+}
+
+/// Checks if a value does not refer to the outer document, to any parameter
+/// which can change, or to any function whose result varies between calls
+fn uncorrelated(v: &Value) -> bool {
+	match v {
+		Value::None
+		| Value::Null
+		| Value::Bool(_)
+		| Value::Number(_)
+		| Value::Strand(_)
+		| Value::Duration(_)
+		| Value::Datetime(_)
+		| Value::Uuid(_)
+		| Value::Bytes(_)
+		| Value::Geometry(_)
+		| Value::Regex(_)
+		| Value::Constant(_)
+		| Value::Table(_) => true,
+		Value::Thing(v) => match &v.id {
+			Id::Number(_) | Id::String(_) | Id::Uuid(_) => true,
+			Id::Array(a) => a.iter().all(uncorrelated),
+			Id::Object(o) => o.values().all(uncorrelated),
+			Id::Generate(_) | Id::Range(_) => false,
+		},
+		Value::Array(v) => v.iter().all(uncorrelated),
+		Value::Object(v) => v.values().all(uncorrelated),
+		// The session parameters can not be changed within a statement
+		Value::Param(v) => PROTECTED_PARAM_NAMES.contains(&v.as_str()),
+		Value::Idiom(v) => uncorrelated_idiom(v),
+		Value::Cast(v) => uncorrelated(&v.1),
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => uncorrelated(v),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => uncorrelated(l) && uncorrelated(r),
+		},
+		Value::Function(v) => match v.as_ref() {
+			Function::Normal(name, args) => {
+				let varying = name == "rand"
+					|| name.starts_with("rand::")
+					|| name == "time::now"
+					|| has_side_effects(name);
+				!varying && args.iter().all(uncorrelated)
+			}
+			_ => false,
+		},
+		Value::Subquery(v) => v.is_uncorrelated(),
+		_ => false,
+	}
+}
+
+fn uncorrelated_idiom(i: &Idiom) -> bool {
+	i.iter().all(|p| match p {
+		Part::All
+		| Part::Flatten
+		| Part::Last
+		| Part::First
+		| Part::Field(_)
+		| Part::Index(_)
+		| Part::Optional
+		| Part::Doc => true,
+		Part::Where(v) | Part::Value(v) | Part::Start(v) => uncorrelated(v),
+		Part::Method(_, args) => args.iter().all(uncorrelated),
+		_ => false,
+	})
+}
+
 impl Display for Subquery {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
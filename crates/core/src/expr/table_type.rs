@@ -34,6 +34,9 @@ impl Display for TableType {
 				if rel.enforced {
 					write!(f, " ENFORCED")?;
 				}
+				if rel.unique {
+					write!(f, " UNIQUE")?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -58,13 +61,14 @@ impl InfoStructure for TableType {
 					tables.into_iter().map(|t| t.0).collect::<Vec<_>>().into(),
 				"out".to_string(), if let Some(Kind::Record(tables)) = rel.to =>
 					tables.into_iter().map(|t| t.0).collect::<Vec<_>>().into(),
-				"enforced".to_string() => rel.enforced.into()
+				"enforced".to_string() => rel.enforced.into(),
+				"unique".to_string(), if rel.unique => true.into(),
 			}),
 		}
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -73,4 +77,7 @@ pub struct Relation {
 	pub to: Option<Kind>,
 	#[revision(start = 2)]
 	pub enforced: bool,
+	/// Whether a pair of records can only be related once
+	#[revision(start = 3)]
+	pub unique: bool,
 }
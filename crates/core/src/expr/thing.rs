@@ -167,7 +167,7 @@ impl Thing {
 
 		let range = prefix?..suffix?;
 		let txn = ctx.tx();
-		let mut stream = txn.stream_keys(range, None, ScanDirection::Forward);
+		let mut stream = txn.stream_keys(ctx, range, None, None, ScanDirection::Forward);
 
 		let mut ids = Vec::new();
 		while let Some(res) = stream.next().await {
@@ -1,3 +1,4 @@
+use crate::expr::order::Collate;
 use crate::expr::part::Next;
 use crate::expr::part::Part;
 use crate::expr::value::Value;
@@ -8,7 +9,7 @@ impl Value {
 		&self,
 		other: &Self,
 		path: &[Part],
-		collate: bool,
+		collate: Option<Collate>,
 		numeric: bool,
 	) -> Option<Ordering> {
 		match path.first() {
@@ -78,9 +79,8 @@ impl Value {
 			},
 			// No more parts so get the value
 			None => match (collate, numeric) {
-				(true, true) => self.natural_lexical_cmp(other),
-				(true, false) => self.lexical_cmp(other),
-				(false, true) => self.natural_cmp(other),
+				(Some(collate), numeric) => self.collated_cmp(other, collate, numeric),
+				(None, true) => self.natural_cmp(other),
 				_ => self.partial_cmp(other),
 			},
 		}
@@ -101,7 +101,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::default().into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: 456 } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: 123 } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -110,7 +110,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something").into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: 456 } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: 123 } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -119,7 +119,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something").into();
 		let one: Value = SqlValue::parse("{ test: { other: null } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: 123 } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -128,7 +128,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something").into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: 456 } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -137,7 +137,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something.*").into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: [4, 5, 6] } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -147,7 +147,7 @@ mod tests {
 		let one: Value =
 			SqlValue::parse("{ test: { other: null, something: [1, 2, 3, 4, 5, 6] } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -157,7 +157,7 @@ mod tests {
 		let one: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
 		let two: Value =
 			SqlValue::parse("{ test: { other: null, something: [1, 2, 3, 4, 5, 6] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -166,7 +166,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something.*").into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: null } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -175,7 +175,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test.something.*").into();
 		let one: Value = SqlValue::parse("{ test: { other: null, something: [4, 5, 6] } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: null } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -185,7 +185,7 @@ mod tests {
 		let one: Value =
 			SqlValue::parse("{ test: { other: null, something: [1, null, 3] } }").into();
 		let two: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -195,7 +195,7 @@ mod tests {
 		let one: Value = SqlValue::parse("{ test: { other: null, something: [1, 2, 3] } }").into();
 		let two: Value =
 			SqlValue::parse("{ test: { other: null, something: [1, null, 3] } }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -204,7 +204,7 @@ mod tests {
 		let idi: Idiom = SqlIdiom::parse("test[$]").into();
 		let one: Value = SqlValue::parse("{ test: [1,5] }").into();
 		let two: Value = SqlValue::parse("{ test: [2,4] }").into();
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, None, false);
 		assert_eq!(res, Some(Ordering::Greater))
 	}
 }
@@ -4,9 +4,9 @@ use geo::Point;
 use rust_decimal::Decimal;
 
 use crate::expr::{
-	Array, Bytes, Closure, Datetime, DecimalExt, Duration, File, Geometry, Ident, Kind, Literal,
-	Number, Object, Range, Regex, Strand, Table, Thing, Uuid, Value, array::Uniq as _,
-	kind::HasKind, value::Null,
+	Array, ArrayConstraints, Bytes, Closure, Datetime, DecimalExt, Duration, File, Geometry, Ident,
+	Kind, Literal, Number, Object, Range, Regex, Strand, Table, Thing, Uuid, Value,
+	array::Uniq as _, kind::HasKind, value::Null,
 };
 
 #[derive(Clone, Debug)]
@@ -20,11 +20,27 @@ pub enum CastError {
 		len: usize,
 		into: String,
 	},
+	/// Cast failed because the array has an element past its maximum length.
+	TooManyElements {
+		index: usize,
+		into: String,
+	},
+	/// Cast failed because an element of an array with unique elements is repeated.
+	DuplicateElement {
+		index: usize,
+		first: usize,
+		into: String,
+	},
 	/// Coerce failed because element of type didn't match.
 	ElementOf {
 		inner: Box<CastError>,
 		into: String,
 	},
+	/// Cast failed because the value is not one of the literals of a union.
+	InvalidLiteral {
+		from: Value,
+		allowed: Vec<Value>,
+	},
 	// Annoying error which doesn't fit in with the rest of the errors and breaks the trait
 	// pattern.
 	//
@@ -56,6 +72,33 @@ impl fmt::Display for CastError {
 			} => {
 				write!(f, "Expected `{into}` buf found an collection of length `{len}`")
 			}
+			CastError::TooManyElements {
+				index,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but found an element at index `{index}`, past the maximum length"
+				)
+			}
+			CastError::DuplicateElement {
+				index,
+				first,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but the element at index `{index}` is a duplicate of the element at index `{first}`"
+				)
+			}
+			CastError::InvalidLiteral {
+				from,
+				allowed,
+			} => {
+				write!(f, "Expected one of ")?;
+				super::coerce::fmt_literals(f, allowed)?;
+				write!(f, " but found a `{from}`")
+			}
 			CastError::RangeSizeLimit {
 				value,
 			} => {
@@ -89,6 +132,27 @@ impl<T> CastErrorExt for Result<T, CastError> {
 	}
 }
 
+/// Checks the length of an array against the bounds of a kind
+fn check_array_len<F>(len: usize, constraints: &ArrayConstraints, into: F) -> Result<(), CastError>
+where
+	F: Fn() -> String,
+{
+	match constraints.max {
+		// An exact length reports the length of the array
+		Some(max) if (len as u64) > max && constraints.exact_len().is_none() => {
+			Err(CastError::TooManyElements {
+				index: max as usize,
+				into: into(),
+			})
+		}
+		_ if !constraints.allows_len(len) => Err(CastError::InvalidLength {
+			len,
+			into: into(),
+		}),
+		_ => Ok(()),
+	}
+}
+
 /// Trait for converting the value using casting rules, calling the functions on this trait results
 /// in similar behavior as casting does in surrealql like `<string> 1`.
 ///
@@ -703,13 +767,13 @@ impl Value {
 			Kind::Regex => self.can_cast_to::<Regex>(),
 			Kind::Range => self.can_cast_to::<Box<Range>>(),
 			Kind::Function(_, _) => self.can_cast_to::<Box<Closure>>(),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.can_cast_to_array_len(t, *l),
-				None => self.can_cast_to_array(t),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.can_cast_to_array(t),
+				false => self.clone().cast_to_set_type_constrained(t, c).is_ok(),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.can_cast_to_array_len(t, *l),
-				None => self.can_cast_to_array(t),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.can_cast_to_array(t),
+				false => self.can_cast_to_array_constrained(t, c),
 			},
 			Kind::Record(t) => {
 				if t.is_empty() {
@@ -742,9 +806,13 @@ impl Value {
 		}
 	}
 
-	fn can_cast_to_array_len(&self, kind: &Kind, len: u64) -> bool {
+	fn can_cast_to_array_constrained(&self, kind: &Kind, constraints: &ArrayConstraints) -> bool {
 		match self {
-			Value::Array(a) => a.len() as u64 == len && a.iter().all(|x| x.can_cast_to_kind(kind)),
+			Value::Array(a) if !constraints.unique => {
+				constraints.allows_len(a.len()) && a.iter().all(|x| x.can_cast_to_kind(kind))
+			}
+			// The elements must be cast before they can be compared
+			Value::Array(_) => self.clone().cast_to_array_constrained(kind, constraints).is_ok(),
 			_ => false,
 		}
 	}
@@ -800,13 +868,13 @@ impl Value {
 			Kind::Regex => self.cast_to::<Regex>().map(Value::from),
 			Kind::Range => self.cast_to::<Box<Range>>().map(Value::from),
 			Kind::Function(_, _) => self.cast_to::<Box<Closure>>().map(Value::from),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.cast_to_set_type_len(t, *l).map(Value::from),
-				None => self.cast_to_set_type(t).map(Value::from),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.cast_to_set_type(t).map(Value::from),
+				false => self.cast_to_set_type_constrained(t, c).map(Value::from),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.cast_to_array_len(t, *l).map(Value::from),
-				None => self.cast_to_array(t).map(Value::from),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.cast_to_array(t).map(Value::from),
+				false => self.cast_to_array_constrained(t, c).map(Value::from),
 			},
 			Kind::Record(t) => match t.is_empty() {
 				true => self.cast_to::<Thing>().map(Value::from),
@@ -822,6 +890,13 @@ impl Value {
 			},
 			Kind::Either(k) => {
 				let Some(k) = k.iter().find(|x| self.can_cast_to_kind(x)) else {
+					// List the allowed values of a union of literals
+					if let Some(allowed) = kind.literal_variants() {
+						return Err(CastError::InvalidLiteral {
+							from: self,
+							allowed,
+						});
+					}
 					return Err(CastError::InvalidKind {
 						from: self,
 						into: kind.to_string(),
@@ -921,22 +996,38 @@ impl Value {
 			.with_element_of(|| format!("array<{kind}>"))
 	}
 
-	/// Try to convert this value to ab `Array` of a certain type and length
-	fn cast_to_array_len(self, kind: &Kind, len: u64) -> Result<Array, CastError> {
+	/// Try to convert this value to ab `Array` of a certain type, within the
+	/// length bounds of the kind, and with unique values if the kind requires it
+	fn cast_to_array_constrained(
+		self,
+		kind: &Kind,
+		constraints: &ArrayConstraints,
+	) -> Result<Array, CastError> {
 		let array = self.cast_to::<Array>()?;
 
-		if (array.len() as u64) != len {
-			return Err(CastError::InvalidLength {
-				len: array.len(),
-				into: format!("array<{kind},{len}>"),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("array<{kind},{len}>"),
+			None => Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
-		array
+		let array = array
 			.into_iter()
 			.map(|value| value.cast_to_kind(kind))
 			.collect::<Result<Array, CastError>>()
-			.with_element_of(|| format!("array<{kind}>"))
+			.with_element_of(|| format!("array<{kind}>"))?;
+
+		if constraints.unique {
+			// Duplicates are compared once the elements are cast
+			if let Some((index, first)) = ArrayConstraints::find_duplicate(&array) {
+				return Err(CastError::DuplicateElement {
+					index,
+					first,
+					into: Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+				});
+			}
+		}
+
+		Ok(array)
 	}
 
 	/// Try to convert this value to an `Array` of a certain type, unique values
@@ -953,8 +1044,13 @@ impl Value {
 		Ok(array)
 	}
 
-	/// Try to convert this value to an `Array` of a certain type, unique values, and length
-	pub(crate) fn cast_to_set_type_len(self, kind: &Kind, len: u64) -> Result<Array, CastError> {
+	/// Try to convert this value to an `Array` of a certain type, unique values,
+	/// and within the length bounds of the kind
+	pub(crate) fn cast_to_set_type_constrained(
+		self,
+		kind: &Kind,
+		constraints: &ArrayConstraints,
+	) -> Result<Array, CastError> {
 		let array = self.cast_to::<Array>()?;
 
 		let array = array
@@ -964,12 +1060,10 @@ impl Value {
 			.with_element_of(|| format!("array<{kind}>"))?
 			.uniq();
 
-		if (array.len() as u64) != len {
-			return Err(CastError::InvalidLength {
-				len: array.len(),
-				into: format!("set<{kind},{len}>"),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("set<{kind},{len}>"),
+			None => Kind::Set(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
 		Ok(array)
 	}
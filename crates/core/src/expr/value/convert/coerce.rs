@@ -8,9 +8,9 @@ use geo::Point;
 use rust_decimal::Decimal;
 
 use crate::expr::{
-	Array, Bytes, Closure, Datetime, Duration, File, Geometry, Ident, Kind, Literal, Number,
-	Object, Range, Regex, Strand, Table, Thing, Uuid, Value, array::Uniq, kind::HasKind,
-	value::Null,
+	Array, ArrayConstraints, Bytes, Closure, Datetime, Duration, File, Geometry, Ident, Kind,
+	Literal, Number, Object, Range, Regex, Strand, Table, Thing, Uuid, Value, array::Uniq,
+	kind::HasKind, value::Null,
 };
 
 #[derive(Clone, Debug)]
@@ -24,12 +24,31 @@ pub enum CoerceError {
 		len: usize,
 		into: String,
 	},
+	// Coerce failed because the array has an element past its maximum length.
+	TooManyElements {
+		index: usize,
+		into: String,
+	},
+	// Coerce failed because an element of an array with unique elements is repeated.
+	DuplicateElement {
+		index: usize,
+		first: usize,
+		into: String,
+	},
 	// Coerce failed because element of type didn't match.
 	ElementOf {
 		inner: Box<CoerceError>,
 		into: String,
 	},
+	// Coerce failed because the value is not one of the literals of a union.
+	InvalidLiteral {
+		from: Value,
+		allowed: Vec<Value>,
+	},
 }
+
+/// The number of allowed literals which are listed in a coercion error
+const MAX_LISTED_LITERALS: usize = 10;
 impl std::error::Error for CoerceError {}
 impl fmt::Display for CoerceError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -53,10 +72,51 @@ impl fmt::Display for CoerceError {
 			} => {
 				write!(f, "Expected `{into}` but found an collection of length `{len}`")
 			}
+			CoerceError::TooManyElements {
+				index,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but found an element at index `{index}`, past the maximum length"
+				)
+			}
+			CoerceError::DuplicateElement {
+				index,
+				first,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but the element at index `{index}` is a duplicate of the element at index `{first}`"
+				)
+			}
+			CoerceError::InvalidLiteral {
+				from,
+				allowed,
+			} => {
+				write!(f, "Expected one of ")?;
+				fmt_literals(f, allowed)?;
+				write!(f, " but found `{from}`")
+			}
 		}
 	}
 }
 
+/// Lists the allowed literals of a union, truncating long lists
+pub(super) fn fmt_literals(f: &mut fmt::Formatter<'_>, allowed: &[Value]) -> fmt::Result {
+	for (i, v) in allowed.iter().take(MAX_LISTED_LITERALS).enumerate() {
+		if i > 0 {
+			f.write_str(", ")?;
+		}
+		write!(f, "`{v}`")?;
+	}
+	if allowed.len() > MAX_LISTED_LITERALS {
+		write!(f, " and {} more", allowed.len() - MAX_LISTED_LITERALS)?;
+	}
+	Ok(())
+}
+
 pub trait CoerceErrorExt {
 	fn with_element_of<F>(self, f: F) -> Self
 	where
@@ -78,6 +138,31 @@ impl<T> CoerceErrorExt for Result<T, CoerceError> {
 	}
 }
 
+/// Checks the length of an array against the bounds of a kind
+fn check_array_len<F>(
+	len: usize,
+	constraints: &ArrayConstraints,
+	into: F,
+) -> Result<(), CoerceError>
+where
+	F: Fn() -> String,
+{
+	match constraints.max {
+		// An exact length reports the length of the array
+		Some(max) if (len as u64) > max && constraints.exact_len().is_none() => {
+			Err(CoerceError::TooManyElements {
+				index: max as usize,
+				into: into(),
+			})
+		}
+		_ if !constraints.allows_len(len) => Err(CoerceError::InvalidLength {
+			len,
+			into: into(),
+		}),
+		_ => Ok(()),
+	}
+}
+
 /// Trait for converting the value using coercion rules.
 ///
 /// Coercion rules are applied whenever a Value needs to be of a specific [`Kind`].
@@ -278,6 +363,38 @@ impl Coerce for Point<f64> {
 	}
 }
 
+impl Coerce for Array {
+	fn can_coerce(v: &Value) -> bool {
+		match v {
+			Value::Array(_) => true,
+			// Only integer ranges with a reasonable length become arrays
+			Value::Range(r) => r.clone().coerce_to_typed::<i64>().is_ok_and(|r| {
+				r.size_hint().1.is_some_and(|x| x <= *crate::cnf::GENERATION_ALLOCATION_LIMIT)
+			}),
+			_ => false,
+		}
+	}
+
+	fn coerce(v: Value) -> Result<Self, CoerceError> {
+		match v {
+			Value::Array(x) => Ok(x),
+			Value::Range(r) => {
+				match r.clone().coerce_to_typed::<i64>().ok().and_then(|r| r.cast_to_array()) {
+					Some(x) => Ok(x),
+					None => Err(CoerceError::InvalidKind {
+						from: Value::Range(r),
+						into: "array".to_string(),
+					}),
+				}
+			}
+			_ => Err(CoerceError::InvalidKind {
+				from: v,
+				into: "array".to_string(),
+			}),
+		}
+	}
+}
+
 impl<T: Coerce + HasKind> Coerce for Vec<T> {
 	fn can_coerce(v: &Value) -> bool {
 		let Value::Array(a) = v else {
@@ -411,7 +528,6 @@ impl_direct! {
 	Duration => Duration,
 	Bytes => Bytes,
 	Object => Object,
-	Array => Array,
 	Thing => Thing,
 	Strand => Strand,
 	Geometry => Geometry,
@@ -443,13 +559,13 @@ impl Value {
 			Kind::Regex => self.can_coerce_to::<Regex>(),
 			Kind::Range => self.can_coerce_to::<Box<Range>>(),
 			Kind::Function(_, _) => self.can_coerce_to::<Box<Closure>>(),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.can_coerce_to_array_len(t, *l),
-				None => self.can_coerce_to_array(t),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.can_coerce_to_array(t),
+				false => self.clone().coerce_to_set_kind_constrained(t, c).is_ok(),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.can_coerce_to_array_len(t, *l),
-				None => self.can_coerce_to_array(t),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.can_coerce_to_array(t),
+				false => self.can_coerce_to_array_constrained(t, c),
 			},
 			Kind::Record(t) => {
 				if t.is_empty() {
@@ -482,10 +598,14 @@ impl Value {
 		}
 	}
 
-	fn can_coerce_to_array_len(&self, kind: &Kind, len: u64) -> bool {
+	fn can_coerce_to_array_constrained(&self, kind: &Kind, constraints: &ArrayConstraints) -> bool {
 		match self {
-			Value::Array(a) => {
-				a.len() as u64 == len && a.iter().all(|x| x.can_coerce_to_kind(kind))
+			Value::Array(a) if !constraints.unique => {
+				constraints.allows_len(a.len()) && a.iter().all(|x| x.can_coerce_to_kind(kind))
+			}
+			// The elements must be coerced before they can be compared
+			Value::Array(_) | Value::Range(_) => {
+				self.clone().coerce_to_array_kind_constrained(kind, constraints).is_ok()
 			}
 			_ => false,
 		}
@@ -494,6 +614,10 @@ impl Value {
 	fn can_coerce_to_array(&self, kind: &Kind) -> bool {
 		match self {
 			Value::Array(a) => a.iter().all(|x| x.can_coerce_to_kind(kind)),
+			Value::Range(_) => self
+				.clone()
+				.coerce_to::<Array>()
+				.is_ok_and(|a| a.iter().all(|x| x.can_coerce_to_kind(kind))),
 			_ => false,
 		}
 	}
@@ -548,13 +672,13 @@ impl Value {
 			Kind::Regex => self.coerce_to::<Regex>().map(Value::from),
 			Kind::Range => self.coerce_to::<Box<Range>>().map(Value::from),
 			Kind::Function(_, _) => self.coerce_to::<Box<Closure>>().map(Value::from),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.coerce_to_set_kind_len(t, *l).map(Value::from),
-				None => self.coerce_to_set_kind(t).map(Value::from),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.coerce_to_set_kind(t).map(Value::from),
+				false => self.coerce_to_set_kind_constrained(t, c).map(Value::from),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.coerce_to_array_type_len(t, *l).map(Value::from),
-				None => self.coerce_to_array_type(t).map(Value::from),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.coerce_to_array_type(t).map(Value::from),
+				false => self.coerce_to_array_kind_constrained(t, c).map(Value::from),
 			},
 			Kind::Record(t) => match t.is_empty() {
 				true => self.coerce_to::<Thing>().map(Value::from),
@@ -571,6 +695,13 @@ impl Value {
 			Kind::Either(k) => {
 				// Check first for valid kind, then convert to not consume the value
 				let Some(k) = k.iter().find(|x| self.can_coerce_to_kind(x)) else {
+					// List the allowed values of a union of literals
+					if let Some(allowed) = kind.literal_variants() {
+						return Err(CoerceError::InvalidLiteral {
+							from: self,
+							allowed,
+						});
+					}
 					return Err(CoerceError::InvalidKind {
 						from: self,
 						into: kind.to_string(),
@@ -657,26 +788,38 @@ impl Value {
 			.with_element_of(|| format!("array<{kind}>"))
 	}
 
-	/// Try to coerce this value to an `Array` of a certain type, and length
-	pub(crate) fn coerce_to_array_type_len(
+	/// Try to coerce this value to an `Array` of a certain type, within the
+	/// length bounds of the kind, and with unique values if the kind requires it
+	pub(crate) fn coerce_to_array_kind_constrained(
 		self,
 		kind: &Kind,
-		len: u64,
+		constraints: &ArrayConstraints,
 	) -> Result<Array, CoerceError> {
 		let array = self.coerce_to::<Array>()?;
 
-		if array.len() as u64 != len {
-			return Err(CoerceError::InvalidLength {
-				len: array.len(),
-				into: format!("array<{kind},{len}>"),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("array<{kind},{len}>"),
+			None => Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
-		array
+		let array = array
 			.into_iter()
 			.map(|value| value.coerce_to_kind(kind))
 			.collect::<Result<Array, CoerceError>>()
-			.with_element_of(|| format!("array<{kind}>"))
+			.with_element_of(|| format!("array<{kind}>"))?;
+
+		if constraints.unique {
+			// Duplicates are compared once the elements are coerced
+			if let Some((index, first)) = ArrayConstraints::find_duplicate(&array) {
+				return Err(CoerceError::DuplicateElement {
+					index,
+					first,
+					into: Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+				});
+			}
+		}
+
+		Ok(array)
 	}
 
 	/// Try to coerce this value to an `Array` of a certain type, unique values
@@ -689,11 +832,12 @@ impl Value {
 			.with_element_of(|| format!("set<{kind}>"))
 	}
 
-	/// Try to coerce this value to an `Array` of a certain type, unique values, and length
-	pub(crate) fn coerce_to_set_kind_len(
+	/// Try to coerce this value to an `Array` of a certain type, unique values,
+	/// and within the length bounds of the kind
+	pub(crate) fn coerce_to_set_kind_constrained(
 		self,
 		kind: &Kind,
-		len: u64,
+		constraints: &ArrayConstraints,
 	) -> Result<Array, CoerceError> {
 		let array = self
 			.coerce_to::<Array>()?
@@ -703,12 +847,10 @@ impl Value {
 			.collect::<Result<Array, CoerceError>>()
 			.with_element_of(|| format!("set<{kind}>"))?;
 
-		if array.len() as u64 != len {
-			return Err(CoerceError::InvalidLength {
-				into: format!("set<{kind}, {len}>"),
-				len: array.len(),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("set<{kind}, {len}>"),
+			None => Kind::Set(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
 		Ok(array)
 	}
@@ -1,6 +1,7 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::expr::FlowResultExt as _;
+use crate::expr::Thing;
 use crate::expr::edges::Edges;
 use crate::expr::field::{Field, Fields};
 use crate::expr::part::Next;
@@ -9,16 +10,105 @@ use crate::expr::statements::select::SelectStatement;
 use crate::expr::value::{Value, Values};
 use anyhow::Result;
 use futures::future::try_join_all;
+use parking_lot::Mutex;
 use reblessive::tree::Stk;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The records which have been selected while processing a `FETCH` clause.
+/// A record which is reached through several paths is only selected once.
+#[derive(Default)]
+pub(crate) struct FetchedRecords(Mutex<HashMap<Thing, Arc<Value>>>);
+
+impl FetchedRecords {
+	/// Selects a record, unless it has already been selected
+	async fn select(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rid: &Thing,
+	) -> Result<Value> {
+		if let Some(v) = self.0.lock().get(rid) {
+			return Ok(v.as_ref().clone());
+		}
+		// Fetch the remote embedded record
+		let stm = SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::from(rid.clone())]),
+			..SelectStatement::default()
+		};
+		let v = stm.compute(stk, ctx, opt, None).await?.first();
+		self.0.lock().insert(rid.clone(), Arc::new(v.clone()));
+		Ok(v)
+	}
+}
+
+/// A path of a `FETCH` clause which is being fetched
+#[derive(Clone)]
+pub(crate) struct Fetching<'a> {
+	/// The path which is fetched, starting from each fetched record
+	path: &'a [Part],
+	/// The number of times the path is still fetched
+	depth: u32,
+	/// The records containing the current value, which are left as record ids
+	ancestors: Vec<Thing>,
+	records: &'a FetchedRecords,
+}
+
+impl<'a> Fetching<'a> {
+	pub(crate) fn new(path: &'a [Part], depth: u32, records: &'a FetchedRecords) -> Self {
+		Self {
+			path,
+			depth,
+			ancestors: Vec::new(),
+			records,
+		}
+	}
+
+	/// Adds the record found in a value to the records containing the fetched values
+	fn enter(&self, v: &Value) -> Option<Self> {
+		let Value::Object(o) = v else {
+			return None;
+		};
+		let rid = o.rid()?;
+		if self.ancestors.last() == Some(&rid) {
+			return None;
+		}
+		let mut ancestors = self.ancestors.clone();
+		ancestors.push(rid);
+		Some(Self {
+			path: self.path,
+			depth: self.depth,
+			ancestors,
+			records: self.records,
+		})
+	}
+}
 
 impl Value {
+	/// Replaces the record ids found at the path with the records they point
+	/// to. A record which is found inside itself is left as a record id.
 	pub(crate) async fn fetch(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		fetching: &Fetching<'_>,
+	) -> Result<()> {
+		self.fetch_path(stk, ctx, opt, fetching.path, fetching).await
+	}
+
+	async fn fetch_path(
 		&mut self,
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		path: &[Part],
+		fetching: &Fetching<'_>,
 	) -> Result<()> {
+		let entered = fetching.enter(self);
+		let fetching = entered.as_ref().unwrap_or(fetching);
 		let mut this = self;
 		let mut iter = path.iter();
 		let mut prev = path;
@@ -38,7 +128,9 @@ impl Value {
 						};
 
 						let mut v = Value::Thing(v);
-						return stk.run(|stk| v.fetch(stk, ctx, opt, iter.as_slice())).await;
+						return stk
+							.run(|stk| v.fetch_path(stk, ctx, opt, iter.as_slice(), fetching))
+							.await;
 					}
 					Value::Thing(x) => {
 						let stm = SelectStatement {
@@ -70,8 +162,9 @@ impl Value {
 					Value::Array(x) => {
 						// apply this path to every entry of the array.
 						stk.scope(|scope| {
-							let futs =
-								x.iter_mut().map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, prev)));
+							let futs = x.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, prev, fetching))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -90,8 +183,9 @@ impl Value {
 					Value::Array(x) => {
 						// apply this path to every entry of the array.
 						stk.scope(|scope| {
-							let futs =
-								x.iter_mut().map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, prev)));
+							let futs = x.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, prev, fetching))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -130,7 +224,9 @@ impl Value {
 								};
 								let mut range = Value::Array(range.to_vec().into());
 								return stk
-									.run(|stk| range.fetch(stk, ctx, opt, iter.as_slice()))
+									.run(|stk| {
+										range.fetch_path(stk, ctx, opt, iter.as_slice(), fetching)
+									})
 									.await;
 							}
 							let idx = v.coerce_to::<i64>()?;
@@ -147,8 +243,9 @@ impl Value {
 					Value::Array(x) => {
 						// apply this path to every entry of the array.
 						stk.scope(|scope| {
-							let futs =
-								x.iter_mut().map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, prev)));
+							let futs = x.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, prev, fetching))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -157,7 +254,10 @@ impl Value {
 						for p in p.iter() {
 							let mut destructure_path = p.path();
 							destructure_path.extend_from_slice(path);
-							stk.run(|stk| this.fetch(stk, ctx, opt, &destructure_path)).await?;
+							stk.run(|stk| {
+								this.fetch_path(stk, ctx, opt, &destructure_path, fetching)
+							})
+							.await?;
 						}
 						return Ok(());
 					}
@@ -173,9 +273,9 @@ impl Value {
 						}
 
 						stk.scope(|scope| {
-							let futs = x
-								.iter_mut()
-								.map(|(_, v)| scope.run(|stk| v.fetch(stk, ctx, opt, next_path)));
+							let futs = x.iter_mut().map(|(_, v)| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, next_path, fetching))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -190,9 +290,9 @@ impl Value {
 						}
 
 						stk.scope(|scope| {
-							let futs = x
-								.iter_mut()
-								.map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, next_path)));
+							let futs = x.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, next_path, fetching))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -227,7 +327,10 @@ impl Value {
 								.catch_return()?
 								.is_truthy()
 							{
-								stk.run(|stk| v.fetch(stk, ctx, opt, iter.as_slice())).await?;
+								stk.run(|stk| {
+									v.fetch_path(stk, ctx, opt, iter.as_slice(), fetching)
+								})
+								.await?;
 							}
 						}
 					}
@@ -242,22 +345,29 @@ impl Value {
 		match this {
 			Value::Array(v) => {
 				stk.scope(|scope| {
-					let futs = v.iter_mut().map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, path)));
+					let futs = v
+						.iter_mut()
+						.map(|v| scope.run(|stk| v.fetch_path(stk, ctx, opt, path, fetching)));
 					try_join_all(futs)
 				})
 				.await?;
 				Ok(())
 			}
 			Value::Thing(v) => {
-				// Clone the thing
-				let val = v.clone();
-				// Fetch the remote embedded record
-				let stm = SelectStatement {
-					expr: Fields(vec![Field::All], false),
-					what: Values(vec![Value::from(val)]),
-					..SelectStatement::default()
-				};
-				*this = stm.compute(stk, ctx, opt, None).await?.first();
+				// A record is not fetched inside itself
+				if fetching.ancestors.contains(v) {
+					return Ok(());
+				}
+				let rid = v.clone();
+				*this = fetching.records.select(stk, ctx, opt, &rid).await?;
+				// Fetch the path again from the fetched record
+				if fetching.depth > 1 {
+					let fetching = Fetching {
+						depth: fetching.depth - 1,
+						..fetching.clone()
+					};
+					stk.run(|stk| this.fetch_path(stk, ctx, opt, fetching.path, &fetching)).await?;
+				}
 				Ok(())
 			}
 			_ => Ok(()),
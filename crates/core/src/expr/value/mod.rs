@@ -17,7 +17,7 @@ mod diff;
 mod each;
 pub(crate) mod every;
 mod extend;
-mod fetch;
+pub(crate) mod fetch;
 mod first;
 mod flatten;
 mod generate;
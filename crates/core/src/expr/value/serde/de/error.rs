@@ -0,0 +1,97 @@
+use serde::de;
+use std::fmt::{self, Display};
+
+/// A part of the path to a value which failed to deserialize
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Part {
+	Index(usize),
+	Field(String),
+}
+
+/// An error which occurred while deserializing a [`Value`](crate::expr::Value),
+/// including the path to the nested value which failed to deserialize.
+///
+/// The error displays as `orders[3].total: expected f64, found NONE`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DeserializeError {
+	path: Vec<Part>,
+	message: String,
+}
+
+impl DeserializeError {
+	/// The path to the value which failed to deserialize, or an empty
+	/// string if the top-level value failed to deserialize
+	pub fn path(&self) -> String {
+		let mut path = String::new();
+		for part in &self.path {
+			match part {
+				Part::Index(i) => path.push_str(&format!("[{i}]")),
+				Part::Field(f) if path.is_empty() => path.push_str(f),
+				Part::Field(f) => path.push_str(&format!(".{f}")),
+			}
+		}
+		path
+	}
+
+	/// The reason that the value failed to deserialize
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	/// Records that the error occurred within an array element
+	pub(super) fn in_index(mut self, index: usize) -> Self {
+		self.path.insert(0, Part::Index(index));
+		self
+	}
+
+	/// Records that the error occurred within an object field
+	pub(super) fn in_field(mut self, field: String) -> Self {
+		self.path.insert(0, Part::Field(field));
+		self
+	}
+}
+
+impl Display for DeserializeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.path.is_empty() {
+			true => write!(f, "{}", self.message),
+			false => write!(f, "{}: {}", self.path(), self.message),
+		}
+	}
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+	fn custom<T: Display>(msg: T) -> Self {
+		Self {
+			path: Vec::new(),
+			message: msg.to_string(),
+		}
+	}
+
+	fn invalid_type(unexp: de::Unexpected, exp: &dyn de::Expected) -> Self {
+		Self::custom(format_args!("expected {exp}, found {unexp}"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::de::Error;
+
+	#[test]
+	fn display_path() {
+		let err = DeserializeError::custom("expected f64, found NONE")
+			.in_field("total".to_owned())
+			.in_index(3)
+			.in_field("orders".to_owned());
+		assert_eq!(err.path(), "orders[3].total");
+		assert_eq!(err.message(), "expected f64, found NONE");
+		assert_eq!(err.to_string(), "orders[3].total: expected f64, found NONE");
+		// Errors at the top level have no path
+		let err = DeserializeError::custom("missing field `id`");
+		assert_eq!(err.to_string(), "missing field `id`");
+	}
+}
@@ -1,12 +1,18 @@
+mod error;
+
+pub use error::DeserializeError;
+
 use crate::expr;
 use crate::expr::Value;
 use anyhow::Result;
-use serde::de::DeserializeOwned;
-use serde_content::Deserializer;
+use rust_decimal::prelude::ToPrimitive;
+use serde::de::{self, DeserializeOwned, Error as _, IntoDeserializer, Unexpected, Visitor};
 use serde_content::Number;
 use serde_content::Serializer;
 use serde_content::Value as Content;
 use std::borrow::Cow;
+use std::collections::{BTreeMap, btree_map};
+use std::vec;
 
 impl Value {
 	fn into_content(self) -> Result<Content<'static>> {
@@ -75,11 +81,474 @@ impl Value {
 }
 
 /// Deserializes a value `T` from `SurrealDB` [`Value`]
+///
+/// Record ids can be deserialized into strings, decimals into floats or
+/// integers, datetimes into `chrono` types, and `NONE` or `NULL` into
+/// options. If deserialization fails, the returned error can be downcast
+/// into a [`DeserializeError`], which includes the path to the failed value.
 pub fn from_value<T>(value: Value) -> Result<T>
 where
 	T: DeserializeOwned,
 {
-	let content = value.into_content()?;
-	let deserializer = Deserializer::new(content).coerce_numbers();
-	T::deserialize(deserializer).map_err(Into::into)
+	T::deserialize(Deserializer::new(value)).map_err(Into::into)
+}
+
+/// A [`serde::Deserializer`] which deserializes directly from a [`Value`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Deserializer {
+	value: Value,
+}
+
+impl Deserializer {
+	/// Create a deserializer for the specified value
+	pub fn new(value: Value) -> Self {
+		Self {
+			value,
+		}
+	}
+
+	/// Describes the value, for use in error messages
+	fn unexpected(&self) -> Unexpected<'_> {
+		match &self.value {
+			Value::None => Unexpected::Other("NONE"),
+			Value::Null => Unexpected::Other("NULL"),
+			Value::Bool(v) => Unexpected::Bool(*v),
+			Value::Number(expr::Number::Int(v)) => Unexpected::Signed(*v),
+			Value::Number(expr::Number::Float(v)) => Unexpected::Float(*v),
+			Value::Number(expr::Number::Decimal(_)) => Unexpected::Other("decimal"),
+			Value::Strand(v) => Unexpected::Str(&v.0),
+			Value::Duration(_) => Unexpected::Other("duration"),
+			Value::Datetime(_) => Unexpected::Other("datetime"),
+			Value::Uuid(_) => Unexpected::Other("uuid"),
+			Value::Array(_) => Unexpected::Seq,
+			Value::Object(_) => Unexpected::Map,
+			Value::Geometry(_) => Unexpected::Other("geometry"),
+			Value::Bytes(v) => Unexpected::Bytes(&v.0),
+			Value::Thing(_) => Unexpected::Other("record id"),
+			_ => Unexpected::Other("value"),
+		}
+	}
+
+	/// Rejects `NONE` and `NULL`, which can only be deserialized into
+	/// optional or unit types
+	fn expect_some<'de, V: Visitor<'de>>(&self, visitor: &V) -> Result<(), DeserializeError> {
+		match self.value {
+			Value::None | Value::Null => {
+				Err(DeserializeError::invalid_type(self.unexpected(), visitor))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Deserializes values without a direct mapping through their
+	/// serialized representation, as used by the types of this crate
+	fn fallback<'de>(value: Value) -> Result<serde_content::Deserializer<'de>, DeserializeError> {
+		let content = value.into_content().map_err(DeserializeError::custom)?;
+		Ok(serde_content::Deserializer::new(content).coerce_numbers())
+	}
+
+	/// Deserializes a number into an integer type
+	fn deserialize_integer<'de, V: Visitor<'de>>(
+		self,
+		visitor: V,
+	) -> Result<V::Value, DeserializeError> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Number(expr::Number::Int(v)) => visitor.visit_i64(v),
+			Value::Number(expr::Number::Float(v))
+				if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 =>
+			{
+				visitor.visit_i64(v as i64)
+			}
+			Value::Number(expr::Number::Decimal(v)) if v.fract().is_zero() => {
+				match (v.to_i64(), v.to_u64(), v.to_i128()) {
+					(Some(v), _, _) => visitor.visit_i64(v),
+					(_, Some(v), _) => visitor.visit_u64(v),
+					(_, _, Some(v)) => visitor.visit_i128(v),
+					_ => {
+						Err(DeserializeError::invalid_type(Unexpected::Other("decimal"), &visitor))
+					}
+				}
+			}
+			_ => self.deserialize_any(visitor),
+		}
+	}
+
+	/// Deserializes a number into a floating point type
+	fn deserialize_float<'de, V: Visitor<'de>>(
+		self,
+		visitor: V,
+	) -> Result<V::Value, DeserializeError> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Number(expr::Number::Decimal(v)) => match v.to_f64() {
+				Some(v) => visitor.visit_f64(v),
+				None => Err(DeserializeError::invalid_type(Unexpected::Other("decimal"), &visitor)),
+			},
+			_ => self.deserialize_any(visitor),
+		}
+	}
+}
+
+macro_rules! deserialize_integer {
+	($($method:ident)*) => {
+		$(
+			fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+				self.deserialize_integer(visitor)
+			}
+		)*
+	};
+}
+
+macro_rules! deserialize_some {
+	($($method:ident)*) => {
+		$(
+			fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+				self.expect_some(&visitor)?;
+				self.deserialize_any(visitor)
+			}
+		)*
+	};
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+	type Error = DeserializeError;
+
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.value {
+			Value::None | Value::Null => visitor.visit_none(),
+			Value::Bool(v) => visitor.visit_bool(v),
+			Value::Number(expr::Number::Int(v)) => visitor.visit_i64(v),
+			Value::Number(expr::Number::Float(v)) => visitor.visit_f64(v),
+			Value::Number(expr::Number::Decimal(v)) => visitor.visit_string(v.to_string()),
+			Value::Strand(v) => visitor.visit_string(v.0),
+			Value::Array(v) => visitor.visit_seq(SeqAccess::new(v.0)),
+			Value::Object(v) => visitor.visit_map(MapAccess::new(v.0)),
+			value => {
+				Self::fallback(value)?.deserialize_any(visitor).map_err(DeserializeError::custom)
+			}
+		}
+	}
+
+	deserialize_integer! {
+		deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+		deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+	}
+
+	deserialize_some! {
+		deserialize_bool deserialize_char deserialize_identifier
+	}
+
+	fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_float(visitor)
+	}
+
+	fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_float(visitor)
+	}
+
+	fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_string(visitor)
+	}
+
+	fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Thing(v) => visitor.visit_string(v.to_string()),
+			Value::Duration(v) => visitor.visit_string(v.to_raw()),
+			Value::Uuid(v) => visitor.visit_string(v.to_raw()),
+			Value::Table(v) => visitor.visit_string(v.0),
+			_ => self.deserialize_any(visitor),
+		}
+	}
+
+	fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_byte_buf(visitor)
+	}
+
+	fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Bytes(v) => visitor.visit_byte_buf(v.0),
+			_ => self.deserialize_any(visitor),
+		}
+	}
+
+	fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.value {
+			Value::None | Value::Null => visitor.visit_none(),
+			_ => visitor.visit_some(self),
+		}
+	}
+
+	fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.value {
+			Value::None | Value::Null => visitor.visit_unit(),
+			_ => self.deserialize_any(visitor),
+		}
+	}
+
+	fn deserialize_unit_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.deserialize_unit(visitor)
+	}
+
+	fn deserialize_newtype_struct<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		match name.starts_with("$surrealdb::private::") {
+			true => Self::fallback(self.value)?
+				.deserialize_newtype_struct(name, visitor)
+				.map_err(DeserializeError::custom),
+			false => visitor.visit_newtype_struct(self),
+		}
+	}
+
+	fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Array(v) => visitor.visit_seq(SeqAccess::new(v.0)),
+			value => {
+				Self::fallback(value)?.deserialize_seq(visitor).map_err(DeserializeError::custom)
+			}
+		}
+	}
+
+	fn deserialize_tuple<V: Visitor<'de>>(
+		self,
+		_len: usize,
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.deserialize_seq(visitor)
+	}
+
+	fn deserialize_tuple_struct<V: Visitor<'de>>(
+		self,
+		_name: &'static str,
+		_len: usize,
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.deserialize_seq(visitor)
+	}
+
+	fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Object(v) => visitor.visit_map(MapAccess::new(v.0)),
+			value => {
+				Self::fallback(value)?.deserialize_map(visitor).map_err(DeserializeError::custom)
+			}
+		}
+	}
+
+	fn deserialize_struct<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		self.expect_some(&visitor)?;
+		match self.value {
+			Value::Object(v) => visitor.visit_map(MapAccess::new(v.0)),
+			value => Self::fallback(value)?
+				.deserialize_struct(name, fields, visitor)
+				.map_err(DeserializeError::custom),
+		}
+	}
+
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		name: &'static str,
+		variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error> {
+		match self.value {
+			Value::Strand(v) => {
+				visitor.visit_enum(IntoDeserializer::<DeserializeError>::into_deserializer(v.0))
+			}
+			value => Self::fallback(value)?
+				.deserialize_enum(name, variants, visitor)
+				.map_err(DeserializeError::custom),
+		}
+	}
+
+	fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		visitor.visit_unit()
+	}
+}
+
+/// Deserializes the elements of an array, recording their index in errors
+struct SeqAccess {
+	iter: vec::IntoIter<Value>,
+	index: usize,
+}
+
+impl SeqAccess {
+	fn new(values: Vec<Value>) -> Self {
+		Self {
+			iter: values.into_iter(),
+			index: 0,
+		}
+	}
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+	type Error = DeserializeError;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: de::DeserializeSeed<'de>,
+	{
+		let Some(value) = self.iter.next() else {
+			return Ok(None);
+		};
+		let index = self.index;
+		self.index += 1;
+		seed.deserialize(Deserializer::new(value)).map(Some).map_err(|e| e.in_index(index))
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.iter.len())
+	}
+}
+
+/// Deserializes the fields of an object, recording their name in errors
+struct MapAccess {
+	iter: btree_map::IntoIter<String, Value>,
+	next: Option<(String, Value)>,
+}
+
+impl MapAccess {
+	fn new(values: BTreeMap<String, Value>) -> Self {
+		Self {
+			iter: values.into_iter(),
+			next: None,
+		}
+	}
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+	type Error = DeserializeError;
+
+	fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+	where
+		K: de::DeserializeSeed<'de>,
+	{
+		let Some((key, value)) = self.iter.next() else {
+			return Ok(None);
+		};
+		let res = seed
+			.deserialize(IntoDeserializer::<DeserializeError>::into_deserializer(key.as_str()))
+			.map(Some);
+		self.next = Some((key, value));
+		res
+	}
+
+	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+	where
+		V: de::DeserializeSeed<'de>,
+	{
+		let Some((key, value)) = self.next.take() else {
+			return Err(DeserializeError::custom("value requested before key"));
+		};
+		seed.deserialize(Deserializer::new(value)).map_err(|e| e.in_field(key))
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.iter.len())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expr::Thing;
+	use chrono::{DateTime, Utc};
+	use rust_decimal::Decimal;
+	use serde::Deserialize;
+
+	fn value(val: &str) -> Value {
+		crate::syn::value(val).unwrap().into()
+	}
+
+	#[derive(Debug, Deserialize, PartialEq)]
+	struct RecordId(String);
+
+	#[derive(Debug, Deserialize, PartialEq)]
+	struct Order {
+		id: RecordId,
+		customer: String,
+		total: f64,
+		exact: Decimal,
+		created: DateTime<Utc>,
+		note: Option<String>,
+		items: Vec<u32>,
+	}
+
+	#[test]
+	fn deserialize_struct() {
+		let val = value(
+			"{ id: order:1, customer: person:tobie, total: 10.5dec, exact: 10.5dec, created: d'2024-01-01T00:00:00Z', note: NONE, items: [1, 2.0, 3dec] }",
+		);
+		let order: Order = from_value(val).unwrap();
+		assert_eq!(
+			order,
+			Order {
+				id: RecordId("order:1".to_owned()),
+				customer: "person:tobie".to_owned(),
+				total: 10.5,
+				exact: Decimal::new(105, 1),
+				created: "2024-01-01T00:00:00Z".parse().unwrap(),
+				note: None,
+				items: vec![1, 2, 3],
+			}
+		);
+	}
+
+	#[test]
+	fn deserialize_options() {
+		assert_eq!(from_value::<Option<i64>>(Value::None).unwrap(), None);
+		assert_eq!(from_value::<Option<i64>>(Value::Null).unwrap(), None);
+		assert_eq!(from_value::<Option<i64>>(Value::from(1)).unwrap(), Some(1));
+		from_value::<()>(Value::None).unwrap();
+	}
+
+	#[test]
+	fn deserialize_types() {
+		let thing: Thing = from_value(value("person:tobie")).unwrap();
+		assert_eq!(thing, crate::syn::thing("person:tobie").unwrap().into());
+		let json: serde_json::Value =
+			from_value(value("{ name: 'Tobie', tags: [1, true] }")).unwrap();
+		assert_eq!(json, serde_json::json!({ "name": "Tobie", "tags": [1, true] }));
+	}
+
+	#[test]
+	fn deserialize_error_path() {
+		#[derive(Debug, Deserialize)]
+		#[allow(dead_code)]
+		struct Total {
+			total: f64,
+		}
+		#[derive(Debug, Deserialize)]
+		#[allow(dead_code)]
+		struct Customer {
+			orders: Vec<Total>,
+		}
+		let val =
+			value("{ orders: [{ total: 1 }, { total: 2.5 }, { total: 3dec }, { total: NONE }] }");
+		let err = from_value::<Customer>(val).unwrap_err();
+		let err = err.downcast_ref::<DeserializeError>().unwrap();
+		assert_eq!(err.path(), "orders[3].total");
+		assert_eq!(err.to_string(), "orders[3].total: expected f64, found NONE");
+		// Errors in nested fields include the whole path
+		let val = value("{ orders: [{ total: 'ten' }] }");
+		let err = from_value::<Customer>(val).unwrap_err();
+		assert_eq!(err.to_string(), "orders[0].total: expected f64, found string \"ten\"");
+	}
 }
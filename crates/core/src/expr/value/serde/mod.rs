@@ -1,5 +1,5 @@
 mod de;
 mod ser;
 
-pub use de::from_value;
+pub use de::{DeserializeError, Deserializer, from_value};
 pub use ser::to_value;
@@ -16,7 +16,7 @@ use crate::expr::{
 	id::{Gen, Id},
 	model::Model,
 };
-use crate::expr::{Closure, ControlFlow, FlowResult, Ident, Kind};
+use crate::expr::{ArrayConstraints, Closure, Collate, ControlFlow, FlowResult, Ident, Kind};
 use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
 
@@ -489,7 +489,7 @@ impl Value {
 			Value::Uuid(_) => Some(Kind::Uuid),
 			Value::Array(arr) => Some(Kind::Array(
 				Box::new(arr.first().and_then(|v| v.kind()).unwrap_or_default()),
-				None,
+				ArrayConstraints::default(),
 			)),
 			Value::Object(_) => Some(Kind::Object),
 			Value::Geometry(geo) => Some(Kind::Geometry(vec![geo.as_type().to_string()])),
@@ -834,6 +834,19 @@ impl Value {
 		}
 	}
 
+	/// Compare this Value to another Value after folding strings with a collation
+	pub fn collated_cmp(&self, other: &Value, collate: Collate, numeric: bool) -> Option<Ordering> {
+		match (self, other) {
+			(Value::Strand(a), Value::Strand(b)) => Some(match (collate, numeric) {
+				(Collate::Lexical, true) => lexicmp::natural_lexical_cmp(a, b),
+				(Collate::Lexical, false) => lexicmp::lexical_cmp(a, b),
+				(collate, true) => lexicmp::natural_cmp(&collate.fold(a), &collate.fold(b)),
+				(collate, false) => collate.fold(a).cmp(&collate.fold(b)),
+			}),
+			_ => self.partial_cmp(other),
+		}
+	}
+
 	/// Validate that a Value is computed or contains only computed Values
 	pub fn validate_computed(&self) -> Result<()> {
 		use Value::*;
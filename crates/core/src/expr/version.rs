@@ -1,6 +1,9 @@
 use super::FlowResultExt;
+use crate::cnf::VERSION_RETENTION;
+use crate::expr::Number;
 use crate::{ctx::Context, dbs::Options, doc::CursorDoc, err::Error, expr::datetime::Datetime};
-use anyhow::Result;
+use anyhow::{Result, ensure};
+use chrono::DateTime;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -41,11 +44,35 @@ impl Version {
 					"Failed to convert datetime to timestamp",
 				))),
 			},
+			// A versionstamp is the nanosecond timestamp of a version
+			Value::Number(Number::Int(v)) if v >= 0 => Ok(v as u64),
 			found => Err(anyhow::Error::new(Error::InvalidVersion {
 				found,
 			})),
 		}
 	}
+
+	/// Converts a version timestamp to a datetime
+	pub(crate) fn to_datetime(ts: u64) -> Datetime {
+		Datetime::from(DateTime::from_timestamp_nanos(ts as i64))
+	}
+
+	/// Ensures that a version can be read, given the configured retention window
+	pub(crate) fn check_retention(ts: u64) -> Result<()> {
+		if *VERSION_RETENTION == 0 {
+			return Ok(());
+		}
+		let now = Datetime::default().to_u64().unwrap_or(u64::MAX);
+		let earliest = now.saturating_sub(VERSION_RETENTION.saturating_mul(1_000_000_000));
+		ensure!(
+			ts >= earliest,
+			Error::VersionBeforeRetention {
+				version: Self::to_datetime(ts).to_string(),
+				earliest: Self::to_datetime(earliest).to_string(),
+			}
+		);
+		Ok(())
+	}
 }
 
 impl fmt::Display for Version {
@@ -0,0 +1,77 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::expr::fmt::Fmt;
+use crate::expr::order::OrderList;
+use crate::expr::{FlowResultExt as _, Function, Value};
+use anyhow::Result;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The `OVER (PARTITION BY ... ORDER BY ...)` clause of a window function
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	/// The expressions which split the rows into separate partitions
+	pub partition: Vec<Value>,
+	/// The ordering of the rows within each partition
+	pub order: Option<OrderList>,
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("OVER (")?;
+		if !self.partition.is_empty() {
+			write!(f, "PARTITION BY {}", Fmt::comma_separated(&self.partition))?;
+			if self.order.is_some() {
+				f.write_str(" ")?;
+			}
+		}
+		if let Some(order) = &self.order {
+			write!(f, "ORDER BY {order}")?;
+		}
+		f.write_str(")")
+	}
+}
+
+impl Window {
+	/// Computes the values which a window function needs from a single row.
+	///
+	/// The window function itself can only be computed once all of the rows
+	/// are known, so this returns an array of the computed function arguments,
+	/// the partition values, and the ordering values of the row.
+	pub(crate) async fn input(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+		func: &Function,
+	) -> Result<Value> {
+		let mut args = Vec::with_capacity(func.args().len());
+		for v in func.args() {
+			args.push(stk.run(|stk| v.compute(stk, ctx, opt, Some(doc))).await.catch_return()?);
+		}
+		let mut partition = Vec::with_capacity(self.partition.len());
+		for v in &self.partition {
+			partition
+				.push(stk.run(|stk| v.compute(stk, ctx, opt, Some(doc))).await.catch_return()?);
+		}
+		let mut order = Vec::new();
+		if let Some(orders) = &self.order {
+			for o in orders.iter() {
+				let v = doc.doc.as_ref();
+				order.push(
+					stk.run(|stk| v.get(stk, ctx, opt, Some(doc), &o.value))
+						.await
+						.catch_return()?,
+				);
+			}
+		}
+		Ok(Value::from(vec![Value::from(args), Value::from(partition), Value::from(order)]))
+	}
+}
@@ -1,25 +1,89 @@
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum With {
 	NoIndex,
+	#[revision(end = 2, convert_fn = "convert_old_index", fields_name = "OldIndexFields")]
 	Index(Vec<String>),
+	/// The indexes which can be used, in order of preference
+	#[revision(start = 2)]
+	Index(Vec<IndexHint>),
+	/// The deleted records of soft delete tables are included
+	#[revision(start = 3)]
+	Deleted,
+}
+
+impl With {
+	fn convert_old_index(fields: OldIndexFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(With::Index(fields.0.into_iter().map(IndexHint::from).collect()))
+	}
+
+	/// The names of the listed indexes which apply to a table, if
+	/// the clause limits the indexes which the table can use
+	pub(crate) fn indexes_for(&self, tb: &str) -> Option<Vec<&str>> {
+		let With::Index(hints) = self else {
+			return None;
+		};
+		let names: Vec<&str> =
+			hints.iter().filter(|h| h.applies_to(tb)).map(|h| h.index.as_str()).collect();
+		(!names.is_empty()).then_some(names)
+	}
 }
 
 impl Display for With {
-	fn fmt(&self, f: &mut Formatter) -> Result {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		f.write_str("WITH")?;
 		match self {
 			With::NoIndex => f.write_str(" NOINDEX"),
 			With::Index(i) => {
 				f.write_str(" INDEX ")?;
-				f.write_str(&i.join(","))
+				let hints: Vec<String> = i.iter().map(ToString::to_string).collect();
+				f.write_str(&hints.join(","))
 			}
+			With::Deleted => f.write_str(" DELETED"),
+		}
+	}
+}
+
+/// An index listed in a WITH INDEX clause, which is either used
+/// for every table of the statement, or only for a single table
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct IndexHint {
+	/// The table which the index is listed for, or none for every table
+	pub table: Option<String>,
+	/// The name of the index
+	pub index: String,
+}
+
+impl IndexHint {
+	/// Checks if the index is listed for the given table
+	pub(crate) fn applies_to(&self, tb: &str) -> bool {
+		self.table.as_deref().is_none_or(|t| t == tb)
+	}
+}
+
+impl From<String> for IndexHint {
+	fn from(index: String) -> Self {
+		Self {
+			table: None,
+			index,
+		}
+	}
+}
+
+impl Display for IndexHint {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let Some(tb) = &self.table {
+			write!(f, "{tb}.")?;
 		}
+		f.write_str(&self.index)
 	}
 }
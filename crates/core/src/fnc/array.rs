@@ -369,7 +369,25 @@ pub async fn fold(
 		for (i, val) in array.into_iter().enumerate() {
 			// TODO: Don't clone the closure every time the function is called.
 			let fnc = Function::Anonymous(mapper.clone().into(), vec![accum, val, i.into()], true);
-			accum = fnc.compute(stk, ctx, opt, doc).await.catch_return()?;
+			// Report the index of the element which caused an error,
+			// but pass thrown values through as they were thrown
+			accum = fnc.compute(stk, ctx, opt, doc).await.catch_return().map_err(|e| match e
+				.downcast_ref::<Error>()
+			{
+				Some(
+					Error::QueryTimedout
+					| Error::StatementTimedout {
+						..
+					}
+					| Error::QueryCancelled
+					| Error::Thrown(_)
+					| Error::ThrownValue(_),
+				) => e,
+				_ => anyhow::Error::new(Error::InvalidFunction {
+					name: String::from("array::fold"),
+					message: format!("The closure failed for the element at index {i}: {e}"),
+				}),
+			})?;
 		}
 		Ok(accum)
 	} else {
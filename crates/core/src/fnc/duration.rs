@@ -38,6 +38,135 @@ pub fn years((val,): (Duration,)) -> Result<Value> {
 	Ok(val.years().into())
 }
 
+/// Parse an ISO-8601 duration such as `P1DT2H30.5S`. Years and months are rejected as their
+/// length depends on the calendar. Errors contain the 1-based position of the offending character.
+fn parse_iso8601(input: &str) -> Result<std::time::Duration, String> {
+	let chars: Vec<char> = input.chars().collect();
+	let unexpected = |pos: usize| match chars.get(pos) {
+		Some(c) => format!("Unexpected character '{c}' at position {}", pos + 1),
+		None => format!("Unexpected end of input at position {}", pos + 1),
+	};
+	if chars.first() != Some(&'P') {
+		return Err(unexpected(0));
+	}
+	let mut pos = 1;
+	let mut time = false;
+	let mut found = false;
+	// The rank of the last designator, used to enforce designator order
+	let mut rank = 0;
+	let mut secs: u64 = 0;
+	let mut nanos: u32 = 0;
+	let overflow = || String::from("The duration is too large");
+	while pos < chars.len() {
+		if chars[pos] == 'T' {
+			if time {
+				return Err(unexpected(pos));
+			}
+			time = true;
+			pos += 1;
+			// The time designator must be followed by a component
+			if pos == chars.len() {
+				return Err(unexpected(pos));
+			}
+			continue;
+		}
+		// Parse the integer part of the component
+		let start = pos;
+		while pos < chars.len() && chars[pos].is_ascii_digit() {
+			pos += 1;
+		}
+		if pos == start {
+			return Err(unexpected(pos));
+		}
+		let whole: String = chars[start..pos].iter().collect();
+		let whole: u64 = whole.parse().map_err(|_| overflow())?;
+		// Parse the optional fractional part of the component
+		let mut fraction = None;
+		if pos < chars.len() && matches!(chars[pos], '.' | ',') {
+			let dot = pos;
+			pos += 1;
+			let start = pos;
+			while pos < chars.len() && chars[pos].is_ascii_digit() {
+				pos += 1;
+			}
+			// Nanoseconds are the smallest supported unit
+			if pos == start || pos - start > 9 {
+				return Err(unexpected(if pos == start {
+					pos
+				} else {
+					start + 9
+				}));
+			}
+			let digits: String = chars[start..pos].iter().collect();
+			let padded = format!("{digits:0<9}");
+			fraction = Some((dot, padded.parse::<u32>().map_err(|_| overflow())?));
+		}
+		// Parse the designator of the component
+		let (next, multiplier) = match (time, chars.get(pos)) {
+			(false, Some('Y' | 'M')) => {
+				return Err(format!(
+					"Unsupported designator '{}' at position {}, as years and months have no fixed length",
+					chars[pos],
+					pos + 1
+				));
+			}
+			(false, Some('W')) => (1, 604_800),
+			(false, Some('D')) => (2, 86_400),
+			(true, Some('H')) => (3, 3_600),
+			(true, Some('M')) => (4, 60),
+			(true, Some('S')) => (5, 1),
+			_ => return Err(unexpected(pos)),
+		};
+		if next <= rank {
+			return Err(unexpected(pos));
+		}
+		// Only the seconds may contain a fraction
+		if let Some((dot, fraction)) = fraction {
+			if next != 5 {
+				return Err(unexpected(dot));
+			}
+			nanos = fraction;
+		}
+		rank = next;
+		found = true;
+		pos += 1;
+		secs =
+			whole.checked_mul(multiplier).and_then(|v| secs.checked_add(v)).ok_or_else(overflow)?;
+	}
+	if !found {
+		return Err(unexpected(pos));
+	}
+	Ok(std::time::Duration::new(secs, nanos))
+}
+
+/// Format a duration as an ISO-8601 duration, using days as the largest unit.
+fn format_iso8601(val: std::time::Duration) -> String {
+	let secs = val.as_secs();
+	let nanos = val.subsec_nanos();
+	let (days, hours, mins, secs) =
+		(secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60, secs % 60);
+	let mut res = String::from("P");
+	if days > 0 {
+		res.push_str(&format!("{days}D"));
+	}
+	if hours > 0 || mins > 0 || secs > 0 || nanos > 0 || days == 0 {
+		res.push('T');
+		if hours > 0 {
+			res.push_str(&format!("{hours}H"));
+		}
+		if mins > 0 {
+			res.push_str(&format!("{mins}M"));
+		}
+		if nanos > 0 {
+			let fraction = format!("{nanos:09}");
+			res.push_str(&format!("{secs}.{}S", fraction.trim_end_matches('0')));
+		} else if secs > 0 || (days == 0 && hours == 0 && mins == 0) {
+			res.push_str(&format!("{secs}S"));
+		}
+	}
+	res
+}
+
 pub mod from {
 
 	use crate::err::Error;
@@ -65,6 +194,16 @@ pub mod from {
 			.map_err(anyhow::Error::new)
 	}
 
+	pub fn iso8601((val,): (String,)) -> Result<Value> {
+		super::parse_iso8601(&val)
+			.map(|x| Duration::from(x).into())
+			.map_err(|message| Error::InvalidArguments {
+				name: String::from("duration::from::iso8601"),
+				message,
+			})
+			.map_err(anyhow::Error::new)
+	}
+
 	pub fn micros((val,): (i64,)) -> Result<Value> {
 		// TODO: Deal with truncation:
 		let val = val as u64;
@@ -113,3 +252,101 @@ pub mod from {
 			.map_err(anyhow::Error::new)
 	}
 }
+
+pub mod to {
+
+	use crate::expr::duration::Duration;
+	use crate::expr::value::Value;
+	use anyhow::Result;
+
+	pub fn iso8601((val,): (Duration,)) -> Result<Value> {
+		Ok(super::format_iso8601(val.0).into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_iso8601, parse_iso8601};
+	use std::time::Duration;
+
+	#[test]
+	fn iso8601_parse() {
+		#[track_caller]
+		fn test(input: &str, secs: u64, nanos: u32) {
+			assert_eq!(parse_iso8601(input), Ok(Duration::new(secs, nanos)), "{input}");
+		}
+
+		test("PT0S", 0, 0);
+		test("P1D", 86_400, 0);
+		test("P1W", 604_800, 0);
+		test("P1W1D", 691_200, 0);
+		test("PT1H", 3_600, 0);
+		test("PT1M", 60, 0);
+		test("PT1S", 1, 0);
+		test("P1DT2H30M", 95_400, 0);
+		test("PT90M", 5_400, 0);
+		test("PT1.5S", 1, 500_000_000);
+		test("PT0,25S", 0, 250_000_000);
+		test("PT0.000000001S", 0, 1);
+		test("P10DT10H10M10.123456789S", 900_610, 123_456_789);
+	}
+
+	#[test]
+	fn iso8601_parse_errors() {
+		#[track_caller]
+		fn test(input: &str, error: &str) {
+			assert_eq!(parse_iso8601(input), Err(error.to_owned()), "{input}");
+		}
+
+		test("", "Unexpected end of input at position 1");
+		test("1D", "Unexpected character '1' at position 1");
+		test("P", "Unexpected end of input at position 2");
+		test("PT", "Unexpected end of input at position 3");
+		test("P1", "Unexpected end of input at position 3");
+		test("P1H", "Unexpected character 'H' at position 3");
+		test("PT1D", "Unexpected character 'D' at position 4");
+		test("P1DT2H3H", "Unexpected character 'H' at position 8");
+		test("PT1M2H", "Unexpected character 'H' at position 6");
+		test("P1DTT1H", "Unexpected character 'T' at position 5");
+		test("PT1.5M", "Unexpected character '.' at position 4");
+		test("PT1.S", "Unexpected character 'S' at position 5");
+		test("PT1.1234567891S", "Unexpected character '1' at position 14");
+		test("P-1D", "Unexpected character '-' at position 2");
+		test("P1DX", "Unexpected character 'X' at position 4");
+		test(
+			"P1Y",
+			"Unsupported designator 'Y' at position 3, as years and months have no fixed length",
+		);
+		test(
+			"P2M",
+			"Unsupported designator 'M' at position 3, as years and months have no fixed length",
+		);
+		test("P99999999999999999999D", "The duration is too large");
+	}
+
+	#[test]
+	fn iso8601_round_trip() {
+		for input in [
+			"PT0S",
+			"P1D",
+			"PT1H",
+			"PT1M",
+			"PT1S",
+			"P1DT2H30M",
+			"P1DT1S",
+			"PT1H1S",
+			"PT1.5S",
+			"PT0.000000001S",
+			"P365DT23H59M59.999999999S",
+			"P7D",
+		] {
+			let parsed = parse_iso8601(input).unwrap();
+			assert_eq!(format_iso8601(parsed), input);
+			assert_eq!(parse_iso8601(&format_iso8601(parsed)).unwrap(), parsed);
+		}
+		for (secs, nanos) in [(0, 0), (59, 0), (3_600, 1), (86_399, 999_999_999), (u64::MAX, 0)] {
+			let duration = Duration::new(secs, nanos);
+			assert_eq!(parse_iso8601(&format_iso8601(duration)).unwrap(), duration);
+		}
+	}
+}
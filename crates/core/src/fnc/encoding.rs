@@ -70,6 +70,13 @@ pub mod cbor {
 				message: "invalid cbor".to_owned(),
 			})
 			.map(Cbor)?;
+		// Reject tags from newer versions of the protocol
+		if let Some(tag) = cbor.unsupported_tag() {
+			return Err(anyhow::Error::new(Error::InvalidArguments {
+				name: "encoding::cbor::decode".to_owned(),
+				message: format!("unsupported tag {tag}"),
+			}));
+		}
 
 		Value::try_from(cbor)
 			.map_err(|v: &str| Error::InvalidArguments {
@@ -0,0 +1,306 @@
+use crate::cnf::GRAPH_PATH_MAX_VISITED;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::graph::{GraphSubject, GraphSubjects};
+use crate::expr::statements::SelectStatement;
+use crate::expr::{Dir, Edges, Fields, Number, Object, Table, Thing, Value, Values};
+use crate::fnc::args::Optional;
+use crate::fnc::record;
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use std::collections::HashMap;
+
+/// The number of edges a path can contain when no `max_depth` option is given
+const DEFAULT_MAX_DEPTH: u32 = 5;
+
+fn invalid(message: String) -> anyhow::Error {
+	anyhow::Error::new(Error::InvalidArguments {
+		name: String::from("graph::path"),
+		message,
+	})
+}
+
+fn positive(option: &str, value: Value) -> Result<u32> {
+	match value {
+		Value::Number(Number::Int(v)) if v > 0 => Ok(v.try_into().unwrap_or(u32::MAX)),
+		v => Err(invalid(format!("The '{option}' option should be a positive integer, found {v}"))),
+	}
+}
+
+fn edges(value: Value) -> Result<GraphSubjects> {
+	let tables = match value {
+		Value::Array(v) => v.0,
+		v => vec![v],
+	};
+	tables
+		.into_iter()
+		.map(|v| match v {
+			Value::Table(v) => Ok(GraphSubject::Table(v)),
+			Value::Strand(v) => Ok(GraphSubject::Table(Table(v.0))),
+			v => Err(invalid(format!(
+				"The 'edge' option should be a table name or an array of table names, found {v}"
+			))),
+		})
+		.collect::<Result<_>>()
+		.map(GraphSubjects)
+}
+
+/// The options of a path search
+struct PathOptions {
+	edges: GraphSubjects,
+	dir: Dir,
+	max_depth: u32,
+	limit: Option<u32>,
+}
+
+impl TryFrom<Option<Object>> for PathOptions {
+	type Error = anyhow::Error;
+	fn try_from(opts: Option<Object>) -> Result<Self> {
+		let mut res = Self {
+			edges: GraphSubjects(Vec::new()),
+			dir: Dir::Out,
+			max_depth: DEFAULT_MAX_DEPTH,
+			limit: None,
+		};
+		for (k, v) in opts.unwrap_or_default().0.into_iter() {
+			match k.as_str() {
+				"edge" => res.edges = edges(v)?,
+				"dir" => {
+					res.dir = match v {
+						Value::Strand(v) if v.as_str() == "out" => Dir::Out,
+						Value::Strand(v) if v.as_str() == "in" => Dir::In,
+						Value::Strand(v) if v.as_str() == "both" => Dir::Both,
+						v => bail!(invalid(format!(
+							"The 'dir' option should be 'out', 'in' or 'both', found {v}"
+						))),
+					}
+				}
+				"max_depth" => res.max_depth = positive("max_depth", v)?,
+				"limit" => res.limit = Some(positive("limit", v)?),
+				k => bail!(invalid(format!("Unknown path option '{k}'"))),
+			}
+		}
+		Ok(res)
+	}
+}
+
+/// A breadth-first search, from one end of the searched paths
+struct Search {
+	dir: Dir,
+	/// The number of edges between the start of the search and each reached record
+	depth: HashMap<Thing, u32>,
+	/// The edges through which each reached record is first reached, with the records they come from
+	parents: HashMap<Thing, Vec<(Thing, Thing)>>,
+	/// The records reached by the last expansion of the search
+	frontier: Vec<Thing>,
+	level: u32,
+}
+
+impl Search {
+	fn new(start: Thing, dir: Dir) -> Self {
+		Self {
+			dir,
+			depth: HashMap::from([(start.clone(), 0)]),
+			parents: HashMap::new(),
+			frontier: vec![start],
+			level: 0,
+		}
+	}
+
+	/// Follows the edges of the frontier records, and returns false once
+	/// more records have been visited than the budget allows
+	async fn expand(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		edges: &GraphSubjects,
+		visible: &mut HashMap<Thing, bool>,
+	) -> Result<bool> {
+		self.level += 1;
+		let mut frontier = Vec::new();
+		for from in std::mem::take(&mut self.frontier) {
+			for (edge, next) in neighbours(stk, ctx, opt, &from, &self.dir, edges).await? {
+				match self.depth.get(&next) {
+					Some(depth) if *depth == self.level => {
+						if let Some(parents) = self.parents.get_mut(&next) {
+							parents.push((edge, from.clone()));
+						}
+					}
+					Some(_) => (),
+					None => {
+						// Records which can not be selected are not traversable
+						if !visible.contains_key(&next) {
+							if visible.len() >= *GRAPH_PATH_MAX_VISITED {
+								return Ok(false);
+							}
+							let exists =
+								record::exists((stk, ctx, Some(opt), None), (next.clone(),))
+									.await?;
+							visible.insert(next.clone(), exists.is_true());
+						}
+						if visible.get(&next) == Some(&true) {
+							self.depth.insert(next.clone(), self.level);
+							self.parents.insert(next.clone(), vec![(edge, from.clone())]);
+							frontier.push(next);
+						}
+					}
+				}
+			}
+		}
+		self.frontier = frontier;
+		Ok(true)
+	}
+
+	/// Lists the paths from the start of the search to a reached record,
+	/// as alternating records and edges
+	fn paths(&self, to: &Thing, limit: usize) -> Vec<Vec<Thing>> {
+		let Some(parents) = self.parents.get(to) else {
+			return vec![vec![to.clone()]];
+		};
+		let mut res = Vec::new();
+		for (edge, from) in parents {
+			for mut path in self.paths(from, limit - res.len()) {
+				path.push(edge.clone());
+				path.push(to.clone());
+				res.push(path);
+			}
+			if res.len() >= limit {
+				break;
+			}
+		}
+		res
+	}
+}
+
+/// Finds the edges of a record in a direction, along with the records at their other end
+async fn neighbours(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	from: &Thing,
+	dir: &Dir,
+	edges: &GraphSubjects,
+) -> Result<Vec<(Thing, Thing)>> {
+	let stm = SelectStatement {
+		expr: Fields::all(),
+		what: Values(vec![Value::from(Edges::new(dir.clone(), from.clone(), edges.clone()))]),
+		..SelectStatement::default()
+	};
+	let Value::Array(found) = stm.compute(stk, ctx, opt, None).await? else {
+		return Ok(Vec::new());
+	};
+	let mut res = Vec::with_capacity(found.len());
+	for v in found {
+		let Value::Object(v) = v else {
+			continue;
+		};
+		let (Some(Value::Thing(id)), Some(Value::Thing(i)), Some(Value::Thing(o))) =
+			(v.get("id"), v.get("in"), v.get("out"))
+		else {
+			continue;
+		};
+		let next = match dir {
+			Dir::Out => o,
+			Dir::In => i,
+			Dir::Both if i == from => o,
+			Dir::Both => i,
+		};
+		res.push((id.clone(), next.clone()));
+	}
+	Ok(res)
+}
+
+pub async fn path(
+	(stk, ctx, opt): (&mut Stk, &Context, &Options),
+	(from, to, Optional(opts)): (Thing, Thing, Optional<Object>),
+) -> Result<Value> {
+	let opts = PathOptions::try_from(opts)?;
+	let limit = opts.limit.unwrap_or(1) as usize;
+	let found = find_paths(stk, ctx, opt, from, to, &opts, limit).await?;
+	if found.is_empty() {
+		return Ok(Value::None);
+	}
+	let mut found: Vec<Value> = found
+		.into_iter()
+		.map(|p| p.into_iter().map(Value::Thing).collect::<Vec<_>>().into())
+		.collect();
+	// A single path is returned unless a limit is given
+	match opts.limit {
+		Some(_) => Ok(found.into()),
+		None => Ok(found.swap_remove(0)),
+	}
+}
+
+/// Looks for the shortest paths between two records, with
+/// a breadth-first search starting from each of the records
+async fn find_paths(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	from: Thing,
+	to: Thing,
+	opts: &PathOptions,
+	limit: usize,
+) -> Result<Vec<Vec<Thing>>> {
+	// The ends of the paths must be visible
+	let mut visible = HashMap::new();
+	for rid in [&from, &to] {
+		if record::exists((stk, ctx, Some(opt), None), (rid.clone(),)).await?.is_false() {
+			return Ok(Vec::new());
+		}
+		visible.insert(rid.clone(), true);
+	}
+	if from == to {
+		return Ok(vec![vec![from]]);
+	}
+	let back = match opts.dir {
+		Dir::Out => Dir::In,
+		Dir::In => Dir::Out,
+		Dir::Both => Dir::Both,
+	};
+	let mut forward = Search::new(from, opts.dir.clone());
+	let mut backward = Search::new(to, back);
+	while forward.level + backward.level < opts.max_depth {
+		// Expand the search with the fewest records to follow
+		let is_forward = forward.frontier.len() <= backward.frontier.len();
+		let (search, other) = if is_forward {
+			(&mut forward, &backward)
+		} else {
+			(&mut backward, &forward)
+		};
+		if search.frontier.is_empty() {
+			break;
+		}
+		if !search.expand(stk, ctx, opt, &opts.edges, &mut visible).await? {
+			break;
+		}
+		// The shortest paths go through the reached records which are closest to the other end
+		let Some(closest) = search.frontier.iter().filter_map(|rid| other.depth.get(rid)).min()
+		else {
+			continue;
+		};
+		let meets: Vec<Thing> = search
+			.frontier
+			.iter()
+			.filter(|rid| other.depth.get(*rid) == Some(closest))
+			.cloned()
+			.collect();
+		let mut res = Vec::new();
+		for rid in &meets {
+			for head in forward.paths(rid, limit - res.len()) {
+				for tail in backward.paths(rid, limit - res.len()) {
+					let mut path = head.clone();
+					path.extend(tail.into_iter().rev().skip(1));
+					res.push(path);
+					if res.len() >= limit {
+						return Ok(res);
+					}
+				}
+			}
+		}
+		return Ok(res);
+	}
+	Ok(Vec::new())
+}
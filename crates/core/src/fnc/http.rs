@@ -5,19 +5,25 @@ use crate::expr::value::Value;
 use anyhow::Result;
 
 #[cfg(not(feature = "http"))]
-pub async fn head(_: &Context, (_, _): (Value, Optional<Value>)) -> Result<Value> {
+pub async fn head(
+	_: &Context,
+	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
-pub async fn get(_: &Context, (_, _): (Value, Optional<Value>)) -> Result<Value> {
+pub async fn get(
+	_: &Context,
+	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
 pub async fn put(
 	_: &Context,
-	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+	(_, _, _, _): (Value, Optional<Value>, Optional<Value>, Optional<Value>),
 ) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
@@ -25,7 +31,7 @@ pub async fn put(
 #[cfg(not(feature = "http"))]
 pub async fn post(
 	_: &Context,
-	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+	(_, _, _, _): (Value, Optional<Value>, Optional<Value>, Optional<Value>),
 ) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
@@ -33,13 +39,16 @@ pub async fn post(
 #[cfg(not(feature = "http"))]
 pub async fn patch(
 	_: &Context,
-	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+	(_, _, _, _): (Value, Optional<Value>, Optional<Value>, Optional<Value>),
 ) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
-pub async fn delete(_: &Context, (_, _): (Value, Optional<Value>)) -> Result<Value> {
+pub async fn delete(
+	_: &Context,
+	(_, _, _): (Value, Optional<Value>, Optional<Value>),
+) -> Result<Value> {
 	anyhow::bail!(Error::HttpDisabled)
 }
 
@@ -73,57 +82,105 @@ fn try_as_opts(
 }
 
 #[cfg(feature = "http")]
-pub async fn head(ctx: &Context, (uri, Optional(opts)): (Value, Optional<Value>)) -> Result<Value> {
+fn try_as_config(
+	ctx: &Context,
+	fn_name: &str,
+	error_message: &str,
+	value: Option<Value>,
+) -> Result<crate::fnc::util::http::RequestConfig> {
+	use crate::fnc::util::http::RequestConfig;
+	let caps = ctx.get_capabilities();
+	match try_as_opts(fn_name, error_message, value)? {
+		Some(opts) => RequestConfig::from_object(fn_name, opts, &caps),
+		None => Ok(RequestConfig::default().capped(&caps)),
+	}
+}
+
+#[cfg(feature = "http")]
+pub async fn head(
+	ctx: &Context,
+	(uri, Optional(opts), Optional(config)): (Value, Optional<Value>, Optional<Value>),
+) -> Result<Value> {
 	let uri = try_as_uri("http::head", uri)?;
 	let opts = try_as_opts("http::head", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::head(ctx, uri, opts).await
+	let config =
+		try_as_config(ctx, "http::head", "The third argument should be an object.", config)?;
+	crate::fnc::util::http::head(ctx, uri, opts, config).await
 }
 
 #[cfg(feature = "http")]
-pub async fn get(ctx: &Context, (uri, Optional(opts)): (Value, Optional<Value>)) -> Result<Value> {
+pub async fn get(
+	ctx: &Context,
+	(uri, Optional(opts), Optional(config)): (Value, Optional<Value>, Optional<Value>),
+) -> Result<Value> {
 	let uri = try_as_uri("http::get", uri)?;
 	let opts = try_as_opts("http::get", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::get(ctx, uri, opts).await
+	let config =
+		try_as_config(ctx, "http::get", "The third argument should be an object.", config)?;
+	crate::fnc::util::http::get(ctx, uri, opts, config).await
 }
 
 #[cfg(feature = "http")]
 pub async fn put(
 	ctx: &Context,
-	(uri, Optional(body), Optional(opts)): (Value, Optional<Value>, Optional<Value>),
+	(uri, Optional(body), Optional(opts), Optional(config)): (
+		Value,
+		Optional<Value>,
+		Optional<Value>,
+		Optional<Value>,
+	),
 ) -> Result<Value> {
 	let uri = try_as_uri("http::put", uri)?;
 	let opts = try_as_opts("http::put", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::put(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let config =
+		try_as_config(ctx, "http::put", "The fourth argument should be an object.", config)?;
+	crate::fnc::util::http::put(ctx, uri, body.unwrap_or(Value::Null), opts, config).await
 }
 
 #[cfg(feature = "http")]
 pub async fn post(
 	ctx: &Context,
-	(uri, Optional(body), Optional(opts)): (Value, Optional<Value>, Optional<Value>),
+	(uri, Optional(body), Optional(opts), Optional(config)): (
+		Value,
+		Optional<Value>,
+		Optional<Value>,
+		Optional<Value>,
+	),
 ) -> Result<Value> {
 	let uri = try_as_uri("http::post", uri)?;
 	let opts = try_as_opts("http::post", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::post(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let config =
+		try_as_config(ctx, "http::post", "The fourth argument should be an object.", config)?;
+	crate::fnc::util::http::post(ctx, uri, body.unwrap_or(Value::Null), opts, config).await
 }
 
 #[cfg(feature = "http")]
 pub async fn patch(
 	ctx: &Context,
-	(uri, Optional(body), Optional(opts)): (Value, Optional<Value>, Optional<Value>),
+	(uri, Optional(body), Optional(opts), Optional(config)): (
+		Value,
+		Optional<Value>,
+		Optional<Value>,
+		Optional<Value>,
+	),
 ) -> Result<Value> {
 	let uri = try_as_uri("http::patch", uri)?;
 	let opts = try_as_opts("http::patch", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::patch(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let config =
+		try_as_config(ctx, "http::patch", "The fourth argument should be an object.", config)?;
+	crate::fnc::util::http::patch(ctx, uri, body.unwrap_or(Value::Null), opts, config).await
 }
 
 #[cfg(feature = "http")]
 pub async fn delete(
 	ctx: &Context,
-	(uri, Optional(opts)): (Value, Optional<Value>),
+	(uri, Optional(opts), Optional(config)): (Value, Optional<Value>, Optional<Value>),
 ) -> Result<Value> {
 	let uri = try_as_uri("http::delete", uri)?;
 	let opts = try_as_opts("http::delete", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::delete(ctx, uri, opts).await
+	let config =
+		try_as_config(ctx, "http::delete", "The third argument should be an object.", config)?;
+	crate::fnc::util::http::delete(ctx, uri, opts, config).await
 }
 
 #[cfg(all(not(target_family = "wasm"), feature = "http"))]
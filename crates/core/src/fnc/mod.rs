@@ -3,6 +3,7 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::capabilities::ExperimentalTarget;
+use crate::dbs::has_side_effects;
 use crate::doc::CursorDoc;
 use crate::expr::Thing;
 use crate::expr::value::Value;
@@ -19,13 +20,16 @@ pub mod duration;
 pub mod encoding;
 pub mod file;
 pub mod geo;
+pub mod graph;
 pub mod http;
 pub mod math;
 pub mod not;
 pub mod object;
 pub mod operate;
+pub mod outbox;
 pub mod parse;
 pub mod rand;
+pub mod range;
 pub mod record;
 pub mod script;
 pub mod search;
@@ -38,6 +42,8 @@ pub mod r#type;
 pub mod util;
 pub mod value;
 pub mod vector;
+pub mod version;
+pub mod window;
 
 /// Attempts to run any function
 pub async fn run(
@@ -48,7 +54,12 @@ pub async fn run(
 	name: &str,
 	args: Vec<Value>,
 ) -> Result<Value> {
-	if name.eq("sleep")
+	// Functions with effects outside of the transaction are not run in a dry run
+	if let Some(skipped) = ctx.get_skipped_functions().filter(|_| has_side_effects(name)) {
+		skipped.add(name);
+		return Ok(Value::None);
+	}
+	let res = if name.eq("sleep")
 		|| name.eq("api::invoke")
 		|| name.eq("array::all")
 		|| name.eq("array::any")
@@ -74,12 +85,17 @@ pub async fn run(
 		|| name.eq("file::rename")
 		|| name.eq("file::rename_if_not_exists")
 		|| name.eq("file::list")
+		|| name.eq("graph::path")
+		|| name.eq("outbox::enqueue")
 		|| name.eq("record::exists")
 		|| name.eq("record::refs")
+		|| name.eq("record::restore")
 		|| name.eq("type::field")
 		|| name.eq("type::fields")
+		|| name.eq("type::field::variants")
 		|| name.eq("value::diff")
 		|| name.eq("value::patch")
+		|| name.eq("version")
 		|| name.eq("sequence::nextval")
 		|| name.starts_with("http")
 		|| name.starts_with("search")
@@ -88,10 +104,15 @@ pub async fn run(
 		|| name.starts_with("crypto::pbkdf2")
 		|| name.starts_with("crypto::scrypt")
 	{
-		stk.run(|stk| asynchronous(stk, ctx, opt, doc, name, args)).await
+		stk.run(|stk| asynchronous(stk, ctx, opt, doc, name, args)).await?
 	} else {
-		synchronous(ctx, doc, name, args)
+		synchronous(ctx, doc, name, args)?
+	};
+	// Check the arrays accumulated by the array functions against the memory limit
+	if let Some(memory) = ctx.get_query_memory().filter(|_| name.starts_with("array::")) {
+		memory.check(&res)?;
 	}
+	Ok(res)
 }
 
 /// Each function is specified by its name (a string literal) followed by its path. The path
@@ -205,6 +226,8 @@ pub fn synchronous(
 		"crypto::sha256" => crypto::sha256,
 		"crypto::sha512" => crypto::sha512,
 		//
+		"dense_rank" => window::dense_rank,
+		//
 		"duration::days" => duration::days,
 		"duration::hours" => duration::hours,
 		"duration::micros" => duration::micros,
@@ -216,12 +239,14 @@ pub fn synchronous(
 		"duration::years" => duration::years,
 		"duration::from::days" => duration::from::days,
 		"duration::from::hours" => duration::from::hours,
+		"duration::from::iso8601" => duration::from::iso8601,
 		"duration::from::micros" => duration::from::micros,
 		"duration::from::millis" => duration::from::millis,
 		"duration::from::mins" => duration::from::mins,
 		"duration::from::nanos" => duration::from::nanos,
 		"duration::from::secs" => duration::from::secs,
 		"duration::from::weeks" => duration::from::weeks,
+		"duration::to::iso8601" => duration::to::iso8601,
 		//
 		exp(Files) "file::bucket" => file::bucket,
 		exp(Files) "file::key" => file::key,
@@ -239,6 +264,9 @@ pub fn synchronous(
 		"geo::hash::encode" => geo::hash::encode,
 		"geo::is::valid" => geo::is::valid,
 		//
+		"lag" => window::lag,
+		"lead" => window::lead,
+		//
 		"math::abs" => math::abs,
 		"math::acos" => math::acos,
 		"math::acot" => math::acot,
@@ -301,6 +329,7 @@ pub fn synchronous(
 		"parse::url::domain" => parse::url::domain,
 		"parse::url::fragment" => parse::url::fragment,
 		"parse::url::host" => parse::url::host,
+		"parse::url::parse" => parse::url::parse,
 		"parse::url::path" => parse::url::path,
 		"parse::url::port" => parse::url::port,
 		"parse::url::query" => parse::url::query,
@@ -320,18 +349,31 @@ pub fn synchronous(
 		"rand::uuid::v7" => rand::uuid::v7,
 		"rand::uuid" => rand::uuid,
 		//
+		"range::contains" => range::contains,
+		"range::end" => range::end,
+		"range::end_inclusive" => range::end_inclusive,
+		"range::start" => range::start,
+		"range::start_inclusive" => range::start_inclusive,
+		//
+		"rank" => window::rank,
+		//
 		"record::id" => record::id,
 		"record::table" => record::tb,
 		"record::tb" => record::tb,
 		//
+		"row_number" => window::row_number,
+		//
 		"session::ac" => session::ac(ctx),
 		"session::db" => session::db(ctx),
+		"session::endpoint" => session::endpoint(ctx),
 		"session::id" => session::id(ctx),
 		"session::ip" => session::ip(ctx),
 		"session::ns" => session::ns(ctx),
 		"session::origin" => session::origin(ctx),
 		"session::rd" => session::rd(ctx),
+		"session::timeout" => session::timeout(ctx),
 		"session::token" => session::token(ctx),
+		"session::variables" => session::variables(ctx),
 		//
 		"string::concat" => string::concat,
 		"string::contains" => string::contains,
@@ -564,6 +606,8 @@ pub async fn asynchronous(
 		exp(Files) "file::exists" => file::exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::list" => file::list((stk, ctx, opt, doc)).await,
 		//
+		"graph::path" => graph::path((stk, ctx, opt)).await,
+		//
 		"http::head" => http::head(ctx).await,
 		"http::get" => http::get(ctx).await,
 		"http::put" => http::put(ctx).await,
@@ -571,23 +615,32 @@ pub async fn asynchronous(
 		"http::patch" => http::patch(ctx).await,
 		"http::delete" => http::delete(ctx).await,
 		//
+		"outbox::enqueue" => outbox::enqueue((ctx, opt)).await,
+		//
 		"record::exists" => record::exists((stk, ctx, Some(opt), doc)).await,
 		"record::refs" => record::refs((stk, ctx, opt, doc)).await,
+		"record::restore" => record::restore((stk, ctx, opt)).await,
 		//
 		"search::analyze" => search::analyze((stk, ctx, Some(opt))).await,
 		"search::score" => search::score((ctx, doc)).await,
+		"search::explain" => search::explain((ctx, doc)).await,
 		"search::highlight" => search::highlight((ctx, doc)).await,
 		"search::offsets" => search::offsets((ctx, doc)).await,
+		"search::stats" => search::stats((ctx, Some(opt))).await,
+		"search::terms" => search::terms((ctx, Some(opt))).await,
 		//
-		"sleep" => sleep::sleep(ctx).await,
+		"sleep" => sleep::sleep((ctx, opt)).await,
 		//
 		"sequence::nextval" => sequence::nextval((ctx, opt)).await,
 		//
 		"type::field" => r#type::field((stk, ctx, Some(opt), doc)).await,
 		"type::fields" => r#type::fields((stk, ctx, Some(opt), doc)).await,
+		"type::field::variants" => r#type::field::variants((ctx, opt)).await,
 		//
 		"value::diff" => value::diff((stk, ctx, Some(opt), doc)).await,
 		"value::patch" => value::patch((stk, ctx, Some(opt), doc)).await,
+		//
+		"version" => version::version((ctx, opt, doc)).await,
 	)
 }
 
@@ -601,7 +654,7 @@ pub async fn idiom(
 	name: &str,
 	mut args: Vec<Value>,
 ) -> Result<Value> {
-	ctx.check_allowed_function(&idiom_name_to_normal(value.kindof(), name))?;
+	ctx.check_allowed_function(opt, &idiom_name_to_normal(value.kindof(), name)).await?;
 	match value {
 		Value::Array(x) => {
 			args.insert(0, Value::Array(x));
@@ -0,0 +1,49 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::expr::value::Value;
+use anyhow::Result;
+
+#[cfg(not(feature = "http"))]
+pub async fn enqueue(_: (&Context, &Options), _: (Value,)) -> Result<Value> {
+	anyhow::bail!(crate::err::Error::HttpDisabled)
+}
+
+/// Stores a message in the outbox of the database, which is
+/// sent in the background once the transaction is committed
+#[cfg(feature = "http")]
+pub async fn enqueue((ctx, opt): (&Context, &Options), (msg,): (Value,)) -> Result<Value> {
+	use crate::dbs::outbox::{self, OutboxMessage};
+	use crate::err::Error;
+	use crate::expr::Object;
+	use url::Url;
+
+	let invalid = |message: &str| Error::InvalidArguments {
+		name: "outbox::enqueue".to_owned(),
+		message: message.to_owned(),
+	};
+	let Value::Object(mut msg) = msg else {
+		anyhow::bail!(invalid("The argument should be an object."));
+	};
+	// Check if the url is valid and allowed
+	let url = match msg.remove("url") {
+		Some(Value::Strand(v)) => v.0,
+		_ => anyhow::bail!(invalid("The 'url' property should be a string.")),
+	};
+	let parsed = Url::parse(&url).map_err(|_| Error::InvalidUrl(url.clone()))?;
+	ctx.check_allowed_net(&parsed).await?;
+	// The payload is sent as the body of the request
+	let payload = msg.remove("payload").unwrap_or_default();
+	let headers = match msg.remove("headers") {
+		Some(Value::Object(v)) => v,
+		None | Some(Value::None) => Object::default(),
+		_ => anyhow::bail!(invalid("The 'headers' property should be an object.")),
+	};
+	if let Some(k) = msg.keys().next() {
+		anyhow::bail!(invalid(&format!("Unknown message property '{k}'")));
+	}
+	// Store the message in the current transaction
+	let (ns, db) = opt.ns_db()?;
+	let message = OutboxMessage::new(url, payload, headers);
+	let id = outbox::enqueue(&ctx.tx(), ns, db, message).await?;
+	Ok(Value::from(id))
+}
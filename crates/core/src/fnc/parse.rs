@@ -46,9 +46,30 @@ pub mod email {
 pub mod url {
 
 	use crate::expr::value::Value;
+	use crate::expr::{Array, Object};
 	use anyhow::Result;
+	use std::collections::BTreeMap;
 	use url::Url;
 
+	/// Decode a query string into an object, collecting repeated keys into an array
+	fn decode_query(url: &Url) -> Value {
+		let mut res = BTreeMap::<String, Value>::new();
+		for (k, v) in url.query_pairs() {
+			let v = Value::from(v.into_owned());
+			match res.get_mut(k.as_ref()) {
+				Some(Value::Array(a)) => a.push(v),
+				Some(existing) => {
+					let first = std::mem::take(existing);
+					*existing = Value::Array(Array(vec![first, v]));
+				}
+				None => {
+					res.insert(k.into_owned(), v);
+				}
+			}
+		}
+		Value::Object(Object(res))
+	}
+
 	pub fn domain((string,): (String,)) -> Result<Value> {
 		match Url::parse(&string) {
 			Ok(v) => match v.domain() {
@@ -100,11 +121,35 @@ pub mod url {
 		}
 	}
 
+	pub fn parse((string,): (String,)) -> Result<Value> {
+		// Parse the URL
+		let Ok(v) = Url::parse(&string) else {
+			return Ok(Value::None);
+		};
+		let mut res = BTreeMap::<String, Value>::new();
+		res.insert("scheme".to_owned(), v.scheme().into());
+		res.insert("host".to_owned(), v.host_str().map(Value::from).unwrap_or_default());
+		res.insert(
+			"port".to_owned(),
+			v.port_or_known_default().map(Value::from).unwrap_or_default(),
+		);
+		res.insert("path".to_owned(), v.path().into());
+		res.insert(
+			"query".to_owned(),
+			match v.query() {
+				Some(_) => decode_query(&v),
+				None => Value::None,
+			},
+		);
+		res.insert("fragment".to_owned(), v.fragment().map(Value::from).unwrap_or_default());
+		Ok(Value::Object(Object(res)))
+	}
+
 	pub fn query((string,): (String,)) -> Result<Value> {
 		// Parse the URL
 		match Url::parse(&string) {
 			Ok(v) => match v.query() {
-				Some(v) => Ok(v.into()),
+				Some(_) => Ok(decode_query(&v)),
 				None => Ok(Value::None),
 			},
 			Err(_) => Ok(Value::None),
@@ -122,6 +167,7 @@ pub mod url {
 	#[cfg(test)]
 	mod tests {
 		use crate::expr::value::Value;
+		use crate::syn;
 
 		#[test]
 		fn port_default_port_specified() {
@@ -146,5 +192,54 @@ pub mod url {
 			let value = super::port(("www.google.com".to_string(),)).unwrap();
 			assert_eq!(value, Value::None);
 		}
+
+		#[test]
+		fn query_decodes_percent_and_plus() {
+			let value =
+				super::query(("http://www.google.com/?q=caf%C3%A9+au+lait&a=%2B".to_string(),))
+					.unwrap();
+			assert_eq!(value, syn::value("{ a: '+', q: 'café au lait' }").unwrap().into());
+		}
+
+		#[test]
+		fn query_repeated_keys() {
+			let value =
+				super::query(("http://www.google.com/?a=1&b=2&a=3&a=4".to_string(),)).unwrap();
+			assert_eq!(value, syn::value("{ a: ['1', '3', '4'], b: '2' }").unwrap().into());
+		}
+
+		#[test]
+		fn query_invalid_url() {
+			let value = super::query(("not a url".to_string(),)).unwrap();
+			assert_eq!(value, Value::None);
+		}
+
+		#[test]
+		fn parse_idn_host() {
+			let value =
+				super::parse(("https://münchen.de:8443/straße?x=%E2%9C%93#top".to_string(),))
+					.unwrap();
+			let expected = syn::value(
+				"{ fragment: 'top', host: 'xn--mnchen-3ya.de', path: '/stra%C3%9Fe', port: 8443, query: { x: '✓' }, scheme: 'https' }",
+			)
+			.unwrap();
+			assert_eq!(value, expected.into());
+		}
+
+		#[test]
+		fn parse_missing_components() {
+			let value = super::parse(("mailto:tobie@surrealdb.com".to_string(),)).unwrap();
+			let expected = syn::value(
+				"{ fragment: NONE, host: NONE, path: 'tobie@surrealdb.com', port: NONE, query: NONE, scheme: 'mailto' }",
+			)
+			.unwrap();
+			assert_eq!(value, expected.into());
+		}
+
+		#[test]
+		fn parse_invalid_url() {
+			let value = super::parse(("://www.google.com".to_string(),)).unwrap();
+			assert_eq!(value, Value::None);
+		}
 	}
 }
@@ -0,0 +1,73 @@
+use crate::expr::Range;
+use crate::expr::value::Value;
+use anyhow::Result;
+use std::ops::Bound;
+
+/// Returns the lower bound of a range, or NONE if it is unbounded.
+pub fn start((range,): (Box<Range>,)) -> Result<Value> {
+	Ok(match range.beg {
+		Bound::Included(v) | Bound::Excluded(v) => v,
+		Bound::Unbounded => Value::None,
+	})
+}
+
+/// Returns the upper bound of a range, or NONE if it is unbounded.
+pub fn end((range,): (Box<Range>,)) -> Result<Value> {
+	Ok(match range.end {
+		Bound::Included(v) | Bound::Excluded(v) => v,
+		Bound::Unbounded => Value::None,
+	})
+}
+
+/// Returns whether the lower bound of a range is included in the range.
+pub fn start_inclusive((range,): (Box<Range>,)) -> Result<Value> {
+	Ok(matches!(range.beg, Bound::Included(_)).into())
+}
+
+/// Returns whether the upper bound of a range is included in the range.
+pub fn end_inclusive((range,): (Box<Range>,)) -> Result<Value> {
+	Ok(matches!(range.end, Bound::Included(_)).into())
+}
+
+/// Returns whether a value falls within a range.
+pub fn contains((range, value): (Box<Range>, Value)) -> Result<Value> {
+	Ok(range.contains(&value).into())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::expr::Range;
+	use crate::expr::value::Value;
+	use std::ops::Bound;
+
+	fn range(beg: Bound<i64>, end: Bound<i64>) -> Box<Range> {
+		Box::new(Range::new(beg.map(Value::from), end.map(Value::from)))
+	}
+
+	#[test]
+	fn start_end() {
+		let r = range(Bound::Included(1), Bound::Excluded(5));
+		assert_eq!(super::start((r.clone(),)).unwrap(), Value::from(1));
+		assert_eq!(super::end((r,)).unwrap(), Value::from(5));
+
+		let r = range(Bound::Unbounded, Bound::Unbounded);
+		assert_eq!(super::start((r.clone(),)).unwrap(), Value::None);
+		assert_eq!(super::end((r,)).unwrap(), Value::None);
+	}
+
+	#[test]
+	fn contains() {
+		let r = range(Bound::Included(1), Bound::Excluded(5));
+		assert_eq!(super::contains((r.clone(), 1.into())).unwrap(), Value::Bool(true));
+		assert_eq!(super::contains((r.clone(), 4.into())).unwrap(), Value::Bool(true));
+		assert_eq!(super::contains((r.clone(), 5.into())).unwrap(), Value::Bool(false));
+		assert_eq!(super::contains((r, 0.into())).unwrap(), Value::Bool(false));
+
+		let r = range(Bound::Excluded(1), Bound::Included(5));
+		assert_eq!(super::contains((r.clone(), 1.into())).unwrap(), Value::Bool(false));
+		assert_eq!(super::contains((r, 5.into())).unwrap(), Value::Bool(true));
+
+		let r = range(Bound::Unbounded, Bound::Included(5));
+		assert_eq!(super::contains((r, i64::MIN.into())).unwrap(), Value::Bool(true));
+	}
+}
@@ -1,7 +1,7 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::capabilities::ExperimentalTarget;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::expr::paths::ID;
 use crate::expr::thing::Thing;
@@ -70,6 +70,13 @@ pub async fn refs(
 	Ok(Value::Array(val))
 }
 
+pub async fn restore(
+	(stk, ctx, opt): (&mut Stk, &Context, &Options),
+	(id,): (Thing,),
+) -> Result<Value> {
+	Document::restore(stk, ctx, opt, id).await
+}
+
 async fn correct_refs_field(ctx: &Context, opt: &Options, ft: &Table, ff: Idiom) -> Result<Idiom> {
 	// Obtain the field definition
 	let (ns, db) = opt.ns_db()?;
@@ -2,6 +2,7 @@ use super::run;
 use crate::fnc::script::modules::impl_module_def;
 
 mod from;
+mod to;
 
 #[non_exhaustive]
 pub struct Package;
@@ -18,5 +19,6 @@ impl_module_def!(
 	"secs" => run,
 	"weeks" => run,
 	"years" => run,
-	"from" => (from::Package)
+	"from" => (from::Package),
+	"to" => (to::Package)
 );
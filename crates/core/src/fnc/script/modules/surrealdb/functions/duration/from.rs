@@ -9,6 +9,7 @@ impl_module_def!(
 	"duration::from",
 	"days" => run,
 	"hours" => run,
+	"iso8601" => run,
 	"micros" => run,
 	"millis" => run,
 	"mins" => run,
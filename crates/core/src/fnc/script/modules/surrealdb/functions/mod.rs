@@ -15,12 +15,15 @@ mod duration;
 mod encoding;
 mod file;
 mod geo;
+mod graph;
 mod http;
 mod math;
 mod meta;
 mod object;
+mod outbox;
 mod parse;
 mod rand;
+mod range;
 mod record;
 mod search;
 mod sequence;
@@ -42,18 +45,26 @@ impl_module_def!(
 	"bytes" => (bytes::Package),
 	"count" => run,
 	"crypto" => (crypto::Package),
+	"dense_rank" => run,
 	"duration" => (duration::Package),
 	"encoding" => (encoding::Package),
 	"file" => (file::Package),
 	"geo" => (geo::Package),
+	"graph" => (graph::Package),
 	"http" => (http::Package),
+	"lag" => run,
+	"lead" => run,
 	"math" => (math::Package),
 	"meta" => (meta::Package),
 	"not" => run,
 	"object" => (object::Package),
+	"outbox" => (outbox::Package),
 	"parse" => (parse::Package),
 	"rand" => (rand::Package),
+	"range" => (range::Package),
+	"rank" => run,
 	"record" => (record::Package),
+	"row_number" => run,
 	"search" => (search::Package),
 	"sequence" => (sequence::Package),
 	"session" => (session::Package),
@@ -62,7 +73,8 @@ impl_module_def!(
 	"time" => (time::Package),
 	"type" => (r#type::Package),
 	"value" => (value::Package),
-	"vector" => (vector::Package)
+	"vector" => (vector::Package),
+	"version" => fut Async
 );
 
 fn run(js_ctx: js::Ctx<'_>, name: &str, args: Vec<Value>) -> Result<Value> {
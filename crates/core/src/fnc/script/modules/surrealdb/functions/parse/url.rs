@@ -10,6 +10,7 @@ impl_module_def!(
 	"domain" => run,
 	"fragment" => run,
 	"host" => run,
+	"parse" => run,
 	"path" => run,
 	"port" => run,
 	"query" => run,
@@ -0,0 +1,15 @@
+use super::run;
+use crate::fnc::script::modules::impl_module_def;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"range",
+	"contains" => run,
+	"end" => run,
+	"end_inclusive" => run,
+	"start" => run,
+	"start_inclusive" => run
+);
@@ -13,5 +13,6 @@ impl_module_def!(
 	"id" => run,
 	"table" => run,
 	"tb" => run,
-	"refs" => fut Async
+	"refs" => fut Async,
+	"restore" => fut Async
 );
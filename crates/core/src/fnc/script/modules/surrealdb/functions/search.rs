@@ -9,7 +9,10 @@ impl_module_def!(
 	Package,
 	"search",
 	"analyze" => fut Async,
+	"explain" => fut Async,
 	"highlight" => fut Async,
 	"offsets" => fut Async,
-	"score" => fut Async
+	"score" => fut Async,
+	"stats" => fut Async,
+	"terms" => fut Async
 );
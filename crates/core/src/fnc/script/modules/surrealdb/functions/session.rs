@@ -8,11 +8,14 @@ impl_module_def!(
 	Package,
 	"session",
 	"db" => run,
+	"endpoint" => run,
 	"id" => run,
 	"ip" => run,
 	"ns" => run,
 	"origin" => run,
 	"ac" => run,
 	"rd" => run,
-	"token" => run
+	"timeout" => run,
+	"token" => run,
+	"variables" => run
 );
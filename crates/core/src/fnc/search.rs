@@ -1,12 +1,21 @@
+use crate::cnf::FULLTEXT_MAX_LISTED_TERMS;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
-use crate::expr::Value;
+use crate::err::Error;
+use crate::expr::index::Index;
+use crate::expr::statements::DefineIndexStatement;
+use crate::expr::{Permission, Value};
 use crate::fnc::get_execution_context;
+use crate::iam::Action;
+use crate::idx::IndexKeyBase;
+use crate::idx::ft::FtIndex;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::highlighter::HighlightParams;
-use anyhow::Result;
+use crate::kvs::TransactionType;
+use anyhow::{Result, bail};
 use reblessive::tree::Stk;
+use std::sync::Arc;
 
 use super::args::Optional;
 
@@ -34,18 +43,41 @@ pub async fn score(
 	Ok(Value::None)
 }
 
-pub async fn highlight(
+pub async fn explain(
 	(ctx, doc): (&Context, Option<&CursorDoc>),
-	(prefix, suffix, match_ref, Optional(partial)): (Value, Value, Value, Optional<bool>),
+	(match_ref,): (Value,),
 ) -> Result<Value> {
 	if let Some((exe, doc, thg)) = get_execution_context(ctx, doc) {
-		let hlp = HighlightParams {
-			prefix,
-			suffix,
-			match_ref,
-			partial: partial.unwrap_or(false),
-		};
+		return exe.explain(ctx, &match_ref, thg, doc.ir.as_ref()).await;
+	}
+	Ok(Value::None)
+}
 
+pub async fn highlight(
+	(ctx, doc): (&Context, Option<&CursorDoc>),
+	(arg1, arg2, Optional(arg3), Optional(partial)): (
+		Value,
+		Value,
+		Optional<Value>,
+		Optional<bool>,
+	),
+) -> Result<Value> {
+	let hlp = match (arg1, arg2, arg3) {
+		// search::highlight(prefix, suffix, match_ref, partial)
+		(prefix, suffix, Some(match_ref)) => {
+			HighlightParams::new(prefix, suffix, match_ref, partial.unwrap_or(false))
+		}
+		// search::highlight(match_ref, options)
+		(match_ref, Value::Object(opts), None) => HighlightParams::from_options(match_ref, opts)?,
+		(_, v, None) => bail!(Error::InvalidArguments {
+			name: String::from("search::highlight"),
+			message: format!(
+				"Expected an object of highlight options as the second argument, found `{}`",
+				v.kindof()
+			),
+		}),
+	};
+	if let Some((exe, doc, thg)) = get_execution_context(ctx, doc) {
 		return exe.highlight(ctx, thg, hlp, doc.doc.as_ref()).await;
 	}
 	Ok(Value::None)
@@ -61,3 +93,83 @@ pub async fn offsets(
 	}
 	Ok(Value::None)
 }
+
+pub async fn stats(
+	(ctx, opt): (&Context, Option<&Options>),
+	(index, term): (String, String),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let ft = read_ft_index(ctx, opt, "search::stats", &index).await?;
+	Ok(ft.term_statistics(ctx, &term).await?.into())
+}
+
+pub async fn terms(
+	(ctx, opt): (&Context, Option<&Options>),
+	(index, prefix, limit): (String, String, i64),
+) -> Result<Value> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	let max = *FULLTEXT_MAX_LISTED_TERMS;
+	let limit = match usize::try_from(limit) {
+		Ok(limit) if (1..=max).contains(&limit) => limit,
+		_ => bail!(Error::InvalidArguments {
+			name: String::from("search::terms"),
+			message: format!("The limit must be between 1 and {max}, found {limit}"),
+		}),
+	};
+	let ft = read_ft_index(ctx, opt, "search::terms", &index).await?;
+	Ok(ft.terms_with_prefix(ctx, &prefix, limit).await?.into())
+}
+
+/// Opens a full-text index for reading, given its name, which can be written
+/// `table.index` when an index with the same name is defined on several tables.
+/// The index can only be read by the users allowed to select any record of its table.
+async fn read_ft_index(ctx: &Context, opt: &Options, name: &str, index: &str) -> Result<FtIndex> {
+	let (ns, db) = opt.ns_db()?;
+	let tx = ctx.tx();
+	let ix: Arc<DefineIndexStatement> = if let Some((tb, ix)) = index.split_once('.') {
+		tx.get_tb_index(ns, db, tb, ix).await?
+	} else {
+		let mut found = Vec::new();
+		for tb in tx.all_tb(ns, db, None).await?.iter() {
+			if let Some(ix) =
+				tx.all_tb_indexes(ns, db, &tb.name).await?.iter().find(|ix| ix.name.0 == index)
+			{
+				found.push(ix.clone());
+			}
+		}
+		match found.len() {
+			0 => bail!(Error::IxNotFound {
+				name: index.to_owned(),
+			}),
+			1 => Arc::new(found.swap_remove(0)),
+			_ => bail!(Error::InvalidArguments {
+				name: name.to_owned(),
+				message: format!(
+					"The index '{index}' is defined on several tables, it can be written `table.{index}`"
+				),
+			}),
+		}
+	};
+	let Index::Search(p) = &ix.index else {
+		bail!(Error::InvalidArguments {
+			name: name.to_owned(),
+			message: format!("The index '{}' is not a full-text index", ix.name),
+		});
+	};
+	// Should we run permissions checks?
+	if opt.check_perms(Action::View)? {
+		let tb = tx.get_tb(ns, db, &ix.what).await?;
+		if !matches!(tb.permissions.select, Permission::Full) {
+			bail!(Error::TablePermissions {
+				table: ix.what.to_string(),
+			});
+		}
+	}
+	drop(tx);
+	let ikb = IndexKeyBase::new(ns, db, &ix)?;
+	FtIndex::new(ctx, opt, p.az.as_str(), ikb, p, TransactionType::Read).await
+}
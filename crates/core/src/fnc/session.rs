@@ -1,14 +1,19 @@
 use crate::ctx::Context;
 use crate::expr::paths::AC;
 use crate::expr::paths::DB;
+use crate::expr::paths::EP;
+use crate::expr::paths::EXP;
 use crate::expr::paths::ID;
 use crate::expr::paths::IP;
 use crate::expr::paths::NS;
 use crate::expr::paths::OR;
+use crate::expr::paths::PV;
 use crate::expr::paths::RD;
 use crate::expr::paths::TK;
 use crate::expr::value::Value;
+use crate::expr::{Duration, Number};
 use anyhow::Result;
+use chrono::Utc;
 
 pub fn ac(ctx: &Context, _: ()) -> Result<Value> {
 	ctx.value("session").unwrap_or(&Value::None).pick(AC.as_ref()).ok()
@@ -18,6 +23,10 @@ pub fn db(ctx: &Context, _: ()) -> Result<Value> {
 	ctx.value("session").unwrap_or(&Value::None).pick(DB.as_ref()).ok()
 }
 
+pub fn endpoint(ctx: &Context, _: ()) -> Result<Value> {
+	ctx.value("session").unwrap_or(&Value::None).pick(EP.as_ref()).ok()
+}
+
 pub fn id(ctx: &Context, _: ()) -> Result<Value> {
 	ctx.value("session").unwrap_or(&Value::None).pick(ID.as_ref()).ok()
 }
@@ -38,6 +47,21 @@ pub fn rd(ctx: &Context, _: ()) -> Result<Value> {
 	ctx.value("session").unwrap_or(&Value::None).pick(RD.as_ref()).ok()
 }
 
+pub fn timeout(ctx: &Context, _: ()) -> Result<Value> {
+	match ctx.value("session").unwrap_or(&Value::None).pick(EXP.as_ref()) {
+		// The session expiry is stored as a unix timestamp in seconds
+		Value::Number(Number::Int(exp)) => {
+			let secs = exp.saturating_sub(Utc::now().timestamp()).max(0) as u64;
+			Ok(Duration::from_secs(secs).into())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
 pub fn token(ctx: &Context, _: ()) -> Result<Value> {
 	ctx.value("session").unwrap_or(&Value::None).pick(TK.as_ref()).ok()
 }
+
+pub fn variables(ctx: &Context, _: ()) -> Result<Value> {
+	ctx.value("session").unwrap_or(&Value::None).pick(PV.as_ref()).ok()
+}
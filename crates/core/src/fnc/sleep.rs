@@ -1,20 +1,35 @@
 use crate::ctx::Context;
+use crate::dbs::Options;
 use crate::expr::Duration;
 use crate::expr::Value;
 use anyhow::Result;
+use std::time;
+use trice::Instant;
+
+/// The interval at which a sleep checks if the query was cancelled.
+const CHECK_INTERVAL: time::Duration = time::Duration::from_millis(100);
 
 /// Sleep during the provided duration parameter.
-pub async fn sleep(ctx: &Context, (dur,): (Duration,)) -> Result<Value> {
+pub async fn sleep((ctx, opt): (&Context, &Options), (dur,): (Duration,)) -> Result<Value> {
+	// Check the sleep is within the capabilities
+	ctx.check_allowed_sleep(&opt.auth.as_ref().into(), dur.0)?;
 	// Calculate the sleep duration
 	let dur = match (ctx.timeout(), dur.0) {
 		(Some(t), d) if t < d => t,
 		(_, d) => d,
 	};
-	// Sleep for the specified time
-	#[cfg(target_family = "wasm")]
-	wasmtimer::tokio::sleep(dur).await;
-	#[cfg(not(target_family = "wasm"))]
-	tokio::time::sleep(dur).await;
+	// Sleep for the specified time, stopping early if cancelled
+	let started = Instant::now();
+	loop {
+		let remaining = dur.saturating_sub(started.elapsed());
+		if remaining.is_zero() || ctx.done(false)?.is_some() {
+			break;
+		}
+		#[cfg(target_family = "wasm")]
+		wasmtimer::tokio::sleep(remaining.min(CHECK_INTERVAL)).await;
+		#[cfg(not(target_family = "wasm"))]
+		tokio::time::sleep(remaining.min(CHECK_INTERVAL)).await;
+	}
 	// Ok all good
 	Ok(Value::None)
 }
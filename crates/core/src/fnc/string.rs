@@ -237,15 +237,110 @@ pub mod distance {
 }
 
 pub mod html {
+	use crate::err::Error;
+	use crate::expr::Object;
 	use crate::expr::value::Value;
-	use anyhow::Result;
+	use crate::fnc::args::Optional;
+	use anyhow::{Result, bail};
+	use std::collections::{HashMap, HashSet};
+
+	/// Tags whose content is always removed, and which can therefore never be allowed
+	const FORBIDDEN_TAGS: [&str; 2] = ["script", "style"];
+
+	/// URL schemes which can execute code, and which can therefore never be allowed
+	const FORBIDDEN_PROTOCOLS: [&str; 3] = ["javascript", "vbscript", "data"];
+
+	fn invalid(message: String) -> anyhow::Error {
+		anyhow::Error::new(Error::InvalidArguments {
+			name: String::from("string::html::sanitize"),
+			message,
+		})
+	}
+
+	fn strings(option: &str, value: Value) -> Result<Vec<String>> {
+		match value {
+			Value::Array(v) => v
+				.into_iter()
+				.map(|v| match v {
+					Value::Strand(v) => Ok(v.0.to_lowercase()),
+					v => Err(invalid(format!(
+						"The '{option}' option should only contain strings, found {v}"
+					))),
+				})
+				.collect(),
+			v => Err(invalid(format!("The '{option}' option should be an array, found {v}"))),
+		}
+	}
 
 	pub fn encode((arg,): (String,)) -> Result<Value> {
 		Ok(ammonia::clean_text(&arg).into())
 	}
 
-	pub fn sanitize((arg,): (String,)) -> Result<Value> {
-		Ok(ammonia::clean(&arg).into())
+	pub fn sanitize((arg, Optional(opts)): (String, Optional<Object>)) -> Result<Value> {
+		// Use the default allowlist when no options are given
+		let Some(opts) = opts else {
+			return Ok(ammonia::clean(&arg).into());
+		};
+		// Collect the configured allowlists
+		let mut tags = None;
+		let mut attributes = None;
+		let mut protocols = None;
+		for (k, v) in opts.0.into_iter() {
+			match k.as_str() {
+				"tags" => tags = Some(strings("tags", v)?),
+				"protocols" => protocols = Some(strings("protocols", v)?),
+				"attributes" => match v {
+					Value::Object(v) => {
+						let mut res = HashMap::new();
+						for (tag, attrs) in v.0.into_iter() {
+							res.insert(tag.to_lowercase(), strings("attributes", attrs)?);
+						}
+						attributes = Some(res);
+					}
+					v => bail!(invalid(format!(
+						"The 'attributes' option should be an object, found {v}"
+					))),
+				},
+				k => bail!(invalid(format!("Unknown sanitize option '{k}'"))),
+			}
+		}
+		// Build the sanitizer from the allowlists
+		let mut builder = ammonia::Builder::default();
+		if let Some(tags) = &tags {
+			if let Some(tag) = tags.iter().find(|t| FORBIDDEN_TAGS.contains(&t.as_str())) {
+				bail!(invalid(format!("The tag '{tag}' can not be allowed")));
+			}
+			builder.tags(tags.iter().map(String::as_str).collect());
+		}
+		if let Some(attributes) = &attributes {
+			let mut generic = HashSet::new();
+			let mut specific = HashMap::new();
+			for (tag, attrs) in attributes.iter() {
+				if FORBIDDEN_TAGS.contains(&tag.as_str()) {
+					bail!(invalid(format!("The attributes of the tag '{tag}' can not be allowed")));
+				}
+				let attrs: HashSet<&str> = attrs.iter().map(String::as_str).collect();
+				match tag.as_str() {
+					"*" => generic.extend(attrs),
+					tag => {
+						specific.insert(tag, attrs);
+					}
+				}
+			}
+			// An explicitly allowed rel attribute conflicts with the automatic one
+			if generic.contains("rel") || specific.values().any(|v| v.contains("rel")) {
+				builder.link_rel(None);
+			}
+			builder.generic_attributes(generic);
+			builder.tag_attributes(specific);
+		}
+		if let Some(protocols) = &protocols {
+			if let Some(p) = protocols.iter().find(|p| FORBIDDEN_PROTOCOLS.contains(&p.as_str())) {
+				bail!(invalid(format!("The protocol '{p}' can not be allowed")));
+			}
+			builder.url_schemes(protocols.iter().map(String::as_str).collect());
+		}
+		Ok(builder.clean(&arg).to_string().into())
 	}
 }
 
@@ -625,13 +720,80 @@ mod tests {
 
 	#[test]
 	fn html_sanitize() {
-		let value = super::html::sanitize((String::from("<div>Hello world!</div>"),)).unwrap();
+		let value =
+			super::html::sanitize((String::from("<div>Hello world!</div>"), Optional(None)))
+				.unwrap();
 		assert_eq!(value, Value::Strand("<div>Hello world!</div>".into()));
 
-		let value = super::html::sanitize((String::from("XSS<script>attack</script>"),)).unwrap();
+		let value =
+			super::html::sanitize((String::from("XSS<script>attack</script>"), Optional(None)))
+				.unwrap();
 		assert_eq!(value, Value::Strand("XSS".into()));
 	}
 
+	#[test]
+	fn html_sanitize_xss_corpus() {
+		#[track_caller]
+		fn test(input: &str, expected: &str) {
+			let value = super::html::sanitize((String::from(input), Optional(None))).unwrap();
+			assert_eq!(value, Value::Strand(expected.into()), "sanitize({input})");
+		}
+
+		test("<script>alert('XSS')</script>", "");
+		test("<SCRIPT SRC=http://xss.rocks/xss.js></SCRIPT>", "");
+		test("<IMG SRC=\"javascript:alert('XSS');\">", "<img>");
+		test("<IMG SRC=JaVaScRiPt:alert('XSS')>", "<img>");
+		test("<a href=\"javascript:alert(1)\">x</a>", "<a rel=\"noopener noreferrer\">x</a>");
+		test("<a href=\"jav&#x09;ascript:alert(1)\">x</a>", "<a rel=\"noopener noreferrer\">x</a>");
+		test("<IMG SRC=# onmouseover=\"alert('xxs')\">", "<img src=\"#\">");
+		test("<img src=x onerror=alert(1)//>", "<img src=\"x\">");
+		test("<svg/onload=alert('XSS')>", "");
+		test("<BODY ONLOAD=alert('XSS')>", "");
+		test("<iframe src=\"javascript:alert(1)\"></iframe>", "");
+		test("<div style=\"background:url(javascript:alert(1))\">x</div>", "<div>x</div>");
+		test("<<SCRIPT>alert(\"XSS\");//<</SCRIPT>", "&lt;");
+		test("<b><i>unbalanced</b></i>", "<b><i>unbalanced</i></b>");
+		test("<div><div><div>deep", "<div><div><div>deep</div></div></div>");
+		test("</p></div>stray closing", "<p></p>stray closing");
+		test("<", "&lt;");
+		test("", "");
+	}
+
+	#[test]
+	fn html_sanitize_allowlist() {
+		let opts = crate::syn::value(
+			"{ tags: ['a', 'b'], attributes: { a: ['href'] }, protocols: ['https'] }",
+		)
+		.unwrap();
+		let Value::Object(opts) = Value::from(opts) else {
+			unreachable!()
+		};
+		let input = "<p><a href=\"https://surrealdb.com\" title=\"x\">ok</a><a href=\"http://surrealdb.com\">no</a><b>bold</b><i>it</i></p>";
+		let value = super::html::sanitize((String::from(input), Optional(Some(opts)))).unwrap();
+		assert_eq!(
+			value,
+			Value::Strand(
+				"<a href=\"https://surrealdb.com\" rel=\"noopener noreferrer\">ok</a><a rel=\"noopener noreferrer\">no</a><b>bold</b>it".into()
+			)
+		);
+	}
+
+	#[test]
+	fn html_sanitize_forbidden_options() {
+		for opts in [
+			"{ tags: ['script'] }",
+			"{ protocols: ['javascript'] }",
+			"{ tags: 'a' }",
+			"{ unknown: true }",
+		] {
+			let Value::Object(opts) = Value::from(crate::syn::value(opts).unwrap()) else {
+				unreachable!()
+			};
+			let res = super::html::sanitize((String::from("<a>x</a>"), Optional(Some(opts))));
+			assert!(res.is_err(), "{opts} should be rejected");
+		}
+	}
+
 	#[test]
 	fn semver_compare() {
 		let value = super::semver::compare((String::from("1.2.3"), String::from("1.0.0"))).unwrap();
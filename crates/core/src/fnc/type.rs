@@ -17,7 +17,8 @@ use geo::Point;
 use reblessive::tree::Stk;
 use rust_decimal::Decimal;
 
-use super::args::Optional;
+use super::args::{Optional, Rest};
+use std::ops::Bound;
 
 pub fn array((val,): (Value,)) -> Result<Value> {
 	Ok(val.cast_to::<Array>()?.into())
@@ -101,8 +102,57 @@ pub fn point((val,): (Value,)) -> Result<Value> {
 	Ok(val.cast_to::<Point<f64>>()?.into())
 }
 
-pub fn range((val,): (Value,)) -> Result<Value> {
-	Ok(val.cast_to::<Box<Range>>()?.into())
+pub fn range((val, Rest(args)): (Value, Rest<Value>)) -> Result<Value> {
+	let invalid = |message: String| Error::InvalidArguments {
+		name: "type::range".to_owned(),
+		message,
+	};
+	let mut args = args.into_iter();
+	// A single argument is cast into a range
+	let Some(end) = args.next() else {
+		return Ok(val.cast_to::<Box<Range>>()?.into());
+	};
+	// Check which of the bounds are included
+	let (mut inclusive_start, mut inclusive_end) = (true, false);
+	match args.next() {
+		Some(Value::Object(opts)) => {
+			for (k, v) in opts.0.into_iter() {
+				match (k.as_str(), v) {
+					("inclusive_start", Value::Bool(v)) => inclusive_start = v,
+					("inclusive_end", Value::Bool(v)) => inclusive_end = v,
+					("inclusive_start" | "inclusive_end", v) => {
+						bail!(invalid(format!("The '{k}' option should be a boolean, found {v}")))
+					}
+					(k, _) => bail!(invalid(format!("Unknown range option '{k}'"))),
+				}
+			}
+		}
+		None | Some(Value::None) => {}
+		Some(_) => bail!(invalid("The third argument should be an object.".to_owned())),
+	}
+	ensure!(args.next().is_none(), invalid("Expected 1 to 3 arguments.".to_owned()));
+	// Both bounds must be of the same type
+	if !val.is_none() && !end.is_none() {
+		let same = match (&val, &end) {
+			(Value::Number(_), Value::Number(_)) => true,
+			(a, b) => a.kindof() == b.kindof(),
+		};
+		ensure!(
+			same,
+			invalid(format!(
+				"The range bounds must be of the same type, found `{}` and `{}`",
+				val.kindof(),
+				end.kindof()
+			))
+		);
+	}
+	// NONE bounds leave that side of the range open
+	let bound = |v: Value, inclusive: bool| match v {
+		Value::None => Bound::Unbounded,
+		v if inclusive => Bound::Included(v),
+		v => Bound::Excluded(v),
+	};
+	Ok(Value::Range(Box::new(Range::new(bound(val, inclusive_start), bound(end, inclusive_end)))))
 }
 
 pub fn record((rid, Optional(tb)): (Value, Optional<Value>)) -> Result<Value> {
@@ -186,6 +236,31 @@ pub fn uuid((val,): (Value,)) -> Result<Value> {
 	val.cast_to::<Uuid>().map(Value::from).map_err(Error::from).map_err(anyhow::Error::new)
 }
 
+pub mod field {
+	use crate::ctx::Context;
+	use crate::dbs::Options;
+	use crate::expr::{Idiom, Kind, Value};
+	use crate::syn;
+	use anyhow::Result;
+
+	/// Returns the values which a field allows, when the field
+	/// is defined with a literal union type, such as a dropdown
+	/// of `"draft" | "published" | "archived"`
+	pub async fn variants(
+		(ctx, opt): (&Context, &Options),
+		(field, table): (String, String),
+	) -> Result<Value> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Parse the string as an Idiom
+		let name: Idiom = syn::idiom(&field)?.into();
+		// Fetch the field definition
+		let fd = ctx.tx().get_tb_field(ns, db, &table, &name.to_string()).await?;
+		// Return the literals of the field type
+		Ok(fd.kind.as_ref().and_then(Kind::literal_variants).unwrap_or_default().into())
+	}
+}
+
 pub mod is {
 	use crate::expr::Geometry;
 	use crate::expr::table::Table;
@@ -1,5 +1,6 @@
 use crate::cnf::SURREALDB_USER_AGENT;
 use crate::ctx::Context;
+use crate::dbs::Capabilities;
 use crate::err::Error;
 use crate::expr::{Bytes, Object, Strand, Value};
 use crate::syn;
@@ -19,6 +20,109 @@ pub(crate) fn uri_is_valid(uri: &str) -> bool {
 	reqwest::Url::parse(uri).is_ok()
 }
 
+/// Options which control how an http function request is sent and received
+#[derive(Clone, Debug)]
+pub(crate) struct RequestConfig {
+	/// The maximum time which the request is allowed to take
+	pub timeout: Option<std::time::Duration>,
+	/// The number of times a failed request is retried
+	pub retries: u32,
+	/// The time to wait before the first retry, doubled on every further retry
+	pub retry_backoff: std::time::Duration,
+	/// Whether non-idempotent requests are also retried
+	pub force_retry: bool,
+	/// The maximum number of bytes accepted in the response body
+	pub max_body: Option<u64>,
+	/// Whether redirect responses are followed
+	pub follow_redirects: bool,
+}
+
+impl Default for RequestConfig {
+	fn default() -> Self {
+		Self {
+			timeout: None,
+			retries: 0,
+			retry_backoff: std::time::Duration::from_millis(100),
+			force_retry: false,
+			max_body: None,
+			follow_redirects: true,
+		}
+	}
+}
+
+impl RequestConfig {
+	/// Parse the request options from an object passed to an http function
+	pub(crate) fn from_object(fn_name: &str, opts: Object, caps: &Capabilities) -> Result<Self> {
+		let invalid = |message: String| Error::InvalidArguments {
+			name: fn_name.to_owned(),
+			message,
+		};
+		let mut config = Self::default();
+		for (k, v) in opts.0.into_iter() {
+			match (k.as_str(), v) {
+				("timeout", Value::Duration(d)) => config.timeout = Some(d.0),
+				("timeout", Value::None | Value::Null) => config.timeout = None,
+				("retries", Value::Number(n)) if n.is_int() && n.as_int() >= 0 => {
+					config.retries = n.as_int().try_into().unwrap_or(u32::MAX)
+				}
+				("retry_backoff", Value::Duration(d)) => config.retry_backoff = d.0,
+				("force_retry", Value::Bool(b)) => config.force_retry = b,
+				("max_body", Value::Number(n)) if n.is_int() && n.as_int() >= 0 => {
+					config.max_body = Some(n.as_int() as u64)
+				}
+				("max_body", Value::Strand(s)) => {
+					use crate::str::ParseBytes;
+					let size = s.as_str().parse_bytes::<u64>().map_err(|e| {
+						invalid(format!("The 'max_body' option is not a valid size: {e}"))
+					})?;
+					config.max_body = Some(size)
+				}
+				("max_body", Value::None | Value::Null) => config.max_body = None,
+				("follow_redirects", Value::Bool(b)) => config.follow_redirects = b,
+				("timeout" | "retry_backoff", v) => {
+					bail!(invalid(format!("The '{k}' option should be a duration, found {v}")))
+				}
+				("retries", v) => {
+					bail!(invalid(format!(
+						"The 'retries' option should be a positive integer, found {v}"
+					)))
+				}
+				("max_body", v) => {
+					bail!(invalid(format!(
+						"The 'max_body' option should be a positive integer or a size string, found {v}"
+					)))
+				}
+				("force_retry" | "follow_redirects", v) => {
+					bail!(invalid(format!("The '{k}' option should be a boolean, found {v}")))
+				}
+				(k, _) => bail!(invalid(format!("Unknown request option '{k}'"))),
+			}
+		}
+		Ok(config.capped(caps))
+	}
+
+	/// Limit the options to the maximums configured in the datastore capabilities
+	pub(crate) fn capped(mut self, caps: &Capabilities) -> Self {
+		if let Some(max) = caps.max_http_timeout() {
+			self.timeout = Some(self.timeout.map_or(max, |t| t.min(max)));
+		}
+		if let Some(max) = caps.max_http_body() {
+			self.max_body = Some(self.max_body.map_or(max, |b| b.min(max)));
+		}
+		self.retries = self.retries.min(caps.max_http_retries());
+		self
+	}
+
+	/// Check whether a request with the specified method may be retried
+	fn can_retry(&self, method: &Method) -> bool {
+		self.force_retry
+			|| matches!(
+				*method,
+				Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+			)
+	}
+}
+
 fn encode_body(req: RequestBuilder, body: Value) -> RequestBuilder {
 	match body {
 		Value::Bytes(v) => req.body(v.0),
@@ -28,30 +132,17 @@ fn encode_body(req: RequestBuilder, body: Value) -> RequestBuilder {
 	}
 }
 
-async fn decode_response(res: Response) -> Result<Value> {
+fn map_error(uri: &Strand, err: reqwest::Error) -> Error {
+	if err.is_timeout() {
+		Error::HttpTimeout(uri.to_string())
+	} else {
+		Error::from(err)
+	}
+}
+
+fn status_error(res: Response) -> Result<Response> {
 	match res.error_for_status() {
-		Ok(res) => match res.headers().get(CONTENT_TYPE) {
-			Some(mime) => match mime.to_str() {
-				Ok(v) if v.starts_with("application/json") => {
-					let txt = res.text().await.map_err(Error::from)?;
-					let val = syn::json(&txt)
-						.context("Failed to parse JSON response")
-						.map_err(|e| Error::Http(e.to_string()))?;
-					Ok(val.into())
-				}
-				Ok(v) if v.starts_with("application/octet-stream") => {
-					let bytes = res.bytes().await.map_err(Error::from)?;
-					Ok(Value::Bytes(Bytes(bytes.into())))
-				}
-				Ok(v) if v.starts_with("text") => {
-					let txt = res.text().await.map_err(Error::from)?;
-					let val = txt.into();
-					Ok(val)
-				}
-				_ => Ok(Value::None),
-			},
-			_ => Ok(Value::None),
-		},
+		Ok(res) => Ok(res),
 		Err(err) => match err.status() {
 			Some(s) => bail!(Error::Http(format!(
 				"{} {}",
@@ -63,12 +154,79 @@ async fn decode_response(res: Response) -> Result<Value> {
 	}
 }
 
+/// Read the response body, aborting the transfer once it grows past the limit
+async fn read_body(uri: &Strand, mut res: Response, max: Option<u64>) -> Result<Vec<u8>> {
+	let Some(max) = max else {
+		return Ok(res.bytes().await.map_err(|e| map_error(uri, e))?.into());
+	};
+	// Reject the response early when the advertised length is too large
+	if res.content_length().is_some_and(|len| len > max) {
+		bail!(Error::HttpBodyTooLarge(max));
+	}
+	let mut body = Vec::new();
+	while let Some(chunk) = res.chunk().await.map_err(|e| map_error(uri, e))? {
+		if body.len() as u64 + chunk.len() as u64 > max {
+			bail!(Error::HttpBodyTooLarge(max));
+		}
+		body.extend_from_slice(&chunk);
+	}
+	Ok(body)
+}
+
+async fn decode_response(uri: &Strand, res: Response, max: Option<u64>) -> Result<Value> {
+	let res = status_error(res)?;
+	match res.headers().get(CONTENT_TYPE) {
+		Some(mime) => match mime.to_str() {
+			Ok(v) if v.starts_with("application/json") => {
+				let body = read_body(uri, res, max).await?;
+				let txt = String::from_utf8_lossy(&body);
+				let val = syn::json(&txt)
+					.context("Failed to parse JSON response")
+					.map_err(|e| Error::Http(e.to_string()))?;
+				Ok(val.into())
+			}
+			Ok(v) if v.starts_with("application/octet-stream") => {
+				let bytes = read_body(uri, res, max).await?;
+				Ok(Value::Bytes(Bytes(bytes)))
+			}
+			Ok(v) if v.starts_with("text") => {
+				let body = read_body(uri, res, max).await?;
+				let val = String::from_utf8_lossy(&body).into_owned().into();
+				Ok(val)
+			}
+			_ => Ok(Value::None),
+		},
+		_ => Ok(Value::None),
+	}
+}
+
+/// Check whether a failed attempt is worth retrying
+fn is_transient(res: &Result<Response, reqwest::Error>) -> bool {
+	match res {
+		Ok(res) => res.status().is_server_error() || res.status().as_u16() == 429,
+		Err(err) => err.is_timeout() || err.is_connect(),
+	}
+}
+
+async fn backoff(ctx: &Context, delay: std::time::Duration) {
+	// Never wait for longer than the query is allowed to run
+	let delay = match ctx.timeout() {
+		Some(t) if t < delay => t,
+		_ => delay,
+	};
+	#[cfg(target_family = "wasm")]
+	wasmtimer::tokio::sleep(delay).await;
+	#[cfg(not(target_family = "wasm"))]
+	tokio::time::sleep(delay).await;
+}
+
 async fn request(
 	ctx: &Context,
 	method: Method,
 	uri: Strand,
 	body: Option<Value>,
 	opts: impl Into<Object>,
+	config: RequestConfig,
 ) -> Result<Value> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
@@ -79,20 +237,24 @@ async fn request(
 
 	#[cfg(not(target_family = "wasm"))]
 	let builder = {
-		let count = *crate::cnf::MAX_HTTP_REDIRECTS;
-		let ctx_clone = ctx.clone();
-		let policy = Policy::custom(move |attempt: Attempt| {
-			let check = task::block_in_place(|| {
-				Handle::current().block_on(ctx_clone.check_allowed_net(attempt.url()))
-			});
-			if let Err(e) = check {
-				return attempt.error(e);
-			}
-			if attempt.previous().len() >= count {
-				return attempt.stop();
-			}
-			attempt.follow()
-		});
+		let policy = if config.follow_redirects {
+			let count = *crate::cnf::MAX_HTTP_REDIRECTS;
+			let ctx_clone = ctx.clone();
+			Policy::custom(move |attempt: Attempt| {
+				let check = task::block_in_place(|| {
+					Handle::current().block_on(ctx_clone.check_allowed_net(attempt.url()))
+				});
+				if let Err(e) = check {
+					return attempt.error(e);
+				}
+				if attempt.previous().len() >= count {
+					return attempt.stop();
+				}
+				attempt.follow()
+			})
+		} else {
+			Policy::none()
+		};
 		let b = builder.redirect(policy);
 		b.dns_resolver(std::sync::Arc::new(
 			crate::fnc::http::resolver::FilteringResolver::from_capabilities(
@@ -104,7 +266,7 @@ async fn request(
 	let cli = builder.build()?;
 
 	let is_head = matches!(method, Method::HEAD);
-	// Start a new HEAD request
+	// Start a new request
 	let mut req = cli.request(method.clone(), url);
 	// Add the User-Agent header
 	if cfg!(not(target_family = "wasm")) {
@@ -120,67 +282,105 @@ async fn request(
 		req = encode_body(req, b);
 	}
 
-	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_family = "wasm"))]
-		Some(d) => req.timeout(d).send().await.map_err(Error::from)?,
-		_ => req.send().await.map_err(Error::from)?,
+	// The request must finish before both the query and the request timeout
+	#[cfg(not(target_family = "wasm"))]
+	let req = match (ctx.timeout(), config.timeout) {
+		(Some(a), Some(b)) => req.timeout(a.min(b)),
+		(Some(d), None) | (None, Some(d)) => req.timeout(d),
+		(None, None) => req,
 	};
 
+	// Only retry requests which are safe to repeat
+	let retries = if config.can_retry(&method) {
+		config.retries
+	} else {
+		0
+	};
+
+	// Send the request and wait, retrying transient failures
+	let mut attempt = 0;
+	let mut delay = config.retry_backoff;
+	let res = loop {
+		let Some(next) = req.try_clone() else {
+			// Streaming bodies can not be resent
+			break req.send().await;
+		};
+		let res = next.send().await;
+		if attempt >= retries || !is_transient(&res) {
+			break res;
+		}
+		if ctx.is_done(true).await? {
+			break res;
+		}
+		backoff(ctx, delay).await;
+		delay = delay.saturating_mul(2);
+		attempt += 1;
+	};
+	let res = res.map_err(|e| map_error(&uri, e))?;
+
 	if is_head {
 		// Check the response status
-		match res.error_for_status() {
-			Ok(_) => Ok(Value::None),
-			Err(err) => match err.status() {
-				Some(s) => bail!(Error::Http(format!(
-					"{} {}",
-					s.as_u16(),
-					s.canonical_reason().unwrap_or_default(),
-				))),
-				None => bail!(Error::Http(err.to_string())),
-			},
-		}
+		status_error(res)?;
+		Ok(Value::None)
 	} else {
 		// Receive the response as a value
-		decode_response(res).await
+		decode_response(&uri, res, config.max_body).await
 	}
 }
 
-pub async fn head(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value> {
-	request(ctx, Method::HEAD, uri, None, opts).await
+pub(crate) async fn head(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	config: RequestConfig,
+) -> Result<Value> {
+	request(ctx, Method::HEAD, uri, None, opts, config).await
 }
 
-pub async fn get(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value> {
-	request(ctx, Method::GET, uri, None, opts).await
+pub(crate) async fn get(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	config: RequestConfig,
+) -> Result<Value> {
+	request(ctx, Method::GET, uri, None, opts, config).await
 }
 
-pub async fn put(
+pub(crate) async fn put(
 	ctx: &Context,
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	config: RequestConfig,
 ) -> Result<Value> {
-	request(ctx, Method::PUT, uri, Some(body), opts).await
+	request(ctx, Method::PUT, uri, Some(body), opts, config).await
 }
 
-pub async fn post(
+pub(crate) async fn post(
 	ctx: &Context,
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	config: RequestConfig,
 ) -> Result<Value> {
-	request(ctx, Method::POST, uri, Some(body), opts).await
+	request(ctx, Method::POST, uri, Some(body), opts, config).await
 }
 
-pub async fn patch(
+pub(crate) async fn patch(
 	ctx: &Context,
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	config: RequestConfig,
 ) -> Result<Value> {
-	request(ctx, Method::PATCH, uri, Some(body), opts).await
+	request(ctx, Method::PATCH, uri, Some(body), opts, config).await
 }
 
-pub async fn delete(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value> {
-	request(ctx, Method::DELETE, uri, None, opts).await
+pub(crate) async fn delete(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	config: RequestConfig,
+) -> Result<Value> {
+	request(ctx, Method::DELETE, uri, None, opts, config).await
 }
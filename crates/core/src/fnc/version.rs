@@ -0,0 +1,30 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::Value;
+use anyhow::Result;
+
+/// Returns the version of the current record, which is incremented every
+/// time that the record is written, or NONE if there is no current record,
+/// or if the table of the record is not defined as VERSIONED.
+pub async fn version(
+	(ctx, opt, doc): (&Context, &Options, Option<&CursorDoc>),
+	_: (),
+) -> Result<Value> {
+	let Some(rid) = doc.and_then(|doc| doc.rid.as_ref()) else {
+		return Ok(Value::None);
+	};
+	let (ns, db) = opt.ns_db()?;
+	// Check if the table keeps record versions
+	match ctx.tx().get_tb(ns, db, &rid.tb).await {
+		Ok(tb) if tb.versioned => {}
+		Ok(_) => return Ok(Value::None),
+		Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => {
+			return Ok(Value::None);
+		}
+		Err(e) => return Err(e),
+	}
+	let version = ctx.tx().get_record_version(ns, db, &rid.tb, &rid.id).await?;
+	Ok(version.into())
+}
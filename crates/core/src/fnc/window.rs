@@ -0,0 +1,42 @@
+//! Window functions, which are computed over the output rows of a SELECT statement.
+//! These are only computed when used with an OVER clause, as in
+//! `SELECT rank() OVER (ORDER BY score DESC) AS position FROM player`.
+
+use super::args::Optional;
+use crate::err::Error;
+use crate::expr::value::Value;
+use anyhow::{Result, bail};
+
+fn unwindowed(name: &str) -> Result<Value> {
+	bail!(Error::InvalidFunction {
+		name: name.to_owned(),
+		message: String::from(
+			"This function can only be used with an OVER clause in the fields of a SELECT statement"
+		),
+	})
+}
+
+/// Returns the position of the row within its window partition.
+pub fn row_number(_: ()) -> Result<Value> {
+	unwindowed("row_number")
+}
+
+/// Returns the rank of the row within its window partition, with gaps after equal rows.
+pub fn rank(_: ()) -> Result<Value> {
+	unwindowed("rank")
+}
+
+/// Returns the rank of the row within its window partition, without gaps after equal rows.
+pub fn dense_rank(_: ()) -> Result<Value> {
+	unwindowed("dense_rank")
+}
+
+/// Returns the value of a previous row within the window partition.
+pub fn lag(_: (Value, Optional<i64>, Optional<Value>)) -> Result<Value> {
+	unwindowed("lag")
+}
+
+/// Returns the value of a following row within the window partition.
+pub fn lead(_: (Value, Optional<i64>, Optional<Value>)) -> Result<Value> {
+	unwindowed("lead")
+}
@@ -1,11 +1,15 @@
 use tokio::sync::RwLock;
 
-use std::{collections::BTreeMap, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 use async_graphql::dynamic::Schema;
 
 use crate::dbs::Session;
-use crate::kvs::Datastore;
+use crate::kvs::{Datastore, LockType, TransactionType};
 
 use super::{error::GqlError, schema::generate_schema};
 
@@ -13,7 +17,7 @@ use super::{error::GqlError, schema::generate_schema};
 pub trait Invalidator: Debug + Clone + Send + Sync + 'static {
 	type MetaData: Debug + Clone + Send + Sync + Hash;
 
-	fn is_valid(datastore: &Datastore, session: &Session, meta: &Self::MetaData) -> bool;
+	async fn is_valid(datastore: &Datastore, session: &Session, meta: &Self::MetaData) -> bool;
 
 	async fn generate(
 		datastore: &Arc<Datastore>,
@@ -28,7 +32,7 @@ pub struct Pessimistic;
 impl Invalidator for Pessimistic {
 	type MetaData = ();
 
-	fn is_valid(_datastore: &Datastore, _session: &Session, _meta: &Self::MetaData) -> bool {
+	async fn is_valid(_datastore: &Datastore, _session: &Session, _meta: &Self::MetaData) -> bool {
 		false
 	}
 
@@ -48,7 +52,7 @@ pub struct Optimistic;
 impl Invalidator for Optimistic {
 	type MetaData = ();
 
-	fn is_valid(_datastore: &Datastore, _session: &Session, _meta: &Self::MetaData) -> bool {
+	async fn is_valid(_datastore: &Datastore, _session: &Session, _meta: &Self::MetaData) -> bool {
 		true
 	}
 
@@ -61,8 +65,50 @@ impl Invalidator for Optimistic {
 	}
 }
 
+/// Regenerates the schema when the definitions it was generated from change
+#[derive(Debug, Clone, Copy)]
+pub struct Definitions;
+
+#[async_trait::async_trait]
+impl Invalidator for Definitions {
+	type MetaData = u64;
+
+	async fn is_valid(datastore: &Datastore, session: &Session, meta: &Self::MetaData) -> bool {
+		fingerprint(datastore, session).await.is_ok_and(|f| f == *meta)
+	}
+
+	async fn generate(
+		datastore: &Arc<Datastore>,
+		session: &Session,
+	) -> Result<(Schema, Self::MetaData), GqlError> {
+		// Take the fingerprint first, so that changes made while the schema
+		// is generated cause it to be regenerated on the next request
+		let meta = fingerprint(datastore, session).await?;
+		let schema = generate_schema(datastore, session).await?;
+		Ok((schema, meta))
+	}
+}
+
+/// Hashes the tables, functions, and configuration a schema is generated from.
+/// Tables are hashed along with their cache timestamps, which change whenever
+/// a field on the table is defined, altered, or removed.
+async fn fingerprint(datastore: &Datastore, session: &Session) -> Result<u64, GqlError> {
+	let ns = session.ns.as_ref().ok_or(GqlError::UnspecifiedNamespace)?;
+	let db = session.db.as_ref().ok_or(GqlError::UnspecifiedDatabase)?;
+	let tx = datastore.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	let cg = tx.get_db_config(ns, db, "graphql").await.ok();
+	let tbs = tx.all_tb(ns, db, None).await?;
+	let fns = tx.all_db_functions(ns, db).await?;
+	tx.cancel().await?;
+	let mut hasher = DefaultHasher::new();
+	cg.hash(&mut hasher);
+	tbs.hash(&mut hasher);
+	fns.hash(&mut hasher);
+	Ok(hasher.finish())
+}
+
 #[derive(Clone)]
-pub struct SchemaCache<I: Invalidator = Pessimistic> {
+pub struct SchemaCache<I: Invalidator = Definitions> {
 	#[expect(clippy::type_complexity)]
 	inner: Arc<RwLock<BTreeMap<(String, String), (Schema, I::MetaData)>>>,
 	pub datastore: Arc<Datastore>,
@@ -92,7 +138,7 @@ impl<I: Invalidator> SchemaCache<I> {
 		{
 			let guard = self.inner.read().await;
 			if let Some(cand) = guard.get(&(ns.to_owned(), db.to_owned())) {
-				if I::is_valid(&self.datastore, session, &cand.1) {
+				if I::is_valid(&self.datastore, session, &cand.1).await {
 					return Ok(cand.0.clone());
 				}
 			}
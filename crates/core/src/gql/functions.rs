@@ -19,7 +19,6 @@ pub async fn process_fns(
 	fns: Arc<[DefineFunctionStatement]>,
 	mut query: Object,
 	types: &mut Vec<Type>,
-	session: &Session,
 	datastore: &Arc<Datastore>,
 ) -> Result<Object, GqlError> {
 	for fnd in fns.iter() {
@@ -27,7 +26,6 @@ pub async fn process_fns(
 			// TODO: handle case where there are no typed functions and give graceful error
 			continue;
 		};
-		let sess1 = session.clone();
 		let kvs1 = datastore.clone();
 		let fnd1 = fnd.clone();
 		let kind1 = kind.clone();
@@ -35,12 +33,11 @@ pub async fn process_fns(
 			format!("fn_{}", fnd.name),
 			kind_to_type(kind.clone(), types)?,
 			move |ctx| {
-				let sess1 = sess1.clone();
 				let kvs1 = kvs1.clone();
 				let fnd1 = fnd1.clone();
 				let kind1 = kind1.clone();
 				FieldFuture::new(async move {
-					let gtx = GQLTx::new(&kvs1, &sess1).await?;
+					let gtx = GQLTx::new(&kvs1, ctx.data::<Session>()?).await?;
 					let gql_args = ctx.args.as_index_map();
 					let mut args = Vec::new();
 
@@ -90,24 +90,41 @@ pub async fn generate_schema(
 	}
 
 	let mut query = Object::new("Query");
+	let mut mutation = None;
 	let mut types: Vec<Type> = Vec::new();
 
 	trace!(ns, db, ?tbs, ?fns, "generating schema");
 
 	match tbs {
 		Some(tbs) if !tbs.is_empty() => {
-			query = process_tbs(tbs, query, &mut types, &tx, ns, db, session, datastore).await?;
+			let (q, m) = process_tbs(
+				tbs,
+				query,
+				Object::new("Mutation"),
+				&mut types,
+				&tx,
+				ns,
+				db,
+				datastore,
+			)
+			.await?;
+			query = q;
+			mutation = Some(m);
 		}
 		_ => {}
 	}
 
 	if let Some(fns) = fns {
-		query = process_fns(fns, query, &mut types, session, datastore).await?;
+		query = process_fns(fns, query, &mut types, datastore).await?;
 	}
 
 	trace!("current Query object for schema: {:?}", query);
 
-	let mut schema = Schema::build("Query", None, None).register(query);
+	let mut schema = match mutation {
+		Some(mutation) => Schema::build("Query", Some("Mutation"), None).register(mutation),
+		None => Schema::build("Query", None, None),
+	}
+	.register(query);
 	for ty in types {
 		trace!("adding type: {ty:?}");
 		schema = schema.register(ty);
@@ -411,7 +428,9 @@ pub fn gql_to_sql_kind(val: &GqlValue, kind: Kind) -> Result<SurValue, GqlError>
 			GqlValue::Boolean(b) => Ok(SurValue::Bool(*b)),
 			bin @ GqlValue::Binary(_) => gql_to_sql_kind(bin, Kind::Bytes),
 			GqlValue::Enum(s) => Ok(SurValue::Strand(s.as_str().into())),
-			arr @ GqlValue::List(_) => gql_to_sql_kind(arr, Kind::Array(Box::new(Kind::Any), None)),
+			arr @ GqlValue::List(_) => {
+				gql_to_sql_kind(arr, Kind::Array(Box::new(Kind::Any), Default::default()))
+			}
 		},
 		Kind::Null => match val {
 			GqlValue::Null => Ok(SurValue::Null),
@@ -634,22 +653,18 @@ pub fn gql_to_sql_kind(val: &GqlValue, kind: Kind) -> Result<SurValue, GqlError>
 			}
 		}
 		Kind::Set(_k, _n) => Err(resolver_error("Sets are not yet supported")),
-		Kind::Array(ref k, n) => match val {
+		Kind::Array(ref k, c) => match val {
 			GqlValue::List(l) => {
 				let list_iter = l.iter().map(|v| gql_to_sql_kind(v, *k.to_owned()));
 				let list: Result<Vec<SurValue>, GqlError> = list_iter.collect();
+				let list = expr::Array::from(list?);
 
-				match (list, n) {
-					(Ok(l), Some(n)) => {
-						if l.len() as u64 == n {
-							Ok(l.into())
-						} else {
-							Err(type_error(kind, val))
-						}
-					}
-					(Ok(l), None) => Ok(l.into()),
-					(Err(e), _) => Err(e),
+				if !c.allows_len(list.len())
+					|| (c.unique && expr::ArrayConstraints::find_duplicate(&list).is_some())
+				{
+					return Err(type_error(kind, val));
 				}
+				Ok(list.into())
 			}
 			_ => Err(type_error(kind, val)),
 		},
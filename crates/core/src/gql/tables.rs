@@ -1,15 +1,18 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::sync::Arc;
 
 use crate::dbs::Session;
 use crate::expr::order::{OrderList, Ordering};
-use crate::expr::statements::{DefineFieldStatement, DefineTableStatement, SelectStatement};
+use crate::expr::statements::{
+	CreateStatement, DefineFieldStatement, DefineTableStatement, DeleteStatement, SelectStatement,
+	UpdateStatement,
+};
 use crate::expr::{self, Table};
-use crate::expr::{Cond, Fields};
+use crate::expr::{Cond, Data, Fields, Output};
 use crate::expr::{Expression, Value as SqlValue};
 use crate::expr::{Idiom, Kind};
 use crate::expr::{LogicalPlan, Thing};
-use crate::gql::ext::TryAsExt;
 use crate::gql::schema::{kind_to_type, unwrap_type};
 use crate::kvs::{Datastore, Transaction};
 use async_graphql::Name;
@@ -26,7 +29,9 @@ use super::error::{GqlError, resolver_error};
 use super::ext::IntoExt;
 use super::schema::{gql_to_sql_kind, sql_value_to_gql_value};
 use crate::gql::error::internal_error;
-use crate::gql::utils::{ErasedRecord, GQLTx, GqlValueUtils, field_val_erase_owned};
+use crate::gql::utils::{
+	ErasedRecord, GQLTx, GqlValueUtils, PrefetchedRecord, field_val_erase_owned,
+};
 
 macro_rules! order {
 	(asc, $field:expr_2021) => {{
@@ -68,13 +73,13 @@ fn filter_name_from_table(tb_name: impl Display) -> String {
 pub async fn process_tbs(
 	tbs: Arc<[DefineTableStatement]>,
 	mut query: Object,
+	mut mutation: Object,
 	types: &mut Vec<Type>,
 	tx: &Transaction,
 	ns: &str,
 	db: &str,
-	session: &Session,
 	datastore: &Arc<Datastore>,
-) -> Result<Object, GqlError> {
+) -> Result<(Object, Object), GqlError> {
 	for tb in tbs.iter() {
 		trace!("Adding table: {}", tb.name);
 		let tb_name = tb.name.to_string();
@@ -106,7 +111,6 @@ pub async fn process_tbs(
 			.field(InputValue::new("not", TypeRef::named(&table_filter_name)));
 		types.push(Type::InputObject(filter_id()));
 
-		let sess1 = session.to_owned();
 		let fds = tx.all_tb_fields(ns, db, &tb.name.0, None).await?;
 		let fds1 = fds.clone();
 		let kvs1 = datastore.clone();
@@ -117,11 +121,10 @@ pub async fn process_tbs(
             TypeRef::named_nn_list_nn(tb.name.to_string()),
             move |ctx| {
                 let tb_name = first_tb_name.clone();
-                let sess1 = sess1.clone();
                 let fds1 = fds1.clone();
                 let kvs1 = kvs1.clone();
                 FieldFuture::new(async move {
-                    let gtx = GQLTx::new(&kvs1, &sess1).await?;
+                    let gtx = GQLTx::new(&kvs1, ctx.data::<Session>()?).await?;
 
                     let args = ctx.args.as_index_map();
                     trace!("received request with args: {args:?}");
@@ -187,18 +190,27 @@ pub async fn process_tbs(
 
                     trace!("parsed filter: {cond:?}");
 
-                    // SELECT VALUE id FROM ...
+                    // The selected fields which are defined on the table
+                    let selected: Vec<String> = ctx
+                        .ctx
+                        .field()
+                        .selection_set()
+                        .map(|f| f.name().to_owned())
+                        .filter(|f| f != "id" && fds1.iter().any(|fd| fd.name.to_string() == *f))
+                        .collect();
+
+                    // SELECT id, selected... FROM ...
+                    let fields = std::iter::once("id".to_owned())
+                        .chain(selected)
+                        .map(|f| expr::Field::Single {
+                            expr: SqlValue::Idiom(Idiom::from(f)),
+                            alias: None,
+                        })
+                        .collect();
                     let ast = LogicalPlan::Select({
                         SelectStatement {
                             what: vec![SqlValue::Table(tb_name.intox())].into(),
-                            expr: Fields(
-                                vec![expr::Field::Single {
-                                    expr: SqlValue::Idiom(Idiom::from("id")),
-                                    alias: None,
-                                }],
-                                // this means the `value` keyword
-                                true,
-                            ),
+                            expr: Fields(fields, false),
                             order: orders.map(|x| Ordering::Order(OrderList(x))),
                             cond,
                             limit,
@@ -223,11 +235,16 @@ pub async fn process_tbs(
                     let out: Result<Vec<FieldValue>, SqlValue> = res_vec
                         .0
                         .into_iter()
-                        .map(|v| {
-                            v.try_as_thing().map(|t| {
-                                let erased: ErasedRecord = (gtx.clone(), t);
-                                field_val_erase_owned(erased)
-                            })
+                        .map(|v| match v {
+                            SqlValue::Object(mut o) => match o.0.remove("id") {
+                                Some(SqlValue::Thing(t)) => {
+                                    let prefetched: PrefetchedRecord = (gtx.clone(), t, o);
+                                    Ok(FieldValue::owned_any(prefetched))
+                                }
+                                Some(v) => Err(v),
+                                None => Err(SqlValue::None),
+                            },
+                            v => Err(v),
                         })
                         .collect();
 
@@ -247,7 +264,6 @@ pub async fn process_tbs(
         .argument(InputValue::new("filter", TypeRef::named(&table_filter_name))),
     );
 
-		let sess2 = session.to_owned();
 		let kvs2 = datastore.to_owned();
 		query = query.field(
 			Field::new(
@@ -257,9 +273,8 @@ pub async fn process_tbs(
 					let tb_name = second_tb_name.clone();
 					let kvs2 = kvs2.clone();
 					FieldFuture::new({
-						let sess2 = sess2.clone();
 						async move {
-							let gtx = GQLTx::new(&kvs2, &sess2).await?;
+							let gtx = GQLTx::new(&kvs2, ctx.data::<Session>()?).await?;
 
 							let args = ctx.args.as_index_map();
 							let id = match args.get("id").and_then(GqlValueUtils::as_string) {
@@ -298,6 +313,46 @@ pub async fn process_tbs(
 			.argument(id_input!()),
 		);
 
+		mutation = mutation
+			.field(
+				Field::new(
+					format!("_create_{}", tb.name),
+					TypeRef::named(tb.name.to_string()),
+					make_mutation_resolver(Mutation::Create, tb.name.to_string(), &fds, datastore),
+				)
+				.description(format!(
+					"Generated from table `{}`\nallows creating a record in a table",
+					tb.name
+				))
+				.argument(InputValue::new("id", TypeRef::named(TypeRef::ID)))
+				.argument(InputValue::new("data", TypeRef::named_nn("object"))),
+			)
+			.field(
+				Field::new(
+					format!("_update_{}", tb.name),
+					TypeRef::named(tb.name.to_string()),
+					make_mutation_resolver(Mutation::Update, tb.name.to_string(), &fds, datastore),
+				)
+				.description(format!(
+					"Generated from table `{}`\nallows merging data into a record by ID",
+					tb.name
+				))
+				.argument(id_input!())
+				.argument(InputValue::new("data", TypeRef::named_nn("object"))),
+			)
+			.field(
+				Field::new(
+					format!("_delete_{}", tb.name),
+					TypeRef::named(TypeRef::ID),
+					make_mutation_resolver(Mutation::Delete, tb.name.to_string(), &fds, datastore),
+				)
+				.description(format!(
+					"Generated from table `{}`\nallows deleting a record by ID, returning the ID if it existed",
+					tb.name
+				))
+				.argument(id_input!()),
+			);
+
 		let mut table_ty_obj = Object::new(tb.name.to_string())
 			.field(Field::new(
 				"id",
@@ -350,15 +405,13 @@ pub async fn process_tbs(
 		types.push(Type::InputObject(table_filter));
 	}
 
-	let sess3 = session.to_owned();
 	let kvs3 = datastore.to_owned();
 	query = query.field(
 		Field::new("_get", TypeRef::named("record"), move |ctx| {
 			FieldFuture::new({
-				let sess3 = sess3.clone();
 				let kvs3 = kvs3.clone();
 				async move {
-					let gtx = GQLTx::new(&kvs3, &sess3).await?;
+					let gtx = GQLTx::new(&kvs3, ctx.data::<Session>()?).await?;
 
 					let args = ctx.args.as_index_map();
 					let id = match args.get("id").and_then(GqlValueUtils::as_string) {
@@ -391,7 +444,124 @@ pub async fn process_tbs(
 		.argument(id_input!()),
 	);
 
-	Ok(query)
+	Ok((query, mutation))
+}
+
+#[derive(Clone, Copy)]
+enum Mutation {
+	Create,
+	Update,
+	Delete,
+}
+
+fn make_mutation_resolver(
+	mutation: Mutation,
+	tb_name: String,
+	fds: &Arc<[DefineFieldStatement]>,
+	datastore: &Arc<Datastore>,
+) -> impl for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static {
+	let fds = fds.clone();
+	let kvs = datastore.clone();
+	move |ctx: ResolverContext| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let kvs = kvs.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Session>()?;
+			let args = ctx.args.as_index_map();
+
+			let id = args.get("id").and_then(GqlValueUtils::as_string);
+			let what = match id {
+				Some(id) => SqlValue::Thing(thing_from_id(&tb_name, id)?),
+				None => SqlValue::Table(tb_name.intox()),
+			};
+
+			// Return the id of the record, or NONE if there was no record
+			let value_id = Output::Fields(Fields(
+				vec![expr::Field::Single {
+					expr: SqlValue::Idiom(Idiom::from("id")),
+					alias: None,
+				}],
+				true,
+			));
+			let ast = match mutation {
+				Mutation::Create => LogicalPlan::Create(CreateStatement {
+					only: true,
+					what: vec![what].into(),
+					data: Some(Data::ContentExpression(data_from_input(args.get("data"), &fds)?)),
+					output: Some(value_id),
+					..Default::default()
+				}),
+				Mutation::Update => LogicalPlan::Update(UpdateStatement {
+					only: true,
+					what: vec![what].into(),
+					data: Some(Data::MergeExpression(data_from_input(args.get("data"), &fds)?)),
+					output: Some(value_id),
+					..Default::default()
+				}),
+				Mutation::Delete => LogicalPlan::Delete(DeleteStatement {
+					only: true,
+					what: vec![what].into(),
+					output: Some(Output::Before),
+					..Default::default()
+				}),
+			};
+
+			trace!("generated mutation ast: {ast:?}");
+
+			// Mutations are executed in their own write transaction
+			let mut res = kvs.process_plan(ast, sess, None).await?;
+			let res = res.remove(0).result?;
+
+			let rid = match res {
+				SqlValue::Thing(t) => t,
+				SqlValue::Object(o) => match o.get("id") {
+					Some(SqlValue::Thing(t)) => t.clone(),
+					_ => return Err(internal_error(format!("expected thing, found: {o:?}")).into()),
+				},
+				SqlValue::None => return Ok(None),
+				v => return Err(internal_error(format!("expected thing, found: {v:?}")).into()),
+			};
+
+			match mutation {
+				Mutation::Delete => Ok(Some(FieldValue::value(GqlValue::String(rid.to_string())))),
+				// Read the changed record in a new transaction
+				_ => {
+					let gtx = GQLTx::new(&kvs, sess).await?;
+					Ok(Some(field_val_erase_owned((gtx, rid))))
+				}
+			}
+		})
+	}
+}
+
+/// Parses an ID argument into a record of the given table
+fn thing_from_id(tb_name: &str, id: String) -> Result<Thing, GqlError> {
+	match Thing::try_from(id.clone()) {
+		Ok(t) if t.tb == tb_name => Ok(t),
+		Ok(t) => Err(resolver_error(format!("record {t} is not in table `{tb_name}`"))),
+		Err(_) => Ok(Thing::from((tb_name.to_owned(), id))),
+	}
+}
+
+/// Converts the data argument of a mutation, using the types of the defined fields
+fn data_from_input(
+	data: Option<&GqlValue>,
+	fds: &[DefineFieldStatement],
+) -> Result<SqlValue, GqlError> {
+	let obj = data
+		.and_then(GqlValueUtils::as_object)
+		.ok_or_else(|| resolver_error("Value of data must be an object"))?;
+	let mut out = BTreeMap::new();
+	for (k, v) in obj.iter() {
+		let kind = fds
+			.iter()
+			.find(|fd| fd.name.to_string() == k.as_str())
+			.and_then(|fd| fd.kind.clone())
+			.unwrap_or_default();
+		out.insert(k.to_string(), gql_to_sql_kind(v, kind)?);
+	}
+	Ok(SqlValue::Object(out.into()))
 }
 
 fn make_table_field_resolver(
@@ -404,12 +574,21 @@ fn make_table_field_resolver(
 		let field_kind = kind.clone();
 		FieldFuture::new({
 			async move {
-				let (gtx, rid) = ctx
-					.parent_value
-					.downcast_ref::<ErasedRecord>()
-					.ok_or_else(|| internal_error("failed to downcast"))?;
-
-				let val = gtx.get_record_field(rid.clone(), fd_name.as_str()).await?;
+				// Use the value of the field if it was selected with the record
+				let (gtx, val) = match ctx.parent_value.downcast_ref::<PrefetchedRecord>() {
+					Some((gtx, rid, _)) if fd_name == "id" => (gtx, SqlValue::Thing(rid.clone())),
+					Some((gtx, rid, fields)) => match fields.get(fd_name.as_str()) {
+						Some(v) => (gtx, v.clone()),
+						None => (gtx, gtx.get_record_field(rid.clone(), fd_name.as_str()).await?),
+					},
+					None => {
+						let (gtx, rid) = ctx
+							.parent_value
+							.downcast_ref::<ErasedRecord>()
+							.ok_or_else(|| internal_error("failed to downcast"))?;
+						(gtx, gtx.get_record_field(rid.clone(), fd_name.as_str()).await?)
+					}
+				};
 
 				let out = match val {
 					SqlValue::Thing(rid) if fd_name != "id" => {
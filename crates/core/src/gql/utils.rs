@@ -137,6 +137,9 @@ impl GQLTx {
 
 pub type ErasedRecord = (GQLTx, Thing);
 
+/// A record along with the fields which were selected when it was queried
+pub type PrefetchedRecord = (GQLTx, Thing, expr::Object);
+
 pub fn field_val_erase_owned(val: ErasedRecord) -> FieldValue<'static> {
 	FieldValue::owned_any(val)
 }
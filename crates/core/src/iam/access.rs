@@ -27,7 +27,7 @@ pub async fn authenticate_record(
 		Err(e) => {
 			match e.downcast_ref() {
 				// If the AUTHENTICATE clause throws a specific error, authentication fails with that error
-				Some(Error::Thrown(_)) => Err(e),
+				Some(Error::Thrown(_) | Error::ThrownValue(_)) => Err(e),
 				// If the AUTHENTICATE clause failed due to an unexpected error, be more specific
 				// This allows clients to handle these errors, which may be retryable
 				Some(Error::Tx(_) | Error::TxFailure | Error::TxRetryable) => {
@@ -71,7 +71,7 @@ pub async fn authenticate_generic(
 		Err(e) => {
 			match e.downcast_ref() {
 				// If the AUTHENTICATE clause throws a specific error, authentication fails with that error
-				Some(Error::Thrown(_)) => Err(e),
+				Some(Error::Thrown(_) | Error::ThrownValue(_)) => Err(e),
 				// If the AUTHENTICATE clause failed due to an unexpected error, be more specific
 				// This allows clients to handle these errors, which may be retryable
 				Some(Error::Tx(_) | Error::TxFailure | Error::TxRetryable) => {
@@ -4,7 +4,7 @@ use super::Level;
 
 use serde::{Deserialize, Serialize};
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,6 +33,8 @@ pub enum ResourceKind {
 	Bucket,
 	#[revision(start = 5)]
 	Sequence,
+	#[revision(start = 6)]
+	Schedule,
 	// IAM
 	Actor,
 }
@@ -69,6 +71,7 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Config(c) => write!(f, "Config::{c}"),
 			ResourceKind::Bucket => write!(f, "Bucket"),
 			ResourceKind::Sequence => write!(f, "Sequence"),
+			ResourceKind::Schedule => write!(f, "Schedule"),
 		}
 	}
 }
@@ -14,6 +14,7 @@ pub mod jwks;
 pub mod reset;
 pub mod signin;
 pub mod signup;
+pub mod throttle;
 pub mod token;
 pub mod verify;
 
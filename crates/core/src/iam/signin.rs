@@ -2,6 +2,7 @@ use super::access::{
 	authenticate_generic, authenticate_record, create_refresh_token_record,
 	revoke_refresh_token_record,
 };
+use super::throttle::{AuthAttempt, throttled};
 use super::verify::{verify_db_creds, verify_ns_creds, verify_root_creds};
 use super::{Actor, Level, Role};
 use crate::cnf::{INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
@@ -49,6 +50,12 @@ impl From<SigninData> for Value {
 pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<SigninData> {
 	// Check vars contains only computed values
 	vars.validate_computed()?;
+	// Check if the attempt is allowed
+	let attempt = AuthAttempt::new(session, &vars);
+	throttled(kvs, attempt, signin_target(kvs, session, vars)).await
+}
+
+async fn signin_target(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<SigninData> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
@@ -283,7 +290,7 @@ pub async fn db_access(
 								}
 								Err(e) => match e.downcast_ref() {
 									// If the SIGNIN clause throws a specific error, authentication fails with that error
-									Some(Error::Thrown(_)) => Err(e),
+									Some(Error::Thrown(_) | Error::ThrownValue(_)) => Err(e),
 									// If the SIGNIN clause failed due to an unexpected error, be more specific
 									// This allows clients to handle these errors, which may be retryable
 									Some(Error::Tx(_) | Error::TxFailure | Error::TxRetryable) => {
@@ -1,4 +1,5 @@
 use super::access::{authenticate_record, create_refresh_token_record};
+use super::throttle::{AuthAttempt, throttled};
 use crate::cnf::{INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::dbs::capabilities::ExperimentalTarget;
@@ -44,6 +45,12 @@ impl From<SignupData> for Value {
 pub async fn signup(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<SignupData> {
 	// Check vars contains only computed values
 	vars.validate_computed()?;
+	// Check if the attempt is allowed
+	let attempt = AuthAttempt::new(session, &vars);
+	throttled(kvs, attempt, signup_target(kvs, session, vars)).await
+}
+
+async fn signup_target(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<SignupData> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
@@ -189,7 +196,7 @@ pub async fn db_access(
 		}
 		Err(e) => match e.downcast_ref() {
 			// If the SIGNUP clause throws a specific error, authentication fails with that error
-			Some(Error::Thrown(_)) => Err(e),
+			Some(Error::Thrown(_) | Error::ThrownValue(_)) => Err(e),
 			// If the SIGNUP clause failed due to an unexpected error, be more specific
 			// This allows clients to handle these errors, which may be retryable
 			Some(Error::Tx(_) | Error::TxFailure | Error::TxRetryable) => {
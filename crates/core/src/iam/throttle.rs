@@ -0,0 +1,458 @@
+//! Throttling of the authentication attempts.
+//!
+//! Every SIGNIN and SIGNUP attempt is identified by the access method, the
+//! identifier of the user, and the IP address of the client. When throttling
+//! is enabled in the capabilities, an attempt is rejected once too many
+//! attempts with the same identity have failed within the configured window.
+//! The attempts are checked before any credentials are looked up, so that the
+//! error never reveals whether the user exists. An attempt is reserved when
+//! it is checked, so that concurrent attempts can not all pass the check
+//! before any of their failures are recorded.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::expr::Object;
+use crate::kvs::Datastore;
+use anyhow::{Result, bail};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::time::Duration;
+use trice::Instant;
+
+/// The number of identities after which the expired attempts are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// The identity of an authentication attempt
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AuthAttempt {
+	/// The namespace of the attempt
+	pub ns: Option<String>,
+	/// The database of the attempt
+	pub db: Option<String>,
+	/// The access method of the attempt
+	pub ac: Option<String>,
+	/// The name of the system user, or the identifier of the bearer grant.
+	/// Attempts with other access methods have no identifier, as their
+	/// variables are chosen freely by the client.
+	pub id: Option<String>,
+	/// The IP address of the client
+	pub ip: Option<String>,
+}
+
+impl AuthAttempt {
+	/// Identifies an attempt from the variables of a SIGNIN or SIGNUP request
+	pub(crate) fn new(session: &Session, vars: &Object) -> Self {
+		let get = |keys: &[&str]| keys.iter().find_map(|k| vars.get(*k)).map(|v| v.to_raw_string());
+		let ac = get(&["AC", "ac"]);
+		// The SIGNIN clause of a record access method defines its own variables,
+		// so a `user` variable would let a client start a new identity on every
+		// attempt. Those attempts are identified by the access method instead.
+		let id = match ac {
+			Some(_) => {
+				get(&["key"]).and_then(|key| super::signin::validate_grant_bearer(&key).ok())
+			}
+			None => get(&["user"]),
+		};
+		Self {
+			ns: get(&["NS", "ns"]),
+			db: get(&["DB", "db"]),
+			ac,
+			id,
+			ip: session.ip.clone(),
+		}
+	}
+}
+
+/// The thresholds used when throttling authentication attempts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuthThrottleLimits {
+	/// The number of failed attempts allowed within the window
+	pub attempts: u32,
+	/// The duration of the sliding window
+	pub window: Duration,
+}
+
+/// A hook which counts the authentication attempts.
+///
+/// The default implementation keeps the attempts in memory, so an embedding
+/// application running several instances can provide an implementation
+/// backed by shared infrastructure with [`Datastore::with_auth_throttle`].
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub trait AuthThrottle: Send + Sync {
+	/// Checks whether an attempt is allowed, and reserves it if so.
+	/// Returns the duration after which the attempt can be retried otherwise.
+	///
+	/// The reserved attempt counts towards the limit until its outcome is
+	/// recorded with [`AuthThrottle::failure`] or [`AuthThrottle::success`],
+	/// so that concurrent attempts can not all pass the check.
+	async fn check(
+		&self,
+		attempt: &AuthAttempt,
+		limits: &AuthThrottleLimits,
+	) -> Result<Option<Duration>>;
+	/// Records a failed attempt
+	async fn failure(&self, attempt: &AuthAttempt, limits: &AuthThrottleLimits) -> Result<()>;
+	/// Records a successful attempt
+	async fn success(&self, attempt: &AuthAttempt, limits: &AuthThrottleLimits) -> Result<()>;
+}
+
+/// The attempts of an identity within the window
+#[derive(Default)]
+struct Attempts {
+	/// The times of the failed attempts
+	failures: VecDeque<Instant>,
+	/// The times of the attempts which have been checked, but not yet recorded.
+	/// These expire with the window, in case an attempt is never recorded.
+	pending: VecDeque<Instant>,
+}
+
+impl Attempts {
+	/// Removes the attempts which are outside of the window
+	fn expire(&mut self, now: Instant, window: Duration) {
+		for times in [&mut self.failures, &mut self.pending] {
+			while times.front().is_some_and(|t| now.duration_since(*t) >= window) {
+				times.pop_front();
+			}
+		}
+	}
+
+	fn is_empty(&self) -> bool {
+		self.failures.is_empty() && self.pending.is_empty()
+	}
+}
+
+/// An in-memory sliding window of the failed attempts of each identity
+#[derive(Default)]
+pub struct MemoryAuthThrottle {
+	attempts: Mutex<HashMap<AuthAttempt, Attempts>>,
+}
+
+impl MemoryAuthThrottle {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Forget the identities without any recent attempt
+	fn prune(map: &mut HashMap<AuthAttempt, Attempts>, now: Instant, window: Duration) {
+		if map.len() >= PRUNE_THRESHOLD {
+			map.retain(|_, v| {
+				v.expire(now, window);
+				!v.is_empty()
+			});
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl AuthThrottle for MemoryAuthThrottle {
+	async fn check(
+		&self,
+		attempt: &AuthAttempt,
+		limits: &AuthThrottleLimits,
+	) -> Result<Option<Duration>> {
+		let now = Instant::now();
+		let mut map = self.attempts.lock();
+		Self::prune(&mut map, now, limits.window);
+		let attempts = map.entry(attempt.clone()).or_default();
+		attempts.expire(now, limits.window);
+		if attempts.failures.len() + attempts.pending.len() < limits.attempts as usize {
+			// Reserve the attempt until its outcome is recorded
+			attempts.pending.push_back(now);
+			return Ok(None);
+		}
+		// The attempt can be retried once the oldest attempt leaves the window
+		let oldest = match (attempts.failures.front(), attempts.pending.front()) {
+			(Some(a), Some(b)) => Some(*a.min(b)),
+			(a, b) => a.or(b).copied(),
+		};
+		let retry =
+			oldest.map(|t| limits.window.saturating_sub(now.duration_since(t))).unwrap_or_default();
+		Ok(Some(retry))
+	}
+
+	async fn failure(&self, attempt: &AuthAttempt, limits: &AuthThrottleLimits) -> Result<()> {
+		let now = Instant::now();
+		let mut map = self.attempts.lock();
+		Self::prune(&mut map, now, limits.window);
+		let attempts = map.entry(attempt.clone()).or_default();
+		attempts.expire(now, limits.window);
+		// The reserved attempt is now a failed attempt
+		attempts.pending.pop_front();
+		attempts.failures.push_back(now);
+		Ok(())
+	}
+
+	async fn success(&self, attempt: &AuthAttempt, _: &AuthThrottleLimits) -> Result<()> {
+		self.attempts.lock().remove(attempt);
+		Ok(())
+	}
+}
+
+/// Runs an authentication attempt, if the throttling capabilities allow it
+pub(crate) async fn throttled<T>(
+	kvs: &Datastore,
+	attempt: AuthAttempt,
+	auth: impl Future<Output = Result<T>>,
+) -> Result<T> {
+	// Check if the attempts are throttled
+	let Some(limits) = kvs.get_capabilities().auth_throttle() else {
+		return auth.await;
+	};
+	let throttle = kvs.auth_throttle();
+	// Check if the attempt is allowed
+	if let Some(retry_after) = throttle.check(&attempt, &limits).await? {
+		debug!("Rejected an authentication attempt after too many failed attempts");
+		bail!(Error::TooManyAuthAttempts {
+			retry_after: retry_after.as_secs().max(1),
+		});
+	}
+	// Record the outcome of the attempt
+	match auth.await {
+		Ok(v) => {
+			throttle.success(&attempt, &limits).await?;
+			Ok(v)
+		}
+		Err(e) => {
+			throttle.failure(&attempt, &limits).await?;
+			Err(e)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Capabilities;
+	use crate::expr::Value;
+	use crate::iam::signin::signin;
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	fn attempt(id: &str) -> AuthAttempt {
+		AuthAttempt {
+			id: Some(id.to_string()),
+			ip: Some("127.0.0.1".to_string()),
+			..Default::default()
+		}
+	}
+
+	fn client(ip: &str) -> Session {
+		Session {
+			ip: Some(ip.to_string()),
+			..Default::default()
+		}
+	}
+
+	fn vars(user: &str, pass: &str) -> Object {
+		let mut vars = Object::default();
+		vars.insert("user".to_string(), Value::from(user));
+		vars.insert("pass".to_string(), Value::from(pass));
+		vars
+	}
+
+	#[tokio::test]
+	async fn memory_throttle_sliding_window() {
+		let throttle = MemoryAuthThrottle::new();
+		let limits = AuthThrottleLimits {
+			attempts: 2,
+			window: Duration::from_millis(200),
+		};
+		let a = attempt("a");
+		let b = attempt("b");
+		// The first failures are allowed
+		throttle.failure(&a, &limits).await.unwrap();
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+		throttle.failure(&a, &limits).await.unwrap();
+		// Further attempts are throttled until the window has passed
+		let retry = throttle.check(&a, &limits).await.unwrap().unwrap();
+		assert!(retry <= limits.window);
+		// Other identities are not affected
+		assert_eq!(throttle.check(&b, &limits).await.unwrap(), None);
+		// The failures expire after the window
+		tokio::time::sleep(limits.window).await;
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+		// A success resets the failures
+		throttle.failure(&a, &limits).await.unwrap();
+		throttle.failure(&a, &limits).await.unwrap();
+		throttle.success(&a, &limits).await.unwrap();
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn memory_throttle_reserves_attempts() {
+		let throttle = MemoryAuthThrottle::new();
+		let limits = AuthThrottleLimits {
+			attempts: 2,
+			window: Duration::from_secs(60),
+		};
+		let a = attempt("a");
+		// Attempts are counted when they are checked
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+		assert!(throttle.check(&a, &limits).await.unwrap().is_some());
+		// Recording a failure does not count the attempt twice
+		throttle.failure(&a, &limits).await.unwrap();
+		assert!(throttle.check(&a, &limits).await.unwrap().is_some());
+		// A success releases the reserved attempts
+		throttle.success(&a, &limits).await.unwrap();
+		assert_eq!(throttle.check(&a, &limits).await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn signin_throttled_without_revealing_users() {
+		let limits = AuthThrottleLimits {
+			attempts: 2,
+			window: Duration::from_secs(60),
+		};
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_auth_throttle(Some(limits)));
+		let sess = Session::owner();
+		ds.execute("DEFINE USER user ON ROOT PASSWORD 'pass'", &sess, None).await.unwrap();
+		// The existing and the missing users are throttled in the same way
+		for user in ["user", "missing"] {
+			for _ in 0..limits.attempts {
+				let mut sess = client("127.0.0.1");
+				let err = signin(&ds, &mut sess, vars(user, "invalid")).await.unwrap_err();
+				assert!(matches!(err.downcast_ref(), Some(Error::InvalidAuth)), "{err}");
+			}
+			// The correct password is rejected until the window has passed
+			let mut sess = client("127.0.0.1");
+			let err = signin(&ds, &mut sess, vars(user, "pass")).await.unwrap_err();
+			assert!(
+				matches!(
+					err.downcast_ref(),
+					Some(Error::TooManyAuthAttempts {
+						retry_after: 1..=60
+					})
+				),
+				"{err}"
+			);
+		}
+		// Attempts from another client are not throttled
+		let mut sess = client("127.0.0.2");
+		signin(&ds, &mut sess, vars("user", "pass")).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn signin_throttled_concurrently() {
+		let limits = AuthThrottleLimits {
+			attempts: 2,
+			window: Duration::from_secs(60),
+		};
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_auth_throttle(Some(limits)));
+		let sess = Session::owner();
+		ds.execute("DEFINE USER user ON ROOT PASSWORD 'pass'", &sess, None).await.unwrap();
+		// Attempts which are started together can not exceed the limit
+		let attempts = (0..10).map(|_| async {
+			let mut sess = client("127.0.0.1");
+			signin(&ds, &mut sess, vars("user", "invalid")).await.unwrap_err()
+		});
+		let errors = futures::future::join_all(attempts).await;
+		let invalid =
+			errors.iter().filter(|e| matches!(e.downcast_ref(), Some(Error::InvalidAuth))).count();
+		let throttled = errors
+			.iter()
+			.filter(|e| matches!(e.downcast_ref(), Some(Error::TooManyAuthAttempts { .. })))
+			.count();
+		assert_eq!(invalid, limits.attempts as usize);
+		assert_eq!(throttled, errors.len() - invalid);
+	}
+
+	#[tokio::test]
+	async fn record_signin_throttled_with_rotating_users() {
+		let limits = AuthThrottleLimits {
+			attempts: 2,
+			window: Duration::from_secs(60),
+		};
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_auth_throttle(Some(limits)));
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS user ON DATABASE TYPE RECORD
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				)
+				DURATION FOR SESSION 2h;
+			CREATE user:test CONTENT { name: 'user', pass: crypto::argon2::generate('pass') };
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+		let record = |user: &str, pass: &str| {
+			let mut vars = vars(user, pass);
+			vars.insert("NS".to_string(), Value::from("test"));
+			vars.insert("DB".to_string(), Value::from("test"));
+			vars.insert("AC".to_string(), Value::from("user"));
+			vars
+		};
+		// A new user variable on every attempt does not start a new identity
+		for i in 0..limits.attempts {
+			let mut sess = client("127.0.0.1");
+			signin(&ds, &mut sess, record(&format!("user{i}"), "invalid")).await.unwrap_err();
+		}
+		let mut sess = client("127.0.0.1");
+		let err = signin(&ds, &mut sess, record("user", "pass")).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::TooManyAuthAttempts { .. })), "{err}");
+		// Attempts from another client are not throttled
+		let mut sess = client("127.0.0.2");
+		signin(&ds, &mut sess, record("user", "pass")).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn signin_with_custom_throttle() {
+		#[derive(Default)]
+		struct Counter(AtomicUsize, AtomicUsize);
+
+		#[async_trait::async_trait]
+		impl AuthThrottle for Counter {
+			async fn check(
+				&self,
+				_: &AuthAttempt,
+				_: &AuthThrottleLimits,
+			) -> Result<Option<Duration>> {
+				Ok(None)
+			}
+			async fn failure(&self, _: &AuthAttempt, _: &AuthThrottleLimits) -> Result<()> {
+				self.0.fetch_add(1, Ordering::Relaxed);
+				Ok(())
+			}
+			async fn success(&self, _: &AuthAttempt, _: &AuthThrottleLimits) -> Result<()> {
+				self.1.fetch_add(1, Ordering::Relaxed);
+				Ok(())
+			}
+		}
+
+		let counter = Arc::new(Counter::default());
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_auth_throttle(Some(
+				AuthThrottleLimits {
+					attempts: 1,
+					window: Duration::from_secs(60),
+				},
+			)))
+			.with_auth_throttle(counter.clone());
+		let sess = Session::owner();
+		ds.execute("DEFINE USER user ON ROOT PASSWORD 'pass'", &sess, None).await.unwrap();
+		let mut sess = Session::default();
+		signin(&ds, &mut sess, vars("user", "invalid")).await.unwrap_err();
+		let mut sess = Session::default();
+		signin(&ds, &mut sess, vars("user", "pass")).await.unwrap();
+		assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+		assert_eq!(counter.1.load(Ordering::Relaxed), 1);
+	}
+}
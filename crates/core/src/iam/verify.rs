@@ -1,7 +1,7 @@
 use crate::dbs::Session;
 use crate::err::Error;
 use crate::expr::Thing;
-use crate::expr::access_type::{AccessType, Jwt, JwtAccessVerify};
+use crate::expr::access_type::{AccessType, Jwt, JwtAccess, JwtAccessVerify};
 use crate::expr::{Algorithm, Value, statements::DefineUserStatement};
 use crate::iam::access::{authenticate_generic, authenticate_record};
 #[cfg(feature = "jwks")]
@@ -178,25 +178,13 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			let de = tx.get_db_access(ns, db, ac).await?;
 			// Ensure that the transaction is cancelled
 			tx.cancel().await?;
-			// Obtain the configuration to verify the token based on the access method
-			let cf = match &de.kind {
-				AccessType::Record(at) => match &at.jwt.verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(anyhow::Error::new(Error::MissingTokenHeader("kid".to_string())))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				}?,
+			// Verify the token with the keys of the access method
+			match &de.kind {
+				AccessType::Record(at) => {
+					verify_jwt(kvs, &at.jwt, token, &token_data.header).await?
+				}
 				_ => bail!(Error::AccessMethodMismatch),
 			};
-			// Verify the token
-			verify_token(token, &cf.0, &cf.1)?;
 			// AUTHENTICATE clause
 			if let Some(au) = &de.authenticate {
 				// Setup the system session for finding the signin record
@@ -243,23 +231,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			match &de.kind {
 				// If the access type is Jwt or Bearer, this is database access
 				AccessType::Jwt(_) | AccessType::Bearer(_) => {
-					let cf = match &de.kind.jwt().verify {
-						JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-						#[cfg(feature = "jwks")]
-						JwtAccessVerify::Jwks(jwks) => {
-							if let Some(kid) = token_data.header.kid {
-								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-							} else {
-								Err(anyhow::Error::new(Error::MissingTokenHeader(
-									"kid".to_string(),
-								)))
-							}
-						}
-						#[cfg(not(feature = "jwks"))]
-						_ => bail!(Error::AccessMethodMismatch),
-					}?;
-					// Verify the token
-					verify_token(token, &cf.0, &cf.1)?;
+					// Verify the token with the keys of the access method
+					verify_jwt(kvs, de.kind.jwt(), token, &token_data.header).await?;
 					// AUTHENTICATE clause
 					if let Some(au) = &de.authenticate {
 						// Setup the system session for executing the clause
@@ -303,24 +276,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				AccessType::Record(at) => match &de.authenticate {
 					Some(au) => {
 						trace!("Access method `{}` is record access with AUTHENTICATE clause", ac);
-						let cf = match &at.jwt.verify {
-							JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-							#[cfg(feature = "jwks")]
-							JwtAccessVerify::Jwks(jwks) => {
-								if let Some(kid) = token_data.header.kid {
-									jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-								} else {
-									Err(anyhow::Error::new(Error::MissingTokenHeader(
-										"kid".to_string(),
-									)))
-								}
-							}
-							#[cfg(not(feature = "jwks"))]
-							_ => bail!(Error::AccessMethodMismatch),
-						}?;
-
-						// Verify the token
-						verify_token(token, &cf.0, &cf.1)?;
+						// Verify the token with the keys of the access method
+						verify_jwt(kvs, &at.jwt, token, &token_data.header).await?;
 						// AUTHENTICATE clause
 						// Setup the system session for finding the signin record
 						let mut sess = Session::editor().with_ns(ns).with_db(db);
@@ -401,25 +358,13 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			let de = tx.get_ns_access(ns, ac).await?;
 			// Ensure that the transaction is cancelled
 			tx.cancel().await?;
-			// Obtain the configuration to verify the token based on the access method
-			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							bail!(Error::MissingTokenHeader("kid".to_string()))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				},
+			// Verify the token with the keys of the access method
+			match &de.kind {
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					verify_jwt(kvs, de.kind.jwt(), token, &token_data.header).await?
+				}
 				_ => bail!(Error::AccessMethodMismatch),
-			}?;
-			// Verify the token
-			verify_token(token, &cf.0, &cf.1)?;
+			};
 			// AUTHENTICATE clause
 			if let Some(au) = &de.authenticate {
 				// Setup the system session for executing the clause
@@ -507,25 +452,13 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			let de = tx.get_root_access(ac).await?;
 			// Ensure that the transaction is cancelled
 			tx.cancel().await?;
-			// Obtain the configuration to verify the token based on the access method
-			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							bail!(Error::MissingTokenHeader("kid".to_string()))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				},
+			// Verify the token with the keys of the access method
+			match &de.kind {
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					verify_jwt(kvs, de.kind.jwt(), token, &token_data.header).await?
+				}
 				_ => bail!(Error::AccessMethodMismatch),
-			}?;
-			// Verify the token
-			verify_token(token, &cf.0, &cf.1)?;
+			};
 			// AUTHENTICATE clause
 			if let Some(au) = &de.authenticate {
 				// Setup the system session for executing the clause
@@ -677,6 +610,57 @@ fn verify_pass(pass: &str, hash: &str) -> Result<()> {
 	}
 }
 
+/// Verifies a token with the keys of a JWT access method.
+///
+/// When the `kid` header of the token identifies one of the additional
+/// keys, the token is only verified with that key. Otherwise, the token is
+/// verified with the primary key first, and then with each of the additional
+/// keys in order, so that tokens signed with a previous key remain valid
+/// while the keys are being rotated.
+#[cfg_attr(not(feature = "jwks"), expect(unused_variables))]
+async fn verify_jwt(
+	kvs: &Datastore,
+	jwt: &JwtAccess,
+	token: &str,
+	header: &jsonwebtoken::Header,
+) -> Result<()> {
+	// Check if the key identifier matches an additional key
+	if let Some(key) = header.kid.as_deref().and_then(|kid| jwt.key(kid)) {
+		let cf = config(key.alg, key.key.as_bytes())?;
+		return verify_token(token, &cf.0, &cf.1);
+	}
+	// Obtain the configuration of the primary key
+	let cf = match &jwt.verify {
+		JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
+		#[cfg(feature = "jwks")]
+		JwtAccessVerify::Jwks(jwks) => {
+			if let Some(kid) = &header.kid {
+				jwks::config(kvs, kid, &jwks.url, header.alg).await
+			} else {
+				Err(anyhow::Error::new(Error::MissingTokenHeader("kid".to_string())))
+			}
+		}
+		#[cfg(not(feature = "jwks"))]
+		_ => bail!(Error::AccessMethodMismatch),
+	};
+	// Verify the token with the primary key
+	let err = match cf.and_then(|cf| verify_token(token, &cf.0, &cf.1)) {
+		Ok(()) => return Ok(()),
+		Err(e) => e,
+	};
+	// Fall back to each of the additional keys
+	for key in jwt.keys.iter() {
+		let Ok(cf) = config(key.alg, key.key.as_bytes()) else {
+			continue;
+		};
+		if decode::<Claims>(token, &cf.0, &cf.1).is_ok() {
+			trace!("Verified the token with the additional key `{}`", key.kid);
+			return Ok(());
+		}
+	}
+	Err(err)
+}
+
 fn verify_token(token: &str, key: &DecodingKey, validation: &Validation) -> Result<()> {
 	match decode::<Claims>(token, key, validation) {
 		Ok(_) => Ok(()),
@@ -2056,4 +2040,103 @@ mod tests {
 			}
 		}
 	}
+
+	#[tokio::test]
+	async fn test_token_db_rotated_keys() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS token ON DATABASE TYPE JWT
+				ALGORITHM HS512 KEY 'new_secret'
+				WITH KEY 'old' ALGORITHM HS256 KEY 'old_secret'
+				DURATION FOR SESSION 2h
+			;
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		let claims = Claims {
+			iss: Some("surrealdb-test".to_string()),
+			iat: Some(Utc::now().timestamp()),
+			nbf: Some(Utc::now().timestamp()),
+			exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			ac: Some("token".to_string()),
+			..Claims::default()
+		};
+		let old_key = EncodingKey::from_secret("old_secret".as_ref());
+		let old_header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::HS256);
+		let old_header_with_kid = jsonwebtoken::Header {
+			kid: Some("old".to_string()),
+			..old_header.clone()
+		};
+		let new_key = EncodingKey::from_secret("new_secret".as_ref());
+
+		// Test with a token signed with the primary key
+		{
+			let enc = encode(&HEADER, &claims, &new_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.ac, Some("token".to_string()));
+		}
+
+		// Test with a token signed with the previous key and its key identifier
+		{
+			let enc = encode(&old_header_with_kid, &claims, &old_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.ac, Some("token".to_string()));
+		}
+
+		// Test with a token signed with the previous key without a key identifier
+		{
+			let enc = encode(&old_header, &claims, &old_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+		}
+
+		// Test with a token which does not match the key of its key identifier
+		{
+			let enc = encode(&old_header_with_kid, &claims, &new_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		// Remove the previous key once the rotation is complete
+		let res = ds
+			.execute("ALTER ACCESS token ON DATABASE REMOVE KEY 'old'", &sess, None)
+			.await
+			.unwrap();
+		res.into_iter().for_each(|r| {
+			r.result.unwrap();
+		});
+
+		// Test that the tokens signed with the previous key are rejected
+		for header in [&old_header, &old_header_with_kid] {
+			let enc = encode(header, &claims, &old_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			match res.unwrap_err().downcast().expect("Unexpected error kind") {
+				Error::InvalidAuth => {}
+				e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+			}
+		}
+
+		// Test that the tokens signed with the primary key are still accepted
+		{
+			let enc = encode(&HEADER, &claims, &new_key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+		}
+	}
 }
@@ -7,6 +7,7 @@ use crate::idx::trees::store::IndexStores;
 use anyhow::Result;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::HashSet;
 
 #[derive(Clone, Copy)]
 pub(super) enum FilteringStage {
@@ -21,6 +22,10 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	Stopwords(HashSet<String>),
+	/// The replacements, with the longest substrings first
+	Mapping(Vec<(String, String)>),
+	Length(u16, u16),
 }
 
 impl Filter {
@@ -55,6 +60,13 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(words) => Filter::Stopwords(words.iter().cloned().collect()),
+			SqlFilter::Mapping(map) => {
+				let mut map: Vec<_> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+				map.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+				Filter::Mapping(map)
+			}
+			SqlFilter::Length(min, max) => Filter::Length(*min, *max),
 		};
 		Ok(f)
 	}
@@ -106,6 +118,9 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::Stopwords(w) => Self::stopwords(w, c),
+			Filter::Mapping(m) => Self::mapping(m, c),
+			Filter::Length(min, max) => Self::length(c, *min, *max),
 		}
 	}
 
@@ -140,6 +155,42 @@ impl Filter {
 		Self::check_term(c, s.stem(&c.to_lowercase()).into())
 	}
 
+	#[inline]
+	fn stopwords(w: &HashSet<String>, c: &str) -> FilterResult {
+		if w.contains(c) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[inline]
+	fn mapping(m: &[(String, String)], c: &str) -> FilterResult {
+		let mut s = String::with_capacity(c.len());
+		let mut rest = c;
+		while let Some(ch) = rest.chars().next() {
+			// The longest substring which starts here is replaced
+			if let Some((k, v)) = m.iter().find(|(k, _)| rest.starts_with(k.as_str())) {
+				s.push_str(v);
+				rest = &rest[k.len()..];
+			} else {
+				s.push(ch);
+				rest = &rest[ch.len_utf8()..];
+			}
+		}
+		Self::check_term(c, s)
+	}
+
+	#[inline]
+	fn length(c: &str, min: u16, max: u16) -> FilterResult {
+		let l = c.chars().count();
+		if l < min as usize || l > max as usize {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
 	#[inline]
 	fn ngram(c: &str, min: u16, max: u16) -> FilterResult {
 		let min = min as usize;
@@ -900,4 +951,34 @@ mod tests {
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_stopwords() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS lowercase,stopwords(['the', 'a']);",
+			"The cat and a dog",
+			&["cat", "and", "dog"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_mapping() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS lowercase,mapping({ 'ß': 'ss', 'ss': 's', ',': '' });",
+			"Die Straße, ist groß!",
+			&["die", "strasse", "ist", "gross", "!"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_length() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS length(2,5);",
+			"A cat is sleeping.",
+			&["cat", "is"],
+		)
+		.await;
+	}
 }
@@ -1,13 +1,16 @@
+use crate::cnf::FULLTEXT_FUZZY_MAX_EXPANSIONS;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::statements::DefineAnalyzerStatement;
 use crate::expr::{FlowResultExt as _, Value};
 use crate::expr::{Function, Strand};
+use crate::idx::ft::Fuzziness;
 use crate::idx::ft::analyzer::filter::FilteringStage;
 use crate::idx::ft::analyzer::tokenizer::{Tokenizer, Tokens};
 use crate::idx::ft::doclength::DocLength;
 use crate::idx::ft::offsets::{Offset, OffsetRecords};
+use crate::idx::ft::phrase::{Phrase, TermPosition};
 use crate::idx::ft::postings::TermFrequency;
 use crate::idx::ft::terms::{TermId, TermLen, Terms};
 use crate::idx::trees::store::IndexStores;
@@ -28,26 +31,23 @@ pub(crate) struct Analyzer {
 	filters: Arc<Option<Vec<Filter>>>,
 }
 
-pub(in crate::idx) type TermsList = Vec<Option<(TermId, TermLen)>>;
+/// A term of the index matched by a query term: the id and length of
+/// the term, and the number of edits from the query term
+pub(in crate::idx) type MatchedTerm = (TermId, TermLen, Fuzziness);
 
+/// For each query term, the terms of the index it matches,
+/// or `None` if it does not match any term of the index
+pub(in crate::idx) type TermsList = Vec<Option<Vec<MatchedTerm>>>;
+
+/// The terms of a value which are known in the index
 pub(in crate::idx) struct TermsSet {
 	set: HashSet<TermId>,
-	has_unknown_terms: bool,
 }
 
 impl TermsSet {
-	/// If the query TermsSet contains terms that are unknown in the index
-	/// of if there is no terms in the set then
-	/// we are sure that it does not match any document
-	pub(in crate::idx) fn is_matchable(&self) -> bool {
-		!(self.has_unknown_terms || self.set.is_empty())
-	}
-
-	pub(in crate::idx) fn is_subset(&self, other: &TermsSet) -> bool {
-		if self.has_unknown_terms {
-			return false;
-		}
-		self.set.is_subset(&other.set)
+	/// Checks if the set contains one of the terms matched by a query term
+	pub(in crate::idx) fn contains_any(&self, matched: &[MatchedTerm]) -> bool {
+		matched.iter().any(|(term_id, _, _)| self.set.contains(term_id))
 	}
 }
 
@@ -66,35 +66,37 @@ impl Analyzer {
 		opt: &Options,
 		t: &Terms,
 		content: String,
-	) -> Result<(TermsList, TermsSet)> {
+		fuzziness: Option<Fuzziness>,
+	) -> Result<TermsList> {
 		let tokens = self.generate_tokens(stk, ctx, opt, FilteringStage::Querying, content).await?;
+		// A fuzziness of zero is an exact match
+		let fuzziness = fuzziness.filter(|f| *f > 0);
 		// We extract the term ids
 		let mut list = Vec::with_capacity(tokens.list().len());
 		let mut unique_tokens = HashSet::new();
-		let mut set = HashSet::new();
 		let tx = ctx.tx();
-		let mut has_unknown_terms = false;
 		for token in tokens.list() {
 			// Tokens can contains duplicated, not need to evaluate them again
 			if unique_tokens.insert(token) {
 				// Is the term known in the index?
-				let opt_term_id = t.get_term_id(&tx, tokens.get_token_string(token)?).await?;
-				list.push(opt_term_id.map(|tid| (tid, token.get_char_len())));
-				if let Some(term_id) = opt_term_id {
-					set.insert(term_id);
+				let term = tokens.get_token_string(token)?;
+				let opt_term_id = t.get_term_id(&tx, term).await?;
+				let mut matched: Vec<MatchedTerm> =
+					opt_term_id.map(|tid| (tid, token.get_char_len(), 0)).into_iter().collect();
+				if let Some(fuzziness) = fuzziness {
+					// The analyzed token is expanded to the close terms of the index
+					let limit = *FULLTEXT_FUZZY_MAX_EXPANSIONS;
+					matched.extend(t.get_fuzzy_term_ids(&tx, term, fuzziness, limit).await?);
+				}
+				if matched.is_empty() {
+					list.push(None);
 				} else {
-					has_unknown_terms = true;
+					list.push(Some(matched));
 				}
 			}
 		}
 		drop(tx);
-		Ok((
-			list,
-			TermsSet {
-				set,
-				has_unknown_terms,
-			},
-		))
+		Ok(list)
 	}
 
 	pub(in crate::idx) async fn extract_indexing_terms(
@@ -108,21 +110,17 @@ impl Analyzer {
 		let mut tv = Vec::new();
 		self.analyze_value(stk, ctx, opt, content, FilteringStage::Indexing, &mut tv).await?;
 		let mut set = HashSet::new();
-		let mut has_unknown_terms = false;
 		let tx = ctx.tx();
 		for tokens in tv {
 			for token in tokens.list() {
 				if let Some(term_id) = t.get_term_id(&tx, tokens.get_token_string(token)?).await? {
 					set.insert(term_id);
-				} else {
-					has_unknown_terms = true;
 				}
 			}
 		}
 		drop(tx);
 		Ok(TermsSet {
 			set,
-			has_unknown_terms,
 		})
 	}
 
@@ -212,6 +210,69 @@ impl Analyzer {
 		Ok((dl, tfid, osid))
 	}
 
+	/// Extracts the terms of each value of a document, with their positions,
+	/// along with the field of the value. It is used to find the phrases of
+	/// a query in the document. The values are numbered as they are indexed.
+	pub(in crate::idx) async fn extract_term_positions(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		content: Vec<Value>,
+	) -> Result<Vec<(usize, Vec<TermPosition>)>> {
+		let mut res = Vec::new();
+		for (field, v) in content.into_iter().enumerate() {
+			let mut inputs = Vec::new();
+			self.analyze_value(stk, ctx, opt, v, FilteringStage::Indexing, &mut inputs).await?;
+			for tks in &inputs {
+				res.push((field, Self::term_positions(tks)?));
+			}
+		}
+		Ok(res)
+	}
+
+	/// Extracts the terms of each position of a phrase of a query
+	pub(in crate::idx) async fn extract_phrase(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		text: String,
+		slop: u32,
+	) -> Result<Phrase> {
+		let tokens = self.generate_tokens(stk, ctx, opt, FilteringStage::Querying, text).await?;
+		let mut positions: Vec<HashSet<String>> = Vec::new();
+		for tp in Self::term_positions(&tokens)? {
+			match positions.get_mut(tp.position as usize) {
+				Some(terms) => {
+					terms.insert(tp.term);
+				}
+				None => positions.push(HashSet::from([tp.term])),
+			}
+		}
+		Ok(Phrase::new(positions, slop))
+	}
+
+	fn term_positions(tks: &Tokens) -> Result<Vec<TermPosition>> {
+		let mut res: Vec<TermPosition> = Vec::with_capacity(tks.list().len());
+		for tk in tks.list() {
+			let o = tk.new_offset(0);
+			// The terms generated from the same token share its position
+			let position = match res.last() {
+				Some(last) if last.start == o.start => last.position,
+				Some(last) => last.position + 1,
+				None => 0,
+			};
+			res.push(TermPosition {
+				term: tks.get_token_string(tk)?.to_owned(),
+				position,
+				start: o.start,
+				end: o.end,
+			});
+		}
+		Ok(res)
+	}
+
 	/// Was marked recursive
 	async fn analyze_content(
 		&self,
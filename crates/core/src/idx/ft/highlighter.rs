@@ -1,7 +1,8 @@
 use crate::err::Error;
 use crate::expr::{Array, Idiom, Object, Value};
 use crate::idx::ft::offsets::{Offset, Position};
-use anyhow::{Result, ensure};
+use crate::idx::ft::phrase::PhraseMatch;
+use anyhow::{Result, bail, ensure};
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::btree_map::Entry as BEntry;
@@ -14,25 +15,82 @@ pub(crate) struct HighlightParams {
 	pub(crate) suffix: Value,
 	pub(crate) match_ref: Value,
 	pub(crate) partial: bool,
+	pub(crate) fragment_size: Option<usize>,
+	pub(crate) max_fragments: usize,
 }
 
 impl HighlightParams {
+	pub(crate) fn new(prefix: Value, suffix: Value, match_ref: Value, partial: bool) -> Self {
+		Self {
+			prefix,
+			suffix,
+			match_ref,
+			partial,
+			fragment_size: None,
+			max_fragments: 1,
+		}
+	}
+
+	/// Create the highlight parameters from an options object, in the form
+	/// `{ prefix, suffix, partial, fragment_size, max_fragments }`
+	pub(crate) fn from_options(match_ref: Value, opts: Object) -> Result<Self> {
+		let invalid = |message: String| Error::InvalidArguments {
+			name: String::from("search::highlight"),
+			message,
+		};
+		let mut hlp = Self::new(Value::from("<em>"), Value::from("</em>"), match_ref, false);
+		for (k, v) in opts {
+			match (k.as_str(), v) {
+				("prefix", v @ Value::Strand(_)) => hlp.prefix = v,
+				("suffix", v @ Value::Strand(_)) => hlp.suffix = v,
+				("partial", Value::Bool(v)) => hlp.partial = v,
+				("fragment_size", Value::Number(n)) if n.is_int() && n.to_int() > 0 => {
+					hlp.fragment_size = Some(n.to_int() as usize);
+				}
+				("max_fragments", Value::Number(n)) if n.is_int() && n.to_int() > 0 => {
+					hlp.max_fragments = n.to_int() as usize;
+				}
+				("prefix" | "suffix", v) => {
+					bail!(invalid(format!(
+						"The option '{k}' must be a string, found `{}`",
+						v.kindof()
+					)))
+				}
+				("partial", v) => {
+					bail!(invalid(format!(
+						"The option '{k}' must be a bool, found `{}`",
+						v.kindof()
+					)))
+				}
+				("fragment_size" | "max_fragments", v) => {
+					bail!(invalid(format!(
+						"The option '{k}' must be a positive integer, found `{v}`"
+					)))
+				}
+				(k, _) => bail!(invalid(format!("Unknown option '{k}'"))),
+			}
+		}
+		Ok(hlp)
+	}
+
 	pub(crate) fn match_ref(&self) -> &Value {
 		&self.match_ref
 	}
 }
 
 pub(super) struct Highlighter {
-	prefix: Vec<char>,
-	suffix: Vec<char>,
+	prefix: String,
+	suffix: String,
 	fields: Vec<(Idiom, Value)>,
 	offseter: Offseter,
+	fragment_size: Option<usize>,
+	max_fragments: usize,
 }
 
 impl Highlighter {
 	pub(super) fn new(hlp: HighlightParams, idiom: &Idiom, doc: &Value) -> Self {
-		let prefix = hlp.prefix.to_raw_string().chars().collect();
-		let suffix = hlp.suffix.to_raw_string().chars().collect();
+		let prefix = hlp.prefix.to_raw_string();
+		let suffix = hlp.suffix.to_raw_string();
 		// Extract the fields we want to highlight
 		let fields = doc.walk(idiom);
 		Self {
@@ -40,6 +98,8 @@ impl Highlighter {
 			prefix,
 			suffix,
 			offseter: Offseter::new(hlp.partial),
+			fragment_size: hlp.fragment_size,
+			max_fragments: hlp.max_fragments,
 		}
 	}
 
@@ -47,6 +107,10 @@ impl Highlighter {
 		self.offseter.highlight(term_len, os);
 	}
 
+	pub(super) fn highlight_phrases(&mut self, phrases: &[PhraseMatch]) {
+		self.offseter.highlight_phrases(phrases);
+	}
+
 	fn extract(val: Value, vals: &mut Vec<String>) {
 		match val {
 			Value::Strand(s) => vals.push(s.0),
@@ -65,6 +129,112 @@ impl Highlighter {
 			_ => {}
 		}
 	}
+
+	/// Render the characters between `beg` and `end`, surrounding
+	/// each highlighted range with the prefix and the suffix.
+	/// Offsets are expressed in characters, so a code point is never split.
+	fn mark(
+		&self,
+		chars: &[char],
+		beg: usize,
+		end: usize,
+		offsets: Option<&BTreeMap<Position, Position>>,
+	) -> Result<String> {
+		let mut out = String::new();
+		let mut cursor = beg;
+		for (s, e) in offsets.into_iter().flatten() {
+			let (s, e) = (*s as usize, *e as usize);
+			ensure!(
+				e <= chars.len(),
+				Error::HighlightError(format!("position overflow: {e} - len: {}", chars.len()))
+			);
+			// Skip the ranges outside of this fragment
+			if s < beg || s >= end {
+				continue;
+			}
+			// Overlapping ranges continue after the previous one
+			let s = s.max(cursor);
+			let e = e.min(end);
+			if e <= s {
+				continue;
+			}
+			out.extend(&chars[cursor..s]);
+			out.push_str(&self.prefix);
+			out.extend(&chars[s..e]);
+			out.push_str(&self.suffix);
+			cursor = e;
+		}
+		out.extend(&chars[cursor..end]);
+		Ok(out)
+	}
+}
+
+/// Compute the fragment windows around each highlighted range. Each window is
+/// centred on its range, its edges are moved to the nearest whitespace within
+/// the window, and overlapping windows are merged together.
+fn fragments(
+	chars: &[char],
+	size: usize,
+	offsets: &BTreeMap<Position, Position>,
+) -> Vec<(usize, usize)> {
+	let len = chars.len();
+	let mut windows: Vec<(usize, usize)> = Vec::new();
+	for (s, e) in offsets {
+		let e = (*e as usize).min(len);
+		let s = (*s as usize).min(e);
+		// Centre the window on the highlighted range
+		let pad = size.saturating_sub(e - s) / 2;
+		let mut beg = s.saturating_sub(pad);
+		let mut end = (e + pad).min(len);
+		// Use any unused space at one edge on the other edge
+		let spare = size.saturating_sub(end - beg);
+		if beg == 0 {
+			end = (end + spare).min(len);
+		} else if end == len {
+			beg = beg.saturating_sub(spare);
+		}
+		// Break on whitespace near the window edges
+		if beg > 0 && !chars[beg - 1].is_whitespace() {
+			if let Some(i) = (beg + 1..=s).find(|&i| chars[i - 1].is_whitespace()) {
+				beg = i;
+			}
+		}
+		if end < len && !chars[end].is_whitespace() {
+			if let Some(i) = (e..end).rev().find(|&i| chars[i].is_whitespace()) {
+				end = i;
+			}
+		}
+		// Trim the whitespace from the window edges
+		while beg < s && chars[beg].is_whitespace() {
+			beg += 1;
+		}
+		while end > e && chars[end - 1].is_whitespace() {
+			end -= 1;
+		}
+		// Merge overlapping windows
+		match windows.last_mut() {
+			Some(last) if beg <= last.1 => {
+				last.0 = last.0.min(beg);
+				last.1 = last.1.max(end);
+			}
+			_ => windows.push((beg, end)),
+		}
+	}
+	windows
+}
+
+/// Compute the leading fragment of a value without any highlighted range
+fn leading_fragment(chars: &[char], size: usize) -> (usize, usize) {
+	let mut end = size.min(chars.len());
+	if end < chars.len() && !chars[end].is_whitespace() {
+		if let Some(i) = (1..end).rev().find(|&i| chars[i].is_whitespace()) {
+			end = i;
+		}
+	}
+	while end > 0 && chars[end - 1].is_whitespace() {
+		end -= 1;
+	}
+	(0, end)
 }
 
 impl TryFrom<Highlighter> for Value {
@@ -75,39 +245,39 @@ impl TryFrom<Highlighter> for Value {
 			return Ok(Self::None);
 		}
 		let mut vals = vec![];
-		for (_, f) in hl.fields {
-			Highlighter::extract(f, &mut vals);
+		for (_, f) in &hl.fields {
+			Highlighter::extract(f.clone(), &mut vals);
 		}
 		let mut res = Vec::with_capacity(vals.len());
-		for (idx, val) in vals.into_iter().enumerate() {
-			if let Some(m) = hl.offseter.offsets.get(&(idx as u32)) {
-				let mut v: Vec<char> = val.chars().collect();
-				let mut l = v.len();
-				let mut d = 0;
-
-				// We use a closure to append the prefix and the suffix
-				let mut append = |s: u32, ix: &Vec<char>| -> Result<()> {
-					let p = (s as usize) + d;
-					ensure!(
-						p <= l,
-						Error::HighlightError(format!("position overflow: {s} - len: {l}"))
-					);
-					v.splice(p..p, ix.clone());
-					let xl = ix.len();
-					d += xl;
-					l += xl;
-					Ok(())
-				};
-
-				for (s, e) in m {
-					append(*s, &hl.prefix)?;
-					append(*e, &hl.suffix)?;
+		match hl.fragment_size {
+			None => {
+				for (idx, val) in vals.iter().enumerate() {
+					let chars: Vec<char> = val.chars().collect();
+					let m = hl.offseter.offsets.get(&(idx as u32));
+					res.push(Value::from(hl.mark(&chars, 0, chars.len(), m)?));
+				}
+			}
+			Some(size) => {
+				for (idx, val) in vals.iter().enumerate() {
+					if let Some(m) = hl.offseter.offsets.get(&(idx as u32)) {
+						let chars: Vec<char> = val.chars().collect();
+						for (beg, end) in fragments(&chars, size, m) {
+							res.push(Value::from(hl.mark(&chars, beg, end, Some(m))?));
+						}
+					}
+				}
+				// Without any match, fall back to the start of the first value
+				if res.is_empty() {
+					if let Some(val) = vals.first() {
+						let chars: Vec<char> = val.chars().collect();
+						let (beg, end) = leading_fragment(&chars, size);
+						res.push(Value::from(hl.mark(&chars, beg, end, None)?));
+					}
+				}
+				res.truncate(hl.max_fragments);
+				if hl.max_fragments > 1 {
+					return Ok(Value::from(res));
 				}
-
-				let s: String = v.iter().collect();
-				res.push(Value::from(s));
-			} else {
-				res.push(Value::from(val));
 			}
 		}
 		Ok(match res.len() {
@@ -157,6 +327,16 @@ impl Offseter {
 			}
 		}
 	}
+
+	/// Each phrase is highlighted as a whole, from its first to its last term
+	pub(super) fn highlight_phrases(&mut self, phrases: &[PhraseMatch]) {
+		for p in phrases {
+			let end = self.offsets.entry(p.index).or_default().entry(p.start).or_insert(p.end);
+			if p.end > *end {
+				*end = p.end;
+			}
+		}
+	}
 }
 
 impl TryFrom<Offseter> for Value {
@@ -182,3 +362,92 @@ impl TryFrom<Offseter> for Value {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{Highlighter, Offseter};
+	use crate::expr::{Idiom, Value};
+	use std::collections::{BTreeMap, HashMap};
+
+	fn highlighter(
+		text: &str,
+		offsets: &[(u32, u32)],
+		fragment_size: Option<usize>,
+		max_fragments: usize,
+	) -> Highlighter {
+		Highlighter {
+			prefix: "<em>".to_string(),
+			suffix: "</em>".to_string(),
+			fields: vec![(Idiom::default(), Value::from(text))],
+			offseter: Offseter {
+				partial: false,
+				offsets: HashMap::from([(0, offsets.iter().copied().collect::<BTreeMap<_, _>>())]),
+			},
+			fragment_size,
+			max_fragments,
+		}
+	}
+
+	fn strings(v: Value) -> Vec<String> {
+		match v {
+			Value::Array(a) => a.0.into_iter().map(|v| v.to_raw_string()).collect(),
+			v => vec![v.to_raw_string()],
+		}
+	}
+
+	#[test]
+	fn highlight_whole_value() {
+		let hl = highlighter("Hello World!", &[(0, 5)], None, 1);
+		assert_eq!(Value::try_from(hl).unwrap(), Value::from("<em>Hello</em> World!"));
+	}
+
+	#[test]
+	fn highlight_fragments() {
+		let text = "one two three four five six seven eight nine ten eleven twelve";
+		// "four" is at 14..18 and "eleven" is at 49..55
+		let hl = highlighter(text, &[(14, 18), (49, 55)], Some(16), 3);
+		assert_eq!(
+			strings(Value::try_from(hl).unwrap()),
+			vec!["three <em>four</em> five", "ten <em>eleven</em>"]
+		);
+		// Only the first fragment is returned as a string
+		let hl = highlighter(text, &[(14, 18), (49, 55)], Some(16), 1);
+		assert_eq!(Value::try_from(hl).unwrap(), Value::from("three <em>four</em> five"));
+	}
+
+	#[test]
+	fn highlight_merges_overlapping_fragments() {
+		let text = "one two three four five six seven";
+		// "three" is at 8..13 and "four" is at 14..18
+		let hl = highlighter(text, &[(8, 13), (14, 18)], Some(20), 3);
+		assert_eq!(
+			strings(Value::try_from(hl).unwrap()),
+			vec!["two <em>three</em> <em>four</em> five"]
+		);
+	}
+
+	#[test]
+	fn highlight_fragments_multibyte() {
+		let text = "Ça va très bien, merci. Où est la gare? Là-bas, près du café";
+		let chars: Vec<char> = text.chars().collect();
+		let find = |w: &str| {
+			let w: Vec<char> = w.chars().collect();
+			let s = chars.windows(w.len()).position(|c| c == w.as_slice()).unwrap();
+			(s as u32, (s + w.len()) as u32)
+		};
+		let hl = highlighter(text, &[find("très"), find("café")], Some(12), 2);
+		assert_eq!(
+			strings(Value::try_from(hl).unwrap()),
+			vec!["va <em>très</em>", "près du <em>café</em>"]
+		);
+	}
+
+	#[test]
+	fn highlight_fragments_without_match() {
+		let hl = Highlighter {
+			offseter: Offseter::new(false),
+			..highlighter("one two three four", &[], Some(10), 2)
+		};
+		assert_eq!(strings(Value::try_from(hl).unwrap()), vec!["one two"]);
+	}
+}
@@ -2,7 +2,9 @@ pub(crate) mod analyzer;
 mod doclength;
 pub(crate) mod highlighter;
 mod offsets;
+pub(super) mod phrase;
 mod postings;
+pub(super) mod query;
 pub(super) mod scorer;
 pub(super) mod termdocs;
 pub(crate) mod terms;
@@ -14,14 +16,15 @@ use crate::expr::scoring::Scoring;
 use crate::expr::statements::DefineAnalyzerStatement;
 use crate::expr::{Idiom, Object, Thing, Value};
 use crate::idx::docids::{DocId, DocIds};
-use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
+use crate::idx::ft::analyzer::{Analyzer, TermsList};
 use crate::idx::ft::doclength::DocLengths;
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
 use crate::idx::ft::offsets::Offsets;
+use crate::idx::ft::phrase::PhraseMatch;
 use crate::idx::ft::postings::Postings;
 use crate::idx::ft::scorer::BM25Scorer;
 use crate::idx::ft::termdocs::{TermDocs, TermsDocs};
-use crate::idx::ft::terms::{TermId, TermLen, Terms};
+use crate::idx::ft::terms::{TermId, Terms};
 use crate::idx::trees::btree::BStatistics;
 use crate::idx::trees::store::IndexStores;
 use crate::idx::{IndexKeyBase, VersionedStore};
@@ -33,11 +36,15 @@ use revision::revisioned;
 use roaring::RoaringTreemap;
 use roaring::treemap::IntoIter;
 use serde::{Deserialize, Serialize};
-use std::ops::BitAnd;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 pub(crate) type MatchRef = u8;
+/// A number of edits between a query term and a term of the index
+pub(crate) type Fuzziness = u8;
+
+/// The highest fuzziness allowed on the matches operator
+pub(crate) const MAX_FUZZINESS: Fuzziness = 2;
 
 pub(crate) struct FtIndex {
 	analyzer: Analyzer,
@@ -87,6 +94,23 @@ impl From<FtStatistics> for Value {
 	}
 }
 
+/// The statistics of a term of the dictionary
+pub(crate) struct TermStatistics {
+	/// The number of documents containing the term
+	doc_freq: u64,
+	/// The number of occurrences of the term in all the documents
+	term_freq: u64,
+}
+
+impl From<TermStatistics> for Value {
+	fn from(stats: TermStatistics) -> Self {
+		let mut res = Object::default();
+		res.insert("df".to_owned(), Value::from(stats.doc_freq));
+		res.insert("tf".to_owned(), Value::from(stats.term_freq));
+		Value::from(res)
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Default, Serialize, Deserialize)]
 struct State {
@@ -352,9 +376,13 @@ impl FtIndex {
 		ctx: &Context,
 		opt: &Options,
 		query_string: String,
-	) -> Result<(TermsList, TermsSet)> {
+		fuzziness: Option<Fuzziness>,
+	) -> Result<TermsList> {
 		let t = self.terms.read().await;
-		let res = self.analyzer.extract_querying_terms(stk, ctx, opt, &t, query_string).await?;
+		let res = self
+			.analyzer
+			.extract_querying_terms(stk, ctx, opt, &t, query_string, fuzziness)
+			.await?;
 		drop(t);
 		Ok(res)
 	}
@@ -363,16 +391,16 @@ impl FtIndex {
 		&self,
 		tx: &Transaction,
 		terms: &TermsList,
-	) -> Result<Vec<Option<(TermId, RoaringTreemap)>>> {
+	) -> Result<Vec<Option<Vec<(TermId, Fuzziness, RoaringTreemap)>>>> {
 		let mut terms_docs = Vec::with_capacity(terms.len());
 		for opt_term in terms {
-			if let Some((term_id, _)) = opt_term {
-				let docs = self.term_docs.get_docs(tx, *term_id).await?;
-				if let Some(docs) = docs {
-					terms_docs.push(Some((*term_id, docs)));
-				} else {
-					terms_docs.push(Some((*term_id, RoaringTreemap::new())));
+			if let Some(matched) = opt_term {
+				let mut matched_docs = Vec::with_capacity(matched.len());
+				for (term_id, _, fuzziness) in matched {
+					let docs = self.term_docs.get_docs(tx, *term_id).await?;
+					matched_docs.push((*term_id, *fuzziness, docs.unwrap_or_default()));
 				}
+				terms_docs.push(Some(matched_docs));
 			} else {
 				terms_docs.push(None);
 			}
@@ -380,32 +408,21 @@ impl FtIndex {
 		Ok(terms_docs)
 	}
 
-	pub(super) fn new_hits_iterator(&self, terms_docs: TermsDocs) -> Result<Option<HitsIterator>> {
-		let mut hits: Option<RoaringTreemap> = None;
-		for opt_term_docs in terms_docs.iter() {
-			if let Some((_, term_docs)) = opt_term_docs {
-				if let Some(h) = hits {
-					hits = Some(h.bitand(term_docs));
-				} else {
-					hits = Some(term_docs.clone());
-				}
-			} else {
-				return Ok(None);
-			}
-		}
-		if let Some(hits) = hits {
-			if !hits.is_empty() {
-				return Ok(Some(HitsIterator::new(self.doc_ids.clone(), hits)));
-			}
-		}
-		Ok(None)
+	pub(super) fn new_hits_iterator(&self, hits: Option<RoaringTreemap>) -> Option<HitsIterator> {
+		hits.filter(|h| !h.is_empty()).map(|h| HitsIterator::new(self.doc_ids.clone(), h))
 	}
 
-	pub(super) fn new_scorer(&self, terms_docs: TermsDocs) -> Result<Option<BM25Scorer>> {
+	/// Creates the scorer of a query, given the boost of each of its terms
+	pub(super) fn new_scorer(
+		&self,
+		terms_docs: TermsDocs,
+		boosts: Vec<f32>,
+	) -> Result<Option<BM25Scorer>> {
 		if let Some(bm25) = &self.bm25 {
 			return Ok(Some(BM25Scorer::new(
 				self.postings.clone(),
 				terms_docs,
+				boosts,
 				self.doc_lengths.clone(),
 				self.state.total_docs_lengths,
 				self.state.doc_count,
@@ -415,11 +432,13 @@ impl FtIndex {
 		Ok(None)
 	}
 
+	#[expect(clippy::too_many_arguments)]
 	pub(super) async fn highlight(
 		&self,
 		tx: &Transaction,
 		thg: &Thing,
-		terms: &[Option<(TermId, TermLen)>],
+		terms: &TermsList,
+		phrases: &[PhraseMatch],
 		hlp: HighlightParams,
 		idiom: &Idiom,
 		doc: &Value,
@@ -430,12 +449,14 @@ impl FtIndex {
 		drop(di);
 		if let Some(doc_id) = doc_id {
 			let mut hl = Highlighter::new(hlp, idiom, doc);
-			for (term_id, term_len) in terms.iter().flatten() {
+			// Each matched term is highlighted with its own length
+			for (term_id, term_len, _) in terms.iter().flatten().flatten() {
 				let o = self.offsets.get_offsets(tx, doc_id, *term_id).await?;
 				if let Some(o) = o {
 					hl.highlight(*term_len, o.0);
 				}
 			}
+			hl.highlight_phrases(phrases);
 			return hl.try_into();
 		}
 		Ok(Value::None)
@@ -445,7 +466,8 @@ impl FtIndex {
 		&self,
 		tx: &Transaction,
 		thg: &Thing,
-		terms: &[Option<(TermId, u32)>],
+		terms: &TermsList,
+		phrases: &[PhraseMatch],
 		partial: bool,
 	) -> Result<Value> {
 		let doc_key: Key = revision::to_vec(thg)?;
@@ -454,12 +476,13 @@ impl FtIndex {
 		drop(di);
 		if let Some(doc_id) = doc_id {
 			let mut or = Offseter::new(partial);
-			for (term_id, term_len) in terms.iter().flatten() {
+			for (term_id, term_len, _) in terms.iter().flatten().flatten() {
 				let o = self.offsets.get_offsets(tx, doc_id, *term_id).await?;
 				if let Some(o) = o {
 					or.highlight(*term_len, o.0);
 				}
 			}
+			or.highlight_phrases(phrases);
 			return or.try_into().map_err(anyhow::Error::new);
 		}
 		Ok(Value::None)
@@ -476,6 +499,42 @@ impl FtIndex {
 		Ok(res)
 	}
 
+	/// Computes the statistics of a term of the dictionary, which are zero
+	/// if the term is not found. The term is not analyzed.
+	pub(crate) async fn term_statistics(
+		&self,
+		ctx: &Context,
+		term: &str,
+	) -> Result<TermStatistics> {
+		let tx = ctx.tx();
+		let mut res = TermStatistics {
+			doc_freq: 0,
+			term_freq: 0,
+		};
+		let term_id = self.terms.read().await.get_term_id(&tx, term).await?;
+		if let Some(term_id) = term_id {
+			if let Some(docs) = self.term_docs.get_docs(&tx, term_id).await? {
+				res.doc_freq = docs.len();
+				let p = self.postings.read().await;
+				for doc_id in docs {
+					res.term_freq += p.get_term_frequency(&tx, term_id, doc_id).await?.unwrap_or(0);
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	/// Lists the terms of the dictionary starting with a prefix, in order
+	pub(crate) async fn terms_with_prefix(
+		&self,
+		ctx: &Context,
+		prefix: &str,
+		limit: usize,
+	) -> Result<Vec<String>> {
+		let tx = ctx.tx();
+		self.terms.read().await.get_terms_with_prefix(&tx, prefix, limit).await
+	}
+
 	pub(crate) async fn finish(&self, ctx: &Context) -> Result<()> {
 		let txn = ctx.tx();
 		self.doc_ids.write().await.finish(&txn).await?;
@@ -529,6 +588,7 @@ mod tests {
 	use crate::expr::statements::DefineAnalyzerStatement;
 	use crate::expr::{Array, Thing, Value};
 	use crate::idx::IndexKeyBase;
+	use crate::idx::ft::query::Query;
 	use crate::idx::ft::scorer::{BM25Scorer, Score};
 	use crate::idx::ft::{FtIndex, HitsIterator};
 	use crate::kvs::{Datastore, LockType::*, TransactionType};
@@ -550,7 +610,7 @@ mod tests {
 			let mut map = HashMap::new();
 			while let Some((k, d)) = hits.next(&tx).await.unwrap() {
 				yield_now!();
-				let s = scr.score(&tx, d).await.unwrap();
+				let s = scr.score(&tx, d, None).await.unwrap();
 				map.insert(k, s);
 			}
 			assert_eq!(map.len(), e.len());
@@ -569,12 +629,13 @@ mod tests {
 		fti: &FtIndex,
 		qs: &str,
 	) -> (Option<HitsIterator>, BM25Scorer) {
-		let (term_list, _) =
-			fti.extract_querying_terms(stk, ctx, opt, qs.to_string()).await.unwrap();
+		let query = Query::parse(qs, &[]).unwrap();
+		let term_list =
+			fti.extract_querying_terms(stk, ctx, opt, qs.to_string(), None).await.unwrap();
 		let tx = ctx.tx();
 		let td = Arc::new(fti.get_terms_docs(&tx, &term_list).await.unwrap());
-		let scr = fti.new_scorer(td.clone()).unwrap().unwrap();
-		let hits = fti.new_hits_iterator(td).unwrap();
+		let scr = fti.new_scorer(td.clone(), vec![1.0; td.len()]).unwrap().unwrap();
+		let hits = fti.new_hits_iterator(query.hits(&[0..td.len()], &td));
 		(hits, scr)
 	}
 
@@ -0,0 +1,203 @@
+//! The phrases of a full-text query.
+//!
+//! A phrase is written between double quotes in the query string. Its terms
+//! must appear in the same order, one after the other, in a value of the
+//! document. A phrase followed by `~n`, like `"quick fox"~3`, still matches
+//! when up to `n` other terms are found between its terms.
+use crate::idx::ft::offsets::Position;
+use std::collections::HashSet;
+
+/// An analyzed term of a document, with the position of its token
+#[derive(Debug)]
+pub(in crate::idx) struct TermPosition {
+	pub(in crate::idx) term: String,
+	/// The number of tokens before this one in the value
+	pub(in crate::idx) position: u32,
+	/// The start of the token, in characters
+	pub(in crate::idx) start: Position,
+	/// The end of the token, in characters
+	pub(in crate::idx) end: Position,
+}
+
+/// An analyzed phrase: the terms which can be found at each of its positions
+pub(in crate::idx) struct Phrase {
+	positions: Vec<HashSet<String>>,
+	slop: u32,
+}
+
+/// An occurrence of a phrase in a document
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(in crate::idx) struct PhraseMatch {
+	/// The value of the document containing the phrase
+	pub(in crate::idx) index: u32,
+	/// The start of the first term of the phrase, in characters
+	pub(in crate::idx) start: Position,
+	/// The end of the last term of the phrase, in characters
+	pub(in crate::idx) end: Position,
+	/// The number of other terms inside the phrase
+	pub(in crate::idx) slack: u32,
+}
+
+impl Phrase {
+	pub(in crate::idx) fn new(positions: Vec<HashSet<String>>, slop: u32) -> Self {
+		Self {
+			positions,
+			slop,
+		}
+	}
+
+	/// Finds the occurrences of the phrase in the analyzed values of a document,
+	/// which are given along with the field they belong to. From each occurrence
+	/// of its first term, the phrase is completed with the closest following
+	/// occurrence of each of its other terms.
+	pub(in crate::idx) fn find(
+		&self,
+		values: &[(usize, Vec<TermPosition>)],
+		field: Option<usize>,
+	) -> Vec<PhraseMatch> {
+		let mut res = Vec::new();
+		let Some((first, others)) = self.positions.split_first() else {
+			return res;
+		};
+		for (index, (f, terms)) in values.iter().enumerate() {
+			if field.is_some_and(|field| field != *f) {
+				continue;
+			}
+			let occurrences: Vec<Vec<&TermPosition>> = others
+				.iter()
+				.map(|p| terms.iter().filter(|t| p.contains(&t.term)).collect())
+				.collect();
+			'starts: for start in terms.iter().filter(|t| first.contains(&t.term)) {
+				let mut last = start;
+				for occ in &occurrences {
+					let i = occ.partition_point(|t| t.position <= last.position);
+					match occ.get(i) {
+						Some(t) => last = t,
+						// The later starts can not be completed either
+						None => break 'starts,
+					}
+				}
+				let slack = last.position - start.position - others.len() as u32;
+				if slack <= self.slop {
+					res.push(PhraseMatch {
+						index: index as u32,
+						start: start.start,
+						end: last.end,
+						slack,
+					});
+				}
+			}
+		}
+		res
+	}
+}
+
+/// The phrases of a query which were found in a document
+pub(in crate::idx) struct PhraseHits {
+	pub(in crate::idx) matches: Vec<PhraseMatch>,
+	/// The factor applied to the score of the document,
+	/// which is higher when the terms of the phrases are closer
+	pub(in crate::idx) boost: f32,
+}
+
+impl PhraseHits {
+	/// Collects the occurrences of the phrases found in a document
+	pub(in crate::idx) fn new<'a, I>(found: I) -> Self
+	where
+		I: Iterator<Item = &'a [PhraseMatch]>,
+	{
+		let mut matches = Vec::new();
+		let mut boost = 1.0;
+		for found in found {
+			if let Some(slack) = found.iter().map(|m| m.slack).min() {
+				boost *= 1.0 + 1.0 / (1.0 + slack as f32);
+				matches.extend_from_slice(found);
+			}
+		}
+		Self {
+			matches,
+			boost,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Phrase, PhraseHits, PhraseMatch, TermPosition};
+
+	fn terms(text: &str) -> Vec<TermPosition> {
+		let mut terms = Vec::new();
+		let mut start = 0;
+		for (position, term) in text.split(' ').enumerate() {
+			let len = term.chars().count() as u32;
+			terms.push(TermPosition {
+				term: term.to_owned(),
+				position: position as u32,
+				start,
+				end: start + len,
+			});
+			start += len + 1;
+		}
+		terms
+	}
+
+	fn values(text: &str) -> Vec<(usize, Vec<TermPosition>)> {
+		vec![(0, terms(text))]
+	}
+
+	fn phrase(text: &str, slop: u32) -> Phrase {
+		Phrase::new(text.split(' ').map(|t| [t.to_owned()].into()).collect(), slop)
+	}
+
+	#[test]
+	fn find_phrase() {
+		let doc = values("the quick brown fox jumps over the lazy dog");
+		// The terms must be consecutive
+		assert!(phrase("quick fox", 0).find(&doc, None).is_empty());
+		assert_eq!(
+			phrase("brown fox", 0).find(&doc, None),
+			vec![PhraseMatch {
+				index: 0,
+				start: 10,
+				end: 19,
+				slack: 0,
+			}]
+		);
+		// The terms can be apart
+		assert_eq!(
+			phrase("quick fox", 1).find(&doc, None),
+			vec![PhraseMatch {
+				index: 0,
+				start: 4,
+				end: 19,
+				slack: 1,
+			}]
+		);
+		// The terms must be in order
+		assert!(phrase("fox quick", 5).find(&doc, None).is_empty());
+		// Every occurrence is found
+		assert_eq!(phrase("the", 0).find(&doc, None).len(), 2);
+	}
+
+	#[test]
+	fn find_phrase_in_field() {
+		let doc = vec![(0, terms("a lazy dog")), (1, terms("the lazy dog"))];
+		let found = phrase("lazy dog", 0).find(&doc, Some(1));
+		assert_eq!(found.iter().map(|m| m.index).collect::<Vec<_>>(), vec![1]);
+		assert_eq!(phrase("lazy dog", 0).find(&doc, None).len(), 2);
+	}
+
+	#[test]
+	fn closer_phrases_are_boosted() {
+		let doc = values("the quick brown fox");
+		let hits =
+			|text: &str| PhraseHits::new([phrase(text, 2).find(&doc, None).as_slice()].into_iter());
+		let exact = hits("brown fox");
+		let apart = hits("quick fox");
+		assert!(exact.boost > apart.boost);
+		assert!(apart.boost > 1.0);
+		let missing = hits("fox quick");
+		assert!(missing.matches.is_empty());
+		assert_eq!(missing.boost, 1.0);
+	}
+}
@@ -0,0 +1,639 @@
+//! The query strings of the matches operator.
+//!
+//! By default, every term of a query string must be found in a document.
+//! The clauses of a query string can also be combined:
+//! - `+fox` requires a clause, and `-fox` excludes the documents matching it,
+//! - `fox OR dog` matches the documents matching either side, and binds less
+//!   tightly than the other clauses, so `quick fox OR dog` is `(quick fox) OR dog`,
+//! - `(fox dog)` groups clauses,
+//! - `"quick fox"~2` is a phrase,
+//! - `title:fox` only searches a clause in one of the fields of the index,
+//! - `fox^2` multiplies the score of the terms of a clause.
+use crate::err::Error;
+use crate::expr::statements::DefineIndexStatement;
+use crate::idx::ft::Fuzziness;
+use crate::idx::ft::termdocs::TermsDocs;
+use crate::idx::ft::terms::TermId;
+use anyhow::{Result, bail};
+use roaring::RoaringTreemap;
+use std::ops::{BitAnd, BitOr, Range};
+
+/// A text of a query string which is analyzed on its own: either consecutive
+/// terms, which must all be found in a document, or a phrase
+#[derive(Debug, PartialEq)]
+pub(in crate::idx) struct Leaf {
+	pub(in crate::idx) text: String,
+	/// The number of other terms allowed inside a phrase,
+	/// or `None` if the leaf is not a phrase
+	pub(in crate::idx) slop: Option<u32>,
+	/// The field of the index the leaf is searched in, or `None` for every field
+	pub(in crate::idx) field: Option<usize>,
+	/// The factor applied to the score of the terms of the leaf
+	pub(in crate::idx) boost: f32,
+	/// If the leaf is part of an excluded clause
+	pub(in crate::idx) excluded: bool,
+}
+
+impl Leaf {
+	/// The documents of the index containing the terms of a phrase,
+	/// or a field, do not necessarily match the leaf
+	fn is_exact(&self) -> bool {
+		self.slop.is_none() && self.field.is_none()
+	}
+}
+
+/// How the leaves of a query string are combined
+#[derive(Debug, PartialEq)]
+enum Node {
+	Leaf(usize),
+	/// Every required node must match, and none of the excluded nodes
+	All(Vec<Node>, Vec<Node>),
+	/// At least one of the nodes must match
+	Any(Vec<Node>),
+}
+
+/// A parsed query string
+#[derive(Debug, PartialEq)]
+pub(in crate::idx) struct Query {
+	leaves: Vec<Leaf>,
+	root: Node,
+}
+
+impl Query {
+	/// Parses a query string. The names of the fields of the index
+	/// are the ones which can be used to scope a clause.
+	pub(in crate::idx) fn parse(qs: &str, fields: &[String]) -> Result<Self> {
+		let tokens = Lexer::lex(qs, fields)?;
+		// An empty query string does not match any document
+		if tokens.is_empty() {
+			return Ok(Self {
+				leaves: Vec::new(),
+				root: Node::All(Vec::new(), Vec::new()),
+			});
+		}
+		let mut parser = Parser {
+			qs,
+			tokens,
+			next: 0,
+			leaves: Vec::new(),
+		};
+		let root = parser.parse_any()?;
+		if let Some(tk) = parser.tokens.get(parser.next) {
+			bail!(invalid(qs, tk.pos, "Unexpected ')'"));
+		}
+		Ok(Self {
+			leaves: parser.leaves,
+			root,
+		})
+	}
+
+	/// The names of the fields which can be used to scope a clause
+	pub(in crate::idx) fn fields(ix: &DefineIndexStatement) -> Vec<String> {
+		ix.cols.iter().map(|i| i.to_string()).collect()
+	}
+
+	pub(in crate::idx) fn leaves(&self) -> &[Leaf] {
+		&self.leaves
+	}
+
+	/// Checks if the records found with the index have to be checked again,
+	/// as the index alone can not resolve the phrases and the fields
+	pub(in crate::idx) fn needs_record_check(&self) -> bool {
+		self.leaves.iter().any(|l| !l.is_exact())
+	}
+
+	/// Combines the documents of the leaves, given the range of the query terms
+	/// of each leaf. The documents of a leaf which is not exact may not match
+	/// it, therefore they are never excluded, and the records are checked again.
+	pub(in crate::idx) fn hits(
+		&self,
+		leaf_terms: &[Range<usize>],
+		terms_docs: &TermsDocs,
+	) -> Option<RoaringTreemap> {
+		let docs: Vec<Option<RoaringTreemap>> = leaf_terms
+			.iter()
+			.map(|r| Self::leaf_hits(terms_docs.get(r.clone()).unwrap_or_default()))
+			.collect();
+		self.root.hits(&self.leaves, &docs)
+	}
+
+	/// The documents containing every term of a leaf,
+	/// or `None` if the leaf does not have any term
+	fn leaf_hits(
+		terms_docs: &[Option<Vec<(TermId, Fuzziness, RoaringTreemap)>>],
+	) -> Option<RoaringTreemap> {
+		let mut hits: Option<RoaringTreemap> = None;
+		for td in terms_docs {
+			// A document matches a query term if it contains any of the matched terms
+			let docs = td
+				.iter()
+				.flatten()
+				.fold(RoaringTreemap::new(), |acc, (_, _, docs)| acc.bitor(docs));
+			hits = Some(match hits {
+				Some(h) => h.bitand(docs),
+				None => docs,
+			});
+		}
+		hits
+	}
+
+	/// Checks if a record matches the query, given if it matches each leaf.
+	/// A leaf without any term, for which `None` is returned, is ignored.
+	pub(in crate::idx) fn matches<F>(&self, leaf: F) -> bool
+	where
+		F: Fn(usize) -> Option<bool>,
+	{
+		self.root.matches(&leaf).unwrap_or(false)
+	}
+}
+
+impl Node {
+	fn is_exact(&self, leaves: &[Leaf]) -> bool {
+		match self {
+			Self::Leaf(i) => leaves.get(*i).is_none_or(Leaf::is_exact),
+			Self::All(required, excluded) => {
+				required.iter().chain(excluded).all(|n| n.is_exact(leaves))
+			}
+			Self::Any(nodes) => nodes.iter().all(|n| n.is_exact(leaves)),
+		}
+	}
+
+	fn hits(&self, leaves: &[Leaf], docs: &[Option<RoaringTreemap>]) -> Option<RoaringTreemap> {
+		match self {
+			Self::Leaf(i) => docs.get(*i).cloned().flatten(),
+			Self::All(required, excluded) => {
+				let mut hits: Option<RoaringTreemap> = None;
+				for n in required {
+					if let Some(docs) = n.hits(leaves, docs) {
+						hits = Some(match hits {
+							Some(h) => h.bitand(docs),
+							None => docs,
+						});
+					}
+				}
+				let mut hits = hits?;
+				for n in excluded.iter().filter(|n| n.is_exact(leaves)) {
+					if let Some(docs) = n.hits(leaves, docs) {
+						hits -= docs;
+					}
+				}
+				Some(hits)
+			}
+			Self::Any(nodes) => {
+				nodes.iter().filter_map(|n| n.hits(leaves, docs)).reduce(|a, b| a.bitor(b))
+			}
+		}
+	}
+
+	fn matches<F>(&self, leaf: &F) -> Option<bool>
+	where
+		F: Fn(usize) -> Option<bool>,
+	{
+		match self {
+			Self::Leaf(i) => leaf(*i),
+			Self::All(required, excluded) => {
+				let mut res = None;
+				for n in required {
+					match n.matches(leaf) {
+						Some(false) => return Some(false),
+						Some(true) => res = Some(true),
+						None => {}
+					}
+				}
+				if excluded.iter().any(|n| n.matches(leaf) == Some(true)) {
+					return Some(false);
+				}
+				res
+			}
+			Self::Any(nodes) => {
+				let mut res = None;
+				for n in nodes {
+					match n.matches(leaf) {
+						Some(true) => return Some(true),
+						Some(false) => res = Some(false),
+						None => {}
+					}
+				}
+				res
+			}
+		}
+	}
+}
+
+fn invalid(qs: &str, pos: usize, message: &str) -> Error {
+	Error::InvalidFtQuery {
+		position: qs[..pos].chars().count(),
+		message: message.to_owned(),
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+	Word,
+	/// A phrase, with the number of other terms allowed inside it
+	Phrase(u32),
+	Required,
+	Excluded,
+	Or,
+	Open,
+	Close,
+	/// The field of the index scoping the next clause
+	Field(usize),
+	Boost(f32),
+}
+
+#[derive(Debug)]
+struct Token<'a> {
+	kind: Kind,
+	/// The start of the token in the query string, in bytes
+	pos: usize,
+	/// The text of a word, or the text inside the quotes of a phrase
+	text: &'a str,
+}
+
+struct Lexer;
+
+impl Lexer {
+	/// The characters which end a word
+	fn is_delimiter(c: char) -> bool {
+		c.is_whitespace() || matches!(c, '(' | ')' | '"' | '^')
+	}
+
+	fn lex<'a>(qs: &'a str, fields: &[String]) -> Result<Vec<Token<'a>>> {
+		let mut tokens = Vec::new();
+		let mut chars = qs.char_indices().peekable();
+		// The prefixes are only allowed at the start of a clause
+		let mut clause_start = true;
+		while let Some((pos, c)) = chars.next() {
+			let token = |kind| Token {
+				kind,
+				pos,
+				text: "",
+			};
+			match c {
+				c if c.is_whitespace() => {
+					clause_start = true;
+					continue;
+				}
+				'(' => {
+					tokens.push(token(Kind::Open));
+					clause_start = true;
+					continue;
+				}
+				')' => tokens.push(token(Kind::Close)),
+				'+' | '-' if clause_start => {
+					if chars.peek().is_none_or(|(_, c)| c.is_whitespace()) {
+						bail!(invalid(qs, pos, &format!("Expected a clause after '{c}'")));
+					}
+					let kind = if c == '+' {
+						Kind::Required
+					} else {
+						Kind::Excluded
+					};
+					tokens.push(token(kind));
+				}
+				'"' => {
+					let start = pos + 1;
+					let Some(len) = qs[start..].find('"') else {
+						bail!(invalid(qs, pos, "The phrase is not closed"));
+					};
+					let end = start + len;
+					while chars.next_if(|(i, _)| *i <= end).is_some() {}
+					let mut slop = 0;
+					if let Some((tilde, _)) = chars.next_if(|(_, c)| *c == '~') {
+						let digits = Self::number(&mut chars, |c| c.is_ascii_digit());
+						let Ok(n) = qs[tilde + 1..tilde + 1 + digits].parse() else {
+							bail!(invalid(qs, tilde, "Expected a number of terms after '~'"));
+						};
+						slop = n;
+					}
+					tokens.push(Token {
+						kind: Kind::Phrase(slop),
+						pos,
+						text: &qs[start..end],
+					});
+				}
+				'^' => {
+					let digits = Self::number(&mut chars, |c| c.is_ascii_digit() || c == '.');
+					let boost = qs[pos + 1..pos + 1 + digits].parse::<f32>();
+					let Some(boost) = boost.ok().filter(|b| b.is_finite()) else {
+						bail!(invalid(qs, pos, "Expected a number after '^'"));
+					};
+					tokens.push(token(Kind::Boost(boost)));
+				}
+				_ => {
+					let mut end = pos + c.len_utf8();
+					while let Some((i, c)) = chars.next_if(|(_, c)| !Self::is_delimiter(*c)) {
+						end = i + c.len_utf8();
+					}
+					let mut text = &qs[pos..end];
+					let mut start = pos;
+					// A clause is scoped when the word starts with the name of a field
+					if let Some(colon) = text.find(':') {
+						if let Some(field) = fields.iter().position(|f| f == &text[..colon]) {
+							tokens.push(token(Kind::Field(field)));
+							start += colon + 1;
+							text = &text[colon + 1..];
+						}
+					}
+					if !text.is_empty() {
+						let kind = if text == "OR" {
+							Kind::Or
+						} else {
+							Kind::Word
+						};
+						tokens.push(Token {
+							kind,
+							pos: start,
+							text,
+						});
+					}
+				}
+			}
+			clause_start = false;
+		}
+		Ok(tokens)
+	}
+
+	/// Consumes the characters of a number, and returns its length in bytes
+	fn number<I, F>(chars: &mut std::iter::Peekable<I>, accept: F) -> usize
+	where
+		I: Iterator<Item = (usize, char)>,
+		F: Fn(char) -> bool,
+	{
+		let mut len = 0;
+		while let Some((_, c)) = chars.next_if(|(_, c)| accept(*c)) {
+			len += c.len_utf8();
+		}
+		len
+	}
+}
+
+struct Parser<'a> {
+	qs: &'a str,
+	tokens: Vec<Token<'a>>,
+	next: usize,
+	leaves: Vec<Leaf>,
+}
+
+impl Parser<'_> {
+	fn peek_kind(&self) -> Option<Kind> {
+		self.tokens.get(self.next).map(|tk| tk.kind)
+	}
+
+	fn eat(&mut self, kind: Kind) -> bool {
+		if self.peek_kind() == Some(kind) {
+			self.next += 1;
+			true
+		} else {
+			false
+		}
+	}
+
+	/// The position of the next token, or the end of the query string
+	fn pos(&self) -> usize {
+		self.tokens.get(self.next).map_or(self.qs.len(), |tk| tk.pos)
+	}
+
+	fn leaf(&mut self, text: &str, slop: Option<u32>) -> Node {
+		self.leaves.push(Leaf {
+			text: text.to_owned(),
+			slop,
+			field: None,
+			boost: 1.0,
+			excluded: false,
+		});
+		Node::Leaf(self.leaves.len() - 1)
+	}
+
+	fn parse_any(&mut self) -> Result<Node> {
+		let first = self.parse_all()?;
+		if self.peek_kind() != Some(Kind::Or) {
+			return Ok(first);
+		}
+		let mut nodes = vec![first];
+		while self.eat(Kind::Or) {
+			nodes.push(self.parse_all()?);
+		}
+		Ok(Node::Any(nodes))
+	}
+
+	fn parse_all(&mut self) -> Result<Node> {
+		let qs = self.qs;
+		let pos = self.pos();
+		let mut required = Vec::new();
+		let mut excluded = Vec::new();
+		// The consecutive words without any operator are analyzed together
+		let mut words: Option<Range<usize>> = None;
+		while let Some(tk) = self.tokens.get(self.next) {
+			if matches!(tk.kind, Kind::Or | Kind::Close) {
+				break;
+			}
+			let boosted = matches!(
+				self.tokens.get(self.next + 1),
+				Some(Token {
+					kind: Kind::Boost(_),
+					..
+				})
+			);
+			if tk.kind == Kind::Word && !boosted {
+				let end = tk.pos + tk.text.len();
+				words = Some(words.map_or(tk.pos..end, |w| w.start..end));
+				self.next += 1;
+				continue;
+			}
+			if let Some(w) = words.take() {
+				required.push(self.leaf(&qs[w], None));
+			}
+			let is_excluded = self.eat(Kind::Excluded);
+			if !is_excluded {
+				self.eat(Kind::Required);
+			}
+			let first = self.leaves.len();
+			let node = self.parse_clause()?;
+			if is_excluded {
+				self.leaves[first..].iter_mut().for_each(|l| l.excluded = true);
+				excluded.push(node);
+			} else {
+				required.push(node);
+			}
+		}
+		if let Some(w) = words {
+			required.push(self.leaf(&qs[w], None));
+		}
+		if required.is_empty() {
+			if excluded.is_empty() {
+				bail!(invalid(qs, pos, "Expected a clause"));
+			}
+			bail!(invalid(qs, pos, "A group of clauses can not only contain excluded clauses"));
+		}
+		if required.len() == 1 && excluded.is_empty() {
+			return Ok(required.remove(0));
+		}
+		Ok(Node::All(required, excluded))
+	}
+
+	fn parse_clause(&mut self) -> Result<Node> {
+		let field = match self.peek_kind() {
+			Some(Kind::Field(f)) => {
+				self.next += 1;
+				Some(f)
+			}
+			_ => None,
+		};
+		let first = self.leaves.len();
+		let pos = self.pos();
+		let node = match self.tokens.get(self.next).map(|tk| (tk.kind, tk.text)) {
+			Some((Kind::Word, text)) => {
+				self.next += 1;
+				self.leaf(text, None)
+			}
+			Some((Kind::Phrase(slop), text)) => {
+				self.next += 1;
+				self.leaf(text, Some(slop))
+			}
+			Some((Kind::Open, _)) => {
+				self.next += 1;
+				let node = self.parse_any()?;
+				if !self.eat(Kind::Close) {
+					bail!(invalid(self.qs, pos, "The group is not closed"));
+				}
+				node
+			}
+			_ => bail!(invalid(self.qs, pos, "Expected a clause")),
+		};
+		let boost = match self.peek_kind() {
+			Some(Kind::Boost(b)) => {
+				self.next += 1;
+				b
+			}
+			_ => 1.0,
+		};
+		// The innermost field wins
+		for l in &mut self.leaves[first..] {
+			l.field = l.field.or(field);
+			l.boost *= boost;
+		}
+		Ok(node)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Leaf, Node, Query};
+	use crate::err::Error;
+
+	fn leaf(text: &str) -> Leaf {
+		Leaf {
+			text: text.to_owned(),
+			slop: None,
+			field: None,
+			boost: 1.0,
+			excluded: false,
+		}
+	}
+
+	fn parse(qs: &str) -> Query {
+		Query::parse(qs, &["title".to_owned(), "body".to_owned()]).unwrap()
+	}
+
+	fn parse_error(qs: &str) -> (usize, String) {
+		match Query::parse(qs, &["title".to_owned()]).unwrap_err().downcast() {
+			Ok(Error::InvalidFtQuery {
+				position,
+				message,
+			}) => (position, message),
+			e => panic!("{e:?}"),
+		}
+	}
+
+	#[test]
+	fn parse_plain_query() {
+		// The terms are analyzed together, as they were before the operators
+		let q = parse("hello  world");
+		assert_eq!(q.leaves, vec![leaf("hello  world")]);
+		assert_eq!(q.root, Node::Leaf(0));
+		assert!(!q.needs_record_check());
+		assert!(parse(" ").leaves.is_empty());
+	}
+
+	#[test]
+	fn parse_operators() {
+		let q = parse("+quick -lazy brown fox OR (dog cat^2.5)");
+		assert_eq!(
+			q.leaves,
+			vec![
+				leaf("quick"),
+				Leaf {
+					excluded: true,
+					..leaf("lazy")
+				},
+				leaf("brown fox"),
+				leaf("dog"),
+				Leaf {
+					boost: 2.5,
+					..leaf("cat")
+				},
+			]
+		);
+		assert_eq!(
+			q.root,
+			Node::Any(vec![
+				Node::All(vec![Node::Leaf(0), Node::Leaf(2)], vec![Node::Leaf(1)]),
+				Node::All(vec![Node::Leaf(3), Node::Leaf(4)], vec![]),
+			])
+		);
+		// The words which are not prefixed keep their hyphens
+		assert_eq!(parse("e-mail").leaves, vec![leaf("e-mail")]);
+	}
+
+	#[test]
+	fn parse_fields_and_phrases() {
+		let q = parse(r#"title:(fox "lazy dog"~2)^2 body:cat other:thing"#);
+		assert_eq!(
+			q.leaves,
+			vec![
+				Leaf {
+					field: Some(0),
+					boost: 2.0,
+					..leaf("fox")
+				},
+				Leaf {
+					slop: Some(2),
+					field: Some(0),
+					boost: 2.0,
+					..leaf("lazy dog")
+				},
+				Leaf {
+					field: Some(1),
+					..leaf("cat")
+				},
+				// An unknown field is part of the terms
+				leaf("other:thing"),
+			]
+		);
+		assert!(q.needs_record_check());
+	}
+
+	#[test]
+	fn parse_malformed_queries() {
+		assert_eq!(parse_error("fox (dog"), (4, "The group is not closed".to_owned()));
+		assert_eq!(parse_error("fox dog)"), (7, "Unexpected ')'".to_owned()));
+		assert_eq!(parse_error("fox \"lazy dog"), (4, "The phrase is not closed".to_owned()));
+		assert_eq!(
+			parse_error("\"lazy dog\"~x"),
+			(10, "Expected a number of terms after '~'".to_owned())
+		);
+		assert_eq!(parse_error("fox^"), (3, "Expected a number after '^'".to_owned()));
+		assert_eq!(parse_error("fox - dog"), (4, "Expected a clause after '-'".to_owned()));
+		assert_eq!(parse_error("fox OR"), (6, "Expected a clause".to_owned()));
+		assert_eq!(parse_error("()"), (1, "Expected a clause".to_owned()));
+		assert_eq!(parse_error("title: "), (7, "Expected a clause".to_owned()));
+		assert_eq!(
+			parse_error("fox OR -dog"),
+			(7, "A group of clauses can not only contain excluded clauses".to_owned())
+		);
+		// The position is counted in characters
+		assert_eq!(parse_error("ééé)"), (3, "Unexpected ')'".to_owned()));
+	}
+}
@@ -1,8 +1,10 @@
+use crate::expr::Value;
 use crate::idx::docids::DocId;
-use crate::idx::ft::Bm25Params;
 use crate::idx::ft::doclength::{DocLength, DocLengths};
 use crate::idx::ft::postings::{Postings, TermFrequency};
 use crate::idx::ft::termdocs::TermsDocs;
+use crate::idx::ft::terms::TermId;
+use crate::idx::ft::{Bm25Params, Fuzziness};
 use crate::kvs::Transaction;
 use anyhow::Result;
 use std::sync::Arc;
@@ -10,9 +12,23 @@ use tokio::sync::RwLock;
 
 pub(super) type Score = f32;
 
+/// The contribution of a matched term to the score of a document
+struct TermScore {
+	term_id: TermId,
+	fuzziness: Fuzziness,
+	term_freq: TermFrequency,
+	term_doc_count: DocLength,
+	doc_length: DocLength,
+	idf: f32,
+	norm: f32,
+	score: Score,
+}
+
 pub(crate) struct BM25Scorer {
 	postings: Arc<RwLock<Postings>>,
 	terms_docs: TermsDocs,
+	/// The factor applied to the score of each query term
+	boosts: Vec<f32>,
 	doc_lengths: Arc<RwLock<DocLengths>>,
 	average_doc_length: f32,
 	doc_count: f32,
@@ -23,6 +39,7 @@ impl BM25Scorer {
 	pub(super) fn new(
 		postings: Arc<RwLock<Postings>>,
 		terms_docs: TermsDocs,
+		boosts: Vec<f32>,
 		doc_lengths: Arc<RwLock<DocLengths>>,
 		total_docs_length: u128,
 		doc_count: u64,
@@ -31,6 +48,7 @@ impl BM25Scorer {
 		Self {
 			postings,
 			terms_docs,
+			boosts,
 			doc_lengths,
 			average_doc_length: (total_docs_length as f32) / (doc_count as f32),
 			doc_count: doc_count as f32,
@@ -38,51 +56,141 @@ impl BM25Scorer {
 		}
 	}
 
-	async fn term_score(
+	/// Computes the contribution of each query term to the score of a document.
+	/// A query term contributes with the best of the terms it matches,
+	/// and the terms without any boost, like the excluded ones, do not contribute.
+	/// The boosts of the query terms can be replaced for a given document.
+	async fn term_scores(
 		&self,
 		tx: &Transaction,
 		doc_id: DocId,
-		term_doc_count: DocLength,
-		term_frequency: TermFrequency,
-	) -> Result<Score> {
+		boosts: Option<&[f32]>,
+	) -> Result<Vec<TermScore>> {
+		let mut scores = Vec::with_capacity(self.terms_docs.len());
+		let p = self.postings.read().await;
 		let dl = self.doc_lengths.read().await;
 		let doc_length = dl.get_doc_length(tx, doc_id).await?.unwrap_or(0);
 		drop(dl);
-		Ok(self.compute_bm25_score(term_frequency as f32, term_doc_count as f32, doc_length as f32))
-	}
-
-	pub(crate) async fn score(&self, tx: &Transaction, doc_id: DocId) -> Result<Option<Score>> {
-		let mut sc = 0.0;
-		let p = self.postings.read().await;
-		for (term_id, docs) in self.terms_docs.iter().flatten() {
-			if docs.contains(doc_id) {
-				let tf = p.get_term_frequency(tx, *term_id, doc_id).await?;
-				if let Some(term_freq) = tf {
-					sc += self.term_score(tx, doc_id, docs.len(), term_freq).await?;
+		let boosts = boosts.unwrap_or(&self.boosts);
+		for (matched_docs, boost) in self.terms_docs.iter().zip(boosts) {
+			let Some(matched_docs) = matched_docs.as_ref().filter(|_| *boost > 0.0) else {
+				continue;
+			};
+			let mut best: Option<TermScore> = None;
+			for (term_id, fuzziness, docs) in matched_docs {
+				if docs.contains(doc_id) {
+					let tf = p.get_term_frequency(tx, *term_id, doc_id).await?;
+					if let Some(term_freq) = tf {
+						let ts = self.term_score(
+							*term_id,
+							*fuzziness,
+							docs.len(),
+							term_freq,
+							doc_length,
+							*boost,
+						);
+						if best.as_ref().is_none_or(|b| ts.score > b.score) {
+							best = Some(ts);
+						}
+					}
 				}
 			}
+			scores.extend(best);
 		}
 		drop(p);
-		Ok(Some(sc))
+		Ok(scores)
 	}
 
-	// https://en.wikipedia.org/wiki/Okapi_BM25
-	// Including the lower-bounding term frequency normalization (2011 CIKM)
-	fn compute_bm25_score(&self, term_freq: f32, term_doc_count: f32, doc_length: f32) -> f32 {
+	pub(crate) async fn score(
+		&self,
+		tx: &Transaction,
+		doc_id: DocId,
+		boosts: Option<&[f32]>,
+	) -> Result<Option<Score>> {
+		let scores = self.term_scores(tx, doc_id, boosts).await?;
+		Ok(Some(scores.iter().fold(0.0, |sc, ts| sc + ts.score)))
+	}
+
+	/// Explains the score of a document, returning the contribution
+	/// of each matched term along with the components of its score
+	pub(crate) async fn explain(
+		&self,
+		tx: &Transaction,
+		doc_id: DocId,
+		boosts: Option<&[f32]>,
+	) -> Result<Vec<(TermId, Value)>> {
+		let scores = self.term_scores(tx, doc_id, boosts).await?;
+		Ok(scores
+			.into_iter()
+			.map(|ts| {
+				let val = Value::from(map! {
+					"tf".to_string() => Value::from(ts.term_freq),
+					"df".to_string() => Value::from(ts.term_doc_count),
+					"idf".to_string() => Value::from(ts.idf),
+					"doc_length".to_string() => Value::from(ts.doc_length),
+					"norm".to_string() => Value::from(ts.norm),
+					"fuzziness".to_string() => Value::from(ts.fuzziness),
+					"score".to_string() => Value::from(ts.score),
+				});
+				(ts.term_id, val)
+			})
+			.collect())
+	}
+
+	fn term_score(
+		&self,
+		term_id: TermId,
+		fuzziness: Fuzziness,
+		term_doc_count: DocLength,
+		term_freq: TermFrequency,
+		doc_length: DocLength,
+		boost: f32,
+	) -> TermScore {
+		let idf = self.compute_idf(term_doc_count as f32);
+		let norm = self.compute_norm(doc_length as f32);
+		let score = self.compute_bm25_score(idf, norm, term_freq as f32);
+		TermScore {
+			term_id,
+			fuzziness,
+			term_freq,
+			term_doc_count,
+			doc_length,
+			idf,
+			norm,
+			score: score * Self::fuzzy_weight(fuzziness) * boost,
+		}
+	}
+
+	/// Fuzzy matches are discounted relative to exact matches,
+	/// and the more edits the higher the discount
+	fn fuzzy_weight(fuzziness: Fuzziness) -> f32 {
+		1.0 / (1.0 + fuzziness as f32)
+	}
+
+	// ln((N - n(qi) + 0.5) / (n(qi) + 0.5))
+	fn compute_idf(&self, term_doc_count: f32) -> f32 {
 		// (n(qi) + 0.5)
 		let denominator = term_doc_count + 0.5;
 		// (N - n(qi) + 0.5)
 		let numerator = self.doc_count - term_doc_count + 0.5;
-		let idf = (numerator / denominator).ln();
+		(numerator / denominator).ln()
+	}
+
+	// The field length normalization: 1 - b + b * (|D| / avgDL)
+	fn compute_norm(&self, doc_length: f32) -> f32 {
+		1.0 - self.bm25.b + self.bm25.b * (doc_length / self.average_doc_length)
+	}
+
+	// https://en.wikipedia.org/wiki/Okapi_BM25
+	// Including the lower-bounding term frequency normalization (2011 CIKM)
+	fn compute_bm25_score(&self, idf: f32, norm: f32, term_freq: f32) -> f32 {
 		if idf.is_nan() {
 			return f32::NAN;
 		}
 		let tf_prim = 1.0 + term_freq.ln();
 		// idf * (k1 + 1)
 		let numerator = idf * (self.bm25.k1 + 1.0) * tf_prim;
-		// 1 - b + b * (|D| / avgDL)
-		let denominator = 1.0 - self.bm25.b + self.bm25.b * (doc_length / self.average_doc_length);
-		// numerator / (k1 * denominator + 1)
-		numerator / (self.bm25.k1 * denominator + 1.0)
+		// numerator / (k1 * norm + 1)
+		numerator / (self.bm25.k1 * norm + 1.0)
 	}
 }
@@ -1,5 +1,6 @@
 use crate::idx::IndexKeyBase;
 use crate::idx::docids::DocId;
+use crate::idx::ft::Fuzziness;
 use crate::idx::ft::doclength::DocLength;
 use crate::idx::ft::terms::TermId;
 use crate::kvs::Transaction;
@@ -7,7 +8,9 @@ use anyhow::Result;
 use roaring::RoaringTreemap;
 use std::sync::Arc;
 
-pub(in crate::idx) type TermsDocs = Arc<Vec<Option<(TermId, RoaringTreemap)>>>;
+/// For each query term, the documents of each of the terms it matches,
+/// along with the number of edits between the query term and the term
+pub(in crate::idx) type TermsDocs = Arc<Vec<Option<Vec<(TermId, Fuzziness, RoaringTreemap)>>>>;
 
 pub(super) struct TermDocs {
 	index_key_base: IndexKeyBase,
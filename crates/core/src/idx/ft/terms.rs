@@ -1,13 +1,17 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::err::Error;
+use crate::idx::ft::Fuzziness;
 use crate::idx::trees::bkeys::FstKeys;
 use crate::idx::trees::btree::{BState, BState1, BState1skip, BStatistics, BTree, BTreeStore};
 use crate::idx::trees::store::TreeNodeProvider;
 use crate::idx::{IndexKeyBase, VersionedStore};
-use crate::kvs::{Key, Transaction, TransactionType, Val};
+use crate::key::index::bu::Bu;
+use crate::kvs::{Key, KeyDecode as _, Transaction, TransactionType, Val};
 use anyhow::Result;
 use revision::{Revisioned, revisioned};
 use roaring::RoaringTreemap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 pub(crate) type TermId = u64;
 pub(crate) type TermLen = u32;
@@ -88,6 +92,100 @@ impl Terms {
 		self.btree.search(tx, &self.store, &term.into()).await
 	}
 
+	/// Returns the term for a term id
+	pub(in crate::idx) async fn get_term(
+		&self,
+		tx: &Transaction,
+		term_id: TermId,
+	) -> Result<Option<String>> {
+		let Some(term) = tx.get(self.index_key_base.new_bu_key(term_id)?, None).await? else {
+			return Ok(None);
+		};
+		Ok(String::from_utf8(term).ok())
+	}
+
+	/// Finds the terms of the dictionary which are within the given number
+	/// of edits of a term, excluding the term itself. A transposition of two
+	/// adjacent characters counts as a single edit. The closest terms are
+	/// returned first, and no more than `limit` terms are returned.
+	pub(super) async fn get_fuzzy_term_ids(
+		&self,
+		tx: &Transaction,
+		term: &str,
+		fuzziness: Fuzziness,
+		limit: usize,
+	) -> Result<Vec<(TermId, TermLen, Fuzziness)>> {
+		let max = fuzziness as usize;
+		let len = term.chars().count();
+		let mut found = Vec::new();
+		let beg = self.index_key_base.new_bu_key(0)?;
+		let end = self.index_key_base.new_bu_key(TermId::MAX)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let res = tx.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for (k, v) in res.result.iter() {
+				let Ok(candidate) = std::str::from_utf8(v) else {
+					continue;
+				};
+				// Terms with a very different length can't be close enough
+				let candidate_len = candidate.chars().count();
+				if candidate_len.abs_diff(len) > max {
+					continue;
+				}
+				let distance = strsim::osa_distance(term, candidate);
+				if distance == 0 || distance > max {
+					continue;
+				}
+				let term_id = Bu::decode(k)?.term_id;
+				found.push((distance as Fuzziness, candidate.to_owned(), term_id, candidate_len));
+			}
+			// Only keep the closest terms, to bound the memory usage
+			if found.len() > limit * 2 {
+				found.sort_unstable();
+				found.truncate(limit);
+			}
+		}
+		found.sort_unstable();
+		found.truncate(limit);
+		Ok(found.into_iter().map(|(d, _, id, len)| (id, len as TermLen, d)).collect())
+	}
+
+	/// Lists the terms of the dictionary starting with a prefix, in order.
+	/// No more than `limit` terms are returned, the first ones in order.
+	pub(super) async fn get_terms_with_prefix(
+		&self,
+		tx: &Transaction,
+		prefix: &str,
+		limit: usize,
+	) -> Result<Vec<String>> {
+		let mut found: BTreeSet<String> = BTreeSet::new();
+		let beg = self.index_key_base.new_bu_key(0)?;
+		let end = self.index_key_base.new_bu_key(TermId::MAX)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let res = tx.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = res.next;
+			for (_, v) in res.result.iter() {
+				let Ok(term) = std::str::from_utf8(v) else {
+					continue;
+				};
+				if !term.starts_with(prefix) {
+					continue;
+				}
+				// Only keep the first terms, to bound the memory usage
+				if found.len() == limit {
+					if found.last().is_none_or(|last| term >= last.as_str()) {
+						continue;
+					}
+					found.pop_last();
+				}
+				found.insert(term.to_owned());
+			}
+		}
+		Ok(found.into_iter().collect())
+	}
+
 	pub(super) async fn remove_term_id(&mut self, tx: &Transaction, term_id: TermId) -> Result<()> {
 		let term_id_key = self.index_key_base.new_bu_key(term_id)?;
 		if let Some(term_key) = tx.get(term_id_key.clone(), None).await? {
@@ -346,6 +444,62 @@ mod tests {
 		finish(tx, t).await;
 	}
 
+	#[test(tokio::test)]
+	async fn test_fuzzy_terms() {
+		const BTREE_ORDER: u32 = 7;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		{
+			let (tx, mut t) = new_operation(&ds, BTREE_ORDER, Write).await;
+			for term in ["receive", "recieve", "receiver", "deceive", "relieve", "banana"] {
+				t.resolve_term_id(&tx, term).await.unwrap();
+			}
+			finish(tx, t).await;
+		}
+
+		let (tx, t) = new_operation(&ds, BTREE_ORDER, Read).await;
+		// A transposition is a single edit, and the term itself is excluded
+		let res = t.get_fuzzy_term_ids(&tx, "receive", 1, 10).await.unwrap();
+		assert_eq!(res, vec![(3, 7, 1), (2, 8, 1), (1, 7, 1)]);
+		// Two edits also match the terms which are further away
+		let res = t.get_fuzzy_term_ids(&tx, "recieve", 2, 10).await.unwrap();
+		assert_eq!(res, vec![(0, 7, 1), (4, 7, 1), (3, 7, 2), (2, 8, 2)]);
+		// The number of terms is capped, keeping the closest ones
+		let res = t.get_fuzzy_term_ids(&tx, "recieve", 2, 1).await.unwrap();
+		assert_eq!(res, vec![(0, 7, 1)]);
+		// No term is within the distance
+		let res = t.get_fuzzy_term_ids(&tx, "apple", 2, 10).await.unwrap();
+		assert!(res.is_empty());
+	}
+
+	#[test(tokio::test)]
+	async fn test_terms_with_prefix() {
+		const BTREE_ORDER: u32 = 7;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		{
+			let (tx, mut t) = new_operation(&ds, BTREE_ORDER, Write).await;
+			for term in ["receiver", "banana", "receive", "recipe", "rec", "deceive"] {
+				t.resolve_term_id(&tx, term).await.unwrap();
+			}
+			finish(tx, t).await;
+		}
+
+		let (tx, t) = new_operation(&ds, BTREE_ORDER, Read).await;
+		// The terms are listed in order
+		let res = t.get_terms_with_prefix(&tx, "rec", 10).await.unwrap();
+		assert_eq!(res, vec!["rec", "receive", "receiver", "recipe"]);
+		// The number of terms is capped, keeping the first ones
+		let res = t.get_terms_with_prefix(&tx, "rec", 2).await.unwrap();
+		assert_eq!(res, vec!["rec", "receive"]);
+		// An empty prefix lists the whole dictionary
+		let res = t.get_terms_with_prefix(&tx, "", 3).await.unwrap();
+		assert_eq!(res, vec!["banana", "deceive", "rec"]);
+		// No term has the prefix
+		let res = t.get_terms_with_prefix(&tx, "apple", 10).await.unwrap();
+		assert!(res.is_empty());
+	}
+
 	fn random_term_freq_vec(term_count: usize) -> Vec<(String, TermFrequency)> {
 		let mut i = 1;
 		let mut vec = Vec::with_capacity(term_count);
@@ -9,6 +9,7 @@ use crate::expr::{Array, Index, Part, Thing, Value};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
+use crate::idx::trigram;
 use crate::key;
 use crate::kvs::TransactionType;
 use anyhow::Result;
@@ -52,6 +53,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Search(p) => self.index_full_text(stk, p).await,
 			Index::MTree(p) => self.index_mtree(stk, p).await,
 			Index::Hnsw(p) => self.index_hnsw(p).await,
+			Index::Trigram => self.index_trigram().await,
 		}
 	}
 
@@ -193,6 +195,12 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_trigram(&mut self) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		let (o, n) = (self.o.take(), self.n.take());
+		trigram::index_trigrams(&self.ctx.tx(), ns, db, self.ix, self.rid, o, n).await
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
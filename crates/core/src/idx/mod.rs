@@ -3,6 +3,7 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub mod trees;
+pub(crate) mod trigram;
 
 use crate::expr::statements::DefineIndexStatement;
 use crate::expr::{Id, Thing};
@@ -57,7 +58,7 @@ impl IndexKeyBase {
 				ns: ns.to_string(),
 				db: db.to_string(),
 				tb: ix.what.to_raw(),
-				ix: ix.name.to_raw(),
+				ix: ix.storage_name(),
 			}),
 		})
 	}
@@ -5,12 +5,15 @@ use crate::err::Error;
 use crate::expr::index::{Distance, Index};
 use crate::expr::statements::DefineIndexStatement;
 use crate::expr::{
-	Array, Cond, Expression, FlowResultExt as _, Idiom, Number, Object, Table, Thing, Value,
+	Array, Cond, Expression, FlowResultExt as _, Idiom, Number, Object, Operator, Table, Thing,
+	Value,
 };
 use crate::idx::IndexKeyBase;
-use crate::idx::docids::DocIds;
-use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
+use crate::idx::docids::{DocId, DocIds};
+use crate::idx::ft::analyzer::{Analyzer, MatchedTerm, TermsList, TermsSet};
 use crate::idx::ft::highlighter::HighlightParams;
+use crate::idx::ft::phrase::{Phrase, PhraseHits, PhraseMatch};
+use crate::idx::ft::query::Query;
 use crate::idx::ft::scorer::BM25Scorer;
 use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::terms::Terms;
@@ -20,9 +23,9 @@ use crate::idx::planner::checker::{HnswConditionChecker, MTreeConditionChecker};
 use crate::idx::planner::iterators::{
 	IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
 	IndexUnionThingIterator, IteratorRange, IteratorRecord, IteratorRef, KnnIterator,
-	KnnIteratorResult, MatchesThingIterator, MultipleIterators, ThingIterator,
-	UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
-	UniqueUnionThingIterator, ValueType,
+	KnnIteratorResult, MatchesThingIterator, MultipleIterators, ReferenceThingIterator,
+	ThingIterator, TrigramThingIterator, UniqueEqualThingIterator, UniqueJoinThingIterator,
+	UniqueRangeThingIterator, UniqueUnionThingIterator, ValueType,
 };
 #[cfg(any(feature = "kv-rocksdb", feature = "kv-tikv"))]
 use crate::idx::planner::iterators::{
@@ -34,13 +37,17 @@ use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexReference};
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
-use crate::kvs::TransactionType;
+use crate::idx::trigram;
+use crate::kvs::{Transaction, TransactionType};
 use anyhow::{Result, ensure};
 use num_traits::{FromPrimitive, ToPrimitive};
+use parking_lot::Mutex;
 use reblessive::tree::Stk;
+use roaring::RoaringTreemap;
 use rust_decimal::Decimal;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::Range;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -80,6 +87,8 @@ pub(super) struct InnerQueryExecutor {
 	mt_entries: HashMap<Arc<Expression>, MtEntry>,
 	hnsw_entries: HashMap<Arc<Expression>, HnswEntry>,
 	knn_bruteforce_entries: HashMap<Arc<Expression>, KnnBruteForceEntry>,
+	/// The condition checked while searching the vector indexes
+	knn_condition: Option<Arc<Cond>>,
 }
 
 impl From<InnerQueryExecutor> for QueryExecutor {
@@ -91,6 +100,11 @@ impl From<InnerQueryExecutor> for QueryExecutor {
 pub(super) enum IteratorEntry {
 	Single(Option<Arc<Expression>>, IndexOption),
 	Range(HashSet<Arc<Expression>>, IndexReference, RangeValue, RangeValue),
+	/// The values of the leading columns, the range of the next column,
+	/// and true if the index is scanned backward
+	PrefixRange(IndexReference, Array, RangeValue, RangeValue, bool),
+	/// The field with a `REFERENCE` clause, and the record id it references
+	Reference(Arc<Idiom>, Thing),
 }
 
 impl IteratorEntry {
@@ -104,6 +118,29 @@ impl IteratorEntry {
 				e.insert("to", Value::from(to));
 				Value::from(Object::from(e))
 			}
+			Self::PrefixRange(ir, prefix, from, to, reverse) => {
+				let mut e = HashMap::default();
+				e.insert("index", Value::from(ir.name.0.clone()));
+				e.insert("prefix", Value::from(prefix.clone()));
+				e.insert("from", Value::from(from));
+				e.insert("to", Value::from(to));
+				e.insert(
+					"direction",
+					Value::from(if *reverse {
+						"backward"
+					} else {
+						"forward"
+					}),
+				);
+				Value::from(Object::from(e))
+			}
+			Self::Reference(id, thing) => {
+				let mut e = HashMap::default();
+				e.insert("reference", Value::from(id.to_string()));
+				e.insert("operator", Value::from(Operator::Equal.to_string()));
+				e.insert("value", Value::Thing(thing.clone()));
+				Value::from(Object::from(e))
+			}
 		}
 	}
 }
@@ -156,7 +193,7 @@ impl InnerQueryExecutor {
 						}
 					};
 					if let Some(e) = ft_entry {
-						if let Matches(_, Some(mr)) = e.0.index_option.op() {
+						if let Matches(_, Some(mr), _) = e.0.index_option.op() {
 							ensure!(
 								mr_entries.insert(*mr, e.clone()).is_none(),
 								Error::DuplicatedMatchRef {
@@ -249,6 +286,7 @@ impl InnerQueryExecutor {
 			mt_entries,
 			hnsw_entries,
 			knn_bruteforce_entries,
+			knn_condition,
 		})
 	}
 
@@ -305,9 +343,13 @@ impl QueryExecutor {
 	}
 
 	/// Returns `true` if the expression is matching the current iterator.
+	/// The records of a full-text query with phrases or fields still have to be checked.
 	pub(crate) fn is_iterator_expression(&self, ir: IteratorRef, exp: &Expression) -> bool {
 		match self.0.it_entries.get(ir) {
-			Some(IteratorEntry::Single(Some(e), ..)) => exp.eq(e.as_ref()),
+			Some(IteratorEntry::Single(Some(e), ..)) => {
+				exp.eq(e.as_ref())
+					&& self.0.exp_entries.get(exp).is_none_or(|ft| !ft.0.query.needs_record_check())
+			}
 			Some(IteratorEntry::Range(es, ..)) => es.contains(exp),
 			_ => false,
 		}
@@ -315,7 +357,18 @@ impl QueryExecutor {
 
 	pub(crate) fn explain(&self, ir: IteratorRef) -> Value {
 		match self.0.it_entries.get(ir) {
-			Some(ie) => ie.explain(),
+			Some(ie) => {
+				let mut e = ie.explain();
+				// Show the condition pushed down into the vector index search
+				if let IteratorEntry::Single(_, io) = ie {
+					if matches!(io.op(), IndexOperator::Knn(..) | IndexOperator::Ann(..)) {
+						if let (Value::Object(o), Some(c)) = (&mut e, &self.0.knn_condition) {
+							o.insert("filter".to_string(), Value::from(c.0.to_string()));
+						}
+					}
+				}
+				e
+			}
 			None => Value::None,
 		}
 	}
@@ -340,6 +393,15 @@ impl QueryExecutor {
 				IteratorEntry::Range(_, ixr, from, to) => {
 					Ok(self.new_range_iterator(ir, opt, ixr, from, to)?)
 				}
+				IteratorEntry::PrefixRange(ixr, prefix, from, to, reverse) => {
+					Ok(Self::new_prefix_range_iterator(ir, opt, ixr, prefix, from, to, *reverse)?)
+				}
+				IteratorEntry::Reference(id, thing) => {
+					let (ns, db) = opt.ns_db()?;
+					let ff = id.to_string();
+					let it = ReferenceThingIterator::new(ir, ns, db, &self.0.table, &ff, thing)?;
+					Ok(Some(ThingIterator::Reference(it)))
+				}
 			}
 		} else {
 			Ok(None)
@@ -361,9 +423,27 @@ impl QueryExecutor {
 			} => self.new_search_index_iterator(irf, io.clone()).await,
 			Index::MTree(_) => Ok(self.new_mtree_index_knn_iterator(irf)),
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::Trigram => Self::new_trigram_index_iterator(opt, irf, ixr, io),
 		}
 	}
 
+	fn new_trigram_index_iterator(
+		opt: &Options,
+		ir: IteratorRef,
+		ix: &IndexReference,
+		io: &IndexOption,
+	) -> Result<Option<ThingIterator>> {
+		if let IndexOperator::Trigram(s) = io.op() {
+			let (ns, db) = opt.ns_db()?;
+			let mut trigrams = BTreeSet::new();
+			trigram::trigrams(s, &mut trigrams);
+			return Ok(Some(ThingIterator::Trigram(TrigramThingIterator::new(
+				ir, ns, db, ix, &trigrams,
+			)?)));
+		}
+		Ok(None)
+	}
+
 	async fn new_index_iterator(
 		&self,
 		opt: &Options,
@@ -761,6 +841,40 @@ impl QueryExecutor {
 		Ok(None)
 	}
 
+	#[cfg_attr(not(any(feature = "kv-rocksdb", feature = "kv-tikv")), expect(unused_variables))]
+	fn new_prefix_range_iterator(
+		ir: IteratorRef,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+		prefix: &Array,
+		from: &RangeValue,
+		to: &RangeValue,
+		reverse: bool,
+	) -> Result<Option<ThingIterator>> {
+		if !matches!(ix.index, Index::Idx | Index::Uniq) {
+			return Ok(None);
+		}
+		let (ns, db) = opt.ns_db()?;
+		let ranges = Self::get_ranges_variants(from, to)
+			.unwrap_or_else(|| vec![IteratorRange::new_ref(ValueType::None, from, to)]);
+		let mut iterators = VecDeque::with_capacity(ranges.len());
+		for range in &ranges {
+			#[cfg(any(feature = "kv-rocksdb", feature = "kv-tikv"))]
+			if reverse {
+				let it =
+					IndexRangeReverseThingIterator::prefix_range(ir, ns, db, ix, prefix, range)?;
+				iterators.push_front(ThingIterator::IndexRangeReverse(it));
+				continue;
+			}
+			let it = IndexRangeThingIterator::prefix_range(ir, ns, db, ix, prefix, range)?;
+			iterators.push_back(ThingIterator::IndexRange(it));
+		}
+		if iterators.len() == 1 {
+			return Ok(iterators.pop_front());
+		}
+		Ok(Some(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators)))))
+	}
+
 	fn get_ranges_variants<'a>(
 		from: &'a RangeValue,
 		to: &'a RangeValue,
@@ -906,11 +1020,10 @@ impl QueryExecutor {
 		io: IndexOption,
 	) -> Result<Option<ThingIterator>> {
 		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir) {
-			if let Matches(_, _) = io.op() {
+			if let Matches(..) = io.op() {
 				if let Some(fti) = self.0.ft_map.get(io.ix_ref()) {
 					if let Some(fte) = self.0.exp_entries.get(exp) {
-						let it =
-							MatchesThingIterator::new(ir, fti, fte.0.terms_docs.clone()).await?;
+						let it = MatchesThingIterator::new(ir, fti, fte.hits()).await?;
 						return Ok(Some(ThingIterator::Matches(it)));
 					}
 				}
@@ -961,6 +1074,7 @@ impl QueryExecutor {
 		ctx: &Context,
 		opt: &Options,
 		thg: &Thing,
+		doc: Option<&CursorDoc>,
 		exp: &Expression,
 		l: Value,
 		r: Value,
@@ -968,7 +1082,7 @@ impl QueryExecutor {
 		if let Some(ft) = self.0.exp_entries.get(exp) {
 			let ix = ft.0.index_option.ix_ref();
 			if self.0.table.eq(&ix.what.0) {
-				return self.matches_with_doc_id(ctx, thg, ft).await;
+				return self.matches_with_doc_id(stk, ctx, opt, thg, doc, ft).await;
 			}
 			return self.matches_with_value(stk, ctx, opt, ft, l, r).await;
 		}
@@ -979,32 +1093,51 @@ impl QueryExecutor {
 		}))
 	}
 
-	async fn matches_with_doc_id(&self, ctx: &Context, thg: &Thing, ft: &FtEntry) -> Result<bool> {
-		// TODO ask emmanual
+	async fn matches_with_doc_id(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		thg: &Thing,
+		doc: Option<&CursorDoc>,
+		ft: &FtEntry,
+	) -> Result<bool> {
 		let doc_key = revision::to_vec(thg)?;
 		let tx = ctx.tx();
 		let di = ft.0.doc_ids.read().await;
 		let doc_id = di.get_doc_id(&tx, doc_key).await?;
 		drop(di);
-		if let Some(doc_id) = doc_id {
-			let term_goals = ft.0.terms_docs.len();
-			// If there is no terms, it can't be a match
-			if term_goals == 0 {
-				return Ok(false);
+		drop(tx);
+		let Some(doc_id) = doc_id else {
+			return Ok(false);
+		};
+		// The phrases and the fields of the query are searched in the values of the record
+		let record = if ft.0.query.needs_record_check() {
+			Some(ft.record_hits(stk, ctx, opt, doc).await?)
+		} else {
+			None
+		};
+		let matched = ft.0.query.matches(|i| {
+			let terms = ft.0.leaf_terms.get(i)?.clone();
+			if terms.is_empty() {
+				return None;
 			}
-			for opt_td in ft.0.terms_docs.iter() {
-				if let Some((_, docs)) = opt_td {
-					if !docs.contains(doc_id) {
-						return Ok(false);
-					}
-				} else {
-					// If one of the term is missing, it can't be a match
-					return Ok(false);
+			let leaf = &ft.0.query.leaves()[i];
+			let found = match (&record, leaf.field) {
+				(Some(rec), Some(field)) => {
+					ft.0.query_terms_list[terms].iter().all(|m| rec.field_contains(field, m))
 				}
-			}
-			return Ok(true);
+				// If one of the terms is missing, it can't be a match
+				_ => ft.0.terms_docs[terms].iter().all(|td| {
+					td.as_ref().is_some_and(|m| m.iter().any(|(_, _, docs)| docs.contains(doc_id)))
+				}),
+			};
+			Some(found && record.as_ref().is_none_or(|rec| rec.phrase_found(i)))
+		});
+		if let Some(rec) = record {
+			ft.set_record_match(thg, matched, rec);
 		}
-		Ok(false)
+		Ok(matched)
 	}
 
 	async fn matches_with_value(
@@ -1016,22 +1149,31 @@ impl QueryExecutor {
 		l: Value,
 		r: Value,
 	) -> Result<bool> {
-		// If the query terms contains terms that are unknown in the index
-		// of if there are no terms in the query
-		// we are sure that it does not match any document
-		if !ft.0.query_terms_set.is_matchable() {
-			return Ok(false);
-		}
 		let v = match ft.0.index_option.id_pos() {
 			IdiomPosition::Left => r,
 			IdiomPosition::Right => l,
 			IdiomPosition::None => return Ok(false),
 		};
+		let positions = if ft.0.phrases.iter().any(Option::is_some) {
+			ft.0.analyzer.extract_term_positions(stk, ctx, opt, vec![v.clone()]).await?
+		} else {
+			Vec::new()
+		};
 		let terms = ft.0.terms.read().await;
 		// Extract the terms set from the record
 		let t = ft.0.analyzer.extract_indexing_terms(stk, ctx, opt, &terms, v).await?;
 		drop(terms);
-		Ok(ft.0.query_terms_set.is_subset(&t))
+		Ok(ft.0.query.matches(|i| {
+			let terms = ft.0.leaf_terms.get(i)?.clone();
+			if terms.is_empty() {
+				return None;
+			}
+			let found = ft.0.query_terms_list[terms]
+				.iter()
+				.all(|m| m.as_deref().is_some_and(|m| t.contains_any(m)));
+			let phrase = ft.0.phrases.get(i).and_then(Option::as_ref);
+			Some(found && phrase.is_none_or(|p| !p.find(&positions, None).is_empty()))
+		}))
 	}
 
 	fn get_ft_entry(&self, match_ref: &Value) -> Option<&FtEntry> {
@@ -1061,7 +1203,10 @@ impl QueryExecutor {
 		if let Some((e, ft)) = self.get_ft_entry_and_index(hlp.match_ref()) {
 			if let Some(id) = e.0.index_option.id_ref() {
 				let tx = ctx.tx();
-				let res = ft.highlight(&tx, thg, &e.0.query_terms_list, hlp, id, doc).await;
+				let rm = e.record_match(thg);
+				let phrases = rm.as_ref().map(|m| m.phrases.matches.as_slice()).unwrap_or_default();
+				let res =
+					ft.highlight(&tx, thg, &e.0.highlighted_terms, phrases, hlp, id, doc).await;
 				return res;
 			}
 		}
@@ -1077,7 +1222,9 @@ impl QueryExecutor {
 	) -> Result<Value> {
 		if let Some((e, ft)) = self.get_ft_entry_and_index(&match_ref) {
 			let tx = ctx.tx();
-			let res = ft.extract_offsets(&tx, thg, &e.0.query_terms_list, partial).await;
+			let rm = e.record_match(thg);
+			let phrases = rm.as_ref().map(|m| m.phrases.matches.as_slice()).unwrap_or_default();
+			let res = ft.extract_offsets(&tx, thg, &e.0.highlighted_terms, phrases, partial).await;
 			return res;
 		}
 		Ok(Value::None)
@@ -1093,22 +1240,43 @@ impl QueryExecutor {
 		if let Some(e) = self.get_ft_entry(match_ref) {
 			if let Some(scorer) = &e.0.scorer {
 				let tx = ctx.tx();
-				let mut doc_id = if let Some(ir) = ir {
-					ir.doc_id()
-				} else {
-					None
-				};
-				if doc_id.is_none() {
-					let key = revision::to_vec(rid)?;
-					let di = e.0.doc_ids.read().await;
-					doc_id = di.get_doc_id(&tx, key).await?;
-					drop(di);
-				}
-				if let Some(doc_id) = doc_id {
-					let score = scorer.score(&tx, doc_id).await?;
+				if let Some(doc_id) = e.doc_id(&tx, rid, ir).await? {
+					let rm = e.record_match(rid);
+					let boosts = rm.as_ref().map(|m| m.boosts.as_slice());
+					let score = scorer.score(&tx, doc_id, boosts).await?;
 					if let Some(score) = score {
-						return Ok(Value::from(score));
+						// The closer the terms of the phrases are, the higher the score
+						let boost = rm.map_or(1.0, |m| m.phrases.boost);
+						return Ok(Value::from(score * boost));
+					}
+				}
+			}
+		}
+		Ok(Value::None)
+	}
+
+	pub(crate) async fn explain(
+		&self,
+		ctx: &Context,
+		match_ref: &Value,
+		rid: &Thing,
+		ir: Option<&Arc<IteratorRecord>>,
+	) -> Result<Value> {
+		if let Some(e) = self.get_ft_entry(match_ref) {
+			if let Some(scorer) = &e.0.scorer {
+				let tx = ctx.tx();
+				if let Some(doc_id) = e.doc_id(&tx, rid, ir).await? {
+					let mut res = Object::default();
+					let rm = e.record_match(rid);
+					let boosts = rm.as_ref().map(|m| m.boosts.as_slice());
+					let terms = e.0.terms.read().await;
+					for (term_id, val) in scorer.explain(&tx, doc_id, boosts).await? {
+						if let Some(term) = terms.get_term(&tx, term_id).await? {
+							res.insert(term, val);
+						}
 					}
+					drop(terms);
+					return Ok(Value::from(res));
 				}
 			}
 		}
@@ -1123,13 +1291,55 @@ struct Inner {
 	index_option: IndexOption,
 	doc_ids: Arc<RwLock<DocIds>>,
 	analyzer: Analyzer,
-	query_terms_set: TermsSet,
+	query: Query,
+	/// The terms of every leaf of the query
 	query_terms_list: TermsList,
+	/// For each leaf of the query, the range of its terms in the list of the query terms
+	leaf_terms: Vec<Range<usize>>,
+	/// The terms which are highlighted on their own,
+	/// which are neither excluded nor part of a phrase
+	highlighted_terms: TermsList,
+	/// The boost of each query term
+	boosts: Vec<f32>,
+	/// For each leaf of the query which is a phrase, the analyzed phrase
+	phrases: Vec<Option<Phrase>>,
+	/// What was found in each matched record, when the records are checked again
+	record_matches: Mutex<HashMap<Thing, Arc<RecordMatch>>>,
 	terms: Arc<RwLock<Terms>>,
 	terms_docs: TermsDocs,
 	scorer: Option<BM25Scorer>,
 }
 
+/// The terms and the phrases of a query found in the values of a record
+struct RecordHits {
+	/// The terms of each field of the index, if the query is scoped to fields
+	fields: Vec<TermsSet>,
+	/// For each leaf of the query which is a phrase, its occurrences
+	phrases: Vec<Option<Vec<PhraseMatch>>>,
+}
+
+/// What was found in a record matching a query whose records are checked again
+struct RecordMatch {
+	/// The phrases of the query found in the record
+	phrases: PhraseHits,
+	/// The boost of each query term, which is zero for the terms
+	/// which were not found in the field their clause is scoped to
+	boosts: Vec<f32>,
+}
+
+impl RecordHits {
+	fn field_contains(&self, field: usize, matched: &Option<Vec<MatchedTerm>>) -> bool {
+		match (self.fields.get(field), matched) {
+			(Some(t), Some(m)) => t.contains_any(m),
+			_ => false,
+		}
+	}
+
+	fn phrase_found(&self, leaf: usize) -> bool {
+		self.phrases.get(leaf).and_then(Option::as_ref).is_none_or(|m| !m.is_empty())
+	}
+}
+
 impl FtEntry {
 	async fn new(
 		stk: &mut Stk,
@@ -1138,19 +1348,52 @@ impl FtEntry {
 		ft: &FtIndex,
 		io: IndexOption,
 	) -> Result<Option<Self>> {
-		if let Matches(qs, _) = io.op() {
-			let (terms_list, terms_set) =
-				ft.extract_querying_terms(stk, ctx, opt, qs.to_owned()).await?;
+		if let Matches(qs, _, fz) = io.op() {
+			let query = Query::parse(qs, &Query::fields(io.ix_ref()))?;
+			let analyzer = ft.analyzer();
+			let mut terms_list = Vec::new();
+			let mut leaf_terms = Vec::with_capacity(query.leaves().len());
+			let mut highlighted_terms = Vec::new();
+			let mut boosts = Vec::new();
+			let mut phrases = Vec::with_capacity(query.leaves().len());
+			// Each leaf of the query is analyzed on its own
+			for leaf in query.leaves() {
+				let list = ft.extract_querying_terms(stk, ctx, opt, leaf.text.clone(), *fz).await?;
+				// The terms of the excluded clauses do not contribute to the score
+				let boost = if leaf.excluded {
+					0.0
+				} else {
+					leaf.boost
+				};
+				boosts.extend(std::iter::repeat_n(boost, list.len()));
+				if !leaf.excluded && leaf.slop.is_none() {
+					highlighted_terms.extend(list.iter().cloned());
+				}
+				let start = terms_list.len();
+				terms_list.extend(list);
+				leaf_terms.push(start..terms_list.len());
+				phrases.push(match leaf.slop {
+					Some(slop) => {
+						Some(analyzer.extract_phrase(stk, ctx, opt, leaf.text.clone(), slop).await?)
+					}
+					None => None,
+				});
+			}
 			let tx = ctx.tx();
 			let terms_docs = Arc::new(ft.get_terms_docs(&tx, &terms_list).await?);
 			drop(tx);
 			Ok(Some(Self(Arc::new(Inner {
 				index_option: io,
 				doc_ids: ft.doc_ids(),
-				analyzer: ft.analyzer(),
-				query_terms_set: terms_set,
+				analyzer,
+				query,
 				query_terms_list: terms_list,
-				scorer: ft.new_scorer(terms_docs.clone())?,
+				leaf_terms,
+				highlighted_terms,
+				phrases,
+				record_matches: Default::default(),
+				scorer: ft.new_scorer(terms_docs.clone(), boosts.clone())?,
+				boosts,
 				terms: ft.terms(),
 				terms_docs,
 			}))))
@@ -1158,6 +1401,105 @@ impl FtEntry {
 			Ok(None)
 		}
 	}
+
+	/// The documents of the index matching the query
+	fn hits(&self) -> Option<RoaringTreemap> {
+		self.0.query.hits(&self.0.leaf_terms, &self.0.terms_docs)
+	}
+
+	/// Searches the terms of the fields, and the phrases, of the query in a record
+	async fn record_hits(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<RecordHits> {
+		let ix = self.0.index_option.ix_ref();
+		let mut values = Vec::with_capacity(ix.cols.len());
+		for i in ix.cols.iter() {
+			values.push(i.compute(stk, ctx, opt, doc).await.catch_return()?);
+		}
+		let mut fields = Vec::new();
+		if self.0.query.leaves().iter().any(|l| l.field.is_some()) {
+			let terms = self.0.terms.read().await;
+			for v in &values {
+				fields.push(
+					self.0
+						.analyzer
+						.extract_indexing_terms(stk, ctx, opt, &terms, v.clone())
+						.await?,
+				);
+			}
+			drop(terms);
+		}
+		let mut phrases = Vec::new();
+		if self.0.phrases.iter().any(Option::is_some) {
+			let positions = self.0.analyzer.extract_term_positions(stk, ctx, opt, values).await?;
+			for (p, leaf) in self.0.phrases.iter().zip(self.0.query.leaves()) {
+				phrases.push(p.as_ref().map(|p| p.find(&positions, leaf.field)));
+			}
+		}
+		Ok(RecordHits {
+			fields,
+			phrases,
+		})
+	}
+
+	/// Keeps what was found in a matched record, for its score and its highlights
+	fn set_record_match(&self, thg: &Thing, matched: bool, rec: RecordHits) {
+		let mut matches = self.0.record_matches.lock();
+		if !matched {
+			matches.remove(thg);
+			return;
+		}
+		let mut boosts = self.0.boosts.clone();
+		for (leaf, terms) in self.0.query.leaves().iter().zip(&self.0.leaf_terms) {
+			if let Some(field) = leaf.field {
+				for i in terms.clone() {
+					if !rec.field_contains(field, &self.0.query_terms_list[i]) {
+						boosts[i] = 0.0;
+					}
+				}
+			}
+		}
+		// The phrases of the excluded clauses are neither boosted nor highlighted
+		let found = rec
+			.phrases
+			.iter()
+			.zip(self.0.query.leaves())
+			.filter(|(_, leaf)| !leaf.excluded)
+			.filter_map(|(m, _)| m.as_deref());
+		let phrases = PhraseHits::new(found);
+		matches.insert(
+			thg.clone(),
+			Arc::new(RecordMatch {
+				phrases,
+				boosts,
+			}),
+		);
+	}
+
+	/// What was found in a record, if it was checked again and matched
+	fn record_match(&self, thg: &Thing) -> Option<Arc<RecordMatch>> {
+		self.0.record_matches.lock().get(thg).cloned()
+	}
+
+	async fn doc_id(
+		&self,
+		tx: &Transaction,
+		rid: &Thing,
+		ir: Option<&Arc<IteratorRecord>>,
+	) -> Result<Option<DocId>> {
+		if let Some(doc_id) = ir.and_then(|ir| ir.doc_id()) {
+			return Ok(Some(doc_id));
+		}
+		let key = revision::to_vec(rid)?;
+		let di = self.0.doc_ids.read().await;
+		let doc_id = di.get_doc_id(tx, key).await?;
+		drop(di);
+		Ok(doc_id)
+	}
 }
 
 #[derive(Clone)]
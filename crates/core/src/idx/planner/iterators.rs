@@ -3,18 +3,20 @@ use crate::dbs::Options;
 use crate::expr::statements::DefineIndexStatement;
 use crate::expr::{Array, Ident, Number, Thing, Value};
 use crate::idx::docids::DocId;
-use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::{FtIndex, HitsIterator};
 use crate::idx::planner::plan::RangeValue;
 use crate::idx::planner::tree::IndexReference;
+use crate::idx::trigram::trigram_array;
 use crate::key::index::Index;
+use crate::key::r#ref;
 use crate::kvs::{Key, Val};
-use crate::kvs::{KeyEncode, Transaction};
+use crate::kvs::{KeyDecode, KeyEncode, Transaction};
 use anyhow::Result;
 use radix_trie::Trie;
+use roaring::RoaringTreemap;
 use rust_decimal::Decimal;
 use std::borrow::Cow;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -120,6 +122,8 @@ pub(crate) enum ThingIterator {
 	UniqueUnion(UniqueUnionThingIterator),
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	Matches(MatchesThingIterator),
+	Trigram(TrigramThingIterator),
+	Reference(ReferenceThingIterator),
 	Knn(KnnIterator),
 	Multiples(Box<MultipleIterators>),
 }
@@ -143,6 +147,8 @@ impl ThingIterator {
 			Self::IndexUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::Matches(i) => i.next_batch(ctx, txn, size).await,
+			Self::Trigram(i) => i.next_batch(ctx, txn, size).await,
+			Self::Reference(i) => i.next_batch(txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
@@ -168,6 +174,8 @@ impl ThingIterator {
 			Self::IndexUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::UniqueUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::Matches(i) => i.next_count(ctx, txn, size).await,
+			Self::Trigram(i) => i.next_count(ctx, txn, size).await,
+			Self::Reference(i) => i.next_count(txn, size).await,
 			Self::Knn(i) => i.next_count(ctx, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
@@ -406,6 +414,60 @@ pub(super) enum ValueType {
 }
 
 impl ValueType {
+	/// The lowest number of this type, if it is a number type
+	fn min_number(&self) -> Option<Number> {
+		match self {
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MIN)),
+			Self::NumberFloat => Some(Number::Float(f64::MIN)),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MIN)),
+		}
+	}
+
+	/// The highest number of this type, if it is a number type
+	fn max_number(&self) -> Option<Number> {
+		match self {
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MAX)),
+			Self::NumberFloat => Some(Number::Float(f64::MAX)),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MAX)),
+		}
+	}
+
+	/// The beginning of the keys starting with the prefix values,
+	/// followed by a value of this type
+	fn prefix_composite_beg(
+		&self,
+		ns: &str,
+		db: &str,
+		ix_what: &Ident,
+		ix_name: &Ident,
+		prefix: &Array,
+	) -> Result<Vec<u8>> {
+		let Some(n) = self.min_number() else {
+			return Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, prefix);
+		};
+		let fd = prefix.clone() + Value::Number(n);
+		Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd)
+	}
+
+	/// The end of the keys starting with the prefix values,
+	/// followed by a value of this type
+	fn prefix_composite_end(
+		&self,
+		ns: &str,
+		db: &str,
+		ix_what: &Ident,
+		ix_name: &Ident,
+		prefix: &Array,
+	) -> Result<Vec<u8>> {
+		let Some(n) = self.max_number() else {
+			return Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, prefix);
+		};
+		let fd = prefix.clone() + Value::Number(n);
+		Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd)
+	}
+
 	fn prefix_beg(&self, ns: &str, db: &str, ix_what: &Ident, ix_name: &Ident) -> Result<Vec<u8>> {
 		match self {
 			Self::None => Index::prefix_beg(ns, db, ix_what, ix_name),
@@ -501,6 +563,57 @@ impl IndexRangeThingIterator {
 		Ok(RangeScan::new(beg, range.from.inclusive, end, range.to.inclusive))
 	}
 
+	/// Scans the keys of a compound index starting with the prefix values,
+	/// followed by a value within the range. The same scan is used for
+	/// unique indexes, as their keys start with the same values.
+	pub(super) fn prefix_range(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		prefix: &Array,
+		range: &IteratorRange<'_>,
+	) -> Result<Self> {
+		Ok(Self {
+			irf,
+			r: Self::prefix_range_scan(ns, db, ix, prefix, range)?,
+		})
+	}
+
+	fn prefix_range_scan(
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		prefix: &Array,
+		range: &IteratorRange<'_>,
+	) -> Result<RangeScan> {
+		let (what, name) = (&ix.what, &ix.name);
+		let beg = match &range.from.value {
+			Value::None => range.value_type.prefix_composite_beg(ns, db, what, name, prefix)?,
+			v => {
+				let fd = prefix.clone() + v.clone();
+				if range.from.inclusive {
+					Index::prefix_ids_composite_beg(ns, db, what, name, &fd)?
+				} else {
+					Index::prefix_ids_composite_end(ns, db, what, name, &fd)?
+				}
+			}
+		};
+		let end = match &range.to.value {
+			Value::None => range.value_type.prefix_composite_end(ns, db, what, name, prefix)?,
+			v => {
+				let fd = prefix.clone() + v.clone();
+				if range.to.inclusive {
+					Index::prefix_ids_composite_end(ns, db, what, name, &fd)?
+				} else {
+					Index::prefix_ids_composite_beg(ns, db, what, name, &fd)?
+				}
+			}
+		};
+		// The composite bounds never match a key, so no key has to be excluded
+		Ok(RangeScan::new(beg, true, end, true))
+	}
+
 	fn compute_beg(
 		ns: &str,
 		db: &str,
@@ -597,6 +710,21 @@ impl IndexRangeReverseThingIterator {
 		})
 	}
 
+	pub(super) fn prefix_range(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		prefix: &Array,
+		range: &IteratorRange<'_>,
+	) -> Result<Self> {
+		let r = IndexRangeThingIterator::prefix_range_scan(ns, db, ix, prefix, range)?;
+		Ok(Self {
+			irf,
+			r: ReverseRangeScan::new(r),
+		})
+	}
+
 	pub(super) fn full_range(
 		irf: IteratorRef,
 		ns: &str,
@@ -1352,9 +1480,9 @@ impl MatchesThingIterator {
 	pub(super) async fn new(
 		irf: IteratorRef,
 		fti: &FtIndex,
-		terms_docs: TermsDocs,
+		hits: Option<RoaringTreemap>,
 	) -> Result<Self> {
-		let hits = fti.new_hits_iterator(terms_docs)?;
+		let hits = fti.new_hits_iterator(hits);
 		let hits_left = if let Some(h) = &hits {
 			h.len()
 		} else {
@@ -1420,6 +1548,145 @@ impl MatchesThingIterator {
 	}
 }
 
+/// Iterates over the records containing every trigram of a string.
+/// The records of each trigram are collected and intersected on the
+/// first batch, they are then returned in the order of their ids.
+pub(crate) struct TrigramThingIterator {
+	irf: IteratorRef,
+	ranges: Vec<(Key, Key)>,
+	candidates: Option<VecDeque<Thing>>,
+}
+
+impl TrigramThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		trigrams: &BTreeSet<String>,
+	) -> Result<Self> {
+		let mut ranges = Vec::with_capacity(trigrams.len());
+		for t in trigrams {
+			let a = trigram_array(t);
+			ranges.push((
+				Index::prefix_ids_beg(ns, db, &ix.what, &ix.name, &a)?,
+				Index::prefix_ids_end(ns, db, &ix.what, &ix.name, &a)?,
+			));
+		}
+		Ok(Self {
+			irf,
+			ranges,
+			candidates: None,
+		})
+	}
+
+	async fn candidates(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<&mut VecDeque<Thing>> {
+		if self.candidates.is_none() {
+			let mut matches: Option<Vec<Thing>> = None;
+			for (beg, end) in &self.ranges {
+				let mut beg = beg.clone();
+				let mut records = HashSet::new();
+				loop {
+					if ctx.is_done(true).await? {
+						break;
+					}
+					let res = IndexEqualThingIterator::next_scan(tx, &mut beg, end, limit).await?;
+					if res.is_empty() {
+						break;
+					}
+					for (_, val) in res {
+						records.insert(revision::from_slice::<Thing>(&val)?);
+					}
+				}
+				let m = match matches {
+					None => {
+						let mut m: Vec<Thing> = records.into_iter().collect();
+						m.sort();
+						m
+					}
+					Some(m) => m.into_iter().filter(|t| records.contains(t)).collect(),
+				};
+				// No record can contain every trigram
+				let empty = m.is_empty();
+				matches = Some(m);
+				if empty {
+					break;
+				}
+			}
+			self.candidates = Some(matches.unwrap_or_default().into());
+		}
+		Ok(self.candidates.get_or_insert_default())
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B> {
+		let irf = self.irf;
+		let candidates = self.candidates(ctx, tx, limit).await?;
+		let n = candidates.len().min(limit as usize);
+		let mut records = B::with_capacity(n);
+		for thg in candidates.drain(..n) {
+			records.add(IndexItemRecord::new_key(thg, irf.into()));
+		}
+		Ok(records)
+	}
+
+	async fn next_count(&mut self, ctx: &Context, tx: &Transaction, limit: u32) -> Result<usize> {
+		let candidates = self.candidates(ctx, tx, limit).await?;
+		let n = candidates.len().min(limit as usize);
+		candidates.drain(..n);
+		Ok(n)
+	}
+}
+
+/// Iterates over the records of a table which reference a record id,
+/// using the references maintained by a field with a `REFERENCE` clause
+pub(crate) struct ReferenceThingIterator {
+	irf: IteratorRef,
+	beg: Vec<u8>,
+	end: Vec<u8>,
+}
+
+impl ReferenceThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ff: &str,
+		thing: &Thing,
+	) -> Result<Self> {
+		Ok(Self {
+			irf,
+			beg: r#ref::ffprefix(ns, db, &thing.tb, &thing.id, tb, ff)?,
+			end: r#ref::ffsuffix(ns, db, &thing.tb, &thing.id, tb, ff)?,
+		})
+	}
+
+	async fn next_batch<B: IteratorBatch>(&mut self, tx: &Transaction, limit: u32) -> Result<B> {
+		let res = IndexEqualThingIterator::next_scan(tx, &mut self.beg, &self.end, limit).await?;
+		let mut records = B::with_capacity(res.len());
+		for (key, _) in res {
+			let key = r#ref::Ref::decode(&key)?;
+			let thg = Thing::from((key.ft, key.fk));
+			records.add(IndexItemRecord::new_key(thg, self.irf.into()));
+		}
+		Ok(records)
+	}
+
+	async fn next_count(&mut self, tx: &Transaction, limit: u32) -> Result<usize> {
+		Ok(IndexEqualThingIterator::next_scan(tx, &mut self.beg, &self.end, limit).await?.len())
+	}
+}
+
 pub(crate) type KnnIteratorResult = (Arc<Thing>, f64, Option<Arc<Value>>);
 
 pub(crate) struct KnnIterator {
@@ -14,7 +14,7 @@ use crate::expr::{Cond, Fields, Groups, Table, order::Ordering};
 use crate::idx::planner::executor::{InnerQueryExecutor, IteratorEntry, QueryExecutor};
 use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
-use crate::idx::planner::plan::{Plan, PlanBuilder, PlanBuilderParameters};
+use crate::idx::planner::plan::{IndexOption, Plan, PlanBuilder, PlanBuilderParameters};
 use crate::idx::planner::tree::Tree;
 use anyhow::Result;
 use reblessive::tree::Stk;
@@ -63,6 +63,121 @@ impl Display for ScanDirection {
 	}
 }
 
+/// The reasons why an index can not serve an expression
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum CandidateRejection {
+	/// The operator can not be resolved by this type of index
+	NonSargableOperator,
+	/// The value is of a type which this index can not be queried with
+	TypeMismatch,
+	/// The field is not the first column of the index
+	NotLeadingColumn,
+	/// The value can not be computed before the iteration
+	NotComputable,
+	/// The index is excluded by the WITH clause
+	ExcludedByWith,
+	/// Another index is already used for the same expression
+	Superseded,
+	/// The index keys are not sorted in the requested ordering
+	UnsupportedOrdering,
+	/// The index can not be compared with the chosen index, as the table
+	/// has no statistics for it
+	MissingStatistics,
+}
+
+impl Display for CandidateRejection {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::NonSargableOperator => f.write_str("NonSargableOperator"),
+			Self::TypeMismatch => f.write_str("TypeMismatch"),
+			Self::NotLeadingColumn => f.write_str("NotLeadingColumn"),
+			Self::NotComputable => f.write_str("NotComputable"),
+			Self::ExcludedByWith => f.write_str("ExcludedByWith"),
+			Self::Superseded => f.write_str("Superseded"),
+			Self::UnsupportedOrdering => f.write_str("UnsupportedOrdering"),
+			Self::MissingStatistics => f.write_str("MissingStatistics"),
+		}
+	}
+}
+
+/// An index which was considered to serve an expression of a query
+pub(crate) struct IndexCandidate {
+	pub(crate) index: String,
+	pub(crate) expression: String,
+	/// The index option which would be used to serve the expression
+	option: Option<IndexOption>,
+	/// The number of records estimated from the table statistics
+	pub(crate) estimated_rows: Option<u64>,
+	pub(crate) rejection: Option<CandidateRejection>,
+}
+
+/// The analysis of how a table is accessed, reported by EXPLAIN VERBOSE
+pub(crate) struct TableAnalysis {
+	pub(crate) table: String,
+	pub(crate) candidates: Vec<IndexCandidate>,
+	/// The chosen access path
+	pub(crate) access: &'static str,
+	/// The indexes used by the chosen access path
+	pub(crate) indexes: Vec<String>,
+}
+
+impl TableAnalysis {
+	async fn new(
+		ctx: &StatementContext<'_>,
+		t: &Table,
+		mut candidates: Vec<IndexCandidate>,
+		plan: &Plan,
+	) -> Result<Self> {
+		let (access, indexes) = match plan {
+			Plan::TableIterator(..) => ("TableIterator", vec![]),
+			Plan::SingleIndex(_, io, _) => ("SingleIndex", vec![io.ix_ref().name.to_raw()]),
+			Plan::MultiIndex(non_range_indexes, ranges_indexes, _) => {
+				let mut indexes: Vec<String> = vec![];
+				let names = non_range_indexes
+					.iter()
+					.map(|(_, io)| io.ix_ref())
+					.chain(ranges_indexes.iter().map(|(ixr, _)| ixr));
+				for ixr in names {
+					let name = ixr.name.to_raw();
+					if !indexes.contains(&name) {
+						indexes.push(name);
+					}
+				}
+				("MultiIndex", indexes)
+			}
+			Plan::SingleIndexRange(ixr, ..) => ("SingleIndexRange", vec![ixr.name.to_raw()]),
+			Plan::SingleIndexPrefixRange(ixr, ..) => {
+				("SingleIndexPrefixRange", vec![ixr.name.to_raw()])
+			}
+			Plan::Reference(..) => ("Reference", vec![]),
+		};
+		// A single index is chosen from the statistics, unless an order of
+		// preference is given in the WITH clause
+		let selective =
+			matches!(plan, Plan::SingleIndex(..)) && !matches!(ctx.with, Some(With::Index(_)));
+		for c in &mut candidates {
+			let Some(io) = c.option.take() else {
+				continue;
+			};
+			c.estimated_rows = PlanBuilder::estimate_rows(ctx, &io).await?;
+			if selective
+				&& c.rejection.is_none()
+				&& c.estimated_rows.is_none()
+				&& PlanBuilder::is_estimable(&io)
+				&& !indexes.contains(&c.index)
+			{
+				c.rejection = Some(CandidateRejection::MissingStatistics);
+			}
+		}
+		Ok(Self {
+			table: t.0.clone(),
+			candidates,
+			access,
+			indexes,
+		})
+	}
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum GrantedPermission {
 	None,
@@ -125,6 +240,33 @@ impl<'a> StatementContext<'a> {
 		Ok(GrantedPermission::Full)
 	}
 
+	/// Checks if the records of the table are limited to the tenant of the session
+	pub(crate) async fn has_tenant(&self, tb: &str) -> Result<bool> {
+		if !self.opt.check_tenant() {
+			return Ok(false);
+		}
+		match self.ctx.tx().get_tb(self.ns, self.db, tb).await {
+			Ok(table) => Ok(table.tenant.is_some()),
+			Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Checks if the deleted records of the table are kept with a tombstone
+	pub(crate) async fn has_soft_delete(&self, tb: &str) -> Result<bool> {
+		match self.ctx.tx().get_tb(self.ns, self.db, tb).await {
+			Ok(table) => Ok(table.soft_delete),
+			Err(e) if matches!(e.downcast_ref(), Some(Error::TbNotFound { .. })) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Checks if any field of the table is defined with EXPIRE
+	pub(crate) async fn has_expiring_fields(&self, tb: &str) -> Result<bool> {
+		let fds = self.ctx.tx().all_tb_fields(self.ns, self.db, tb, None).await?;
+		Ok(fds.iter().any(|fd| fd.expire))
+	}
+
 	pub(crate) fn check_record_strategy(
 		&self,
 		all_expressions_with_index: bool,
@@ -223,6 +365,7 @@ pub(crate) struct QueryPlanner {
 	ordering_indexes: Vec<IteratorRef>,
 	granted_permissions: HashMap<String, GrantedPermission>,
 	any_specific_permission: bool,
+	analyses: Vec<TableAnalysis>,
 }
 
 impl QueryPlanner {
@@ -236,6 +379,7 @@ impl QueryPlanner {
 			ordering_indexes: vec![],
 			granted_permissions: HashMap::default(),
 			any_specific_permission: false,
+			analyses: vec![],
 		}
 	}
 
@@ -246,18 +390,30 @@ impl QueryPlanner {
 		ctx: &StatementContext<'_>,
 		tb: &str,
 	) -> Result<GrantedPermission> {
-		if ctx.is_perm {
-			if let Some(p) = self.granted_permissions.get(tb) {
-				return Ok(*p);
-			}
-			let p = ctx.check_table_permission(tb).await?;
-			self.granted_permissions.insert(tb.to_string(), p);
-			if matches!(p, GrantedPermission::Specific) {
-				self.any_specific_permission = true;
+		if let Some(p) = self.granted_permissions.get(tb) {
+			return Ok(*p);
+		}
+		let p = match ctx.is_perm {
+			true => ctx.check_table_permission(tb).await?,
+			false => GrantedPermission::Full,
+		};
+		// Expired records, deleted records, and the records of other tenants, are
+		// filtered out according to their values, so the record content needs to be fetched
+		let p = match p {
+			GrantedPermission::Full
+				if ctx.has_expiring_fields(tb).await?
+					|| ctx.has_tenant(tb).await?
+					|| ctx.has_soft_delete(tb).await? =>
+			{
+				GrantedPermission::Specific
 			}
-			return Ok(p);
+			p => p,
+		};
+		self.granted_permissions.insert(tb.to_string(), p);
+		if matches!(p, GrantedPermission::Specific) {
+			self.any_specific_permission = true;
 		}
-		Ok(GrantedPermission::Full)
+		Ok(p)
 	}
 
 	pub(crate) async fn add_iterables(
@@ -288,14 +444,21 @@ impl QueryPlanner {
 			root: tree.root,
 			gp,
 			compound_indexes: tree.index_map.compound_indexes,
+			compound_ranges: tree.index_map.compound_ranges,
+			compound_orders: tree.index_map.compound_orders,
 			order_limit: tree.index_map.order_limit,
 			with_indexes: tree.with_indexes,
 			all_and: tree.all_and,
 			all_expressions_with_index: tree.all_expressions_with_index,
 			all_and_groups: tree.all_and_groups,
 			reverse_scan: ctx.ctx.tx().reverse_scan(),
+			references: tree.references,
 		};
-		match PlanBuilder::build(ctx, p).await? {
+		let plan = PlanBuilder::build(ctx, p).await?;
+		if let Some(candidates) = tree.candidates {
+			self.analyses.push(TableAnalysis::new(ctx, &t, candidates, &plan).await?);
+		}
+		match plan {
 			Plan::SingleIndex(exp, io, rs) => {
 				if io.require_distinct() {
 					self.requires_distinct = true;
@@ -328,6 +491,19 @@ impl QueryPlanner {
 				}
 				self.add(t.clone(), Some(ir), exe, it, keys_only);
 			}
+			Plan::SingleIndexPrefixRange(ixr, prefix, rq, rs, order) => {
+				let reverse = order.unwrap_or(false);
+				let ie = IteratorEntry::PrefixRange(ixr, prefix, rq.from, rq.to, reverse);
+				let ir = exe.add_iterator(ie);
+				if order.is_some() {
+					self.ordering_indexes.push(ir);
+				}
+				self.add(t.clone(), Some(ir), exe, it, rs);
+			}
+			Plan::Reference(id, thing, rs) => {
+				let ir = exe.add_iterator(IteratorEntry::Reference(id, thing));
+				self.add(t.clone(), Some(ir), exe, it, rs);
+			}
 			Plan::TableIterator(reason, rs, sc) => {
 				if let Some(reason) = reason {
 					self.fallbacks.push(reason);
@@ -374,6 +550,10 @@ impl QueryPlanner {
 		&self.fallbacks
 	}
 
+	pub(crate) fn analyses(&self) -> &[TableAnalysis] {
+		&self.analyses
+	}
+
 	pub(crate) fn is_order(&self, irf: &IteratorRef) -> bool {
 		self.ordering_indexes.contains(irf)
 	}
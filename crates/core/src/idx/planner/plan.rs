@@ -1,9 +1,11 @@
+use crate::expr::index::Index;
 use crate::expr::with::With;
-use crate::expr::{Array, Expression, Idiom, Number, Object};
+use crate::expr::{Array, Expression, Idiom, Number, Object, Thing};
 use crate::expr::{Operator, Value};
-use crate::idx::ft::MatchRef;
+use crate::idx::ft::{Fuzziness, MatchRef};
 use crate::idx::planner::tree::{
-	CompoundIndexes, GroupRef, IdiomCol, IdiomPosition, IndexReference, Node,
+	CompoundIndexes, CompoundOrders, CompoundRanges, GroupRef, IdiomCol, IdiomPosition,
+	IndexReference, Node,
 };
 use crate::idx::planner::{GrantedPermission, RecordStrategy, ScanDirection, StatementContext};
 use anyhow::Result;
@@ -28,12 +30,15 @@ pub(super) struct PlanBuilderParameters {
 	pub(super) root: Option<Node>,
 	pub(super) gp: GrantedPermission,
 	pub(super) compound_indexes: CompoundIndexes,
+	pub(super) compound_ranges: CompoundRanges,
+	pub(super) compound_orders: CompoundOrders,
 	pub(super) order_limit: Option<IndexOption>,
 	pub(super) with_indexes: Option<Vec<IndexReference>>,
 	pub(super) all_and: bool,
 	pub(super) all_expressions_with_index: bool,
 	pub(super) all_and_groups: HashMap<GroupRef, bool>,
 	pub(super) reverse_scan: bool,
+	pub(super) references: Vec<(Arc<Idiom>, Thing)>,
 }
 
 impl PlanBuilder {
@@ -52,6 +57,16 @@ impl PlanBuilder {
 			return Self::table_iterator(ctx, Some("WITH NOINDEX"), p.gp).await;
 		}
 
+		// The indexes do not store the deleted records of soft delete tables
+		if let Some(With::Deleted) = ctx.with {
+			return Self::table_iterator(ctx, Some("WITH DELETED"), p.gp).await;
+		}
+
+		// The indexes only store the current version of the records
+		if ctx.opt.version.is_some() {
+			return Self::table_iterator(ctx, Some("VERSION"), p.gp).await;
+		}
+
 		// Browse the AST and collect information
 		if let Some(root) = &p.root {
 			if let Err(e) = b.eval_node(root) {
@@ -61,6 +76,12 @@ impl PlanBuilder {
 
 		// If all boolean operators are AND, we can use the single index plan
 		if p.all_and {
+			// Evaluate the record strategy
+			let record_strategy = ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
+			// The possible plans, in the order they are used by default
+			let mut plans = Vec::new();
+			// Look for a compound index with equalities followed by a range
+			let compound_range = b.take_compound_range(&p.compound_indexes, &p.compound_ranges);
 			// We try first the largest compound indexed
 			let mut compound_index = None;
 			for (ixr, vals) in p.compound_indexes {
@@ -75,47 +96,59 @@ impl PlanBuilder {
 					}
 				}
 			}
+			// The prefix range is preferred when it uses more columns
+			if let Some((cols, ixr, prefix, rq)) = compound_range {
+				if compound_index.as_ref().is_none_or(|(c, _)| cols > *c) {
+					// Check if the scan matches the order, in a supported direction
+					let order = match p.compound_orders.get(&ixr) {
+						Some((col, reverse)) if *col == prefix.len() => {
+							(p.reverse_scan || !reverse).then_some(*reverse)
+						}
+						_ => None,
+					};
+					plans.push(Plan::SingleIndexPrefixRange(
+						ixr,
+						prefix,
+						rq,
+						record_strategy,
+						order,
+					));
+				}
+			}
 			if let Some((_, io)) = compound_index {
-				// Evaluate if we can use keys only
-				let record_strategy =
-					ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
-				// Return the plan
-				return Ok(Plan::SingleIndex(None, io, record_strategy));
+				plans.push(Plan::SingleIndex(None, io, record_strategy));
 			}
-
 			// We take the "first" range query if one is available
-			if let Some((_, group)) = b.groups.into_iter().next() {
+			if let Some((_, group)) = b.groups.pop_first() {
 				if let Some((ir, rq)) = group.take_first_range() {
-					// Evaluate the record strategy
-					let record_strategy =
-						ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
-					let is_order = if let Some(io) = p.order_limit {
+					let is_order = if let Some(io) = &p.order_limit {
 						io.ixr == ir
 					} else {
 						false
 					};
-					// Return the plan
-					return Ok(Plan::SingleIndexRange(ir, rq, record_strategy, is_order));
+					plans.push(Plan::SingleIndexRange(ir, rq, record_strategy, is_order));
 				}
 			}
-
-			// Otherwise, we try to find the most interesting (todo: TBD) single index option
-			if let Some((e, i)) = b.non_range_indexes.pop() {
-				// Evaluate the record strategy
-				let record_strategy =
-					ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
-				// Return the plan
-				return Ok(Plan::SingleIndex(Some(e), i, record_strategy));
+			// Otherwise, we try to find the most interesting single index option
+			if let Some((e, i)) = b.take_selective_index(ctx).await? {
+				plans.push(Plan::SingleIndex(Some(e), i, record_strategy));
 			}
 			// If there is an order option
 			if let Some(o) = p.order_limit {
-				// Evaluate the record strategy
-				let record_strategy =
-					ctx.check_record_strategy(p.all_expressions_with_index, p.gp)?;
 				// Check it is compatible with the reverse scan capability
 				if Self::check_order_scan(p.reverse_scan, o.op()) {
-					// Return the plan
-					return Ok(Plan::SingleIndex(None, o.clone(), record_strategy));
+					plans.push(Plan::SingleIndex(None, o, record_strategy));
+				}
+			}
+			if !plans.is_empty() {
+				// The index listed first in the WITH clause is preferred
+				let i = b.preferred(plans.iter().map(Plan::ix_ref)).unwrap_or(0);
+				return Ok(plans.swap_remove(i));
+			}
+			// Otherwise, the records may be found by the references of a field
+			if b.with_indexes.is_none() {
+				if let Some((id, thing)) = p.references.into_iter().next() {
+					return Ok(Plan::Reference(id, thing, record_strategy));
 				}
 			}
 		}
@@ -151,6 +184,69 @@ impl PlanBuilder {
 		Ok(Plan::TableIterator(reason, rs, sc))
 	}
 
+	/// Takes the index option which is estimated to match the fewest records.
+	/// The estimates are only used when every table involved has been analyzed,
+	/// otherwise the last index option is used.
+	async fn take_selective_index(
+		&mut self,
+		ctx: &StatementContext<'_>,
+	) -> Result<Option<(Arc<Expression>, IndexOption)>> {
+		// The index listed first in the WITH clause is preferred to the estimates
+		if let Some(i) =
+			self.preferred(self.non_range_indexes.iter().map(|(_, io)| Some(io.ix_ref())))
+		{
+			return Ok(Some(self.non_range_indexes.remove(i)));
+		}
+		if self.non_range_indexes.len() > 1 {
+			let mut best: Option<(usize, u64)> = None;
+			for (i, (_, io)) in self.non_range_indexes.iter().enumerate() {
+				let Some(rows) = Self::estimate_rows(ctx, io).await? else {
+					best = None;
+					break;
+				};
+				// On a tie, we prefer the last index option
+				if best.is_none_or(|(_, b)| rows <= b) {
+					best = Some((i, rows));
+				}
+			}
+			if let Some((i, _)) = best {
+				return Ok(Some(self.non_range_indexes.remove(i)));
+			}
+		}
+		Ok(self.non_range_indexes.pop())
+	}
+
+	/// Estimates the number of records matched by an index option
+	pub(super) async fn estimate_rows(
+		ctx: &StatementContext<'_>,
+		io: &IndexOption,
+	) -> Result<Option<u64>> {
+		let txn = ctx.ctx.tx();
+		let Some(stats) = txn.get_tb_statistics(ctx.ns, ctx.db, &io.ixr.what).await? else {
+			return Ok(None);
+		};
+		let rows = match io.ixr.index {
+			Index::Uniq => Some(1),
+			Index::Idx => stats.estimate(&io.ixr.name),
+			_ => None,
+		};
+		Ok(match io.op() {
+			IndexOperator::Equality(_) => rows,
+			IndexOperator::Union(v) => match v.as_ref() {
+				Value::Array(a) => rows.map(|r| r * a.len() as u64),
+				_ => rows,
+			},
+			_ => None,
+		})
+	}
+
+	/// Checks if the number of records matched by an index option can be
+	/// estimated once the table has been analyzed
+	pub(super) fn is_estimable(io: &IndexOption) -> bool {
+		matches!(io.ixr.index, Index::Idx | Index::Uniq)
+			&& matches!(io.op(), IndexOperator::Equality(_) | IndexOperator::Union(_))
+	}
+
 	/// Check if we have an explicit list of index that we should use
 	fn filter_index_option(&self, io: Option<&IndexOption>) -> Option<IndexOption> {
 		if let Some(io) = io {
@@ -171,6 +267,20 @@ impl PlanBuilder {
 		true
 	}
 
+	/// Finds the position of the option using the index which is listed first
+	/// in the WITH clause, when the clause lists several indexes for the table.
+	/// On a tie, the first option is taken.
+	fn preferred<'a, I>(&self, ixrs: I) -> Option<usize>
+	where
+		I: Iterator<Item = Option<&'a IndexReference>>,
+	{
+		let wi = self.with_indexes.as_ref().filter(|wi| wi.len() > 1)?;
+		ixrs.enumerate()
+			.filter_map(|(i, ixr)| Some((wi.iter().position(|w| w == ixr?)?, i)))
+			.min()
+			.map(|(_, i)| i)
+	}
+
 	/// Check if the ordering is compatible with the datastore transaction capabilities
 	fn check_order_scan(has_reverse_scan: bool, op: &IndexOperator) -> bool {
 		has_reverse_scan || matches!(op, IndexOperator::Order(false))
@@ -231,6 +341,72 @@ impl PlanBuilder {
 		))
 	}
 
+	/// Takes the compound index whose leading columns are each matched by a
+	/// single value, and whose next column is bounded by range parts. When
+	/// several indexes are possible, the one using the most columns is taken.
+	fn take_compound_range(
+		&self,
+		compound_indexes: &CompoundIndexes,
+		compound_ranges: &CompoundRanges,
+	) -> Option<(IdiomCol, IndexReference, Array, UnionRangeQueryBuilder)> {
+		let mut best: Option<(IdiomCol, IndexReference, Array, UnionRangeQueryBuilder)> = None;
+		for (ixr, ranges) in compound_ranges {
+			let Some(columns) = compound_indexes.get(ixr) else {
+				continue;
+			};
+			if let Some((cols, prefix, rq)) = self.check_compound_range(ixr, columns, ranges) {
+				// On a tie, the index name keeps the plan consistent
+				if let Some((c, b, ..)) = &best {
+					if cols < *c || (cols == *c && ixr.name >= b.name) {
+						continue;
+					}
+				}
+				best = Some((cols, ixr.clone(), prefix, rq));
+			}
+		}
+		best
+	}
+
+	/// Check if a compound index can be scanned over a range of the column
+	/// following its leading columns matched by equalities.
+	fn check_compound_range(
+		&self,
+		ixr: &IndexReference,
+		columns: &[Vec<Arc<Value>>],
+		ranges: &[(IdiomCol, Operator, Arc<Value>)],
+	) -> Option<(IdiomCol, Array, UnionRangeQueryBuilder)> {
+		// Check the index can be used
+		if !self.allowed_index(ixr) {
+			return None;
+		}
+		// Collect the leading columns matched by a single value
+		let mut prefix = Vec::new();
+		for vals in columns {
+			let Some(v) = vals.first() else {
+				break;
+			};
+			if v.is_none_or_null() || vals.iter().any(|o| o != v) {
+				break;
+			}
+			prefix.push(v.as_ref().clone());
+		}
+		let col = prefix.len();
+		if col == 0 || col == columns.len() {
+			return None;
+		}
+		// Bound the next column with its range parts
+		let mut rq = UnionRangeQueryBuilder::default();
+		for (c, op, val) in ranges {
+			if *c == col {
+				rq.add_part(op, val);
+			}
+		}
+		if rq.from.value.is_none() && rq.to.value.is_none() {
+			return None;
+		}
+		Some((col + 1, Array(prefix), rq))
+	}
+
 	fn cartesian_product(values: &[Vec<Arc<Value>>]) -> Vec<Vec<Arc<Value>>> {
 		values.iter().fold(vec![vec![]], |acc, v| {
 			acc.iter()
@@ -308,6 +484,36 @@ pub(super) enum Plan {
 	/// 3. A record strategy
 	/// 4. True if it matches an order option
 	SingleIndexRange(IndexReference, UnionRangeQueryBuilder, RecordStrategy, bool),
+	/// Index scan for records matching the leading columns of a compound index,
+	/// and a range on the next column
+	/// 1. The reference to index
+	/// 2. The values of the leading columns
+	/// 3. The range of the next column
+	/// 4. A record strategy
+	/// 5. The order option matched by the scan (false = ascending, true = descending)
+	SingleIndexPrefixRange(
+		IndexReference,
+		Array,
+		UnionRangeQueryBuilder,
+		RecordStrategy,
+		Option<bool>,
+	),
+	/// Scan of the records referencing a record id, through a field with a `REFERENCE` clause
+	/// 1. The referencing field
+	/// 2. The referenced record id
+	/// 3. A record strategy
+	Reference(Arc<Idiom>, Thing, RecordStrategy),
+}
+
+impl Plan {
+	/// The index used by a single index plan
+	fn ix_ref(&self) -> Option<&IndexReference> {
+		match self {
+			Self::SingleIndex(_, io, _) => Some(io.ix_ref()),
+			Self::SingleIndexRange(ixr, ..) | Self::SingleIndexPrefixRange(ixr, ..) => Some(ixr),
+			Self::TableIterator(..) | Self::MultiIndex(..) | Self::Reference(..) => None,
+		}
+	}
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -328,9 +534,11 @@ pub(super) enum IndexOperator {
 	Union(Arc<Value>),
 	Join(Vec<IndexOption>),
 	RangePart(Operator, Arc<Value>),
-	Matches(String, Option<MatchRef>),
+	Matches(String, Option<MatchRef>, Option<Fuzziness>),
 	Knn(Arc<Vec<Number>>, u32),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	/// The records containing every trigram of the string
+	Trigram(String),
 	/// false = ascending, true = descending
 	Order(bool),
 }
@@ -404,8 +612,8 @@ impl IndexOption {
 				let joins = Value::from(joins);
 				e.insert("joins", joins);
 			}
-			IndexOperator::Matches(qs, a) => {
-				e.insert("operator", Value::from(Operator::Matches(*a).to_string()));
+			IndexOperator::Matches(qs, a, f) => {
+				e.insert("operator", Value::from(Operator::Matches(*a, *f).to_string()));
 				e.insert("value", Value::from(qs.to_owned()));
 			}
 			IndexOperator::RangePart(op, v) => {
@@ -424,6 +632,10 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::Trigram(s) => {
+				e.insert("operator", Value::from(Operator::Contain.to_string()));
+				e.insert("value", Value::from(s.to_owned()));
+			}
 			IndexOperator::Order(reverse) => {
 				e.insert(
 					"operator",
@@ -573,17 +785,24 @@ impl UnionRangeQueryBuilder {
 
 	fn add(&mut self, exp: Arc<Expression>, io: IndexOption) -> bool {
 		if let IndexOperator::RangePart(op, val) = io.op() {
-			match op {
-				Operator::LessThan => self.to.set_to(val),
-				Operator::LessThanOrEqual => self.to.set_to_inclusive(val),
-				Operator::MoreThan => self.from.set_from(val),
-				Operator::MoreThanOrEqual => self.from.set_from_inclusive(val),
-				_ => return false,
+			if !self.add_part(op, val) {
+				return false;
 			}
 			self.exps.insert(exp);
 		}
 		true
 	}
+
+	fn add_part(&mut self, op: &Operator, val: &Value) -> bool {
+		match op {
+			Operator::LessThan => self.to.set_to(val),
+			Operator::LessThanOrEqual => self.to.set_to_inclusive(val),
+			Operator::MoreThan => self.from.set_from(val),
+			Operator::MoreThanOrEqual => self.from.set_from_inclusive(val),
+			_ => return false,
+		}
+		true
+	}
 }
 
 #[cfg(test)]
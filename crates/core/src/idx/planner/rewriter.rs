@@ -1,7 +1,8 @@
 use crate::expr::id::range::IdRange;
 use crate::expr::part::DestructurePart;
 use crate::expr::{
-	Array, Cast, Cond, Expression, Function, Id, Idiom, Model, Object, Part, Range, Thing, Value,
+	Array, Cast, Cond, Expression, Function, Id, Idiom, Model, Object, Operator, Part, Range,
+	Thing, Value,
 };
 use crate::idx::planner::executor::KnnExpressions;
 
@@ -12,10 +13,14 @@ pub(super) struct KnnConditionRewriter<'a>(&'a KnnExpressions);
 
 impl<'a> KnnConditionRewriter<'a> {
 	// This function rebuild the same condition, but replaces any KnnExpression by a `true` value
+	// When nothing remains to be checked, there is no condition to evaluate during the KNN search
 	#[expect(clippy::mutable_key_type)]
 	pub(super) fn build(expressions: &'a KnnExpressions, cond: &Cond) -> Option<Cond> {
 		let b = Self(expressions);
-		b.eval_value(&cond.0).map(Cond)
+		match b.eval_value(&cond.0)? {
+			Value::Bool(true) => None,
+			v => Some(Cond(v)),
+		}
 	}
 
 	fn eval_value(&self, v: &Value) -> Option<Value> {
@@ -260,6 +265,25 @@ impl<'a> KnnConditionRewriter<'a> {
 		if self.0.contains(e) {
 			return Some(Value::Bool(true));
 		}
+		// The operands replaced by a `true` value are removed from a conjunction
+		if let Expression::Binary {
+			l,
+			o: Operator::And,
+			r,
+		} = e
+		{
+			return match (self.eval_value(l)?, self.eval_value(r)?) {
+				(Value::Bool(true), v) | (v, Value::Bool(true)) => Some(v),
+				(l, r) => Some(
+					Expression::Binary {
+						l,
+						o: Operator::And,
+						r,
+					}
+					.into(),
+				),
+			};
+		}
 		self.eval_expression(e).map(|e| e.into())
 	}
 
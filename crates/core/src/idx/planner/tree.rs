@@ -1,20 +1,25 @@
+use crate::ctx::Context;
 use crate::dbs::Options;
+use crate::dbs::capabilities::ExperimentalTarget;
+use crate::err::Error;
 use crate::expr::FlowResultExt as _;
 use crate::expr::index::Index;
 use crate::expr::statements::{DefineFieldStatement, DefineIndexStatement};
 use crate::expr::{
-	Array, Cond, Expression, Idiom, Kind, Number, Operator, Order, Part, Subquery, Table, Value,
-	With,
+	Array, Cond, Expression, Function, Idiom, Kind, Number, Operator, Order, Part, Subquery, Table,
+	Thing, Value, With,
 	order::{OrderList, Ordering},
 };
-use crate::idx::planner::StatementContext;
+use crate::idx::ft::query::Query;
 use crate::idx::planner::executor::{
 	KnnBruteForceExpression, KnnBruteForceExpressions, KnnExpressions,
 };
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
+use crate::idx::planner::{CandidateRejection, IndexCandidate, StatementContext};
+use crate::idx::trigram::TRIGRAM_LEN;
 use crate::kvs::Transaction;
-use anyhow::Result;
+use anyhow::{Result, bail};
 use reblessive::tree::Stk;
 use std::collections::HashMap;
 use std::hash::Hash;
@@ -34,6 +39,10 @@ pub(super) struct Tree {
 	pub(super) all_and: bool,
 	/// Does a group contain only AND relations?
 	pub(super) all_and_groups: HashMap<GroupRef, bool>,
+	/// The indexes considered for each expression, when explained verbosely
+	pub(super) candidates: Option<Vec<IndexCandidate>>,
+	/// The equalities which can be resolved by the references of a field
+	pub(super) references: Vec<(Arc<Idiom>, Thing)>,
 }
 
 impl Tree {
@@ -45,6 +54,7 @@ impl Tree {
 		table: &'a Table,
 	) -> Result<Self> {
 		let mut b = TreeBuilder::new(stm_ctx, table);
+		b.eval_with().await?;
 		if let Some(cond) = stm_ctx.cond {
 			b.eval_cond(stk, cond).await?;
 		}
@@ -60,6 +70,8 @@ impl Tree {
 				&& b.leaf_nodes_with_index_count == b.leaf_nodes_count,
 			all_and: b.all_and.unwrap_or(true),
 			all_and_groups: b.all_and_groups,
+			candidates: b.candidates,
+			references: b.references,
 		})
 	}
 }
@@ -84,6 +96,8 @@ struct TreeBuilder<'a> {
 	leaf_nodes_with_index_count: usize,
 	all_and: Option<bool>,
 	all_and_groups: HashMap<GroupRef, bool>,
+	candidates: Option<Vec<IndexCandidate>>,
+	references: Vec<(Arc<Idiom>, Thing)>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -98,15 +112,12 @@ pub(super) type RemoteIndexRefs = Arc<Vec<(Arc<Idiom>, LocalIndexRefs)>>;
 
 impl<'a> TreeBuilder<'a> {
 	fn new(ctx: &'a StatementContext<'a>, table: &'a Table) -> Self {
-		let with_indexes = match ctx.with {
-			Some(With::Index(ixs)) => Some(Vec::with_capacity(ixs.len())),
-			_ => None,
-		};
 		let first_order = if let Some(Ordering::Order(OrderList(o))) = ctx.order {
 			o.first()
 		} else {
 			None
 		};
+		let candidates = ctx.stm.explain().is_some_and(|e| e.1).then(Vec::new);
 		Self {
 			ctx,
 			table,
@@ -116,7 +127,7 @@ impl<'a> TreeBuilder<'a> {
 			resolved_expressions: Default::default(),
 			resolved_idioms: Default::default(),
 			index_map: Default::default(),
-			with_indexes,
+			with_indexes: None,
 			knn_brute_force_expressions: Default::default(),
 			knn_expressions: Default::default(),
 			idioms_record_options: Default::default(),
@@ -127,6 +138,8 @@ impl<'a> TreeBuilder<'a> {
 			all_and_groups: Default::default(),
 			leaf_nodes_count: 0,
 			leaf_nodes_with_index_count: 0,
+			candidates,
+			references: Default::default(),
 		}
 	}
 
@@ -134,30 +147,111 @@ impl<'a> TreeBuilder<'a> {
 		if self.schemas.contains_key(table) {
 			return Ok(());
 		}
-		let l = SchemaCache::new(self.ctx.opt, table, tx).await?;
+		let l = SchemaCache::new(self.ctx.ctx, self.ctx.opt, table, tx).await?;
 		self.schemas.insert(table.clone(), l);
 		Ok(())
 	}
 
+	/// Collects the indexes which the WITH clause lists for the table, in their
+	/// order of preference. An index which is still building is left out, but
+	/// an index which is not defined on the table is an error.
+	async fn eval_with(&mut self) -> Result<()> {
+		let Some(names) = self.ctx.with.and_then(|w| w.indexes_for(&self.table.0)) else {
+			return Ok(());
+		};
+		let tx = self.ctx.ctx.tx();
+		self.lazy_load_schema_resolver(&tx, self.table).await?;
+		let Some(schema) = self.schemas.get(self.table).cloned() else {
+			return Ok(());
+		};
+		let mut wi: Vec<IndexReference> = Vec::with_capacity(names.len());
+		for name in names {
+			if let Some(idx) = schema.indexes.iter().position(|ix| ix.name.0 == name) {
+				let ixr = schema.new_reference(idx);
+				if !wi.contains(&ixr) {
+					wi.push(ixr);
+				}
+				continue;
+			}
+			let all = tx.all_tb_indexes(self.ctx.ns, self.ctx.db, self.table).await?;
+			if !all.iter().any(|ix| ix.name.0 == name) {
+				let available = if all.is_empty() {
+					"none".to_owned()
+				} else {
+					all.iter().map(|ix| ix.name.to_raw()).collect::<Vec<_>>().join(", ")
+				};
+				bail!(Error::WithIxNotFound {
+					name: name.to_owned(),
+					table: self.table.0.clone(),
+					available,
+				});
+			}
+		}
+		self.with_indexes = Some(wi);
+		Ok(())
+	}
+
 	async fn eval_order(&mut self) -> Result<()> {
 		if let Some(o) = self.first_order {
 			if let Node::IndexedField(id, irf) = self.resolve_idiom(&o.value).await? {
+				// Index keys are sorted by their raw value, with NONE and NULL values
+				// first, so they can not be used for collated or numeric orderings, or
+				// when NONE and NULL values are placed at the other end
+				let sorted = o.collate.is_none() && !o.numeric && o.nulls_first() == o.direction;
 				for (ixr, id_col) in &irf {
-					if *id_col == 0 {
-						self.index_map.order_limit = Some(IndexOption::new(
-							ixr.clone(),
-							Some(id),
-							IdiomPosition::None,
-							IndexOperator::Order(!o.direction),
-						));
-						break;
-					}
+					let rejection = match (*id_col, sorted) {
+						(0, true) if self.index_map.order_limit.is_none() => {
+							self.index_map.order_limit = Some(IndexOption::new(
+								ixr.clone(),
+								Some(id.clone()),
+								IdiomPosition::None,
+								IndexOperator::Order(!o.direction),
+							));
+							None
+						}
+						(0, true) => Some(CandidateRejection::Superseded),
+						(0, false) => Some(CandidateRejection::UnsupportedOrdering),
+						(col, true) => {
+							// The order can still be matched by a prefix scan
+							self.index_map.compound_orders.insert(ixr.clone(), (col, !o.direction));
+							Some(CandidateRejection::NotLeadingColumn)
+						}
+						_ => Some(CandidateRejection::NotLeadingColumn),
+					};
+					self.add_candidate(ixr, || format!("ORDER BY {o}"), None, rejection);
 				}
 			}
 		}
 		Ok(())
 	}
 
+	/// Keep track of an index which was considered for an expression
+	fn add_candidate<F: FnOnce() -> String>(
+		&mut self,
+		ixr: &IndexReference,
+		expression: F,
+		option: Option<&IndexOption>,
+		rejection: Option<CandidateRejection>,
+	) {
+		let Some(candidates) = &mut self.candidates else {
+			return;
+		};
+		let rejection = rejection.or_else(|| match &self.ctx.with {
+			Some(With::NoIndex) => Some(CandidateRejection::ExcludedByWith),
+			_ if self.with_indexes.as_ref().is_some_and(|wi| !wi.contains(ixr)) => {
+				Some(CandidateRejection::ExcludedByWith)
+			}
+			_ => None,
+		});
+		candidates.push(IndexCandidate {
+			index: ixr.name.to_raw(),
+			expression: expression(),
+			option: option.cloned(),
+			estimated_rows: None,
+			rejection,
+		});
+	}
+
 	async fn eval_cond(&mut self, stk: &mut Stk, cond: &Cond) -> Result<()> {
 		self.root = Some(self.eval_value(stk, 0, &cond.0).await?);
 		self.knn_condition = if self.knn_expressions.is_empty() {
@@ -183,11 +277,11 @@ impl<'a> TreeBuilder<'a> {
 			| Value::Datetime(_)
 			| Value::Param(_)
 			| Value::Null
-			| Value::None
-			| Value::Function(_) => {
+			| Value::None => {
 				self.leaf_nodes_count += 1;
 				Ok(Node::Computable)
 			}
+			Value::Function(f) => self.eval_function(stk, group, f).await,
 			Value::Array(a) => self.eval_array(stk, a).await,
 			Value::Subquery(s) => self.eval_subquery(stk, s).await,
 			_ => Ok(Node::Unsupported(format!("Unsupported value: {}", v))),
@@ -205,6 +299,51 @@ impl<'a> TreeBuilder<'a> {
 		})
 	}
 
+	/// A `string::contains(field, value)` call can be resolved by a trigram index.
+	/// It is planned as a `field CONTAINS value` expression, but other indexes
+	/// are not considered, as they do not support substrings.
+	async fn eval_function(
+		&mut self,
+		stk: &mut Stk,
+		group: GroupRef,
+		f: &Function,
+	) -> Result<Node> {
+		if let Function::Normal(name, args) = f {
+			if let ("string::contains", [l @ Value::Idiom(_), r]) = (name.as_str(), args.as_slice())
+			{
+				let exp = Arc::new(Expression::Binary {
+					l: l.clone(),
+					o: Operator::Contain,
+					r: r.clone(),
+				});
+				self.check_boolean_operator(group, &Operator::Contain);
+				let left = stk.run(|stk| self.eval_value(stk, group, l)).await?;
+				let right = stk.run(|stk| self.eval_value(stk, group, r)).await?;
+				if left == Node::Computable && right == Node::Computable {
+					return Ok(Node::Computable);
+				}
+				let right = Arc::new(self.compute(stk, r, right).await?);
+				let io = if let Node::IndexedField(id, irs) = &left {
+					self.lookup_trigram_option(irs, id, &right, &exp, f)
+				} else {
+					None
+				};
+				self.check_leaf_node_with_index(io.as_ref());
+				// The expression is not shared with the actual CONTAINS expressions,
+				// as these are resolved differently
+				return Ok(Node::Expression {
+					group,
+					io,
+					left: Arc::new(left),
+					right,
+					exp,
+				});
+			}
+		}
+		self.leaf_nodes_count += 1;
+		Ok(Node::Computable)
+	}
+
 	async fn eval_array(&mut self, stk: &mut Stk, a: &Array) -> Result<Node> {
 		self.leaf_nodes_count += 1;
 		let mut values = Vec::with_capacity(a.len());
@@ -273,19 +412,14 @@ impl<'a> TreeBuilder<'a> {
 		}
 		let mut irs = Vec::new();
 		for (idx, ix) in schema.indexes.iter().enumerate() {
-			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
-				let ixr = schema.new_reference(idx);
-				// Check if the WITH clause allow the index to be used
-				if let Some(With::Index(ixs)) = &self.ctx.with {
-					if ixs.contains(&ix.name.0) {
-						if let Some(wi) = &mut self.with_indexes {
-							wi.push(ixr.clone());
-						} else {
-							self.with_indexes = Some(vec![ixr.clone()]);
-						}
-					}
+			// A partial index only contains the records matching its own condition
+			if let Some(c) = &ix.cond {
+				if t != self.table || !Self::implies(self.ctx.cond, c) {
+					continue;
 				}
-				irs.push((ixr, idiom_index));
+			}
+			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
+				irs.push((schema.new_reference(idx), idiom_index));
 			}
 		}
 		let i = Arc::new(i.clone());
@@ -297,6 +431,45 @@ impl<'a> TreeBuilder<'a> {
 		irs
 	}
 
+	/// Checks if every record matching the query condition also matches the index condition.
+	/// This is a syntactic check: each AND operand of the index condition must be an AND operand
+	/// of the query condition.
+	fn implies(cond: Option<&Cond>, predicate: &Cond) -> bool {
+		let Some(cond) = cond else {
+			return false;
+		};
+		let mut operands = Vec::new();
+		Self::and_operands(&cond.0, &mut operands);
+		let mut required = Vec::new();
+		Self::and_operands(&predicate.0, &mut required);
+		required.iter().all(|r| operands.contains(r))
+	}
+
+	fn and_operands<'b>(v: &'b Value, operands: &mut Vec<&'b Value>) {
+		match v {
+			Value::Expression(e) => {
+				if let Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} = e.as_ref()
+				{
+					Self::and_operands(l, operands);
+					Self::and_operands(r, operands);
+					return;
+				}
+			}
+			Value::Subquery(s) => {
+				if let Subquery::Value(v) = s.as_ref() {
+					Self::and_operands(v, operands);
+					return;
+				}
+			}
+			_ => {}
+		}
+		operands.push(v);
+	}
+
 	async fn resolve_record_field(
 		&mut self,
 		tx: &Transaction,
@@ -397,6 +570,9 @@ impl<'a> TreeBuilder<'a> {
 				} else {
 					None
 				};
+				if io.is_none() {
+					self.eval_reference(o, &left, &right);
+				}
 				if let Some(id) = left.is_field() {
 					self.eval_bruteforce_knn(id, &right, &exp)?;
 				} else if let Some(id) = right.is_field() {
@@ -416,6 +592,34 @@ impl<'a> TreeBuilder<'a> {
 		}
 	}
 
+	/// Collects an equality between a field with a `REFERENCE` clause and a record id,
+	/// as the records matching it can be found by the references of the field
+	fn eval_reference(&mut self, op: &Operator, left: &Node, right: &Node) {
+		if !matches!(op, Operator::Equal | Operator::Exact) {
+			return;
+		}
+		let (id, v) = if let (Some(id), Some(v)) = (left.is_field(), right.is_computed()) {
+			(id, v)
+		} else if let (Some(id), Some(v)) = (right.is_field(), left.is_computed()) {
+			(id, v)
+		} else {
+			return;
+		};
+		let Value::Thing(thing) = v.as_ref() else {
+			return;
+		};
+		// The references are only maintained while the capability is enabled
+		let caps = self.ctx.ctx.get_capabilities();
+		if !caps.allows_experimental(&ExperimentalTarget::RecordReferences) {
+			return;
+		}
+		if let Some(schema) = self.schemas.get(self.table) {
+			if schema.fields.iter().any(|fd| fd.name == *id && fd.reference.is_some()) {
+				self.references.push((Arc::new(id.clone()), thing.clone()));
+			}
+		}
+	}
+
 	fn check_boolean_operator(&mut self, gr: GroupRef, op: &Operator) {
 		match op {
 			Operator::Neg | Operator::Or => {
@@ -438,6 +642,18 @@ impl<'a> TreeBuilder<'a> {
 
 	fn check_leaf_node_with_index(&mut self, io: Option<&IndexOption>) {
 		if let Some(io) = io {
+			// The records of a trigram index still have to match the exact condition
+			if matches!(io.op(), IndexOperator::Trigram(_)) {
+				return;
+			}
+			// The records of a full-text index still have to contain the phrases and
+			// the fields of the query. A malformed query string fails once it is executed.
+			if let IndexOperator::Matches(qs, _, _) = io.op() {
+				let fields = Query::fields(io.ix_ref());
+				if Query::parse(qs, &fields).ok().is_none_or(|q| q.needs_record_check()) {
+					return;
+				}
+			}
 			if let Some(wi) = &self.with_indexes {
 				if !wi.contains(io.ix_ref()) {
 					return;
@@ -489,7 +705,7 @@ impl<'a> TreeBuilder<'a> {
 	) -> Result<Option<IndexOption>> {
 		let mut res = None;
 		for (ixr, col) in irs.iter() {
-			let op = match &ixr.index {
+			let iop = match &ixr.index {
 				Index::Idx => self.eval_index_operator(ixr, op, n, p, *col),
 				Index::Uniq => self.eval_index_operator(ixr, op, n, p, *col),
 				Index::Search {
@@ -499,17 +715,67 @@ impl<'a> TreeBuilder<'a> {
 				Index::Hnsw(_) if *col == 0 => self.eval_hnsw_knn(e, op, n)?,
 				_ => None,
 			};
-			if res.is_none() {
-				if let Some(op) = op {
-					let io = IndexOption::new(ixr.clone(), Some(id.clone()), p, op);
+			let rejection = match iop {
+				Some(_) if res.is_some() => Some(CandidateRejection::Superseded),
+				Some(iop) => {
+					let io = IndexOption::new(ixr.clone(), Some(id.clone()), p, iop);
 					self.index_map.options.push((e.clone(), io.clone()));
 					res = Some(io);
+					None
 				}
-			}
+				None => Some(Self::eval_rejection(ixr, op, n, p, *col)),
+			};
+			let io = res.as_ref().filter(|_| rejection.is_none());
+			self.add_candidate(ixr, || e.to_string(), io, rejection);
 		}
 		Ok(res)
 	}
 
+	/// Explains why an index can not be used for an operator
+	fn eval_rejection(
+		ixr: &IndexReference,
+		op: &Operator,
+		n: &Node,
+		p: IdiomPosition,
+		col: IdiomCol,
+	) -> CandidateRejection {
+		let Some(v) = n.is_computed() else {
+			return CandidateRejection::NotComputable;
+		};
+		let supported = match &ixr.index {
+			Index::Idx | Index::Uniq => match (op, p) {
+				(Operator::Inside, IdiomPosition::Left)
+				| (Operator::ContainAny | Operator::ContainAll, IdiomPosition::Left)
+					if !v.is_array() =>
+				{
+					return CandidateRejection::TypeMismatch;
+				}
+				(Operator::Equal | Operator::Exact, _)
+				| (Operator::Contain, IdiomPosition::Left)
+				| (Operator::Inside, _)
+				| (Operator::ContainAny | Operator::ContainAll, IdiomPosition::Left)
+				| (
+					Operator::LessThan
+					| Operator::LessThanOrEqual
+					| Operator::MoreThan
+					| Operator::MoreThanOrEqual,
+					_,
+				) => true,
+				_ => false,
+			},
+			Index::Search(_) => matches!(op, Operator::Matches(..)),
+			Index::MTree(_) => matches!(op, Operator::Knn(_, None)),
+			Index::Hnsw(_) => matches!(op, Operator::Ann(_, _)),
+			// Only `string::contains` calls are resolved by trigram indexes
+			Index::Trigram => false,
+		};
+		match (supported, col) {
+			(false, _) => CandidateRejection::NonSargableOperator,
+			(true, 0) => CandidateRejection::TypeMismatch,
+			(true, _) => CandidateRejection::NotLeadingColumn,
+		}
+	}
+
 	fn lookup_join_index_ref(&self, irs: &LocalIndexRefs) -> Option<(IndexReference, IdiomCol)> {
 		for (ixr, id_col) in irs.iter().filter(|(_, id_col)| 0.eq(id_col)) {
 			match &ixr.index {
@@ -522,8 +788,53 @@ impl<'a> TreeBuilder<'a> {
 
 	fn eval_matches_operator(op: &Operator, n: &Node) -> Option<IndexOperator> {
 		if let Some(v) = n.is_computed() {
-			if let Operator::Matches(mr) = op {
-				return Some(IndexOperator::Matches(v.to_raw_string(), *mr));
+			if let Operator::Matches(mr, fz) = op {
+				return Some(IndexOperator::Matches(v.to_raw_string(), *mr, *fz));
+			}
+		}
+		None
+	}
+
+	fn lookup_trigram_option(
+		&mut self,
+		irs: &LocalIndexRefs,
+		id: &Arc<Idiom>,
+		n: &Node,
+		e: &Arc<Expression>,
+		f: &Function,
+	) -> Option<IndexOption> {
+		let mut res = None;
+		for (ixr, col) in irs.iter().filter(|(ixr, _)| ixr.index == Index::Trigram) {
+			let rejection = if *col != 0 {
+				Some(CandidateRejection::NotLeadingColumn)
+			} else if n.is_computed().is_none() {
+				Some(CandidateRejection::NotComputable)
+			} else if let Some(iop) = Self::eval_trigram_operator(n) {
+				if res.is_some() {
+					Some(CandidateRejection::Superseded)
+				} else {
+					let io =
+						IndexOption::new(ixr.clone(), Some(id.clone()), IdiomPosition::Left, iop);
+					self.index_map.options.push((e.clone(), io.clone()));
+					res = Some(io);
+					None
+				}
+			} else {
+				Some(CandidateRejection::TypeMismatch)
+			};
+			let io = res.as_ref().filter(|_| rejection.is_none());
+			self.add_candidate(ixr, || f.to_string(), io, rejection);
+		}
+		res
+	}
+
+	/// Substrings shorter than a trigram can not be looked up
+	fn eval_trigram_operator(n: &Node) -> Option<IndexOperator> {
+		if let Some(v) = n.is_computed() {
+			if let Value::Strand(s) = v.as_ref() {
+				if s.chars().count() >= TRIGRAM_LEN {
+					return Some(IndexOperator::Trigram(s.as_str().to_owned()));
+				}
 			}
 		}
 		None
@@ -625,6 +936,7 @@ impl<'a> TreeBuilder<'a> {
 					if col == 0 {
 						return Some(IndexOperator::RangePart(p.transform(op), v));
 					}
+					self.index_map.check_compound_range(ixr, col, p.transform(op), v);
 				}
 				_ => {}
 			}
@@ -643,12 +955,21 @@ impl<'a> TreeBuilder<'a> {
 
 pub(super) type CompoundIndexes = HashMap<IndexReference, Vec<Vec<Arc<Value>>>>;
 
+/// For each compound index, the range parts bounding its non-leading columns
+pub(super) type CompoundRanges = HashMap<IndexReference, Vec<(IdiomCol, Operator, Arc<Value>)>>;
+
+/// For each compound index, the non-leading column matching the first ORDER BY
+/// field, and the direction of the ordering (false = ascending, true = descending)
+pub(super) type CompoundOrders = HashMap<IndexReference, (IdiomCol, bool)>;
+
 /// For each expression a possible index option
 #[derive(Default)]
 pub(super) struct IndexesMap {
 	pub(super) options: Vec<(Arc<Expression>, IndexOption)>,
 	/// For each index, tells if the columns are requested
 	pub(super) compound_indexes: CompoundIndexes,
+	pub(super) compound_ranges: CompoundRanges,
+	pub(super) compound_orders: CompoundOrders,
 	pub(super) order_limit: Option<IndexOption>,
 }
 
@@ -666,6 +987,16 @@ impl IndexesMap {
 			self.check_compound(ixr, col, &Arc::new(v.clone()))
 		}
 	}
+
+	pub(crate) fn check_compound_range(
+		&mut self,
+		ixr: &IndexReference,
+		col: usize,
+		op: Operator,
+		val: Arc<Value>,
+	) {
+		self.compound_ranges.entry(ixr.clone()).or_default().push((col, op, val));
+	}
 }
 
 #[derive(Debug, Clone)]
@@ -712,9 +1043,27 @@ struct SchemaCache {
 }
 
 impl SchemaCache {
-	async fn new(opt: &Options, table: &Table, tx: &Transaction) -> Result<Self> {
+	#[cfg_attr(target_family = "wasm", expect(unused_variables))]
+	async fn new(ctx: &Context, opt: &Options, table: &Table, tx: &Transaction) -> Result<Self> {
 		let (ns, db) = opt.ns_db()?;
-		let indexes = tx.all_tb_indexes(ns, db, table).await?;
+		let mut indexes = tx.all_tb_indexes(ns, db, table).await?;
+		// Indexes which are still building, or whose build was cancelled, are incomplete
+		let mut ready = Vec::with_capacity(indexes.len());
+		for ix in indexes.iter() {
+			if ix.cancelled {
+				continue;
+			}
+			#[cfg(not(target_family = "wasm"))]
+			if let Some(ib) = ctx.get_index_builder() {
+				if !ib.get_status(ns, db, ix).await.is_ready() {
+					continue;
+				}
+			}
+			ready.push(ix.clone());
+		}
+		if ready.len() < indexes.len() {
+			indexes = ready.into();
+		}
 		let fields = tx.all_tb_fields(ns, db, table, None).await?;
 		Ok(Self {
 			indexes,
@@ -0,0 +1,60 @@
+use crate::expr::{Object, Value};
+
+/// The health of a vector index, computed from its persisted structure.
+///
+/// The identifiers of the nodes are never reused, so the difference
+/// between the allocated identifiers and the stored nodes gives the
+/// number of nodes which have been removed since the index was built.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct IndexHealth {
+	/// The number of nodes currently stored
+	pub(crate) nodes: u64,
+	/// The number of nodes which have been removed
+	pub(crate) tombstones: u64,
+}
+
+impl IndexHealth {
+	pub(in crate::idx) fn new(nodes: usize, allocated: u64) -> Self {
+		let nodes = nodes as u64;
+		Self {
+			nodes,
+			tombstones: allocated.saturating_sub(nodes),
+		}
+	}
+
+	/// The ratio of removed nodes over all the nodes ever allocated
+	pub(crate) fn tombstone_ratio(&self) -> f64 {
+		let allocated = self.nodes + self.tombstones;
+		if allocated == 0 {
+			0.0
+		} else {
+			self.tombstones as f64 / allocated as f64
+		}
+	}
+}
+
+impl From<IndexHealth> for Value {
+	fn from(h: IndexHealth) -> Self {
+		let mut res = Object::default();
+		res.insert("nodes".to_owned(), Value::from(h.nodes));
+		res.insert("tombstones".to_owned(), Value::from(h.tombstones));
+		res.insert("tombstone_ratio".to_owned(), Value::from(h.tombstone_ratio()));
+		Value::from(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::IndexHealth;
+
+	#[test]
+	fn tombstone_ratio() {
+		assert_eq!(IndexHealth::default().tombstone_ratio(), 0.0);
+		let h = IndexHealth::new(3, 4);
+		assert_eq!(h.nodes, 3);
+		assert_eq!(h.tombstones, 1);
+		assert_eq!(h.tombstone_ratio(), 0.25);
+		// The allocation counter can never be lower than the stored nodes
+		assert_eq!(IndexHealth::new(2, 1).tombstones, 0);
+	}
+}
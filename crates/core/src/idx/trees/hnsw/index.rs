@@ -1,14 +1,15 @@
 use crate::expr::index::{HnswParams, VectorType};
 use crate::expr::{Id, Number, Value};
-use crate::idx::IndexKeyBase;
 use crate::idx::planner::checker::HnswConditionChecker;
 use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::trees::health::IndexHealth;
 use crate::idx::trees::hnsw::docs::{HnswDocs, VecDocs};
 use crate::idx::trees::hnsw::elements::HnswElements;
 use crate::idx::trees::hnsw::flavor::HnswFlavor;
-use crate::idx::trees::hnsw::{ElementId, HnswSearch};
+use crate::idx::trees::hnsw::{ElementId, HnswSearch, HnswState};
 use crate::idx::trees::knn::{KnnResult, KnnResultBuilder};
 use crate::idx::trees::vector::{SharedVector, Vector};
+use crate::idx::{IndexKeyBase, VersionedStore};
 use crate::kvs::Transaction;
 #[cfg(debug_assertions)]
 use ahash::HashMap;
@@ -129,6 +130,18 @@ impl HnswIndex {
 		Ok(())
 	}
 
+	/// Compares the stored elements of the graph with the element ids which were allocated
+	pub(crate) async fn health(tx: &Transaction, ikb: &IndexKeyBase) -> Result<IndexHealth> {
+		let Some(val) = tx.get(ikb.new_hs_key()?, None).await? else {
+			return Ok(IndexHealth::default());
+		};
+		let state: HnswState = VersionedStore::try_from(val)?;
+		let beg = ikb.new_he_key(0)?;
+		let end = ikb.new_he_key(ElementId::MAX)?;
+		let nodes = tx.count(beg..end).await?;
+		Ok(IndexHealth::new(nodes, state.next_element_id))
+	}
+
 	// Ensure the layers are up-to-date
 	pub async fn check_state(&mut self, tx: &Transaction) -> Result<()> {
 		self.hnsw.check_state(tx).await
@@ -1,3 +1,4 @@
+use crate::cnf::KNN_FILTER_MAX_VISITED;
 use crate::err::Error;
 use crate::idx::IndexKeyBase;
 use crate::idx::planner::checker::HnswConditionChecker;
@@ -207,7 +208,13 @@ where
 		let elements = search.elements();
 
 		while let Some((dist, doc)) = candidates.pop_first() {
-			if dist > f_dist {
+			// The candidates which don't match the condition are skipped,
+			// so the search continues until `ef` matching elements are found
+			if dist > f_dist && w.len() >= ef {
+				break;
+			}
+			// Bound the number of elements visited for selective conditions
+			if visited.len() >= *KNN_FILTER_MAX_VISITED {
 				break;
 			}
 			if let Some(neighbourhood) = self.graph.get_edges(&doc) {
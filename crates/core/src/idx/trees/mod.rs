@@ -2,6 +2,7 @@ pub mod bkeys;
 pub mod btree;
 pub mod dynamicset;
 mod graph;
+pub(crate) mod health;
 pub mod hnsw;
 pub(in crate::idx) mod knn;
 pub mod mtree;
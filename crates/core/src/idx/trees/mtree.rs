@@ -1,3 +1,4 @@
+use crate::cnf::KNN_FILTER_MAX_VISITED;
 use crate::ctx::Context;
 use ahash::{HashMap, HashMapExt, HashSet};
 use anyhow::Result;
@@ -20,6 +21,7 @@ use crate::idx::docids::{DocId, DocIds};
 use crate::idx::planner::checker::MTreeConditionChecker;
 use crate::idx::planner::iterators::KnnIteratorResult;
 use crate::idx::trees::btree::BStatistics;
+use crate::idx::trees::health::IndexHealth;
 use crate::idx::trees::knn::{Ids64, KnnResult, KnnResultBuilder, PriorityNode};
 use crate::idx::trees::store::{NodeId, StoredNode, TreeNode, TreeNodeProvider, TreeStore};
 use crate::idx::trees::vector::{SharedVector, Vector};
@@ -159,6 +161,18 @@ impl MTreeIndex {
 		res
 	}
 
+	/// Compares the stored nodes of the tree with the node ids which were allocated
+	pub(crate) async fn health(tx: &Transaction, ikb: &IndexKeyBase) -> Result<IndexHealth> {
+		let Some(val) = tx.get(ikb.new_vm_key(None)?, None).await? else {
+			return Ok(IndexHealth::default());
+		};
+		let state: MState = VersionedStore::try_from(val)?;
+		let beg = ikb.new_vm_key(Some(0))?;
+		let end = ikb.new_vm_key(Some(NodeId::MAX))?;
+		let nodes = tx.count(beg..end).await?;
+		Ok(IndexHealth::new(nodes, state.next_node_id))
+	}
+
 	pub(crate) async fn statistics(&self, tx: &Transaction) -> Result<MtStatistics> {
 		Ok(MtStatistics {
 			doc_ids: self.doc_ids.read().await.statistics(tx).await?,
@@ -215,7 +229,17 @@ impl MTree {
 		}
 		#[cfg(debug_assertions)]
 		let mut visited_nodes = HashMap::default();
+		// The nodes visited when looking for records matching a condition are bounded
+		let max_visited = match chk {
+			MTreeConditionChecker::MTree(_) => usize::MAX,
+			MTreeConditionChecker::MTreeCondition(_) => *KNN_FILTER_MAX_VISITED,
+		};
+		let mut visited = 0;
 		while let Some(e) = queue.pop() {
+			if visited >= max_visited {
+				break;
+			}
+			visited += 1;
 			let id = e.id();
 			let node = search.store.get_node_txn(search.ctx, id).await?;
 			#[cfg(debug_assertions)]
@@ -259,6 +259,33 @@ impl IndexStores {
 		self.remove_index(ns, db, tx.get_tb_index(ns, db, tb, ix).await?.as_ref()).await
 	}
 
+	/// Releases the in-memory structures of a generation of an index
+	#[cfg(not(target_family = "wasm"))]
+	pub(crate) async fn index_released(
+		&self,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<()> {
+		self.remove_index(ns, db, ix).await
+	}
+
+	#[cfg(not(target_family = "wasm"))]
+	pub(crate) async fn index_build_cancelled(
+		&self,
+		ib: Option<&IndexBuilder>,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<bool> {
+		let incomplete = match ib {
+			Some(ib) => ib.cancel(ns, db, ix).await?,
+			None => false,
+		};
+		self.remove_index(ns, db, ix).await?;
+		Ok(incomplete)
+	}
+
 	pub(crate) async fn namespace_removed(
 		&self,
 		#[cfg(not(target_family = "wasm"))] ib: Option<&IndexBuilder>,
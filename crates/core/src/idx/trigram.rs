@@ -0,0 +1,107 @@
+//! Trigram indexes store a key for every distinct trigram of the indexed
+//! strings, so that the records containing a substring of at least three
+//! characters can be found by intersecting the records of its trigrams.
+//!
+//! The keys use the layout of non-unique indexes, with the trigram as the
+//! indexed value, and the trigrams are computed over the lowercase
+//! characters (not the bytes) of the strings. The index only returns
+//! candidates, which are then filtered with the exact condition.
+use crate::expr::statements::DefineIndexStatement;
+use crate::expr::{Array, Thing, Value};
+use crate::key::index::Index;
+use crate::kvs::Transaction;
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+/// The number of characters of a trigram
+pub(crate) const TRIGRAM_LEN: usize = 3;
+
+/// Collects the distinct lowercase trigrams of a string.
+/// Each character is lowercased on its own, so that the trigrams of a
+/// substring are always trigrams of the string containing it.
+pub(crate) fn trigrams(s: &str, set: &mut BTreeSet<String>) {
+	let chars: Vec<char> = s.chars().flat_map(char::to_lowercase).collect();
+	for w in chars.windows(TRIGRAM_LEN) {
+		set.insert(w.iter().collect());
+	}
+}
+
+/// Collects the trigrams of the strings of the indexed values.
+/// Arrays are flattened, and any other value is ignored.
+fn values_trigrams(vals: &[Value], set: &mut BTreeSet<String>) {
+	for v in vals {
+		match v {
+			Value::Strand(s) => trigrams(s, set),
+			Value::Array(a) => values_trigrams(&a.0, set),
+			_ => {}
+		}
+	}
+}
+
+/// The value of the index key for a trigram
+pub(crate) fn trigram_array(t: &str) -> Array {
+	Array(vec![Value::from(t)])
+}
+
+/// Updates the trigrams of a record, given its old and new indexed values.
+/// Only the trigrams which were removed or added are written.
+pub(crate) async fn index_trigrams(
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+	ix: &DefineIndexStatement,
+	rid: &Thing,
+	o: Option<Vec<Value>>,
+	n: Option<Vec<Value>>,
+) -> Result<()> {
+	let mut old = BTreeSet::new();
+	if let Some(o) = o {
+		values_trigrams(&o, &mut old);
+	}
+	let mut new = BTreeSet::new();
+	if let Some(n) = n {
+		values_trigrams(&n, &mut new);
+	}
+	// Delete the trigrams the record no longer contains
+	for t in old.difference(&new) {
+		let fd = trigram_array(t);
+		let key = Index::new(ns, db, &ix.what, &ix.name, &fd, Some(&rid.id));
+		tx.del(key).await?;
+	}
+	// Add the new trigrams of the record
+	let val = revision::to_vec(rid)?;
+	for t in new.difference(&old) {
+		let fd = trigram_array(t);
+		let key = Index::new(ns, db, &ix.what, &ix.name, &fd, Some(&rid.id));
+		tx.set(key, val.clone(), None).await?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn collect(s: &str) -> Vec<String> {
+		let mut set = BTreeSet::new();
+		trigrams(s, &mut set);
+		set.into_iter().collect()
+	}
+
+	#[test]
+	fn test_trigrams() {
+		assert_eq!(collect("Tobie"), vec!["bie", "obi", "tob"]);
+		assert_eq!(collect("aaaa"), vec!["aaa"]);
+		assert!(collect("ab").is_empty());
+		assert!(collect("").is_empty());
+	}
+
+	#[test]
+	fn test_trigrams_multibyte() {
+		// The trigrams are made of characters, not bytes
+		assert_eq!(collect("Gödel"), vec!["del", "göd", "öde"]);
+		assert_eq!(collect("日本語"), vec!["日本語"]);
+		// A capital sigma is always lowercased the same way
+		assert_eq!(collect("ΟΔΟΣ"), vec!["δοσ", "οδο"]);
+	}
+}
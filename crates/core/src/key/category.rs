@@ -49,6 +49,8 @@ pub enum Category {
 	///
 	/// crate::key::namespace::all           /*{ns}
 	NamespaceRoot,
+	/// crate::key::namespace::au            /*{ns}!au{au}
+	NamespaceAudit,
 	/// crate::key::namespace::db            /*{ns}!db{db}
 	DatabaseAlias,
 	/// crate::key::namespace::access::ac    /*{ns}!ac{ac}
@@ -78,8 +80,12 @@ pub enum Category {
 	DatabaseBucket,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
+	/// crate::key::database::mg             /*{ns}*{db}!mg{mg}
+	DatabaseMigration,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
+	/// crate::key::database::ob             /*{ns}*{db}!ob{ob}
+	DatabaseOutbox,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
@@ -94,6 +100,12 @@ pub enum Category {
 	DatabaseConfig,
 	/// crate::key::database::sq             /*{ns}*{db}*sq{sq}
 	DatabaseSequence,
+	/// crate::key::database::sc             /*{ns}*{db}!sc{sc}
+	DatabaseSchedule,
+	/// crate::key::database::sh             /*{ns}*{db}!sh{sc}{sh}
+	DatabaseScheduleHistory,
+	/// crate::key::database::ss             /*{ns}*{db}!ss{sc}
+	DatabaseScheduleState,
 	///
 	/// ------------------------------
 	///
@@ -109,6 +121,16 @@ pub enum Category {
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::la                /*{ns}*{db}*{tb}!la{lq}{group}{shard}
+	TableLiveAggregate,
+	/// crate::key::table::lm                /*{ns}*{db}*{tb}!lm{lq}{group}{field}{ord}{id}
+	TableLiveAggregateOrder,
+	/// crate::key::table::vc                /*{ns}*{db}*{tb}!vc{ft}{id}
+	TableViewChange,
+	/// crate::key::table::vr                /*{ns}*{db}*{tb}!vr{id}
+	TableRecordVersion,
+	/// crate::key::table::st                /*{ns}*{db}*{tb}!st
+	TableStatistics,
 	///
 	/// ------------------------------
 	///
@@ -194,6 +216,7 @@ impl Display for Category {
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
 			Self::NamespaceRoot => "NamespaceRoot",
+			Self::NamespaceAudit => "NamespaceAudit",
 			Self::DatabaseAlias => "DatabaseAlias",
 			Self::DatabaseIdentifier => "DatabaseIdentifier",
 			Self::NamespaceAccess => "NamespaceAccess",
@@ -208,7 +231,9 @@ impl Display for Category {
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
 			Self::DatabaseBucket => "DatabaseBucket",
 			Self::DatabaseFunction => "DatabaseFunction",
+			Self::DatabaseMigration => "DatabaseMigration",
 			Self::DatabaseModel => "DatabaseModel",
+			Self::DatabaseOutbox => "DatabaseOutbox",
 			Self::DatabaseParameter => "DatabaseParameter",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
@@ -216,6 +241,9 @@ impl Display for Category {
 			Self::DatabaseUser => "DatabaseUser",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseSequence => "DatabaseSequence",
+			Self::DatabaseSchedule => "DatabaseSchedule",
+			Self::DatabaseScheduleHistory => "DatabaseScheduleHistory",
+			Self::DatabaseScheduleState => "DatabaseScheduleState",
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
 			Self::TableEvent => "TableEvent",
@@ -223,6 +251,11 @@ impl Display for Category {
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableLiveAggregate => "TableLiveAggregate",
+			Self::TableLiveAggregateOrder => "TableLiveAggregateOrder",
+			Self::TableViewChange => "TableViewChange",
+			Self::TableRecordVersion => "TableRecordVersion",
+			Self::TableStatistics => "TableStatistics",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -0,0 +1,79 @@
+//! Stores an applied migration
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Mg<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub mg: &'a str,
+}
+impl_key!(Mg<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, mg: &'a str) -> Mg<'a> {
+	Mg::new(ns, db, mg)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!mg\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!mg\xff");
+	Ok(k)
+}
+
+impl Categorise for Mg<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseMigration
+	}
+}
+
+impl<'a> Mg<'a> {
+	pub fn new(ns: &'a str, db: &'a str, mg: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'm',
+			_e: b'g',
+			mg,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Mg::new(
+			"testns",
+			"testdb",
+			"testmg",
+		);
+		let enc = Mg::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!mgtestmg\0");
+
+		let dec = Mg::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
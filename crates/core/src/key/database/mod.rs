@@ -6,9 +6,14 @@ pub mod az;
 pub mod bu;
 pub mod cg;
 pub mod fc;
+pub mod mg;
 pub mod ml;
+pub mod ob;
 pub mod pa;
+pub mod sc;
+pub mod sh;
 pub mod sq;
+pub mod ss;
 pub mod tb;
 pub mod ti;
 pub mod ts;
@@ -0,0 +1,85 @@
+//! Stores a message of the outbox of a database
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ob<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ob: Uuid,
+}
+impl_key!(Ob<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ob: Uuid) -> Ob<'a> {
+	Ob::new(ns, db, ob)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ob\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ob\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00");
+	Ok(k)
+}
+
+impl Categorise for Ob<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseOutbox
+	}
+}
+
+impl<'a> Ob<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ob: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'o',
+			_e: b'b',
+			ob,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let ob = Uuid::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]);
+		let val = Ob::new("testns", "testdb", ob);
+		let enc = Ob::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00!ob\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		let dec = Ob::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!ob\0");
+	}
+}
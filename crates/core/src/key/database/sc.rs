@@ -0,0 +1,79 @@
+//! Stores a DEFINE SCHEDULE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Sc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub sc: &'a str,
+}
+impl_key!(Sc<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str) -> Sc<'a> {
+	Sc::new(ns, db, sc)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!sc\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!sc\xff");
+	Ok(k)
+}
+
+impl Categorise for Sc<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseSchedule
+	}
+}
+
+impl<'a> Sc<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b'c',
+			sc,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Sc::new(
+			"testns",
+			"testdb",
+			"testsc",
+		);
+		let enc = Sc::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!sctestsc\0");
+
+		let dec = Sc::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,102 @@
+//! Stores a run in the history of a schedule
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Sh<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub sc: &'a str,
+	pub sh: Uuid,
+}
+impl_key!(Sh<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str, sh: Uuid) -> Sh<'a> {
+	Sh::new(ns, db, sc, sh)
+}
+
+pub fn prefix(ns: &str, db: &str, sc: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!sh");
+	k.extend_from_slice(sc.as_bytes());
+	k.extend_from_slice(b"\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, sc: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!sh");
+	k.extend_from_slice(sc.as_bytes());
+	k.extend_from_slice(
+		b"\x00\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00",
+	);
+	Ok(k)
+}
+
+impl Categorise for Sh<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseScheduleHistory
+	}
+}
+
+impl<'a> Sh<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str, sh: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b'h',
+			sc,
+			sh,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let sh = Uuid::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]);
+		let val = Sh::new("testns", "testdb", "testsc", sh);
+		let enc = Sh::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0!shtestsc\0\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		let dec = Sh::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb", "testsc").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!shtestsc\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix("testns", "testdb", "testsc").unwrap();
+		assert_eq!(
+			val,
+			b"/*testns\0*testdb\0!shtestsc\0\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00"
+		);
+	}
+}
@@ -0,0 +1,66 @@
+//! Stores a the run state of a schedule
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ss<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub sc: &'a str,
+}
+impl_key!(Ss<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str) -> Ss<'a> {
+	Ss::new(ns, db, sc)
+}
+
+impl Categorise for Ss<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseScheduleState
+	}
+}
+
+impl<'a> Ss<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b's',
+			sc,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ss::new(
+			"testns",
+			"testdb",
+			"testsc",
+		);
+		let enc = Ss::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!sstestsc\0");
+
+		let dec = Ss::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
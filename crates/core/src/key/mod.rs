@@ -18,6 +18,7 @@
 ///
 /// crate::key::namespace::all           /*{ns}
 /// crate::key::namespace::ac            /*{ns}!ac{ac}
+/// crate::key::namespace::au            /*{ns}!au{au}
 /// crate::key::namespace::db            /*{ns}!db{db}
 /// crate::key::namespace::di            /+{ns id}!di
 /// crate::key::namespace::lg            /*{ns}!lg{lg}
@@ -31,8 +32,13 @@
 /// crate::key::database::az             /*{ns}*{db}!az{az}
 /// crate::key::database::bu             /*{ns}*{db}!bu{bu}
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
+/// crate::key::database::mg             /*{ns}*{db}!mg{mg}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
+/// crate::key::database::ob             /*{ns}*{db}!ob{ob}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::sc             /*{ns}*{db}!sc{sc}
+/// crate::key::database::sh             /*{ns}*{db}!sh{sc}{sh}
+/// crate::key::database::ss             /*{ns}*{db}!ss{sc}
 /// crate::key::database::sq             /*{ns}*{db}!sq{sq}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
@@ -49,7 +55,12 @@
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
+/// crate::key::table::la                /*{ns}*{db}*{tb}!la{lq}{group}{shard}
+/// crate::key::table::lm                /*{ns}*{db}*{tb}!lm{lq}{group}{field}{ord}{id}
 /// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
+/// crate::key::table::vc                /*{ns}*{db}*{tb}!vc{ft}{id}
+/// crate::key::table::vr                /*{ns}*{db}*{tb}!vr{id}
+/// crate::key::table::st                /*{ns}*{db}*{tb}!st
 ///
 /// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 /// crate::key::index::bc                /*{ns}*{db}*{tb}+{ix}!bc{id}
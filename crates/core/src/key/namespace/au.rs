@@ -0,0 +1,90 @@
+//! Stores an entry of the audit log of a namespace
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Au<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub au: Uuid,
+}
+impl_key!(Au<'a>);
+
+pub fn new(ns: &str, au: Uuid) -> Au<'_> {
+	Au::new(ns, au)
+}
+
+pub fn prefix(ns: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns).encode()?;
+	k.extend_from_slice(b"!au\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns).encode()?;
+	k.extend_from_slice(b"!au\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00");
+	Ok(k)
+}
+
+impl Categorise for Au<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceAudit
+	}
+}
+
+impl<'a> Au<'a> {
+	pub fn new(ns: &'a str, au: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'a',
+			_d: b'u',
+			au,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let au = Uuid::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]);
+		let val = Au::new("testns", au);
+		let enc = Au::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00!au\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		let dec = Au::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns").unwrap();
+		assert_eq!(val, b"/*testns\0!au\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns").unwrap();
+		assert_eq!(
+			val,
+			b"/*testns\0!au\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00"
+		);
+	}
+}
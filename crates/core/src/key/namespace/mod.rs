@@ -1,6 +1,7 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod au;
 pub mod db;
 pub mod di;
 pub mod us;
@@ -26,6 +26,9 @@ impl Tl {
 	pub(crate) fn new(task: &TaskLeaseType) -> Self {
 		let task = match task {
 			TaskLeaseType::ChangeFeedCleanup => 1,
+			TaskLeaseType::RecordExpiry => 2,
+			TaskLeaseType::OutboxDelivery => 3,
+			TaskLeaseType::ScheduleRunner => 4,
 		};
 		Self {
 			__: b'/',
@@ -51,4 +54,37 @@ mod tests {
 		let dec = Tl::decode(&enc).unwrap();
 		assert_eq!(val, dec);
 	}
+
+	#[test]
+	fn key_record_expiry() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tl::new(&TaskLeaseType::RecordExpiry);
+		let enc = Tl::encode(&val).unwrap();
+		assert_eq!(enc, b"/!tl\0\x02");
+		let dec = Tl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn key_outbox_delivery() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tl::new(&TaskLeaseType::OutboxDelivery);
+		let enc = Tl::encode(&val).unwrap();
+		assert_eq!(enc, b"/!tl\0\x03");
+		let dec = Tl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn key_schedule_runner() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tl::new(&TaskLeaseType::ScheduleRunner);
+		let enc = Tl::encode(&val).unwrap();
+		assert_eq!(enc, b"/!tl\0\x04");
+		let dec = Tl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
 }
@@ -0,0 +1,207 @@
+//! Stores the aggregate state of a grouped LIVE SELECT query
+use crate::expr::array::Array;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+}
+impl_key!(Prefix<'a>);
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+struct GroupPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+	pub group: Cow<'a, Array>,
+}
+impl_key!(GroupPrefix<'a>);
+
+impl<'a> GroupPrefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid, group: &'a Array) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'a',
+			lq,
+			group: Cow::Borrowed(group),
+		}
+	}
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'a',
+			lq,
+		}
+	}
+}
+
+/// La is used to store the aggregate state of a single group of a LIVE
+/// SELECT query with a GROUP clause.
+///
+/// The state of each group is split across a number of shards, so that
+/// records which are written concurrently rarely update the same key.
+/// The value of the la is the aggregate state of the records in the shard.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct La<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+	pub group: Cow<'a, Array>,
+	pub shard: u16,
+}
+impl_key!(La<'a>);
+
+pub fn new<'a>(
+	ns: &'a str,
+	db: &'a str,
+	tb: &'a str,
+	lq: Uuid,
+	group: &'a Array,
+	shard: u16,
+) -> La<'a> {
+	La::new(ns, db, tb, lq, group, shard)
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, lq: Uuid) -> Result<Vec<u8>> {
+	Prefix::new(ns, db, tb, lq).encode()
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, lq: Uuid) -> Result<Vec<u8>> {
+	let mut k = prefix(ns, db, tb, lq)?;
+	k.push(0xff);
+	Ok(k)
+}
+
+pub fn group_prefix(ns: &str, db: &str, tb: &str, lq: Uuid, group: &Array) -> Result<Vec<u8>> {
+	GroupPrefix::new(ns, db, tb, lq, group).encode()
+}
+
+pub fn group_suffix(ns: &str, db: &str, tb: &str, lq: Uuid, group: &Array) -> Result<Vec<u8>> {
+	let mut k = group_prefix(ns, db, tb, lq, group)?;
+	k.extend_from_slice(&[0xff, 0xff]);
+	Ok(k)
+}
+
+impl Categorise for La<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableLiveAggregate
+	}
+}
+
+impl<'a> La<'a> {
+	pub fn new(
+		ns: &'a str,
+		db: &'a str,
+		tb: &'a str,
+		lq: Uuid,
+		group: &'a Array,
+		shard: u16,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'a',
+			lq,
+			group: Cow::Borrowed(group),
+			shard,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let group = vec!["open"].into();
+		let val = La::new("testns", "testdb", "testtb", live_query_id, &group, 3);
+		let enc = La::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!la\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\0\0\0\x04open\0\x01\0\x03"
+		);
+
+		let dec = La::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+		// The key is within the range of its group
+		let beg = super::group_prefix("testns", "testdb", "testtb", live_query_id, &group).unwrap();
+		let end = super::group_suffix("testns", "testdb", "testtb", live_query_id, &group).unwrap();
+		assert!(beg < enc && enc < end);
+	}
+
+	#[test]
+	fn prefix() {
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = super::prefix("testns", "testdb", "testtb", live_query_id).unwrap();
+		assert_eq!(
+			val,
+			b"/*testns\x00*testdb\x00*testtb\x00!la\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		)
+	}
+}
@@ -0,0 +1,252 @@
+//! Stores the ordered values of a minimum or maximum field of a grouped LIVE SELECT query
+use crate::expr::Id;
+use crate::expr::array::Array;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+}
+impl_key!(Prefix<'a>);
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'm',
+			lq,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+struct FieldPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+	pub group: Cow<'a, Array>,
+	pub field: u16,
+}
+impl_key!(FieldPrefix<'a>);
+
+impl<'a> FieldPrefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid, group: &'a Array, field: u16) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'm',
+			lq,
+			group: Cow::Borrowed(group),
+			field,
+		}
+	}
+}
+
+/// Lm is used to store the value of a single record for a `min` or `max`
+/// aggregate field of a LIVE SELECT query with a GROUP clause.
+///
+/// The keys of a field are ordered by the value, so that the minimum or the
+/// maximum value of a group is found without computing the group again when
+/// the record holding it is removed. The value of the lm is the value of the
+/// record for the field.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Lm<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+	pub group: Cow<'a, Array>,
+	pub field: u16,
+	pub ord: u64,
+	pub sub: u32,
+	pub id: Id,
+}
+impl_key!(Lm<'a>);
+
+#[expect(clippy::too_many_arguments)]
+pub fn new<'a>(
+	ns: &'a str,
+	db: &'a str,
+	tb: &'a str,
+	lq: Uuid,
+	group: &'a Array,
+	field: u16,
+	ord: (u64, u32),
+	id: &Id,
+) -> Lm<'a> {
+	Lm::new(ns, db, tb, lq, group, field, ord, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, lq: Uuid) -> Result<Vec<u8>> {
+	Prefix::new(ns, db, tb, lq).encode()
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, lq: Uuid) -> Result<Vec<u8>> {
+	let mut k = prefix(ns, db, tb, lq)?;
+	k.push(0xff);
+	Ok(k)
+}
+
+pub fn field_prefix(
+	ns: &str,
+	db: &str,
+	tb: &str,
+	lq: Uuid,
+	group: &Array,
+	field: u16,
+) -> Result<Vec<u8>> {
+	FieldPrefix::new(ns, db, tb, lq, group, field).encode()
+}
+
+pub fn field_suffix(
+	ns: &str,
+	db: &str,
+	tb: &str,
+	lq: Uuid,
+	group: &Array,
+	field: u16,
+) -> Result<Vec<u8>> {
+	// The first key after all of the keys of the field
+	let mut k = field_prefix(ns, db, tb, lq, group, field)?;
+	while let Some(b) = k.pop() {
+		if b < 0xff {
+			k.push(b + 1);
+			break;
+		}
+	}
+	Ok(k)
+}
+
+impl Categorise for Lm<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableLiveAggregateOrder
+	}
+}
+
+impl<'a> Lm<'a> {
+	#[expect(clippy::too_many_arguments)]
+	pub fn new(
+		ns: &'a str,
+		db: &'a str,
+		tb: &'a str,
+		lq: Uuid,
+		group: &'a Array,
+		field: u16,
+		ord: (u64, u32),
+		id: Id,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'm',
+			lq,
+			group: Cow::Borrowed(group),
+			field,
+			ord: ord.0,
+			sub: ord.1,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let group = vec!["open"].into();
+		let id = Id::from("test");
+		let val = Lm::new("testns", "testdb", "testtb", live_query_id, &group, 1, (2, 3), id);
+		let enc = Lm::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!lm\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\0\0\0\x04open\0\x01\0\x01\0\0\0\0\0\0\0\x02\0\0\0\x03\0\0\0\x01test\0"
+		);
+
+		let dec = Lm::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn order() {
+		use super::*;
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let group = vec!["open"].into();
+		// The keys of a field are ordered by the value, and then by the record id
+		let key = |ord, id: &str| {
+			Lm::new("testns", "testdb", "testtb", live_query_id, &group, 0, ord, Id::from(id))
+				.encode()
+				.unwrap()
+		};
+		assert!(key((1, 0), "b") < key((1, 1), "a"));
+		assert!(key((1, 1), "a") < key((1, 1), "b"));
+		assert!(key((1, 1), "b") < key((2, 0), "a"));
+		// The keys are within the range of their field
+		let beg =
+			super::field_prefix("testns", "testdb", "testtb", live_query_id, &group, 0).unwrap();
+		let end =
+			super::field_suffix("testns", "testdb", "testtb", live_query_id, &group, 0).unwrap();
+		assert!(beg < key((0, 0), "a") && key((u64::MAX, u32::MAX), "z") < end);
+	}
+}
@@ -3,4 +3,9 @@ pub mod ev;
 pub mod fd;
 pub mod ft;
 pub mod ix;
+pub mod la;
+pub mod lm;
 pub mod lq;
+pub mod st;
+pub mod vc;
+pub mod vr;
@@ -0,0 +1,99 @@
+//! Stores a record change which is pending for a suspended DEFINE TABLE AS view
+use crate::expr::Id;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Vc is used to track the records of a foreign table which have changed
+/// while the maintenance of a view is suspended.
+///
+/// The value of the vc is the record as it was before its first change
+/// since the view was suspended, or NONE if the record did not exist.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Vc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ft: &'a str,
+	pub id: Id,
+}
+impl_key!(Vc<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, ft: &'a str, id: &Id) -> Vc<'a> {
+	Vc::new(ns, db, tb, ft, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!vc\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!vc\xff");
+	Ok(k)
+}
+
+impl Categorise for Vc<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableViewChange
+	}
+}
+
+impl<'a> Vc<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ft: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'c',
+			ft,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Vc::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testft",
+			"testid".into(),
+		);
+		let enc = Vc::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!vctestft\0\0\0\0\x01testid\0");
+
+		let dec = Vc::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!vc\0");
+	}
+}
@@ -0,0 +1,96 @@
+//! Stores the version of a record
+use crate::expr::Id;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{KeyEncode, impl_key};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Vr is used to track the version of a record of a VERSIONED table, which
+/// is incremented every time that the record is written.
+///
+/// The version is kept when the record is deleted, so that a record which
+/// is deleted and created again never reuses the version of the old record.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Vr<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Id,
+}
+impl_key!(Vr<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, id: &Id) -> Vr<'a> {
+	Vr::new(ns, db, tb, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!vr\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!vr\xff");
+	Ok(k)
+}
+
+impl Categorise for Vr<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableRecordVersion
+	}
+}
+
+impl<'a> Vr<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'r',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Vr::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testid".into(),
+		);
+		let enc = Vr::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!vr\0\0\0\x01testid\0");
+
+		let dec = Vr::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!vr\0");
+	}
+}
@@ -59,6 +59,9 @@ pub trait Transaction: requirements::TransactionRequirements {
 	/// Returns if the transaction supports scanning in reverse.
 	fn supports_reverse_scan(&self) -> bool;
 
+	/// Returns if the transaction deletes ranges of keys transactionally.
+	fn supports_transactional_range_delete(&self) -> bool;
+
 	/// Get the name of the transaction type.
 	fn kind(&self) -> &'static str;
 
@@ -199,6 +202,25 @@ pub trait Transaction: requirements::TransactionRequirements {
 		Ok(out)
 	}
 
+	/// Insert, update, or delete many keys in the datastore.
+	///
+	/// Each key is set to its value, or deleted when it has no value. This function applies all of the writes with a single request to the underlying datastore where this is supported.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(writes = writes.len()))]
+	async fn setm(&mut self, writes: Vec<(Key, Option<Val>)>) -> Result<()> {
+		// Check to see if transaction is closed
+		ensure!(!self.closed(), Error::TxFinished);
+		// Check to see if transaction is writable
+		ensure!(self.writeable(), Error::TxReadonly);
+		// Continue with function logic
+		for (key, val) in writes.into_iter() {
+			match val {
+				Some(val) => self.set(key, val, None).await?,
+				None => self.del(key).await?,
+			}
+		}
+		Ok(())
+	}
+
 	/// Retrieve a range of prefixed keys from the datastore.
 	///
 	/// This function fetches all matching key-value pairs from the underlying datastore in grouped batches.
@@ -546,8 +568,12 @@ pub trait Transaction: requirements::TransactionRequirements {
 
 	/// Release the last save point.
 	fn release_last_save_point(&mut self) -> Result<()> {
-		self.get_save_points().pop()?;
-		Ok(())
+		self.get_save_points().release()
+	}
+
+	/// Check if releasing a save point also removes it from the underlying transaction.
+	fn releases_save_points(&self) -> bool {
+		true
 	}
 
 	/// Prepare a save point for a key.
@@ -0,0 +1,385 @@
+//! A binary format for database exports, which can be imported without parsing SurrealQL.
+//!
+//! An archive starts with an uncompressed header, consisting of the [`MAGIC`] bytes, the
+//! big-endian `u16` format version, a byte of flags, and a big-endian `u64` length followed by
+//! a preamble of SurrealQL definition statements, which remains readable in any text viewer.
+//!
+//! The remainder of the archive, which is compressed with zstd if the [`FLAG_ZSTD`] flag is
+//! set, is a sequence of frames. A table frame contains the length-prefixed name of the table
+//! which the following records belong to. A record frame contains a length-prefixed key, which
+//! is relative to the table, and a length-prefixed value, in the internal serialisation of the
+//! key-value store. An end frame marks the end of the archive, so that truncated archives can
+//! be detected on import.
+use super::Datastore;
+use super::KeyEncode as _;
+use super::LockType::*;
+use super::Transaction;
+use super::TransactionType::*;
+use super::export::Config;
+use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::dbs::{Response, Session};
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+use bytes::{Buf, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+#[cfg(not(target_family = "wasm"))]
+use std::io::Write;
+use std::pin::{Pin, pin};
+
+/// The bytes which every binary export starts with
+pub(crate) const MAGIC: &[u8; 16] = b"SURREALDB-EXPORT";
+/// The latest version of the binary export format
+const VERSION: u16 = 1;
+/// The length of the header, before the preamble
+const HEADER_LEN: usize = MAGIC.len() + 2 + 1 + 8;
+
+/// The flag which is set when the frames are compressed with zstd
+const FLAG_ZSTD: u8 = 1;
+
+const FRAME_TABLE: u8 = b'T';
+const FRAME_RECORD: u8 = b'R';
+const FRAME_END: u8 = b'E';
+
+/// Checks whether the start of an import is a binary export
+pub(crate) fn is_archive(head: &[u8]) -> bool {
+	head.starts_with(MAGIC)
+}
+
+fn invalid(message: impl Into<String>) -> anyhow::Error {
+	anyhow::Error::new(Error::InvalidBinaryExport(message.into()))
+}
+
+#[cfg(target_family = "wasm")]
+fn compression_unsupported() -> anyhow::Error {
+	anyhow::Error::new(Error::Unimplemented(
+		"Compressed binary exports are not supported on this platform".to_owned(),
+	))
+}
+
+/// Writes frames into the archive, optionally compressing them
+enum Encoder {
+	Plain(Vec<u8>),
+	#[cfg(not(target_family = "wasm"))]
+	Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+	fn new(compression: bool) -> Result<Self> {
+		if !compression {
+			return Ok(Self::Plain(Vec::new()));
+		}
+		#[cfg(not(target_family = "wasm"))]
+		return Ok(Self::Zstd(zstd::stream::write::Encoder::new(Vec::new(), 0)?));
+		#[cfg(target_family = "wasm")]
+		Err(compression_unsupported())
+	}
+
+	fn write_frame(&mut self, kind: u8, parts: &[&[u8]]) -> Result<()> {
+		let mut frame = Vec::with_capacity(1 + parts.iter().map(|p| 4 + p.len()).sum::<usize>());
+		frame.push(kind);
+		for part in parts {
+			let len = u32::try_from(part.len())
+				.map_err(|_| invalid("a frame exceeds the maximum length"))?;
+			frame.extend_from_slice(&len.to_be_bytes());
+			frame.extend_from_slice(part);
+		}
+		match self {
+			Self::Plain(v) => v.extend_from_slice(&frame),
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(v) => v.write_all(&frame)?,
+		}
+		Ok(())
+	}
+
+	/// Takes the output which has been written so far
+	fn take(&mut self) -> Vec<u8> {
+		match self {
+			Self::Plain(v) => std::mem::take(v),
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(v) => std::mem::take(v.get_mut()),
+		}
+	}
+
+	/// Writes the end frame, and returns the remaining output
+	fn finish(mut self) -> Result<Vec<u8>> {
+		self.write_frame(FRAME_END, &[])?;
+		match self {
+			Self::Plain(v) => Ok(v),
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(v) => Ok(v.finish()?),
+		}
+	}
+}
+
+/// Reads frames from the archive, optionally decompressing them
+enum Decoder {
+	Plain(BytesMut),
+	#[cfg(not(target_family = "wasm"))]
+	Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>, BytesMut),
+}
+
+enum Frame {
+	Table(String),
+	Record(Vec<u8>, Vec<u8>),
+	End,
+}
+
+impl Decoder {
+	fn new(flags: u8) -> Result<Self> {
+		ensure!(flags & !FLAG_ZSTD == 0, invalid(format!("unknown flags {flags:#04x}")));
+		if flags & FLAG_ZSTD == 0 {
+			return Ok(Self::Plain(BytesMut::new()));
+		}
+		#[cfg(not(target_family = "wasm"))]
+		return Ok(Self::Zstd(zstd::stream::write::Decoder::new(Vec::new())?, BytesMut::new()));
+		#[cfg(target_family = "wasm")]
+		Err(compression_unsupported())
+	}
+
+	/// Adds more data from the archive
+	fn push(&mut self, data: &[u8]) -> Result<()> {
+		match self {
+			Self::Plain(buf) => buf.extend_from_slice(data),
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(dec, buf) => {
+				dec.write_all(data).map_err(|e| invalid(e.to_string()))?;
+				dec.flush().map_err(|e| invalid(e.to_string()))?;
+				buf.extend_from_slice(dec.get_ref());
+				dec.get_mut().clear();
+			}
+		}
+		Ok(())
+	}
+
+	/// Reads the next complete frame, if enough data is available
+	fn next(&mut self) -> Result<Option<Frame>> {
+		let buf = match self {
+			Self::Plain(buf) => buf,
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(_, buf) => buf,
+		};
+		let len = |at: usize| -> Option<usize> {
+			let bytes = buf.get(at..at + 4)?;
+			Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+		};
+		match buf.first().copied() {
+			None => Ok(None),
+			Some(FRAME_END) => {
+				buf.advance(1);
+				Ok(Some(Frame::End))
+			}
+			Some(FRAME_TABLE) => {
+				let Some(tb) = len(1).filter(|tb| buf.len() >= 5 + tb) else {
+					return Ok(None);
+				};
+				buf.advance(5);
+				let tb = String::from_utf8(buf.split_to(tb).to_vec())
+					.map_err(|_| invalid("a table name is not valid UTF-8"))?;
+				Ok(Some(Frame::Table(tb)))
+			}
+			Some(FRAME_RECORD) => {
+				let Some(k) = len(1) else {
+					return Ok(None);
+				};
+				let Some(v) = len(5 + k).filter(|v| buf.len() >= 9 + k + v) else {
+					return Ok(None);
+				};
+				buf.advance(5);
+				let key = buf.split_to(k).to_vec();
+				buf.advance(4);
+				let val = buf.split_to(v).to_vec();
+				Ok(Some(Frame::Record(key, val)))
+			}
+			Some(kind) => Err(invalid(format!("unknown frame type {kind:#04x}"))),
+		}
+	}
+
+	/// Checks that no data follows the end frame
+	fn is_empty(&self) -> bool {
+		match self {
+			Self::Plain(buf) => buf.is_empty(),
+			#[cfg(not(target_family = "wasm"))]
+			Self::Zstd(_, buf) => buf.is_empty(),
+		}
+	}
+}
+
+impl Transaction {
+	/// Writes the database contents in the binary export format.
+	pub(super) async fn export_binary(
+		&self,
+		ns: &str,
+		db: &str,
+		cfg: Config,
+		chn: Sender<Vec<u8>>,
+	) -> Result<()> {
+		// Versioned records can not be represented as key-value pairs
+		ensure!(
+			!cfg.versions,
+			Error::Unimplemented("Exporting versions in the binary export format".to_owned())
+		);
+		// Fetch all of the included tables for this NS / DB
+		let tables = self.all_tb(ns, db, None).await?;
+		let tables = tables.iter().filter(|tb| cfg.tables.includes(&tb.name));
+		// Collect all of the definitions into the preamble
+		let (snd, rcv) = async_channel::unbounded();
+		self.export_metadata(&cfg, &snd, ns, db).await?;
+		if cfg.includes_structure() {
+			for table in tables.clone() {
+				self.export_table_structure(ns, db, table, &cfg, &snd).await?;
+			}
+		}
+		drop(snd);
+		let mut preamble = Vec::new();
+		while let Ok(v) = rcv.try_recv() {
+			preamble.extend(v);
+		}
+		// Output the header and the preamble
+		let flags = if cfg.compression {
+			FLAG_ZSTD
+		} else {
+			0
+		};
+		let mut header = Vec::with_capacity(HEADER_LEN + preamble.len());
+		header.extend_from_slice(MAGIC);
+		header.extend_from_slice(&VERSION.to_be_bytes());
+		header.push(flags);
+		header.extend_from_slice(&(preamble.len() as u64).to_be_bytes());
+		header.extend(preamble);
+		chn.send(header).await?;
+		// Output the records of each table
+		let mut enc = Encoder::new(cfg.compression)?;
+		if cfg.records {
+			for table in tables {
+				enc.write_frame(FRAME_TABLE, &[table.name.as_bytes()])?;
+				// Export all data stored under the table, including graph
+				// edges, references, and index entries, but skip the
+				// definitions, which are already part of the preamble
+				let beg = crate::key::table::all::new(ns, db, &table.name).encode()?;
+				let mut end = beg.clone();
+				end.push(0xff);
+				let len = beg.len();
+				let mut next = Some(beg..end);
+				while let Some(rng) = next {
+					let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+					next = batch.next;
+					for (k, v) in batch.result {
+						if k.get(len) != Some(&b'!') {
+							enc.write_frame(FRAME_RECORD, &[&k[len..], &v])?;
+						}
+					}
+					// Send each batch, so memory usage stays bounded
+					let out = enc.take();
+					if !out.is_empty() {
+						chn.send(out).await?;
+					}
+				}
+			}
+		}
+		chn.send(enc.finish()?).await?;
+		Ok(())
+	}
+}
+
+impl Datastore {
+	/// Imports a database from the binary export format.
+	///
+	/// The definitions in the preamble are executed as SurrealQL, after which the
+	/// records are written directly as key-value pairs, in batched transactions.
+	pub(crate) async fn import_archive<S>(&self, sess: &Session, stream: S) -> Result<Vec<Response>>
+	where
+		S: Stream<Item = Result<Bytes>>,
+	{
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Records are written without permission checks on each table
+		self.check(sess, Action::Edit, ResourceKind::Any.on_db(&ns, &db))?;
+		let mut stream = pin!(stream);
+		// Read the header and the preamble
+		let mut head = BytesMut::new();
+		fill(&mut stream, &mut head, HEADER_LEN).await?;
+		ensure!(head.starts_with(MAGIC), invalid("the export does not start with the header"));
+		let version = u16::from_be_bytes([head[16], head[17]]);
+		ensure!(
+			version <= VERSION,
+			Error::BinaryExportVersion {
+				version,
+				supported: VERSION,
+			}
+		);
+		let mut dec = Decoder::new(head[18])?;
+		let len = u64::from_be_bytes(head[19..HEADER_LEN].try_into()?) as usize;
+		head.advance(HEADER_LEN);
+		fill(&mut stream, &mut head, len).await?;
+		let preamble = head.split_to(len);
+		let preamble = std::str::from_utf8(&preamble)
+			.map_err(|_| invalid("the preamble is not valid UTF-8"))?;
+		// Execute the definitions
+		let res = self.execute(preamble, sess, None).await?;
+		if res.iter().any(|v| v.result.is_err()) {
+			return Ok(res);
+		}
+		// Write the records in batches
+		dec.push(&head)?;
+		drop(head);
+		let mut txn = self.transaction(Write, Optimistic).await?;
+		let mut count = 0;
+		let mut prefix = None;
+		let result = async {
+			loop {
+				match dec.next()? {
+					Some(Frame::Table(tb)) => {
+						prefix = Some(crate::key::table::all::new(&ns, &db, &tb).encode()?);
+					}
+					Some(Frame::Record(k, v)) => {
+						let Some(prefix) = &prefix else {
+							bail!(invalid("a record does not belong to a table"));
+						};
+						let mut key = prefix.clone();
+						key.extend(k);
+						txn.set(key, v, None).await?;
+						count += 1;
+						// Commit the transaction at the end of each batch
+						if count % *EXPORT_BATCH_SIZE == 0 {
+							txn.commit().await?;
+							txn = self.transaction(Write, Optimistic).await?;
+						}
+					}
+					Some(Frame::End) => {
+						ensure!(
+							dec.is_empty() && stream.next().await.is_none(),
+							invalid("unexpected data after the end of the export")
+						);
+						return txn.commit().await;
+					}
+					None => match stream.next().await {
+						Some(v) => dec.push(&v?)?,
+						None => bail!(invalid("the export ended unexpectedly")),
+					},
+				}
+			}
+		}
+		.await;
+		if let Err(e) = result {
+			let _ = txn.cancel().await;
+			return Err(e);
+		}
+		Ok(res)
+	}
+}
+
+/// Reads from the stream until the buffer contains at least `len` bytes
+async fn fill<S>(stream: &mut Pin<&mut S>, buf: &mut BytesMut, len: usize) -> Result<()>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	while buf.len() < len {
+		match stream.next().await {
+			Some(v) => buf.extend_from_slice(&v?),
+			None => bail!(invalid("the export ended unexpectedly")),
+		}
+	}
+	Ok(())
+}
@@ -6,6 +6,7 @@ mod weight;
 use anyhow::Result;
 pub(crate) use entry::Entry;
 pub(crate) use lookup::Lookup;
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
 pub(crate) type Cache = quick_cache::sync::Cache<key::Key, Entry, weight::Weight>;
@@ -13,6 +14,8 @@ pub(crate) type Cache = quick_cache::sync::Cache<key::Key, Entry, weight::Weight
 pub struct DatastoreCache {
 	/// Store the cache entries
 	cache: Cache,
+	/// Incremented whenever the definitions of a table change
+	catalog_version: AtomicU64,
 }
 
 impl DatastoreCache {
@@ -25,6 +28,7 @@ impl DatastoreCache {
 		);
 		Self {
 			cache,
+			catalog_version: AtomicU64::new(0),
 		}
 	}
 
@@ -42,11 +46,20 @@ impl DatastoreCache {
 	pub(crate) fn clear_tb(&self, ns: &str, db: &str, tb: &str) {
 		let key = Lookup::Tb(ns, db, tb);
 		self.cache.remove(&key);
+		self.catalog_version.fetch_add(1, Ordering::AcqRel);
 	}
 
 	/// Clear all items from the datastore cache
 	pub(crate) fn clear(&self) {
 		self.cache.clear();
+		self.catalog_version.fetch_add(1, Ordering::AcqRel);
+	}
+
+	/// The version of the table, field, index and event definitions.
+	/// This changes every time that one of these definitions changes
+	/// on this node, so that anything derived from them can be rebuilt.
+	pub(crate) fn catalog_version(&self) -> u64 {
+		self.catalog_version.load(Ordering::Acquire)
 	}
 
 	pub fn get_live_queries_version(&self, ns: &str, db: &str, tb: &str) -> Result<Uuid> {
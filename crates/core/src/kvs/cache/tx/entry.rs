@@ -15,7 +15,9 @@ use crate::expr::statements::DefineTableStatement;
 use crate::expr::statements::DefineUserStatement;
 use crate::expr::statements::LiveStatement;
 use crate::expr::statements::define::DefineConfigStatement;
-use crate::expr::statements::define::{ApiDefinition, BucketDefinition, DefineSequenceStatement};
+use crate::expr::statements::define::{
+	ApiDefinition, BucketDefinition, DefineScheduleStatement, DefineSequenceStatement,
+};
 use anyhow::Result;
 use std::any::Any;
 use std::sync::Arc;
@@ -69,6 +71,8 @@ pub(crate) enum Entry {
 	Pas(Arc<[DefineParamStatement]>),
 	/// A slice of DefineSequenceStatement specified on a namespace.
 	Sqs(Arc<[DefineSequenceStatement]>),
+	/// A slice of DefineScheduleStatement specified on a database.
+	Scs(Arc<[DefineScheduleStatement]>),
 	/// A slice of DefineEventStatement specified on a table.
 	Evs(Arc<[DefineEventStatement]>),
 	/// A slice of DefineFieldStatement specified on a table.
@@ -223,6 +227,14 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Sqs"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineScheduleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Scs`].
+	pub(crate) fn try_into_scs(self) -> Result<Arc<[DefineScheduleStatement]>> {
+		match self {
+			Entry::Scs(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Scs"),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineFunctionStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Fcs`].
 	pub(crate) fn try_into_fcs(self) -> Result<Arc<[DefineFunctionStatement]>> {
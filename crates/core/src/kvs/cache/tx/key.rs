@@ -46,6 +46,8 @@ pub(crate) enum Key {
 	Tbs(String, String),
 	/// A cache key for sequences (on a database)
 	Seq(String, String),
+	/// A cache key for schedules (on a database)
+	Scs(String, String),
 	/// A cache key for events (on a table)
 	Evs(String, String, String),
 	/// A cache key for fieds (on a table)
@@ -96,6 +98,8 @@ pub(crate) enum Key {
 	Pa(String, String, String),
 	/// A cache key for a sequence (on a database)
 	Sq(String, String, String),
+	/// A cache key for a schedule (on a database)
+	Sc(String, String, String),
 	/// A cache key for a table
 	Tb(String, String, String),
 	/// A cache key for an event (on a table)
@@ -133,6 +137,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cgs(a, b) => Key::Cgs(a.to_string(), b.to_string()),
 			Lookup::Pas(a, b) => Key::Pas(a.to_string(), b.to_string()),
 			Lookup::Sqs(a, b) => Key::Seq(a.to_string(), b.to_string()),
+			Lookup::Scs(a, b) => Key::Scs(a.to_string(), b.to_string()),
 			Lookup::Tbs(a, b) => Key::Tbs(a.to_string(), b.to_string()),
 			Lookup::Evs(a, b, c) => Key::Evs(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Fds(a, b, c) => Key::Fds(a.to_string(), b.to_string(), c.to_string()),
@@ -160,6 +165,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Cg(a, b, c) => Key::Cg(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Sq(a, b,c) => Key::Sq(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::Sc(a, b, c) => Key::Sc(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Ev(a, b, c, d) => Key::Ev(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Fd(a, b, c, d) => Key::Fd(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
@@ -45,6 +45,8 @@ pub(crate) enum Lookup<'a> {
 	Pas(&'a str, &'a str),
 	/// A cache key for sequences (on a database)
 	Sqs(&'a str, &'a str),
+	/// A cache key for schedules (on a database)
+	Scs(&'a str, &'a str),
 	/// A cache key for tables
 	Tbs(&'a str, &'a str),
 	/// A cache key for events (on a table)
@@ -97,6 +99,8 @@ pub(crate) enum Lookup<'a> {
 	Pa(&'a str, &'a str, &'a str),
 	/// A cache key for a sequence (on a database)
 	Sq(&'a str, &'a str, &'a str),
+	/// A cache key for a schedule (on a database)
+	Sc(&'a str, &'a str, &'a str),
 	/// A cache key for a table
 	Tb(&'a str, &'a str, &'a str),
 	/// A cache key for an event (on a table)
@@ -0,0 +1,218 @@
+//! Draining of the running queries when the datastore shuts down.
+//!
+//! Every query which runs on the datastore holds a [`Running`] guard. Once
+//! the datastore starts to drain, no new guards are handed out, so new
+//! queries are rejected. The live queries on this node receive a `KILLED`
+//! notification which explains that the server is shutting down. After the
+//! grace period, the queries which are still running are cancelled, in the
+//! same way as a cancelled query, through the cancellation of their context.
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Canceller;
+use crate::dbs::{Action, Notification};
+use crate::err::Error;
+use crate::expr::Value;
+use crate::kvs::Datastore;
+use crate::kvs::KeyDecode as _;
+use crate::kvs::{LockType::*, TransactionType::*};
+use anyhow::{Result, ensure};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use trice::Instant;
+
+const TARGET: &str = "surrealdb::core::kvs::drain";
+
+/// The result sent in the `KILLED` notification of the live queries
+pub const SHUTTING_DOWN: &str = "The server is shutting down";
+
+/// How often the running queries are checked while draining
+const INTERVAL: Duration = Duration::from_millis(10);
+
+/// The queries which are running on a datastore
+#[derive(Default)]
+pub(crate) struct Activity {
+	/// Whether new queries are rejected
+	draining: AtomicBool,
+	/// The id of the next running query
+	next: AtomicU64,
+	/// The cancellation of each running query
+	running: Mutex<HashMap<u64, Canceller>>,
+}
+
+impl Activity {
+	/// Registers a running query, which is cancelled with the given canceller
+	pub(crate) fn start(self: &Arc<Self>, canceller: Canceller) -> Result<Running> {
+		ensure!(!self.draining.load(Ordering::Acquire), Error::ServerShuttingDown);
+		let id = self.next.fetch_add(1, Ordering::Relaxed);
+		self.running.lock().insert(id, canceller);
+		Ok(Running {
+			activity: self.clone(),
+			id,
+		})
+	}
+
+	/// The number of running queries
+	fn len(&self) -> usize {
+		self.running.lock().len()
+	}
+
+	/// Waits until nothing is running, returning false if the timeout passed first
+	async fn wait(&self, timeout: Duration) -> bool {
+		let started = Instant::now();
+		while self.len() > 0 {
+			if started.elapsed() >= timeout {
+				return false;
+			}
+			#[cfg(target_family = "wasm")]
+			wasmtimer::tokio::sleep(INTERVAL).await;
+			#[cfg(not(target_family = "wasm"))]
+			tokio::time::sleep(INTERVAL).await;
+		}
+		true
+	}
+
+	/// Cancels everything which is running, returning how many were cancelled
+	fn cancel(&self) -> usize {
+		let running = self.running.lock();
+		running.values().for_each(Canceller::cancel);
+		running.len()
+	}
+}
+
+/// A query which is running on the datastore
+pub(crate) struct Running {
+	activity: Arc<Activity>,
+	id: u64,
+}
+
+impl Drop for Running {
+	fn drop(&mut self) {
+		self.activity.running.lock().remove(&self.id);
+	}
+}
+
+impl Datastore {
+	/// Registers a query which is about to run on the datastore
+	pub(crate) fn start_running(&self, canceller: Canceller) -> Result<Running> {
+		self.activity.start(canceller)
+	}
+
+	/// Checks if the datastore rejects new queries, as it is shutting down
+	pub fn is_draining(&self) -> bool {
+		self.activity.draining.load(Ordering::Acquire)
+	}
+
+	/// Drains the datastore before it is shut down.
+	///
+	/// New queries are rejected from now on, and the live queries on this
+	/// node are sent a `KILLED` notification. This then waits for up to the
+	/// grace period for the running queries to finish, and cancels those
+	/// which are still running.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::drain", skip(self))]
+	pub async fn drain(&self, grace: Duration) -> Result<()> {
+		// Stop accepting new queries
+		if self.activity.draining.swap(true, Ordering::AcqRel) {
+			return Ok(());
+		}
+		info!(target: TARGET, "Draining {} running queries", self.activity.len());
+		// Tell the live queries that the server is shutting down
+		self.kill_live_queries().await?;
+		// Wait for the running queries to finish
+		if !self.activity.wait(grace).await {
+			let count = self.activity.cancel();
+			warn!(
+				target: TARGET,
+				"Cancelled {count} queries which were still running after {grace:?}"
+			);
+		}
+		Ok(())
+	}
+
+	/// Sends a `KILLED` notification to every live query on this node
+	async fn kill_live_queries(&self) -> Result<()> {
+		// Check if notifications are enabled
+		let Some(chn) = self.notification_sender() else {
+			return Ok(());
+		};
+		// Find the live queries on this node
+		let mut ids = Vec::new();
+		let beg = crate::key::node::lq::prefix(self.id())?;
+		let end = crate::key::node::lq::suffix(self.id())?;
+		let mut next = Some(beg..end);
+		let txn = self.transaction(Read, Optimistic).await?;
+		while let Some(rng) = next {
+			let res = catch!(txn, txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await);
+			next = res.next;
+			for k in res.result.iter() {
+				ids.push(catch!(txn, crate::key::node::lq::Lq::decode(k)).lq);
+			}
+		}
+		txn.cancel().await?;
+		// Notify the subscribers
+		trace!(target: TARGET, "Notifying {} live queries of the shutdown", ids.len());
+		for id in ids {
+			chn.send(Notification::new(
+				id.into(),
+				Action::Killed,
+				Value::None,
+				Value::from(SHUTTING_DOWN),
+			))
+			.await?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+
+	#[tokio::test]
+	async fn queries_are_rejected_while_draining() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE person:1", &sess, None).await.unwrap();
+		ds.drain(Duration::from_secs(1)).await.unwrap();
+		assert!(ds.is_draining());
+		let err = ds.execute("SELECT * FROM person", &sess, None).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::ServerShuttingDown)));
+	}
+
+	#[tokio::test]
+	async fn running_queries_are_cancelled_after_the_grace_period() {
+		let ds = Arc::new(Datastore::new("memory").await.unwrap());
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let query = tokio::spawn({
+			let ds = ds.clone();
+			async move { ds.execute("SLEEP 10s; CREATE person:1", &sess, None).await }
+		});
+		// Wait for the query to start
+		while ds.activity.len() == 0 {
+			tokio::time::sleep(INTERVAL).await;
+		}
+		let started = Instant::now();
+		ds.drain(Duration::from_millis(100)).await.unwrap();
+		let res = query.await.unwrap().unwrap();
+		assert!(started.elapsed() < Duration::from_secs(5));
+		assert!(res[1].result.is_err());
+		assert_eq!(ds.activity.len(), 0);
+	}
+
+	#[tokio::test]
+	async fn live_queries_are_killed() {
+		let ds = Datastore::new("memory").await.unwrap().with_notifications();
+		let sess = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let mut res = ds.execute("LIVE SELECT * FROM person", &sess, None).await.unwrap();
+		let Value::Uuid(id) = res.remove(0).result.unwrap() else {
+			panic!("expected a live query id");
+		};
+		ds.drain(Duration::ZERO).await.unwrap();
+		let notification = ds.notifications().unwrap().try_recv().unwrap();
+		assert_eq!(notification.id, id);
+		assert_eq!(notification.action, Action::Killed);
+		assert_eq!(notification.result, Value::from(SHUTTING_DOWN));
+	}
+}
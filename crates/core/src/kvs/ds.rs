@@ -1,24 +1,34 @@
+use super::archive;
+use super::drain::Activity;
 use super::export;
+use super::import;
+use super::scanner::FetchSize;
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::Version;
+use super::writes::WriteBuffer;
 use crate::buc::BucketConnections;
 use crate::cf;
-use crate::ctx::MutableContext;
+use crate::cnf::WRITE_BATCH_SIZE;
+use crate::ctx::{Canceller, MutableContext};
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::{
 	ArbitraryQueryTarget, ExperimentalTarget, MethodTarget, RouteTarget,
 };
 use crate::dbs::node::Timestamp;
+use crate::dbs::registry::FunctionFuture;
 use crate::dbs::{
-	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
+	Attach, Capabilities, Executor, FunctionRegistry, Notification, Options, QueryFrame, Response,
+	Session, SlowQueryLog, Variables,
 };
 use crate::err::Error;
 use crate::expr::LogicalPlan;
-use crate::expr::{Base, FlowResultExt as _, Value, statements::DefineUserStatement};
+use crate::expr::statements::{DefineUserStatement, LiveStatement};
+use crate::expr::{Array, Base, FlowResultExt as _, Kind, Value};
 #[cfg(feature = "jwks")]
 use crate::iam::jwks::JwksCache;
+use crate::iam::throttle::{AuthThrottle, MemoryAuthThrottle};
 use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
 use crate::idx::trees::store::IndexStores;
 use crate::kvs::cache::ds::DatastoreCache;
@@ -29,31 +39,37 @@ use crate::kvs::clock::SystemClock;
 use crate::kvs::index::IndexBuilder;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::tasklease::{LeaseHandler, TaskLeaseType};
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
+use crate::kvs::{
+	KeyDecode as _, Live, LockType, LockType::*, TransactionType, TransactionType::*,
+};
 use crate::sql::Query;
+#[cfg(feature = "arrow")]
+use crate::sql::{SqlValue, statement::Statement};
 use crate::syn;
-use crate::syn::parser::{ParserSettings, StatementStream};
+use crate::syn::parser::ParserSettings;
 #[allow(unused_imports)]
 use anyhow::bail;
 use anyhow::{Result, ensure};
 use async_channel::{Receiver, Sender};
 use bytes::{Bytes, BytesMut};
 use dashmap::DashMap;
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use reblessive::TreeStack;
 use std::fmt;
 #[cfg(storage)]
 use std::path::PathBuf;
 use std::pin::pin;
 use std::sync::Arc;
-use std::task::{Poll, ready};
+use std::sync::atomic::AtomicBool;
 use std::time::Duration;
 #[cfg(not(target_family = "wasm"))]
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncRead;
 #[cfg(feature = "jwks")]
 use tokio::sync::RwLock;
 use tracing::instrument;
 use tracing::trace;
+use trice::Instant;
 use uuid::Uuid;
 #[cfg(target_family = "wasm")]
 use wasmtimer::std::{SystemTime, UNIX_EPOCH};
@@ -101,6 +117,16 @@ pub struct Datastore {
 	buckets: Arc<BucketConnections>,
 	// The sequences
 	sequences: Sequences,
+	// The functions registered by the embedding application
+	functions: Arc<FunctionRegistry>,
+	// The hook which counts the authentication attempts
+	auth_throttle: Arc<dyn AuthThrottle>,
+	// The configuration of the slow query log
+	slow_query_log: Option<Arc<SlowQueryLog>>,
+	// The queries and transactions which are running
+	activity: Arc<Activity>,
+	// Whether the delivery of the outbox messages is paused
+	outbox_paused: Arc<AtomicBool>,
 }
 
 #[derive(Clone)]
@@ -109,6 +135,10 @@ pub(super) struct TransactionFactory {
 	clock: Arc<SizedClock>,
 	// The inner datastore type
 	flavor: Arc<DatastoreFlavor>,
+	// The number of writes which a bulk statement applies in each batch
+	write_batch_size: usize,
+	// The number of keys which a table or range scan fetches in each batch
+	fetch_size: FetchSize,
 }
 
 impl TransactionFactory {
@@ -116,6 +146,8 @@ impl TransactionFactory {
 		Self {
 			clock,
 			flavor: flavor.into(),
+			write_batch_size: *WRITE_BATCH_SIZE,
+			fetch_size: FetchSize::default(),
 		}
 	}
 
@@ -176,8 +208,11 @@ impl TransactionFactory {
 				inner,
 				stash: super::stash::Stash::default(),
 				cf: cf::Writer::new(),
+				save_points: Vec::new(),
+				writes: WriteBuffer::new(self.write_batch_size),
 			},
-		))
+		)
+		.with_fetch_size(self.fetch_size))
 	}
 }
 
@@ -408,6 +443,11 @@ impl Datastore {
 				cache: Arc::new(DatastoreCache::new()),
 				buckets: Arc::new(DashMap::new()),
 				sequences: Sequences::new(tf),
+				functions: Arc::new(FunctionRegistry::default()),
+				auth_throttle: Arc::new(MemoryAuthThrottle::new()),
+				slow_query_log: None,
+				activity: Arc::new(Activity::default()),
+				outbox_paused: Arc::new(AtomicBool::new(false)),
 			}
 		})
 	}
@@ -433,6 +473,11 @@ impl Datastore {
 			cache: Arc::new(DatastoreCache::new()),
 			buckets: Arc::new(DashMap::new()),
 			sequences: Sequences::new(self.transaction_factory.clone()),
+			functions: self.functions,
+			auth_throttle: self.auth_throttle,
+			slow_query_log: self.slow_query_log,
+			activity: Arc::new(Activity::default()),
+			outbox_paused: self.outbox_paused,
 			transaction_factory: self.transaction_factory,
 		}
 	}
@@ -479,6 +524,36 @@ impl Datastore {
 		self
 	}
 
+	/// Set the hook which counts the authentication attempts.
+	/// The attempts are only throttled when enabled in the capabilities.
+	pub fn with_auth_throttle(mut self, throttle: Arc<dyn AuthThrottle>) -> Self {
+		self.auth_throttle = throttle;
+		self
+	}
+
+	/// Report the statements which take longer than a threshold
+	pub fn with_slow_query_log(mut self, log: Option<SlowQueryLog>) -> Self {
+		self.slow_query_log = log.map(Arc::new);
+		self
+	}
+
+	/// Set the number of writes which a bulk INSERT statement buffers before
+	/// they are applied to the transaction, or 0 to apply every write immediately
+	pub fn with_write_batch_size(mut self, size: usize) -> Self {
+		self.transaction_factory.write_batch_size = size;
+		self
+	}
+
+	/// Set the number of keys which the first batch of a table or range scan
+	/// fetches, and the largest batch which the following batches grow to
+	pub fn with_scan_batch_size(mut self, initial: u32, max: u32) -> Self {
+		self.transaction_factory.fetch_size = FetchSize {
+			initial,
+			max,
+		};
+		self
+	}
+
 	#[cfg(storage)]
 	/// Set a temporary directory for ordering of large result sets
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
@@ -526,6 +601,50 @@ impl Datastore {
 		&self.capabilities
 	}
 
+	/// Register a Rust function which can be called as `fn::<name>` from SurrealQL
+	///
+	/// The arguments are checked and coerced against the specified kinds before
+	/// the function is called, in the same way as with DEFINE FUNCTION. Calling
+	/// the function is subject to the same capabilities as any `fn::` function,
+	/// and a function defined with the same name in the selected database is
+	/// called instead of the registered function.
+	pub fn register_function(
+		&self,
+		name: &str,
+		args: Vec<Kind>,
+		function: impl Fn(&[Value]) -> Result<Value> + Send + Sync + 'static,
+	) -> Result<()> {
+		self.functions.register_function(name, args, Arc::new(function))
+	}
+
+	/// Register an asynchronous Rust function which can be called as `fn::<name>` from SurrealQL
+	pub fn register_async_function(
+		&self,
+		name: &str,
+		args: Vec<Kind>,
+		function: impl Fn(Vec<Value>) -> FunctionFuture + Send + Sync + 'static,
+	) -> Result<()> {
+		self.functions.register_async_function(name, args, Arc::new(function))
+	}
+
+	/// Get the functions registered with this Datastore
+	pub fn get_functions(&self) -> &Arc<FunctionRegistry> {
+		&self.functions
+	}
+
+	pub(crate) fn auth_throttle(&self) -> &Arc<dyn AuthThrottle> {
+		&self.auth_throttle
+	}
+
+	pub(crate) fn slow_query_log(&self) -> Option<&Arc<SlowQueryLog>> {
+		self.slow_query_log.as_ref()
+	}
+
+	/// The channel which the live query notifications are sent to, if enabled
+	pub(crate) fn notification_sender(&self) -> Option<&Sender<Notification>> {
+		self.notification_channel.as_ref().map(|v| &v.0)
+	}
+
 	#[cfg(feature = "jwks")]
 	pub(crate) fn jwks_cache(&self) -> &Arc<RwLock<JwksCache>> {
 		&self.jwks_cache
@@ -776,6 +895,107 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Removes expired records as a background task.
+	///
+	/// This method deletes all records which have expired, from every table
+	/// which has any fields defined with EXPIRE. It uses a distributed task
+	/// lease mechanism to ensure that only one node in a cluster performs
+	/// this maintenance operation at a time.
+	///
+	/// Records which have expired are already hidden from queries, so this
+	/// only ensures that they are eventually removed from the datastore.
+	///
+	/// # Parameters
+	/// * `interval` - Duration specifying how often this task is run
+	///
+	/// # Errors
+	/// * Propagates any errors from the underlying database operations
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn expiry_process(&self, interval: &Duration) -> Result<()> {
+		let lh = LeaseHandler::new(
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::RecordExpiry,
+			*interval * 2,
+		)?;
+		// Attempt to acquire a lease for the RecordExpiry task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Removing expired records");
+		// Remove expired records from all databases
+		self.expiry_cleanup(Some(&lh)).await
+	}
+
+	/// Sends the pending outbox messages as a background task.
+	///
+	/// This method sends the messages which were enqueued with the
+	/// `outbox::enqueue` function, and which are due to be sent, across all
+	/// databases. It uses a distributed task lease mechanism to ensure that
+	/// only one node in a cluster sends the messages at a time. Nothing is
+	/// sent while the delivery is paused with [`Datastore::pause_outbox`].
+	///
+	/// # Parameters
+	/// * `interval` - Duration specifying how often this task is run
+	///
+	/// # Errors
+	/// * Propagates any errors from the underlying database operations
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn outbox_process(&self, interval: &Duration) -> Result<()> {
+		// Check if the delivery is paused
+		if self.is_outbox_paused() {
+			return Ok(());
+		}
+		let lh = LeaseHandler::new(
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::OutboxDelivery,
+			*interval * 2,
+		)?;
+		// Attempt to acquire a lease for the OutboxDelivery task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Sending pending outbox messages");
+		// Send the pending messages of all databases
+		self.outbox_dispatch(Some(&lh)).await
+	}
+
+	/// Runs the schedules which are due as a background task.
+	///
+	/// This method runs the statements of the schedules which were defined
+	/// with `DEFINE SCHEDULE`, and whose cron expression is due, across all
+	/// databases. It uses a distributed task lease mechanism to ensure that
+	/// only one node in a cluster runs the schedules at a time.
+	///
+	/// # Parameters
+	/// * `interval` - Duration specifying how often this task is run
+	///
+	/// # Errors
+	/// * Propagates any errors from the underlying database operations
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn schedule_process(&self, interval: &Duration) -> Result<()> {
+		let lh = LeaseHandler::new(
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::ScheduleRunner,
+			*interval * 2,
+		)?;
+		// Attempt to acquire a lease for the ScheduleRunner task
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running the schedules which are due");
+		// Run the due schedules of all databases
+		self.schedule_dispatch(Some(&lh), chrono::Utc::now()).await
+	}
+
 	/// Performs a database import from SQL
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn startup(&self, sql: &str, sess: &Session) -> Result<Vec<Response>> {
@@ -788,10 +1008,15 @@ impl Datastore {
 	}
 
 	/// Run the datastore shutdown tasks, perfoming any necessary cleanup
+	///
+	/// The running queries are given the grace period to finish, before
+	/// they are cancelled. See [`Datastore::drain`] for the details.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
-	pub async fn shutdown(&self) -> Result<()> {
+	pub async fn shutdown(&self, grace: Duration) -> Result<()> {
 		// Output function invocation details to logs
 		trace!(target: TARGET, "Running datastore shutdown operations");
+		// Wait for the running queries to finish
+		self.drain(grace).await?;
 		// Delete this datastore from the cluster
 		self.delete_node(self.id).await?;
 		// Run any storag engine shutdown tasks
@@ -883,6 +1108,21 @@ impl Datastore {
 			})
 		})?;
 
+		// Check if this is a binary export, which is imported without parsing
+		let mut query = pin!(query);
+		let mut head = BytesMut::new();
+		while head.len() < archive::MAGIC.len() {
+			match query.next().await {
+				Some(v) => head.extend_from_slice(&v?),
+				None => break,
+			}
+		}
+		let is_archive = archive::is_archive(&head);
+		let query = futures::stream::iter([Ok(head.freeze())]).chain(query);
+		if is_archive {
+			return self.import_archive(sess, query).await;
+		}
+
 		// Create a new query options
 		let opt = self.setup_options(sess);
 
@@ -892,9 +1132,116 @@ impl Datastore {
 		sess.context(&mut ctx);
 		// Store the query variables
 		vars.attach(&mut ctx)?;
+		// Cancel the import if the datastore is shutting down
+		let _running = self.start_running(ctx.add_cancel())?;
 		// Process all statements
 
-		let parser_settings = ParserSettings {
+		let stream = import::statements(Self::parser_settings(&ctx), query);
+		let stream = stream.map(|(_, stmt)| stmt);
+
+		Executor::execute_stream(self, Arc::new(ctx), opt, true, stream).await
+	}
+
+	/// Import SurrealQL statements from an async reader
+	///
+	/// Statements are parsed and executed one at a time, so memory use stays
+	/// bounded regardless of the size of the source. Progress is sent to the
+	/// configured channel at most once per interval, and the failures of any
+	/// statements are collected into the returned report, along with the line
+	/// on which each statement began. Unless the import is `strict`, execution
+	/// continues after a failed statement. A statement which can not be parsed
+	/// always ends the import.
+	///
+	/// ```rust,no_run
+	/// use anyhow::Error;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::kvs::import::Config;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(),Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let file = tokio::fs::File::open("backup.surql").await?;
+	///     let report = ds.import_stream(&ses, file, Config::default()).await?;
+	///     for failure in report.failures {
+	///         println!("line {}: {}", failure.line, failure.error);
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_stream<R>(
+		&self,
+		sess: &Session,
+		reader: R,
+		cfg: import::Config,
+	) -> Result<import::Report>
+	where
+		R: AsyncRead,
+	{
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+
+		// Check if anonymous actors can execute queries when auth is enabled
+		// TODO(sgirones): Check this as part of the authorisation layer
+		self.check_anon(sess).map_err(|_| {
+			Error::from(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "process".to_string(),
+				resource: "query".to_string(),
+			})
+		})?;
+
+		// Create a new query options
+		let opt = self.setup_options(sess);
+
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Cancel running statements when the import is aborted
+		ctx.set_canceller(&cfg.abort);
+		// Abort the import if the datastore is shutting down
+		let _running = self.start_running(cfg.abort.clone())?;
+		// Process all statements
+		let stream = import::statements(Self::parser_settings(&ctx), import::read(reader));
+
+		let mut report = import::Report::default();
+		let mut emitted = Instant::now();
+		Executor::execute_import_stream(self, Arc::new(ctx), opt, stream, |start, end, errors| {
+			let failed = !errors.is_empty();
+			report.progress = end.progress;
+			report.failures.extend(errors.into_iter().map(|error| import::Failure {
+				line: start.line,
+				error,
+			}));
+			if let Some(chn) = &cfg.progress {
+				if emitted.elapsed() >= cfg.interval {
+					emitted = Instant::now();
+					// Progress is only informational, so skip an event if the channel is full
+					let _ = chn.try_send(report.progress.clone());
+				}
+			}
+			if cfg.abort.is_cancelled() {
+				report.aborted = true;
+				return false;
+			}
+			!(cfg.strict && failed)
+		})
+		.await?;
+
+		// Always send the final progress of the import
+		if let Some(chn) = &cfg.progress {
+			let _ = chn.send(report.progress.clone()).await;
+		}
+
+		Ok(report)
+	}
+
+	/// The parser settings for statements executed within a context
+	fn parser_settings(ctx: &MutableContext) -> ParserSettings {
+		ParserSettings {
 			references_enabled: ctx
 				.get_capabilities()
 				.allows_experimental(&ExperimentalTarget::RecordReferences),
@@ -906,67 +1253,7 @@ impl Datastore {
 				.allows_experimental(&ExperimentalTarget::DefineApi),
 			files_enabled: ctx.get_capabilities().allows_experimental(&ExperimentalTarget::Files),
 			..Default::default()
-		};
-		let mut statements_stream = StatementStream::new_with_settings(parser_settings);
-		let mut buffer = BytesMut::new();
-		let mut parse_size = 4096;
-		let mut bytes_stream = pin!(query);
-		let mut complete = false;
-		let mut filling = true;
-
-		let stream = futures::stream::poll_fn(move |cx| {
-			loop {
-				// fill the buffer to at least parse_size when filling is required.
-				while filling {
-					let bytes = ready!(bytes_stream.as_mut().poll_next(cx));
-					let bytes = match bytes {
-						Some(Err(e)) => return Poll::Ready(Some(Err(e))),
-						Some(Ok(x)) => x,
-						None => {
-							complete = true;
-							filling = false;
-							break;
-						}
-					};
-
-					buffer.extend_from_slice(&bytes);
-					filling = buffer.len() < parse_size
-				}
-
-				// if we finished streaming we can parse with complete so that the parser can be sure
-				// of it's results.
-				if complete {
-					return match statements_stream.parse_complete(&mut buffer) {
-						Err(e) => {
-							Poll::Ready(Some(Err(anyhow::Error::new(Error::InvalidQuery(e)))))
-						}
-						Ok(None) => Poll::Ready(None),
-						Ok(Some(x)) => Poll::Ready(Some(Ok(x))),
-					};
-				}
-
-				// otherwise try to parse a single statement.
-				match statements_stream.parse_partial(&mut buffer) {
-					Err(e) => {
-						return Poll::Ready(Some(Err(anyhow::Error::new(Error::InvalidQuery(e)))));
-					}
-					Ok(Some(x)) => return Poll::Ready(Some(Ok(x))),
-					Ok(None) => {
-						// Couldn't parse a statement for sure.
-						if buffer.len() >= parse_size && parse_size < u32::MAX as usize {
-							// the buffer already contained more or equal to parse_size bytes
-							// this means we are trying to parse a statement of more then buffer size.
-							// so we need to increase the buffer size.
-							parse_size = (parse_size + 1).next_power_of_two();
-						}
-						// start filling the buffer again.
-						filling = true;
-					}
-				}
-			}
-		});
-
-		Executor::execute_stream(self, Arc::new(ctx), opt, true, stream).await
+		}
 	}
 
 	/// Execute a pre-parsed SQL query
@@ -1012,12 +1299,123 @@ impl Datastore {
 		let mut ctx = self.setup_ctx()?;
 		// Start an execution context
 		sess.context(&mut ctx);
+		// Limit the duration of the query
+		if let Some(timeout) = sess.query_timeout {
+			ctx.add_timeout(timeout)?;
+		}
 		// Store the query variables
 		vars.attach(&mut ctx)?;
+		// Cancel the query if the datastore is shutting down
+		let _running = self.start_running(ctx.add_cancel())?;
 		// Process all statements
 		Executor::execute(self, ctx.freeze(), opt, ast).await
 	}
 
+	/// Parse and execute an SQL query, streaming the results to a channel
+	///
+	/// The rows of each SELECT statement are sent as they are produced, and
+	/// the result of each statement ends with a [`QueryFrame::Done`] frame.
+	/// The channel should be received from while the query runs, as the
+	/// query waits while the channel is full, and is cancelled if the
+	/// receiver is dropped.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn execute_streamed(
+		&self,
+		txt: &str,
+		sess: &Session,
+		vars: Variables,
+		frames: Sender<QueryFrame>,
+	) -> Result<()> {
+		// Parse the SQL query text
+		let ast = syn::parse_with_capabilities(txt, &self.capabilities)?;
+		// Process the AST
+		self.process_streamed(ast, sess, vars, frames).await
+	}
+
+	/// Execute a pre-parsed SQL query, streaming the results to a channel
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_streamed(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		frames: Sender<QueryFrame>,
+	) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Check if anonymous actors can execute queries when auth is enabled
+		self.check_anon(sess).map_err(|_| {
+			Error::from(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "process".to_string(),
+				resource: "query".to_string(),
+			})
+		})?;
+		// Create a new query options
+		let opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Limit the duration of the query
+		if let Some(timeout) = sess.query_timeout {
+			ctx.add_timeout(timeout)?;
+		}
+		// Store the query variables
+		vars.attach(&mut ctx)?;
+		// Cancel the query if the datastore is shutting down
+		let _running = self.start_running(ctx.add_cancel())?;
+		// Process all statements
+		let stream = futures::stream::iter(ast.into_iter().map(Ok));
+		Executor::execute_streamed(self, ctx.freeze(), opt, stream, frames).await
+	}
+
+	/// Execute a pre-parsed SQL query within a transaction of the caller,
+	/// which is left open so that it can be committed or cancelled later.
+	/// The live query notifications are sent to the given channel, so that
+	/// they are only delivered once the transaction is committed. The query
+	/// is cancelled along with the transaction when the datastore drains.
+	pub(crate) async fn process_in_transaction(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		txn: Arc<Transaction>,
+		notifications: Sender<Notification>,
+		canceller: &Canceller,
+	) -> Result<Vec<Response>> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Check if anonymous actors can execute queries when auth is enabled
+		self.check_anon(sess).map_err(|_| {
+			Error::from(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "process".to_string(),
+				resource: "query".to_string(),
+			})
+		})?;
+		// Create a new query options
+		let mut opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Limit the duration of the query
+		if let Some(timeout) = sess.query_timeout {
+			ctx.add_timeout(timeout)?;
+		}
+		// Store the query variables
+		vars.attach(&mut ctx)?;
+		// Hold the notifications until the transaction is committed
+		if ctx.has_notifications() {
+			opt.sender = Some(notifications);
+		}
+		// Cancel the query along with the transaction
+		ctx.set_canceller(canceller);
+		// Process all statements
+		Executor::execute_in_transaction(self, ctx.freeze(), opt, txn, ast).await
+	}
+
 	pub async fn process_plan(
 		&self,
 		plan: LogicalPlan,
@@ -1043,8 +1441,14 @@ impl Datastore {
 		let mut ctx = self.setup_ctx()?;
 		// Start an execution context
 		sess.context(&mut ctx);
+		// Limit the duration of the query
+		if let Some(timeout) = sess.query_timeout {
+			ctx.add_timeout(timeout)?;
+		}
 		// Store the query variables
 		vars.attach(&mut ctx)?;
+		// Cancel the query if the datastore is shutting down
+		let _running = self.start_running(ctx.add_cancel())?;
 
 		// Process all statements
 		Executor::execute_plan(self, ctx.freeze(), opt, plan).await
@@ -1212,6 +1616,137 @@ impl Datastore {
 		self.notification_channel.as_ref().map(|v| v.1.clone())
 	}
 
+	/// Sends the initial state of a live query.
+	///
+	/// The state of each group of a LIVE SELECT query with a GROUP clause
+	/// is computed when the live query is created. This sends a `CREATE`
+	/// notification with the current value of each group. For a LIVE SELECT
+	/// query with a SNAPSHOT clause, this sends a `SNAPSHOT` notification
+	/// for each record which currently matches the query, reading the table
+	/// in batches so that a large snapshot is streamed to the notification
+	/// channel. This should be called once the caller is ready to receive
+	/// notifications for the live query. Other live queries are ignored.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn live_query_snapshot(&self, id: Uuid) -> Result<()> {
+		// Check if notifications are enabled
+		let Some((chn, _)) = &self.notification_channel else {
+			return Ok(());
+		};
+		// Fetch the live query definition
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		let res = match self.live_query_definition(&txn, id).await {
+			// Fetch the current state of each group
+			Ok(Some((lq, stm))) if stm.group.is_some() => {
+				self.live_query_groups(&txn, id, &lq, &stm).await.map(Some)
+			}
+			// Send the records which currently match the query
+			Ok(Some((lq, stm))) if stm.snapshot => {
+				self.live_query_records(txn.clone(), &lq, &stm, chn).await.map(|_| None)
+			}
+			Ok(_) => Ok(None),
+			Err(e) => Err(e),
+		};
+		txn.cancel().await?;
+		// Send a notification for each group
+		for notification in res?.unwrap_or_default() {
+			chn.send(notification).await?;
+		}
+		Ok(())
+	}
+
+	/// Fetches the definition of a live query on this node
+	async fn live_query_definition(
+		&self,
+		txn: &Transaction,
+		id: Uuid,
+	) -> Result<Option<(Live, LiveStatement)>> {
+		// Fetch the live query on this node
+		let key = crate::key::node::lq::new(self.id(), id);
+		let Some(val) = txn.get(key, None).await? else {
+			return Ok(None);
+		};
+		let lq: Live = revision::from_slice(&val)?;
+		// Fetch the live query definition
+		let key = crate::key::table::lq::new(&lq.ns, &lq.db, &lq.tb, id);
+		let Some(val) = txn.get(key, None).await? else {
+			return Ok(None);
+		};
+		let stm: LiveStatement = revision::from_slice(&val)?;
+		Ok(Some((lq, stm)))
+	}
+
+	/// Fetches the current state of each group of an aggregate live query
+	async fn live_query_groups(
+		&self,
+		txn: &Transaction,
+		id: Uuid,
+		lq: &Live,
+		stm: &LiveStatement,
+	) -> Result<Vec<Notification>> {
+		let Some(groups) = &stm.group else {
+			return Ok(Vec::new());
+		};
+		// Fetch the groups, which are stored in one or more shards
+		let beg = crate::key::table::la::prefix(&lq.ns, &lq.db, &lq.tb, id)?;
+		let end = crate::key::table::la::suffix(&lq.ns, &lq.db, &lq.tb, id)?;
+		let mut keys: Vec<Array> = Vec::new();
+		for k in txn.keys(beg..end, u32::MAX, None).await? {
+			let key = crate::key::table::la::La::decode(&k)?;
+			if keys.last() != Some(&key.group) {
+				keys.push(key.group.into_owned());
+			}
+		}
+		// Fetch the state of each group
+		let mut res = Vec::new();
+		for group in keys {
+			let state = stm.aggregate_load(txn, &lq.ns, &lq.db, &lq.tb, &group).await?;
+			let result = stm.aggregate_output(&group, &state)?;
+			res.push(Notification {
+				id: id.into(),
+				action: crate::dbs::Action::Create,
+				record: Value::Array(group),
+				result,
+			});
+		}
+		// A GROUP ALL query always has a single group
+		if groups.is_empty() && res.is_empty() {
+			let group = Array::new();
+			let result = stm.aggregate_output(&group, &stm.aggregate_empty()?)?;
+			res.push(Notification {
+				id: id.into(),
+				action: crate::dbs::Action::Create,
+				record: Value::Array(group),
+				result,
+			});
+		}
+		Ok(res)
+	}
+
+	/// Sends the records which currently match a snapshot live query
+	async fn live_query_records(
+		&self,
+		txn: Arc<Transaction>,
+		lq: &Live,
+		stm: &LiveStatement,
+		chn: &Sender<Notification>,
+	) -> Result<()> {
+		// Create a new memory stack
+		let mut stack = TreeStack::new();
+		// Create the options for the live query
+		let opt = Options::default()
+			.with_id(self.id)
+			.with_ns(Some(lq.ns.as_str().into()))
+			.with_db(Some(lq.db.as_str().into()))
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled);
+		// Create a new context
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(txn);
+		let ctx = ctx.freeze();
+		// Stream the records in the table
+		stack.enter(|stk| stm.snapshot_scan(stk, &ctx, &opt, &lq.tb, chn)).finish().await
+	}
+
 	/// Performs a database import from SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn import(&self, sql: &str, sess: &Session) -> Result<Vec<Response>> {
@@ -1258,16 +1793,92 @@ impl Datastore {
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
 		// Create a new readonly transaction
-		let txn = self.transaction(Read, Optimistic).await?;
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		// Create a context for computing the export conditions
+		let scope = match cfg.conditions.is_empty() {
+			true => None,
+			false => {
+				let mut ctx = self.setup_ctx()?;
+				sess.context(&mut ctx);
+				ctx.set_transaction(txn.clone());
+				Some((ctx.freeze(), self.setup_options(sess)))
+			}
+		};
 		// Return an async export job
 		Ok(async move {
 			// Process the export
-			txn.export(&ns, &db, cfg, chn).await?;
+			let scope = scope.as_ref().map(|(ctx, opt)| (ctx, opt));
+			txn.export_with_scope(&ns, &db, cfg, chn, scope).await?;
 			// Everything ok
 			Ok(())
 		})
 	}
 
+	/// Executes a single statement, and writes its result to a channel as
+	/// Arrow record batches
+	///
+	/// The rows are converted one record batch at a time as the statement
+	/// produces them, so only the rows used to infer the schema and the rows
+	/// of the current batch are held in memory, although statements which
+	/// sort or group their rows still collect them first. When the statement
+	/// selects every field from a single schemafull table, the columns are
+	/// taken from the field definitions of the table instead of being
+	/// inferred. The statement is cancelled if the receiver is dropped.
+	#[cfg(feature = "arrow")]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn query_arrow(
+		&self,
+		txt: &str,
+		sess: &Session,
+		vars: Variables,
+		cfg: crate::arrow::Config,
+		chn: Sender<Vec<u8>>,
+	) -> Result<()> {
+		// Parse the SQL query text
+		let ast = syn::parse_with_capabilities(txt, &self.capabilities)?;
+		ensure!(
+			ast.0.0.len() == 1,
+			Error::Arrow("Only the result of a single statement can be exported".to_owned())
+		);
+		// Check if the rows are records from a single table
+		let table = match &ast.0.0[0] {
+			Statement::Select(stm)
+				if stm.expr.is_all()
+					&& stm.omit.is_none()
+					&& stm.split.is_none()
+					&& stm.group.is_none()
+					&& stm.fetch.is_none() =>
+			{
+				match stm.what.0.as_slice() {
+					[SqlValue::Table(tb)] => Some(tb.0.clone()),
+					_ => None,
+				}
+			}
+			_ => None,
+		};
+		// Use the field definitions of schemafull tables
+		let fields = match (table, crate::iam::check::check_ns_db(sess)) {
+			(Some(tb), Ok((ns, db))) => {
+				let txn = self.transaction(Read, Optimistic).await?;
+				let fields = match txn.get_tb(&ns, &db, &tb).await {
+					Ok(def) if def.full => Some(txn.all_tb_fields(&ns, &db, &tb, None).await),
+					_ => None,
+				};
+				txn.cancel().await?;
+				fields.transpose()?
+			}
+			_ => None,
+		};
+		// Write the rows while the statement produces them
+		let (send, recv) = async_channel::bounded(1);
+		let (res, out) = futures::join!(
+			self.process_streamed(ast, sess, vars, send),
+			crate::arrow::export(recv, fields, cfg, chn)
+		);
+		// The statement is cancelled when the rows can't be written
+		out.and(res)
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<()> {
@@ -1291,6 +1902,10 @@ impl Datastore {
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled)
+			.with_timeout(sess.timeout)
+			.with_readonly(sess.readonly)
+			.with_stats(sess.stats)
+			.with_dry_run(sess.dry_run)
 	}
 	pub fn setup_ctx(&self) -> Result<MutableContext> {
 		let mut ctx = MutableContext::from_ds(
@@ -1304,6 +1919,7 @@ impl Datastore {
 			#[cfg(storage)]
 			self.temporary_directory.clone(),
 			self.buckets.clone(),
+			self.functions.clone(),
 		)?;
 		// Setup the notification channel
 		if let Some(channel) = &self.notification_channel {
@@ -1387,4 +2003,59 @@ mod test {
 		assert_eq!(res, Value::Number(Number::Int(2)));
 		Ok(())
 	}
+
+	#[tokio::test]
+	pub async fn registered_functions() -> Result<()> {
+		use crate::dbs::capabilities::{FuncTarget, Targets};
+		use crate::expr::Part;
+
+		let dbs = Datastore::new("memory").await?.with_capabilities(Capabilities::all());
+		dbs.register_function("tax::vat", vec![Kind::Number], |args| {
+			Ok(Value::from(args[0].clone().coerce_to::<f64>()? * 1.25))
+		})?;
+		dbs.register_async_function("echo", vec![Kind::Option(Box::new(Kind::String))], |args| {
+			Box::pin(async move { Ok(args.into_iter().next().unwrap_or_default()) })
+		})?;
+		// Registering the same name twice fails
+		assert!(dbs.register_function("fn::echo", vec![], |_| Ok(Value::None)).is_err());
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let mut res = dbs
+			.execute(
+				"fn::tax::vat(10); fn::echo('hi'); fn::echo(); fn::tax::vat('ten');",
+				&ses,
+				None,
+			)
+			.await?;
+		assert_eq!(res.remove(0).result?, Value::from(12.5));
+		assert_eq!(res.remove(0).result?, Value::from("hi"));
+		assert_eq!(res.remove(0).result?, Value::None);
+		assert!(res.remove(0).result.is_err());
+		// Functions defined in the database take precedence over registered functions
+		let mut res = dbs
+			.execute(
+				"DEFINE FUNCTION fn::echo($v: string) { RETURN 'defined' }; fn::echo('hi'); REMOVE FUNCTION fn::echo; fn::echo('hi');",
+				&ses,
+				None,
+			)
+			.await?;
+		res.remove(0).result?;
+		assert_eq!(res.remove(0).result?, Value::from("defined"));
+		res.remove(0).result?;
+		assert_eq!(res.remove(0).result?, Value::from("hi"));
+		// Registered functions are listed in the root info
+		let mut res = dbs.execute("INFO FOR ROOT", &ses, None).await?;
+		let info = res.remove(0).result?;
+		assert_eq!(
+			info.pick(&[Part::from("functions"), Part::from("tax::vat")]),
+			Value::from("fn::tax::vat(number)")
+		);
+		// Registered functions are subject to capabilities
+		let dbs = dbs.with_capabilities(
+			Capabilities::all()
+				.without_functions(Targets::Some(["fn::tax::vat".parse::<FuncTarget>()?].into())),
+		);
+		let mut res = dbs.execute("fn::tax::vat(10)", &ses, None).await?;
+		assert!(res.remove(0).result.is_err());
+		Ok(())
+	}
 }
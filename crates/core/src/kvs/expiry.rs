@@ -0,0 +1,71 @@
+use crate::dbs::Session;
+use crate::expr::Value;
+use crate::kvs::Datastore;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{LockType::*, TransactionType::*};
+use anyhow::Result;
+
+impl Datastore {
+	/// Deletes all records which have expired, across all databases.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, lh))]
+	pub(crate) async fn expiry_cleanup(&self, lh: Option<&LeaseHandler>) -> Result<()> {
+		// Store the fields which expire records
+		let mut fields = Vec::new();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Fetch all namespaces
+		let nss = catch!(txn, txn.all_ns().await);
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Get the namespace name
+			let ns = &ns.name;
+			// Fetch all databases
+			let dbs = catch!(txn, txn.all_db(ns).await);
+			// Loop over all databases
+			for db in dbs.iter() {
+				// Get the database name
+				let db = &db.name;
+				// Fetch all tables
+				let tbs = catch!(txn, txn.all_tb(ns, db, None).await);
+				// Loop over all tables
+				for tb in tbs.iter() {
+					// Fetch all fields
+					let fds = catch!(txn, txn.all_tb_fields(ns, db, &tb.name, None).await);
+					// Store the fields which expire records
+					for fd in fds.iter().filter(|fd| fd.expire) {
+						fields.push((
+							ns.to_raw(),
+							db.to_raw(),
+							tb.name.to_raw(),
+							fd.name.to_string(),
+						));
+					}
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Delete the expired records for each field
+		for (ns, db, tb, fd) in fields {
+			// Setup the session for this database
+			let sess = Session::owner().with_ns(&ns).with_db(&db);
+			// Expired records are removed by the DELETE statement,
+			// so that live queries and changefeeds are notified
+			let sql = format!("DELETE type::table($table) WHERE {fd} <= time::now()");
+			let vars = map! { "table".to_string() => Value::from(tb) };
+			for res in self.execute(&sql, &sess, Some(vars)).await? {
+				res.result?;
+			}
+			// Possibly renew the lease
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Everything ok
+		Ok(())
+	}
+}
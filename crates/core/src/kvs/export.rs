@@ -1,18 +1,28 @@
 use super::KeyDecode as _;
 use super::Transaction;
 use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::expr::Cond;
+use crate::expr::FlowResultExt as _;
 use crate::expr::Value;
+use crate::expr::index::Index;
 use crate::expr::paths::EDGE;
 use crate::expr::paths::IN;
 use crate::expr::paths::OUT;
 use crate::expr::statements::DefineTableStatement;
 use crate::key::thing;
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use async_channel::Sender;
 use chrono::TimeZone;
 use chrono::prelude::Utc;
+use reblessive::TreeStack;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -25,6 +35,12 @@ pub struct Config {
 	pub versions: bool,
 	pub records: bool,
 	pub sequences: bool,
+	pub schedules: bool,
+	pub format: ExportFormat,
+	pub compression: bool,
+	pub definitions: bool,
+	pub changefeeds: bool,
+	pub conditions: BTreeMap<String, Cond>,
 }
 
 impl Default for Config {
@@ -39,6 +55,12 @@ impl Default for Config {
 			versions: false,
 			records: true,
 			sequences: true,
+			schedules: true,
+			format: ExportFormat::default(),
+			compression: false,
+			definitions: true,
+			changefeeds: true,
+			conditions: BTreeMap::new(),
 		}
 	}
 }
@@ -73,11 +95,42 @@ impl Config {
 				bool_prop!(analyzers);
 				bool_prop!(versions);
 				bool_prop!(records);
+				bool_prop!(schedules);
+				bool_prop!(compression);
+				bool_prop!(definitions);
+				bool_prop!(changefeeds);
 
 				if let Some(v) = obj.get("tables") {
 					config.tables = v.try_into()?;
 				}
 
+				if let Some(v) = obj.get("format") {
+					config.format = v.try_into()?;
+				}
+
+				match obj.get("conditions") {
+					Some(Value::Object(v)) => {
+						for (table, cond) in v.iter() {
+							match cond {
+								Value::Strand(cond) => config.condition(table, cond)?,
+								v => {
+									return Err(anyhow::Error::new(Error::InvalidExportConfig(
+										v.to_owned(),
+										"a string".into(),
+									)));
+								}
+							}
+						}
+					}
+					Some(v) => {
+						return Err(anyhow::Error::new(Error::InvalidExportConfig(
+							v.to_owned(),
+							"an object".into(),
+						)));
+					}
+					_ => (),
+				}
+
 				Ok(config)
 			}
 			v => Err(anyhow::Error::new(Error::InvalidExportConfig(
@@ -86,6 +139,47 @@ impl Config {
 			))),
 		}
 	}
+
+	/// Only export the records of a table which match a `WHERE` condition.
+	///
+	/// The condition is parsed immediately, so that an invalid condition is
+	/// reported before the export starts.
+	pub fn condition(&mut self, table: &str, cond: &str) -> Result<()> {
+		let cond = crate::syn::value(cond)?;
+		self.conditions.insert(table.to_owned(), Cond(cond.into()));
+		Ok(())
+	}
+
+	/// Check that the config can be used to export a database
+	pub(crate) fn validate(&self) -> Result<()> {
+		for table in self.conditions.keys() {
+			if !self.tables.includes(table) {
+				return Err(anyhow::Error::new(Error::InvalidExportConfig(
+					Value::from(table.as_str()),
+					"a condition for an exported table".into(),
+				)));
+			}
+		}
+		if !self.conditions.is_empty() {
+			ensure!(
+				!self.versions,
+				Error::Unimplemented("Exporting versions with conditions".to_owned())
+			);
+			ensure!(
+				self.format == ExportFormat::Sql,
+				Error::Unimplemented(
+					"Exporting with conditions in the binary export format".to_owned()
+				)
+			);
+		}
+		Ok(())
+	}
+
+	/// Check if we should export the definitions of an exported table
+	pub(crate) fn includes_structure(&self) -> bool {
+		// The records of a table can not be imported without its definition
+		self.definitions || self.records
+	}
 }
 
 impl From<Config> for Value {
@@ -99,6 +193,15 @@ impl From<Config> for Value {
 			"versions" => config.versions.into(),
 			"records" => config.records.into(),
 			"sequences" => config.sequences.into(),
+			"schedules" => config.schedules.into(),
+			"format" => config.format.to_string().into(),
+			"compression" => config.compression.into(),
+			"definitions" => config.definitions.into(),
+			"changefeeds" => config.changefeeds.into(),
+			"conditions" => Value::from(config.conditions
+				.into_iter()
+				.map(|(table, cond)| (table, cond.0.to_string().into()))
+				.collect::<BTreeMap<String, Value>>()),
 			"tables" => match config.tables {
 				TableConfig::All => true.into(),
 				TableConfig::None => false.into(),
@@ -186,6 +289,39 @@ impl TableConfig {
 	}
 }
 
+/// The format in which a database is exported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ExportFormat {
+	/// SurrealQL statements, which are parsed and executed on import
+	#[default]
+	Sql,
+	/// A binary format, which imports records without parsing SurrealQL
+	Binary,
+}
+
+impl fmt::Display for ExportFormat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Sql => f.write_str("sql"),
+			Self::Binary => f.write_str("binary"),
+		}
+	}
+}
+
+impl TryFrom<&Value> for ExportFormat {
+	type Error = anyhow::Error;
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Strand(v) if v.as_str() == "sql" => Ok(ExportFormat::Sql),
+			Value::Strand(v) if v.as_str() == "binary" => Ok(ExportFormat::Binary),
+			v => Err(anyhow::Error::new(Error::InvalidExportConfig(
+				v.to_owned(),
+				"'sql' or 'binary'".into(),
+			))),
+		}
+	}
+}
+
 struct InlineCommentWriter<'a, F>(&'a mut F);
 impl<F: fmt::Write> fmt::Write for InlineCommentWriter<'_, F> {
 	fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -217,6 +353,9 @@ impl<F: fmt::Display> fmt::Display for InlineCommentDisplay<F> {
 	}
 }
 
+/// The context in which the conditions of an export are computed
+pub(crate) type Scope<'a> = Option<(&'a Context, &'a Options)>;
+
 impl Transaction {
 	/// Writes the full database contents as binary SQL.
 	pub async fn export(
@@ -226,14 +365,37 @@ impl Transaction {
 		cfg: Config,
 		chn: Sender<Vec<u8>>,
 	) -> Result<()> {
+		self.export_with_scope(ns, db, cfg, chn, None).await
+	}
+
+	/// Writes the database contents as binary SQL, computing the
+	/// conditions of the export within the provided scope.
+	pub(crate) async fn export_with_scope(
+		&self,
+		ns: &str,
+		db: &str,
+		cfg: Config,
+		chn: Sender<Vec<u8>>,
+		scope: Scope<'_>,
+	) -> Result<()> {
+		// Check the config before exporting anything
+		cfg.validate()?;
+		ensure!(
+			cfg.conditions.is_empty() || scope.is_some(),
+			Error::Unimplemented("Exporting with conditions outside of a datastore".to_owned())
+		);
+		// Check if a binary export was requested
+		if cfg.format == ExportFormat::Binary {
+			return self.export_binary(ns, db, cfg, chn).await;
+		}
 		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
 		self.export_metadata(&cfg, &chn, ns, db).await?;
 		// Output TABLES
-		self.export_tables(ns, db, &cfg, &chn).await?;
+		self.export_tables(ns, db, &cfg, &chn, scope).await?;
 		Ok(())
 	}
 
-	async fn export_metadata(
+	pub(super) async fn export_metadata(
 		&self,
 		cfg: &Config,
 		chn: &Sender<Vec<u8>>,
@@ -243,6 +405,15 @@ impl Transaction {
 		// Output OPTIONS
 		self.export_section("OPTION", vec!["OPTION IMPORT"], chn).await?;
 
+		// Only output the definitions which exported records depend on
+		if !cfg.definitions {
+			if cfg.records {
+				let analyzers = self.referenced_analyzers(cfg, ns, db).await?;
+				self.export_section("ANALYZERS", analyzers, chn).await?;
+			}
+			return Ok(());
+		}
+
 		// Output USERS
 		if cfg.users {
 			let users = self.all_db_users(ns, db).await?;
@@ -279,9 +450,35 @@ impl Transaction {
 			self.export_section("SEQUENCES", sequences.to_vec(), chn).await?;
 		}
 
+		// Output SCHEDULES
+		if cfg.schedules {
+			let schedules = self.all_db_schedules(ns, db).await?;
+			self.export_section("SCHEDULES", schedules.to_vec(), chn).await?;
+		}
+
 		Ok(())
 	}
 
+	/// Fetches the analyzers which are used by the indexes of exported tables
+	async fn referenced_analyzers(&self, cfg: &Config, ns: &str, db: &str) -> Result<Vec<String>> {
+		let mut names = BTreeSet::new();
+		let tables = self.all_tb(ns, db, None).await?;
+		for table in tables.iter().filter(|tb| cfg.tables.includes(&tb.name)) {
+			let indexes = self.all_tb_indexes(ns, db, &table.name).await?;
+			for index in indexes.iter() {
+				if let Index::Search(p) = &index.index {
+					names.insert(p.az.0.clone());
+				}
+			}
+		}
+		let analyzers = self.all_db_analyzers(ns, db).await?;
+		Ok(analyzers
+			.iter()
+			.filter(|az| names.contains(&az.name.0))
+			.map(ToString::to_string)
+			.collect())
+	}
+
 	async fn export_section<T: ToString>(
 		&self,
 		title: &str,
@@ -311,6 +508,7 @@ impl Transaction {
 		db: &str,
 		cfg: &Config,
 		chn: &Sender<Vec<u8>>,
+		scope: Scope<'_>,
 	) -> Result<()> {
 		// Check if tables are included in the export config
 		if !cfg.tables.is_any() {
@@ -318,6 +516,8 @@ impl Transaction {
 		}
 		// Fetch all of the tables for this NS / DB
 		let tables = self.all_tb(ns, db, None).await?;
+		// The tables outside of the export which records link to
+		let mut links = BTreeMap::new();
 		// Loop over all of the tables in order
 		for table in tables.iter() {
 			// Check if this table is included in the export config
@@ -325,28 +525,60 @@ impl Transaction {
 				continue;
 			}
 			// Export the table definition structure first
-			self.export_table_structure(ns, db, table, chn).await?;
+			if cfg.includes_structure() {
+				self.export_table_structure(ns, db, table, cfg, chn).await?;
+			}
 			// Then export the table data if its desired
 			if cfg.records {
-				self.export_table_data(ns, db, table, cfg, chn).await?;
+				let linked = self.export_table_data(ns, db, table, cfg, chn, scope).await?;
+				if !linked.is_empty() {
+					links.insert(table.name.to_raw(), linked);
+				}
+			}
+		}
+		// Record links to tables outside of the export are left as they
+		// are, but are listed so that the missing records can be found
+		if !links.is_empty() {
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!("-- WARNINGS")).await?;
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!("")).await?;
+			for (table, linked) in links {
+				for link in linked {
+					let warning = format!(
+						"Records in table '{table}' link to table '{link}', which was not exported"
+					);
+					warn!("{warning}");
+					chn.send(bytes!(format!("-- {}", InlineCommentDisplay(warning)))).await?;
+				}
 			}
+			chn.send(bytes!("")).await?;
 		}
 
 		Ok(())
 	}
 
-	async fn export_table_structure(
+	pub(super) async fn export_table_structure(
 		&self,
 		ns: &str,
 		db: &str,
 		table: &DefineTableStatement,
+		cfg: &Config,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!(format!("-- TABLE: {}", InlineCommentDisplay(&table.name)))).await?;
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!("")).await?;
-		chn.send(bytes!(format!("{};", table))).await?;
+		if cfg.changefeeds {
+			chn.send(bytes!(format!("{};", table))).await?;
+		} else {
+			let table = DefineTableStatement {
+				changefeed: None,
+				..table.clone()
+			};
+			chn.send(bytes!(format!("{};", table))).await?;
+		}
 		chn.send(bytes!("")).await?;
 		// Export all table field definitions for this table
 		let fields = self.all_tb_fields(ns, db, &table.name, None).await?;
@@ -377,7 +609,8 @@ impl Transaction {
 		table: &DefineTableStatement,
 		cfg: &Config,
 		chn: &Sender<Vec<u8>>,
-	) -> Result<()> {
+		scope: Scope<'_>,
+	) -> Result<BTreeSet<String>> {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!(format!("-- TABLE DATA: {}", InlineCommentDisplay(&table.name)))).await?;
 		chn.send(bytes!("-- ------------------------------")).await?;
@@ -386,6 +619,8 @@ impl Transaction {
 		let beg = crate::key::thing::prefix(ns, db, &table.name)?;
 		let end = crate::key::thing::suffix(ns, db, &table.name)?;
 		let mut next = Some(beg..end);
+		let mut links = BTreeSet::new();
+		let cond = cfg.conditions.get(table.name.as_str()).zip(scope);
 
 		while let Some(rng) = next {
 			if cfg.versions {
@@ -395,7 +630,7 @@ impl Transaction {
 				if batch.result.is_empty() {
 					break;
 				}
-				self.export_versioned_data(batch.result, chn).await?;
+				self.export_versioned_data(batch.result, &cfg.tables, &mut links, chn).await?;
 			} else {
 				let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
 				next = batch.next;
@@ -403,14 +638,48 @@ impl Transaction {
 				if batch.result.is_empty() {
 					break;
 				}
-				self.export_regular_data(batch.result, chn).await?;
+				let values = match cond {
+					Some((cond, (ctx, opt))) => {
+						Self::filter_regular_data(batch.result, cond, ctx, opt).await?
+					}
+					None => batch.result,
+				};
+				self.export_regular_data(values, &cfg.tables, &mut links, chn).await?;
 			}
 			// Fetch more records
 			continue;
 		}
 
 		chn.send(bytes!("")).await?;
-		Ok(())
+		Ok(links)
+	}
+
+	/// Removes the records which don't match the condition of an export.
+	async fn filter_regular_data(
+		regular_values: Vec<(Vec<u8>, Vec<u8>)>,
+		cond: &Cond,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+		let mut stack = TreeStack::new();
+		let mut values = Vec::with_capacity(regular_values.len());
+		for (k, v) in regular_values {
+			let key = thing::Thing::decode(&k)?;
+			let mut doc: Value = revision::from_slice(&v)?;
+			// The condition can refer to the id of the record
+			let rid = Arc::new(crate::expr::Thing::from((key.tb, key.id)));
+			doc.def(&rid);
+			let doc = CursorDoc::new(Some(rid), None, doc);
+			let res = stack
+				.enter(|stk| cond.compute(stk, ctx, opt, Some(&doc)))
+				.finish()
+				.await
+				.catch_return()?;
+			if res.is_truthy() {
+				values.push((k, v));
+			}
+		}
+		Ok(values)
 	}
 
 	/// Processes a value and generates the appropriate SQL command.
@@ -486,6 +755,8 @@ impl Transaction {
 	///
 	/// * `versioned_values` - A vector of tuples containing the versioned values to be exported.
 	///   Each tuple consists of a key, value, version, and a boolean indicating if the record is a tombstone.
+	/// * `tables` - The tables which are included in the export.
+	/// * `links` - The tables outside of the export which records link to.
 	/// * `chn` - A reference to the channel to which the SQL commands will be sent.
 	///
 	/// # Returns
@@ -494,6 +765,8 @@ impl Transaction {
 	async fn export_versioned_data(
 		&self,
 		versioned_values: Vec<(Vec<u8>, Vec<u8>, u64, bool)>,
+		tables: &TableConfig,
+		links: &mut BTreeSet<String>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		// Initialize a vector to hold graph edge records.
@@ -515,6 +788,10 @@ impl Transaction {
 			} else {
 				revision::from_slice(&v)?
 			};
+			// Collect the tables outside of the export which the record links to.
+			if let TableConfig::Some(_) = tables {
+				linked_tables(&v, tables, links);
+			}
 			// Process the value and generate the appropriate SQL command.
 			let sql = Self::process_value(
 				k,
@@ -574,6 +851,8 @@ impl Transaction {
 	///
 	/// * `regular_values` - A vector of tuples containing the regular values to be exported.
 	///   Each tuple consists of a key and a value.
+	/// * `tables` - The tables which are included in the export.
+	/// * `links` - The tables outside of the export which records link to.
 	/// * `chn` - A reference to the channel to which the SQL commands will be sent.
 	///
 	/// # Returns
@@ -582,6 +861,8 @@ impl Transaction {
 	async fn export_regular_data(
 		&self,
 		regular_values: Vec<(Vec<u8>, Vec<u8>)>,
+		tables: &TableConfig,
+		links: &mut BTreeSet<String>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<()> {
 		// Initialize vectors to hold normal records and graph edge records.
@@ -592,6 +873,10 @@ impl Transaction {
 		for (k, v) in regular_values {
 			let k = thing::Thing::decode(&k)?;
 			let v: Value = revision::from_slice(&v)?;
+			// Collect the tables outside of the export which the record links to.
+			if let TableConfig::Some(_) = tables {
+				linked_tables(&v, tables, links);
+			}
 			// Process the value and categorize it into records_relate or records_normal.
 			Self::process_value(k, v, &mut records_relate, &mut records_normal, None, None);
 		}
@@ -613,3 +898,15 @@ impl Transaction {
 		Ok(())
 	}
 }
+
+/// Collects the tables outside of the export which a value links to.
+fn linked_tables(v: &Value, tables: &TableConfig, links: &mut BTreeSet<String>) {
+	match v {
+		Value::Thing(v) if !tables.includes(&v.tb) => {
+			links.insert(v.tb.clone());
+		}
+		Value::Array(v) => v.iter().for_each(|v| linked_tables(v, tables, links)),
+		Value::Object(v) => v.values().for_each(|v| linked_tables(v, tables, links)),
+		_ => (),
+	}
+}
@@ -175,6 +175,10 @@ impl super::api::Transaction for Transaction {
 		false
 	}
 
+	fn supports_transactional_range_delete(&self) -> bool {
+		true
+	}
+
 	/// Behaviour if unclosed
 	fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -0,0 +1,309 @@
+use crate::ctx::Canceller;
+use crate::err::Error;
+use crate::sql::statement::Statement;
+use crate::sql::statements::DefineStatement;
+use crate::sql::{Data, SqlValue};
+use crate::syn::parser::{ParserSettings, StatementStream};
+use anyhow::Result;
+use async_channel::Sender;
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use std::task::{Poll, ready};
+use std::time::Duration;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The number of bytes which are read from the source at a time
+const READ_SIZE: usize = 64 * 1024;
+
+#[derive(Clone)]
+pub struct Config {
+	/// Stop the import after the first statement which fails
+	pub strict: bool,
+	/// The minimum time between two progress events
+	pub interval: Duration,
+	/// The channel which progress events are sent to
+	pub progress: Option<Sender<Progress>>,
+	/// Aborts the import once cancelled
+	pub abort: Canceller,
+}
+
+impl Default for Config {
+	fn default() -> Config {
+		Config {
+			strict: false,
+			interval: Duration::from_secs(1),
+			progress: None,
+			abort: Canceller::default(),
+		}
+	}
+}
+
+/// The progress of an import
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Progress {
+	/// The number of bytes consumed from the source
+	pub bytes: u64,
+	/// The number of statements executed
+	pub statements: u64,
+	/// The table which was most recently defined or inserted into
+	pub table: Option<String>,
+}
+
+/// A statement which failed during an import
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Failure {
+	/// The line, starting at 1, on which the statement began. Errors from
+	/// within a transaction are reported on the line of its BEGIN statement.
+	pub line: usize,
+	/// The error returned by the statement
+	pub error: anyhow::Error,
+}
+
+/// The outcome of an import
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct Report {
+	/// The progress of the import when it finished
+	pub progress: Progress,
+	/// The statements which failed, in the order they were executed
+	pub failures: Vec<Failure>,
+	/// Whether the import was aborted before reaching the end of the source
+	pub aborted: bool,
+}
+
+impl Report {
+	/// Whether every statement in the source was executed without errors
+	pub fn is_ok(&self) -> bool {
+		self.failures.is_empty() && !self.aborted
+	}
+}
+
+/// The position of a statement within the imported source
+#[derive(Clone, Debug)]
+pub(super) struct Position {
+	/// The line on which the statement began
+	pub line: usize,
+	/// The progress of the import once the statement has been executed
+	pub progress: Progress,
+}
+
+/// Converts an async reader into a stream of bytes.
+pub(super) fn read<R>(reader: R) -> impl Stream<Item = Result<Bytes>>
+where
+	R: AsyncRead,
+{
+	let mut reader = Box::pin(reader);
+	let mut chunk = vec![0; READ_SIZE];
+	futures::stream::poll_fn(move |cx| {
+		let mut buf = ReadBuf::new(&mut chunk);
+		match ready!(reader.as_mut().poll_read(cx, &mut buf)) {
+			Err(e) => Poll::Ready(Some(Err(anyhow::Error::new(Error::Io(e))))),
+			Ok(()) if buf.filled().is_empty() => Poll::Ready(None),
+			Ok(()) => Poll::Ready(Some(Ok(Bytes::copy_from_slice(buf.filled())))),
+		}
+	})
+}
+
+/// Parses a stream of bytes into a stream of statements, along with the
+/// position of each statement within the source.
+///
+/// Only a single statement is buffered at a time, so memory use is bounded by
+/// the size of the largest statement rather than by the size of the source.
+pub(super) fn statements<S>(
+	settings: ParserSettings,
+	query: S,
+) -> impl Stream<Item = (Position, Result<Statement>)>
+where
+	S: Stream<Item = Result<Bytes>>,
+{
+	let mut statements_stream = StatementStream::new_with_settings(settings);
+	let mut buffer = BytesMut::new();
+	let mut parse_size = 4096;
+	let mut bytes_stream = Box::pin(query);
+	let mut complete = false;
+	let mut filling = true;
+	let mut read = 0u64;
+	let mut progress = Progress::default();
+
+	futures::stream::poll_fn(move |cx| {
+		loop {
+			// fill the buffer to at least parse_size when filling is required.
+			while filling {
+				let bytes = ready!(bytes_stream.as_mut().poll_next(cx));
+				let bytes = match bytes {
+					Some(Err(e)) => {
+						let pos = Position {
+							line: statements_stream.statement_line(),
+							progress: progress.clone(),
+						};
+						return Poll::Ready(Some((pos, Err(e))));
+					}
+					Some(Ok(x)) => x,
+					None => {
+						complete = true;
+						filling = false;
+						break;
+					}
+				};
+
+				read += bytes.len() as u64;
+				buffer.extend_from_slice(&bytes);
+				filling = buffer.len() < parse_size
+			}
+
+			// if we finished streaming we can parse with complete so that the parser can be sure
+			// of it's results.
+			let res = if complete {
+				match statements_stream.parse_complete(&mut buffer) {
+					Ok(None) => return Poll::Ready(None),
+					res => res,
+				}
+			} else {
+				// otherwise try to parse a single statement.
+				match statements_stream.parse_partial(&mut buffer) {
+					Ok(None) => {
+						// Couldn't parse a statement for sure.
+						if buffer.len() >= parse_size && parse_size < u32::MAX as usize {
+							// the buffer already contained more or equal to parse_size bytes
+							// this means we are trying to parse a statement of more then buffer size.
+							// so we need to increase the buffer size.
+							parse_size = (parse_size + 1).next_power_of_two();
+						}
+						// start filling the buffer again.
+						filling = true;
+						continue;
+					}
+					res => res,
+				}
+			};
+
+			let res = match res {
+				Ok(Some(x)) => {
+					progress.statements += 1;
+					if let Some(table) = table(&x) {
+						progress.table = Some(table);
+					}
+					Ok(x)
+				}
+				Ok(None) => unreachable!("empty parse results are handled above"),
+				Err(e) => Err(anyhow::Error::new(Error::InvalidQuery(e))),
+			};
+			progress.bytes = read - buffer.len() as u64;
+
+			let pos = Position {
+				line: statements_stream.statement_line(),
+				progress: progress.clone(),
+			};
+			return Poll::Ready(Some((pos, res)));
+		}
+	})
+}
+
+/// Returns the table which a statement defines or inserts records into.
+fn table(stmt: &Statement) -> Option<String> {
+	match stmt {
+		Statement::Define(DefineStatement::Table(stmt)) => Some(stmt.name.0.clone()),
+		Statement::Insert(stmt) => match (&stmt.into, &stmt.data) {
+			(Some(SqlValue::Table(tb)), _) => Some(tb.0.clone()),
+			// Exported records are inserted without a target table,
+			// so use the table of the first record instead.
+			(None, Data::SingleExpression(SqlValue::Array(records))) => match records.0.first() {
+				Some(SqlValue::Object(record)) => match record.0.get("id") {
+					Some(SqlValue::Thing(id)) => Some(id.tb.clone()),
+					_ => None,
+				},
+				_ => None,
+			},
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+
+	const SOURCE: &str = "DEFINE TABLE person SCHEMAFULL;
+DEFINE FIELD name ON person TYPE string;
+INSERT [ { id: person:one, name: 'one' } ];
+INSERT [ { id: person:two, name: 2 } ];
+
+CREATE person:three SET name = 3;
+INSERT INTO animal { id: animal:one };
+";
+
+	async fn import(cfg: Config) -> (Datastore, Report) {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let report = ds.import_stream(&ses, SOURCE.as_bytes(), cfg).await.unwrap();
+		(ds, report)
+	}
+
+	#[tokio::test]
+	async fn collects_failures() {
+		let (chn, recv) = async_channel::unbounded();
+		let cfg = Config {
+			progress: Some(chn),
+			..Default::default()
+		};
+		let (ds, report) = import(cfg).await;
+		assert!(!report.is_ok());
+		assert!(!report.aborted);
+		// Both failing statements are reported on the line they began
+		let lines: Vec<_> = report.failures.iter().map(|f| f.line).collect();
+		assert_eq!(lines, vec![4, 6]);
+		assert_eq!(report.progress.statements, 6);
+		assert_eq!(report.progress.bytes, SOURCE.trim_end().len() as u64);
+		assert_eq!(report.progress.table.as_deref(), Some("animal"));
+		// The final progress is always sent
+		let mut last = None;
+		while let Ok(progress) = recv.try_recv() {
+			last = Some(progress);
+		}
+		assert_eq!(last.as_ref(), Some(&report.progress));
+		// Statements after the failures were still executed
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let mut res = ds.execute("SELECT VALUE id FROM animal", &ses, None).await.unwrap();
+		let ids = res.remove(0).result.unwrap();
+		assert_eq!(ids.to_string(), "[animal:one]");
+	}
+
+	#[tokio::test]
+	async fn stops_when_strict() {
+		let cfg = Config {
+			strict: true,
+			..Default::default()
+		};
+		let (_, report) = import(cfg).await;
+		assert_eq!(report.failures.len(), 1);
+		assert_eq!(report.failures[0].line, 4);
+		assert_eq!(report.progress.statements, 4);
+		assert_eq!(report.progress.table.as_deref(), Some("person"));
+		assert!(!report.aborted);
+	}
+
+	#[tokio::test]
+	async fn aborts() {
+		let cfg = Config::default();
+		cfg.abort.cancel();
+		let (_, report) = import(cfg).await;
+		assert!(report.aborted);
+		assert_eq!(report.progress.statements, 1);
+	}
+
+	#[tokio::test]
+	async fn stops_at_parse_errors() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let source = "CREATE person:one;\n\nCREATE person:two SET;\nCREATE person:three;\n";
+		let report = ds.import_stream(&ses, source.as_bytes(), Config::default()).await.unwrap();
+		assert_eq!(report.failures.len(), 1);
+		assert_eq!(report.failures[0].line, 3);
+		assert_eq!(report.progress.statements, 1);
+	}
+}
@@ -4,8 +4,8 @@ use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
-use crate::expr::statements::DefineIndexStatement;
-use crate::expr::{Id, Object, Thing, Value};
+use crate::expr::statements::{DefineIndexStatement, DefineTableStatement};
+use crate::expr::{Datetime, Id, Object, Thing, Value};
 use crate::idx::index::IndexOperation;
 use crate::key::index::ia::Ia;
 use crate::key::index::ip::Ip;
@@ -14,7 +14,7 @@ use crate::kvs::LockType::Optimistic;
 use crate::kvs::ds::TransactionFactory;
 use crate::kvs::{Key, Transaction, TransactionType, Val};
 use crate::mem::ALLOC;
-use anyhow::{Result, ensure};
+use anyhow::{Result, bail, ensure};
 use dashmap::DashMap;
 use dashmap::mapref::entry::Entry;
 use futures::channel::oneshot::{Receiver, Sender, channel};
@@ -22,11 +22,12 @@ use reblessive::TreeStack;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::ops::Range;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use tokio::task;
 use tokio::task::JoinHandle;
+use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub(crate) enum BuildingStatus {
@@ -36,6 +37,12 @@ pub(crate) enum BuildingStatus {
 		initial: Option<usize>,
 		updated: Option<usize>,
 		pending: Option<usize>,
+		/// An estimate of the number of records to index initially
+		total: Option<usize>,
+		/// The number of batches which have been indexed
+		batch: usize,
+		/// When the build was started
+		started: Datetime,
 	},
 	Ready {
 		initial: Option<usize>,
@@ -58,6 +65,8 @@ impl Default for BuildingStatus {
 pub(crate) enum ConsumeResult {
 	/// The document has been enqueued to be indexed
 	Enqueued,
+	/// The build has been cancelled, the document is indexed when the index is rebuilt
+	Skipped,
 	/// The index has been built, the document can be indexed normally
 	Ignored(Option<Vec<Value>>, Option<Vec<Value>>),
 }
@@ -67,9 +76,13 @@ impl BuildingStatus {
 		matches!(self, Self::Error(_))
 	}
 
-	fn is_ready(&self) -> bool {
+	pub(crate) fn is_ready(&self) -> bool {
 		matches!(self, Self::Ready { .. })
 	}
+
+	fn is_aborted(&self) -> bool {
+		matches!(self, Self::Aborted)
+	}
 }
 
 impl From<BuildingStatus> for Value {
@@ -82,6 +95,9 @@ impl From<BuildingStatus> for Value {
 				initial,
 				pending,
 				updated,
+				total,
+				batch,
+				started,
 			} => {
 				if let Some(c) = initial {
 					o.insert("initial".to_string(), c.into());
@@ -92,6 +108,11 @@ impl From<BuildingStatus> for Value {
 				if let Some(c) = updated {
 					o.insert("updated".to_string(), c.into());
 				}
+				if let Some(c) = total {
+					o.insert("total".to_string(), c.into());
+				}
+				o.insert("batch".to_string(), batch.into());
+				o.insert("started".to_string(), started.into());
 				"indexing"
 			}
 			BuildingStatus::Ready {
@@ -146,6 +167,9 @@ impl IndexKey {
 pub(crate) struct IndexBuilder {
 	tf: TransactionFactory,
 	indexes: Arc<DashMap<IndexKey, IndexBuilding>>,
+	/// The concurrent rebuilds, which build the next generation of an index
+	/// while the current generation is still used by queries
+	rebuilds: Arc<DashMap<IndexKey, IndexBuilding>>,
 }
 
 impl IndexBuilder {
@@ -153,6 +177,7 @@ impl IndexBuilder {
 		Self {
 			tf,
 			indexes: Default::default(),
+			rebuilds: Default::default(),
 		}
 	}
 
@@ -161,9 +186,10 @@ impl IndexBuilder {
 		ctx: &Context,
 		opt: Options,
 		ix: Arc<DefineIndexStatement>,
+		rebuild: bool,
 		sdr: Option<Sender<Result<()>>>,
 	) -> Result<IndexBuilding> {
-		let building = Arc::new(Building::new(ctx, self.tf.clone(), opt, ix)?);
+		let building = Arc::new(Building::new(ctx, self.tf.clone(), opt, ix, rebuild)?);
 		let b = building.clone();
 		let jh = task::spawn(async move {
 			let r = b.run().await;
@@ -194,7 +220,66 @@ impl IndexBuilder {
 		} else {
 			(None, None)
 		};
-		match self.indexes.entry(key) {
+		// If the index is currently rebuilt, we return error
+		if let Some(r) = self.rebuilds.get(&key) {
+			ensure!(
+				r.1.is_finished(),
+				Error::IndexAlreadyBuilding {
+					name: ix.name.to_raw(),
+				}
+			);
+		}
+		Self::start_entry(&self.indexes, key, || self.start_building(ctx, opt, ix, false, sdr))?;
+		Ok(rcv)
+	}
+
+	/// Rebuilds the structure of an index in the background, under the next generation.
+	/// The current generation is still used by queries, and updated by writes,
+	/// until the new generation is complete and atomically replaces it.
+	pub(crate) async fn rebuild(
+		&self,
+		ctx: &Context,
+		opt: Options,
+		ix: &DefineIndexStatement,
+	) -> Result<()> {
+		let (ns, db) = opt.ns_db()?;
+		let key = IndexKey::new(ns, db, &ix.what, &ix.name);
+		// The current generation must be complete to be used during the rebuild
+		ensure!(
+			!ix.cancelled,
+			Error::IndexNotRebuildable {
+				name: ix.name.to_raw(),
+				reason: "the index has not been built".to_string(),
+			}
+		);
+		let current = self.indexes.get(&key).map(|r| (r.0.clone(), r.1.is_finished()));
+		if let Some((b, finished)) = current {
+			ensure!(
+				finished,
+				Error::IndexAlreadyBuilding {
+					name: ix.name.to_raw(),
+				}
+			);
+			ensure!(
+				b.status.read().await.is_ready(),
+				Error::IndexNotRebuildable {
+					name: ix.name.to_raw(),
+					reason: "the index has not been built".to_string(),
+				}
+			);
+		}
+		let ix = Arc::new(DefineIndexStatement {
+			generation: ix.generation + 1,
+			..ix.clone()
+		});
+		Self::start_entry(&self.rebuilds, key, || self.start_building(ctx, opt, ix, true, None))
+	}
+
+	fn start_entry<F>(map: &DashMap<IndexKey, IndexBuilding>, key: IndexKey, start: F) -> Result<()>
+	where
+		F: FnOnce() -> Result<IndexBuilding>,
+	{
+		match map.entry(key) {
 			Entry::Occupied(e) => {
 				// If the building is currently running, we return error
 				ensure!(
@@ -203,16 +288,14 @@ impl IndexBuilder {
 						name: e.key().ix.clone(),
 					}
 				);
-				let ib = self.start_building(ctx, opt, ix, sdr)?;
-				e.replace_entry(ib);
+				e.replace_entry(start()?);
 			}
 			Entry::Vacant(e) => {
 				// No index is currently building, we can start building it
-				let ib = self.start_building(ctx, opt, ix, sdr)?;
-				e.insert(ib);
+				e.insert(start()?);
 			}
 		};
-		Ok(rcv)
+		Ok(())
 	}
 
 	pub(crate) async fn consume(
@@ -225,6 +308,16 @@ impl IndexBuilder {
 		rid: &Thing,
 	) -> Result<ConsumeResult> {
 		let key = IndexKey::new(ns, db, &ix.what, &ix.name);
+		// A rebuild keeps a copy of the values, which are still indexed in the current generation
+		let (old_values, new_values) = if let Some(r) = self.rebuilds.get(&key) {
+			let (b, _) = r.value();
+			match b.maybe_consume(ctx, old_values, new_values, rid).await? {
+				ConsumeResult::Ignored(o, n) => (o, n),
+				res => return Ok(res),
+			}
+		} else {
+			(old_values, new_values)
+		};
 		if let Some(r) = self.indexes.get(&key) {
 			let (b, _) = r.value();
 			return b.maybe_consume(ctx, old_values, new_values, rid).await;
@@ -241,16 +334,80 @@ impl IndexBuilder {
 		let key = IndexKey::new(ns, db, &ix.what, &ix.name);
 		if let Some(a) = self.indexes.get(&key) {
 			a.value().0.status.read().await.clone()
+		} else if ix.cancelled {
+			// The build was cancelled before this node was started
+			BuildingStatus::Aborted
 		} else {
 			BuildingStatus::default()
 		}
 	}
 
+	/// The status of the last concurrent rebuild of an index, if any
+	pub(crate) async fn get_rebuild_status(
+		&self,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Option<BuildingStatus> {
+		let key = IndexKey::new(ns, db, &ix.what, &ix.name);
+		let b = self.rebuilds.get(&key)?.value().0.clone();
+		let status = b.status.read().await.clone();
+		Some(status)
+	}
+
+	/// Stops the building of an index, and removes the entries which have already been indexed.
+	/// The index is not used by queries, and not updated by writes, until it is rebuilt.
+	/// Cancelling a concurrent rebuild only removes the next generation, the current one is kept.
+	/// Returns true if the index itself is left incomplete, rather than its next generation.
+	pub(crate) async fn cancel(
+		&self,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<bool> {
+		let key = IndexKey::new(ns, db, &ix.what, &ix.name);
+		let rebuild = self.rebuilds.get(&key).is_some_and(|r| !r.1.is_finished());
+		let indexes = if rebuild {
+			&self.rebuilds
+		} else {
+			&self.indexes
+		};
+		let Some((key, (b, mut jh))) = indexes.remove(&key) else {
+			bail!(Error::IndexNotBuilding {
+				name: ix.name.to_raw(),
+			});
+		};
+		if b.status.read().await.is_ready() {
+			indexes.insert(key, (b, jh));
+			bail!(Error::IndexNotBuilding {
+				name: ix.name.to_raw(),
+			});
+		}
+		// Stop the building, and wait for the current batch to be committed
+		b.abort();
+		if let Err(e) = (&mut jh).await {
+			warn!("The index building task failed: {e}");
+		}
+		// The building may have completed in the meantime
+		if b.status.read().await.is_ready() {
+			indexes.insert(key, (b, jh));
+			bail!(Error::IndexNotBuilding {
+				name: ix.name.to_raw(),
+			});
+		}
+		let res = b.cancel().await;
+		indexes.insert(key, (b, jh));
+		res.map(|_| !rebuild)
+	}
+
 	pub(crate) fn remove_index(&self, ns: &str, db: &str, tb: &str, ix: &str) -> Result<()> {
 		let key = IndexKey::new(ns, db, tb, ix);
 		if let Some((_, b)) = self.indexes.remove(&key) {
 			b.0.abort();
 		}
+		if let Some((_, b)) = self.rebuilds.remove(&key) {
+			b.0.abort();
+		}
 		Ok(())
 	}
 }
@@ -314,9 +471,16 @@ struct Building {
 	tf: TransactionFactory,
 	ix: Arc<DefineIndexStatement>,
 	tb: String,
+	/// The name under which the data of the index is stored
+	storage: String,
+	/// Whether this build replaces the current generation of the index once complete
+	rebuild: bool,
 	status: Arc<RwLock<BuildingStatus>>,
 	queue: Arc<RwLock<QueueSequences>>,
 	aborted: AtomicBool,
+	started: Datetime,
+	total: OnceLock<usize>,
+	batch: AtomicUsize,
 }
 
 impl Building {
@@ -325,16 +489,22 @@ impl Building {
 		tf: TransactionFactory,
 		opt: Options,
 		ix: Arc<DefineIndexStatement>,
+		rebuild: bool,
 	) -> Result<Self> {
 		Ok(Self {
 			ctx: MutableContext::new_concurrent(ctx).freeze(),
 			opt,
 			tf,
 			tb: ix.what.to_raw(),
+			storage: ix.storage_name(),
+			rebuild,
 			ix,
 			status: Arc::new(RwLock::new(BuildingStatus::Started)),
 			queue: Default::default(),
 			aborted: AtomicBool::new(false),
+			started: Datetime::default(),
+			total: OnceLock::new(),
+			batch: AtomicUsize::new(0),
 		})
 	}
 
@@ -346,6 +516,23 @@ impl Building {
 		}
 	}
 
+	async fn set_indexing_status(
+		&self,
+		initial: Option<usize>,
+		pending: Option<usize>,
+		updated: Option<usize>,
+	) {
+		self.set_status(BuildingStatus::Indexing {
+			initial,
+			pending,
+			updated,
+			total: self.total.get().copied(),
+			batch: self.batch.load(Ordering::Relaxed),
+			started: self.started.clone(),
+		})
+		.await;
+	}
+
 	async fn maybe_consume(
 		&self,
 		ctx: &Context,
@@ -356,11 +543,20 @@ impl Building {
 		let mut queue = self.queue.write().await;
 		// Now that the queue is locked, we have the possibility to assess if the asynchronous build is done.
 		if queue.is_empty() {
+			let status = self.status.read().await;
 			// If the appending queue is empty and the index is built...
-			if self.status.read().await.is_ready() {
+			if status.is_ready() {
 				// ... we return the values back, so the document can be updated the usual way
 				return Ok(ConsumeResult::Ignored(old_values, new_values));
 			}
+			// If the build has been cancelled, the document will be indexed by the next build
+			if status.is_aborted() {
+				// ... unless this is a rebuild, in which case the current generation is still in use
+				if self.rebuild {
+					return Ok(ConsumeResult::Ignored(old_values, new_values));
+				}
+				return Ok(ConsumeResult::Skipped);
+			}
 		}
 
 		let tx = ctx.tx();
@@ -381,17 +577,21 @@ impl Building {
 			tx.set(ip, revision::to_vec(&PrimaryAppending(idx))?, None).await?;
 		}
 		drop(queue);
+		// During a rebuild, the document is also indexed in the current generation
+		if self.rebuild {
+			return Ok(ConsumeResult::Ignored(a.old_values, a.new_values));
+		}
 		Ok(ConsumeResult::Enqueued)
 	}
 
 	fn new_ia_key(&self, i: u32) -> Result<Ia> {
 		let (ns, db) = self.opt.ns_db()?;
-		Ok(Ia::new(ns, db, &self.ix.what, &self.ix.name, i))
+		Ok(Ia::new(ns, db, &self.ix.what, &self.storage, i))
 	}
 
 	fn new_ip_key(&self, id: Id) -> Result<Ip> {
 		let (ns, db) = self.opt.ns_db()?;
-		Ok(Ip::new(ns, db, &self.ix.what, &self.ix.name, id))
+		Ok(Ip::new(ns, db, &self.ix.what, &self.storage, id))
 	}
 
 	async fn new_read_tx(&self) -> Result<Transaction> {
@@ -405,28 +605,102 @@ impl Building {
 		Ok(ctx.freeze())
 	}
 
+	/// Remove the index data
+	async fn clean(&self) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		self.set_status(BuildingStatus::Cleaning).await;
+		let ctx = self.new_write_tx_ctx().await?;
+		let key = crate::key::index::all::new(ns, db, &self.tb, &self.storage);
+		let tx = ctx.tx();
+		tx.delp(key).await?;
+		tx.commit().await?;
+		// A previous rebuild may have left in-memory structures for this generation
+		if self.rebuild {
+			self.ctx.get_index_stores().index_released(ns, db, &self.ix).await?;
+		}
+		Ok(())
+	}
+
+	/// Replace the current generation of the index with the one which has just been built
+	async fn swap(&self) -> Result<()> {
+		let (ns, db) = self.opt.ns_db()?;
+		let ctx = self.new_write_tx_ctx().await?;
+		let tx = ctx.tx();
+		let old = catch!(tx, tx.get_tb_index(ns, db, &self.tb, &self.ix.name).await);
+		catch!(tx, self.replace_generation(&tx, ns, db, &old).await);
+		tx.commit().await?;
+		// Clear the cache
+		if let Some(cache) = self.ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.tb);
+		}
+		// Release the in-memory structures of the previous generation
+		self.ctx.get_index_stores().index_released(ns, db, &old).await
+	}
+
+	async fn replace_generation(
+		&self,
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		old: &DefineIndexStatement,
+	) -> Result<()> {
+		// Store the definition with the new generation
+		let key = crate::key::table::ix::new(ns, db, &self.tb, &self.ix.name);
+		let ix = DefineIndexStatement {
+			generation: self.ix.generation,
+			..old.clone()
+		};
+		tx.set(key, revision::to_vec(&ix)?, None).await?;
+		// Remove the data of the previous generation
+		let name = old.storage_name();
+		let key = crate::key::index::all::new(ns, db, &self.tb, &name);
+		tx.delp(key).await?;
+		// Refresh the table cache for indexes
+		let key = crate::key::database::tb::new(ns, db, &self.tb);
+		let tb = tx.get_tb(ns, db, &self.tb).await?;
+		tx.set(
+			key,
+			revision::to_vec(&DefineTableStatement {
+				cache_indexes_ts: Uuid::now_v7(),
+				..tb.as_ref().clone()
+			})?,
+			None,
+		)
+		.await?;
+		Ok(())
+	}
+
+	/// Remove the index data once the building has been stopped
+	async fn cancel(&self) -> Result<()> {
+		let mut queue = self.queue.write().await;
+		self.clean().await?;
+		queue.clear();
+		self.set_status(BuildingStatus::Aborted).await;
+		Ok(())
+	}
+
 	async fn run(&self) -> Result<()> {
 		let (ns, db) = self.opt.ns_db()?;
 		// Remove the index data
-		{
-			self.set_status(BuildingStatus::Cleaning).await;
-			let ctx = self.new_write_tx_ctx().await?;
-			let key = crate::key::index::all::new(ns, db, &self.tb, &self.ix.name);
-			let tx = ctx.tx();
-			tx.delp(key).await?;
-			tx.commit().await?;
-		}
+		self.clean().await?;
 		// First iteration, we index every key
 		let beg = thing::prefix(ns, db, &self.tb)?;
 		let end = thing::suffix(ns, db, &self.tb)?;
+		// Estimate the number of records to index
+		{
+			let tx = self.new_read_tx().await?;
+			let total = catch!(tx, tx.count(beg.clone()..end.clone()).await);
+			tx.cancel().await?;
+			let _ = self.total.set(total);
+		}
 		let mut next = Some(beg..end);
 		let mut initial_count = 0;
 		// Set the initial status
-		self.set_status(BuildingStatus::Indexing {
-			initial: Some(initial_count),
-			pending: Some(self.queue.read().await.pending() as usize),
-			updated: None,
-		})
+		self.set_indexing_status(
+			Some(initial_count),
+			Some(self.queue.read().await.pending() as usize),
+			None,
+		)
 		.await;
 		while let Some(rng) = next {
 			if self.is_aborted().await {
@@ -456,13 +730,14 @@ impl Building {
 				);
 				tx.commit().await?;
 			}
+			self.batch.fetch_add(1, Ordering::Relaxed);
 		}
 		// Second iteration, we index/remove any records that has been added or removed since the initial indexing
-		self.set_status(BuildingStatus::Indexing {
-			initial: Some(initial_count),
-			pending: Some(self.queue.read().await.pending() as usize),
-			updated: Some(0),
-		})
+		self.set_indexing_status(
+			Some(initial_count),
+			Some(self.queue.read().await.pending() as usize),
+			Some(0),
+		)
 		.await;
 		let mut updates_count = 0;
 		let mut next_to_index = None;
@@ -479,6 +754,9 @@ impl Building {
 				if queue.is_empty() {
 					// If the batch is empty, we are done.
 					// Due to the lock on self.queue, we know that no external process can add an item to the queue.
+					if self.rebuild {
+						self.swap().await?;
+					}
 					self.set_status(BuildingStatus::Ready {
 						initial: Some(initial_count),
 						pending: Some(queue.pending() as usize),
@@ -506,6 +784,7 @@ impl Building {
 				);
 				tx.commit().await?;
 			}
+			self.batch.fetch_add(1, Ordering::Relaxed);
 		}
 		Ok(())
 	}
@@ -559,11 +838,11 @@ impl Building {
 
 			// Increment the count and update the status
 			*count += 1;
-			self.set_status(BuildingStatus::Indexing {
-				initial: Some(*count),
-				pending: Some(self.queue.read().await.pending() as usize),
-				updated: None,
-			})
+			self.set_indexing_status(
+				Some(*count),
+				Some(self.queue.read().await.pending() as usize),
+				None,
+			)
 			.await;
 		}
 		Ok(())
@@ -597,11 +876,11 @@ impl Building {
 				tx.del(ip).await?;
 
 				*count += 1;
-				self.set_status(BuildingStatus::Indexing {
-					initial: Some(initial),
-					pending: Some(self.queue.read().await.pending() as usize),
-					updated: Some(*count),
-				})
+				self.set_indexing_status(
+					Some(initial),
+					Some(self.queue.read().await.pending() as usize),
+					Some(*count),
+				)
 				.await;
 			}
 		}
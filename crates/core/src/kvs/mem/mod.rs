@@ -120,6 +120,10 @@ impl super::api::Transaction for Transaction {
 		false
 	}
 
+	fn supports_transactional_range_delete(&self) -> bool {
+		true
+	}
+
 	/// Behaviour if unclosed
 	fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -498,4 +502,8 @@ impl super::api::Transaction for Transaction {
 	fn release_last_save_point(&mut self) -> Result<()> {
 		Ok(())
 	}
+
+	fn releases_save_points(&self) -> bool {
+		false
+	}
 }
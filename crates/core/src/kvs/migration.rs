@@ -0,0 +1,382 @@
+//! Schema migrations of a database.
+//!
+//! A migration has an id, an `up` query which applies it, and an optional
+//! `down` query which reverts it. The migrations which were applied to a
+//! database are recorded in the database, along with the position at which
+//! they were applied, and a checksum of their parsed `up` query, so that
+//! changes to the whitespace or the comments of a migration are ignored, but
+//! changes to its statements are detected.
+//!
+//! Each migration is applied in its own transaction, which also records the
+//! migration, so a migration which fails leaves no changes behind. Statements
+//! which the storage engine can not run within a transaction, such as
+//! truncating a table on engines without transactional range deletes, fail
+//! the migration.
+use super::Datastore;
+use super::KeyEncode as _;
+use super::LockType::*;
+use super::TransactionType::*;
+use crate::ctx::Canceller;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::{Datetime, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::key::database::mg;
+use crate::sql::Query;
+use crate::syn;
+use anyhow::{Result, bail, ensure};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A migration of the schema, or the data, of a database
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Migration {
+	/// The unique id of the migration
+	pub id: String,
+	/// The query which applies the migration
+	pub up: Query,
+	/// The query which reverts the migration
+	pub down: Option<Query>,
+}
+
+impl Migration {
+	/// Creates a migration which can not be reverted
+	pub fn new(id: impl Into<String>, up: Query) -> Self {
+		Self {
+			id: id.into(),
+			up,
+			down: None,
+		}
+	}
+	/// Creates a migration by parsing its `up` query
+	pub fn parse(id: impl Into<String>, up: &str) -> Result<Self> {
+		Ok(Self::new(id, syn::parse(up)?))
+	}
+	/// Sets the query which reverts the migration
+	pub fn with_down(mut self, down: Query) -> Self {
+		self.down = Some(down);
+		self
+	}
+	/// Returns the checksum of the parsed `up` query
+	pub fn checksum(&self) -> String {
+		blake3::hash(self.up.to_string().as_bytes()).to_hex().to_string()
+	}
+}
+
+/// A migration which was applied to a database
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[non_exhaustive]
+pub struct AppliedMigration {
+	/// The id of the migration
+	pub id: String,
+	/// The checksum of the `up` query when the migration was applied
+	pub checksum: String,
+	/// The position of the migration among the applied migrations
+	pub position: u64,
+	/// The time at which the migration was applied
+	pub applied_at: Datetime,
+}
+
+impl InfoStructure for AppliedMigration {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"id".to_string() => Value::from(self.id),
+			"checksum".to_string() => Value::from(self.checksum),
+			"position".to_string() => Value::from(self.position),
+			"applied_at".to_string() => Value::Datetime(self.applied_at),
+		})
+	}
+}
+
+impl From<AppliedMigration> for Value {
+	fn from(v: AppliedMigration) -> Self {
+		v.structure()
+	}
+}
+
+impl Datastore {
+	/// Applies the migrations which were not applied to the database of the
+	/// session yet, in the order in which they are specified.
+	///
+	/// No migration is applied if a migration which was already applied was
+	/// changed since. Returns the ids of the migrations which were applied.
+	pub async fn migrate(&self, sess: &Session, migrations: &[Migration]) -> Result<Vec<String>> {
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Migrations can define and remove any resource
+		self.check(sess, Action::Edit, ResourceKind::Any.on_db(&ns, &db))?;
+		let mut ids = HashSet::new();
+		for m in migrations {
+			ensure!(
+				ids.insert(m.id.as_str()),
+				Error::MigrationDuplicate {
+					id: m.id.clone(),
+				}
+			);
+		}
+		// Check that the applied migrations were not changed
+		let applied = self.applied(&ns, &db).await?;
+		for m in migrations {
+			if let Some(a) = applied.iter().find(|a| a.id == m.id) {
+				let current = m.checksum();
+				ensure!(
+					a.checksum == current,
+					Error::MigrationChanged {
+						id: m.id.clone(),
+						applied: a.checksum.clone(),
+						current,
+					}
+				);
+			}
+		}
+		// Apply the pending migrations in order
+		let mut position = applied.iter().map(|a| a.position + 1).max().unwrap_or(0);
+		let mut done = Vec::new();
+		for m in migrations.iter().filter(|m| !applied.iter().any(|a| a.id == m.id)) {
+			let record = (m.checksum(), position);
+			if self.run_migration(sess, &ns, &db, &m.id, &m.up, Some(record)).await? {
+				done.push(m.id.clone());
+				position += 1;
+			}
+		}
+		Ok(done)
+	}
+
+	/// Reverts the migration which was applied last to the database of the
+	/// session, by running its `down` query.
+	///
+	/// Returns the id of the reverted migration, or None if no migration was applied.
+	pub async fn revert(&self, sess: &Session, migrations: &[Migration]) -> Result<Option<String>> {
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Migrations can define and remove any resource
+		self.check(sess, Action::Edit, ResourceKind::Any.on_db(&ns, &db))?;
+		let applied = self.applied(&ns, &db).await?;
+		let Some(last) = applied.last() else {
+			return Ok(None);
+		};
+		let Some(m) = migrations.iter().find(|m| m.id == last.id) else {
+			bail!(Error::MigrationNotFound {
+				id: last.id.clone(),
+			});
+		};
+		let current = m.checksum();
+		ensure!(
+			last.checksum == current,
+			Error::MigrationChanged {
+				id: m.id.clone(),
+				applied: last.checksum.clone(),
+				current,
+			}
+		);
+		let Some(down) = &m.down else {
+			bail!(Error::MigrationIrreversible {
+				id: m.id.clone(),
+			});
+		};
+		match self.run_migration(sess, &ns, &db, &m.id, down, None).await? {
+			true => Ok(Some(m.id.clone())),
+			false => Ok(None),
+		}
+	}
+
+	/// Returns the migrations which were applied to the database of the
+	/// session, in the order in which they were applied.
+	pub async fn applied_migrations(&self, sess: &Session) -> Result<Vec<AppliedMigration>> {
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check that the session can view the database
+		self.check(sess, Action::View, ResourceKind::Any.on_db(&ns, &db))?;
+		self.applied(&ns, &db).await
+	}
+
+	/// Reads the applied migrations of a database, in the order in which they were applied
+	async fn applied(&self, ns: &str, db: &str) -> Result<Vec<AppliedMigration>> {
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res = txn.getr(mg::prefix(ns, db)?..mg::suffix(ns, db)?, None).await;
+		txn.cancel().await?;
+		let mut applied = res?
+			.into_iter()
+			.map(|(_, v)| revision::from_slice(&v))
+			.collect::<Result<Vec<AppliedMigration>, _>>()?;
+		applied.sort_by_key(|v| v.position);
+		Ok(applied)
+	}
+
+	/// Runs the query of a migration in a transaction, recording the migration
+	/// as applied at the given position, or removing its record if it is reverted.
+	///
+	/// Returns false if another node applied, or reverted, the migration first.
+	async fn run_migration(
+		&self,
+		sess: &Session,
+		ns: &str,
+		db: &str,
+		id: &str,
+		query: &Query,
+		record: Option<(String, u64)>,
+	) -> Result<bool> {
+		let canceller = Canceller::default();
+		let _running = self.start_running(canceller.clone())?;
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		let key = mg::new(ns, db, id).encode()?;
+		// Check the record of the migration within the transaction
+		if txn.exists(key.clone(), None).await? == record.is_some() {
+			txn.cancel().await?;
+			return Ok(false);
+		}
+		let (sender, receiver) = async_channel::unbounded();
+		let res = self
+			.process_in_transaction(query.clone(), sess, None, txn.clone(), sender, &canceller)
+			.await;
+		let failed = match res {
+			Ok(res) => res.into_iter().find_map(|r| r.result.err()).map(|e| e.to_string()),
+			Err(e) => Some(e.to_string()),
+		};
+		if let Some(message) = failed {
+			// A failed statement already cancelled the transaction
+			if !txn.closed().await {
+				txn.cancel().await?;
+			}
+			bail!(Error::MigrationFailed {
+				id: id.to_owned(),
+				message,
+			});
+		}
+		// Record, or remove, the migration along with its changes
+		match record {
+			Some((checksum, position)) => {
+				let strict = self.setup_options(sess).strict;
+				txn.get_or_add_ns(ns, strict).await?;
+				txn.get_or_add_db(ns, db, strict).await?;
+				let val = AppliedMigration {
+					id: id.to_owned(),
+					checksum,
+					position,
+					applied_at: txn.timestamp().into(),
+				};
+				txn.set(key, revision::to_vec(&val)?, None).await?;
+			}
+			None => txn.del(key).await?,
+		}
+		let mut lock = txn.lock().await;
+		if let Err(e) = lock.complete_changes(false).await {
+			let _ = lock.cancel().await;
+			return Err(e);
+		}
+		lock.commit().await?;
+		drop(lock);
+		// Flush the notifications to the live queries
+		if let Some(sink) = self.notification_sender() {
+			while let Ok(notification) = receiver.try_recv() {
+				if sink.send(notification).await.is_err() {
+					break;
+				}
+			}
+		}
+		Ok(true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Migration;
+	use crate::dbs::Session;
+	use crate::err::Error;
+	use crate::kvs::Datastore;
+
+	fn migrations() -> Vec<Migration> {
+		vec![
+			Migration::parse("1-person", "DEFINE TABLE person SCHEMALESS;").unwrap(),
+			Migration::parse("2-email", "DEFINE INDEX email ON person FIELDS email UNIQUE;")
+				.unwrap()
+				.with_down(crate::syn::parse("REMOVE INDEX email ON person;").unwrap()),
+		]
+	}
+
+	#[tokio::test]
+	async fn migrations_are_applied_once_in_order() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let applied = ds.migrate(&sess, &migrations()[..1]).await.unwrap();
+		assert_eq!(applied, vec!["1-person"]);
+		let applied = ds.migrate(&sess, &migrations()).await.unwrap();
+		assert_eq!(applied, vec!["2-email"]);
+		assert!(ds.migrate(&sess, &migrations()).await.unwrap().is_empty());
+		// The applied migrations are listed in order
+		let list = ds.applied_migrations(&sess).await.unwrap();
+		let ids: Vec<_> = list.iter().map(|m| (m.id.as_str(), m.position)).collect();
+		assert_eq!(ids, vec![("1-person", 0), ("2-email", 1)]);
+		assert_eq!(list[1].checksum, migrations()[1].checksum());
+		// The migrations were applied
+		let res = ds.execute("INFO FOR TABLE person", &sess, None).await.unwrap();
+		let info = res.into_iter().next().unwrap().result.unwrap().to_string();
+		assert!(info.contains("DEFINE INDEX email"), "{info}");
+	}
+
+	#[tokio::test]
+	async fn changed_migrations_are_detected() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.migrate(&sess, &migrations()).await.unwrap();
+		// Whitespace and comments are not changes
+		let mut same = migrations();
+		same[0] =
+			Migration::parse("1-person", "-- people\nDEFINE  TABLE\n\tperson SCHEMALESS").unwrap();
+		assert!(ds.migrate(&sess, &same).await.unwrap().is_empty());
+		// Changed statements are
+		let mut changed = migrations();
+		changed.push(Migration::parse("3-name", "DEFINE FIELD name ON person").unwrap());
+		changed[0] = Migration::parse("1-person", "DEFINE TABLE person SCHEMAFULL;").unwrap();
+		let err = ds.migrate(&sess, &changed).await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref(), Some(Error::MigrationChanged { id, .. }) if id == "1-person"),
+			"{err}"
+		);
+		// No migration was applied
+		assert_eq!(ds.applied_migrations(&sess).await.unwrap().len(), 2);
+	}
+
+	#[tokio::test]
+	async fn failed_migrations_leave_no_changes() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let failing = vec![
+			Migration::parse("1-person", "DEFINE TABLE person;").unwrap(),
+			Migration::parse("2-fail", "CREATE person:one; THROW 'broken';").unwrap(),
+			Migration::parse("3-never", "CREATE person:two;").unwrap(),
+		];
+		let err = ds.migrate(&sess, &failing).await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref(), Some(Error::MigrationFailed { id, message }) if id == "2-fail" && message.contains("broken")),
+			"{err}"
+		);
+		let list = ds.applied_migrations(&sess).await.unwrap();
+		assert_eq!(list.len(), 1);
+		let res = ds.execute("SELECT * FROM person", &sess, None).await.unwrap();
+		assert_eq!(res.into_iter().next().unwrap().result.unwrap().to_string(), "[]");
+		// Duplicate ids are rejected
+		let err = ds.migrate(&sess, &[failing[0].clone(), failing[0].clone()]).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::MigrationDuplicate { .. })), "{err}");
+	}
+
+	#[tokio::test]
+	async fn migrations_are_reverted() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.migrate(&sess, &migrations()).await.unwrap();
+		assert_eq!(ds.revert(&sess, &migrations()).await.unwrap().as_deref(), Some("2-email"));
+		let res = ds.execute("INFO FOR TABLE person", &sess, None).await.unwrap();
+		let info = res.into_iter().next().unwrap().result.unwrap().to_string();
+		assert!(!info.contains("DEFINE INDEX email"), "{info}");
+		// The first migration has no down query
+		let err = ds.revert(&sess, &migrations()).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::MigrationIrreversible { .. })), "{err}");
+		// The reverted migration is applied again
+		assert_eq!(ds.migrate(&sess, &migrations()).await.unwrap(), vec!["2-email"]);
+	}
+}
@@ -14,21 +14,29 @@
 //! - `mem`: in-memory database
 
 pub mod export;
+pub mod import;
+pub mod migration;
+pub mod schema;
 
 mod api;
+mod archive;
 mod batch;
 mod cf;
 mod clock;
 mod ds;
+mod expiry;
 mod key;
 mod live;
 mod node;
+mod outbox;
 mod scanner;
+mod schedule;
 mod stash;
 mod threadpool;
 mod tr;
 mod tx;
 mod version;
+mod writes;
 
 mod fdb;
 mod indxdb;
@@ -38,6 +46,7 @@ mod surrealkv;
 mod tikv;
 
 pub(crate) mod cache;
+pub(crate) mod drain;
 
 #[cfg(not(target_family = "wasm"))]
 mod index;
@@ -51,6 +60,7 @@ mod util;
 pub(crate) use key::impl_key;
 pub use key::{KeyDecode, KeyDecodeOwned, KeyEncode};
 
+pub use drain::SHUTTING_DOWN;
 pub use ds::Datastore;
 pub use live::Live;
 pub use tr::{Check, LockType, TransactionType, Transactor};
@@ -58,6 +68,7 @@ pub use tx::Transaction;
 
 #[cfg(not(target_family = "wasm"))]
 pub(crate) use index::{ConsumeResult, IndexBuilder};
+pub(crate) use scanner::ScanSummary;
 
 /// The key part of a key-value pair. An alias for [`Vec<u8>`].
 pub type Key = Vec<u8>;
@@ -182,6 +182,20 @@ impl Datastore {
 							let tlq = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, nlq.lq);
 							// Delete the table live query
 							catch!(txn, txn.clr(tlq).await);
+							// Get the key prefix for any aggregate state
+							let tla = catch!(
+								txn,
+								crate::key::table::la::prefix(&val.ns, &val.db, &val.tb, nlq.lq)
+							);
+							// Delete any aggregate state
+							catch!(txn, txn.clrp(tla).await);
+							// Get the key prefix for any ordered aggregate values
+							let tlm = catch!(
+								txn,
+								crate::key::table::lm::prefix(&val.ns, &val.db, &val.tb, nlq.lq)
+							);
+							// Delete any ordered aggregate values
+							catch!(txn, txn.clrp(tlm).await);
 							// Delete the node live query
 							catch!(txn, txn.clr(nlq).await);
 						}
@@ -278,6 +292,24 @@ impl Datastore {
 								catch!(txn, txn.clr(nlq).await);
 								// Delete the table live query
 								catch!(txn, txn.clr(tlq).await);
+								// Get the key prefix for any aggregate state
+								let tla = catch!(
+									txn,
+									crate::key::table::la::prefix(
+										&ns.name, &db.name, &tb.name, lid
+									)
+								);
+								// Delete any aggregate state
+								catch!(txn, txn.clrp(tla).await);
+								// Get the key prefix for any ordered aggregate values
+								let tlm = catch!(
+									txn,
+									crate::key::table::lm::prefix(
+										&ns.name, &db.name, &tb.name, lid
+									)
+								);
+								// Delete any ordered aggregate values
+								catch!(txn, txn.clrp(tlm).await);
 							}
 						}
 						// Pause and yield execution
@@ -320,6 +352,14 @@ impl Datastore {
 				let tlq = crate::key::table::lq::new(&lq.ns, &lq.db, &lq.tb, id);
 				// Delete the table live query
 				catch!(txn, txn.clr(tlq).await);
+				// Get the key prefix for any aggregate state
+				let tla = catch!(txn, crate::key::table::la::prefix(&lq.ns, &lq.db, &lq.tb, id));
+				// Delete any aggregate state
+				catch!(txn, txn.clrp(tla).await);
+				// Get the key prefix for any ordered aggregate values
+				let tlm = catch!(txn, crate::key::table::lm::prefix(&lq.ns, &lq.db, &lq.tb, id));
+				// Delete any ordered aggregate values
+				catch!(txn, txn.clrp(tlm).await);
 				// Delete the node live query
 				catch!(txn, txn.clr(nlq).await);
 			}
@@ -0,0 +1,283 @@
+use crate::cnf::{
+	NORMAL_FETCH_SIZE, OUTBOX_MAX_ATTEMPTS, OUTBOX_MAX_RETRY_DELAY, OUTBOX_REQUEST_TIMEOUT,
+	OUTBOX_RETENTION, OUTBOX_RETRY_BACKOFF,
+};
+use crate::dbs::outbox::{OutboxMessage, OutboxStatus};
+use crate::expr::Datetime;
+use crate::key::database::ob;
+use crate::kvs::Datastore;
+use crate::kvs::KeyDecode;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{LockType::*, TransactionType::*};
+use anyhow::Result;
+use chrono::{TimeDelta, Utc};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+impl Datastore {
+	/// Pauses the delivery of the outbox messages by this datastore.
+	///
+	/// The messages which are enqueued while the delivery is paused are
+	/// kept, and are sent once the delivery is resumed.
+	pub fn pause_outbox(&self) {
+		self.outbox_paused.store(true, Ordering::Release);
+	}
+
+	/// Resumes the delivery of the outbox messages by this datastore
+	pub fn resume_outbox(&self) {
+		self.outbox_paused.store(false, Ordering::Release);
+	}
+
+	/// Checks whether the delivery of the outbox messages is paused
+	pub fn is_outbox_paused(&self) -> bool {
+		self.outbox_paused.load(Ordering::Acquire)
+	}
+
+	/// Sends the pending outbox messages, across all databases.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, lh))]
+	pub(crate) async fn outbox_dispatch(&self, lh: Option<&LeaseHandler>) -> Result<()> {
+		// Store the databases which have an outbox
+		let mut dbs = Vec::new();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Fetch all namespaces
+		let nss = catch!(txn, txn.all_ns().await);
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Fetch all databases
+			for db in catch!(txn, txn.all_db(&ns.name).await).iter() {
+				dbs.push((ns.name.to_raw(), db.name.to_raw()));
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Send the messages of each database
+		for (ns, db) in dbs {
+			// Stop sending the messages once paused
+			if self.is_outbox_paused() {
+				break;
+			}
+			self.outbox_dispatch_db(lh, &ns, &db).await?;
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Sends the pending outbox messages of a database, and removes the
+	/// delivered and failed messages which are past the retention period
+	async fn outbox_dispatch_db(
+		&self,
+		lh: Option<&LeaseHandler>,
+		ns: &str,
+		db: &str,
+	) -> Result<()> {
+		let retention = TimeDelta::seconds(*OUTBOX_RETENTION as i64);
+		let mut next = Some(ob::prefix(ns, db)?..ob::suffix(ns, db)?);
+		while let Some(rng) = next {
+			// Fetch the next batch of messages
+			let txn = self.transaction(Read, Optimistic).await?;
+			let res = catch!(txn, txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await);
+			txn.cancel().await?;
+			next = res.next;
+			for (k, v) in res.result {
+				// Stop sending the messages once paused
+				if self.is_outbox_paused() {
+					return Ok(());
+				}
+				let id = ob::Ob::decode(&k)?.ob;
+				let mut message: OutboxMessage = revision::from_slice(&v)?;
+				let now = Utc::now();
+				match message.status {
+					// The message is due to be sent
+					OutboxStatus::Pending if message.next.0 <= now => {
+						let res = self.outbox_send(&message).await;
+						message.attempts += 1;
+						message.updated = Datetime::default();
+						match res {
+							Ok(_) => {
+								message.status = OutboxStatus::Delivered;
+								message.error = None;
+							}
+							Err(e) => {
+								message.error = Some(e.to_string());
+								if message.attempts >= *OUTBOX_MAX_ATTEMPTS {
+									message.status = OutboxStatus::Failed;
+								} else {
+									let delay = retry_delay(message.attempts);
+									message.next = Datetime(Utc::now() + delay);
+								}
+							}
+						}
+						// Record the attempt, unless the message was removed meanwhile
+						let key = ob::new(ns, db, id);
+						let txn = self.transaction(Write, Optimistic).await?;
+						if catch!(txn, txn.exists(key.clone(), None).await) {
+							catch!(txn, txn.set(key, revision::to_vec(&message)?, None).await);
+						}
+						txn.commit().await?;
+					}
+					// The message is waiting for its next attempt
+					OutboxStatus::Pending => {}
+					// The message is past the retention period
+					_ if message.updated.0 + retention <= now => {
+						let txn = self.transaction(Write, Optimistic).await?;
+						catch!(txn, txn.del(ob::new(ns, db, id)).await);
+						txn.commit().await?;
+					}
+					// The message is kept for the operator to inspect
+					_ => {}
+				}
+				// Possibly renew the lease
+				if let Some(lh) = lh {
+					lh.try_maintain_lease().await?;
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	/// Posts an outbox message to its url
+	#[cfg(feature = "http")]
+	async fn outbox_send(&self, message: &OutboxMessage) -> Result<()> {
+		use crate::fnc::util::http::{RequestConfig, post};
+		let ctx = self.setup_ctx()?.freeze();
+		let config = RequestConfig {
+			timeout: Some(Duration::from_secs(*OUTBOX_REQUEST_TIMEOUT)),
+			..Default::default()
+		};
+		let url = message.url.clone().into();
+		post(&ctx, url, message.payload.clone(), message.headers.clone(), config).await?;
+		Ok(())
+	}
+
+	/// Posts an outbox message to its url
+	#[cfg(not(feature = "http"))]
+	async fn outbox_send(&self, _: &OutboxMessage) -> Result<()> {
+		anyhow::bail!(crate::err::Error::HttpDisabled)
+	}
+}
+
+/// The delay before the next attempt to send a message, which doubles
+/// with every failed attempt, up to the maximum retry delay
+fn retry_delay(attempts: u32) -> TimeDelta {
+	let max = Duration::from_secs(*OUTBOX_MAX_RETRY_DELAY);
+	let delay = Duration::from_millis(*OUTBOX_RETRY_BACKOFF)
+		.saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+		.min(max);
+	TimeDelta::from_std(delay).unwrap_or(TimeDelta::MAX)
+}
+
+#[cfg(all(test, feature = "http"))]
+mod tests {
+	use crate::dbs::outbox::{OutboxStatus, entries};
+	use crate::dbs::{Capabilities, Session};
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType::*;
+	use crate::kvs::TransactionType::*;
+	use wiremock::matchers::{body_json, header, method, path};
+	use wiremock::{Mock, MockServer, ResponseTemplate};
+
+	async fn statuses(ds: &Datastore) -> Vec<(OutboxStatus, u32)> {
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = entries(&txn, "test", "test").await.unwrap();
+		txn.cancel().await.unwrap();
+		res.into_iter().map(|e| (e.message.status, e.message.attempts)).collect()
+	}
+
+	#[test]
+	fn retry_delay_is_exponential() {
+		assert_eq!(super::retry_delay(1).num_milliseconds(), 1000);
+		assert_eq!(super::retry_delay(2).num_milliseconds(), 2000);
+		assert_eq!(super::retry_delay(4).num_milliseconds(), 8000);
+		assert_eq!(super::retry_delay(100).num_seconds(), 3600);
+	}
+
+	#[tokio::test]
+	async fn messages_are_delivered_after_commit() {
+		let server = MockServer::start().await;
+		Mock::given(method("POST"))
+			.and(path("/hook"))
+			.and(header("x-key", "abc"))
+			.and(body_json(serde_json::json!({ "id": "person:tobie" })))
+			.respond_with(ResponseTemplate::new(200))
+			.expect(1)
+			.mount(&server)
+			.await;
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = format!(
+			"
+			DEFINE EVENT hook ON person WHEN $event = 'CREATE' THEN {{
+				outbox::enqueue({{ url: '{}/hook', payload: {{ id: <string> $after.id }}, headers: {{ 'x-key': 'abc' }} }});
+			}};
+			CREATE person:tobie;
+			BEGIN;
+			CREATE person:jaime;
+			CANCEL;
+			",
+			server.uri()
+		);
+		for res in ds.execute(&sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// Only the message of the committed change is enqueued
+		assert_eq!(statuses(&ds).await, vec![(OutboxStatus::Pending, 0)]);
+		// Nothing is sent while the delivery is paused
+		ds.pause_outbox();
+		ds.outbox_dispatch(None).await.unwrap();
+		assert_eq!(statuses(&ds).await, vec![(OutboxStatus::Pending, 0)]);
+		// The message is sent once the delivery is resumed
+		ds.resume_outbox();
+		ds.outbox_dispatch(None).await.unwrap();
+		assert_eq!(statuses(&ds).await, vec![(OutboxStatus::Delivered, 1)]);
+		// Delivered messages are not sent again
+		ds.outbox_dispatch(None).await.unwrap();
+		server.verify().await;
+		// The delivery status can be listed by the operator
+		let mut res = ds.execute("INFO FOR DATABASE OUTBOX", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(val.pick(&["status".into()]).to_string(), "['delivered']");
+		assert_eq!(val.pick(&["attempts".into()]).to_string(), "[1]");
+	}
+
+	#[tokio::test]
+	async fn failed_messages_are_retried() {
+		let server = MockServer::start().await;
+		Mock::given(method("POST"))
+			.respond_with(ResponseTemplate::new(503))
+			.expect(1)
+			.mount(&server)
+			.await;
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = format!("RETURN outbox::enqueue({{ url: '{}', payload: 'ping' }})", server.uri());
+		for res in ds.execute(&sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		ds.outbox_dispatch(None).await.unwrap();
+		// The message is not sent again before the retry delay
+		ds.outbox_dispatch(None).await.unwrap();
+		server.verify().await;
+		assert_eq!(statuses(&ds).await, vec![(OutboxStatus::Pending, 1)]);
+		let mut res = ds.execute("INFO FOR DATABASE OUTBOX", &sess, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert_eq!(
+			val.pick(&["error".into()]).to_string(),
+			"['There was an error processing a remote HTTP request: 503 Service Unavailable']"
+		);
+	}
+
+	#[tokio::test]
+	async fn messages_respect_the_network_capabilities() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "RETURN outbox::enqueue({ url: 'http://127.0.0.1/hook' })";
+		let res = ds.execute(sql, &sess, None).await.unwrap().remove(0).result;
+		assert!(res.is_err());
+		assert!(statuses(&ds).await.is_empty());
+	}
+}
@@ -316,6 +316,10 @@ impl super::api::Transaction for Transaction {
 		true
 	}
 
+	fn supports_transactional_range_delete(&self) -> bool {
+		true
+	}
+
 	/// Behaviour if unclosed
 	fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -694,6 +698,10 @@ impl super::api::Transaction for Transaction {
 	fn release_last_save_point(&mut self) -> Result<()> {
 		Ok(())
 	}
+
+	fn releases_save_points(&self) -> bool {
+		false
+	}
 }
 
 impl Transaction {
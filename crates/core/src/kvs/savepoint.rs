@@ -68,6 +68,23 @@ impl SavePoints {
 		}
 	}
 
+	pub(super) fn release(&mut self) -> Result<()> {
+		let sp = self.pop()?;
+		// Merge the released save point into its parent, so that
+		// rolling back the parent also reverts these changes
+		if let Some(current) = &mut self.current {
+			for (key, sv) in sp {
+				match current.get_mut(&key) {
+					Some(v) => v.last_operation = sv.last_operation,
+					None => {
+						current.insert(key, sv);
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
 	pub(super) fn is_saved_key(&self, key: &Key) -> Option<bool> {
 		self.current.as_ref().map(|current| current.contains_key(key))
 	}
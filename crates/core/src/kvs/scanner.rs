@@ -1,6 +1,8 @@
 use super::Key;
 use super::Val;
 use super::tx::Transaction;
+use crate::cnf::{MAX_FETCH_SIZE, NORMAL_FETCH_SIZE};
+use crate::ctx::MutableContext;
 use crate::err::Error;
 use crate::idx::planner::ScanDirection;
 use anyhow::Result;
@@ -18,11 +20,55 @@ type FutureResult<'a, I> = Pin<Box<dyn Future<Output = Result<Vec<I>>> + 'a + Se
 #[cfg(target_family = "wasm")]
 type FutureResult<'a, I> = Pin<Box<dyn Future<Output = Result<Vec<I>>> + 'a>>;
 
-pub(super) struct Scanner<'a, I> {
+/// The number of keys which a table or range scan fetches in each batch
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FetchSize {
+	/// The number of keys fetched by the first batch
+	pub(crate) initial: u32,
+	/// The largest number of keys fetched by a batch
+	pub(crate) max: u32,
+}
+
+impl Default for FetchSize {
+	fn default() -> Self {
+		Self {
+			initial: *NORMAL_FETCH_SIZE,
+			max: *MAX_FETCH_SIZE,
+		}
+	}
+}
+
+/// The batches which a scan fetched from the datastore
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ScanSummary {
+	/// The number of batches, each a round trip to the datastore
+	pub(crate) batches: usize,
+	/// The number of keys which were fetched
+	pub(crate) keys: usize,
+	/// The number of keys requested by the largest batch
+	pub(crate) largest: u32,
+}
+
+impl ScanSummary {
+	/// Adds the batches of another scan
+	pub(crate) fn add(&mut self, other: ScanSummary) {
+		self.batches += other.batches;
+		self.keys += other.keys;
+		self.largest = self.largest.max(other.largest);
+	}
+}
+
+pub struct Scanner<'a, I> {
 	/// The store which started this range scan
 	store: &'a Transaction,
-	/// The number of keys to fetch at once
+	/// The context which is checked before fetching each batch
+	ctx: &'a MutableContext,
+	/// The number of keys to fetch in the next batch
 	batch: u32,
+	/// The largest number of keys to fetch at once
+	max: u32,
+	/// The number of keys requested by the running batch
+	fetching: u32,
 	/// The key range for this range scan
 	range: Range<Key>,
 	/// The results from the last range scan
@@ -37,30 +83,48 @@ pub(super) struct Scanner<'a, I> {
 	limit: Option<usize>,
 	/// The scan direction
 	sc: ScanDirection,
+	/// The batches fetched so far
+	summary: ScanSummary,
 }
 
 impl<'a, I> Scanner<'a, I> {
-	pub fn new(
+	pub(super) fn new(
 		store: &'a Transaction,
-		batch: u32,
+		ctx: &'a MutableContext,
+		size: FetchSize,
 		range: Range<Key>,
 		version: Option<u64>,
 		limit: Option<usize>,
 		sc: ScanDirection,
 	) -> Self {
+		let max = size.max.max(size.initial).max(1);
+		// When the number of keys is known, fetch them at once
+		let batch = match limit {
+			Some(l) => (l.min(max as usize) as u32).max(1),
+			None => size.initial.max(1),
+		};
 		Scanner {
 			store,
+			ctx,
 			batch,
+			max,
+			fetching: 0,
 			range,
 			future: None,
 			results: VecDeque::new(),
-			exhausted: false,
+			exhausted: limit == Some(0),
 			version,
 			limit,
 			sc,
+			summary: ScanSummary::default(),
 		}
 	}
 
+	/// The batches which were fetched so far
+	pub(crate) fn summary(&self) -> ScanSummary {
+		self.summary
+	}
+
 	fn next_poll<S, K>(&mut self, cx: &mut Context, scan: S, key: K) -> Poll<Option<Result<I>>>
 	where
 		S: Fn(Range<Key>, u32) -> FutureResult<'a, I>,
@@ -76,6 +140,15 @@ impl<'a, I> Scanner<'a, I> {
 		}
 		// Check if there is no pending future task
 		if self.future.is_none() {
+			// Check if the context is finished before fetching more
+			match self.ctx.should_stop() {
+				Ok(false) => (),
+				Ok(true) => {
+					self.exhausted = true;
+					return Poll::Ready(None);
+				}
+				Err(e) => return Poll::Ready(Some(Err(e))),
+			}
 			// Clone the range to use when scanning
 			let range = self.range.clone();
 			// Compute the batch size. It can't be more what is left to collect
@@ -83,6 +156,10 @@ impl<'a, I> Scanner<'a, I> {
 				.limit
 				.map(|l| (self.batch as usize).min(l) as u32)
 				.unwrap_or_else(|| self.batch);
+			// Record the round trip to the datastore
+			self.summary.batches += 1;
+			self.summary.largest = self.summary.largest.max(batch);
+			self.fetching = batch;
 			// Prepare a future to scan for results
 			self.future = Some(scan(range, batch));
 		}
@@ -103,12 +180,20 @@ impl<'a, I> Scanner<'a, I> {
 						}
 						// There are results that need streaming
 						false => {
+							self.summary.keys += v.len();
 							if let Some(l) = &mut self.limit {
-								*l -= v.len();
+								*l = l.saturating_sub(v.len());
+								// We fetched every key which was asked for
+								if *l == 0 {
+									self.exhausted = true;
+								}
 							}
 							// We fetched the last elements in the range
-							if v.len() < self.batch as usize {
+							if v.len() < self.fetching as usize {
 								self.exhausted = true;
+							} else {
+								// Fetch more keys at once while the scan continues
+								self.batch = self.batch.saturating_mul(2).min(self.max);
 							}
 							// Get the last element of the results
 							let last = v.last().ok_or_else(|| {
@@ -186,3 +271,222 @@ impl Stream for Scanner<'_, Key> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::expr::{FlowResultExt as _, Value};
+	use crate::kvs::savepoint::SavePoints;
+	use crate::kvs::{Check, Datastore, LockType::*, TransactionType::*, Transactor, api};
+	use async_trait::async_trait;
+	use futures::StreamExt;
+	use reblessive::TreeStack;
+	use std::sync::{Arc, Mutex};
+
+	/// A datastore transaction which records the size of
+	/// each batch scanned from within a range of keys
+	struct Recording {
+		inner: Box<dyn api::Transaction>,
+		prefix: Key,
+		batches: Arc<Mutex<Vec<u32>>>,
+	}
+
+	impl Recording {
+		fn record(&self, rng: &Range<Key>, limit: u32) {
+			if rng.start.starts_with(&self.prefix) {
+				self.batches.lock().unwrap().push(limit);
+			}
+		}
+	}
+
+	#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+	#[cfg_attr(not(target_family = "wasm"), async_trait)]
+	impl api::Transaction for Recording {
+		fn supports_reverse_scan(&self) -> bool {
+			self.inner.supports_reverse_scan()
+		}
+		fn supports_transactional_range_delete(&self) -> bool {
+			self.inner.supports_transactional_range_delete()
+		}
+		fn kind(&self) -> &'static str {
+			self.inner.kind()
+		}
+		fn check_level(&mut self, check: Check) {
+			self.inner.check_level(check)
+		}
+		fn closed(&self) -> bool {
+			self.inner.closed()
+		}
+		fn writeable(&self) -> bool {
+			self.inner.writeable()
+		}
+		async fn cancel(&mut self) -> Result<()> {
+			self.inner.cancel().await
+		}
+		async fn commit(&mut self) -> Result<()> {
+			self.inner.commit().await
+		}
+		async fn exists(&mut self, key: Key, version: Option<u64>) -> Result<bool> {
+			self.inner.exists(key, version).await
+		}
+		async fn get(&mut self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+			self.inner.get(key, version).await
+		}
+		async fn set(&mut self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+			self.inner.set(key, val, version).await
+		}
+		async fn put(&mut self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+			self.inner.put(key, val, version).await
+		}
+		async fn putc(&mut self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+			self.inner.putc(key, val, chk).await
+		}
+		async fn del(&mut self, key: Key) -> Result<()> {
+			self.inner.del(key).await
+		}
+		async fn delc(&mut self, key: Key, chk: Option<Val>) -> Result<()> {
+			self.inner.delc(key, chk).await
+		}
+		async fn keys(
+			&mut self,
+			rng: Range<Key>,
+			limit: u32,
+			version: Option<u64>,
+		) -> Result<Vec<Key>> {
+			self.record(&rng, limit);
+			self.inner.keys(rng, limit, version).await
+		}
+		async fn scan(
+			&mut self,
+			rng: Range<Key>,
+			limit: u32,
+			version: Option<u64>,
+		) -> Result<Vec<(Key, Val)>> {
+			self.record(&rng, limit);
+			self.inner.scan(rng, limit, version).await
+		}
+		fn get_save_points(&mut self) -> &mut SavePoints {
+			self.inner.get_save_points()
+		}
+	}
+
+	/// Starts a transaction which records the batches scanned below a prefix
+	async fn recording(ds: &Datastore, prefix: Key) -> (Transaction, Arc<Mutex<Vec<u32>>>) {
+		let batches = Arc::new(Mutex::new(Vec::new()));
+		let Transactor {
+			inner,
+			stash,
+			cf,
+			save_points,
+			writes,
+		} = ds.transaction(Read, Optimistic).await.unwrap().inner();
+		let inner = Box::new(Recording {
+			inner,
+			prefix,
+			batches: batches.clone(),
+		});
+		let tx = Transactor {
+			inner,
+			stash,
+			cf,
+			save_points,
+			writes,
+		};
+		(Transaction::new(true, tx), batches)
+	}
+
+	async fn insert_keys(ds: &Datastore, count: usize) {
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		for i in 0..count {
+			tx.set(format!("k{i:05}").into_bytes(), "v", None).await.unwrap();
+		}
+		tx.commit().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn batches_grow_up_to_the_maximum() {
+		let ds = Datastore::new("memory").await.unwrap();
+		insert_keys(&ds, 2000).await;
+		let (tx, batches) = recording(&ds, b"k".to_vec()).await;
+		let tx = tx.with_fetch_size(FetchSize {
+			initial: 100,
+			max: 400,
+		});
+		let ctx = MutableContext::background();
+		let rng = b"k".to_vec()..b"l".to_vec();
+		let mut stream = tx.stream_keys(&ctx, rng, None, None, ScanDirection::Forward);
+		let mut count = 0;
+		while let Some(key) = stream.next().await {
+			key.unwrap();
+			count += 1;
+		}
+		assert_eq!(count, 2000);
+		assert_eq!(*batches.lock().unwrap(), vec![100, 200, 400, 400, 400, 400, 400]);
+		assert_eq!(
+			stream.summary(),
+			ScanSummary {
+				batches: 7,
+				keys: 2000,
+				largest: 400,
+			}
+		);
+		drop(stream);
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn limits_are_fetched_at_once() {
+		let ds = Datastore::new("memory").await.unwrap();
+		insert_keys(&ds, 2000).await;
+		let ctx = MutableContext::background();
+		for (limit, expected) in [(0, vec![]), (10, vec![10]), (1000, vec![400, 400, 200])] {
+			let (tx, batches) = recording(&ds, b"k".to_vec()).await;
+			let tx = tx.with_fetch_size(FetchSize {
+				initial: 100,
+				max: 400,
+			});
+			let rng = b"k".to_vec()..b"l".to_vec();
+			let stream = tx.stream(&ctx, rng, None, Some(limit), ScanDirection::Forward);
+			let res: Vec<_> = stream.collect().await;
+			assert_eq!(res.len(), limit);
+			assert_eq!(*batches.lock().unwrap(), expected);
+			tx.cancel().await.unwrap();
+		}
+	}
+
+	#[tokio::test]
+	async fn select_limit_stops_the_table_scan() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "FOR $i IN 0..2000 { CREATE t SET n = $i }";
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res.into_iter().all(|r| r.result.is_ok()));
+		let prefix = crate::key::thing::prefix("test", "test", "t").unwrap();
+		for (sql, expected) in [
+			("SELECT * FROM t LIMIT 10", vec![10]),
+			("SELECT * FROM t LIMIT 10 START 5", vec![5, 10]),
+			// The filtered records are scanned in batches until the iterator is cancelled
+			("SELECT * FROM t WHERE n >= 0 LIMIT 10", vec![*NORMAL_FETCH_SIZE]),
+		] {
+			let (tx, batches) = recording(&ds, prefix.clone()).await;
+			let tx = tx.enclose();
+			let val: Value = crate::syn::value(&format!("({sql})")).unwrap().into();
+			let opt = ds.setup_options(&sess);
+			let mut ctx = ds.setup_ctx().unwrap();
+			sess.context(&mut ctx);
+			ctx.set_transaction(tx.clone());
+			let ctx = ctx.freeze();
+			let mut stack = TreeStack::new();
+			let res = stack
+				.enter(|stk| val.compute(stk, &ctx, &opt, None))
+				.finish()
+				.await
+				.catch_return()
+				.unwrap();
+			assert!(matches!(res, Value::Array(a) if a.len() == 10), "{sql}");
+			assert_eq!(*batches.lock().unwrap(), expected, "{sql}");
+			tx.cancel().await.unwrap();
+		}
+	}
+}
@@ -0,0 +1,332 @@
+use crate::cnf::{SCHEDULE_CONCURRENCY, SCHEDULE_HISTORY_RETENTION, SCHEDULE_RUN_TIMEOUT};
+use crate::dbs::Session;
+use crate::dbs::schedule::{self, Cron, ScheduleRun, ScheduleState};
+use crate::expr::statements::DefineScheduleStatement;
+use crate::expr::{Datetime, LogicalPlan, Value};
+use crate::key::database::{sh, ss};
+use crate::kvs::Datastore;
+use crate::kvs::Transaction;
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{LockType::*, TransactionType::*};
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+impl Datastore {
+	/// Runs the schedules which are due, across all databases.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, lh))]
+	pub(crate) async fn schedule_dispatch(
+		&self,
+		lh: Option<&LeaseHandler>,
+		now: DateTime<Utc>,
+	) -> Result<()> {
+		// Store the schedules of all databases
+		let mut schedules = Vec::new();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Fetch all namespaces
+		let nss = catch!(txn, txn.all_ns().await);
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Fetch all databases
+			for db in catch!(txn, txn.all_db(&ns.name).await).iter() {
+				// Fetch all schedules
+				for sc in catch!(txn, txn.all_db_schedules(&ns.name, &db.name).await).iter() {
+					schedules.push((ns.name.to_raw(), db.name.to_raw(), sc.clone()));
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Run the schedules which are due, a few at a time
+		let mut runs = futures::stream::iter(schedules)
+			.map(|(ns, db, sc)| async move {
+				let res = self.schedule_run(&ns, &db, &sc, now).await;
+				(ns, db, sc, res)
+			})
+			.buffer_unordered((*SCHEDULE_CONCURRENCY).max(1));
+		while let Some((ns, db, sc, res)) = runs.next().await {
+			// A failing schedule does not stop the other schedules
+			if let Err(e) = res {
+				error!(
+					target: "surrealdb::core::kvs::ds",
+					"Failed to run schedule {} in {ns}/{db}: {e}",
+					sc.name
+				);
+			}
+			// Possibly renew the lease
+			if let Some(lh) = lh {
+				lh.try_maintain_lease().await?;
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Runs a schedule if it is due, and removes the runs of the
+	/// schedule which are past the retention period
+	async fn schedule_run(
+		&self,
+		ns: &str,
+		db: &str,
+		sc: &DefineScheduleStatement,
+		now: DateTime<Utc>,
+	) -> Result<()> {
+		let cron: Cron = sc.cron.parse()?;
+		// Claim the run, so that it is not started again
+		let txn = self.transaction(Write, Optimistic).await?;
+		let id = run!(txn, claim(&txn, ns, db, &sc.name, &cron, now).await)?;
+		// Run the statements as the system user of the schedule
+		if let Some(id) = id {
+			trace!(target: "surrealdb::core::kvs::ds", "Running schedule {} in {ns}/{db}", sc.name);
+			let timeout = Duration::from_secs(*SCHEDULE_RUN_TIMEOUT);
+			let mut sess = Session::default().with_ns(ns).with_db(db).with_query_timeout(timeout);
+			sess.au = Arc::new(sc.auth(ns, db));
+			let plan = LogicalPlan::Value(Value::Block(Box::new(sc.then.clone())));
+			let error = match self.process_plan(plan, &sess, None).await {
+				Ok(res) => res.into_iter().find_map(|r| r.result.err()).map(|e| e.to_string()),
+				Err(e) => Some(e.to_string()),
+			};
+			// Record the outcome of the run
+			let txn = self.transaction(Write, Optimistic).await?;
+			run!(txn, finish(&txn, ns, db, &sc.name, id, error).await)?;
+		}
+		// Remove the runs which are past the retention period
+		let txn = self.transaction(Write, Optimistic).await?;
+		run!(txn, cleanup(&txn, ns, db, &sc.name, now).await)
+	}
+}
+
+/// Claims the next run of a schedule, if the schedule is due, and if the
+/// previous run of the schedule is not still in progress
+async fn claim(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	sc: &str,
+	cron: &Cron,
+	now: DateTime<Utc>,
+) -> Result<Option<Uuid>> {
+	let key = ss::new(ns, db, sc);
+	// The schedule was removed meanwhile
+	let Some(v) = txn.get(key.clone(), None).await? else {
+		return Ok(None);
+	};
+	let mut state: ScheduleState = revision::from_slice(&v)?;
+	let mut changed = false;
+	// Check the run which is in progress
+	if let Some(id) = state.running {
+		let key = sh::new(ns, db, sc, id);
+		if let Some(v) = txn.get(key.clone(), None).await? {
+			let mut run: ScheduleRun = revision::from_slice(&v)?;
+			// The run is still in progress
+			let stale = TimeDelta::seconds(*SCHEDULE_RUN_TIMEOUT as i64) * 2;
+			if run.started.0 + stale > now {
+				return Ok(None);
+			}
+			// The node which started the run stopped before it finished
+			run.finished = Some(Datetime::from(now));
+			run.error = Some("The run did not finish before the node running it stopped".into());
+			txn.set(key, revision::to_vec(&run)?, None).await?;
+		}
+		state.running = None;
+		changed = true;
+	}
+	// Start a run if the schedule is due
+	let id = match &state.next {
+		Some(next) if next.0 <= now => {
+			let id = Uuid::now_v7();
+			let run = ScheduleRun {
+				started: Datetime::from(now),
+				finished: None,
+				error: None,
+			};
+			txn.set(sh::new(ns, db, sc, id), revision::to_vec(&run)?, None).await?;
+			// The runs which were missed are skipped
+			state.next = cron.next_after(now).map(Datetime::from);
+			state.running = Some(id);
+			changed = true;
+			Some(id)
+		}
+		_ => None,
+	};
+	if changed {
+		txn.set(key, revision::to_vec(&state)?, None).await?;
+	}
+	Ok(id)
+}
+
+/// Records the outcome of a run, unless the schedule was removed meanwhile
+async fn finish(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	sc: &str,
+	id: Uuid,
+	error: Option<String>,
+) -> Result<()> {
+	let key = ss::new(ns, db, sc);
+	let Some(v) = txn.get(key.clone(), None).await? else {
+		return Ok(());
+	};
+	let mut state: ScheduleState = revision::from_slice(&v)?;
+	if state.running != Some(id) {
+		return Ok(());
+	}
+	state.running = None;
+	txn.set(key, revision::to_vec(&state)?, None).await?;
+	let key = sh::new(ns, db, sc, id);
+	if let Some(v) = txn.get(key.clone(), None).await? {
+		let mut run: ScheduleRun = revision::from_slice(&v)?;
+		run.finished = Some(Datetime::default());
+		run.error = error;
+		txn.set(key, revision::to_vec(&run)?, None).await?;
+	}
+	Ok(())
+}
+
+/// Removes the finished runs of a schedule which are past the retention period
+async fn cleanup(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	sc: &str,
+	now: DateTime<Utc>,
+) -> Result<()> {
+	let retention = TimeDelta::seconds(*SCHEDULE_HISTORY_RETENTION as i64);
+	for entry in schedule::runs(txn, ns, db, sc).await? {
+		if entry.run.finished.is_some_and(|v| v.0 + retention <= now) {
+			txn.del(sh::new(ns, db, sc, entry.id)).await?;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+	use chrono::{TimeDelta, Utc};
+
+	async fn query(ds: &Datastore, sql: &str) -> Vec<Value> {
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		res.into_iter().map(|r| r.result.unwrap()).collect()
+	}
+
+	async fn statuses(ds: &Datastore, sc: &str) -> String {
+		let sql = format!("(INFO FOR SCHEDULE {sc}).runs.status");
+		query(ds, &sql).await.remove(0).to_string()
+	}
+
+	#[tokio::test]
+	async fn schedules_are_run_when_due() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(
+			&ds,
+			"
+			DEFINE SCHEDULE cleanup ON DATABASE CRON '* * * * *' THEN {
+				CREATE run;
+			};
+			DEFINE SCHEDULE broken ON DATABASE CRON '* * * * *' THEN {
+				THROW 'failed';
+			};
+			DEFINE SCHEDULE viewer ON DATABASE CRON '* * * * *' ROLES VIEWER THEN {
+				CREATE run;
+			};
+			",
+		)
+		.await;
+		// Nothing is run before the schedules are due
+		ds.schedule_dispatch(None, Utc::now()).await.unwrap();
+		assert_eq!(statuses(&ds, "cleanup").await, "[]");
+		// Each schedule is run once when due
+		let now = Utc::now() + TimeDelta::minutes(2);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(statuses(&ds, "cleanup").await, "['success']");
+		assert_eq!(statuses(&ds, "broken").await, "['failed']");
+		assert_eq!(statuses(&ds, "viewer").await, "['failed']");
+		let res = query(&ds, "(INFO FOR SCHEDULE broken).runs.error").await;
+		assert_eq!(res[0].to_string(), "['An error occurred: failed']");
+		// The statements are run with the roles of the schedule
+		let res = query(&ds, "count(SELECT * FROM run)").await;
+		assert_eq!(res[0].to_string(), "1");
+		// The missed runs are skipped
+		let now = now + TimeDelta::minutes(10);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(statuses(&ds, "cleanup").await, "['success', 'success']");
+		// The runs are removed after the retention period
+		let now = now + TimeDelta::days(365);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(statuses(&ds, "cleanup").await, "['success']");
+	}
+
+	#[tokio::test]
+	async fn schedules_are_not_run_concurrently() {
+		use crate::dbs::schedule::{ScheduleState, runs};
+		use crate::key::database::ss;
+		use crate::kvs::LockType::*;
+		use crate::kvs::TransactionType::*;
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE SCHEDULE cleanup ON DATABASE CRON '* * * * *' THEN { CREATE run };")
+			.await;
+		let now = Utc::now() + TimeDelta::minutes(2);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		// Mark the run as still in progress
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		let key = ss::new("test", "test", "cleanup");
+		let mut state: ScheduleState =
+			revision::from_slice(&txn.get(key.clone(), None).await.unwrap().unwrap()).unwrap();
+		let id = runs(&txn, "test", "test", "cleanup").await.unwrap().remove(0).id;
+		state.running = Some(id);
+		txn.set(key, revision::to_vec(&state).unwrap(), None).await.unwrap();
+		txn.commit().await.unwrap();
+		// The schedule is not run while the previous run is in progress
+		let now = now + TimeDelta::minutes(2);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(query(&ds, "count(SELECT * FROM run)").await[0].to_string(), "1");
+		// Unless the previous run is stale
+		let now = now + TimeDelta::days(1);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(query(&ds, "count(SELECT * FROM run)").await[0].to_string(), "2");
+		assert_eq!(statuses(&ds, "cleanup").await, "['failed', 'success']");
+		// Removing the schedule removes its state and run history
+		query(&ds, "REMOVE SCHEDULE cleanup").await;
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let res = ds.execute("INFO FOR SCHEDULE cleanup", &sess, None).await.unwrap();
+		assert!(res.into_iter().next().unwrap().result.is_err());
+	}
+
+	#[tokio::test]
+	async fn schedules_are_run_when_another_fails() {
+		use crate::key::database::ss;
+		use crate::kvs::LockType::*;
+		use crate::kvs::TransactionType::*;
+		let ds = Datastore::new("memory").await.unwrap();
+		query(
+			&ds,
+			"
+			DEFINE SCHEDULE archive ON DATABASE CRON '* * * * *' THEN { CREATE run };
+			DEFINE SCHEDULE cleanup ON DATABASE CRON '* * * * *' THEN { CREATE run };
+			",
+		)
+		.await;
+		// Corrupt the state of the first schedule
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		let key = ss::new("test", "test", "archive");
+		txn.set(key, vec![0xff], None).await.unwrap();
+		txn.commit().await.unwrap();
+		// The other schedule is still run
+		let now = Utc::now() + TimeDelta::minutes(2);
+		ds.schedule_dispatch(None, now).await.unwrap();
+		assert_eq!(statuses(&ds, "cleanup").await, "['success']");
+		assert_eq!(query(&ds, "count(SELECT * FROM run)").await[0].to_string(), "1");
+	}
+}
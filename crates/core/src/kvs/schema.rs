@@ -0,0 +1,461 @@
+//! Comparison of the schemas of two databases.
+//!
+//! A [`Schema`] contains the params, functions, accesses, tables, fields,
+//! indexes, and events which are defined in a database. It is read from a
+//! live database with [`Datastore::schema`], or from the statements of an
+//! export with [`Schema::parse`]. The definitions of two schemas are compared
+//! by their properties, as shown by INFO statements, so any change to their
+//! permissions or comments is detected as well.
+//!
+//! The [`SchemaDiff`] returned by [`diff`] lists the added, removed, and
+//! changed definitions, and can be turned into the statements which change
+//! the first schema into the second one.
+use super::Datastore;
+use super::LockType::*;
+use super::TransactionType::*;
+use crate::dbs::Session;
+use crate::expr::statements::info::InfoStructure;
+use crate::expr::statements::{
+	DefineStatement, RemoveAccessStatement, RemoveEventStatement, RemoveFieldStatement,
+	RemoveFunctionStatement, RemoveIndexStatement, RemoveParamStatement, RemoveTableStatement,
+};
+use crate::expr::{Base, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::sql::Query;
+use crate::sql::statement::Statement;
+use crate::syn;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// The kind of a definition, in the order in which the kinds depend on each other
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum DefinitionKind {
+	Param,
+	Function,
+	Access,
+	Table,
+	Field,
+	Index,
+	Event,
+}
+
+/// A definition of a schema
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Definition {
+	/// The kind of the definition
+	pub kind: DefinitionKind,
+	/// The table which the definition is defined on
+	pub table: Option<String>,
+	/// The name of the definition
+	pub name: String,
+	/// The statement of the definition
+	pub statement: DefineStatement,
+}
+
+type DefinitionKey = (DefinitionKind, Option<String>, String);
+
+impl Definition {
+	/// Returns the definition of a statement, if the statement is part of a schema
+	fn new(statement: DefineStatement) -> Option<Self> {
+		let (kind, table, name) = match &statement {
+			DefineStatement::Param(v) => (DefinitionKind::Param, None, v.name.0.clone()),
+			DefineStatement::Function(v) => (DefinitionKind::Function, None, v.name.0.clone()),
+			DefineStatement::Access(v) if v.base == Base::Db => {
+				(DefinitionKind::Access, None, v.name.0.clone())
+			}
+			DefineStatement::Table(v) => (DefinitionKind::Table, None, v.name.0.clone()),
+			DefineStatement::Field(v) => {
+				(DefinitionKind::Field, Some(v.what.0.clone()), v.name.to_string())
+			}
+			DefineStatement::Index(v) => {
+				(DefinitionKind::Index, Some(v.what.0.clone()), v.name.0.clone())
+			}
+			DefineStatement::Event(v) => {
+				(DefinitionKind::Event, Some(v.what.0.clone()), v.name.0.clone())
+			}
+			_ => return None,
+		};
+		Some(Self {
+			kind,
+			table,
+			name,
+			statement,
+		})
+	}
+
+	fn key(&self) -> DefinitionKey {
+		(self.kind, self.table.clone(), self.name.clone())
+	}
+
+	/// Returns the properties of the definition, as shown by INFO statements
+	fn properties(&self) -> BTreeMap<String, Value> {
+		let structure = match self.statement.clone() {
+			DefineStatement::Param(v) => v.structure(),
+			DefineStatement::Function(v) => v.structure(),
+			DefineStatement::Access(v) => v.structure(),
+			DefineStatement::Table(v) => v.structure(),
+			DefineStatement::Field(v) => v.structure(),
+			DefineStatement::Index(v) => v.structure(),
+			DefineStatement::Event(v) => v.structure(),
+			_ => Value::None,
+		};
+		match structure {
+			Value::Object(v) => v.0,
+			_ => BTreeMap::new(),
+		}
+	}
+
+	/// Returns the statement which defines, or redefines, the definition
+	fn define(&self, overwrite: bool) -> String {
+		let mut stm = self.statement.clone();
+		match &mut stm {
+			DefineStatement::Param(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Function(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Access(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Table(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Field(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Index(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			DefineStatement::Event(v) => (v.if_not_exists, v.overwrite) = (false, overwrite),
+			_ => {}
+		}
+		stm.to_string()
+	}
+
+	/// Returns the statement which removes the definition
+	fn remove(&self) -> String {
+		match &self.statement {
+			DefineStatement::Param(v) => RemoveParamStatement {
+				name: v.name.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Function(v) => RemoveFunctionStatement {
+				name: v.name.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Access(v) => RemoveAccessStatement {
+				name: v.name.clone(),
+				base: v.base.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Table(v) => RemoveTableStatement {
+				name: v.name.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Field(v) => RemoveFieldStatement {
+				name: v.name.clone(),
+				what: v.what.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Index(v) => RemoveIndexStatement {
+				name: v.name.clone(),
+				what: v.what.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			DefineStatement::Event(v) => RemoveEventStatement {
+				name: v.name.clone(),
+				what: v.what.clone(),
+				..Default::default()
+			}
+			.to_string(),
+			_ => String::new(),
+		}
+	}
+}
+
+/// The definitions of a database
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+	definitions: BTreeMap<DefinitionKey, Definition>,
+}
+
+impl Schema {
+	/// Collects the definitions of the statements of a query, such as an export
+	pub fn from_query(query: &Query) -> Self {
+		let mut schema = Self::default();
+		for stm in query.0.iter() {
+			if let Statement::Define(v) = stm {
+				schema.insert(v.clone().into());
+			}
+		}
+		schema
+	}
+
+	/// Collects the definitions of the statements of an export
+	pub fn parse(sql: &str) -> Result<Self> {
+		Ok(Self::from_query(&syn::parse(sql)?))
+	}
+
+	/// Returns the definitions, in the order in which they depend on each other
+	pub fn definitions(&self) -> impl Iterator<Item = &Definition> {
+		self.definitions.values()
+	}
+
+	fn insert(&mut self, statement: DefineStatement) {
+		if let Some(def) = Definition::new(statement) {
+			self.definitions.insert(def.key(), def);
+		}
+	}
+}
+
+/// A definition which differs between two schemas
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Change {
+	/// The definition in the first schema
+	pub before: Definition,
+	/// The definition in the second schema
+	pub after: Definition,
+	/// The properties which differ between the definitions
+	pub properties: Vec<PropertyChange>,
+}
+
+/// A property which differs between two definitions
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PropertyChange {
+	/// The name of the property, such as `permissions` or `comment`
+	pub name: String,
+	/// The value of the property in the first schema, or NONE if it is not set
+	pub before: Value,
+	/// The value of the property in the second schema, or NONE if it is not set
+	pub after: Value,
+}
+
+/// The differences between two schemas
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct SchemaDiff {
+	/// The definitions which are only in the second schema
+	pub added: Vec<Definition>,
+	/// The definitions which are only in the first schema
+	pub removed: Vec<Definition>,
+	/// The definitions which differ between the schemas
+	pub changed: Vec<Change>,
+}
+
+impl SchemaDiff {
+	/// Checks if the schemas are the same
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+
+	/// Returns the statements which change the first schema into the second one.
+	///
+	/// The removed definitions are removed first, in the reverse order of their
+	/// dependencies, after which the added definitions are defined, and the changed
+	/// definitions are overwritten, so that tables are defined before their fields,
+	/// and fields before their indexes.
+	pub fn to_statements(&self) -> Vec<String> {
+		let mut statements = Vec::new();
+		// Removing a table removes the definitions on the table
+		let tables: HashSet<&str> = self
+			.removed
+			.iter()
+			.filter(|v| v.kind == DefinitionKind::Table)
+			.map(|v| v.name.as_str())
+			.collect();
+		for def in self.removed.iter().rev() {
+			if def.table.as_deref().is_some_and(|tb| tables.contains(tb)) {
+				continue;
+			}
+			statements.push(def.remove());
+		}
+		let mut defs: Vec<_> = self
+			.added
+			.iter()
+			.map(|v| (v, false))
+			.chain(self.changed.iter().map(|v| (&v.after, true)))
+			.collect();
+		defs.sort_by_key(|(v, _)| v.key());
+		statements.extend(defs.into_iter().map(|(v, overwrite)| v.define(overwrite)));
+		statements
+	}
+}
+
+/// Compares two schemas, returning the changes from the first schema to the second one
+pub fn diff(a: &Schema, b: &Schema) -> SchemaDiff {
+	let mut diff = SchemaDiff::default();
+	for (key, before) in a.definitions.iter() {
+		let Some(after) = b.definitions.get(key) else {
+			diff.removed.push(before.clone());
+			continue;
+		};
+		let (mut old, mut new) = (before.properties(), after.properties());
+		let names: BTreeSet<String> = old.keys().chain(new.keys()).cloned().collect();
+		let properties: Vec<_> = names
+			.into_iter()
+			.filter_map(|name| {
+				let before = old.remove(&name).unwrap_or_default();
+				let after = new.remove(&name).unwrap_or_default();
+				(before != after).then_some(PropertyChange {
+					name,
+					before,
+					after,
+				})
+			})
+			.collect();
+		if !properties.is_empty() {
+			diff.changed.push(Change {
+				before: before.clone(),
+				after: after.clone(),
+				properties,
+			});
+		}
+	}
+	for (key, after) in b.definitions.iter() {
+		if !a.definitions.contains_key(key) {
+			diff.added.push(after.clone());
+		}
+	}
+	diff
+}
+
+impl Datastore {
+	/// Reads the schema of the database of the session
+	pub async fn schema(&self, sess: &Session) -> Result<Schema> {
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check that the session can view the database
+		self.check(sess, Action::View, ResourceKind::Any.on_db(&ns, &db))?;
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res = async {
+			let mut schema = Schema::default();
+			for v in txn.all_db_params(&ns, &db).await?.iter() {
+				schema.insert(DefineStatement::Param(v.clone()));
+			}
+			for v in txn.all_db_functions(&ns, &db).await?.iter() {
+				schema.insert(DefineStatement::Function(v.clone()));
+			}
+			for v in txn.all_db_accesses(&ns, &db).await?.iter() {
+				schema.insert(DefineStatement::Access(v.clone()));
+			}
+			for tb in txn.all_tb(&ns, &db, None).await?.iter() {
+				schema.insert(DefineStatement::Table(tb.clone()));
+				for v in txn.all_tb_fields(&ns, &db, &tb.name, None).await?.iter() {
+					schema.insert(DefineStatement::Field(v.clone()));
+				}
+				for v in txn.all_tb_indexes(&ns, &db, &tb.name).await?.iter() {
+					schema.insert(DefineStatement::Index(v.clone()));
+				}
+				for v in txn.all_tb_events(&ns, &db, &tb.name).await?.iter() {
+					schema.insert(DefineStatement::Event(v.clone()));
+				}
+			}
+			Ok(schema)
+		}
+		.await;
+		txn.cancel().await?;
+		res
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{DefinitionKind, Schema, diff};
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+
+	const STAGING: &str = "
+		DEFINE PARAM $limit VALUE 10;
+		DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; };
+		DEFINE TABLE person SCHEMAFULL PERMISSIONS FOR select FULL;
+		DEFINE FIELD name ON person TYPE string COMMENT 'full name';
+		DEFINE FIELD email ON person TYPE string;
+		DEFINE INDEX email ON person FIELDS email UNIQUE;
+		DEFINE TABLE post;
+		DEFINE EVENT created ON post WHEN $event = 'CREATE' THEN { CREATE log SET post = $after.id };
+	";
+
+	const PRODUCTION: &str = "
+		DEFINE PARAM $limit VALUE 10;
+		DEFINE TABLE person    SCHEMAFULL PERMISSIONS FOR select NONE;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE FIELD age ON person TYPE int;
+		DEFINE TABLE legacy;
+		DEFINE FIELD old ON legacy;
+	";
+
+	async fn datastore(sql: &str) -> (Datastore, Session) {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let res = ds.execute(sql, &sess, None).await.unwrap();
+		assert!(res.into_iter().all(|r| r.result.is_ok()));
+		(ds, sess)
+	}
+
+	#[tokio::test]
+	async fn schemas_are_compared() {
+		let (ds, sess) = datastore(PRODUCTION).await;
+		let production = ds.schema(&sess).await.unwrap();
+		let staging = Schema::parse(STAGING).unwrap();
+		let diff = diff(&production, &staging);
+		let names = |defs: &[super::Definition]| {
+			defs.iter().map(|v| (v.kind, v.name.clone())).collect::<Vec<_>>()
+		};
+		assert_eq!(
+			names(&diff.added),
+			vec![
+				(DefinitionKind::Function, "greet".to_string()),
+				(DefinitionKind::Table, "post".to_string()),
+				(DefinitionKind::Field, "email".to_string()),
+				(DefinitionKind::Index, "email".to_string()),
+				(DefinitionKind::Event, "created".to_string()),
+			]
+		);
+		assert_eq!(
+			names(&diff.removed),
+			vec![
+				(DefinitionKind::Table, "legacy".to_string()),
+				(DefinitionKind::Field, "old".to_string()),
+				(DefinitionKind::Field, "age".to_string()),
+			]
+		);
+		// Permissions and comments are compared
+		let changed: Vec<_> = diff
+			.changed
+			.iter()
+			.map(|c| {
+				let props: Vec<_> = c.properties.iter().map(|p| p.name.as_str()).collect();
+				(c.after.name.as_str(), props)
+			})
+			.collect();
+		assert_eq!(changed, vec![("person", vec!["permissions"]), ("name", vec!["comment"])]);
+		// The statements change the production schema into the staging schema
+		let statements = diff.to_statements();
+		assert_eq!(statements[0], "REMOVE FIELD age ON person");
+		assert_eq!(statements[1], "REMOVE TABLE legacy");
+		assert_eq!(statements.len(), 9);
+		let sql = statements.join(";\n");
+		let res = ds.execute(&sql, &sess, None).await.unwrap();
+		assert!(res.into_iter().all(|r| r.result.is_ok()), "{sql}");
+		let migrated = ds.schema(&sess).await.unwrap();
+		assert!(super::diff(&migrated, &staging).is_empty());
+	}
+
+	#[tokio::test]
+	async fn exports_match_the_live_schema() {
+		let (ds, sess) = datastore(STAGING).await;
+		let (send, recv) = async_channel::unbounded();
+		ds.export(&sess, send).await.unwrap().await.unwrap();
+		let mut bytes = Vec::new();
+		while let Ok(v) = recv.try_recv() {
+			bytes.extend(v);
+		}
+		let export = Schema::parse(&String::from_utf8(bytes).unwrap()).unwrap();
+		let live = ds.schema(&sess).await.unwrap();
+		assert_eq!(live.definitions().count(), 8);
+		assert!(diff(&live, &export).is_empty());
+		// Whitespace and comments are not changes
+		let reformatted = format!("-- staging\n{}", STAGING.replace('\n', "\n\n\t"));
+		let reformatted = Schema::parse(&reformatted).unwrap();
+		assert!(diff(&live, &reformatted).is_empty());
+	}
+}
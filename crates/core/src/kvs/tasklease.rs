@@ -15,6 +15,12 @@ use uuid::Uuid;
 pub(crate) enum TaskLeaseType {
 	/// Task for cleaning up old changefeed data
 	ChangeFeedCleanup,
+	/// Task for removing records which have expired
+	RecordExpiry,
+	/// Task for sending the pending outbox messages
+	OutboxDelivery,
+	/// Task for running the schedules which are due
+	ScheduleRunner,
 }
 
 #[revisioned(revision = 1)]
@@ -196,6 +196,73 @@ pub async fn range(new_ds: impl CreateDs) {
 	);
 }
 
+async fn prefix_range(new_ds: impl CreateDs, index: &str) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("056804f2-b379-4397-9ceb-af8ebd527beb").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+
+	// Run the test
+	let sql = format!(
+		"
+		USE NS test; USE DB test;
+		{index};
+		FOR $i IN 1..101 {{ CREATE event SET tenant = $i % 2, created = $i; }};
+		SELECT created FROM event WHERE tenant = 0 AND created > 50 ORDER BY created DESC LIMIT 3;
+		SELECT created FROM event WHERE tenant = 0 AND created <= 50 ORDER BY created DESC LIMIT 3;
+		SELECT created FROM event WHERE tenant = 0 AND created > 50 ORDER BY created DESC LIMIT 3 EXPLAIN;
+	"
+	);
+	let mut r = ds.execute(&sql, &Session::owner(), None).await.unwrap();
+	//Check the result
+	for _ in 0..4 {
+		check(&mut r, "NONE");
+	}
+	check(&mut r, "[{ created: 100 }, { created: 98 }, { created: 96 }]");
+	check(&mut r, "[{ created: 50 }, { created: 48 }, { created: 46 }]");
+	check(
+		&mut r,
+		"[
+				{
+					detail: {
+						plan: {
+							direction: 'backward',
+							from: {
+								inclusive: false,
+								value: 50
+							},
+							index: 'idx',
+							prefix: [
+								0
+							],
+							to: {
+								inclusive: false,
+								value: NONE
+							}
+						},
+						table: 'event'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						limit: 3,
+						type: 'MemoryOrderedLimit'
+					},
+					operation: 'Collector'
+				}
+			]",
+	);
+}
+
+pub async fn prefix_range_standard(new_ds: impl CreateDs) {
+	prefix_range(new_ds, "DEFINE INDEX idx ON TABLE event FIELDS tenant, created").await;
+}
+
+pub async fn prefix_range_unique(new_ds: impl CreateDs) {
+	prefix_range(new_ds, "DEFINE INDEX idx ON TABLE event FIELDS tenant, created UNIQUE").await;
+}
+
 macro_rules! define_tests {
 	($new_ds:ident) => {
 		#[tokio::test]
@@ -213,6 +280,16 @@ macro_rules! define_tests {
 		async fn reverse_iterator_range() {
 			super::reverse_iterator::range($new_ds).await;
 		}
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn reverse_iterator_prefix_range_standard() {
+			super::reverse_iterator::prefix_range_standard($new_ds).await;
+		}
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn reverse_iterator_prefix_range_unique() {
+			super::reverse_iterator::prefix_range_unique($new_ds).await;
+		}
 	};
 }
 use crate::expr::Value;
@@ -16,6 +16,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use tikv::TimestampExt;
 use tikv::TransactionOptions;
+use tikv::transaction::Mutation;
 use tikv::{CheckLevel, Config, TransactionClient};
 
 const TARGET: &str = "surrealdb::core::kvs::tikv";
@@ -162,6 +163,10 @@ impl super::api::Transaction for Transaction {
 		true
 	}
 
+	fn supports_transactional_range_delete(&self) -> bool {
+		false
+	}
+
 	/// Behaviour if unclosed
 	fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -352,6 +357,33 @@ impl super::api::Transaction for Transaction {
 		Ok(())
 	}
 
+	/// Insert, update, or delete many keys in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(writes = writes.len()))]
+	async fn setm(&mut self, writes: Vec<(Key, Option<Val>)>) -> Result<()> {
+		// Check to see if transaction is closed
+		ensure!(!self.done, Error::TxFinished);
+		// Check to see if transaction is writable
+		ensure!(self.write, Error::TxReadonly);
+		// The save points need the previous value of each key
+		if self.save_points.is_some() {
+			for (key, val) in writes.into_iter() {
+				match val {
+					Some(val) => self.set(key, val, None).await?,
+					None => self.del(key).await?,
+				}
+			}
+			return Ok(());
+		}
+		// Apply the writes with a single request
+		let mutations = writes.into_iter().map(|(key, val)| match val {
+			Some(val) => Mutation::Put(key.into(), val),
+			None => Mutation::Delete(key.into()),
+		});
+		self.inner.batch_mutate(mutations).await?;
+		// Return result
+		Ok(())
+	}
+
 	/// Delete a key if the current value matches a condition
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
 	async fn delc(&mut self, key: Key, chk: Option<Val>) -> Result<()> {
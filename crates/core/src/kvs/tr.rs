@@ -5,6 +5,7 @@ use super::Version;
 #[allow(unused_imports, reason = "Not used when none of the storage backends are enabled.")]
 use super::api::Transaction;
 use crate::cf;
+use crate::cf::ChangeAudit;
 
 use crate::doc::CursorValue;
 use crate::idg::u32::U32;
@@ -12,12 +13,15 @@ use crate::key::debug::Sprintable;
 use crate::kvs::batch::Batch;
 
 use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::err::Error;
 use crate::expr;
 use crate::expr::thing::Thing;
 use crate::kvs::KeyDecode as _;
 use crate::kvs::stash::Stash;
+use crate::kvs::util;
+use crate::kvs::writes::WriteBuffer;
 use crate::vs::VersionStamp;
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use expr::statements::DefineTableStatement;
 use std::fmt;
 use std::fmt::Debug;
@@ -72,6 +76,17 @@ pub struct Transactor {
 	pub(super) inner: Box<dyn super::api::Transaction>,
 	pub(super) stash: Stash,
 	pub(super) cf: cf::Writer,
+	pub(super) save_points: Vec<SavePoint>,
+	pub(super) writes: WriteBuffer,
+}
+
+/// The state of the transaction when a save point was created.
+pub(super) struct SavePoint {
+	/// The number of released save points, created after this
+	/// one, which are still held by the underlying transaction.
+	released: usize,
+	/// The number of buffered change feed mutations for each table.
+	changes: cf::Checkpoint,
 }
 
 impl fmt::Display for Transactor {
@@ -114,6 +129,7 @@ impl Transactor {
 	/// This reverses all changes made within the transaction.
 	#[instrument(level = "trace", target = TARGET, skip_all)]
 	pub(crate) async fn cancel(&mut self) -> Result<()> {
+		self.writes.clear();
 		self.inner.cancel().await
 	}
 
@@ -122,6 +138,7 @@ impl Transactor {
 	/// This attempts to commit all changes made within the transaction.
 	#[instrument(level = "trace", target = TARGET, skip_all)]
 	pub(crate) async fn commit(&mut self) -> Result<()> {
+		self.flush_writes().await?;
 		self.inner.commit().await
 	}
 
@@ -133,6 +150,12 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Exists");
+		if version.is_none() {
+			if let Some(val) = self.writes.get(&key) {
+				return Ok(val.is_some());
+			}
+		}
+		self.flush_key(&key).await?;
 		self.inner.exists(key, version).await
 	}
 
@@ -144,6 +167,12 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Get");
+		if version.is_none() {
+			if let Some(val) = self.writes.get(&key) {
+				return Ok(val.cloned());
+			}
+		}
+		self.flush_key(&key).await?;
 		self.inner.get(key, version).await
 	}
 
@@ -158,6 +187,9 @@ impl Transactor {
 			keys_encoded.push(k.encode_owned()?);
 		}
 		trace!(target: TARGET, keys = keys_encoded.sprint(), "GetM");
+		if keys_encoded.iter().any(|k| self.writes.contains(k)) {
+			self.flush_writes().await?;
+		}
 		self.inner.getm(keys_encoded).await
 	}
 
@@ -173,6 +205,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "GetR");
+		self.flush_range(&rng).await?;
 		self.inner.getr(rng, version).await
 	}
 
@@ -186,6 +219,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "GetP");
+		self.flush_range(&util::to_prefix_range(&key)?).await?;
 		self.inner.getp(key).await
 	}
 
@@ -198,6 +232,12 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Set");
+		if self.writes.is_active() && version.is_none() {
+			self.check_writeable()?;
+			self.writes.set(key, val.into());
+			return self.flush_if_full().await;
+		}
+		self.flush_key(&key).await?;
 		self.inner.set(key, val.into(), version).await
 	}
 
@@ -210,6 +250,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "Replace");
+		self.flush_key(&key).await?;
 		self.inner.replace(key, val.into()).await
 	}
 
@@ -222,6 +263,18 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Put");
+		if self.writes.is_active() && version.is_none() {
+			self.check_writeable()?;
+			// Check the buffered write, or otherwise the stored value
+			let exists = match self.writes.get(&key) {
+				Some(val) => val.is_some(),
+				None => self.inner.exists(key.clone(), None).await?,
+			};
+			ensure!(!exists, Error::TxKeyAlreadyExists);
+			self.writes.set(key, val.into());
+			return self.flush_if_full().await;
+		}
+		self.flush_key(&key).await?;
 		self.inner.put(key, val.into(), version).await
 	}
 
@@ -234,6 +287,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "PutC");
+		self.flush_key(&key).await?;
 		self.inner.putc(key, val.into(), chk.map(Into::into)).await
 	}
 
@@ -245,6 +299,12 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "Del");
+		if self.writes.is_active() {
+			self.check_writeable()?;
+			self.writes.del(key);
+			return self.flush_if_full().await;
+		}
+		self.flush_key(&key).await?;
 		self.inner.del(key).await
 	}
 
@@ -257,6 +317,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "DelC");
+		self.flush_key(&key).await?;
 		self.inner.delc(key, chk.map(Into::into)).await
 	}
 
@@ -272,6 +333,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), "DelR");
+		self.flush_range(&rng).await?;
 		self.inner.delr(rng).await
 	}
 
@@ -285,6 +347,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "DelP");
+		self.flush_range(&util::to_prefix_range(&key)?).await?;
 		self.inner.delp(key).await
 	}
 
@@ -296,6 +359,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "Clr");
+		self.flush_key(&key).await?;
 		self.inner.clr(key).await
 	}
 
@@ -308,6 +372,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "ClrC");
+		self.flush_key(&key).await?;
 		self.inner.clrc(key, chk.map(Into::into)).await
 	}
 
@@ -323,6 +388,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), "ClrR");
+		self.flush_range(&rng).await?;
 		self.inner.clrr(rng).await
 	}
 
@@ -336,6 +402,7 @@ impl Transactor {
 	{
 		let key: Key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "ClrP");
+		self.flush_range(&util::to_prefix_range(&key)?).await?;
 		self.inner.clrp(key).await
 	}
 
@@ -359,6 +426,7 @@ impl Transactor {
 		if rng.start > rng.end {
 			return Ok(vec![]);
 		}
+		self.flush_range(&rng).await?;
 		self.inner.keys(rng, limit, version).await
 	}
 
@@ -382,6 +450,7 @@ impl Transactor {
 		if rng.start > rng.end {
 			return Ok(vec![]);
 		}
+		self.flush_range(&rng).await?;
 		self.inner.keysr(rng, limit, version).await
 	}
 
@@ -405,6 +474,7 @@ impl Transactor {
 		if rng.start > rng.end {
 			return Ok(vec![]);
 		}
+		self.flush_range(&rng).await?;
 		self.inner.scan(rng, limit, version).await
 	}
 
@@ -425,6 +495,7 @@ impl Transactor {
 		if rng.start > rng.end {
 			return Ok(vec![]);
 		}
+		self.flush_range(&rng).await?;
 		self.inner.scanr(rng, limit, version).await
 	}
 
@@ -445,6 +516,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "Batch");
+		self.flush_range(&rng).await?;
 		self.inner.batch_keys(rng, batch, version).await
 	}
 
@@ -460,6 +532,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), "Count");
+		self.flush_range(&rng).await?;
 		self.inner.count(rng).await
 	}
 
@@ -480,6 +553,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "Batch");
+		self.flush_range(&rng).await?;
 		self.inner.batch_keys_vals(rng, batch, version).await
 	}
 
@@ -499,6 +573,7 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg..end;
 		trace!(target: TARGET, rng = rng.sprint(), "BatchVersions");
+		self.flush_range(&rng).await?;
 		self.inner.batch_keys_vals_versions(rng, batch).await
 	}
 
@@ -512,6 +587,7 @@ impl Transactor {
 		K: KeyEncode + Debug,
 	{
 		let key = key.encode_owned()?;
+		self.flush_writes().await?;
 		self.inner.get_timestamp(key).await
 	}
 
@@ -530,19 +606,46 @@ impl Transactor {
 		let ts_key = ts_key.encode_owned()?;
 		let prefix = prefix.encode_owned()?;
 		let suffix = suffix.encode_owned()?;
+		self.flush_writes().await?;
 		self.inner.set_versionstamp(ts_key, prefix, suffix, val.into()).await
 	}
 
-	pub(crate) fn new_save_point(&mut self) {
-		self.inner.new_save_point()
+	pub(crate) async fn new_save_point(&mut self) -> Result<()> {
+		self.flush_writes().await?;
+		self.inner.new_save_point();
+		self.save_points.push(SavePoint {
+			released: 0,
+			changes: self.cf.checkpoint(),
+		});
+		Ok(())
 	}
 
 	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<()> {
+		// The buffered writes were made after the save point
+		self.flush_writes().await?;
+		if let Some(sp) = self.save_points.pop() {
+			// Roll back any released save points which are still held
+			for _ in 0..sp.released {
+				self.inner.rollback_to_save_point().await?;
+			}
+			// Discard any change feed mutations since the save point
+			self.cf.restore(&sp.changes);
+		}
 		self.inner.rollback_to_save_point().await
 	}
 
 	pub(crate) fn release_last_save_point(&mut self) -> Result<()> {
-		self.inner.release_last_save_point()
+		self.inner.release_last_save_point()?;
+		if let Some(sp) = self.save_points.pop() {
+			// Some transactions keep hold of released save points, so
+			// these need to be rolled back along with the parent
+			if !self.inner.releases_save_points() {
+				if let Some(parent) = self.save_points.last_mut() {
+					parent.released += sp.released + 1;
+				}
+			}
+		}
+		Ok(())
 	}
 }
 
@@ -550,6 +653,65 @@ impl Transactor {
 // Additional methods
 // --------------------------------------------------
 impl Transactor {
+	/// Start buffering the writes which don't depend on the current value
+	/// of a key, applying them to the transaction in batches.
+	///
+	/// This is used by the bulk statements, and must be matched by a call
+	/// to [`Transactor::end_write_batch`] once the statement has finished.
+	pub(crate) fn begin_write_batch(&mut self) {
+		self.writes.begin();
+	}
+
+	/// Stop buffering the writes of a statement, applying the buffered
+	/// writes once no other statement buffers its writes anymore.
+	pub(crate) async fn end_write_batch(&mut self) -> Result<()> {
+		if self.writes.end() {
+			self.flush_writes().await?;
+		}
+		Ok(())
+	}
+
+	/// Apply the buffered writes to the transaction
+	async fn flush_writes(&mut self) -> Result<()> {
+		if !self.writes.is_empty() {
+			let writes = self.writes.take();
+			trace!(target: TARGET, writes = writes.len(), "SetM");
+			self.inner.setm(writes).await?;
+		}
+		Ok(())
+	}
+
+	/// Apply the buffered writes once a batch is full
+	async fn flush_if_full(&mut self) -> Result<()> {
+		if self.writes.is_full() {
+			self.flush_writes().await?;
+		}
+		Ok(())
+	}
+
+	/// Apply the buffered writes before a key is read or written conditionally
+	async fn flush_key(&mut self, key: &[u8]) -> Result<()> {
+		if self.writes.contains(key) {
+			self.flush_writes().await?;
+		}
+		Ok(())
+	}
+
+	/// Apply the buffered writes before a range of keys is read or deleted
+	async fn flush_range(&mut self, rng: &Range<Key>) -> Result<()> {
+		if self.writes.overlaps(rng) {
+			self.flush_writes().await?;
+		}
+		Ok(())
+	}
+
+	/// Check that the writes can be buffered, as they would fail otherwise
+	fn check_writeable(&self) -> Result<()> {
+		ensure!(!self.inner.closed(), Error::TxFinished);
+		ensure!(self.inner.writeable(), Error::TxReadonly);
+		Ok(())
+	}
+
 	// change will record the change in the changefeed if enabled.
 	// To actually persist the record changes into the underlying kvs,
 	// you must call the `complete_changes` function and then commit the transaction.
@@ -563,8 +725,9 @@ impl Transactor {
 		previous: CursorValue,
 		current: CursorValue,
 		store_difference: bool,
+		audit: Option<ChangeAudit>,
 	) {
-		self.cf.record_cf_change(ns, db, tb, id.clone(), previous, current, store_difference)
+		self.cf.record_cf_change(ns, db, tb, id.clone(), previous, current, store_difference, audit)
 	}
 
 	// Records the table (re)definition in the changefeed if enabled.
@@ -578,6 +741,17 @@ impl Transactor {
 		self.cf.define_table(ns, db, tb, dt)
 	}
 
+	// Records the truncation of the table in the changefeed if enabled.
+	pub(crate) fn record_table_truncate(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		dt: &DefineTableStatement,
+	) {
+		self.cf.truncate_table(ns, db, tb, dt)
+	}
+
 	pub(crate) async fn get_idg(&mut self, key: &Key) -> Result<U32> {
 		Ok(if let Some(v) = self.stash.get(key) {
 			v
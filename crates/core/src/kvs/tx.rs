@@ -5,7 +5,8 @@ use super::Version;
 use super::batch::Batch;
 use super::tr::Check;
 use super::util;
-use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::cnf::COUNT_BATCH_SIZE;
+use crate::ctx::MutableContext;
 use crate::dbs::node::Node;
 use crate::err::Error;
 use crate::expr::Id;
@@ -25,23 +26,28 @@ use crate::expr::statements::DefineParamStatement;
 use crate::expr::statements::DefineTableStatement;
 use crate::expr::statements::DefineUserStatement;
 use crate::expr::statements::LiveStatement;
+use crate::expr::statements::analyze::TableStatistics;
 use crate::expr::statements::define::BucketDefinition;
 use crate::expr::statements::define::DefineConfigStatement;
-use crate::expr::statements::define::{ApiDefinition, DefineSequenceStatement};
+use crate::expr::statements::define::{
+	ApiDefinition, DefineScheduleStatement, DefineSequenceStatement,
+};
 use crate::idx::planner::ScanDirection;
 use crate::idx::trees::store::cache::IndexTreeCaches;
 use crate::key::database::sq::Sq;
 use crate::kvs::Transactor;
 use crate::kvs::cache;
 use crate::kvs::cache::tx::TransactionCache;
-use crate::kvs::scanner::Scanner;
+use crate::kvs::scanner::{FetchSize, Scanner};
+use crate::vs::VersionStamp;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use futures::lock::Mutex;
 use futures::lock::MutexGuard;
-use futures::stream::Stream;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
 #[non_exhaustive]
@@ -56,6 +62,16 @@ pub struct Transaction {
 	index_caches: IndexTreeCaches,
 	/// Does this supports reverse scan
 	reverse_scan: bool,
+	/// Can this transaction write to the datastore
+	writeable: bool,
+	/// Does this delete key ranges transactionally
+	transactional_range_delete: bool,
+	/// The time at which this transaction was started
+	timestamp: DateTime<Utc>,
+	/// The number of bytes read, when the reads are measured
+	bytes_read: Option<AtomicU64>,
+	/// The number of keys which the streams fetch in each batch
+	fetch_size: FetchSize,
 }
 
 impl Transaction {
@@ -64,9 +80,40 @@ impl Transaction {
 		Transaction {
 			local,
 			reverse_scan: tx.inner.supports_reverse_scan(),
+			writeable: tx.inner.writeable(),
+			transactional_range_delete: tx.inner.supports_transactional_range_delete(),
 			tx: Mutex::new(tx),
 			cache: TransactionCache::new(),
 			index_caches: IndexTreeCaches::default(),
+			timestamp: Utc::now(),
+			bytes_read: None,
+			fetch_size: FetchSize::default(),
+		}
+	}
+
+	/// Set the number of keys which the streams fetch in each batch
+	pub(crate) fn with_fetch_size(mut self, size: FetchSize) -> Self {
+		self.fetch_size = size;
+		self
+	}
+
+	/// Measure the number of bytes read by this transaction
+	pub(crate) fn with_read_metrics(mut self) -> Self {
+		self.bytes_read = Some(AtomicU64::new(0));
+		self
+	}
+
+	/// The number of bytes read so far, when the reads are measured
+	pub(crate) fn bytes_read(&self) -> Option<u64> {
+		self.bytes_read.as_ref().map(|v| v.load(Ordering::Relaxed))
+	}
+
+	/// Records the bytes read from the datastore, when the reads are measured.
+	/// The size is only computed when the reads are measured.
+	#[inline]
+	fn record_read(&self, size: impl FnOnce() -> usize) {
+		if let Some(v) = &self.bytes_read {
+			v.fetch_add(size() as u64, Ordering::Relaxed);
 		}
 	}
 
@@ -95,6 +142,24 @@ impl Transaction {
 		self.reverse_scan
 	}
 
+	/// Check if the transaction can write to the datastore
+	pub fn writeable(&self) -> bool {
+		self.writeable
+	}
+
+	/// Check if the transaction deletes key ranges transactionally
+	pub fn transactional_range_delete(&self) -> bool {
+		self.transactional_range_delete
+	}
+
+	/// Retrieve the time at which the transaction was started.
+	///
+	/// Records expire according to this time, so that the same
+	/// records remain visible for the whole of the transaction.
+	pub fn timestamp(&self) -> DateTime<Utc> {
+		self.timestamp
+	}
+
 	/// Check if the transaction is finished.
 	///
 	/// If the transaction has been canceled or committed,
@@ -136,7 +201,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.get(key, version).await
+		let res = self.lock().await.get(key, version).await?;
+		self.record_read(|| res.as_ref().map_or(0, |v| v.len()));
+		Ok(res)
 	}
 
 	/// Retrieve a batch set of keys from the datastore.
@@ -145,7 +212,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.getm(keys).await
+		let res = self.lock().await.getm(keys).await?;
+		self.record_read(|| res.iter().flatten().map(|v| v.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a specific prefix of keys from the datastore.
@@ -156,7 +225,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.getp(key).await
+		let res = self.lock().await.getp(key).await?;
+		self.record_read(|| res.iter().map(|(k, v)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -167,7 +238,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.getr(rng, version).await
+		let res = self.lock().await.getr(rng, version).await?;
+		self.record_read(|| res.iter().map(|(k, v)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	/// Delete a key from the datastore.
@@ -300,7 +373,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.keys(rng, limit, version).await
+		let res = self.lock().await.keys(rng, limit, version).await?;
+		self.record_read(|| res.iter().map(|k| k.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -316,7 +391,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.keysr(rng, limit, version).await
+		let res = self.lock().await.keysr(rng, limit, version).await?;
+		self.record_read(|| res.iter().map(|k| k.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -332,7 +409,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.scan(rng, limit, version).await
+		let res = self.lock().await.scan(rng, limit, version).await?;
+		self.record_read(|| res.iter().map(|(k, v)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
@@ -345,7 +424,9 @@ impl Transaction {
 	where
 		K: Into<Key> + Debug,
 	{
-		self.lock().await.scanr(rng, limit, version).await
+		let res = self.lock().await.scanr(rng, limit, version).await?;
+		self.record_read(|| res.iter().map(|(k, v)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	/// Count the total number of keys within a range in the datastore.
@@ -359,6 +440,36 @@ impl Transaction {
 		self.lock().await.count(rng).await
 	}
 
+	/// Count the total number of keys within a range in the datastore.
+	///
+	/// This function fetches the keys in batches, with multiple requests to the underlying datastore.
+	/// Before each batch the context is checked, returning an error if it has timed out.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn count_cancellable<K>(
+		&self,
+		ctx: &MutableContext,
+		rng: Range<K>,
+		version: Option<u64>,
+	) -> Result<usize>
+	where
+		K: KeyEncode + Debug,
+	{
+		let beg: Key = rng.start.encode_owned()?;
+		let end: Key = rng.end.encode_owned()?;
+		let mut len = 0;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Check if the context is finished
+			if ctx.should_stop()? {
+				break;
+			}
+			let res = self.batch_keys(rng, *COUNT_BATCH_SIZE, version).await?;
+			next = res.next;
+			len += res.result.len();
+		}
+		Ok(len)
+	}
+
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
 	///
 	/// This function fetches the keys in batches, with multiple requests to the underlying datastore.
@@ -372,7 +483,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.batch_keys(rng, batch, version).await
+		let res = self.lock().await.batch_keys(rng, batch, version).await?;
+		self.record_read(|| res.result.iter().map(|k| k.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -388,7 +501,9 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.batch_keys_vals(rng, batch, version).await
+		let res = self.lock().await.batch_keys_vals(rng, batch, version).await?;
+		self.record_read(|| res.result.iter().map(|(k, v)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -403,31 +518,43 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.batch_keys_vals_versions(rng, batch).await
+		let res = self.lock().await.batch_keys_vals_versions(rng, batch).await?;
+		self.record_read(|| res.result.iter().map(|(k, v, _, _)| k.len() + v.len()).sum());
+		Ok(res)
 	}
 
 	/// Retrieve a stream over a specific range of keys in the datastore.
 	///
 	/// This function fetches the key-value pairs in batches, with multiple requests to the underlying datastore.
+	/// The stream ends with an error if the context times out, or ends early if it is cancelled.
+	/// The batches grow while the stream is consumed, and a limit is fetched in a single batch.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
-	pub fn stream(
-		&self,
+	pub fn stream<'a>(
+		&'a self,
+		ctx: &'a MutableContext,
 		rng: Range<Vec<u8>>,
 		version: Option<u64>,
 		limit: Option<usize>,
 		sc: ScanDirection,
-	) -> impl Stream<Item = Result<(Key, Val)>> + '_ {
-		Scanner::<(Key, Val)>::new(self, *NORMAL_FETCH_SIZE, rng, version, limit, sc)
+	) -> Scanner<'a, (Key, Val)> {
+		Scanner::new(self, ctx, self.fetch_size, rng, version, limit, sc)
 	}
 
+	/// Retrieve a stream of keys over a specific range in the datastore.
+	///
+	/// This function fetches the keys in batches, with multiple requests to the underlying datastore.
+	/// The stream ends with an error if the context times out, or ends early if it is cancelled.
+	/// The batches grow while the stream is consumed, and a limit is fetched in a single batch.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
-	pub fn stream_keys(
-		&self,
+	pub fn stream_keys<'a>(
+		&'a self,
+		ctx: &'a MutableContext,
 		rng: Range<Vec<u8>>,
+		version: Option<u64>,
 		limit: Option<usize>,
 		sc: ScanDirection,
-	) -> impl Stream<Item = Result<Key>> + '_ {
-		Scanner::<Key>::new(self, *NORMAL_FETCH_SIZE, rng, None, limit, sc)
+	) -> Scanner<'a, Key> {
+		Scanner::new(self, ctx, self.fetch_size, rng, version, limit, sc)
 	}
 
 	// --------------------------------------------------
@@ -762,6 +889,28 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve all schedule definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_schedules(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineScheduleStatement]>> {
+		let qey = cache::tx::Lookup::Scs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_scs(),
+			None => {
+				let beg = crate::key::database::sc::prefix(ns, db)?;
+				let end = crate::key::database::sc::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Scs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all function definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_functions(
@@ -1164,6 +1313,31 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve the current versionstamp of a database, without incrementing it.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_versionstamp(&self, ns: &str, db: &str) -> Result<Option<VersionStamp>> {
+		let key = crate::key::database::vs::new(ns, db);
+		match self.get(key, None).await? {
+			Some(val) => Ok(Some(VersionStamp::from_slice(&val)?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Retrieve the statistics of a table, if it has been analyzed.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub(crate) async fn get_tb_statistics(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Option<TableStatistics>> {
+		let key = crate::key::table::st::new(ns, db, tb);
+		match self.get(key, None).await? {
+			Some(val) => Ok(Some(revision::from_slice(&val)?)),
+			None => Ok(None),
+		}
+	}
+
 	/// Retrieve a specific user definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_user(
@@ -1368,6 +1542,31 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve a specific schedule definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_schedule(
+		&self,
+		ns: &str,
+		db: &str,
+		sc: &str,
+	) -> Result<Arc<DefineScheduleStatement>> {
+		let qey = cache::tx::Lookup::Sc(ns, db, sc);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::sc::new(ns, db, sc).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::ScNotFound {
+					name: sc.to_owned(),
+				})?;
+				let val: DefineScheduleStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific function definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_function(
@@ -1661,6 +1860,39 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Fetch the version of a specific record, which is 0 if the record has never been written.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_record_version(&self, ns: &str, db: &str, tb: &str, id: &Id) -> Result<u64> {
+		let key = crate::key::table::vr::new(ns, db, tb, id);
+		match self.get(key, None).await? {
+			Some(val) => Ok(revision::from_slice(&val)?),
+			None => Ok(0),
+		}
+	}
+
+	/// Increment the version of a specific record, returning the new version.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn next_record_version(&self, ns: &str, db: &str, tb: &str, id: &Id) -> Result<u64> {
+		let version = self.get_record_version(ns, db, tb, id).await? + 1;
+		let key = crate::key::table::vr::new(ns, db, tb, id);
+		self.set(key, revision::to_vec(&version)?, None).await?;
+		Ok(version)
+	}
+
+	/// Set the version of a specific record.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn set_record_version(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		id: &Id,
+		version: u64,
+	) -> Result<()> {
+		let key = crate::key::table::vr::new(ns, db, tb, id);
+		self.set(key, revision::to_vec(&version)?, None).await
+	}
+
 	/// Get or add a namespace with a default configuration, only if we are in dynamic mode.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_or_add_ns(
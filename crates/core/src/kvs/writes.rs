@@ -0,0 +1,188 @@
+//! Batching of the writes of a bulk statement.
+//!
+//! While a bulk INSERT statement runs, the writes which don't depend on the
+//! current value of a key, such as the record values and the index entries,
+//! are buffered in the transaction, and are applied to the underlying
+//! transaction in batches, using the batch write of the storage engine. A
+//! read of a single key is answered from the buffer when the key was written
+//! in the batch, and any other read, or conditional write, of a buffered key
+//! or range first applies the buffered writes. The events, the live queries,
+//! and the other statements which run for each record therefore see exactly
+//! the same data as when every write is applied immediately.
+use super::{Key, Val};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// The writes which are buffered before they are applied to the transaction
+#[derive(Default)]
+pub(super) struct WriteBuffer {
+	/// The number of writes which are applied in each batch
+	size: usize,
+	/// The number of statements which are batching their writes
+	depth: usize,
+	/// The latest value of each written key, or None if it was deleted
+	writes: BTreeMap<Key, Option<Val>>,
+}
+
+impl WriteBuffer {
+	pub fn new(size: usize) -> Self {
+		Self {
+			size,
+			..Default::default()
+		}
+	}
+	/// Check if writes are currently buffered
+	pub fn is_active(&self) -> bool {
+		self.depth > 0 && self.size > 0
+	}
+	/// Check if nothing is buffered
+	pub fn is_empty(&self) -> bool {
+		self.writes.is_empty()
+	}
+	/// Check if a batch of writes should be applied
+	pub fn is_full(&self) -> bool {
+		self.writes.len() >= self.size
+	}
+	/// Start buffering the writes of a statement
+	pub fn begin(&mut self) {
+		self.depth += 1;
+	}
+	/// Stop buffering the writes of a statement, returning
+	/// true once no statement buffers its writes anymore
+	pub fn end(&mut self) -> bool {
+		self.depth = self.depth.saturating_sub(1);
+		self.depth == 0
+	}
+	/// Buffer the value of a key
+	pub fn set(&mut self, key: Key, val: Val) {
+		self.writes.insert(key, Some(val));
+	}
+	/// Buffer the deletion of a key
+	pub fn del(&mut self, key: Key) {
+		self.writes.insert(key, None);
+	}
+	/// Get the buffered write of a key, if it was written
+	pub fn get(&self, key: &[u8]) -> Option<Option<&Val>> {
+		self.writes.get(key).map(Option::as_ref)
+	}
+	/// Check if a key was written
+	pub fn contains(&self, key: &[u8]) -> bool {
+		self.writes.contains_key(key)
+	}
+	/// Check if any key within a range was written
+	pub fn overlaps(&self, rng: &Range<Key>) -> bool {
+		rng.start < rng.end && self.writes.range(rng.clone()).next().is_some()
+	}
+	/// Take the buffered writes, in key order
+	pub fn take(&mut self) -> Vec<(Key, Option<Val>)> {
+		std::mem::take(&mut self.writes).into_iter().collect()
+	}
+	/// Discard the buffered writes
+	pub fn clear(&mut self) {
+		self.writes.clear();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::err::Error;
+	use crate::expr::Value;
+	use crate::kvs::Datastore;
+	use crate::kvs::{LockType::*, TransactionType::*};
+
+	#[tokio::test]
+	async fn buffered_writes_are_read_back() {
+		let ds = Datastore::new("memory").await.unwrap().with_write_batch_size(3);
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		let mut txn = tx.lock().await;
+		txn.set("a", "1", None).await.unwrap();
+		txn.begin_write_batch();
+		txn.set("b", "2", None).await.unwrap();
+		txn.del("a").await.unwrap();
+		// Single keys are read from the buffer
+		assert_eq!(txn.get("b", None).await.unwrap(), Some(b"2".to_vec()));
+		assert!(!txn.exists("a", None).await.unwrap());
+		assert!(txn.writes.contains(b"b"));
+		// Inserting an existing key still fails
+		let err = txn.put("b", "3", None).await.unwrap_err();
+		assert!(matches!(err.downcast_ref(), Some(Error::TxKeyAlreadyExists)));
+		txn.put("a", "4", None).await.unwrap();
+		// Ranges apply the buffered writes before they are read
+		let keys = txn.keys("a".."c", 10, None).await.unwrap();
+		assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec()]);
+		assert!(txn.writes.is_empty());
+		// The writes are applied once the batch is full
+		for key in ["c", "d", "e"] {
+			txn.set(key, "5", None).await.unwrap();
+		}
+		assert!(txn.writes.is_empty());
+		txn.set("f", "6", None).await.unwrap();
+		txn.end_write_batch().await.unwrap();
+		assert!(txn.writes.is_empty());
+		drop(txn);
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("a", None).await.unwrap(), Some(b"4".to_vec()));
+		assert_eq!(tx.get("f", None).await.unwrap(), Some(b"6".to_vec()));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn batched_inserts_match_unbatched_inserts() {
+		let sql = "
+			DEFINE INDEX email ON person FIELDS email UNIQUE;
+			DEFINE INDEX age ON person FIELDS age;
+			DEFINE EVENT count ON person WHEN $event = 'CREATE' THEN {
+				UPSERT stats:person SET total = count(SELECT * FROM person), last = $after.id;
+			};
+			INSERT INTO person $rows;
+			SELECT VALUE total FROM stats;
+			SELECT count() FROM person WHERE age = 3 GROUP ALL;
+			INSERT INTO person [{ id: 600, email: 'a6' }];
+			INSERT IGNORE INTO person [{ id: 5, email: 'new' }, { id: 601, email: 'new' }];
+			SELECT VALUE id FROM person WHERE email = 'new';
+			INSERT INTO person [{ id: 2, age: 20 }, { id: 602 }] ON DUPLICATE KEY UPDATE age += 1;
+			SELECT VALUE age FROM person:2;
+		";
+		let rows: Vec<Value> = (0..500)
+			.map(|i| {
+				Value::from(map! {
+					"id".to_string() => Value::from(i),
+					"age".to_string() => Value::from(i % 10),
+					"email".to_string() => Value::from(format!("a{i}")),
+				})
+			})
+			.collect();
+		let vars = map! { "rows".to_string() => Value::from(rows) };
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let mut outputs = Vec::new();
+		for size in [0, 7] {
+			let ds = Datastore::new("memory").await.unwrap().with_write_batch_size(size);
+			let res = ds.execute(sql, &sess, Some(vars.clone())).await.unwrap();
+			let output: Vec<_> = res
+				.into_iter()
+				.map(|r| match r.result {
+					Ok(v) => v,
+					Err(e) => Value::from(e.to_string()),
+				})
+				.collect();
+			outputs.push(output);
+		}
+		let (unbatched, batched) = (&outputs[0], &outputs[1]);
+		assert_eq!(unbatched, batched);
+		// The event saw each record once it was written
+		assert_eq!(batched[4], value("[500]"));
+		// The index entries were written
+		assert_eq!(batched[5], value("[{ count: 50 }]"));
+		// The unique index still rejects duplicates
+		assert!(matches!(&batched[6], Value::Strand(s) if s.as_str().contains("already contains")));
+		assert_eq!(batched[8], value("[person:601]"));
+		// Existing records are updated
+		assert_eq!(batched[10], value("[3]"));
+	}
+
+	fn value(sql: &str) -> Value {
+		crate::syn::value(sql).unwrap().into()
+	}
+}
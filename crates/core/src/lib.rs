@@ -35,6 +35,8 @@ mod key;
 mod sys;
 
 pub mod api;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod cnf;
 pub mod ctx;
 pub mod dbs;
@@ -1,18 +1,33 @@
 #[cfg(not(target_family = "wasm"))]
 use crate::gql::SchemaCache;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 use uuid::Uuid;
 
 use super::Data;
 use super::Method;
+use super::PreparedQueries;
 use super::RpcError;
 use super::RpcProtocolV1;
 use super::RpcProtocolV2;
 use crate::dbs::Session;
+use crate::expr::Value;
 use crate::kvs::Datastore;
 use crate::sql::Array;
 
+/// How the notifications of a live query are coalesced into batches.
+///
+/// A batch is sent once it contains `size` notifications, or once its first
+/// notification has waited for `within`, whichever happens first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NotificationBatch {
+	/// The maximum number of notifications in a batch
+	pub size: usize,
+	/// The longest time a notification waits for the batch to fill up
+	pub within: Duration,
+}
+
 #[expect(async_fn_in_trait)]
 pub trait RpcContext {
 	/// The datastore for this RPC interface
@@ -37,6 +52,16 @@ pub trait RpcContext {
 	fn handle_live(&self, _lqid: &Uuid) -> impl std::future::Future<Output = ()> + Send {
 		async { unimplemented!("handle_live function must be implemented if LQ_SUPPORT = true") }
 	}
+	/// Batches the notifications of a LIVE statement which is about to be executed
+	fn handle_live_batch(
+		&self,
+		_lqid: &Uuid,
+		_batch: NotificationBatch,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async {
+			unimplemented!("handle_live_batch function must be implemented if LQ_SUPPORT = true")
+		}
+	}
 	/// Handles the execution of a KILL statement
 	fn handle_kill(&self, _lqid: &Uuid) -> impl std::future::Future<Output = ()> + Send {
 		async { unimplemented!("handle_kill function must be implemented if LQ_SUPPORT = true") }
@@ -59,6 +84,18 @@ pub trait RpcContext {
 		unimplemented!("graphql_schema_cache function must be implemented if GQL_SUPPORT = true")
 	}
 
+	// ------------------------------
+	// Prepared queries
+	// ------------------------------
+
+	/// Prepared queries are disabled by default
+	const PREPARED_SUPPORT: bool = false;
+
+	/// Returns the prepared queries of this connection
+	fn prepared_queries(&self) -> &PreparedQueries {
+		unimplemented!("prepared_queries function must be implemented if PREPARED_SUPPORT = true")
+	}
+
 	// ------------------------------
 	// Method execution
 	// ------------------------------
@@ -81,4 +118,22 @@ pub trait RpcContext {
 			_ => RpcProtocolV1::execute(self, method, params).await,
 		}
 	}
+
+	/// Executes a `query` method whose results are streamed, sending each
+	/// frame of the results to the channel as it is produced. This is only
+	/// supported in version 2 of the protocol, see [`super::is_streamed_query`].
+	async fn execute_streamed(
+		&self,
+		version: Option<u8>,
+		params: Array,
+		frames: tokio::sync::mpsc::Sender<Value>,
+	) -> Result<(), RpcError>
+	where
+		Self: RpcProtocolV2,
+	{
+		match version {
+			Some(2) => RpcProtocolV2::query_streamed(self, params, frames).await,
+			_ => Err(RpcError::InvalidParams),
+		}
+	}
 }
@@ -23,6 +23,12 @@ pub enum RpcError {
 	BadLQConfig,
 	#[error("A GraphQL request was made, but GraphQL is not supported by the context")]
 	BadGQLConfig,
+	#[error("A prepared query was used, but prepared queries are not supported by the context")]
+	BadPreparedConfig,
+	#[error(
+		"The prepared query was not found, it may have been evicted or prepared on another connection"
+	)]
+	PreparedQueryNotFound,
 	#[error("Error: {0}")]
 	Thrown(String),
 }
@@ -28,14 +28,23 @@ const TAG_CUSTOM_DURATION: u64 = 14;
 const TAG_FUTURE: u64 = 15;
 
 // Ranges (49->51 is unassigned)
+//
+// A range is encoded as a two element array of its start and end bounds,
+// where each bound is either tagged as included or excluded, wrapping the
+// value of the bound, or is `null` for an unbounded range side. Record id
+// ranges use the same encoding, with record id keys as the bound values.
 const TAG_RANGE: u64 = 49;
 const TAG_BOUND_INCLUDED: u64 = 50;
 const TAG_BOUND_EXCLUDED: u64 = 51;
 
 // Custom tags (55->60 is unassigned)
 const TAG_FILE: u64 = 55;
+// const TAG_BYTES_SUBTYPE: u64 = 56;
 
 // Custom Geometries (88->95 is unassigned)
+//
+// A geometry collection is encoded as an array of the tagged geometries
+// which it contains, and may itself contain nested geometry collections.
 const TAG_GEOMETRY_POINT: u64 = 88;
 const TAG_GEOMETRY_LINE: u64 = 89;
 const TAG_GEOMETRY_POLYGON: u64 = 90;
@@ -44,9 +53,52 @@ const TAG_GEOMETRY_MULTILINE: u64 = 92;
 const TAG_GEOMETRY_MULTIPOLYGON: u64 = 93;
 const TAG_GEOMETRY_COLLECTION: u64 = 94;
 
+// All of the tags which can be decoded
+const TAGS: &[u64] = &[
+	TAG_SPEC_DATETIME,
+	TAG_SPEC_UUID,
+	TAG_NONE,
+	TAG_TABLE,
+	TAG_RECORDID,
+	TAG_STRING_UUID,
+	TAG_STRING_DECIMAL,
+	TAG_CUSTOM_DATETIME,
+	TAG_STRING_DURATION,
+	TAG_CUSTOM_DURATION,
+	TAG_FUTURE,
+	TAG_RANGE,
+	TAG_BOUND_INCLUDED,
+	TAG_BOUND_EXCLUDED,
+	TAG_FILE,
+	TAG_GEOMETRY_POINT,
+	TAG_GEOMETRY_LINE,
+	TAG_GEOMETRY_POLYGON,
+	TAG_GEOMETRY_MULTIPOINT,
+	TAG_GEOMETRY_MULTILINE,
+	TAG_GEOMETRY_MULTIPOLYGON,
+	TAG_GEOMETRY_COLLECTION,
+];
+
 #[derive(Debug)]
 pub struct Cbor(pub CborData);
 
+impl Cbor {
+	/// Finds the first tag within this value which this version is unable
+	/// to decode, such as a tag allocated for a value type by a newer client
+	pub fn unsupported_tag(&self) -> Option<u64> {
+		fn find(v: &CborData) -> Option<u64> {
+			match v {
+				CborData::Tag(t, _) if !TAGS.contains(t) => Some(*t),
+				CborData::Tag(_, v) => find(v),
+				CborData::Array(v) => v.iter().find_map(find),
+				CborData::Map(v) => v.iter().find_map(|(k, v)| find(k).or_else(|| find(v))),
+				_ => None,
+			}
+		}
+		find(&self.0)
+	}
+}
+
 impl TryFrom<Cbor> for sql::SqlValue {
 	type Error = &'static str;
 	fn try_from(val: Cbor) -> Result<Self, &'static str> {
@@ -15,6 +15,17 @@ pub fn parse_value(val: Vec<u8>) -> Result<SqlValue, RpcError> {
 		.map_err(|_| RpcError::ParseError)
 		.map(Cbor)?;
 
+	convert(cbor)
+}
+
+/// Converts decoded CBOR data into a value, which is shared
+/// with the formats which use the same tag conventions
+pub(super) fn convert(cbor: Cbor) -> Result<SqlValue, RpcError> {
+	// Reject tags from newer versions of the protocol
+	if let Some(tag) = cbor.unsupported_tag() {
+		return Err(RpcError::Thrown(format!("unsupported tag {tag}")));
+	}
+
 	SqlValue::try_from(cbor).map_err(|v: &str| RpcError::Thrown(v.into()))
 }
 
@@ -33,3 +44,97 @@ pub fn res(res: impl ResTrait) -> Result<Vec<u8>, RpcError> {
 	// Return the message length, and message as binary
 	Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::{Bytes, File, Id, Table, Thing};
+	use crate::syn::Parse;
+	use rstest::rstest;
+
+	fn encode(val: SqlValue) -> Vec<u8> {
+		let val = Cbor::try_from(val).unwrap();
+		let mut res = Vec::new();
+		ciborium::into_writer(&val.0, &mut res).unwrap();
+		res
+	}
+
+	fn thing(id: Id) -> SqlValue {
+		SqlValue::Thing(Thing {
+			tb: "person".to_owned(),
+			id,
+		})
+	}
+
+	#[rstest]
+	#[case::none(SqlValue::None)]
+	#[case::null(SqlValue::Null)]
+	#[case::bool(SqlValue::Bool(true))]
+	#[case::int(SqlValue::from(i64::MIN))]
+	#[case::float(SqlValue::from(1.5))]
+	#[case::float_infinity(SqlValue::from(f64::INFINITY))]
+	#[case::decimal(SqlValue::parse("1.2300dec"))]
+	#[case::strand(SqlValue::from("foo"))]
+	#[case::duration(SqlValue::parse("1y2w3d4h5m6s7ms8us9ns"))]
+	#[case::datetime(SqlValue::parse("d'2024-02-29T12:34:56.789012345Z'"))]
+	#[case::uuid(SqlValue::parse("u'018f2a2e-59c1-7c6e-8b3e-0c1d2e3f4a5b'"))]
+	#[case::bytes(SqlValue::Bytes(Bytes(vec![0, 1, 2, 254, 255])))]
+	#[case::array(SqlValue::parse("[1, 'two', 3.5, NONE, NULL, [2dec]]"))]
+	#[case::object(SqlValue::parse("{ a: 1, b: { c: [1s, person:1] } }"))]
+	#[case::thing_number(thing(Id::from(1)))]
+	#[case::thing_string(thing(Id::from("tobie")))]
+	#[case::thing_uuid(SqlValue::parse("person:u'018f2a2e-59c1-7c6e-8b3e-0c1d2e3f4a5b'"))]
+	#[case::thing_array(SqlValue::parse("temperature:['London', d'2024-01-01T00:00:00Z']"))]
+	#[case::thing_object(SqlValue::parse("user:{ name: 'tobie', age: 30 }"))]
+	#[case::thing_range(SqlValue::Thing(crate::syn::thing_with_range("person:1..=10").unwrap()))]
+	#[case::table(SqlValue::Table(Table("person".to_owned())))]
+	#[case::range(SqlValue::parse("1..5"))]
+	#[case::range_inclusive(SqlValue::parse("1..=5"))]
+	#[case::range_exclusive_start(SqlValue::parse("1>..=5"))]
+	#[case::range_unbounded_start(SqlValue::parse("..5"))]
+	#[case::range_unbounded_end(SqlValue::parse("1.."))]
+	#[case::range_typed(SqlValue::parse("d'2024-01-01T00:00:00Z'..d'2025-01-01T00:00:00Z'"))]
+	#[case::geometry_point(SqlValue::parse("(-0.118092, 51.509865)"))]
+	#[case::geometry_line(SqlValue::parse(
+		"{ type: 'LineString', coordinates: [[1.0, 2.0], [3.0, 4.0]] }"
+	))]
+	#[case::geometry_polygon(SqlValue::parse(
+		"{ type: 'Polygon', coordinates: [[[-111.0, 45.0], [-111.0, 41.0], [-104.0, 41.0], [-111.0, 45.0]]] }"
+	))]
+	#[case::geometry_multipoint(SqlValue::parse(
+		"{ type: 'MultiPoint', coordinates: [[1.0, 2.0], [3.0, 4.0]] }"
+	))]
+	#[case::geometry_multiline(SqlValue::parse(
+		"{ type: 'MultiLineString', coordinates: [[[1.0, 2.0], [3.0, 4.0]]] }"
+	))]
+	#[case::geometry_multipolygon(SqlValue::parse(
+		"{ type: 'MultiPolygon', coordinates: [[[[-111.0, 45.0], [-111.0, 41.0], [-104.0, 41.0], [-111.0, 45.0]]]] }"
+	))]
+	#[case::geometry_collection(SqlValue::parse(
+		"{ type: 'GeometryCollection', geometries: [{ type: 'Point', coordinates: [1.0, 2.0] }, { type: 'GeometryCollection', geometries: [] }] }"
+	))]
+	#[case::future(SqlValue::parse("<future> { time::now() }"))]
+	#[case::file(SqlValue::File(File {
+		bucket: "bucket".to_owned(),
+		key: "/some/key.txt".to_owned(),
+	}))]
+	fn round_trip(#[case] value: SqlValue) {
+		let bytes = encode(value.clone());
+		let decoded = parse_value(bytes.clone()).unwrap();
+		assert_eq!(decoded, value);
+		// The value should encode into identical bytes again
+		assert_eq!(encode(decoded), bytes);
+	}
+
+	#[rstest]
+	#[case::top_level(Data::Tag(200, Box::new(Data::Null)))]
+	#[case::in_array(Data::Array(vec![Data::Integer(1.into()), Data::Tag(200, Box::new(Data::Null))]))]
+	#[case::in_map(Data::Map(vec![(Data::Text("a".to_owned()), Data::Tag(200, Box::new(Data::Null)))]))]
+	#[case::in_range(Data::Tag(49, Box::new(Data::Array(vec![Data::Tag(200, Box::new(Data::Null)), Data::Null]))))]
+	fn unsupported_tag(#[case] data: Data) {
+		let mut bytes = Vec::new();
+		ciborium::into_writer(&data, &mut bytes).unwrap();
+		let err = parse_value(bytes).unwrap_err();
+		assert!(matches!(err, RpcError::Thrown(e) if e == "unsupported tag 200"));
+	}
+}
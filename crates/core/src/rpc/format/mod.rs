@@ -1,6 +1,7 @@
 pub mod bincode;
 pub mod cbor;
 pub mod json;
+pub mod msgpack;
 pub mod revision;
 
 use ::revision::Revisioned;
@@ -9,9 +10,10 @@ use serde::Serialize;
 use super::{RpcError, request::Request};
 use crate::{expr::Value, sql::SqlValue};
 
-pub const PROTOCOLS: [&str; 4] = [
+pub const PROTOCOLS: [&str; 5] = [
 	"json",     // For basic JSON serialisation
 	"cbor",     // For basic CBOR serialisation
+	"msgpack",  // For basic MessagePack serialisation
 	"bincode",  // For full internal serialisation
 	"revision", // For full versioned serialisation
 ];
@@ -21,6 +23,7 @@ pub const PROTOCOLS: [&str; 4] = [
 pub enum Format {
 	Json,        // For basic JSON serialisation
 	Cbor,        // For basic CBOR serialisation
+	Msgpack,     // For basic MessagePack serialisation
 	Bincode,     // For full internal serialisation
 	Revision,    // For full versioned serialisation
 	Unsupported, // Unsupported format
@@ -35,8 +38,9 @@ impl From<&str> for Format {
 		match v {
 			s if s == PROTOCOLS[0] => Format::Json,
 			s if s == PROTOCOLS[1] => Format::Cbor,
-			s if s == PROTOCOLS[2] => Format::Bincode,
-			s if s == PROTOCOLS[3] => Format::Revision,
+			s if s == PROTOCOLS[2] => Format::Msgpack,
+			s if s == PROTOCOLS[3] => Format::Bincode,
+			s if s == PROTOCOLS[4] => Format::Revision,
 			_ => Format::Unsupported,
 		}
 	}
@@ -49,6 +53,7 @@ impl Format {
 		match self {
 			Self::Json => json::req(&val),
 			Self::Cbor => cbor::req(val),
+			Self::Msgpack => msgpack::req(val),
 			Self::Bincode => bincode::req(&val),
 			Self::Revision => revision::req(val),
 			Self::Unsupported => Err(RpcError::InvalidRequest),
@@ -60,6 +65,7 @@ impl Format {
 		match self {
 			Self::Json => json::res(val),
 			Self::Cbor => cbor::res(val),
+			Self::Msgpack => msgpack::res(val),
 			Self::Bincode => bincode::res(val),
 			Self::Revision => revision::res(val),
 			Self::Unsupported => Err(RpcError::InvalidRequest),
@@ -72,6 +78,7 @@ impl Format {
 		match self {
 			Self::Json => json::parse_value(&val),
 			Self::Cbor => cbor::parse_value(val),
+			Self::Msgpack => msgpack::parse_value(val),
 			Self::Bincode => bincode::parse_value(&val),
 			Self::Revision => revision::parse_value(val),
 			Self::Unsupported => Err(RpcError::InvalidRequest),
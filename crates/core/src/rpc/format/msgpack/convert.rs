@@ -0,0 +1,107 @@
+use ciborium::Value as CborData;
+use rmpv::Value as Data;
+
+use crate::rpc::format::cbor::Cbor;
+
+// The maximum depth of nested values, including
+// values nested within extension type payloads
+const MAX_DEPTH: usize = 1024;
+
+/// A MessagePack value, encoded using the same tag conventions as CBOR.
+///
+/// Every CBOR tag is encoded as a MessagePack extension type with the same
+/// number, where the extension payload is the MessagePack encoding of the
+/// tagged value. Values are decoded into CBOR data, and then converted into
+/// SurrealQL values by the CBOR conversion, so the formats can not drift.
+#[derive(Debug)]
+pub struct Pack(pub Data);
+
+impl TryFrom<Pack> for Cbor {
+	type Error = &'static str;
+	fn try_from(val: Pack) -> Result<Self, &'static str> {
+		decode(val.0, 0).map(Cbor)
+	}
+}
+
+impl TryFrom<Cbor> for Pack {
+	type Error = &'static str;
+	fn try_from(val: Cbor) -> Result<Self, &'static str> {
+		encode(val.0).map(Pack)
+	}
+}
+
+fn decode(val: Data, depth: usize) -> Result<CborData, &'static str> {
+	if depth > MAX_DEPTH {
+		return Err("Exceeded the maximum nesting depth of a MessagePack value");
+	}
+	match val {
+		Data::Nil => Ok(CborData::Null),
+		Data::Boolean(v) => Ok(CborData::Bool(v)),
+		Data::Integer(v) => match (v.as_i64(), v.as_u64()) {
+			(Some(v), _) => Ok(CborData::Integer(v.into())),
+			(_, Some(v)) => Ok(CborData::Integer(v.into())),
+			_ => Err("Expected a MessagePack integer within the 64 bit range"),
+		},
+		Data::F32(v) => Ok(CborData::Float(v.into())),
+		Data::F64(v) => Ok(CborData::Float(v)),
+		Data::String(v) => match v.into_str() {
+			Some(v) => Ok(CborData::Text(v)),
+			None => Err("Expected a MessagePack string with valid UTF-8"),
+		},
+		Data::Binary(v) => Ok(CborData::Bytes(v)),
+		Data::Array(v) => v
+			.into_iter()
+			.map(|v| decode(v, depth + 1))
+			.collect::<Result<Vec<_>, _>>()
+			.map(CborData::Array),
+		Data::Map(v) => v
+			.into_iter()
+			.map(|(k, v)| Ok((decode(k, depth + 1)?, decode(v, depth + 1)?)))
+			.collect::<Result<Vec<_>, &str>>()
+			.map(CborData::Map),
+		Data::Ext(t, v) => {
+			// Negative extension types are reserved by the spec
+			let Ok(t) = u64::try_from(t) else {
+				return Err("Encountered a reserved MessagePack extension type");
+			};
+			let v = rmpv::decode::read_value(&mut v.as_slice())
+				.map_err(|_| "Expected a valid MessagePack value as the extension payload")?;
+			Ok(CborData::Tag(t, Box::new(decode(v, depth + 1)?)))
+		}
+	}
+}
+
+fn encode(val: CborData) -> Result<Data, &'static str> {
+	match val {
+		CborData::Null => Ok(Data::Nil),
+		CborData::Bool(v) => Ok(Data::Boolean(v)),
+		CborData::Integer(v) => match (i64::try_from(v), u64::try_from(v)) {
+			(Ok(v), _) => Ok(Data::from(v)),
+			(_, Ok(v)) => Ok(Data::from(v)),
+			_ => Err("Cannot encode an integer outside of the 64 bit range into MessagePack"),
+		},
+		CborData::Float(v) => Ok(Data::F64(v)),
+		CborData::Text(v) => Ok(Data::from(v)),
+		CborData::Bytes(v) => Ok(Data::Binary(v)),
+		CborData::Array(v) => {
+			v.into_iter().map(encode).collect::<Result<Vec<_>, _>>().map(Data::Array)
+		}
+		CborData::Map(v) => v
+			.into_iter()
+			.map(|(k, v)| Ok((encode(k)?, encode(v)?)))
+			.collect::<Result<Vec<_>, &str>>()
+			.map(Data::Map),
+		CborData::Tag(t, v) => {
+			let Ok(t) = i8::try_from(t) else {
+				return Err(
+					"Cannot encode a tag outside of the extension type range into MessagePack",
+				);
+			};
+			let mut res = Vec::new();
+			rmpv::encode::write_value(&mut res, &encode(*v)?)
+				.map_err(|_| "Failed to encode a MessagePack extension payload")?;
+			Ok(Data::Ext(t, res))
+		}
+		_ => Err("Found unsupported CBOR data being encoded into a MessagePack value"),
+	}
+}
@@ -0,0 +1,100 @@
+mod convert;
+
+pub use convert::Pack;
+
+use crate::expr::Value;
+use crate::rpc::RpcError;
+use crate::rpc::format::cbor::{self, Cbor};
+use crate::rpc::request::Request;
+use crate::sql::SqlValue;
+
+use super::ResTrait;
+
+pub fn parse_value(val: Vec<u8>) -> Result<SqlValue, RpcError> {
+	let pack = rmpv::decode::read_value(&mut val.as_slice())
+		.map_err(|_| RpcError::ParseError)
+		.map(Pack)?;
+
+	let cbor = Cbor::try_from(pack).map_err(|v: &str| RpcError::Thrown(v.into()))?;
+
+	cbor::convert(cbor)
+}
+
+pub fn req(val: Vec<u8>) -> Result<Request, RpcError> {
+	parse_value(val)?.try_into()
+}
+
+pub fn res(res: impl ResTrait) -> Result<Vec<u8>, RpcError> {
+	// Convert the response into a value
+	let val: Value = res.into();
+	let val: Cbor = val.try_into()?;
+	let val: Pack = val.try_into()?;
+	// Create a new vector for encoding output
+	let mut res = Vec::new();
+	// Serialize the value into MessagePack binary data
+	rmpv::encode::write_value(&mut res, &val.0).map_err(|e| RpcError::Thrown(e.to_string()))?;
+	// Return the message length, and message as binary
+	Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::Parse;
+	use rmpv::Value as Data;
+	use rstest::rstest;
+
+	fn write(data: &Data) -> Vec<u8> {
+		let mut res = Vec::new();
+		rmpv::encode::write_value(&mut res, data).unwrap();
+		res
+	}
+
+	#[rstest]
+	#[case::none(SqlValue::None)]
+	#[case::null(SqlValue::Null)]
+	#[case::int(SqlValue::from(i64::MIN))]
+	#[case::float(SqlValue::from(1.5))]
+	#[case::decimal(SqlValue::parse("1.2300dec"))]
+	#[case::strand(SqlValue::from("foo"))]
+	#[case::duration(SqlValue::parse("1y2w3d4h5m6s7ms8us9ns"))]
+	#[case::datetime(SqlValue::parse("d'2024-02-29T12:34:56.789012345Z'"))]
+	#[case::uuid(SqlValue::parse("u'018f2a2e-59c1-7c6e-8b3e-0c1d2e3f4a5b'"))]
+	#[case::thing(SqlValue::parse("person:['London', d'2024-01-01T00:00:00Z']"))]
+	#[case::range(SqlValue::parse("1..=5"))]
+	#[case::geometry(SqlValue::parse("(-0.118092, 51.509865)"))]
+	#[case::object(SqlValue::parse("{ a: 1, b: { c: [1s, person:1, NONE] } }"))]
+	fn round_trip(#[case] value: SqlValue) {
+		let bytes = res(Value::from(value.clone())).unwrap();
+		assert_eq!(parse_value(bytes).unwrap(), value);
+	}
+
+	#[test]
+	fn extension_types() {
+		// A record id is encoded with the record id extension type
+		let bytes = res(Value::from(SqlValue::parse("person:1"))).unwrap();
+		let Data::Ext(8, payload) = rmpv::decode::read_value(&mut bytes.as_slice()).unwrap() else {
+			panic!("expected a record id extension type");
+		};
+		let payload = rmpv::decode::read_value(&mut payload.as_slice()).unwrap();
+		assert_eq!(payload, Data::Array(vec![Data::from("person"), Data::from(1)]));
+	}
+
+	#[rstest]
+	#[case::empty(vec![])]
+	#[case::truncated_array(vec![0x93, 0x01])]
+	#[case::truncated_string(vec![0xdb, 0x00, 0x00, 0x10, 0x00, 0x61])]
+	#[case::invalid_utf8(vec![0xa2, 0xc3, 0x28])]
+	#[case::reserved_marker(vec![0xc1])]
+	#[case::reserved_ext(write(&Data::Ext(-1, vec![0xc0])))]
+	#[case::unsupported_ext(write(&Data::Ext(100, vec![0xc0])))]
+	#[case::invalid_ext_payload(write(&Data::Ext(8, vec![0x93])))]
+	#[case::invalid_ext_value(write(&Data::Ext(8, vec![0x01])))]
+	#[case::deeply_nested_ext((0..2000).fold(vec![0xc0], |acc, _| write(&Data::Ext(6, acc))))]
+	#[case::deeply_nested_array([vec![0x91; 100_000], vec![0xc0]].concat())]
+	fn malformed(#[case] bytes: Vec<u8>) {
+		// Malformed input must produce an error instead of a panic
+		assert!(parse_value(bytes.clone()).is_err());
+		assert!(req(bytes).is_err());
+	}
+}
@@ -28,6 +28,9 @@ pub enum Method {
 	Run,
 	GraphQL,
 	InsertRelation,
+	Prepare,
+	Execute,
+	Unprepare,
 }
 
 impl Method {
@@ -79,6 +82,9 @@ impl Method {
 			"run" => Self::Run,
 			"graphql" => Self::GraphQL,
 			"insert_relation" => Self::InsertRelation,
+			"prepare" => Self::Prepare,
+			"execute" => Self::Execute,
+			"unprepare" => Self::Unprepare,
 			_ => Self::Unknown,
 		}
 	}
@@ -114,6 +120,9 @@ impl Method {
 			Self::Run => "run",
 			Self::GraphQL => "graphql",
 			Self::InsertRelation => "insert_relation",
+			Self::Prepare => "prepare",
+			Self::Execute => "execute",
+			Self::Unprepare => "unprepare",
 		}
 	}
 }
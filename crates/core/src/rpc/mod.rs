@@ -2,6 +2,7 @@ mod args;
 mod context;
 mod error;
 mod method;
+mod prepared;
 mod protocol;
 mod response;
 
@@ -9,12 +10,15 @@ pub mod format;
 pub mod request;
 pub(crate) mod statement_options;
 
+pub use context::NotificationBatch;
 pub use context::RpcContext;
 pub use error::RpcError;
 pub use format::Format;
 pub use method::Method;
+pub use prepared::PreparedQueries;
 pub use request::Request;
 pub use response::Data;
+pub use statement_options::is_streamed_query;
 
 pub use protocol::v1::RpcProtocolV1;
 pub use protocol::v2::RpcProtocolV2;
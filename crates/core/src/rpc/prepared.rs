@@ -0,0 +1,183 @@
+//! Prepared queries for the RPC connections.
+//!
+//! A query is prepared once on a connection, which returns a handle that the
+//! client then uses to execute the query with different variables, without
+//! the server parsing the query text again. The prepared queries are kept for
+//! each connection, in a cache which evicts the least recently used query once
+//! the limit in the capabilities is reached. A query is parsed again when it
+//! is executed after any table, field, index or event definition has changed.
+use crate::kvs::Datastore;
+use crate::rpc::RpcError;
+use crate::sql::{Query, SqlValue};
+use crate::syn;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A query which was prepared on a connection
+struct Prepared {
+	/// The text of the query
+	sql: String,
+	/// The parsed query
+	query: Query,
+	/// The catalog version which the query was parsed with
+	version: u64,
+	/// When the query was last used
+	used: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+	/// Incremented every time that a query is used
+	clock: u64,
+	/// The prepared queries, by handle
+	queries: HashMap<Uuid, Prepared>,
+}
+
+/// The prepared queries of a connection
+#[derive(Default)]
+pub struct PreparedQueries {
+	inner: Mutex<Inner>,
+}
+
+impl PreparedQueries {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses and stores a query, returning its handle
+	pub(crate) fn prepare(&self, kvs: &Datastore, sql: String) -> Result<Uuid, RpcError> {
+		// Check if prepared queries are enabled
+		let limit = kvs.get_capabilities().prepared_queries();
+		if limit == 0 {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Parse the query before it is stored
+		let version = kvs.get_cache().catalog_version();
+		let query = syn::parse_with_capabilities(&sql, kvs.get_capabilities())?;
+		let id = Uuid::new_v4();
+		let mut inner = self.inner.lock();
+		// Evict the least recently used queries
+		while inner.queries.len() >= limit {
+			let Some(oldest) = inner.queries.iter().min_by_key(|(_, q)| q.used).map(|(k, _)| *k)
+			else {
+				break;
+			};
+			inner.queries.remove(&oldest);
+		}
+		inner.clock += 1;
+		let used = inner.clock;
+		inner.queries.insert(
+			id,
+			Prepared {
+				sql,
+				query,
+				version,
+				used,
+			},
+		);
+		Ok(id)
+	}
+
+	/// Retrieves a prepared query for execution, parsing
+	/// it again if the definitions have changed since
+	pub(crate) fn get(&self, kvs: &Datastore, id: &Uuid) -> Result<Query, RpcError> {
+		let version = kvs.get_cache().catalog_version();
+		let mut inner = self.inner.lock();
+		inner.clock += 1;
+		let used = inner.clock;
+		let Some(prepared) = inner.queries.get_mut(id) else {
+			return Err(RpcError::PreparedQueryNotFound);
+		};
+		prepared.used = used;
+		if prepared.version != version {
+			prepared.query = syn::parse_with_capabilities(&prepared.sql, kvs.get_capabilities())?;
+			prepared.version = version;
+		}
+		Ok(prepared.query.clone())
+	}
+
+	/// Removes a prepared query, returning whether it existed
+	pub(crate) fn remove(&self, id: &Uuid) -> bool {
+		self.inner.lock().queries.remove(id).is_some()
+	}
+
+	/// The number of prepared queries
+	pub fn len(&self) -> usize {
+		self.inner.lock().queries.len()
+	}
+
+	/// Checks if there are no prepared queries
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+/// Parses the handle of a prepared query from the method arguments
+pub(crate) fn handle(value: SqlValue) -> Result<Uuid, RpcError> {
+	match value {
+		SqlValue::Uuid(v) => Ok(v.0),
+		SqlValue::Strand(v) => Uuid::parse_str(&v).map_err(|_| RpcError::InvalidParams),
+		_ => Err(RpcError::InvalidParams),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::{Capabilities, Session};
+
+	#[tokio::test]
+	async fn least_recently_used_queries_are_evicted() {
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_prepared_queries(2));
+		let cache = PreparedQueries::new();
+		let a = cache.prepare(&ds, "SELECT * FROM a".to_string()).unwrap();
+		let b = cache.prepare(&ds, "SELECT * FROM b".to_string()).unwrap();
+		// Using the first query makes the second the least recently used
+		cache.get(&ds, &a).unwrap();
+		let c = cache.prepare(&ds, "SELECT * FROM c".to_string()).unwrap();
+		assert_eq!(cache.len(), 2);
+		assert_eq!(cache.get(&ds, &a).unwrap().to_string(), "SELECT * FROM a;");
+		assert!(matches!(cache.get(&ds, &b), Err(RpcError::PreparedQueryNotFound)));
+		assert_eq!(cache.get(&ds, &c).unwrap().to_string(), "SELECT * FROM c;");
+		// Removed queries are rejected
+		assert!(cache.remove(&c));
+		assert!(!cache.remove(&c));
+		assert!(matches!(cache.get(&ds, &c), Err(RpcError::PreparedQueryNotFound)));
+	}
+
+	#[tokio::test]
+	async fn queries_are_parsed_again_after_definitions_change() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE person SET age = 10", &sess, None).await.unwrap();
+		let cache = PreparedQueries::new();
+		let id = cache.prepare(&ds, "SELECT * FROM person WHERE age > $age".to_string()).unwrap();
+		let version = |cache: &PreparedQueries| cache.inner.lock().queries[&id].version;
+		let prepared = version(&cache);
+		// Changes to the data do not change the catalog version
+		ds.execute("CREATE person SET age = 20", &sess, None).await.unwrap();
+		cache.get(&ds, &id).unwrap();
+		assert_eq!(version(&cache), prepared);
+		// Changes to the indexes invalidate the prepared query
+		ds.execute("DEFINE INDEX age ON person FIELDS age", &sess, None).await.unwrap();
+		assert_ne!(ds.get_cache().catalog_version(), prepared);
+		let query = cache.get(&ds, &id).unwrap();
+		assert_eq!(version(&cache), ds.get_cache().catalog_version());
+		assert_eq!(query.to_string(), "SELECT * FROM person WHERE age > $age;");
+	}
+
+	#[tokio::test]
+	async fn prepared_queries_can_be_disabled() {
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capabilities(Capabilities::default().with_prepared_queries(0));
+		let cache = PreparedQueries::new();
+		let res = cache.prepare(&ds, "SELECT * FROM a".to_string());
+		assert!(matches!(res, Err(RpcError::MethodNotAllowed)));
+	}
+}
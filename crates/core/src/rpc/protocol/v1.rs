@@ -12,6 +12,7 @@ use crate::rpc::Data;
 use crate::rpc::Method;
 use crate::rpc::RpcContext;
 use crate::rpc::RpcError;
+use crate::rpc::prepared;
 use crate::{
 	dbs::{QueryType, Response, capabilities::MethodTarget},
 	expr::Value,
@@ -68,6 +69,9 @@ pub trait RpcProtocolV1: RpcContext {
 			Method::Run => self.run(params).await,
 			Method::GraphQL => self.graphql(params).await,
 			Method::InsertRelation => self.insert_relation(params).await,
+			Method::Prepare => self.prepare(params).await,
+			Method::Execute => self.execute_prepared(params).await,
+			Method::Unprepare => self.unprepare(params).await,
 			_ => Err(RpcError::MethodNotFound),
 		}
 	}
@@ -344,6 +348,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the SQL query string
 		let sql = KillStatement {
 			id,
+			..Default::default()
 		}
 		.into();
 		// Specify the query parameters
@@ -814,6 +819,76 @@ pub trait RpcProtocolV1: RpcContext {
 		self.query_inner(query, vars).await.map(Into::into).map_err(RpcError::from)
 	}
 
+	// ------------------------------
+	// Methods for prepared queries
+	// ------------------------------
+
+	async fn prepare(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok(SqlValue::Strand(query)) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse and store the query
+		let id = self.prepared_queries().prepare(self.kvs(), query.0)?;
+		// Return the handle of the query
+		Ok(Value::from(id).into())
+	}
+
+	async fn execute_prepared(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok((id, vars)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Retrieve the prepared query
+		let id = prepared::handle(id)?;
+		let query = self.prepared_queries().get(self.kvs(), &id)?;
+		// Specify the query variables
+		let vars = match vars {
+			SqlValue::Object(v) => {
+				let mut v: crate::expr::Object = v.into();
+				Some(mrg! {v.0, self.session().parameters})
+			}
+			SqlValue::None | SqlValue::Null => Some(self.session().parameters.clone()),
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// Execute the prepared query
+		self.query_inner(SqlValue::Query(query), vars).await.map(Into::into).map_err(RpcError::from)
+	}
+
+	async fn unprepare(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok(id) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Remove the prepared query
+		let id = prepared::handle(id)?;
+		if !self.prepared_queries().remove(&id) {
+			return Err(RpcError::PreparedQueryNotFound);
+		}
+		// Return nothing
+		Ok(Value::None.into())
+	}
+
 	// ------------------------------
 	// Methods for running functions
 	// ------------------------------
@@ -963,13 +1038,14 @@ pub trait RpcProtocolV1: RpcContext {
 			_ => return Err(RpcError::InvalidParams),
 		};
 		// Process and cache the graphql schema
+		let session = self.session();
 		let schema = self
 			.graphql_schema_cache()
-			.get_schema(&self.session())
+			.get_schema(&session)
 			.await
 			.map_err(|e| RpcError::Thrown(e.to_string()))?;
-		// Execute the request against the schema
-		let res = schema.execute(req).await;
+		// Execute the request against the schema, as the current session
+		let res = schema.execute(req.data((*session).clone())).await;
 		// Serialize the graphql response
 		let out = if pretty {
 			let mut buf = Vec::new();
@@ -1016,13 +1092,23 @@ pub trait RpcProtocolV1: RpcContext {
 			QueryType::Live => {
 				if let Ok(Value::Uuid(lqid)) = &res.result {
 					self.handle_live(&lqid.0).await;
+					// Send the initial state of an aggregate live query
+					if let Err(err) = self.kvs().live_query_snapshot(lqid.0).await {
+						warn!("Failed to send the initial state of live query {lqid}: {err}");
+					}
 				}
 			}
-			QueryType::Kill => {
-				if let Ok(Value::Uuid(lqid)) = &res.result {
-					self.handle_kill(&lqid.0).await;
+			QueryType::Kill => match &res.result {
+				Ok(Value::Uuid(lqid)) => self.handle_kill(&lqid.0).await,
+				Ok(Value::Array(lqids)) => {
+					for lqid in lqids.iter() {
+						if let Value::Uuid(lqid) = lqid {
+							self.handle_kill(&lqid.0).await;
+						}
+					}
 				}
-			}
+				_ => {}
+			},
 			_ => {}
 		}
 	}
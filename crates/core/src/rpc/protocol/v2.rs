@@ -10,10 +10,12 @@ use crate::rpc::Data;
 use crate::rpc::Method;
 use crate::rpc::RpcContext;
 use crate::rpc::RpcError;
-use crate::rpc::statement_options::StatementOptions;
+use crate::rpc::prepared;
+use crate::rpc::statement_options::{QueryOptions, StatementOptions};
 use crate::sql::Uuid;
+use crate::syn;
 use crate::{
-	dbs::{QueryType, Response, capabilities::MethodTarget},
+	dbs::{QueryFrame, QueryType, Response, Session, capabilities::MethodTarget},
 	expr::Value,
 	rpc::args::Take,
 	sql::{
@@ -71,6 +73,9 @@ pub trait RpcProtocolV2: RpcContext {
 			Method::Relate => self.relate(params).await,
 			Method::Run => self.run(params).await,
 			Method::GraphQL => self.graphql(params).await,
+			Method::Prepare => self.prepare(params).await,
+			Method::Execute => self.execute_prepared(params).await,
+			Method::Unprepare => self.unprepare(params).await,
 			_ => Err(RpcError::MethodNotFound),
 		}
 	}
@@ -342,6 +347,7 @@ pub trait RpcProtocolV2: RpcContext {
 		// Specify the SQL query string
 		let sql = KillStatement {
 			id,
+			..Default::default()
 		}
 		.into();
 		// Specify the query parameters
@@ -367,9 +373,17 @@ pub trait RpcProtocolV2: RpcContext {
 		}
 		// Specify the query parameters
 		let var = Some(opts.merge_vars(&self.session().parameters));
+		// Batch the notifications before any can be sent
+		let id = Uuid::new_v4();
+		if let Some(batch) = opts.batch {
+			if !Self::LQ_SUPPORT {
+				return Err(RpcError::BadLQConfig);
+			}
+			self.handle_live_batch(&id.0, batch).await;
+		}
 		// Specify the SQL query string
 		let sql = LiveStatement {
-			id: Uuid::new_v4(),
+			id,
 			node: Uuid::new_v4(),
 			what: what.could_be_table(),
 			expr: if opts.diff {
@@ -379,13 +393,19 @@ pub trait RpcProtocolV2: RpcContext {
 			},
 			cond: opts.cond,
 			fetch: opts.fetch,
+			snapshot: opts.snapshot,
 			..Default::default()
 		}
 		.into();
 		// Execute the query on the database
-		let mut res = self.query_inner(SqlValue::Query(sql), var).await?;
+		let res = self.query_inner(SqlValue::Query(sql), var).await;
 		// Extract the first query result
-		Ok(res.remove(0).result?.into())
+		let res = res.and_then(|mut res| res.remove(0).result.map_err(Into::into));
+		// Forget the batching of a live query which was never started
+		if res.is_err() && opts.batch.is_some() {
+			self.handle_kill(&id.0).await;
+		}
+		Ok(res?.into())
 	}
 
 	// ------------------------------
@@ -766,24 +786,158 @@ pub trait RpcProtocolV2: RpcContext {
 			return Err(RpcError::MethodNotAllowed);
 		}
 		// Process the method arguments
-		let Ok((query, vars)) = params.needs_one_or_two() else {
+		let Ok((query, vars, opts)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Check the query input type
 		if !(query.is_query() || query.is_strand()) {
 			return Err(RpcError::InvalidParams);
 		}
+		// Process the execution options
+		let opts = QueryOptions::process_options(opts)?;
 		// Specify the query variables
-		let vars = match vars {
-			SqlValue::Object(v) => {
-				let mut v: crate::expr::Object = v.into();
-				Some(mrg! {v.0, self.session().parameters})
+		let vars = self.query_variables(vars)?;
+		// Execute the specified query
+		let session = opts.session(&self.session());
+		self.query_inner_with_session(query, vars, &session).await.map(Into::into)
+	}
+
+	/// Executes a `query` method whose results are streamed, sending each
+	/// frame of the results to the channel as it is produced. The rows of
+	/// the SELECT statements are sent in `rows` frames while they run, each
+	/// statement ends with a `done` frame, and the results end with an `end`
+	/// frame. The query is cancelled if the channel closes.
+	async fn query_streamed(
+		&self,
+		params: Array,
+		frames: tokio::sync::mpsc::Sender<Value>,
+	) -> Result<(), RpcError> {
+		// Check if capabilities allow executing queries
+		if !self.kvs().allows_rpc_method(&MethodTarget {
+			method: Method::Query,
+		}) {
+			warn!("Capabilities denied RPC method call attempt, target: '{}'", Method::Query);
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Process the method arguments
+		let Ok((query, vars, opts)) = params.needs_one_two_or_three() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Process the execution options
+		let opts = QueryOptions::process_options(opts)?;
+		// Specify the query variables
+		let vars = self.query_variables(vars)?;
+		let session = opts.session(&self.session());
+		// If no live query handler force realtime off
+		if !Self::LQ_SUPPORT && session.rt {
+			return Err(RpcError::BadLQConfig);
+		}
+		// Parse the query before anything is sent
+		let query = match query {
+			SqlValue::Query(sql) => sql,
+			SqlValue::Strand(sql) => {
+				syn::parse_with_capabilities(&sql, self.kvs().get_capabilities())?
 			}
-			SqlValue::None | SqlValue::Null => Some(self.session().parameters.clone()),
 			_ => return Err(RpcError::InvalidParams),
 		};
-		// Execute the specified query
-		self.query_inner(query, vars).await.map(Into::into)
+		// The frames are sent while the query runs
+		let (send, recv) = async_channel::bounded(1);
+		let run = self.kvs().process_streamed(query, &session, vars, send);
+		let forward = async {
+			while let Ok(frame) = recv.recv().await {
+				if let QueryFrame::Done {
+					response,
+					..
+				} = &frame
+				{
+					self.handle_live_query_results(response).await;
+				}
+				// Dropping the receiver cancels the query
+				if frames.send(frame.into_value()?).await.is_err() {
+					break;
+				}
+			}
+			Ok::<_, RpcError>(())
+		};
+		let (res, sent) = futures::join!(run, forward);
+		res?;
+		sent?;
+		// Mark the end of the results
+		let end = Value::from(map! {
+			"frame".to_string() => Value::from("end"),
+		});
+		let _ = frames.send(end).await;
+		Ok(())
+	}
+
+	// ------------------------------
+	// Methods for prepared queries
+	// ------------------------------
+
+	async fn prepare(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok(SqlValue::Strand(query)) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse and store the query
+		let id = self.prepared_queries().prepare(self.kvs(), query.0)?;
+		// Return the handle of the query
+		Ok(Value::from(id).into())
+	}
+
+	async fn execute_prepared(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok((id, vars, opts)) = params.needs_one_two_or_three() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Retrieve the prepared query
+		let id = prepared::handle(id)?;
+		let query = self.prepared_queries().get(self.kvs(), &id)?;
+		// Process the execution options
+		let opts = QueryOptions::process_options(opts)?;
+		// Specify the query variables
+		let vars = self.query_variables(vars)?;
+		// Execute the prepared query
+		let session = opts.session(&self.session());
+		self.query_inner_with_session(SqlValue::Query(query), vars, &session).await.map(Into::into)
+	}
+
+	async fn unprepare(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if this context supports prepared queries
+		if !Self::PREPARED_SUPPORT {
+			return Err(RpcError::BadPreparedConfig);
+		}
+		// Process the method arguments
+		let Ok(id) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Remove the prepared query
+		let id = prepared::handle(id)?;
+		if !self.prepared_queries().remove(&id) {
+			return Err(RpcError::PreparedQueryNotFound);
+		}
+		// Return nothing
+		Ok(Value::None.into())
 	}
 
 	// ------------------------------
@@ -935,13 +1089,14 @@ pub trait RpcProtocolV2: RpcContext {
 			_ => return Err(RpcError::InvalidParams),
 		};
 		// Process and cache the graphql schema
+		let session = self.session();
 		let schema = self
 			.graphql_schema_cache()
-			.get_schema(&self.session())
+			.get_schema(&session)
 			.await
 			.map_err(|e| RpcError::Thrown(e.to_string()))?;
-		// Execute the request against the schema
-		let res = schema.execute(req).await;
+		// Execute the request against the schema, as the current session
+		let res = schema.execute(req.data((*session).clone())).await;
 		// Serialize the graphql response
 		let out = if pretty {
 			let mut buf = Vec::new();
@@ -960,19 +1115,42 @@ pub trait RpcProtocolV2: RpcContext {
 	// Private methods
 	// ------------------------------
 
+	/// The query variables, which take precedence over
+	/// the session parameters for this query only
+	fn query_variables(&self, vars: SqlValue) -> Result<Option<BTreeMap<String, Value>>, RpcError> {
+		match vars {
+			SqlValue::Object(v) => {
+				let v: crate::expr::Object = v.into();
+				let mut vars = self.session().parameters.clone();
+				Ok(Some(mrg! {vars, v.0}))
+			}
+			SqlValue::None | SqlValue::Null => Ok(Some(self.session().parameters.clone())),
+			_ => Err(RpcError::InvalidParams),
+		}
+	}
+
 	async fn query_inner(
 		&self,
 		query: SqlValue,
 		vars: Option<BTreeMap<String, Value>>,
+	) -> Result<Vec<Response>, RpcError> {
+		self.query_inner_with_session(query, vars, &self.session()).await
+	}
+
+	async fn query_inner_with_session(
+		&self,
+		query: SqlValue,
+		vars: Option<BTreeMap<String, Value>>,
+		session: &Session,
 	) -> Result<Vec<Response>, RpcError> {
 		// If no live query handler force realtime off
-		if !Self::LQ_SUPPORT && self.session().rt {
+		if !Self::LQ_SUPPORT && session.rt {
 			return Err(RpcError::BadLQConfig);
 		}
 		// Execute the query on the database
 		let res = match query {
-			SqlValue::Query(sql) => self.kvs().process(sql, &self.session(), vars).await?,
-			SqlValue::Strand(sql) => self.kvs().execute(&sql, &self.session(), vars).await?,
+			SqlValue::Query(sql) => self.kvs().process(sql, session, vars).await?,
+			SqlValue::Strand(sql) => self.kvs().execute(&sql, session, vars).await?,
 			_ => {
 				return Err(RpcError::from(anyhow::Error::new(Error::unreachable(
 					"Unexpected query type: {query:?}",
@@ -994,13 +1172,23 @@ pub trait RpcProtocolV2: RpcContext {
 			QueryType::Live => {
 				if let Ok(Value::Uuid(lqid)) = &res.result {
 					self.handle_live(&lqid.0).await;
+					// Send the initial state of an aggregate live query
+					if let Err(err) = self.kvs().live_query_snapshot(lqid.0).await {
+						warn!("Failed to send the initial state of live query {lqid}: {err}");
+					}
 				}
 			}
-			QueryType::Kill => {
-				if let Ok(Value::Uuid(lqid)) = &res.result {
-					self.handle_kill(&lqid.0).await;
+			QueryType::Kill => match &res.result {
+				Ok(Value::Uuid(lqid)) => self.handle_kill(&lqid.0).await,
+				Ok(Value::Array(lqids)) => {
+					for lqid in lqids.iter() {
+						if let Value::Uuid(lqid) = lqid {
+							self.handle_kill(&lqid.0).await;
+						}
+					}
 				}
-			}
+				_ => {}
+			},
 			_ => {}
 		}
 	}
@@ -18,6 +18,9 @@ pub enum Data {
 	Query(Vec<dbs::Response>),
 	/// Live queries return a notification
 	Live(Notification),
+	/// Live queries which opted into batching return an array of
+	/// notifications, in the order in which they were produced
+	LiveBatch(Vec<Notification>),
 	// Add new variants here
 }
 
@@ -39,6 +42,12 @@ impl From<Notification> for Data {
 	}
 }
 
+impl From<Vec<Notification>> for Data {
+	fn from(v: Vec<Notification>) -> Self {
+		Data::LiveBatch(v)
+	}
+}
+
 impl From<Vec<dbs::Response>> for Data {
 	fn from(v: Vec<dbs::Response>) -> Self {
 		Data::Query(v)
@@ -52,6 +61,7 @@ impl TryFrom<Data> for Value {
 		match val {
 			Data::Query(v) => expr::to_value(v),
 			Data::Live(v) => expr::to_value(v),
+			Data::LiveBatch(v) => expr::to_value(v),
 			Data::Other(v) => Ok(v),
 		}
 	}
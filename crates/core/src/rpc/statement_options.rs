@@ -1,16 +1,19 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use crate::{
-	dbs::{Capabilities, sql_variables_to_expr_variables},
+	dbs::{Capabilities, Session, sql_variables_to_expr_variables},
 	expr::Value,
-	sql::{Cond, Data, Fetchs, Fields, Limit, Number, Output, SqlValue, Start, Timeout, Version},
+	sql::{
+		Array, Cond, Data, Fetchs, Fields, Limit, Number, Output, SqlValue, Start, Timeout, Version,
+	},
 	syn::{
 		fetchs_with_capabilities, fields_with_capabilities, output_with_capabilities,
 		value_with_capabilities,
 	},
 };
 
-use super::RpcError;
+use super::{Method, NotificationBatch, RpcError};
 
 #[derive(Clone, Debug)]
 pub(crate) enum RpcData {
@@ -103,6 +106,14 @@ pub(crate) struct StatementOptions {
 	/// - A string, containing fields to fetch.
 	/// - For the `select` and `live` methods
 	pub fetch: Option<Fetchs>,
+	/// - An object, with the maximum `size` of a batch of notifications, and
+	///   the duration `within` which a batch is sent even if it is not full
+	/// - For the `live` method
+	pub batch: Option<NotificationBatch>,
+	/// - A boolean, stating whether the records which currently match the
+	///   live query are sent before any changes
+	/// - For the `live` method
+	pub snapshot: bool,
 }
 
 impl StatementOptions {
@@ -261,6 +272,34 @@ impl StatementOptions {
 				}
 			}
 
+			// Process "snapshot" option
+			if let Some(v) = obj.remove("snapshot") {
+				if let SqlValue::Bool(v) = v {
+					self.snapshot = v;
+				} else {
+					return Err(RpcError::InvalidParams);
+				}
+			}
+
+			// Process "batch" option
+			if let Some(v) = obj.remove("batch") {
+				let SqlValue::Object(mut v) = v else {
+					return Err(RpcError::InvalidParams);
+				};
+				let size = match v.remove("size") {
+					Some(SqlValue::Number(Number::Int(v))) if v > 0 => v as usize,
+					_ => return Err(RpcError::InvalidParams),
+				};
+				let within = match v.remove("within") {
+					Some(v) => duration(v)?,
+					_ => return Err(RpcError::InvalidParams),
+				};
+				self.batch = Some(NotificationBatch {
+					size,
+					within,
+				});
+			}
+
 			Ok(self)
 		} else {
 			Err(RpcError::InvalidParams)
@@ -281,3 +320,172 @@ impl StatementOptions {
 		}
 	}
 }
+
+/// Parses a duration, which is sent as a string in JSON
+fn duration(v: SqlValue) -> Result<Duration, RpcError> {
+	match v {
+		SqlValue::Duration(v) => Ok(v.0),
+		SqlValue::Strand(v) => match crate::sql::Duration::try_from(v.as_str()) {
+			Ok(v) => Ok(v.0),
+			Err(_) => Err(RpcError::InvalidParams),
+		},
+		_ => Err(RpcError::InvalidParams),
+	}
+}
+
+/// Execution options for the `query` method.
+///
+/// Fields which are not recognised are ignored, so that clients can send
+/// options which are only supported by newer servers.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct QueryOptions {
+	/// - A duration, or a string containing a duration, stating how long the whole query can run
+	/// - Capped by the query timeout of the server
+	pub timeout: Option<Duration>,
+	/// - A boolean, stating whether statements which could modify data are rejected
+	pub readonly: bool,
+	/// - A boolean, stating whether execution statistics are returned with each statement result
+	pub stats: bool,
+	/// - A boolean, stating whether the results are sent in frames as they are produced
+	pub stream: bool,
+	/// - A boolean, stating whether the query is validated without writing any of its changes
+	pub dry_run: bool,
+}
+
+impl QueryOptions {
+	pub(crate) fn process_options(opts: SqlValue) -> Result<Self, RpcError> {
+		let mut res = Self::default();
+		let mut obj = match opts {
+			SqlValue::Object(obj) => obj,
+			SqlValue::None | SqlValue::Null => return Ok(res),
+			_ => return Err(RpcError::InvalidParams),
+		};
+
+		// Process "timeout" option
+		if let Some(v) = obj.remove("timeout") {
+			res.timeout = Some(duration(v)?);
+		}
+
+		// Process "readonly" option
+		if let Some(v) = obj.remove("readonly") {
+			if let SqlValue::Bool(v) = v {
+				res.readonly = v;
+			} else {
+				return Err(RpcError::InvalidParams);
+			}
+		}
+
+		// Process "stats" option
+		if let Some(v) = obj.remove("stats") {
+			if let SqlValue::Bool(v) = v {
+				res.stats = v;
+			} else {
+				return Err(RpcError::InvalidParams);
+			}
+		}
+
+		// Process "stream" option
+		if let Some(v) = obj.remove("stream") {
+			if let SqlValue::Bool(v) = v {
+				res.stream = v;
+			} else {
+				return Err(RpcError::InvalidParams);
+			}
+		}
+
+		// Process "dry_run" option
+		if let Some(v) = obj.remove("dry_run") {
+			if let SqlValue::Bool(v) = v {
+				res.dry_run = v;
+			} else {
+				return Err(RpcError::InvalidParams);
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// The session used for a single query, which is never stored
+	pub(crate) fn session(&self, session: &Session) -> Session {
+		let mut session = session.clone();
+		session.query_timeout = self.timeout;
+		session.readonly = self.readonly;
+		session.stats = self.stats;
+		session.dry_run = self.dry_run;
+		session
+	}
+}
+
+/// Checks if a request is a `query` method which asked for its results to be
+/// streamed, which is only supported in version 2 of the RPC protocol
+pub fn is_streamed_query(version: Option<u8>, method: Method, params: &Array) -> bool {
+	if version != Some(2) || method != Method::Query {
+		return false;
+	}
+	match params.get(2) {
+		Some(SqlValue::Object(opts)) => matches!(opts.get("stream"), Some(SqlValue::Bool(true))),
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn;
+
+	#[test]
+	fn query_options() {
+		let opts = QueryOptions::process_options(
+			syn::value(
+				"{ timeout: 5s, readonly: true, stats: true, stream: true, dry_run: true, unknown: 'ignored' }",
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(opts.timeout, Some(Duration::from_secs(5)));
+		assert!(opts.readonly);
+		assert!(opts.stats);
+		assert!(opts.stream);
+		assert!(opts.dry_run);
+		let opts = QueryOptions::process_options(SqlValue::None).unwrap();
+		assert_eq!(opts.timeout, None);
+		assert!(!opts.readonly);
+		assert!(!opts.stats);
+		assert!(!opts.stream);
+		assert!(!opts.dry_run);
+		// Durations can be sent as strings
+		let opts =
+			QueryOptions::process_options(syn::value("{ timeout: '100ms' }").unwrap()).unwrap();
+		assert_eq!(opts.timeout, Some(Duration::from_millis(100)));
+		// Options of the wrong type are rejected
+		assert!(QueryOptions::process_options(syn::value("{ timeout: 'soon' }").unwrap()).is_err());
+		assert!(QueryOptions::process_options(syn::value("{ readonly: 1 }").unwrap()).is_err());
+		assert!(QueryOptions::process_options(syn::value("{ stats: 'yes' }").unwrap()).is_err());
+		assert!(QueryOptions::process_options(syn::value("{ stream: 1 }").unwrap()).is_err());
+		assert!(QueryOptions::process_options(syn::value("{ dry_run: 'yes' }").unwrap()).is_err());
+		assert!(QueryOptions::process_options(syn::value("[]").unwrap()).is_err());
+	}
+
+	#[test]
+	fn batch_option() {
+		let caps = Capabilities::default();
+		let mut opts = StatementOptions::default();
+		opts.process_options(
+			syn::value("{ batch: { size: 100, within: '50ms' } }").unwrap(),
+			&caps,
+		)
+		.unwrap();
+		assert_eq!(
+			opts.batch,
+			Some(NotificationBatch {
+				size: 100,
+				within: Duration::from_millis(50),
+			})
+		);
+		for v in ["{ batch: 100 }", "{ batch: { size: 0, within: 1s } }", "{ batch: { size: 1 } }"]
+		{
+			let mut opts = StatementOptions::default();
+			assert!(opts.process_options(syn::value(v).unwrap(), &caps).is_err(), "{v}");
+		}
+	}
+}
@@ -122,7 +122,7 @@ impl AccessType {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct JwtAccess {
@@ -131,6 +131,10 @@ pub struct JwtAccess {
 	// Issue is optional
 	// It is possible to only verify externally issued tokens
 	pub issue: Option<JwtAccessIssue>,
+	// Additional keys which can verify tokens
+	// These allow rotating keys without invalidating the existing tokens
+	#[revision(start = 2)]
+	pub keys: Vec<JwtAccessKey>,
 }
 
 impl Default for JwtAccess {
@@ -148,6 +152,7 @@ impl Default for JwtAccess {
 				alg,
 				key,
 			}),
+			keys: Vec::new(),
 		}
 	}
 }
@@ -168,6 +173,9 @@ impl Display for JwtAccess {
 				write!(f, "URL {}", QuoteStr(&v.url),)?;
 			}
 		}
+		for k in &self.keys {
+			write!(f, " WITH {k}")?;
+		}
 		if let Some(iss) = &self.issue {
 			write!(f, " WITH ISSUER KEY {}", QuoteStr(&iss.key))?;
 		}
@@ -180,6 +188,7 @@ impl From<JwtAccess> for crate::expr::JwtAccess {
 		Self {
 			verify: v.verify.into(),
 			issue: v.issue.map(Into::into),
+			keys: v.keys.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -189,6 +198,7 @@ impl From<crate::expr::JwtAccess> for JwtAccess {
 		Self {
 			verify: v.verify.into(),
 			issue: v.issue.map(Into::into),
+			keys: v.keys.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -215,10 +225,52 @@ impl JwtAccess {
 			}
 			None => None,
 		};
+		for key in jwt.keys.iter_mut() {
+			// If algorithm is symmetric, the verification key is a secret
+			if key.alg.is_symmetric() {
+				key.key = "[REDACTED]".to_string();
+			}
+		}
 		jwt
 	}
 }
 
+/// An additional key, identified by a key identifier, which can verify tokens
+#[revisioned(revision = 1)]
+#[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct JwtAccessKey {
+	pub kid: String,
+	pub alg: Algorithm,
+	pub key: String,
+}
+
+impl Display for JwtAccessKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "KEY {} ALGORITHM {} KEY {}", QuoteStr(&self.kid), self.alg, QuoteStr(&self.key))
+	}
+}
+
+impl From<JwtAccessKey> for crate::expr::access_type::JwtAccessKey {
+	fn from(v: JwtAccessKey) -> Self {
+		Self {
+			kid: v.kid,
+			alg: v.alg.into(),
+			key: v.key,
+		}
+	}
+}
+
+impl From<crate::expr::access_type::JwtAccessKey> for JwtAccessKey {
+	fn from(v: crate::expr::access_type::JwtAccessKey) -> Self {
+		Self {
+			kid: v.kid,
+			alg: v.alg.into(),
+			key: v.key,
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
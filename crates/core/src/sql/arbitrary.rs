@@ -32,6 +32,7 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 		Ok(Self {
 			expiry: time::Duration::new(u64::arbitrary(u)?, u32::arbitrary(u)?),
 			store_diff: bool::arbitrary(u)?,
+			store_audit: bool::arbitrary(u)?,
 		})
 	}
 }
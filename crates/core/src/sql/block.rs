@@ -5,7 +5,7 @@ use crate::sql::statements::{
 	AlterStatement, BreakStatement, ContinueStatement, CreateStatement, DefineStatement,
 	DeleteStatement, ForeachStatement, IfelseStatement, InsertStatement, OutputStatement,
 	RelateStatement, RemoveStatement, SelectStatement, SetStatement, ThrowStatement,
-	UpdateStatement, UpsertStatement,
+	TrycatchStatement, UpdateStatement, UpsertStatement,
 };
 use crate::sql::value::SqlValue;
 use revision::revisioned;
@@ -94,7 +94,7 @@ impl Display for Block {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -123,6 +123,8 @@ pub enum Entry {
 	Alter(AlterStatement),
 	#[revision(start = 5)]
 	Info(InfoStatement),
+	#[revision(start = 6)]
+	Trycatch(TrycatchStatement),
 }
 
 impl PartialOrd for Entry {
@@ -155,6 +157,7 @@ impl Display for Entry {
 			Self::Foreach(v) => write!(f, "{v}"),
 			Self::Alter(v) => write!(f, "{v}"),
 			Self::Info(v) => write!(f, "{v}"),
+			Self::Trycatch(v) => write!(f, "{v}"),
 		}
 	}
 }
@@ -182,6 +185,7 @@ impl From<Entry> for crate::expr::Entry {
 			Entry::Upsert(v) => Self::Upsert(v.into()),
 			Entry::Alter(v) => Self::Alter(v.into()),
 			Entry::Info(v) => Self::Info(v.into()),
+			Entry::Trycatch(v) => Self::Trycatch(v.into()),
 		}
 	}
 }
@@ -209,6 +213,7 @@ impl From<crate::expr::Entry> for Entry {
 			crate::expr::Entry::Upsert(v) => Self::Upsert(v.into()),
 			crate::expr::Entry::Alter(v) => Self::Alter(v.into()),
 			crate::expr::Entry::Info(v) => Self::Info(v.into()),
+			crate::expr::Entry::Trycatch(v) => Self::Trycatch(v.into()),
 		}
 	}
 }
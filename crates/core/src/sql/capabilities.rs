@@ -0,0 +1,57 @@
+use crate::sql::Strand;
+use crate::sql::fmt::Fmt;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The functions and statements which are allowed or denied within a namespace or database,
+/// on top of the capabilities of the datastore.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ScopeCapabilities {
+	pub allow_functions: Vec<Strand>,
+	pub deny_functions: Vec<Strand>,
+	pub allow_statements: Vec<Strand>,
+	pub deny_statements: Vec<Strand>,
+}
+
+impl Display for ScopeCapabilities {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "CAPABILITIES")?;
+		for (rule, targets) in [
+			("ALLOW FUNCTIONS", &self.allow_functions),
+			("DENY FUNCTIONS", &self.deny_functions),
+			("ALLOW STATEMENTS", &self.allow_statements),
+			("DENY STATEMENTS", &self.deny_statements),
+		] {
+			if !targets.is_empty() {
+				write!(f, " {rule} {}", Fmt::comma_separated(targets))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl From<ScopeCapabilities> for crate::expr::ScopeCapabilities {
+	fn from(v: ScopeCapabilities) -> Self {
+		crate::expr::ScopeCapabilities {
+			allow_functions: v.allow_functions.into_iter().map(Into::into).collect(),
+			deny_functions: v.deny_functions.into_iter().map(Into::into).collect(),
+			allow_statements: v.allow_statements.into_iter().map(Into::into).collect(),
+			deny_statements: v.deny_statements.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl From<crate::expr::ScopeCapabilities> for ScopeCapabilities {
+	fn from(v: crate::expr::ScopeCapabilities) -> Self {
+		ScopeCapabilities {
+			allow_functions: v.allow_functions.into_iter().map(Into::into).collect(),
+			deny_functions: v.deny_functions.into_iter().map(Into::into).collect(),
+			allow_statements: v.allow_statements.into_iter().map(Into::into).collect(),
+			deny_statements: v.deny_statements.into_iter().map(Into::into).collect(),
+		}
+	}
+}
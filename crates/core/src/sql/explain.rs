@@ -2,15 +2,20 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+/// An EXPLAIN clause, where the first value is true when FULL,
+/// and the second value is true when VERBOSE
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Explain(pub bool);
+pub struct Explain(pub bool, #[revision(start = 2)] pub bool);
 
 impl fmt::Display for Explain {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.write_str("EXPLAIN")?;
+		if self.1 {
+			f.write_str(" VERBOSE")?;
+		}
 		if self.0 {
 			f.write_str(" FULL")?;
 		}
@@ -20,11 +25,11 @@ impl fmt::Display for Explain {
 
 impl From<Explain> for crate::expr::Explain {
 	fn from(v: Explain) -> Self {
-		Self(v.0)
+		Self(v.0, v.1)
 	}
 }
 impl From<crate::expr::Explain> for Explain {
 	fn from(v: crate::expr::Explain) -> Self {
-		Self(v.0)
+		Self(v.0, v.1)
 	}
 }
@@ -45,13 +45,16 @@ impl From<crate::expr::Fetchs> for Fetchs {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Fetch(
 	#[revision(end = 2, convert_fn = "convert_fetch_idiom")] pub Idiom,
 	#[revision(start = 2)] pub SqlValue,
+	/// The number of times the path is fetched again from the fetched records
+	#[revision(start = 3)]
+	pub Option<u32>,
 );
 
 impl Fetch {
@@ -67,7 +70,7 @@ impl Fetch {
 
 impl From<SqlValue> for Fetch {
 	fn from(value: SqlValue) -> Self {
-		Self(value)
+		Self(value, None)
 	}
 }
 
@@ -80,18 +83,22 @@ impl Deref for Fetch {
 
 impl Display for Fetch {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		Display::fmt(&self.0, f)
+		Display::fmt(&self.0, f)?;
+		if let Some(depth) = self.1 {
+			write!(f, " DEPTH {depth}")?;
+		}
+		Ok(())
 	}
 }
 
 impl From<Fetch> for crate::expr::Fetch {
 	fn from(v: Fetch) -> Self {
-		crate::expr::Fetch(v.0.into())
+		crate::expr::Fetch(v.0.into(), v.1)
 	}
 }
 
 impl From<crate::expr::Fetch> for Fetch {
 	fn from(v: crate::expr::Fetch) -> Self {
-		Fetch(v.0.into())
+		Fetch(v.0.into(), v.1)
 	}
 }
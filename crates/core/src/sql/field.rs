@@ -1,4 +1,4 @@
-use crate::sql::{Idiom, SqlValue, fmt::Fmt};
+use crate::sql::{Idiom, SqlValue, Window, fmt::Fmt};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter, Write};
@@ -72,7 +72,7 @@ impl Display for Fields {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -86,6 +86,14 @@ pub enum Field {
 		/// The `quality` in `SELECT rating AS quality FROM ...`
 		alias: Option<Idiom>,
 	},
+	/// The 'rank() OVER (ORDER BY score)' in `SELECT rank() OVER (ORDER BY score) FROM ...`
+	#[revision(start = 2)]
+	Window {
+		expr: SqlValue,
+		window: Window,
+		/// The `position` in `SELECT rank() OVER (ORDER BY score) AS position FROM ...`
+		alias: Option<Idiom>,
+	},
 }
 
 impl Display for Field {
@@ -104,6 +112,19 @@ impl Display for Field {
 					Ok(())
 				}
 			}
+			Self::Window {
+				expr,
+				window,
+				alias,
+			} => {
+				write!(f, "{expr} {window}")?;
+				if let Some(alias) = alias {
+					f.write_str(" AS ")?;
+					Display::fmt(alias, f)
+				} else {
+					Ok(())
+				}
+			}
 		}
 	}
 }
@@ -119,6 +140,15 @@ impl From<Field> for crate::expr::field::Field {
 				expr: expr.into(),
 				alias: alias.map(Into::into),
 			},
+			Field::Window {
+				expr,
+				window,
+				alias,
+			} => Self::Window {
+				expr: expr.into(),
+				window: window.into(),
+				alias: alias.map(Into::into),
+			},
 		}
 	}
 }
@@ -134,6 +164,15 @@ impl From<crate::expr::field::Field> for Field {
 				expr: expr.into(),
 				alias: alias.map(Into::into),
 			},
+			crate::expr::field::Field::Window {
+				expr,
+				window,
+				alias,
+			} => Self::Window {
+				expr: expr.into(),
+				window: window.into(),
+				alias: alias.map(Into::into),
+			},
 		}
 	}
 }
@@ -1,10 +1,13 @@
+use crate::sql::escape::QuoteStr;
+use crate::sql::fmt::Fmt;
 use crate::sql::language::Language;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +19,15 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	/// Removes the tokens which are in the list
+	#[revision(start = 2)]
+	Stopwords(Vec<String>),
+	/// Replaces the substrings of each token with their mapped values
+	#[revision(start = 2)]
+	Mapping(BTreeMap<String, String>),
+	/// Removes the tokens whose number of characters is outside of the range
+	#[revision(start = 2)]
+	Length(u16, u16),
 }
 
 impl Display for Filter {
@@ -28,6 +40,23 @@ impl Display for Filter {
 			Self::Snowball(lang) => write!(f, "SNOWBALL({lang})"),
 			Self::Uppercase => f.write_str("UPPERCASE"),
 			Self::Mapper(path) => write!(f, "MAPPER({path})"),
+			Self::Stopwords(words) => {
+				write!(
+					f,
+					"STOPWORDS([{}])",
+					Fmt::comma_separated(words.iter().map(|w| QuoteStr(w)))
+				)
+			}
+			Self::Mapping(map) => write!(
+				f,
+				"MAPPING({{ {} }})",
+				Fmt::comma_separated(map.iter().map(|(k, v)| format!(
+					"{}: {}",
+					QuoteStr(k),
+					QuoteStr(v)
+				)))
+			),
+			Self::Length(min, max) => write!(f, "LENGTH({min},{max})"),
 		}
 	}
 }
@@ -42,6 +71,9 @@ impl From<Filter> for crate::expr::Filter {
 			Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			Filter::Uppercase => Self::Uppercase,
 			Filter::Mapper(path) => Self::Mapper(path),
+			Filter::Stopwords(words) => Self::Stopwords(words),
+			Filter::Mapping(map) => Self::Mapping(map),
+			Filter::Length(min, max) => Self::Length(min, max),
 		}
 	}
 }
@@ -56,6 +88,9 @@ impl From<crate::expr::Filter> for Filter {
 			crate::expr::Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			crate::expr::Filter::Uppercase => Self::Uppercase,
 			crate::expr::Filter::Mapper(path) => Self::Mapper(path),
+			crate::expr::Filter::Stopwords(words) => Self::Stopwords(words),
+			crate::expr::Filter::Mapping(map) => Self::Mapping(map),
+			crate::expr::Filter::Length(min, max) => Self::Length(min, max),
 		}
 	}
 }
@@ -4,7 +4,7 @@ use crate::sql::field::Fields;
 use crate::sql::group::Groups;
 use crate::sql::idiom::Idiom;
 use crate::sql::limit::Limit;
-use crate::sql::order::{OldOrders, Order, OrderList, Ordering};
+use crate::sql::order::{Collate, OldOrders, Order, OrderList, Ordering};
 use crate::sql::split::Splits;
 use crate::sql::start::Start;
 use crate::sql::table::Tables;
@@ -63,9 +63,10 @@ impl Graph {
 			x.0.into_iter()
 				.map(|x| Order {
 					value: x.order,
-					collate: x.collate,
+					collate: x.collate.then_some(Collate::Lexical),
 					numeric: x.numeric,
 					direction: x.direction,
+					nulls: None,
 				})
 				.collect();
 
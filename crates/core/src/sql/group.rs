@@ -1,5 +1,6 @@
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
+use crate::sql::order::Collate;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
@@ -48,11 +49,16 @@ impl From<crate::expr::Groups> for Groups {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Group(pub Idiom);
+pub struct Group(
+	pub Idiom,
+	/// The collation which the grouped strings are compared with
+	#[revision(start = 2)]
+	pub Option<Collate>,
+);
 
 impl Deref for Group {
 	type Target = Idiom;
@@ -63,18 +69,22 @@ impl Deref for Group {
 
 impl Display for Group {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		Display::fmt(&self.0, f)
+		Display::fmt(&self.0, f)?;
+		if let Some(collate) = &self.1 {
+			write!(f, " {collate}")?;
+		}
+		Ok(())
 	}
 }
 
 impl From<Group> for crate::expr::Group {
 	fn from(v: Group) -> Self {
-		Self(v.0.into())
+		Self(v.0.into(), v.1.map(Into::into))
 	}
 }
 
 impl From<crate::expr::Group> for Group {
 	fn from(v: crate::expr::Group) -> Self {
-		Self(v.0.into())
+		Self(v.0.into(), v.1.map(Into::into))
 	}
 }
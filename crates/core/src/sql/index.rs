@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -25,6 +25,9 @@ pub enum Index {
 	/// HNSW index for distance based metrics
 	#[revision(start = 2)]
 	Hnsw(HnswParams),
+	/// Index of the lowercase trigrams of strings, for substring searches
+	#[revision(start = 3)]
+	Trigram,
 }
 
 impl From<Index> for crate::expr::index::Index {
@@ -35,6 +38,7 @@ impl From<Index> for crate::expr::index::Index {
 			Index::Search(p) => Self::Search(p.into()),
 			Index::MTree(p) => Self::MTree(p.into()),
 			Index::Hnsw(p) => Self::Hnsw(p.into()),
+			Index::Trigram => Self::Trigram,
 		}
 	}
 }
@@ -47,6 +51,7 @@ impl From<crate::expr::index::Index> for Index {
 			crate::expr::index::Index::Search(p) => Self::Search(p.into()),
 			crate::expr::index::Index::MTree(p) => Self::MTree(p.into()),
 			crate::expr::index::Index::Hnsw(p) => Self::Hnsw(p.into()),
+			crate::expr::index::Index::Trigram => Self::Trigram,
 		}
 	}
 }
@@ -371,6 +376,7 @@ impl Display for Index {
 		match self {
 			Self::Idx => Ok(()),
 			Self::Uniq => f.write_str("UNIQUE"),
+			Self::Trigram => f.write_str("TRIGRAM"),
 			Self::Search(p) => {
 				write!(
 					f,
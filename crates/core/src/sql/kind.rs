@@ -16,7 +16,7 @@ use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter, Write};
 
 /// The kind, or data type, of a value or field.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -65,9 +65,17 @@ pub enum Kind {
 	/// Can be any of the kinds in the vec.
 	Either(Vec<Kind>),
 	/// A set type.
+	#[revision(end = 3, convert_fn = "convert_old_set", fields_name = "OldSetFields")]
 	Set(Box<Kind>, Option<u64>),
+	/// A set type, whose duplicated elements are removed.
+	#[revision(start = 3)]
+	Set(Box<Kind>, ArrayConstraints),
 	/// An array type.
+	#[revision(end = 3, convert_fn = "convert_old_array", fields_name = "OldArrayFields")]
 	Array(Box<Kind>, Option<u64>),
+	/// An array type.
+	#[revision(start = 3)]
+	Array(Box<Kind>, ArrayConstraints),
 	/// A function type.
 	/// The first option is the argument types, the second is the optional return type.
 	Function(Option<Vec<Kind>>, Option<Box<Kind>>),
@@ -93,6 +101,14 @@ impl Default for Kind {
 }
 
 impl Kind {
+	fn convert_old_set(fields: OldSetFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(Kind::Set(fields.0, ArrayConstraints::from_len(fields.1)))
+	}
+
+	fn convert_old_array(fields: OldArrayFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(Kind::Array(fields.0, ArrayConstraints::from_len(fields.1)))
+	}
+
 	/// Returns the kind of a type.
 	pub(crate) fn of<T: HasKind>() -> Kind {
 		T::kind()
@@ -205,8 +221,12 @@ impl From<Kind> for crate::expr::Kind {
 			Kind::Either(kinds) => {
 				crate::expr::Kind::Either(kinds.into_iter().map(Into::into).collect())
 			}
-			Kind::Set(k, l) => crate::expr::Kind::Set(Box::new(k.as_ref().clone().into()), l),
-			Kind::Array(k, l) => crate::expr::Kind::Array(Box::new(k.as_ref().clone().into()), l),
+			Kind::Set(k, c) => {
+				crate::expr::Kind::Set(Box::new(k.as_ref().clone().into()), c.into())
+			}
+			Kind::Array(k, c) => {
+				crate::expr::Kind::Array(Box::new(k.as_ref().clone().into()), c.into())
+			}
 			Kind::Function(args, ret) => crate::expr::Kind::Function(
 				args.map(|args| args.into_iter().map(Into::into).collect()),
 				ret.map(|ret| Box::new((*ret).into())),
@@ -253,8 +273,8 @@ impl From<crate::expr::Kind> for Kind {
 				}
 				Self::Either(kinds)
 			}
-			crate::expr::Kind::Set(k, l) => Self::Set(Box::new((*k).into()), l),
-			crate::expr::Kind::Array(k, l) => Self::Array(Box::new((*k).into()), l),
+			crate::expr::Kind::Set(k, c) => Self::Set(Box::new((*k).into()), c.into()),
+			crate::expr::Kind::Array(k, c) => Self::Array(Box::new((*k).into()), c.into()),
 			crate::expr::Kind::Function(args, ret) => Self::Function(
 				args.map(|args| args.into_iter().map(Into::into).collect()),
 				ret.map(|ret| Box::new((*ret).into())),
@@ -296,20 +316,20 @@ impl<T: HasKind> HasKind for Option<T> {
 impl<T: HasKind> HasKind for Vec<T> {
 	fn kind() -> Kind {
 		let kind = T::kind();
-		Kind::Array(Box::new(kind), None)
+		Kind::Array(Box::new(kind), ArrayConstraints::default())
 	}
 }
 
 impl HasKind for Array {
 	fn kind() -> Kind {
-		Kind::Array(Box::new(Kind::Any), None)
+		Kind::Array(Box::new(Kind::Any), ArrayConstraints::default())
 	}
 }
 
 impl<T: HasKind, const SIZE: usize> HasKind for [T; SIZE] {
 	fn kind() -> Kind {
 		let kind = T::kind();
-		Kind::Array(Box::new(kind), Some(SIZE as u64))
+		Kind::Array(Box::new(kind), ArrayConstraints::exact(SIZE as u64))
 	}
 }
 
@@ -435,16 +455,22 @@ impl Display for Kind {
 					write!(f, "geometry<{}>", Fmt::verbar_separated(k))
 				}
 			}
-			Kind::Set(k, l) => match (k, l) {
-				(k, None) if k.is_any() => write!(f, "set"),
-				(k, None) => write!(f, "set<{k}>"),
-				(k, Some(l)) => write!(f, "set<{k}, {l}>"),
-			},
-			Kind::Array(k, l) => match (k, l) {
-				(k, None) if k.is_any() => write!(f, "array"),
-				(k, None) => write!(f, "array<{k}>"),
-				(k, Some(l)) => write!(f, "array<{k}, {l}>"),
+			Kind::Set(k, c) => match (k, c.is_unbounded()) {
+				(k, true) if k.is_any() => f.write_str("set"),
+				(k, true) => write!(f, "set<{k}>"),
+				(k, false) => write!(f, "set<{k}, {c}>"),
 			},
+			Kind::Array(k, c) => {
+				match (k, c.is_unbounded()) {
+					(k, true) if k.is_any() => f.write_str("array")?,
+					(k, true) => write!(f, "array<{k}>")?,
+					(k, false) => write!(f, "array<{k}, {c}>")?,
+				}
+				if c.unique {
+					f.write_str(" UNIQUE")?;
+				}
+				Ok(())
+			}
 			Kind::Either(k) => write!(f, "{}", Fmt::verbar_separated(k)),
 			Kind::Range => f.write_str("range"),
 			Kind::Literal(l) => write!(f, "{}", l),
@@ -464,6 +490,107 @@ impl Display for Kind {
 	}
 }
 
+/// The constraints on the length, and on the elements, of an array or a set kind
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ArrayConstraints {
+	/// The minimum length, inclusive
+	pub min: Option<u64>,
+	/// The maximum length, inclusive
+	pub max: Option<u64>,
+	/// Whether the duplicated elements are rejected
+	pub unique: bool,
+}
+
+impl ArrayConstraints {
+	/// Requires an exact length
+	pub fn exact(len: u64) -> Self {
+		Self {
+			min: Some(len),
+			max: Some(len),
+			unique: false,
+		}
+	}
+
+	/// Bounds the length, where both bounds are inclusive
+	pub fn bounds(min: Option<u64>, max: Option<u64>) -> Self {
+		Self {
+			min,
+			max,
+			unique: false,
+		}
+	}
+
+	/// Requires the elements to be unique
+	pub fn with_unique(mut self, unique: bool) -> Self {
+		self.unique = unique;
+		self
+	}
+
+	fn from_len(len: Option<u64>) -> Self {
+		len.map(Self::exact).unwrap_or_default()
+	}
+
+	/// The length required by these constraints, if it is exact
+	pub(crate) fn exact_len(&self) -> Option<u64> {
+		self.min.filter(|min| self.max == Some(*min))
+	}
+
+	/// Checks if these constraints do not bound the length
+	pub(crate) fn is_unbounded(&self) -> bool {
+		self.min.is_none() && self.max.is_none()
+	}
+
+	/// Checks if a length is within the bounds
+	pub(crate) fn allows_len(&self, len: usize) -> bool {
+		let len = len as u64;
+		self.min.is_none_or(|min| len >= min) && self.max.is_none_or(|max| len <= max)
+	}
+
+	/// Finds the first element which is a duplicate of a previous element,
+	/// returning its index along with the index of the previous element
+	pub(crate) fn find_duplicate(array: &Array) -> Option<(usize, usize)> {
+		let mut seen = BTreeMap::new();
+		for (index, value) in array.iter().enumerate() {
+			if let Some(first) = seen.insert(value, index) {
+				return Some((index, first));
+			}
+		}
+		None
+	}
+}
+
+impl From<ArrayConstraints> for crate::expr::ArrayConstraints {
+	fn from(v: ArrayConstraints) -> Self {
+		Self::bounds(v.min, v.max).with_unique(v.unique)
+	}
+}
+
+impl From<crate::expr::ArrayConstraints> for ArrayConstraints {
+	fn from(v: crate::expr::ArrayConstraints) -> Self {
+		Self::bounds(v.min, v.max).with_unique(v.unique)
+	}
+}
+
+impl Display for ArrayConstraints {
+	/// Writes the length bounds, in the syntax of the kind parser
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let Some(len) = self.exact_len() {
+			return write!(f, "{len}");
+		}
+		if let Some(min) = self.min {
+			write!(f, "{min}")?;
+		}
+		f.write_str("..")?;
+		if let Some(max) = self.max {
+			write!(f, "={max}")?;
+		}
+		Ok(())
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -487,11 +614,14 @@ impl Literal {
 			Self::Array(a) => {
 				if let Some(inner) = a.first() {
 					if a.iter().all(|x| x == inner) {
-						return Kind::Array(Box::new(inner.to_owned()), Some(a.len() as u64));
+						return Kind::Array(
+							Box::new(inner.to_owned()),
+							ArrayConstraints::exact(a.len() as u64),
+						);
 					}
 				}
 
-				Kind::Array(Box::new(Kind::Any), None)
+				Kind::Array(Box::new(Kind::Any), ArrayConstraints::default())
 			}
 			Self::Object(_) => Kind::Object,
 			Self::DiscriminatedObject(_, _) => Kind::Object,
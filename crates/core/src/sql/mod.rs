@@ -10,6 +10,7 @@ pub(crate) mod base;
 pub(crate) mod block;
 pub(crate) mod bytes;
 pub(crate) mod bytesize;
+pub(crate) mod capabilities;
 pub(crate) mod cast;
 pub(crate) mod change_feed_include;
 pub(crate) mod changefeed;
@@ -74,6 +75,7 @@ pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -94,6 +96,7 @@ pub use self::block::Block;
 pub use self::block::Entry;
 pub use self::bytes::Bytes;
 pub use self::bytesize::Bytesize;
+pub use self::capabilities::ScopeCapabilities;
 pub use self::cast::Cast;
 pub use self::changefeed::ChangeFeed;
 pub use self::closure::Closure;
@@ -124,6 +127,7 @@ pub use self::ident::Ident;
 pub use self::idiom::Idiom;
 pub use self::idiom::Idioms;
 pub use self::index::Index;
+pub use self::kind::ArrayConstraints;
 pub use self::kind::Kind;
 pub use self::kind::Literal;
 pub use self::limit::Limit;
@@ -134,7 +138,7 @@ pub use self::number::Number;
 pub use self::object::Object;
 pub use self::operation::Operation;
 pub use self::operator::Operator;
-pub use self::order::Order;
+pub use self::order::{Collate, Nulls, Order};
 pub use self::output::Output;
 pub use self::param::Param;
 pub use self::part::Part;
@@ -165,7 +169,8 @@ pub use self::value::serde::from_value;
 pub use self::value::serde::to_value;
 pub use self::version::Version;
 pub use self::view::View;
-pub use self::with::With;
+pub use self::window::Window;
+pub use self::with::{IndexHint, With};
 
 // module reexporting parsing function to prevent a breaking change.
 mod parser {
@@ -1,4 +1,4 @@
-use crate::idx::ft::MatchRef;
+use crate::idx::ft::{Fuzziness, MatchRef};
 use crate::sql::index::Distance;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -8,7 +8,7 @@ use std::fmt::Write;
 use super::SqlValue;
 
 /// Binary operators.
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -45,7 +45,7 @@ pub enum Operator {
 	AllLike, // *~
 	#[revision(end = 3, convert_fn = "like_convert")]
 	AnyLike, // ?~
-	Matches(Option<MatchRef>), // @{ref}@
+	Matches(Option<MatchRef>, #[revision(start = 4)] Option<Fuzziness>), // @{ref}~{fuzziness}@
 	//
 	LessThan,        // <
 	LessThanOrEqual, // <=
@@ -124,12 +124,15 @@ impl fmt::Display for Operator {
 			Self::NoneInside => f.write_str("NONEINSIDE"),
 			Self::Outside => f.write_str("OUTSIDE"),
 			Self::Intersects => f.write_str("INTERSECTS"),
-			Self::Matches(reference) => {
+			Self::Matches(reference, fuzziness) => {
+				f.write_char('@')?;
 				if let Some(r) = reference {
-					write!(f, "@{r}@")
-				} else {
-					f.write_str("@@")
+					write!(f, "{r}")?;
+				}
+				if let Some(d) = fuzziness {
+					write!(f, "~{d}")?;
 				}
+				f.write_char('@')
 			}
 			Self::Knn(k, dist) => {
 				if let Some(d) = dist {
@@ -168,7 +171,7 @@ impl From<Operator> for crate::expr::Operator {
 			Operator::NotEqual => crate::expr::Operator::NotEqual,
 			Operator::AllEqual => crate::expr::Operator::AllEqual,
 			Operator::AnyEqual => crate::expr::Operator::AnyEqual,
-			Operator::Matches(r) => crate::expr::Operator::Matches(r),
+			Operator::Matches(r, d) => crate::expr::Operator::Matches(r, d),
 			Operator::LessThan => crate::expr::Operator::LessThan,
 			Operator::LessThanOrEqual => crate::expr::Operator::LessThanOrEqual,
 			Operator::MoreThan => crate::expr::Operator::MoreThan,
@@ -214,7 +217,7 @@ impl From<crate::expr::Operator> for Operator {
 			crate::expr::Operator::NotEqual => Self::NotEqual,
 			crate::expr::Operator::AllEqual => Self::AllEqual,
 			crate::expr::Operator::AnyEqual => Self::AnyEqual,
-			crate::expr::Operator::Matches(r) => Self::Matches(r),
+			crate::expr::Operator::Matches(r, d) => Self::Matches(r, d),
 			crate::expr::Operator::LessThan => Self::LessThan,
 			crate::expr::Operator::LessThanOrEqual => Self::LessThanOrEqual,
 			crate::expr::Operator::MoreThan => Self::MoreThan,
@@ -283,7 +286,7 @@ impl BindingPower {
 			| Operator::LessThanOrEqual
 			| Operator::MoreThan
 			| Operator::MoreThanOrEqual
-			| Operator::Matches(_)
+			| Operator::Matches(..)
 			| Operator::Contain
 			| Operator::NotContain
 			| Operator::ContainAll
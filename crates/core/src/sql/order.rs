@@ -72,24 +72,38 @@ impl From<crate::expr::order::OrderList> for OrderList {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Order {
 	/// The value to order by
 	pub value: Idiom,
-	pub collate: bool,
+	#[revision(end = 2, convert_fn = "convert_old_collate")]
+	pub old_collate: bool,
+	/// The collation used to compare strings
+	#[revision(start = 2)]
+	pub collate: Option<Collate>,
 	pub numeric: bool,
 	/// true if the direction is ascending
 	pub direction: bool,
+	/// Where NONE and NULL values are placed, if specified
+	#[revision(start = 3)]
+	pub nulls: Option<Nulls>,
+}
+
+impl Order {
+	fn convert_old_collate(&mut self, _rev: u16, old: bool) -> Result<(), revision::Error> {
+		self.collate = old.then_some(Collate::Lexical);
+		Ok(())
+	}
 }
 
 impl fmt::Display for Order {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", self.value)?;
-		if self.collate {
-			write!(f, " COLLATE")?;
+		if let Some(collate) = &self.collate {
+			write!(f, " {collate}")?;
 		}
 		if self.numeric {
 			write!(f, " NUMERIC")?;
@@ -97,6 +111,9 @@ impl fmt::Display for Order {
 		if !self.direction {
 			write!(f, " DESC")?;
 		}
+		if let Some(nulls) = &self.nulls {
+			write!(f, " {nulls}")?;
+		}
 		Ok(())
 	}
 }
@@ -105,9 +122,10 @@ impl From<Order> for crate::expr::order::Order {
 	fn from(v: Order) -> Self {
 		Self {
 			value: v.value.into(),
-			collate: v.collate,
+			collate: v.collate.map(Into::into),
 			numeric: v.numeric,
 			direction: v.direction,
+			nulls: v.nulls.map(Into::into),
 		}
 	}
 }
@@ -115,9 +133,93 @@ impl From<crate::expr::order::Order> for Order {
 	fn from(v: crate::expr::order::Order) -> Self {
 		Self {
 			value: v.value.into(),
-			collate: v.collate,
+			collate: v.collate.map(Into::into),
 			numeric: v.numeric,
 			direction: v.direction,
+			nulls: v.nulls.map(Into::into),
+		}
+	}
+}
+
+/// Where NONE and NULL values are placed in an ORDER BY clause
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Nulls {
+	/// NONE and NULL values are placed before all other values
+	First,
+	/// NONE and NULL values are placed after all other values
+	Last,
+}
+
+impl fmt::Display for Nulls {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::First => write!(f, "NULLS FIRST"),
+			Self::Last => write!(f, "NULLS LAST"),
+		}
+	}
+}
+
+impl From<Nulls> for crate::expr::order::Nulls {
+	fn from(v: Nulls) -> Self {
+		match v {
+			Nulls::First => Self::First,
+			Nulls::Last => Self::Last,
+		}
+	}
+}
+
+impl From<crate::expr::order::Nulls> for Nulls {
+	fn from(v: crate::expr::order::Nulls) -> Self {
+		match v {
+			crate::expr::order::Nulls::First => Self::First,
+			crate::expr::order::Nulls::Last => Self::Last,
+		}
+	}
+}
+
+/// The collation used when comparing strings in an ORDER BY clause
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Collate {
+	/// Lexical ordering, where letters are compared before their case
+	Lexical,
+	/// Ordering which ignores the case of letters
+	NoCase,
+	/// Ordering which ignores both the case and the accents of letters
+	Unicode,
+}
+
+impl fmt::Display for Collate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Lexical => write!(f, "COLLATE"),
+			Self::NoCase => write!(f, "COLLATE NOCASE"),
+			Self::Unicode => write!(f, "COLLATE UNICODE"),
+		}
+	}
+}
+
+impl From<Collate> for crate::expr::order::Collate {
+	fn from(v: Collate) -> Self {
+		match v {
+			Collate::Lexical => Self::Lexical,
+			Collate::NoCase => Self::NoCase,
+			Collate::Unicode => Self::Unicode,
+		}
+	}
+}
+
+impl From<crate::expr::order::Collate> for Collate {
+	fn from(v: crate::expr::order::Collate) -> Self {
+		match v {
+			crate::expr::order::Collate::Lexical => Self::Lexical,
+			crate::expr::order::Collate::NoCase => Self::NoCase,
+			crate::expr::order::Collate::Unicode => Self::Unicode,
 		}
 	}
 }
@@ -1,3 +1,4 @@
+use crate::err::Error;
 use crate::sql::fmt::Pretty;
 use crate::sql::function::Function;
 use crate::sql::model::Model;
@@ -11,10 +12,12 @@ use crate::sql::statements::SelectStatement;
 use crate::sql::statements::UpdateStatement;
 use crate::sql::statements::UpsertStatement;
 use crate::sql::statements::{DefineStatement, RemoveStatement};
-use crate::sql::{Statement, Statements};
+use crate::sql::{Statement, Statements, closure, param};
 
+use anyhow::Result;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt::Write;
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -22,6 +25,9 @@ use std::str;
 
 pub(crate) const TOKEN: &str = "$surrealdb::private::sql::Query";
 
+type Content = serde_content::Value<'static>;
+type Data = serde_content::Data<'static>;
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[serde(rename = "$surrealdb::private::sql::Query")]
@@ -132,6 +138,141 @@ impl DerefMut for Query {
 	}
 }
 
+impl Query {
+	/// Returns the names of the parameters which need to be bound to run the
+	/// query, in alphabetical order.
+	///
+	/// This excludes the parameters which are defined by the query itself,
+	/// with a `LET` or a `FOR` statement or as the argument of a closure, and
+	/// the parameters which the database provides, such as `$this`, `$auth`,
+	/// or `$before`. Parameters defined in the database with `DEFINE PARAM`
+	/// are not known to the query, so they are included.
+	pub fn params(&self) -> Result<BTreeSet<String>> {
+		// The statements are walked through their serialized representation
+		let content = serde_content::Serializer::new()
+			.serialize(self)
+			.map_err(|e| Error::Serialization(e.to_string()))?;
+		let mut params = Params::default();
+		params.walk(&content);
+		Ok(params.used.difference(&params.defined).cloned().collect())
+	}
+}
+
+/// The parameters which the database provides while a query runs
+const PROVIDED_PARAMS: &[&str] = &[
+	"access",
+	"action",
+	"after",
+	"auth",
+	"before",
+	"event",
+	"input",
+	"parent",
+	"reference",
+	"request",
+	"session",
+	"this",
+	"token",
+	"value",
+];
+
+/// The parameters which are used and defined by a query
+#[derive(Default)]
+struct Params {
+	used: BTreeSet<String>,
+	defined: BTreeSet<String>,
+}
+
+impl Params {
+	fn walk(&mut self, content: &Content) {
+		match content {
+			Content::Seq(v) | Content::Tuple(v) => v.iter().for_each(|v| self.walk(v)),
+			Content::Map(v) => v.iter().for_each(|(_, v)| self.walk(v)),
+			Content::Option(Some(v)) => self.walk(v),
+			Content::Struct(v) => {
+				match v.name.as_ref() {
+					param::TOKEN => {
+						let name = first_string(&v.data).filter(|v| !PROVIDED_PARAMS.contains(v));
+						if let Some(name) = name {
+							self.used.insert(name.to_owned());
+						}
+						return;
+					}
+					// LET $name = ...
+					"SetStatement" => self.define(field(&v.data, "name")),
+					// FOR $param IN ...
+					"ForeachStatement" => self.define(field(&v.data, "param")),
+					// |$arg: kind| ...
+					closure::TOKEN => {
+						if let Some(Content::Seq(args)) = field(&v.data, "args") {
+							for arg in args {
+								if let Content::Tuple(arg) = arg {
+									self.define(arg.first());
+								}
+							}
+						}
+					}
+					_ => {}
+				}
+				self.walk_data(&v.data);
+			}
+			Content::Enum(v) => self.walk_data(&v.data),
+			_ => {}
+		}
+	}
+
+	fn walk_data(&mut self, data: &Data) {
+		match data {
+			Data::Unit => {}
+			Data::NewType {
+				value,
+			} => self.walk(value),
+			Data::Tuple {
+				values,
+			} => values.iter().for_each(|v| self.walk(v)),
+			Data::Struct {
+				fields,
+			} => fields.iter().for_each(|(_, v)| self.walk(v)),
+		}
+	}
+
+	/// Records the name of a parameter which the query defines
+	fn define(&mut self, content: Option<&Content>) {
+		if let Some(name) = content.and_then(first_string_in) {
+			self.defined.insert(name.to_owned());
+		}
+	}
+}
+
+/// Finds a field of a serialized struct
+fn field<'a>(data: &'a Data, name: &str) -> Option<&'a Content> {
+	match data {
+		Data::Struct {
+			fields,
+		} => fields.iter().find(|(k, _)| k == name).map(|(_, v)| v),
+		_ => None,
+	}
+}
+
+/// Finds the first string of a serialized value, such as the name of an
+/// identifier, which is wrapped in one or more newtype structs
+fn first_string(data: &Data) -> Option<&str> {
+	match data {
+		Data::NewType {
+			value,
+		} => first_string_in(value),
+		_ => None,
+	}
+}
+
+fn first_string_in<'a>(content: &'a Content) -> Option<&'a str> {
+	match content {
+		Content::String(v) => Some(v.as_ref()),
+		Content::Struct(v) => first_string(&v.data),
+		_ => None,
+	}
+}
+
 impl IntoIterator for Query {
 	type Item = Statement;
 	type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -157,3 +298,38 @@ impl From<crate::expr::Query> for Query {
 		Self(v.0.into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::syn;
+
+	fn params(sql: &str) -> Vec<String> {
+		syn::parse(sql).unwrap().params().unwrap().into_iter().collect()
+	}
+
+	#[test]
+	fn params_used_by_the_query() {
+		assert_eq!(
+			params("SELECT * FROM person WHERE age > $age AND name = $name"),
+			["age", "name"]
+		);
+		assert_eq!(
+			params("CREATE person CONTENT $data; UPDATE $id SET tags += $tag"),
+			["data", "id", "tag"]
+		);
+		assert_eq!(params("SELECT * FROM person WHERE age > 18"), Vec::<String>::new());
+	}
+
+	#[test]
+	fn params_defined_by_the_query_are_not_used() {
+		assert_eq!(params("LET $min = $age + 1; SELECT * FROM person WHERE age > $min"), ["age"]);
+		assert_eq!(params("FOR $i IN $list { CREATE person SET n = $i }"), ["list"]);
+		assert_eq!(params("RETURN array::map($items, |$v| $v * $factor)"), ["factor", "items"]);
+		assert_eq!(
+			params(
+				"DEFINE EVENT log ON person THEN { CREATE log SET before = $before, by = $auth.id, at = $now }"
+			),
+			["now"]
+		);
+	}
+}
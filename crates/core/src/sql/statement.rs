@@ -8,7 +8,7 @@ use crate::sql::{
 		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
 		LiveStatement, OptionStatement, OutputStatement, RelateStatement, RemoveStatement,
 		SelectStatement, SetStatement, ShowStatement, SleepStatement, ThrowStatement,
-		UpdateStatement, UpsertStatement, UseStatement,
+		TruncateStatement, TrycatchStatement, UpdateStatement, UpsertStatement, UseStatement,
 	},
 	value::SqlValue,
 };
@@ -62,7 +62,7 @@ impl From<crate::expr::LogicalPlans> for Statements {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -103,6 +103,10 @@ pub enum Statement {
 	// TODO(gguillemas): Document once bearer access is no longer experimental.
 	#[revision(start = 5)]
 	Access(AccessStatement),
+	#[revision(start = 6)]
+	Truncate(TruncateStatement),
+	#[revision(start = 7)]
+	Trycatch(TrycatchStatement),
 }
 
 impl Display for Statement {
@@ -136,6 +140,8 @@ impl Display for Statement {
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
 			Self::Sleep(v) => write!(Pretty::from(f), "{v}"),
 			Self::Throw(v) => write!(Pretty::from(f), "{v}"),
+			Self::Truncate(v) => write!(Pretty::from(f), "{v}"),
+			Self::Trycatch(v) => write!(Pretty::from(f), "{v}"),
 			Self::Update(v) => write!(Pretty::from(f), "{v}"),
 			Self::Upsert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Use(v) => write!(Pretty::from(f), "{v}"),
@@ -177,6 +183,8 @@ impl From<Statement> for crate::expr::LogicalPlan {
 			Statement::Upsert(v) => crate::expr::LogicalPlan::Upsert(v.into()),
 			Statement::Alter(v) => crate::expr::LogicalPlan::Alter(v.into()),
 			Statement::Access(v) => crate::expr::LogicalPlan::Access(v.into()),
+			Statement::Truncate(v) => crate::expr::LogicalPlan::Truncate(v.into()),
+			Statement::Trycatch(v) => crate::expr::LogicalPlan::Trycatch(v.into()),
 		}
 	}
 }
@@ -215,6 +223,8 @@ impl From<crate::expr::LogicalPlan> for Statement {
 			crate::expr::LogicalPlan::Upsert(v) => Self::Upsert(v.into()),
 			crate::expr::LogicalPlan::Alter(v) => Self::Alter(v.into()),
 			crate::expr::LogicalPlan::Access(v) => Self::Access(v.into()),
+			crate::expr::LogicalPlan::Truncate(v) => Self::Truncate(v.into()),
+			crate::expr::LogicalPlan::Trycatch(v) => Self::Trycatch(v.into()),
 		}
 	}
 }
@@ -0,0 +1,60 @@
+use crate::sql::access_type::JwtAccessKey;
+use crate::sql::escape::QuoteStr;
+use crate::sql::{Base, Ident};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterAccessStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+	pub add_keys: Vec<JwtAccessKey>,
+	pub remove_keys: Vec<String>,
+}
+
+impl Display for AlterAccessStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER ACCESS")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for k in &self.add_keys {
+			write!(f, " ADD {k}")?
+		}
+		for k in &self.remove_keys {
+			write!(f, " REMOVE KEY {}", QuoteStr(k))?
+		}
+		Ok(())
+	}
+}
+
+impl From<AlterAccessStatement> for crate::expr::statements::alter::AlterAccessStatement {
+	fn from(v: AlterAccessStatement) -> Self {
+		crate::expr::statements::alter::AlterAccessStatement {
+			name: v.name.into(),
+			base: v.base.into(),
+			if_exists: v.if_exists,
+			add_keys: v.add_keys.into_iter().map(Into::into).collect(),
+			remove_keys: v.remove_keys,
+		}
+	}
+}
+
+impl From<crate::expr::statements::alter::AlterAccessStatement> for AlterAccessStatement {
+	fn from(v: crate::expr::statements::alter::AlterAccessStatement) -> Self {
+		AlterAccessStatement {
+			name: v.name.into(),
+			base: v.base.into(),
+			if_exists: v.if_exists,
+			add_keys: v.add_keys.into_iter().map(Into::into).collect(),
+			remove_keys: v.remove_keys,
+		}
+	}
+}
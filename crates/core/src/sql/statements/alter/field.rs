@@ -6,7 +6,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -24,6 +24,9 @@ pub struct AlterFieldStatement {
 	pub comment: Option<Option<Strand>>,
 	pub reference: Option<Option<Reference>>,
 	pub default_always: Option<bool>,
+	/// Whether the existing records are checked against the altered definition
+	#[revision(start = 2)]
+	pub validate: bool,
 }
 
 impl Display for AlterFieldStatement {
@@ -97,6 +100,9 @@ impl Display for AlterFieldStatement {
 				write!(f, " DROP REFERENCE")?;
 			}
 		}
+		if self.validate {
+			write!(f, " VALIDATE")?;
+		}
 		Ok(())
 	}
 }
@@ -117,6 +123,7 @@ impl From<AlterFieldStatement> for crate::expr::statements::alter::AlterFieldSta
 			comment: v.comment.map(|opt| opt.map(Into::into)),
 			reference: v.reference.map(|opt| opt.map(Into::into)),
 			default_always: v.default_always,
+			validate: v.validate,
 		}
 	}
 }
@@ -137,6 +144,7 @@ impl From<crate::expr::statements::alter::AlterFieldStatement> for AlterFieldSta
 			comment: v.comment.map(|opt| opt.map(Into::into)),
 			reference: v.reference.map(|opt| opt.map(Into::into)),
 			default_always: v.default_always,
+			validate: v.validate,
 		}
 	}
 }
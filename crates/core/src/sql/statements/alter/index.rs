@@ -0,0 +1,52 @@
+use crate::sql::Ident;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterIndexStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub cancel_build: bool,
+}
+
+impl Display for AlterIndexStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER INDEX")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.cancel_build {
+			write!(f, " CANCEL BUILD")?
+		}
+		Ok(())
+	}
+}
+
+impl From<AlterIndexStatement> for crate::expr::statements::alter::AlterIndexStatement {
+	fn from(v: AlterIndexStatement) -> Self {
+		crate::expr::statements::alter::AlterIndexStatement {
+			name: v.name.into(),
+			what: v.what.into(),
+			if_exists: v.if_exists,
+			cancel_build: v.cancel_build,
+		}
+	}
+}
+
+impl From<crate::expr::statements::alter::AlterIndexStatement> for AlterIndexStatement {
+	fn from(v: crate::expr::statements::alter::AlterIndexStatement) -> Self {
+		AlterIndexStatement {
+			name: v.name.into(),
+			what: v.what.into(),
+			if_exists: v.if_exists,
+			cancel_build: v.cancel_build,
+		}
+	}
+}
@@ -1,8 +1,12 @@
+mod access;
 mod field;
+mod index;
 mod sequence;
 mod table;
 
+pub use access::AlterAccessStatement;
 pub use field::AlterFieldStatement;
+pub use index::AlterIndexStatement;
 pub use sequence::AlterSequenceStatement;
 pub use table::AlterTableStatement;
 
@@ -10,7 +14,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -20,6 +24,10 @@ pub enum AlterStatement {
 	Sequence(AlterSequenceStatement),
 	#[revision(start = 3)]
 	Field(AlterFieldStatement),
+	#[revision(start = 4)]
+	Index(AlterIndexStatement),
+	#[revision(start = 5)]
+	Access(AlterAccessStatement),
 }
 
 impl Display for AlterStatement {
@@ -28,6 +36,8 @@ impl Display for AlterStatement {
 			Self::Table(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
 			Self::Field(v) => Display::fmt(v, f),
+			Self::Index(v) => Display::fmt(v, f),
+			Self::Access(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -38,6 +48,8 @@ impl From<AlterStatement> for crate::expr::statements::AlterStatement {
 			AlterStatement::Table(v) => Self::Table(v.into()),
 			AlterStatement::Sequence(v) => Self::Sequence(v.into()),
 			AlterStatement::Field(v) => Self::Field(v.into()),
+			AlterStatement::Index(v) => Self::Index(v.into()),
+			AlterStatement::Access(v) => Self::Access(v.into()),
 		}
 	}
 }
@@ -48,6 +60,8 @@ impl From<crate::expr::statements::AlterStatement> for AlterStatement {
 			crate::expr::statements::AlterStatement::Table(v) => Self::Table(v.into()),
 			crate::expr::statements::AlterStatement::Sequence(v) => Self::Sequence(v.into()),
 			crate::expr::statements::AlterStatement::Field(v) => Self::Field(v.into()),
+			crate::expr::statements::AlterStatement::Index(v) => Self::Index(v.into()),
+			crate::expr::statements::AlterStatement::Access(v) => Self::Access(v.into()),
 		}
 	}
 }
@@ -65,7 +79,7 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(15, enc.len());
+		assert_eq!(17, enc.len());
 	}
 
 	#[test]
@@ -86,6 +100,27 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(30, enc.len());
+		assert_eq!(31, enc.len());
+	}
+
+	#[test]
+	fn check_alter_serialize_index() {
+		let stm = AlterStatement::Index(AlterIndexStatement {
+			name: Ident::from("test"),
+			what: Ident::from("test"),
+			..Default::default()
+		});
+		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
+		assert_eq!(17, enc.len());
+	}
+
+	#[test]
+	fn check_alter_serialize_access() {
+		let stm = AlterStatement::Access(AlterAccessStatement {
+			name: Ident::from("test"),
+			..Default::default()
+		});
+		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
+		assert_eq!(14, enc.len());
 	}
 }
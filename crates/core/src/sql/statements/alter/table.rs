@@ -1,5 +1,5 @@
 use crate::sql::fmt::{is_pretty, pretty_indent};
-use crate::sql::{ChangeFeed, Ident, Permissions, Strand};
+use crate::sql::{ChangeFeed, Ident, Permissions, SqlValue, Strand};
 use crate::sql::{Kind, TableType};
 use anyhow::Result;
 
@@ -7,7 +7,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -21,6 +21,22 @@ pub struct AlterTableStatement {
 	pub changefeed: Option<Option<ChangeFeed>>,
 	pub comment: Option<Option<Strand>>,
 	pub kind: Option<TableType>,
+	#[revision(start = 3)]
+	pub rename: Option<Ident>,
+	#[revision(start = 3)]
+	pub cascade: bool,
+	/// Suspend or resume the maintenance of a view
+	#[revision(start = 4)]
+	pub suspend: Option<bool>,
+	/// Recompute the contents of a view
+	#[revision(start = 4)]
+	pub rebuild: bool,
+	/// The condition which every record of the table must conform to
+	#[revision(start = 5)]
+	pub assert: Option<Option<SqlValue>>,
+	/// Whether the existing records are checked against the `ASSERT` clause
+	#[revision(start = 5)]
+	pub validate: bool,
 }
 
 impl AlterTableStatement {
@@ -89,6 +105,32 @@ impl Display for AlterTableStatement {
 				write!(f, " DROP CHANGEFEED")?;
 			}
 		}
+		if let Some(rename) = &self.rename {
+			write!(f, " RENAME TO {rename}")?;
+			if self.cascade {
+				f.write_str(" CASCADE")?;
+			}
+		}
+		if let Some(suspend) = self.suspend {
+			f.write_str(if suspend {
+				" SUSPEND"
+			} else {
+				" RESUME"
+			})?;
+		}
+		if self.rebuild {
+			f.write_str(" REBUILD")?;
+		}
+		if let Some(assert) = &self.assert {
+			if let Some(assert) = assert {
+				write!(f, " ASSERT {assert}")?;
+				if self.validate {
+					f.write_str(" VALIDATE")?;
+				}
+			} else {
+				write!(f, " DROP ASSERT")?;
+			}
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -112,6 +154,12 @@ impl From<AlterTableStatement> for crate::expr::statements::alter::AlterTableSta
 			changefeed: v.changefeed.map(|opt| opt.map(Into::into)),
 			comment: v.comment.map(|opt| opt.map(Into::into)),
 			kind: v.kind.map(Into::into),
+			rename: v.rename.map(Into::into),
+			cascade: v.cascade,
+			suspend: v.suspend,
+			rebuild: v.rebuild,
+			assert: v.assert.map(|v| v.map(Into::into)),
+			validate: v.validate,
 		}
 	}
 }
@@ -126,6 +174,12 @@ impl From<crate::expr::statements::alter::AlterTableStatement> for AlterTableSta
 			changefeed: v.changefeed.map(|opt| opt.map(Into::into)),
 			comment: v.comment.map(|opt| opt.map(Into::into)),
 			kind: v.kind.map(Into::into),
+			rename: v.rename.map(Into::into),
+			cascade: v.cascade,
+			suspend: v.suspend,
+			rebuild: v.rebuild,
+			assert: v.assert.map(|v| v.map(Into::into)),
+			validate: v.validate,
 		}
 	}
 }
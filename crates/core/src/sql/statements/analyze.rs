@@ -5,18 +5,21 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum AnalyzeStatement {
 	Idx(Ident, Ident),
+	#[revision(start = 2)]
+	Tb(Ident),
 }
 
 impl From<AnalyzeStatement> for crate::expr::statements::analyze::AnalyzeStatement {
 	fn from(value: AnalyzeStatement) -> Self {
 		match value {
 			AnalyzeStatement::Idx(tb, idx) => Self::Idx(tb.into(), idx.into()),
+			AnalyzeStatement::Tb(tb) => Self::Tb(tb.into()),
 		}
 	}
 }
@@ -27,6 +30,7 @@ impl From<crate::expr::statements::analyze::AnalyzeStatement> for AnalyzeStateme
 			crate::expr::statements::analyze::AnalyzeStatement::Idx(tb, idx) => {
 				Self::Idx(tb.into(), idx.into())
 			}
+			crate::expr::statements::analyze::AnalyzeStatement::Tb(tb) => Self::Tb(tb.into()),
 		}
 	}
 }
@@ -35,6 +39,7 @@ impl Display for AnalyzeStatement {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Self::Idx(tb, idx) => write!(f, "ANALYZE INDEX {idx} ON {tb}"),
+			Self::Tb(tb) => write!(f, "ANALYZE TABLE {tb}"),
 		}
 	}
 }
@@ -1,27 +1,40 @@
+use crate::sql::Ident;
+
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct BreakStatement;
+pub struct BreakStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
+}
 
 impl fmt::Display for BreakStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("BREAK")
+		f.write_str("BREAK")?;
+		if let Some(ref v) = self.label {
+			write!(f, " {v}")?;
+		}
+		Ok(())
 	}
 }
 
 impl From<BreakStatement> for crate::expr::statements::BreakStatement {
-	fn from(_v: BreakStatement) -> Self {
-		Self {}
+	fn from(v: BreakStatement) -> Self {
+		Self {
+			label: v.label.map(Into::into),
+		}
 	}
 }
 
 impl From<crate::expr::statements::BreakStatement> for BreakStatement {
-	fn from(_v: crate::expr::statements::BreakStatement) -> Self {
-		Self {}
+	fn from(v: crate::expr::statements::BreakStatement) -> Self {
+		Self {
+			label: v.label.map(Into::into),
+		}
 	}
 }
@@ -1,27 +1,40 @@
+use crate::sql::Ident;
+
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct ContinueStatement;
+pub struct ContinueStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
+}
 
 impl fmt::Display for ContinueStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("CONTINUE")
+		f.write_str("CONTINUE")?;
+		if let Some(ref v) = self.label {
+			write!(f, " {v}")?;
+		}
+		Ok(())
 	}
 }
 
 impl From<ContinueStatement> for crate::expr::statements::ContinueStatement {
-	fn from(_v: ContinueStatement) -> Self {
-		Self {}
+	fn from(v: ContinueStatement) -> Self {
+		Self {
+			label: v.label.map(Into::into),
+		}
 	}
 }
 
 impl From<crate::expr::statements::ContinueStatement> for ContinueStatement {
-	fn from(_v: crate::expr::statements::ContinueStatement) -> Self {
-		Self {}
+	fn from(v: crate::expr::statements::ContinueStatement) -> Self {
+		Self {
+			label: v.label.map(Into::into),
+		}
 	}
 }
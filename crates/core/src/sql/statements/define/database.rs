@@ -1,10 +1,10 @@
-use crate::sql::{Ident, Strand, changefeed::ChangeFeed};
+use crate::sql::{Ident, ScopeCapabilities, Strand, changefeed::ChangeFeed};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -17,6 +17,8 @@ pub struct DefineDatabaseStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	#[revision(start = 4)]
+	pub capabilities: Option<ScopeCapabilities>,
 }
 
 impl Display for DefineDatabaseStatement {
@@ -35,6 +37,9 @@ impl Display for DefineDatabaseStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.capabilities {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -48,6 +53,7 @@ impl From<DefineDatabaseStatement> for crate::expr::statements::DefineDatabaseSt
 			changefeed: v.changefeed.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			capabilities: v.capabilities.map(Into::into),
 		}
 	}
 }
@@ -61,6 +67,7 @@ impl From<crate::expr::statements::DefineDatabaseStatement> for DefineDatabaseSt
 			changefeed: v.changefeed.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			capabilities: v.capabilities.map(Into::into),
 		}
 	}
 }
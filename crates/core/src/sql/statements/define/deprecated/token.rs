@@ -35,6 +35,7 @@ impl From<DefineTokenStatement> for DefineAccessStatement {
 					alg: tk.kind,
 					key: tk.code,
 				}),
+				keys: Vec::new(),
 			}),
 			// unused fields
 			authenticate: None,
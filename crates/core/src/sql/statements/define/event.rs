@@ -4,7 +4,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -18,6 +18,12 @@ pub struct DefineEventStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	/// The order in which the events on a table are processed
+	#[revision(start = 4)]
+	pub order: Option<u32>,
+	/// A condition which is checked before the event context is created
+	#[revision(start = 4)]
+	pub guard: Option<SqlValue>,
 }
 
 impl Display for DefineEventStatement {
@@ -29,7 +35,17 @@ impl Display for DefineEventStatement {
 		if self.overwrite {
 			write!(f, " OVERWRITE")?
 		}
-		write!(f, " {} ON {} WHEN {} THEN {}", self.name, self.what, self.when, self.then)?;
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if let Some(ref v) = self.order {
+			write!(f, " ORDER {v}")?
+		}
+		if let Some(ref v) = self.guard {
+			write!(f, " IF {v}")?
+		}
+		// An overwrite may leave out the bodies of the event
+		if !self.then.is_empty() || !self.overwrite {
+			write!(f, " WHEN {} THEN {}", self.when, self.then)?;
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -47,6 +63,8 @@ impl From<DefineEventStatement> for crate::expr::statements::DefineEventStatemen
 			comment: v.comment.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			order: v.order,
+			guard: v.guard.map(Into::into),
 		}
 	}
 }
@@ -61,6 +79,8 @@ impl From<crate::expr::statements::DefineEventStatement> for DefineEventStatemen
 			comment: v.comment.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			order: v.order,
+			guard: v.guard.map(Into::into),
 		}
 	}
 }
@@ -7,7 +7,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 6)]
+#[revisioned(revision = 9)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,6 +33,19 @@ pub struct DefineFieldStatement {
 	pub reference: Option<Reference>,
 	#[revision(start = 6)]
 	pub default_always: bool,
+	/// Whether the record expires at the datetime stored in this field
+	#[revision(start = 7)]
+	pub expire: bool,
+	/// Whether the field is set to the datetime at which the record was created
+	#[revision(start = 8)]
+	pub created: bool,
+	/// Whether the field is set to the datetime at which the record was last written
+	#[revision(start = 8)]
+	pub modified: bool,
+	/// The expression which computes the field when the record is read,
+	/// as the field is never stored
+	#[revision(start = 9)]
+	pub computed: Option<SqlValue>,
 }
 
 impl Display for DefineFieldStatement {
@@ -62,6 +75,18 @@ impl Display for DefineFieldStatement {
 		if self.readonly {
 			write!(f, " READONLY")?
 		}
+		if self.expire {
+			write!(f, " EXPIRE")?
+		}
+		if self.created {
+			write!(f, " CREATED")?
+		}
+		if self.modified {
+			write!(f, " MODIFIED")?
+		}
+		if let Some(ref v) = self.computed {
+			write!(f, " COMPUTED {v}")?
+		}
 		if let Some(ref v) = self.value {
 			write!(f, " VALUE {v}")?
 		}
@@ -106,6 +131,10 @@ impl From<DefineFieldStatement> for crate::expr::statements::DefineFieldStatemen
 			overwrite: v.overwrite,
 			reference: v.reference.map(Into::into),
 			default_always: v.default_always,
+			expire: v.expire,
+			created: v.created,
+			modified: v.modified,
+			computed: v.computed.map(Into::into),
 		}
 	}
 }
@@ -127,6 +156,10 @@ impl From<crate::expr::statements::DefineFieldStatement> for DefineFieldStatemen
 			overwrite: v.overwrite,
 			reference: v.reference.map(Into::into),
 			default_always: v.default_always,
+			expire: v.expire,
+			created: v.created,
+			modified: v.modified,
+			computed: v.computed.map(Into::into),
 		}
 	}
 }
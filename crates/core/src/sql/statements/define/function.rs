@@ -6,7 +6,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -22,6 +22,9 @@ pub struct DefineFunctionStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub returns: Option<Kind>,
+	/// Further signatures of this function, with different numbers of arguments
+	#[revision(start = 5)]
+	pub overloads: Vec<DefineFunctionStatement>,
 }
 
 impl fmt::Display for DefineFunctionStatement {
@@ -55,6 +58,11 @@ impl fmt::Display for DefineFunctionStatement {
 			None
 		};
 		write!(f, "PERMISSIONS {}", self.permissions)?;
+		drop(_indent);
+		// Output each overload as a separate statement
+		for v in self.overloads.iter() {
+			write!(f, "; {v}")?;
+		}
 		Ok(())
 	}
 }
@@ -70,6 +78,7 @@ impl From<DefineFunctionStatement> for crate::expr::statements::DefineFunctionSt
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
 			returns: v.returns.map(Into::into),
+			overloads: v.overloads.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -85,6 +94,7 @@ impl From<crate::expr::statements::DefineFunctionStatement> for DefineFunctionSt
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
 			returns: v.returns.map(Into::into),
+			overloads: v.overloads.into_iter().map(Into::into).collect(),
 		}
 	}
 }
@@ -1,9 +1,9 @@
-use crate::sql::{Ident, Idioms, Index, Strand};
+use crate::sql::{Cond, Ident, Idioms, Index, Strand};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -19,6 +19,8 @@ pub struct DefineIndexStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub concurrently: bool,
+	#[revision(start = 5)]
+	pub cond: Option<Cond>,
 }
 
 impl Display for DefineIndexStatement {
@@ -31,6 +33,9 @@ impl Display for DefineIndexStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {} ON {} FIELDS {}", self.name, self.what, self.cols)?;
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
@@ -55,6 +60,9 @@ impl From<DefineIndexStatement> for crate::expr::statements::DefineIndexStatemen
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
 			concurrently: v.concurrently,
+			cond: v.cond.map(Into::into),
+			generation: 0,
+			cancelled: false,
 		}
 	}
 }
@@ -70,6 +78,7 @@ impl From<crate::expr::statements::DefineIndexStatement> for DefineIndexStatemen
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
 			concurrently: v.concurrently,
+			cond: v.cond.map(Into::into),
 		}
 	}
 }
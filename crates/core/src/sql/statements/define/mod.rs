@@ -12,6 +12,7 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod schedule;
 mod sequence;
 mod table;
 mod user;
@@ -29,8 +30,9 @@ pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use schedule::DefineScheduleStatement;
 pub use sequence::DefineSequenceStatement;
-pub use table::DefineTableStatement;
+pub use table::{DefineTableStatement, Tenant};
 pub use user::DefineUserStatement;
 
 pub use deprecated::scope::DefineScopeStatement;
@@ -44,7 +46,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -81,6 +83,8 @@ pub enum DefineStatement {
 	Bucket(DefineBucketStatement),
 	#[revision(start = 5)]
 	Sequence(DefineSequenceStatement),
+	#[revision(start = 6)]
+	Schedule(DefineScheduleStatement),
 }
 
 // Revision implementations
@@ -119,6 +123,7 @@ impl Display for DefineStatement {
 			Self::Api(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Schedule(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -142,6 +147,7 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Api(v) => Self::Api(v.into()),
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			DefineStatement::Schedule(v) => Self::Schedule(v.into()),
 		}
 	}
 }
@@ -165,6 +171,7 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::DefineStatement::Schedule(v) => Self::Schedule(v.into()),
 		}
 	}
 }
@@ -182,6 +189,23 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(13, enc.len());
+		assert_eq!(14, enc.len());
+	}
+
+	#[test]
+	fn check_define_function_overloads() {
+		let stm = DefineFunctionStatement {
+			name: Ident::from("greet"),
+			overloads: vec![DefineFunctionStatement {
+				name: Ident::from("greet"),
+				args: vec![(Ident::from("name"), crate::sql::Kind::String)],
+				..Default::default()
+			}],
+			..Default::default()
+		};
+		// The overloads are kept when converting to and from an expression
+		let exp: crate::expr::statements::DefineFunctionStatement = stm.clone().into();
+		assert_eq!(exp.overloads.len(), 1);
+		assert_eq!(DefineFunctionStatement::from(exp), stm);
 	}
 }
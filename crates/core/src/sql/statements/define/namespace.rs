@@ -1,10 +1,10 @@
-use crate::sql::{Ident, Strand};
+use crate::sql::{Ident, ScopeCapabilities, Strand};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +16,8 @@ pub struct DefineNamespaceStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	#[revision(start = 4)]
+	pub capabilities: Option<ScopeCapabilities>,
 }
 
 impl Display for DefineNamespaceStatement {
@@ -31,6 +33,9 @@ impl Display for DefineNamespaceStatement {
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if let Some(ref v) = self.capabilities {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -43,6 +48,7 @@ impl From<DefineNamespaceStatement> for crate::expr::statements::DefineNamespace
 			comment: v.comment.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			capabilities: v.capabilities.map(Into::into),
 		}
 	}
 }
@@ -55,6 +61,7 @@ impl From<crate::expr::statements::DefineNamespaceStatement> for DefineNamespace
 			comment: v.comment.map(Into::into),
 			if_not_exists: v.if_not_exists,
 			overwrite: v.overwrite,
+			capabilities: v.capabilities.map(Into::into),
 		}
 	}
 }
@@ -0,0 +1,72 @@
+use crate::sql::{Block, Ident, Strand};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineScheduleStatement {
+	pub name: Ident,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+	/// The cron expression which specifies when the schedule is run
+	pub cron: Strand,
+	/// The roles of the system user which runs the statements
+	pub roles: Vec<Ident>,
+	pub then: Block,
+	pub comment: Option<Strand>,
+}
+
+impl Display for DefineScheduleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE SCHEDULE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} ON DATABASE CRON {}", self.name, self.cron)?;
+		write!(
+			f,
+			" ROLES {}",
+			self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<_>>().join(", ")
+		)?;
+		write!(f, " THEN {}", self.then)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl From<DefineScheduleStatement> for crate::expr::statements::define::DefineScheduleStatement {
+	fn from(v: DefineScheduleStatement) -> Self {
+		Self {
+			name: v.name.into(),
+			if_not_exists: v.if_not_exists,
+			overwrite: v.overwrite,
+			cron: v.cron.into(),
+			roles: v.roles.into_iter().map(Into::into).collect(),
+			then: v.then.into(),
+			comment: v.comment.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::statements::define::DefineScheduleStatement> for DefineScheduleStatement {
+	fn from(v: crate::expr::statements::define::DefineScheduleStatement) -> Self {
+		Self {
+			name: v.name.into(),
+			if_not_exists: v.if_not_exists,
+			overwrite: v.overwrite,
+			cron: v.cron.into(),
+			roles: v.roles.into_iter().map(Into::into).collect(),
+			then: v.then.into(),
+			comment: v.comment.map(Into::into),
+		}
+	}
+}
@@ -4,7 +4,7 @@ use crate::sql::paths::{IN, OUT};
 
 use crate::kvs::Transaction;
 use crate::sql::{Ident, Permissions, Strand, View, changefeed::ChangeFeed};
-use crate::sql::{Idiom, Kind, TableType};
+use crate::sql::{Idiom, Kind, SqlValue, TableType};
 use anyhow::Result;
 
 use revision::Error as RevisionError;
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 use uuid::Uuid;
 
-#[revisioned(revision = 6)]
+#[revisioned(revision = 10)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -48,6 +48,58 @@ pub struct DefineTableStatement {
 	/// The last time that a LIVE query was added to this table
 	#[revision(start = 5, end = 6, convert_fn = "convert_cache_ts")]
 	pub cache_lives_ts: Uuid,
+	/// The tenant which the records of this table belong to
+	#[revision(start = 7)]
+	pub tenant: Option<Tenant>,
+	/// The condition which every record of this table must conform to
+	#[revision(start = 8)]
+	pub assert: Option<SqlValue>,
+	/// Whether the existing records are checked against the `ASSERT` clause
+	#[revision(start = 8)]
+	pub validate: bool,
+	/// Whether the deleted records are kept with a tombstone, instead of being removed
+	#[revision(start = 9)]
+	pub soft_delete: bool,
+	/// Whether the records keep a version, which is incremented on every write
+	#[revision(start = 10)]
+	pub versioned: bool,
+}
+
+/// The `TENANT` clause of a table, which limits every session to the
+/// records whose tenant field matches the tenant value of the session.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Tenant {
+	/// The field which holds the tenant of a record
+	pub field: Idiom,
+	/// The tenant of the session, such as `$auth.tenant`
+	pub value: SqlValue,
+}
+
+impl Display for Tenant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TENANT {} VALUE {}", self.field, self.value)
+	}
+}
+
+impl From<Tenant> for crate::expr::statements::Tenant {
+	fn from(v: Tenant) -> Self {
+		crate::expr::statements::Tenant {
+			field: v.field.into(),
+			value: v.value.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::Tenant> for Tenant {
+	fn from(v: crate::expr::statements::Tenant) -> Self {
+		Tenant {
+			field: v.field.into(),
+			value: v.value.into(),
+		}
+	}
 }
 
 impl DefineTableStatement {
@@ -151,6 +203,9 @@ impl Display for DefineTableStatement {
 				if rel.enforced {
 					write!(f, " ENFORCED")?;
 				}
+				if rel.unique {
+					write!(f, " UNIQUE")?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -159,6 +214,12 @@ impl Display for DefineTableStatement {
 		if self.drop {
 			f.write_str(" DROP")?;
 		}
+		if self.soft_delete {
+			f.write_str(" SOFT DELETE")?;
+		}
+		if self.versioned {
+			f.write_str(" VERSIONED")?;
+		}
 		f.write_str(if self.full {
 			" SCHEMAFULL"
 		} else {
@@ -173,6 +234,15 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.tenant {
+			write!(f, " {v}")?;
+		}
+		if let Some(ref v) = self.assert {
+			write!(f, " ASSERT {v}")?;
+			if self.validate {
+				f.write_str(" VALIDATE")?;
+			}
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -202,6 +272,12 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			cache_events_ts: v.cache_events_ts,
 			cache_tables_ts: v.cache_tables_ts,
 			cache_indexes_ts: v.cache_indexes_ts,
+			suspended: None,
+			tenant: v.tenant.map(Into::into),
+			assert: v.assert.map(Into::into),
+			validate: v.validate,
+			soft_delete: v.soft_delete,
+			versioned: v.versioned,
 		}
 	}
 }
@@ -224,6 +300,11 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			cache_events_ts: v.cache_events_ts,
 			cache_tables_ts: v.cache_tables_ts,
 			cache_indexes_ts: v.cache_indexes_ts,
+			tenant: v.tenant.map(Into::into),
+			assert: v.assert.map(Into::into),
+			validate: v.validate,
+			soft_delete: v.soft_delete,
+			versioned: v.versioned,
 		}
 	}
 }
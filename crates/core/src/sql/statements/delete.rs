@@ -1,10 +1,11 @@
-use crate::sql::{Cond, Explain, Output, SqlValues, Timeout, With};
+use crate::sql::order::Ordering;
+use crate::sql::{Cond, Explain, Limit, Output, SqlValues, Timeout, With};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -15,6 +16,10 @@ pub struct DeleteStatement {
 	#[revision(start = 3)]
 	pub with: Option<With>,
 	pub cond: Option<Cond>,
+	#[revision(start = 4)]
+	pub order: Option<Ordering>,
+	#[revision(start = 4)]
+	pub limit: Option<Limit>,
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
@@ -35,6 +40,12 @@ impl fmt::Display for DeleteStatement {
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.output {
 			write!(f, " {v}")?
 		}
@@ -58,6 +69,8 @@ impl From<DeleteStatement> for crate::expr::statements::DeleteStatement {
 			what: v.what.into(),
 			with: v.with.map(Into::into),
 			cond: v.cond.map(Into::into),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
@@ -73,6 +86,8 @@ impl From<crate::expr::statements::DeleteStatement> for DeleteStatement {
 			what: v.what.into(),
 			with: v.with.map(Into::into),
 			cond: v.cond.map(Into::into),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
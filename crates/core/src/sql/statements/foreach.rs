@@ -1,29 +1,48 @@
-use crate::sql::{Block, Param, SqlValue};
+use crate::sql::{Block, Ident, Kind, Param, SqlValue};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct ForeachStatement {
+	#[revision(start = 2)]
+	pub label: Option<Ident>,
 	pub param: Param,
+	#[revision(start = 3)]
+	pub kind: Option<Kind>,
+	#[revision(start = 2)]
+	pub index: Option<Param>,
 	pub range: SqlValue,
 	pub block: Block,
 }
 
 impl Display for ForeachStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "FOR {} IN {} {}", self.param, self.range, self.block)
+		if let Some(ref v) = self.label {
+			write!(f, "{v}: ")?;
+		}
+		write!(f, "FOR {}", self.param)?;
+		if let Some(ref v) = self.kind {
+			write!(f, ": {v}")?;
+		}
+		if let Some(ref v) = self.index {
+			write!(f, ", {v}")?;
+		}
+		write!(f, " IN {} {}", self.range, self.block)
 	}
 }
 
 impl From<ForeachStatement> for crate::expr::statements::ForeachStatement {
 	fn from(v: ForeachStatement) -> Self {
 		Self {
+			label: v.label.map(Into::into),
 			param: v.param.into(),
+			kind: v.kind.map(Into::into),
+			index: v.index.map(Into::into),
 			range: v.range.into(),
 			block: v.block.into(),
 		}
@@ -33,7 +52,10 @@ impl From<ForeachStatement> for crate::expr::statements::ForeachStatement {
 impl From<crate::expr::statements::ForeachStatement> for ForeachStatement {
 	fn from(v: crate::expr::statements::ForeachStatement) -> Self {
 		Self {
+			label: v.label.map(Into::into),
 			param: v.param.into(),
+			kind: v.kind.map(Into::into),
+			index: v.index.map(Into::into),
 			range: v.range.into(),
 			block: v.block.into(),
 		}
@@ -4,7 +4,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 8)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +29,15 @@ pub enum InfoStatement {
 	#[revision(start = 3)]
 	#[revision(override(revision = 3, discriminant = 10))]
 	Index(Ident, Ident, bool),
+
+	#[revision(start = 6)]
+	Audit(Base),
+
+	#[revision(start = 7)]
+	Outbox,
+
+	#[revision(start = 8)]
+	Schedule(Ident),
 }
 
 impl InfoStatement {
@@ -40,6 +49,9 @@ impl InfoStatement {
 			InfoStatement::Tb(t, _, v) => InfoStatement::Tb(t, true, v),
 			InfoStatement::User(u, b, _) => InfoStatement::User(u, b, true),
 			InfoStatement::Index(i, t, _) => InfoStatement::Index(i, t, true),
+			InfoStatement::Audit(b) => InfoStatement::Audit(b),
+			InfoStatement::Outbox => InfoStatement::Outbox,
+			InfoStatement::Schedule(s) => InfoStatement::Schedule(s),
 		}
 	}
 
@@ -86,6 +98,10 @@ impl fmt::Display for InfoStatement {
 			},
 			Self::Index(i, t, false) => write!(f, "INFO FOR INDEX {i} ON {t}"),
 			Self::Index(i, t, true) => write!(f, "INFO FOR INDEX {i} ON {t} STRUCTURE"),
+			Self::Audit(Base::Db) => f.write_str("INFO FOR DATABASE AUDIT"),
+			Self::Audit(_) => f.write_str("INFO FOR NAMESPACE AUDIT"),
+			Self::Outbox => f.write_str("INFO FOR DATABASE OUTBOX"),
+			Self::Schedule(s) => write!(f, "INFO FOR SCHEDULE {s}"),
 		}
 	}
 }
@@ -99,6 +115,9 @@ impl From<InfoStatement> for crate::expr::statements::InfoStatement {
 			InfoStatement::Tb(t, v, ver) => Self::Tb(t.into(), v, ver.map(Into::into)),
 			InfoStatement::User(u, b, v) => Self::User(u.into(), b.map(Into::into), v),
 			InfoStatement::Index(i, t, v) => Self::Index(i.into(), t.into(), v),
+			InfoStatement::Audit(b) => Self::Audit(b.into()),
+			InfoStatement::Outbox => Self::Outbox,
+			InfoStatement::Schedule(s) => Self::Schedule(s.into()),
 		}
 	}
 }
@@ -118,6 +137,9 @@ impl From<crate::expr::statements::InfoStatement> for InfoStatement {
 			crate::expr::statements::InfoStatement::Index(i, t, v) => {
 				Self::Index(i.into(), t.into(), v)
 			}
+			crate::expr::statements::InfoStatement::Audit(b) => Self::Audit(b.into()),
+			crate::expr::statements::InfoStatement::Outbox => Self::Outbox,
+			crate::expr::statements::InfoStatement::Schedule(s) => Self::Schedule(s.into()),
 		}
 	}
 }
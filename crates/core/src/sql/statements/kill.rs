@@ -1,22 +1,36 @@
-use crate::sql::SqlValue;
+use crate::sql::{Ident, SqlValue};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
+	// or Array of Uuids of Live Queries
 	pub id: SqlValue,
+	// Kill all live queries of the current session,
+	// or of the table when a table is specified
+	#[revision(start = 2)]
+	pub all: bool,
+	#[revision(start = 2)]
+	pub table: Option<Ident>,
 }
 
 impl fmt::Display for KillStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "KILL {}", self.id)
+		if !self.all {
+			return write!(f, "KILL {}", self.id);
+		}
+		write!(f, "KILL ALL")?;
+		if let Some(ref v) = self.table {
+			write!(f, " FOR TABLE {v}")?
+		}
+		Ok(())
 	}
 }
 
@@ -24,6 +38,8 @@ impl From<KillStatement> for crate::expr::statements::KillStatement {
 	fn from(v: KillStatement) -> Self {
 		Self {
 			id: v.id.into(),
+			all: v.all,
+			table: v.table.map(Into::into),
 		}
 	}
 }
@@ -32,6 +48,8 @@ impl From<crate::expr::statements::KillStatement> for KillStatement {
 	fn from(v: crate::expr::statements::KillStatement) -> Self {
 		Self {
 			id: v.id.into(),
+			all: v.all,
+			table: v.table.map(Into::into),
 		}
 	}
 }
@@ -1,11 +1,11 @@
 use crate::iam::Auth;
-use crate::sql::{Cond, Fetchs, Fields, SqlValue, Uuid};
+use crate::sql::{Cond, Fetchs, Fields, Groups, SqlValue, Uuid};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +16,8 @@ pub struct LiveStatement {
 	pub what: SqlValue,
 	pub cond: Option<Cond>,
 	pub fetch: Option<Fetchs>,
+	#[revision(start = 2)]
+	pub group: Option<Groups>,
 	// When a live query is created, we must also store the
 	// authenticated session of the user who made the query,
 	// so we can check it later when sending notifications.
@@ -28,6 +30,10 @@ pub struct LiveStatement {
 	// This is optional as it is only set by the database
 	// runtime when storing the live query to storage.
 	pub(crate) session: Option<SqlValue>,
+	/// Whether the current matching records are sent
+	/// before any changes, when the live query starts.
+	#[revision(start = 3)]
+	pub snapshot: bool,
 }
 
 impl LiveStatement {
@@ -55,7 +61,9 @@ impl LiveStatement {
 		expr: Fields,
 		what: SqlValue,
 		cond: Option<Cond>,
+		group: Option<Groups>,
 		fetch: Option<Fetchs>,
+		snapshot: bool,
 	) -> Self {
 		LiveStatement {
 			id: Uuid::new_v4(),
@@ -63,7 +71,9 @@ impl LiveStatement {
 			expr,
 			what,
 			cond,
+			group,
 			fetch,
+			snapshot,
 			..Default::default()
 		}
 	}
@@ -75,9 +85,15 @@ impl fmt::Display for LiveStatement {
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.group {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if self.snapshot {
+			f.write_str(" SNAPSHOT")?
+		}
 		Ok(())
 	}
 }
@@ -91,8 +107,10 @@ impl From<LiveStatement> for crate::expr::statements::LiveStatement {
 			what: v.what.into(),
 			cond: v.cond.map(Into::into),
 			fetch: v.fetch.map(Into::into),
+			group: v.group.map(Into::into),
 			auth: v.auth,
 			session: v.session.map(Into::into),
+			snapshot: v.snapshot,
 		}
 	}
 }
@@ -105,8 +123,10 @@ impl From<crate::expr::statements::LiveStatement> for LiveStatement {
 			what: v.what.into(),
 			cond: v.cond.map(Into::into),
 			fetch: v.fetch.map(Into::into),
+			group: v.group.map(Into::into),
 			auth: v.auth,
 			session: v.session.map(Into::into),
+			snapshot: v.snapshot,
 		}
 	}
 }
@@ -25,6 +25,8 @@ pub(crate) mod set;
 pub(crate) mod show;
 pub(crate) mod sleep;
 pub(crate) mod throw;
+pub(crate) mod truncate;
+pub(crate) mod trycatch;
 pub(crate) mod update;
 pub(crate) mod upsert;
 pub(crate) mod r#use;
@@ -52,6 +54,8 @@ pub use self::set::SetStatement;
 pub use self::show::ShowStatement;
 pub use self::sleep::SleepStatement;
 pub use self::throw::ThrowStatement;
+pub use self::truncate::TruncateStatement;
+pub use self::trycatch::TrycatchStatement;
 pub use self::update::UpdateStatement;
 pub use self::upsert::UpsertStatement;
 pub use self::r#use::UseStatement;
@@ -62,7 +66,7 @@ pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
 	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DefineUserStatement,
+	DefineTableStatement, DefineUserStatement, Tenant,
 };
 
 pub use self::remove::{
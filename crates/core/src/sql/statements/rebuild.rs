@@ -37,7 +37,7 @@ impl From<crate::expr::statements::rebuild::RebuildStatement> for RebuildStateme
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -45,6 +45,8 @@ pub struct RebuildIndexStatement {
 	pub name: Ident,
 	pub what: Ident,
 	pub if_exists: bool,
+	#[revision(start = 2)]
+	pub concurrently: bool,
 }
 
 impl Display for RebuildIndexStatement {
@@ -54,6 +56,9 @@ impl Display for RebuildIndexStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.concurrently {
+			write!(f, " CONCURRENTLY")?
+		}
 		Ok(())
 	}
 }
@@ -64,6 +69,7 @@ impl From<RebuildIndexStatement> for crate::expr::statements::rebuild::RebuildIn
 			name: v.name.into(),
 			what: v.what.into(),
 			if_exists: v.if_exists,
+			concurrently: v.concurrently,
 		}
 	}
 }
@@ -74,6 +80,7 @@ impl From<crate::expr::statements::rebuild::RebuildIndexStatement> for RebuildIn
 			name: v.name.into(),
 			what: v.what.into(),
 			if_exists: v.if_exists,
+			concurrently: v.concurrently,
 		}
 	}
 }
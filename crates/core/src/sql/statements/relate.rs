@@ -4,7 +4,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -19,6 +19,8 @@ pub struct RelateStatement {
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
+	#[revision(start = 3)]
+	pub zip: bool,
 }
 
 impl fmt::Display for RelateStatement {
@@ -31,6 +33,9 @@ impl fmt::Display for RelateStatement {
 		if self.uniq {
 			f.write_str(" UNIQUE")?
 		}
+		if self.zip {
+			f.write_str(" ZIP")?
+		}
 		if let Some(ref v) = self.data {
 			write!(f, " {v}")?
 		}
@@ -59,6 +64,7 @@ impl From<RelateStatement> for crate::expr::statements::RelateStatement {
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
+			zip: v.zip,
 		}
 	}
 }
@@ -75,6 +81,7 @@ impl From<crate::expr::statements::RelateStatement> for RelateStatement {
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
+			zip: v.zip,
 		}
 	}
 }
@@ -4,7 +4,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -13,6 +13,8 @@ pub struct RemoveFieldStatement {
 	pub what: Ident,
 	#[revision(start = 2)]
 	pub if_exists: bool,
+	#[revision(start = 3)]
+	pub cascade: bool,
 }
 
 impl Display for RemoveFieldStatement {
@@ -22,6 +24,9 @@ impl Display for RemoveFieldStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.cascade {
+			write!(f, " CASCADE")?
+		}
 		Ok(())
 	}
 }
@@ -32,6 +37,7 @@ impl From<RemoveFieldStatement> for crate::expr::statements::RemoveFieldStatemen
 			name: v.name.into(),
 			if_exists: v.if_exists,
 			what: v.what.into(),
+			cascade: v.cascade,
 		}
 	}
 }
@@ -42,6 +48,7 @@ impl From<crate::expr::statements::RemoveFieldStatement> for RemoveFieldStatemen
 			name: v.name.into(),
 			if_exists: v.if_exists,
 			what: v.what.into(),
+			cascade: v.cascade,
 		}
 	}
 }
@@ -9,6 +9,7 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod schedule;
 mod sequence;
 mod table;
 mod user;
@@ -24,6 +25,7 @@ pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use schedule::RemoveScheduleStatement;
 pub use sequence::RemoveSequenceStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
@@ -51,6 +53,7 @@ pub enum RemoveStatement {
 	Model(RemoveModelStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Schedule(RemoveScheduleStatement),
 }
 
 impl Display for RemoveStatement {
@@ -70,6 +73,7 @@ impl Display for RemoveStatement {
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Bucket(v) => Display::fmt(v, f),
 			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Schedule(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -91,6 +95,7 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Model(v) => Self::Model(v.into()),
 			RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			RemoveStatement::Schedule(v) => Self::Schedule(v.into()),
 		}
 	}
 }
@@ -112,6 +117,7 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Model(v) => Self::Model(v.into()),
 			crate::expr::statements::RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::RemoveStatement::Schedule(v) => Self::Schedule(v.into()),
 		}
 	}
 }
@@ -0,0 +1,43 @@
+use crate::sql::Ident;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemoveScheduleStatement {
+	pub name: Ident,
+	pub if_exists: bool,
+}
+
+impl Display for RemoveScheduleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE SCHEDULE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {}", self.name)?;
+		Ok(())
+	}
+}
+
+impl From<RemoveScheduleStatement> for crate::expr::statements::remove::RemoveScheduleStatement {
+	fn from(v: RemoveScheduleStatement) -> Self {
+		crate::expr::statements::remove::RemoveScheduleStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::remove::RemoveScheduleStatement> for RemoveScheduleStatement {
+	fn from(v: crate::expr::statements::remove::RemoveScheduleStatement) -> Self {
+		RemoveScheduleStatement {
+			name: v.name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
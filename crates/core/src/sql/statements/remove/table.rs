@@ -4,7 +4,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -14,6 +14,8 @@ pub struct RemoveTableStatement {
 	pub if_exists: bool,
 	#[revision(start = 3)]
 	pub expunge: bool,
+	#[revision(start = 4)]
+	pub cascade: bool,
 }
 
 impl Display for RemoveTableStatement {
@@ -23,6 +25,9 @@ impl Display for RemoveTableStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {}", self.name)?;
+		if self.cascade {
+			write!(f, " CASCADE")?
+		}
 		Ok(())
 	}
 }
@@ -33,6 +38,7 @@ impl From<RemoveTableStatement> for crate::expr::statements::RemoveTableStatemen
 			name: v.name.into(),
 			if_exists: v.if_exists,
 			expunge: v.expunge,
+			cascade: v.cascade,
 		}
 	}
 }
@@ -43,6 +49,7 @@ impl From<crate::expr::statements::RemoveTableStatement> for RemoveTableStatemen
 			name: v.name.into(),
 			if_exists: v.if_exists,
 			expunge: v.expunge,
+			cascade: v.cascade,
 		}
 	}
 }
@@ -1,7 +1,7 @@
 use crate::sql::{
 	Cond, Explain, Fetchs, Fields, Groups, Idioms, Limit, Splits, SqlValues, Start, Timeout,
 	Version, With,
-	order::{OldOrders, Order, OrderList, Ordering},
+	order::{Collate, OldOrders, Order, OrderList, Ordering},
 };
 use anyhow::Result;
 
@@ -9,7 +9,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -25,6 +25,9 @@ pub struct SelectStatement {
 	pub cond: Option<Cond>,
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
+	/// The condition applied to the output rows, after any grouping.
+	#[revision(start = 5)]
+	pub having: Option<Cond>,
 	#[revision(end = 4, convert_fn = "convert_old_orders")]
 	pub old_order: Option<OldOrders>,
 	#[revision(start = 4)]
@@ -35,6 +38,9 @@ pub struct SelectStatement {
 	pub version: Option<Version>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
+	/// The number of tasks requested by a PARALLEL clause
+	#[revision(start = 6)]
+	pub parallel_tasks: Option<u32>,
 	pub explain: Option<Explain>,
 	#[revision(start = 3)]
 	pub tempfiles: bool,
@@ -60,9 +66,10 @@ impl SelectStatement {
 			x.0.into_iter()
 				.map(|x| Order {
 					value: x.order,
-					collate: x.collate,
+					collate: x.collate.then_some(Collate::Lexical),
 					numeric: x.numeric,
 					direction: x.direction,
+					nulls: None,
 				})
 				.collect();
 
@@ -95,6 +102,9 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.having {
+			write!(f, " HAVING {}", v.0)?
+		}
 		if let Some(ref v) = self.order {
 			write!(f, " {v}")?
 		}
@@ -116,6 +126,9 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
+		if let Some(v) = self.parallel_tasks {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
@@ -134,6 +147,7 @@ impl From<SelectStatement> for crate::expr::statements::SelectStatement {
 			cond: v.cond.map(Into::into),
 			split: v.split.map(Into::into),
 			group: v.group.map(Into::into),
+			having: v.having.map(Into::into),
 			order: v.order.map(Into::into),
 			limit: v.limit.map(Into::into),
 			start: v.start.map(Into::into),
@@ -141,6 +155,7 @@ impl From<SelectStatement> for crate::expr::statements::SelectStatement {
 			version: v.version.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
+			parallel_tasks: v.parallel_tasks,
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
 		}
@@ -158,6 +173,7 @@ impl From<crate::expr::statements::SelectStatement> for SelectStatement {
 			cond: v.cond.map(Into::into),
 			split: v.split.map(Into::into),
 			group: v.group.map(Into::into),
+			having: v.having.map(Into::into),
 			order: v.order.map(Into::into),
 			limit: v.limit.map(Into::into),
 			start: v.start.map(Into::into),
@@ -165,6 +181,7 @@ impl From<crate::expr::statements::SelectStatement> for SelectStatement {
 			version: v.version.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
+			parallel_tasks: v.parallel_tasks,
 			explain: v.explain.map(Into::into),
 			tempfiles: v.tempfiles,
 		}
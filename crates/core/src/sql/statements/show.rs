@@ -48,13 +48,15 @@ impl From<crate::expr::statements::show::ShowSince> for ShowSince {
 }
 
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct ShowStatement {
 	pub table: Option<Table>,
 	pub since: ShowSince,
+	#[revision(start = 2)]
+	pub until: Option<ShowSince>,
 	pub limit: Option<u32>,
 }
 
@@ -69,6 +71,11 @@ impl fmt::Display for ShowStatement {
 			ShowSince::Timestamp(ref v) => write!(f, " SINCE {}", v)?,
 			ShowSince::Versionstamp(ref v) => write!(f, " SINCE {}", v)?,
 		}
+		match self.until {
+			Some(ShowSince::Timestamp(ref v)) => write!(f, " UNTIL {}", v)?,
+			Some(ShowSince::Versionstamp(ref v)) => write!(f, " UNTIL {}", v)?,
+			None => {}
+		}
 		if let Some(ref v) = self.limit {
 			write!(f, " LIMIT {}", v)?
 		}
@@ -81,6 +88,7 @@ impl From<ShowStatement> for crate::expr::statements::ShowStatement {
 		crate::expr::statements::ShowStatement {
 			table: v.table.map(Into::into),
 			since: v.since.into(),
+			until: v.until.map(Into::into),
 			limit: v.limit,
 		}
 	}
@@ -91,6 +99,7 @@ impl From<crate::expr::statements::ShowStatement> for ShowStatement {
 		ShowStatement {
 			table: v.table.map(Into::into),
 			since: v.since.into(),
+			until: v.until.map(Into::into),
 			limit: v.limit,
 		}
 	}
@@ -0,0 +1,35 @@
+use crate::sql::Ident;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TruncateStatement {
+	pub name: Ident,
+}
+
+impl Display for TruncateStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TRUNCATE TABLE {}", self.name)
+	}
+}
+
+impl From<TruncateStatement> for crate::expr::statements::TruncateStatement {
+	fn from(v: TruncateStatement) -> Self {
+		Self {
+			name: v.name.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::TruncateStatement> for TruncateStatement {
+	fn from(v: crate::expr::statements::TruncateStatement) -> Self {
+		Self {
+			name: v.name.into(),
+		}
+	}
+}
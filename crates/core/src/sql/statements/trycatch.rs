@@ -0,0 +1,45 @@
+use crate::sql::{Block, Param};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TrycatchStatement {
+	pub block: Block,
+	pub param: Option<Param>,
+	pub catch: Block,
+}
+
+impl Display for TrycatchStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TRY {} CATCH", self.block)?;
+		if let Some(ref v) = self.param {
+			write!(f, " ({v})")?;
+		}
+		write!(f, " {}", self.catch)
+	}
+}
+
+impl From<TrycatchStatement> for crate::expr::statements::TrycatchStatement {
+	fn from(v: TrycatchStatement) -> Self {
+		Self {
+			block: v.block.into(),
+			param: v.param.map(Into::into),
+			catch: v.catch.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::TrycatchStatement> for TrycatchStatement {
+	fn from(v: crate::expr::statements::TrycatchStatement) -> Self {
+		Self {
+			block: v.block.into(),
+			param: v.param.map(Into::into),
+			catch: v.catch.into(),
+		}
+	}
+}
@@ -1,10 +1,11 @@
-use crate::sql::{Cond, Data, Explain, Output, SqlValues, Timeout, With};
+use crate::sql::order::Ordering;
+use crate::sql::{Cond, Data, Explain, Limit, Output, SqlValue, SqlValues, Timeout, With};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -15,7 +16,14 @@ pub struct UpdateStatement {
 	#[revision(start = 3)]
 	pub with: Option<With>,
 	pub data: Option<Data>,
+	/// The version which the records are expected to be at
+	#[revision(start = 5)]
+	pub if_version: Option<SqlValue>,
 	pub cond: Option<Cond>,
+	#[revision(start = 4)]
+	pub order: Option<Ordering>,
+	#[revision(start = 4)]
+	pub limit: Option<Limit>,
 	pub output: Option<Output>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
@@ -36,9 +44,18 @@ impl fmt::Display for UpdateStatement {
 		if let Some(ref v) = self.data {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.if_version {
+			write!(f, " IF VERSION {v}")?
+		}
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.output {
 			write!(f, " {v}")?
 		}
@@ -62,7 +79,10 @@ impl From<UpdateStatement> for crate::expr::statements::UpdateStatement {
 			what: v.what.into(),
 			with: v.with.map(Into::into),
 			data: v.data.map(Into::into),
+			if_version: v.if_version.map(Into::into),
 			cond: v.cond.map(Into::into),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
@@ -78,7 +98,10 @@ impl From<crate::expr::statements::UpdateStatement> for UpdateStatement {
 			what: v.what.into(),
 			with: v.with.map(Into::into),
 			data: v.data.map(Into::into),
+			if_version: v.if_version.map(Into::into),
 			cond: v.cond.map(Into::into),
+			order: v.order.map(Into::into),
+			limit: v.limit.map(Into::into),
 			output: v.output.map(Into::into),
 			timeout: v.timeout.map(Into::into),
 			parallel: v.parallel,
@@ -33,6 +33,9 @@ impl Display for TableType {
 				if rel.enforced {
 					write!(f, " ENFORCED")?;
 				}
+				if rel.unique {
+					write!(f, " UNIQUE")?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -62,7 +65,7 @@ impl From<crate::expr::TableType> for TableType {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -71,6 +74,9 @@ pub struct Relation {
 	pub to: Option<Kind>,
 	#[revision(start = 2)]
 	pub enforced: bool,
+	/// Whether a pair of records can only be related once
+	#[revision(start = 3)]
+	pub unique: bool,
 }
 
 impl From<Relation> for crate::expr::Relation {
@@ -79,6 +85,7 @@ impl From<Relation> for crate::expr::Relation {
 			from: v.from.map(Into::into),
 			to: v.to.map(Into::into),
 			enforced: v.enforced,
+			unique: v.unique,
 		}
 	}
 }
@@ -89,6 +96,7 @@ impl From<crate::expr::Relation> for Relation {
 			from: v.from.map(Into::into),
 			to: v.to.map(Into::into),
 			enforced: v.enforced,
+			unique: v.unique,
 		}
 	}
 }
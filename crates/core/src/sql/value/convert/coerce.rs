@@ -8,9 +8,9 @@ use geo::Point;
 use rust_decimal::Decimal;
 
 use crate::sql::{
-	Array, Bytes, Closure, Datetime, Duration, File, Geometry, Ident, Kind, Literal, Number,
-	Object, Range, Regex, SqlValue, Strand, Table, Thing, Uuid, array::Uniq, kind::HasKind,
-	value::Null,
+	Array, ArrayConstraints, Bytes, Closure, Datetime, Duration, File, Geometry, Ident, Kind,
+	Literal, Number, Object, Range, Regex, SqlValue, Strand, Table, Thing, Uuid, array::Uniq,
+	kind::HasKind, value::Null,
 };
 
 #[derive(Clone, Debug)]
@@ -24,6 +24,17 @@ pub enum CoerceError {
 		len: usize,
 		into: String,
 	},
+	// Coerce failed because the array has an element past its maximum length.
+	TooManyElements {
+		index: usize,
+		into: String,
+	},
+	// Coerce failed because an element of an array with unique elements is repeated.
+	DuplicateElement {
+		index: usize,
+		first: usize,
+		into: String,
+	},
 	// Coerce failed because element of type didn't match.
 	ElementOf {
 		inner: Box<CoerceError>,
@@ -53,6 +64,25 @@ impl fmt::Display for CoerceError {
 			} => {
 				write!(f, "Expected `{into}` but found an collection of length `{len}`")
 			}
+			CoerceError::TooManyElements {
+				index,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but found an element at index `{index}`, past the maximum length"
+				)
+			}
+			CoerceError::DuplicateElement {
+				index,
+				first,
+				into,
+			} => {
+				write!(
+					f,
+					"Expected `{into}` but the element at index `{index}` is a duplicate of the element at index `{first}`"
+				)
+			}
 		}
 	}
 }
@@ -78,6 +108,31 @@ impl<T> CoerceErrorExt for Result<T, CoerceError> {
 	}
 }
 
+/// Checks the length of an array against the bounds of a kind
+fn check_array_len<F>(
+	len: usize,
+	constraints: &ArrayConstraints,
+	into: F,
+) -> Result<(), CoerceError>
+where
+	F: Fn() -> String,
+{
+	match constraints.max {
+		// An exact length reports the length of the array
+		Some(max) if (len as u64) > max && constraints.exact_len().is_none() => {
+			Err(CoerceError::TooManyElements {
+				index: max as usize,
+				into: into(),
+			})
+		}
+		_ if !constraints.allows_len(len) => Err(CoerceError::InvalidLength {
+			len,
+			into: into(),
+		}),
+		_ => Ok(()),
+	}
+}
+
 /// Trait for converting the value using coercion rules.
 ///
 /// Coercion rules are applied whenever a Value needs to be of a specific [`Kind`].
@@ -443,13 +498,13 @@ impl SqlValue {
 			Kind::Regex => self.can_coerce_to::<Regex>(),
 			Kind::Range => self.can_coerce_to::<Box<Range>>(),
 			Kind::Function(_, _) => self.can_coerce_to::<Box<Closure>>(),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.can_coerce_to_array_len(t, *l),
-				None => self.can_coerce_to_array(t),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.can_coerce_to_array(t),
+				false => self.clone().coerce_to_set_kind_constrained(t, c).is_ok(),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.can_coerce_to_array_len(t, *l),
-				None => self.can_coerce_to_array(t),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.can_coerce_to_array(t),
+				false => self.can_coerce_to_array_constrained(t, c),
 			},
 			Kind::Record(t) => {
 				if t.is_empty() {
@@ -482,10 +537,14 @@ impl SqlValue {
 		}
 	}
 
-	fn can_coerce_to_array_len(&self, kind: &Kind, len: u64) -> bool {
+	fn can_coerce_to_array_constrained(&self, kind: &Kind, constraints: &ArrayConstraints) -> bool {
 		match self {
-			SqlValue::Array(a) => {
-				a.len() as u64 == len && a.iter().all(|x| x.can_coerce_to_kind(kind))
+			SqlValue::Array(a) if !constraints.unique => {
+				constraints.allows_len(a.len()) && a.iter().all(|x| x.can_coerce_to_kind(kind))
+			}
+			// The elements must be coerced before they can be compared
+			SqlValue::Array(_) => {
+				self.clone().coerce_to_array_kind_constrained(kind, constraints).is_ok()
 			}
 			_ => false,
 		}
@@ -548,13 +607,13 @@ impl SqlValue {
 			Kind::Regex => self.coerce_to::<Regex>().map(SqlValue::from),
 			Kind::Range => self.coerce_to::<Box<Range>>().map(SqlValue::from),
 			Kind::Function(_, _) => self.coerce_to::<Box<Closure>>().map(SqlValue::from),
-			Kind::Set(t, l) => match l {
-				Some(l) => self.coerce_to_set_kind_len(t, *l).map(SqlValue::from),
-				None => self.coerce_to_set_kind(t).map(SqlValue::from),
+			Kind::Set(t, c) => match c.is_unbounded() {
+				true => self.coerce_to_set_kind(t).map(SqlValue::from),
+				false => self.coerce_to_set_kind_constrained(t, c).map(SqlValue::from),
 			},
-			Kind::Array(t, l) => match l {
-				Some(l) => self.coerce_to_array_type_len(t, *l).map(SqlValue::from),
-				None => self.coerce_to_array_type(t).map(SqlValue::from),
+			Kind::Array(t, c) => match c.is_unbounded() && !c.unique {
+				true => self.coerce_to_array_type(t).map(SqlValue::from),
+				false => self.coerce_to_array_kind_constrained(t, c).map(SqlValue::from),
 			},
 			Kind::Record(t) => match t.is_empty() {
 				true => self.coerce_to::<Thing>().map(SqlValue::from),
@@ -657,26 +716,38 @@ impl SqlValue {
 			.with_element_of(|| format!("array<{kind}>"))
 	}
 
-	/// Try to coerce this value to an `Array` of a certain type, and length
-	pub(crate) fn coerce_to_array_type_len(
+	/// Try to coerce this value to an `Array` of a certain type, within the
+	/// length bounds of the kind, and with unique values if the kind requires it
+	pub(crate) fn coerce_to_array_kind_constrained(
 		self,
 		kind: &Kind,
-		len: u64,
+		constraints: &ArrayConstraints,
 	) -> Result<Array, CoerceError> {
 		let array = self.coerce_to::<Array>()?;
 
-		if array.len() as u64 != len {
-			return Err(CoerceError::InvalidLength {
-				len: array.len(),
-				into: format!("array<{kind},{len}>"),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("array<{kind},{len}>"),
+			None => Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
-		array
+		let array = array
 			.into_iter()
 			.map(|value| value.coerce_to_kind(kind))
 			.collect::<Result<Array, CoerceError>>()
-			.with_element_of(|| format!("array<{kind}>"))
+			.with_element_of(|| format!("array<{kind}>"))?;
+
+		if constraints.unique {
+			// Duplicates are compared once the elements are coerced
+			if let Some((index, first)) = ArrayConstraints::find_duplicate(&array) {
+				return Err(CoerceError::DuplicateElement {
+					index,
+					first,
+					into: Kind::Array(Box::new(kind.clone()), *constraints).to_string(),
+				});
+			}
+		}
+
+		Ok(array)
 	}
 
 	/// Try to coerce this value to an `Array` of a certain type, unique values
@@ -689,11 +760,12 @@ impl SqlValue {
 			.with_element_of(|| format!("set<{kind}>"))
 	}
 
-	/// Try to coerce this value to an `Array` of a certain type, unique values, and length
-	pub(crate) fn coerce_to_set_kind_len(
+	/// Try to coerce this value to an `Array` of a certain type, unique values,
+	/// and within the length bounds of the kind
+	pub(crate) fn coerce_to_set_kind_constrained(
 		self,
 		kind: &Kind,
-		len: u64,
+		constraints: &ArrayConstraints,
 	) -> Result<Array, CoerceError> {
 		let array = self
 			.coerce_to::<Array>()?
@@ -703,12 +775,10 @@ impl SqlValue {
 			.collect::<Result<Array, CoerceError>>()
 			.with_element_of(|| format!("set<{kind}>"))?;
 
-		if array.len() as u64 != len {
-			return Err(CoerceError::InvalidLength {
-				into: format!("set<{kind}, {len}>"),
-				len: array.len(),
-			});
-		}
+		check_array_len(array.len(), constraints, || match constraints.exact_len() {
+			Some(len) => format!("set<{kind}, {len}>"),
+			None => Kind::Set(Box::new(kind.clone()), *constraints).to_string(),
+		})?;
 
 		Ok(array)
 	}
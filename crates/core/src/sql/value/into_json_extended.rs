@@ -0,0 +1,383 @@
+//! Lossless conversion between values and extended JSON.
+//!
+//! Extended JSON keeps the JSON null, boolean, string, array, and finite
+//! number types as they are, and represents every other value as an object
+//! with a single key which identifies the type of the value:
+//!
+//! | Value                     | Extended JSON                               |
+//! | ------------------------- | ------------------------------------------- |
+//! | `NONE`                    | `{ "$none": null }`                         |
+//! | `NaN`, `Infinity`         | `{ "$float": "NaN" }`                       |
+//! | `1.23dec`                 | `{ "$decimal": "1.23" }`                    |
+//! | `1h30m`                   | `{ "$duration": "1h30m" }`                  |
+//! | `d"2024-01-01T00:00:00Z"` | `{ "$datetime": "2024-01-01T00:00:00Z" }`   |
+//! | `u"0190..."`              | `{ "$uuid": "0190..." }`                    |
+//! | The bytes of `"foo"`      | `{ "$bytes": "Zm9v" }`                      |
+//! | `person:1`                | `{ "$record": "person:1" }`                 |
+//! | The table `person`        | `{ "$table": "person" }`                    |
+//! | `(1, 2)`                  | `{ "$geometry": { "type": "Point", ... } }` |
+//! | A query                   | `{ "$query": "SELECT * FROM person" }`      |
+//! | Any other value           | `{ "$surql": "$param" }`                    |
+//!
+//! Bytes are encoded using standard base64, and geometries are encoded as
+//! GeoJSON. Objects which contain a key starting with a `$` are wrapped in
+//! `{ "$object": { ... } }`, so that they can not be mistaken for one of
+//! the types above. When converting back, objects with a single key which
+//! is not one of the types above, such as `{ "$foo": 1 }`, are kept as
+//! plain objects.
+
+use crate::dbs::Capabilities;
+use crate::err::Error;
+use crate::sql::{Array, Bytes, Number, Object, SqlValue, Strand, Table, Uuid};
+use crate::syn;
+use anyhow::{Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rust_decimal::Decimal;
+use serde_json::Map;
+use serde_json::Value as Json;
+use std::str::FromStr;
+
+const NONE: &str = "$none";
+const FLOAT: &str = "$float";
+const DECIMAL: &str = "$decimal";
+const DURATION: &str = "$duration";
+const DATETIME: &str = "$datetime";
+const UUID: &str = "$uuid";
+const BYTES: &str = "$bytes";
+const RECORD: &str = "$record";
+const TABLE: &str = "$table";
+const GEOMETRY: &str = "$geometry";
+const OBJECT: &str = "$object";
+const QUERY: &str = "$query";
+const SURQL: &str = "$surql";
+
+/// The keys which identify the type of a tagged value
+const TAGS: [&str; 13] = [
+	NONE, FLOAT, DECIMAL, DURATION, DATETIME, UUID, BYTES, RECORD, TABLE, GEOMETRY, OBJECT, QUERY,
+	SURQL,
+];
+
+impl SqlValue {
+	/// Converts a `Value` into extended JSON.
+	///
+	/// Unlike [`SqlValue::into_json`], this keeps the type of every value, so
+	/// that the result can be converted back using
+	/// [`SqlValue::from_json_extended`].
+	pub fn into_json_extended(self) -> Json {
+		match self {
+			SqlValue::None => tagged(NONE, Json::Null),
+			SqlValue::Null => Json::Null,
+			SqlValue::Bool(v) => Json::Bool(v),
+			SqlValue::Number(Number::Int(v)) => v.into(),
+			SqlValue::Number(Number::Float(v)) => match v {
+				v if v.is_nan() => tagged(FLOAT, "NaN".into()),
+				v if v == f64::INFINITY => tagged(FLOAT, "Infinity".into()),
+				v if v == f64::NEG_INFINITY => tagged(FLOAT, "-Infinity".into()),
+				v => v.into(),
+			},
+			SqlValue::Number(Number::Decimal(v)) => tagged(DECIMAL, v.to_string().into()),
+			SqlValue::Strand(v) => Json::String(v.0),
+			SqlValue::Duration(v) => tagged(DURATION, v.to_raw().into()),
+			SqlValue::Datetime(v) => tagged(DATETIME, v.to_raw().into()),
+			SqlValue::Uuid(v) => tagged(UUID, v.to_raw().into()),
+			SqlValue::Bytes(v) => tagged(BYTES, STANDARD.encode(v.0).into()),
+			SqlValue::Thing(v) => tagged(RECORD, v.to_string().into()),
+			SqlValue::Table(v) => tagged(TABLE, v.0.into()),
+			SqlValue::Geometry(v) => tagged(GEOMETRY, SqlValue::Geometry(v).into_json()),
+			SqlValue::Array(v) => {
+				Json::Array(v.0.into_iter().map(SqlValue::into_json_extended).collect())
+			}
+			SqlValue::Object(v) => {
+				// Wrap objects which could be mistaken for a tagged value
+				let escape = v.0.keys().any(|k| k.starts_with('$'));
+				let map: Map<String, Json> =
+					v.0.into_iter().map(|(k, v)| (k, v.into_json_extended())).collect();
+				match escape {
+					true => tagged(OBJECT, Json::Object(map)),
+					false => Json::Object(map),
+				}
+			}
+			SqlValue::Query(v) => tagged(QUERY, v.to_string().into()),
+			v => tagged(SURQL, v.to_string().into()),
+		}
+	}
+
+	/// Converts extended JSON into a `Value`.
+	///
+	/// This is the inverse of [`SqlValue::into_json_extended`]. Plain JSON is
+	/// accepted too, in which case only the JSON types are produced. Queries
+	/// and other values which are stored as SurrealQL are parsed with the
+	/// specified capabilities, which should be those of the datastore.
+	pub fn from_json_extended(json: Json, capabilities: &Capabilities) -> Result<SqlValue> {
+		Ok(match json {
+			Json::Null => SqlValue::Null,
+			Json::Bool(v) => SqlValue::Bool(v),
+			Json::Number(v) => match (v.as_i64(), v.as_u64(), v.as_f64()) {
+				(Some(v), _, _) => SqlValue::from(v),
+				(None, Some(v), _) => SqlValue::from(Decimal::from(v)),
+				(None, None, Some(v)) => SqlValue::from(v),
+				_ => bail!(invalid(format!("The number {v} is not supported"))),
+			},
+			Json::String(v) => SqlValue::Strand(Strand::from(v)),
+			Json::Array(v) => SqlValue::Array(Array(
+				v.into_iter()
+					.map(|v| SqlValue::from_json_extended(v, capabilities))
+					.collect::<Result<_>>()?,
+			)),
+			Json::Object(mut v) => {
+				// Objects with a single key identifying a type are tagged values
+				let tag = match v.len() {
+					1 => v.keys().find(|k| TAGS.contains(&k.as_str())).cloned(),
+					_ => None,
+				};
+				match tag {
+					Some(tag) => {
+						let value = v.remove(&tag).unwrap_or_default();
+						from_tagged(&tag, value, capabilities)?
+					}
+					None => from_object(v, capabilities)?,
+				}
+			}
+		})
+	}
+}
+
+/// Creates an object with a single key, which identifies the type of the value
+fn tagged(tag: &str, value: Json) -> Json {
+	let mut map = Map::with_capacity(1);
+	map.insert(tag.to_owned(), value);
+	Json::Object(map)
+}
+
+/// Creates the error returned for invalid extended JSON
+fn invalid(message: String) -> Error {
+	Error::InvalidExtendedJson {
+		message,
+	}
+}
+
+/// Converts the entries of a JSON object into an object
+fn from_object(map: Map<String, Json>, capabilities: &Capabilities) -> Result<SqlValue> {
+	let obj = map
+		.into_iter()
+		.map(|(k, v)| Ok((k, SqlValue::from_json_extended(v, capabilities)?)))
+		.collect::<Result<_>>()?;
+	Ok(SqlValue::Object(Object(obj)))
+}
+
+/// Converts the contents of a tagged object into the value it identifies
+fn from_tagged(tag: &str, value: Json, capabilities: &Capabilities) -> Result<SqlValue> {
+	// Tagged values other than these are stored as a string
+	let value = match (tag, value) {
+		(NONE, Json::Null) => return Ok(SqlValue::None),
+		(OBJECT, Json::Object(v)) => return from_object(v, capabilities),
+		(GEOMETRY, v @ Json::Object(_)) => {
+			return match syn::value(&v.to_string())? {
+				v @ SqlValue::Geometry(_) => Ok(v),
+				_ => {
+					bail!(invalid(format!("The value of '{tag}' is not a valid GeoJSON geometry")))
+				}
+			};
+		}
+		(_, Json::String(v)) if ![NONE, OBJECT, GEOMETRY].contains(&tag) => v,
+		_ => bail!(invalid(format!("The value of '{tag}' has an unexpected type"))),
+	};
+	Ok(match tag {
+		FLOAT => match value.as_str() {
+			"NaN" => SqlValue::from(f64::NAN),
+			"Infinity" => SqlValue::from(f64::INFINITY),
+			"-Infinity" => SqlValue::from(f64::NEG_INFINITY),
+			_ => bail!(invalid(format!("The float '{value}' is not supported"))),
+		},
+		DECIMAL => match Decimal::from_str(&value) {
+			Ok(v) => SqlValue::from(v),
+			Err(_) => bail!(invalid(format!("The decimal '{value}' is not valid"))),
+		},
+		DURATION => SqlValue::Duration(syn::duration(&value)?),
+		DATETIME => SqlValue::Datetime(syn::datetime(&value)?),
+		UUID => match uuid::Uuid::parse_str(&value) {
+			Ok(v) => SqlValue::Uuid(Uuid(v)),
+			Err(_) => bail!(invalid(format!("The uuid '{value}' is not valid"))),
+		},
+		BYTES => match STANDARD.decode(&value) {
+			Ok(v) => SqlValue::Bytes(Bytes(v)),
+			Err(_) => bail!(invalid(format!("The bytes '{value}' are not valid base64"))),
+		},
+		RECORD => SqlValue::Thing(syn::thing(&value)?),
+		TABLE => SqlValue::Table(Table(value)),
+		QUERY => SqlValue::Query(syn::parse_with_capabilities(&value, capabilities)?),
+		SURQL => syn::value_with_capabilities(&value, capabilities)?,
+		_ => bail!(invalid(format!("The type '{tag}' is not supported"))),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::capabilities::{ExperimentalTarget, Targets};
+	use crate::sql::{File, Id, Thing};
+	use crate::syn::Parse;
+	use rstest::rstest;
+	use serde_json::json;
+	use std::collections::BTreeMap;
+
+	fn capabilities() -> Capabilities {
+		Capabilities::all().with_experimental(Targets::<ExperimentalTarget>::All)
+	}
+
+	fn file() -> SqlValue {
+		SqlValue::File(File {
+			bucket: "bucket".to_owned(),
+			key: "/some/key.txt".to_owned(),
+		})
+	}
+
+	fn recursive_thing() -> SqlValue {
+		SqlValue::Thing(Thing {
+			tb: "a".to_owned(),
+			id: Id::from(Array(vec![SqlValue::Thing(Thing {
+				tb: "b".to_owned(),
+				id: Id::from(Object(BTreeMap::from([(
+					"c".to_owned(),
+					SqlValue::Thing(Thing {
+						tb: "d".to_owned(),
+						id: Id::from(1),
+					}),
+				)]))),
+			})])),
+		})
+	}
+
+	#[rstest]
+	#[case::none(SqlValue::None)]
+	#[case::null(SqlValue::Null)]
+	#[case::bool(SqlValue::Bool(true))]
+	#[case::int(SqlValue::from(i64::MIN))]
+	#[case::float(SqlValue::from(1.5))]
+	#[case::float_whole(SqlValue::from(2.0))]
+	#[case::float_infinity(SqlValue::from(f64::INFINITY))]
+	#[case::float_negative_infinity(SqlValue::from(f64::NEG_INFINITY))]
+	#[case::decimal(SqlValue::parse("1.2300dec"))]
+	#[case::decimal_large(SqlValue::from(Decimal::from(u64::MAX)))]
+	#[case::strand(SqlValue::from("foo"))]
+	#[case::strand_record(SqlValue::from("person:1"))]
+	#[case::duration(SqlValue::parse("1y2w3d4h5m6s7ms8us9ns"))]
+	#[case::datetime(SqlValue::parse("d'2024-02-29T12:34:56.789012345Z'"))]
+	#[case::uuid(SqlValue::parse("u'018f2a2e-59c1-7c6e-8b3e-0c1d2e3f4a5b'"))]
+	#[case::bytes(SqlValue::Bytes(Bytes(vec![0, 1, 2, 254, 255])))]
+	#[case::thing(SqlValue::parse("person:tobie"))]
+	#[case::thing_escaped(SqlValue::parse("person:⟨tobie morgan⟩"))]
+	#[case::thing_array(SqlValue::parse("temperature:['London', d'2024-01-01T00:00:00Z']"))]
+	#[case::thing_object(SqlValue::parse("user:{ name: 'tobie', age: 30 }"))]
+	#[case::thing_recursive(recursive_thing())]
+	#[case::table(SqlValue::Table(Table("person".to_owned())))]
+	#[case::array(SqlValue::parse("[1, 'two', 3.0, NONE, NULL, person:1, [2dec]]"))]
+	#[case::object(SqlValue::parse("{ a: 1, b: { c: [1s, person:1] } }"))]
+	#[case::object_escaped(SqlValue::parse("{ '$record': 'person:1' }"))]
+	#[case::object_tagged(SqlValue::parse("{ '$none': NONE, other: true }"))]
+	#[case::geometry_point(SqlValue::parse("(-0.118092, 51.509865)"))]
+	#[case::geometry_polygon(SqlValue::parse(
+		"{ type: 'Polygon', coordinates: [[[-111.0, 45.0], [-111.0, 41.0], [-104.0, 41.0], [-111.0, 45.0]]] }"
+	))]
+	#[case::geometry_collection(SqlValue::parse(
+		"{ type: 'GeometryCollection', geometries: [{ type: 'Point', coordinates: [1.0, 2.0] }] }"
+	))]
+	#[case::param(SqlValue::parse("$param"))]
+	#[case::idiom(SqlValue::parse("person.name[0]"))]
+	#[case::mock(SqlValue::parse("|person:1..10|"))]
+	#[case::regex(SqlValue::parse("/a.*b/"))]
+	#[case::cast(SqlValue::parse("<int> '1'"))]
+	#[case::block(SqlValue::parse("{ LET $a = 1; RETURN $a; }"))]
+	#[case::range(SqlValue::parse("1..=10"))]
+	#[case::idiom_graph(SqlValue::parse("person:1->likes->person"))]
+	#[case::future(SqlValue::parse("<future> { time::now() }"))]
+	#[case::constant(SqlValue::parse("math::pi"))]
+	#[case::function(SqlValue::parse("string::len('foo')"))]
+	#[case::subquery(SqlValue::parse("(SELECT * FROM person)"))]
+	#[case::expression(SqlValue::parse("1 + 2"))]
+	#[case::query(SqlValue::Query(syn::parse("SELECT * FROM person; CREATE person:1;").unwrap()))]
+	#[case::model(SqlValue::parse("ml::classifier<1.0.0>([1, 2])"))]
+	#[case::closure(SqlValue::parse("|$a: int| $a + 1"))]
+	#[case::file(file())]
+	fn test_json_extended_roundtrip(#[case] value: SqlValue) {
+		let json = value.clone().into_json_extended();
+		assert_eq!(SqlValue::from_json_extended(json.clone(), &capabilities()).unwrap(), value);
+		// The value can be sent as JSON text too
+		let text = serde_json::to_string(&json).unwrap();
+		let json: Json = serde_json::from_str(&text).unwrap();
+		assert_eq!(SqlValue::from_json_extended(json, &capabilities()).unwrap(), value);
+	}
+
+	#[test]
+	fn test_json_extended_format() {
+		let value = SqlValue::parse(
+			"{ id: person:1, name: 'Tobie', age: 30, born: d'1990-01-01T00:00:00Z', ttl: 1h, score: 1.5dec, tags: [NONE] }",
+		);
+		assert_eq!(
+			value.into_json_extended(),
+			json!({
+				"id": { "$record": "person:1" },
+				"name": "Tobie",
+				"age": 30,
+				"born": { "$datetime": "1990-01-01T00:00:00Z" },
+				"ttl": { "$duration": "1h" },
+				"score": { "$decimal": "1.5" },
+				"tags": [{ "$none": null }],
+			})
+		);
+		let value = SqlValue::parse("{ '$record': 'person:1' }");
+		assert_eq!(value.into_json_extended(), json!({ "$object": { "$record": "person:1" } }));
+	}
+
+	#[test]
+	fn test_json_extended_invalid() {
+		let tests = [
+			json!({ "$record": 1 }),
+			json!({ "$record": "not a record" }),
+			json!({ "$bytes": "not base64!" }),
+			json!({ "$float": "1.0" }),
+			json!({ "$geometry": { "type": "Unknown" } }),
+		];
+		for json in tests {
+			SqlValue::from_json_extended(json.clone(), &capabilities()).unwrap_err();
+		}
+	}
+
+	#[test]
+	fn test_json_extended_untagged() {
+		let tests = [
+			(json!({ "$foo": 1 }), "{ '$foo': 1 }"),
+			(json!({ "$unknown": "value" }), "{ '$unknown': 'value' }"),
+			(json!({ "$foo": { "$record": "person:1" } }), "{ '$foo': person:1 }"),
+		];
+		for (json, value) in tests {
+			let res = SqlValue::from_json_extended(json, &capabilities()).unwrap();
+			assert_eq!(res, SqlValue::parse(value));
+		}
+	}
+
+	#[test]
+	fn test_json_extended_capabilities() {
+		let json = file().into_json_extended();
+		// Experimental values are rejected unless they are allowed
+		SqlValue::from_json_extended(json.clone(), &Capabilities::default()).unwrap_err();
+		let capabilities = Capabilities::default()
+			.with_experimental(Targets::Some([ExperimentalTarget::Files].into()));
+		assert_eq!(SqlValue::from_json_extended(json, &capabilities).unwrap(), file());
+	}
+
+	#[test]
+	fn test_json_extended_lossy() {
+		let value =
+			SqlValue::parse("{ id: person:1, born: d'1990-01-01T00:00:00Z', tags: [NONE] }");
+		assert_eq!(
+			value.into_json(),
+			json!({
+				"id": "person:1",
+				"born": "1990-01-01T00:00:00Z",
+				"tags": [null],
+			})
+		);
+	}
+}
@@ -8,6 +8,7 @@ mod value;
 mod clear;
 mod flatten;
 mod into_json;
+mod into_json_extended;
 mod put;
 mod walk;
 
@@ -13,7 +13,7 @@ use crate::sql::{
 	id::{Gen, Id},
 	model::Model,
 };
-use crate::sql::{Closure, Ident, Kind};
+use crate::sql::{ArrayConstraints, Closure, Ident, Kind};
 use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
 
@@ -498,7 +498,7 @@ impl SqlValue {
 			SqlValue::Uuid(_) => Some(Kind::Uuid),
 			SqlValue::Array(arr) => Some(Kind::Array(
 				Box::new(arr.first().and_then(|v| v.kind()).unwrap_or_default()),
-				None,
+				ArrayConstraints::default(),
 			)),
 			SqlValue::Object(_) => Some(Kind::Object),
 			SqlValue::Geometry(geo) => Some(Kind::Geometry(vec![geo.as_type().to_string()])),
@@ -0,0 +1,52 @@
+use crate::sql::SqlValue;
+use crate::sql::fmt::Fmt;
+use crate::sql::order::OrderList;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The `OVER (PARTITION BY ... ORDER BY ...)` clause of a window function
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	/// The expressions which split the rows into separate partitions
+	pub partition: Vec<SqlValue>,
+	/// The ordering of the rows within each partition
+	pub order: Option<OrderList>,
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("OVER (")?;
+		if !self.partition.is_empty() {
+			write!(f, "PARTITION BY {}", Fmt::comma_separated(&self.partition))?;
+			if self.order.is_some() {
+				f.write_str(" ")?;
+			}
+		}
+		if let Some(order) = &self.order {
+			write!(f, "ORDER BY {order}")?;
+		}
+		f.write_str(")")
+	}
+}
+
+impl From<Window> for crate::expr::Window {
+	fn from(v: Window) -> Self {
+		Self {
+			partition: v.partition.into_iter().map(Into::into).collect(),
+			order: v.order.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::Window> for Window {
+	fn from(v: crate::expr::Window) -> Self {
+		Self {
+			partition: v.partition.into_iter().map(Into::into).collect(),
+			order: v.order.map(Into::into),
+		}
+	}
+}
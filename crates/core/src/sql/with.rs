@@ -1,25 +1,40 @@
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum With {
 	NoIndex,
+	#[revision(end = 2, convert_fn = "convert_old_index", fields_name = "OldIndexFields")]
 	Index(Vec<String>),
+	/// The indexes which can be used, in order of preference
+	#[revision(start = 2)]
+	Index(Vec<IndexHint>),
+	/// The deleted records of soft delete tables are included
+	#[revision(start = 3)]
+	Deleted,
+}
+
+impl With {
+	fn convert_old_index(fields: OldIndexFields, _revision: u16) -> Result<Self, revision::Error> {
+		Ok(With::Index(fields.0.into_iter().map(IndexHint::from).collect()))
+	}
 }
 
 impl Display for With {
-	fn fmt(&self, f: &mut Formatter) -> Result {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		f.write_str("WITH")?;
 		match self {
 			With::NoIndex => f.write_str(" NOINDEX"),
 			With::Index(i) => {
 				f.write_str(" INDEX ")?;
-				f.write_str(&i.join(","))
+				let hints: Vec<String> = i.iter().map(ToString::to_string).collect();
+				f.write_str(&hints.join(","))
 			}
+			With::Deleted => f.write_str(" DELETED"),
 		}
 	}
 }
@@ -28,7 +43,8 @@ impl From<With> for crate::expr::With {
 	fn from(v: With) -> Self {
 		match v {
 			With::NoIndex => Self::NoIndex,
-			With::Index(i) => Self::Index(i),
+			With::Index(i) => Self::Index(i.into_iter().map(Into::into).collect()),
+			With::Deleted => Self::Deleted,
 		}
 	}
 }
@@ -36,7 +52,57 @@ impl From<crate::expr::With> for With {
 	fn from(v: crate::expr::With) -> Self {
 		match v {
 			crate::expr::With::NoIndex => Self::NoIndex,
-			crate::expr::With::Index(i) => Self::Index(i),
+			crate::expr::With::Index(i) => Self::Index(i.into_iter().map(Into::into).collect()),
+			crate::expr::With::Deleted => Self::Deleted,
+		}
+	}
+}
+
+/// An index listed in a WITH INDEX clause, which is either used
+/// for every table of the statement, or only for a single table
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct IndexHint {
+	/// The table which the index is listed for, or none for every table
+	pub table: Option<String>,
+	/// The name of the index
+	pub index: String,
+}
+
+impl From<String> for IndexHint {
+	fn from(index: String) -> Self {
+		Self {
+			table: None,
+			index,
+		}
+	}
+}
+
+impl Display for IndexHint {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let Some(tb) = &self.table {
+			write!(f, "{tb}.")?;
+		}
+		f.write_str(&self.index)
+	}
+}
+
+impl From<IndexHint> for crate::expr::IndexHint {
+	fn from(v: IndexHint) -> Self {
+		Self {
+			table: v.table,
+			index: v.index,
+		}
+	}
+}
+
+impl From<crate::expr::IndexHint> for IndexHint {
+	fn from(v: crate::expr::IndexHint) -> Self {
+		Self {
+			table: v.table,
+			index: v.index,
 		}
 	}
 }
@@ -32,6 +32,8 @@ pub static RESERVED_KEYWORD: phf::Set<UniCase<&'static str>> = phf_set! {
 	UniCase::ascii("SHOW"),
 	UniCase::ascii("SLEEP"),
 	UniCase::ascii("THROW"),
+	UniCase::ascii("TRUNCATE"),
+	UniCase::ascii("TRY"),
 	UniCase::ascii("UPDATE"),
 	UniCase::ascii("UPSERT"),
 	UniCase::ascii("USE"),
@@ -59,8 +61,10 @@ pub fn could_be_reserved(s: &str) -> bool {
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map! {
 	// Keywords
 	UniCase::ascii("ACCESS") => TokenKind::Keyword(Keyword::Access),
+	UniCase::ascii("ADD") => TokenKind::Keyword(Keyword::Add),
 	UniCase::ascii("AFTER") => TokenKind::Keyword(Keyword::After),
 	UniCase::ascii("ALGORITHM") => TokenKind::Keyword(Keyword::Algorithm),
+	UniCase::ascii("ALLOW") => TokenKind::Keyword(Keyword::Allow),
 	UniCase::ascii("ALL") => TokenKind::Keyword(Keyword::All),
 	UniCase::ascii("ALTER") => TokenKind::Keyword(Keyword::Alter),
 	UniCase::ascii("ALWAYS") => TokenKind::Keyword(Keyword::Always),
@@ -73,6 +77,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ASCII") => TokenKind::Keyword(Keyword::Ascii),
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
+	UniCase::ascii("AUDIT") => TokenKind::Keyword(Keyword::Audit),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
 	UniCase::ascii("AUTO") => TokenKind::Keyword(Keyword::Auto),
 	UniCase::ascii("BACKEND") => TokenKind::Keyword(Keyword::Backend),
@@ -84,26 +89,35 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("BM25") => TokenKind::Keyword(Keyword::Bm25),
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
 	UniCase::ascii("BUCKET") => TokenKind::Keyword(Keyword::Bucket),
+	UniCase::ascii("BUILD") => TokenKind::Keyword(Keyword::Build),
 	UniCase::ascii("BY") => TokenKind::Keyword(Keyword::By),
 	UniCase::ascii("CAMEL") => TokenKind::Keyword(Keyword::Camel),
 	UniCase::ascii("CANCEL") => TokenKind::Keyword(Keyword::Cancel),
 	UniCase::ascii("CASCADE") => TokenKind::Keyword(Keyword::Cascade),
+	UniCase::ascii("CATCH") => TokenKind::Keyword(Keyword::Catch),
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
 	UniCase::ascii("CHANGES") => TokenKind::Keyword(Keyword::Changes),
+	UniCase::ascii("CAPABILITIES") => TokenKind::Keyword(Keyword::Capabilities),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
 	UniCase::ascii("CLASS") => TokenKind::Keyword(Keyword::Class),
 	UniCase::ascii("COMMENT") => TokenKind::Keyword(Keyword::Comment),
 	UniCase::ascii("COMMIT") => TokenKind::Keyword(Keyword::Commit),
 	UniCase::ascii("CONCURRENTLY") => TokenKind::Keyword(Keyword::Concurrently),
 	UniCase::ascii("CONFIG") => TokenKind::Keyword(Keyword::Config),
+	UniCase::ascii("COMPUTED") => TokenKind::Keyword(Keyword::Computed),
 	UniCase::ascii("CONTENT") => TokenKind::Keyword(Keyword::Content),
 	UniCase::ascii("CONTINUE") => TokenKind::Keyword(Keyword::Continue),
 	UniCase::ascii("CREATE") => TokenKind::Keyword(Keyword::Create),
+	UniCase::ascii("CREATED") => TokenKind::Keyword(Keyword::Created),
+	UniCase::ascii("CRON") => TokenKind::Keyword(Keyword::Cron),
 	UniCase::ascii("DATABASE") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DB") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DEFAULT") => TokenKind::Keyword(Keyword::Default),
 	UniCase::ascii("DEFINE") => TokenKind::Keyword(Keyword::Define),
 	UniCase::ascii("DELETE") => TokenKind::Keyword(Keyword::Delete),
+	UniCase::ascii("DELETED") => TokenKind::Keyword(Keyword::Deleted),
+	UniCase::ascii("DENY") => TokenKind::Keyword(Keyword::Deny),
+	UniCase::ascii("DEPTH") => TokenKind::Keyword(Keyword::Depth),
 	UniCase::ascii("DESCENDING") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DESC") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DIFF") => TokenKind::Keyword(Keyword::Diff),
@@ -124,6 +138,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
 	UniCase::ascii("EXCLUDE") => TokenKind::Keyword(Keyword::Exclude),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
+	UniCase::ascii("EXPIRE") => TokenKind::Keyword(Keyword::Expire),
 	UniCase::ascii("EXPIRED") => TokenKind::Keyword(Keyword::Expired),
 	UniCase::ascii("EXPLAIN") => TokenKind::Keyword(Keyword::Explain),
 	UniCase::ascii("EXPUNGE") => TokenKind::Keyword(Keyword::Expunge),
@@ -134,6 +149,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("FIELDS") => TokenKind::Keyword(Keyword::Fields),
 	UniCase::ascii("COLUMNS") => TokenKind::Keyword(Keyword::Fields),
 	UniCase::ascii("FILTERS") => TokenKind::Keyword(Keyword::Filters),
+	UniCase::ascii("FIRST") => TokenKind::Keyword(Keyword::First),
 	UniCase::ascii("FLEXIBLE") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEXI") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEX") => TokenKind::Keyword(Keyword::Flexible),
@@ -145,6 +161,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("GRANT") => TokenKind::Keyword(Keyword::Grant),
 	UniCase::ascii("GRAPHQL") => TokenKind::Keyword(Keyword::Graphql),
 	UniCase::ascii("GROUP") => TokenKind::Keyword(Keyword::Group),
+	UniCase::ascii("HAVING") => TokenKind::Keyword(Keyword::Having),
 	UniCase::ascii("HEADERS") => TokenKind::Keyword(Keyword::Headers),
 	UniCase::ascii("HIGHLIGHTS") => TokenKind::Keyword(Keyword::Highlights),
 	UniCase::ascii("HNSW") => TokenKind::Keyword(Keyword::Hnsw),
@@ -162,6 +179,8 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KEY") => TokenKind::Keyword(Keyword::Key),
 	UniCase::ascii("KEEP_PRUNED_CONNECTIONS") => TokenKind::Keyword(Keyword::KeepPrunedConnections),
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
+	UniCase::ascii("LAST") => TokenKind::Keyword(Keyword::Last),
+	UniCase::ascii("LENGTH") => TokenKind::Keyword(Keyword::Length),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
@@ -170,19 +189,24 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("M") => TokenKind::Keyword(Keyword::M),
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
 	UniCase::ascii("MAPPER") => TokenKind::Keyword(Keyword::Mapper),
+	UniCase::ascii("MAPPING") => TokenKind::Keyword(Keyword::Mapping),
 	UniCase::ascii("MIDDLEWARE") => TokenKind::Keyword(Keyword::Middleware),
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
 	UniCase::ascii("MODEL") => TokenKind::Keyword(Keyword::Model),
+	UniCase::ascii("MODIFIED") => TokenKind::Keyword(Keyword::Modified),
 	UniCase::ascii("MTREE") => TokenKind::Keyword(Keyword::MTree),
 	UniCase::ascii("MTREE_CACHE") => TokenKind::Keyword(Keyword::MTreeCache),
 	UniCase::ascii("NAMESPACE") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NS") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NGRAM") => TokenKind::Keyword(Keyword::Ngram),
 	UniCase::ascii("NO") => TokenKind::Keyword(Keyword::No),
+	UniCase::ascii("NOCASE") => TokenKind::Keyword(Keyword::NoCase),
 	UniCase::ascii("NOINDEX") => TokenKind::Keyword(Keyword::NoIndex),
 	UniCase::ascii("NONE") => TokenKind::Keyword(Keyword::None),
+	UniCase::ascii("NOVALIDATE") => TokenKind::Keyword(Keyword::NoValidate),
 	UniCase::ascii("NULL") => TokenKind::Keyword(Keyword::Null),
+	UniCase::ascii("NULLS") => TokenKind::Keyword(Keyword::Nulls),
 	UniCase::ascii("NUMERIC") => TokenKind::Keyword(Keyword::Numeric),
 	UniCase::ascii("OMIT") => TokenKind::Keyword(Keyword::Omit),
 	UniCase::ascii("ON") => TokenKind::Keyword(Keyword::On),
@@ -190,9 +214,12 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("OPTION") => TokenKind::Keyword(Keyword::Option),
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
 	UniCase::ascii("ORIGINAL") => TokenKind::Keyword(Keyword::Original),
+	UniCase::ascii("OUTBOX") => TokenKind::Keyword(Keyword::Outbox),
+	UniCase::ascii("OVER") => TokenKind::Keyword(Keyword::Over),
 	UniCase::ascii("OVERWRITE") => TokenKind::Keyword(Keyword::Overwrite),
 	UniCase::ascii("PARALLEL") => TokenKind::Keyword(Keyword::Parallel),
 	UniCase::ascii("PARAM") => TokenKind::Keyword(Keyword::Param),
+	UniCase::ascii("PARTITION") => TokenKind::Keyword(Keyword::Partition),
 	UniCase::ascii("PASSHASH") => TokenKind::Keyword(Keyword::Passhash),
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
@@ -210,13 +237,17 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("REFERENCE") => TokenKind::Keyword(Keyword::Reference),
 	UniCase::ascii("REFRESH") => TokenKind::Keyword(Keyword::Refresh),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
+	UniCase::ascii("RENAME") => TokenKind::Keyword(Keyword::Rename),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
+	UniCase::ascii("RESTRICT") => TokenKind::Keyword(Keyword::Restrict),
+	UniCase::ascii("RESUME") => TokenKind::Keyword(Keyword::Resume),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
 	UniCase::ascii("REVOKE") => TokenKind::Keyword(Keyword::Revoke),
 	UniCase::ascii("REVOKED") => TokenKind::Keyword(Keyword::Revoked),
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("SCHEDULE") => TokenKind::Keyword(Keyword::Schedule),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -232,14 +263,20 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SIGNUP") => TokenKind::Keyword(Keyword::Signup),
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
+	UniCase::ascii("SNAPSHOT") => TokenKind::Keyword(Keyword::Snapshot),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SOFT") => TokenKind::Keyword(Keyword::Soft),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STATEMENTS") => TokenKind::Keyword(Keyword::Statements),
+	UniCase::ascii("STOPWORDS") => TokenKind::Keyword(Keyword::Stopwords),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
+	UniCase::ascii("SUSPEND") => TokenKind::Keyword(Keyword::Suspend),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TABLES") => TokenKind::Keyword(Keyword::Tables),
 	UniCase::ascii("TB") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TEMPFILES") => TokenKind::Keyword(Keyword::TempFiles),
+	UniCase::ascii("TENANT") => TokenKind::Keyword(Keyword::Tenant),
 	UniCase::ascii("TERMS_CACHE") => TokenKind::Keyword(Keyword::TermsCache),
 	UniCase::ascii("TERMS_ORDER") => TokenKind::Keyword(Keyword::TermsOrder),
 	UniCase::ascii("THEN") => TokenKind::Keyword(Keyword::Then),
@@ -249,23 +286,32 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
+	UniCase::ascii("TRIGRAM") => TokenKind::Keyword(Keyword::Trigram),
+	UniCase::ascii("TRUNCATE") => TokenKind::Keyword(Keyword::Truncate),
+	UniCase::ascii("TRY") => TokenKind::Keyword(Keyword::Try),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
+	UniCase::ascii("UNICODE") => TokenKind::Keyword(Keyword::Unicode),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
+	UniCase::ascii("UNTIL") => TokenKind::Keyword(Keyword::Until),
 	UniCase::ascii("UPDATE") => TokenKind::Keyword(Keyword::Update),
 	UniCase::ascii("UPSERT") => TokenKind::Keyword(Keyword::Upsert),
 	UniCase::ascii("UPPERCASE") => TokenKind::Keyword(Keyword::Uppercase),
 	UniCase::ascii("URL") => TokenKind::Keyword(Keyword::Url),
 	UniCase::ascii("USE") => TokenKind::Keyword(Keyword::Use),
 	UniCase::ascii("USER") => TokenKind::Keyword(Keyword::User),
+	UniCase::ascii("VALIDATE") => TokenKind::Keyword(Keyword::Validate),
 	UniCase::ascii("VALUE") => TokenKind::Keyword(Keyword::Value),
 	UniCase::ascii("VALUES") => TokenKind::Keyword(Keyword::Values),
+	UniCase::ascii("VERBOSE") => TokenKind::Keyword(Keyword::Verbose),
 	UniCase::ascii("VERSION") => TokenKind::Keyword(Keyword::Version),
+	UniCase::ascii("VERSIONED") => TokenKind::Keyword(Keyword::Versioned),
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
 	UniCase::ascii("WITH") => TokenKind::Keyword(Keyword::With),
+	UniCase::ascii("ZIP") => TokenKind::Keyword(Keyword::Zip),
 	UniCase::ascii("ALLINSIDE") => TokenKind::Keyword(Keyword::AllInside),
 	UniCase::ascii("ANDKW") => TokenKind::Keyword(Keyword::AndKw),
 	UniCase::ascii("ANYINSIDE") => TokenKind::Keyword(Keyword::AnyInside),
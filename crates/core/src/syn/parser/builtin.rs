@@ -103,6 +103,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("crypto::scrypt::compare") => PathKind::Function,
 		UniCase::ascii("crypto::scrypt::generate") => PathKind::Function,
 		//
+		UniCase::ascii("dense_rank") => PathKind::Function,
+		//
 		UniCase::ascii("duration::days") => PathKind::Function,
 		UniCase::ascii("duration::hours") => PathKind::Function,
 		UniCase::ascii("duration::micros") => PathKind::Function,
@@ -114,12 +116,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("duration::years") => PathKind::Function,
 		UniCase::ascii("duration::from::days") => PathKind::Function,
 		UniCase::ascii("duration::from::hours") => PathKind::Function,
+		UniCase::ascii("duration::from::iso8601") => PathKind::Function,
 		UniCase::ascii("duration::from::micros") => PathKind::Function,
 		UniCase::ascii("duration::from::millis") => PathKind::Function,
 		UniCase::ascii("duration::from::mins") => PathKind::Function,
 		UniCase::ascii("duration::from::nanos") => PathKind::Function,
 		UniCase::ascii("duration::from::secs") => PathKind::Function,
 		UniCase::ascii("duration::from::weeks") => PathKind::Function,
+		UniCase::ascii("duration::to::iso8601") => PathKind::Function,
 		//
 		UniCase::ascii("encoding::base64::decode") => PathKind::Function,
 		UniCase::ascii("encoding::base64::encode") => PathKind::Function,
@@ -148,6 +152,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("geo::hash::encode") => PathKind::Function,
 		UniCase::ascii("geo::is::valid") => PathKind::Function,
 		//
+		UniCase::ascii("graph::path") => PathKind::Function,
+		//
 		UniCase::ascii("http::head") => PathKind::Function,
 		UniCase::ascii("http::get") => PathKind::Function,
 		UniCase::ascii("http::put") => PathKind::Function,
@@ -155,6 +161,9 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("http::patch") => PathKind::Function,
 		UniCase::ascii("http::delete") => PathKind::Function,
 		//
+		UniCase::ascii("lag") => PathKind::Function,
+		UniCase::ascii("lead") => PathKind::Function,
+		//
 		UniCase::ascii("math::abs") => PathKind::Function,
 		UniCase::ascii("math::acos") => PathKind::Function,
 		UniCase::ascii("math::acot") => PathKind::Function,
@@ -213,11 +222,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("object::remove") => PathKind::Function,
 		UniCase::ascii("object::values") => PathKind::Function,
 		//
+		UniCase::ascii("outbox::enqueue") => PathKind::Function,
+		//
 		UniCase::ascii("parse::email::host") => PathKind::Function,
 		UniCase::ascii("parse::email::user") => PathKind::Function,
 		UniCase::ascii("parse::url::domain") => PathKind::Function,
 		UniCase::ascii("parse::url::fragment") => PathKind::Function,
 		UniCase::ascii("parse::url::host") => PathKind::Function,
+		UniCase::ascii("parse::url::parse") => PathKind::Function,
 		UniCase::ascii("parse::url::path") => PathKind::Function,
 		UniCase::ascii("parse::url::port") => PathKind::Function,
 		UniCase::ascii("parse::url::query") => PathKind::Function,
@@ -237,27 +249,44 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("rand::uuid::v7") => PathKind::Function,
 		UniCase::ascii("rand::uuid") => PathKind::Function,
 		//
+		UniCase::ascii("range::contains") => PathKind::Function,
+		UniCase::ascii("range::end") => PathKind::Function,
+		UniCase::ascii("range::end_inclusive") => PathKind::Function,
+		UniCase::ascii("range::start") => PathKind::Function,
+		UniCase::ascii("range::start_inclusive") => PathKind::Function,
+		//
+		UniCase::ascii("rank") => PathKind::Function,
+		//
 		UniCase::ascii("record::exists") => PathKind::Function,
 		UniCase::ascii("record::id") => PathKind::Function,
 		UniCase::ascii("record::table") => PathKind::Function,
 		UniCase::ascii("record::tb") => PathKind::Function,
 		UniCase::ascii("record::refs") => PathKind::Function,
+		UniCase::ascii("record::restore") => PathKind::Function,
+		//
+		UniCase::ascii("row_number") => PathKind::Function,
 		//
 		UniCase::ascii("search::analyze") => PathKind::Function,
 		UniCase::ascii("search::score") => PathKind::Function,
+		UniCase::ascii("search::explain") => PathKind::Function,
 		UniCase::ascii("search::highlight") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
+		UniCase::ascii("search::stats") => PathKind::Function,
+		UniCase::ascii("search::terms") => PathKind::Function,
 		//
 		UniCase::ascii("sequence::nextval") => PathKind::Function,
 		//
 		UniCase::ascii("session::ac") => PathKind::Function,
 		UniCase::ascii("session::db") => PathKind::Function,
+		UniCase::ascii("session::endpoint") => PathKind::Function,
 		UniCase::ascii("session::id") => PathKind::Function,
 		UniCase::ascii("session::ip") => PathKind::Function,
 		UniCase::ascii("session::ns") => PathKind::Function,
 		UniCase::ascii("session::origin") => PathKind::Function,
 		UniCase::ascii("session::rd") => PathKind::Function,
+		UniCase::ascii("session::timeout") => PathKind::Function,
 		UniCase::ascii("session::token") => PathKind::Function,
+		UniCase::ascii("session::variables") => PathKind::Function,
 		//
 		UniCase::ascii("sleep") => PathKind::Function,
 		//
@@ -361,6 +390,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("type::decimal") => PathKind::Function,
 		UniCase::ascii("type::duration") => PathKind::Function,
 		UniCase::ascii("type::field") => PathKind::Function,
+		UniCase::ascii("type::field::variants") => PathKind::Function,
 		UniCase::ascii("type::fields") => PathKind::Function,
 		UniCase::ascii("type::file") => PathKind::Function,
 		UniCase::ascii("type::float") => PathKind::Function,
@@ -425,6 +455,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("vector::similarity::jaccard") => PathKind::Function,
 		UniCase::ascii("vector::similarity::pearson") => PathKind::Function,
 		UniCase::ascii("vector::similarity::spearman") => PathKind::Function,
+		//
+		UniCase::ascii("version") => PathKind::Function,
 		// constants
 		UniCase::ascii("math::E") => PathKind::Constant(Constant::MathE),
 		UniCase::ascii("math::FRAC_1_PI") => PathKind::Constant(Constant::MathFrac1Pi),
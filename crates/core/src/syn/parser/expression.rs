@@ -5,6 +5,7 @@ use std::ops::Bound;
 use reblessive::Stk;
 
 use super::mac::{expected_whitespace, unexpected};
+use crate::idx::ft::{Fuzziness, MAX_FUZZINESS};
 use crate::sql::operator::BindingPower;
 use crate::sql::{
 	Cast, Expression, Function, Number, Operator, Part, Range, SqlValue, value::TryNeg,
@@ -294,6 +295,34 @@ impl Parser<'_> {
 		Ok(op)
 	}
 
+	/// Parses the rest of a matches operator, after the first `@`.
+	///
+	/// The operator can have an optional match reference, followed by an
+	/// optional fuzziness, e.g. `@@`, `@1@`, `@~1@` or `@1~2@`.
+	fn parse_matches_op(&mut self) -> ParseResult<Operator> {
+		if self.eat(t!("@")) {
+			return Ok(Operator::Matches(None, None));
+		}
+		let reference = if self.peek_kind() == t!("~") {
+			None
+		} else {
+			Some(self.next_token_value()?)
+		};
+		let fuzziness = if self.eat(t!("~")) {
+			let token = self.peek();
+			let fuzziness: Fuzziness = self.next_token_value()?;
+			if fuzziness > MAX_FUZZINESS {
+				bail!("Invalid fuzziness of {fuzziness} for the matches operator",
+					@token.span => "The fuzziness must be an edit distance between 0 and {MAX_FUZZINESS}")
+			}
+			Some(fuzziness)
+		} else {
+			None
+		};
+		expected!(self, t!("@"));
+		Ok(Operator::Matches(reference, fuzziness))
+	}
+
 	fn expression_is_relation(value: &SqlValue) -> bool {
 		if let SqlValue::Expression(x) = value {
 			return Self::operator_is_relation(x.operator());
@@ -347,16 +376,7 @@ impl Parser<'_> {
 				bail!("Invalid operator '{}'",token.kind,
 					@token.span => "The like operators have been removed. Please use the similarity functions, like string::similarity::smithwaterman, instead.")
 			}
-			t!("@") => {
-				let reference = (!self.eat(t!("@")))
-					.then(|| {
-						let number = self.next_token_value()?;
-						expected!(self, t!("@"));
-						Ok(number)
-					})
-					.transpose()?;
-				Operator::Matches(reference)
-			}
+			t!("@") => self.parse_matches_op()?,
 			t!("<=") => Operator::LessThanOrEqual,
 			t!("<") => Operator::LessThan,
 			t!(">=") => Operator::MoreThanOrEqual,
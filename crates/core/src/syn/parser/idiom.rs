@@ -31,36 +31,29 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects the next tokens to be of a field set.
 	pub(crate) async fn parse_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+		self.parse_fields_with(ctx, false).await
+	}
+
+	/// Parse fields of a selecting query, where the fields are allowed to be window functions:
+	/// `rank() OVER (ORDER BY score)` in `SELECT rank() OVER (ORDER BY score) FROM baz`.
+	///
+	/// # Parser State
+	/// Expects the next tokens to be of a field set.
+	pub(crate) async fn parse_select_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+		self.parse_fields_with(ctx, true).await
+	}
+
+	async fn parse_fields_with(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Fields> {
 		if self.eat(t!("VALUE")) {
-			let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-			let alias = if self.eat(t!("AS")) {
-				Some(self.parse_plain_idiom(ctx).await?)
-			} else {
-				None
-			};
-			Ok(Fields(
-				vec![Field::Single {
-					expr,
-					alias,
-				}],
-				true,
-			))
+			let field = self.parse_single_field(ctx, windows).await?;
+			Ok(Fields(vec![field], true))
 		} else {
 			let mut fields = Vec::new();
 			loop {
 				let field = if self.eat(t!("*")) {
 					Field::All
 				} else {
-					let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-					let alias = if self.eat(t!("AS")) {
-						Some(self.parse_plain_idiom(ctx).await?)
-					} else {
-						None
-					};
-					Field::Single {
-						expr,
-						alias,
-					}
+					self.parse_single_field(ctx, windows).await?
 				};
 				fields.push(field);
 				if !self.eat(t!(",")) {
@@ -71,6 +64,38 @@ impl Parser<'_> {
 		}
 	}
 
+	async fn parse_single_field(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Field> {
+		let before = self.peek().span;
+		let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		let window = if windows && self.peek_kind() == t!("OVER") {
+			let span = before.covers(self.last_span());
+			self.pop_peek();
+			if !matches!(expr, SqlValue::Function(_)) {
+				bail!("Unexpected OVER clause, window functions must be function calls",
+					@span => "This is not a function call")
+			}
+			Some(self.parse_window(ctx).await?)
+		} else {
+			None
+		};
+		let alias = if self.eat(t!("AS")) {
+			Some(self.parse_plain_idiom(ctx).await?)
+		} else {
+			None
+		};
+		Ok(match window {
+			Some(window) => Field::Window {
+				expr,
+				window,
+				alias,
+			},
+			None => Field::Single {
+				expr,
+				alias,
+			},
+		})
+	}
+
 	/// Parses a list of idioms separated by a `,`
 	pub(super) async fn parse_idiom_list(&mut self, ctx: &mut Stk) -> ParseResult<Vec<Idiom>> {
 		let mut res = vec![self.parse_plain_idiom(ctx).await?];
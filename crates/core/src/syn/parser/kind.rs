@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use reblessive::Stk;
 
 use crate::{
-	sql::{Duration, Idiom, Kind, Strand, Table, kind::Literal},
+	sql::{ArrayConstraints, Duration, Idiom, Kind, Strand, Table, kind::Literal},
 	syn::{
 		error::bail,
 		lexer::compound,
@@ -127,24 +127,34 @@ impl Parser<'_> {
 			}
 			t!("ARRAY") => {
 				let span = self.peek().span;
-				if self.eat(t!("<")) {
+				let (kind, constraints) = if self.eat(t!("<")) {
 					let kind = ctx.run(|ctx| self.parse_inner_kind(ctx)).await?;
-					let size = self.eat(t!(",")).then(|| self.next_token_value()).transpose()?;
+					let constraints = if self.eat(t!(",")) {
+						self.parse_array_constraints()?
+					} else {
+						ArrayConstraints::default()
+					};
 					self.expect_closing_delimiter(t!(">"), span)?;
-					Ok(Kind::Array(Box::new(kind), size))
+					(kind, constraints)
 				} else {
-					Ok(Kind::Array(Box::new(Kind::Any), None))
-				}
+					(Kind::Any, ArrayConstraints::default())
+				};
+				let unique = self.eat(t!("UNIQUE"));
+				Ok(Kind::Array(Box::new(kind), constraints.with_unique(unique)))
 			}
 			t!("SET") => {
 				let span = self.peek().span;
 				if self.eat(t!("<")) {
 					let kind = ctx.run(|ctx| self.parse_inner_kind(ctx)).await?;
-					let size = self.eat(t!(",")).then(|| self.next_token_value()).transpose()?;
+					let constraints = if self.eat(t!(",")) {
+						self.parse_array_constraints()?
+					} else {
+						ArrayConstraints::default()
+					};
 					self.expect_closing_delimiter(t!(">"), span)?;
-					Ok(Kind::Set(Box::new(kind), size))
+					Ok(Kind::Set(Box::new(kind), constraints))
 				} else {
-					Ok(Kind::Set(Box::new(Kind::Any), None))
+					Ok(Kind::Set(Box::new(Kind::Any), ArrayConstraints::default()))
 				}
 			}
 			t!("REFERENCES") => {
@@ -193,6 +203,49 @@ impl Parser<'_> {
 	}
 
 	/// Parse the kind of gemoetry
+	/// Parse the length of an array or a set kind, which is either an exact
+	/// length like `10`, or a range of lengths like `1..10`, `1..=10` or `1..`
+	fn parse_array_constraints(&mut self) -> ParseResult<ArrayConstraints> {
+		let start = self.peek().span;
+		let min = if matches!(self.peek().kind, TokenKind::Digits) {
+			Some(self.next_token_value::<u64>()?)
+		} else {
+			None
+		};
+		match (self.eat_whitespace(t!("..")), min) {
+			(true, _) => (),
+			(false, Some(len)) => return Ok(ArrayConstraints::exact(len)),
+			(false, None) => {
+				let found = self.next().kind;
+				bail!("Unexpected token `{}` expected a length or a range of lengths", found, @self.last_span());
+			}
+		}
+		let inclusive = self.eat_whitespace(t!("="));
+		let max = if matches!(self.peek_whitespace().kind, TokenKind::Digits) {
+			Some(self.next_token_value::<u64>()?)
+		} else if inclusive {
+			let found = self.next().kind;
+			bail!("Unexpected token `{}` expected a maximum length", found, @self.last_span());
+		} else {
+			None
+		};
+		let span = start.covers(self.last_span());
+		// The maximum of an exclusive range is the length below it
+		let max = match max {
+			Some(max) if !inclusive => match max.checked_sub(1) {
+				Some(max) => Some(max),
+				None => bail!("The range of lengths is empty", @span),
+			},
+			max => max,
+		};
+		if let (Some(min), Some(max)) = (min, max) {
+			if min > max {
+				bail!("The range of lengths is empty", @span => "The minimum length is greater than the maximum length");
+			}
+		}
+		Ok(ArrayConstraints::bounds(min, max))
+	}
+
 	fn parse_geometry_kind(&mut self) -> ParseResult<String> {
 		let next = self.next();
 		match next.kind {
@@ -496,7 +549,7 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("array", format!("{}", out));
-		assert_eq!(out, Kind::Array(Box::new(Kind::Any), None));
+		assert_eq!(out, Kind::Array(Box::new(Kind::Any), ArrayConstraints::default()));
 	}
 
 	#[test]
@@ -505,7 +558,7 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("array<float>", format!("{}", out));
-		assert_eq!(out, Kind::Array(Box::new(Kind::Float), None));
+		assert_eq!(out, Kind::Array(Box::new(Kind::Float), ArrayConstraints::default()));
 	}
 
 	#[test]
@@ -514,7 +567,7 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("array<float, 10>", format!("{}", out));
-		assert_eq!(out, Kind::Array(Box::new(Kind::Float), Some(10)));
+		assert_eq!(out, Kind::Array(Box::new(Kind::Float), ArrayConstraints::exact(10)));
 	}
 
 	#[test]
@@ -523,7 +576,7 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("set", format!("{}", out));
-		assert_eq!(out, Kind::Set(Box::new(Kind::Any), None));
+		assert_eq!(out, Kind::Set(Box::new(Kind::Any), ArrayConstraints::default()));
 	}
 
 	#[test]
@@ -532,7 +585,7 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("set<float>", format!("{}", out));
-		assert_eq!(out, Kind::Set(Box::new(Kind::Float), None));
+		assert_eq!(out, Kind::Set(Box::new(Kind::Float), ArrayConstraints::default()));
 	}
 
 	#[test]
@@ -541,7 +594,56 @@ mod tests {
 		let res = kind(sql);
 		let out = res.unwrap();
 		assert_eq!("set<float, 10>", format!("{}", out));
-		assert_eq!(out, Kind::Set(Box::new(Kind::Float), Some(10)));
+		assert_eq!(out, Kind::Set(Box::new(Kind::Float), ArrayConstraints::exact(10)));
+	}
+
+	#[test]
+	fn kind_array_length_range() {
+		let out = kind("array<float, 1..10>").unwrap();
+		assert_eq!("array<float, 1..=9>", format!("{}", out));
+		assert_eq!(
+			out,
+			Kind::Array(Box::new(Kind::Float), ArrayConstraints::bounds(Some(1), Some(9)))
+		);
+		let out = kind("array<float, 1..=10>").unwrap();
+		assert_eq!("array<float, 1..=10>", format!("{}", out));
+		let out = kind("array<float, 2..>").unwrap();
+		assert_eq!("array<float, 2..>", format!("{}", out));
+		assert_eq!(
+			out,
+			Kind::Array(Box::new(Kind::Float), ArrayConstraints::bounds(Some(2), None))
+		);
+		let out = kind("array<float, ..=3>").unwrap();
+		assert_eq!("array<float, ..=3>", format!("{}", out));
+		assert_eq!(
+			out,
+			Kind::Array(Box::new(Kind::Float), ArrayConstraints::bounds(None, Some(3)))
+		);
+		// A range of a single length is an exact length
+		let out = kind("set<float, 3..=3>").unwrap();
+		assert_eq!("set<float, 3>", format!("{}", out));
+		// The ranges must not be empty
+		kind("array<float, 3..3>").unwrap_err();
+		kind("array<float, 4..=3>").unwrap_err();
+		kind("array<float, ..0>").unwrap_err();
+		kind("array<float, ..=>").unwrap_err();
+	}
+
+	#[test]
+	fn kind_array_unique() {
+		let out = kind("array<string, 1..=10> UNIQUE").unwrap();
+		assert_eq!("array<string, 1..=10> UNIQUE", format!("{}", out));
+		assert_eq!(
+			out,
+			Kind::Array(
+				Box::new(Kind::String),
+				ArrayConstraints::bounds(Some(1), Some(10)).with_unique(true)
+			)
+		);
+		let out = kind("array UNIQUE | string").unwrap();
+		assert_eq!("array UNIQUE | string", format!("{}", out));
+		let out = kind("option<array<int> UNIQUE>").unwrap();
+		assert_eq!("option<array<int> UNIQUE>", format!("{}", out));
 	}
 
 	#[test]
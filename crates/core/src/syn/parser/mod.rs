@@ -399,6 +399,7 @@ pub struct StatementStream {
 	settings: ParserSettings,
 	col_offset: usize,
 	line_offset: usize,
+	statement_line: usize,
 }
 
 impl StatementStream {
@@ -413,9 +414,23 @@ impl StatementStream {
 			settings,
 			col_offset: 0,
 			line_offset: 0,
+			statement_line: 1,
 		}
 	}
 
+	/// Returns the line, starting at 1, on which the most recently parsed statement began.
+	///
+	/// After a parsing error this is the line of the statement which failed to parse.
+	pub fn statement_line(&self) -> usize {
+		self.statement_line
+	}
+
+	/// updates the line on which the next statement in the buffer begins.
+	fn mark_statement_line(&mut self, bytes: &[u8], offset: u32) {
+		let skipped = bytes[..offset as usize].iter().filter(|x| **x == b'\n').count();
+		self.statement_line = self.line_offset + skipped + 1;
+	}
+
 	/// updates the line and column offset after consuming bytes.
 	fn accumulate_line_col(&mut self, bytes: &[u8]) {
 		// The parser should have ensured that bytes is a valid utf-8 string.
@@ -472,6 +487,7 @@ impl StatementStream {
 			return Ok(None);
 		}
 
+		self.mark_statement_line(slice, parser.peek().span.offset);
 		let res = self.stack.enter(|ctx| parser.parse_statement(ctx)).finish();
 		if parser.peek().is_eof() {
 			if buffer.len() > u32::MAX as usize {
@@ -540,6 +556,7 @@ impl StatementStream {
 			return Ok(None);
 		}
 
+		self.mark_statement_line(slice, parser.peek().span.offset);
 		match self.stack.enter(|ctx| parser.parse_statement(ctx)).finish() {
 			Ok(x) => {
 				if !parser.peek().is_eof() && !parser.eat(t!(";")) {
@@ -1,10 +1,12 @@
 use reblessive::Stk;
 
-use crate::sql::statements::alter::{AlterFieldStatement, AlterSequenceStatement};
+use crate::sql::statements::alter::{
+	AlterAccessStatement, AlterFieldStatement, AlterIndexStatement, AlterSequenceStatement,
+};
 use crate::syn::error::bail;
 use crate::{
 	sql::{
-		TableType,
+		Strand, TableType,
 		statements::{AlterStatement, AlterTableStatement},
 	},
 	syn::{
@@ -23,6 +25,8 @@ impl Parser<'_> {
 			t!("TABLE") => self.parse_alter_table(ctx).await.map(AlterStatement::Table),
 			t!("FIELD") => self.parse_alter_field(ctx).await.map(AlterStatement::Field),
 			t!("SEQUENCE") => self.parse_alter_sequence().await.map(AlterStatement::Sequence),
+			t!("INDEX") => self.parse_alter_index().map(AlterStatement::Index),
+			t!("ACCESS") => self.parse_alter_access().map(AlterStatement::Access),
 			_ => unexpected!(self, next, "a alter statement keyword"),
 		}
 	}
@@ -58,8 +62,12 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.changefeed = Some(None);
 						}
+						t!("ASSERT") => {
+							self.pop_peek();
+							res.assert = Some(None);
+						}
 						_ => {
-							unexpected!(self, peek, "`COMMENT` or `CHANGEFEED`")
+							unexpected!(self, peek, "`COMMENT`, `CHANGEFEED`, or `ASSERT`")
 						}
 					}
 				}
@@ -102,6 +110,29 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(Some(self.parse_changefeed()?))
 				}
+				t!("RENAME") => {
+					self.pop_peek();
+					expected!(self, t!("TO"));
+					res.rename = Some(self.next_token_value()?);
+					res.cascade = self.eat(t!("CASCADE"));
+				}
+				t!("SUSPEND") => {
+					self.pop_peek();
+					res.suspend = Some(true);
+				}
+				t!("RESUME") => {
+					self.pop_peek();
+					res.suspend = Some(false);
+				}
+				t!("REBUILD") => {
+					self.pop_peek();
+					res.rebuild = true;
+				}
+				t!("ASSERT") => {
+					self.pop_peek();
+					res.assert = Some(Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?));
+					res.validate = self.eat(t!("VALIDATE"));
+				}
 				_ => break,
 			}
 		}
@@ -195,7 +226,7 @@ impl Parser<'_> {
 				}
 				t!("READONLY") => {
 					self.pop_peek();
-					res.flex = Some(true)
+					res.readonly = Some(true)
 				}
 				t!("VALUE") => {
 					self.pop_peek();
@@ -229,6 +260,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.reference = Some(Some(self.parse_reference(ctx).await?));
 				}
+				t!("VALIDATE") => {
+					self.pop_peek();
+					res.validate = true;
+				}
+				t!("NOVALIDATE") => {
+					self.pop_peek();
+					res.validate = false;
+				}
 				_ => break,
 			}
 		}
@@ -256,4 +295,71 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	pub(crate) fn parse_alter_index(&mut self) -> ParseResult<AlterIndexStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let mut res = AlterIndexStatement {
+			name,
+			what,
+			if_exists,
+			..Default::default()
+		};
+
+		if self.eat(t!("CANCEL")) {
+			expected!(self, t!("BUILD"));
+			res.cancel_build = true;
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) fn parse_alter_access(&mut self) -> ParseResult<AlterAccessStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+		let mut res = AlterAccessStatement {
+			name,
+			base,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("ADD") => {
+					self.pop_peek();
+					expected!(self, t!("KEY"));
+					res.add_keys.push(self.parse_jwt_key()?);
+				}
+				t!("REMOVE") => {
+					self.pop_peek();
+					expected!(self, t!("KEY"));
+					res.remove_keys.push(self.next_token_value::<Strand>()?.0);
+				}
+				_ => break,
+			}
+		}
+
+		if res.add_keys.is_empty() && res.remove_keys.is_empty() {
+			let peek = self.peek();
+			unexpected!(self, peek, "`ADD KEY` or `REMOVE KEY`")
+		}
+
+		Ok(res)
+	}
 }
@@ -1,6 +1,7 @@
 use reblessive::Stk;
 
 use crate::api::method::Method;
+use crate::dbs::schedule::Cron;
 use crate::sql::SqlValue;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::index::HnswParams;
@@ -9,14 +10,15 @@ use crate::sql::statements::define::config::ConfigInner;
 use crate::sql::statements::define::config::api::{ApiConfig, RequestMiddleware};
 use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig};
 use crate::sql::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineSequenceStatement,
+	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineScheduleStatement,
+	DefineSequenceStatement,
 };
 use crate::syn::error::bail;
 use crate::syn::token::Token;
 use crate::{
 	sql::{
-		AccessType, Ident, Idioms, Index, Kind, Param, Permissions, Scoring, SqlValues, Strand,
-		TableType, access_type,
+		AccessType, Ident, Idiom, Idioms, Index, Kind, Param, Part, Permissions, Scoring,
+		SqlValues, Strand, TableType, access_type,
 		base::Base,
 		filter::Filter,
 		index::{Distance, VectorType},
@@ -24,7 +26,7 @@ use crate::{
 			DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement,
 			DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
 			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-			DefineTableStatement, DefineUserStatement, define::config::graphql,
+			DefineTableStatement, DefineUserStatement, Tenant, define::config::graphql,
 		},
 		table_type,
 		tokenizer::Tokenizer,
@@ -38,6 +40,7 @@ use crate::{
 		token::{Keyword, TokenKind, t},
 	},
 };
+use std::collections::BTreeMap;
 
 impl Parser<'_> {
 	pub(crate) async fn parse_define_stmt(
@@ -69,6 +72,7 @@ impl Parser<'_> {
 			t!("CONFIG") => self.parse_define_config(ctx).await.map(DefineStatement::Config),
 			t!("BUCKET") => self.parse_define_bucket(ctx, next).await.map(DefineStatement::Bucket),
 			t!("SEQUENCE") => self.parse_define_sequence().map(DefineStatement::Sequence),
+			t!("SCHEDULE") => self.parse_define_schedule(ctx).await.map(DefineStatement::Schedule),
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -92,9 +96,18 @@ impl Parser<'_> {
 			..Default::default()
 		};
 
-		while let t!("COMMENT") = self.peek_kind() {
-			self.pop_peek();
-			res.comment = Some(self.next_token_value()?);
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				t!("CAPABILITIES") => {
+					self.pop_peek();
+					res.capabilities = Some(self.parse_capabilities()?);
+				}
+				_ => break,
+			}
 		}
 
 		Ok(res)
@@ -127,6 +140,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("CAPABILITIES") => {
+					self.pop_peek();
+					res.capabilities = Some(self.parse_capabilities()?);
+				}
 				_ => break,
 			}
 		}
@@ -739,6 +756,15 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.drop = true;
 				}
+				t!("SOFT") => {
+					self.pop_peek();
+					expected!(self, t!("DELETE"));
+					res.soft_delete = true;
+				}
+				t!("VERSIONED") => {
+					self.pop_peek();
+					res.versioned = true;
+				}
 				t!("TYPE") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -777,6 +803,27 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("TENANT") => {
+					self.pop_peek();
+					let field = self.parse_local_idiom(ctx).await?;
+					let value = if self.eat(t!("VALUE")) {
+						ctx.run(|ctx| self.parse_value_field(ctx)).await?
+					} else {
+						// Default to the same field of the authenticated record
+						let mut parts = vec![Part::Start(SqlValue::Param(Param::from("auth")))];
+						parts.extend(field.0.iter().cloned());
+						SqlValue::Idiom(Idiom(parts))
+					};
+					res.tenant = Some(Tenant {
+						field,
+						value,
+					});
+				}
+				t!("ASSERT") => {
+					self.pop_peek();
+					res.assert = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+					res.validate = self.eat(t!("VALIDATE"));
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -927,6 +974,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.when = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
 				}
+				t!("ORDER") => {
+					self.pop_peek();
+					res.order = Some(self.next_token_value()?);
+				}
+				t!("IF") => {
+					self.pop_peek();
+					res.guard = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
 				t!("THEN") => {
 					self.pop_peek();
 					res.then = SqlValues(vec![ctx.run(|ctx| self.parse_value_field(ctx)).await?]);
@@ -966,6 +1021,10 @@ impl Parser<'_> {
 			overwrite,
 			..Default::default()
 		};
+		// The span of a CREATED or MODIFIED clause
+		let mut timestamp = None;
+		// The span of a COMPUTED clause
+		let mut computed = None;
 
 		loop {
 			match self.peek_kind() {
@@ -982,6 +1041,25 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.readonly = true;
 				}
+				t!("EXPIRE") => {
+					self.pop_peek();
+					res.expire = true;
+				}
+				t!("CREATED") => {
+					self.pop_peek();
+					res.created = true;
+					timestamp = Some(self.last_span());
+				}
+				t!("MODIFIED") => {
+					self.pop_peek();
+					res.modified = true;
+					timestamp = Some(self.last_span());
+				}
+				t!("COMPUTED") => {
+					self.pop_peek();
+					computed = Some(self.last_span());
+					res.computed = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
 				t!("VALUE") => {
 					self.pop_peek();
 					res.value = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
@@ -1017,9 +1095,45 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.reference = Some(self.parse_reference(ctx).await?);
 				}
+				// ON DELETE is the same as REFERENCE ON DELETE
+				t!("ON") => {
+					if !self.settings.references_enabled {
+						bail!(
+							"Experimental capability `record_references` is not enabled",
+							@self.peek().span => "Use of `ON DELETE` clause is still experimental"
+						)
+					}
+
+					res.reference = Some(self.parse_reference(ctx).await?);
+				}
 				_ => break,
 			}
 		}
+		// The value of a COMPUTED field is never stored
+		if let Some(span) = computed {
+			if res.value.is_some()
+				|| res.default.is_some()
+				|| res.readonly
+				|| res.expire
+				|| res.created
+				|| res.modified
+				|| res.reference.is_some()
+			{
+				bail!("A COMPUTED field can not have a VALUE, DEFAULT, READONLY, EXPIRE, CREATED, MODIFIED or REFERENCE clause",
+					@span => "the value of this field is computed when the record is read");
+			}
+		}
+		// The value of a CREATED or MODIFIED field is generated
+		if let Some(span) = timestamp {
+			if res.created && res.modified {
+				bail!("A field can not be both CREATED and MODIFIED",
+					@span => "only one of these clauses can be specified");
+			}
+			if res.value.is_some() || res.default.is_some() {
+				bail!("A CREATED or MODIFIED field can not have a VALUE or DEFAULT clause",
+					@span => "the value of this field is generated");
+			}
+		}
 
 		Ok(res)
 	}
@@ -1058,10 +1172,21 @@ impl Parser<'_> {
 						res.cols.0.push(self.parse_local_idiom(ctx).await?);
 					}
 				}
+				t!("COLLATE") => {
+					// Index keys are always sorted by their raw value
+					bail!("A COLLATE clause is not supported on index fields, and only applies to ORDER BY and GROUP BY clauses", @self.peek().span);
+				}
 				t!("UNIQUE") => {
 					self.pop_peek();
 					res.index = Index::Uniq;
 				}
+				t!("TRIGRAM") => {
+					self.pop_peek();
+					res.index = Index::Trigram;
+				}
+				t!("WHERE") => {
+					res.cond = self.try_parse_condition(ctx).await?;
+				}
 				t!("SEARCH") => {
 					self.pop_peek();
 					let mut analyzer: Option<Ident> = None;
@@ -1354,6 +1479,58 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path.into()))
 							}
+							t!("STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let list_span = expected!(self, t!("[")).span;
+								let mut words = Vec::new();
+								loop {
+									if self.eat(t!("]")) {
+										break;
+									}
+									let word: Strand = self.next_token_value()?;
+									words.push(word.0);
+									if !self.eat(t!(",")) {
+										self.expect_closing_delimiter(t!("]"), list_span)?;
+										break;
+									}
+								}
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Stopwords(words))
+							}
+							t!("MAPPING") => {
+								let open_span = expected!(self, t!("(")).span;
+								let map_span = expected!(self, t!("{")).span;
+								let mut map = BTreeMap::new();
+								loop {
+									if self.eat(t!("}")) {
+										break;
+									}
+									let key = self.parse_object_key()?;
+									if key.is_empty() {
+										bail!("The keys of a MAPPING filter must not be empty", @self.last_span());
+									}
+									expected!(self, t!(":"));
+									let value: Strand = self.next_token_value()?;
+									map.insert(key, value.0);
+									if !self.eat(t!(",")) {
+										self.expect_closing_delimiter(t!("}"), map_span)?;
+										break;
+									}
+								}
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Mapping(map))
+							}
+							t!("LENGTH") => {
+								let open_span = expected!(self, t!("(")).span;
+								let a: u16 = self.next_token_value()?;
+								expected!(self, t!(","));
+								let b: u16 = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								if a > b {
+									bail!("The minimum length of a LENGTH filter must not be greater than the maximum length", @self.last_span());
+								}
+								filters.push(Filter::Length(a, b))
+							}
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -1492,6 +1669,76 @@ impl Parser<'_> {
 		})
 	}
 
+	pub async fn parse_define_schedule(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<DefineScheduleStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		expected!(self, t!("DATABASE"));
+		expected!(self, t!("CRON"));
+		let cron: Strand = self.next_token_value()?;
+		// Check the cron expression when the schedule is defined
+		if let Err(e) = cron.0.parse::<Cron>() {
+			bail!("{e}", @self.last_span());
+		}
+		let mut res = DefineScheduleStatement {
+			name,
+			if_not_exists,
+			overwrite,
+			cron,
+			// Schedules are run as an editor of the database by default
+			roles: vec!["Editor".into()],
+			..Default::default()
+		};
+		let mut then = None;
+		loop {
+			match self.peek_kind() {
+				t!("ROLES") => {
+					self.pop_peek();
+					let mut roles = Vec::new();
+					loop {
+						let token = self.peek();
+						let role = self.next_token_value::<Ident>()?;
+						if !matches!(role.to_lowercase().as_str(), "viewer" | "editor" | "owner") {
+							unexpected!(self, token, "an existent role");
+						}
+						roles.push(role);
+						if !self.eat(t!(",")) {
+							res.roles = roles;
+							break;
+						}
+					}
+				}
+				t!("THEN") => {
+					self.pop_peek();
+					let next = expected!(self, t!("{")).span;
+					then = Some(self.parse_block(ctx, next).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		let Some(then) = then else {
+			let peek = self.peek();
+			unexpected!(self, peek, "a THEN clause");
+		};
+		res.then = then;
+		Ok(res)
+	}
+
 	pub async fn parse_define_config(
 		&mut self,
 		stk: &mut Stk,
@@ -1670,6 +1917,7 @@ impl Parser<'_> {
 			from: None,
 			to: None,
 			enforced: false,
+			unique: false,
 		};
 		loop {
 			match self.peek_kind() {
@@ -1689,6 +1937,9 @@ impl Parser<'_> {
 		if self.eat(t!("ENFORCED")) {
 			res.enforced = true;
 		}
+		if self.eat(t!("UNIQUE")) {
+			res.unique = true;
+		}
 		Ok(res)
 	}
 
@@ -1757,6 +2008,17 @@ impl Parser<'_> {
 			_ => unexpected!(self, peek, "`ALGORITHM`, or `URL`"),
 		}
 
+		// Additional keys which can verify tokens
+		while self.peek_kind() == t!("WITH") && self.peek1().kind == t!("KEY") {
+			self.pop_peek();
+			self.pop_peek();
+			let key = self.parse_jwt_key()?;
+			if res.keys.iter().any(|k| k.kid == key.kid) {
+				bail!("Duplicate key identifier `{}`", key.kid, @self.last_span() => "the key identifier was already defined");
+			}
+			res.keys.push(key);
+		}
+
 		if self.eat(t!("WITH")) {
 			expected!(self, t!("ISSUER"));
 			loop {
@@ -1801,4 +2063,22 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	/// Parses an additional key after the `KEY` keyword.
+	/// `KEY 'kid' ALGORITHM HS512 KEY 'secret'`
+	pub fn parse_jwt_key(&mut self) -> ParseResult<access_type::JwtAccessKey> {
+		let kid = self.next_token_value::<Strand>()?.0;
+		expected!(self, t!("ALGORITHM"));
+		let next = self.next();
+		let TokenKind::Algorithm(alg) = next.kind else {
+			unexpected!(self, next, "a valid algorithm")
+		};
+		expected!(self, t!("KEY"));
+		let key = self.next_token_value::<Strand>()?.0;
+		Ok(access_type::JwtAccessKey {
+			kid,
+			alg,
+			key,
+		})
+	}
 }
@@ -1,7 +1,7 @@
 use reblessive::Stk;
 
 use crate::{
-	sql::{SqlValues, statements::DeleteStatement},
+	sql::{Fields, SqlValues, statements::DeleteStatement},
 	syn::{
 		parser::{ParseResult, Parser},
 		token::t,
@@ -16,8 +16,10 @@ impl Parser<'_> {
 		self.eat(t!("FROM"));
 		let only = self.eat(t!("ONLY"));
 		let what = SqlValues(self.parse_what_list(ctx).await?);
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(false)?;
 		let cond = self.try_parse_condition(ctx).await?;
+		let order = self.try_parse_orders(ctx, &Fields::all(), self.last_span()).await?;
+		let limit = self.try_parse_limit(ctx).await?;
 		let output = self.try_parse_output(ctx).await?;
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
@@ -28,6 +30,8 @@ impl Parser<'_> {
 			what,
 			with,
 			cond,
+			order,
+			limit,
 			output,
 			timeout,
 			parallel,
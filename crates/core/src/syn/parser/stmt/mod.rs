@@ -5,13 +5,13 @@ use crate::sql::statements::rebuild::{RebuildIndexStatement, RebuildStatement};
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement,
+	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement, TruncateStatement,
 	access::{
 		AccessStatement, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke,
 		AccessStatementShow, Subject,
 	},
 };
-use crate::sql::{Duration, Fields, Ident, Param};
+use crate::sql::{Array, Base, Duration, Fields, Ident, Param};
 use crate::syn::error::bail;
 use crate::syn::lexer::compound;
 use crate::syn::parser::enter_query_recursion;
@@ -21,7 +21,7 @@ use crate::{
 		Expression, Operator, SqlValue, Statement, Statements,
 		statements::{
 			BeginStatement, BreakStatement, CancelStatement, CommitStatement, ContinueStatement,
-			ForeachStatement, InfoStatement, OutputStatement, UseStatement,
+			ForeachStatement, InfoStatement, OutputStatement, TrycatchStatement, UseStatement,
 			analyze::AnalyzeStatement,
 		},
 	},
@@ -113,7 +113,9 @@ impl Parser<'_> {
 			}
 			t!("BREAK") => {
 				self.pop_peek();
-				Ok(Statement::Break(BreakStatement))
+				Ok(Statement::Break(BreakStatement {
+					label: self.parse_loop_label()?,
+				}))
 			}
 			t!("CANCEL") => {
 				self.pop_peek();
@@ -125,7 +127,9 @@ impl Parser<'_> {
 			}
 			t!("CONTINUE") => {
 				self.pop_peek();
-				Ok(Statement::Continue(ContinueStatement))
+				Ok(Statement::Continue(ContinueStatement {
+					label: self.parse_loop_label()?,
+				}))
 			}
 			t!("CREATE") => {
 				self.pop_peek();
@@ -203,6 +207,14 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_throw_stmt(ctx)).await.map(Statement::Throw)
 			}
+			t!("TRUNCATE") => {
+				self.pop_peek();
+				self.parse_truncate_stmt().map(Statement::Truncate)
+			}
+			t!("TRY") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_try_stmt(ctx)).await.map(Statement::Trycatch)
+			}
 			t!("UPDATE") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_update_stmt(ctx)).await.map(Statement::Update)
@@ -215,6 +227,9 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_use_stmt().map(Statement::Use)
 			}
+			TokenKind::Identifier if self.peek_for_label() => {
+				ctx.run(|ctx| self.parse_labelled_for_stmt(ctx)).await.map(Statement::Foreach)
+			}
 			_ => {
 				// TODO: Provide information about keywords.
 				let value = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
@@ -249,11 +264,15 @@ impl Parser<'_> {
 			}
 			t!("BREAK") => {
 				self.pop_peek();
-				Ok(Entry::Break(BreakStatement))
+				Ok(Entry::Break(BreakStatement {
+					label: self.parse_loop_label()?,
+				}))
 			}
 			t!("CONTINUE") => {
 				self.pop_peek();
-				Ok(Entry::Continue(ContinueStatement))
+				Ok(Entry::Continue(ContinueStatement {
+					label: self.parse_loop_label()?,
+				}))
 			}
 			t!("CREATE") => {
 				self.pop_peek();
@@ -307,6 +326,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_throw_stmt(ctx).await.map(Entry::Throw)
 			}
+			t!("TRY") => {
+				self.pop_peek();
+				self.parse_try_stmt(ctx).await.map(Entry::Trycatch)
+			}
 			t!("UPDATE") => {
 				self.pop_peek();
 				self.parse_update_stmt(ctx).await.map(Entry::Update)
@@ -319,6 +342,9 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_info_stmt(ctx).await.map(Entry::Info)
 			}
+			TokenKind::Identifier if self.peek_for_label() => {
+				self.parse_labelled_for_stmt(ctx).await.map(Entry::Foreach)
+			}
 			_ => {
 				// TODO: Provide information about keywords.
 				let v = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
@@ -487,13 +513,22 @@ impl Parser<'_> {
 
 	/// Parsers a analyze statement.
 	fn parse_analyze(&mut self) -> ParseResult<AnalyzeStatement> {
-		expected!(self, t!("INDEX"));
+		let next = self.next();
+		match next.kind {
+			t!("INDEX") => {
+				let index = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let table = self.next_token_value()?;
 
-		let index = self.next_token_value()?;
-		expected!(self, t!("ON"));
-		let table = self.next_token_value()?;
+				Ok(AnalyzeStatement::Idx(table, index))
+			}
+			t!("TABLE") => {
+				let table = self.next_token_value()?;
 
-		Ok(AnalyzeStatement::Idx(table, index))
+				Ok(AnalyzeStatement::Tb(table))
+			}
+			_ => unexpected!(self, next, "`INDEX` or `TABLE`"),
+		}
 	}
 
 	/// Parsers a begin statement.
@@ -560,18 +595,85 @@ impl Parser<'_> {
 	/// Expects `FOR` to already be consumed.
 	pub(super) async fn parse_for_stmt(&mut self, stk: &mut Stk) -> ParseResult<ForeachStatement> {
 		let param = self.next_token_value()?;
+		let kind = if self.eat(t!(":")) {
+			Some(self.parse_inner_kind(stk).await?)
+		} else {
+			None
+		};
+		let index = if self.eat(t!(",")) {
+			Some(self.next_token_value()?)
+		} else {
+			None
+		};
 		expected!(self, t!("IN"));
 		let range = stk.run(|stk| self.parse_value_inherit(stk)).await?;
 
 		let span = expected!(self, t!("{")).span;
 		let block = self.parse_block(stk, span).await?;
 		Ok(ForeachStatement {
+			label: None,
 			param,
+			kind,
+			index,
 			range,
 			block,
 		})
 	}
 
+	/// Checks if the next tokens are a loop label followed by a FOR statement.
+	fn peek_for_label(&mut self) -> bool {
+		self.peek1().kind == t!(":")
+			&& self.peek_token_at(2).kind == t!("FOR")
+			&& self.peek_token_at(3).kind == TokenKind::Parameter
+	}
+
+	/// Parsers a labelled FOR statement.
+	///
+	/// # Parser State
+	/// Expects the next tokens to be a label followed by `:` and `FOR`.
+	async fn parse_labelled_for_stmt(&mut self, stk: &mut Stk) -> ParseResult<ForeachStatement> {
+		let label = self.next_token_value()?;
+		expected!(self, t!(":"));
+		expected!(self, t!("FOR"));
+		let mut stmt = self.parse_for_stmt(stk).await?;
+		stmt.label = Some(label);
+		Ok(stmt)
+	}
+
+	/// Parses the optional loop label of a BREAK or CONTINUE statement.
+	fn parse_loop_label(&mut self) -> ParseResult<Option<Ident>> {
+		if self.peek_kind() == TokenKind::Identifier {
+			Ok(Some(self.next_token_value()?))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Parsers a TRY statement.
+	///
+	/// # Parser State
+	/// Expects `TRY` to already be consumed.
+	pub(super) async fn parse_try_stmt(&mut self, stk: &mut Stk) -> ParseResult<TrycatchStatement> {
+		let span = expected!(self, t!("{")).span;
+		let block = self.parse_block(stk, span).await?;
+		expected!(self, t!("CATCH"));
+		let param = if self.eat(t!("(")) {
+			let start = self.last_span();
+			let param = self.next_token_value()?;
+			self.expect_closing_delimiter(t!(")"), start)?;
+			Some(param)
+		} else {
+			None
+		};
+		let span = expected!(self, t!("{")).span;
+		let catch = self.parse_block(stk, span).await?;
+		Ok(TrycatchStatement {
+			block,
+			param,
+			catch,
+		})
+	}
+
 	/// Parsers a INFO statement.
 	///
 	/// # Parser State
@@ -581,12 +683,27 @@ impl Parser<'_> {
 		let next = self.next();
 		let mut stmt = match next.kind {
 			t!("ROOT") => InfoStatement::Root(false),
-			t!("NAMESPACE") => InfoStatement::Ns(false),
-			t!("DATABASE") => InfoStatement::Db(false, None),
+			t!("NAMESPACE") => match self.eat(t!("AUDIT")) {
+				true => return Ok(InfoStatement::Audit(Base::Ns)),
+				false => InfoStatement::Ns(false),
+			},
+			t!("DATABASE") => {
+				if self.eat(t!("AUDIT")) {
+					return Ok(InfoStatement::Audit(Base::Db));
+				}
+				if self.eat(t!("OUTBOX")) {
+					return Ok(InfoStatement::Outbox);
+				}
+				InfoStatement::Db(false, None)
+			}
 			t!("TABLE") => {
 				let ident = self.next_token_value()?;
 				InfoStatement::Tb(ident, false, None)
 			}
+			t!("SCHEDULE") => {
+				let ident = self.next_token_value()?;
+				return Ok(InfoStatement::Schedule(ident));
+			}
 			t!("USER") => {
 				let ident = self.next_token_value()?;
 				let base = self.eat(t!("ON")).then(|| self.parse_base(false)).transpose()?;
@@ -618,16 +735,66 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `KILL` to already be consumed.
 	pub(super) fn parse_kill_stmt(&mut self) -> ParseResult<KillStatement> {
+		if self.eat(t!("ALL")) {
+			let table = if self.eat(t!("FOR")) {
+				expected!(self, t!("TABLE"));
+				Some(self.next_token_value()?)
+			} else {
+				None
+			};
+			return Ok(KillStatement {
+				id: SqlValue::None,
+				all: true,
+				table,
+			});
+		}
 		let peek = self.peek();
 		let id = match peek.kind {
-			t!("u\"") | t!("u'") | TokenKind::Glued(Glued::Uuid) => {
-				self.next_token_value().map(SqlValue::Uuid)?
+			t!("[") => {
+				self.pop_peek();
+				let mut ids = Vec::new();
+				loop {
+					if self.eat(t!("]")) {
+						break;
+					}
+					ids.push(self.parse_kill_id()?);
+					if !self.eat(t!(",")) {
+						self.expect_closing_delimiter(t!("]"), peek.span)?;
+						break;
+					}
+				}
+				SqlValue::Array(Array(ids))
 			}
-			t!("$param") => self.next_token_value().map(SqlValue::Param)?,
-			_ => unexpected!(self, peek, "a UUID or a parameter"),
+			_ => self.parse_kill_id()?,
 		};
 		Ok(KillStatement {
 			id,
+			all: false,
+			table: None,
+		})
+	}
+
+	/// Parses a single live query id of a KILL statement
+	fn parse_kill_id(&mut self) -> ParseResult<SqlValue> {
+		let peek = self.peek();
+		match peek.kind {
+			t!("u\"") | t!("u'") | TokenKind::Glued(Glued::Uuid) => {
+				self.next_token_value().map(SqlValue::Uuid)
+			}
+			t!("$param") => self.next_token_value().map(SqlValue::Param),
+			_ => unexpected!(self, peek, "a UUID or a parameter"),
+		}
+	}
+
+	/// Parsers a TRUNCATE statement.
+	///
+	/// # Parser State
+	/// Expects `TRUNCATE` to already be consumed.
+	pub(super) fn parse_truncate_stmt(&mut self) -> ParseResult<TruncateStatement> {
+		expected!(self, t!("TABLE"));
+		let name = self.next_token_value()?;
+		Ok(TruncateStatement {
+			name,
 		})
 	}
 
@@ -638,6 +805,7 @@ impl Parser<'_> {
 	pub(super) async fn parse_live_stmt(&mut self, stk: &mut Stk) -> ParseResult<LiveStatement> {
 		expected!(self, t!("SELECT"));
 
+		let before = self.peek().span;
 		let expr = match self.peek_kind() {
 			t!("DIFF") => {
 				self.pop_peek();
@@ -645,15 +813,26 @@ impl Parser<'_> {
 			}
 			_ => self.parse_fields(stk).await?,
 		};
+		let fields_span = before.covers(self.last_span());
 		expected!(self, t!("FROM"));
 		let what = match self.peek().kind {
 			t!("$param") => SqlValue::Param(self.next_token_value()?),
 			_ => SqlValue::Table(self.next_token_value()?),
 		};
 		let cond = self.try_parse_condition(stk).await?;
+		let group = self.try_parse_group(stk, &expr, fields_span).await?;
 		let fetch = self.try_parse_fetch(stk).await?;
+		let snapshot = self.peek_kind() == t!("SNAPSHOT");
+		if snapshot {
+			let token = self.pop_peek();
+			// The initial state of each group is always sent
+			if group.is_some() {
+				bail!("A LIVE SELECT statement with a GROUP clause can not have a SNAPSHOT clause",
+					@token.span => "the initial state of each group is always sent");
+			}
+		}
 
-		Ok(LiveStatement::from_source_parts(expr, what, cond, fetch))
+		Ok(LiveStatement::from_source_parts(expr, what, cond, group, fetch, snapshot))
 	}
 
 	/// Parsers a OPTION statement.
@@ -692,11 +871,13 @@ impl Parser<'_> {
 				expected!(self, t!("ON"));
 				self.eat(t!("TABLE"));
 				let what = self.next_token_value()?;
+				let concurrently = self.eat(t!("CONCURRENTLY"));
 
 				RebuildStatement::Index(RebuildIndexStatement {
 					what,
 					name,
 					if_exists,
+					concurrently,
 				})
 			}
 			_ => unexpected!(self, next, "a rebuild statement keyword"),
@@ -764,7 +945,22 @@ impl Parser<'_> {
 		};
 
 		expected!(self, t!("SINCE"));
+		let since = self.parse_show_since()?;
+
+		let until = self.eat(t!("UNTIL")).then(|| self.parse_show_since()).transpose()?;
+
+		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+
+		Ok(ShowStatement {
+			table,
+			since,
+			until,
+			limit,
+		})
+	}
 
+	/// Parsers the bound of a SHOW CHANGES range, either a version stamp or a date-time.
+	fn parse_show_since(&mut self) -> ParseResult<ShowSince> {
 		let next = self.peek();
 		let since = match next.kind {
 			TokenKind::Digits => {
@@ -782,14 +978,7 @@ impl Parser<'_> {
 			}
 			_ => unexpected!(self, next, "a version stamp or a date-time"),
 		};
-
-		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
-
-		Ok(ShowStatement {
-			table,
-			since,
-			limit,
-		})
+		Ok(since)
 	}
 
 	/// Parsers a SLEEP statement
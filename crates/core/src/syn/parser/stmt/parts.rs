@@ -2,13 +2,15 @@
 
 use reblessive::Stk;
 
+use crate::dbs::capabilities::{FuncTarget, StatementTarget};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
-use crate::sql::{Explain, Fetch, With};
+use crate::sql::{Explain, Fetch, IndexHint, With};
 use crate::syn::error::bail;
 use crate::{
 	sql::{
-		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
-		Permission, Permissions, SqlValue, Tables, Timeout, View,
+		Base, Collate, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom,
+		Output, Permission, Permissions, ScopeCapabilities, SqlValue, Strand, Tables, Timeout,
+		View,
 		changefeed::ChangeFeed,
 		index::{Distance, VectorType},
 	},
@@ -125,13 +127,28 @@ impl Parser<'_> {
 	}
 
 	pub async fn parse_fetchs(&mut self, ctx: &mut Stk) -> ParseResult<Fetchs> {
-		let mut fetchs = self.try_parse_param_or_idiom_or_fields(ctx).await?;
+		let mut fetchs = self.parse_fetch(ctx).await?;
 		while self.eat(t!(",")) {
-			fetchs.append(&mut self.try_parse_param_or_idiom_or_fields(ctx).await?);
+			fetchs.append(&mut self.parse_fetch(ctx).await?);
 		}
 		Ok(Fetchs(fetchs))
 	}
 
+	/// Parses the fetches of a single item of a `FETCH` clause, with its optional depth
+	async fn parse_fetch(&mut self, ctx: &mut Stk) -> ParseResult<Vec<Fetch>> {
+		let mut fetchs = self.try_parse_param_or_idiom_or_fields(ctx).await?;
+		if self.eat(t!("DEPTH")) {
+			let depth = self.next_token_value::<u32>()?;
+			if depth == 0 {
+				bail!("The DEPTH of a FETCH must be greater than 0", @self.last_span());
+			}
+			for fetch in fetchs.iter_mut() {
+				fetch.1 = Some(depth);
+			}
+		}
+		Ok(fetchs)
+	}
+
 	pub async fn try_parse_param_or_idiom_or_fields(
 		&mut self,
 		ctx: &mut Stk,
@@ -169,6 +186,25 @@ impl Parser<'_> {
 		Ok(Some(Cond(v)))
 	}
 
+	/// Parses an IF VERSION clause if present
+	pub async fn try_parse_if_version(&mut self, ctx: &mut Stk) -> ParseResult<Option<SqlValue>> {
+		if !self.eat(t!("IF")) {
+			return Ok(None);
+		}
+		expected!(self, t!("VERSION"));
+		let v = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		Ok(Some(v))
+	}
+
+	/// Parses a statement HAVING clause if present
+	pub async fn try_parse_having(&mut self, ctx: &mut Stk) -> ParseResult<Option<Cond>> {
+		if !self.eat(t!("HAVING")) {
+			return Ok(None);
+		}
+		let v = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		Ok(Some(Cond(v)))
+	}
+
 	pub(crate) fn check_idiom<'a>(
 		kind: MissingKind,
 		fields: &'a Fields,
@@ -178,12 +214,21 @@ impl Parser<'_> {
 	) -> ParseResult<&'a Field> {
 		let mut found = None;
 		for field in fields.iter() {
-			let Field::Single {
-				expr,
-				alias,
-			} = field
-			else {
-				unreachable!()
+			let (expr, alias) = match field {
+				Field::Single {
+					expr,
+					alias,
+				} => (expr, alias),
+				// Windows are computed after splitting and grouping, so only ORDER can use them
+				Field::Window {
+					..
+				} if !matches!(kind, MissingKind::Order) => continue,
+				Field::Window {
+					expr,
+					alias,
+					..
+				} => (expr, alias),
+				Field::All => unreachable!(),
 			};
 
 			if let Some(alias) = alias {
@@ -263,7 +308,8 @@ impl Parser<'_> {
 			Self::check_idiom(MissingKind::Group, fields, fields_span, &group, group_span)?;
 		}
 
-		let mut groups = Groups(vec![Group(group)]);
+		let collate = self.try_parse_collate();
+		let mut groups = Groups(vec![Group(group, collate)]);
 		while self.eat(t!(",")) {
 			let before = self.peek().span;
 			let group = self.parse_basic_idiom(ctx).await?;
@@ -271,12 +317,32 @@ impl Parser<'_> {
 			if !has_all {
 				Self::check_idiom(MissingKind::Group, fields, fields_span, &group, group_span)?;
 			}
-			groups.0.push(Group(group));
+			let collate = self.try_parse_collate();
+			groups.0.push(Group(group, collate));
 		}
 
 		Ok(Some(groups))
 	}
 
+	/// Parses a collation if the next token is `COLLATE`.
+	/// Otherwise returns None
+	pub(crate) fn try_parse_collate(&mut self) -> Option<Collate> {
+		if !self.eat(t!("COLLATE")) {
+			return None;
+		}
+		match self.peek_kind() {
+			t!("NOCASE") => {
+				self.pop_peek();
+				Some(Collate::NoCase)
+			}
+			t!("UNICODE") => {
+				self.pop_peek();
+				Some(Collate::Unicode)
+			}
+			_ => Some(Collate::Lexical),
+		}
+	}
+
 	/// Parse a permissions production
 	///
 	/// # Parser State
@@ -424,17 +490,73 @@ impl Parser<'_> {
 	/// Expects the parser to have already eating the `CHANGEFEED` keyword
 	pub fn parse_changefeed(&mut self) -> ParseResult<ChangeFeed> {
 		let expiry = self.next_token_value::<Duration>()?.0;
-		let store_diff = if self.eat(t!("INCLUDE")) {
-			expected!(self, t!("ORIGINAL"));
-			true
-		} else {
-			false
+		let mut res = ChangeFeed {
+			expiry,
+			store_diff: false,
+			store_audit: false,
 		};
+		if self.eat(t!("INCLUDE")) {
+			loop {
+				let next = self.next();
+				match next.kind {
+					t!("ORIGINAL") => res.store_diff = true,
+					t!("AUDIT") => res.store_audit = true,
+					_ => unexpected!(self, next, "'ORIGINAL' or 'AUDIT'"),
+				}
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		Ok(res)
+	}
 
-		Ok(ChangeFeed {
-			expiry,
-			store_diff,
-		})
+	/// Parses the capabilities of a namespace or database
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `CAPABILITIES` keyword
+	pub fn parse_capabilities(&mut self) -> ParseResult<ScopeCapabilities> {
+		let mut res = ScopeCapabilities::default();
+		loop {
+			let peek = self.peek();
+			let allow = match peek.kind {
+				t!("ALLOW") => true,
+				t!("DENY") => false,
+				// At least one rule is required
+				_ if res == ScopeCapabilities::default() => {
+					unexpected!(self, peek, "`ALLOW` or `DENY`")
+				}
+				_ => break,
+			};
+			self.pop_peek();
+			let next = self.next();
+			let functions = match next.kind {
+				t!("FUNCTIONS") => true,
+				t!("STATEMENTS") => false,
+				_ => unexpected!(self, next, "`FUNCTIONS` or `STATEMENTS`"),
+			};
+			loop {
+				let target = self.next_token_value::<Strand>()?;
+				let span = self.last_span();
+				if functions {
+					if let Err(e) = target.0.parse::<FuncTarget>() {
+						bail!("Invalid function target {target}: {e}", @span);
+					}
+				} else if let Err(e) = target.0.parse::<StatementTarget>() {
+					bail!("Invalid statement target {target}: {e}", @span);
+				}
+				match (allow, functions) {
+					(true, true) => res.allow_functions.push(target),
+					(false, true) => res.deny_functions.push(target),
+					(true, false) => res.allow_statements.push(target),
+					(false, false) => res.deny_statements.push(target),
+				}
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		Ok(res)
 	}
 
 	/// Parses a reference
@@ -450,11 +572,20 @@ impl Parser<'_> {
 				t!("CASCADE") => ReferenceDeleteStrategy::Cascade,
 				t!("IGNORE") => ReferenceDeleteStrategy::Ignore,
 				t!("UNSET") => ReferenceDeleteStrategy::Unset,
+				// SET NONE is the same as UNSET
+				t!("SET") => {
+					expected!(self, t!("NONE"));
+					ReferenceDeleteStrategy::Unset
+				}
 				t!("THEN") => ReferenceDeleteStrategy::Custom(
 					ctx.run(|ctx| self.parse_value_field(ctx)).await?,
 				),
 				_ => {
-					unexpected!(self, next, "`REJECT`, `CASCASE`, `IGNORE`, `UNSET` or `THEN`")
+					unexpected!(
+						self,
+						next,
+						"`REJECT`, `CASCADE`, `IGNORE`, `UNSET`, `SET NONE` or `THEN`"
+					)
 				}
 			}
 		} else {
@@ -483,7 +614,12 @@ impl Parser<'_> {
 		}
 
 		let cond = self.try_parse_condition(stk).await?;
+		let before_group = self.peek().span;
 		let group = self.try_parse_group(stk, &fields, fields_span).await?;
+		// The records of a view are identified by the raw values of their group
+		if group.iter().flat_map(|g| g.iter()).any(|g| g.1.is_some()) {
+			bail!("A COLLATE clause is not supported in the GROUP BY clause of a table view", @before_group.covers(self.last_span()));
+		}
 
 		Ok(View {
 			expr: fields,
@@ -543,10 +679,14 @@ impl Parser<'_> {
 		Ok(name)
 	}
 	pub(super) fn try_parse_explain(&mut self) -> ParseResult<Option<Explain>> {
-		Ok(self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL")))))
+		if !self.eat(t!("EXPLAIN")) {
+			return Ok(None);
+		}
+		let verbose = self.eat(t!("VERBOSE"));
+		Ok(Some(Explain(self.eat(t!("FULL")), verbose)))
 	}
 
-	pub(super) fn try_parse_with(&mut self) -> ParseResult<Option<With>> {
+	pub(super) fn try_parse_with(&mut self, deleted: bool) -> ParseResult<Option<With>> {
 		if !self.eat(t!("WITH")) {
 			return Ok(None);
 		}
@@ -558,14 +698,29 @@ impl Parser<'_> {
 				With::NoIndex
 			}
 			t!("INDEX") => {
-				let mut index = vec![self.next_token_value::<Ident>()?.0];
+				let mut index = vec![self.parse_index_hint()?];
 				while self.eat(t!(",")) {
-					index.push(self.next_token_value::<Ident>()?.0);
+					index.push(self.parse_index_hint()?);
 				}
 				With::Index(index)
 			}
+			t!("DELETED") if deleted => With::Deleted,
+			_ if deleted => unexpected!(self, next, "`NO`, `NOINDEX`, `INDEX` or `DELETED`"),
 			_ => unexpected!(self, next, "`NO`, `NOINDEX` or `INDEX`"),
 		};
 		Ok(Some(with))
 	}
+
+	/// Parses an index of a WITH INDEX clause, which can be prefixed with the
+	/// table it is used for, as in `person.idx_email`
+	fn parse_index_hint(&mut self) -> ParseResult<IndexHint> {
+		let name = self.next_token_value::<Ident>()?.0;
+		if !self.eat(t!(".")) {
+			return Ok(IndexHint::from(name));
+		}
+		Ok(IndexHint {
+			table: Some(name),
+			index: self.next_token_value::<Ident>()?.0,
+		})
+	}
 }
@@ -16,6 +16,7 @@ impl Parser<'_> {
 		let only = self.eat(t!("ONLY"));
 		let (kind, from, with) = stk.run(|stk| self.parse_relation(stk)).await?;
 		let uniq = self.eat(t!("UNIQUE"));
+		let zip = self.eat(t!("ZIP"));
 
 		let data = self.try_parse_data(stk).await?;
 		let output = self.try_parse_output(stk).await?;
@@ -31,6 +32,7 @@ impl Parser<'_> {
 			output,
 			timeout,
 			parallel,
+			zip,
 		})
 	}
 
@@ -1,6 +1,6 @@
 use reblessive::Stk;
 
-use crate::sql::statements::remove::RemoveSequenceStatement;
+use crate::sql::statements::remove::{RemoveScheduleStatement, RemoveSequenceStatement};
 use crate::{
 	sql::{
 		Param,
@@ -135,11 +135,13 @@ impl Parser<'_> {
 				};
 
 				let name = self.next_token_value()?;
+				let cascade = self.parse_remove_cascade();
 
 				RemoveStatement::Table(crate::sql::statements::RemoveTableStatement {
 					name,
 					if_exists,
 					expunge,
+					cascade,
 				})
 			}
 			t!("EVENT") => {
@@ -171,11 +173,13 @@ impl Parser<'_> {
 				expected!(self, t!("ON"));
 				self.eat(t!("TABLE"));
 				let table = self.next_token_value()?;
+				let cascade = self.parse_remove_cascade();
 
 				RemoveStatement::Field(RemoveFieldStatement {
 					name: idiom,
 					what: table,
 					if_exists,
+					cascade,
 				})
 			}
 			t!("INDEX") => {
@@ -223,6 +227,19 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("SCHEDULE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				RemoveStatement::Schedule(RemoveScheduleStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("USER") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -259,4 +276,13 @@ impl Parser<'_> {
 		};
 		Ok(res)
 	}
+
+	/// Parses the optional `CASCADE` or `RESTRICT` clause, defaulting to `RESTRICT`
+	fn parse_remove_cascade(&mut self) -> bool {
+		if self.eat(t!("CASCADE")) {
+			return true;
+		}
+		self.eat(t!("RESTRICT"));
+		false
+	}
 }
@@ -2,13 +2,18 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		Field, Fields, Idioms, Limit, Order, Split, Splits, SqlValues, Start, Version,
+		Field, Fields, Idioms, Limit, Nulls, Order, Split, Splits, SqlValues, Start, Version,
+		Window,
 		order::{OrderList, Ordering},
 		statements::SelectStatement,
 	},
 	syn::{
-		parser::{ParseResult, Parser, mac::expected},
-		token::{Span, t},
+		error::bail,
+		parser::{
+			ParseResult, Parser,
+			mac::{expected, unexpected},
+		},
+		token::{Span, TokenKind, t},
 	},
 };
 
@@ -20,7 +25,7 @@ impl Parser<'_> {
 		stk: &mut Stk,
 	) -> ParseResult<SelectStatement> {
 		let before = self.peek().span;
-		let expr = self.parse_fields(stk).await?;
+		let expr = self.parse_select_fields(stk).await?;
 		let fields_span = before.covers(self.last_span());
 
 		let omit = if self.eat(t!("OMIT")) {
@@ -39,10 +44,11 @@ impl Parser<'_> {
 		}
 		let what = SqlValues(what);
 
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(true)?;
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(stk, &expr, fields_span).await?;
 		let group = self.try_parse_group(stk, &expr, fields_span).await?;
+		let having = self.try_parse_having(stk).await?;
 		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
 		let (limit, start) = if let t!("START") = self.peek_kind() {
 			let start = self.try_parse_start(stk).await?;
@@ -57,6 +63,15 @@ impl Parser<'_> {
 		let version = self.try_parse_version(stk).await?;
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
+		let parallel_tasks = if parallel && matches!(self.peek_kind(), TokenKind::Digits) {
+			let tasks = self.next_token_value::<u32>()?;
+			if tasks == 0 {
+				bail!("The number of PARALLEL tasks must be greater than 0", @self.last_span());
+			}
+			Some(tasks)
+		} else {
+			None
+		};
 		let tempfiles = self.eat(t!("TEMPFILES"));
 		let explain = self.try_parse_explain()?;
 
@@ -69,6 +84,7 @@ impl Parser<'_> {
 			cond,
 			split,
 			group,
+			having,
 			order,
 			limit,
 			start,
@@ -76,6 +92,7 @@ impl Parser<'_> {
 			version,
 			timeout,
 			parallel,
+			parallel_tasks,
 			tempfiles,
 			explain,
 		})
@@ -163,9 +180,42 @@ impl Parser<'_> {
 		Ok(Some(Ordering::Order(OrderList(orders))))
 	}
 
+	/// Parses the window of a window function: `(PARTITION BY foo ORDER BY bar)`
+	///
+	/// # Parser State
+	/// Expects the `OVER` keyword to already be consumed.
+	pub(crate) async fn parse_window(&mut self, ctx: &mut Stk) -> ParseResult<Window> {
+		let start = expected!(self, t!("(")).span;
+		let mut partition = Vec::new();
+		if self.eat(t!("PARTITION")) {
+			expected!(self, t!("BY"));
+			loop {
+				partition.push(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		let order = if self.eat(t!("ORDER")) {
+			self.eat(t!("BY"));
+			let mut orders = vec![self.parse_order(ctx).await?];
+			while self.eat(t!(",")) {
+				orders.push(self.parse_order(ctx).await?);
+			}
+			Some(OrderList(orders))
+		} else {
+			None
+		};
+		self.expect_closing_delimiter(t!(")"), start)?;
+		Ok(Window {
+			partition,
+			order,
+		})
+	}
+
 	async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
 		let start = self.parse_basic_idiom(ctx).await?;
-		let collate = self.eat(t!("COLLATE"));
+		let collate = self.try_parse_collate();
 		let numeric = self.eat(t!("NUMERIC"));
 		let direction = match self.peek_kind() {
 			t!("ASCENDING") => {
@@ -178,11 +228,22 @@ impl Parser<'_> {
 			}
 			_ => true,
 		};
+		let nulls = if self.eat(t!("NULLS")) {
+			let next = self.next();
+			match next.kind {
+				t!("FIRST") => Some(Nulls::First),
+				t!("LAST") => Some(Nulls::Last),
+				_ => unexpected!(self, next, "'FIRST' or 'LAST'"),
+			}
+		} else {
+			None
+		};
 		Ok(Order {
 			value: start,
 			collate,
 			numeric,
 			direction,
+			nulls,
 		})
 	}
 
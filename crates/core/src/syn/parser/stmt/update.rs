@@ -1,7 +1,7 @@
 use reblessive::Stk;
 
 use crate::{
-	sql::{SqlValues, statements::UpdateStatement},
+	sql::{Fields, SqlValues, statements::UpdateStatement},
 	syn::{
 		parser::{ParseResult, Parser},
 		token::t,
@@ -12,9 +12,12 @@ impl Parser<'_> {
 	pub async fn parse_update_stmt(&mut self, stk: &mut Stk) -> ParseResult<UpdateStatement> {
 		let only = self.eat(t!("ONLY"));
 		let what = SqlValues(self.parse_what_list(stk).await?);
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(false)?;
 		let data = self.try_parse_data(stk).await?;
+		let if_version = self.try_parse_if_version(stk).await?;
 		let cond = self.try_parse_condition(stk).await?;
+		let order = self.try_parse_orders(stk, &Fields::all(), self.last_span()).await?;
+		let limit = self.try_parse_limit(stk).await?;
 		let output = self.try_parse_output(stk).await?;
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
@@ -25,7 +28,10 @@ impl Parser<'_> {
 			what,
 			with,
 			data,
+			if_version,
 			cond,
+			order,
+			limit,
 			output,
 			timeout,
 			parallel,
@@ -12,7 +12,7 @@ impl Parser<'_> {
 	pub async fn parse_upsert_stmt(&mut self, stk: &mut Stk) -> ParseResult<UpsertStatement> {
 		let only = self.eat(t!("ONLY"));
 		let what = SqlValues(self.parse_what_list(stk).await?);
-		let with = self.try_parse_with()?;
+		let with = self.try_parse_with(false)?;
 		let data = self.try_parse_data(stk).await?;
 		let cond = self.try_parse_condition(stk).await?;
 		let output = self.try_parse_output(stk).await?;
@@ -1,14 +1,16 @@
 use crate::{
 	sql::{
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, SqlValue, SqlValues, Start, Statement,
-		Strand, Subquery, Table, TableType, Tables, Thing, Timeout, Uuid, Version, With,
+		Algorithm, Array, ArrayConstraints, Base, Block, Cond, Data, Datetime, Dir, Duration,
+		Edges, Explain, Expression, Fetch, Fetchs, Field, Fields, Function, Future, Graph, Group,
+		Groups, Id, Ident, Idiom, Idioms, Index, IndexHint, Kind, Limit, Number, Object, Operator,
+		Order, Output, Param, Part, Permission, Permissions, ScopeCapabilities, Scoring, Split,
+		Splits, SqlValue, SqlValues, Start, Statement, Strand, Subquery, Table, TableType, Tables,
+		Thing, Timeout, Uuid, Version, Window, With,
 		access::AccessDuration,
 		access_type::{
 			AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess,
-			JwtAccessIssue, JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey, RecordAccess,
+			JwtAccessIssue, JwtAccessKey, JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey,
+			RecordAccess,
 		},
 		block::Entry,
 		changefeed::ChangeFeed,
@@ -16,25 +18,29 @@ use crate::{
 		graph::{GraphSubject, GraphSubjects},
 		index::{Distance, HnswParams, MTreeParams, SearchParams, VectorType},
 		language::Language,
-		order::{OrderList, Ordering},
+		order::{Collate, Nulls, OrderList, Ordering},
 		statements::{
-			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
-			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
-			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
-			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-			RemoveUserStatement, SelectStatement, ThrowStatement, UpdateStatement, UpsertStatement,
-			UseStatement,
+			AccessStatement, AlterStatement, BeginStatement, BreakStatement, CancelStatement,
+			CommitStatement, ContinueStatement, CreateStatement, DefineAccessStatement,
+			DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement,
+			DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
+			DefineNamespaceStatement, DefineParamStatement, DefineStatement, DefineTableStatement,
+			DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement, InsertStatement,
+			KillStatement, OptionStatement, OutputStatement, RelateStatement,
+			RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement,
+			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
+			RemoveTableStatement, RemoveUserStatement, SelectStatement, ThrowStatement,
+			TruncateStatement, TrycatchStatement, UpdateStatement, UpsertStatement, UseStatement,
 			access::{
 				self, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke,
 				AccessStatementShow,
 			},
+			alter::AlterAccessStatement,
 			analyze::AnalyzeStatement,
+			define::DefineScheduleStatement,
+			rebuild::{RebuildIndexStatement, RebuildStatement},
+			remove::RemoveScheduleStatement,
 			show::{ShowSince, ShowStatement},
 			sleep::SleepStatement,
 		},
@@ -58,7 +64,9 @@ pub fn parse_analyze() {
 	assert_eq!(
 		res,
 		Statement::Analyze(AnalyzeStatement::Idx(Ident("a".to_string()), Ident("b".to_string())))
-	)
+	);
+	let res = test_parse!(parse_stmt, r#"ANALYZE TABLE a"#).unwrap();
+	assert_eq!(res, Statement::Analyze(AnalyzeStatement::Tb(Ident("a".to_string()))))
 }
 
 #[test]
@@ -72,7 +80,14 @@ pub fn parse_begin() {
 #[test]
 pub fn parse_break() {
 	let res = test_parse!(parse_stmt, r#"BREAK"#).unwrap();
-	assert_eq!(res, Statement::Break(BreakStatement));
+	assert_eq!(res, Statement::Break(BreakStatement::default()));
+	let res = test_parse!(parse_stmt, r#"BREAK outer"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Break(BreakStatement {
+			label: Some(Ident("outer".to_owned())),
+		})
+	);
 }
 
 #[test]
@@ -94,7 +109,14 @@ pub fn parse_commit() {
 #[test]
 pub fn parse_continue() {
 	let res = test_parse!(parse_stmt, r#"CONTINUE"#).unwrap();
-	assert_eq!(res, Statement::Continue(ContinueStatement));
+	assert_eq!(res, Statement::Continue(ContinueStatement::default()));
+	let res = test_parse!(parse_stmt, r#"CONTINUE outer"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Continue(ContinueStatement {
+			label: Some(Ident("outer".to_owned())),
+		})
+	);
 }
 
 #[test]
@@ -146,6 +168,7 @@ fn parse_define_namespace() {
 			comment: Some(Strand("test".to_string())),
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		}))
 	);
 
@@ -158,6 +181,7 @@ fn parse_define_namespace() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		}))
 	)
 }
@@ -176,9 +200,11 @@ fn parse_define_database() {
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(60) * 10,
 				store_diff: true,
+				store_audit: false,
 			}),
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		}))
 	);
 
@@ -192,10 +218,55 @@ fn parse_define_database() {
 			changefeed: None,
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		}))
 	)
 }
 
+#[test]
+fn parse_define_capabilities() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE NAMESPACE a CAPABILITIES DENY FUNCTIONS "http", "crypto::*" DENY STATEMENTS "REMOVE DATABASE""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Namespace(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.capabilities,
+		Some(ScopeCapabilities {
+			deny_functions: vec![Strand("http".to_string()), Strand("crypto::*".to_string())],
+			deny_statements: vec![Strand("REMOVE DATABASE".to_string())],
+			..Default::default()
+		})
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE DATABASE a CAPABILITIES ALLOW FUNCTIONS "http::get" ALLOW STATEMENTS "define""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Database(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.capabilities,
+		Some(ScopeCapabilities {
+			allow_functions: vec![Strand("http::get".to_string())],
+			allow_statements: vec![Strand("define".to_string())],
+			..Default::default()
+		})
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE NAMESPACE a CAPABILITIES"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE NAMESPACE a CAPABILITIES DENY TABLES "a""#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE NAMESPACE a CAPABILITIES DENY FUNCTIONS "a::*::b""#)
+		.unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE NAMESPACE a CAPABILITIES DENY STATEMENTS "SELECT""#)
+		.unwrap_err();
+}
+
 #[test]
 fn parse_define_function() {
 	let res = test_parse!(
@@ -213,7 +284,10 @@ fn parse_define_function() {
 			name: Ident("foo::bar".to_string()),
 			args: vec![
 				(Ident("a".to_string()), Kind::Number),
-				(Ident("b".to_string()), Kind::Array(Box::new(Kind::Bool), Some(3)))
+				(
+					Ident("b".to_string()),
+					Kind::Array(Box::new(Kind::Bool), ArrayConstraints::exact(3))
+				)
 			],
 			block: Block(vec![Entry::Output(OutputStatement {
 				what: ident_field("a"),
@@ -224,6 +298,7 @@ fn parse_define_function() {
 			if_not_exists: false,
 			overwrite: false,
 			returns: None,
+			overloads: Vec::new(),
 		}))
 	)
 }
@@ -409,6 +484,7 @@ fn parse_define_token() {
 					key: "foo".to_string(),
 				}),
 				issue: None,
+				keys: Vec::new(),
 			}),
 			authenticate: None,
 			// Default durations.
@@ -486,6 +562,7 @@ fn parse_define_token_jwks() {
 					url: "http://example.com/.well-known/jwks.json".to_string(),
 				}),
 				issue: None,
+				keys: Vec::new(),
 			}),
 			authenticate: None,
 			// Default durations.
@@ -613,6 +690,7 @@ fn parse_define_access_jwt_key() {
 						key: "foo".to_string(),
 					}),
 					issue: None,
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -648,6 +726,7 @@ fn parse_define_access_jwt_key() {
 						alg: Algorithm::EdDSA,
 						key: "bar".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -683,6 +762,7 @@ fn parse_define_access_jwt_key() {
 						alg: Algorithm::EdDSA,
 						key: "bar".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: Some(SqlValue::Bool(true)),
 				// Default durations.
@@ -718,6 +798,7 @@ fn parse_define_access_jwt_key() {
 						alg: Algorithm::Hs256,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -753,6 +834,7 @@ fn parse_define_access_jwt_key() {
 						alg: Algorithm::Hs256,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -787,6 +869,7 @@ fn parse_define_access_jwt_key() {
 						alg: Algorithm::Hs256,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -867,6 +950,7 @@ fn parse_define_access_jwt_key() {
 						key: "foo".to_string(),
 					}),
 					issue: None,
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -899,6 +983,7 @@ fn parse_define_access_jwt_key() {
 						key: "foo".to_string(),
 					}),
 					issue: None,
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -915,6 +1000,85 @@ fn parse_define_access_jwt_key() {
 	}
 }
 
+#[test]
+fn parse_define_access_jwt_additional_keys() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ACCESS a ON DATABASE TYPE JWT ALGORITHM HS512 KEY "new" WITH KEY "old" ALGORITHM HS256 KEY "foo" WITH KEY "older" ALGORITHM EDDSA KEY "bar""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Access(DefineAccessStatement {
+			name: Ident("a".to_string()),
+			base: Base::Db,
+			kind: AccessType::Jwt(JwtAccess {
+				verify: JwtAccessVerify::Key(JwtAccessVerifyKey {
+					alg: Algorithm::Hs512,
+					key: "new".to_string(),
+				}),
+				issue: Some(JwtAccessIssue {
+					alg: Algorithm::Hs512,
+					key: "new".to_string(),
+				}),
+				keys: vec![
+					JwtAccessKey {
+						kid: "old".to_string(),
+						alg: Algorithm::Hs256,
+						key: "foo".to_string(),
+					},
+					JwtAccessKey {
+						kid: "older".to_string(),
+						alg: Algorithm::EdDSA,
+						key: "bar".to_string(),
+					},
+				],
+			}),
+			authenticate: None,
+			// Default durations.
+			duration: AccessDuration {
+				grant: Some(Duration::from_days(30).unwrap()),
+				token: Some(Duration::from_hours(1).unwrap()),
+				session: None,
+			},
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+		})),
+	);
+	// The key identifiers must be unique
+	test_parse!(
+		parse_stmt,
+		r#"DEFINE ACCESS a ON DATABASE TYPE JWT ALGORITHM HS512 KEY "new" WITH KEY "old" ALGORITHM HS256 KEY "foo" WITH KEY "old" ALGORITHM HS256 KEY "bar""#
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_alter_access() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"ALTER ACCESS IF EXISTS a ON NAMESPACE ADD KEY "new" ALGORITHM HS384 KEY "foo" REMOVE KEY "old""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Access(AlterAccessStatement {
+			name: Ident("a".to_string()),
+			base: Base::Ns,
+			if_exists: true,
+			add_keys: vec![JwtAccessKey {
+				kid: "new".to_string(),
+				alg: Algorithm::Hs384,
+				key: "foo".to_string(),
+			}],
+			remove_keys: vec!["old".to_string()],
+		})),
+	);
+	// At least one key must be added or removed
+	test_parse!(parse_stmt, r#"ALTER ACCESS a ON DATABASE"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_access_jwt_jwks() {
 	// With comment. Verify only.
@@ -934,6 +1098,7 @@ fn parse_define_access_jwt_jwks() {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
 					}),
 					issue: None,
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -968,6 +1133,7 @@ fn parse_define_access_jwt_jwks() {
 						alg: Algorithm::Hs384,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -1002,6 +1168,7 @@ fn parse_define_access_jwt_jwks() {
 						alg: Algorithm::Hs384,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1035,6 +1202,7 @@ fn parse_define_access_jwt_jwks() {
 						alg: Algorithm::Ps256,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				// Default durations.
@@ -1069,6 +1237,7 @@ fn parse_define_access_jwt_jwks() {
 						alg: Algorithm::Ps256,
 						key: "foo".to_string(),
 					}),
+					keys: Vec::new(),
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1278,6 +1447,7 @@ fn parse_define_access_record() {
 							// Issuer key matches verification key by default in symmetric algorithms.
 							key: "foo".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 					bearer: None,
 				}),
@@ -1317,6 +1487,7 @@ fn parse_define_access_record() {
 							alg: Algorithm::Ps512,
 							key: "bar".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 					bearer: None,
 				}),
@@ -1360,6 +1531,7 @@ fn parse_define_access_record() {
 							alg: Algorithm::Ps512,
 							key: "bar".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 					bearer: Some(BearerAccess {
 						kind: BearerAccessType::Refresh,
@@ -1373,6 +1545,7 @@ fn parse_define_access_record() {
 								alg: Algorithm::Ps512,
 								key: "bar".to_string(),
 							}),
+							keys: Vec::new(),
 						},
 					}),
 				}),
@@ -1414,6 +1587,7 @@ fn parse_define_access_record() {
 							alg: Algorithm::Ps512,
 							key: "bar".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 					bearer: Some(BearerAccess {
 						kind: BearerAccessType::Refresh,
@@ -1427,6 +1601,7 @@ fn parse_define_access_record() {
 								alg: Algorithm::Ps512,
 								key: "bar".to_string(),
 							}),
+							keys: Vec::new(),
 						},
 					}),
 				}),
@@ -1466,6 +1641,7 @@ fn parse_define_access_record() {
 							alg: Algorithm::Rs256,
 							key: "bar".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 					bearer: None,
 				}),
@@ -1731,6 +1907,7 @@ fn parse_define_access_bearer() {
 							// Issuer key matches verification key by default in symmetric algorithms.
 							key: "foo".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 				}),
 				authenticate: None,
@@ -1772,6 +1949,7 @@ fn parse_define_access_bearer() {
 							// Issuer key matches verification key by default in symmetric algorithms.
 							key: "foo".to_string(),
 						}),
+						keys: Vec::new(),
 					},
 				}),
 				authenticate: None,
@@ -1837,7 +2015,10 @@ fn parse_define_table() {
 				),
 				what: Tables(vec![Table("bar".to_owned())]),
 				cond: None,
-				group: Some(Groups(vec![Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+				group: Some(Groups(vec![Group(
+					Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+					None
+				)])),
 			}),
 			permissions: Permissions {
 				select: Permission::Specific(SqlValue::Expression(Box::new(
@@ -1854,6 +2035,7 @@ fn parse_define_table() {
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(1),
 				store_diff: true,
+				store_audit: false,
 			}),
 			comment: None,
 			if_not_exists: false,
@@ -1863,8 +2045,124 @@ fn parse_define_table() {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			tenant: None,
+			assert: None,
+			validate: false,
+			soft_delete: false,
+			versioned: false,
+		}))
+	);
+}
+
+#[test]
+fn parse_define_table_changefeed_audit() {
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL, AUDIT"#)
+			.unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	let changefeed = table.changefeed.unwrap();
+	assert!(changefeed.store_diff);
+	assert!(changefeed.store_audit);
+	assert_eq!(changefeed.to_string(), "CHANGEFEED 1h INCLUDE ORIGINAL, AUDIT");
+	// The audit can be stored without the original value
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TABLE person CHANGEFEED 1h INCLUDE AUDIT"#).unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	let changefeed = table.changefeed.unwrap();
+	assert!(!changefeed.store_diff);
+	assert!(changefeed.store_audit);
+}
+
+#[test]
+fn parse_define_table_tenant() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE post TENANT org.id"#).unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	let tenant = table.tenant.unwrap();
+	assert_eq!(tenant.field.to_string(), "org.id");
+	// The tenant defaults to the same field of the authenticated record
+	assert_eq!(
+		tenant.value,
+		SqlValue::Idiom(Idiom(vec![
+			Part::Start(SqlValue::Param(Param::from("auth"))),
+			Part::Field(Ident("org".to_owned())),
+			Part::Field(Ident("id".to_owned())),
+		]))
+	);
+	let explicit =
+		test_parse!(parse_stmt, r#"DEFINE TABLE post TENANT org.id VALUE $auth.org.id"#).unwrap();
+	assert_eq!(
+		explicit,
+		Statement::Define(DefineStatement::Table(DefineTableStatement {
+			tenant: Some(tenant),
+			..table
 		}))
 	);
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TABLE post TENANT tenant VALUE $session.pv.tenant"#)
+			.unwrap();
+	assert_eq!(
+		res.to_string(),
+		"DEFINE TABLE post TYPE ANY SCHEMALESS TENANT tenant VALUE $session.pv.tenant PERMISSIONS NONE"
+	);
+}
+
+#[test]
+fn parse_define_table_assert() {
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TABLE booking ASSERT ends > starts VALIDATE"#).unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	assert_eq!(table.assert.as_ref().unwrap().to_string(), "ends > starts");
+	assert!(table.validate);
+	assert_eq!(
+		table.to_string(),
+		"DEFINE TABLE booking TYPE ANY SCHEMALESS ASSERT ends > starts VALIDATE PERMISSIONS NONE"
+	);
+	let res = test_parse!(parse_stmt, r#"ALTER TABLE booking ASSERT email OR phone"#).unwrap();
+	let Statement::Alter(AlterStatement::Table(alter)) = res else {
+		panic!("expected an ALTER TABLE statement: {res:?}");
+	};
+	assert_eq!(alter.assert.unwrap().unwrap().to_string(), "email OR phone");
+	assert!(!alter.validate);
+	let res = test_parse!(parse_stmt, r#"ALTER TABLE booking DROP ASSERT"#).unwrap();
+	let Statement::Alter(AlterStatement::Table(alter)) = res else {
+		panic!("expected an ALTER TABLE statement: {res:?}");
+	};
+	assert_eq!(alter.assert, Some(None));
+}
+
+#[test]
+fn parse_define_table_soft_delete() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE customer SOFT DELETE"#).unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	assert!(table.soft_delete);
+	assert_eq!(
+		table.to_string(),
+		"DEFINE TABLE customer TYPE ANY SOFT DELETE SCHEMALESS PERMISSIONS NONE"
+	);
+	test_parse!(parse_stmt, r#"DEFINE TABLE customer SOFT"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_table_versioned() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE person VERSIONED"#).unwrap();
+	let Statement::Define(DefineStatement::Table(table)) = res else {
+		panic!("expected a DEFINE TABLE statement: {res:?}");
+	};
+	assert!(table.versioned);
+	assert_eq!(
+		table.to_string(),
+		"DEFINE TABLE person TYPE ANY VERSIONED SCHEMALESS PERMISSIONS NONE"
+	);
 }
 
 #[test]
@@ -1883,10 +2181,44 @@ fn parse_define_event() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			order: None,
+			guard: None,
 		}))
 	)
 }
 
+#[test]
+fn parse_define_event_order() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE EVENT OVERWRITE event ON TABLE table ORDER 2 IF $enabled THEN null"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Event(DefineEventStatement {
+			name: Ident("event".to_owned()),
+			what: Ident("table".to_owned()),
+			when: SqlValue::Bool(true),
+			then: SqlValues(vec![SqlValue::Null]),
+			comment: None,
+			if_not_exists: false,
+			overwrite: true,
+			order: Some(2),
+			guard: Some(SqlValue::Param(Param(Ident("enabled".to_owned())))),
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE EVENT OVERWRITE event ON table ORDER 2 IF $enabled WHEN true THEN NULL"
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE EVENT OVERWRITE event ON TABLE table ORDER 3"#).unwrap();
+	assert_eq!(res.to_string(), "DEFINE EVENT OVERWRITE event ON table ORDER 3");
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -1909,7 +2241,10 @@ fn parse_define_field() {
 				flex: true,
 				kind: Some(Kind::Option(Box::new(Kind::Either(vec![
 					Kind::Number,
-					Kind::Array(Box::new(Kind::Record(vec![Table("foo".to_owned())])), Some(10))
+					Kind::Array(
+						Box::new(Kind::Record(vec![Table("foo".to_owned())])),
+						ArrayConstraints::exact(10)
+					)
 				])))),
 				readonly: false,
 				value: Some(SqlValue::Null),
@@ -1926,6 +2261,10 @@ fn parse_define_field() {
 				overwrite: false,
 				reference: None,
 				default_always: false,
+				expire: false,
+				created: false,
+				modified: false,
+				computed: None,
 			}))
 		)
 	}
@@ -1960,9 +2299,66 @@ fn parse_define_field() {
 				overwrite: false,
 				reference: None,
 				default_always: false,
+				expire: false,
+				created: false,
+				modified: false,
+				computed: None,
 			}))
 		)
 	}
+
+	// Generated timestamps
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE FIELD created_at ON TABLE bar TYPE datetime CREATED"#
+		)
+		.unwrap();
+		let Statement::Define(DefineStatement::Field(field)) = res else {
+			panic!("expected a DEFINE FIELD statement")
+		};
+		assert!(field.created);
+		assert!(!field.modified);
+		assert_eq!(
+			field.to_string(),
+			"DEFINE FIELD created_at ON bar TYPE datetime CREATED PERMISSIONS FULL"
+		);
+
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE FIELD updated_at ON TABLE bar MODIFIED"#).unwrap();
+		let Statement::Define(DefineStatement::Field(field)) = res else {
+			panic!("expected a DEFINE FIELD statement")
+		};
+		assert!(!field.created);
+		assert!(field.modified);
+
+		test_parse!(parse_stmt, r#"DEFINE FIELD at ON TABLE bar CREATED MODIFIED"#).unwrap_err();
+		test_parse!(parse_stmt, r#"DEFINE FIELD at ON TABLE bar MODIFIED VALUE time::now()"#)
+			.unwrap_err();
+		test_parse!(parse_stmt, r#"DEFINE FIELD at ON TABLE bar DEFAULT time::now() CREATED"#)
+			.unwrap_err();
+	}
+
+	// Computed fields
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE FIELD full_name ON person COMPUTED string::concat(first, ' ', last) TYPE string"#
+		)
+		.unwrap();
+		let Statement::Define(DefineStatement::Field(field)) = res else {
+			panic!("expected a DEFINE FIELD statement")
+		};
+		assert!(field.computed.is_some());
+		assert_eq!(
+			field.to_string(),
+			"DEFINE FIELD full_name ON person TYPE string COMPUTED string::concat(first, ' ', last) PERMISSIONS FULL"
+		);
+
+		test_parse!(parse_stmt, r#"DEFINE FIELD a ON person COMPUTED b VALUE c"#).unwrap_err();
+		test_parse!(parse_stmt, r#"DEFINE FIELD a ON person DEFAULT c COMPUTED b"#).unwrap_err();
+		test_parse!(parse_stmt, r#"DEFINE FIELD a ON person COMPUTED b READONLY"#).unwrap_err();
+	}
 }
 
 #[test]
@@ -2010,7 +2406,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -2027,7 +2424,47 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a WHERE active UNIQUE"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Uniq,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: Some(Cond(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("active".to_owned()))])))),
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a TRIGRAM"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Trigram,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -2052,7 +2489,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -2079,7 +2517,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 }
@@ -2117,6 +2556,84 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[test]
+fn parse_define_analyzer_parameterised_filters() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ANALYZER ana TOKENIZERS BLANK FILTERS STOPWORDS(["the", 'a']), MAPPING({ "ß": "ss", ",": "" }), LENGTH(2,40)"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			name: Ident("ana".to_owned()),
+			tokenizers: Some(vec![Tokenizer::Blank]),
+			filters: Some(vec![
+				Filter::Stopwords(vec!["the".to_owned(), "a".to_owned()]),
+				Filter::Mapping(
+					[("ß".to_owned(), "ss".to_owned()), (",".to_owned(), String::new())]
+						.into_iter()
+						.collect()
+				),
+				Filter::Length(2, 40),
+			]),
+			comment: None,
+			function: None,
+			if_not_exists: false,
+			overwrite: false,
+		})),
+	);
+	test_parse!(parse_stmt, r#"DEFINE ANALYZER ana FILTERS LENGTH(3,2)"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE ANALYZER ana FILTERS MAPPING({ "": "a" })"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE ANALYZER ana FILTERS STOPWORDS("the")"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_schedule() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SCHEDULE cleanup ON DATABASE CRON '0 3 * * *' THEN { RETURN a } COMMENT 'test'"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Schedule(DefineScheduleStatement {
+			name: Ident("cleanup".to_owned()),
+			if_not_exists: false,
+			overwrite: false,
+			cron: Strand("0 3 * * *".to_owned()),
+			roles: vec![Ident("Editor".to_owned())],
+			then: Block(vec![Entry::Output(OutputStatement {
+				what: ident_field("a"),
+				fetch: None,
+			})]),
+			comment: Some(Strand("test".to_owned())),
+		})),
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SCHEDULE OVERWRITE cleanup ON DB CRON '@hourly' ROLES VIEWER THEN { RETURN a }"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Schedule(stmt)) = res else {
+		panic!("expected a DEFINE SCHEDULE statement");
+	};
+	assert!(stmt.overwrite);
+	assert_eq!(stmt.roles, vec![Ident("VIEWER".to_owned())]);
+
+	test_parse!(parse_stmt, r#"DEFINE SCHEDULE cleanup ON DATABASE CRON '0 3 * *' THEN {}"#)
+		.unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE SCHEDULE cleanup ON DATABASE CRON '61 * * * *' THEN {}"#)
+		.unwrap_err();
+	test_parse!(
+		parse_stmt,
+		r#"DEFINE SCHEDULE cleanup ON DATABASE CRON '@daily' ROLES ADMIN THEN {}"#
+	)
+	.unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE SCHEDULE cleanup ON DATABASE CRON '@daily'"#).unwrap_err();
+}
+
 #[test]
 fn parse_delete() {
 	let res = test_parse!(
@@ -2133,12 +2650,17 @@ fn parse_delete() {
 				32,
 				64
 			))]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned())
+			])),
 			cond: Some(Cond(SqlValue::Number(Number::Int(2)))),
+			order: None,
+			limit: None,
 			output: Some(Output::After),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		})
 	);
 }
@@ -2167,30 +2689,60 @@ fn parse_delete_2() {
 				Part::Last,
 				Part::Where(SqlValue::Bool(true)),
 			]))]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned())
+			])),
 			cond: Some(Cond(SqlValue::Null)),
+			order: None,
+			limit: None,
 			output: Some(Output::Null),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(60 * 60)))),
 			parallel: true,
-			explain: Some(Explain(false)),
+			explain: Some(Explain(false, false)),
 		})
 	)
 }
 
 #[test]
-pub fn parse_for() {
+fn parse_delete_order_limit() {
 	let res = test_parse!(
 		parse_stmt,
-		r#"FOR $foo IN (SELECT foo FROM bar) * 2 {
-			BREAK
-		}"#
+		r#"DELETE FROM audit WHERE created < $cutoff ORDER BY created ASC LIMIT 1000 RETURN BEFORE"#
+	)
+	.unwrap();
+	let Statement::Delete(stmt) = res else {
+		panic!()
+	};
+	let order = OrderList(vec![Order {
+		value: Idiom(vec![Part::Field(Ident("created".to_owned()))]),
+		collate: None,
+		numeric: false,
+		direction: true,
+		nulls: None,
+	}]);
+	assert_eq!(stmt.order, Some(Ordering::Order(order)));
+	assert_eq!(stmt.limit, Some(Limit(SqlValue::Number(Number::Int(1000)))));
+	assert_eq!(stmt.output, Some(Output::Before));
+}
+
+#[test]
+pub fn parse_for() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"FOR $foo IN (SELECT foo FROM bar) * 2 {
+			BREAK
+		}"#
 	)
 	.unwrap();
 
 	assert_eq!(
 		res,
 		Statement::Foreach(ForeachStatement {
+			label: None,
 			param: Param(Ident("foo".to_owned())),
+			kind: None,
+			index: None,
 			range: SqlValue::Expression(Box::new(Expression::Binary {
 				l: SqlValue::Subquery(Box::new(Subquery::Select(SelectStatement {
 					expr: Fields(
@@ -2208,11 +2760,70 @@ pub fn parse_for() {
 				o: Operator::Mul,
 				r: SqlValue::Number(Number::Int(2))
 			})),
-			block: Block(vec![Entry::Break(BreakStatement)])
+			block: Block(vec![Entry::Break(BreakStatement::default())])
 		})
 	)
 }
 
+#[test]
+pub fn parse_for_labelled() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"outer: FOR $row, $i IN $rows {
+			FOR $cell IN $row {
+				BREAK outer
+			}
+		}"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Foreach(ForeachStatement {
+			label: Some(Ident("outer".to_owned())),
+			param: Param(Ident("row".to_owned())),
+			kind: None,
+			index: Some(Param(Ident("i".to_owned()))),
+			range: SqlValue::Param(Param(Ident("rows".to_owned()))),
+			block: Block(vec![Entry::Foreach(ForeachStatement {
+				label: None,
+				param: Param(Ident("cell".to_owned())),
+				kind: None,
+				index: None,
+				range: SqlValue::Param(Param(Ident("row".to_owned()))),
+				block: Block(vec![Entry::Break(BreakStatement {
+					label: Some(Ident("outer".to_owned())),
+				})]),
+			})]),
+		})
+	);
+	assert_eq!(
+		res.to_string(),
+		"outer: FOR $row, $i IN $rows { FOR $cell IN $row { BREAK outer; }; }"
+	);
+
+	// A record id is not mistaken for a label
+	let res = test_parse!(parse_stmt, r#"outer:for"#).unwrap();
+	assert!(matches!(res, Statement::Value(SqlValue::Thing(_))));
+}
+
+#[test]
+pub fn parse_for_kind() {
+	let res = test_parse!(parse_stmt, r#"FOR $p: record<person>, $i IN $people {}"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Foreach(ForeachStatement {
+			label: None,
+			param: Param(Ident("p".to_owned())),
+			kind: Some(Kind::Record(vec![Table("person".to_owned())])),
+			index: Some(Param(Ident("i".to_owned()))),
+			range: SqlValue::Param(Param(Ident("people".to_owned()))),
+			block: Block::default(),
+		})
+	);
+	assert_eq!(res.to_string(), "FOR $p: record<person>, $i IN $people {}");
+}
+
 #[test]
 fn parse_if() {
 	let res =
@@ -2276,6 +2887,18 @@ fn parse_info() {
 		res,
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false))
 	);
+
+	let res = test_parse!(parse_stmt, "INFO FOR NS AUDIT").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Audit(Base::Ns)));
+
+	let res = test_parse!(parse_stmt, "INFO FOR DATABASE AUDIT").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Audit(Base::Db)));
+
+	let res = test_parse!(parse_stmt, "INFO FOR DATABASE OUTBOX").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Outbox));
+
+	let res = test_parse!(parse_stmt, "INFO FOR SCHEDULE cleanup").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Schedule(Ident("cleanup".to_owned()))));
 }
 
 #[test]
@@ -2339,21 +2962,26 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 				SqlValue::Table(Table("a".to_owned())),
 				SqlValue::Number(Number::Int(1))
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned())
+			])),
 			cond: Some(Cond(SqlValue::Bool(true))),
 			split: Some(Splits(vec![
 				Split(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Split(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
 			group: Some(Groups(vec![
-				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
-				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
+				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]), None),
+				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))]), None),
 			])),
+			having: None,
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
-				collate: true,
+				collate: Some(Collate::Lexical),
 				numeric: true,
 				direction: true,
+				nulls: None,
 			}]))),
 			limit: Some(Limit(SqlValue::Thing(Thing {
 				tb: "a".to_owned(),
@@ -2362,18 +2990,233 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			start: Some(Start(SqlValue::Object(Object(
 				[("a".to_owned(), SqlValue::Bool(true))].into_iter().collect()
 			)))),
-			fetch: Some(Fetchs(vec![Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident(
-				"foo".to_owned()
-			))])))])),
+			fetch: Some(Fetchs(vec![Fetch(
+				SqlValue::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
+				None
+			)])),
 			version: Some(Version(SqlValue::Datetime(Datetime(expected_datetime)))),
 			timeout: None,
 			parallel: false,
+			parallel_tasks: None,
 			tempfiles: false,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 	);
 }
 
+#[test]
+fn parse_select_having() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT count() AS total FROM person GROUP BY age HAVING total > 1"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.having,
+		Some(Cond(SqlValue::Expression(Box::new(Expression::Binary {
+			l: SqlValue::Idiom(Idiom(vec![Part::Field(Ident("total".to_owned()))])),
+			o: Operator::MoreThan,
+			r: SqlValue::Number(Number::Int(1)),
+		}))))
+	);
+	assert_eq!(
+		stmt.to_string(),
+		"SELECT count() AS total FROM person GROUP BY age HAVING total > 1"
+	);
+}
+
+#[test]
+fn parse_select_window() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT rank() OVER (PARTITION BY team ORDER BY score DESC) AS position FROM player"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.expr,
+		Fields(
+			vec![Field::Window {
+				expr: SqlValue::Function(Box::new(Function::Normal("rank".to_owned(), vec![]))),
+				window: Window {
+					partition: vec![ident_field("team")],
+					order: Some(OrderList(vec![Order {
+						value: Idiom(vec![Part::Field(Ident("score".to_owned()))]),
+						collate: None,
+						numeric: false,
+						direction: false,
+						nulls: None,
+					}])),
+				},
+				alias: Some(Idiom(vec![Part::Field(Ident("position".to_owned()))])),
+			}],
+			false,
+		)
+	);
+	assert_eq!(
+		stmt.to_string(),
+		"SELECT rank() OVER (PARTITION BY team ORDER BY score DESC) AS position FROM player"
+	);
+
+	// Only function calls can be computed over a window
+	test_parse!(parse_stmt, r#"SELECT score OVER (ORDER BY score) FROM player"#).unwrap_err();
+	// Windows can only be used in the fields of a SELECT statement
+	test_parse!(parse_stmt, r#"LIVE SELECT rank() OVER (ORDER BY score) FROM player"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_order_collate() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM person ORDER BY name COLLATE NOCASE, city COLLATE UNICODE NUMERIC DESC, age COLLATE"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	let order = |name: &str, collate, numeric, direction| Order {
+		value: Idiom(vec![Part::Field(Ident(name.to_owned()))]),
+		collate,
+		numeric,
+		direction,
+		nulls: None,
+	};
+	let list = OrderList(vec![
+		order("name", Some(Collate::NoCase), false, true),
+		order("city", Some(Collate::Unicode), true, false),
+		order("age", Some(Collate::Lexical), false, true),
+	]);
+	assert_eq!(
+		list.to_string(),
+		"name COLLATE NOCASE, city COLLATE UNICODE NUMERIC DESC, age COLLATE"
+	);
+	assert_eq!(stmt.order, Some(Ordering::Order(list)));
+	// Groups are compared with their collation
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT city, name FROM person GROUP BY city COLLATE UNICODE, name COLLATE"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	let group =
+		|name: &str, collate| Group(Idiom(vec![Part::Field(Ident(name.to_owned()))]), collate);
+	let groups =
+		Groups(vec![group("city", Some(Collate::Unicode)), group("name", Some(Collate::Lexical))]);
+	assert_eq!(groups.to_string(), "GROUP BY city COLLATE UNICODE, name COLLATE");
+	assert_eq!(stmt.group, Some(groups));
+	// Index keys and the records of a view are identified by their raw value
+	test_parse!(parse_stmt, r#"DEFINE INDEX name ON person FIELDS name COLLATE UNICODE"#)
+		.unwrap_err();
+	test_parse!(
+		parse_stmt,
+		r#"DEFINE TABLE names AS SELECT name, count() FROM person GROUP BY name COLLATE NOCASE"#
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_select_order_nulls() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM person ORDER BY score DESC NULLS LAST, name NULLS FIRST, age"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	let order = |name: &str, direction, nulls| Order {
+		value: Idiom(vec![Part::Field(Ident(name.to_owned()))]),
+		collate: None,
+		numeric: false,
+		direction,
+		nulls,
+	};
+	let list = OrderList(vec![
+		order("score", false, Some(Nulls::Last)),
+		order("name", true, Some(Nulls::First)),
+		order("age", true, None),
+	]);
+	assert_eq!(list.to_string(), "score DESC NULLS LAST, name NULLS FIRST, age");
+	assert_eq!(stmt.order, Some(Ordering::Order(list)));
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person ORDER BY score NULLS"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_explain_verbose() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM person EXPLAIN VERBOSE FULL"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.explain, Some(Explain(true, true)));
+	assert_eq!(Explain(true, true).to_string(), "EXPLAIN VERBOSE FULL");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM person EXPLAIN VERBOSE"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.explain, Some(Explain(false, true)));
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person EXPLAIN FULL VERBOSE"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_hints() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM a, b WITH INDEX a.ix_a, ix_c, b.ix_b PARALLEL 4"#)
+			.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.with,
+		Some(With::Index(vec![
+			IndexHint {
+				table: Some("a".to_owned()),
+				index: "ix_a".to_owned(),
+			},
+			IndexHint::from("ix_c".to_owned()),
+			IndexHint {
+				table: Some("b".to_owned()),
+				index: "ix_b".to_owned(),
+			},
+		]))
+	);
+	assert!(stmt.parallel);
+	assert_eq!(stmt.parallel_tasks, Some(4));
+	assert_eq!(stmt.to_string(), "SELECT * FROM a, b WITH INDEX a.ix_a,ix_c,b.ix_b PARALLEL 4");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM a PARALLEL"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert!(stmt.parallel);
+	assert_eq!(stmt.parallel_tasks, None);
+
+	test_parse!(parse_stmt, r#"SELECT * FROM a PARALLEL 0"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM a WITH INDEX a."#).unwrap_err();
+}
+
+#[test]
+fn parse_select_with_deleted() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM customer WITH DELETED"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.with, Some(With::Deleted));
+	assert_eq!(stmt.to_string(), "SELECT * FROM customer WITH DELETED");
+	// Only the deleted records of a SELECT statement can be included
+	test_parse!(parse_stmt, r#"UPDATE customer WITH DELETED SET active = true"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DELETE customer WITH DELETED"#).unwrap_err();
+}
+
 #[test]
 fn parse_show() {
 	let res = test_parse!(parse_stmt, r#"SHOW CHANGES FOR TABLE foo SINCE 1 LIMIT 10"#).unwrap();
@@ -2383,6 +3226,7 @@ fn parse_show() {
 		Statement::Show(ShowStatement {
 			table: Some(Table("foo".to_owned())),
 			since: ShowSince::Versionstamp(1),
+			until: None,
 			limit: Some(10)
 		})
 	);
@@ -2409,6 +3253,33 @@ fn parse_show() {
 		Statement::Show(ShowStatement {
 			table: None,
 			since: ShowSince::Timestamp(Datetime(expected_datetime)),
+			until: None,
+			limit: None
+		})
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"SHOW CHANGES FOR TABLE foo SINCE 1 UNTIL d"2012-04-23T18:25:43.0000511Z" LIMIT 10"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Show(ShowStatement {
+			table: Some(Table("foo".to_owned())),
+			since: ShowSince::Versionstamp(1),
+			until: Some(ShowSince::Timestamp(Datetime(expected_datetime))),
+			limit: Some(10)
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"SHOW CHANGES FOR DATABASE SINCE 1 UNTIL 5"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Show(ShowStatement {
+			table: None,
+			since: ShowSince::Versionstamp(1),
+			until: Some(ShowSince::Versionstamp(5)),
 			limit: None
 		})
 	)
@@ -2489,6 +3360,48 @@ fn parse_throw() {
 	assert_eq!(res, expect)
 }
 
+#[test]
+fn parse_try() {
+	let res =
+		test_parse!(parse_stmt, r"TRY { THROW { code: 1 } } CATCH ($err) { $err.code }").unwrap();
+	let expect = Statement::Trycatch(TrycatchStatement {
+		block: Block(vec![Entry::Throw(ThrowStatement {
+			error: SqlValue::Object(Object(
+				[("code".to_owned(), SqlValue::Number(Number::Int(1)))].into_iter().collect(),
+			)),
+		})]),
+		param: Some(Param(Ident("err".to_owned()))),
+		catch: Block(vec![Entry::Value(SqlValue::Idiom(Idiom(vec![
+			Part::Start(SqlValue::Param(Param(Ident("err".to_owned())))),
+			Part::Field(Ident("code".to_owned())),
+		])))]),
+	});
+	assert_eq!(res, expect);
+	assert_eq!(res.to_string(), "TRY { THROW { code: 1 }; } CATCH ($err) { $err.code }");
+
+	let res = test_parse!(parse_stmt, r"TRY { 1 } CATCH { 2 }").unwrap();
+	let Statement::Trycatch(res) = res else {
+		panic!("expected a TRY statement")
+	};
+	assert_eq!(res.param, None);
+
+	test_parse!(parse_stmt, r"TRY { 1 }").unwrap_err();
+}
+
+#[test]
+fn parse_truncate() {
+	let res = test_parse!(parse_stmt, r"TRUNCATE TABLE person").unwrap();
+	assert_eq!(
+		res,
+		Statement::Truncate(TruncateStatement {
+			name: Ident("person".to_owned()),
+		})
+	);
+	assert_eq!(res.to_string(), "TRUNCATE TABLE person");
+
+	test_parse!(parse_stmt, r"TRUNCATE person").unwrap_err();
+}
+
 #[test]
 fn parse_insert() {
 	let res = test_parse!(
@@ -2582,6 +3495,7 @@ fn parse_insert_select() {
 					cond: None,
 					split: None,
 					group: None,
+					having: None,
 					order: None,
 					limit: None,
 					start: None,
@@ -2589,6 +3503,7 @@ fn parse_insert_select() {
 					version: None,
 					timeout: None,
 					parallel: false,
+					parallel_tasks: None,
 					explain: None,
 					tempfiles: false
 				}
@@ -2610,7 +3525,9 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: SqlValue::Param(Param(Ident("param".to_owned())))
+			id: SqlValue::Param(Param(Ident("param".to_owned()))),
+			all: false,
+			table: None,
 		})
 	);
 
@@ -2618,7 +3535,43 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: SqlValue::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002")))
+			id: SqlValue::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			all: false,
+			table: None,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"KILL [u"e72bee20-f49b-11ec-b939-0242ac120002", $param]"#)
+		.unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: SqlValue::Array(Array(vec![
+				SqlValue::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+				SqlValue::Param(Param(Ident("param".to_owned()))),
+			])),
+			all: false,
+			table: None,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"KILL ALL"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: SqlValue::None,
+			all: true,
+			table: None,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"KILL ALL FOR TABLE person"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: SqlValue::None,
+			all: true,
+			table: Some(Ident("person".to_owned())),
 		})
 	);
 }
@@ -2653,15 +3606,121 @@ fn parse_live() {
 	assert_eq!(
 		stmt.fetch,
 		Some(Fetchs(vec![
-			Fetch(SqlValue::Idiom(Idiom(vec![
-				Part::Field(Ident("a".to_owned())),
-				Part::Where(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))),
-			]))),
-			Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("b".to_owned()))]))),
+			Fetch(
+				SqlValue::Idiom(Idiom(vec![
+					Part::Field(Ident("a".to_owned())),
+					Part::Where(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))),
+				])),
+				None
+			),
+			Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("b".to_owned()))])), None),
 		])),
 	)
 }
 
+#[test]
+fn parse_live_group() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT count() FROM order WHERE status = 'open' GROUP ALL"#
+	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.expr,
+		Fields(
+			vec![Field::Single {
+				expr: SqlValue::Function(Box::new(Function::Normal("count".to_owned(), vec![]))),
+				alias: None,
+			}],
+			false,
+		)
+	);
+	assert_eq!(stmt.group, Some(Groups(vec![])));
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT status, math::sum(total) AS total FROM order GROUP BY status"#
+	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.group,
+		Some(Groups(vec![Group(Idiom(vec![Part::Field(Ident("status".to_owned()))]), None)]))
+	);
+
+	test_parse!(parse_stmt, r#"LIVE SELECT math::sum(total) FROM order GROUP BY status"#)
+		.unwrap_err();
+}
+
+#[test]
+fn parse_live_snapshot() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT * FROM task WHERE done = false FETCH owner SNAPSHOT"#
+	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert!(stmt.snapshot);
+	assert_eq!(stmt.to_string(), "LIVE SELECT * FROM task WHERE done = false FETCH owner SNAPSHOT");
+	assert_eq!(
+		stmt.cond,
+		Some(Cond(SqlValue::Expression(Box::new(Expression::Binary {
+			l: SqlValue::Idiom(Idiom(vec![Part::Field(Ident("done".to_owned()))])),
+			o: Operator::Equal,
+			r: SqlValue::Bool(false),
+		}))))
+	);
+	assert_eq!(
+		stmt.fetch,
+		Some(Fetchs(vec![Fetch(
+			SqlValue::Idiom(Idiom(vec![Part::Field(Ident("owner".to_owned()))])),
+			None
+		)]))
+	);
+
+	let res = test_parse!(parse_stmt, r#"LIVE SELECT * FROM task"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert!(!stmt.snapshot);
+
+	test_parse!(parse_stmt, r#"LIVE SELECT count() FROM task GROUP ALL SNAPSHOT"#).unwrap_err();
+}
+
+#[test]
+fn parse_fetch_depth() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM person FETCH friends DEPTH 3, owner"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.fetch,
+		Some(Fetchs(vec![
+			Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("friends".to_owned()))])), Some(3)),
+			Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident("owner".to_owned()))])), None),
+		]))
+	);
+	assert_eq!(stmt.to_string(), "SELECT * FROM person FETCH friends DEPTH 3, owner");
+
+	let res =
+		test_parse!(parse_stmt, r#"LIVE SELECT * FROM person FETCH friends DEPTH 2"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.to_string(), "LIVE SELECT * FROM person FETCH friends DEPTH 2");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person FETCH friends DEPTH 0"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM person FETCH friends DEPTH"#).unwrap_err();
+}
+
 #[test]
 fn parse_option() {
 	let res = test_parse!(parse_stmt, r#"OPTION value = true"#).unwrap();
@@ -2674,6 +3733,32 @@ fn parse_option() {
 	)
 }
 
+#[test]
+fn parse_rebuild() {
+	let res = test_parse!(parse_stmt, r#"REBUILD INDEX IF EXISTS idx ON TABLE tb"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rebuild(RebuildStatement::Index(RebuildIndexStatement {
+			name: Ident("idx".to_owned()),
+			what: Ident("tb".to_owned()),
+			if_exists: true,
+			concurrently: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REBUILD INDEX idx ON tb CONCURRENTLY"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rebuild(RebuildStatement::Index(RebuildIndexStatement {
+			name: Ident("idx".to_owned()),
+			what: Ident("tb".to_owned()),
+			if_exists: false,
+			concurrently: true,
+		}))
+	);
+	assert_eq!(res.to_string(), "REBUILD INDEX idx ON tb CONCURRENTLY");
+}
+
 #[test]
 fn parse_return() {
 	let res = test_parse!(parse_stmt, r#"RETURN RETRUN FETCH RETURN"#).unwrap();
@@ -2681,7 +3766,7 @@ fn parse_return() {
 		res,
 		Statement::Output(OutputStatement {
 			what: ident_field("RETRUN"),
-			fetch: Some(Fetchs(vec![Fetch(ident_field("RETURN"))]))
+			fetch: Some(Fetchs(vec![Fetch(ident_field("RETURN"), None)]))
 		}),
 	)
 }
@@ -2723,10 +3808,24 @@ fn parse_relate() {
 			output: Some(Output::None),
 			timeout: None,
 			parallel: true,
+			zip: false,
 		}),
 	)
 }
 
+#[test]
+fn parse_relate_zip() {
+	let res = test_parse!(parse_stmt, r#"RELATE $people->bought->$products ZIP CONTENT { at: 1 }"#)
+		.unwrap();
+	let Statement::Relate(stmt) = res else {
+		panic!()
+	};
+	assert!(stmt.zip);
+	assert!(!stmt.uniq);
+	assert_eq!(stmt.kind, SqlValue::Table(Table("bought".to_owned())));
+	assert_eq!(stmt.to_string(), "RELATE $people -> bought -> $products ZIP CONTENT { at: 1 }");
+}
+
 #[test]
 fn parse_remove() {
 	let res = test_parse!(parse_stmt, r#"REMOVE NAMESPACE ns"#).unwrap();
@@ -2792,6 +3891,29 @@ fn parse_remove() {
 			name: Ident("foo".to_owned()),
 			if_exists: false,
 			expunge: false,
+			cascade: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE TABLE foo CASCADE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Table(RemoveTableStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: false,
+			expunge: false,
+			cascade: true,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE TABLE foo RESTRICT"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Table(RemoveTableStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: false,
+			expunge: false,
+			cascade: false,
 		}))
 	);
 
@@ -2816,6 +3938,18 @@ fn parse_remove() {
 			]),
 			what: Ident("bar".to_owned()),
 			if_exists: false,
+			cascade: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE FIELD foo ON bar CASCADE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Field(RemoveFieldStatement {
+			name: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+			what: Ident("bar".to_owned()),
+			if_exists: false,
+			cascade: true,
 		}))
 	);
 
@@ -2847,6 +3981,15 @@ fn parse_remove() {
 			if_exists: false,
 		}))
 	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE SCHEDULE IF EXISTS cleanup"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Schedule(RemoveScheduleStatement {
+			name: Ident("cleanup".to_owned()),
+			if_exists: true,
+		}))
+	);
 }
 
 #[test]
@@ -2873,7 +4016,11 @@ fn parse_update() {
 					})
 				]))
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned())
+			])),
+			if_version: None,
 			cond: Some(Cond(SqlValue::Bool(true))),
 			data: Some(Data::UnsetExpression(vec![
 				Idiom(vec![Part::Field(Ident("foo".to_string())), Part::Flatten]),
@@ -2887,14 +4034,29 @@ fn parse_update() {
 				]),
 				Idiom(vec![Part::Field(Ident("c".to_string())), Part::All])
 			])),
+			order: None,
+			limit: None,
 			output: Some(Output::Diff),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(true))
+			explain: Some(Explain(true, false))
 		})
 	);
 }
 
+#[test]
+fn parse_update_if_version() {
+	let res =
+		test_parse!(parse_stmt, r#"UPDATE person:1 SET name = 'Tobie' IF VERSION $v WHERE true"#)
+			.unwrap();
+	let Statement::Update(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.if_version, Some(SqlValue::Param(Param(Ident("v".to_owned())))));
+	assert_eq!(stmt.to_string(), "UPDATE person:1 SET name = 'Tobie' IF VERSION $v WHERE true");
+	test_parse!(parse_stmt, r#"UPDATE person:1 SET name = 'Tobie' IF $v"#).unwrap_err();
+}
+
 #[test]
 fn parse_upsert() {
 	let res = test_parse!(
@@ -2919,7 +4081,10 @@ fn parse_upsert() {
 					})
 				]))
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned())
+			])),
 			cond: Some(Cond(SqlValue::Bool(true))),
 			data: Some(Data::UnsetExpression(vec![
 				Idiom(vec![Part::Field(Ident("foo".to_string())), Part::Flatten]),
@@ -2936,7 +4101,7 @@ fn parse_upsert() {
 			output: Some(Output::Diff),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(false))
+			explain: Some(Explain(false, false))
 		})
 	);
 }
@@ -1,10 +1,11 @@
 use crate::{
 	sql::{
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
-		Permission, Permissions, Regex, Scoring, Script, Split, Splits, SqlValue, SqlValues, Start,
-		Statement, Strand, Subquery, Table, TableType, Tables, Thing, Timeout, Uuid, Version, With,
+		Algorithm, Array, ArrayConstraints, Base, Block, Cond, Data, Datetime, Dir, Duration,
+		Edges, Explain, Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id,
+		Ident, Idiom, Idioms, Index, IndexHint, Kind, Limit, Number, Object, Operator, Order,
+		Output, Param, Part, Permission, Permissions, Regex, Scoring, Script, Split, Splits,
+		SqlValue, SqlValues, Start, Statement, Strand, Subquery, Table, TableType, Tables, Thing,
+		Timeout, Uuid, Version, With,
 		access::AccessDuration,
 		access_type::{AccessType, JwtAccess, JwtAccessVerify, JwtAccessVerifyKey, RecordAccess},
 		block::Entry,
@@ -13,7 +14,7 @@ use crate::{
 		graph::{GraphSubject, GraphSubjects},
 		index::{Distance, MTreeParams, SearchParams, VectorType},
 		language::Language,
-		order::{OrderList, Ordering},
+		order::{Collate, OrderList, Ordering},
 		statements::{
 			BeginStatement, BreakStatement, CancelStatement, CommitStatement, ContinueStatement,
 			CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
@@ -134,12 +135,12 @@ fn statements() -> Vec<Statement> {
 		Statement::Analyze(AnalyzeStatement::Idx(Ident("a".to_string()), Ident("b".to_string()))),
 		Statement::Begin(BeginStatement),
 		Statement::Begin(BeginStatement),
-		Statement::Break(BreakStatement),
+		Statement::Break(BreakStatement::default()),
 		Statement::Cancel(CancelStatement),
 		Statement::Cancel(CancelStatement),
 		Statement::Commit(CommitStatement),
 		Statement::Commit(CommitStatement),
-		Statement::Continue(ContinueStatement),
+		Statement::Continue(ContinueStatement::default()),
 		Statement::Create(CreateStatement {
 			only: true,
 			what: SqlValues(vec![SqlValue::Table(Table("foo".to_owned()))]),
@@ -172,6 +173,7 @@ fn statements() -> Vec<Statement> {
 			comment: Some(Strand("test".to_string())),
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		})),
 		Statement::Define(DefineStatement::Namespace(DefineNamespaceStatement {
 			id: None,
@@ -179,6 +181,7 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		})),
 		Statement::Define(DefineStatement::Database(DefineDatabaseStatement {
 			id: None,
@@ -187,9 +190,11 @@ fn statements() -> Vec<Statement> {
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(60) * 10,
 				store_diff: false,
+				store_audit: false,
 			}),
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		})),
 		Statement::Define(DefineStatement::Database(DefineDatabaseStatement {
 			id: None,
@@ -198,12 +203,16 @@ fn statements() -> Vec<Statement> {
 			changefeed: None,
 			if_not_exists: false,
 			overwrite: false,
+			capabilities: None,
 		})),
 		Statement::Define(DefineStatement::Function(DefineFunctionStatement {
 			name: Ident("foo::bar".to_string()),
 			args: vec![
 				(Ident("a".to_string()), Kind::Number),
-				(Ident("b".to_string()), Kind::Array(Box::new(Kind::Bool), Some(3))),
+				(
+					Ident("b".to_string()),
+					Kind::Array(Box::new(Kind::Bool), ArrayConstraints::exact(3)),
+				),
 			],
 			block: Block(vec![Entry::Output(OutputStatement {
 				what: ident_field("a"),
@@ -214,6 +223,7 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			returns: None,
+			overloads: Vec::new(),
 		})),
 		Statement::Define(DefineStatement::Access(DefineAccessStatement {
 			name: Ident("a".to_string()),
@@ -227,6 +237,7 @@ fn statements() -> Vec<Statement> {
 						key: "foo".to_string(),
 					}),
 					issue: None,
+					keys: Vec::new(),
 				},
 				bearer: None,
 			}),
@@ -271,7 +282,10 @@ fn statements() -> Vec<Statement> {
 				),
 				what: Tables(vec![Table("bar".to_owned())]),
 				cond: None,
-				group: Some(Groups(vec![Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+				group: Some(Groups(vec![Group(
+					Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+					None,
+				)])),
 			}),
 			permissions: Permissions {
 				select: Permission::Specific(SqlValue::Expression(Box::new(
@@ -288,6 +302,7 @@ fn statements() -> Vec<Statement> {
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(1),
 				store_diff: false,
+				store_audit: false,
 			}),
 			comment: None,
 			if_not_exists: false,
@@ -297,6 +312,11 @@ fn statements() -> Vec<Statement> {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			tenant: None,
+			assert: None,
+			validate: false,
+			soft_delete: false,
+			versioned: false,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -306,6 +326,8 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			order: None,
+			guard: None,
 		})),
 		Statement::Define(DefineStatement::Field(DefineFieldStatement {
 			name: Idiom(vec![
@@ -318,7 +340,10 @@ fn statements() -> Vec<Statement> {
 			flex: true,
 			kind: Some(Kind::Option(Box::new(Kind::Either(vec![
 				Kind::Number,
-				Kind::Array(Box::new(Kind::Record(vec![Table("foo".to_owned())])), Some(10)),
+				Kind::Array(
+					Box::new(Kind::Record(vec![Table("foo".to_owned())])),
+					ArrayConstraints::exact(10),
+				),
 			])))),
 			readonly: false,
 			value: Some(SqlValue::Null),
@@ -335,6 +360,10 @@ fn statements() -> Vec<Statement> {
 			overwrite: false,
 			reference: None,
 			default_always: false,
+			expire: false,
+			created: false,
+			modified: false,
+			computed: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -363,6 +392,7 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -373,6 +403,7 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -391,6 +422,7 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
 			name: Ident("ana".to_owned()),
@@ -420,12 +452,17 @@ fn statements() -> Vec<Statement> {
 				32,
 				64,
 			))]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned()),
+			])),
 			cond: Some(Cond(SqlValue::Number(Number::Int(2)))),
+			order: None,
+			limit: None,
 			output: Some(Output::After),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Delete(DeleteStatement {
 			only: true,
@@ -441,15 +478,23 @@ fn statements() -> Vec<Statement> {
 				Part::Last,
 				Part::Where(SqlValue::Bool(true)),
 			]))]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned()),
+			])),
 			cond: Some(Cond(SqlValue::Null)),
+			order: None,
+			limit: None,
 			output: Some(Output::Null),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(60 * 60)))),
 			parallel: true,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Foreach(ForeachStatement {
+			label: None,
 			param: Param(Ident("foo".to_owned())),
+			kind: None,
+			index: None,
 			range: SqlValue::Expression(Box::new(Expression::Binary {
 				l: SqlValue::Subquery(Box::new(Subquery::Select(SelectStatement {
 					expr: Fields(
@@ -467,7 +512,7 @@ fn statements() -> Vec<Statement> {
 				o: Operator::Mul,
 				r: SqlValue::Number(Number::Int(2)),
 			})),
-			block: Block(vec![Entry::Break(BreakStatement)]),
+			block: Block(vec![Entry::Break(BreakStatement::default())]),
 		}),
 		Statement::Ifelse(IfelseStatement {
 			exprs: vec![
@@ -519,21 +564,26 @@ fn statements() -> Vec<Statement> {
 				SqlValue::Table(Table("a".to_owned())),
 				SqlValue::Number(Number::Int(1)),
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned()),
+			])),
 			cond: Some(Cond(SqlValue::Bool(true))),
 			split: Some(Splits(vec![
 				Split(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Split(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
 			group: Some(Groups(vec![
-				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
-				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
+				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]), None),
+				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))]), None),
 			])),
+			having: None,
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
-				collate: true,
+				collate: Some(Collate::Lexical),
 				numeric: true,
 				direction: true,
+				nulls: None,
 			}]))),
 			limit: Some(Limit(SqlValue::Thing(Thing {
 				tb: "a".to_owned(),
@@ -542,14 +592,16 @@ fn statements() -> Vec<Statement> {
 			start: Some(Start(SqlValue::Object(Object(
 				[("a".to_owned(), SqlValue::Bool(true))].into_iter().collect(),
 			)))),
-			fetch: Some(Fetchs(vec![Fetch(SqlValue::Idiom(Idiom(vec![Part::Field(Ident(
-				"foo".to_owned(),
-			))])))])),
+			fetch: Some(Fetchs(vec![Fetch(
+				SqlValue::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
+				None,
+			)])),
 			version: Some(Version(SqlValue::Datetime(Datetime(expected_datetime)))),
 			timeout: None,
 			parallel: false,
+			parallel_tasks: None,
 			tempfiles: false,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
@@ -559,11 +611,13 @@ fn statements() -> Vec<Statement> {
 		Statement::Show(ShowStatement {
 			table: Some(Table("foo".to_owned())),
 			since: ShowSince::Versionstamp(1),
+			until: None,
 			limit: Some(10),
 		}),
 		Statement::Show(ShowStatement {
 			table: None,
 			since: ShowSince::Timestamp(Datetime(expected_datetime)),
+			until: None,
 			limit: None,
 		}),
 		Statement::Sleep(SleepStatement {
@@ -631,10 +685,12 @@ fn statements() -> Vec<Statement> {
 		}),
 		Statement::Kill(KillStatement {
 			id: SqlValue::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			all: false,
+			table: None,
 		}),
 		Statement::Output(OutputStatement {
 			what: ident_field("RETRUN"),
-			fetch: Some(Fetchs(vec![Fetch(ident_field("RETURN"))])),
+			fetch: Some(Fetchs(vec![Fetch(ident_field("RETURN"), None)])),
 		}),
 		Statement::Relate(RelateStatement {
 			only: true,
@@ -664,6 +720,7 @@ fn statements() -> Vec<Statement> {
 			output: Some(Output::None),
 			timeout: None,
 			parallel: true,
+			zip: false,
 		}),
 		Statement::Remove(RemoveStatement::Function(RemoveFunctionStatement {
 			name: Ident("foo::bar".to_owned()),
@@ -677,6 +734,7 @@ fn statements() -> Vec<Statement> {
 			]),
 			what: Ident("bar".to_owned()),
 			if_exists: false,
+			cascade: false,
 		})),
 		Statement::Update(UpdateStatement {
 			only: true,
@@ -693,7 +751,11 @@ fn statements() -> Vec<Statement> {
 					}),
 				])),
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned()),
+			])),
+			if_version: None,
 			cond: Some(Cond(SqlValue::Bool(true))),
 			data: Some(Data::UnsetExpression(vec![
 				Idiom(vec![Part::Field(Ident("foo".to_string())), Part::Flatten]),
@@ -707,10 +769,12 @@ fn statements() -> Vec<Statement> {
 				]),
 				Idiom(vec![Part::Field(Ident("c".to_string())), Part::All]),
 			])),
+			order: None,
+			limit: None,
 			output: Some(Output::Diff),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Upsert(UpsertStatement {
 			only: true,
@@ -727,7 +791,10 @@ fn statements() -> Vec<Statement> {
 					}),
 				])),
 			]),
-			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
+			with: Some(With::Index(vec![
+				IndexHint::from("index".to_owned()),
+				IndexHint::from("index_2".to_owned()),
+			])),
 			cond: Some(Cond(SqlValue::Bool(true))),
 			data: Some(Data::UnsetExpression(vec![
 				Idiom(vec![Part::Field(Ident("foo".to_string())), Part::Flatten]),
@@ -744,7 +811,7 @@ fn statements() -> Vec<Statement> {
 			output: Some(Output::Diff),
 			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
 			parallel: true,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Value(SqlValue::Function(Box::new(crate::sql::Function::Script(
 			Script(" ((1 + 1)) ".to_owned()),
@@ -808,3 +875,23 @@ fn test_streaming() {
 		panic!("Not all statements parsed")
 	}
 }
+
+#[test]
+fn test_streaming_statement_line() {
+	let source = "INFO FOR ROOT;\n\n-- a comment\nINFO FOR NS;\nINFO\nFOR DB; INFO FOR ROOT;\n";
+	let mut statements = StatementStream::new();
+	let mut buffer = BytesMut::new();
+	let mut lines = Vec::new();
+
+	for b in source.as_bytes() {
+		if statements.parse_partial(&mut buffer).unwrap().is_some() {
+			lines.push(statements.statement_line());
+		}
+		buffer.extend_from_slice(&[*b]);
+	}
+	while statements.parse_complete(&mut buffer).unwrap().is_some() {
+		lines.push(statements.statement_line());
+	}
+
+	assert_eq!(lines, vec![1, 4, 5, 6]);
+}
@@ -263,3 +263,30 @@ fn datetime_error() {
 fn empty_string() {
 	test_parse!(parse_value_field, "").unwrap_err();
 }
+
+#[test]
+fn matches_fuzziness() {
+	for (sql, reference, fuzziness) in [
+		("title @@ 'recieve'", None, None),
+		("title @1@ 'recieve'", Some(1), None),
+		("title @~1@ 'recieve'", None, Some(1)),
+		("title @1~2@ 'recieve'", Some(1), Some(2)),
+	] {
+		let res = test_parse!(parse_value_field, sql).unwrap();
+		let SqlValue::Expression(x) = &res else {
+			panic!("expected an expression, found {res:?}");
+		};
+		let Expression::Binary {
+			o,
+			..
+		} = x.as_ref()
+		else {
+			panic!("expected a binary expression, found {x:?}");
+		};
+		assert_eq!(o, &Operator::Matches(reference, fuzziness));
+		assert_eq!(res.to_string(), sql);
+	}
+	// The fuzziness is bounded
+	test_parse!(parse_value_field, "title @1~3@ 'recieve'").unwrap_err();
+	test_parse!(parse_value_field, "title @~@ 'recieve'").unwrap_err();
+}
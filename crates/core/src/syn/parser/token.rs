@@ -30,7 +30,8 @@ impl Parser<'_> {
 				| t!("LET") | t!("SHOW")
 				| t!("SLEEP")
 				| t!("THROW")
-				| t!("UPDATE")
+				| t!("TRUNCATE")
+				| t!("TRY") | t!("UPDATE")
 				| t!("UPSERT")
 				| t!("USE")
 		)
@@ -131,6 +132,7 @@ impl Parser<'_> {
 				| t!("LET") | t!("SHOW")
 				| t!("SLEEP")
 				| t!("THROW")
+				| t!("TRUNCATE")
 				| t!("USE")
 		)
 	}
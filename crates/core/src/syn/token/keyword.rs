@@ -25,8 +25,10 @@ macro_rules! keyword {
 
 keyword! {
 	Access => "ACCESS",
+	Add => "ADD",
 	After => "AFTER",
 	Algorithm => "ALGORITHM",
+	Allow => "ALLOW",
 	All => "ALL",
 	Alter => "ALTER",
 	Always => "ALWAYS",
@@ -38,6 +40,7 @@ keyword! {
 	Ascii => "ASCII",
 	Assert => "ASSERT",
 	At => "AT",
+	Audit => "AUDIT",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
 	Backend => "BACKEND",
@@ -47,6 +50,7 @@ keyword! {
 	Begin => "BEGIN",
 	Blank => "BLANK",
 	Bucket => "BUCKET",
+	Build => "BUILD",
 	Reject => "REJECT",
 	Bm25 => "BM25",
 	Break => "BREAK",
@@ -54,21 +58,29 @@ keyword! {
 	Camel => "CAMEL",
 	Cancel => "CANCEL",
 	Cascade => "CASCADE",
+	Catch => "CATCH",
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
+	Capabilities => "CAPABILITIES",
 	Capacity => "CAPACITY",
 	Class => "CLASS",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
 	Concurrently => "CONCURRENTLY",
 	Config => "CONFIG",
+	Computed => "COMPUTED",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Create => "CREATE",
+	Created => "CREATED",
+	Cron => "CRON",
 	Database => "DATABASE",
 	Default => "DEFAULT",
 	Define => "DEFINE",
 	Delete => "DELETE",
+	Deleted => "DELETED",
+	Deny => "DENY",
+	Depth => "DEPTH",
 	Descending => "DESCENDING",
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
@@ -87,6 +99,7 @@ keyword! {
 	Enforced => "ENFORCED",
 	Exclude => "EXCLUDE",
 	Exists => "EXISTS",
+	Expire => "EXPIRE",
 	Expired => "EXPIRED",
 	Explain => "EXPLAIN",
 	Expunge => "EXPUNGE",
@@ -96,6 +109,7 @@ keyword! {
 	Field => "FIELD",
 	Fields => "FIELDS",
 	Filters => "FILTERS",
+	First => "FIRST",
 	Flexible => "FLEXIBLE",
 	For => "FOR",
 	From => "FROM",
@@ -105,6 +119,7 @@ keyword! {
 	Grant => "GRANT",
 	Graphql => "GRAPHQL",
 	Group => "GROUP",
+	Having => "HAVING",
 	Headers => "HEADERS",
 	Highlights => "HIGHLIGHTS",
 	Hnsw => "HNSW",
@@ -122,6 +137,8 @@ keyword! {
 	Key => "KEY",
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
+	Last => "LAST",
+	Length => "LENGTH",
 	Let => "LET",
 	Limit => "LIMIT",
 	Live => "LIVE",
@@ -130,17 +147,22 @@ keyword! {
 	M => "M",
 	M0 => "M0",
 	Mapper => "MAPPER",
+	Mapping => "MAPPING",
 	Middleware => "MIDDLEWARE",
 	Merge => "MERGE",
 	Model => "MODEL",
+	Modified => "MODIFIED",
 	MTree => "MTREE",
 	MTreeCache => "MTREE_CACHE",
 	Namespace => "NAMESPACE",
 	Ngram => "NGRAM",
 	No => "NO",
+	NoCase => "NOCASE",
 	NoIndex => "NOINDEX",
 	None => "NONE",
+	NoValidate => "NOVALIDATE",
 	Null => "NULL",
+	Nulls => "NULLS",
 	Numeric => "NUMERIC",
 	Omit => "OMIT",
 	On => "ON",
@@ -148,9 +170,12 @@ keyword! {
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
+	Outbox => "OUTBOX",
+	Over => "OVER",
 	Overwrite => "OVERWRITE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
+	Partition => "PARTITION",
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
@@ -168,12 +193,16 @@ keyword! {
 	Relate => "RELATE",
 	Relation => "RELATION",
 	Remove => "REMOVE",
+	Rename => "RENAME",
 	Replace => "REPLACE",
+	Restrict => "RESTRICT",
+	Resume => "RESUME",
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Schedule => "SCHEDULE",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -187,13 +216,19 @@ keyword! {
 	Signup => "SIGNUP",
 	Since => "SINCE",
 	Sleep => "SLEEP",
+	Snapshot => "SNAPSHOT",
 	Snowball => "SNOWBALL",
+	Soft => "SOFT",
 	Split => "SPLIT",
 	Start => "START",
+	Statements => "STATEMENTS",
+	Stopwords => "STOPWORDS",
 	Structure => "STRUCTURE",
+	Suspend => "SUSPEND",
 	Table => "TABLE",
 	Tables => "TABLES",
 	TempFiles => "TEMPFILES",
+	Tenant => "TENANT",
 	TermsCache => "TERMS_CACHE",
 	TermsOrder => "TERMS_ORDER",
 	Then => "THEN",
@@ -203,23 +238,32 @@ keyword! {
 	Token => "TOKEN",
 	To => "TO",
 	Transaction => "TRANSACTION",
+	Trigram => "TRIGRAM",
+	Truncate => "TRUNCATE",
+	Try => "TRY",
 	True => "true",
 	Type => "TYPE",
+	Unicode => "UNICODE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
+	Until => "UNTIL",
 	Update => "UPDATE",
 	Upsert => "UPSERT",
 	Uppercase => "UPPERCASE",
 	Url => "URL",
 	Use => "USE",
 	User => "USER",
+	Validate => "VALIDATE",
 	Value => "VALUE",
 	Values => "VALUES",
+	Verbose => "VERBOSE",
 	Version => "VERSION",
+	Versioned => "VERSIONED",
 	Vs => "VS",
 	When => "WHEN",
 	Where => "WHERE",
 	With => "WITH",
+	Zip => "ZIP",
 	AllInside => "ALLINSIDE",
 	AndKw => "ANDKW",
 	AnyInside => "ANYINSIDE",
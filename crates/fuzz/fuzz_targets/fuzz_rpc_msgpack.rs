@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use surrealdb::rpc::format::Format;
+
+fuzz_target!(|data: &[u8]| {
+	// Don't crash.
+	_ = Format::Msgpack.req(data);
+});
@@ -7,7 +7,7 @@ use std::{
 		Arc,
 		atomic::{AtomicUsize, Ordering},
 	},
-	time::SystemTime,
+	time::{Duration, SystemTime},
 };
 
 use anyhow::{Context, Result};
@@ -239,7 +239,7 @@ impl Provisioner {
 	pub async fn shutdown(mut self) -> Result<()> {
 		mem::drop(self.send);
 		while let Some(x) = self.recv.recv().await {
-			x.shutdown().await.context("Datastore failed to shutdown properly")?;
+			x.shutdown(Duration::ZERO).await.context("Datastore failed to shutdown properly")?;
 		}
 
 		if let Some(dir) = self.create_info.dir.as_ref() {
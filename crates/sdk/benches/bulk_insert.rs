@@ -0,0 +1,89 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb_core::expr::{Array, Object, Value};
+use tokio::runtime::{Builder, Runtime};
+
+const ROWS: usize = 100_000;
+
+// The number of writes in each batch, where 0 applies every write immediately
+const BATCH_SIZES: [(&str, usize); 2] = [("unbatched", 0), ("batched", 1000)];
+
+fn bench_bulk_insert(c: &mut Criterion) {
+	let mut group = c.benchmark_group("bulk_insert");
+	group.sample_size(10);
+	group.measurement_time(Duration::from_secs(30));
+	group.throughput(Throughput::Elements(ROWS as u64));
+
+	let rt = Runtime::new().unwrap();
+
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+	let vars = BTreeMap::from([("rows".to_string(), rows())]);
+
+	for backend in backends() {
+		for (name, size) in BATCH_SIZES {
+			let dbs = rt.block_on(prepare_data(&path(backend, name), size));
+			group.bench_function(format!("{backend} - {name}"), |b| {
+				b.to_async(Builder::new_multi_thread().build().unwrap())
+					.iter(|| run(&dbs, &ses, &vars))
+			});
+			rt.block_on(async { drop(dbs) });
+		}
+	}
+
+	group.finish();
+}
+
+fn backends() -> Vec<&'static str> {
+	#[allow(unused_mut)]
+	let mut backends = vec!["memory"];
+	#[cfg(feature = "kv-rocksdb")]
+	backends.push("rocksdb");
+	backends
+}
+
+fn path(backend: &str, name: &str) -> String {
+	match backend {
+		"rocksdb" => format!(
+			"rocksdb:///tmp/bench-bulk-insert-{name}-{}.db",
+			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
+		),
+		_ => backend.to_string(),
+	}
+}
+
+fn rows() -> Value {
+	let rows = (0..ROWS)
+		.map(|n| {
+			Value::Object(Object::from(BTreeMap::from([
+				("n", Value::from(n)),
+				("tag", Value::from(format!("tag-{}", n % 100))),
+			])))
+		})
+		.collect::<Vec<_>>();
+	Value::Array(Array::from(rows))
+}
+
+async fn prepare_data(path: &str, size: usize) -> Datastore {
+	let dbs = Datastore::new(path).await.unwrap().with_write_batch_size(size);
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+	let sql = "DEFINE INDEX n ON t FIELDS n; DEFINE INDEX tag ON t FIELDS tag;";
+	let res = dbs.execute(sql, &ses, None).await.unwrap();
+	for r in res {
+		r.result.unwrap();
+	}
+	dbs
+}
+
+async fn run(dbs: &Datastore, ses: &Session, vars: &BTreeMap<String, Value>) {
+	// The records have random ids, so every iteration inserts new records
+	let sql = "INSERT INTO t $rows RETURN NONE";
+	let mut r = dbs.execute(black_box(sql), ses, Some(vars.clone())).await.unwrap();
+	r.remove(0).result.unwrap();
+	black_box(r);
+}
+
+criterion_group!(benches, bench_bulk_insert);
+criterion_main!(benches);
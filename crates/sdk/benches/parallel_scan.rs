@@ -0,0 +1,65 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use surrealdb_core::expr::{Array, Object, Value};
+use tokio::runtime::{Builder, Runtime};
+
+const ROWS: usize = 2_000_000;
+
+// The number of rows which are inserted by each statement
+const CHUNK: usize = 10_000;
+
+// A filter which evaluates several expressions for every record
+const FILTER: &str = "string::starts_with(tag, 'tag-1') AND n % 7 = 3 AND string::len(name) > 5";
+
+fn bench_parallel_scan(c: &mut Criterion) {
+	let mut group = c.benchmark_group("parallel_scan");
+	group.sample_size(10);
+	group.measurement_time(Duration::from_secs(60));
+	group.throughput(Throughput::Elements(ROWS as u64));
+
+	let rt = Runtime::new().unwrap();
+	let dbs = rt.block_on(prepare_data());
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+
+	for (name, parallel) in [("sequential", ""), ("parallel", " PARALLEL")] {
+		let sql = format!("SELECT count() FROM t WHERE {FILTER} GROUP ALL{parallel}");
+		group.bench_function(name, |b| {
+			b.to_async(Builder::new_multi_thread().build().unwrap()).iter(|| run(&dbs, &ses, &sql))
+		});
+	}
+
+	rt.block_on(async { drop(dbs) });
+	group.finish();
+}
+
+async fn prepare_data() -> Datastore {
+	let dbs = Datastore::new("memory").await.unwrap();
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+	for chunk in 0..ROWS / CHUNK {
+		let rows = (chunk * CHUNK..(chunk + 1) * CHUNK)
+			.map(|n| {
+				Value::Object(Object::from(BTreeMap::from([
+					("n", Value::from(n)),
+					("tag", Value::from(format!("tag-{}", n % 100))),
+					("name", Value::from(format!("name-{n}"))),
+				])))
+			})
+			.collect::<Vec<_>>();
+		let vars = BTreeMap::from([("rows".to_string(), Value::Array(Array::from(rows)))]);
+		let mut res =
+			dbs.execute("INSERT INTO t $rows RETURN NONE", &ses, Some(vars)).await.unwrap();
+		res.remove(0).result.unwrap();
+	}
+	dbs
+}
+
+async fn run(dbs: &Datastore, ses: &Session, sql: &str) {
+	let mut r = dbs.execute(black_box(sql), ses, None).await.unwrap();
+	black_box(r.remove(0).result.unwrap());
+}
+
+criterion_group!(benches, bench_parallel_scan);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::Datastore;
+use tokio::runtime::{Builder, Runtime};
+
+const SAMPLES: usize = 10_000;
+
+fn bench_query_stats(c: &mut Criterion) {
+	let mut group = c.benchmark_group("query_stats");
+	group.sample_size(10);
+	group.measurement_time(Duration::from_secs(15));
+
+	let rt = Runtime::new().unwrap();
+
+	let dbs = rt.block_on(prepare_data());
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+
+	group.throughput(Throughput::Elements(SAMPLES as u64));
+
+	for (name, sql) in
+		[("table", "SELECT * FROM i WHERE v > 0"), ("index", "SELECT * FROM i WHERE n > 0")]
+	{
+		// The statistics are not collected by default
+		group.bench_function(format!("{name} - without stats"), |b| {
+			b.to_async(Builder::new_multi_thread().build().unwrap()).iter(|| run(&dbs, &ses, sql))
+		});
+		// The statistics are collected for each statement
+		let ses = ses.clone().with_stats(true);
+		group.bench_function(format!("{name} - with stats"), |b| {
+			b.to_async(Builder::new_multi_thread().build().unwrap()).iter(|| run(&dbs, &ses, sql))
+		});
+	}
+
+	group.finish();
+
+	rt.block_on(async { drop(dbs) });
+}
+
+async fn prepare_data() -> Datastore {
+	let dbs = Datastore::new("memory").await.unwrap();
+	let ses = Session::owner().with_ns("bench").with_db("bench");
+	let sql = format!(
+		"DEFINE INDEX n ON i FIELDS n; CREATE |i:{SAMPLES}| SET v = rand::int(1, 100), n = rand::int(1, 100) RETURN NONE"
+	);
+	let res = dbs.execute(&sql, &ses, None).await.unwrap();
+	for r in res {
+		r.result.unwrap();
+	}
+	dbs
+}
+
+async fn run(dbs: &Datastore, ses: &Session, sql: &str) {
+	let r = dbs.execute(black_box(sql), ses, None).await.unwrap();
+	black_box(r);
+}
+
+criterion_group!(benches, bench_query_stats);
+criterion_main!(benches);
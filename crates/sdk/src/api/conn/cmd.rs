@@ -95,6 +95,14 @@ pub(crate) enum Command {
 		query: Cow<'static, str>,
 		variables: CoreObject,
 	},
+	Prepare {
+		query: String,
+	},
+	ExecutePrepared {
+		txn: Option<Uuid>,
+		handle: Uuid,
+		variables: CoreObject,
+	},
 	ExportFile {
 		path: PathBuf,
 		config: Option<DbExportConfig>,
@@ -398,6 +406,27 @@ impl Command {
 					transaction: txn,
 				}
 			}
+			Command::Prepare {
+				query,
+			} => RouterRequest {
+				id,
+				method: "prepare",
+				params: Some(CoreValue::Array(vec![CoreValue::from(query)].into())),
+				transaction: None,
+			},
+			Command::ExecutePrepared {
+				txn,
+				handle,
+				variables,
+			} => {
+				let params: Vec<CoreValue> = vec![CoreValue::from(handle), variables.into()];
+				RouterRequest {
+					id,
+					method: "execute",
+					params: Some(params.into()),
+					transaction: txn,
+				}
+			}
 			Command::ExportFile {
 				..
 			}
@@ -10,10 +10,16 @@ use crate::api::opt::Endpoint;
 use async_channel::Receiver;
 use async_channel::Sender;
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
-use surrealdb_core::expr::{Value as CoreValue, from_value as from_core_value};
+use surrealdb_core::expr::{
+	Object as CoreObject, Value as CoreValue, from_value as from_core_value,
+};
+use uuid::Uuid;
 
 mod cmd;
 pub(crate) use cmd::Command;
@@ -45,6 +51,8 @@ pub struct Router {
 	pub(crate) config: Config,
 	pub(crate) last_id: AtomicI64,
 	pub(crate) features: HashSet<ExtraFeatures>,
+	/// The handles of the queries prepared on the server, by query text
+	pub(crate) prepared: Mutex<HashMap<String, Uuid>>,
 }
 
 impl Router {
@@ -52,6 +60,11 @@ impl Router {
 		self.last_id.fetch_add(1, Ordering::SeqCst)
 	}
 
+	fn prepared(&self) -> MutexGuard<'_, HashMap<String, Uuid>> {
+		// The map is never left in an inconsistent state, so a poisoned lock can be used
+		self.prepared.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
 	pub(crate) fn send(
 		&self,
 		command: Command,
@@ -172,6 +185,62 @@ impl Router {
 			self.recv_query(rx).await
 		})
 	}
+
+	/// Execute a query through a handle prepared on the server.
+	/// The query is prepared the first time it is executed, and prepared
+	/// again when the server rejects the handle, which happens after the
+	/// server evicted it, or after the connection was re-established.
+	pub(crate) fn execute_prepared(
+		&self,
+		txn: Option<Uuid>,
+		query: String,
+		variables: CoreObject,
+	) -> BoxFuture<'_, Result<Response>> {
+		Box::pin(async move {
+			let mut retried = false;
+			loop {
+				let cached = self.prepared().get(&query).copied();
+				let handle = match cached {
+					Some(handle) => handle,
+					None => {
+						let rx = self
+							.send(Command::Prepare {
+								query: query.clone(),
+							})
+							.await?;
+						let CoreValue::Uuid(handle) = self.recv(rx).await? else {
+							return Err(Error::InternalError(
+								"the prepare method did not return a uuid".to_owned(),
+							)
+							.into());
+						};
+						self.prepared().insert(query.clone(), handle.0);
+						handle.0
+					}
+				};
+				let res = self
+					.execute_query(Command::ExecutePrepared {
+						txn,
+						handle,
+						variables: variables.clone(),
+					})
+					.await;
+				match res {
+					Err(e)
+						if !retried
+							&& matches!(
+								e.downcast_ref(),
+								Some(Error::PreparedQueryNotFound(_))
+							) =>
+					{
+						self.prepared().remove(&query);
+						retried = true;
+					}
+					res => return res,
+				}
+			}
+		})
+	}
 }
 
 /// The database response sent from the router to the caller
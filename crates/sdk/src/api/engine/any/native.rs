@@ -167,6 +167,7 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "protocol-ws")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::PreparedQueries);
 						let mut endpoint = address;
 						endpoint.url = endpoint.url.join(engine::remote::ws::PATH)?;
 						#[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -210,6 +211,7 @@ impl conn::Sealed for Any {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
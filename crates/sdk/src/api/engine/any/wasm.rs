@@ -136,6 +136,7 @@ impl conn::Sealed for Any {
 					#[cfg(feature = "protocol-ws")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::PreparedQueries);
 						let mut endpoint = address;
 						endpoint.url = endpoint.url.join(engine::remote::ws::PATH)?;
 						spawn_local(engine::remote::ws::wasm::run_router(
@@ -159,6 +160,7 @@ impl conn::Sealed for Any {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
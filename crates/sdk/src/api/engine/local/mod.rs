@@ -845,6 +845,12 @@ async fn router(
 			let response = process(response);
 			Ok(DbResponse::Query(response))
 		}
+		Command::Prepare {
+			..
+		}
+		| Command::ExecutePrepared {
+			..
+		} => Err(crate::api::Error::PreparedQueriesNotSupported.into()),
 
 		#[cfg(target_family = "wasm")]
 		Command::ExportFile {
@@ -1148,9 +1154,16 @@ async fn router(
 
 			// Need to compute because certain keys might not be allowed to be set and those should
 			// be rejected by an error.
+			// The parameters are also stored in the session, for session::variables()
 			match kvs.compute(value, &*session.read().await, Some(tmp_vars)).await? {
-				CoreValue::None => vars.write().await.remove(&key),
-				v => vars.write().await.insert(key, v),
+				CoreValue::None => {
+					session.write().await.parameters.remove(&key);
+					vars.write().await.remove(&key);
+				}
+				v => {
+					session.write().await.parameters.insert(key.clone(), v.clone());
+					vars.write().await.insert(key, v);
+				}
 			};
 
 			Ok(DbResponse::Other(CoreValue::None))
@@ -1158,6 +1171,7 @@ async fn router(
 		Command::Unset {
 			key,
 		} => {
+			session.write().await.parameters.remove(&key);
 			vars.write().await.remove(&key);
 			Ok(DbResponse::Other(CoreValue::None))
 		}
@@ -1166,6 +1180,8 @@ async fn router(
 			notification_sender,
 		} => {
 			live_queries.write().await.insert(uuid, notification_sender);
+			// Send the initial state of an aggregate live query
+			kvs.live_query_snapshot(uuid).await?;
 			Ok(DbResponse::Other(CoreValue::None))
 		}
 		Command::Kill {
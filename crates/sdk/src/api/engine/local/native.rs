@@ -15,6 +15,7 @@ use std::{
 	collections::{BTreeMap, HashMap, HashSet},
 	sync::{Arc, atomic::AtomicI64},
 	task::Poll,
+	time::Duration,
 };
 use surrealdb_core::{dbs::Session, iam::Level, kvs::Datastore, options::EngineOptions};
 use tokio::sync::{RwLock, watch};
@@ -46,6 +47,7 @@ impl conn::Sealed for Db {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -106,6 +108,7 @@ pub(crate) async fn run_router(
 		.with_strict_mode(address.config.strict)
 		.with_query_timeout(address.config.query_timeout)
 		.with_transaction_timeout(address.config.transaction_timeout)
+		.with_slow_query_log(address.config.slow_query_log)
 		.with_capabilities(address.config.capabilities);
 
 	#[cfg(storage)]
@@ -193,5 +196,5 @@ pub(crate) async fn run_router(
 	// Wait for background tasks to finish
 	tasks.resolve().await.ok();
 	// Delete this node from the cluster
-	kvs.shutdown().await.ok();
+	kvs.shutdown(Duration::ZERO).await.ok();
 }
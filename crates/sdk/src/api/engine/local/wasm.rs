@@ -24,6 +24,7 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::task::Poll;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
@@ -54,6 +55,7 @@ impl conn::Sealed for Db {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -105,6 +107,7 @@ pub(crate) async fn run_router(
 		.with_strict_mode(address.config.strict)
 		.with_query_timeout(address.config.query_timeout)
 		.with_transaction_timeout(address.config.transaction_timeout)
+		.with_slow_query_log(address.config.slow_query_log)
 		.with_capabilities(address.config.capabilities);
 
 	let kvs = Arc::new(kvs);
@@ -187,5 +190,5 @@ pub(crate) async fn run_router(
 	// Wait for background tasks to finish
 	tasks.resolve().await.ok();
 	// Delete this node from the cluster
-	kvs.shutdown().await.ok();
+	kvs.shutdown(Duration::ZERO).await.ok();
 }
@@ -65,6 +65,7 @@ impl conn::Sealed for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -183,6 +183,8 @@ pub(crate) enum Data {
 	Other(CoreValue),
 	Query(Vec<dbs::QueryMethodResponse>),
 	Live(dbs::Notification),
+	/// Only sent for live queries which opted into batching, which this client never does
+	LiveBatch(Vec<dbs::Notification>),
 }
 
 type ServerResult = std::result::Result<Data, Failure>;
@@ -194,6 +196,7 @@ impl From<Failure> for Error {
 			-32602 => Self::InvalidParams(failure.message),
 			-32603 => Self::InternalError(failure.message),
 			-32700 => Self::ParseError(failure.message),
+			-32001 => Self::PreparedQueryNotFound(failure.message),
 			_ => Self::Query(failure.message),
 		}
 	}
@@ -231,7 +234,7 @@ impl DbResponse {
 				}))
 			}
 			// Live notifications don't call this method
-			Data::Live(..) => unreachable!(),
+			Data::Live(..) | Data::LiveBatch(..) => unreachable!(),
 		}
 	}
 }
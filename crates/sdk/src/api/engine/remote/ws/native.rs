@@ -131,6 +131,7 @@ impl conn::Sealed for Client {
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::PreparedQueries);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
@@ -138,6 +139,7 @@ impl conn::Sealed for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -69,6 +69,7 @@ impl conn::Sealed for Client {
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::PreparedQueries);
 
 			let waiter = watch::channel(Some(WaitFor::Connection));
 			let router = Router {
@@ -76,6 +77,7 @@ impl conn::Sealed for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -81,6 +81,17 @@ pub enum Error {
 	#[error("Invalid params: {0}")]
 	InvalidParams(String),
 
+	/// The fields of the struct passed to `Query::check_params` do not match the parameters of the query
+	#[error(
+		"The bindings do not match the parameters of the query, missing: {missing:?}, extra: {extra:?}"
+	)]
+	MismatchedParams {
+		/// The parameters which the query uses, but which are not bound
+		missing: Vec<String>,
+		/// The fields which the query does not use
+		extra: Vec<String>,
+	},
+
 	/// Internal server error
 	#[error("Internal error: {0}")]
 	InternalError(String),
@@ -255,6 +266,15 @@ pub enum Error {
 	/// The engine used does not support data versioning
 	#[error("The '{0}' engine does not support data versioning")]
 	VersionsNotSupported(String),
+
+	/// The protocol or storage engine being used does not support prepared queries
+	#[error("The protocol or storage engine does not support prepared queries")]
+	PreparedQueriesNotSupported,
+
+	/// The server no longer has the prepared query, because it was evicted
+	/// from its cache or prepared on a previous connection
+	#[error("{0}")]
+	PreparedQueryNotFound(String),
 }
 
 impl serde::ser::Error for Error {
@@ -20,7 +20,7 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
-use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
+use surrealdb_core::kvs::export::{Config as DbExportConfig, ExportFormat, TableConfig};
 
 /// A database export future
 #[derive(Debug)]
@@ -149,6 +149,69 @@ where
 		}
 		self
 	}
+
+	/// The format to export the database in
+	///
+	/// A binary export can be imported much faster than a SurrealQL export,
+	/// but does not support exporting versions:
+	/// ```
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// # let target = ();
+	/// use surrealdb::kvs::export::ExportFormat;
+	/// db.export(target).with_config().format(ExportFormat::Binary);
+	/// ```
+	pub fn format(mut self, format: ExportFormat) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
+
+	/// Whether to compress a binary export with zstd
+	pub fn compression(mut self, compression: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.compression = compression;
+		}
+		self
+	}
+
+	/// Whether to export the definitions of the database
+	///
+	/// The definitions of exported tables, and the analyzers which they use,
+	/// are still exported alongside their records, so that the export can be
+	/// imported into an empty database.
+	pub fn definitions(mut self, definitions: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.definitions = definitions;
+		}
+		self
+	}
+
+	/// Whether to export the changefeed clauses of table definitions
+	pub fn changefeeds(mut self, changefeeds: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.changefeeds = changefeeds;
+		}
+		self
+	}
+
+	/// Only export the records of a table which match a `WHERE` condition
+	///
+	/// The condition is parsed straight away, and returns an error if it is invalid:
+	/// ```
+	/// # fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// # let target = ();
+	/// db.export(target).with_config().tables(vec!["user"]).condition("user", "tenant = 'acme'")?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn condition(mut self, table: &str, cond: &str) -> Result<Self> {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.condition(table, cond)?;
+		}
+		Ok(self)
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -654,6 +654,7 @@ where
 			txn: None,
 			inner: result.map(|x| x.0),
 			client: Cow::Borrowed(self),
+			prepared: false,
 		}
 	}
 
@@ -18,7 +18,7 @@ use futures::future::Either;
 use futures::stream::SelectAll;
 use indexmap::IndexMap;
 use serde::Serialize;
-use serde::de::DeserializeOwned;
+use serde::de::{self, DeserializeOwned, Visitor};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::future::IntoFuture;
@@ -37,6 +37,7 @@ pub struct Query<'r, C: Connection> {
 	pub(crate) txn: Option<Uuid>,
 	pub(crate) client: Cow<'r, Surreal<C>>,
 	pub(crate) inner: Result<ValidQuery>,
+	pub(crate) prepared: bool,
 }
 
 impl<C> WithTransaction for Query<'_, C>
@@ -80,6 +81,7 @@ where
 				bindings,
 				register_live_queries,
 			}),
+			prepared: false,
 		}
 	}
 
@@ -92,11 +94,13 @@ where
 				txn: self.txn,
 				client: self.client,
 				inner: f(x),
+				prepared: self.prepared,
 			},
 			x => Query {
 				txn: self.txn,
 				client: self.client,
 				inner: x,
+				prepared: self.prepared,
 			},
 		}
 	}
@@ -107,6 +111,7 @@ where
 			txn: self.txn,
 			client: Cow::Owned(self.client.into_owned()),
 			inner: self.inner,
+			prepared: self.prepared,
 		}
 	}
 }
@@ -173,13 +178,19 @@ where
 					let mut query = sql::Query::default();
 					query.0.0 = query_statements;
 
-					let mut response = router
-						.execute_query(Command::Query {
-							txn: self.txn,
-							query,
-							variables: bindings,
-						})
-						.await?;
+					let mut response = if self.prepared
+						&& router.features.contains(&ExtraFeatures::PreparedQueries)
+					{
+						router.execute_prepared(self.txn, query.to_string(), bindings).await?
+					} else {
+						router
+							.execute_query(Command::Query {
+								txn: self.txn,
+								query,
+								variables: bindings,
+							})
+							.await?
+					};
 
 					for idx in query_indicies {
 						let Some((_, result)) = response.results.get(&idx) else {
@@ -270,6 +281,32 @@ where
 		WithStats(self)
 	}
 
+	/// Executes the query through a handle prepared on the server
+	///
+	/// The server parses the query once, and only the variables are sent
+	/// when the same query is executed again on the connection. This is
+	/// ignored by the engines which do not support prepared queries.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+	/// for age in [18, 21, 30] {
+	///     let response = db.query("SELECT * FROM user WHERE age > $age")
+	///         .bind(("age", age))
+	///         .prepared()
+	///         .await?;
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn prepared(mut self) -> Self {
+		self.prepared = true;
+		self
+	}
+
 	/// Binds a parameter or parameters to a query
 	///
 	/// # Examples
@@ -349,6 +386,110 @@ where
 			Ok(valid)
 		})
 	}
+
+	/// Checks that the fields of a struct match the parameters of the query
+	///
+	/// The query fails with [`Error::MismatchedParams`] if it uses a parameter
+	/// which is neither a field of the struct nor already bound, or if the
+	/// struct has a field which the query does not use. Parameters which the
+	/// query defines itself, such as with `LET`, and those which the database
+	/// provides, such as `$auth`, are not expected to be bound. The field names
+	/// are read from the `Deserialize` implementation of the struct, so they
+	/// follow its `#[serde(rename)]` attributes.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde::{Deserialize, Serialize};
+	///
+	/// #[derive(Serialize, Deserialize)]
+	/// struct Params {
+	///     age: u32,
+	///     name: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db.query("SELECT * FROM user WHERE age > $age AND name = $name")
+	///     .check_params::<Params>()
+	///     .bind(Params {
+	///         age: 18,
+	///         name: "John Doe".to_owned(),
+	///     })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn check_params<T: DeserializeOwned>(self) -> Self {
+		self.map_valid(move |valid| {
+			let (used, bindings) = match &valid {
+				ValidQuery::Raw {
+					query,
+					bindings,
+				} => (surrealdb_core::syn::parse(query)?.params()?, bindings),
+				ValidQuery::Normal {
+					query,
+					bindings,
+					..
+				} => (sql::Query::from(query.clone()).params()?, bindings),
+			};
+			let fields = field_names::<T>()?;
+			let missing: Vec<String> = used
+				.iter()
+				.filter(|p| !fields.contains(&p.as_str()) && !bindings.contains_key(p.as_str()))
+				.cloned()
+				.collect();
+			let extra: Vec<String> =
+				fields.iter().filter(|f| !used.contains(**f)).map(|f| f.to_string()).collect();
+			if !missing.is_empty() || !extra.is_empty() {
+				return Err(Error::MismatchedParams {
+					missing,
+					extra,
+				}
+				.into());
+			}
+			Ok(valid)
+		})
+	}
+}
+
+/// Returns the names of the fields of a struct, as they are deserialized
+fn field_names<T: DeserializeOwned>() -> Result<&'static [&'static str]> {
+	let mut fields = None;
+	// The deserializer records the fields, and then stops the deserialization
+	T::deserialize(FieldNames(&mut fields)).ok();
+	fields.ok_or_else(|| {
+		let name = std::any::type_name::<T>();
+		Error::InvalidParams(format!("`{name}` is not a struct with named fields")).into()
+	})
+}
+
+/// A deserializer which records the fields of the struct which is deserialized
+struct FieldNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+	type Error = de::value::Error;
+
+	fn deserialize_any<V: Visitor<'de>>(self, _: V) -> std::result::Result<V::Value, Self::Error> {
+		Err(de::Error::custom("expected a struct"))
+	}
+
+	fn deserialize_struct<V: Visitor<'de>>(
+		self,
+		_: &'static str,
+		fields: &'static [&'static str],
+		_: V,
+	) -> std::result::Result<V::Value, Self::Error> {
+		*self.0 = Some(fields);
+		Err(de::Error::custom("the fields were recorded"))
+	}
+
+	serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+		option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+		ignored_any
+	}
 }
 
 pub(crate) type QueryResult = Result<CoreValue>;
@@ -109,6 +109,8 @@ async fn api() {
 		.query(CommitStatement::default())
 		.await
 		.unwrap();
+	let _: QueryResponse =
+		DB.query("SELECT * FROM user WHERE age > $age").bind(("age", 18)).prepared().await.unwrap();
 
 	// create
 	let _: Option<User> = DB.create(USER).await.unwrap();
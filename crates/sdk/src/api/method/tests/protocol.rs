@@ -54,11 +54,13 @@ impl conn::Sealed for Client {
 			let (route_tx, route_rx) = async_channel::bounded(capacity);
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
+			features.insert(ExtraFeatures::PreparedQueries);
 			let router = Router {
 				features,
 				sender: route_tx,
 				config: address.config,
 				last_id: AtomicI64::new(0),
+				prepared: Default::default(),
 			};
 			server::mock(route_rx);
 			Ok((OnceLock::with_value(router), watch::channel(None)).into())
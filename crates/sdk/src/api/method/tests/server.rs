@@ -29,6 +29,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 					..
 				} => Ok(DbResponse::Other("c6c0e36c-e2cf-42cb-b2d5-75415249b261".to_owned().into())),
 				Command::Version => Ok(DbResponse::Other("1.0.0".into())),
+				Command::Prepare {
+					..
+				} => Ok(DbResponse::Other(uuid::Uuid::nil().into())),
 				Command::Use {
 					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
@@ -47,6 +50,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				| Command::RawQuery {
 					..
 				}
+				| Command::ExecutePrepared {
+					..
+				}
 				| Command::Patch {
 					..
 				}
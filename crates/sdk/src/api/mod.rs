@@ -259,6 +259,7 @@ where
 pub(crate) enum ExtraFeatures {
 	Backup,
 	LiveQueries,
+	PreparedQueries,
 }
 
 #[derive(Debug)]
@@ -1,11 +1,12 @@
 //! The capabilities that can be enabled for a database instance
 
-use std::{collections::HashSet, mem};
+use std::{collections::HashSet, mem, time::Duration};
 
 use surrealdb_core::dbs::capabilities::{
 	Capabilities as CoreCapabilities, ExperimentalTarget, FuncTarget, ParseFuncTargetError,
 	ParseNetTargetError, Targets,
 };
+use surrealdb_core::iam::throttle::AuthThrottleLimits;
 
 /// A list of features that are still experimental
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -180,6 +181,72 @@ impl Capabilities {
 		}
 	}
 
+	/// Set the maximum duration that a single sleep can run for, for users other than root users.
+	pub fn with_max_sleep(self, limit: Option<Duration>) -> Self {
+		Self {
+			cap: self.cap.with_max_sleep(limit),
+		}
+	}
+
+	/// Set the maximum total duration that a single query can sleep for, for users other than
+	/// root users.
+	pub fn with_max_query_sleep(self, limit: Option<Duration>) -> Self {
+		Self {
+			cap: self.cap.with_max_query_sleep(limit),
+		}
+	}
+
+	/// Set the estimated number of bytes which a single statement can hold in its intermediate
+	/// results, such as the rows which are grouped or sorted, before the statement fails.
+	pub fn with_max_query_memory(self, limit: Option<u64>) -> Self {
+		Self {
+			cap: self.cap.with_max_query_memory(limit),
+		}
+	}
+
+	/// Set the estimated number of bytes which a single statement can hold in its intermediate
+	/// results in the specified namespace, overriding the limit for all namespaces.
+	pub fn with_namespace_max_query_memory(self, ns: impl Into<String>, limit: u64) -> Self {
+		Self {
+			cap: self.cap.with_namespace_max_query_memory(ns, limit),
+		}
+	}
+
+	/// Set the estimated number of bytes which an ORDER BY clause sorts in memory. Larger
+	/// results are sorted in runs, which are written to the temporary directory and merged.
+	pub fn with_sort_memory(self, limit: u64) -> Self {
+		Self {
+			cap: self.cap.with_sort_memory(limit),
+		}
+	}
+
+	/// Set the number of bytes which an ORDER BY clause can write to the temporary directory,
+	/// before the statement fails.
+	pub fn with_max_sort_spill(self, limit: Option<u64>) -> Self {
+		Self {
+			cap: self.cap.with_max_sort_spill(limit),
+		}
+	}
+
+	/// Set the number of entries which are kept in the audit log of definition changes of each
+	/// namespace, after which the oldest entries are removed. A size of 0 disables the audit log.
+	pub fn with_audit_log_size(self, size: usize) -> Self {
+		Self {
+			cap: self.cap.with_audit_log_size(size),
+		}
+	}
+
+	/// Set the number of failed authentication attempts allowed for the same user and client
+	/// within a sliding window, after which further attempts are rejected.
+	pub fn with_auth_throttle(self, attempts: u32, window: Duration) -> Self {
+		Self {
+			cap: self.cap.with_auth_throttle(Some(AuthThrottleLimits {
+				attempts,
+				window,
+			})),
+		}
+	}
+
 	/// Set the allow list to allow all functions
 	pub fn allow_all_functions(&mut self) -> &mut Self {
 		*self.cap.allowed_functions_mut() = Targets::All;
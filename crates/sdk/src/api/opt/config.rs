@@ -2,7 +2,10 @@ use crate::opt::capabilities::Capabilities;
 #[cfg(storage)]
 use std::path::PathBuf;
 use std::time::Duration;
-use surrealdb_core::{dbs::Capabilities as CoreCapabilities, iam::Level};
+use surrealdb_core::{
+	dbs::{Capabilities as CoreCapabilities, SlowQueryLog},
+	iam::Level,
+};
 
 /// Configuration for server connection, including: strictness, notifications, query_timeout, transaction_timeout
 #[derive(Debug, Clone, Default)]
@@ -11,6 +14,7 @@ pub struct Config {
 	pub(crate) ast_payload: bool,
 	pub(crate) query_timeout: Option<Duration>,
 	pub(crate) transaction_timeout: Option<Duration>,
+	pub(crate) slow_query_log: Option<SlowQueryLog>,
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	pub(crate) tls_config: Option<super::Tls>,
 	// Only used by the local engines
@@ -69,6 +73,12 @@ impl Config {
 		self
 	}
 
+	/// Report the statements which run for longer than a threshold
+	pub fn slow_query_log(mut self, log: impl Into<Option<SlowQueryLog>>) -> Self {
+		self.slow_query_log = log.into();
+		self
+	}
+
 	/// Set the default user
 	pub fn user(mut self, user: crate::opt::auth::Root<'_>) -> Self {
 		self.auth = Level::Root;
@@ -1,7 +1,9 @@
 use crate::{Result, error::Api as ApiError};
 use chrono::{DateTime, Utc};
 use revision::revisioned;
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde::de::{self, DeserializeOwned, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
 	cmp::{Ordering, PartialEq, PartialOrd},
 	fmt,
@@ -14,13 +16,14 @@ use surrealdb_core::{
 		Array as CoreArray, Datetime as CoreDatetime, Id as CoreId, Number as CoreNumber,
 		Thing as CoreThing, Value as CoreValue,
 	},
-	sql::SqlValue as CoreSqlValue,
+	sql::{SqlValue as CoreSqlValue, Thing as CoreSqlThing},
 	syn,
 };
 use uuid::Uuid;
 
 mod obj;
 pub use obj::{IntoIter, Iter, IterMut, Object};
+pub use surrealdb_core::expr::DeserializeError;
 
 pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T> {
 	surrealdb_core::expr::from_value(value.0)
@@ -260,13 +263,28 @@ transparent_wrapper!(
 	///
 	/// Record id's consist of a table name and a key.
 	/// For example the record id `user:tkwse1j5o0anqjxonvzx` has the table `user` and the key `tkwse1j5o0anqjxonvzx`.
+	///
+	/// Record ids can be compared, sorted, and used as the keys of maps. They are
+	/// parsed from and displayed as SurrealQL, so that `person:⟨tobie haitink⟩`
+	/// keeps its escaping. Human-readable formats, such as JSON, serialize record
+	/// ids in their extended JSON form, `{ "$record": "person:tobie" }`.
 	#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 	pub struct RecordId(CoreThing)
 );
-impl_serialize_wrapper!(RecordId);
+
+/// The key of the extended JSON form of a record id
+const RECORD: &str = "$record";
 
 impl RecordId {
-	pub fn from_table_key<S, K>(table: S, key: K) -> Self
+	/// Creates a record id from a table name and a key
+	///
+	/// ```
+	/// use surrealdb::RecordId;
+	///
+	/// let id = RecordId::new("person", "tobie");
+	/// assert_eq!(id.to_string(), "person:tobie");
+	/// ```
+	pub fn new<S, K>(table: S, key: K) -> Self
 	where
 		S: Into<String>,
 		K: Into<RecordIdKey>,
@@ -276,6 +294,29 @@ impl RecordId {
 		Self(CoreThing::from((tb, key.0)))
 	}
 
+	pub fn from_table_key<S, K>(table: S, key: K) -> Self
+	where
+		S: Into<String>,
+		K: Into<RecordIdKey>,
+	{
+		Self::new(table, key)
+	}
+
+	/// Creates a record id with a UUID key, such as `person:u'0190...'`
+	pub fn from_uuid<S: Into<String>>(table: S, key: Uuid) -> Self {
+		Self::new(table, key)
+	}
+
+	/// Creates a record id with a number key, such as `person:1`
+	pub fn from_num<S: Into<String>>(table: S, key: i64) -> Self {
+		Self::new(table, key)
+	}
+
+	/// Creates a record id with an array key, such as `temperature:['London', 2024]`
+	pub fn from_array<S: Into<String>>(table: S, key: Vec<Value>) -> Self {
+		Self::new(table, key)
+	}
+
 	pub fn table(&self) -> &str {
 		&self.0.tb
 	}
@@ -303,6 +344,117 @@ where
 	}
 }
 
+impl From<CoreThing> for RecordId {
+	fn from(thing: CoreThing) -> Self {
+		Self(thing)
+	}
+}
+
+impl From<RecordId> for CoreThing {
+	fn from(id: RecordId) -> Self {
+		id.0
+	}
+}
+
+impl From<CoreSqlThing> for RecordId {
+	fn from(thing: CoreSqlThing) -> Self {
+		Self(thing.into())
+	}
+}
+
+impl From<RecordId> for CoreSqlThing {
+	fn from(id: RecordId) -> Self {
+		id.0.into()
+	}
+}
+
+impl ::revision::Revisioned for RecordId {
+	fn revision() -> u16 {
+		CoreValue::revision()
+	}
+
+	fn serialize_revisioned<W: std::io::Write>(
+		&self,
+		w: &mut W,
+	) -> std::result::Result<(), revision::Error> {
+		self.0.serialize_revisioned(w)
+	}
+
+	fn deserialize_revisioned<R: std::io::Read>(
+		r: &mut R,
+	) -> std::result::Result<Self, revision::Error> {
+		::revision::Revisioned::deserialize_revisioned(r).map(Self::from_inner)
+	}
+}
+
+impl Serialize for RecordId {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		if serializer.is_human_readable() {
+			let mut map = serializer.serialize_map(Some(1))?;
+			map.serialize_entry(RECORD, &self.0.to_string())?;
+			map.end()
+		} else {
+			self.0.serialize(serializer)
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for RecordId {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_struct("RecordId", &["tb", "id"], RecordIdVisitor)
+		} else {
+			CoreThing::deserialize(deserializer).map(Self)
+		}
+	}
+}
+
+/// Deserializes the extended JSON form of a record id, along with the
+/// table and key fields which record ids were serialized as before
+struct RecordIdVisitor;
+
+impl<'de> Visitor<'de> for RecordIdVisitor {
+	type Value = RecordId;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a record id")
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<RecordId, E> {
+		v.parse().map_err(E::custom)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<RecordId, A::Error> {
+		let tb: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		let id: CoreId = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+		Ok(RecordId(CoreThing::from((tb, id))))
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<RecordId, A::Error> {
+		let mut tb: Option<String> = None;
+		let mut id: Option<CoreId> = None;
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				RECORD => return self.visit_str(&map.next_value::<String>()?),
+				"tb" => tb = Some(map.next_value()?),
+				"id" => id = Some(map.next_value()?),
+				_ => return Err(de::Error::unknown_field(&key, &[RECORD, "tb", "id"])),
+			}
+		}
+		match (tb, id) {
+			(Some(tb), Some(id)) => Ok(RecordId(CoreThing::from((tb, id)))),
+			(None, _) => Err(de::Error::missing_field("tb")),
+			(_, None) => Err(de::Error::missing_field("id")),
+		}
+	}
+}
+
 transparent_wrapper!(
 	/// The number type of surrealql.
 	/// Can contain either a 64 bit float, 64 bit integer or a decimal.
@@ -568,6 +720,10 @@ pub enum Action {
 	Create,
 	Update,
 	Delete,
+	/// All records in the table were removed by `TRUNCATE TABLE`
+	Truncate,
+	/// A record which matched the live query when it was started with `SNAPSHOT`
+	Snapshot,
 }
 
 impl Action {
@@ -577,6 +733,8 @@ impl Action {
 			CoreAction::Create => Self::Create,
 			CoreAction::Update => Self::Update,
 			CoreAction::Delete => Self::Delete,
+			CoreAction::Truncate => Self::Truncate,
+			CoreAction::Snapshot => Self::Snapshot,
 			_ => panic!("unimplemented variant of action"),
 		}
 	}
@@ -594,3 +752,96 @@ pub struct Notification<R> {
 	pub action: Action,
 	pub data: R,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	#[test]
+	fn record_ids_are_constructed_from_keys() {
+		let uuid = Uuid::nil();
+		assert_eq!(RecordId::new("person", "tobie").to_string(), "person:tobie");
+		assert_eq!(RecordId::from_num("person", 1).to_string(), "person:1");
+		assert_eq!(
+			RecordId::from_uuid("person", uuid).to_string(),
+			"person:u'00000000-0000-0000-0000-000000000000'"
+		);
+		let key = vec![
+			Value::from_inner(CoreValue::from("London")),
+			Value::from_inner(CoreValue::from(2024)),
+		];
+		assert_eq!(
+			RecordId::from_array("temperature", key).to_string(),
+			"temperature:['London', 2024]"
+		);
+		assert_eq!(RecordId::new("person", "tobie"), RecordId::from(("person", "tobie")));
+		assert_ne!(RecordId::new("person", "1"), RecordId::from_num("person", 1));
+	}
+
+	#[test]
+	fn record_ids_are_parsed_with_escaping() {
+		let id = RecordId::new("person", "tobie haitink");
+		assert_eq!(id.to_string(), "person:⟨tobie haitink⟩");
+		assert_eq!(id.to_string().parse::<RecordId>().unwrap(), id);
+		let id: RecordId = "person:⟨1⟩".parse().unwrap();
+		assert_eq!(id.key(), &RecordIdKey::from("1"));
+		"person".parse::<RecordId>().unwrap_err();
+	}
+
+	#[test]
+	fn record_ids_are_map_keys() {
+		let mut ages = HashMap::new();
+		ages.insert(RecordId::new("person", "tobie"), 30);
+		ages.insert(RecordId::from_num("person", 1), 40);
+		assert_eq!(ages[&"person:tobie".parse::<RecordId>().unwrap()], 30);
+		let mut ids = vec![RecordId::from_num("person", 2), RecordId::from_num("person", 1)];
+		ids.sort();
+		assert_eq!(ids, vec![RecordId::from_num("person", 1), RecordId::from_num("person", 2)]);
+	}
+
+	#[test]
+	fn record_ids_convert_to_and_from_things() {
+		let id = RecordId::new("person", "tobie");
+		let thing = CoreThing::from(id.clone());
+		assert_eq!(thing.to_string(), "person:tobie");
+		assert_eq!(RecordId::from(thing), id);
+		let thing = CoreSqlThing::from(id.clone());
+		assert_eq!(RecordId::from(thing), id);
+	}
+
+	#[test]
+	fn record_ids_are_serialized_as_extended_json() {
+		let id = RecordId::new("person", "tobie haitink");
+		let json = serde_json::to_value(&id).unwrap();
+		assert_eq!(json, serde_json::json!({ "$record": "person:⟨tobie haitink⟩" }));
+		assert_eq!(serde_json::from_value::<RecordId>(json).unwrap(), id);
+		// Record ids serialized as their table and key are still accepted
+		let json = serde_json::json!({ "tb": "person", "id": { "String": "tobie haitink" } });
+		assert_eq!(serde_json::from_value::<RecordId>(json).unwrap(), id);
+		serde_json::from_value::<RecordId>(serde_json::json!({ "$record": "person" })).unwrap_err();
+	}
+
+	#[test]
+	fn record_ids_convert_to_and_from_values() {
+		#[derive(Debug, PartialEq, Serialize, Deserialize)]
+		struct Person {
+			id: RecordId,
+			friends: Vec<RecordId>,
+		}
+		let person = Person {
+			id: RecordId::new("person", "tobie"),
+			friends: vec![RecordId::from_num("person", 1)],
+		};
+		let value = to_value(person).unwrap();
+		assert_eq!(
+			value.get("id").clone().into_inner(),
+			CoreValue::Thing("person:tobie".parse().unwrap())
+		);
+		let person: Person = from_value(value).unwrap();
+		assert_eq!(person.friends, vec![RecordId::from_num("person", 1)]);
+		// Records can be read back from their extended JSON form
+		let value: Value = r#"{ id: { "$record": "person:tobie" }, friends: [] }"#.parse().unwrap();
+		assert_eq!(from_value::<Person>(value).unwrap().id, RecordId::new("person", "tobie"));
+	}
+}
@@ -196,6 +196,141 @@ relate person:`a`->`friends2\`;\nDEFINE USER IF NOT EXISTS pwned ON ROOT PASSWOR
 	assert_eq!(export_text, export_text_2);
 }
 
+pub async fn export_import_binary(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records, with an index and graph edges
+	db.query(
+		"
+		DEFINE TABLE user SCHEMAFULL;
+		DEFINE FIELD name ON user TYPE string;
+		DEFINE INDEX name ON user FIELDS name UNIQUE;
+		FOR $i IN 0..10 { CREATE type::thing('user', $i) SET name = 'User ' + <string> $i };
+		RELATE user:0->knows->user:1;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("export.bin");
+
+	// Export with compression, and import into a new database
+	db.export(&file)
+		.with_config()
+		.format(surrealdb::kvs::export::ExportFormat::Binary)
+		.compression(true)
+		.await
+		.unwrap();
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	db.import(&file).await.unwrap();
+
+	// Verify that the records, index entries, and edges exist post-import
+	let mut response = db
+		.query("SELECT VALUE name FROM user WHERE name = 'User 5'")
+		.query("SELECT VALUE ->knows->user.name FROM ONLY user:0")
+		.query("CREATE user SET name = 'User 5'")
+		.await
+		.unwrap();
+	let name: Option<String> = response.take(0).unwrap();
+	assert_eq!(name.as_deref(), Some("User 5"));
+	let friends: Vec<String> = response.take(1).unwrap();
+	assert_eq!(friends, vec!["User 1".to_owned()]);
+	// The unique index should reject duplicates
+	response.take::<Value>(2).unwrap_err();
+}
+
+pub async fn export_filtered(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records for two tenants, which link to another table
+	db.query(
+		"
+		DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name };
+		DEFINE ANALYZER simple TOKENIZERS blank;
+		DEFINE TABLE post SCHEMAFULL CHANGEFEED 1d;
+		DEFINE FIELD tenant ON post TYPE string;
+		DEFINE FIELD author ON post TYPE record<user>;
+		DEFINE INDEX body ON post FIELDS tenant SEARCH ANALYZER simple;
+		CREATE user:one, user:two;
+		CREATE post:one SET tenant = 'acme', author = user:one;
+		CREATE post:two SET tenant = 'other', author = user:two;
+		CREATE post:three SET tenant = 'acme', author = user:two;
+		",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("export.surql");
+
+	// Invalid conditions are rejected before exporting
+	db.export(&file).with_config().condition("post", "tenant = ").unwrap_err();
+
+	// Export the records of one tenant, without any other definitions
+	db.export(&file)
+		.with_config()
+		.tables(vec!["post"])
+		.definitions(false)
+		.changefeeds(false)
+		.condition("post", "tenant = 'acme'")
+		.unwrap()
+		.await
+		.unwrap();
+	let export = std::fs::read_to_string(&file).unwrap();
+	assert!(!export.contains("DEFINE FUNCTION"), "{export}");
+	assert!(!export.contains("CHANGEFEED"), "{export}");
+	assert!(export.contains("DEFINE ANALYZER simple"), "{export}");
+	assert!(export.contains("DEFINE TABLE post"), "{export}");
+	assert!(
+		export.contains("-- Records in table 'post' link to table 'user', which was not exported"),
+		"{export}"
+	);
+
+	// The export can be imported into an empty database
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	db.import(&file).await.unwrap();
+	let mut response = db.query("SELECT VALUE record::id(id) FROM post ORDER BY id").await.unwrap();
+	let ids: Vec<String> = response.take(0).unwrap();
+	assert_eq!(ids, vec!["one".to_owned(), "three".to_owned()]);
+}
+
+pub async fn import_binary_newer_version(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	drop(permit);
+
+	let dir = temp_dir::TempDir::new().unwrap();
+	let file = dir.path().join("export.bin");
+
+	// Write a header with a newer version of the format
+	let mut bytes = b"SURREALDB-EXPORT".to_vec();
+	bytes.extend_from_slice(&u16::MAX.to_be_bytes());
+	bytes.push(0);
+	bytes.extend_from_slice(&0u64.to_be_bytes());
+	std::fs::write(&file, bytes).unwrap();
+
+	let error = db.import(&file).await.unwrap_err().to_string();
+	assert!(error.contains("uses format version 65535"), "{error}");
+}
+
 define_include_tests!(backup => {
 	#[tokio::test]
 	export_import,
@@ -209,4 +344,13 @@ define_include_tests!(backup => {
 
 	#[tokio::test]
 	export_escaped_table_names,
+
+	#[tokio::test]
+	export_import_binary,
+
+	#[tokio::test]
+	import_binary_newer_version,
+
+	#[tokio::test]
+	export_filtered,
 });
@@ -451,6 +451,54 @@ pub async fn query_binds(new_db: impl CreateDb) {
 	assert_eq!(record.name, "John Doe");
 }
 
+pub async fn query_check_params(new_db: impl CreateDb) {
+	#[derive(Serialize, Deserialize)]
+	struct Params {
+		name: String,
+		#[serde(rename = "min")]
+		minimum: i64,
+	}
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let params = || Params {
+		name: "John Doe".to_owned(),
+		minimum: 18,
+	};
+	let sql = "LET $age = $min + 1; CREATE user:john SET name = $name, age = $age";
+	let mut response = db.query(sql).check_params::<Params>().bind(params()).await.unwrap();
+	let Some(record): Option<RecordName> = response.take(1).unwrap() else {
+		panic!("query returned no record");
+	};
+	assert_eq!(record.name, "John Doe");
+	// Parameters bound separately are not missing
+	db.query("SELECT * FROM user WHERE name = $name AND age > $min AND $flag")
+		.bind(("flag", true))
+		.check_params::<Params>()
+		.bind(params())
+		.await
+		.unwrap();
+	// Typos in the parameter names fail before the query runs
+	let error = db
+		.query("SELECT * FROM user WHERE name = $nmae AND age > $min")
+		.check_params::<Params>()
+		.bind(params())
+		.await
+		.unwrap_err();
+	match error.downcast_ref() {
+		Some(ApiError::MismatchedParams {
+			missing,
+			extra,
+		}) => {
+			assert_eq!(missing, &["nmae"]);
+			assert_eq!(extra, &["name"]);
+		}
+		_ => panic!("unexpected error: {error}"),
+	}
+	// Only structs with named fields can be checked
+	db.query("RETURN $value").check_params::<i64>().await.unwrap_err();
+}
+
 pub async fn query_with_stats(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -1472,10 +1520,16 @@ pub async fn changefeed(new_db: impl CreateDb) {
 	let mut response = db.query(sql).await.unwrap();
 	drop(permit);
 	let v: Value = response.take(0).unwrap();
-	let CoreValue::Array(array) = v.into_inner() else {
+	let CoreValue::Object(envelope) = v.into_inner() else {
+		panic!()
+	};
+	let Some(CoreValue::Array(array)) = envelope.get("changes").cloned() else {
 		panic!()
 	};
 	assert_eq!(array.len(), 5);
+	let Some(CoreValue::Number(_)) = envelope.get("versionstamp") else {
+		panic!()
+	};
 	// DEFINE TABLE
 	let a = array.first().unwrap();
 	let CoreValue::Object(a) = a.clone() else {
@@ -1749,6 +1803,8 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	query_binds,
 	#[test_log::test(tokio::test)]
+	query_check_params,
+	#[test_log::test(tokio::test)]
 	query_with_stats,
 	#[test_log::test(tokio::test)]
 	query_chaining,
@@ -346,6 +346,20 @@ mod mem {
 		db.query(surql).await.unwrap().check().unwrap();
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn session_variables() {
+		let db = Surreal::new::<Mem>(()).await.unwrap();
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		db.set("tenant", "acme").await.unwrap();
+		let mut res = db.query("RETURN session::variables()").await.unwrap();
+		let value: surrealdb::Value = res.take(0).unwrap();
+		assert_eq!(value.to_string(), "{ tenant: 'acme' }");
+		db.unset("tenant").await.unwrap();
+		let mut res = db.query("RETURN session::variables()").await.unwrap();
+		let value: surrealdb::Value = res.take(0).unwrap();
+		assert_eq!(value.to_string(), "{  }");
+	}
+
 	include_tests!(new_db => basic, serialisation, live, backup);
 }
 
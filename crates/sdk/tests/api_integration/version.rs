@@ -1,5 +1,6 @@
 #![cfg(feature = "kv-surrealkv")]
 
+use serde::Deserialize;
 use surrealdb::Value;
 use ulid::Ulid;
 
@@ -212,6 +213,84 @@ pub async fn info_for_table_with_versioned_fields(new_db: impl CreateDb) {
 	));
 }
 
+pub async fn select_with_version_across_the_query(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	// Create the initial records, edges and index, and record the timestamp.
+	let sql = "
+		DEFINE INDEX name ON person FIELDS name;
+		CREATE person:jaime SET name = 'Jaime';
+		CREATE person:tobie SET name = 'Tobie', friend = person:jaime;
+		RELATE person:tobie->knows->person:jaime;
+	";
+	let _ = db.query(sql).await.unwrap().check().unwrap();
+	let version = chrono::Utc::now().to_rfc3339();
+
+	// Update the records, and add a new edge.
+	let sql = "
+		UPDATE person:jaime SET name = 'Jaime v2';
+		UPDATE person:tobie SET name = 'Tobie v2';
+		CREATE person:john SET name = 'John';
+		RELATE person:tobie->knows->person:john;
+	";
+	let _ = db.query(sql).await.unwrap().check().unwrap();
+
+	// The record links, graph traversals and subqueries read the same version.
+	let sql = format!(
+		"SELECT
+			name,
+			friend.name AS friend,
+			->knows->person.name AS knows,
+			(SELECT VALUE name FROM person WHERE name = 'Jaime') AS named
+		FROM person:tobie VERSION d'{version}'"
+	);
+	let mut response = db.query(sql).await.unwrap().check().unwrap();
+	let name: Option<String> = response.take("name").unwrap();
+	assert_eq!(name.as_deref(), Some("Tobie"));
+	let friend: Option<String> = response.take("friend").unwrap();
+	assert_eq!(friend.as_deref(), Some("Jaime"));
+	let knows: Option<Vec<String>> = response.take("knows").unwrap();
+	assert_eq!(knows, Some(vec!["Jaime".to_owned()]));
+	let named: Option<Vec<String>> = response.take("named").unwrap();
+	assert_eq!(named, Some(vec!["Jaime".to_owned()]));
+
+	// The fetched records read the same version.
+	#[derive(Deserialize)]
+	struct Friend {
+		name: String,
+	}
+	let sql = format!("SELECT friend FROM person:tobie FETCH friend VERSION d'{version}'");
+	let mut response = db.query(sql).await.unwrap().check().unwrap();
+	let friend: Option<Friend> = response.take("friend").unwrap();
+	assert_eq!(friend.map(|f| f.name).as_deref(), Some("Jaime"));
+
+	// The index can not serve past versions, so the table is scanned.
+	let sql = format!("SELECT VALUE name FROM person WHERE name = 'Jaime' VERSION d'{version}'");
+	let mut response = db.query(sql).await.unwrap().check().unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Jaime".to_owned()]);
+
+	// The counts read the same version.
+	let sql = format!("SELECT count() FROM person GROUP ALL VERSION d'{version}'");
+	let mut response = db.query(sql).await.unwrap().check().unwrap();
+	let count: Option<i64> = response.take("count").unwrap();
+	assert_eq!(count, Some(2));
+
+	// A versionstamp can be used instead of a datetime.
+	let ts = chrono::DateTime::parse_from_rfc3339(&version).unwrap().timestamp_nanos_opt().unwrap();
+	let sql = format!("SELECT VALUE name FROM person:jaime VERSION {ts}");
+	let mut response = db.query(sql).await.unwrap().check().unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Jaime".to_owned()]);
+
+	// A statement reading a past version can not modify data.
+	let sql = format!("SELECT *, (CREATE person:jane) AS jane FROM person VERSION d'{version}'");
+	let error = db.query(sql).await.unwrap().check().unwrap_err().to_string();
+	assert!(error.contains("Can not modify data in a query reading the version"), "{error}");
+}
+
 define_include_tests!(version => {
 	#[test_log::test(tokio::test)]
 	select_with_version,
@@ -223,4 +302,6 @@ define_include_tests!(version => {
 	info_for_db_with_versioned_tables,
 	#[test_log::test(tokio::test)]
 	info_for_table_with_versioned_fields,
+	#[test_log::test(tokio::test)]
+	select_with_version_across_the_query,
 });
@@ -74,15 +74,16 @@ async fn database_change_feeds() -> Result<()> {
 
 	let potential_show_changes_values: Vec<Value> = second_timestamp
 		.map(|(vs1, vs2)| {
-			let vs1 = vs1.into_u128();
-			let vs2 = vs2.into_u128();
+			let next = vs2.into_u64_lossy() + 1;
+			let vs1 = vs1.into_u64_lossy();
+			let vs2 = vs2.into_u64_lossy();
 			SqlValue::parse(
 				format!(
-					r#"[
+					r#"{{ changes: [
 						{{ versionstamp: {}, changes: [ {{ update: {{ id: person:test, name: 'Name: Tobie' }} }} ] }},
 						{{ versionstamp: {}, changes: [ {{ delete: {{ id: person:test }} }} ] }}
-						]"#,
-					vs1, vs2
+						], versionstamp: {} }}"#,
+					vs1, vs2, next
 				)
 				.as_str(),
 			)
@@ -177,7 +178,7 @@ async fn database_change_feeds() -> Result<()> {
 	dbs.changefeed_process_at(None, current_time).await?;
 	let res = &mut dbs.execute(sql, &ses, None).await?;
 	let tmp = res.remove(0).result?;
-	let val: Value = SqlValue::parse("[]").into();
+	let val: Value = SqlValue::parse("{ changes: [], versionstamp: NONE }").into();
 	assert_eq!(val, tmp);
 	//
 	Ok(())
@@ -299,24 +300,25 @@ async fn table_change_feeds() -> Result<()> {
 	});
 	let allowed_values: Vec<Value> = sixth
 			.map(|(vs1, vs2, vs3, vs4, vs5, vs6)| {
+				let next = vs6.into_u64_lossy() + 1;
 				let (vs1, vs2, vs3, vs4, vs5, vs6) = (
-					vs1.into_u128(),
-					vs2.into_u128(),
-					vs3.into_u128(),
-					vs4.into_u128(),
-					vs5.into_u128(),
-					vs6.into_u128(),
+					vs1.into_u64_lossy(),
+					vs2.into_u64_lossy(),
+					vs3.into_u64_lossy(),
+					vs4.into_u64_lossy(),
+					vs5.into_u64_lossy(),
+					vs6.into_u64_lossy(),
 				);
 				SqlValue::parse(
 					format!(
-						r#"[
+						r#"{{ changes: [
 						{{ versionstamp: {vs1}, changes: [ {{ define_table: {{ name: 'person' }} }} ] }},
 						{{ versionstamp: {vs2}, changes: [ {{ update: {{ id: person:test, name: 'Name: Tobie' }} }} ] }},
 						{{ versionstamp: {vs3}, changes: [ {{ update: {{ id: person:test, name: 'Name: Jaime' }} }} ] }},
 						{{ versionstamp: {vs4}, changes: [ {{ update: {{ id: person:test, name: 'Name: Tobie' }} }} ] }},
 						{{ versionstamp: {vs5}, changes: [ {{ delete: {{ id: person:test }} }} ] }},
 						{{ versionstamp: {vs6}, changes: [ {{ update: {{ id: person:1000, name: 'Name: Yusuke' }} }} ] }}
-						]"#
+						], versionstamp: {next} }}"#
 					)
 					.as_str(),
 				).into()
@@ -353,7 +355,7 @@ async fn table_change_feeds() -> Result<()> {
 	dbs.changefeed_process_at(None, end_ts + 3600).await?;
 	let res = &mut dbs.execute(sql, &ses, None).await?;
 	let tmp = res.remove(0).result?;
-	let val = SqlValue::parse("[]").into();
+	let val = SqlValue::parse("{ changes: [], versionstamp: NONE }").into();
 	assert_eq!(tmp, val);
 	//
 	Ok(())
@@ -406,7 +408,10 @@ async fn changefeed_with_ts() -> Result<()> {
         SHOW CHANGES FOR TABLE user SINCE 0 LIMIT 10;
     ";
 	let value: Value = db.execute(sql, &ses, None).await?.remove(0).result?;
-	let Value::Array(array) = value.clone() else {
+	let Value::Object(envelope) = value.clone() else {
+		unreachable!()
+	};
+	let Some(Value::Array(array)) = envelope.get("changes").cloned() else {
 		unreachable!()
 	};
 	assert_eq!(array.len(), 5);
@@ -533,6 +538,30 @@ async fn changefeed_with_ts() -> Result<()> {
 		)
 		.unwrap()
 	);
+	//
+	// Show changes since a change set, which is included
+	//
+	let sql = format!("SHOW CHANGES FOR TABLE user SINCE {versionstamp5} LIMIT 10;");
+	let value: Value = db.execute(&sql, &ses, None).await?.remove(0).result?;
+	let Value::Object(resumed) = value else {
+		unreachable!()
+	};
+	let Some(Value::Array(changes)) = resumed.get("changes").cloned() else {
+		unreachable!()
+	};
+	assert_eq!(changes.len(), 1);
+	assert_eq!(changes[0], array[4]);
+	//
+	// Resume the changes from the returned versionstamp
+	//
+	let Some(Value::Number(next)) = envelope.get("versionstamp") else {
+		unreachable!()
+	};
+	assert_eq!(next.to_int(), versionstamp5.to_int() + 1);
+	let sql = format!("SHOW CHANGES FOR TABLE user SINCE {next} LIMIT 10;");
+	let value: Value = db.execute(&sql, &ses, None).await?.remove(0).result?;
+	let val: Value = SqlValue::parse("{ changes: [], versionstamp: NONE }").into();
+	assert_eq!(value, val);
 	// Save timestamp 2
 	let ts2_dt = "2023-08-01T00:00:05Z";
 	let ts2 = DateTime::parse_from_rfc3339(ts2_dt).unwrap();
@@ -542,7 +571,10 @@ async fn changefeed_with_ts() -> Result<()> {
 	//
 	let sql = format!("SHOW CHANGES FOR TABLE user SINCE d'{ts1_dt}' LIMIT 10; ");
 	let value: Value = db.execute(&sql, &ses, None).await?.remove(0).result?;
-	let Value::Array(array) = value.clone() else {
+	let Value::Object(envelope) = value.clone() else {
+		unreachable!()
+	};
+	let Some(Value::Array(array)) = envelope.get("changes").cloned() else {
 		unreachable!()
 	};
 	assert_eq!(array.len(), 4);
@@ -579,9 +611,49 @@ async fn changefeed_with_ts() -> Result<()> {
 	//
 	let sql = format!("SHOW CHANGES FOR TABLE user SINCE d'{ts3_dt}' LIMIT 10; ");
 	let value: Value = db.execute(&sql, &ses, None).await?.remove(0).result?;
-	let Value::Array(array) = value.clone() else {
+	let Value::Object(envelope) = value.clone() else {
+		unreachable!()
+	};
+	let Some(Value::Array(array)) = envelope.get("changes").cloned() else {
 		unreachable!()
 	};
 	assert_eq!(array.len(), 0);
 	Ok(())
 }
+
+#[tokio::test]
+async fn table_change_feeds_audit() -> Result<()> {
+	let dbs = new_ds().await?;
+	let mut ses = Session::owner().with_ns("test-cf-audit").with_db("test-cf-audit");
+	ses.or = Some("http://surrealdb.com".to_string());
+	dbs.changefeed_process_at(None, 0).await?;
+	// Changes recorded before the audit is enabled have no audit details
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		CREATE person:tobie SET name = 'Tobie';
+		DEFINE TABLE OVERWRITE person CHANGEFEED 1h INCLUDE AUDIT;
+		UPDATE person:tobie SET name = 'Jaime';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	dbs.changefeed_process_at(None, 1).await?;
+	let sql = "SHOW CHANGES FOR TABLE person SINCE 0 LIMIT 10;";
+	let value: Value = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	let json = value.into_json();
+	let changes = json["changes"].as_array().unwrap();
+	assert_eq!(changes.len(), 4);
+	// CREATE
+	let create = &changes[1]["changes"][0];
+	assert_eq!(create["update"]["name"], "Tobie");
+	assert!(create.get("audit").is_none(), "{create}");
+	// UPDATE
+	let update = &changes[3]["changes"][0];
+	assert_eq!(update["update"]["name"], "Jaime");
+	let audit = &update["audit"];
+	assert_eq!(audit["identity"], "system_auth");
+	assert_eq!(audit["origin"], "http://surrealdb.com");
+	assert!(audit["statement"].as_str().unwrap().starts_with("UPDATE person:tobie SET name ="));
+	assert_eq!(audit["fingerprint"].as_str().unwrap().len(), 16);
+	Ok(())
+}
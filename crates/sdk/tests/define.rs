@@ -236,6 +236,11 @@ async fn define_statement_index_concurrently_building_status(
 							continue;
 						}
 						"indexing" => {
+							assert_eq!(o.get("total"), Some(&Value::from(initial_size)), "{tmp:#}");
+							assert!(
+								matches!(o.get("started"), Some(Value::Datetime(_))),
+								"{tmp:#}"
+							);
 							{
 								if new_initial != initial_count {
 									assert!(new_initial > initial_count, "{new_initial:?}");
@@ -325,6 +330,62 @@ async fn define_statement_index_concurrently_building_status_full_text_overwrite
 		.await
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn define_statement_index_concurrently_cancel_build() -> Result<()> {
+	let session = Session::owner().with_ns("test").with_db("test");
+	let ds = new_ds().await?;
+	// Populate initial records
+	for i in 0..10000 {
+		let mut responses = ds
+			.execute(
+				&format!("CREATE user:{i} SET email = 'test{i}@surrealdb.com';"),
+				&session,
+				None,
+			)
+			.await?;
+		skip_ok(&mut responses, 1)?;
+	}
+	// Create the index concurrently, and cancel the building straight away
+	let sql = "
+		DEFINE INDEX test ON user FIELDS email CONCURRENTLY;
+		ALTER INDEX test ON user CANCEL BUILD;
+		INFO FOR INDEX test ON user;
+		SELECT * FROM user WHERE email = 'test1@surrealdb.com' EXPLAIN;
+		SELECT VALUE id FROM user WHERE email = 'test1@surrealdb.com';
+	";
+	let mut t = Test::new_ds_session(ds, session, sql).await?;
+	t.skip_ok(2)?;
+	t.expect_val("{ building: { status: 'aborted' } }")?;
+	// The cancelled index is not used by the query planner
+	t.expect_val(
+		"[
+			{ detail: { direction: 'forward', table: 'user' }, operation: 'Iterate Table' },
+			{ detail: { type: 'Memory' }, operation: 'Collector' }
+		]",
+	)?;
+	t.expect_val("[user:1]")?;
+	// The cancellation is stored with the index, so that it is kept after a restart
+	let txn = t.ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	assert!(txn.get_tb_index("test", "test", "user", "test").await?.cancelled);
+	txn.cancel().await?;
+	// Once rebuilt, the index is used again
+	let sql = "
+		REBUILD INDEX test ON user;
+		INFO FOR INDEX test ON user;
+		SELECT VALUE id FROM user WHERE email = 'test1@surrealdb.com';
+		ALTER INDEX test ON user CANCEL BUILD;
+	";
+	let mut t = Test::new_ds_session(t.ds, t.session, sql).await?;
+	t.skip_ok(1)?;
+	t.expect_val("{ building: { initial: 10000, pending: 0, status: 'ready', updated: 0 } }")?;
+	t.expect_val("[user:1]")?;
+	t.expect_error("Database index `test` is not currently building")?;
+	let txn = t.ds.transaction(TransactionType::Read, LockType::Optimistic).await?;
+	assert!(!txn.get_tb_index("test", "test", "user", "test").await?.cancelled);
+	txn.cancel().await?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_analyzer() -> Result<()> {
 	let sql = r#"
@@ -358,6 +419,7 @@ async fn define_statement_analyzer() -> Result<()> {
 			models: {},
 			params: {},
 			tables: {},
+			schedules: {},
 			sequences: {},
 			users: {},
 		}"#,
@@ -703,10 +765,10 @@ async fn permissions_checks_define_function() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -749,10 +811,10 @@ async fn permissions_checks_define_analyzer() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, sequences: { }, users: {  } }",
+			"{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, schedules: {  }, sequences: { }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: {}, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: {}, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -880,10 +942,10 @@ async fn permissions_checks_define_access_db() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -1020,10 +1082,10 @@ async fn permissions_checks_define_user_db() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -1069,10 +1131,10 @@ async fn permissions_checks_define_access_record() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -1115,10 +1177,10 @@ async fn permissions_checks_define_param() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -1158,10 +1220,10 @@ async fn permissions_checks_define_table() {
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }",
 		],
 		vec![
-			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, sequences: { }, tables: {  }, users: {  } }",
+			"{ accesses: {  }, analyzers: {  }, apis: {  }, buckets: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, schedules: {  }, sequences: { }, tables: {  }, users: {  } }",
 		],
 	];
 
@@ -281,6 +281,43 @@ async fn function_record_table() -> Result<()> {
 	Ok(())
 }
 
+// --------------------------------------------------
+// session
+// --------------------------------------------------
+
+#[tokio::test]
+async fn function_session_introspection() -> Result<()> {
+	let sql = r#"
+		RETURN session::endpoint();
+		RETURN session::timeout() > 50m AND session::timeout() <= 1h;
+		RETURN session::variables();
+		RETURN session::origin();
+	"#;
+	let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+	let mut ses = Session::owner().with_ns("test").with_db("test").with_ep("ws");
+	ses.exp = Some(now.as_secs() as i64 + 3600);
+	ses.or = Some("http://localhost".to_string());
+	ses.parameters.insert("tenant".to_string(), Value::from("acme"));
+	let dbs = new_ds().await?;
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.remove(0).result?, Value::from("ws"));
+	assert_eq!(res.remove(0).result?, Value::Bool(true));
+	assert_eq!(format!("{}", res.remove(0).result?), "{ tenant: 'acme' }");
+	assert_eq!(res.remove(0).result?, Value::from("http://localhost"));
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_session_introspection_without_connection() -> Result<()> {
+	let sql = r#"
+		RETURN session::endpoint();
+		RETURN session::timeout();
+		RETURN session::variables();
+	"#;
+	Test::new(sql).await?.expect_vals(&["NONE", "NONE", "{}"])?;
+	Ok(())
+}
+
 // --------------------------------------------------
 // string
 // --------------------------------------------------
@@ -3886,6 +3923,237 @@ pub async fn function_http_error() -> Result<()> {
 	Ok(())
 }
 
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_timeout() -> Result<()> {
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/some/path"))
+		.respond_with(
+			ResponseTemplate::new(200)
+				.set_body_string("some text result")
+				.set_delay(std::time::Duration::from_secs(5)),
+		)
+		.expect(1)
+		.mount(&server)
+		.await;
+
+	let query =
+		format!(r#"RETURN http::get("{}/some/path", {{}}, {{ timeout: 100ms }})"#, server.uri());
+
+	Test::new(&query).await?.expect_error(&format!(
+		"The remote HTTP request to '{}/some/path' timed out",
+		server.uri()
+	))?;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_retries() -> Result<()> {
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(503))
+		.expect(3)
+		.mount(&server)
+		.await;
+	Mock::given(method("POST"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(503))
+		.expect(1)
+		.mount(&server)
+		.await;
+
+	let query = format!(
+		r#"
+		RETURN http::get("{0}/some/path", {{}}, {{ retries: 2, retry_backoff: 1ms }});
+		RETURN http::post("{0}/some/path", {{}}, {{}}, {{ retries: 2, retry_backoff: 1ms }});
+		"#,
+		server.uri()
+	);
+
+	Test::new(&query).await?.expect_errors(&[
+		"There was an error processing a remote HTTP request: 503 Service Unavailable",
+		"There was an error processing a remote HTTP request: 503 Service Unavailable",
+	])?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_force_retry() -> Result<()> {
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(503))
+		.expect(3)
+		.mount(&server)
+		.await;
+
+	let query = format!(
+		r#"RETURN http::post("{}/some/path", {{}}, {{}}, {{ retries: 2, retry_backoff: 1ms, force_retry: true }})"#,
+		server.uri()
+	);
+
+	Test::new(&query).await?.expect_error(
+		"There was an error processing a remote HTTP request: 503 Service Unavailable",
+	)?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_max_body() -> Result<()> {
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(2)
+		.mount(&server)
+		.await;
+
+	let query = format!(
+		r#"
+		RETURN http::get("{0}/some/path", {{}}, {{ max_body: 4 }});
+		RETURN http::get("{0}/some/path", {{}}, {{ max_body: '1kb' }});
+		"#,
+		server.uri()
+	);
+
+	let mut test = Test::new(&query).await?;
+	test.expect_error("The remote HTTP response body exceeded the maximum size of 4 bytes")?;
+	test.expect_val("'some text result'")?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_capped_options() -> Result<()> {
+	use surrealdb::dbs::capabilities::Capabilities;
+	use surrealdb::kvs::Datastore;
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(503))
+		.expect(2)
+		.mount(&server)
+		.await;
+	Mock::given(method("GET"))
+		.and(path("/other/path"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(1)
+		.mount(&server)
+		.await;
+
+	let ds = Datastore::new("memory").await?.with_capabilities(
+		Capabilities::all().with_max_http_retries(1).with_max_http_body(Some(4)),
+	);
+
+	let query = format!(
+		r#"
+		RETURN http::get("{0}/some/path", {{}}, {{ retries: 5, retry_backoff: 1ms }});
+		RETURN http::get("{0}/other/path");
+		"#,
+		server.uri()
+	);
+
+	let mut test = Test::new_ds(ds, &query).await?;
+	test.expect_error(
+		"There was an error processing a remote HTTP request: 503 Service Unavailable",
+	)?;
+	test.expect_error("The remote HTTP response body exceeded the maximum size of 4 bytes")?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_follow_redirects() -> Result<()> {
+	use wiremock::{
+		Mock, ResponseTemplate,
+		matchers::{method, path},
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/some/path"))
+		.respond_with(ResponseTemplate::new(302).insert_header("location", "/other/path"))
+		.expect(1)
+		.mount(&server)
+		.await;
+	Mock::given(method("GET"))
+		.and(path("/other/path"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(0)
+		.mount(&server)
+		.await;
+
+	let query = format!(
+		r#"RETURN http::get("{}/some/path", {{}}, {{ follow_redirects: false }})"#,
+		server.uri()
+	);
+	test_queries(&query, &["NONE"]).await?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_invalid_options() -> Result<()> {
+	Test::new(
+		r#"
+	RETURN http::get("http://localhost/", {}, { timeout: 5 });
+	RETURN http::get("http://localhost/", {}, { unknown: true });
+	"#,
+	)
+	.await?
+	.expect_errors(&[
+		"Incorrect arguments for function http::get(). The 'timeout' option should be a duration, found 5",
+		"Incorrect arguments for function http::get(). Unknown request option 'unknown'",
+	])?;
+	Ok(())
+}
+
 #[cfg(all(feature = "http", feature = "scripting"))]
 #[tokio::test]
 pub async fn function_http_get_from_script() -> Result<()> {
@@ -90,7 +90,10 @@ async fn concurrency() -> Result<()> {
 
 		match res {
 			Err(err) => {
-				if matches!(err.downcast_ref(), Some(Error::QueryTimedout)) {
+				if matches!(
+					err.downcast_ref(),
+					Some(Error::QueryTimedout | Error::StatementTimedout { .. })
+				) {
 					Ok(false)
 				} else {
 					Err(err)
@@ -758,3 +758,346 @@ async fn select_where_matches_analyser_with_mapper() -> Result<()> {
 	t.skip_ok(2)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_matches_highlight_fragments() -> Result<()> {
+	let sql = r"
+		CREATE blog:1 SET content = 'The quick brown fox jumps over the lazy dog. Meanwhile the sleepy cat watches from the warm windowsill.';
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_content ON blog FIELDS content SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT VALUE search::highlight(1, { prefix: '<mark>', suffix: '</mark>' }) FROM blog WHERE content @1@ 'fox';
+		SELECT VALUE search::highlight(1, { fragment_size: 20 }) FROM blog WHERE content @1@ 'fox cat';
+		SELECT VALUE search::highlight(1, { fragment_size: 20, max_fragments: 3 }) FROM blog WHERE content @1@ 'fox cat';
+		SELECT VALUE search::highlight(1, { fragment_size: 0 }) FROM blog WHERE content @1@ 'fox';
+		SELECT VALUE search::highlight(1, { colour: 'red' }) FROM blog WHERE content @1@ 'fox';
+		SELECT VALUE search::highlight(1, 'fox') FROM blog WHERE content @1@ 'fox';
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(3)?;
+	t.expect_val(
+		"['The quick brown <mark>fox</mark> jumps over the lazy dog. Meanwhile the sleepy cat watches from the warm windowsill.']",
+	)?;
+	t.expect_val("['brown <em>fox</em> jumps']")?;
+	t.expect_val("[['brown <em>fox</em> jumps', 'sleepy <em>cat</em> watches']]")?;
+	t.expect_error(
+		"Incorrect arguments for function search::highlight(). The option 'fragment_size' must be a positive integer, found `0`",
+	)?;
+	t.expect_error(
+		"Incorrect arguments for function search::highlight(). Unknown option 'colour'",
+	)?;
+	t.expect_error(
+		"Incorrect arguments for function search::highlight(). Expected an object of highlight options as the second argument, found `string`",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_fuzzy() -> Result<()> {
+	let sql = r"
+		CREATE blog:1 SET title = 'Please receive the parcel';
+		CREATE blog:2 SET title = 'The deceiver';
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT VALUE id FROM blog WHERE title @1@ 'recieve';
+		SELECT id, search::highlight('<em>', '</em>', 1) AS title FROM blog WHERE title @1~1@ 'RECIEVE';
+		SELECT VALUE search::highlight('<em>', '</em>', 1) FROM blog WHERE title @1~2@ 'deceive parsel';
+		SELECT VALUE id FROM blog WHERE title @~0@ 'recieve';
+		SELECT id FROM blog WHERE title @1~1@ 'recieve' EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(4)?;
+	// Without fuzziness only the exact terms are matched
+	t.expect_val("[]")?;
+	// The matched variant is highlighted
+	t.expect_val("[{ id: blog:1, title: 'Please <em>receive</em> the parcel' }]")?;
+	// Every query term must match one of its variants
+	t.expect_val("['Please <em>receive</em> the <em>parcel</em>']")?;
+	t.expect_val("[]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'blog_title',
+						operator: '@1~1@',
+						value: 'recieve'
+					},
+					table: 'blog',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_fuzzy_score() -> Result<()> {
+	let sql = r"
+		CREATE doc:1 SET text = 'receive';
+		CREATE doc:2 SET text = 'recieve';
+		CREATE doc:3 SET text = 'apple';
+		CREATE doc:4 SET text = 'banana';
+		DEFINE ANALYZER simple TOKENIZERS blank,class;
+		DEFINE INDEX doc_text ON doc FIELDS text SEARCH ANALYZER simple BM25;
+		LET $s = SELECT id, search::score(1) AS score FROM doc WHERE text @1~1@ 'receive' ORDER BY score DESC;
+		RETURN $s.id;
+		RETURN $s[0].score > 0 AND $s[0].score == $s[1].score * 2;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(7)?;
+	// The exact match scores higher than the fuzzy match
+	t.expect_val("[doc:1, doc:2]")?;
+	// A single edit halves the score
+	t.expect_val("true")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_fuzzy_with_stemming() -> Result<()> {
+	let sql = r"
+		DEFINE ANALYZER stemmed TOKENIZERS blank,class FILTERS lowercase,snowball(english);
+		CREATE letter:1 SET text = 'We are receiving letters';
+		DEFINE INDEX letter_text ON letter FIELDS text SEARCH ANALYZER stemmed BM25 HIGHLIGHTS;
+		SELECT VALUE id FROM letter WHERE text @1@ 'recieves';
+		SELECT VALUE search::highlight('<b>', '</b>', 1) FROM letter WHERE text @1~1@ 'recieves';
+		SELECT VALUE id FROM letter WHERE text @1~1@ 'recieved letter';
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(3)?;
+	// 'recieves' is stemmed to 'reciev', which is not indexed
+	t.expect_val("[]")?;
+	// The fuzziness applies to the stemmed token, so 'reciev' matches 'receiv'
+	// even though 'recieves' is more than one edit away from 'receiving'
+	t.expect_val("['We are <b>receiving</b> letters']")?;
+	t.expect_val("[letter:1]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_explain() -> Result<()> {
+	let sql = r"
+		CREATE blog:1 SET title = 'the quick brown fox';
+		CREATE blog:2 SET title = 'the fox';
+		CREATE blog:3 SET title = 'a lazy dog';
+		CREATE blog:4 SET title = 'a sleepy cat';
+		CREATE blog:5 SET title = 'an old owl';
+		DEFINE ANALYZER simple TOKENIZERS blank,class;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25;
+		LET $e = SELECT id, search::score(1) AS score, search::explain(1) AS explain FROM blog WHERE title @1@ 'the fox' ORDER BY id;
+		RETURN $e.id;
+		RETURN object::keys($e[0].explain);
+		RETURN object::keys($e[0].explain.fox);
+		RETURN $e.explain.fox.tf;
+		RETURN $e.explain.fox.df;
+		RETURN $e.explain.fox.doc_length;
+		RETURN $e[0].explain.fox.norm > 1 AND $e[1].explain.fox.norm < 1;
+		RETURN $e[1].score > $e[0].score;
+		SELECT VALUE search::explain(1) FROM blog WHERE title @1@ 'lazy';
+		SELECT VALUE search::explain(2) FROM blog WHERE title @1@ 'lazy';
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(18)?;
+	t.skip_ok(8)?;
+	t.expect_val("[blog:1, blog:2]")?;
+	// The explanation is keyed by term
+	t.expect_val("['fox', 'the']")?;
+	t.expect_val("['df', 'doc_length', 'fuzziness', 'idf', 'norm', 'score', 'tf']")?;
+	t.expect_val("[1, 1]")?;
+	t.expect_val("[2, 2]")?;
+	t.expect_val("[4, 2]")?;
+	// The longer document is penalised by the field length normalisation
+	t.expect_val("true")?;
+	t.expect_val("true")?;
+	t.expect_val(
+		"[{ lazy: { df: 1, doc_length: 3, fuzziness: 0, idf: 1.0986123085021973, norm: 1, score: 1.0986123085021973, tf: 1 } }]",
+	)?;
+	// An unknown match reference has no explanation
+	t.expect_val("[NONE]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_bm25_parameters() -> Result<()> {
+	let sql = r"
+		CREATE blog:1 SET title = 'the quick brown fox';
+		CREATE blog:2 SET title = 'the fox';
+		CREATE blog:3 SET title = 'a lazy dog';
+		CREATE blog:4 SET title = 'a sleepy cat';
+		CREATE blog:5 SET title = 'an old owl';
+		DEFINE ANALYZER simple TOKENIZERS blank,class;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25(1.2,0);
+		LET $e = SELECT search::score(1) AS score, search::explain(1) AS explain FROM blog WHERE title @1@ 'fox' ORDER BY id;
+		RETURN $e.explain.fox.norm;
+		RETURN $e[0].score == $e[1].score;
+		DEFINE INDEX OVERWRITE blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25(1.2,1);
+		LET $e = SELECT search::score(1) AS score, search::explain(1) AS explain FROM blog WHERE title @1@ 'fox' ORDER BY id;
+		RETURN $e.explain.fox.norm;
+		RETURN $e[1].score > $e[0].score;
+		DEFINE INDEX blog_invalid ON blog FIELDS title SEARCH ANALYZER simple BM25(1.2,1.5);
+		DEFINE INDEX blog_invalid ON blog FIELDS title SEARCH ANALYZER simple BM25(-1,0.75);
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(16)?;
+	t.skip_ok(8)?;
+	// Without length normalisation both documents score the same
+	t.expect_val("[1, 1]")?;
+	t.expect_val("true")?;
+	t.skip_ok(2)?;
+	// With full length normalisation the shorter document scores higher
+	t.expect_val("[1.3333333730697632, 0.6666666865348816]")?;
+	t.expect_val("true")?;
+	t.expect_error("Invalid BM25 parameters: b must be between 0 and 1, found 1.5")?;
+	t.expect_error("Invalid BM25 parameters: k1 must be a non-negative number, found -1")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_phrases() -> Result<()> {
+	let sql = r#"
+		CREATE blog:1 SET title = 'the quick brown fox jumps';
+		CREATE blog:2 SET title = 'the brown quick fox jumps';
+		CREATE blog:3 SET title = 'a quick fox';
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT VALUE id FROM blog WHERE title @1@ '"quick fox"' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ '"quick fox"~1' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ '"fox quick"~3';
+		SELECT VALUE id FROM blog WHERE title @1@ 'jumps "QUICK FOX"';
+		SELECT count() FROM blog WHERE title @1@ '"quick fox"' GROUP ALL;
+		SELECT VALUE search::highlight('<em>', '</em>', 1) FROM blog WHERE title @1@ '"quick brown fox" jumps';
+		SELECT VALUE search::offsets(1) FROM blog WHERE title @1@ '"quick brown fox"';
+		LET $s = SELECT id, search::score(1) AS score FROM blog WHERE title @1@ '"quick fox"~1' ORDER BY score DESC;
+		RETURN $s.id;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(14)?;
+	t.skip_ok(5)?;
+	// The terms of a phrase must be consecutive and in order
+	t.expect_val("[blog:2, blog:3]")?;
+	// Other terms are allowed inside a phrase followed by a slop
+	t.expect_val("[blog:1, blog:2, blog:3]")?;
+	t.expect_val("[]")?;
+	// The terms outside of the phrases must also match
+	t.expect_val("[blog:2]")?;
+	t.expect_val("[{ count: 2 }]")?;
+	// The whole phrase is highlighted
+	t.expect_val("['the <em>quick brown fox</em> <em>jumps</em>']")?;
+	t.expect_val("[{ 0: [{ e: 19, s: 4 }] }]")?;
+	t.skip_ok(1)?;
+	// The closer the terms of a phrase are, the higher the score
+	t.expect_val("[blog:3, blog:2, blog:1]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_operators() -> Result<()> {
+	let sql = r#"
+		CREATE blog:1 SET title = 'the quick brown fox', body = 'a lazy dog';
+		CREATE blog:2 SET title = 'a lazy dog', body = 'the quick fox';
+		CREATE blog:3 SET title = 'a red fox', body = 'the sleeping cat';
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_content ON blog FIELDS title, body SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT VALUE id FROM blog WHERE title @1@ 'fox -dog' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ 'cat OR dog' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ '+fox +(red OR lazy) -brown' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ 'title:fox' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ 'title:dog OR body:cat' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE title @1@ 'fox -title:dog' ORDER BY id;
+		LET $s = SELECT id, search::score(1) AS score FROM blog WHERE title @1@ 'title:fox^3 OR body:fox' ORDER BY score DESC;
+		RETURN $s.id;
+		LET $s = SELECT id, search::score(1) AS score FROM blog WHERE title @1@ 'title:fox OR body:fox^3' ORDER BY score DESC;
+		RETURN $s.id;
+		SELECT VALUE id FROM blog WHERE title @1@ 'fox (dog';
+		SELECT VALUE id FROM blog WHERE title @1@ 'fox - dog';
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(17)?;
+	t.skip_ok(5)?;
+	// Excluded clauses remove the records matching them
+	t.expect_val("[blog:3]")?;
+	t.expect_val("[blog:1, blog:2, blog:3]")?;
+	t.expect_val("[blog:2, blog:3]")?;
+	// A clause can be scoped to one of the fields of the index
+	t.expect_val("[blog:1, blog:3]")?;
+	t.expect_val("[blog:2, blog:3]")?;
+	t.expect_val("[blog:1, blog:3]")?;
+	// The boost of a field changes the order of the records
+	t.skip_ok(1)?;
+	t.expect_val("[blog:3, blog:1, blog:2]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[blog:2, blog:3, blog:1]")?;
+	// A malformed query string is rejected
+	t.expect_error("Invalid full-text query at character 4: The group is not closed")?;
+	t.expect_error("Invalid full-text query at character 4: Expected a clause after '-'")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn search_stats_and_terms() -> Result<()> {
+	let sql = r"
+		DEFINE ACCESS user ON DATABASE TYPE RECORD;
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE TABLE blog PERMISSIONS FULL;
+		DEFINE TABLE note PERMISSIONS FOR select WHERE author = $auth;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25;
+		DEFINE INDEX note_text ON note FIELDS text SEARCH ANALYZER simple BM25;
+		DEFINE INDEX blog_id ON blog FIELDS id;
+		DEFINE INDEX content ON blog FIELDS title SEARCH ANALYZER simple BM25;
+		DEFINE INDEX content ON note FIELDS text SEARCH ANALYZER simple BM25;
+		CREATE blog:1 SET title = 'The fox and the other fox';
+		CREATE blog:2 SET title = 'A fox jumps';
+		CREATE blog:3 SET title = 'A lazy dog';
+		CREATE note:1 SET text = 'A secret fox', author = user:2;
+		RETURN search::stats('blog_title', 'fox');
+		RETURN search::stats('blog.content', 'fox');
+		RETURN search::stats('blog_title', 'cat');
+		RETURN search::terms('blog_title', 'f', 10);
+		RETURN search::terms('blog_title', '', 3);
+		RETURN search::terms('blog_title', 'f', 0);
+		RETURN search::stats('unknown', 'fox');
+		RETURN search::stats('content', 'fox');
+		RETURN search::stats('blog_id', 'fox');
+	";
+	let dbs = new_ds().await?;
+	let mut t = Test::new_ds(dbs, sql).await?;
+	t.expect_size(22)?;
+	t.skip_ok(13)?;
+	// The document frequency and the total term frequency
+	t.expect_val("{ df: 2, tf: 3 }")?;
+	t.expect_val("{ df: 2, tf: 3 }")?;
+	t.expect_val("{ df: 0, tf: 0 }")?;
+	// The terms are listed in order
+	t.expect_val("['fox']")?;
+	t.expect_val("['a', 'and', 'dog']")?;
+	t.expect_error(
+		"Incorrect arguments for function search::terms(). The limit must be between 1 and 1000, found 0",
+	)?;
+	t.expect_error("The index 'unknown' does not exist")?;
+	t.expect_error("Incorrect arguments for function search::stats(). The index 'content' is defined on several tables, it can be written `table.content`")?;
+	t.expect_error(
+		"Incorrect arguments for function search::stats(). The index 'blog_id' is not a full-text index",
+	)?;
+	// A record user can only read the indexes of the tables it can fully select
+	let sql = r"
+		RETURN search::stats('blog_title', 'fox');
+		RETURN search::terms('note_text', 's', 10);
+	";
+	let ses = Session::for_record("test", "test", "user", SqlValue::parse("user:1").into());
+	let dbs = t.ds;
+	let mut t = Test::new_ds_session(dbs, ses, sql).await?;
+	t.expect_size(2)?;
+	t.expect_val("{ df: 2, tf: 3 }")?;
+	t.expect_error("You don't have permission to run this query on the `note` table")?;
+	Ok(())
+}
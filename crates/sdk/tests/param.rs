@@ -34,6 +34,7 @@ async fn define_global_param() -> Result<()> {
 			functions: {},
 			models: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
+			schedules: {},
 			sequences: {},
 			tables: {},
 			users: {},
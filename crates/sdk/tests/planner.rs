@@ -51,9 +51,9 @@ async fn select_where_iterate_three_multi_index_with_all_index() -> Result<()> {
 	// OR results
 	check_result(&mut res, "[{ name: 'Jaime' }, { name: 'Lizzie' }, { name: 'Tobie' }]")?;
 	check_result(&mut res, THREE_MULTI_INDEX_EXPLAIN)?;
-	// AND results
+	// AND results, using the index listed first
 	check_result(&mut res, "[{name: 'Jaime'}]")?;
-	check_result(&mut res, SINGLE_INDEX_FT_EXPLAIN)?;
+	check_result(&mut res, SINGLE_INDEX_UNIQ_EXPLAIN)?;
 	Ok(())
 }
 
@@ -2523,6 +2523,117 @@ async fn select_composite_unique_index() -> Result<()> {
 	select_composite_index(true).await
 }
 
+async fn select_composite_index_prefix_range(unique: bool) -> Result<()> {
+	//
+	let sql = format!(
+		"
+		DEFINE INDEX idx ON TABLE event FIELDS tenant, created {};
+		CREATE event:1 SET tenant = 'a', created = 1;
+		CREATE event:2 SET tenant = 'a', created = 2;
+		CREATE event:3 SET tenant = 'a', created = 3;
+		CREATE event:4 SET tenant = 'a', created = 4;
+		CREATE event:5 SET tenant = 'b', created = 2;
+		CREATE event:6 SET tenant = 'b', created = 3;
+		SELECT id, created FROM event WHERE tenant = 'a' AND created > 1 ORDER BY created EXPLAIN;
+		SELECT id, created FROM event WHERE tenant = 'a' AND created > 1 ORDER BY created;
+		SELECT id, created FROM event WHERE tenant = 'a' AND created > 1 ORDER BY created LIMIT 2;
+		SELECT VALUE id FROM event WHERE tenant = 'a' AND created >= 2 AND created < 4 EXPLAIN;
+		SELECT VALUE id FROM event WHERE tenant = 'a' AND created >= 2 AND created < 4;
+		SELECT VALUE id FROM event WHERE tenant = 'a' AND created > 2 AND created <= 4;
+		SELECT VALUE id FROM event WHERE tenant = 'b' AND created <= 2;
+		SELECT id, created FROM event WHERE tenant = 'a' AND created > 1 ORDER BY created DESC;
+		SELECT id, created FROM event WHERE tenant = 'a' AND created > 1 ORDER BY created DESC LIMIT 2;
+		SELECT VALUE id FROM event WHERE tenant = 'c' AND created > 1;
+	",
+		if unique {
+			"UNIQUE"
+		} else {
+			""
+		}
+	);
+	let mut t = Test::new(&sql).await?;
+	//
+	t.expect_size(17)?;
+	t.skip_ok(7)?;
+	//
+	t.expect_vals(&[
+		"[
+			{
+				detail: {
+					plan: {
+						direction: 'forward',
+						from: {
+							inclusive: false,
+							value: 1
+						},
+						index: 'idx',
+						prefix: ['a'],
+						to: {
+							inclusive: false,
+							value: NONE
+						}
+					},
+					table: 'event'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[{ created: 2, id: event:2 }, { created: 3, id: event:3 }, { created: 4, id: event:4 }]",
+		"[{ created: 2, id: event:2 }, { created: 3, id: event:3 }]",
+		"[
+			{
+				detail: {
+					plan: {
+						direction: 'forward',
+						from: {
+							inclusive: true,
+							value: 2
+						},
+						index: 'idx',
+						prefix: ['a'],
+						to: {
+							inclusive: false,
+							value: 4
+						}
+					},
+					table: 'event'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[event:2, event:3]",
+		"[event:3, event:4]",
+		"[event:5]",
+		"[{ created: 4, id: event:4 }, { created: 3, id: event:3 }, { created: 2, id: event:2 }]",
+		"[{ created: 4, id: event:4 }, { created: 3, id: event:3 }]",
+		"[]",
+	])?;
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_composite_standard_index_prefix_range() -> Result<()> {
+	select_composite_index_prefix_range(false).await
+}
+
+#[tokio::test]
+async fn select_composite_unique_index_prefix_range() -> Result<()> {
+	select_composite_index_prefix_range(true).await
+}
+
 #[tokio::test]
 async fn select_where_index_boolean_behaviour() -> Result<()> {
 	let sql = r"
@@ -2840,3 +2951,73 @@ async fn select_limit_start_array() -> Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_string_contains_with_trigram_index() -> Result<()> {
+	let sql = r"
+		DEFINE INDEX name_trgm ON person FIELDS name TRIGRAM;
+		CREATE person:1 SET name = 'Tobie Morgan Hitchcock' RETURN NONE;
+		CREATE person:2 SET name = 'Jaime Morgan Hitchcock' RETURN NONE;
+		CREATE person:3 SET name = 'Kurt Gödel' RETURN NONE;
+		SELECT id FROM person WHERE string::contains(name, 'Morgan') EXPLAIN;
+		SELECT id FROM person WHERE string::contains(name, 'Morgan');
+		SELECT id FROM person WHERE string::contains(name, 'morgan');
+		SELECT id FROM person WHERE string::contains(name, 'ödel');
+		SELECT id FROM person WHERE string::contains(name, 'Mo') EXPLAIN;
+		UPDATE person:1 SET name = 'Tobie' RETURN NONE;
+		DELETE person:2;
+		SELECT id FROM person WHERE string::contains(name, 'Morgan');
+		SELECT id FROM person WHERE string::contains(name, 'obi');
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(13)?;
+	t.skip_ok(4)?;
+	t.expect_val(
+		"[
+				{
+					detail: {
+						plan: {
+							index: 'name_trgm',
+							operator: 'CONTAINS',
+							value: 'Morgan'
+						},
+						table: 'person',
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]",
+	)?;
+	t.expect_val("[{ id: person:1 }, { id: person:2 }]")?;
+	// The candidates are filtered with the case sensitive function
+	t.expect_val("[]")?;
+	// The trigrams are made of characters rather than bytes
+	t.expect_val("[{ id: person:3 }]")?;
+	// A substring shorter than a trigram can not use the index
+	t.expect_val(
+		"[
+				{
+					detail: {
+						direction: 'forward',
+						table: 'person'
+					},
+					operation: 'Iterate Table'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]",
+	)?;
+	t.skip_ok(2)?;
+	t.expect_val("[]")?;
+	t.expect_val("[{ id: person:1 }]")?;
+	Ok(())
+}
@@ -4,9 +4,12 @@ use parse::Parse;
 mod helpers;
 use helpers::*;
 
+use std::time::{Duration, SystemTime};
 use surrealdb::Result;
 use surrealdb::dbs::Session;
+use surrealdb::err::Error;
 use surrealdb::sql::SqlValue;
+use surrealdb_core::expr::Value;
 
 #[tokio::test]
 async fn rebuild_index_statement() -> Result<()> {
@@ -83,3 +86,87 @@ async fn rebuild_index_statement() -> Result<()> {
 	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
 	Ok(())
 }
+
+#[tokio::test]
+async fn rebuild_index_concurrently() -> Result<()> {
+	let sql = "
+		DEFINE INDEX hnsw_pts ON pts FIELDS point HNSW DIMENSION 2;
+		DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 2;
+		DEFINE INDEX idx_pts ON pts FIELDS point;
+		FOR $i IN 1..11 { CREATE type::thing('pts', $i) SET point = [$i, $i]; };
+		DELETE pts WHERE point[0] > 6;
+		INFO FOR INDEX hnsw_pts ON pts;
+		INFO FOR INDEX mt_pts ON pts;
+		REBUILD INDEX idx_pts ON pts CONCURRENTLY;
+		REBUILD INDEX hnsw_pts ON pts CONCURRENTLY;
+		CREATE pts:11 SET point = [11, 11];
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(10)?;
+	t.skip_ok(5)?;
+	// The removed vectors are reported as tombstones
+	t.expect_val(
+		"{
+			building: { initial: 0, pending: 0, status: 'ready', updated: 0 },
+			health: { nodes: 6, tombstone_ratio: 0.4, tombstones: 4 }
+		}",
+	)?;
+	t.expect_val(
+		"{
+			building: { initial: 0, pending: 0, status: 'ready', updated: 0 },
+			health: { nodes: 1, tombstone_ratio: 0.0, tombstones: 0 }
+		}",
+	)?;
+	t.expect_error(
+		"Database index `idx_pts` can not be rebuilt concurrently: only MTREE and HNSW indexes can be rebuilt concurrently",
+	)?;
+	t.skip_ok(2)?;
+	// Loop until the index is rebuilt
+	let now = SystemTime::now();
+	let time_out = Duration::from_secs(60);
+	loop {
+		if now.elapsed().map_err(|e| Error::Internal(e.to_string()))?.gt(&time_out) {
+			panic!("Time-out {time_out:?}");
+		}
+		let mut r = t.ds.execute("INFO FOR INDEX hnsw_pts ON pts", &t.session, None).await?;
+		let tmp = r.remove(0).result?;
+		if let Value::Object(o) = &tmp {
+			// While the index is rebuilt, the current structure is still ready to be used
+			assert_eq!(building_status(o.get("building")), Some("ready"), "{tmp:#}");
+			if let Some(s) = building_status(o.get("rebuilding")) {
+				match s {
+					"started" | "cleaning" | "indexing" => {}
+					"ready" => break,
+					_ => panic!("Unexpected status: {tmp:#}"),
+				}
+			}
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	}
+	// The rebuilt structure has no tombstones, and contains every record
+	let sql = "
+		INFO FOR INDEX hnsw_pts ON pts;
+		SELECT VALUE id FROM pts WHERE point <|1,40|> [11, 11];
+		SELECT VALUE id FROM pts WHERE point <|1,40|> [1, 1];
+	";
+	let mut t = Test::new_ds_session(t.ds, t.session, sql).await?;
+	let tmp = t.next_value()?;
+	let Value::Object(o) = &tmp else {
+		panic!("{tmp:#}");
+	};
+	let val = SqlValue::parse("{ nodes: 7, tombstone_ratio: 0.0, tombstones: 0 }");
+	assert_eq!(o.get("health").map(|h| format!("{h:#}")), Some(format!("{val:#}")), "{tmp:#}");
+	t.expect_val("[pts:11]")?;
+	t.expect_val("[pts:1]")?;
+	Ok(())
+}
+
+/// The status of a building, as returned by `INFO FOR INDEX`
+fn building_status(v: Option<&Value>) -> Option<&str> {
+	if let Some(Value::Object(o)) = v {
+		if let Some(Value::Strand(s)) = o.get("status") {
+			return Some(s.as_str());
+		}
+	}
+	None
+}
@@ -296,6 +296,7 @@ async fn relate_enforced() -> Result<()> {
 	functions: {},
 	models: {},
 	params: {},
+	schedules: {},
 	sequences: {},
 	tables: {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
@@ -308,3 +309,63 @@ async fn relate_enforced() -> Result<()> {
 	t.expect_value(info)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn graph_path() -> Result<()> {
+	let sql = "
+		DEFINE TABLE person SCHEMALESS PERMISSIONS FOR select WHERE hidden != true;
+		DEFINE TABLE knows TYPE RELATION SCHEMALESS PERMISSIONS FULL;
+		CREATE person:1, person:3, person:4, person:5, person:6;
+		CREATE person:2 SET hidden = true;
+		RELATE person:1->knows:a->person:2;
+		RELATE person:1->knows:b->person:3;
+		RELATE person:2->knows:c->person:4;
+		RELATE person:3->knows:d->person:4;
+		RELATE person:4->knows:e->person:5;
+		RETURN graph::path(person:1, person:4, { edge: 'knows', max_depth: 5 });
+		RETURN graph::path(person:1, person:4, { edge: 'knows', limit: 5 });
+		RETURN graph::path(person:1, person:5, { max_depth: 2 });
+		RETURN graph::path(person:1, person:6);
+		RETURN graph::path(person:5, person:1);
+		RETURN graph::path(person:5, person:1, { dir: 'in' });
+		RETURN graph::path(person:1, person:1);
+		RETURN graph::path(person:1, person:4, { depth: 2 });
+		RETURN graph::path(person:1, person:4, { limit: 0 });
+	";
+	let dbs = new_ds().await?;
+	let mut t = Test::new_ds(dbs, sql).await?;
+	t.expect_size(18)?;
+	t.skip_ok(9)?;
+	// The first of the shortest paths
+	t.expect_val("[person:1, knows:a, person:2, knows:c, person:4]")?;
+	// Every shortest path, up to the limit
+	t.expect_val(
+		"[
+			[person:1, knows:a, person:2, knows:c, person:4],
+			[person:1, knows:b, person:3, knows:d, person:4]
+		]",
+	)?;
+	// The paths are not longer than the maximum depth
+	t.expect_val("NONE")?;
+	t.expect_val("NONE")?;
+	// The edges are followed in their direction
+	t.expect_val("NONE")?;
+	t.expect_val("[person:5, knows:e, person:4, knows:c, person:2, knows:a, person:1]")?;
+	t.expect_val("[person:1]")?;
+	t.expect_error("Incorrect arguments for function graph::path(). Unknown path option 'depth'")?;
+	t.expect_error(
+		"Incorrect arguments for function graph::path(). The 'limit' option should be a positive integer, found 0",
+	)?;
+	// The records which a record user can not select are not traversed
+	let sql = "
+		RETURN graph::path(person:1, person:4, { limit: 5 });
+		RETURN graph::path(person:1, person:2);
+	";
+	let ses = Session::for_record("test", "test", "user", SqlValue::parse("user:1").into());
+	let dbs = t.ds;
+	let mut t = Test::new_ds_session(dbs, ses, sql).await?;
+	t.expect_size(2)?;
+	t.expect_val("[[person:1, knows:b, person:3, knows:d, person:4]]")?;
+	t.expect_val("NONE")?;
+	Ok(())
+}
@@ -267,6 +267,7 @@ async fn loose_mode_all_ok() -> Result<()> {
 			functions: {},
 			models: {},
 			params: {},
+			schedules: {},
 			sequences: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -5,6 +5,18 @@ use std::time::{Duration, Instant};
 use helpers::{Test, new_ds};
 use surrealdb::Result;
 use surrealdb_core::dbs::Session;
+use surrealdb_core::err::Error;
+
+/// Checks that an error identifies the statement which exceeded its timeout
+fn timed_out(statement: &'static str) -> impl Fn(&anyhow::Error) -> bool {
+	move |e| {
+		matches!(
+			e.downcast_ref(),
+			Some(Error::StatementTimedout { statement: s, timeout, elapsed })
+				if s.starts_with(statement) && elapsed >= timeout
+		)
+	}
+}
 
 #[tokio::test]
 async fn statement_timeouts() -> Result<()> {
@@ -19,18 +31,48 @@ async fn statement_timeouts() -> Result<()> {
 		SELECT * FROM person TIMEOUT 0s;
 		DELETE person:test TIMEOUT 0s;
 	";
-	let error = "The query was not executed because it exceeded the timeout";
 	Test::new(sql)
 		.await?
 		.expect_val("{ id: person:ok, test: true }")?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?
-		.expect_error(error)?;
+		.expect_error_func(timed_out("CREATE person:test"))?
+		.expect_error_func(timed_out("UPSERT person:test"))?
+		.expect_error_func(timed_out("UPDATE person:test"))?
+		.expect_error_func(timed_out("INSERT { id: person:test"))?
+		.expect_error_func(timed_out("RELATE person:test->know->person:ok"))?
+		.expect_error_func(timed_out("SELECT * FROM person"))?
+		.expect_error_func(timed_out("SELECT * FROM person"))?
+		.expect_error_func(timed_out("DELETE person:test"))?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn statement_timeout_message() -> Result<()> {
+	let sql = "SELECT * FROM person TIMEOUT 0s;";
+	let error = "The statement 'SELECT * FROM person TIMEOUT 0ns' was cancelled after running for ";
+	Test::new(sql).await?.expect_error_func(|e| e.to_string().starts_with(error))?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn session_timeout() -> Result<()> {
+	let sql = "
+		SLEEP 1s;
+		SLEEP 10ms;
+		CREATE ONLY person:test SET slept = sleep(200ms) TIMEOUT 1s;
+		SELECT * FROM person TIMEOUT 0s;
+	";
+	let session =
+		Session::owner().with_ns("test").with_db("test").with_timeout(Duration::from_millis(100));
+	Test::new_ds_session(new_ds().await?, session, sql)
+		.await?
+		// The session timeout applies to statements without a timeout
+		.expect_error_func(timed_out("SLEEP 1s"))?
+		.expect_val("NONE")?
+		// A statement timeout takes precedence over the session timeout
+		.expect_val("{ id: person:test }")?
+		.expect_error_func(timed_out("SELECT * FROM person"))?;
 
 	Ok(())
 }
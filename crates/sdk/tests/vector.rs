@@ -328,6 +328,7 @@ async fn select_mtree_knn_with_condition() -> Result<()> {
 					{
 						detail: {
 							plan: {
+								filter: 'flag = true',
 								index: 'mt_pt1',
 								operator: '<|2|>',
 								value: [44f]
@@ -400,6 +401,7 @@ async fn select_hnsw_knn_with_condition() -> Result<()> {
 					{
 						detail: {
 							plan: {
+								filter: 'flag = true',
 								index: 'hn_pt1',
 								operator: '<|2,40|>',
 								value: [44f]
@@ -505,6 +507,92 @@ async fn select_bruteforce_knn_with_condition() -> Result<()> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_knn_with_selective_condition() -> Result<()> {
+	let sql = r"
+		DEFINE INDEX hn_emb ON doc FIELDS embedding HNSW DIMENSION 1 M 4 EFC 20;
+		DEFINE INDEX mt_emb ON note FIELDS embedding MTREE DIMENSION 1 CAPACITY 3;
+		FOR $i IN 1..201 {
+			LET $category = IF $i % 50 = 0 { 'legal' } ELSE { 'other' };
+			CREATE type::thing('doc', $i) SET embedding = [$i], category = $category;
+			CREATE type::thing('note', $i) SET embedding = [$i], tags = [$category];
+		};
+		LET $vec = [1];
+		SELECT id, vector::distance::knn() AS distance FROM doc
+			WHERE category = 'legal' AND embedding <|3,4|> $vec ORDER BY distance;
+		SELECT id, vector::distance::knn() AS distance FROM doc
+			WHERE category = 'legal' AND embedding <|3,4|> $vec ORDER BY distance EXPLAIN;
+		SELECT id, vector::distance::knn() AS distance FROM note
+			WHERE tags CONTAINS 'legal' AND embedding <|3|> $vec ORDER BY distance;
+		SELECT id, vector::distance::knn() AS distance FROM note
+			WHERE tags CONTAINS 'legal' AND embedding <|3|> $vec ORDER BY distance EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.skip_ok(4)?;
+	// The HNSW search continues until enough matching records are found
+	t.expect_val(
+		"[
+			{ id: doc:50, distance: 49f },
+			{ id: doc:100, distance: 99f },
+			{ id: doc:150, distance: 149f }
+		]",
+	)?;
+	t.expect_val(
+		r#"[
+			{
+				detail: {
+					plan: {
+						filter: "category = 'legal'",
+						index: 'hn_emb',
+						operator: '<|3,4|>',
+						value: [1]
+					},
+					table: 'doc',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]"#,
+	)?;
+	// The MTREE search skips the records which don't match the condition
+	t.expect_val(
+		"[
+			{ id: note:50, distance: 49f },
+			{ id: note:100, distance: 99f },
+			{ id: note:150, distance: 149f }
+		]",
+	)?;
+	t.expect_val(
+		r#"[
+			{
+				detail: {
+					plan: {
+						filter: "tags CONTAINS 'legal'",
+						index: 'mt_emb',
+						operator: '<|3|>',
+						value: [1]
+					},
+					table: 'note',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]"#,
+	)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn check_hnsw_persistence() -> Result<()> {
 	let sql = r"
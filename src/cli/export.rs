@@ -5,7 +5,7 @@ use clap::Args;
 use futures_util::StreamExt;
 use surrealdb::Connection;
 use surrealdb::engine::any::{self, connect};
-use surrealdb::kvs::export::TableConfig;
+use surrealdb::kvs::export::{ExportFormat, TableConfig};
 use surrealdb::method::{Export, ExportConfig};
 use tokio::io::{self, AsyncWriteExt};
 
@@ -47,6 +47,21 @@ struct ExportConfigArguments {
 	/// Whether records should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	records: Option<bool>,
+	/// Whether definitions should be exported, apart from those which exported records depend on
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	definitions: Option<bool>,
+	/// Whether the changefeed clauses of table definitions should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	changefeeds: Option<bool>,
+	/// Only export the records of a table which match a condition, in the form `table=condition`
+	#[arg(long = "where", value_name = "TABLE=CONDITION", value_parser = super::validator::export_condition)]
+	conditions: Vec<(String, String)>,
+	/// Whether to export in the binary format, which is faster to import
+	#[arg(long)]
+	binary: bool,
+	/// Whether to compress the binary export with zstd
+	#[arg(long, requires = "binary")]
+	compress: bool,
 }
 
 #[derive(Args, Debug)]
@@ -122,7 +137,7 @@ pub async fn init(
 	debug!("Exporting data from the database");
 	if file == "-" {
 		// Prepare the backup
-		let mut backup = apply_config(config, client.export(())).await?;
+		let mut backup = apply_config(config, client.export(()))?.await?;
 		// Get a handle to standard output
 		let mut stdout = io::stdout();
 		// Write the backup to standard output
@@ -130,7 +145,7 @@ pub async fn init(
 			stdout.write_all(&bytes?).await?;
 		}
 	} else {
-		apply_config(config, client.export(file)).await?;
+		apply_config(config, client.export(file))?.await?;
 	}
 	info!("The SurrealQL file was exported successfully");
 	// Everything OK
@@ -140,7 +155,7 @@ pub async fn init(
 fn apply_config<C: Connection, R>(
 	config: ExportConfigArguments,
 	export: Export<C, R>,
-) -> Export<C, R, ExportConfig> {
+) -> Result<Export<C, R, ExportConfig>> {
 	let mut export = export.with_config();
 
 	if config.only {
@@ -187,5 +202,21 @@ fn apply_config<C: Connection, R>(
 		export = export.records(value);
 	}
 
-	export
+	if let Some(value) = config.definitions {
+		export = export.definitions(value);
+	}
+
+	if let Some(value) = config.changefeeds {
+		export = export.changefeeds(value);
+	}
+
+	for (table, cond) in config.conditions {
+		export = export.condition(&table, &cond)?;
+	}
+
+	if config.binary {
+		export = export.format(ExportFormat::Binary).compression(config.compress);
+	}
+
+	Ok(export)
 }
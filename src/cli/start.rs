@@ -67,6 +67,34 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_CHANGEFEED_GC_INTERVAL", long = "changefeed-gc-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "10s")]
 	changefeed_gc_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove records which have expired",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_RECORD_EXPIRY_INTERVAL", long = "record-expiry-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	record_expiry_interval: Duration,
+	#[arg(
+		help = "The interval at which to send the pending outbox messages",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_OUTBOX_DELIVERY_INTERVAL", long = "outbox-delivery-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	outbox_delivery_interval: Duration,
+	#[arg(
+		help = "The interval at which to run the schedules which are due",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SCHEDULE_INTERVAL", long = "schedule-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "5s")]
+	schedule_interval: Duration,
+	#[arg(
+		help = "The maximum duration to wait for running queries and transactions to finish when shutting down",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SHUTDOWN_GRACE_PERIOD", long = "shutdown-grace-period", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	shutdown_grace_period: Duration,
 	//
 	// Authentication
 	//
@@ -164,6 +192,10 @@ pub async fn init(
 		node_membership_check_interval,
 		node_membership_cleanup_interval,
 		changefeed_gc_interval,
+		record_expiry_interval,
+		outbox_delivery_interval,
+		schedule_interval,
+		shutdown_grace_period,
 		no_banner,
 		no_identification_headers,
 		..
@@ -191,7 +223,10 @@ pub async fn init(
 		.with_node_membership_refresh_interval(node_membership_refresh_interval)
 		.with_node_membership_check_interval(node_membership_check_interval)
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
-		.with_changefeed_gc_interval(changefeed_gc_interval);
+		.with_changefeed_gc_interval(changefeed_gc_interval)
+		.with_record_expiry_interval(record_expiry_interval)
+		.with_outbox_delivery_interval(outbox_delivery_interval)
+		.with_schedule_interval(schedule_interval);
 	// Configure the config
 	let config = Config {
 		bind: listen_addresses.first().copied().unwrap(),
@@ -200,6 +235,7 @@ pub async fn init(
 		user,
 		pass,
 		no_identification_headers,
+		shutdown_grace_period,
 		engine,
 		crt,
 		key,
@@ -226,7 +262,7 @@ pub async fn init(
 	// Wait for background tasks to finish
 	nodetasks.resolve().await?;
 	// Shutdown the datastore
-	datastore.shutdown().await?;
+	datastore.shutdown(shutdown_grace_period).await?;
 	// All ok
 	Ok(())
 }
@@ -7,9 +7,10 @@ use std::{
 
 use surrealdb::dbs::capabilities::{
 	ArbitraryQueryTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget,
-	Targets,
+	SleepTarget, Targets,
 };
 use surrealdb::kvs::export::TableConfig;
+use surrealdb_core::str::ParseBytes;
 
 pub(crate) mod parser;
 
@@ -149,6 +150,20 @@ pub(crate) fn query_arbitrary_targets(
 	Ok(Targets::Some(result))
 }
 
+pub(crate) fn sleep_targets(value: &str) -> Result<Targets<SleepTarget>, String> {
+	if ["*", ""].contains(&value) {
+		return Ok(Targets::All);
+	}
+
+	let mut result = HashSet::new();
+
+	for target in value.split(',').filter(|s| !s.is_empty()) {
+		result.insert(SleepTarget::from_str(target).map_err(|e| e.to_string())?);
+	}
+
+	Ok(Targets::Some(result))
+}
+
 pub(crate) fn method_targets(value: &str) -> Result<Targets<MethodTarget>, String> {
 	if ["*", ""].contains(&value) {
 		return Ok(Targets::All);
@@ -189,6 +204,26 @@ pub(crate) fn export_tables(value: &str) -> Result<TableConfig, String> {
 	Ok(TableConfig::Some(value.split(",").filter(|s| !s.is_empty()).map(str::to_string).collect()))
 }
 
+pub(crate) fn export_condition(value: &str) -> Result<(String, String), String> {
+	match value.split_once('=') {
+		Some((table, cond)) if !table.trim().is_empty() && !cond.trim().is_empty() => {
+			Ok((table.trim().to_owned(), cond.trim().to_owned()))
+		}
+		_ => Err("Expected a table and a condition, in the form `table=condition`".to_owned()),
+	}
+}
+
+pub(crate) fn bytes(value: &str) -> Result<u64, String> {
+	value.parse_bytes::<u64>().map_err(|e| format!("invalid size: {e}"))
+}
+
+pub(crate) fn namespace_bytes(value: &str) -> Result<(String, u64), String> {
+	match value.split_once('=') {
+		Some((ns, size)) if !ns.trim().is_empty() => Ok((ns.trim().to_owned(), bytes(size)?)),
+		_ => Err("Expected a namespace and a size, in the form `namespace=size`".to_owned()),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -299,4 +334,43 @@ mod tests {
 			)
 		);
 	}
+
+	#[test]
+	fn test_sleep_targets() {
+		assert_eq!(sleep_targets("*").unwrap(), Targets::<SleepTarget>::All);
+		assert_eq!(sleep_targets("").unwrap(), Targets::<SleepTarget>::All);
+		assert_eq!(
+			sleep_targets("root,namespace").unwrap(),
+			Targets::<SleepTarget>::Some(
+				vec![SleepTarget::Root, SleepTarget::Namespace].into_iter().collect()
+			)
+		);
+		assert!(sleep_targets("system").is_err());
+	}
+
+	#[test]
+	fn test_namespace_bytes() {
+		assert_eq!(bytes("512").unwrap(), 512);
+		assert_eq!(bytes("64MiB").unwrap(), 64 * 1024 * 1024);
+		assert!(bytes("lots").is_err());
+		assert_eq!(namespace_bytes("test=1KiB").unwrap(), ("test".to_owned(), 1024));
+		assert!(namespace_bytes("test").is_err());
+		assert!(namespace_bytes("=1KiB").is_err());
+		assert!(namespace_bytes("test=lots").is_err());
+	}
+
+	#[test]
+	fn test_export_condition() {
+		assert_eq!(
+			export_condition("user=tenant = 'acme'").unwrap(),
+			("user".to_owned(), "tenant = 'acme'".to_owned())
+		);
+		assert_eq!(
+			export_condition(" user = age > 18 ").unwrap(),
+			("user".to_owned(), "age > 18".to_owned())
+		);
+		assert!(export_condition("user").is_err());
+		assert!(export_condition("=age > 18").is_err());
+		assert!(export_condition("user=").is_err());
+	}
 }
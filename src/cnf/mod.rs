@@ -41,6 +41,16 @@ pub static HTTP_MAX_ML_BODY_SIZE: LazyLock<usize> =
 pub static HTTP_MAX_SQL_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_SQL_BODY_SIZE", usize, 1 << 20);
 
+/// The maximum number of rows in each Arrow record batch returned by the HTTP /sql endpoint (default: 8192)
+#[cfg(feature = "arrow")]
+pub static HTTP_ARROW_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_HTTP_ARROW_BATCH_SIZE", usize, 8192);
+
+/// The number of rows sampled to infer the Arrow schema of results from the HTTP /sql endpoint (default: 1000)
+#[cfg(feature = "arrow")]
+pub static HTTP_ARROW_INFER_ROWS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_HTTP_ARROW_INFER_ROWS", usize, 1000);
+
 /// The maximum HTTP body size of the HTTP /api endpoint (default: 1 MiB)
 pub static HTTP_MAX_API_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_API_BODY_SIZE", usize, 4 << 20);
@@ -6,10 +6,13 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::dbs::Session;
+use surrealdb::dbs::SlowQueryLog;
 use surrealdb::dbs::capabilities::{
-	ArbitraryQueryTarget, Capabilities, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget,
-	RouteTarget, Targets,
+	ArbitraryQueryTarget, Capabilities, DEFAULT_AUDIT_LOG_SIZE, DEFAULT_AUTH_ATTEMPTS_WINDOW,
+	DEFAULT_MAX_HTTP_RETRIES, DEFAULT_PREPARED_QUERIES, DEFAULT_SORT_MEMORY, ExperimentalTarget,
+	FuncTarget, LiveQueryOverflow, MethodTarget, NetTarget, RouteTarget, SleepTarget, Targets,
 };
+use surrealdb::iam::throttle::AuthThrottleLimits;
 use surrealdb::kvs::Datastore;
 use surrealdb::opt::capabilities::Capabilities as SdkCapabilities;
 
@@ -29,6 +32,13 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TRANSACTION_TIMEOUT", long)]
 	#[arg(value_parser = super::cli::validator::duration)]
 	transaction_timeout: Option<Duration>,
+	#[arg(help = "Log the statements which run for at least this duration")]
+	#[arg(env = "SURREAL_SLOW_QUERY_THRESHOLD", long)]
+	#[arg(value_parser = super::cli::validator::duration)]
+	slow_query_threshold: Option<Duration>,
+	#[arg(help = "Also store the slow statements as records in this table of their database")]
+	#[arg(env = "SURREAL_SLOW_QUERY_TABLE", long, requires = "slow_query_threshold")]
+	slow_query_table: Option<String>,
 	#[arg(help = "Whether to allow unauthenticated access", help_heading = "Authentication")]
 	#[arg(env = "SURREAL_UNAUTHENTICATED", long = "unauthenticated")]
 	#[arg(default_value_t = false)]
@@ -36,7 +46,9 @@ pub struct StartCommandDbsOptions {
 	#[command(flatten)]
 	#[command(next_help_heading = "Capabilities")]
 	capabilities: DbsCapabilities,
-	#[arg(help = "Sets the directory for storing temporary database files")]
+	#[arg(
+		help = "Sets the directory for storing temporary database files, such as the sorted runs of large ORDER BY clauses"
+	)]
 	#[arg(env = "SURREAL_TEMPORARY_DIRECTORY", long = "temporary-directory")]
 	#[arg(value_parser = super::cli::validator::dir_exists)]
 	temporary_directory: Option<PathBuf>,
@@ -219,6 +231,125 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	#[arg(default_missing_value_os = "", num_args = 0..)]
 	#[arg(value_parser = super::cli::validator::route_targets)]
 	deny_http: Option<Targets<RouteTarget>>,
+
+	#[arg(
+		help = "The maximum duration that a single SLEEP statement or sleep() function call can run for"
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_SLEEP", long)]
+	#[arg(value_parser = super::cli::validator::duration)]
+	max_sleep: Option<Duration>,
+
+	#[arg(help = "The maximum total duration that a single query can sleep for")]
+	#[arg(env = "SURREAL_CAPS_MAX_QUERY_SLEEP", long)]
+	#[arg(value_parser = super::cli::validator::duration)]
+	max_query_sleep: Option<Duration>,
+
+	#[arg(
+		help = "Allow certain user levels to sleep without the maximum sleep limits. Defaults to root users only.",
+		long_help = r#"Allow certain user levels to sleep without the maximum sleep limits. Defaults to root users only. Alternatively, you can provide a comma-separated list of user levels
+User levels must be one of "guest", "record", "database", "namespace" or "root".
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_UNLIMITED_SLEEP", long)]
+	// If the arg is provided without value, then assume it's "", which gets parsed into Targets::All
+	#[arg(default_missing_value_os = "", num_args = 0..)]
+	#[arg(value_parser = super::cli::validator::sleep_targets)]
+	unlimited_sleep: Option<Targets<SleepTarget>>,
+
+	#[arg(
+		help = "The maximum number of notifications which can be queued for each live query on a WebSocket connection. Unbounded by default.",
+		long_help = r#"The maximum number of notifications which can be queued for each live query on a WebSocket connection. Unbounded by default.
+Each connection can ask for a smaller queue, and a different overflow policy, with the "surreal-live-query-queue" and "surreal-live-query-overflow" headers.
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_LIVE_QUERY_QUEUE_SIZE", long)]
+	live_query_queue_size: Option<usize>,
+
+	#[arg(
+		help = "What happens when the bounded notification queue of a live query is full. Defaults to killing the live query.",
+		long_help = r#"What happens when the bounded notification queue of a live query is full. Defaults to killing the live query.
+The policy must be one of "drop_oldest", "drop_newest" or "kill". When the live query is killed, the client receives a KILLED notification explaining that it fell behind, and should re-sync before starting a new live query.
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_LIVE_QUERY_OVERFLOW", long)]
+	live_query_overflow: Option<LiveQueryOverflow>,
+
+	#[arg(
+		help = "The number of failed signin or signup attempts allowed for the same user and client within the attempts window. Disabled by default."
+	)]
+	#[arg(env = "SURREAL_CAPS_AUTH_ATTEMPTS", long)]
+	#[arg(value_parser = clap::value_parser!(u32).range(1..))]
+	auth_attempts: Option<u32>,
+
+	#[arg(
+		help = "The sliding window over which the failed authentication attempts are counted. Defaults to 1 minute."
+	)]
+	#[arg(env = "SURREAL_CAPS_AUTH_ATTEMPTS_WINDOW", long)]
+	#[arg(value_parser = super::cli::validator::duration)]
+	auth_attempts_window: Option<Duration>,
+
+	#[arg(
+		help = "The number of prepared queries which are kept on each WebSocket connection. Prepared queries are disabled when set to 0. Defaults to 100."
+	)]
+	#[arg(env = "SURREAL_CAPS_PREPARED_QUERIES", long)]
+	prepared_queries: Option<usize>,
+
+	#[arg(
+		help = "The estimated memory which a single statement can hold in its intermediate results, such as the rows which are grouped or sorted, before it fails. Unlimited by default."
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_QUERY_MEMORY", long)]
+	#[arg(value_parser = super::cli::validator::bytes)]
+	max_query_memory: Option<u64>,
+
+	#[arg(
+		help = "The estimated memory which a single statement can hold in a namespace, overriding the limit for all namespaces",
+		long_help = r#"The estimated memory which a single statement can hold in a namespace, overriding the limit for all namespaces. This can be specified multiple times, or as a comma-separated list in the environment variable.
+Each limit must be in the form "namespace=size", for example "analytics=1GiB".
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_NAMESPACE_MAX_QUERY_MEMORY", long, value_delimiter = ',')]
+	#[arg(value_name = "NAMESPACE=SIZE", value_parser = super::cli::validator::namespace_bytes)]
+	namespace_max_query_memory: Vec<(String, u64)>,
+
+	#[arg(
+		help = "The estimated memory which an ORDER BY clause sorts in memory. When a temporary directory is set, larger results are sorted in runs which are written to the temporary directory and merged. Defaults to 64MiB."
+	)]
+	#[arg(env = "SURREAL_CAPS_SORT_MEMORY", long)]
+	#[arg(value_parser = super::cli::validator::bytes)]
+	sort_memory: Option<u64>,
+
+	#[arg(
+		help = "The maximum size of the runs which an ORDER BY clause can write to the temporary directory, before the statement fails. Unlimited by default."
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_SORT_SPILL", long)]
+	#[arg(value_parser = super::cli::validator::bytes)]
+	max_sort_spill: Option<u64>,
+
+	#[arg(
+		help = "The number of entries which are kept in the audit log of definition changes of each namespace, after which the oldest entries are removed. A value of 0 disables the audit log. Defaults to 10000."
+	)]
+	#[arg(env = "SURREAL_CAPS_AUDIT_LOG_SIZE", long)]
+	audit_log_size: Option<usize>,
+
+	#[arg(
+		help = "The maximum duration that a single http function request can take. Unlimited by default."
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_HTTP_TIMEOUT", long)]
+	#[arg(value_parser = super::cli::validator::duration)]
+	max_http_timeout: Option<Duration>,
+
+	#[arg(
+		help = "The maximum number of times that a failed http function request can be retried. Defaults to 5."
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_HTTP_RETRIES", long)]
+	max_http_retries: Option<u32>,
+
+	#[arg(
+		help = "The maximum size of the response body which an http function accepts. Unlimited by default."
+	)]
+	#[arg(env = "SURREAL_CAPS_MAX_HTTP_BODY", long)]
+	#[arg(value_parser = super::cli::validator::bytes)]
+	max_http_body: Option<u64>,
 }
 
 impl DbsCapabilities {
@@ -462,12 +593,56 @@ impl DbsCapabilities {
 		}
 	}
 
+	fn get_unlimited_sleep(&self) -> Targets<SleepTarget> {
+		match &self.unlimited_sleep {
+			Some(targets) => targets.clone(),
+			None => Targets::from(SleepTarget::Root),
+		}
+	}
+
+	fn get_live_query_queue_size(&self) -> Option<usize> {
+		// A queue must be able to hold at least one notification
+		self.live_query_queue_size.map(|v| v.max(1))
+	}
+
+	fn get_live_query_overflow(&self) -> LiveQueryOverflow {
+		self.live_query_overflow.unwrap_or_default()
+	}
+
+	fn get_auth_throttle(&self) -> Option<AuthThrottleLimits> {
+		// Throttling is only enabled when a number of attempts is provided
+		self.auth_attempts.map(|attempts| AuthThrottleLimits {
+			attempts,
+			window: self.auth_attempts_window.unwrap_or(DEFAULT_AUTH_ATTEMPTS_WINDOW),
+		})
+	}
+
+	fn get_prepared_queries(&self) -> usize {
+		self.prepared_queries.unwrap_or(DEFAULT_PREPARED_QUERIES)
+	}
+
+	fn get_sort_memory(&self) -> u64 {
+		self.sort_memory.unwrap_or(DEFAULT_SORT_MEMORY)
+	}
+
+	fn get_audit_log_size(&self) -> usize {
+		self.audit_log_size.unwrap_or(DEFAULT_AUDIT_LOG_SIZE)
+	}
+
+	fn get_max_http_retries(&self) -> u32 {
+		self.max_http_retries.unwrap_or(DEFAULT_MAX_HTTP_RETRIES)
+	}
+
 	pub fn into_cli_capabilities(self) -> Capabilities {
 		merge_capabilities(SdkCapabilities::all().into(), self)
 	}
 }
 
 fn merge_capabilities(initial: Capabilities, caps: DbsCapabilities) -> Capabilities {
+	let initial = caps
+		.namespace_max_query_memory
+		.iter()
+		.fold(initial, |acc, (ns, limit)| acc.with_namespace_max_query_memory(ns, *limit));
 	initial
 		.with_scripting(caps.get_scripting())
 		.with_guest_access(caps.get_allow_guests())
@@ -483,6 +658,20 @@ fn merge_capabilities(initial: Capabilities, caps: DbsCapabilities) -> Capabilit
 		.without_experimental(caps.get_deny_experimental())
 		.with_arbitrary_query(caps.get_allow_arbitrary_query())
 		.without_arbitrary_query(caps.get_deny_arbitrary_query())
+		.with_max_sleep(caps.max_sleep)
+		.with_max_query_sleep(caps.max_query_sleep)
+		.with_unlimited_sleep(caps.get_unlimited_sleep())
+		.with_live_query_queue_size(caps.get_live_query_queue_size())
+		.with_live_query_overflow(caps.get_live_query_overflow())
+		.with_auth_throttle(caps.get_auth_throttle())
+		.with_prepared_queries(caps.get_prepared_queries())
+		.with_max_query_memory(caps.max_query_memory)
+		.with_sort_memory(caps.get_sort_memory())
+		.with_max_sort_spill(caps.max_sort_spill)
+		.with_audit_log_size(caps.get_audit_log_size())
+		.with_max_http_timeout(caps.max_http_timeout)
+		.with_max_http_retries(caps.get_max_http_retries())
+		.with_max_http_body(caps.max_http_body)
 }
 
 impl From<DbsCapabilities> for Capabilities {
@@ -498,6 +687,8 @@ pub async fn init(
 		strict_mode,
 		query_timeout,
 		transaction_timeout,
+		slow_query_threshold,
+		slow_query_table,
 		unauthenticated,
 		capabilities,
 		temporary_directory,
@@ -516,6 +707,14 @@ pub async fn init(
 	if let Some(v) = transaction_timeout {
 		debug!("Maximum transaction processing timeout is {v:?}");
 	}
+	// Configure the slow query log
+	let slow_query_log = slow_query_threshold.map(|v| {
+		debug!("Logging the statements which run for at least {v:?}");
+		match slow_query_table {
+			Some(tb) => SlowQueryLog::new(v).with_table(tb),
+			None => SlowQueryLog::new(v),
+		}
+	});
 	// Log whether authentication is disabled
 	if unauthenticated {
 		warn!(
@@ -539,6 +738,7 @@ pub async fn init(
 		.with_strict_mode(strict_mode)
 		.with_query_timeout(query_timeout)
 		.with_transaction_timeout(transaction_timeout)
+		.with_slow_query_log(slow_query_log)
 		.with_auth_enabled(!unauthenticated)
 		.with_temporary_directory(temporary_directory)
 		.with_capabilities(capabilities);
@@ -1065,6 +1265,22 @@ mod tests {
 			deny_net: None,
 			deny_rpc: None,
 			deny_http: None,
+			max_sleep: None,
+			max_query_sleep: None,
+			unlimited_sleep: None,
+			live_query_queue_size: None,
+			live_query_overflow: None,
+			auth_attempts: None,
+			auth_attempts_window: None,
+			prepared_queries: None,
+			max_query_memory: None,
+			namespace_max_query_memory: vec![],
+			sort_memory: None,
+			max_sort_spill: None,
+			audit_log_size: None,
+			max_http_timeout: None,
+			max_http_retries: None,
+			max_http_body: None,
 		};
 		assert_eq!(caps.get_allow_experimental(), Targets::All);
 		assert_eq!(caps.get_allow_arbitrary_query(), Targets::All);
@@ -79,8 +79,11 @@ where
 				);
 			}
 
-			let session =
-				req.extensions().get::<Session>().expect("session extractor should always succeed");
+			let session = req
+				.extensions()
+				.get::<Session>()
+				.cloned()
+				.expect("session extractor should always succeed");
 
 			let Some(_ns) = session.ns.as_ref() else {
 				return Ok(to_rejection(resolver_error("No namespace specified")).into_response());
@@ -98,7 +101,7 @@ where
 				debug_assert!(Arc::ptr_eq(&state.datastore, &cache.datastore));
 			}
 
-			let executor = match cache.get_schema(session).await {
+			let executor = match cache.get_schema(&session).await {
 				Ok(e) => e,
 				Err(e) => {
 					info!(?e, "error generating schema");
@@ -117,7 +120,9 @@ where
 					Ok(req) => req,
 					Err(err) => return Ok(err.into_response()),
 				};
-				let stream = Executor::execute_stream(&executor, req.0, None);
+				// Resolvers execute the request as the current session
+				let req = req.0.data(session.clone());
+				let stream = Executor::execute_stream(&executor, req, None);
 				let body = Body::from_stream(
 					create_multipart_mixed_stream(stream, Duration::from_secs(30))
 						.map(Ok::<_, std::io::Error>),
@@ -132,7 +137,8 @@ where
 						Ok(req) => req,
 						Err(err) => return Ok(err.into_response()),
 					};
-				Ok(GraphQLResponse(executor.execute_batch(req.0).await).into_response())
+				let req = req.0.data(session.clone());
+				Ok(GraphQLResponse(executor.execute_batch(req).await).into_response())
 			}
 		})
 	}
@@ -2,7 +2,8 @@ use axum::{
 	Json,
 	response::{IntoResponse, Response},
 };
-use http::{HeaderName, StatusCode};
+use http::header::RETRY_AFTER;
+use http::{HeaderName, HeaderValue, StatusCode};
 use opentelemetry::global::Error as OpentelemetryError;
 use serde::{Serialize, Serializer};
 use std::error::Error as StdError;
@@ -140,6 +141,16 @@ impl IntoResponse for ResponseError {
 						description: Some("Your authentication details are invalid. Reauthenticate using valid authentication parameters.".to_string()),
 						information: Some("There was a problem with authentication".to_string())
 					}.into_response(),
+					surrealdb_core::err::Error::TooManyAuthAttempts{ retry_after } => {
+						let mut res = ErrorMessage{
+							code: StatusCode::TOO_MANY_REQUESTS,
+							details: Some("Too many requests".to_string()),
+							description: Some("Too many authentication attempts have failed recently. Retry after the specified duration.".to_string()),
+							information: Some(e.to_string()),
+						}.into_response();
+						res.headers_mut().insert(RETRY_AFTER, HeaderValue::from(*retry_after));
+						res
+					},
 					surrealdb_core::err::Error::IamError(SurrealIamError::NotAllowed{ .. }) => ErrorMessage{
 						code: StatusCode::FORBIDDEN,
 						details: Some("Forbidden".to_string()),
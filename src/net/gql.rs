@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use axum::routing::post_service;
 
-use surrealdb::gql::cache::Pessimistic;
+use surrealdb::gql::cache::Definitions;
 use surrealdb::kvs::Datastore;
 
 use crate::gql::GraphQL;
@@ -12,6 +12,6 @@ pub(super) fn router<S>(ds: Arc<Datastore>) -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	let service = GraphQL::new(Pessimistic, ds);
+	let service = GraphQL::new(Definitions, ds);
 	Router::new().route("/graphql", post_service(service))
 }
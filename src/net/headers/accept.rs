@@ -8,8 +8,12 @@ use http::HeaderValue;
 pub enum Accept {
 	TextPlain,
 	ApplicationJson,
+	ApplicationNdjson,
 	ApplicationCbor,
+	ApplicationMsgpack,
 	ApplicationOctetStream,
+	ApplicationArrowStream,
+	ApplicationParquet,
 	Surrealdb,
 }
 
@@ -18,8 +22,12 @@ impl std::fmt::Display for Accept {
 		match self {
 			Accept::TextPlain => write!(f, "text/plain"),
 			Accept::ApplicationJson => write!(f, "application/json"),
+			Accept::ApplicationNdjson => write!(f, "application/x-ndjson"),
 			Accept::ApplicationCbor => write!(f, "application/cbor"),
+			Accept::ApplicationMsgpack => write!(f, "application/msgpack"),
 			Accept::ApplicationOctetStream => write!(f, "application/octet-stream"),
+			Accept::ApplicationArrowStream => write!(f, "application/vnd.apache.arrow.stream"),
+			Accept::ApplicationParquet => write!(f, "application/vnd.apache.parquet"),
 			Accept::Surrealdb => write!(f, "application/surrealdb"),
 		}
 	}
@@ -41,8 +49,12 @@ impl Header for Accept {
 		match parts[0] {
 			"text/plain" => Ok(Accept::TextPlain),
 			"application/json" => Ok(Accept::ApplicationJson),
+			"application/x-ndjson" => Ok(Accept::ApplicationNdjson),
 			"application/cbor" => Ok(Accept::ApplicationCbor),
+			"application/msgpack" => Ok(Accept::ApplicationMsgpack),
 			"application/octet-stream" => Ok(Accept::ApplicationOctetStream),
+			"application/vnd.apache.arrow.stream" => Ok(Accept::ApplicationArrowStream),
+			"application/vnd.apache.parquet" => Ok(Accept::ApplicationParquet),
 			"application/surrealdb" => Ok(Accept::Surrealdb),
 			// TODO: Support more (all?) mime-types
 			_ => Err(headers::Error::invalid()),
@@ -199,7 +199,8 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<()> {
 	let rpc_state = Arc::new(RpcState::new());
 
 	// Setup the graceful shutdown handler
-	let shutdown_handler = graceful_shutdown(rpc_state.clone(), ct.clone(), handle.clone());
+	let shutdown_handler =
+		graceful_shutdown(ds.clone(), rpc_state.clone(), ct.clone(), handle.clone());
 
 	let axum_app = axum_app.with_state(rpc_state.clone());
 
@@ -12,7 +12,7 @@ use crate::net::error::Error as NetError;
 use crate::rpc::RpcState;
 use crate::rpc::format::HttpFormat;
 use crate::rpc::http::Http;
-use crate::rpc::response::IntoRpcResponse;
+use crate::rpc::response::{IntoRpcResponse, Response, failure, success};
 use crate::rpc::websocket::Websocket;
 use axum::extract::DefaultBodyLimit;
 use axum::extract::State;
@@ -25,15 +25,20 @@ use axum::{
 use axum_extra::TypedHeader;
 use axum_extra::headers::Header;
 use bytes::Bytes;
+use futures::Stream;
 use http::HeaderMap;
 use http::header::SEC_WEBSOCKET_PROTOCOL;
 use surrealdb::dbs::Session;
-use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::dbs::capabilities::{LiveQueryOverflow, RouteTarget};
+use surrealdb::headers::{LIVE_QUERY_OVERFLOW, LIVE_QUERY_QUEUE};
 use surrealdb::kvs::Datastore;
 use surrealdb::mem::ALLOC;
 use surrealdb::rpc::RpcContext;
 use surrealdb::rpc::format::Format;
 use surrealdb::rpc::format::PROTOCOLS;
+use surrealdb::rpc::is_streamed_query;
+use surrealdb::rpc::request::Request;
+use tokio::sync::mpsc::channel;
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::request_id::RequestId;
 use uuid::Uuid;
@@ -103,8 +108,35 @@ async fn get_handler(
 			},
 		},
 	};
+	// Get the capabilities of the datastore
+	let caps = db.get_capabilities();
+	// Check if a live query queue size header is specified
+	let queue = match headers.get(&LIVE_QUERY_QUEUE) {
+		Some(v) => match v.to_str().ok().and_then(|v| v.parse::<usize>().ok()) {
+			// A queue must be able to hold at least one notification
+			Some(v) => Some(v.max(1)),
+			None => return Err(NetError::Request),
+		},
+		None => None,
+	};
+	// Check if a live query overflow policy header is specified
+	let overflow = match headers.get(&LIVE_QUERY_OVERFLOW) {
+		Some(v) => match v.to_str().ok().and_then(|v| v.parse::<LiveQueryOverflow>().ok()) {
+			Some(v) => v,
+			None => return Err(NetError::Request),
+		},
+		None => caps.live_query_overflow(),
+	};
+	// The connection can't queue more than the capabilities allow
+	session.live_query_queue_size = match (queue, caps.live_query_queue_size()) {
+		(Some(v), Some(max)) => Some(v.min(max)),
+		(v, max) => v.or(max),
+	};
+	session.live_query_overflow = overflow;
 	// This session supports live queries
 	session.rt = true;
+	// Store the endpoint type in session
+	session.ep = Some("ws".to_string());
 	// Store the connection id in session
 	session.id = Some(id.to_string());
 	// Check if a connection with this id already exists
@@ -149,7 +181,7 @@ async fn handle_socket(
 
 async fn post_handler(
 	Extension(state): Extension<AppState>,
-	Extension(session): Extension<Session>,
+	Extension(mut session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
 	content_type: TypedHeader<ContentType>,
 	body: Bytes,
@@ -175,6 +207,8 @@ async fn post_handler(
 			return Err(NetError::InvalidType.into());
 		}
 	}
+	// Store the endpoint type in session
+	session.ep = Some("http".to_string());
 	// Create a new HTTP instance
 	let rpc = Http::new(&state.datastore, session);
 	// Check to see available memory
@@ -184,6 +218,10 @@ async fn post_handler(
 	// Parse the HTTP request body
 	match fmt.req_http(body) {
 		Ok(req) => {
+			// Send the results of a streamed query in chunks, as they are produced
+			if is_streamed_query(req.version, req.method, &req.params) {
+				return Ok(fmt.res_http_stream(streamed(rpc, req)));
+			}
 			// Execute the specified method
 			let res = RpcContext::execute(&rpc, req.version, req.txn, req.method, req.params).await;
 			// Return the HTTP response
@@ -192,3 +230,23 @@ async fn post_handler(
 		Err(err) => Err(err.into()),
 	}
 }
+
+/// Executes a query whose results are streamed, returning a response for
+/// each frame of the results. The query is cancelled if the client
+/// disconnects, as the responses are then no longer received.
+fn streamed(rpc: Http, req: Request) -> impl Stream<Item = Response> + Send + 'static {
+	let (send, recv) = channel(1);
+	let task = tokio::spawn(async move {
+		RpcContext::execute_streamed(&rpc, req.version, req.txn, req.params, send).await
+	});
+	futures::stream::unfold((recv, Some(task)), |(mut recv, task)| async move {
+		if let Some(frame) = recv.recv().await {
+			return Some((success(None, frame), (recv, task)));
+		}
+		// Return an error which happened before any frame was sent
+		match task?.await {
+			Ok(Err(err)) => Some((failure(None, err.into()), (recv, None))),
+			_ => None,
+		}
+	})
+}
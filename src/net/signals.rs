@@ -1,18 +1,22 @@
+use crate::cli::CF;
 use crate::rpc::{self, RpcState};
 use crate::telemetry;
 use anyhow::Result;
 use axum_server::Handle;
 use std::sync::Arc;
+use surrealdb::kvs::Datastore;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
 /// Start a graceful shutdown:
 /// * Signal the Axum Handle when a shutdown signal is received.
+/// * Drain the running queries and transactions, for up to the grace period.
 /// * Stop all WebSocket connections.
 /// * Flush all telemetry data.
 ///
 /// A second signal will force an immediate shutdown.
 pub fn graceful_shutdown(
+	ds: Arc<Datastore>,
 	state: Arc<RpcState>,
 	canceller: CancellationToken,
 	http_handle: Handle,
@@ -37,14 +41,16 @@ pub fn graceful_shutdown(
 			let state = state.clone();
 			// Spawn a background task
 			tokio::spawn(async move {
+				// Get the shutdown grace period
+				let grace = CF.get().unwrap().shutdown_grace_period;
 				// Stop accepting new HTTP connections
-				http_handle.graceful_shutdown(None);
+				http_handle.graceful_shutdown(Some(grace));
+				// Drain the datastore, and close the WebSocket connections
+				rpc::graceful_shutdown(ds, state, grace).await;
 				// Wait for all connections to close
 				while http_handle.connection_count() > 0 {
 					tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 				}
-				// Stop accepting new WebSocket connections
-				rpc::graceful_shutdown(state).await;
 				// Cancel the cancellation token
 				canceller.cancel();
 				// Flush all telemetry data
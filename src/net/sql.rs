@@ -3,6 +3,8 @@ use super::error::ResponseError;
 use super::headers::Accept;
 use super::output::Output;
 use crate::cnf::HTTP_MAX_SQL_BODY_SIZE;
+#[cfg(feature = "arrow")]
+use crate::cnf::{HTTP_ARROW_BATCH_SIZE, HTTP_ARROW_INFER_ROWS};
 use crate::net::error::Error as NetError;
 use crate::net::input::bytes_to_utf8;
 use crate::net::output;
@@ -10,18 +12,25 @@ use crate::net::params::Params;
 use anyhow::Context;
 use axum::Extension;
 use axum::Router;
+use axum::body::Body;
 use axum::extract::DefaultBodyLimit;
 use axum::extract::Query;
 use axum::extract::WebSocketUpgrade;
 use axum::extract::ws::Message;
 use axum::extract::ws::WebSocket;
 use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::options;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
+use http::header::{CONTENT_TYPE, HeaderValue};
+use serde_json::{Value as Json, json};
 use surrealdb::dbs::Session;
 use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::syn;
+#[cfg(feature = "arrow")]
+use surrealdb_core::arrow;
 use tower_http::limit::RequestBodyLimitLayer;
 
 pub(super) fn router<S>() -> Router<S>
@@ -40,7 +49,7 @@ async fn post_handler(
 	output: Option<TypedHeader<Accept>>,
 	params: Query<Params>,
 	sql: Bytes,
-) -> Result<Output, ResponseError> {
+) -> Result<Response, ResponseError> {
 	// Get a database reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
@@ -54,18 +63,93 @@ async fn post_handler(
 	}
 	// Convert the received sql query
 	let sql = bytes_to_utf8(&sql).context("Non UTF-8 request body").map_err(ResponseError)?;
+	// Columnar results are converted from the result of a single statement
+	#[cfg(feature = "arrow")]
+	if let Some(Accept::ApplicationArrowStream | Accept::ApplicationParquet) = output.as_deref() {
+		let format = match output.as_deref() {
+			Some(Accept::ApplicationParquet) => arrow::Format::Parquet,
+			_ => arrow::Format::Ipc,
+		};
+		let cfg = arrow::Config {
+			batch_size: *HTTP_ARROW_BATCH_SIZE,
+			infer_rows: *HTTP_ARROW_INFER_ROWS,
+			format,
+		};
+		let sql = sql.to_owned();
+		let vars = params.0.parse().into();
+		let db = state.datastore.clone();
+		// The query is cancelled if the client disconnects, as the receiver is then dropped
+		let (send, recv) = async_channel::bounded(1);
+		let task =
+			tokio::spawn(async move { db.query_arrow(&sql, &session, vars, cfg, send).await });
+		// Return an error which happened before any of the output was written
+		let Ok(first) = recv.recv().await else {
+			let err = match task.await {
+				Ok(Err(err)) => err,
+				Ok(Ok(())) => anyhow::anyhow!("The query did not return a result"),
+				Err(err) => anyhow::Error::new(err),
+			};
+			return Err(ResponseError(err));
+		};
+		// The output ends early with the error which stopped the query
+		let end = futures::stream::once(task).filter_map(|res| async move {
+			match res {
+				Ok(Ok(())) => None,
+				Ok(Err(err)) => Some(Err(err)),
+				Err(err) => Some(Err(anyhow::Error::new(err))),
+			}
+		});
+		let chunks = futures::stream::iter([first])
+			.chain(recv)
+			.map(Ok)
+			.chain(end)
+			.map(|v: anyhow::Result<Vec<u8>>| v.map(Bytes::from));
+		let header = match format {
+			arrow::Format::Ipc => HeaderValue::from(Accept::ApplicationArrowStream),
+			arrow::Format::Parquet => HeaderValue::from(Accept::ApplicationParquet),
+		};
+		return Ok(([(CONTENT_TYPE, header)], Body::from_stream(chunks)).into_response());
+	}
+	// Stream the results as lines of JSON while the query runs
+	if let Some(Accept::ApplicationNdjson) = output.as_deref() {
+		let ast =
+			syn::parse_with_capabilities(sql, db.get_capabilities()).map_err(ResponseError)?;
+		let vars = params.0.parse().into();
+		let db = state.datastore.clone();
+		// The query is cancelled if the client disconnects, as the receiver is then dropped
+		let (send, recv) = async_channel::bounded(1);
+		let task =
+			tokio::spawn(async move { db.process_streamed(ast, &session, vars, send).await });
+		let frames = recv.map(|frame| frame.into_value().map(Json::from));
+		// The last line marks the end of the results, or the error which stopped the query
+		let end = futures::stream::once(async move {
+			let err = match task.await {
+				Ok(Ok(())) => return Ok(json!({ "frame": "end" })),
+				Ok(Err(err)) => err.to_string(),
+				Err(err) => err.to_string(),
+			};
+			Ok(json!({ "frame": "end", "error": err }))
+		});
+		let lines = frames.chain(end).map(|v: anyhow::Result<Json>| {
+			let mut line = serde_json::to_vec(&v?)?;
+			line.push(b'\n');
+			Ok::<_, anyhow::Error>(Bytes::from(line))
+		});
+		let header = HeaderValue::from(Accept::ApplicationNdjson);
+		return Ok(([(CONTENT_TYPE, header)], Body::from_stream(lines)).into_response());
+	}
 	// Execute the received sql query
 	match db.execute(sql, &session, params.0.parse().into()).await {
 		Ok(res) => match output.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => {
-				Ok(Output::json(&output::simplify(res).map_err(ResponseError)?))
+				Ok(Output::json(&output::simplify(res).map_err(ResponseError)?).into_response())
 			}
 			Some(Accept::ApplicationCbor) => {
-				Ok(Output::cbor(&output::simplify(res).map_err(ResponseError)?))
+				Ok(Output::cbor(&output::simplify(res).map_err(ResponseError)?).into_response())
 			}
 			// Internal serialization
-			Some(Accept::Surrealdb) => Ok(Output::full(&res)),
+			Some(Accept::Surrealdb) => Ok(Output::full(&res).into_response()),
 			// An incorrect content-type was requested
 			_ => Err(NetError::InvalidType.into()),
 		},
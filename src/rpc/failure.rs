@@ -3,12 +3,15 @@ use revision::revisioned;
 use serde::Serialize;
 use std::borrow::Cow;
 use surrealdb::rpc::RpcError;
+use surrealdb_core::err::Error;
 use surrealdb_core::expr::Value;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: Cow<'static, str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) data: Option<Value>,
 }
 
 #[revisioned(revision = 1)]
@@ -52,7 +55,24 @@ impl From<RpcError> for Failure {
 			RpcError::InvalidRequest => Failure::INVALID_REQUEST,
 			RpcError::MethodNotFound => Failure::METHOD_NOT_FOUND,
 			RpcError::InvalidParams => Failure::INVALID_PARAMS,
-			RpcError::InternalError(_) => Failure::custom(err.to_string()),
+			RpcError::PreparedQueryNotFound => Failure::PREPARED_QUERY_NOT_FOUND,
+			RpcError::InternalError(ref e) => match e.downcast_ref() {
+				// Include any value thrown with a THROW statement
+				Some(Error::ThrownValue(v)) => Failure {
+					data: Some(v.clone()),
+					..Failure::custom(err.to_string())
+				},
+				// Include when the authentication attempt can be retried
+				Some(Error::TooManyAuthAttempts {
+					retry_after,
+				}) => Failure {
+					data: Some(Value::from(map! {
+						String::from("retry_after") => Value::from(*retry_after),
+					})),
+					..Failure::custom(err.to_string())
+				},
+				_ => Failure::custom(err.to_string()),
+			},
 			RpcError::Thrown(_) => Failure::custom(err.to_string()),
 			_ => Failure::custom(err.to_string()),
 		}
@@ -64,6 +84,7 @@ impl From<Failure> for Value {
 		map! {
 			String::from("code") => Value::from(err.code),
 			String::from("message") => Value::from(err.message.to_string()),
+			String::from("data"), if let Some(data) = err.data => data,
 		}
 		.into()
 	}
@@ -73,26 +94,37 @@ impl Failure {
 	pub const PARSE_ERROR: Failure = Failure {
 		code: -32700,
 		message: Cow::Borrowed("Parse error"),
+		data: None,
 	};
 
 	pub const INVALID_REQUEST: Failure = Failure {
 		code: -32600,
 		message: Cow::Borrowed("Invalid Request"),
+		data: None,
 	};
 
 	pub const METHOD_NOT_FOUND: Failure = Failure {
 		code: -32601,
 		message: Cow::Borrowed("Method not found"),
+		data: None,
 	};
 
 	pub const INVALID_PARAMS: Failure = Failure {
 		code: -32602,
 		message: Cow::Borrowed("Invalid params"),
+		data: None,
 	};
 
 	pub const INTERNAL_ERROR: Failure = Failure {
 		code: -32603,
 		message: Cow::Borrowed("Internal error"),
+		data: None,
+	};
+
+	pub const PREPARED_QUERY_NOT_FOUND: Failure = Failure {
+		code: -32001,
+		message: Cow::Borrowed("Prepared query not found"),
+		data: None,
 	};
 
 	pub fn custom<S>(message: S) -> Failure
@@ -102,6 +134,7 @@ impl Failure {
 		Failure {
 			code: -32000,
 			message: message.into(),
+			data: None,
 		}
 	}
 }
@@ -1,10 +1,12 @@
 use crate::net::headers::{Accept, ContentType};
 use crate::rpc::failure::Failure;
 use crate::rpc::response::Response;
+use axum::body::Body;
 use axum::extract::ws::Message;
 use axum::response::IntoResponse;
 use axum::response::Response as AxumResponse;
 use bytes::Bytes;
+use futures::{Stream, StreamExt};
 use http::header::{CONTENT_TYPE, HeaderValue};
 use surrealdb::rpc::RpcError;
 use surrealdb::rpc::format::Format;
@@ -15,8 +17,12 @@ impl From<&Accept> for Format {
 		match value {
 			Accept::TextPlain => Format::Unsupported,
 			Accept::ApplicationJson => Format::Json,
+			Accept::ApplicationNdjson => Format::Unsupported,
 			Accept::ApplicationCbor => Format::Cbor,
+			Accept::ApplicationMsgpack => Format::Msgpack,
 			Accept::ApplicationOctetStream => Format::Unsupported,
+			Accept::ApplicationArrowStream => Format::Unsupported,
+			Accept::ApplicationParquet => Format::Unsupported,
 			Accept::Surrealdb => Format::Bincode,
 		}
 	}
@@ -28,6 +34,7 @@ impl From<&ContentType> for Format {
 			ContentType::TextPlain => Format::Unsupported,
 			ContentType::ApplicationJson => Format::Json,
 			ContentType::ApplicationCbor => Format::Cbor,
+			ContentType::ApplicationMsgpack => Format::Msgpack,
 			ContentType::ApplicationOctetStream => Format::Unsupported,
 			ContentType::Surrealdb => Format::Bincode,
 		}
@@ -39,6 +46,7 @@ impl From<&Format> for ContentType {
 		match format {
 			Format::Json => ContentType::ApplicationJson,
 			Format::Cbor => ContentType::ApplicationCbor,
+			Format::Msgpack => ContentType::ApplicationMsgpack,
 			Format::Unsupported => ContentType::ApplicationOctetStream,
 			Format::Bincode => ContentType::Surrealdb,
 			_ => ContentType::TextPlain,
@@ -79,6 +87,12 @@ pub trait HttpFormat {
 	fn req_http(&self, body: Bytes) -> Result<Request, RpcError>;
 	/// Process a HTTP RPC response
 	fn res_http(&self, res: Response) -> Result<AxumResponse, RpcError>;
+	/// Process a HTTP RPC response which is sent in chunks, as
+	/// the responses are produced. JSON responses are sent on
+	/// separate lines, while other formats are self-delimiting.
+	fn res_http_stream<S>(&self, res: S) -> AxumResponse
+	where
+		S: Stream<Item = Response> + Send + 'static;
 }
 
 impl HttpFormat for Format {
@@ -103,4 +117,23 @@ impl HttpFormat for Format {
 			Ok(([(CONTENT_TYPE, HeaderValue::from(ContentType::from(self)))], res).into_response())
 		}
 	}
+	/// Process a HTTP RPC response which is sent in chunks
+	fn res_http_stream<S>(&self, res: S) -> AxumResponse
+	where
+		S: Stream<Item = Response> + Send + 'static,
+	{
+		let fmt = *self;
+		let chunks = res.map(move |res| {
+			let mut res = fmt.res(res)?;
+			if matches!(fmt, Format::Json) {
+				res.push(b'\n');
+			}
+			Ok::<_, RpcError>(Bytes::from(res))
+		});
+		let header = match self {
+			Format::Json => HeaderValue::from(Accept::ApplicationNdjson),
+			_ => HeaderValue::from(ContentType::from(self)),
+		};
+		([(CONTENT_TYPE, header)], Body::from_stream(chunks)).into_response()
+	}
 }
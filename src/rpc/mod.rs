@@ -2,19 +2,16 @@ pub mod failure;
 pub mod format;
 pub mod http;
 pub mod response;
+pub mod subscription;
 pub mod websocket;
 
-use crate::rpc::response::success;
+use crate::rpc::subscription::Queued;
 use crate::rpc::websocket::Websocket;
-use crate::telemetry::metrics::ws::NotificationContext;
-use futures::stream::FuturesUnordered;
-use opentelemetry::Context as TelemetryContext;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::kvs::Datastore;
 use tokio::sync::RwLock;
-use tokio_stream::StreamExt;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -43,13 +40,15 @@ impl RpcState {
 }
 
 /// Performs notification delivery to the WebSockets
+///
+/// Notifications are added to the queue of their live query on the WebSocket
+/// connection, so that a slow client can never hold up delivery to any other
+/// connection. See [`subscription`] for the bounds and overflow policies.
 pub(crate) async fn notifications(
 	ds: Arc<Datastore>,
 	state: Arc<RpcState>,
 	canceller: CancellationToken,
 ) {
-	// Store messages being delivered
-	let mut futures = FuturesUnordered::new();
 	// Listen to the notifications channel
 	if let Some(channel) = ds.notifications() {
 		// Loop continuously
@@ -59,15 +58,13 @@ pub(crate) async fn notifications(
 				biased;
 				// Check if this has shutdown
 				_ = canceller.cancelled() => break,
-				// Process any buffered messages
-				Some(_) = futures.next() => continue,
 				// Receive a notification on the channel
 				Ok(notification) = channel.recv() => {
 					// Get the id for this notification
-					let id = notification.id.as_ref();
+					let lqid = notification.id.0;
 					// Get the WebSocket for this notification
 					let websocket = {
-						state.live_queries.read().await.get(id).copied()
+						state.live_queries.read().await.get(&lqid).copied()
 					};
 					// Ensure the specified WebSocket exists
 					if let Some(id) = websocket.as_ref() {
@@ -77,21 +74,23 @@ pub(crate) async fn notifications(
 						};
 						// Ensure the specified WebSocket exists
 						if let Some(rpc) = websocket {
-							// Serialize the message to send
-							let message = success(None, notification);
-							// Add telemetry metrics
-							let cx = TelemetryContext::new();
-							let not_ctx = NotificationContext::default()
-								  .with_live_id(id.to_string());
-							let cx = Arc::new(cx.with_value(not_ctx));
-							// Get the WebSocket output format
-							let format = rpc.format;
-							// Get the WebSocket sending channel
-							let sender = rpc.channel.clone();
-							// Send the notification to the client
-							let future = message.send(cx, format, sender);
-							// Pus the future to the pipeline
-							futures.push(future);
+							// Get the notification queue for this live query
+							let (sub, created) = {
+								// The bound and overflow policy of this connection
+								let session = rpc.session.load();
+								let capacity = session.live_query_queue_size;
+								let overflow = session.live_query_overflow;
+								rpc.subscriptions.get_or_create(lqid, capacity, overflow)
+							};
+							// Start delivering notifications for new live queries
+							if created {
+								tokio::spawn(sub.clone().deliver(rpc.clone()));
+							}
+							// Kill live queries which have fallen behind
+							if sub.push(notification) == Queued::Killed {
+								warn!("Killed live query {lqid} on websocket {id}, as the client fell behind");
+								tokio::spawn(kill(ds.clone(), rpc, lqid));
+							}
 						}
 					}
 				},
@@ -100,12 +99,42 @@ pub(crate) async fn notifications(
 	}
 }
 
-/// Closes all WebSocket connections, waiting for graceful shutdown
-pub(crate) async fn graceful_shutdown(state: Arc<RpcState>) {
-	// Close WebSocket connections, ensuring queued messages are processed
+/// Kills a live query whose client fell behind
+async fn kill(ds: Arc<Datastore>, rpc: Arc<Websocket>, lqid: Uuid) {
+	// Stop routing notifications to the WebSocket
+	rpc.state.live_queries.write().await.remove(&lqid);
+	rpc.subscriptions.remove(&lqid);
+	// Remove the live query from the datastore
+	if let Err(err) = ds.delete_queries(vec![lqid]).await {
+		error!("Error killing live query {lqid}: {err}");
+	}
+}
+
+/// Drains the datastore and closes all WebSocket connections, waiting for graceful shutdown
+///
+/// Once shutting down, the WebSocket connections no longer accept requests.
+/// The live queries are sent a `KILLED` notification, and each connection
+/// is closed once its notifications have been delivered, after the running
+/// queries have finished or have been cancelled at the end of the grace period.
+pub(crate) async fn graceful_shutdown(ds: Arc<Datastore>, state: Arc<RpcState>, grace: Duration) {
+	// Stop accepting new requests
 	for (_, rpc) in state.web_sockets.read().await.iter() {
 		rpc.shutdown.cancel();
 	}
+	// Wait for the running queries to finish
+	if let Err(err) = ds.drain(grace).await {
+		error!("Error draining the datastore: {err}");
+	}
+	// Wait for the notifications of the killed live queries to be routed
+	if let Some(channel) = ds.notifications() {
+		while !channel.is_empty() {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	}
+	// Close WebSocket connections, ensuring queued notifications are delivered
+	for (_, rpc) in state.web_sockets.read().await.iter() {
+		tokio::spawn(Websocket::close(rpc.clone()));
+	}
 	// Wait for all existing WebSocket connections to finish sending
 	while !state.web_sockets.read().await.is_empty() {
 		tokio::time::sleep(Duration::from_millis(250)).await;
@@ -0,0 +1,432 @@
+//! Notification queues for the live queries of a WebSocket connection.
+//!
+//! Every live query on a connection has its own queue, which is drained by a
+//! dedicated task into the response channel of the WebSocket. When a client
+//! reads slower than notifications are produced, the response channel fills
+//! up, and notifications accumulate in the queue of the live query.
+//!
+//! The queues are unbounded by default, so that no notification is ever
+//! dropped. A bound is set for a connection by the capabilities of the
+//! datastore, or by the connection itself with the `surreal-live-query-queue`
+//! header, which can only lower the bound of the capabilities. Once a bounded
+//! queue is full, the overflow policy of the connection decides which
+//! notifications are discarded.
+//!
+//! Notifications for a single live query are always delivered in the order
+//! in which they were produced. Nothing is reordered or dropped unless the
+//! queue overflows, and with the `drop_oldest` or `drop_newest` policies the
+//! notifications which remain are still delivered in order. Notifications
+//! for different live queries on the same connection may be interleaved.
+//!
+//! Live queries which opted into batching receive their notifications as an
+//! array, in the order in which they were produced, instead of one message
+//! per notification. A batch is sent once it is full, once its first
+//! notification has waited for the batch window, or immediately before a
+//! KILLED notification, which is always sent on its own in a batch of one.
+//! Clients which don't opt in never receive batches.
+
+use crate::rpc::response::success;
+use crate::rpc::websocket::Websocket;
+use crate::telemetry::metrics::ws::{
+	NotificationContext, observe_live_query_dropped, observe_live_query_queued,
+};
+use opentelemetry::Context as TelemetryContext;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use surrealdb::dbs::capabilities::LiveQueryOverflow;
+use surrealdb::dbs::{Action, Notification};
+use surrealdb::rpc::{Data, NotificationBatch};
+use surrealdb_core::expr::Value;
+use tokio::sync::Notify;
+use tokio::time::{Instant, sleep_until};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// The message sent to a client when its live query is killed for falling behind
+const LIVE_QUERY_FELL_BEHIND: &str = "The live query was killed, as the client fell behind and notifications were lost. Re-sync the data and start a new live query";
+
+/// The outcome of queueing a notification
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Queued {
+	/// The notification was queued for delivery
+	Queued,
+	/// The queue was full, and a notification was dropped
+	Dropped,
+	/// The queue was full, and the live query must be killed
+	Killed,
+}
+
+#[derive(Default)]
+struct Queue {
+	/// The notifications waiting to be delivered
+	items: VecDeque<Notification>,
+	/// Whether no more notifications can be queued
+	closed: bool,
+}
+
+/// The notification queue of a single live query
+pub(crate) struct Subscription {
+	/// The id of the live query
+	id: Uuid,
+	/// The maximum number of queued notifications, if the queue is bounded
+	capacity: Option<usize>,
+	/// What happens when the queue is full
+	overflow: LiveQueryOverflow,
+	/// How the notifications are batched, if the client opted in
+	batch: Option<NotificationBatch>,
+	/// The queued notifications
+	queue: Mutex<Queue>,
+	/// Signals the delivery task when the queue changes
+	ready: Notify,
+	/// Cancelled once the delivery task has finished
+	finished: CancellationToken,
+}
+
+impl Subscription {
+	fn new(
+		id: Uuid,
+		capacity: Option<usize>,
+		overflow: LiveQueryOverflow,
+		batch: Option<NotificationBatch>,
+	) -> Self {
+		Self {
+			id,
+			capacity: capacity.map(|v| v.max(1)),
+			overflow,
+			batch,
+			queue: Mutex::default(),
+			ready: Notify::new(),
+			finished: CancellationToken::new(),
+		}
+	}
+
+	/// Adds a notification to the queue, applying the overflow policy if it is full
+	pub(crate) fn push(&self, notification: Notification) -> Queued {
+		let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+		if queue.closed {
+			return Queued::Dropped;
+		}
+		let res = if self.capacity.is_none_or(|v| queue.items.len() < v) {
+			queue.items.push_back(notification);
+			observe_live_query_queued(1);
+			Queued::Queued
+		} else {
+			observe_live_query_dropped(self.overflow, 1);
+			match self.overflow {
+				LiveQueryOverflow::DropNewest => Queued::Dropped,
+				LiveQueryOverflow::DropOldest => {
+					queue.items.pop_front();
+					queue.items.push_back(notification);
+					Queued::Dropped
+				}
+				_ => {
+					// Discard everything, and tell the client why the live query ended
+					observe_live_query_queued(-(queue.items.len() as i64) + 1);
+					queue.items.clear();
+					queue.items.push_back(Notification::new(
+						self.id.into(),
+						Action::Killed,
+						Value::None,
+						Value::from(LIVE_QUERY_FELL_BEHIND.to_string()),
+					));
+					queue.closed = true;
+					Queued::Killed
+				}
+			}
+		};
+		drop(queue);
+		self.ready.notify_one();
+		res
+	}
+
+	/// Stops queueing notifications, while still delivering those already queued
+	pub(crate) fn close(&self) {
+		self.queue.lock().unwrap_or_else(|e| e.into_inner()).closed = true;
+		self.ready.notify_one();
+	}
+
+	/// Takes the next notification, or returns whether the queue is finished
+	fn pop(&self) -> Result<Notification, bool> {
+		let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+		match queue.items.pop_front() {
+			Some(v) => {
+				observe_live_query_queued(-1);
+				Ok(v)
+			}
+			None => Err(queue.closed),
+		}
+	}
+
+	/// Waits for the next notification, or returns `None` once the queue is
+	/// closed and empty, or the connection is closed
+	async fn next(&self, canceller: &CancellationToken) -> Option<Notification> {
+		loop {
+			match self.pop() {
+				Ok(v) => return Some(v),
+				// The queue is closed, and everything was delivered
+				Err(true) => return None,
+				// Wait for the next notification
+				Err(false) => {
+					tokio::select! {
+						biased;
+						_ = canceller.cancelled() => return None,
+						_ = self.ready.notified() => continue,
+					}
+				}
+			}
+		}
+	}
+
+	/// Fills a batch until it is full, the batch window has passed, or the
+	/// queue is closed. A KILLED notification ends the batch, and is returned
+	/// separately so that it is sent after the batch.
+	async fn fill(
+		&self,
+		canceller: &CancellationToken,
+		batch: NotificationBatch,
+		items: &mut Vec<Notification>,
+	) -> Option<Notification> {
+		let deadline = Instant::now() + batch.within;
+		while items.len() < batch.size {
+			match self.pop() {
+				Ok(v) if v.action == Action::Killed => return Some(v),
+				Ok(v) => items.push(v),
+				Err(true) => break,
+				Err(false) => {
+					tokio::select! {
+						biased;
+						_ = canceller.cancelled() => break,
+						_ = sleep_until(deadline) => break,
+						_ = self.ready.notified() => continue,
+					}
+				}
+			}
+		}
+		None
+	}
+
+	/// Sends a message to the WebSocket, waiting until the WebSocket has room
+	/// for it. Returns false if the connection was closed.
+	async fn send(&self, rpc: &Websocket, data: Data) -> bool {
+		// Add telemetry metrics
+		let cx = TelemetryContext::new();
+		let not_ctx = NotificationContext::default().with_live_id(self.id.to_string());
+		let cx = Arc::new(cx.with_value(not_ctx));
+		// Send the notification to the client
+		let message = success(None, data);
+		tokio::select! {
+			biased;
+			_ = rpc.canceller.cancelled() => false,
+			_ = message.send(cx, rpc.format, rpc.channel.clone()) => true,
+		}
+	}
+
+	/// Delivers the queued notifications to the WebSocket, until the queue is
+	/// closed and empty, or the connection is closed
+	pub(crate) async fn deliver(self: Arc<Self>, rpc: Arc<Websocket>) {
+		while let Some(notification) = self.next(&rpc.canceller).await {
+			let sent = match self.batch {
+				// Send each notification in its own message
+				None => self.send(&rpc, notification.into()).await,
+				// A KILLED notification is never held back
+				Some(_) if notification.action == Action::Killed => {
+					self.send(&rpc, vec![notification].into()).await
+				}
+				// Coalesce the notifications into batches
+				Some(batch) => {
+					let mut items =
+						Vec::with_capacity(self.capacity.map_or(batch.size, |v| batch.size.min(v)));
+					items.push(notification);
+					let killed = self.fill(&rpc.canceller, batch, &mut items).await;
+					let mut sent = self.send(&rpc, items.into()).await;
+					if let Some(killed) = killed {
+						sent = sent && self.send(&rpc, vec![killed].into()).await;
+					}
+					sent
+				}
+			};
+			if !sent {
+				break;
+			}
+		}
+		// Anything left is never delivered
+		let remaining = {
+			let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+			queue.closed = true;
+			std::mem::take(&mut queue.items).len()
+		};
+		observe_live_query_queued(-(remaining as i64));
+		// Signal that nothing more will be sent
+		self.finished.cancel();
+	}
+}
+
+#[derive(Default)]
+struct Registry {
+	/// The notification queues of the live queries
+	queues: HashMap<Uuid, Arc<Subscription>>,
+	/// The batching of live queries which have no queue yet
+	batches: HashMap<Uuid, NotificationBatch>,
+}
+
+/// The notification queues of the live queries on a WebSocket connection
+#[derive(Default)]
+pub(crate) struct Subscriptions(Mutex<Registry>);
+
+impl Subscriptions {
+	/// Fetches the queue of a live query, along with whether it was created
+	pub(crate) fn get_or_create(
+		&self,
+		id: Uuid,
+		capacity: Option<usize>,
+		overflow: LiveQueryOverflow,
+	) -> (Arc<Subscription>, bool) {
+		let mut subs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(sub) = subs.queues.get(&id) {
+			return (sub.clone(), false);
+		}
+		let batch = subs.batches.remove(&id);
+		let sub = Arc::new(Subscription::new(id, capacity, overflow, batch));
+		subs.queues.insert(id, sub.clone());
+		(sub, true)
+	}
+
+	/// Batches the notifications of a live query, before it produces any
+	pub(crate) fn batch(&self, id: Uuid, batch: NotificationBatch) {
+		self.0.lock().unwrap_or_else(|e| e.into_inner()).batches.insert(id, batch);
+	}
+
+	/// Closes and removes the queue of a live query
+	pub(crate) fn remove(&self, id: &Uuid) {
+		let mut subs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		subs.batches.remove(id);
+		if let Some(sub) = subs.queues.remove(id) {
+			sub.close();
+		}
+	}
+
+	/// Closes and removes the queues of all live queries, waiting
+	/// until the notifications already queued have been delivered
+	pub(crate) async fn flush(&self) {
+		let subs = {
+			let mut subs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+			subs.batches.clear();
+			subs.queues.drain().map(|(_, sub)| sub).collect::<Vec<_>>()
+		};
+		for sub in subs {
+			sub.close();
+			sub.finished.cancelled().await;
+		}
+	}
+
+	/// Closes and removes the queues of all live queries
+	pub(crate) fn clear(&self) {
+		let mut subs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		subs.batches.clear();
+		for (_, sub) in subs.queues.drain() {
+			sub.close();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	fn notification(id: Uuid, n: i64) -> Notification {
+		Notification::new(id.into(), Action::Create, Value::None, Value::from(n))
+	}
+
+	fn drain(sub: &Subscription) -> Vec<Value> {
+		std::iter::from_fn(|| sub.pop().ok()).map(|n| n.result).collect()
+	}
+
+	#[test]
+	fn preserves_order() {
+		let id = Uuid::new_v4();
+		let sub = Subscription::new(id, Some(3), LiveQueryOverflow::Kill, None);
+		for n in 0..3 {
+			assert_eq!(sub.push(notification(id, n)), Queued::Queued);
+		}
+		assert_eq!(drain(&sub), vec![Value::from(0), Value::from(1), Value::from(2)]);
+	}
+
+	#[test]
+	fn unbounded() {
+		let id = Uuid::new_v4();
+		let sub = Subscription::new(id, None, LiveQueryOverflow::Kill, None);
+		for n in 0..100 {
+			assert_eq!(sub.push(notification(id, n)), Queued::Queued);
+		}
+		assert_eq!(drain(&sub), (0..100i64).map(Value::from).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn drop_oldest() {
+		let id = Uuid::new_v4();
+		let sub = Subscription::new(id, Some(2), LiveQueryOverflow::DropOldest, None);
+		sub.push(notification(id, 0));
+		sub.push(notification(id, 1));
+		assert_eq!(sub.push(notification(id, 2)), Queued::Dropped);
+		assert_eq!(drain(&sub), vec![Value::from(1), Value::from(2)]);
+	}
+
+	#[test]
+	fn drop_newest() {
+		let id = Uuid::new_v4();
+		let sub = Subscription::new(id, Some(2), LiveQueryOverflow::DropNewest, None);
+		sub.push(notification(id, 0));
+		sub.push(notification(id, 1));
+		assert_eq!(sub.push(notification(id, 2)), Queued::Dropped);
+		assert_eq!(drain(&sub), vec![Value::from(0), Value::from(1)]);
+	}
+
+	#[test]
+	fn kill() {
+		let id = Uuid::new_v4();
+		let sub = Subscription::new(id, Some(1), LiveQueryOverflow::Kill, None);
+		sub.push(notification(id, 0));
+		assert_eq!(sub.push(notification(id, 1)), Queued::Killed);
+		// Nothing is queued once the live query has been killed
+		assert_eq!(sub.push(notification(id, 2)), Queued::Dropped);
+		let killed = sub.pop().unwrap();
+		assert_eq!(killed.action, Action::Killed);
+		assert_eq!(killed.result, Value::from(LIVE_QUERY_FELL_BEHIND.to_string()));
+		assert!(sub.pop().unwrap_err());
+	}
+
+	#[tokio::test]
+	async fn batches() {
+		let id = Uuid::new_v4();
+		let batch = NotificationBatch {
+			size: 2,
+			within: Duration::from_millis(20),
+		};
+		let sub = Subscription::new(id, Some(10), LiveQueryOverflow::Kill, Some(batch));
+		let canceller = CancellationToken::new();
+		for n in 0..3 {
+			sub.push(notification(id, n));
+		}
+		// A batch is sent as soon as it is full
+		let mut items = vec![sub.next(&canceller).await.unwrap()];
+		assert!(sub.fill(&canceller, batch, &mut items).await.is_none());
+		assert_eq!(items.len(), 2);
+		// A batch which is not full is sent after the batch window
+		let started = std::time::Instant::now();
+		let mut items = vec![sub.next(&canceller).await.unwrap()];
+		assert!(sub.fill(&canceller, batch, &mut items).await.is_none());
+		assert_eq!(
+			items.iter().map(|n| n.result.clone()).collect::<Vec<_>>(),
+			vec![Value::from(2)]
+		);
+		assert!(started.elapsed() >= batch.within);
+		// A KILLED notification ends the batch
+		sub.push(notification(id, 3));
+		sub.push(Notification::new(id.into(), Action::Killed, Value::None, Value::None));
+		let mut items = vec![sub.next(&canceller).await.unwrap()];
+		let killed = sub.fill(&canceller, batch, &mut items).await.unwrap();
+		assert_eq!(killed.action, Action::Killed);
+		assert_eq!(items.len(), 1);
+	}
+}
@@ -7,29 +7,38 @@ use crate::cnf::{PKG_NAME, PKG_VERSION};
 use crate::rpc::CONN_CLOSED_ERR;
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
-use crate::rpc::response::{IntoRpcResponse, failure};
+use crate::rpc::response::{IntoRpcResponse, failure, success};
+use crate::rpc::subscription::Subscriptions;
 use crate::telemetry;
 use crate::telemetry::metrics::ws::RequestContext;
 use crate::telemetry::traces::rpc::span_for_request;
 use arc_swap::ArcSwap;
-use axum::extract::ws::{CloseFrame, Message, WebSocket, close_code::AGAIN};
+use axum::extract::ws::{
+	CloseFrame, Message, WebSocket,
+	close_code::{AGAIN, AWAY},
+};
 use core::fmt;
 use futures::stream::FuturesUnordered;
 use futures::{Sink, SinkExt, StreamExt};
 use opentelemetry::Context as TelemetryContext;
 use opentelemetry::trace::FutureExt;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 use surrealdb::dbs::Session;
-use surrealdb::gql::{Pessimistic, SchemaCache};
+use surrealdb::gql::SchemaCache;
 use surrealdb::kvs::Datastore;
 use surrealdb::mem::ALLOC;
 use surrealdb::rpc::Data;
 use surrealdb::rpc::Method;
+use surrealdb::rpc::NotificationBatch;
+use surrealdb::rpc::PreparedQueries;
 use surrealdb::rpc::RpcContext;
 use surrealdb::rpc::format::Format;
+use surrealdb::rpc::is_streamed_query;
 use surrealdb::sql::Array;
 use surrealdb::sql::SqlValue;
+use surrealdb_core::expr::Value;
 use surrealdb_core::rpc::RpcProtocolV1;
 use surrealdb_core::rpc::RpcProtocolV2;
 use tokio::sync::Semaphore;
@@ -63,10 +72,16 @@ pub struct Websocket {
 	pub(crate) shutdown: CancellationToken,
 	/// A cancellation token for cancelling all spawned tasks
 	pub(crate) canceller: CancellationToken,
+	/// The number of requests which are being processed
+	pub(crate) pending: AtomicUsize,
 	/// The channels used to send and receive WebSocket messages
 	pub(crate) channel: Sender<Message>,
 	/// The GraphQL schema cache stored in advance
-	pub(crate) gql_schema: SchemaCache<Pessimistic>,
+	pub(crate) gql_schema: SchemaCache,
+	/// The notification queues of the live queries on this connection
+	pub(crate) subscriptions: Subscriptions,
+	/// The queries which were prepared on this connection
+	pub(crate) prepared: PreparedQueries,
 }
 
 impl Websocket {
@@ -91,9 +106,12 @@ impl Websocket {
 			lock: Arc::new(Semaphore::new(1)),
 			shutdown: CancellationToken::new(),
 			canceller: CancellationToken::new(),
+			pending: AtomicUsize::new(0),
 			session: ArcSwap::from(Arc::new(session)),
 			channel: sender.clone(),
 			gql_schema: SchemaCache::new(datastore.clone()),
+			subscriptions: Subscriptions::default(),
+			prepared: PreparedQueries::new(),
 			datastore,
 		});
 		// Add this WebSocket to the list
@@ -201,6 +219,8 @@ impl Websocket {
 				_ = canceller.cancelled() => break,
 				// Retrieve a response from the channel
 				Some(res) = internal_receiver.recv() => {
+					// Check if this closes the connection
+					let close = matches!(res, Message::Close(_));
 					// Check if the socket is buffered
 					let res = match buffer {
 						// Send the message to the socket buffer
@@ -219,6 +239,17 @@ impl Websocket {
 						// Exit out of the loop
 						break;
 					}
+					// Ensure the close frame is sent before stopping
+					if close {
+						// Flush the WebSocket socket buffer
+						if let Err(err) = socket.flush().await {
+							trace!("WebSocket error when sending the close message: {err}");
+						}
+						// Cancel the WebSocket tasks
+						canceller.cancel();
+						// Exit out of the loop
+						break;
+					}
 				},
 				// Wait for a short period of time
 				_ = tokio::time::sleep(period), if buffer => {
@@ -244,8 +275,6 @@ impl Websocket {
 		mut socket: impl StreamExt<Item = Result<Message, axum::Error>> + Unpin,
 		internal_sender: Sender<Message>,
 	) {
-		// Clone the WebSocket shutdown token
-		let shutdown = rpc.shutdown.clone();
 		// Clone the WebSocket cancellation token
 		let canceller = rpc.canceller.clone();
 		// Store spawned tasks so we can wait for them
@@ -257,8 +286,6 @@ impl Websocket {
 				biased;
 				// Remove any completed tasks
 				_ = tasks.next(), if !tasks.is_empty() => {},
-				// Check if we are shutting down
-				_ = shutdown.cancelled() => break,
 				// Check if we should teardown
 				_ = canceller.cancelled() => break,
 				// Wait for the next received message
@@ -306,20 +333,6 @@ impl Websocket {
 				}
 			}
 		}
-		// Continue with the shutdown process
-		tokio::select! {
-			// Process brances in order
-			biased;
-			// Check if we have been cancelled
-			_ = canceller.cancelled() => (),
-			// Check if we are shutting down
-			_ = shutdown.cancelled() => {
-				// Wait for all tasks to finish
-				while tasks.next().await.is_some() {
-					// Do nothing
-				}
-			},
-		}
 		// Cancel the WebSocket tasks
 		canceller.cancel();
 		// Ensure everything is dropped
@@ -328,6 +341,8 @@ impl Websocket {
 
 	/// Handle an individual WebSocket message
 	async fn handle_message(rpc: &Arc<Websocket>, msg: Message, chn: Sender<Message>) {
+		// Mark the request as being processed
+		rpc.pending.fetch_add(1, Ordering::AcqRel);
 		// Clone the WebSocket cancellation token
 		let shutdown = rpc.shutdown.clone();
 		// Clone the WebSocket cancellation token
@@ -382,6 +397,12 @@ impl Websocket {
 									.with_context(otel_cx.as_ref().clone())
 									.await;
 							}
+							// Send the results of a streamed query in frames
+							else if is_streamed_query(req.version, req.method, &req.params) {
+								Self::process_streamed(rpc.clone(), req.version, req.params, req.id.map(Into::into), otel_cx.clone(), chn)
+									.with_context(otel_cx.as_ref().clone())
+									.await;
+							}
 							// Otherwise process the request message
 							else {
 								// Process the message
@@ -405,6 +426,8 @@ impl Websocket {
 		}
 		.instrument(span)
 		.await;
+		// Mark the request as processed
+		rpc.pending.fetch_sub(1, Ordering::AcqRel);
 	}
 
 	/// Process a WebSocket message and generate a response
@@ -424,6 +447,67 @@ impl Websocket {
 		RpcContext::execute(rpc.as_ref(), version, txn, method, params).await.map_err(Into::into)
 	}
 
+	/// Process a query whose results are streamed, sending
+	/// a response for each frame of the results
+	async fn process_streamed(
+		rpc: Arc<Websocket>,
+		version: Option<u8>,
+		params: Array,
+		id: Option<Value>,
+		otel_cx: Arc<TelemetryContext>,
+		chn: Sender<Message>,
+	) {
+		debug!("Process streamed RPC request");
+		let (send, mut recv) = channel(1);
+		let run = RpcContext::execute_streamed(rpc.as_ref(), version, params, send);
+		let forward = async {
+			while let Some(frame) = recv.recv().await {
+				success(id.clone(), frame).send(otel_cx.clone(), rpc.format, chn.clone()).await;
+				if chn.is_closed() {
+					break;
+				}
+			}
+			// Dropping the receiver cancels the query
+			drop(recv);
+		};
+		let (res, _) = futures::join!(run, forward);
+		// Send an error which happened before any frame was sent
+		if let Err(err) = res {
+			failure(id, err.into()).send(otel_cx, rpc.format, chn).await;
+		}
+	}
+
+	/// Close the WebSocket connection once the server has been drained,
+	/// after the notifications of the killed live queries are delivered
+	pub(crate) async fn close(rpc: Arc<Websocket>) {
+		// Wait for the responses of the requests being processed
+		while rpc.pending.load(Ordering::Acquire) > 0 {
+			if rpc.canceller.is_cancelled() {
+				return;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		// Deliver the queued notifications
+		rpc.subscriptions.flush().await;
+		// Create a custom close frame
+		let frame = CloseFrame {
+			code: AWAY,
+			reason: SERVER_SHUTTING_DOWN.into(),
+		};
+		// Respond with a close message
+		tokio::select! {
+			biased;
+			_ = rpc.canceller.cancelled() => (),
+			res = rpc.channel.send(Message::Close(Some(frame))) => {
+				if let Err(err) = res {
+					debug!("WebSocket error when sending close message: {err}");
+					// Cancel the WebSocket tasks
+					rpc.canceller.cancel();
+				}
+			}
+		}
+	}
+
 	/// Reject a WebSocket message due to server overloading
 	async fn close_socket(rpc: Arc<Websocket>, chn: Sender<Message>) {
 		// Log the error as a warning
@@ -480,11 +564,18 @@ impl RpcContext for Websocket {
 		trace!("Registered live query {lqid} on websocket {}", self.id);
 	}
 
+	/// Batches the notifications of a LIVE statement
+	async fn handle_live_batch(&self, lqid: &Uuid, batch: NotificationBatch) {
+		self.subscriptions.batch(*lqid, batch);
+	}
+
 	/// Handles the execution of a KILL statement
 	async fn handle_kill(&self, lqid: &Uuid) {
 		if let Some(id) = self.state.live_queries.write().await.remove(lqid) {
 			trace!("Unregistered live query {lqid} on websocket {id}");
 		}
+		// Deliver anything already queued, and then stop
+		self.subscriptions.remove(lqid);
 	}
 
 	/// Handles the cleanup of live queries
@@ -499,6 +590,8 @@ impl RpcContext for Websocket {
 			}
 			true
 		});
+		// Stop delivering notifications on this connection
+		self.subscriptions.clear();
 		// Garbage collect the live queries on this connection
 		if let Err(err) = self.kvs().delete_queries(gc).await {
 			error!("Error handling RPC connection: {err}");
@@ -515,4 +608,15 @@ impl RpcContext for Websocket {
 	fn graphql_schema_cache(&self) -> &SchemaCache {
 		&self.gql_schema
 	}
+
+	// ------------------------------
+	// Prepared queries
+	// ------------------------------
+
+	/// Prepared queries are enabled on WebSockets
+	const PREPARED_SUPPORT: bool = true;
+
+	fn prepared_queries(&self) -> &PreparedQueries {
+		&self.prepared
+	}
 }
@@ -8,6 +8,7 @@ use opentelemetry::{
 };
 use opentelemetry::{KeyValue, global};
 use std::sync::LazyLock;
+use surrealdb::dbs::capabilities::LiveQueryOverflow;
 
 static METER: LazyLock<Meter> = LazyLock::new(|| global::meter("surrealdb.rpc"));
 
@@ -49,6 +50,22 @@ pub static RPC_SERVER_RESPONSE_SIZE: LazyLock<Histogram<u64>> = LazyLock::new(||
 		.init()
 });
 
+pub static RPC_SERVER_LIVE_QUERY_QUEUE_DEPTH: LazyLock<UpDownCounter<i64>> = LazyLock::new(|| {
+	METER
+		.i64_up_down_counter("rpc.server.live_query.queue_depth")
+		.with_description("The number of live query notifications waiting to be delivered.")
+		.init()
+});
+
+pub static RPC_SERVER_LIVE_QUERY_DROPPED: LazyLock<Counter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_counter("rpc.server.live_query.dropped")
+		.with_description(
+			"The number of live query notifications dropped because a client fell behind.",
+		)
+		.init()
+});
+
 fn otel_common_attrs() -> Vec<KeyValue> {
 	let mut common = vec![KeyValue::new("rpc.service", "surrealdb")];
 	if let Some(namespace) = TELEMETRY_NAMESPACE.clone() {
@@ -74,6 +91,18 @@ pub(super) fn observe_active_connection(value: i64) -> Result<(), MetricsError>
 	Ok(())
 }
 
+/// Records notifications being added to, or removed from, a live query queue.
+pub fn observe_live_query_queued(value: i64) {
+	RPC_SERVER_LIVE_QUERY_QUEUE_DEPTH.add(value, &otel_common_attrs());
+}
+
+/// Records notifications dropped from a full live query queue.
+pub fn observe_live_query_dropped(policy: LiveQueryOverflow, value: u64) {
+	let mut attrs = otel_common_attrs();
+	attrs.push(KeyValue::new("rpc.overflow", policy.to_string()));
+	RPC_SERVER_LIVE_QUERY_DROPPED.add(value, &attrs);
+}
+
 //
 // Record an RPC command
 //
@@ -1064,16 +1064,10 @@ mod cli_integration {
 				.unwrap();
 			let output = remove_debug_info(output).replace('\n', "");
 			let allowed = [
-				// Delete these
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 2 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 4 }]]",
-				// Keep these
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 131072 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
-				"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 262144 }]]",
+				"[{ changes: [{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 2 }], versionstamp: 3 }]",
+				"[{ changes: [{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }], versionstamp: 4 }]",
+				"[{ changes: [{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }], versionstamp: 4 }]",
+				"[{ changes: [{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 4 }], versionstamp: 5 }]",
 			];
 			allowed
 				.into_iter()
@@ -1098,7 +1092,11 @@ mod cli_integration {
 				.output()
 				.unwrap();
 			let output = remove_debug_info(output);
-			assert_eq!(output, "[[]]\n\n".to_owned(), "failed to send sql: {args}");
+			assert_eq!(
+				output,
+				"[{ changes: [], versionstamp: NONE }]\n\n".to_owned(),
+				"failed to send sql: {args}"
+			);
 		}
 		server.finish().unwrap();
 	}
@@ -1178,7 +1176,13 @@ mod cli_integration {
 	#[cfg(unix)]
 	#[test(tokio::test)]
 	async fn test_server_second_signal_handling() {
-		let (addr, mut server) = common::start_server_without_auth().await.unwrap();
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--shutdown-grace-period 60s".to_owned(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
 
 		// Create a long-lived WS connection so the server don't shutdown gracefully
 		let socket =
@@ -1241,6 +1245,70 @@ mod cli_integration {
 		server.finish().unwrap();
 	}
 
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn test_server_drains_on_shutdown() {
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--shutdown-grace-period 1s".to_owned(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		let mut socket =
+			Socket::connect(&addr, None, Format::Json).await.expect("Failed to connect to server");
+		socket.send_message_use(Some("test"), Some("test")).await.unwrap();
+		let res = socket.send_message_query("LIVE SELECT * FROM person").await.unwrap();
+		let lqid = res[0]["result"].as_str().unwrap().to_owned();
+
+		let send_future = socket.send_request("query", json!(["SLEEP 30s;"]));
+
+		let signal_send_fut = async {
+			// Make sure the SLEEP query is being executed
+			tokio::time::timeout(time::Duration::from_secs(10), async {
+				loop {
+					let err = server.stdout_and_stderr();
+					if err.contains("SLEEP 30s") {
+						break;
+					}
+					tokio::time::sleep(time::Duration::from_secs(1)).await;
+				}
+			})
+			.await
+			.expect("Server didn't start executing the SLEEP query");
+
+			info!("* Send SIGINT signal");
+			server
+				.send_signal(nix::sys::signal::Signal::SIGINT)
+				.expect("Failed to send SIGINT to server");
+		};
+
+		// The query is cancelled once the grace period has passed
+		let (res, _) = futures::future::join(send_future, signal_send_fut).await;
+		let res = res.unwrap();
+		assert_eq!(res["result"][0]["status"], "ERR", "{res:?}");
+
+		// The live query is told that the server is shutting down
+		let msg = socket.receive_other_message().await.unwrap();
+		assert_eq!(msg["result"]["id"], lqid, "{msg:?}");
+		assert_eq!(msg["result"]["action"], "KILLED", "{msg:?}");
+		assert_eq!(msg["result"]["result"], "The server is shutting down", "{msg:?}");
+
+		info!("* Waiting for server to exit gracefully ...");
+		tokio::time::timeout(time::Duration::from_secs(5), async {
+			loop {
+				if let Ok(Some(exit)) = server.status() {
+					assert!(exit.success(), "Server didn't shutdown successfully");
+					break;
+				}
+				tokio::time::sleep(time::Duration::from_millis(100)).await;
+			}
+		})
+		.await
+		.expect("Server didn't exit after draining");
+	}
+
 	#[test(tokio::test)]
 	async fn test_capabilities() {
 		// Default capabilities only allow functions
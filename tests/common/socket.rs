@@ -47,6 +47,7 @@ struct SigninParams<'a> {
 
 enum SocketMsg {
 	SendAwait {
+		version: Option<u8>,
 		method: String,
 		args: serde_json::Value,
 		channel: oneshot::Sender<serde_json::Value>,
@@ -203,14 +204,18 @@ impl Socket {
 		stream: &mut WsStream,
 		id: u64,
 		format: Format,
+		version: Option<u8>,
 		method: &str,
 		args: serde_json::Value,
 	) -> Result<()> {
-		let msg = json!({
+		let mut msg = json!({
 			"id": id,
 			"method": method,
 			"params": args,
 		});
+		if let Some(version) = version {
+			msg["version"] = json!(version);
+		}
 
 		let msg = Self::to_msg(format, &msg)?;
 
@@ -238,16 +243,16 @@ impl Socket {
 						return Ok(());
 					};
 					match msg{
-						SocketMsg::SendAwait { method, args, channel } => {
+						SocketMsg::SendAwait { version, method, args, channel } => {
 							let id = next_id;
 							next_id += 1;
 							awaiting.insert(id,channel);
-							Self::send_msg(&mut stream,id,format,&method, args).await?;
+							Self::send_msg(&mut stream,id,format,version,&method, args).await?;
 						},
 						SocketMsg::Send { method, args } => {
 							let id = next_id;
 							next_id += 1;
-							Self::send_msg(&mut stream,id,format,&method, args).await?;
+							Self::send_msg(&mut stream,id,format,None,&method, args).await?;
 						},
 						SocketMsg::Close{ channel } => {
 							stream.close(None).await?;
@@ -281,11 +286,22 @@ impl Socket {
 		&self,
 		method: &str,
 		params: serde_json::Value,
+	) -> Result<serde_json::Value> {
+		self.send_versioned_request(None, method, params).await
+	}
+
+	/// Send a request using a specific version of the RPC protocol and receive a reponse
+	pub async fn send_versioned_request(
+		&self,
+		version: Option<u8>,
+		method: &str,
+		params: serde_json::Value,
 	) -> Result<serde_json::Value> {
 		let (send, recv) = oneshot::channel();
 		if (self
 			.sender
 			.send(SocketMsg::SendAwait {
+				version,
 				method: method.to_string(),
 				args: params,
 				channel: send,
@@ -505,4 +505,112 @@ mod graphql_integration {
 
 		Ok(())
 	}
+
+	#[test(tokio::test)]
+	async fn mutations() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE foo SCHEMAFUL;
+                    DEFINE FIELD val ON foo TYPE int;
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// create, update, and delete records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{_create_foo(id: "foo:1", data: {val: 42}){id, val}}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data": {"_create_foo": {"id": "foo:1", "val": 42}}});
+			assert_eq!(expected, body);
+
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{_update_foo(id: "foo:1", data: {val: 43}){id, val}}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data": {"_update_foo": {"id": "foo:1", "val": 43}}});
+			assert_eq!(expected, body);
+
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"mutation{_delete_foo(id: "foo:1")}"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data": {"_delete_foo": "foo:1"}});
+			assert_eq!(expected, body);
+
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"query{foo{id}}"#}).to_string())
+				.send()
+				.await?;
+			let body = res.json::<serde_json::Value>().await?;
+			assert_eq!(json!({"data": {"foo": []}}), body);
+		}
+
+		// the schema is regenerated when definitions change
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE FIELD name ON foo TYPE string;
+                    CREATE foo:2 SET val = 2, name = "two";
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"query{foo{id, name}}"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let expected = json!({"data": {"foo": [{"id": "foo:2", "name": "two"}]}});
+			assert_eq!(expected, body);
+		}
+
+		Ok(())
+	}
 }
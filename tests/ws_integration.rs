@@ -32,6 +32,8 @@ use std::time::Duration;
 
 const HDR_SURREAL: &str = "surreal-id";
 const HDR_REQUEST: &str = "x-request-id";
+const HDR_LIVE_QUERY_QUEUE: &str = "surreal-live-query-queue";
+const HDR_LIVE_QUERY_OVERFLOW: &str = "surreal-live-query-overflow";
 
 pub async fn ping(cfg_server: Option<Format>, cfg_format: Format) {
 	// Setup database server
@@ -625,6 +627,169 @@ pub async fn query(cfg_server: Option<Format>, cfg_format: Format) {
 	server.finish().unwrap();
 }
 
+pub async fn query_options(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	socket.send_message_query("CREATE tester:one").await.unwrap();
+	// Read-only queries can select data
+	let res = socket
+		.send_versioned_request(
+			Some(2),
+			"query",
+			json!(["SELECT * FROM tester", null, { "readonly": true }]),
+		)
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["status"], "OK", "result: {res:?}");
+	assert_eq!(res["result"][0]["result"].as_array().unwrap().len(), 1, "result: {res:?}");
+	// Read-only queries can not modify data
+	let res = socket
+		.send_versioned_request(
+			Some(2),
+			"query",
+			json!([
+				"CREATE tester:two; BEGIN; CREATE tester:three; COMMIT;",
+				null,
+				{ "readonly": true }
+			]),
+		)
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["status"], "ERR", "result: {res:?}");
+	assert_eq!(res["result"][1]["status"], "ERR", "result: {res:?}");
+	let res = socket.send_message_query("SELECT * FROM tester").await.unwrap();
+	assert_eq!(res[0]["result"].as_array().unwrap().len(), 1, "result: {res:?}");
+	// The query is cancelled after the timeout
+	let res = socket
+		.send_versioned_request(
+			Some(2),
+			"query",
+			json!(["SLEEP 10s", null, { "timeout": "100ms" }]),
+		)
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["status"], "ERR", "result: {res:?}");
+	assert_eq!(
+		res["result"][0]["result"], "The query was not executed because it exceeded the timeout",
+		"result: {res:?}"
+	);
+	// Unknown options are ignored
+	let res = socket
+		.send_versioned_request(Some(2), "query", json!(["RETURN 1", null, { "unknown": true }]))
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["result"], 1, "result: {res:?}");
+	// Options of the wrong type are rejected
+	let res = socket
+		.send_versioned_request(Some(2), "query", json!(["RETURN 1", null, { "readonly": 1 }]))
+		.await
+		.unwrap();
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// Version 1 of the protocol does not accept options
+	let res = socket
+		.send_versioned_request(Some(1), "query", json!(["RETURN 1", null, { "readonly": true }]))
+		.await
+		.unwrap();
+	assert!(res["error"].is_object(), "result: {res:?}");
+	server.finish().unwrap();
+}
+
+pub async fn query_streamed(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	socket.send_message_query("FOR $i IN 0..2500 { CREATE tester }").await.unwrap();
+	// The first frame is the response to the request
+	let res = socket
+		.send_versioned_request(
+			Some(2),
+			"query",
+			json!([
+				"SELECT * FROM tester; SELECT * FROM nothing; THROW 'failed'",
+				null,
+				{ "stream": true, "stats": true }
+			]),
+		)
+		.await
+		.unwrap();
+	let mut frames = vec![res["result"].clone()];
+	// The other frames are sent with the same id
+	while frames.last().unwrap()["frame"] != "end" {
+		let res = tokio::time::timeout(Duration::from_secs(5), socket.receive_other_message())
+			.await
+			.unwrap()
+			.unwrap();
+		frames.push(res["result"].clone());
+	}
+	// The rows are sent in batches
+	let rows: Vec<_> = frames.iter().filter(|f| f["frame"] == "rows" && f["query"] == 0).collect();
+	assert_eq!(rows.len(), 3, "frames: {frames:?}");
+	let total: usize = rows.iter().map(|f| f["rows"].as_array().unwrap().len()).sum();
+	assert_eq!(total, 2500, "frames: {frames:?}");
+	// Each statement ends with a frame, which doesn't repeat the rows
+	let done: Vec<_> = frames.iter().filter(|f| f["frame"] == "done").collect();
+	assert_eq!(done.len(), 3, "frames: {frames:?}");
+	assert_eq!(done[0]["status"], "OK", "frames: {frames:?}");
+	assert_eq!(done[0]["rows"], 2500, "frames: {frames:?}");
+	assert_eq!(done[0]["partial"], false, "frames: {frames:?}");
+	assert_eq!(done[0]["stats"]["returned"], 2500, "frames: {frames:?}");
+	assert!(done[0].get("result").is_none(), "frames: {frames:?}");
+	assert_eq!(done[1]["query"], 1, "frames: {frames:?}");
+	assert_eq!(done[1]["rows"], 0, "frames: {frames:?}");
+	assert_eq!(done[2]["query"], 2, "frames: {frames:?}");
+	assert_eq!(done[2]["status"], "ERR", "frames: {frames:?}");
+	assert!(done[2]["result"].is_string(), "frames: {frames:?}");
+	// Version 1 of the protocol does not stream the results
+	let res = socket
+		.send_versioned_request(Some(1), "query", json!(["RETURN 1", null, { "stream": true }]))
+		.await
+		.unwrap();
+	assert!(res["error"].is_object(), "result: {res:?}");
+	server.finish().unwrap();
+}
+
+pub async fn query_variables(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	// Variables are only defined for the query they are sent with
+	let res = socket
+		.send_versioned_request(Some(2), "query", json!(["RETURN $name", { "name": "query" }]))
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["result"], "query", "result: {res:?}");
+	let res =
+		socket.send_versioned_request(Some(2), "query", json!(["RETURN $name"])).await.unwrap();
+	assert_eq!(res["result"][0]["result"], json!(null), "result: {res:?}");
+	// Variables take precedence over the session parameters
+	socket.send_request("let", json!(["name", "session"])).await.unwrap();
+	let res = socket
+		.send_versioned_request(Some(2), "query", json!(["RETURN $name", { "name": "query" }]))
+		.await
+		.unwrap();
+	assert_eq!(res["result"][0]["result"], "query", "result: {res:?}");
+	let res =
+		socket.send_versioned_request(Some(2), "query", json!(["RETURN $name"])).await.unwrap();
+	assert_eq!(res["result"][0]["result"], "session", "result: {res:?}");
+	server.finish().unwrap();
+}
+
 pub async fn version(cfg_server: Option<Format>, cfg_format: Format) {
 	// Setup database server
 	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
@@ -821,6 +986,87 @@ pub async fn live_rpc(cfg_server: Option<Format>, cfg_format: Format) {
 	server.finish().unwrap();
 }
 
+pub async fn live_batch(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	// Send LIVE command with batching enabled
+	let res = socket
+		.send_versioned_request(
+			Some(2),
+			"live",
+			json!(["tester", { "batch": { "size": 3, "within": "5s" } }]),
+		)
+		.await
+		.unwrap();
+	assert!(res["result"].is_string(), "result: {res:?}");
+	let live = res["result"].as_str().unwrap().to_owned();
+	// Create three test records
+	let res = socket
+		.send_request("query", json!(["CREATE tester:1; CREATE tester:2; CREATE tester:3"]))
+		.await
+		.unwrap();
+	assert!(res["result"].is_array(), "result: {res:?}");
+	// The notifications arrive together in a single frame
+	let msgs = socket.receive_all_other_messages(1, Duration::from_secs(2)).await.unwrap();
+	let res = msgs[0]["result"].as_array();
+	assert!(res.is_some(), "Expected a batch of notifications: {msgs:?}");
+	let res = res.unwrap();
+	assert_eq!(res.len(), 3, "result: {res:?}");
+	assert!(res.iter().all(|v| v["id"] == live), "result: {res:?}");
+	assert!(res.iter().all(|v| v["action"] == "CREATE"), "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+}
+
+pub async fn live_snapshot(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	// Create a test record
+	let res =
+		socket.send_request("query", json!(["CREATE tester:1 SET name = 'foo'"])).await.unwrap();
+	assert!(res["result"].is_array(), "result: {res:?}");
+	// Send LIVE command with a snapshot
+	let res = socket
+		.send_versioned_request(Some(2), "live", json!(["tester", { "snapshot": true }]))
+		.await
+		.unwrap();
+	assert!(res["result"].is_string(), "result: {res:?}");
+	let live = res["result"].as_str().unwrap().to_owned();
+	// The existing record is sent before any changes
+	let res =
+		socket.send_request("query", json!(["CREATE tester:2 SET name = 'bar'"])).await.unwrap();
+	assert!(res["result"].is_array(), "result: {res:?}");
+	let msgs = socket.receive_all_other_messages(2, Duration::from_secs(1)).await.unwrap();
+	assert!(msgs.iter().all(|v| common::is_notification_from_lq(v, &live)), "result: {msgs:?}");
+	assert_eq!(msgs[0]["result"]["action"], "SNAPSHOT", "result: {msgs:?}");
+	assert_eq!(msgs[0]["result"]["result"]["id"], "tester:1", "result: {msgs:?}");
+	assert_eq!(msgs[1]["result"]["action"], "CREATE", "result: {msgs:?}");
+	assert_eq!(msgs[1]["result"]["result"]["id"], "tester:2", "result: {msgs:?}");
+	// Send LIVE SELECT query with a snapshot
+	let res = socket
+		.send_request("query", json!(["LIVE SELECT * FROM tester WHERE name = 'bar' SNAPSHOT"]))
+		.await
+		.unwrap();
+	let live = res["result"][0]["result"].as_str().unwrap().to_owned();
+	let msgs = socket.receive_all_other_messages(1, Duration::from_secs(1)).await.unwrap();
+	assert!(common::is_notification_from_lq(&msgs[0], &live), "result: {msgs:?}");
+	assert_eq!(msgs[0]["result"]["result"]["id"], "tester:2", "result: {msgs:?}");
+	// Test passed
+	server.finish().unwrap();
+}
+
 pub async fn kill(cfg_server: Option<Format>, cfg_format: Format) {
 	// Setup database server
 	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
@@ -1925,6 +2171,47 @@ pub async fn relate_rpc(cfg_server: Option<Format>, cfg_format: Format) {
 	server.finish().unwrap();
 }
 
+pub async fn prepared_queries(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	socket.send_message_query("CREATE foo:1 SET val = 1; CREATE foo:2 SET val = 2").await.unwrap();
+	// Prepare a query
+	let res = socket
+		.send_request("prepare", json!(["SELECT VALUE val FROM foo WHERE val > $min"]))
+		.await
+		.unwrap();
+	let handle = res["result"].clone();
+	assert!(handle.is_string(), "result: {res:?}");
+	// Execute the query with different variables
+	let res = socket.send_request("execute", json!([handle, {"min": 0}])).await.unwrap();
+	assert_eq!(res["result"][0]["result"], json!([1, 2]), "result: {res:?}");
+	let res = socket.send_request("execute", json!([handle, {"min": 1}])).await.unwrap();
+	assert_eq!(res["result"][0]["result"], json!([2]), "result: {res:?}");
+	// The query is still executed after an index is defined
+	socket.send_message_query("DEFINE INDEX val ON foo FIELDS val").await.unwrap();
+	let res = socket.send_request("execute", json!([handle, {"min": 1}])).await.unwrap();
+	assert_eq!(res["result"][0]["result"], json!([2]), "result: {res:?}");
+	// The handle is not valid on another connection
+	let mut other = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	other.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	other.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	let res = other.send_request("execute", json!([handle, {"min": 1}])).await.unwrap();
+	assert_eq!(res["error"]["code"], -32001, "result: {res:?}");
+	// The handle is rejected once the query is removed
+	let res = socket.send_request("unprepare", json!([handle])).await.unwrap();
+	assert!(res["result"].is_null(), "result: {res:?}");
+	let res = socket.send_request("execute", json!([handle, {"min": 1}])).await.unwrap();
+	assert_eq!(res["error"]["code"], -32001, "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+}
+
 pub async fn temporary_directory(cfg_server: Option<Format>, cfg_format: Format) {
 	// Setup database server
 	let temp_dir = TempDir::new().unwrap();
@@ -2069,6 +2356,55 @@ pub async fn session_id_undefined(cfg_server: Option<Format>, cfg_format: Format
 	server.finish().unwrap();
 }
 
+pub async fn live_query_queue(cfg_server: Option<Format>, cfg_format: Format) {
+	// The queues of a connection are unbounded by default
+	{
+		let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+		let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+		socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+		let mut res = socket.send_message_query("RETURN $session.lq").await.unwrap();
+		assert_eq!(res.remove(0)["result"], json!({ "overflow": "kill", "queue": null }));
+		server.finish().unwrap();
+	}
+	// Each connection can set the bound of its queues, within the capabilities
+	{
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			args: "--live-query-queue-size 100".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		// A connection without the headers uses the capabilities
+		let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+		socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+		let mut res = socket.send_message_query("RETURN $session.lq").await.unwrap();
+		assert_eq!(res.remove(0)["result"], json!({ "overflow": "kill", "queue": 100 }));
+		// A connection can ask for a smaller queue, and another overflow policy
+		let mut headers = HeaderMap::new();
+		headers.insert(HDR_LIVE_QUERY_QUEUE, HeaderValue::from_static("10"));
+		headers.insert(HDR_LIVE_QUERY_OVERFLOW, HeaderValue::from_static("drop_oldest"));
+		let mut socket =
+			Socket::connect_with_headers(&addr, cfg_server, cfg_format, headers).await.unwrap();
+		socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+		let mut res = socket.send_message_query("RETURN $session.lq").await.unwrap();
+		assert_eq!(res.remove(0)["result"], json!({ "overflow": "drop_oldest", "queue": 10 }));
+		// A connection can't ask for a larger queue than the capabilities allow
+		let mut headers = HeaderMap::new();
+		headers.insert(HDR_LIVE_QUERY_QUEUE, HeaderValue::from_static("1000"));
+		let mut socket =
+			Socket::connect_with_headers(&addr, cfg_server, cfg_format, headers).await.unwrap();
+		socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+		let mut res = socket.send_message_query("RETURN $session.lq").await.unwrap();
+		assert_eq!(res.remove(0)["result"], json!({ "overflow": "kill", "queue": 100 }));
+		// An invalid header is rejected
+		let mut headers = HeaderMap::new();
+		headers.insert(HDR_LIVE_QUERY_OVERFLOW, HeaderValue::from_static("block"));
+		let res = Socket::connect_with_headers(&addr, cfg_server, cfg_format, headers).await;
+		assert!(res.is_err());
+		server.finish().unwrap();
+	}
+}
+
 pub async fn rpc_capability(cfg_server: Option<Format>, cfg_format: Format) {
 	// Deny some
 	{
@@ -2343,6 +2679,12 @@ define_include_tests! {
 	#[test_log::test(tokio::test)]
 	query,
 	#[test_log::test(tokio::test)]
+	query_options,
+	#[test_log::test(tokio::test)]
+	query_streamed,
+	#[test_log::test(tokio::test)]
+	query_variables,
+	#[test_log::test(tokio::test)]
 	version,
 	#[test_log::test(tokio::test)]
 	concurrency,
@@ -2351,6 +2693,10 @@ define_include_tests! {
 	#[test_log::test(tokio::test)]
 	live_rpc,
 	#[test_log::test(tokio::test)]
+	live_batch,
+	#[test_log::test(tokio::test)]
+	live_snapshot,
+	#[test_log::test(tokio::test)]
 	kill,
 	#[test_log::test(tokio::test)]
 	live_table_removal,
@@ -2377,6 +2723,8 @@ define_include_tests! {
 	#[test_log::test(tokio::test)]
 	relate_rpc,
 	#[test_log::test(tokio::test)]
+	prepared_queries,
+	#[test_log::test(tokio::test)]
 	temporary_directory,
 	#[test_log::test(tokio::test)]
 	session_id_defined,
@@ -2389,5 +2737,7 @@ define_include_tests! {
 	#[test_log::test(tokio::test)]
 	session_id_undefined,
 	#[test_log::test(tokio::test)]
+	live_query_queue,
+	#[test_log::test(tokio::test)]
 	rpc_capability,
 }